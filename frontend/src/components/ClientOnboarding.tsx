@@ -6,6 +6,10 @@ import {
   validateConfig,
   getConsultantIp,
   generateAgentScript,
+  listClients,
+  issueAgentToken,
+  listAgentTokens,
+  revokeAgentToken,
 } from "@/lib/commands";
 import type {
   TemplateInfo,
@@ -14,6 +18,9 @@ import type {
   ValidationResult,
   LogEntry,
   AgentScriptResponse,
+  AgentToken,
+  Client,
+  ConsultantInterface,
 } from "@/types";
 import { cn } from "@/lib/utils";
 import {
@@ -57,6 +64,7 @@ export function ClientOnboarding() {
   const [scriptPreview, setScriptPreview] = useState<string>("");
   const [generatedScript, setGeneratedScript] = useState<GenerateScriptResponse | null>(null);
   const [consultantIp, setConsultantIp] = useState<string>("");
+  const [interfaces, setInterfaces] = useState<ConsultantInterface[]>([]);
   const [isGenerating, setIsGenerating] = useState(false);
   const [isValidating, setIsValidating] = useState(false);
   const [logs, setLogs] = useState<LogEntry[]>([]);
@@ -65,7 +73,10 @@ export function ClientOnboarding() {
 
   // Agent script state (Task A)
   const [agentClientIp, setAgentClientIp] = useState<string>("");
-  const [agentAuthToken, setAgentAuthToken] = useState<string>("");
+  const [clients, setClients] = useState<Client[]>([]);
+  const [agentClientId, setAgentClientId] = useState<string>("");
+  const [agentTokens, setAgentTokens] = useState<AgentToken[]>([]);
+  const [agentTokenId, setAgentTokenId] = useState<string>("");
   const [agentCallbackPort, setAgentCallbackPort] = useState<number>(443);
   const [agentUseTls, setAgentUseTls] = useState<boolean>(true);
   const [agentHeartbeatInterval, setAgentHeartbeatInterval] = useState<number>(30);
@@ -76,15 +87,20 @@ export function ClientOnboarding() {
   useEffect(() => {
     async function loadData() {
       try {
-        const [templateData, ip] = await Promise.all([
+        const [templateData, ipInfo, clientData] = await Promise.all([
           listTemplates(),
           getConsultantIp(),
+          listClients(),
         ]);
+        const ip = ipInfo.suggestedIp ?? "";
         setTemplates(templateData);
-        setConsultantIp(ip);
+        setClients(clientData);
+        setAgentClientId(clientData[0]?.id ?? "");
+        setInterfaces(ipInfo.interfaces);
+        handleConsultantIpChange(ip);
         setAgentClientIp(ip); // Set default agent callback IP
         addLog("info", "Factory module initialized");
-        addLog("info", `Consultant IP detected: ${ip}`);
+        addLog("info", `Consultant IP detected: ${ip || "none"} (${ipInfo.interfaces.length} interfaces)`);
       } catch (error) {
         addLog("error", `Failed to initialize: ${error}`);
       }
@@ -92,6 +108,25 @@ export function ClientOnboarding() {
     loadData();
   }, []);
 
+  // Load the selected client's live agent tokens
+  useEffect(() => {
+    if (!agentClientId) {
+      setAgentTokens([]);
+      setAgentTokenId("");
+      return;
+    }
+    listAgentTokens(agentClientId)
+      .then((tokens) => {
+        const now = Date.now();
+        const live = tokens.filter(
+          (t) => !t.revokedAt && (!t.expiresAt || new Date(t.expiresAt).getTime() > now)
+        );
+        setAgentTokens(live);
+        setAgentTokenId(live[0]?.id ?? "");
+      })
+      .catch((error) => addLog("error", `Failed to load agent tokens: ${error}`));
+  }, [agentClientId]);
+
   // Add log entry
   const addLog = (level: LogEntry["level"], message: string) => {
     setLogs((prev) => [
@@ -100,6 +135,12 @@ export function ClientOnboarding() {
     ]);
   };
 
+  // Keep the injected consultant IP in the script config
+  const handleConsultantIpChange = (ip: string) => {
+    setConsultantIp(ip);
+    setConfig((prev) => ({ ...prev, consultantIp: ip || undefined }));
+  };
+
   // Handle validation
   const handleValidate = async () => {
     setIsValidating(true);
@@ -210,12 +251,9 @@ export function ClientOnboarding() {
       addLog("error", "Client IP is required for agent script generation");
       return;
     }
-
-    // Auto-generate auth token if empty
-    const authToken = agentAuthToken || crypto.randomUUID();
-    if (!agentAuthToken) {
-      setAgentAuthToken(authToken);
-      addLog("info", "Generated new authentication token");
+    if (!agentTokenId) {
+      addLog("error", "Create an authentication token before generating the agent script");
+      return;
     }
 
     setIsGeneratingAgent(true);
@@ -224,7 +262,7 @@ export function ClientOnboarding() {
     try {
       const result = await generateAgentScript({
         clientIp: agentClientIp,
-        authToken,
+        tokenId: agentTokenId,
         callbackPort: agentCallbackPort,
         useTls: agentUseTls,
         heartbeatInterval: agentHeartbeatInterval,
@@ -232,6 +270,7 @@ export function ClientOnboarding() {
 
       setGeneratedAgentScript(result);
       addLog("success", `Agent script generated: ${result.scriptId}`);
+      addLog("info", `Agent script written to ${result.outputPath}`);
       result.warnings.forEach((warn) => addLog("warn", warn));
     } catch (error) {
       addLog("error", `Agent script generation failed: ${error}`);
@@ -240,11 +279,38 @@ export function ClientOnboarding() {
     }
   };
 
-  // Copy agent script to clipboard
+  // Create a new agent token for the selected client
+  const handleCreateAgentToken = async () => {
+    if (!agentClientId) return;
+    try {
+      const token = await issueAgentToken(agentClientId);
+      setAgentTokens((prev) => [token, ...prev]);
+      setAgentTokenId(token.id);
+      addLog("info", `Created authentication token ending ${token.lastFour}`);
+    } catch (error) {
+      addLog("error", `Failed to create authentication token: ${error}`);
+    }
+  };
+
+  // Revoke the selected agent token
+  const handleRevokeAgentToken = async () => {
+    if (!agentTokenId) return;
+    try {
+      const token = await revokeAgentToken(agentTokenId);
+      const remaining = agentTokens.filter((t) => t.id !== token.id);
+      setAgentTokens(remaining);
+      setAgentTokenId(remaining[0]?.id ?? "");
+      addLog("warn", `Revoked authentication token ending ${token.lastFour}`);
+    } catch (error) {
+      addLog("error", `Failed to revoke authentication token: ${error}`);
+    }
+  };
+
+  // Copy the agent script's file path (the preview has the token redacted)
   const handleCopyAgentScript = async () => {
-    if (generatedAgentScript?.scriptContent) {
-      await navigator.clipboard.writeText(generatedAgentScript.scriptContent);
-      addLog("info", "Agent script copied to clipboard");
+    if (generatedAgentScript?.outputPath) {
+      await navigator.clipboard.writeText(generatedAgentScript.outputPath);
+      addLog("info", "Agent script path copied to clipboard");
     }
   };
 
@@ -329,9 +395,27 @@ export function ClientOnboarding() {
               <div className="mt-4 flex items-center gap-2 text-sm">
                 <Wifi className="w-4 h-4 text-secure" />
                 <span className="text-slate-400">Consultant IP:</span>
-                <code className="text-optio-400 bg-slate-900 px-2 py-0.5 rounded">
-                  {consultantIp || "Detecting..."}
-                </code>
+                {interfaces.length > 0 ? (
+                  <select
+                    value={consultantIp}
+                    onChange={(e) => handleConsultantIpChange(e.target.value)}
+                    className="px-2 py-0.5 bg-slate-900 border border-slate-700 rounded text-optio-400 font-mono focus:outline-none focus:ring-2 focus:ring-optio-500"
+                  >
+                    {interfaces
+                      .filter((iface) => !iface.isLoopback)
+                      .flatMap((iface) =>
+                        [...iface.ipv4, ...iface.ipv6].map((ip) => (
+                          <option key={`${iface.name}-${ip}`} value={ip}>
+                            {ip} ({iface.name}{iface.isVirtual ? ", virtual/VPN" : ""})
+                          </option>
+                        ))
+                      )}
+                  </select>
+                ) : (
+                  <code className="text-optio-400 bg-slate-900 px-2 py-0.5 rounded">
+                    {consultantIp || "Detecting..."}
+                  </code>
+                )}
               </div>
             </div>
 
@@ -685,22 +769,46 @@ export function ClientOnboarding() {
                     Authentication Token
                   </label>
                   <div className="flex gap-2">
-                    <input
-                      type="text"
-                      value={agentAuthToken}
-                      onChange={(e) => setAgentAuthToken(e.target.value)}
-                      placeholder="Leave empty to auto-generate"
-                      className="flex-1 px-4 py-2.5 bg-slate-900 border border-slate-700 rounded-lg text-white placeholder-slate-500 focus:outline-none focus:ring-2 focus:ring-optio-500 focus:border-transparent font-mono text-sm"
-                    />
+                    <select
+                      value={agentClientId}
+                      onChange={(e) => setAgentClientId(e.target.value)}
+                      className="px-4 py-2.5 bg-slate-900 border border-slate-700 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-optio-500 focus:border-transparent text-sm"
+                    >
+                      {clients.length === 0 && <option value="">No clients</option>}
+                      {clients.map((c) => (
+                        <option key={c.id} value={c.id}>{c.name}</option>
+                      ))}
+                    </select>
+                    <select
+                      value={agentTokenId}
+                      onChange={(e) => setAgentTokenId(e.target.value)}
+                      className="flex-1 px-4 py-2.5 bg-slate-900 border border-slate-700 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-optio-500 focus:border-transparent font-mono text-sm"
+                    >
+                      {agentTokens.length === 0 && <option value="">No active tokens</option>}
+                      {agentTokens.map((t) => (
+                        <option key={t.id} value={t.id}>
+                          {t.label ? `${t.label} - ` : ""}optio_at_****{t.lastFour} ({new Date(t.createdAt).toLocaleDateString()}
+                          {t.expiresAt ? `, expires ${new Date(t.expiresAt).toLocaleDateString()}` : ""})
+                        </option>
+                      ))}
+                    </select>
+                    <button
+                      onClick={handleCreateAgentToken}
+                      disabled={!agentClientId}
+                      className="px-4 py-2.5 bg-slate-700 text-white rounded-lg hover:bg-slate-600 transition-colors disabled:opacity-50"
+                    >
+                      New Token
+                    </button>
                     <button
-                      onClick={() => setAgentAuthToken(crypto.randomUUID())}
-                      className="px-4 py-2.5 bg-slate-700 text-white rounded-lg hover:bg-slate-600 transition-colors"
+                      onClick={handleRevokeAgentToken}
+                      disabled={!agentTokenId}
+                      className="px-4 py-2.5 bg-slate-700 text-white rounded-lg hover:bg-slate-600 transition-colors disabled:opacity-50"
                     >
-                      Generate
+                      Revoke
                     </button>
                   </div>
                   <p className="text-xs text-slate-500 mt-1">
-                    Unique token for authenticating the agent connection
+                    Tokens are kept in the OS credential store and only written into the generated script file
                   </p>
                 </div>
               </div>
@@ -736,7 +844,7 @@ export function ClientOnboarding() {
             <div className="flex gap-3">
               <button
                 onClick={handleGenerateAgentScript}
-                disabled={isGeneratingAgent || !agentClientIp}
+                disabled={isGeneratingAgent || !agentClientIp || !agentTokenId}
                 className="flex items-center gap-2 px-6 py-2.5 bg-optio-600 text-white rounded-lg hover:bg-optio-700 transition-colors disabled:opacity-50"
               >
                 {isGeneratingAgent ? (
@@ -764,6 +872,10 @@ export function ClientOnboarding() {
                     <p>
                       <strong>Generated:</strong> {new Date(generatedAgentScript.generatedAt).toLocaleString()}
                     </p>
+                    <p>
+                      <strong>File:</strong>{" "}
+                      <code className="bg-slate-800 px-2 py-0.5 rounded">{generatedAgentScript.outputPath}</code>
+                    </p>
                     <p>
                       <strong>Callback:</strong>{" "}
                       <code className="bg-slate-800 px-2 py-0.5 rounded">{agentClientIp}:{agentCallbackPort}</code>
@@ -787,7 +899,7 @@ export function ClientOnboarding() {
                     className="flex items-center gap-2 px-4 py-2 bg-slate-700 text-white rounded-lg hover:bg-slate-600 transition-colors"
                   >
                     <Copy className="w-4 h-4" />
-                    Copy Script
+                    Copy File Path
                   </button>
                 </div>
 
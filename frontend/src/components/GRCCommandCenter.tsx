@@ -1,4 +1,5 @@
 import { useState, useEffect } from "react";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import {
   listFrameworks,
   getFrameworkControls,
@@ -9,6 +10,8 @@ import {
   getControlAssessments,
   listClients,
   getComplianceStatus,
+  exportAssessment,
+  importAssessment,
 } from "@/lib/commands";
 import type {
   FrameworkInfo,
@@ -19,6 +22,7 @@ import type {
   Client,
   ComplianceStatus,
   ComplianceStatusReport,
+  TransferWarning,
 } from "@/types";
 import { cn } from "@/lib/utils";
 import {
@@ -37,6 +41,8 @@ import {
   RefreshCw,
   TrendingUp,
   Activity,
+  Download,
+  Upload,
 } from "lucide-react";
 
 type ViewMode = "heatmap" | "list";
@@ -58,6 +64,47 @@ export function GRCCommandCenter() {
   const [showNewAssessmentModal, setShowNewAssessmentModal] = useState(false);
   const [complianceStatusReport, setComplianceStatusReport] = useState<ComplianceStatusReport | null>(null);
 
+  const handleExportAssessment = async () => {
+    if (!selectedAssessment) return;
+    // Evidence files travel in a zip bundle
+    const includeFiles = window.confirm("Include evidence files in the bundle?");
+    const extension = includeFiles ? "zip" : "json";
+    const path = await save({
+      defaultPath: `${selectedAssessment.name.replace(/[^\w-]+/g, "_")}.optio.${extension}`,
+      filters: [{ name: "Optio assessment", extensions: [extension] }],
+    });
+    if (!path) return;
+
+    try {
+      const result = await exportAssessment(selectedAssessment.id, path, includeFiles);
+      const notes = transferNotes(result.warnings);
+      window.alert(
+        `Exported ${result.controlAssessments} control assessments and ${result.evidence} evidence items.${notes}`
+      );
+    } catch (error) {
+      window.alert(`Export failed: ${error}`);
+    }
+  };
+
+  const handleImportAssessment = async () => {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: "Optio assessment", extensions: ["json", "zip"] }],
+    });
+    if (!path || Array.isArray(path)) return;
+
+    try {
+      const summary = await importAssessment(path, "MERGE_NEWEST");
+      const notes = transferNotes(summary.warnings);
+      window.alert(
+        `Imported: ${summary.created} created, ${summary.updated} updated, ${summary.skipped} skipped.${notes}`
+      );
+      setAssessments(await listAssessments());
+    } catch (error) {
+      window.alert(`Import failed: ${error}`);
+    }
+  };
+
   // Load initial data
   useEffect(() => {
     async function loadData() {
@@ -204,13 +251,30 @@ export function GRCCommandCenter() {
               <p className="text-slate-400">Governance, Risk & Compliance Management</p>
             </div>
           </div>
-          <button
-            onClick={() => setShowNewAssessmentModal(true)}
-            className="flex items-center gap-2 px-4 py-2 bg-optio-600 text-white rounded-lg hover:bg-optio-700 transition-colors"
-          >
-            <Plus className="w-4 h-4" />
-            New Assessment
-          </button>
+          <div className="flex items-center gap-2">
+            <button
+              onClick={handleImportAssessment}
+              className="flex items-center gap-2 px-4 py-2 bg-slate-800 text-slate-300 rounded-lg hover:bg-slate-700 transition-colors"
+            >
+              <Upload className="w-4 h-4" />
+              Import
+            </button>
+            <button
+              onClick={handleExportAssessment}
+              disabled={!selectedAssessment}
+              className="flex items-center gap-2 px-4 py-2 bg-slate-800 text-slate-300 rounded-lg hover:bg-slate-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+            >
+              <Download className="w-4 h-4" />
+              Export
+            </button>
+            <button
+              onClick={() => setShowNewAssessmentModal(true)}
+              className="flex items-center gap-2 px-4 py-2 bg-optio-600 text-white rounded-lg hover:bg-optio-700 transition-colors"
+            >
+              <Plus className="w-4 h-4" />
+              New Assessment
+            </button>
+          </div>
         </div>
 
         {/* Framework Toggle */}
@@ -902,5 +966,12 @@ function formatFramework(fw: string): string {
   if (fw === "NistCsf2") return "NIST_CSF_2";
   if (fw === "Soc2TypeII") return "SOC_2_TYPE_II";
   if (fw === "Gdpr") return "GDPR";
+  if (fw === "Iso27001") return "ISO27001";
   return fw;
 }
+
+// Transfer warnings as lines for an alert
+function transferNotes(warnings: TransferWarning[]): string {
+  if (warnings.length === 0) return "";
+  return `\n\n${warnings.map((w) => `${w.label}: ${w.message}`).join("\n")}`;
+}
@@ -8,10 +8,12 @@
  */
 
 import { useState, useEffect } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
 import {
   getCloudReadinessItems,
   getK8sHardeningChecklist,
   getK8sSeverityStats,
+  auditK8sFromFiles,
   getFinOpsTemplates,
   compareCloudProviders,
 } from "@/lib/commands";
@@ -19,6 +21,7 @@ import type {
   CloudReadinessItem,
   K8sHardeningCheck,
   K8sSeverityStats,
+  K8sManifestAudit,
   FinOpsTemplate,
   ProviderComparison,
   ResourceInput,
@@ -220,11 +223,36 @@ function K8sHardeningTab() {
   const [loading, setLoading] = useState(true);
   const [selectedCategory, setSelectedCategory] = useState<string>("all");
   const [selectedSeverity, setSelectedSeverity] = useState<string>("all");
+  const [manifestAudit, setManifestAudit] = useState<K8sManifestAudit | null>(null);
+  const [auditing, setAuditing] = useState(false);
+  const [auditError, setAuditError] = useState<string | null>(null);
 
   useEffect(() => {
     loadData();
   }, []);
 
+  const handleAuditManifests = async () => {
+    const selected = await open({
+      multiple: true,
+      filters: [{ name: "Kubernetes manifests", extensions: ["yaml", "yml", "json"] }],
+    });
+    if (!selected) return;
+
+    setAuditing(true);
+    setAuditError(null);
+    try {
+      const paths = Array.isArray(selected) ? selected : [selected];
+      setManifestAudit(await auditK8sFromFiles(paths));
+    } catch (err) {
+      setAuditError(String(err));
+    } finally {
+      setAuditing(false);
+    }
+  };
+
+  const checkTitle = (checkId: string) =>
+    checks.find((check) => check.id === checkId)?.title ?? checkId;
+
   const loadData = async () => {
     try {
       const [checksData, statsData] = await Promise.all([
@@ -339,11 +367,80 @@ function K8sHardeningTab() {
             </select>
           </div>
         </div>
-        <div className="text-slate-400 text-sm">
-          {filteredChecks.length} checks
+        <div className="flex items-center space-x-4">
+          <div className="text-slate-400 text-sm">
+            {filteredChecks.length} checks
+          </div>
+          <button
+            onClick={handleAuditManifests}
+            disabled={auditing}
+            className="px-3 py-1.5 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white text-sm rounded-md transition-colors"
+          >
+            {auditing ? "Auditing..." : "Audit Manifests"}
+          </button>
         </div>
       </div>
 
+      {auditError && (
+        <div className="bg-red-900/30 border border-red-700 rounded-lg p-3 text-red-300 text-sm">
+          {auditError}
+        </div>
+      )}
+
+      {/* Manifest Audit Results */}
+      {manifestAudit && (
+        <div className="bg-slate-800 border border-slate-700 rounded-lg p-4 space-y-4">
+          <div className="flex items-center justify-between">
+            <h3 className="text-white font-medium">Manifest Audit</h3>
+            <div className="text-slate-400 text-sm">
+              {manifestAudit.workloads.length} workloads from {manifestAudit.files.length} files
+              {" · "}
+              {manifestAudit.summary.passed}/{manifestAudit.summary.totalChecks} checks passed
+            </div>
+          </div>
+
+          {manifestAudit.findings.length === 0 ? (
+            <p className="text-green-400 text-sm">No pod-security issues found.</p>
+          ) : (
+            <div className="space-y-2">
+              {manifestAudit.findings.map((finding) => (
+                <div key={finding.id} className="bg-slate-900 rounded p-3">
+                  <div className="flex items-center space-x-2 mb-1">
+                    <span className={`px-2 py-0.5 rounded text-xs font-medium ${
+                      severityColors[finding.severity.charAt(0) + finding.severity.slice(1).toLowerCase()] || "bg-slate-600"
+                    } text-white`}>
+                      {finding.severity}
+                    </span>
+                    <span className="text-white text-sm">{checkTitle(finding.checkId)}</span>
+                    <span className="text-slate-500 text-xs">
+                      {finding.resourceType}/{finding.namespace ? `${finding.namespace}/` : ""}{finding.resourceName}
+                    </span>
+                  </div>
+                  <p className="text-slate-400 text-xs">{finding.details}</p>
+                </div>
+              ))}
+            </div>
+          )}
+
+          {manifestAudit.unparsed.length > 0 && (
+            <div>
+              <div className="text-yellow-400 text-sm font-medium mb-1">
+                {manifestAudit.unparsed.length} resources could not be evaluated
+              </div>
+              <ul className="space-y-1">
+                {manifestAudit.unparsed.map((item, i) => (
+                  <li key={i} className="text-slate-400 text-xs">
+                    {item.source}
+                    {item.document !== null && ` (document ${item.document})`}
+                    {item.name && ` ${item.kind ?? ""}/${item.name}`}: {item.reason}
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+        </div>
+      )}
+
       {/* NSA/CISA Reference Banner */}
       <div className="bg-slate-800/50 border border-slate-700 rounded-lg p-4">
         <div className="flex items-center space-x-2 mb-2">
@@ -6,6 +6,7 @@
  */
 
 import { useState, useEffect } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
 import {
   checkNmap,
   getScanTypeList,
@@ -13,9 +14,11 @@ import {
   getCommonPortList,
   validateScanTarget,
   previewScanCommand,
+  planScanTargets,
   scanNetwork,
   getDefaultScanPorts,
   getExtendedScanPorts,
+  importScanResults,
 } from "@/lib/commands";
 import type {
   NmapInfo,
@@ -23,7 +26,9 @@ import type {
   Asset,
   CommonPort,
   TargetValidation,
+  ScanTargetPlan,
   ScanNetworkResponse,
+  ScanImportSummary,
 } from "@/types";
 
 type NetworkTab = "scanner" | "assets" | "ports" | "native";
@@ -142,11 +147,34 @@ function AssetInventoryTab() {
   const [filterCategory, setFilterCategory] = useState<string>("all");
   const [filterCriticality, setFilterCriticality] = useState<string>("all");
   const [searchTerm, setSearchTerm] = useState("");
+  const [importing, setImporting] = useState(false);
+  const [importSummary, setImportSummary] = useState<ScanImportSummary | null>(null);
+  const [importError, setImportError] = useState<string | null>(null);
 
   useEffect(() => {
     loadAssets();
   }, []);
 
+  const handleImport = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: "Nmap output", extensions: ["xml", "gnmap"] }],
+    });
+    if (!selected || Array.isArray(selected)) return;
+
+    setImporting(true);
+    setImportError(null);
+    setImportSummary(null);
+    try {
+      setImportSummary(await importScanResults("demo-client", selected));
+      await loadAssets();
+    } catch (err) {
+      setImportError(String(err));
+    } finally {
+      setImporting(false);
+    }
+  };
+
   const loadAssets = async () => {
     try {
       const data = await getDemoAssets("demo-client");
@@ -240,8 +268,39 @@ function AssetInventoryTab() {
         </div>
       </div>
 
+      {/* Import */}
+      {(importSummary || importError) && (
+        <div
+          className={`border rounded-lg p-4 text-sm ${
+            importError ? "bg-red-900/30 border-red-700 text-red-300" : "bg-slate-800 border-slate-700 text-slate-300"
+          }`}
+        >
+          {importError ? (
+            importError
+          ) : importSummary && (
+            <>
+              <div className="text-white font-medium mb-1">{importSummary.scanName}</div>
+              <div>
+                {importSummary.hostsImported} live hosts imported: {importSummary.newAssets} new,{" "}
+                {importSummary.updatedAssets} updated
+              </div>
+              {importSummary.warnings.map((warning) => (
+                <div key={warning} className="text-yellow-400 mt-1">{warning}</div>
+              ))}
+            </>
+          )}
+        </div>
+      )}
+
       {/* Filters */}
       <div className="flex items-center space-x-4">
+        <button
+          onClick={handleImport}
+          disabled={importing}
+          className="px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:bg-slate-700 disabled:cursor-not-allowed text-white font-medium rounded-md transition-colors"
+        >
+          {importing ? "Importing..." : "Import Nmap XML"}
+        </button>
         <input
           type="text"
           placeholder="Search by name or IP..."
@@ -669,6 +728,7 @@ function NetworkScannerTab({ nmapInstalled }: { nmapInstalled: boolean }) {
   const [target, setTarget] = useState("");
   const [targetValidation, setTargetValidation] = useState<TargetValidation | null>(null);
   const [commandPreview, setCommandPreview] = useState<string>("");
+  const [targetPlan, setTargetPlan] = useState<ScanTargetPlan | null>(null);
   const [ports, setPorts] = useState("");
   const [aggressive, setAggressive] = useState(false);
 
@@ -696,8 +756,12 @@ function NetworkScannerTab({ nmapInstalled }: { nmapInstalled: boolean }) {
       previewScanCommand([target], selectedScanType, ports || undefined, aggressive)
         .then(setCommandPreview)
         .catch(console.error);
+      planScanTargets([target], undefined, selectedScanType, ports || undefined, aggressive)
+        .then(setTargetPlan)
+        .catch(console.error);
     } else {
       setCommandPreview("");
+      setTargetPlan(null);
     }
   }, [target, selectedScanType, ports, aggressive, targetValidation]);
 
@@ -829,6 +893,19 @@ function NetworkScannerTab({ nmapInstalled }: { nmapInstalled: boolean }) {
             <div className="bg-slate-900 rounded-lg p-4 font-mono text-sm text-green-400 overflow-x-auto">
               {commandPreview}
             </div>
+            {targetPlan && (
+              <div className="mt-2 space-y-1 text-sm">
+                <p className="text-slate-400">{targetPlan.estimate.summary}</p>
+                {targetPlan.plan.warnings.map((warning) => (
+                  <p
+                    key={warning}
+                    className={warning.startsWith("PUBLIC") ? "text-red-400 font-medium" : "text-yellow-400"}
+                  >
+                    {warning}
+                  </p>
+                ))}
+              </div>
+            )}
           </div>
         )}
 
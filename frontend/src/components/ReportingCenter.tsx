@@ -156,6 +156,9 @@ export function ReportingCenter() {
       setActiveTab("reports");
       // Reset form
       setFormData((prev) => ({ ...prev, title: "" }));
+      if (report.warnings.length > 0) {
+        alert(`Report generated with warnings:\n${report.warnings.join("\n")}`);
+      }
     } catch (error) {
       console.error("Failed to generate report:", error);
       alert(`Failed to generate report: ${error}`);
@@ -5,68 +5,189 @@
  */
 
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   Client,
   CreateClientRequest,
   UpdateClientRequest,
+  ClientDependencies,
+  ClientDeletionPreview,
+  ClientDeletionSummary,
+  ClientOverview,
+  ClientDashboard,
+  PortfolioFilter,
+  PortfolioOverview,
+  PortfolioSort,
   GenerateScriptRequest,
   GenerateScriptResponse,
   TemplateInfo,
   PreviewRequest,
   ValidateConfigRequest,
   ValidationResult,
+  ScriptSyntaxReport,
+  TemplateLint,
+  ScriptManifest,
+  ScriptVerification,
+  SigningKeyInfo,
+  ScriptRecord,
+  ScriptRecordSummary,
+  ScriptRegeneration,
+  PrepAuditReport,
   SystemInfo,
+  DatabaseStatus,
+  BackupManifest,
+  BackupRestore,
+  ConsultantIpInfo,
+  ConsultantInterface,
+  SubnetConsultantIp,
+  OnboardingStatus,
+  ActivityEvent,
+  AuditExportFormat,
+  AuditChainVerification,
+  SearchEntityType,
+  SearchHit,
+  ActivityModule,
   FrameworkInfo,
   Control,
+  ControlSearchResult,
+  ComplianceStatus,
   Assessment,
   CreateAssessmentRequest,
   ControlAssessment,
+  ControlAssessmentRevision,
   UpdateControlAssessmentRequest,
   Evidence,
+  EvidenceCoverage,
   CreateEvidenceRequest,
   AssessmentSummary,
+  AssessmentComparison,
+  AssessmentSnapshot,
+  SnapshotComparison,
   CloudReadinessItem,
   CloudReadinessAssessment,
-  PerformReadinessAssessmentRequest,
+  SaveReadinessAnswersRequest,
+  ReadinessScore,
   K8sHardeningCheck,
   K8sHardeningAudit,
   PerformK8sAuditRequest,
   K8sSeverityStats,
+  K8sAuditComparison,
+  K8sManifestAudit,
+  K8sAuditResult,
   FinOpsTemplate,
   FinOpsAnalysis,
   GenerateFinOpsAnalysisRequest,
   CalculateResourceCostRequest,
   ProviderComparison,
   CompareProvidersRequest,
+  ResourceCostComparison,
+  CompareResourceCostsRequest,
+  GenerateTcoProjectionRequest,
+  TcoProjection,
+  PricingProfile,
+  SavePricingProfileRequest,
+  PricingImportSummary,
+  UtilizationImportSummary,
   NmapInfo,
   ScanTypeInfo,
   CommonPort,
   TargetValidation,
+  ScanTargetPlan,
+  CustomArgsValidation,
   ScanJob,
+  ScanDetail,
+  ScanDiff,
+  ScanImportSummary,
   CreateScanRequest,
   Asset,
   AssetGroup,
+  AssetChange,
+  AssetFilter,
+  AssetPage,
+  AssetPatch,
+  AssetExportFormat,
+  AssetExportSummary,
+  AssetImportSummary,
+  AssetView,
   UpdateAssetRequest,
   CreateGroupRequest,
   NetworkStats,
+  ServicePolicy,
+  UpsertServicePolicyRequest,
+  AssetPolicyViolations,
+  VulnImportSummary,
+  AssetVulnerabilityReport,
+  VulnerabilityMatch,
   ReportTemplate,
   ReportTypeInfo,
   ExportFormatInfo,
   GenerateReportRequest,
   Report,
   ReportContent,
+  CustomSection,
+  CustomSectionRequest,
+  ReportPreviewSection,
+  ReportPreviewDone,
   ReportSummary,
   ReportStats,
   // Task A & B types
   GenerateAgentScriptRequest,
+  AgentToken,
+  AgentListenerStatus,
+  ConnectedAgent,
+  AgentTelemetry,
   AgentScriptResponse,
   ScanNetworkRequest,
   ScanNetworkResponse,
   ScannedHost,
+  FindingRule,
+  NetworkFinding,
+  CreateVerificationScanRequest,
+  ScanSchedule,
+  CreateScanScheduleRequest,
+  ScanProfile,
+  ScanProfileRequest,
+  ScheduledScanCompleted,
   // Phase 3 types
   ComplianceStatusReport,
+  ComplianceSnapshot,
+  ComplianceTrend,
+  ComplianceOverview,
+  ControlMapping,
+  FrameworkProjection,
+  CategoryGrouping,
+  SetCategoryGroupingRequest,
+  RiskItem,
+  SaveRiskRequest,
+  RiskMatrix,
+  ProcessingActivity,
+  SaveProcessingActivityRequest,
+  ProcessingActivityResult,
+  EffortMapping,
+  RemediationItem,
+  RemediationPlan,
+  RemediationStats,
+  ClientRemediationItem,
+  UpdateRemediationItemRequest,
+  AssessmentExportResult,
+  AssessmentImportSummary,
+  ConflictStrategy,
+  PolicyType,
+  PolicyDocument,
   GenerateExecutivePdfRequest,
   PdfGenerationResult,
+  ExportFileResult,
+  BrandingProfile,
+  SaveBrandingProfileRequest,
+  FindingTemplate,
+  UpsertFindingTemplateRequest,
+  CvssScore,
+  Finding,
+  FindingOverrides,
+  WorkspaceExportOptions,
+  WorkspaceExportProgress,
+  WorkspaceExportSummary,
+  WorkspaceArchiveValidation,
 } from "@/types";
 
 // ============================================================================
@@ -82,6 +203,27 @@ export async function generateClientScript(
   return invoke<GenerateScriptResponse>("generate_client_script", { request });
 }
 
+/**
+ * List a client's generated scripts, newest first
+ */
+export async function listGeneratedScripts(clientId: string): Promise<ScriptRecordSummary[]> {
+  return invoke<ScriptRecordSummary[]>("list_generated_scripts", { clientId });
+}
+
+/**
+ * Get a generated script with the config and content it was produced from
+ */
+export async function getGeneratedScript(scriptId: string): Promise<ScriptRecord | null> {
+  return invoke<ScriptRecord | null>("get_generated_script", { scriptId });
+}
+
+/**
+ * Replay a generated script through the current template and compare the output
+ */
+export async function regenerateScript(scriptId: string): Promise<ScriptRegeneration> {
+  return invoke<ScriptRegeneration>("regenerate_script", { scriptId });
+}
+
 /**
  * List available script templates
  */
@@ -105,6 +247,54 @@ export async function validateConfig(
   return invoke<ValidationResult>("validate_config", { request });
 }
 
+/**
+ * Check a generated script for PowerShell syntax errors.
+ * Uses the PowerShell parser when available, otherwise a built-in check.
+ */
+export async function validateGeneratedScript(
+  content: string
+): Promise<ScriptSyntaxReport> {
+  return invoke<ScriptSyntaxReport>("validate_generated_script", { content });
+}
+
+/**
+ * Lint a template by name, or template source being edited, for unknown
+ * placeholders and structural problems.
+ */
+export async function lintTemplate(nameOrContent: string): Promise<TemplateLint> {
+  return invoke<TemplateLint>("lint_template", { nameOrContent });
+}
+
+/**
+ * Check a script against its integrity manifest, or the manifest in the
+ * script's comment footer when none is given.
+ */
+export async function verifyScript(content: string, manifest?: ScriptManifest): Promise<ScriptVerification> {
+  return invoke<ScriptVerification>("verify_script", { content, manifest });
+}
+
+/**
+ * List the script signing public keys, current and retired
+ */
+export async function listSigningKeys(): Promise<SigningKeyInfo[]> {
+  return invoke<SigningKeyInfo[]>("list_signing_keys");
+}
+
+/**
+ * Replace the script signing key; scripts signed with the old key still verify
+ */
+export async function rotateSigningKey(): Promise<SigningKeyInfo> {
+  return invoke<SigningKeyInfo>("rotate_signing_key");
+}
+
+/**
+ * Read the JSON written by an audit-only script and compare each setting
+ * with what the prep script would change
+ */
+export async function parsePrepAuditResult(path: string): Promise<PrepAuditReport> {
+  return invoke<PrepAuditReport>("parse_prep_audit_result", { path });
+}
+
 /**
  * Generate an agent script with hardcoded connection parameters (Task A)
  * Creates a PowerShell script for establishing reverse connections to Optio
@@ -115,6 +305,33 @@ export async function generateAgentScript(
   return invoke<AgentScriptResponse>("generate_agent_script", { request });
 }
 
+/**
+ * Create an agent auth token for a client
+ * The token itself stays in the OS credential store; only metadata is returned.
+ * Without ttlDays the token lasts until revoked.
+ */
+export async function issueAgentToken(
+  clientId: string,
+  label?: string,
+  ttlDays?: number
+): Promise<AgentToken> {
+  return invoke<AgentToken>("issue_agent_token", { clientId, label, ttlDays });
+}
+
+/**
+ * List a client's agent tokens, newest first
+ */
+export async function listAgentTokens(clientId: string): Promise<AgentToken[]> {
+  return invoke<AgentToken[]>("list_agent_tokens", { clientId });
+}
+
+/**
+ * Revoke an agent token so callbacks presenting it are rejected
+ */
+export async function revokeAgentToken(tokenId: string): Promise<AgentToken> {
+  return invoke<AgentToken>("revoke_agent_token", { tokenId });
+}
+
 // ============================================================================
 // Client Commands
 // ============================================================================
@@ -148,10 +365,64 @@ export async function updateClient(request: UpdateClientRequest): Promise<Client
 }
 
 /**
- * Delete a client
+ * Count the records that reference a client, for confirming a delete
+ */
+export async function getClientDependencies(id: string): Promise<ClientDependencies> {
+  return invoke<ClientDependencies>("get_client_dependencies", { id });
+}
+
+/**
+ * Get a client with counts of its assessments, assets, reports and open risks
+ */
+export async function getClientOverview(clientId: string): Promise<ClientOverview> {
+  return invoke<ClientOverview>("get_client_overview", { clientId });
+}
+
+/**
+ * Get everything a client's dashboard shows in one call
+ */
+export async function getClientDashboard(clientId: string): Promise<ClientDashboard> {
+  return invoke<ClientDashboard>("get_client_dashboard", { clientId });
+}
+
+/**
+ * Get every client's compliance, risk, findings, scanning and remediation,
+ * sorted by health score (worst first) unless another order is given
+ */
+export async function getPortfolioOverview(
+  filter?: PortfolioFilter,
+  sortBy?: PortfolioSort
+): Promise<PortfolioOverview> {
+  return invoke<PortfolioOverview>("get_portfolio_overview", { filter, sortBy });
+}
+
+/**
+ * Export the portfolio table as CSV, with the same filter and order
+ */
+export async function exportPortfolioCsv(
+  filter?: PortfolioFilter,
+  sortBy?: PortfolioSort
+): Promise<string> {
+  return invoke<string>("export_portfolio_csv", { filter, sortBy });
+}
+
+/**
+ * Count everything deleting a client removes, for the confirmation dialog
+ */
+export async function previewClientDeletion(id: string): Promise<ClientDeletionPreview> {
+  return invoke<ClientDeletionPreview>("preview_client_deletion", { id });
+}
+
+/**
+ * Delete a client and all of its data, optionally archiving its workspace
+ * to `archivePath` first
  */
-export async function deleteClient(id: string): Promise<boolean> {
-  return invoke<boolean>("delete_client", { id });
+export async function deleteClient(
+  id: string,
+  archive?: boolean,
+  archivePath?: string
+): Promise<ClientDeletionSummary> {
+  return invoke<ClientDeletionSummary>("delete_client", { id, archive, archivePath });
 }
 
 // ============================================================================
@@ -166,10 +437,206 @@ export async function getSystemInfo(): Promise<SystemInfo> {
 }
 
 /**
- * Get consultant's local IP address
+ * List the consultant's network interfaces with the suggested IP, and the
+ * client's preferred IP when clientId is given
+ */
+export async function getConsultantIp(clientId?: string): Promise<ConsultantIpInfo> {
+  return invoke<ConsultantIpInfo>("get_consultant_ip", { clientId });
+}
+
+/**
+ * Persist the consultant IP injected into a client's scripts.
+ * Loopback and link-local addresses are rejected.
+ */
+export async function setPreferredConsultantIp(clientId: string, ip: string): Promise<void> {
+  return invoke<void>("set_preferred_consultant_ip", { clientId, ip });
+}
+
+/**
+ * List the consultant's non-loopback interfaces with subnets and routing
+ */
+export async function listNetworkInterfaces(): Promise<ConsultantInterface[]> {
+  return invoke<ConsultantInterface[]>("list_network_interfaces");
+}
+
+/**
+ * Persist the interface whose address is suggested by default; null clears it
+ */
+export async function setPreferredInterface(name: string | null): Promise<void> {
+  return invoke<void>("set_preferred_interface", { name });
+}
+
+/**
+ * Suggest the consultant IP that reaches an engagement subnet
+ */
+export async function suggestConsultantIpForSubnet(targetSubnet: string): Promise<SubnetConsultantIp> {
+  return invoke<SubnetConsultantIp>("suggest_consultant_ip_for_subnet", { targetSubnet });
+}
+
+// ============================================================================
+// Database Commands
+// ============================================================================
+
+/**
+ * Get whether the database is starting, locked (needs its passphrase), ready or failed
+ */
+export async function getDatabaseStatus(): Promise<DatabaseStatus> {
+  return invoke<DatabaseStatus>("get_database_status");
+}
+
+/**
+ * Subscribe to database status changes, sent once startup finishes and on unlock
+ */
+export async function onDatabaseStatus(
+  handler: (status: DatabaseStatus) => void
+): Promise<UnlistenFn> {
+  return listen<DatabaseStatus>("database-status", (event) => handler(event.payload));
+}
+
+/**
+ * Open the encrypted database with its passphrase
+ */
+export async function unlockDatabase(passphrase: string): Promise<DatabaseStatus> {
+  return invoke<DatabaseStatus>("unlock_database", { passphrase });
+}
+
+/**
+ * Encrypt the existing database under a passphrase (at least 12 characters).
+ * The passphrase is needed at every start and can't be recovered.
+ */
+export async function setDatabasePassphrase(passphrase: string): Promise<DatabaseStatus> {
+  return invoke<DatabaseStatus>("set_database_passphrase", { passphrase });
+}
+
+/**
+ * Change the database passphrase; the data stays encrypted under the same key
+ */
+export async function changeDatabasePassphrase(
+  currentPassphrase: string,
+  newPassphrase: string
+): Promise<DatabaseStatus> {
+  return invoke<DatabaseStatus>("change_database_passphrase", {
+    currentPassphrase,
+    newPassphrase,
+  });
+}
+
+/**
+ * Back up the database now, optionally labelled with why
+ */
+export async function createDatabaseBackup(label?: string): Promise<BackupManifest> {
+  return invoke<BackupManifest>("create_database_backup", { label: label ?? null });
+}
+
+/**
+ * List database backups, newest first
+ */
+export async function listDatabaseBackups(): Promise<BackupManifest[]> {
+  return invoke<BackupManifest[]>("list_database_backups");
+}
+
+/**
+ * Replace the database with a backup. The current database is backed up
+ * first and returned as the safety backup.
+ */
+export async function restoreDatabaseBackup(backupId: string): Promise<BackupRestore> {
+  return invoke<BackupRestore>("restore_database_backup", { backupId });
+}
+
+/**
+ * Delete all but the newest `keepLast` backups, returning the deleted ones
+ */
+export async function pruneDatabaseBackups(keepLast: number): Promise<BackupManifest[]> {
+  return invoke<BackupManifest[]>("prune_database_backups", { keepLast });
+}
+
+// ============================================================================
+// Onboarding Commands
+// ============================================================================
+
+/**
+ * Get first-run onboarding milestone status
+ */
+export async function getOnboardingStatus(): Promise<OnboardingStatus> {
+  return invoke<OnboardingStatus>("get_onboarding_status");
+}
+
+/**
+ * Permanently hide onboarding
+ */
+export async function dismissOnboarding(): Promise<OnboardingStatus> {
+  return invoke<OnboardingStatus>("dismiss_onboarding");
+}
+
+// ============================================================================
+// Activity Commands
+// ============================================================================
+
+/**
+ * Get a client's engagement timeline, newest first, optionally for one module
+ */
+export async function getClientTimeline(
+  clientId: string,
+  limit?: number,
+  offset?: number,
+  moduleFilter?: ActivityModule
+): Promise<ActivityEvent[]> {
+  return invoke<ActivityEvent[]>("get_client_timeline", { clientId, limit, offset, moduleFilter });
+}
+
+/**
+ * Get the most recent activity across all clients
+ */
+export async function getRecentActivity(limit?: number): Promise<ActivityEvent[]> {
+  return invoke<ActivityEvent[]>("get_recent_activity", { limit });
+}
+
+/**
+ * Delete a client's activity recorded before a date (RFC 3339), returning
+ * the number of events removed
+ */
+export async function purgeActivityLog(clientId: string, beforeDate: string): Promise<number> {
+  return invoke<number>("purge_activity_log", { clientId, beforeDate });
+}
+
+// ============================================================================
+// Audit Log Commands
+// ============================================================================
+
+/**
+ * Export audit records between two dates (YYYY-MM-DD or RFC 3339, both
+ * inclusive) as CSV or JSON Lines, with their chain hashes
+ */
+export async function exportAuditLog(
+  format: AuditExportFormat,
+  from?: string,
+  to?: string
+): Promise<string> {
+  return invoke<string>("export_audit_log", { from: from ?? null, to: to ?? null, format });
+}
+
+/**
+ * Check the audit chain for edited, removed or reordered records
+ */
+export async function verifyAuditChain(): Promise<AuditChainVerification> {
+  return invoke<AuditChainVerification>("verify_audit_chain");
+}
+
+// ============================================================================
+// Search Commands
+// ============================================================================
+
+/**
+ * Search clients, assets, controls, control assessments, evidence and
+ * reports, best matches first
  */
-export async function getConsultantIp(): Promise<string> {
-  return invoke<string>("get_consultant_ip");
+export async function searchEverything(
+  query: string,
+  clientId?: string,
+  entityTypes?: SearchEntityType[],
+  limit?: number
+): Promise<SearchHit[]> {
+  return invoke<SearchHit[]>("search_everything", { query, clientId, entityTypes, limit });
 }
 
 // ============================================================================
@@ -190,6 +657,26 @@ export async function getFrameworkControls(framework: string): Promise<Control[]
   return invoke<Control[]>("get_framework_controls_cmd", { framework });
 }
 
+/**
+ * Search a framework's controls by code, title, description and guidance
+ * Results are ranked with highlight offsets; an empty query lists every
+ * control that passes the category/status filters. The status filter
+ * applies to the given assessment.
+ */
+export async function searchControls(
+  framework: string,
+  query: string,
+  options: { category?: string; statusFilter?: ComplianceStatus; assessmentId?: string } = {}
+): Promise<ControlSearchResult[]> {
+  return invoke<ControlSearchResult[]>("search_controls", {
+    framework,
+    query,
+    category: options.category ?? null,
+    statusFilter: options.statusFilter ?? null,
+    assessmentId: options.assessmentId ?? null,
+  });
+}
+
 /**
  * Create a new assessment
  */
@@ -231,10 +718,29 @@ export async function updateAssessmentStatus(
 }
 
 /**
- * Delete an assessment
+ * Delete an assessment (completed ones require force)
+ */
+export async function deleteAssessment(id: string, force?: boolean): Promise<boolean> {
+  return invoke<boolean>("delete_assessment", { id, force });
+}
+
+/**
+ * Reopen a completed or archived assessment, moving it back to In Progress
+ */
+export async function reopenAssessment(id: string): Promise<Assessment> {
+  return invoke<Assessment>("reopen_assessment", { id });
+}
+
+/**
+ * Clone an assessment into a new Draft for reassessment, optionally resetting
+ * every control to Not Assessed; evidence is carried forward by reference
  */
-export async function deleteAssessment(id: string): Promise<boolean> {
-  return invoke<boolean>("delete_assessment", { id });
+export async function cloneAssessment(
+  assessmentId: string,
+  newName: string,
+  resetStatuses: boolean
+): Promise<Assessment> {
+  return invoke<Assessment>("clone_assessment", { assessmentId, newName, resetStatuses });
 }
 
 /**
@@ -255,6 +761,33 @@ export async function getControlAssessments(
   return invoke<ControlAssessment[]>("get_control_assessments", { assessmentId });
 }
 
+/**
+ * Get every saved version of a control's assessment, oldest first
+ */
+export async function getControlHistory(
+  assessmentId: string,
+  controlId: string
+): Promise<ControlAssessmentRevision[]> {
+  return invoke<ControlAssessmentRevision[]>("get_control_history", { assessmentId, controlId });
+}
+
+/**
+ * Make an earlier revision of a control's assessment current again
+ */
+export async function revertControlAssessment(
+  assessmentId: string,
+  controlId: string,
+  revision: number,
+  revertedBy: string
+): Promise<ControlAssessment> {
+  return invoke<ControlAssessment>("revert_control_assessment", {
+    assessmentId,
+    controlId,
+    revision,
+    revertedBy,
+  });
+}
+
 /**
  * Batch update multiple controls
  */
@@ -294,6 +827,43 @@ export async function deleteEvidence(id: string): Promise<boolean> {
   return invoke<boolean>("delete_evidence", { id });
 }
 
+/**
+ * Get the evidence in an assessment linked to a control
+ */
+export async function getEvidenceForControl(
+  assessmentId: string,
+  controlId: string
+): Promise<Evidence[]> {
+  return invoke<Evidence[]>("get_evidence_for_control", { assessmentId, controlId });
+}
+
+/**
+ * Evidence coverage of every control in an assessment's framework
+ */
+export async function getEvidenceCoverage(assessmentId: string): Promise<EvidenceCoverage> {
+  return invoke<EvidenceCoverage>("get_evidence_coverage", { assessmentId });
+}
+
+/**
+ * Link existing evidence to more controls
+ */
+export async function linkEvidenceToControls(
+  evidenceId: string,
+  controlIds: string[]
+): Promise<Evidence> {
+  return invoke<Evidence>("link_evidence_to_controls", { evidenceId, controlIds });
+}
+
+/**
+ * Remove evidence links to controls
+ */
+export async function unlinkEvidenceFromControls(
+  evidenceId: string,
+  controlIds: string[]
+): Promise<Evidence> {
+  return invoke<Evidence>("unlink_evidence_from_controls", { evidenceId, controlIds });
+}
+
 /**
  * Get assessment summary with compliance scores
  */
@@ -303,6 +873,50 @@ export async function getAssessmentSummary(
   return invoke<AssessmentSummary>("get_assessment_summary", { assessmentId });
 }
 
+/**
+ * Compare two assessments of the same framework (idA being the earlier one)
+ */
+export async function compareAssessments(
+  idA: string,
+  idB: string
+): Promise<AssessmentComparison> {
+  return invoke<AssessmentComparison>("compare_assessments", { idA, idB });
+}
+
+/**
+ * Freeze an assessment's summary and control results under a label
+ */
+export async function snapshotAssessment(
+  assessmentId: string,
+  label: string
+): Promise<AssessmentSnapshot> {
+  return invoke<AssessmentSnapshot>("snapshot_assessment", { assessmentId, label });
+}
+
+/**
+ * List an assessment's snapshots, oldest first
+ */
+export async function listSnapshots(assessmentId: string): Promise<AssessmentSnapshot[]> {
+  return invoke<AssessmentSnapshot[]>("list_snapshots", { assessmentId });
+}
+
+/**
+ * Get an assessment snapshot
+ */
+export async function getSnapshot(id: string): Promise<AssessmentSnapshot> {
+  return invoke<AssessmentSnapshot>("get_snapshot", { id });
+}
+
+/**
+ * Compare two snapshots of the same framework (idA being the earlier one)
+ */
+export async function compareSnapshots(
+  idA: string,
+  idB: string
+): Promise<SnapshotComparison> {
+  return invoke<SnapshotComparison>("compare_snapshots", { idA, idB });
+}
+
 // ============================================================================
 // Infrastructure Commands (Cloud Migration & K8s Hardening)
 // ============================================================================
@@ -324,12 +938,28 @@ export async function getCloudReadinessByCategory(
 }
 
 /**
- * Perform cloud readiness assessment
+ * Save answers to a client's cloud readiness checklist
  */
-export async function assessCloudReadiness(
-  request: PerformReadinessAssessmentRequest
+export async function saveCloudReadinessAnswers(
+  request: SaveReadinessAnswersRequest
 ): Promise<CloudReadinessAssessment> {
-  return invoke<CloudReadinessAssessment>("assess_cloud_readiness", { request });
+  return invoke<CloudReadinessAssessment>("save_cloud_readiness_answers", { request });
+}
+
+/**
+ * Get a client's cloud readiness assessment, if one has been started
+ */
+export async function getCloudReadinessAssessment(
+  clientId: string
+): Promise<CloudReadinessAssessment | null> {
+  return invoke<CloudReadinessAssessment | null>("get_cloud_readiness_assessment", { clientId });
+}
+
+/**
+ * Score a client's cloud readiness assessment
+ */
+export async function assessCloudReadiness(clientId: string): Promise<ReadinessScore> {
+  return invoke<ReadinessScore>("assess_cloud_readiness", { clientId });
 }
 
 /**
@@ -358,14 +988,91 @@ export async function auditK8sHardening(
 }
 
 /**
- * Get K8s severity statistics
+ * Audit exported or Helm-rendered manifests (files or directories) for
+ * pod-security issues
  */
-export async function getK8sSeverityStats(): Promise<K8sSeverityStats> {
-  return invoke<K8sSeverityStats>("get_k8s_severity_stats");
+export async function auditK8sFromFiles(paths: string[]): Promise<K8sManifestAudit> {
+  return invoke<K8sManifestAudit>("audit_k8s_from_files", { paths });
 }
 
 /**
- * Get FinOps resource templates
+ * Scan a directory of rendered manifests, or a single manifest file
+ */
+export async function scanK8sManifests(path: string): Promise<K8sManifestAudit> {
+  return invoke<K8sManifestAudit>("scan_k8s_manifests", { path });
+}
+
+/**
+ * Audit a client's live cluster through kubectl and save the result; with
+ * no namespaces, all namespaces are read
+ */
+export async function auditK8sCluster(
+  clientId: string,
+  kubeconfigPath?: string,
+  context?: string,
+  namespaces?: string[]
+): Promise<K8sAuditResult> {
+  return invoke<K8sAuditResult>("audit_k8s_cluster", {
+    clientId,
+    kubeconfigPath,
+    context,
+    namespaces,
+  });
+}
+
+/**
+ * Save a K8s hardening audit of a client's cluster; each save is a new audit
+ */
+export async function saveK8sAudit(
+  request: PerformK8sAuditRequest
+): Promise<K8sHardeningAudit> {
+  return invoke<K8sHardeningAudit>("save_k8s_audit", { request });
+}
+
+/**
+ * List a client's K8s hardening audits, newest first
+ */
+export async function listK8sAudits(clientId: string): Promise<K8sHardeningAudit[]> {
+  return invoke<K8sHardeningAudit[]>("list_k8s_audits", { clientId });
+}
+
+/**
+ * Get a K8s hardening audit with its results
+ */
+export async function getK8sAudit(auditId: string): Promise<K8sHardeningAudit> {
+  return invoke<K8sHardeningAudit>("get_k8s_audit", { auditId });
+}
+
+/**
+ * Compare a re-audit (auditB) against an earlier audit (auditA)
+ */
+export async function compareK8sAudits(
+  auditA: string,
+  auditB: string
+): Promise<K8sAuditComparison> {
+  return invoke<K8sAuditComparison>("compare_k8s_audits", { auditA, auditB });
+}
+
+/**
+ * Get K8s severity statistics for an audit's failing checks, or for the
+ * whole checklist without an audit id
+ */
+export async function getK8sSeverityStats(auditId?: string): Promise<K8sSeverityStats> {
+  return invoke<K8sSeverityStats>("get_k8s_severity_stats", { auditId });
+}
+
+/**
+ * Failing checks by severity across a client's audits, oldest first
+ */
+export async function getK8sSeverityTrend(
+  clientId: string,
+  clusterName?: string
+): Promise<K8sSeverityStats[]> {
+  return invoke<K8sSeverityStats[]>("get_k8s_severity_trend", { clientId, clusterName });
+}
+
+/**
+ * Get FinOps resource templates
  */
 export async function getFinOpsTemplates(): Promise<FinOpsTemplate[]> {
   return invoke<FinOpsTemplate[]>("get_finops_templates");
@@ -398,6 +1105,88 @@ export async function compareCloudProviders(
   return invoke<ProviderComparison[]>("compare_cloud_providers", { request });
 }
 
+/**
+ * Compare each resource's monthly cost across AWS, Azure and GCP
+ */
+export async function compareResourceCosts(
+  request: CompareResourceCostsRequest
+): Promise<ResourceCostComparison> {
+  return invoke<ResourceCostComparison>("compare_resource_costs", { request });
+}
+
+/**
+ * Project on-prem vs cloud costs over several years
+ */
+export async function generateTcoProjection(
+  request: GenerateTcoProjectionRequest
+): Promise<TcoProjection> {
+  return invoke<TcoProjection>("generate_tco_projection", { request });
+}
+
+/**
+ * Create a pricing profile with custom cloud rates
+ */
+export async function createPricingProfile(
+  request: SavePricingProfileRequest
+): Promise<PricingProfile> {
+  return invoke<PricingProfile>("create_pricing_profile", { request });
+}
+
+/**
+ * List pricing profiles, optionally for one provider
+ */
+export async function listPricingProfiles(
+  provider?: string
+): Promise<PricingProfile[]> {
+  return invoke<PricingProfile[]>("list_pricing_profiles", { provider });
+}
+
+/**
+ * Get a pricing profile by ID
+ */
+export async function getPricingProfile(
+  id: string
+): Promise<PricingProfile | null> {
+  return invoke<PricingProfile | null>("get_pricing_profile", { id });
+}
+
+/**
+ * Replace a pricing profile's rates
+ */
+export async function updatePricingProfile(
+  id: string,
+  request: SavePricingProfileRequest
+): Promise<PricingProfile> {
+  return invoke<PricingProfile>("update_pricing_profile", { id, request });
+}
+
+/**
+ * Delete a pricing profile
+ */
+export async function deletePricingProfile(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_pricing_profile", { id });
+}
+
+/**
+ * Load pricing profiles from a CSV of vendor rates
+ */
+export async function importPricingCsv(
+  path: string
+): Promise<PricingImportSummary> {
+  return invoke<PricingImportSummary>("import_pricing_csv", { path });
+}
+
+/**
+ * Store per-server utilization from a CSV export (vCenter, RVTools, monitoring)
+ * on the client's assets, so FinOps analyses size VMs from measured load
+ */
+export async function importUtilizationCsv(
+  clientId: string,
+  path: string
+): Promise<UtilizationImportSummary> {
+  return invoke<UtilizationImportSummary>("import_utilization_csv", { clientId, path });
+}
+
 // ============================================================================
 // Network Intelligence Commands
 // ============================================================================
@@ -409,6 +1198,13 @@ export async function checkNmap(): Promise<NmapInfo> {
   return invoke<NmapInfo>("check_nmap");
 }
 
+/**
+ * Set the Nmap binary to use instead of searching PATH (null clears it)
+ */
+export async function setNmapPath(path: string | null): Promise<NmapInfo> {
+  return invoke<NmapInfo>("set_nmap_path", { path });
+}
+
 /**
  * Get available scan types
  */
@@ -430,6 +1226,13 @@ export async function validateScanTarget(target: string): Promise<TargetValidati
   return invoke<TargetValidation>("validate_scan_target", { target });
 }
 
+/**
+ * Check custom Nmap arguments before launching a custom scan
+ */
+export async function validateCustomNmapArgs(args: string): Promise<CustomArgsValidation> {
+  return invoke<CustomArgsValidation>("validate_custom_nmap_args", { args });
+}
+
 /**
  * Create a new scan job
  */
@@ -442,15 +1245,43 @@ export async function createScan(request: CreateScanRequest): Promise<ScanJob> {
  */
 export async function previewScanCommand(
   targets: string[],
-  scanType: string,
+  scanType?: string,
   ports?: string,
-  aggressive?: boolean
+  aggressive?: boolean,
+  maxPacketsPerSecond?: number,
+  profileId?: string
 ): Promise<string> {
   return invoke<string>("preview_scan_command", {
     targets,
     scanType,
     ports,
-    aggressive: aggressive ?? false,
+    aggressive,
+    maxPacketsPerSecond,
+    profileId,
+  });
+}
+
+/**
+ * Plan a scan's targets: hosts touched after exclusions, public address
+ * warnings and an ETA
+ */
+export async function planScanTargets(
+  targets: string[],
+  excludeTargets?: string[],
+  scanType?: string,
+  ports?: string,
+  aggressive?: boolean,
+  maxPacketsPerSecond?: number,
+  profileId?: string
+): Promise<ScanTargetPlan> {
+  return invoke<ScanTargetPlan>("plan_scan_targets", {
+    targets,
+    excludeTargets,
+    scanType,
+    ports,
+    aggressive,
+    maxPacketsPerSecond,
+    profileId,
   });
 }
 
@@ -462,19 +1293,86 @@ export async function listScans(clientId: string): Promise<ScanJob[]> {
 }
 
 /**
- * Get a specific scan by ID
+ * Get a scan with its stored hosts, ports and script output
+ */
+export async function getScan(scanId: string): Promise<ScanDetail | null> {
+  return invoke<ScanDetail | null>("get_scan", { scanId });
+}
+
+/**
+ * Get the original Nmap XML of a scan, for exporting as evidence
  */
-export async function getScan(scanId: string): Promise<ScanJob | null> {
-  return invoke<ScanJob | null>("get_scan", { scanId });
+export async function getScanRawXml(scanId: string): Promise<string | null> {
+  return invoke<string | null>("get_scan_raw_xml", { scanId });
 }
 
 /**
- * Delete a scan
+ * Delete a scan and its stored results
  */
 export async function deleteScan(scanId: string): Promise<boolean> {
   return invoke<boolean>("delete_scan", { scanId });
 }
 
+/**
+ * Run a queued scan, or resume an interrupted one from its first
+ * incomplete batch. Returns immediately; progress arrives via onScanProgress.
+ */
+export async function resumeScan(scanId: string): Promise<ScanJob> {
+  return invoke<ScanJob>("resume_scan", { scanId });
+}
+
+/**
+ * Cancel a running scan; false if it was not running
+ */
+export async function cancelScan(scanId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_scan", { scanId });
+}
+
+/**
+ * Subscribe to scan progress, sent after each batch and when a scan ends
+ */
+export async function onScanProgress(handler: (job: ScanJob) => void): Promise<UnlistenFn> {
+  return listen<ScanJob>("scan-progress", (event) => handler(event.payload));
+}
+
+/**
+ * Compare an earlier scan with a later scan of the same client
+ */
+export async function diffScans(scanIdA: string, scanIdB: string): Promise<ScanDiff> {
+  return invoke<ScanDiff>("diff_scans", { scanIdA, scanIdB });
+}
+
+/**
+ * Compare a scan with a baseline scan, or with the client's asset inventory
+ * when no baseline is given
+ */
+export async function compareScans(scanId: string, baselineScanId?: string): Promise<ScanDiff> {
+  return invoke<ScanDiff>("compare_scans", { scanId, baselineScanId });
+}
+
+/**
+ * Import an Nmap XML (-oX) or greppable (-oG) file produced outside the app
+ * into a client's scans and asset inventory
+ */
+export async function importScanResults(
+  clientId: string,
+  path: string,
+  name?: string
+): Promise<ScanImportSummary> {
+  return invoke<ScanImportSummary>("import_scan_results", { clientId, path, name });
+}
+
+/**
+ * Import Nmap XML or greppable output from a file path or pasted text
+ */
+export async function importNmapXml(
+  clientId: string,
+  source: { path: string } | { content: string },
+  name?: string
+): Promise<ScanImportSummary> {
+  return invoke<ScanImportSummary>("import_nmap_xml", { clientId, ...source, name });
+}
+
 /**
  * Get all assets for a client
  */
@@ -482,6 +1380,18 @@ export async function listAssets(clientId: string): Promise<Asset[]> {
   return invoke<Asset[]>("list_assets", { clientId });
 }
 
+/**
+ * Query a client's assets with a server-side filter, one page at a time
+ */
+export async function queryAssets(
+  clientId: string,
+  filter?: AssetFilter,
+  limit?: number,
+  offset?: number
+): Promise<AssetPage> {
+  return invoke<AssetPage>("query_assets", { clientId, filter, limit, offset });
+}
+
 /**
  * Get demo assets for development
  */
@@ -496,6 +1406,13 @@ export async function getAsset(assetId: string): Promise<Asset | null> {
   return invoke<Asset | null>("get_asset", { assetId });
 }
 
+/**
+ * Get the changes recorded for an asset across scans, oldest first
+ */
+export async function getAssetHistory(assetId: string): Promise<AssetChange[]> {
+  return invoke<AssetChange[]>("get_asset_history", { assetId });
+}
+
 /**
  * Update an asset
  */
@@ -510,6 +1427,72 @@ export async function deleteAsset(assetId: string): Promise<boolean> {
   return invoke<boolean>("delete_asset", { assetId });
 }
 
+/**
+ * Set criticality, owner, location, or tags on many assets at once
+ */
+export async function bulkUpdateAssets(assetIds: string[], patch: AssetPatch): Promise<number> {
+  return invoke<number>("bulk_update_assets", { assetIds, patch });
+}
+
+/**
+ * Export a client's assets to CSV or JSON, one row per asset
+ */
+export async function exportAssets(
+  clientId: string,
+  format: AssetExportFormat,
+  path: string,
+  filter?: AssetFilter,
+  excelBom?: boolean
+): Promise<AssetExportSummary> {
+  return invoke<AssetExportSummary>("export_assets", { clientId, format, path, filter, excelBom });
+}
+
+/**
+ * Import assets from a CSV or JSON export, upserting by IP address
+ */
+export async function importAssets(
+  clientId: string,
+  path: string,
+  format?: AssetExportFormat
+): Promise<AssetImportSummary> {
+  return invoke<AssetImportSummary>("import_assets", { clientId, path, format });
+}
+
+/**
+ * Add tags to many assets
+ */
+export async function addAssetTags(assetIds: string[], tags: string[]): Promise<number> {
+  return invoke<number>("add_asset_tags", { assetIds, tags });
+}
+
+/**
+ * Remove tags from many assets
+ */
+export async function removeAssetTags(assetIds: string[], tags: string[]): Promise<number> {
+  return invoke<number>("remove_asset_tags", { assetIds, tags });
+}
+
+/**
+ * Save a named asset filter, replacing one with the same name
+ */
+export async function saveAssetView(clientId: string, name: string, filter: AssetFilter): Promise<AssetView> {
+  return invoke<AssetView>("save_asset_view", { clientId, name, filter });
+}
+
+/**
+ * List a client's saved asset views
+ */
+export async function listAssetViews(clientId: string): Promise<AssetView[]> {
+  return invoke<AssetView[]>("list_asset_views", { clientId });
+}
+
+/**
+ * Delete a saved asset view
+ */
+export async function deleteAssetView(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_asset_view", { id });
+}
+
 /**
  * Get network statistics for a client
  */
@@ -517,6 +1500,34 @@ export async function getNetworkStats(clientId: string): Promise<NetworkStats> {
   return invoke<NetworkStats>("get_network_stats", { clientId });
 }
 
+/**
+ * List the service policies in effect, defaults first
+ */
+export async function listServicePolicies(): Promise<ServicePolicy[]> {
+  return invoke<ServicePolicy[]>("list_service_policies");
+}
+
+/**
+ * Add a service policy, or replace one (including a default)
+ */
+export async function upsertServicePolicy(request: UpsertServicePolicyRequest): Promise<ServicePolicy> {
+  return invoke<ServicePolicy>("upsert_service_policy", { request });
+}
+
+/**
+ * Delete a user service policy; deleting a default's override restores it
+ */
+export async function deleteServicePolicy(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_service_policy", { id });
+}
+
+/**
+ * Check a client's assets against the service policies
+ */
+export async function evaluateServicePolicies(clientId: string): Promise<AssetPolicyViolations[]> {
+  return invoke<AssetPolicyViolations[]>("evaluate_service_policies", { clientId });
+}
+
 /**
  * Create a new asset group
  */
@@ -546,164 +1557,861 @@ export async function removeAssetFromGroup(groupId: string, assetId: string): Pr
 }
 
 // ============================================================================
-// Native TCP Scanner Commands (Task B)
+// Vulnerability Enrichment Commands
 // ============================================================================
 
 /**
- * Scan a network using the native Rust TCP scanner (Task B)
- * This is a lightweight alternative to Nmap that uses async TCP connects
- * to detect open ports on hosts within a CIDR range.
+ * Import CVEs from an NVD JSON 2.0 file or a list in the bundled format
  */
-export async function scanNetwork(
-  request: ScanNetworkRequest
-): Promise<ScanNetworkResponse> {
-  return invoke<ScanNetworkResponse>("scan_network", { request });
+export async function importVulnDatabase(filePath: string): Promise<VulnImportSummary> {
+  return invoke<VulnImportSummary>("import_vuln_database", { filePath });
 }
 
 /**
- * Quick scan of a single host
+ * Match an asset's service versions against the CVE dataset
  */
-export async function scanSingleHost(
-  ip: string,
-  ports?: number[]
-): Promise<ScannedHost> {
-  return invoke<ScannedHost>("scan_single_host", { ip, ports: ports ?? null });
+export async function enrichAssetVulnerabilities(assetId: string): Promise<AssetVulnerabilityReport> {
+  return invoke<AssetVulnerabilityReport>("enrich_asset_vulnerabilities", { assetId });
 }
 
 /**
- * Get the default ports used for scanning (22, 80, 443, 3389)
+ * An asset's CVE matches from its last enrichment, known exploited first
  */
-export async function getDefaultScanPorts(): Promise<number[]> {
-  return invoke<number[]>("get_default_scan_ports");
+export async function getAssetVulnerabilities(assetId: string): Promise<VulnerabilityMatch[]> {
+  return invoke<VulnerabilityMatch[]>("get_asset_vulnerabilities", { assetId });
 }
 
 /**
- * Get the extended ports list for thorough scanning (24 common ports)
+ * Match every asset of a client against the CVE dataset
  */
-export async function getExtendedScanPorts(): Promise<number[]> {
-  return invoke<number[]>("get_extended_scan_ports");
+export async function enrichClientAssets(clientId: string): Promise<AssetVulnerabilityReport[]> {
+  return invoke<AssetVulnerabilityReport[]>("enrich_client_assets", { clientId });
 }
 
 // ============================================================================
-// Reporting Commands
+// Agent Listener Commands
 // ============================================================================
 
 /**
- * Get all available report templates
+ * Start listening for agent callbacks. With TLS (the default, as for agent
+ * scripts) agents connect to their engagement's self-signed certificate.
  */
-export async function getReportTemplateList(): Promise<ReportTemplate[]> {
-  return invoke<ReportTemplate[]>("get_report_template_list");
+export async function startAgentListener(port: number, useTls = true): Promise<AgentListenerStatus> {
+  return invoke<AgentListenerStatus>("start_agent_listener", { port, useTls });
 }
 
 /**
- * Get template for a specific report type
+ * Stop the agent listener; false if it was not running
  */
-export async function getTemplateByType(reportType: string): Promise<ReportTemplate> {
-  return invoke<ReportTemplate>("get_template_by_type", { reportType });
+export async function stopAgentListener(): Promise<boolean> {
+  return invoke<boolean>("stop_agent_listener");
 }
 
 /**
- * Get all report type options
+ * Get whether the agent listener is running
  */
-export async function getReportTypes(): Promise<ReportTypeInfo[]> {
-  return invoke<ReportTypeInfo[]>("get_report_types");
+export async function getAgentListenerStatus(): Promise<AgentListenerStatus> {
+  return invoke<AgentListenerStatus>("get_agent_listener_status");
 }
 
 /**
- * Get all export format options
+ * List the agents that have called back for a client, connected ones first
  */
-export async function getExportFormatList(): Promise<ExportFormatInfo[]> {
-  return invoke<ExportFormatInfo[]>("get_export_format_list");
+export async function listConnectedAgents(clientId: string): Promise<ConnectedAgent[]> {
+  return invoke<ConnectedAgent[]>("list_connected_agents", { clientId });
 }
 
 /**
- * Generate a new report
+ * Get the telemetry an agent has sent, newest first
  */
-export async function generateReport(request: GenerateReportRequest): Promise<Report> {
-  return invoke<Report>("generate_report", { request });
+export async function getAgentTelemetry(agentId: string): Promise<AgentTelemetry[]> {
+  return invoke<AgentTelemetry[]>("get_agent_telemetry", { agentId });
 }
 
 /**
- * Preview report content without saving
+ * Subscribe to agents connecting and disconnecting
  */
-export async function previewReport(request: GenerateReportRequest): Promise<ReportContent> {
-  return invoke<ReportContent>("preview_report", { request });
+export async function onAgentConnectionChanged(
+  handler: (agent: ConnectedAgent, connected: boolean) => void
+): Promise<UnlistenFn> {
+  const unlistenConnected = await listen<ConnectedAgent>("agent-connected", (event) => handler(event.payload, true));
+  const unlistenDisconnected = await listen<ConnectedAgent>("agent-disconnected", (event) => handler(event.payload, false));
+  return () => {
+    unlistenConnected();
+    unlistenDisconnected();
+  };
 }
 
+// ============================================================================
+// Findings Library Commands
+// ============================================================================
+
 /**
- * Export report to HTML
+ * List the finding templates in effect, built-ins first
  */
-export async function exportReportHtml(reportId: string): Promise<string> {
-  return invoke<string>("export_report_html", { reportId });
+export async function listFindingTemplates(): Promise<FindingTemplate[]> {
+  return invoke<FindingTemplate[]>("list_finding_templates");
 }
 
 /**
- * Export report to Markdown
+ * Add a finding template, or replace one (including a built-in)
  */
-export async function exportReportMarkdown(reportId: string): Promise<string> {
-  return invoke<string>("export_report_markdown", { reportId });
+export async function upsertFindingTemplate(request: UpsertFindingTemplateRequest): Promise<FindingTemplate> {
+  return invoke<FindingTemplate>("upsert_finding_template", { request });
 }
 
 /**
- * Export report to JSON
+ * Delete a user finding template; deleting a built-in's override restores it
  */
-export async function exportReportJson(reportId: string): Promise<string> {
-  return invoke<string>("export_report_json", { reportId });
+export async function deleteFindingTemplate(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_finding_template", { id });
 }
 
 /**
- * List all reports
+ * Score a CVSS v3.1 base vector; rejects malformed vectors
  */
-export async function listReports(clientId?: string): Promise<ReportSummary[]> {
-  return invoke<ReportSummary[]>("list_reports", { clientId: clientId ?? null });
+export async function calculateCvssScore(vector: string): Promise<CvssScore> {
+  return invoke<CvssScore>("calculate_cvss_score", { vector });
 }
 
 /**
- * Get a specific report by ID
+ * Record a finding for a client from a library template
  */
-export async function getReport(reportId: string): Promise<Report | null> {
-  return invoke<Report | null>("get_report", { reportId });
+export async function instantiateFinding(
+  templateId: string,
+  clientId: string,
+  overrides?: FindingOverrides
+): Promise<Finding> {
+  return invoke<Finding>("instantiate_finding", { templateId, clientId, overrides });
 }
 
 /**
- * Delete a report
+ * List a client's findings, most severe and highest CVSS score first
  */
-export async function deleteReport(reportId: string): Promise<boolean> {
-  return invoke<boolean>("delete_report", { reportId });
+export async function listFindings(clientId: string): Promise<Finding[]> {
+  return invoke<Finding[]>("list_findings", { clientId });
 }
 
 /**
- * Get report statistics
+ * Get a finding by ID
  */
-export async function getReportStats(clientId?: string): Promise<ReportStats> {
-  return invoke<ReportStats>("get_report_stats", { clientId: clientId ?? null });
+export async function getFinding(id: string): Promise<Finding | null> {
+  return invoke<Finding | null>("get_finding", { id });
 }
 
 /**
- * Generate demo reports for development
+ * Change a finding's details, linked assets or status
  */
-export async function generateDemoReports(
-  clientId: string,
-  clientName: string
-): Promise<ReportSummary[]> {
+export async function updateFinding(id: string, overrides: FindingOverrides): Promise<Finding> {
+  return invoke<Finding>("update_finding", { id, overrides });
+}
+
+/**
+ * Delete a finding
+ */
+export async function deleteFinding(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_finding", { id });
+}
+
+// ============================================================================
+// Native TCP Scanner Commands (Task B)
+// ============================================================================
+
+/**
+ * Scan a network using the native Rust TCP scanner (Task B)
+ * This is a lightweight alternative to Nmap that uses async TCP connects
+ * to detect open ports on hosts within a CIDR range.
+ */
+export async function scanNetwork(
+  request: ScanNetworkRequest
+): Promise<ScanNetworkResponse> {
+  return invoke<ScanNetworkResponse>("scan_network", { request });
+}
+
+/**
+ * Quick scan of a single host
+ */
+export async function scanSingleHost(
+  ip: string,
+  ports?: number[]
+): Promise<ScannedHost> {
+  return invoke<ScannedHost>("scan_single_host", { ip, ports: ports ?? null });
+}
+
+/**
+ * Get the default ports used for scanning (22, 80, 443, 3389)
+ */
+export async function getDefaultScanPorts(): Promise<number[]> {
+  return invoke<number[]>("get_default_scan_ports");
+}
+
+/**
+ * Get the extended ports list for thorough scanning (24 common ports)
+ */
+export async function getExtendedScanPorts(): Promise<number[]> {
+  return invoke<number[]>("get_extended_scan_ports");
+}
+
+// ============================================================================
+// Findings & Verification Commands
+// ============================================================================
+
+/**
+ * Get the built-in finding detection rules
+ */
+export async function getFindingRuleList(): Promise<FindingRule[]> {
+  return invoke<FindingRule[]>("get_finding_rule_list");
+}
+
+/**
+ * Run detection rules against scan results and record new findings
+ */
+export async function detectNetworkFindings(
+  clientId: string,
+  hosts: ScannedHost[]
+): Promise<NetworkFinding[]> {
+  return invoke<NetworkFinding[]>("detect_network_findings", { clientId, hosts });
+}
+
+/**
+ * List findings for a client
+ */
+export async function listNetworkFindings(clientId: string): Promise<NetworkFinding[]> {
+  return invoke<NetworkFinding[]>("list_network_findings", { clientId });
+}
+
+/**
+ * Re-scan the hosts/ports behind the selected findings and update their status
+ */
+export async function createVerificationScan(
+  request: CreateVerificationScanRequest
+): Promise<ScanJob> {
+  return invoke<ScanJob>("create_verification_scan", { request });
+}
+
+// ============================================================================
+// Scan Profile Commands
+// ============================================================================
+
+/**
+ * Save scan defaults (exclusions, ports, timing) as a named profile
+ */
+export async function createScanProfile(request: ScanProfileRequest): Promise<ScanProfile> {
+  return invoke<ScanProfile>("create_scan_profile", { request });
+}
+
+/**
+ * List the global scan profiles and, given a client, that client's own
+ */
+export async function listScanProfiles(clientId?: string): Promise<ScanProfile[]> {
+  return invoke<ScanProfile[]>("list_scan_profiles", { clientId });
+}
+
+/**
+ * Change a scan profile's name, settings or notes
+ */
+export async function updateScanProfile(id: string, request: ScanProfileRequest): Promise<ScanProfile> {
+  return invoke<ScanProfile>("update_scan_profile", { id, request });
+}
+
+/**
+ * Delete a scan profile
+ */
+export async function deleteScanProfile(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_scan_profile", { id });
+}
+
+// ============================================================================
+// Scan Schedule Commands
+// ============================================================================
+
+/**
+ * Create a recurring scan, run in the background while the app is open
+ */
+export async function createScanSchedule(
+  request: CreateScanScheduleRequest
+): Promise<ScanSchedule> {
+  return invoke<ScanSchedule>("create_scan_schedule", { request });
+}
+
+/**
+ * List scan schedules, optionally for one client
+ */
+export async function listScanSchedules(clientId?: string): Promise<ScanSchedule[]> {
+  return invoke<ScanSchedule[]>("list_scan_schedules", { clientId });
+}
+
+/**
+ * Change a scan schedule's name, scan, timing or missed-run handling
+ */
+export async function updateScanSchedule(
+  id: string,
+  request: CreateScanScheduleRequest
+): Promise<ScanSchedule> {
+  return invoke<ScanSchedule>("update_scan_schedule", { id, request });
+}
+
+/**
+ * Enable or disable a scan schedule
+ */
+export async function toggleScanSchedule(id: string, enabled: boolean): Promise<ScanSchedule> {
+  return invoke<ScanSchedule>("toggle_scan_schedule", { id, enabled });
+}
+
+/**
+ * Delete a scan schedule
+ */
+export async function deleteScanSchedule(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_scan_schedule", { id });
+}
+
+/**
+ * Subscribe to scheduled scan completions (e.g. to show a toast)
+ */
+export async function onScheduledScanCompleted(
+  handler: (event: ScheduledScanCompleted) => void
+): Promise<UnlistenFn> {
+  return listen<ScheduledScanCompleted>("scheduled-scan-completed", (event) => handler(event.payload));
+}
+
+// ============================================================================
+// Reporting Commands
+// ============================================================================
+
+/**
+ * Get all available report templates
+ */
+export async function getReportTemplateList(): Promise<ReportTemplate[]> {
+  return invoke<ReportTemplate[]>("get_report_template_list");
+}
+
+/**
+ * Get template for a specific report type
+ */
+export async function getTemplateByType(reportType: string): Promise<ReportTemplate> {
+  return invoke<ReportTemplate>("get_template_by_type", { reportType });
+}
+
+/**
+ * Get all report type options
+ */
+export async function getReportTypes(): Promise<ReportTypeInfo[]> {
+  return invoke<ReportTypeInfo[]>("get_report_types");
+}
+
+/**
+ * Get all export format options
+ */
+export async function getExportFormatList(): Promise<ExportFormatInfo[]> {
+  return invoke<ExportFormatInfo[]>("get_export_format_list");
+}
+
+/**
+ * Generate a new report
+ */
+export async function generateReport(request: GenerateReportRequest): Promise<Report> {
+  return invoke<Report>("generate_report", { request });
+}
+
+/**
+ * Preview report content without saving
+ */
+export async function previewReport(request: GenerateReportRequest): Promise<ReportContent> {
+  return invoke<ReportContent>("preview_report", { request });
+}
+
+/**
+ * Render a client's record of processing activities as a stored report,
+ * exportable like any other
+ */
+export async function generateRopaReport(
+  clientId: string,
+  author?: string,
+  organization?: string
+): Promise<Report> {
+  return invoke<Report>("generate_ropa_report", { clientId, author, organization });
+}
+
+/**
+ * Start previewing a report in the background, returning the preview id
+ *
+ * Sections arrive through onReportPreviewSection as they're rendered, then
+ * onReportPreviewDone fires once. Full engagement reports must be previewed
+ * this way.
+ */
+export async function startReportPreview(request: GenerateReportRequest): Promise<string> {
+  return invoke<string>("start_report_preview", { request });
+}
+
+/**
+ * Stop a streaming preview; false if it already finished
+ */
+export async function cancelReportPreview(previewId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_report_preview", { previewId });
+}
+
+/**
+ * Subscribe to sections of streaming previews as they're rendered
+ */
+export async function onReportPreviewSection(
+  handler: (event: ReportPreviewSection) => void
+): Promise<UnlistenFn> {
+  return listen<ReportPreviewSection>("report-preview:section", (event) => handler(event.payload));
+}
+
+/**
+ * Subscribe to streaming previews finishing, failing or being cancelled
+ */
+export async function onReportPreviewDone(
+  handler: (event: ReportPreviewDone) => void
+): Promise<UnlistenFn> {
+  return listen<ReportPreviewDone>("report-preview:done", (event) => handler(event.payload));
+}
+
+/**
+ * Export report to HTML
+ */
+export async function exportReportHtml(reportId: string): Promise<string> {
+  return invoke<string>("export_report_html", { reportId });
+}
+
+/**
+ * Export report to Markdown
+ */
+export async function exportReportMarkdown(reportId: string): Promise<string> {
+  return invoke<string>("export_report_markdown", { reportId });
+}
+
+/**
+ * Export report tables and findings to CSV
+ */
+export async function exportReportCsv(reportId: string): Promise<string> {
+  return invoke<string>("export_report_csv", { reportId });
+}
+
+/**
+ * Export report to an editable Word document at outputPath
+ */
+export async function exportReportDocx(reportId: string, outputPath: string): Promise<ExportFileResult> {
+  return invoke<ExportFileResult>("export_report_docx", { reportId, outputPath });
+}
+
+/**
+ * Export report to a PDF at outputPath, with a contents page and numbered pages
+ */
+export async function exportReportPdf(reportId: string, outputPath: string): Promise<ExportFileResult> {
+  return invoke<ExportFileResult>("export_report_pdf", { reportId, outputPath });
+}
+
+/**
+ * Export report to JSON
+ */
+export async function exportReportJson(reportId: string): Promise<string> {
+  return invoke<string>("export_report_json", { reportId });
+}
+
+/**
+ * Add a user-written section to a report
+ */
+export async function addCustomSection(
+  reportId: string,
+  section: CustomSectionRequest
+): Promise<CustomSection> {
+  return invoke<CustomSection>("add_custom_section", { reportId, section });
+}
+
+/**
+ * Update a custom section's title, position or content
+ */
+export async function updateCustomSection(
+  id: string,
+  section: CustomSectionRequest
+): Promise<CustomSection> {
+  return invoke<CustomSection>("update_custom_section", { id, section });
+}
+
+/**
+ * Remove a custom section from its report
+ */
+export async function deleteCustomSection(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_custom_section", { id });
+}
+
+/**
+ * List a report's custom sections
+ */
+export async function listCustomSections(reportId: string): Promise<CustomSection[]> {
+  return invoke<CustomSection[]>("list_custom_sections", { reportId });
+}
+
+/**
+ * List all reports
+ */
+export async function listReports(clientId?: string): Promise<ReportSummary[]> {
+  return invoke<ReportSummary[]>("list_reports", { clientId: clientId ?? null });
+}
+
+/**
+ * Get a specific report by ID
+ */
+export async function getReport(reportId: string): Promise<Report | null> {
+  return invoke<Report | null>("get_report", { reportId });
+}
+
+/**
+ * Delete a report
+ */
+export async function deleteReport(reportId: string): Promise<boolean> {
+  return invoke<boolean>("delete_report", { reportId });
+}
+
+/**
+ * Get report statistics
+ */
+export async function getReportStats(clientId?: string): Promise<ReportStats> {
+  return invoke<ReportStats>("get_report_stats", { clientId: clientId ?? null });
+}
+
+/**
+ * Generate demo reports for development
+ */
+export async function generateDemoReports(
+  clientId: string,
+  clientName: string
+): Promise<ReportSummary[]> {
   return invoke<ReportSummary[]>("generate_demo_reports", { clientId, clientName });
 }
 
+/**
+ * Save an organization's report branding (logo, colors, footer)
+ * Reports for the organization use it unless they set explicit values.
+ */
+export async function saveBrandingProfile(
+  request: SaveBrandingProfileRequest
+): Promise<BrandingProfile> {
+  return invoke<BrandingProfile>("save_branding_profile", { request });
+}
+
+/**
+ * Get an organization's report branding, if one has been saved
+ */
+export async function getBrandingProfile(organization: string): Promise<BrandingProfile | null> {
+  return invoke<BrandingProfile | null>("get_branding_profile", { organization });
+}
+
 // ============================================================================
 // GRC Compliance Status Commands (Phase 3)
 // ============================================================================
 
 /**
  * Get compliance status for a specific framework
- * Returns overall completion and compliance percentages for all categories
+ * Returns overall completion and compliance percentages for all categories.
+ * When grouped, groupBreakdown rolls categories up into the client's groups.
  */
 export async function getComplianceStatus(
   framework: string,
-  clientId?: string
+  clientId?: string,
+  grouped?: boolean
 ): Promise<ComplianceStatusReport> {
   return invoke<ComplianceStatusReport>("get_compliance_status", {
     framework,
     clientId: clientId ?? null,
+    grouped: grouped ?? null,
+  });
+}
+
+/**
+ * Snapshot a client's current compliance status for a framework
+ * Snapshots within the same hour replace each other.
+ */
+export async function recordComplianceSnapshot(
+  clientId: string,
+  framework: string
+): Promise<ComplianceSnapshot> {
+  return invoke<ComplianceSnapshot>("record_compliance_snapshot", { clientId, framework });
+}
+
+/**
+ * Get a client's compliance over time for a framework, optionally from an RFC 3339 date
+ */
+export async function getComplianceTrend(
+  clientId: string,
+  framework: string,
+  since?: string
+): Promise<ComplianceTrend> {
+  return invoke<ComplianceTrend>("get_compliance_trend", {
+    clientId,
+    framework,
+    since: since ?? null,
+  });
+}
+
+/**
+ * Get a client's compliance across all assessed frameworks, optionally with
+ * per-framework weights (e.g. { SOC2: 2 }) instead of weighting by assessed controls
+ */
+export async function getClientComplianceOverview(
+  clientId: string,
+  weights?: Record<string, number>
+): Promise<ComplianceOverview> {
+  return invoke<ComplianceOverview>("get_client_compliance_overview", {
+    clientId,
+    weights: weights ?? null,
+  });
+}
+
+/**
+ * Get the mappings from one framework's controls onto another's (by control code)
+ */
+export async function getControlMappings(
+  fromFramework: string,
+  toFramework: string
+): Promise<ControlMapping[]> {
+  return invoke<ControlMapping[]>("get_control_mappings_cmd", { fromFramework, toFramework });
+}
+
+/**
+ * Project an assessment onto another framework.
+ * Controls whose mapped sources are all compliant are inherited as compliant.
+ */
+export async function projectAssessmentToFramework(
+  assessmentId: string,
+  targetFramework: string
+): Promise<FrameworkProjection> {
+  return invoke<FrameworkProjection>("project_assessment_to_framework", {
+    assessmentId,
+    targetFramework,
+  });
+}
+
+/**
+ * Create or replace a client's category grouping for a framework
+ */
+export async function setCategoryGrouping(
+  request: SetCategoryGroupingRequest
+): Promise<CategoryGrouping> {
+  return invoke<CategoryGrouping>("set_category_grouping", { request });
+}
+
+/**
+ * Get a client's category grouping for a framework (null if not configured)
+ */
+export async function getCategoryGrouping(
+  clientId: string,
+  framework: string
+): Promise<CategoryGrouping | null> {
+  return invoke<CategoryGrouping | null>("get_category_grouping", { clientId, framework });
+}
+
+/**
+ * Remove a client's category grouping, reverting to native categories
+ */
+export async function deleteCategoryGrouping(
+  clientId: string,
+  framework: string
+): Promise<boolean> {
+  return invoke<boolean>("delete_category_grouping", { clientId, framework });
+}
+
+// ============================================================================
+// Records of Processing Commands
+// ============================================================================
+
+/**
+ * Add an activity to a client's record of processing (GDPR Article 30)
+ */
+export async function createProcessingActivity(
+  request: SaveProcessingActivityRequest
+): Promise<ProcessingActivityResult> {
+  return invoke<ProcessingActivityResult>("create_processing_activity", { request });
+}
+
+/**
+ * List a client's processing activities by name
+ */
+export async function listProcessingActivities(clientId: string): Promise<ProcessingActivity[]> {
+  return invoke<ProcessingActivity[]>("list_processing_activities", { clientId });
+}
+
+/**
+ * Get a processing activity by ID
+ */
+export async function getProcessingActivity(id: string): Promise<ProcessingActivity | null> {
+  return invoke<ProcessingActivity | null>("get_processing_activity", { id });
+}
+
+/**
+ * Replace a processing activity's details
+ */
+export async function updateProcessingActivity(
+  id: string,
+  request: SaveProcessingActivityRequest
+): Promise<ProcessingActivityResult> {
+  return invoke<ProcessingActivityResult>("update_processing_activity", { id, request });
+}
+
+/**
+ * Delete a processing activity
+ */
+export async function deleteProcessingActivity(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_processing_activity", { id });
+}
+
+// ============================================================================
+// Risk Register Commands
+// ============================================================================
+
+/**
+ * Add a risk to a client's register
+ */
+export async function createRisk(request: SaveRiskRequest): Promise<RiskItem> {
+  return invoke<RiskItem>("create_risk", { request });
+}
+
+/**
+ * List a client's risks, highest inherent score first
+ */
+export async function listRisks(clientId: string): Promise<RiskItem[]> {
+  return invoke<RiskItem[]>("list_risks", { clientId });
+}
+
+/**
+ * Get a risk by ID
+ */
+export async function getRisk(id: string): Promise<RiskItem | null> {
+  return invoke<RiskItem | null>("get_risk", { id });
+}
+
+/**
+ * Replace a risk's details
+ */
+export async function updateRisk(
+  id: string,
+  request: SaveRiskRequest
+): Promise<RiskItem> {
+  return invoke<RiskItem>("update_risk", { id, request });
+}
+
+/**
+ * Delete a risk
+ */
+export async function deleteRisk(id: string): Promise<boolean> {
+  return invoke<boolean>("delete_risk", { id });
+}
+
+/**
+ * Get the 5x5 likelihood/impact heatmap of a client's active risks
+ */
+export async function getRiskMatrix(clientId: string): Promise<RiskMatrix> {
+  return invoke<RiskMatrix>("get_risk_matrix", { clientId });
+}
+
+/**
+ * Build and store the remediation plan for an assessment's gaps.
+ * Regenerating keeps owners, due dates and progress for controls still open.
+ */
+export async function generateRemediationPlan(
+  assessmentId: string,
+  effortMapping?: EffortMapping
+): Promise<RemediationPlan> {
+  return invoke<RemediationPlan>("generate_remediation_plan", { assessmentId, effortMapping });
+}
+
+/**
+ * Get the stored remediation plan for an assessment
+ */
+export async function getRemediationPlan(assessmentId: string): Promise<RemediationPlan | null> {
+  return invoke<RemediationPlan | null>("get_remediation_plan", { assessmentId });
+}
+
+/**
+ * Assign an owner, due date and status to a remediation item
+ */
+export async function updateRemediationItem(
+  itemId: string,
+  request: UpdateRemediationItemRequest
+): Promise<RemediationItem> {
+  return invoke<RemediationItem>("update_remediation_item", { itemId, request });
+}
+
+/**
+ * Remove a remediation item from its plan
+ */
+export async function deleteRemediationItem(itemId: string): Promise<boolean> {
+  return invoke<boolean>("delete_remediation_item", { itemId });
+}
+
+/**
+ * A client's open remediation items past their due date, across assessments
+ */
+export async function listOverdueRemediations(clientId: string): Promise<ClientRemediationItem[]> {
+  return invoke<ClientRemediationItem[]>("list_overdue_remediations", { clientId });
+}
+
+/**
+ * Remediation counts by status and priority for a client
+ */
+export async function getRemediationStats(clientId: string): Promise<RemediationStats> {
+  return invoke<RemediationStats>("get_remediation_stats", { clientId });
+}
+
+/**
+ * Export an assessment's remediation plan as CSV or Markdown
+ */
+export async function exportRemediationPlan(
+  assessmentId: string,
+  format: "csv" | "markdown"
+): Promise<string> {
+  return invoke<string>("export_remediation_plan", { assessmentId, format });
+}
+
+// ============================================================================
+// Assessment Transfer Commands
+// ============================================================================
+
+/**
+ * Export an assessment to a bundle for another Optio instance; a path
+ * ending in .zip gets a zip bundle, anything else a single JSON file
+ */
+export async function exportAssessment(
+  assessmentId: string,
+  path: string,
+  includeEvidenceFiles?: boolean
+): Promise<AssessmentExportResult> {
+  return invoke<AssessmentExportResult>("export_assessment", {
+    assessmentId,
+    path,
+    includeEvidenceFiles,
+  });
+}
+
+/**
+ * Import an assessment bundle, JSON or zip, exported by another Optio instance
+ */
+export async function importAssessment(
+  path: string,
+  conflictStrategy: ConflictStrategy
+): Promise<AssessmentImportSummary> {
+  return invoke<AssessmentImportSummary>("import_assessment", { path, conflictStrategy });
+}
+
+// ============================================================================
+// Policy Generation Commands
+// ============================================================================
+
+/**
+ * Draft policy documents that close an assessment's control gaps.
+ * Omit policyTypes/controlIds to cover every gap.
+ */
+export async function generatePolicyDocuments(
+  assessmentId: string,
+  policyTypes?: PolicyType[],
+  controlIds?: string[]
+): Promise<PolicyDocument[]> {
+  return invoke<PolicyDocument[]>("generate_policy_documents", {
+    assessmentId,
+    policyTypes,
+    controlIds,
   });
 }
 
@@ -735,3 +2443,39 @@ export async function generateDemoPdf(
 export async function openPdfLocation(filePath: string): Promise<boolean> {
   return invoke<boolean>("open_pdf_location", { filePath });
 }
+
+// ============================================================================
+// Workspace Archive Commands
+// ============================================================================
+
+/**
+ * Export everything held for a client into one zip archive.
+ * Progress arrives via onWorkspaceExportProgress.
+ */
+export async function exportClientWorkspace(
+  clientId: string,
+  destPath: string,
+  options?: WorkspaceExportOptions
+): Promise<WorkspaceExportSummary> {
+  return invoke<WorkspaceExportSummary>("export_client_workspace", {
+    clientId,
+    destPath,
+    options,
+  });
+}
+
+/**
+ * Subscribe to progress of a running workspace export
+ */
+export async function onWorkspaceExportProgress(
+  handler: (progress: WorkspaceExportProgress) => void
+): Promise<UnlistenFn> {
+  return listen<WorkspaceExportProgress>("workspace-export-progress", (event) => handler(event.payload));
+}
+
+/**
+ * Check a workspace archive's files against the hashes in its manifest
+ */
+export async function validateWorkspaceArchive(path: string): Promise<WorkspaceArchiveValidation> {
+  return invoke<WorkspaceArchiveValidation>("validate_workspace_archive", { path });
+}
@@ -17,16 +17,60 @@ export interface Client {
   notes: string | null;
   createdAt: string;
   updatedAt: string;
+  contacts: ClientContact[];
+  industry: IndustrySector | null;
+  companySize: CompanySize | null;
+  engagementStart: string | null;
+  engagementEnd: string | null;
+  defaultFramework: string | null;
+  /** Framework new assessments default to, from the preference or industry */
+  preferredFramework: string | null;
 }
 
-export interface CreateClientRequest {
+export interface ClientContact {
+  name: string;
+  role: string | null;
+  email: string | null;
+  phone: string | null;
+  /** Named on report cover pages */
+  primary: boolean;
+}
+
+export type IndustrySector =
+  | "FINANCE"
+  | "HEALTHCARE"
+  | "GOVERNMENT"
+  | "EDUCATION"
+  | "RETAIL"
+  | "MANUFACTURING"
+  | "TECHNOLOGY"
+  | "ENERGY"
+  | "LEGAL"
+  | "NON_PROFIT"
+  | "OTHER";
+
+export type CompanySize = "SMALL" | "MEDIUM" | "LARGE" | "ENTERPRISE";
+
+/** Engagement context shared by the create and update requests */
+export interface ClientProfileRequest {
+  contacts?: ClientContact[];
+  industry?: IndustrySector;
+  companySize?: CompanySize;
+  /** RFC 3339 */
+  engagementStart?: string;
+  /** RFC 3339 */
+  engagementEnd?: string;
+  defaultFramework?: string;
+}
+
+export interface CreateClientRequest extends ClientProfileRequest {
   name: string;
   targetSubnet?: string;
   contactEmail?: string;
   notes?: string;
 }
 
-export interface UpdateClientRequest {
+export interface UpdateClientRequest extends ClientProfileRequest {
   id: string;
   name: string;
   targetSubnet?: string;
@@ -34,6 +78,195 @@ export interface UpdateClientRequest {
   notes?: string;
 }
 
+/** Records that reference a client, shown before deleting it */
+export interface ClientDependencies {
+  assessments: number;
+  assets: number;
+  scans: number;
+  risks: number;
+  reports: number;
+  generatedScripts: number;
+}
+
+/** Everything deleting a client removes, by entity type */
+export interface ClientDeletionPreview {
+  assessments: number;
+  controlAssessments: number;
+  evidence: number;
+  /** Evidence with a file on disk */
+  evidenceFiles: number;
+  remediationItems: number;
+  risks: number;
+  processingActivities: number;
+  findings: number;
+  assets: number;
+  assetGroups: number;
+  scans: number;
+  scanSchedules: number;
+  networkFindings: number;
+  reports: number;
+  /** Reports with an exported file on disk */
+  reportFiles: number;
+  generatedScripts: number;
+  agentTokens: number;
+  k8sAudits: number;
+  cloudReadinessAssessments: number;
+  activityEvents: number;
+}
+
+/** Outcome of deleting a client */
+export interface ClientDeletionSummary {
+  deleted: boolean;
+  /** Workspace archive written before deleting, if one was asked for */
+  archive: WorkspaceExportSummary | null;
+  /** Evidence and report files removed from the app data dir */
+  filesRemoved: number;
+  /** Files that could not be removed */
+  warnings: string[];
+}
+
+/** A client with its dashboard counts */
+export interface ClientOverview {
+  client: Client;
+  assessments: number;
+  /** Assessments not yet completed or archived */
+  activeAssessments: number;
+  assets: number;
+  reports: number;
+  /** Register risks that aren't closed */
+  openRisks: number;
+}
+
+export type DashboardSection =
+  | "COMPLIANCE"
+  | "ASSETS"
+  | "SCANNING"
+  | "REMEDIATION"
+  | "REPORTS"
+  | "CLOUD_READINESS";
+
+export interface DashboardSectionError {
+  section: DashboardSection;
+  error: string;
+}
+
+/** The latest assessment of one framework */
+export interface FrameworkCompliance {
+  framework: string;
+  assessmentId: string;
+  assessmentName: string;
+  status: AssessmentStatus;
+  startedAt: string;
+  overallCompliance: number;
+  highRiskGaps: number;
+}
+
+export interface DashboardAssets {
+  totalAssets: number;
+  activeAssets: number;
+  byCategory: CategoryCount[];
+  byCriticality: CriticalityCount[];
+}
+
+export interface DashboardScanning {
+  totalScans: number;
+  lastScanId: string | null;
+  lastScanName: string | null;
+  /** When the latest completed scan finished */
+  lastScanAt: string | null;
+  /** Assets first discovered by the latest completed scan */
+  newHosts: number;
+}
+
+export interface DashboardCloudReadiness {
+  assessmentId: string;
+  overallScore: number;
+  tier: ReadinessTier;
+  answeredItems: number;
+  totalItems: number;
+}
+
+/** Everything a client's dashboard shows; sections that failed to load are null and listed in errors */
+export interface ClientDashboard {
+  clientId: string;
+  clientName: string;
+  generatedAt: string;
+  compliance: FrameworkCompliance[] | null;
+  assets: DashboardAssets | null;
+  scanning: DashboardScanning | null;
+  remediation: RemediationStats | null;
+  recentReports: ReportSummary[] | null;
+  cloudReadiness: DashboardCloudReadiness | null;
+  errors: DashboardSectionError[];
+}
+
+// ============================================================================
+// Portfolio Types
+// ============================================================================
+
+/** Issues counted by severity, with the overall rating ("Critical", "High", "Moderate", "Low" or "Unknown") */
+export interface RiskSummary {
+  criticalCount: number;
+  highCount: number;
+  mediumCount: number;
+  lowCount: number;
+  overallRiskRating: string;
+}
+
+/** Latest compliance of one framework for a client */
+export interface PortfolioFramework {
+  framework: string;
+  assessmentId: string;
+  startedAt: string;
+  overallCompliance: number;
+}
+
+/** A client's row in the portfolio */
+export interface PortfolioClient {
+  clientId: string;
+  clientName: string;
+  /** Latest non-archived assessment of each framework */
+  frameworks: PortfolioFramework[];
+  /** Null without assessments */
+  averageCompliance: number | null;
+  /** Open findings and compliance gaps of the latest assessments */
+  risk: RiskSummary;
+  /** Null when the client has no assessment, finding or completed scan yet */
+  overallRisk: RiskLevel | null;
+  openCriticalFindings: number;
+  openHighFindings: number;
+  lastScanAt: string | null;
+  daysSinceLastScan: number | null;
+  overdueRemediation: number;
+  /**
+   * 0-100, higher is healthier: compliance x 0.4 (up to 40), 30 less 10 per
+   * open critical and 5 per open high finding, 15 for a scan within 30 days
+   * falling to 0 at 120, and 15 less 3 per overdue remediation item
+   */
+  healthScore: number;
+}
+
+/** Order of the portfolio; all but NAME put clients needing attention first */
+export type PortfolioSort =
+  | "NAME"
+  | "HEALTH_SCORE"
+  | "RISK"
+  | "COMPLIANCE"
+  | "LAST_SCAN"
+  | "OVERDUE_REMEDIATION";
+
+export interface PortfolioFilter {
+  /** Only clients with an assessment of this framework */
+  framework?: string;
+  /** Only clients rated at this overall risk or above */
+  minRisk?: RiskLevel;
+}
+
+export interface PortfolioOverview {
+  generatedAt: string;
+  clients: PortfolioClient[];
+}
+
 // ============================================================================
 // Factory Types (Script Generation)
 // ============================================================================
@@ -46,14 +279,20 @@ export interface ScriptConfigOptions {
   agentInstaller?: string;
   enableFirewallLogging: boolean;
   customCommands?: string[];
+  /** Defaults to the client's preferred or the detected consultant IP */
+  consultantIp?: string;
+  /** Record current state to JSON instead of changing anything */
+  auditOnly?: boolean;
 }
 
 export interface GenerateScriptRequest {
   clientId: string;
-  clientName: string;
+  /** Defaults to the stored client's name */
+  clientName?: string;
   targetSubnet: string;
   templateName: string;
   config: ScriptConfigOptions;
+  strict?: boolean;
 }
 
 export interface GenerateScriptResponse {
@@ -63,6 +302,39 @@ export interface GenerateScriptResponse {
   scriptId: string;
   generatedAt: string;
   warnings: string[];
+  /** Signed hash of the script, also appended to it as a comment footer */
+  manifest: ScriptManifest;
+}
+
+/** Signed integrity manifest for a generated script */
+export interface ScriptManifest {
+  scriptId: string;
+  generatedAt: string;
+  /** Hex SHA-256 of the script without its manifest footer */
+  sha256: string;
+  keyId: string;
+  /** Base64 ed25519 public key */
+  publicKey: string;
+  /** Base64 ed25519 signature */
+  signature: string;
+}
+
+export interface SigningKeyInfo {
+  keyId: string;
+  publicKey: string;
+  createdAt: string;
+  /** Set once the key has been rotated out */
+  retiredAt: string | null;
+}
+
+export interface ScriptVerification {
+  valid: boolean;
+  hashMatches: boolean;
+  signatureValid: boolean;
+  /** Signed by one of our keys, current or retired */
+  knownKey: boolean;
+  manifest: ScriptManifest | null;
+  problems: string[];
 }
 
 export interface TemplateInfo {
@@ -81,6 +353,7 @@ export interface PreviewRequest {
 }
 
 export interface ValidateConfigRequest {
+  clientId?: string;
   clientName: string;
   targetSubnet: string;
   config: ScriptConfigOptions;
@@ -92,6 +365,111 @@ export interface ValidationResult {
   warnings: string[];
 }
 
+export interface ScriptSyntaxError {
+  line: number;
+  column: number;
+  message: string;
+}
+
+export interface ScriptSyntaxReport {
+  valid: boolean;
+  checker: "pwsh" | "powershell" | "internal";
+  errors: ScriptSyntaxError[];
+}
+
+/** Placeholder and structure check of a script template */
+export interface TemplateLint {
+  /** No unknown placeholders and no structural errors */
+  valid: boolean;
+  /** Every placeholder used, in order of appearance */
+  variables: string[];
+  /** Placeholders the generator has no value for */
+  unknownVariables: string[];
+  errors: ScriptSyntaxError[];
+  /** e.g. a missing #Requires line */
+  warnings: string[];
+}
+
+/** Resolved configuration a script was generated from (snake_case, as stored) */
+export interface ScriptConfig {
+  client_id: string;
+  client_name: string;
+  target_subnet: string;
+  consultant_ip: string;
+  enable_winrm: boolean;
+  configure_dns: boolean;
+  dns_servers: string[];
+  install_agent: boolean;
+  agent_installer: string | null;
+  enable_firewall_logging: boolean;
+  custom_commands: string[];
+}
+
+export interface ScriptRecord {
+  scriptId: string;
+  clientId: string;
+  templateName: string;
+  config: ScriptConfig;
+  /** SHA-256 of the content, hex-encoded */
+  contentHash: string;
+  content: string | null;
+  warnings: string[];
+  outputPath: string | null;
+  generatedAt: string;
+}
+
+export interface ScriptRecordSummary {
+  scriptId: string;
+  templateName: string;
+  targetSubnet: string;
+  consultantIp: string;
+  contentHash: string;
+  warningCount: number;
+  outputPath: string | null;
+  auditOnly: boolean;
+  generatedAt: string;
+}
+
+export interface ScriptLineChange {
+  kind: "added" | "removed";
+  line: number;
+  text: string;
+}
+
+/** A stored generation replayed through the current template */
+export interface ScriptRegeneration {
+  scriptId: string;
+  templateName: string;
+  storedHash: string;
+  contentHash: string;
+  unchanged: boolean;
+  content: string;
+  warnings: string[];
+  changes: ScriptLineChange[];
+}
+
+export interface AuditSettingComparison {
+  setting: string;
+  current: string;
+  intended: string;
+  /** Running the prep script would change this setting */
+  changeRequired: boolean;
+}
+
+/** Current vs intended state captured by an audit-only script */
+export interface PrepAuditReport {
+  schemaVersion: number;
+  scriptId: string;
+  clientId: string;
+  hostname: string;
+  collectedAt: string | null;
+  /** Intended state came from the recorded script rather than the file */
+  fromRecordedScript: boolean;
+  settings: AuditSettingComparison[];
+  changesRequired: number;
+  warnings: string[];
+}
+
 // ============================================================================
 // System Types
 // ============================================================================
@@ -105,6 +483,192 @@ export interface SystemInfo {
   localIp: string | null;
 }
 
+export interface ConsultantInterface {
+  name: string;
+  ipv4: string[];
+  ipv6: string[];
+  /** Attached networks in CIDR notation */
+  subnets: string[];
+  /** VPN tunnel or virtual adapter, judged by name */
+  isVirtual: boolean;
+  isLoopback: boolean;
+  hasDefaultRoute: boolean;
+}
+
+export interface ConsultantIpInfo {
+  interfaces: ConsultantInterface[];
+  suggestedIp: string | null;
+  /** Persisted choice for the client, if any */
+  preferredIp: string | null;
+  /** Interface whose address is suggested by default, if chosen */
+  preferredInterface: string | null;
+}
+
+/** Consultant IP that can reach an engagement subnet */
+export interface SubnetConsultantIp {
+  targetSubnet: string;
+  ip: string | null;
+  interface: string | null;
+  directlyAttached: boolean;
+}
+
+// ============================================================================
+// Database Types
+// ============================================================================
+
+/** LOCKED: the database is encrypted and waiting for its passphrase */
+export type DatabaseState = "STARTING" | "LOCKED" | "READY" | "FAILED";
+
+export interface DatabaseStatus {
+  state: DatabaseState;
+  encrypted: boolean;
+  /** Why opening failed */
+  message: string | null;
+}
+
+export interface TableRowCount {
+  table: string;
+  rows: number;
+}
+
+export interface BackupManifest {
+  id: string;
+  /** Why it was taken, e.g. "pre-migration" */
+  label: string | null;
+  createdAt: string;
+  appVersion: string;
+  schemaVersion: number;
+  encrypted: boolean;
+  sizeBytes: number;
+  tables: TableRowCount[];
+}
+
+export interface BackupRestore {
+  restored: BackupManifest;
+  /** Backup of the database as it was before the restore */
+  safetyBackup: BackupManifest;
+}
+
+// ============================================================================
+// Onboarding Types
+// ============================================================================
+
+export interface MilestoneStatus {
+  id: string;
+  title: string;
+  hint: string;
+  link: ViewMode;
+  completed: boolean;
+  completedAt: string | null;
+}
+
+export interface OnboardingStatus {
+  milestones: MilestoneStatus[];
+  completedCount: number;
+  totalCount: number;
+  nextMilestone: string | null;
+  dismissed: boolean;
+  visible: boolean;
+}
+
+// ============================================================================
+// Activity Types
+// ============================================================================
+
+export type ActivityEventType =
+  | "assessment_created"
+  | "assessment_imported"
+  | "control_assessed"
+  | "evidence_added"
+  | "evidence_removed"
+  | "scan_created"
+  | "scan_imported"
+  | "script_generated"
+  | "report_generated"
+  | "policy_generated";
+
+/** Module an activity event came from */
+export type ActivityModule = "factory" | "network" | "grc" | "reporting";
+
+export interface ActivityEvent {
+  id: string;
+  clientId: string;
+  eventType: ActivityEventType;
+  actor: string;
+  entityType: string;
+  entityId: string | null;
+  summary: string;
+  detail: Record<string, unknown>;
+  createdAt: string;
+}
+
+// ============================================================================
+// Audit Log Types
+// ============================================================================
+
+export type AuditOperation =
+  | "scan_launched"
+  | "script_generated"
+  | "agent_token_issued"
+  | "report_exported";
+
+export type AuditExportFormat = "csv" | "jsonl";
+
+export interface AuditRecord {
+  /** Position in the chain, from 1 */
+  sequence: number;
+  id: string;
+  timestamp: string;
+  /** OS user the operation ran as */
+  actor: string;
+  operation: AuditOperation;
+  entityType: string;
+  entityId: string | null;
+  detail: Record<string, unknown>;
+  prevHash: string;
+  hash: string;
+}
+
+export interface AuditChainVerification {
+  valid: boolean;
+  recordsChecked: number;
+  /** First record that doesn't fit the chain */
+  brokenAtSequence: number | null;
+  problem: string | null;
+  /** Hash of the last record checked */
+  headHash: string;
+}
+
+// ============================================================================
+// Search Types
+// ============================================================================
+
+export type SearchEntityType =
+  | "CLIENT"
+  | "ASSET"
+  | "CONTROL"
+  | "CONTROL_ASSESSMENT"
+  | "EVIDENCE"
+  | "REPORT";
+
+/** Matched text within a snippet, as character offsets (end exclusive) */
+export interface SnippetHighlight {
+  start: number;
+  end: number;
+}
+
+export interface SearchHit {
+  entityType: SearchEntityType;
+  id: string;
+  title: string;
+  /** Text around the first match, with "…" where it was cut */
+  snippet: string;
+  highlights: SnippetHighlight[];
+  /** Null for catalog controls */
+  clientId: string | null;
+  score: number;
+}
+
 // ============================================================================
 // UI State Types
 // ============================================================================
@@ -164,6 +728,15 @@ export interface CategoryInfo {
   name: string;
   description: string;
   color: string;
+  order: number;
+}
+
+/** What a control looks like at one maturity level */
+export interface MaturityDescriptor {
+  /** 1 (Initial) to 5 (Optimizing) */
+  level: number;
+  name: string;
+  description: string;
 }
 
 export interface Control {
@@ -174,9 +747,29 @@ export interface Control {
   subcategory: string | null;
   title: string;
   description: string;
+  /** Implementation guidance: what good looks like */
   guidance: string | null;
   crossReferences: string[];
   priority: number;
+  /** Control-specific level descriptors; empty where the library has none */
+  maturityDescriptions: MaturityDescriptor[];
+}
+
+export type ControlField = "CODE" | "TITLE" | "DESCRIPTION" | "GUIDANCE";
+
+/** Matched text within a control field, as character offsets (end exclusive) */
+export interface MatchHighlight {
+  field: ControlField;
+  start: number;
+  end: number;
+}
+
+export interface ControlSearchResult {
+  control: Control;
+  /** Status in the assessment searched against, if one was given */
+  status: ComplianceStatus | null;
+  score: number;
+  highlights: MatchHighlight[];
 }
 
 export interface Assessment {
@@ -190,13 +783,16 @@ export interface Assessment {
   completedAt: string | null;
   leadAssessor: string;
   status: AssessmentStatus;
+  /** Assessment this one was cloned from */
+  sourceAssessmentId: string | null;
 }
 
 export interface CreateAssessmentRequest {
   clientId: string;
   name: string;
   description?: string;
-  framework: string;
+  /** Defaults to the client's preferred framework */
+  framework?: string;
   scope?: string;
   leadAssessor: string;
 }
@@ -211,11 +807,31 @@ export interface ControlAssessment {
   remediation: string | null;
   remediationTarget: string | null;
   riskRating: number | null;
+  /** Maturity level, 1-5 */
+  maturityLevel: number | null;
   evidenceIds: string[];
   assessedAt: string;
   assessedBy: string;
 }
 
+/** One saved version of a control's assessment */
+export interface ControlAssessmentRevision {
+  revision: number;
+  assessmentId: string;
+  controlId: string;
+  status: ComplianceStatus;
+  notes: string | null;
+  gapDescription: string | null;
+  remediation: string | null;
+  remediationTarget: string | null;
+  riskRating: number | null;
+  maturityLevel: number | null;
+  assessedAt: string;
+  assessedBy: string;
+  /** Revision this one restored, when saved by a revert */
+  revertedFrom: number | null;
+}
+
 export interface UpdateControlAssessmentRequest {
   assessmentId: string;
   controlId: string;
@@ -225,7 +841,11 @@ export interface UpdateControlAssessmentRequest {
   remediation?: string;
   remediationTarget?: string;
   riskRating?: number;
+  /** Maturity level, 1 (Initial) to 5 (Optimizing) */
+  maturityLevel?: number;
   assessedBy: string;
+  /** Raise a register risk for high-rated non-compliance (default true) */
+  createRisk?: boolean;
 }
 
 export interface Evidence {
@@ -241,6 +861,27 @@ export interface Evidence {
   collectedAt: string;
   collectedBy: string;
   notes: string | null;
+  /** Referenced from an earlier assessment rather than collected for this one */
+  carriedForward: boolean;
+}
+
+export interface ControlEvidenceCoverage {
+  controlCode: string;
+  title: string;
+  category: string;
+  status: ComplianceStatus;
+  evidenceCount: number;
+  /** Marked compliant without any supporting evidence */
+  unsupported: boolean;
+}
+
+export interface EvidenceCoverage {
+  assessmentId: string;
+  framework: string;
+  totalEvidence: number;
+  controlsWithEvidence: number;
+  unsupportedControls: number;
+  controls: ControlEvidenceCoverage[];
 }
 
 export interface CreateEvidenceRequest {
@@ -266,6 +907,8 @@ export interface CategoryScore {
   notAssessed: number;
   notApplicable: number;
   compliancePercentage: number;
+  /** Average maturity of the rated controls, if any were rated */
+  averageMaturity: number | null;
 }
 
 export interface AssessmentSummary {
@@ -278,9 +921,80 @@ export interface AssessmentSummary {
   nonCompliant: number;
   notAssessed: number;
   notApplicable: number;
+  /** Average maturity of the rated controls, if any were rated */
+  averageMaturity: number | null;
   categoryScores: CategoryScore[];
   highRiskGaps: number;
   evidenceCount: number;
+  /** Change since the assessment this one was cloned from */
+  sourceComparison: AssessmentComparison | null;
+}
+
+export interface CategoryDelta {
+  category: string;
+  displayName: string;
+  color: string;
+  baselineCompliant: number;
+  currentCompliant: number;
+  compliantDelta: number;
+  baselinePercentage: number;
+  currentPercentage: number;
+  /** Change in compliance percentage points */
+  percentageDelta: number;
+}
+
+export interface AssessmentComparison {
+  baselineId: string;
+  currentId: string;
+  framework: string;
+  baselineCompliance: number;
+  currentCompliance: number;
+  complianceDelta: number;
+  compliantDelta: number;
+  nonCompliantDelta: number;
+  categories: CategoryDelta[];
+}
+
+/** One framework control's result when a snapshot was taken */
+export interface SnapshotControl {
+  code: string;
+  title: string;
+  category: string;
+  status: ComplianceStatus;
+  riskRating: number | null;
+  maturityLevel: number | null;
+}
+
+/** An assessment's summary and control results frozen under a label */
+export interface AssessmentSnapshot {
+  id: string;
+  assessmentId: string;
+  clientId: string;
+  framework: string;
+  label: string;
+  takenAt: string;
+  summary: AssessmentSummary;
+  controls: SnapshotControl[];
+}
+
+export interface ControlStatusChange {
+  code: string;
+  title: string;
+  category: string;
+  baselineStatus: ComplianceStatus;
+  currentStatus: ComplianceStatus;
+}
+
+export interface SnapshotComparison {
+  baselineSnapshotId: string;
+  baselineLabel: string;
+  baselineTakenAt: string;
+  currentSnapshotId: string;
+  currentLabel: string;
+  currentTakenAt: string;
+  comparison: AssessmentComparison;
+  /** Controls whose status changed, in framework order */
+  changedControls: ControlStatusChange[];
 }
 
 // ============================================================================
@@ -325,7 +1039,7 @@ export type K8sHardeningCategory =
 
 export type Severity = "Critical" | "High" | "Medium" | "Low";
 
-export type K8sCheckStatus = "Pass" | "Fail" | "Warn" | "NotChecked";
+export type K8sCheckStatus = "PASS" | "FAIL" | "WARN" | "NOT_APPLICABLE" | "NOT_CHECKED";
 
 export type ResourceType =
   | "VirtualMachine"
@@ -344,42 +1058,62 @@ export interface CloudReadinessItem {
   category: ReadinessCategory;
   title: string;
   description: string;
-  guidance: string;
+  guidance: string | null;
   priority: number;
-  dependencies: string[];
-  estimatedEffortDays: number;
+  order: number;
 }
 
-export interface ReadinessResponse {
+export type ReadinessAnswer = "YES" | "PARTIAL" | "NO" | "NOT_APPLICABLE";
+
+export interface ReadinessItemAnswer {
   itemId: string;
-  status: ReadinessStatus;
+  answer: ReadinessAnswer;
   notes: string | null;
-  blockers: string | null;
+  answeredAt: string;
 }
 
 export interface CloudReadinessAssessment {
   id: string;
   clientId: string;
-  clientName: string;
+  name: string;
   targetProvider: CloudProvider;
-  assessmentDate: string;
-  responses: ReadinessResponse[];
+  targetDate: string | null;
+  answers: ReadinessItemAnswer[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type ReadinessTier = "NOT_READY" | "EMERGING" | "READY" | "OPTIMIZED";
+
+export interface CategoryReadinessScore {
+  category: ReadinessCategory;
+  displayName: string;
+  color: string;
+  total: number;
+  completed: number;
+  percentage: number;
+  weight: number;
+}
+
+export interface ReadinessScore {
+  assessmentId: string;
   overallScore: number;
-  categoryScores: Record<string, number>;
-  criticalBlockers: string[];
-  recommendations: string[];
+  tier: ReadinessTier;
+  categoryScores: CategoryReadinessScore[];
+  totalItems: number;
+  answeredItems: number;
 }
 
-export interface PerformReadinessAssessmentRequest {
+export interface SaveReadinessAnswersRequest {
   clientId: string;
-  clientName: string;
-  targetProvider: string;
-  itemStatuses: ReadinessItemStatus[];
+  name?: string;
+  targetProvider?: string;
+  answers: ReadinessAnswerInput[];
 }
 
-export interface ReadinessItemStatus {
+export interface ReadinessAnswerInput {
   itemId: string;
-  status: string;
+  answer: string;
   notes?: string;
 }
 
@@ -399,54 +1133,204 @@ export interface K8sHardeningCheck {
 export interface K8sCheckResultData {
   checkId: string;
   status: K8sCheckStatus;
-  finding: string | null;
+  notes: string | null;
+  evidenceRef: string | null;
   affectedResources: string[];
-  remediationApplied: boolean;
 }
 
 export interface K8sHardeningAudit {
   id: string;
   clientId: string;
   clusterName: string;
-  clusterVersion: string;
-  auditDate: string;
+  clusterLabel: string | null;
+  clusterVersion: string | null;
+  contextName: string | null;
+  auditedBy: string | null;
+  auditedAt: string;
   results: K8sCheckResultData[];
-  overallScore: number;
-  criticalFindings: number;
-  highFindings: number;
-  mediumFindings: number;
-  lowFindings: number;
-  recommendations: string[];
 }
 
 export interface PerformK8sAuditRequest {
   clientId: string;
   clusterName: string;
-  clusterVersion: string;
+  clusterLabel?: string;
+  clusterVersion?: string;
+  contextName?: string;
+  auditedBy?: string;
   checkResults: K8sCheckResult[];
 }
 
 export interface K8sCheckResult {
   checkId: string;
   status: string;
-  finding?: string;
+  notes?: string;
+  evidenceRef?: string;
   affectedResources?: string[];
 }
 
 export interface K8sSeverityStats {
+  auditId: string | null;
+  clusterName: string | null;
+  auditedAt: string | null;
   total: number;
   critical: number;
   high: number;
   medium: number;
   low: number;
+  info: number;
+  passed: number;
+  failed: number;
+  notChecked: number;
 }
 
-// FinOps Types
-export interface ResourceSpecs {
-  vcpus?: number;
-  memoryGb?: number;
-  storageGb?: number;
-  bandwidthGbps?: number;
+export interface K8sCheckChange {
+  checkId: string;
+  title: string;
+  severity: K8sFindingSeverity;
+  previous: K8sCheckStatus;
+  current: K8sCheckStatus;
+}
+
+export interface K8sCategoryComparison {
+  category: K8sHardeningCategory;
+  displayName: string;
+  newlyPassing: K8sCheckChange[];
+  newlyFailing: K8sCheckChange[];
+  unchanged: K8sCheckChange[];
+  otherChanges: K8sCheckChange[];
+}
+
+export interface K8sSeverityComparison {
+  severity: K8sFindingSeverity;
+  newlyPassing: number;
+  newlyFailing: number;
+  unchanged: number;
+}
+
+export interface K8sAuditComparison {
+  baselineId: string;
+  currentId: string;
+  baselineCluster: string;
+  currentCluster: string;
+  newlyPassing: number;
+  newlyFailing: number;
+  unchanged: number;
+  categories: K8sCategoryComparison[];
+  severities: K8sSeverityComparison[];
+}
+
+export type K8sFindingSeverity = "CRITICAL" | "HIGH" | "MEDIUM" | "LOW" | "INFO";
+
+export interface K8sHardeningFinding {
+  id: string;
+  auditId: string;
+  checkId: string;
+  result: "PASS" | "FAIL" | "WARNING" | "ERROR" | "SKIPPED";
+  severity: K8sFindingSeverity;
+  resourceType: string | null;
+  resourceName: string | null;
+  namespace: string | null;
+  details: string | null;
+  foundAt: string;
+}
+
+export interface K8sCategoryResult {
+  category: K8sHardeningCategory;
+  displayName: string;
+  color: string;
+  total: number;
+  passed: number;
+  failed: number;
+  scorePercentage: number;
+}
+
+export interface K8sAuditSummary {
+  auditId: string;
+  totalChecks: number;
+  passed: number;
+  failed: number;
+  warnings: number;
+  criticalFindings: number;
+  highFindings: number;
+  categoryResults: K8sCategoryResult[];
+}
+
+export interface K8sWorkload {
+  kind: string;
+  name: string;
+  namespace: string | null;
+  source: string;
+  containers: string[];
+  failedChecks: number;
+}
+
+export interface K8sUnparsedResource {
+  source: string;
+  document: number | null;
+  kind: string | null;
+  name: string | null;
+  reason: string;
+}
+
+export interface K8sFileFindings {
+  source: string;
+  workloads: number;
+  findings: K8sHardeningFinding[];
+}
+
+export interface K8sCheckFindings {
+  checkId: string;
+  title: string;
+  severity: Severity;
+  passed: number;
+  failed: number;
+  affected: string[];
+}
+
+export interface K8sManifestAudit {
+  id: string;
+  auditedAt: string;
+  files: string[];
+  workloads: K8sWorkload[];
+  findings: K8sHardeningFinding[];
+  unparsed: K8sUnparsedResource[];
+  skipped: K8sUnparsedResource[];
+  byFile: K8sFileFindings[];
+  byCheck: K8sCheckFindings[];
+  severityStats: K8sSeverityStats;
+  summary: K8sAuditSummary;
+}
+
+export type K8sClusterCheckOutcome = "PASS" | "FAIL" | "MANUAL";
+
+export interface K8sClusterCheck {
+  checkId: string;
+  title: string;
+  severity: Severity;
+  outcome: K8sClusterCheckOutcome;
+  evidence: string[];
+  error: string | null;
+}
+
+export interface K8sResourceError {
+  resource: string;
+  namespace: string | null;
+  error: string;
+}
+
+export interface K8sAuditResult {
+  audit: K8sHardeningAudit;
+  checks: K8sClusterCheck[];
+  workloads: number;
+  resourceErrors: K8sResourceError[];
+}
+
+// FinOps Types
+export interface ResourceSpecs {
+  vcpus?: number;
+  memoryGb?: number;
+  storageGb?: number;
+  bandwidthGbps?: number;
   iops?: number;
 }
 
@@ -492,6 +1376,37 @@ export interface FinOpsAnalysis {
   resourceBreakdown: ResourceCostEstimate[];
   recommendations: CostRecommendation[];
   assumptions: string[];
+  /** Null for on-demand analyses */
+  commitmentAnalysis: CommitmentAnalysis | null;
+}
+
+export type CommitmentTerm =
+  | "ON_DEMAND"
+  | "ONE_YEAR_NO_UPFRONT"
+  | "ONE_YEAR_ALL_UPFRONT"
+  | "THREE_YEAR";
+
+export type CommitmentClass = "COMPUTE" | "DATABASE" | "ON_DEMAND_ONLY";
+
+/** On-demand vs committed monthly cost for one resource class */
+export interface CommitmentClassCost {
+  class: CommitmentClass;
+  onDemandMonthly: number;
+  committedMonthly: number;
+  discountPercentage: number;
+}
+
+export interface CommitmentAnalysis {
+  term: CommitmentTerm;
+  byClass: CommitmentClassCost[];
+  onDemandMonthly: number;
+  committedMonthly: number;
+  /** Paid when the commitment is bought; zero for monthly payment */
+  upfrontPayment: number;
+  /** Utilization of committed capacity below which on-demand would be cheaper */
+  breakEvenUtilizationPercentage: number;
+  /** Commitment still owed if the committed workload is shut down halfway through the term */
+  downsizeExposure: number;
 }
 
 export interface FinOpsTemplate {
@@ -506,6 +1421,9 @@ export interface GenerateFinOpsAnalysisRequest {
   migrationStrategy: string;
   currentCosts: OnPremCostsInput;
   resources: ResourceInput[];
+  pricingProfileId?: string;
+  /** Defaults to on-demand */
+  commitment?: CommitmentTerm;
 }
 
 export interface OnPremCostsInput {
@@ -540,16 +1458,157 @@ export interface CalculateResourceCostRequest {
   bandwidthGbps?: number;
   iops?: number;
   provider: string;
+  pricingProfileId?: string;
+}
+
+export type CommitmentStrategy =
+  | "ON_DEMAND"
+  | "RESERVED_1YR"
+  | "RESERVED_3YR"
+  | "SAVINGS_PLAN";
+
+export interface GrowthRateInput {
+  resourceType: string;
+  annualRatePercent: number;
+}
+
+export interface TcoProjectionInput {
+  horizonYears: number;
+  commitmentStrategy: CommitmentStrategy;
+  growthRates: GrowthRateInput[];
+  currentCosts: OnPremCostsInput;
+  migrationStrategy: string;
+}
+
+export interface GenerateTcoProjectionRequest {
+  clientId: string;
+  targetProvider: string;
+  resources: ResourceInput[];
+  projection: TcoProjectionInput;
+}
+
+export interface TcoYear {
+  year: number;
+  onPremCost: number;
+  cloudCost: number;
+  migrationCost: number;
+  netSavings: number;
+  cumulativeSavings: number;
+}
+
+export interface TcoProjection {
+  id: string;
+  clientId: string;
+  provider: CloudProvider;
+  migrationStrategy: MigrationStrategy;
+  commitment: CommitmentStrategy;
+  horizonYears: number;
+  years: TcoYear[];
+  totalOnPremCost: number;
+  totalCloudCost: number;
+  cumulativeSavings: number;
+  breakEvenMonth: number | null;
+  assumptions: string[];
 }
 
 export interface ProviderComparison {
   provider: string;
   monthlyCost: number;
   annualCost: number;
+  tco: TcoProjection | null;
 }
 
 export interface CompareProvidersRequest {
   resources: ResourceInput[];
+  projection?: TcoProjectionInput;
+}
+
+/** One resource's monthly cost on each provider */
+export interface ResourceProviderCosts {
+  name: string;
+  resourceType: ResourceType;
+  aws: number;
+  azure: number;
+  gcp: number;
+}
+
+export interface ProviderCostTotal {
+  provider: CloudProvider;
+  monthlyCost: number;
+  annualCost: number;
+  /** Monthly cost above the cheapest provider; zero for the cheapest */
+  deltaVsCheapest: number;
+}
+
+/** Side-by-side cost of a set of resources on each provider */
+export interface ResourceCostComparison {
+  id: string;
+  clientId: string;
+  commitment: CommitmentStrategy;
+  perResource: ResourceProviderCosts[];
+  totalsByProvider: ProviderCostTotal[];
+  cheapestProvider: CloudProvider;
+  /** One analysis per provider, when current costs were given */
+  analyses: FinOpsAnalysis[];
+}
+
+export interface CompareResourceCostsRequest {
+  clientId: string;
+  resources: ResourceInput[];
+  /** Defaults to on-demand */
+  commitmentStrategy?: CommitmentStrategy;
+  /** With migrationStrategy, also runs a full FinOps analysis per provider */
+  currentCosts?: OnPremCostsInput;
+  migrationStrategy?: MigrationStrategy;
+}
+
+export interface PricingProfile {
+  id: string;
+  name: string;
+  provider: CloudProvider;
+  region: string;
+  computePerVcpu: number | null;
+  memoryPerGb: number | null;
+  storagePerGbMonth: number | null;
+  egressPerGb: number | null;
+  managedDbMultiplier: number | null;
+  k8sClusterBase: number | null;
+  notes: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SavePricingProfileRequest {
+  name: string;
+  provider: string;
+  region: string;
+  computePerVcpu?: number;
+  memoryPerGb?: number;
+  storagePerGbMonth?: number;
+  egressPerGb?: number;
+  managedDbMultiplier?: number;
+  k8sClusterBase?: number;
+  notes?: string;
+}
+
+export interface PricingImportSummary {
+  profiles: PricingProfile[];
+  warnings: string[];
+}
+
+/** A utilization row that matched no asset, with the closest asset names */
+export interface UnmatchedUtilizationRow {
+  line: number;
+  server: string;
+  suggestions: string[];
+}
+
+export interface UtilizationImportSummary {
+  /** Assets that now carry utilization */
+  matched: number;
+  unmatched: UnmatchedUtilizationRow[];
+  /** Rows that were skipped, with the reason */
+  warnings: string[];
 }
 
 // ============================================================================
@@ -570,6 +1629,7 @@ export type ScanType =
 export type ScanStatus =
   | "queued"
   | "running"
+  | "parsing"
   | "completed"
   | "failed"
   | "cancelled";
@@ -614,8 +1674,23 @@ export interface NmapInfo {
   installed: boolean;
   version: string | null;
   path: string | null;
+  pathSource: NmapSource | null;
+  /** Null off Windows, where Npcap isn't needed */
+  npcapInstalled: boolean | null;
+  privileges: ScanPrivileges;
+}
+
+export type NmapSource = "CONFIGURED" | "PATH" | "KNOWN_LOCATION";
+
+export interface ScanPrivileges {
+  elevated: boolean;
+  nmapCapabilities: boolean;
+  packetDriver: boolean;
+  remedy: string | null;
 }
 
+export type ScanPrivilegeMode = 'ELEVATED' | 'CAPABILITIES' | 'UNPRIVILEGED';
+
 export interface ScanTypeInfo {
   scanType: ScanType;
   name: string;
@@ -637,6 +1712,53 @@ export interface TargetValidation {
   error: string | null;
 }
 
+export type TargetKind = "IPV4" | "IPV6" | "CIDR" | "RANGE" | "HOSTNAME";
+
+export interface TargetCount {
+  target: string;
+  kind: TargetKind;
+  hosts: number;
+  excluded: number;
+  public: boolean;
+}
+
+export interface TargetOverlap {
+  target: string;
+  exclude: string;
+  hosts: number;
+}
+
+export interface TargetPlan {
+  totalHosts: number;
+  excludedHosts: number;
+  publicHosts: number;
+  perTargetCounts: TargetCount[];
+  overlaps: TargetOverlap[];
+  rfc1918Only: boolean;
+  warnings: string[];
+}
+
+export interface ScanEstimate {
+  hosts: number;
+  timingTemplate: number;
+  etaSeconds: number;
+  summary: string;
+}
+
+export interface ScanTargetPlan {
+  plan: TargetPlan;
+  estimate: ScanEstimate;
+}
+
+export interface CustomArgsValidation {
+  valid: boolean;
+  /** The arguments as they will be passed to Nmap, denied options removed */
+  normalizedArgs: string[];
+  /** Options that clash with ones Optio adds */
+  warnings: string[];
+  errors: string[];
+}
+
 export interface ScanConfig {
   targets: string[];
   scanType: ScanType;
@@ -645,6 +1767,13 @@ export interface ScanConfig {
   excludeTargets?: string[];
   aggressive: boolean;
   skipDiscovery: boolean;
+  privilegeMode: ScanPrivilegeMode;
+  /** Targets per batch; unset scans all targets in one Nmap run */
+  batchSize?: number | null;
+  /** Batches run in parallel (default 1) */
+  batchConcurrency?: number | null;
+  /** Passed to Nmap as --max-rate */
+  maxPacketsPerSecond?: number | null;
 }
 
 export interface ScanJob {
@@ -658,19 +1787,91 @@ export interface ScanJob {
   completedAt: string | null;
   error: string | null;
   progress: number;
-  rawOutput: string | null;
+  totals: ScanTotals | null;
+  verification: VerificationInfo | null;
+  import: ScanImportInfo | null;
+  warnings: string[];
+  batches: ScanBatchProgress | null;
+  /** Nmap's own progress; only on "scan-progress" events while running */
+  live?: ScanLiveProgress;
+}
+
+export interface ScanLiveProgress {
+  percentDone: number;
+  hostsCompleted: number;
+  /** Time left in Nmap's current phase */
+  etaSeconds: number | null;
+  task: string | null;
+}
+
+export interface ScanBatchProgress {
+  completed: number;
+  total: number;
+}
+
+export interface ScanTotals {
+  hostsScanned: number;
+  hostsUp: number;
+  openPorts: number;
+  durationSeconds: number;
+}
+
+export interface ScanResults {
+  scanId: string;
+  hosts: DiscoveredHost[];
+  hostsScanned: number;
+  hostsUp: number;
+  durationSeconds: number;
+  nmapVersion: string | null;
+  commandLine: string;
+  startTime: string;
+  endTime: string;
+}
+
+export interface ScanDetail {
+  scan: ScanJob;
+  results: ScanResults | null;
+  hasRawXml: boolean;
+}
+
+/** Nmap output formats that can be imported (-oX, -oG) */
+export type NmapOutputFormat = "xml" | "greppable";
+
+export interface ScanImportInfo {
+  sourceFile: string;
+  format: NmapOutputFormat;
+  commandLine: string;
+  nmapVersion: string | null;
+  scanStarted: string;
+  importedAt: string;
+}
+
+export interface ScanImportSummary {
+  scanId: string;
+  scanName: string;
+  hostsImported: number;
+  newAssets: number;
+  updatedAssets: number;
+  warnings: string[];
 }
 
 export interface CreateScanRequest {
   clientId: string;
   name: string;
   targets: string[];
-  scanType: string;
+  /** Saved profile whose settings the fields below override */
+  profileId?: string;
+  /** Required unless a profile is given */
+  scanType?: string;
   customArgs?: string;
   ports?: string;
+  /** Added to the profile's exclusions */
   excludeTargets?: string[];
-  aggressive: boolean;
-  skipDiscovery: boolean;
+  aggressive?: boolean;
+  skipDiscovery?: boolean;
+  batchSize?: number;
+  batchConcurrency?: number;
+  maxPacketsPerSecond?: number;
 }
 
 export interface DiscoveredPort {
@@ -699,6 +1900,54 @@ export interface DiscoveredHost {
   status: string;
   ports: DiscoveredPort[];
   osMatches: OsMatch[];
+  uptimeSeconds: number | null;
+  lastBoot: string | null;
+  distance: number | null;
+}
+
+export interface PortRef {
+  port: number;
+  protocol: Protocol;
+  service: string | null;
+}
+
+export interface DiffHost {
+  ipAddress: string;
+  macAddress: string | null;
+  hostname: string | null;
+  openPorts: PortRef[];
+}
+
+export interface ServiceChange {
+  port: number;
+  protocol: Protocol;
+  oldService: string | null;
+  newService: string | null;
+}
+
+export interface OsChange {
+  oldOs: string;
+  newOs: string;
+}
+
+export interface HostDiff {
+  ipAddress: string;
+  previousIpAddress: string | null;
+  macAddress: string | null;
+  hostname: string | null;
+  portsOpened: PortRef[];
+  portsClosed: PortRef[];
+  serviceChanges: ServiceChange[];
+  osChange: OsChange | null;
+}
+
+export interface ScanDiff {
+  scanIdA: string;
+  scanIdB: string;
+  hostsAppeared: DiffHost[];
+  hostsDisappeared: DiffHost[];
+  hostsChanged: HostDiff[];
+  hostsUnchanged: number;
 }
 
 export interface AssetService {
@@ -738,6 +1987,88 @@ export interface AssetGroup {
   color: string | null;
 }
 
+/** Server-side asset filter; every condition that is set must hold */
+export interface AssetFilter {
+  categories?: AssetCategory[];
+  criticalities?: AssetCriticality[];
+  statuses?: AssetStatus[];
+  /** All of these tags (case-insensitive) */
+  tags?: string[];
+  openPort?: number | null;
+  /** Substring of a service name or product/version */
+  service?: string | null;
+  operatingSystem?: string | null;
+  /** RFC 3339 */
+  lastSeenAfter?: string | null;
+  lastSeenBefore?: string | null;
+  sortBy?: AssetSortField | null;
+  descending?: boolean;
+}
+
+export type AssetSortField =
+  | "name"
+  | "ip_address"
+  | "category"
+  | "criticality"
+  | "status"
+  | "first_seen"
+  | "last_seen";
+
+export interface AssetPage {
+  assets: Asset[];
+  total: number;
+  limit: number;
+  offset: number;
+}
+
+/** Fields set on many assets at once; an empty owner or location clears it */
+export interface AssetPatch {
+  criticality?: AssetCriticality | null;
+  owner?: string | null;
+  location?: string | null;
+  /** Replaces the assets' tags */
+  tags?: string[] | null;
+}
+
+export type AssetExportFormat = "csv" | "json";
+
+export interface AssetExportSummary {
+  filePath: string;
+  rows: number;
+}
+
+export interface AssetImportSummary {
+  created: number;
+  updated: number;
+  /** Rows that were skipped, with the reason */
+  warnings: string[];
+}
+
+export interface AssetView {
+  id: string;
+  clientId: string;
+  name: string;
+  filter: AssetFilter;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type AssetChangeType =
+  | "port_opened"
+  | "port_closed"
+  | "os_changed"
+  | "hostname_changed";
+
+export interface AssetChange {
+  id: string;
+  assetId: string;
+  scanId: string;
+  changeType: AssetChangeType;
+  oldValue: string | null;
+  newValue: string | null;
+  changedAt: string;
+}
+
 export interface UpdateAssetRequest {
   id: string;
   name: string;
@@ -772,6 +2103,86 @@ export interface ServiceCount {
   count: number;
 }
 
+/** Saved scan defaults; profiles without a client are global */
+export interface ScanProfile {
+  id: string;
+  clientId: string | null;
+  name: string;
+  config: ScanConfig;
+  notes: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ScanProfileRequest {
+  /** Leave unset for a profile every client can use */
+  clientId?: string;
+  name: string;
+  scanType: string;
+  customArgs?: string;
+  ports?: string;
+  excludeTargets?: string[];
+  aggressive?: boolean;
+  skipDiscovery?: boolean;
+  batchSize?: number;
+  batchConcurrency?: number;
+  maxPacketsPerSecond?: number;
+  notes?: string;
+}
+
+export type ScheduleFrequency =
+  | { type: "interval"; minutes: number }
+  | { type: "daily"; hour: number; minute: number };
+
+/** What a schedule does about a run that came due while the app was closed */
+export type MissedRunPolicy = "run_once" | "skip";
+
+export interface ScanSchedule {
+  id: string;
+  clientId: string;
+  name: string;
+  config: ScanConfig;
+  frequency: ScheduleFrequency;
+  enabled: boolean;
+  missedRuns: MissedRunPolicy;
+  nextRun: string | null;
+  lastRun: string | null;
+  lastStatus: ScanStatus | null;
+  lastError: string | null;
+  lastScanId: string | null;
+  createdAt: string;
+}
+
+export interface CreateScanScheduleRequest {
+  clientId: string;
+  name: string;
+  targets: string[];
+  scanType: string;
+  customArgs?: string;
+  ports?: string;
+  excludeTargets?: string[];
+  aggressive: boolean;
+  skipDiscovery: boolean;
+  batchSize?: number;
+  batchConcurrency?: number;
+  maxPacketsPerSecond?: number;
+  frequency: ScheduleFrequency;
+  /** Defaults to "run_once" */
+  missedRuns?: MissedRunPolicy;
+}
+
+/** Payload of the "scheduled-scan-completed" event */
+export interface ScheduledScanCompleted {
+  scheduleId: string;
+  scheduleName: string;
+  clientId: string;
+  scanId: string;
+  status: ScanStatus;
+  hostsUp: number;
+  assetsUpdated: number;
+  error: string | null;
+}
+
 export interface ScanSummary {
   id: string;
   name: string;
@@ -789,22 +2200,156 @@ export interface NetworkStats {
   byCriticality: CriticalityCount[];
   topServices: ServiceCount[];
   recentScans: ScanSummary[];
+  vulnerableAssets: number;
+  knownVulnerabilities: number;
+  byVulnerabilitySeverity: CriticalityCount[];
+  /** Services breaking a service policy, across all assets */
+  policyViolations: number;
 }
 
-// ============================================================================
-// Reporting Types
-// ============================================================================
+/**
+ * A rule about which services may be exposed where. Empty lists match
+ * anything; a user policy with a default's id replaces that default.
+ */
+export interface ServicePolicy {
+  id: string;
+  name: string;
+  ports: number[];
+  services: string[];
+  categories: AssetCategory[];
+  criticalities: AssetCriticality[];
+  /** Asset tags that exempt an asset, e.g. "jump-host" */
+  exemptTags: string[];
+  severity: AssetCriticality;
+  message: string;
+  recommendation: string | null;
+  enabled: boolean;
+  builtIn: boolean;
+}
 
-export type ReportType =
-  | "ExecutiveSummary"
-  | "TechnicalAssessment"
+export interface UpsertServicePolicyRequest {
+  /** Policy to replace; a new policy is created when unset */
+  id?: string;
+  name: string;
+  ports?: number[];
+  services?: string[];
+  categories?: AssetCategory[];
+  criticalities?: AssetCriticality[];
+  exemptTags?: string[];
+  severity: AssetCriticality;
+  message: string;
+  recommendation?: string;
+  enabled?: boolean;
+}
+
+export interface PolicyViolation {
+  policyId: string;
+  policyName: string;
+  port: number;
+  service: string;
+  severity: AssetCriticality;
+  message: string;
+  recommendation: string | null;
+}
+
+export interface AssetPolicyViolations {
+  assetId: string;
+  assetName: string;
+  ipAddress: string;
+  category: AssetCategory;
+  criticality: AssetCriticality;
+  violations: PolicyViolation[];
+}
+
+// ============================================================================
+// Vulnerability Enrichment Types
+// ============================================================================
+
+/** Affected versions of one product: an exact version, or a range */
+export interface AffectedProduct {
+  vendor: string;
+  product: string;
+  version: string | null;
+  startIncluding: string | null;
+  startExcluding: string | null;
+  endIncluding: string | null;
+  endExcluding: string | null;
+}
+
+export interface VulnEntry {
+  cveId: string;
+  summary: string;
+  cvssScore: number;
+  affected: AffectedProduct[];
+}
+
+/** A CVE from the CISA Known Exploited Vulnerabilities catalog */
+export interface KnownExploitedVuln {
+  cveId: string;
+  vendorProject: string;
+  product: string;
+  name: string;
+  dateAdded: string | null;
+  dueDate: string | null;
+  ransomware: boolean;
+}
+
+export interface VulnImportSummary {
+  entries: VulnEntry[];
+  knownExploited: KnownExploitedVuln[];
+  warnings: string[];
+}
+
+export interface VulnerabilityMatch {
+  assetId: string;
+  assetName: string;
+  ipAddress: string;
+  cveId: string;
+  port: number;
+  protocol: Protocol;
+  product: string;
+  version: string;
+  cvssScore: number;
+  severity: AssetCriticality;
+  summary: string;
+  matchedRange: string;
+  matchedAt: string;
+  /** Listed in the CISA Known Exploited Vulnerabilities catalog */
+  kev: boolean;
+}
+
+/** A service that couldn't be checked against the CVE dataset */
+export interface UnmatchedService {
+  port: number;
+  protocol: Protocol;
+  name: string;
+  detail: string | null;
+  reason: string;
+}
+
+export interface AssetVulnerabilityReport {
+  assetId: string;
+  assetName: string;
+  ipAddress: string;
+  matches: VulnerabilityMatch[];
+  unmatched: UnmatchedService[];
+  servicesChecked: number;
+}
+
+// ============================================================================
+// Reporting Types
+// ============================================================================
+
+export type ReportType =
+  | "ExecutiveSummary"
+  | "TechnicalAssessment"
   | "ComplianceReport"
   | "NetworkAssessment"
   | "CloudReadiness"
   | "SecurityFindings"
   | "FullEngagement";
 
-export type ExportFormat = "Pdf" | "Html" | "Markdown" | "Docx" | "Json";
+export type ExportFormat = "Pdf" | "Html" | "Markdown" | "Docx" | "Json" | "Csv";
 
 export type ReportStatus =
   | "Draft"
@@ -857,6 +2402,38 @@ export interface GenerateReportRequest {
   includeCharts: boolean;
   classification?: string;
   notes?: string;
+  framework?: string;
+  groupCategories?: boolean;
+  baselineScanId?: string;
+  followupScanId?: string;
+  useDemoData?: boolean;
+  costProjections?: TcoProjection[];
+  providerComparison?: ResourceCostComparison;
+  dataSources?: DataSource[];
+  logoPath?: string;
+  primaryColor?: string;
+  /** Template section ids to include; defaults to the template's defaults */
+  selectedSections?: string[];
+  /** Per-section choices; sections left out keep the template's default */
+  sections?: SectionSelection[];
+  /** Kubernetes hardening audit for the technical assessment */
+  k8sAuditId?: string;
+  /** Existing report to regenerate in place, keeping its custom sections */
+  reportId?: string;
+  /** Assessment snapshot to take the compliance data from */
+  snapshotId?: string;
+}
+
+/** Whether one template section goes into a report */
+export interface SectionSelection {
+  id: string;
+  included: boolean;
+}
+
+export interface DataSource {
+  sourceType: string;
+  sourceId: string;
+  included: boolean;
 }
 
 export interface ReportConfig {
@@ -874,9 +2451,16 @@ export interface ReportConfig {
   includeCharts: boolean;
   logoPath: string | null;
   primaryColor: string | null;
+  secondaryColor: string | null;
+  footerText: string | null;
   notes: string | null;
   classification: string | null;
-  dataSources: string[];
+  dataSources: DataSource[];
+  selectedSections: string[] | null;
+  /** Client's primary contact for the cover page */
+  clientContact: string | null;
+  /** Assessment snapshot the compliance data was frozen from */
+  snapshotId: string | null;
 }
 
 export interface ReportContent {
@@ -884,6 +2468,80 @@ export interface ReportContent {
   generatedAt: string;
   wordCount: number;
   pageEstimate: number;
+  toc: TocEntry[];
+  /** Where each template section landed, for placing custom sections */
+  anchors: SectionAnchor[];
+}
+
+export interface SectionAnchor {
+  sectionId: string;
+  sectionCount: number;
+}
+
+export type SectionPlacement = "before" | "after";
+
+/** A user-written section placed before or after a template section */
+export interface CustomSection {
+  id: string;
+  reportId: string;
+  title: string;
+  placement: SectionPlacement;
+  anchorSectionId: string;
+  blocks: ContentBlock[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CustomSectionRequest {
+  title: string;
+  placement: SectionPlacement;
+  anchorSectionId: string;
+  /** Paragraph, bullet list, table, callout and code blocks */
+  blocks: ContentBlock[];
+}
+
+export interface TocEntry {
+  number: string;
+  title: string;
+  sectionId: string;
+  level: number;
+}
+
+export interface ReportMetadata {
+  title: string;
+  subtitle: string | null;
+  author: string;
+  organization: string | null;
+  clientName: string;
+  clientContact: string | null;
+  reportDate: string;
+  classification: string | null;
+  version: string;
+  pageCount: number | null;
+  logoPath: string | null;
+  primaryColor: string | null;
+  secondaryColor: string | null;
+  footerText: string | null;
+}
+
+/** A section of a streaming preview, sent as it's rendered */
+export interface ReportPreviewSection {
+  previewId: string;
+  /** Position of the section in the report */
+  index: number;
+  section: ReportSection;
+}
+
+/** The end of a streaming preview */
+export interface ReportPreviewDone {
+  previewId: string;
+  /** Absent when the preview failed or was cancelled */
+  metadata: ReportMetadata | null;
+  toc: TocEntry[];
+  sectionCount: number;
+  dataSources: DataSource[];
+  cancelled: boolean;
+  error: string | null;
 }
 
 export interface ReportSection {
@@ -905,7 +2563,8 @@ export type ContentBlock =
   | { type: "Callout"; calloutType: CalloutType; title: string; content: string }
   | { type: "KeyValue"; pairs: Record<string, string> }
   | { type: "PageBreak" }
-  | { type: "Spacer"; height: number };
+  | { type: "Spacer"; height: number }
+  | { type: "RawHtml"; html: string };
 
 export interface TableData {
   headers: string[];
@@ -961,6 +2620,8 @@ export interface Report {
   fileSize: number | null;
   filePath: string | null;
   error: string | null;
+  /** Branding that couldn't be applied, such as an unreadable logo; only set on generation */
+  warnings: string[];
 }
 
 export interface ReportSummary {
@@ -998,7 +2659,7 @@ export interface ReportStats {
 
 export interface GenerateAgentScriptRequest {
   clientIp: string;
-  authToken: string;
+  tokenId: string;
   callbackPort?: number;
   useTls?: boolean;
   heartbeatInterval?: number;
@@ -1006,12 +2667,60 @@ export interface GenerateAgentScriptRequest {
 
 export interface AgentScriptResponse {
   success: boolean;
+  outputPath: string;
   scriptContent: string;
   scriptId: string;
   generatedAt: string;
   warnings: string[];
 }
 
+export interface AgentToken {
+  id: string;
+  clientId: string;
+  lastFour: string;
+  label: string | null;
+  createdAt: string;
+  /** Null for tokens that never expire */
+  expiresAt: string | null;
+  revokedAt: string | null;
+  lastUsedAt: string | null;
+}
+
+export interface AgentListenerStatus {
+  running: boolean;
+  port: number | null;
+  /** Whether agents must connect over TLS */
+  tls: boolean;
+  startedAt: string | null;
+}
+
+export interface ConnectedAgent {
+  id: string;
+  clientId: string;
+  tokenId: string;
+  scriptId: string;
+  hostname: string;
+  username: string | null;
+  osVersion: string | null;
+  remoteIp: string;
+  /** Host details from the latest telemetry */
+  domain: string | null;
+  ipAddresses: string[];
+  totalMemoryMb: number | null;
+  lastTelemetryAt: string | null;
+  connectedAt: string;
+  lastHeartbeat: string;
+  /** Null while the agent is connected */
+  disconnectedAt: string | null;
+}
+
+export interface AgentTelemetry {
+  id: number;
+  agentId: string;
+  receivedAt: string;
+  data: Record<string, unknown>;
+}
+
 // ============================================================================
 // Native TCP Scanner Types (Task B - Core Mechanics)
 // ============================================================================
@@ -1044,6 +2753,148 @@ export interface ScannedPort {
   service: string;
 }
 
+// ============================================================================
+// Findings & Verification Types
+// ============================================================================
+
+export type FindingStatus = "open" | "remediated" | "unverifiable";
+
+export interface FindingRule {
+  id: string;
+  title: string;
+  severity: AssetCriticality;
+  ports: number[];
+  description: string;
+  recommendation: string;
+}
+
+export interface NetworkFinding {
+  id: string;
+  clientId: string;
+  ruleId: string;
+  host: string;
+  port: number;
+  service: string;
+  title: string;
+  severity: AssetCriticality;
+  status: FindingStatus;
+  evidence: string[];
+  notes: string | null;
+  detectedAt: string;
+  updatedAt: string;
+  verificationScanId: string | null;
+}
+
+export interface VerificationTarget {
+  hosts: string[];
+  ports: number[];
+}
+
+export interface VerificationSummary {
+  verifiedFixed: number;
+  stillPresent: number;
+  unreachable: number;
+  evaluatedAt: string;
+}
+
+export interface VerificationInfo {
+  findingIds: string[];
+  targets: VerificationTarget[];
+  summary: VerificationSummary | null;
+}
+
+// ============================================================================
+// Findings Library Types
+// ============================================================================
+
+/**
+ * A reusable finding write-up; a user template with a built-in's id
+ * replaces that built-in.
+ */
+export interface FindingTemplate {
+  id: string;
+  title: string;
+  severity: AssetCriticality;
+  /** CVSS v3.1 base vector, e.g. "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" */
+  cvssVector: string | null;
+  cvssScore: number | null;
+  description: string;
+  impact: string;
+  recommendation: string;
+  references: string[];
+  tags: string[];
+  builtIn: boolean;
+}
+
+export interface UpsertFindingTemplateRequest {
+  /** Template to replace; a new template is created when unset */
+  id?: string;
+  title: string;
+  severity: AssetCriticality;
+  cvssVector?: string;
+  description: string;
+  impact?: string;
+  recommendation: string;
+  references?: string[];
+  tags?: string[];
+}
+
+export interface CvssScore {
+  /** The vector in canonical metric order */
+  vector: string;
+  baseScore: number;
+  impactSubscore: number;
+  exploitabilitySubscore: number;
+  severity: AssetCriticality;
+}
+
+/** A finding recorded against a client, usually from a template */
+export interface Finding {
+  id: string;
+  clientId: string;
+  templateId: string | null;
+  title: string;
+  severity: AssetCriticality;
+  cvssVector: string | null;
+  cvssScore: number | null;
+  description: string;
+  impact: string;
+  recommendation: string;
+  references: string[];
+  tags: string[];
+  assetIds: string[];
+  assessmentId: string | null;
+  status: FindingStatus;
+  notes: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/**
+ * Changes to a finding; unset fields are kept. A new CVSS vector also
+ * re-rates the severity unless one is given, and an empty vector clears it.
+ */
+export interface FindingOverrides {
+  title?: string;
+  severity?: AssetCriticality;
+  cvssVector?: string;
+  description?: string;
+  impact?: string;
+  recommendation?: string;
+  references?: string[];
+  tags?: string[];
+  assetIds?: string[];
+  assessmentId?: string;
+  status?: FindingStatus;
+  notes?: string;
+}
+
+export interface CreateVerificationScanRequest {
+  clientId: string;
+  findingIds: string[];
+  name?: string;
+}
+
 // ============================================================================
 // Compliance Status Types (Phase 3 - GRC Database)
 // ============================================================================
@@ -1060,6 +2911,300 @@ export interface CategoryComplianceStatus {
   nonCompliant: number;
   completionPercentage: number;
   compliancePercentage: number;
+  /** Average maturity of the rated controls, if any were rated */
+  averageMaturity: number | null;
+  /** Controls with a maturity level */
+  maturityRated: number;
+}
+
+export interface CategoryGroup {
+  name: string;
+  order: number;
+  categoryCodes: string[];
+}
+
+export interface CategoryGrouping {
+  id: string;
+  clientId: string;
+  framework: string;
+  groups: CategoryGroup[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SetCategoryGroupingRequest {
+  clientId: string;
+  framework: string;
+  groups: CategoryGroup[];
+}
+
+// Assessment transfer
+export type ConflictStrategy = "SKIP_EXISTING" | "OVERWRITE" | "MERGE_NEWEST";
+
+export type TransferAction = "CREATED" | "UPDATED" | "SKIPPED";
+
+export interface AssessmentExportResult {
+  filePath: string;
+  controlAssessments: number;
+  evidence: number;
+  evidenceFiles: number;
+  warnings: TransferWarning[];
+}
+
+export interface TransferWarning {
+  recordType: TransferRecord["recordType"];
+  id: string;
+  label: string;
+  message: string;
+}
+
+export interface TransferRecord {
+  recordType: "client" | "assessment" | "control_assessment" | "evidence" | "category_grouping";
+  id: string;
+  label: string;
+  action: TransferAction;
+  reason?: string;
+}
+
+export interface AssessmentImportSummary {
+  assessmentId: string;
+  clientId: string;
+  created: number;
+  updated: number;
+  skipped: number;
+  records: TransferRecord[];
+  warnings: TransferWarning[];
+}
+
+// Policy generation
+export type PolicyType =
+  | "INFORMATION_SECURITY"
+  | "ACCESS_CONTROL"
+  | "ASSET_MANAGEMENT"
+  | "RISK_MANAGEMENT"
+  | "DATA_PROTECTION"
+  | "INCIDENT_RESPONSE"
+  | "BUSINESS_CONTINUITY"
+  | "LOGGING_MONITORING"
+  | "CHANGE_MANAGEMENT"
+  | "SUPPLIER_SECURITY";
+
+export interface PolicyDocument {
+  policyType: PolicyType;
+  title: string;
+  /** Codes of the controls the policy cites */
+  controlCodes: string[];
+  /** A firm template from the override directory was used */
+  customTemplate: boolean;
+  content: ReportContent;
+  html: string;
+  markdown: string;
+  warnings: string[];
+}
+
+// Risk register
+export type RiskStatus = "OPEN" | "MITIGATING" | "ACCEPTED" | "CLOSED";
+
+export type RiskLevel = "LOW" | "MEDIUM" | "HIGH" | "CRITICAL";
+
+export interface RiskItem {
+  id: string;
+  clientId: string;
+  assessmentId?: string;
+  title: string;
+  description: string;
+  /** 1-5 */
+  likelihood: number;
+  /** 1-5 */
+  impact: number;
+  inherentScore: number;
+  residualLikelihood?: number;
+  residualImpact?: number;
+  residualScore?: number;
+  owner?: string;
+  status: RiskStatus;
+  controlIds: string[];
+  findingIds: string[];
+  dueDate?: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SaveRiskRequest {
+  clientId: string;
+  assessmentId?: string;
+  title: string;
+  description: string;
+  likelihood: number;
+  impact: number;
+  residualLikelihood?: number;
+  residualImpact?: number;
+  owner?: string;
+  status?: RiskStatus;
+  controlIds?: string[];
+  findingIds?: string[];
+  dueDate?: string;
+}
+
+export interface RiskMatrixCell {
+  likelihood: number;
+  impact: number;
+  score: number;
+  level: RiskLevel;
+  riskIds: string[];
+}
+
+export interface RiskMatrix {
+  clientId: string;
+  cells: RiskMatrixCell[];
+  totalRisks: number;
+  criticalCount: number;
+  highCount: number;
+  mediumCount: number;
+  lowCount: number;
+}
+
+// Records of processing (GDPR Article 30)
+export type LawfulBasis =
+  | "CONSENT"
+  | "CONTRACT"
+  | "LEGAL_OBLIGATION"
+  | "VITAL_INTERESTS"
+  | "PUBLIC_TASK"
+  | "LEGITIMATE_INTERESTS";
+
+export type TransferSafeguard =
+  | "ADEQUACY_DECISION"
+  | "STANDARD_CONTRACTUAL_CLAUSES"
+  | "BINDING_CORPORATE_RULES"
+  | "CODE_OF_CONDUCT"
+  | "CERTIFICATION"
+  | "DEROGATION";
+
+export interface ThirdCountryTransfer {
+  country: string;
+  recipient?: string;
+  /** Unset until a safeguard has been documented */
+  safeguard?: TransferSafeguard;
+}
+
+export interface ProcessingActivity {
+  id: string;
+  clientId: string;
+  name: string;
+  purpose: string;
+  lawfulBasis: LawfulBasis;
+  dataCategories: string[];
+  dataSubjectCategories: string[];
+  recipients: string[];
+  transfers: ThirdCountryTransfer[];
+  retentionPeriod?: string;
+  securityMeasures?: string;
+  /** GDPR control codes, e.g. "Art. 30" */
+  controlCodes: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SaveProcessingActivityRequest {
+  clientId: string;
+  name: string;
+  purpose: string;
+  /** Required */
+  lawfulBasis?: LawfulBasis;
+  dataCategories?: string[];
+  dataSubjectCategories?: string[];
+  recipients?: string[];
+  transfers?: ThirdCountryTransfer[];
+  retentionPeriod?: string;
+  securityMeasures?: string;
+  controlCodes?: string[];
+}
+
+export interface ProcessingActivityResult {
+  activity: ProcessingActivity;
+  /** Gaps that didn't block saving, e.g. a transfer with no safeguard */
+  warnings: string[];
+}
+
+// Remediation plans
+export type EffortEstimate = "SMALL" | "MEDIUM" | "LARGE";
+
+export type RemediationItemStatus = "OPEN" | "IN_PROGRESS" | "BLOCKED" | "DONE" | "ACCEPTED_RISK";
+
+export type RemediationPriority = "CRITICAL" | "HIGH" | "MEDIUM" | "LOW";
+
+/** How gaps map to effort; omitted fields use the defaults (S / M / L at priority 5) */
+export interface EffortMapping {
+  partiallyCompliant?: EffortEstimate;
+  nonCompliant?: EffortEstimate;
+  nonCompliantHighPriority?: EffortEstimate;
+  /** Control priority (1-5) treated as high */
+  highPriority?: number;
+  /** Fixed estimates by control code */
+  overrides?: Record<string, EffortEstimate>;
+}
+
+export interface RemediationItem {
+  id: string;
+  controlId: string;
+  controlCode: string;
+  controlTitle: string;
+  category: string;
+  complianceStatus: ComplianceStatus;
+  /** 1-5 */
+  riskRating?: number;
+  gap?: string;
+  recommendedAction: string;
+  effort: EffortEstimate;
+  ownerRole: string;
+  owner?: string;
+  dueDate?: string;
+  /** e.g. "Q3 2026" */
+  targetQuarter?: string;
+  status: RemediationItemStatus;
+  updatedAt: string;
+}
+
+export interface RemediationCategory {
+  category: string;
+  displayName: string;
+  /** Most urgent first */
+  items: RemediationItem[];
+}
+
+export interface RemediationPlan {
+  id: string;
+  clientId: string;
+  assessmentId: string;
+  framework: string;
+  /** Ordered by each category's most urgent item */
+  categories: RemediationCategory[];
+  generatedAt: string;
+  updatedAt: string;
+}
+
+/** A remediation item with the assessment it belongs to */
+export interface ClientRemediationItem extends RemediationItem {
+  assessmentId: string;
+  assessmentName: string;
+}
+
+/** A client's remediation items rolled up across assessments */
+export interface RemediationStats {
+  total: number;
+  /** Items neither done nor accepted */
+  outstanding: number;
+  overdue: number;
+  byStatus: Partial<Record<RemediationItemStatus, number>>;
+  /** Outstanding items only */
+  byPriority: Partial<Record<RemediationPriority, number>>;
+}
+
+export interface UpdateRemediationItemRequest {
+  owner?: string;
+  dueDate?: string;
+  status: RemediationItemStatus;
 }
 
 export interface ComplianceStatusReport {
@@ -1073,11 +3218,108 @@ export interface ComplianceStatusReport {
   nonCompliantControls: number;
   notApplicableControls: number;
   categoryBreakdown: CategoryComplianceStatus[];
+  groupBreakdown: CategoryComplianceStatus[];
+  /** Average maturity of the rated controls, if any were rated */
+  averageMaturity: number | null;
   networkHealthScore: number | null;
   totalAssets: number | null;
   lastUpdated: string;
 }
 
+export interface ComplianceSnapshot {
+  id: string;
+  clientId: string;
+  framework: string;
+  takenAt: string;
+  report: ComplianceStatusReport;
+}
+
+export interface ComplianceTrendPoint {
+  takenAt: string;
+  completionPercentage: number;
+  compliancePercentage: number;
+}
+
+export interface CategoryTrend {
+  code: string;
+  name: string;
+  color: string;
+  points: ComplianceTrendPoint[];
+}
+
+/** Compliance over time, oldest point first */
+export interface ComplianceTrend {
+  clientId: string;
+  framework: string;
+  points: ComplianceTrendPoint[];
+  categories: CategoryTrend[];
+}
+
+export type OverviewWeighting = "CONTROL_COUNT" | "CUSTOM";
+
+export interface FrameworkScore {
+  framework: string;
+  /** Weight in the overall score; 0 when nothing applicable was assessed */
+  weight: number;
+  status: ComplianceStatusReport;
+}
+
+export interface RelatedControl {
+  framework: string;
+  code: string;
+}
+
+export interface OverviewGap {
+  framework: string;
+  controlCode: string;
+  controlTitle: string;
+  riskRating: number;
+  gapDescription: string | null;
+  /** Gaps in other frameworks on controls mapped to this one */
+  relatedControls: RelatedControl[];
+}
+
+/** A client's compliance across every framework it is assessed against */
+export interface ComplianceOverview {
+  clientId: string;
+  frameworks: FrameworkScore[];
+  overallCompliancePercentage: number;
+  overallCompletionPercentage: number;
+  weighting: OverviewWeighting;
+  /** Highest risk first, de-duplicated across mapped controls */
+  highRiskGaps: OverviewGap[];
+  totalControls: number;
+  unassessedControls: number;
+  generatedAt: string;
+}
+
+export type MappingStrength = "FULL" | "PARTIAL";
+
+export interface ControlMapping {
+  sourceControl: string;
+  targetControls: string[];
+  strength: MappingStrength;
+}
+
+export interface ProjectedControl {
+  code: string;
+  title: string;
+  category: string;
+  status: ComplianceStatus;
+  sourceControls: string[];
+  strength: MappingStrength | null;
+  inheritedCompliant: boolean;
+}
+
+export interface FrameworkProjection {
+  assessmentId: string;
+  sourceFramework: string;
+  report: ComplianceStatusReport;
+  controls: ProjectedControl[];
+  inheritedCompliantControls: number;
+  unmappedControls: string[];
+}
+
 // ============================================================================
 // PDF Generation Types (Phase 3 - Executive Reporting)
 // ============================================================================
@@ -1089,6 +3331,36 @@ export interface GenerateExecutivePdfRequest {
   framework?: string;
   includeNetworkData: boolean;
   includeComplianceData: boolean;
+  groupCategories?: boolean;
+  organization?: string;
+  logoPath?: string;
+  primaryColor?: string;
+  includeToc?: boolean;
+}
+
+export interface BrandingProfile {
+  organization: string;
+  firmName: string;
+  logoPath: string | null;
+  primaryColor: string;
+  secondaryColor: string | null;
+  footerText: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SaveBrandingProfileRequest {
+  organization: string;
+  firmName: string;
+  logoPath?: string;
+  primaryColor?: string;
+  secondaryColor?: string;
+  footerText?: string;
+}
+
+export interface ExportFileResult {
+  filePath: string;
+  fileSize: number;
 }
 
 export interface PdfGenerationResult {
@@ -1099,6 +3371,44 @@ export interface PdfGenerationResult {
   message: string;
 }
 
+// ============================================================================
+// Workspace Archive Types
+// ============================================================================
+
+export interface WorkspaceExportOptions {
+  /** Leave out agent token metadata and redact tokens in script text */
+  excludeAgentTokens?: boolean;
+  /** Leave out the raw Nmap XML of scans */
+  excludeRawScanXml?: boolean;
+}
+
+export interface WorkspaceExportProgress {
+  clientId: string;
+  completed: number;
+  total: number;
+  /** Archive path of the artifact just handled; empty when it was skipped */
+  current: string;
+}
+
+export interface WorkspaceExportSummary {
+  filePath: string;
+  artifactCount: number;
+  totalBytes: number;
+  warnings: string[];
+}
+
+export interface WorkspaceArchiveValidation {
+  valid: boolean;
+  clientId: string;
+  clientName: string;
+  exportedAt: string;
+  artifactCount: number;
+  verified: number;
+  mismatched: string[];
+  missing: string[];
+  unlisted: string[];
+}
+
 // ============================================================================
 // Error Types
 // ============================================================================
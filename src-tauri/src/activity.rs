@@ -0,0 +1,369 @@
+//! Engagement Activity Log
+//!
+//! Append-only timeline of what happened during an engagement: assessments
+//! created, controls assessed, scans queued, scripts and reports generated,
+//! evidence collected. Events are typed and carry a JSON detail blob so they
+//! can be rendered (e.g. in an engagement summary) rather than only listed.
+//!
+//! Logging is best-effort, like onboarding progress: a failure to append an
+//! event is logged and never fails the command that triggered it.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Initialize activity log schema
+pub fn init_activity_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Engagement activity timeline
+        CREATE TABLE IF NOT EXISTS activity_log (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            event_type TEXT NOT NULL,
+            actor TEXT NOT NULL,
+            entity_type TEXT NOT NULL,
+            entity_id TEXT,
+            summary TEXT NOT NULL,
+            detail TEXT NOT NULL DEFAULT '{}',
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_activity_log_client ON activity_log(client_id, created_at);
+        CREATE INDEX IF NOT EXISTS idx_activity_log_created ON activity_log(created_at);
+    "#)?;
+
+    tracing::info!("Activity log schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Kind of engagement activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityEventType {
+    AssessmentCreated,
+    AssessmentImported,
+    ControlAssessed,
+    EvidenceAdded,
+    EvidenceRemoved,
+    ScanCreated,
+    ScanImported,
+    ScriptGenerated,
+    ReportGenerated,
+    PolicyGenerated,
+}
+
+impl ActivityEventType {
+    pub const ALL: [ActivityEventType; 10] = [
+        ActivityEventType::AssessmentCreated,
+        ActivityEventType::AssessmentImported,
+        ActivityEventType::ControlAssessed,
+        ActivityEventType::EvidenceAdded,
+        ActivityEventType::EvidenceRemoved,
+        ActivityEventType::ScanCreated,
+        ActivityEventType::ScanImported,
+        ActivityEventType::ScriptGenerated,
+        ActivityEventType::ReportGenerated,
+        ActivityEventType::PolicyGenerated,
+    ];
+
+    /// Module whose commands record this kind of event
+    pub fn module(&self) -> ActivityModule {
+        match self {
+            ActivityEventType::AssessmentCreated
+            | ActivityEventType::AssessmentImported
+            | ActivityEventType::ControlAssessed
+            | ActivityEventType::EvidenceAdded
+            | ActivityEventType::EvidenceRemoved
+            | ActivityEventType::PolicyGenerated => ActivityModule::Grc,
+            ActivityEventType::ScanCreated | ActivityEventType::ScanImported => ActivityModule::Network,
+            ActivityEventType::ScriptGenerated => ActivityModule::Factory,
+            ActivityEventType::ReportGenerated => ActivityModule::Reporting,
+        }
+    }
+}
+
+/// Application module an event came from, for filtering a timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityModule {
+    Factory,
+    Network,
+    Grc,
+    Reporting,
+}
+
+/// A single entry in a client's engagement timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEvent {
+    /// Unique event identifier
+    pub id: String,
+    /// Client the activity belongs to
+    pub client_id: String,
+    /// What happened
+    pub event_type: ActivityEventType,
+    /// Who performed it (assessor name, or the OS user)
+    pub actor: String,
+    /// Kind of entity affected (assessment, scan, report, ...)
+    pub entity_type: String,
+    /// Affected entity
+    pub entity_id: Option<String>,
+    /// One-line human-readable summary
+    pub summary: String,
+    /// Structured detail for rendering the event
+    pub detail: serde_json::Value,
+    /// When the event was recorded
+    pub created_at: String,
+}
+
+impl ActivityEvent {
+    /// New event attributed to the current OS user, with empty detail
+    pub fn new(
+        client_id: impl Into<String>,
+        event_type: ActivityEventType,
+        entity_type: &str,
+        entity_id: Option<&str>,
+        summary: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            client_id: client_id.into(),
+            event_type,
+            actor: whoami::username(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.map(str::to_string),
+            summary: summary.into(),
+            detail: serde_json::json!({}),
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Attribute the event to a named person instead of the OS user
+    pub fn by(mut self, actor: &str) -> Self {
+        if !actor.trim().is_empty() {
+            self.actor = actor.to_string();
+        }
+        self
+    }
+
+    pub fn with_detail(mut self, detail: serde_json::Value) -> Self {
+        self.detail = detail;
+        self
+    }
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Activity log repository
+pub struct ActivityRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ActivityRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ActivityRepository { db }
+    }
+
+    pub fn create(&self, event: &ActivityEvent) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT INTO activity_log
+               (id, client_id, event_type, actor, entity_type, entity_id, summary, detail, created_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"#,
+            params![
+                event.id,
+                event.client_id,
+                format!("{:?}", event.event_type),
+                event.actor,
+                event.entity_type,
+                event.entity_id,
+                event.summary,
+                event.detail.to_string(),
+                event.created_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// A client's timeline, newest first, optionally limited to one module
+    pub fn list_by_client(
+        &self,
+        client_id: &str,
+        module: Option<ActivityModule>,
+        limit: usize,
+        offset: usize,
+    ) -> OptioResult<Vec<ActivityEvent>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        // Event type names are fixed identifiers, safe to inline
+        let module_filter = module
+            .map(|module| {
+                let types = ActivityEventType::ALL
+                    .iter()
+                    .filter(|t| t.module() == module)
+                    .map(|t| format!("'{:?}'", t))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(" AND event_type IN ({})", types)
+            })
+            .unwrap_or_default();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM activity_log WHERE client_id = ?1{}
+             ORDER BY created_at DESC, rowid DESC LIMIT ?2 OFFSET ?3",
+            ACTIVITY_COLUMNS, module_filter
+        ))?;
+
+        let events = stmt.query_map(params![client_id, limit as i64, offset as i64], |row| Ok(parse_activity_row(row)))?
+        .filter_map(|r| r.ok())
+        .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(events)
+    }
+
+    /// Most recent activity across all clients, newest first
+    pub fn list_recent(&self, limit: usize) -> OptioResult<Vec<ActivityEvent>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM activity_log ORDER BY created_at DESC, rowid DESC LIMIT ?1",
+            ACTIVITY_COLUMNS
+        ))?;
+
+        let events = stmt.query_map(params![limit as i64], |row| Ok(parse_activity_row(row)))?
+        .filter_map(|r| r.ok())
+        .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(events)
+    }
+
+    /// Delete a client's events recorded before `before`, returning how many
+    /// were removed
+    pub fn purge_before(&self, client_id: &str, before: DateTime<Utc>) -> OptioResult<usize> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let purged = conn.execute(
+            "DELETE FROM activity_log WHERE client_id = ?1 AND created_at < ?2",
+            params![client_id, before.to_rfc3339()],
+        )?;
+
+        Ok(purged)
+    }
+}
+
+const ACTIVITY_COLUMNS: &str = "id, client_id, event_type, actor, entity_type, entity_id, summary, detail, created_at";
+
+fn parse_activity_row(row: &rusqlite::Row) -> OptioResult<ActivityEvent> {
+    let event_type_str: String = row.get(2)?;
+    let detail_json: String = row.get(7)?;
+
+    Ok(ActivityEvent {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        event_type: parse_event_type(&event_type_str)?,
+        actor: row.get(3)?,
+        entity_type: row.get(4)?,
+        entity_id: row.get(5)?,
+        summary: row.get(6)?,
+        detail: serde_json::from_str(&detail_json)?,
+        created_at: row.get(8)?,
+    })
+}
+
+fn parse_event_type(s: &str) -> OptioResult<ActivityEventType> {
+    match s {
+        "AssessmentCreated" => Ok(ActivityEventType::AssessmentCreated),
+        "AssessmentImported" => Ok(ActivityEventType::AssessmentImported),
+        "ControlAssessed" => Ok(ActivityEventType::ControlAssessed),
+        "EvidenceAdded" => Ok(ActivityEventType::EvidenceAdded),
+        "EvidenceRemoved" => Ok(ActivityEventType::EvidenceRemoved),
+        "ScanCreated" => Ok(ActivityEventType::ScanCreated),
+        "ScanImported" => Ok(ActivityEventType::ScanImported),
+        "ScriptGenerated" => Ok(ActivityEventType::ScriptGenerated),
+        "ReportGenerated" => Ok(ActivityEventType::ReportGenerated),
+        "PolicyGenerated" => Ok(ActivityEventType::PolicyGenerated),
+        _ => Err(OptioError::Database(format!("Unknown activity event type: {}", s))),
+    }
+}
+
+// ============================================================================
+// Logging
+// ============================================================================
+
+/// Append an event to the activity log
+///
+/// Best-effort: failures are logged and never fail the calling command.
+pub fn log(db: &Database, event: ActivityEvent) {
+    if let Err(e) = ActivityRepository::new(db).create(&event) {
+        tracing::warn!("Failed to record {:?} activity: {}", event.event_type, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        init_activity_schema(&db).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_timeline_round_trip() {
+        let db = test_db();
+        for i in 0..5 {
+            log(&db, ActivityEvent::new("c1", ActivityEventType::ScanCreated, "scan", Some(&format!("s{}", i)), format!("Queued scan {}", i))
+                .with_detail(serde_json::json!({ "targets": ["10.0.0.0/24"] })));
+        }
+        log(&db, ActivityEvent::new("c2", ActivityEventType::ReportGenerated, "report", Some("r1"), "Generated report").by("Jane Auditor"));
+
+        let repo = ActivityRepository::new(&db);
+        let page = repo.list_by_client("c1", None, 2, 1).unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].entity_id.as_deref(), Some("s3"));
+        assert_eq!(page[0].detail["targets"][0], "10.0.0.0/24");
+
+        let recent = repo.list_recent(1).unwrap();
+        assert_eq!(recent[0].event_type, ActivityEventType::ReportGenerated);
+        assert_eq!(recent[0].actor, "Jane Auditor");
+    }
+
+    #[test]
+    fn test_module_filter_and_purge() {
+        let db = test_db();
+        let mut old = ActivityEvent::new("c1", ActivityEventType::ScriptGenerated, "script", None, "Generated script");
+        old.created_at = "2024-01-01T00:00:00+00:00".to_string();
+        log(&db, old);
+        log(&db, ActivityEvent::new("c1", ActivityEventType::ControlAssessed, "assessment", Some("a1"), "Assessed GV.OC-01"));
+        log(&db, ActivityEvent::new("c1", ActivityEventType::EvidenceAdded, "evidence", Some("e1"), "Added evidence"));
+        log(&db, ActivityEvent::new("c2", ActivityEventType::ScriptGenerated, "script", None, "Generated script"));
+
+        let repo = ActivityRepository::new(&db);
+        let grc = repo.list_by_client("c1", Some(ActivityModule::Grc), 50, 0).unwrap();
+        assert_eq!(grc.len(), 2);
+        assert!(grc.iter().all(|e| e.event_type.module() == ActivityModule::Grc));
+        assert_eq!(repo.list_by_client("c1", Some(ActivityModule::Factory), 50, 0).unwrap().len(), 1);
+
+        let cutoff = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(repo.purge_before("c1", cutoff).unwrap(), 1);
+        assert_eq!(repo.list_by_client("c1", None, 50, 0).unwrap().len(), 2);
+        assert_eq!(repo.list_by_client("c2", None, 50, 0).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,501 @@
+//! Operations Audit Trail
+//!
+//! Record of the potentially sensitive operations run from this machine:
+//! scans launched, scripts generated, agent tokens issued and reports
+//! exported. Unlike the engagement timeline in [`crate::activity`], this is
+//! the consultant's own evidence of what was run against a client, so it is
+//! written before or with the operation and a failure to write it fails the
+//! operation.
+//!
+//! Rows can't be updated or deleted, and each stores the SHA-256 of the
+//! previous row's hash and its own content, so an edited, removed or
+//! reordered row breaks the chain from that point on.
+
+use crate::db::{add_missing_columns, Database};
+use crate::error::{OptioError, OptioResult};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// `prev_hash` of the first row
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Hash-chain the audit log and make it append-only
+///
+/// The `audit_log` table itself is created with the core schema; this adds
+/// the chain columns to it.
+pub fn init_audit_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    add_missing_columns(&conn, "audit_log", &[
+        ("sequence", "INTEGER"),
+        ("actor", "TEXT"),
+        ("prev_hash", "TEXT"),
+        ("hash", "TEXT"),
+    ])?;
+
+    conn.execute_batch(r#"
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_audit_log_sequence ON audit_log(sequence);
+
+        CREATE TRIGGER IF NOT EXISTS audit_log_no_update BEFORE UPDATE ON audit_log
+        BEGIN
+            SELECT RAISE(ABORT, 'The audit log is append-only');
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS audit_log_no_delete BEFORE DELETE ON audit_log
+        BEGIN
+            SELECT RAISE(ABORT, 'The audit log is append-only');
+        END;
+    "#)?;
+
+    tracing::info!("Audit log schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Kind of audited operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    ScanLaunched,
+    ScriptGenerated,
+    AgentTokenIssued,
+    ReportExported,
+}
+
+impl AuditOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOperation::ScanLaunched => "scan_launched",
+            AuditOperation::ScriptGenerated => "script_generated",
+            AuditOperation::AgentTokenIssued => "agent_token_issued",
+            AuditOperation::ReportExported => "report_exported",
+        }
+    }
+}
+
+fn parse_operation(s: &str) -> OptioResult<AuditOperation> {
+    match s {
+        "scan_launched" => Ok(AuditOperation::ScanLaunched),
+        "script_generated" => Ok(AuditOperation::ScriptGenerated),
+        "agent_token_issued" => Ok(AuditOperation::AgentTokenIssued),
+        "report_exported" => Ok(AuditOperation::ReportExported),
+        _ => Err(OptioError::Database(format!("Unknown audit operation: {}", s))),
+    }
+}
+
+/// An operation about to be recorded
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub operation: AuditOperation,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub detail: serde_json::Value,
+}
+
+impl AuditEntry {
+    pub fn new(operation: AuditOperation, entity_type: &str, entity_id: Option<&str>) -> Self {
+        Self {
+            operation,
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.map(str::to_string),
+            detail: serde_json::json!({}),
+        }
+    }
+
+    pub fn with_detail(mut self, detail: serde_json::Value) -> Self {
+        self.detail = detail;
+        self
+    }
+}
+
+/// A recorded operation and its place in the chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// Position in the chain, from 1
+    pub sequence: i64,
+    pub id: String,
+    pub timestamp: String,
+    /// OS user the operation ran as
+    pub actor: String,
+    pub operation: AuditOperation,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub detail: serde_json::Value,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// Outcome of checking the chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditChainVerification {
+    pub valid: bool,
+    pub records_checked: usize,
+    /// First row that doesn't fit the chain
+    pub broken_at_sequence: Option<i64>,
+    pub problem: Option<String>,
+    /// Hash of the last row checked
+    pub head_hash: String,
+}
+
+/// Format for exporting the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditExportFormat {
+    Csv,
+    /// One JSON record per line
+    Jsonl,
+}
+
+impl std::str::FromStr for AuditExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(AuditExportFormat::Csv),
+            "jsonl" => Ok(AuditExportFormat::Jsonl),
+            _ => Err(format!("Unknown audit log format: {}", s)),
+        }
+    }
+}
+
+// ============================================================================
+// Hash Chain
+// ============================================================================
+
+/// A row as stored, with its detail exactly as written so it hashes the same
+struct StoredRow {
+    sequence: i64,
+    id: String,
+    timestamp: String,
+    actor: String,
+    operation: String,
+    entity_type: String,
+    entity_id: Option<String>,
+    detail: String,
+    prev_hash: String,
+    hash: String,
+}
+
+impl StoredRow {
+    /// SHA-256 over the previous hash and the row's fields, encoded as a
+    /// JSON array so no two rows hash the same input
+    fn content_hash(&self) -> String {
+        let content = serde_json::json!([
+            self.prev_hash,
+            self.sequence,
+            self.id,
+            self.timestamp,
+            self.actor,
+            self.operation,
+            self.entity_type,
+            self.entity_id,
+            self.detail,
+        ]);
+        Sha256::digest(content.to_string().as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn into_record(self) -> OptioResult<AuditRecord> {
+        Ok(AuditRecord {
+            sequence: self.sequence,
+            id: self.id,
+            timestamp: self.timestamp,
+            actor: self.actor,
+            operation: parse_operation(&self.operation)?,
+            entity_type: self.entity_type,
+            entity_id: self.entity_id,
+            detail: serde_json::from_str(&self.detail)?,
+            prev_hash: self.prev_hash,
+            hash: self.hash,
+        })
+    }
+}
+
+const AUDIT_COLUMNS: &str = "sequence, id, timestamp, actor, action, entity_type, entity_id, details, prev_hash, hash";
+
+fn parse_stored_row(row: &rusqlite::Row) -> rusqlite::Result<StoredRow> {
+    Ok(StoredRow {
+        sequence: row.get(0)?,
+        id: row.get(1)?,
+        timestamp: row.get(2)?,
+        actor: row.get(3)?,
+        operation: row.get(4)?,
+        entity_type: row.get(5)?,
+        entity_id: row.get(6)?,
+        detail: row.get::<_, Option<String>>(7)?.unwrap_or_else(|| "{}".to_string()),
+        prev_hash: row.get(8)?,
+        hash: row.get(9)?,
+    })
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Audit log repository
+pub struct AuditLogRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AuditLogRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        AuditLogRepository { db }
+    }
+
+    /// Append an entry to the chain, attributed to the current OS user
+    pub fn append(&self, entry: &AuditEntry) -> OptioResult<AuditRecord> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.unchecked_transaction()?;
+
+        let (last_sequence, prev_hash) = tx
+            .query_row(
+                "SELECT sequence, hash FROM audit_log WHERE sequence IS NOT NULL ORDER BY sequence DESC LIMIT 1",
+                [],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?
+            .unwrap_or((0, GENESIS_HASH.to_string()));
+
+        let mut row = StoredRow {
+            sequence: last_sequence + 1,
+            id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            actor: whoami::username(),
+            operation: entry.operation.as_str().to_string(),
+            entity_type: entry.entity_type.clone(),
+            entity_id: entry.entity_id.clone(),
+            detail: entry.detail.to_string(),
+            prev_hash,
+            hash: String::new(),
+        };
+        row.hash = row.content_hash();
+
+        tx.execute(
+            &format!("INSERT INTO audit_log ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)", AUDIT_COLUMNS),
+            params![
+                row.sequence,
+                row.id,
+                row.timestamp,
+                row.actor,
+                row.operation,
+                row.entity_type,
+                row.entity_id,
+                row.detail,
+                row.prev_hash,
+                row.hash,
+            ],
+        )?;
+        tx.commit()?;
+
+        row.into_record()
+    }
+
+    /// Every chained row in order; rows from before the chain have no sequence
+    fn stored_rows(&self) -> OptioResult<Vec<StoredRow>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM audit_log WHERE sequence IS NOT NULL ORDER BY sequence",
+            AUDIT_COLUMNS
+        ))?;
+        let rows = stmt.query_map([], parse_stored_row)?.collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Records timestamped between `from` and `to`, both inclusive, in order
+    pub fn list_between(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> OptioResult<Vec<AuditRecord>> {
+        self.stored_rows()?
+            .into_iter()
+            .filter(|row| {
+                let at = DateTime::parse_from_rfc3339(&row.timestamp).map(|t| t.with_timezone(&Utc)).ok();
+                at.is_some_and(|at| from.map_or(true, |from| at >= from) && to.map_or(true, |to| at <= to))
+            })
+            .map(StoredRow::into_record)
+            .collect()
+    }
+
+    /// Walk the chain from the first row, stopping at the first that doesn't fit
+    pub fn verify(&self) -> OptioResult<AuditChainVerification> {
+        let rows = self.stored_rows()?;
+        let mut head_hash = GENESIS_HASH.to_string();
+
+        for (checked, row) in rows.iter().enumerate() {
+            let expected_sequence = checked as i64 + 1;
+            let problem = if row.sequence != expected_sequence {
+                Some(format!("Expected record {} but found {}; records are missing", expected_sequence, row.sequence))
+            } else if row.prev_hash != head_hash {
+                Some("Previous hash doesn't match the record before it".to_string())
+            } else if row.content_hash() != row.hash {
+                Some("Record content doesn't match its hash".to_string())
+            } else {
+                None
+            };
+
+            if let Some(problem) = problem {
+                tracing::warn!("Audit chain broken at record {}: {}", row.sequence, problem);
+                return Ok(AuditChainVerification {
+                    valid: false,
+                    records_checked: checked,
+                    broken_at_sequence: Some(row.sequence),
+                    problem: Some(problem),
+                    head_hash,
+                });
+            }
+            head_hash = row.hash.clone();
+        }
+
+        Ok(AuditChainVerification {
+            valid: true,
+            records_checked: rows.len(),
+            broken_at_sequence: None,
+            problem: None,
+            head_hash,
+        })
+    }
+}
+
+// ============================================================================
+// Recording and Export
+// ============================================================================
+
+/// Record an operation, failing if it can't be recorded
+///
+/// Call it before the operation takes effect where possible, and don't go
+/// ahead when it fails.
+pub fn record(db: &Database, entry: AuditEntry) -> OptioResult<AuditRecord> {
+    AuditLogRepository::new(db).append(&entry).map_err(|e| {
+        tracing::error!("Failed to record {:?} in the audit log: {}", entry.operation, e);
+        e
+    })
+}
+
+/// Parse an export bound: an RFC 3339 timestamp, or a date meaning the start
+/// of that day for `from` and its end for `to`
+pub fn parse_export_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value.trim()) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {} (expected YYYY-MM-DD or an RFC 3339 timestamp)", value))?;
+    let start = date
+        .and_hms_opt(0, 0, 0)
+        .map(|t| Utc.from_utc_datetime(&t))
+        .ok_or_else(|| format!("Invalid date: {}", value))?;
+    Ok(if end_of_day { start + Duration::days(1) - Duration::nanoseconds(1) } else { start })
+}
+
+/// Export records with their hashes, so the export can be checked too
+pub fn export_records(records: &[AuditRecord], format: AuditExportFormat) -> Result<String, String> {
+    match format {
+        AuditExportFormat::Jsonl => records
+            .iter()
+            .map(|record| serde_json::to_string(record).map(|line| line + "\n").map_err(|e| e.to_string()))
+            .collect(),
+        AuditExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record([
+                "Sequence", "Timestamp", "Actor", "Operation", "Entity Type", "Entity ID", "Detail", "Previous Hash", "Hash",
+            ]).map_err(|e| e.to_string())?;
+
+            for record in records {
+                writer.write_record([
+                    record.sequence.to_string().as_str(),
+                    &record.timestamp,
+                    &record.actor,
+                    record.operation.as_str(),
+                    &record.entity_type,
+                    record.entity_id.as_deref().unwrap_or(""),
+                    &record.detail.to_string(),
+                    &record.prev_hash,
+                    &record.hash,
+                ]).map_err(|e| e.to_string())?;
+            }
+
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_audit_schema(&db).unwrap();
+        db
+    }
+
+    fn record_scans(db: &Database, count: usize) {
+        for i in 0..count {
+            record(db, AuditEntry::new(AuditOperation::ScanLaunched, "scan", Some(&format!("s{}", i)))
+                .with_detail(serde_json::json!({ "targets": ["10.0.0.0/24"], "nmapArgs": ["-sS", "-T3"] })))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_chain_verifies_and_exports() {
+        let db = test_db();
+        record_scans(&db, 3);
+        record(&db, AuditEntry::new(AuditOperation::ReportExported, "report", Some("r1"))).unwrap();
+
+        let repo = AuditLogRepository::new(&db);
+        let verification = repo.verify().unwrap();
+        assert!(verification.valid);
+        assert_eq!(verification.records_checked, 4);
+
+        let records = repo.list_between(None, None).unwrap();
+        assert_eq!(records[0].prev_hash, GENESIS_HASH);
+        assert_eq!(records[1].prev_hash, records[0].hash);
+        assert_eq!(verification.head_hash, records[3].hash);
+
+        let jsonl = export_records(&records, AuditExportFormat::Jsonl).unwrap();
+        assert_eq!(jsonl.lines().count(), 4);
+        assert!(jsonl.lines().next().unwrap().contains("\"operation\":\"scan_launched\""));
+        let csv = export_records(&records, AuditExportFormat::Csv).unwrap();
+        assert!(csv.starts_with("Sequence,Timestamp,Actor,Operation"));
+
+        let future = parse_export_bound("2999-01-01", false).unwrap();
+        assert!(repo.list_between(Some(future), None).unwrap().is_empty());
+        assert_eq!(repo.list_between(None, Some(future)).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_rows_cannot_be_changed_and_tampering_is_detected() {
+        let db = test_db();
+        record_scans(&db, 3);
+
+        {
+            let conn = db.conn.lock().unwrap();
+            assert!(conn.execute("UPDATE audit_log SET actor = 'someone' WHERE sequence = 2", []).is_err());
+            assert!(conn.execute("DELETE FROM audit_log WHERE sequence = 2", []).is_err());
+
+            // Bypass the triggers the way someone editing the file would
+            conn.execute_batch(
+                "DROP TRIGGER audit_log_no_update;
+                 UPDATE audit_log SET details = '{\"targets\":[\"10.9.9.0/24\"]}' WHERE sequence = 2;",
+            ).unwrap();
+        }
+
+        let verification = AuditLogRepository::new(&db).verify().unwrap();
+        assert!(!verification.valid);
+        assert_eq!(verification.broken_at_sequence, Some(2));
+        assert_eq!(verification.records_checked, 1);
+    }
+}
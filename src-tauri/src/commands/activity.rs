@@ -0,0 +1,58 @@
+//! Activity Commands
+//!
+//! Engagement timeline, recent activity across clients, and retention
+//! cleanup.
+
+use crate::activity::{ActivityEvent, ActivityModule, ActivityRepository};
+use crate::db::Database;
+use chrono::Utc;
+use tauri::State;
+
+/// Default page size for activity queries
+const DEFAULT_ACTIVITY_LIMIT: usize = 50;
+
+/// Get a client's engagement timeline, newest first, optionally for one
+/// module
+#[tauri::command]
+pub async fn get_client_timeline(
+    db: State<'_, Database>,
+    client_id: String,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    module_filter: Option<ActivityModule>,
+) -> Result<Vec<ActivityEvent>, String> {
+    ActivityRepository::new(&db)
+        .list_by_client(&client_id, module_filter, limit.unwrap_or(DEFAULT_ACTIVITY_LIMIT), offset.unwrap_or(0))
+        .map_err(|e| e.to_string())
+}
+
+/// Get the most recent activity across all clients
+#[tauri::command]
+pub async fn get_recent_activity(
+    db: State<'_, Database>,
+    limit: Option<usize>,
+) -> Result<Vec<ActivityEvent>, String> {
+    ActivityRepository::new(&db)
+        .list_recent(limit.unwrap_or(DEFAULT_ACTIVITY_LIMIT))
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a client's activity recorded before a date, e.g. before
+/// off-boarding, returning the number of events removed
+#[tauri::command]
+pub async fn purge_activity_log(
+    db: State<'_, Database>,
+    client_id: String,
+    before_date: String,
+) -> Result<usize, String> {
+    let before = chrono::DateTime::parse_from_rfc3339(&before_date)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    let purged = ActivityRepository::new(&db)
+        .purge_before(&client_id, before)
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!("Purged {} activity events for client {}", purged, client_id);
+    Ok(purged)
+}
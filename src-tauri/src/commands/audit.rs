@@ -0,0 +1,39 @@
+//! Audit Log Commands
+//!
+//! Export and verification of the hash-chained audit trail of scans,
+//! generated scripts, agent tokens and report exports.
+
+use crate::audit::{
+    export_records, parse_export_bound, AuditChainVerification, AuditExportFormat, AuditLogRepository,
+};
+use crate::db::Database;
+use tauri::State;
+
+/// Export audit records between two dates, as CSV or JSON Lines
+///
+/// `from` and `to` are inclusive and take a date (`YYYY-MM-DD`) or an
+/// RFC 3339 timestamp; either can be left out. Records keep their hashes so
+/// the export can be checked against the chain.
+#[tauri::command]
+pub async fn export_audit_log(
+    db: State<'_, Database>,
+    from: Option<String>,
+    to: Option<String>,
+    format: String,
+) -> Result<String, String> {
+    let format: AuditExportFormat = format.parse()?;
+    let from = from.as_deref().map(|f| parse_export_bound(f, false)).transpose()?;
+    let to = to.as_deref().map(|t| parse_export_bound(t, true)).transpose()?;
+
+    let records = AuditLogRepository::new(&db)
+        .list_between(from, to)
+        .map_err(|e| e.to_string())?;
+    tracing::info!("Exported {} audit records", records.len());
+    export_records(&records, format)
+}
+
+/// Check the audit chain for edited, removed or reordered records
+#[tauri::command]
+pub async fn verify_audit_chain(db: State<'_, Database>) -> Result<AuditChainVerification, String> {
+    AuditLogRepository::new(&db).verify().map_err(|e| e.to_string())
+}
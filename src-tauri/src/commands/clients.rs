@@ -2,10 +2,17 @@
 //!
 //! CRUD operations for client profiles stored in the local database.
 
-use crate::db::{Client, ClientRepository, Database};
+use crate::commands::grc::parse_framework_param;
+use crate::commands::reporting::ReportingState;
+use crate::db::{Client, ClientContact, ClientDependencies, ClientOverviewCounts, ClientRepository, CompanySize, Database, IndustrySector};
+use crate::deletion::{self, ClientDeletionPreview};
 use crate::error::OptioError;
+use crate::grc::models::Framework;
+use crate::onboarding::{self, Milestone};
+use crate::workspace::{export_workspace, WorkspaceExportOptions, WorkspaceExportSummary};
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
 
 /// Request to create a new client
 #[derive(Debug, Deserialize)]
@@ -15,6 +22,51 @@ pub struct CreateClientRequest {
     pub target_subnet: Option<String>,
     pub contact_email: Option<String>,
     pub notes: Option<String>,
+    #[serde(flatten)]
+    pub profile: ClientProfileRequest,
+}
+
+/// Engagement context shared by the create and update requests
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientProfileRequest {
+    pub contacts: Option<Vec<ClientContact>>,
+    pub industry: Option<String>,
+    pub company_size: Option<String>,
+    /// RFC 3339
+    pub engagement_start: Option<String>,
+    /// RFC 3339
+    pub engagement_end: Option<String>,
+    pub default_framework: Option<String>,
+}
+
+impl ClientProfileRequest {
+    /// Validate the profile and copy it onto `client`
+    fn apply_to(self, client: &mut Client) -> Result<(), String> {
+        let contacts = self.contacts.unwrap_or_default();
+        if contacts.iter().any(|c| c.name.trim().is_empty()) {
+            return Err("Every contact needs a name".to_string());
+        }
+        if contacts.iter().filter(|c| c.primary).count() > 1 {
+            return Err("Only one contact can be the primary contact".to_string());
+        }
+
+        let engagement_start = self.engagement_start.as_deref().map(parse_date_param).transpose()?;
+        let engagement_end = self.engagement_end.as_deref().map(parse_date_param).transpose()?;
+        if let (Some(start), Some(end)) = (engagement_start, engagement_end) {
+            if end < start {
+                return Err("Engagement end date is before its start date".to_string());
+            }
+        }
+
+        client.contacts = contacts;
+        client.industry = self.industry.as_deref().map(parse_industry_param).transpose()?;
+        client.company_size = self.company_size.as_deref().map(parse_company_size_param).transpose()?;
+        client.engagement_start = engagement_start;
+        client.engagement_end = engagement_end;
+        client.default_framework = self.default_framework.as_deref().map(parse_framework_param).transpose()?;
+        Ok(())
+    }
 }
 
 /// Client response for the frontend
@@ -28,10 +80,19 @@ pub struct ClientResponse {
     pub notes: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub contacts: Vec<ClientContact>,
+    pub industry: Option<IndustrySector>,
+    pub company_size: Option<CompanySize>,
+    pub engagement_start: Option<String>,
+    pub engagement_end: Option<String>,
+    pub default_framework: Option<Framework>,
+    /// Framework new assessments default to, from the preference or industry
+    pub preferred_framework: Option<Framework>,
 }
 
 impl From<Client> for ClientResponse {
     fn from(client: Client) -> Self {
+        let preferred_framework = client.preferred_framework();
         ClientResponse {
             id: client.id,
             name: client.name,
@@ -40,6 +101,13 @@ impl From<Client> for ClientResponse {
             notes: client.notes,
             created_at: client.created_at.to_rfc3339(),
             updated_at: client.updated_at.to_rfc3339(),
+            contacts: client.contacts,
+            industry: client.industry,
+            company_size: client.company_size,
+            engagement_start: client.engagement_start.map(|d| d.to_rfc3339()),
+            engagement_end: client.engagement_end.map(|d| d.to_rfc3339()),
+            default_framework: client.default_framework,
+            preferred_framework,
         }
     }
 }
@@ -52,15 +120,17 @@ pub async fn create_client(
 ) -> Result<ClientResponse, String> {
     tracing::info!("Creating client: {}", request.name);
 
-    let client = Client::new(
+    let mut client = Client::new(
         request.name,
         request.target_subnet,
         request.contact_email,
         request.notes,
     );
+    request.profile.apply_to(&mut client)?;
 
     let repo = ClientRepository::new(&db);
     repo.create(&client).map_err(|e| e.to_string())?;
+    onboarding::advance(&db, &[Milestone::FirstClient]);
 
     Ok(ClientResponse::from(client))
 }
@@ -99,6 +169,8 @@ pub struct UpdateClientRequest {
     pub target_subnet: Option<String>,
     pub contact_email: Option<String>,
     pub notes: Option<String>,
+    #[serde(flatten)]
+    pub profile: ClientProfileRequest,
 }
 
 /// Update an existing client
@@ -117,26 +189,147 @@ pub async fn update_client(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Client not found: {}", request.id))?;
 
-    let updated = Client {
+    let mut updated = Client {
         id: request.id,
         name: request.name,
         target_subnet: request.target_subnet,
         contact_email: request.contact_email,
         notes: request.notes,
-        created_at: existing.created_at,
         updated_at: chrono::Utc::now(),
+        ..existing
     };
+    request.profile.apply_to(&mut updated)?;
 
     repo.update(&updated).map_err(|e| e.to_string())?;
 
     Ok(ClientResponse::from(updated))
 }
 
-/// Delete a client
+/// Count the assessments, assets, scans, risks, reports and generated
+/// scripts that reference a client, for confirming a delete
 #[tauri::command]
-pub async fn delete_client(db: State<'_, Database>, id: String) -> Result<bool, String> {
-    tracing::info!("Deleting client: {}", id);
+pub async fn get_client_dependencies(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<ClientDependencies, String> {
+    ClientRepository::new(&db).dependencies(&id).map_err(|e| e.to_string())
+}
+
+/// A client with its dashboard counts
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientOverview {
+    pub client: ClientResponse,
+    #[serde(flatten)]
+    pub counts: ClientOverviewCounts,
+}
 
+/// Get a client with counts of its assessments, assets, reports and open risks
+#[tauri::command]
+pub async fn get_client_overview(db: State<'_, Database>, client_id: String) -> Result<ClientOverview, String> {
     let repo = ClientRepository::new(&db);
-    repo.delete(&id).map_err(|e| e.to_string())
+    let client = repo
+        .get(&client_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Client not found: {}", client_id))?;
+    let counts = repo.overview(&client_id).map_err(|e| e.to_string())?;
+
+    Ok(ClientOverview { client: ClientResponse::from(client), counts })
+}
+
+/// Count everything deleting a client removes, by entity type, for the
+/// confirmation dialog
+#[tauri::command]
+pub async fn preview_client_deletion(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<ClientDeletionPreview, String> {
+    deletion::preview_client_deletion(&db, &id).map_err(|e| e.to_string())
+}
+
+/// Outcome of deleting a client
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDeletionSummary {
+    pub deleted: bool,
+    /// Workspace archive written before deleting, if one was asked for
+    pub archive: Option<WorkspaceExportSummary>,
+    /// Evidence and report files removed from the app data dir
+    pub files_removed: usize,
+    /// Files that could not be removed
+    pub warnings: Vec<String>,
+}
+
+/// Delete a client with all of its assessments, evidence, assets, scans,
+/// reports, network findings and remediation items
+///
+/// With `archive`, the client's workspace is first exported to
+/// `archive_path`; nothing is deleted if that fails.
+#[tauri::command]
+pub async fn delete_client(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    reporting: State<'_, ReportingState>,
+    id: String,
+    archive: Option<bool>,
+    archive_path: Option<String>,
+) -> Result<ClientDeletionSummary, String> {
+    tracing::info!("Deleting client: {}", id);
+
+    let archive = if archive.unwrap_or(false) {
+        let path = archive_path
+            .filter(|p| !p.trim().is_empty())
+            .ok_or_else(|| "Choose where to save the archive before deleting".to_string())?;
+        let summary = export_workspace(&db, &id, Path::new(&path), &WorkspaceExportOptions::default(), |_| {})
+            .map_err(|e| format!("Failed to archive client before deleting: {}", e))?;
+        Some(summary)
+    } else {
+        None
+    };
+
+    let files = match deletion::delete_client_data(&db, &id).map_err(|e| e.to_string())? {
+        Some(files) => files,
+        None => return Ok(ClientDeletionSummary { deleted: false, archive, files_removed: 0, warnings: vec![] }),
+    };
+    reporting.reports.lock().map_err(|e| e.to_string())?.retain(|r| r.client_id != id);
+
+    let app_data_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let (files_removed, warnings) = deletion::remove_app_data_files(&app_data_dir, &files);
+    tracing::info!("Deleted client {} and {} files", id, files_removed);
+
+    Ok(ClientDeletionSummary { deleted: true, archive, files_removed, warnings })
+}
+
+fn parse_industry_param(s: &str) -> Result<IndustrySector, String> {
+    match s.to_uppercase().replace('-', "_").as_str() {
+        "FINANCE" | "FINANCIAL_SERVICES" => Ok(IndustrySector::Finance),
+        "HEALTHCARE" => Ok(IndustrySector::Healthcare),
+        "GOVERNMENT" => Ok(IndustrySector::Government),
+        "EDUCATION" => Ok(IndustrySector::Education),
+        "RETAIL" => Ok(IndustrySector::Retail),
+        "MANUFACTURING" => Ok(IndustrySector::Manufacturing),
+        "TECHNOLOGY" => Ok(IndustrySector::Technology),
+        "ENERGY" => Ok(IndustrySector::Energy),
+        "LEGAL" => Ok(IndustrySector::Legal),
+        "NON_PROFIT" | "NONPROFIT" => Ok(IndustrySector::NonProfit),
+        "OTHER" => Ok(IndustrySector::Other),
+        _ => Err(format!("Unknown industry: {}", s)),
+    }
+}
+
+fn parse_company_size_param(s: &str) -> Result<CompanySize, String> {
+    match s.to_uppercase().as_str() {
+        "SMALL" => Ok(CompanySize::Small),
+        "MEDIUM" => Ok(CompanySize::Medium),
+        "LARGE" => Ok(CompanySize::Large),
+        "ENTERPRISE" => Ok(CompanySize::Enterprise),
+        _ => Err(format!("Unknown company size: {}", s)),
+    }
+}
+
+fn parse_date_param(s: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .map(|d| d.with_timezone(&chrono::Utc))
+        .map_err(|e| format!("Invalid date: {}", e))
 }
@@ -0,0 +1,266 @@
+//! Client Dashboard Commands
+//!
+//! One call gathering everything a client's dashboard shows: compliance per
+//! framework, the asset inventory, scanning, remediation, reports and cloud
+//! readiness. Each section is loaded on its own, so a section whose query
+//! fails is left out with its error instead of failing the whole dashboard.
+
+use crate::commands::grc::summarize_assessment;
+use crate::db::{ClientRepository, Database};
+use crate::grc::models::{Assessment, AssessmentStatus, Framework};
+use crate::grc::remediation::{RemediationPlanRepository, RemediationStats};
+use crate::grc::repository::AssessmentRepository;
+use crate::infrastructure::cloud_readiness::{score_readiness, CloudReadinessRepository};
+use crate::infrastructure::models::ReadinessTier;
+use crate::network::inventory::AssetInventory;
+use crate::network::models::{CategoryCount, CriticalityCount, ScanStatus};
+use crate::network::repository::{AssetRepository, ScanRepository};
+use crate::reporting::models::ReportSummary;
+use crate::reporting::repository::ReportRepository;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+
+/// Reports listed on the dashboard
+const RECENT_REPORT_LIMIT: usize = 5;
+
+/// A part of the dashboard, named in its load errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DashboardSection {
+    Compliance,
+    Assets,
+    Scanning,
+    Remediation,
+    Reports,
+    CloudReadiness,
+}
+
+/// Why a dashboard section couldn't be loaded
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardSectionError {
+    pub section: DashboardSection,
+    pub error: String,
+}
+
+/// The latest assessment of one framework
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkCompliance {
+    pub framework: Framework,
+    pub assessment_id: String,
+    pub assessment_name: String,
+    pub status: AssessmentStatus,
+    pub started_at: DateTime<Utc>,
+    pub overall_compliance: f64,
+    /// Non-compliant controls with a risk rating of 4 or more
+    pub high_risk_gaps: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardAssets {
+    pub total_assets: usize,
+    pub active_assets: usize,
+    pub by_category: Vec<CategoryCount>,
+    pub by_criticality: Vec<CriticalityCount>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardScanning {
+    pub total_scans: usize,
+    pub last_scan_id: Option<String>,
+    pub last_scan_name: Option<String>,
+    /// When the latest completed scan finished
+    pub last_scan_at: Option<String>,
+    /// Assets first discovered by the latest completed scan
+    pub new_hosts: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardCloudReadiness {
+    pub assessment_id: String,
+    pub overall_score: f64,
+    pub tier: ReadinessTier,
+    pub answered_items: usize,
+    pub total_items: usize,
+}
+
+/// Everything a client's dashboard shows
+///
+/// A section is None when the client has no data for it, or when loading it
+/// failed; failures are listed in `errors`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDashboard {
+    pub client_id: String,
+    pub client_name: String,
+    pub generated_at: String,
+    /// Latest non-archived assessment of each framework
+    pub compliance: Option<Vec<FrameworkCompliance>>,
+    pub assets: Option<DashboardAssets>,
+    pub scanning: Option<DashboardScanning>,
+    pub remediation: Option<RemediationStats>,
+    pub recent_reports: Option<Vec<ReportSummary>>,
+    pub cloud_readiness: Option<DashboardCloudReadiness>,
+    pub errors: Vec<DashboardSectionError>,
+}
+
+/// Get a client's dashboard in one call
+///
+/// The sections are loaded one after another on a blocking thread, off the
+/// async runtime.
+#[tauri::command]
+pub async fn get_client_dashboard(app: AppHandle, client_id: String) -> Result<ClientDashboard, String> {
+    tokio::task::spawn_blocking(move || load_dashboard(&app.state::<Database>(), &client_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn load_dashboard(db: &Database, client_id: &str) -> Result<ClientDashboard, String> {
+    let client = ClientRepository::new(db)
+        .get(client_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Client not found: {}", client_id))?;
+
+    let mut errors = Vec::new();
+    let compliance = section(DashboardSection::Compliance, load_compliance(db, client_id), &mut errors);
+    let assets = section(DashboardSection::Assets, load_assets(db, client_id), &mut errors);
+    let scanning = section(DashboardSection::Scanning, load_scanning(db, client_id), &mut errors);
+    let remediation = section(DashboardSection::Remediation, load_remediation(db, client_id), &mut errors);
+    let recent_reports = section(DashboardSection::Reports, load_recent_reports(db, client_id), &mut errors);
+    let cloud_readiness = section(DashboardSection::CloudReadiness, load_cloud_readiness(db, client_id), &mut errors);
+
+    Ok(ClientDashboard {
+        client_id: client.id,
+        client_name: client.name,
+        generated_at: Utc::now().to_rfc3339(),
+        compliance,
+        assets,
+        scanning,
+        remediation,
+        recent_reports,
+        cloud_readiness,
+        errors,
+    })
+}
+
+type SectionResult<T> = Result<Option<T>, String>;
+
+/// A loaded section's data, or None with its error recorded
+fn section<T>(section: DashboardSection, result: SectionResult<T>, errors: &mut Vec<DashboardSectionError>) -> Option<T> {
+    result.unwrap_or_else(|error| {
+        errors.push(DashboardSectionError { section, error });
+        None
+    })
+}
+
+fn load_compliance(db: &Database, client_id: &str) -> SectionResult<Vec<FrameworkCompliance>> {
+    let assessments = AssessmentRepository::new(db).list_by_client(client_id).map_err(|e| e.to_string())?;
+
+    let mut latest: HashMap<Framework, Assessment> = HashMap::new();
+    for assessment in assessments.into_iter().filter(|a| a.status != AssessmentStatus::Archived) {
+        let is_newer = latest
+            .get(&assessment.framework)
+            .map_or(true, |current| assessment.started_at >= current.started_at);
+        if is_newer {
+            latest.insert(assessment.framework, assessment);
+        }
+    }
+    if latest.is_empty() {
+        return Ok(None);
+    }
+
+    let mut frameworks = latest
+        .into_values()
+        .map(|assessment| {
+            let summary = summarize_assessment(db, &assessment)?;
+            Ok(FrameworkCompliance {
+                framework: assessment.framework,
+                assessment_id: assessment.id,
+                assessment_name: assessment.name,
+                status: assessment.status,
+                started_at: assessment.started_at,
+                overall_compliance: summary.overall_compliance,
+                high_risk_gaps: summary.high_risk_gaps,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    frameworks.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(Some(frameworks))
+}
+
+fn load_assets(db: &Database, client_id: &str) -> SectionResult<DashboardAssets> {
+    let stats = AssetInventory::new(db).get_stats(client_id).map_err(|e| e.to_string())?;
+    if stats.total_assets == 0 {
+        return Ok(None);
+    }
+    Ok(Some(DashboardAssets {
+        total_assets: stats.total_assets,
+        active_assets: stats.active_assets,
+        by_category: stats.by_category,
+        by_criticality: stats.by_criticality,
+    }))
+}
+
+fn load_scanning(db: &Database, client_id: &str) -> SectionResult<DashboardScanning> {
+    let scans = ScanRepository::new(db).list_by_client(client_id).map_err(|e| e.to_string())?;
+    if scans.is_empty() {
+        return Ok(None);
+    }
+
+    // Newest first, so the first completed scan is the latest
+    let last = scans.iter().find(|s| s.status == ScanStatus::Completed);
+    let new_hosts = match last {
+        Some(scan) => AssetRepository::new(db)
+            .list_by_client(client_id)
+            .map_err(|e| e.to_string())?
+            .iter()
+            .filter(|asset| asset.scan_ids.first() == Some(&scan.id))
+            .count(),
+        None => 0,
+    };
+
+    Ok(Some(DashboardScanning {
+        total_scans: scans.len(),
+        last_scan_id: last.map(|s| s.id.clone()),
+        last_scan_name: last.map(|s| s.name.clone()),
+        last_scan_at: last.map(|s| s.completed_at.clone().unwrap_or_else(|| s.created_at.clone())),
+        new_hosts,
+    }))
+}
+
+fn load_remediation(db: &Database, client_id: &str) -> SectionResult<RemediationStats> {
+    let items = RemediationPlanRepository::new(db).get_by_client(client_id).map_err(|e| e.to_string())?;
+    if items.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(RemediationStats::from_items(items.iter().map(|i| &i.item), Utc::now())))
+}
+
+fn load_recent_reports(db: &Database, client_id: &str) -> SectionResult<Vec<ReportSummary>> {
+    let mut reports = ReportRepository::new(db).list_summaries(Some(client_id)).map_err(|e| e.to_string())?;
+    if reports.is_empty() {
+        return Ok(None);
+    }
+    reports.truncate(RECENT_REPORT_LIMIT);
+    Ok(Some(reports))
+}
+
+fn load_cloud_readiness(db: &Database, client_id: &str) -> SectionResult<DashboardCloudReadiness> {
+    let assessment = CloudReadinessRepository::new(db).get_by_client(client_id).map_err(|e| e.to_string())?;
+    Ok(assessment.map(|assessment| {
+        let score = score_readiness(&assessment);
+        DashboardCloudReadiness {
+            assessment_id: score.assessment_id,
+            overall_score: score.overall_score,
+            tier: score.tier,
+            answered_items: score.answered_items,
+            total_items: score.total_items,
+        }
+    }))
+}
@@ -0,0 +1,161 @@
+//! Database Commands
+//!
+//! Tauri commands for unlocking the local database, managing its
+//! encryption at rest, and backing it up and restoring it.
+
+use crate::db::{self, Database};
+use crate::db_backup::{self, BackupManifest, BackupRestore};
+use crate::db_encryption::{
+    change_passphrase, encrypt_existing_database, is_encrypted, unlock, DatabaseSession, DatabaseState,
+    DatabaseStatus, KeyDerivation, DATABASE_STATUS_EVENT,
+};
+use crate::error::OptioError;
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// Commands that work before the database is open
+const COMMANDS_WITHOUT_DATABASE: &[&str] = &[
+    "get_database_status",
+    "unlock_database",
+    "list_database_backups",
+    "prune_database_backups",
+];
+
+/// Wrap the command handler so commands that need the database fail with
+/// `DatabaseLocked` until it is unlocked
+///
+/// An encrypted database isn't managed as state until it is unlocked, and
+/// Tauri would otherwise reject those commands with a bare "state not
+/// managed" error.
+pub fn require_open_database<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let webview = invoke.message.webview();
+        if COMMANDS_WITHOUT_DATABASE.contains(&invoke.message.command()) || webview.try_state::<Database>().is_some() {
+            return handler(invoke);
+        }
+
+        let status = webview.state::<DatabaseSession>().status();
+        let error = match status.state {
+            DatabaseState::Locked => OptioError::DatabaseLocked("Unlock it with your passphrase".to_string()),
+            DatabaseState::Failed => {
+                OptioError::Database(status.message.unwrap_or_else(|| "The database failed to open".to_string()))
+            }
+            DatabaseState::Starting | DatabaseState::Ready => {
+                OptioError::Database("The database is still opening".to_string())
+            }
+        };
+        invoke.resolver.reject(error.to_string());
+        true
+    }
+}
+
+/// Whether the database is starting, locked, ready or failed to open
+#[tauri::command]
+pub async fn get_database_status(session: State<'_, DatabaseSession>) -> Result<DatabaseStatus, String> {
+    Ok(session.status())
+}
+
+/// Open an encrypted database with its passphrase
+#[tauri::command]
+pub async fn unlock_database(
+    app_handle: AppHandle,
+    session: State<'_, DatabaseSession>,
+    passphrase: String,
+) -> Result<DatabaseStatus, String> {
+    if app_handle.try_state::<Database>().is_some() {
+        return Ok(session.status());
+    }
+
+    let path = db::database_path(&app_handle).map_err(|e| e.to_string())?;
+    let key = unlock(&path, &passphrase).map_err(|e| e.to_string())?;
+    db::initialize(&app_handle, Some(&key)).await.map_err(|e| e.to_string())?;
+    session.set_key(key);
+    tracing::info!("Database unlocked");
+
+    let status = session.set_status(DatabaseState::Ready, true, None);
+    if let Err(e) = app_handle.emit(DATABASE_STATUS_EVENT, &status) {
+        tracing::warn!("Failed to emit database status: {}", e);
+    }
+    Ok(status)
+}
+
+/// Turn on encryption, encrypting the existing database under `passphrase`
+///
+/// The passphrase is needed at every start from then on; it can't be
+/// recovered if lost.
+#[tauri::command]
+pub async fn set_database_passphrase(
+    db: State<'_, Database>,
+    session: State<'_, DatabaseSession>,
+    passphrase: String,
+) -> Result<DatabaseStatus, String> {
+    let key = encrypt_existing_database(&db, &passphrase).map_err(|e| e.to_string())?;
+    session.set_key(key);
+    Ok(session.set_status(DatabaseState::Ready, true, None))
+}
+
+/// Re-wrap the database key under a new passphrase
+#[tauri::command]
+pub async fn change_database_passphrase(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    session: State<'_, DatabaseSession>,
+    current_passphrase: String,
+    new_passphrase: String,
+) -> Result<DatabaseStatus, String> {
+    let path = db::database_path(&app_handle).map_err(|e| e.to_string())?;
+    if !is_encrypted(&path) {
+        return Err("The database is not encrypted; set a passphrase first".to_string());
+    }
+    let session_key = session.key().ok_or_else(|| "The database has not been unlocked".to_string())?;
+
+    let derivation = KeyDerivation::load(&path)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "The database is not encrypted".to_string())?;
+    let current = derivation
+        .unwrap_key(&current_passphrase)
+        .map_err(|_| "Current passphrase is incorrect".to_string())?;
+    if current != session_key {
+        return Err("Current passphrase is incorrect".to_string());
+    }
+
+    let key = change_passphrase(&db, &current, &new_passphrase).map_err(|e| e.to_string())?;
+    session.set_key(key);
+    Ok(session.status())
+}
+
+/// Back up the database now
+#[tauri::command]
+pub async fn create_database_backup(
+    db: State<'_, Database>,
+    session: State<'_, DatabaseSession>,
+    label: Option<String>,
+) -> Result<BackupManifest, String> {
+    db_backup::create_backup(&db, label.as_deref(), session.key().as_ref()).map_err(|e| e.to_string())
+}
+
+/// Backups of the database, newest first
+#[tauri::command]
+pub async fn list_database_backups(app_handle: AppHandle) -> Result<Vec<BackupManifest>, String> {
+    let path = db::database_path(&app_handle).map_err(|e| e.to_string())?;
+    db_backup::list_backups(&path).map_err(|e| e.to_string())
+}
+
+/// Replace the database with a backup, keeping a safety copy of the current one
+#[tauri::command]
+pub async fn restore_database_backup(
+    db: State<'_, Database>,
+    session: State<'_, DatabaseSession>,
+    backup_id: String,
+) -> Result<BackupRestore, String> {
+    db_backup::restore_backup(&db, &backup_id, session.key().as_ref()).map_err(|e| e.to_string())
+}
+
+/// Delete all but the newest `keep_last` backups, returning the deleted ones
+#[tauri::command]
+pub async fn prune_database_backups(app_handle: AppHandle, keep_last: usize) -> Result<Vec<BackupManifest>, String> {
+    let path = db::database_path(&app_handle).map_err(|e| e.to_string())?;
+    db_backup::prune_backups(&path, keep_last).map_err(|e| e.to_string())
+}
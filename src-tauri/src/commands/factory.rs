@@ -3,10 +3,20 @@
 //! "The Factory" - Dynamic client provisioning with PowerShell script generation.
 //! Manufactures unique, state-aware scripts for each engagement.
 
+use crate::activity::{self, ActivityEvent, ActivityEventType};
+use crate::audit::{self, AuditEntry, AuditOperation};
+use crate::commands::system::{consultant_ip_route_problem, resolve_consultant_ip};
+use crate::db::{ClientRepository, Database};
 use crate::error::{OptioError, OptioResult};
-use crate::factory::{ScriptConfig, ScriptGenerator, TemplateInfo, AgentScriptConfig, generate_agent_script as factory_generate_agent};
+use crate::factory::{AuditIntendedState, PrepAuditReport, ScriptConfig, ScriptGenerator, ScriptSyntaxReport, TemplateInfo, TemplateLint, AgentScriptConfig, consultant_ip_problem, generate_agent_script as factory_generate_agent, validate_script_syntax, lint_template as factory_lint_template, build_prep_audit_report, parse_prep_audit};
+use crate::factory::signing::{manifest_footer, verify_script as verify_script_manifest, ScriptManifest, ScriptSigner, ScriptVerification, SigningKeyInfo};
+use crate::factory::history::{content_hash, regenerate, ScriptHistoryRepository, ScriptRecord, ScriptRecordSummary, ScriptRegeneration};
+use crate::factory::tokens::{redact_agent_tokens, AgentToken, AgentTokenRepository};
+use crate::network::agent_listener::AgentRepository;
+use crate::network::agent_tls::EngagementCertificate;
+use crate::secrets::OsKeychain;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, State};
 use std::path::PathBuf;
 
 /// Request payload for script generation
@@ -15,14 +25,16 @@ use std::path::PathBuf;
 pub struct GenerateScriptRequest {
     /// Client identifier
     pub client_id: String,
-    /// Client display name
-    pub client_name: String,
+    /// Client display name; defaults to the stored client's name
+    pub client_name: Option<String>,
     /// Target subnet for the engagement
     pub target_subnet: String,
     /// Template to use for generation
     pub template_name: String,
     /// Configuration options
     pub config: ScriptConfigOptions,
+    /// Fail generation when the script has syntax errors (default: warn)
+    pub strict: Option<bool>,
 }
 
 /// Configuration options for script generation
@@ -43,6 +55,10 @@ pub struct ScriptConfigOptions {
     pub enable_firewall_logging: bool,
     /// Custom PowerShell commands to include
     pub custom_commands: Option<Vec<String>>,
+    /// Consultant IP to inject (default: the client's preferred or detected IP)
+    pub consultant_ip: Option<String>,
+    /// Generate a read-only script that records current state instead of changing it
+    pub audit_only: Option<bool>,
 }
 
 /// Response from script generation
@@ -61,28 +77,38 @@ pub struct GenerateScriptResponse {
     pub generated_at: String,
     /// Warnings or notes
     pub warnings: Vec<String>,
+    /// Signed hash of the script, also appended to it as a comment footer
+    pub manifest: ScriptManifest,
 }
 
 /// Generate a client provisioning script
 #[tauri::command]
 pub async fn generate_client_script(
     app_handle: AppHandle,
+    db: State<'_, Database>,
     request: GenerateScriptRequest,
 ) -> Result<GenerateScriptResponse, String> {
+    let client_name = match request.client_name.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => ClientRepository::new(&db)
+            .get(&request.client_id)
+            .map_err(|e| e.to_string())?
+            .map(|c| c.name)
+            .ok_or_else(|| format!("Client not found: {}", request.client_id))?,
+    };
     tracing::info!(
         "Generating script for client: {} ({})",
-        request.client_name,
+        client_name,
         request.client_id
     );
 
     // Get the consultant's IP for injection
-    let consultant_ip = crate::commands::system::detect_local_ip()
-        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let consultant_ip = resolve_consultant_ip(&db, Some(&request.client_id), request.config.consultant_ip.as_deref());
 
     // Build the script configuration
     let config = ScriptConfig {
         client_id: request.client_id.clone(),
-        client_name: request.client_name.clone(),
+        client_name: client_name.clone(),
         target_subnet: request.target_subnet.clone(),
         consultant_ip,
         enable_winrm: request.config.enable_winrm,
@@ -92,6 +118,7 @@ pub async fn generate_client_script(
         agent_installer: request.config.agent_installer,
         enable_firewall_logging: request.config.enable_firewall_logging,
         custom_commands: request.config.custom_commands.unwrap_or_default(),
+        audit_only: request.config.audit_only.unwrap_or(false),
     };
 
     // Get the templates directory
@@ -99,33 +126,166 @@ pub async fn generate_client_script(
 
     // Generate the script
     let generator = ScriptGenerator::new(templates_dir);
-    let result = generator
+    let mut result = generator
         .generate(&request.template_name, &config)
         .map_err(|e| e.to_string())?;
 
+    // Check the script parses before it leaves the consultant's machine
+    let syntax = validate_script_syntax(&result.content);
+    if !syntax.valid {
+        let details = syntax.errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        if request.strict.unwrap_or(false) {
+            return Err(format!("Generated script has syntax errors: {}", details.join("; ")));
+        }
+        result.warnings.extend(details.into_iter().map(|d| format!("Syntax ({}): {}", syntax.checker, d)));
+    }
+
     // Write to the output directory
     let output_dir = get_output_dir(&app_handle)?;
     let output_filename = format!(
-        "{}_{}.ps1",
-        sanitize_filename(&request.client_name),
+        "{}_{}{}.ps1",
+        sanitize_filename(&client_name),
+        if config.audit_only { "audit_" } else { "" },
         chrono::Utc::now().format("%Y%m%d_%H%M%S")
     );
     let output_path = output_dir.join(&output_filename);
 
-    std::fs::write(&output_path, &result.content).map_err(|e| e.to_string())?;
+    // Sign the script so the client can check it wasn't altered in transit
+    let manifest = get_signer(&app_handle)?.sign(&result.content, &result.script_id, result.generated_at);
+    let footer = manifest_footer(&manifest);
+
+    audit::record(&db, AuditEntry::new(AuditOperation::ScriptGenerated, "script", Some(&result.script_id))
+        .with_detail(serde_json::json!({
+            "clientId": request.client_id,
+            "templateName": request.template_name,
+            "targetSubnet": request.target_subnet,
+            "consultantIp": config.consultant_ip,
+            "auditOnly": config.audit_only,
+            "outputPath": output_path.to_string_lossy(),
+            "contentHash": content_hash(&result.content),
+        })))
+        .map_err(|e| e.to_string())?;
+
+    std::fs::write(&output_path, format!("{}{}", result.content, footer)).map_err(|e| e.to_string())?;
 
     tracing::info!("Script generated: {:?}", output_path);
 
+    let summary = if config.audit_only {
+        format!("Generated audit-only script from {}", request.template_name)
+    } else {
+        format!("Generated provisioning script from {}", request.template_name)
+    };
+
+    // Keep the exact config and output so the script can be explained later
+    ScriptHistoryRepository::new(&db)
+        .create(&ScriptRecord {
+            script_id: result.script_id.clone(),
+            client_id: request.client_id.clone(),
+            template_name: request.template_name.clone(),
+            config,
+            content_hash: content_hash(&result.content),
+            content: Some(redact_agent_tokens(&result.content)),
+            warnings: result.warnings.clone(),
+            output_path: Some(output_path.to_string_lossy().to_string()),
+            generated_at: result.generated_at,
+        })
+        .map_err(|e| format!("Script written to {:?} but not recorded: {}", output_path, e))?;
+
+    activity::log(&db, ActivityEvent::new(
+        &request.client_id,
+        ActivityEventType::ScriptGenerated,
+        "script",
+        Some(&result.script_id),
+        summary,
+    )
+    .with_detail(serde_json::json!({
+        "templateName": request.template_name,
+        "outputPath": output_path.to_string_lossy(),
+        "targetSubnet": request.target_subnet,
+        "warnings": result.warnings.len(),
+    })));
+
     Ok(GenerateScriptResponse {
         success: true,
         output_path: output_path.to_string_lossy().to_string(),
-        script_content: result.content,
+        script_content: format!("{}{}", redact_agent_tokens(&result.content), footer),
         script_id: result.script_id,
         generated_at: result.generated_at.to_rfc3339(),
         warnings: result.warnings,
+        manifest,
     })
 }
 
+/// Check a script against its integrity manifest
+///
+/// Without a manifest, the one in the script's comment footer is used.
+/// Scripts signed with a rotated-out key still verify.
+#[tauri::command]
+pub async fn verify_script(
+    app_handle: AppHandle,
+    content: String,
+    manifest: Option<ScriptManifest>,
+) -> Result<ScriptVerification, String> {
+    let signer = get_signer(&app_handle)?;
+    Ok(verify_script_manifest(&content, manifest.as_ref(), signer.public_keys()))
+}
+
+/// List the script signing public keys, current and retired, to share with
+/// clients who verify scripts themselves
+#[tauri::command]
+pub async fn list_signing_keys(app_handle: AppHandle) -> Result<Vec<SigningKeyInfo>, String> {
+    Ok(get_signer(&app_handle)?.public_keys().to_vec())
+}
+
+/// Replace the script signing key, keeping the old public key so scripts
+/// signed with it still verify
+#[tauri::command]
+pub async fn rotate_signing_key(app_handle: AppHandle) -> Result<SigningKeyInfo, String> {
+    get_signer(&app_handle)?.rotate().map_err(|e| e.to_string())
+}
+
+/// List a client's generated scripts, newest first
+#[tauri::command]
+pub async fn list_generated_scripts(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<ScriptRecordSummary>, String> {
+    let records = ScriptHistoryRepository::new(&db)
+        .list_by_client(&client_id)
+        .map_err(|e| e.to_string())?;
+    Ok(records.iter().map(ScriptRecordSummary::from).collect())
+}
+
+/// Get a generated script with the config and content it was produced from
+#[tauri::command]
+pub async fn get_generated_script(
+    db: State<'_, Database>,
+    script_id: String,
+) -> Result<Option<ScriptRecord>, String> {
+    ScriptHistoryRepository::new(&db)
+        .get(&script_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Replay a generated script's stored config through the current template
+///
+/// Reports whether the output still matches the stored hash and, if not,
+/// which lines would change. Nothing is written or recorded.
+#[tauri::command]
+pub async fn regenerate_script(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    script_id: String,
+) -> Result<ScriptRegeneration, String> {
+    let record = ScriptHistoryRepository::new(&db)
+        .get(&script_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Generated script not found: {}", script_id))?;
+
+    let templates_dir = get_templates_dir(&app_handle)?;
+    regenerate(ScriptGenerator::new(templates_dir), &record).map_err(|e| e.to_string())
+}
+
 /// List available script templates
 #[tauri::command]
 pub async fn list_templates(app_handle: AppHandle) -> Result<Vec<TemplateInfo>, String> {
@@ -148,10 +308,10 @@ pub struct PreviewRequest {
 #[tauri::command]
 pub async fn get_script_preview(
     app_handle: AppHandle,
+    db: State<'_, Database>,
     request: PreviewRequest,
 ) -> Result<String, String> {
-    let consultant_ip = crate::commands::system::detect_local_ip()
-        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let consultant_ip = resolve_consultant_ip(&db, None, request.config.consultant_ip.as_deref());
 
     let config = ScriptConfig {
         client_id: "preview".to_string(),
@@ -165,21 +325,83 @@ pub async fn get_script_preview(
         agent_installer: request.config.agent_installer,
         enable_firewall_logging: request.config.enable_firewall_logging,
         custom_commands: request.config.custom_commands.unwrap_or_default(),
+        audit_only: request.config.audit_only.unwrap_or(false),
     };
 
+    // Previews tolerate unknown placeholders so they can be seen in context
     let templates_dir = get_templates_dir(&app_handle)?;
-    let generator = ScriptGenerator::new(templates_dir);
+    let generator = ScriptGenerator::new(templates_dir).with_strict_placeholders(false);
     let result = generator
         .generate(&request.template_name, &config)
         .map_err(|e| e.to_string())?;
 
-    Ok(result.content)
+    Ok(redact_agent_tokens(&result.content))
+}
+
+/// Check a generated script for PowerShell syntax errors
+#[tauri::command]
+pub async fn validate_generated_script(content: String) -> Result<ScriptSyntaxReport, String> {
+    Ok(validate_script_syntax(&content))
+}
+
+/// Lint a template's placeholders and structure before generating from it
+///
+/// A single word is taken as the name of a template (custom or built-in);
+/// anything else as template source, e.g. one being edited.
+#[tauri::command]
+pub async fn lint_template(app_handle: AppHandle, name_or_content: String) -> Result<TemplateLint, String> {
+    let is_name = !name_or_content.is_empty()
+        && name_or_content.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'));
+    let content = if is_name {
+        let templates_dir = get_templates_dir(&app_handle)?;
+        ScriptGenerator::new(templates_dir)
+            .template_content(&name_or_content)
+            .map_err(|e| e.to_string())?
+    } else {
+        name_or_content
+    };
+
+    Ok(factory_lint_template(&content))
+}
+
+/// Read the JSON file written by an audit-only script and compare it with
+/// what the prep script would change
+///
+/// The intended state comes from Optio's record of the script when one
+/// exists, falling back to the copy embedded in the file.
+#[tauri::command]
+pub async fn parse_prep_audit_result(
+    db: State<'_, Database>,
+    path: String,
+) -> Result<PrepAuditReport, String> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read audit file {}: {}", path, e))?;
+    let audit = parse_prep_audit(&content).map_err(|e| e.to_string())?;
+
+    let record = if audit.script_id.is_empty() {
+        None
+    } else {
+        ScriptHistoryRepository::new(&db)
+            .get(&audit.script_id)
+            .map_err(|e| e.to_string())?
+    };
+
+    match (record, &audit.intended) {
+        (Some(record), _) => Ok(build_prep_audit_report(&audit, &AuditIntendedState::from(&record.config), true)),
+        (None, Some(intended)) => Ok(build_prep_audit_report(&audit, intended, false)),
+        (None, None) => Err(format!(
+            "Audit file has no intended state and script {} is not recorded",
+            audit.script_id
+        )),
+    }
 }
 
 /// Validation request for configuration
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidateConfigRequest {
+    /// Client whose persisted consultant IP applies when none is given
+    pub client_id: Option<String>,
     pub client_name: String,
     pub target_subnet: String,
     pub config: ScriptConfigOptions,
@@ -196,7 +418,10 @@ pub struct ValidationResult {
 
 /// Validate script configuration before generation
 #[tauri::command]
-pub async fn validate_config(request: ValidateConfigRequest) -> Result<ValidationResult, String> {
+pub async fn validate_config(
+    db: State<'_, Database>,
+    request: ValidateConfigRequest,
+) -> Result<ValidationResult, String> {
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
@@ -236,6 +461,23 @@ pub async fn validate_config(request: ValidateConfigRequest) -> Result<Validatio
         }
     }
 
+    // Validate an explicitly chosen consultant IP
+    if let Some(ip) = request.config.consultant_ip.as_deref().map(str::trim).filter(|ip| !ip.is_empty()) {
+        if ip.parse::<std::net::IpAddr>().is_err() {
+            errors.push(format!("Invalid consultant IP: {}", ip));
+        } else if let Some(problem) = consultant_ip_problem(ip) {
+            warnings.push(problem);
+        }
+    }
+
+    // The IP that will be injected must be reachable from the target subnet
+    if errors.is_empty() {
+        let consultant_ip = resolve_consultant_ip(&db, request.client_id.as_deref(), request.config.consultant_ip.as_deref());
+        if let Some(problem) = consultant_ip_route_problem(&consultant_ip, &request.target_subnet) {
+            warnings.push(problem);
+        }
+    }
+
     // Add warnings for potentially risky configurations
     if request.config.enable_winrm {
         warnings.push("WinRM enablement will modify Windows Remote Management settings".to_string());
@@ -272,6 +514,15 @@ fn get_templates_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
     }
 }
 
+fn get_signer(app_handle: &AppHandle) -> Result<ScriptSigner, String> {
+    let app_data = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+
+    ScriptSigner::load_or_create(&app_data.join("signing")).map_err(|e| e.to_string())
+}
+
 fn get_output_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
     let app_data = app_handle
         .path()
@@ -330,11 +581,11 @@ fn is_valid_ip(ip: &str) -> bool {
 pub struct GenerateAgentScriptRequest {
     /// IP address of the Optio server (callback target)
     pub client_ip: String,
-    /// Authentication token for secure communication
-    pub auth_token: String,
+    /// Agent token to embed, from `issue_agent_token`
+    pub token_id: String,
     /// Callback port (default: 443)
     pub callback_port: Option<u16>,
-    /// Enable TLS for callback connection
+    /// Connect over TLS to the client's listener certificate (default: true)
     pub use_tls: Option<bool>,
     /// Heartbeat interval in seconds
     pub heartbeat_interval: Option<u32>,
@@ -346,7 +597,9 @@ pub struct GenerateAgentScriptRequest {
 pub struct AgentScriptResponse {
     /// Whether generation was successful
     pub success: bool,
-    /// The generated script content
+    /// Path to the generated script, the only copy with the token in full
+    pub output_path: String,
+    /// Script content for preview, with the token redacted
     pub script_content: String,
     /// Unique script identifier
     pub script_id: String,
@@ -359,9 +612,13 @@ pub struct AgentScriptResponse {
 /// Generate an agent script with hardcoded connection parameters for reverse callback
 ///
 /// This creates a PowerShell script that will establish a connection back to Optio
-/// with the specified IP and authentication token hardcoded into the script.
+/// with the specified IP and authentication token hardcoded into the script. The
+/// token is read from the OS credential store and only written to the script
+/// file; the returned content has it redacted.
 #[tauri::command]
 pub async fn generate_agent_script(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
     request: GenerateAgentScriptRequest,
 ) -> Result<AgentScriptResponse, String> {
     tracing::info!(
@@ -369,23 +626,129 @@ pub async fn generate_agent_script(
         request.client_ip
     );
 
+    let tokens = AgentTokenRepository::new(&db);
+    let token = tokens
+        .get(&request.token_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Agent token not found: {}", request.token_id))?;
+    let auth_token = tokens.reveal(&token.id, &OsKeychain).map_err(|e| e.to_string())?;
+    let tls = if request.use_tls.unwrap_or(true) {
+        let certificate = EngagementCertificate::load_or_create(&token.client_id, &OsKeychain).map_err(|e| e.to_string())?;
+        Some(certificate.pin())
+    } else {
+        None
+    };
+
     let config = AgentScriptConfig {
         client_ip: request.client_ip,
-        auth_token: request.auth_token,
+        auth_token,
         callback_port: request.callback_port.unwrap_or(443),
-        use_tls: request.use_tls.unwrap_or(true),
+        tls,
         heartbeat_interval: request.heartbeat_interval.unwrap_or(30),
     };
 
     let result = factory_generate_agent(&config).map_err(|e| e.to_string())?;
 
-    tracing::info!("Agent script generated: {}", result.script_id);
+    let output_path = get_output_dir(&app_handle)?.join(format!(
+        "agent_{}_{}.ps1",
+        sanitize_filename(&token.client_id),
+        chrono::Utc::now().format("%Y%m%d_%H%M%S")
+    ));
+    audit::record(&db, AuditEntry::new(AuditOperation::ScriptGenerated, "script", Some(&result.script_id))
+        .with_detail(serde_json::json!({
+            "clientId": token.client_id,
+            "templateName": "agent",
+            "tokenId": token.id,
+            "callbackIp": config.client_ip,
+            "callbackPort": config.callback_port,
+            "tls": config.tls.is_some(),
+            "outputPath": output_path.to_string_lossy(),
+        })))
+        .map_err(|e| e.to_string())?;
+    // The listener only accepts agents running a script recorded for their token's client
+    AgentRepository::new(&db)
+        .record_script(&result.script_id, &token, result.generated_at)
+        .map_err(|e| e.to_string())?;
+    std::fs::write(&output_path, &result.content).map_err(|e| e.to_string())?;
+
+    tracing::info!("Agent script generated: {} ({:?})", result.script_id, output_path);
+
+    activity::log(&db, ActivityEvent::new(
+        &token.client_id,
+        ActivityEventType::ScriptGenerated,
+        "script",
+        Some(&result.script_id),
+        format!("Generated agent callback script with token {}", token.redacted()),
+    )
+    .with_detail(serde_json::json!({
+        "tokenId": token.id,
+        "outputPath": output_path.to_string_lossy(),
+        "callbackIp": config.client_ip,
+    })));
 
     Ok(AgentScriptResponse {
         success: true,
-        script_content: result.content,
+        output_path: output_path.to_string_lossy().to_string(),
+        script_content: redact_agent_tokens(&result.content),
         script_id: result.script_id,
         generated_at: result.generated_at.to_rfc3339(),
         warnings: result.warnings,
     })
 }
+
+/// Issue an agent auth token for a client
+///
+/// The token is stored in the OS credential store; only its metadata is
+/// returned. Without `ttl_days` the token lasts until revoked. A token
+/// whose issue can't be recorded in the audit log is revoked again.
+#[tauri::command]
+pub async fn issue_agent_token(
+    db: State<'_, Database>,
+    client_id: String,
+    label: Option<String>,
+    ttl_days: Option<u32>,
+) -> Result<AgentToken, String> {
+    let tokens = AgentTokenRepository::new(&db);
+    let token = tokens
+        .create(&client_id, label.as_deref(), ttl_days, &OsKeychain)
+        .map_err(|e| e.to_string())?;
+
+    let recorded = audit::record(&db, AuditEntry::new(AuditOperation::AgentTokenIssued, "agent_token", Some(&token.id))
+        .with_detail(serde_json::json!({
+            "clientId": token.client_id,
+            "label": token.label,
+            "lastFour": token.last_four,
+            "expiresAt": token.expires_at,
+        })));
+    if let Err(e) = recorded {
+        if let Err(revoke_error) = tokens.revoke(&token.id, &OsKeychain) {
+            tracing::error!("Failed to revoke unrecorded agent token {}: {}", token.id, revoke_error);
+        }
+        return Err(e.to_string());
+    }
+
+    Ok(token)
+}
+
+/// List a client's agent tokens, newest first
+#[tauri::command]
+pub async fn list_agent_tokens(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<AgentToken>, String> {
+    AgentTokenRepository::new(&db)
+        .list_by_client(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Revoke an agent token so callbacks presenting it are rejected
+#[tauri::command]
+pub async fn revoke_agent_token(
+    db: State<'_, Database>,
+    token_id: String,
+) -> Result<AgentToken, String> {
+    AgentTokenRepository::new(&db)
+        .revoke(&token_id, &OsKeychain)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Agent token not found: {}", token_id))
+}
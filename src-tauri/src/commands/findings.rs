@@ -0,0 +1,192 @@
+//! Findings Library Commands
+//!
+//! Tauri commands for finding templates, CVSS scoring and client findings.
+
+use crate::db::Database;
+use crate::findings::{
+    cvss::{score_cvss_vector, CvssScore},
+    library::{
+        default_finding_templates, finding_templates, instantiate_finding as new_finding, score_template, Finding,
+        FindingOverrides, FindingRepository, FindingTemplate, FindingTemplateRepository,
+    },
+};
+use crate::network::models::Criticality;
+use chrono::Utc;
+use serde::Deserialize;
+use tauri::State;
+use uuid::Uuid;
+
+// ============================================================================
+// Finding Template Commands
+// ============================================================================
+
+/// Create or replace a finding template request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertFindingTemplateRequest {
+    /// Omit to add a template; a built-in's id overrides it
+    pub id: Option<String>,
+    pub title: String,
+    pub severity: String,
+    pub cvss_vector: Option<String>,
+    pub description: String,
+    pub impact: Option<String>,
+    pub recommendation: String,
+    pub references: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+}
+
+/// List the finding templates in effect, built-ins first
+#[tauri::command]
+pub async fn list_finding_templates(db: State<'_, Database>) -> Result<Vec<FindingTemplate>, String> {
+    finding_templates(&db).map_err(|e| e.to_string())
+}
+
+/// Add a finding template, or replace one (including a built-in)
+#[tauri::command]
+pub async fn upsert_finding_template(
+    db: State<'_, Database>,
+    request: UpsertFindingTemplateRequest,
+) -> Result<FindingTemplate, String> {
+    if request.title.trim().is_empty() {
+        return Err("Template title is required".to_string());
+    }
+    if request.description.trim().is_empty() {
+        return Err("Template description is required".to_string());
+    }
+    if request.recommendation.trim().is_empty() {
+        return Err("Template recommendation is required".to_string());
+    }
+    let list = |items: Option<Vec<String>>| -> Vec<String> {
+        items.unwrap_or_default()
+            .into_iter()
+            .map(|i| i.trim().to_string())
+            .filter(|i| !i.is_empty())
+            .collect()
+    };
+
+    let id = request.id.filter(|id| !id.trim().is_empty()).unwrap_or_else(|| Uuid::new_v4().to_string());
+    let mut template = FindingTemplate {
+        built_in: default_finding_templates().iter().any(|t| t.id == id),
+        id,
+        title: request.title.trim().to_string(),
+        severity: parse_severity(&request.severity)?,
+        cvss_vector: request.cvss_vector,
+        cvss_score: None,
+        description: request.description,
+        impact: request.impact.unwrap_or_default(),
+        recommendation: request.recommendation,
+        references: list(request.references),
+        tags: list(request.tags),
+    };
+    score_template(&mut template)?;
+
+    FindingTemplateRepository::new(&db).save(&template).map_err(|e| e.to_string())?;
+    tracing::info!("Saved finding template {}", template.id);
+
+    Ok(template)
+}
+
+/// Delete a user finding template
+///
+/// Deleting the override of a built-in restores the built-in; built-ins
+/// themselves can't be deleted.
+#[tauri::command]
+pub async fn delete_finding_template(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    let deleted = FindingTemplateRepository::new(&db).delete(&id).map_err(|e| e.to_string())?;
+    if !deleted && default_finding_templates().iter().any(|t| t.id == id) {
+        return Err("Built-in finding templates can't be deleted".to_string());
+    }
+    Ok(deleted)
+}
+
+/// Score a CVSS v3.1 base vector
+#[tauri::command]
+pub async fn calculate_cvss_score(vector: String) -> Result<CvssScore, String> {
+    score_cvss_vector(&vector)
+}
+
+// ============================================================================
+// Client Finding Commands
+// ============================================================================
+
+/// Record a finding for a client from a library template
+#[tauri::command]
+pub async fn instantiate_finding(
+    db: State<'_, Database>,
+    template_id: String,
+    client_id: String,
+    overrides: Option<FindingOverrides>,
+) -> Result<Finding, String> {
+    let template = finding_templates(&db)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| format!("Finding template not found: {}", template_id))?;
+
+    let finding = new_finding(&template, &client_id, overrides.unwrap_or_default())?;
+    FindingRepository::new(&db).save(&finding).map_err(|e| e.to_string())?;
+    tracing::info!("Created finding {} from template {}", finding.id, template_id);
+
+    Ok(finding)
+}
+
+/// List a client's findings, most severe and highest CVSS score first
+#[tauri::command]
+pub async fn list_findings(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<Finding>, String> {
+    FindingRepository::new(&db).list_by_client(&client_id).map_err(|e| e.to_string())
+}
+
+/// Get a finding by ID
+#[tauri::command]
+pub async fn get_finding(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<Option<Finding>, String> {
+    FindingRepository::new(&db).get(&id).map_err(|e| e.to_string())
+}
+
+/// Change a finding's details, links or status
+#[tauri::command]
+pub async fn update_finding(
+    db: State<'_, Database>,
+    id: String,
+    overrides: FindingOverrides,
+) -> Result<Finding, String> {
+    let repo = FindingRepository::new(&db);
+    let mut finding = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Finding not found: {}", id))?;
+
+    finding.apply(overrides)?;
+    finding.updated_at = Utc::now();
+
+    repo.save(&finding).map_err(|e| e.to_string())?;
+    Ok(finding)
+}
+
+/// Delete a finding
+#[tauri::command]
+pub async fn delete_finding(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    FindingRepository::new(&db).delete(&id).map_err(|e| e.to_string())
+}
+
+fn parse_severity(s: &str) -> Result<Criticality, String> {
+    match s.to_lowercase().as_str() {
+        "critical" => Ok(Criticality::Critical),
+        "high" => Ok(Criticality::High),
+        "medium" => Ok(Criticality::Medium),
+        "low" => Ok(Criticality::Low),
+        "informational" | "info" => Ok(Criticality::Informational),
+        _ => Err(format!("Unknown severity: {}", s)),
+    }
+}
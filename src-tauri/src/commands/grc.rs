@@ -2,16 +2,28 @@
 //!
 //! Tauri commands for GRC (Governance, Risk, Compliance) operations.
 
-use crate::db::Database;
+use crate::activity::{self, ActivityEvent, ActivityEventType};
+use crate::db::{ClientRepository, Database};
+use crate::onboarding::{self, Milestone};
+use crate::reporting::generator::remediation_plan_to_markdown;
 use crate::grc::{
     models::*,
-    frameworks::{get_framework_controls, get_available_frameworks, get_framework_categories, FrameworkInfo, CategoryInfo},
-    repository::{AssessmentRepository, ControlAssessmentRepository, EvidenceRepository},
+    frameworks::{build_compliance_report, build_evidence_coverage, get_control_mappings, get_framework_controls, get_available_frameworks, get_framework_categories, project_control_statuses, rollup_category_groups, search_framework_controls, validate_category_groups, FrameworkInfo, CategoryInfo},
+    repository::{AssessmentRepository, CategoryGroupingRepository, ControlAssessmentRepository, EvidenceRepository},
+    risk::{build_risk_matrix, validate_rating, RiskItem, RiskMatrix, RiskRepository, RiskStatus},
+    ropa::{validate_processing_activity, LawfulBasis, ProcessingActivity, ProcessingActivityRepository, ThirdCountryTransfer},
+    trend::{build_compliance_trend, current_compliance_status, snapshot_compliance, ComplianceSnapshot, ComplianceSnapshotRepository, ComplianceTrend},
+    snapshot::{AssessmentSnapshot, AssessmentSnapshotRepository, SnapshotComparison},
+    overview::{build_compliance_overview, ComplianceOverview},
+    remediation::{build_remediation_plan, remediation_plan_to_csv, ClientRemediationItem, EffortMapping, RemediationItem, RemediationPlan, RemediationPlanRepository, RemediationStats},
+    transfer::{export_bundle, import_bundle, read_bundle, write_bundle_archive, AssessmentImportSummary, ConflictStrategy, TransferWarning},
+    policy_generator::{collect_policy_gaps, PolicyDocument, PolicyGenerator, PolicyType},
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::State;
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
 use uuid::Uuid;
 
 // ============================================================================
@@ -31,6 +43,50 @@ pub async fn get_framework_controls_cmd(framework: String) -> Result<Vec<Control
     Ok(get_framework_controls(fw))
 }
 
+/// Search a framework's controls, ranked by relevance with match offsets
+///
+/// Optionally narrowed to a category code and, against an assessment, to
+/// controls with a given compliance status. An empty query lists every
+/// control that passes the filters.
+#[tauri::command]
+pub async fn search_controls(
+    db: State<'_, Database>,
+    framework: String,
+    query: Option<String>,
+    category: Option<String>,
+    status_filter: Option<String>,
+    assessment_id: Option<String>,
+) -> Result<Vec<ControlSearchResult>, String> {
+    let fw = parse_framework_param(&framework)?;
+    let status_filter = status_filter.as_deref().map(parse_compliance_status_param).transpose()?;
+
+    let control_assessments = match assessment_id {
+        Some(id) => {
+            let assessment = AssessmentRepository::new(&db).get(&id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Assessment not found: {}", id))?;
+            if assessment.framework != fw {
+                return Err(format!(
+                    "Assessment {} is for {}, not {}",
+                    id,
+                    assessment.framework.display_name(),
+                    fw.display_name()
+                ));
+            }
+            Some(ControlAssessmentRepository::new(&db).get_by_assessment(&id).map_err(|e| e.to_string())?)
+        }
+        None => None,
+    };
+
+    Ok(search_framework_controls(
+        fw,
+        query.as_deref().unwrap_or(""),
+        category.as_deref().filter(|c| !c.trim().is_empty()),
+        status_filter,
+        control_assessments.as_deref(),
+    ))
+}
+
 // ============================================================================
 // Assessment Commands
 // ============================================================================
@@ -42,7 +98,8 @@ pub struct CreateAssessmentRequest {
     pub client_id: String,
     pub name: String,
     pub description: Option<String>,
-    pub framework: String,
+    /// Defaults to the client's preferred framework
+    pub framework: Option<String>,
     pub scope: Option<String>,
     pub lead_assessor: String,
 }
@@ -53,7 +110,14 @@ pub async fn create_assessment(
     db: State<'_, Database>,
     request: CreateAssessmentRequest,
 ) -> Result<Assessment, String> {
-    let framework = parse_framework_param(&request.framework)?;
+    let framework = match request.framework.as_deref() {
+        Some(framework) => parse_framework_param(framework)?,
+        None => ClientRepository::new(&db)
+            .get(&request.client_id)
+            .map_err(|e| e.to_string())?
+            .and_then(|client| client.preferred_framework())
+            .ok_or_else(|| "Choose a framework: the client has no default framework or industry set".to_string())?,
+    };
 
     let assessment = Assessment {
         id: Uuid::new_v4().to_string(),
@@ -66,10 +130,24 @@ pub async fn create_assessment(
         completed_at: None,
         lead_assessor: request.lead_assessor,
         status: AssessmentStatus::Draft,
+        source_assessment_id: None,
     };
 
     let repo = AssessmentRepository::new(&db);
     repo.create(&assessment).map_err(|e| e.to_string())?;
+    onboarding::advance(&db, &[Milestone::FirstAssessment]);
+    activity::log(&db, ActivityEvent::new(
+        &assessment.client_id,
+        ActivityEventType::AssessmentCreated,
+        "assessment",
+        Some(&assessment.id),
+        format!("Created {} assessment \"{}\"", assessment.framework.display_name(), assessment.name),
+    )
+    .by(&assessment.lead_assessor)
+    .with_detail(serde_json::json!({
+        "framework": assessment.framework,
+        "name": assessment.name,
+    })));
 
     Ok(assessment)
 }
@@ -104,6 +182,9 @@ pub async fn list_assessments(
 }
 
 /// Update assessment status
+///
+/// Only forward moves through the lifecycle (and a review sending work back
+/// to In Progress) are accepted; see `AssessmentStatus::allowed_transitions`.
 #[tauri::command]
 pub async fn update_assessment_status(
     db: State<'_, Database>,
@@ -112,19 +193,157 @@ pub async fn update_assessment_status(
 ) -> Result<bool, String> {
     let status = parse_assessment_status_param(&status)?;
     let repo = AssessmentRepository::new(&db);
-    repo.update_status(&id, status).map_err(|e| e.to_string())
+
+    let assessment = match repo.get(&id).map_err(|e| e.to_string())? {
+        Some(assessment) => assessment,
+        None => return Ok(false),
+    };
+    if assessment.status == status {
+        return Ok(true);
+    }
+    assessment.status.validate_transition(status)?;
+
+    let updated = repo.update_status(&id, status).map_err(|e| e.to_string())?;
+
+    // Completion is a natural point on the compliance trend line
+    if updated && status == AssessmentStatus::Completed {
+        if let Err(e) = snapshot_compliance(&db, &assessment.client_id, assessment.framework) {
+            tracing::warn!("Failed to snapshot compliance for assessment {}: {}", id, e);
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Reopen a completed or archived assessment for further work
+///
+/// Moves it back to In Progress and clears its completion time, unlocking
+/// control updates and evidence changes.
+#[tauri::command]
+pub async fn reopen_assessment(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<Assessment, String> {
+    let repo = AssessmentRepository::new(&db);
+    let assessment = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment not found: {}", id))?;
+
+    if !assessment.status.is_locked() {
+        return Err(format!(
+            "Only completed or archived assessments can be reopened; this one is {}",
+            assessment.status.display_name()
+        ));
+    }
+
+    repo.update_status(&id, AssessmentStatus::InProgress).map_err(|e| e.to_string())?;
+    repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment not found: {}", id))
 }
 
 /// Delete assessment
+///
+/// Completed assessments are kept unless `force` is set.
 #[tauri::command]
 pub async fn delete_assessment(
     db: State<'_, Database>,
     id: String,
+    force: Option<bool>,
 ) -> Result<bool, String> {
     let repo = AssessmentRepository::new(&db);
+    let completed = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .is_some_and(|a| a.status == AssessmentStatus::Completed);
+    if completed && !force.unwrap_or(false) {
+        return Err("Cannot delete a completed assessment without forcing it; archive it instead".to_string());
+    }
+
     repo.delete(&id).map_err(|e| e.to_string())
 }
 
+/// Clone an assessment as the starting point for a reassessment
+///
+/// The copy starts as a Draft linked back to its source. Control results are
+/// copied as they stand, or with `reset_statuses` start again as Not Assessed
+/// with the old notes and gap descriptions kept as prior-year annotations.
+/// The source's evidence is carried forward by reference, not duplicated.
+#[tauri::command]
+pub async fn clone_assessment(
+    db: State<'_, Database>,
+    assessment_id: String,
+    new_name: String,
+    reset_statuses: bool,
+) -> Result<Assessment, String> {
+    let name = new_name.trim();
+    if name.is_empty() {
+        return Err("Assessment name is required".to_string());
+    }
+
+    let repo = AssessmentRepository::new(&db);
+    let source = repo.get(&assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment not found: {}", assessment_id))?;
+
+    let assessment = Assessment {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        started_at: Utc::now(),
+        completed_at: None,
+        status: AssessmentStatus::Draft,
+        source_assessment_id: Some(source.id.clone()),
+        ..source.clone()
+    };
+
+    let controls: Vec<ControlAssessment> = ControlAssessmentRepository::new(&db)
+        .get_by_assessment(&source.id)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|ca| ca.carry_forward(&assessment.id, reset_statuses))
+        .collect();
+    let evidence_ids: Vec<String> = EvidenceRepository::new(&db)
+        .get_by_assessment(&source.id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|e| e.id)
+        .collect();
+
+    repo.create_clone(&assessment, &controls, &evidence_ids).map_err(|e| e.to_string())?;
+    activity::log(&db, ActivityEvent::new(
+        &assessment.client_id,
+        ActivityEventType::AssessmentCreated,
+        "assessment",
+        Some(&assessment.id),
+        format!("Cloned assessment \"{}\" from \"{}\"", assessment.name, source.name),
+    )
+    .by(&assessment.lead_assessor)
+    .with_detail(serde_json::json!({
+        "sourceAssessmentId": source.id,
+        "resetStatuses": reset_statuses,
+        "controls": controls.len(),
+        "carriedEvidence": evidence_ids.len(),
+    })));
+
+    Ok(assessment)
+}
+
+/// Reject changes to a completed or archived assessment
+fn ensure_assessment_editable(db: &Database, assessment_id: &str) -> Result<(), String> {
+    let assessment = AssessmentRepository::new(db)
+        .get(assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment not found: {}", assessment_id))?;
+
+    if assessment.status.is_locked() {
+        return Err(format!(
+            "Assessment \"{}\" is {}; reopen it before making changes",
+            assessment.name,
+            assessment.status.display_name()
+        ));
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Control Assessment Commands
 // ============================================================================
@@ -141,16 +360,31 @@ pub struct UpdateControlAssessmentRequest {
     pub remediation: Option<String>,
     pub remediation_target: Option<String>,
     pub risk_rating: Option<u8>,
+    /// Maturity level, 1 (Initial) to 5 (Optimizing)
+    pub maturity_level: Option<u8>,
     pub assessed_by: String,
+    /// Raise a register risk for serious gaps (default true)
+    pub create_risk: Option<bool>,
 }
 
+/// Risk rating at or above which a non-compliant control raises a register risk
+const AUTO_RISK_MIN_RATING: u8 = 4;
+
 /// Update a control's assessment status
+///
+/// A NonCompliant control with a risk rating of 4 or more also raises an
+/// Open risk in the client's register, unless one is already tracking the
+/// control or the request opts out.
 #[tauri::command]
 pub async fn update_control_assessment(
     db: State<'_, Database>,
     request: UpdateControlAssessmentRequest,
 ) -> Result<ControlAssessment, String> {
     let status = parse_compliance_status_param(&request.status)?;
+    if let Some(level) = request.maturity_level {
+        validate_maturity_level(level)?;
+    }
+    ensure_assessment_editable(&db, &request.assessment_id)?;
 
     let remediation_target = request.remediation_target
         .map(|s| chrono::DateTime::parse_from_rfc3339(&s)
@@ -168,6 +402,7 @@ pub async fn update_control_assessment(
         remediation: request.remediation,
         remediation_target,
         risk_rating: request.risk_rating,
+        maturity_level: request.maturity_level,
         evidence_ids: vec![],
         assessed_at: Utc::now(),
         assessed_by: request.assessed_by,
@@ -175,10 +410,80 @@ pub async fn update_control_assessment(
 
     let repo = ControlAssessmentRepository::new(&db);
     repo.upsert(&ca).map_err(|e| e.to_string())?;
+    onboarding::advance(&db, &[Milestone::TenControlsAssessed]);
+    log_control_activity(&db, &ca.assessment_id, std::slice::from_ref(&ca.control_id), ca.status, &ca.assessed_by);
+
+    let serious_gap = ca.status == ComplianceStatus::NonCompliant
+        && ca.risk_rating.is_some_and(|r| r >= AUTO_RISK_MIN_RATING);
+    if serious_gap && request.create_risk.unwrap_or(true) {
+        raise_control_risk(&db, &ca);
+    }
 
     Ok(ca)
 }
 
+/// Open a register risk for a non-compliant control
+///
+/// Best-effort, like activity logging: failures are logged and never fail
+/// the control update.
+fn raise_control_risk(db: &Database, ca: &ControlAssessment) {
+    let assessment = match AssessmentRepository::new(db).get(&ca.assessment_id) {
+        Ok(Some(assessment)) => assessment,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Failed to look up assessment {}: {}", ca.assessment_id, e);
+            return;
+        }
+    };
+
+    let repo = RiskRepository::new(db);
+    match repo.find_active_for_control(&assessment.client_id, &ca.control_id) {
+        Ok(Some(_)) => return,
+        Ok(None) => {}
+        Err(e) => {
+            tracing::warn!("Failed to check risks for control {}: {}", ca.control_id, e);
+            return;
+        }
+    }
+
+    let control_title = get_framework_controls(assessment.framework)
+        .into_iter()
+        .find(|c| c.id == ca.control_id || c.code == ca.control_id)
+        .map(|c| c.title)
+        .unwrap_or_else(|| ca.control_id.clone());
+    let rating = ca.risk_rating.unwrap_or(AUTO_RISK_MIN_RATING).clamp(1, 5);
+    let now = Utc::now();
+
+    let mut risk = RiskItem {
+        id: Uuid::new_v4().to_string(),
+        client_id: assessment.client_id,
+        assessment_id: Some(ca.assessment_id.clone()),
+        title: format!("{}: {}", ca.control_id, control_title),
+        description: ca.gap_description.clone()
+            .or_else(|| ca.notes.clone())
+            .unwrap_or_else(|| format!("Control {} assessed as non-compliant", ca.control_id)),
+        likelihood: rating,
+        impact: rating,
+        inherent_score: 0,
+        residual_likelihood: None,
+        residual_impact: None,
+        residual_score: None,
+        owner: None,
+        status: RiskStatus::Open,
+        control_ids: vec![ca.control_id.clone()],
+        finding_ids: vec![],
+        due_date: ca.remediation_target,
+        created_at: now,
+        updated_at: now,
+    };
+    risk.rescore();
+
+    match repo.save(&risk) {
+        Ok(()) => tracing::info!("Raised risk {} for non-compliant control {}", risk.id, ca.control_id),
+        Err(e) => tracing::warn!("Failed to raise risk for control {}: {}", ca.control_id, e),
+    }
+}
+
 /// Get all control assessments for an assessment
 #[tauri::command]
 pub async fn get_control_assessments(
@@ -189,6 +494,41 @@ pub async fn get_control_assessments(
     repo.get_by_assessment(&assessment_id).map_err(|e| e.to_string())
 }
 
+/// Every saved version of a control's assessment, oldest first
+#[tauri::command]
+pub async fn get_control_history(
+    db: State<'_, Database>,
+    assessment_id: String,
+    control_id: String,
+) -> Result<Vec<ControlAssessmentRevision>, String> {
+    ControlAssessmentRepository::new(&db)
+        .history(&assessment_id, &control_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Make an earlier revision of a control's assessment current again
+///
+/// The revision is copied forward as the newest, so the history keeps the
+/// change that was undone.
+#[tauri::command]
+pub async fn revert_control_assessment(
+    db: State<'_, Database>,
+    assessment_id: String,
+    control_id: String,
+    revision: u32,
+    reverted_by: String,
+) -> Result<ControlAssessment, String> {
+    ensure_assessment_editable(&db, &assessment_id)?;
+
+    let ca = ControlAssessmentRepository::new(&db)
+        .revert(&assessment_id, &control_id, revision, &reverted_by)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Control {} has no revision {}", control_id, revision))?;
+    log_control_activity(&db, &ca.assessment_id, std::slice::from_ref(&ca.control_id), ca.status, &ca.assessed_by);
+
+    Ok(ca)
+}
+
 /// Batch update control assessments request
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -206,6 +546,7 @@ pub async fn batch_update_controls(
     request: BatchUpdateControlsRequest,
 ) -> Result<usize, String> {
     let status = parse_compliance_status_param(&request.status)?;
+    ensure_assessment_editable(&db, &request.assessment_id)?;
     let repo = ControlAssessmentRepository::new(&db);
 
     let mut updated = 0;
@@ -220,6 +561,7 @@ pub async fn batch_update_controls(
             remediation: None,
             remediation_target: None,
             risk_rating: None,
+            maturity_level: None,
             evidence_ids: vec![],
             assessed_at: Utc::now(),
             assessed_by: request.assessed_by.clone(),
@@ -227,6 +569,8 @@ pub async fn batch_update_controls(
         repo.upsert(&ca).map_err(|e| e.to_string())?;
         updated += 1;
     }
+    onboarding::advance(&db, &[Milestone::TenControlsAssessed]);
+    log_control_activity(&db, &request.assessment_id, &request.control_ids, status, &request.assessed_by);
 
     Ok(updated)
 }
@@ -257,6 +601,7 @@ pub async fn create_evidence(
     request: CreateEvidenceRequest,
 ) -> Result<Evidence, String> {
     let evidence_type = parse_evidence_type_param(&request.evidence_type)?;
+    ensure_assessment_editable(&db, &request.assessment_id)?;
 
     let evidence = Evidence {
         id: Uuid::new_v4().to_string(),
@@ -271,11 +616,28 @@ pub async fn create_evidence(
         collected_at: Utc::now(),
         collected_by: request.collected_by,
         notes: request.notes,
+        carried_forward: false,
     };
 
     let repo = EvidenceRepository::new(&db);
     repo.create(&evidence).map_err(|e| e.to_string())?;
 
+    if let Some(client_id) = assessment_client_id(&db, &evidence.assessment_id) {
+        activity::log(&db, ActivityEvent::new(
+            client_id,
+            ActivityEventType::EvidenceAdded,
+            "evidence",
+            Some(&evidence.id),
+            format!("Added evidence \"{}\"", evidence.title),
+        )
+        .by(&evidence.collected_by)
+        .with_detail(serde_json::json!({
+            "assessmentId": evidence.assessment_id,
+            "evidenceType": evidence.evidence_type,
+            "controlIds": evidence.control_ids,
+        })));
+    }
+
     Ok(evidence)
 }
 
@@ -296,7 +658,137 @@ pub async fn delete_evidence(
     id: String,
 ) -> Result<bool, String> {
     let repo = EvidenceRepository::new(&db);
-    repo.delete(&id).map_err(|e| e.to_string())
+    let evidence = repo.get(&id).map_err(|e| e.to_string())?;
+    if let Some(evidence) = &evidence {
+        ensure_assessment_editable(&db, &evidence.assessment_id)?;
+    }
+    let deleted = repo.delete(&id).map_err(|e| e.to_string())?;
+
+    if let Some(evidence) = evidence.filter(|_| deleted) {
+        if let Some(client_id) = assessment_client_id(&db, &evidence.assessment_id) {
+            activity::log(&db, ActivityEvent::new(
+                client_id,
+                ActivityEventType::EvidenceRemoved,
+                "evidence",
+                Some(&evidence.id),
+                format!("Removed evidence \"{}\"", evidence.title),
+            )
+            .with_detail(serde_json::json!({
+                "assessmentId": evidence.assessment_id,
+                "evidenceType": evidence.evidence_type,
+            })));
+        }
+    }
+
+    Ok(deleted)
+}
+
+/// Get the evidence in an assessment linked to a control
+#[tauri::command]
+pub async fn get_evidence_for_control(
+    db: State<'_, Database>,
+    assessment_id: String,
+    control_id: String,
+) -> Result<Vec<Evidence>, String> {
+    let repo = EvidenceRepository::new(&db);
+    repo.get_by_control(&assessment_id, &control_id).map_err(|e| e.to_string())
+}
+
+/// Evidence coverage of every control in an assessment's framework
+#[tauri::command]
+pub async fn get_evidence_coverage(
+    db: State<'_, Database>,
+    assessment_id: String,
+) -> Result<EvidenceCoverage, String> {
+    evidence_coverage(&db, &assessment_id)
+}
+
+pub(crate) fn evidence_coverage(db: &Database, assessment_id: &str) -> Result<EvidenceCoverage, String> {
+    let assessment = AssessmentRepository::new(db)
+        .get(assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment not found: {}", assessment_id))?;
+    let control_assessments = ControlAssessmentRepository::new(db)
+        .get_by_assessment(assessment_id)
+        .map_err(|e| e.to_string())?;
+    let evidence = EvidenceRepository::new(db)
+        .get_by_assessment(assessment_id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(build_evidence_coverage(&assessment, &control_assessments, &evidence))
+}
+
+/// Link existing evidence to more controls
+#[tauri::command]
+pub async fn link_evidence_to_controls(
+    db: State<'_, Database>,
+    evidence_id: String,
+    control_ids: Vec<String>,
+) -> Result<Evidence, String> {
+    let repo = EvidenceRepository::new(&db);
+    let evidence = repo.get(&evidence_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Evidence not found: {}", evidence_id))?;
+    ensure_assessment_editable(&db, &evidence.assessment_id)?;
+
+    repo.link_controls(&evidence_id, &control_ids).map_err(|e| e.to_string())?;
+    repo.get(&evidence_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Evidence not found: {}", evidence_id))
+}
+
+/// Remove evidence links to controls
+#[tauri::command]
+pub async fn unlink_evidence_from_controls(
+    db: State<'_, Database>,
+    evidence_id: String,
+    control_ids: Vec<String>,
+) -> Result<Evidence, String> {
+    let repo = EvidenceRepository::new(&db);
+    if let Some(evidence) = repo.get(&evidence_id).map_err(|e| e.to_string())? {
+        ensure_assessment_editable(&db, &evidence.assessment_id)?;
+    }
+    repo.unlink_controls(&evidence_id, &control_ids).map_err(|e| e.to_string())?;
+    repo.get(&evidence_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Evidence not found: {}", evidence_id))
+}
+
+/// Client that owns an assessment, for attributing activity
+fn assessment_client_id(db: &Database, assessment_id: &str) -> Option<String> {
+    match AssessmentRepository::new(db).get(assessment_id) {
+        Ok(assessment) => assessment.map(|a| a.client_id),
+        Err(e) => {
+            tracing::warn!("Failed to look up assessment {}: {}", assessment_id, e);
+            None
+        }
+    }
+}
+
+/// Record single or batch control assessment updates
+fn log_control_activity(
+    db: &Database,
+    assessment_id: &str,
+    control_ids: &[String],
+    status: ComplianceStatus,
+    assessed_by: &str,
+) {
+    let client_id = match assessment_client_id(db, assessment_id) {
+        Some(id) => id,
+        None => return,
+    };
+
+    let summary = match control_ids {
+        [control_id] => format!("Assessed {} as {}", control_id, status.display_name()),
+        _ => format!("Assessed {} controls as {}", control_ids.len(), status.display_name()),
+    };
+
+    activity::log(db, ActivityEvent::new(client_id, ActivityEventType::ControlAssessed, "assessment", Some(assessment_id), summary)
+        .by(assessed_by)
+        .with_detail(serde_json::json!({
+            "controlIds": control_ids,
+            "status": status,
+        })));
 }
 
 // ============================================================================
@@ -304,6 +796,8 @@ pub async fn delete_evidence(
 // ============================================================================
 
 /// Get assessment summary with compliance scores
+///
+/// A cloned assessment also carries its comparison against the source.
 #[tauri::command]
 pub async fn get_assessment_summary(
     db: State<'_, Database>,
@@ -315,11 +809,61 @@ pub async fn get_assessment_summary(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| "Assessment not found".to_string())?;
 
+    let mut summary = summarize_assessment(&db, &assessment)?;
+    summary.source_comparison = source_comparison(&db, &assessment, &summary)?;
+    Ok(summary)
+}
+
+/// Compare two assessments of the same framework, `id_a` being the earlier one
+///
+/// Reports the change in compliant controls per category, for year-over-year
+/// progress.
+#[tauri::command]
+pub async fn compare_assessments(
+    db: State<'_, Database>,
+    id_a: String,
+    id_b: String,
+) -> Result<AssessmentComparison, String> {
+    let repo = AssessmentRepository::new(&db);
+    let load = |id: &str| -> Result<Assessment, String> {
+        repo.get(id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Assessment not found: {}", id))
+    };
+    let baseline = summarize_assessment(&db, &load(&id_a)?)?;
+    let current = summarize_assessment(&db, &load(&id_b)?)?;
+
+    AssessmentComparison::between(&baseline, &current)
+}
+
+/// Comparison of a cloned assessment against the one it was cloned from
+///
+/// None when the assessment isn't a clone or its source has since been deleted.
+pub(crate) fn source_comparison(
+    db: &Database,
+    assessment: &Assessment,
+    summary: &AssessmentSummary,
+) -> Result<Option<AssessmentComparison>, String> {
+    let source_id = match assessment.source_assessment_id {
+        Some(ref id) => id,
+        None => return Ok(None),
+    };
+    let source = match AssessmentRepository::new(db).get(source_id).map_err(|e| e.to_string())? {
+        Some(source) => source,
+        None => return Ok(None),
+    };
+
+    let baseline = summarize_assessment(db, &source)?;
+    AssessmentComparison::between(&baseline, summary).map(Some)
+}
+
+pub(crate) fn summarize_assessment(db: &Database, assessment: &Assessment) -> Result<AssessmentSummary, String> {
+    let assessment_id = assessment.id.clone();
     let controls = get_framework_controls(assessment.framework);
-    let control_repo = ControlAssessmentRepository::new(&db);
+    let control_repo = ControlAssessmentRepository::new(db);
     let assessments = control_repo.get_by_assessment(&assessment_id).map_err(|e| e.to_string())?;
 
-    let evidence_repo = EvidenceRepository::new(&db);
+    let evidence_repo = EvidenceRepository::new(db);
     let evidence_count = evidence_repo.count_by_assessment(&assessment_id).map_err(|e| e.to_string())?;
 
     // Build assessment map
@@ -339,6 +883,7 @@ pub async fn get_assessment_summary(
     // Category breakdown
     let mut category_stats: std::collections::HashMap<String, (String, String, usize, usize, usize, usize, usize, usize)> =
         std::collections::HashMap::new();
+    let mut category_maturity: std::collections::HashMap<String, Vec<u8>> = std::collections::HashMap::new();
 
     for control in &controls {
         let status = assessment_map
@@ -350,6 +895,10 @@ pub async fn get_assessment_summary(
             .get(&control.id)
             .and_then(|ca| ca.risk_rating);
 
+        if let Some(level) = assessment_map.get(&control.id).and_then(|ca| ca.rated_maturity()) {
+            category_maturity.entry(control.category.clone()).or_default().push(level);
+        }
+
         match status {
             ComplianceStatus::Compliant => compliant += 1,
             ComplianceStatus::PartiallyCompliant => partially_compliant += 1,
@@ -382,7 +931,7 @@ pub async fn get_assessment_summary(
     }
 
     // Calculate category scores
-    let category_scores: Vec<CategoryScore> = category_stats
+    let mut category_scores: Vec<CategoryScore> = category_stats
         .into_iter()
         .map(|(cat, (name, color, total, comp, partial, non_comp, not_ass, na))| {
             let applicable = total - na;
@@ -391,6 +940,7 @@ pub async fn get_assessment_summary(
             } else {
                 100.0
             };
+            let maturity = average_maturity(category_maturity.get(&cat).cloned().unwrap_or_default());
 
             CategoryScore {
                 category: cat,
@@ -403,10 +953,18 @@ pub async fn get_assessment_summary(
                 not_assessed: not_ass,
                 not_applicable: na,
                 compliance_percentage: (score * 10.0).round() / 10.0,
+                average_maturity: maturity,
             }
         })
         .collect();
 
+    // Present categories in the framework's canonical order
+    let category_order: HashMap<String, u32> = get_framework_categories(assessment.framework)
+        .into_iter()
+        .map(|c| (c.code, c.order))
+        .collect();
+    category_scores.sort_by_key(|c| category_order.get(&c.category).copied().unwrap_or(u32::MAX));
+
     // Calculate overall compliance
     let total = controls.len();
     let applicable = total - not_applicable;
@@ -426,180 +984,904 @@ pub async fn get_assessment_summary(
         non_compliant,
         not_assessed,
         not_applicable,
+        average_maturity: average_maturity(category_maturity.into_values().flatten()),
         category_scores,
         high_risk_gaps,
         evidence_count,
+        source_comparison: None,
     })
 }
 
+// ============================================================================
+// Assessment Snapshot Commands
+// ============================================================================
+
+/// Freeze an assessment's summary and control results under a label
+///
+/// Reports generated from the snapshot keep the figures it was taken with,
+/// however the assessment changes afterwards.
+#[tauri::command]
+pub async fn snapshot_assessment(
+    db: State<'_, Database>,
+    assessment_id: String,
+    label: String,
+) -> Result<AssessmentSnapshot, String> {
+    if label.trim().is_empty() {
+        return Err("Snapshot label is required".to_string());
+    }
+    let assessment = AssessmentRepository::new(&db)
+        .get(&assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Assessment not found".to_string())?;
+
+    let mut summary = summarize_assessment(&db, &assessment)?;
+    summary.source_comparison = source_comparison(&db, &assessment, &summary)?;
+    let control_assessments = ControlAssessmentRepository::new(&db)
+        .get_by_assessment(&assessment.id)
+        .map_err(|e| e.to_string())?;
+
+    let snapshot = AssessmentSnapshot::new(&assessment, &label, summary, control_assessments, Utc::now());
+    AssessmentSnapshotRepository::new(&db).create(&snapshot).map_err(|e| e.to_string())?;
+    Ok(snapshot)
+}
+
+/// List an assessment's snapshots, oldest first
+#[tauri::command]
+pub async fn list_snapshots(
+    db: State<'_, Database>,
+    assessment_id: String,
+) -> Result<Vec<AssessmentSnapshot>, String> {
+    AssessmentSnapshotRepository::new(&db)
+        .list_by_assessment(&assessment_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get an assessment snapshot
+#[tauri::command]
+pub async fn get_snapshot(db: State<'_, Database>, id: String) -> Result<AssessmentSnapshot, String> {
+    load_snapshot(&db, &id)
+}
+
+/// Compare two snapshots of the same framework, `id_a` being the earlier one
+///
+/// Reports the per-category compliance deltas and every control whose status
+/// changed in between.
+#[tauri::command]
+pub async fn compare_snapshots(
+    db: State<'_, Database>,
+    id_a: String,
+    id_b: String,
+) -> Result<SnapshotComparison, String> {
+    let baseline = load_snapshot(&db, &id_a)?;
+    let current = load_snapshot(&db, &id_b)?;
+    SnapshotComparison::between(&baseline, &current)
+}
+
+pub(crate) fn load_snapshot(db: &Database, id: &str) -> Result<AssessmentSnapshot, String> {
+    AssessmentSnapshotRepository::new(db)
+        .get(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment snapshot not found: {}", id))
+}
+
 // ============================================================================
 // Compliance Status Command (Task A)
 // ============================================================================
 
 /// Get compliance status for a specific framework
-/// Returns the overall completion and compliance percentages for all NIST CSF categories
+/// Returns the overall completion and compliance percentages for all NIST CSF categories.
+/// With `grouped` set, also rolls the categories up into the client's category groups.
 #[tauri::command]
 pub async fn get_compliance_status(
     db: State<'_, Database>,
     framework: String,
     client_id: Option<String>,
+    grouped: Option<bool>,
 ) -> Result<ComplianceStatusReport, String> {
     let fw = parse_framework_param(&framework)?;
-    let controls = get_framework_controls(fw);
-    let categories = get_framework_categories(fw);
+    let mut report = current_compliance_status(&db, fw, client_id.as_deref()).map_err(|e| e.to_string())?;
+
+    if grouped.unwrap_or(false) {
+        let cid = client_id
+            .as_deref()
+            .ok_or_else(|| "Category grouping requires a client".to_string())?;
+        let groups = CategoryGroupingRepository::new(&db)
+            .groups_for(cid, fw)
+            .map_err(|e| e.to_string())?;
+        report.group_breakdown = rollup_category_groups(&report.category_breakdown, &groups);
+    }
 
-    // Get all assessments for this framework
-    let assessment_repo = AssessmentRepository::new(&db);
-    let control_repo = ControlAssessmentRepository::new(&db);
+    Ok(report)
+}
 
-    let assessments = if let Some(ref cid) = client_id {
-        assessment_repo.list_by_client(cid).map_err(|e| e.to_string())?
-    } else {
-        assessment_repo.list_all().map_err(|e| e.to_string())?
-    };
+/// Snapshot a client's current compliance status for a framework
+///
+/// Snapshots taken within the same hour replace each other.
+#[tauri::command]
+pub async fn record_compliance_snapshot(
+    db: State<'_, Database>,
+    client_id: String,
+    framework: String,
+) -> Result<ComplianceSnapshot, String> {
+    let fw = parse_framework_param(&framework)?;
+    snapshot_compliance(&db, &client_id, fw).map_err(|e| e.to_string())
+}
 
-    // Filter to only this framework
-    let framework_assessments: Vec<_> = assessments
-        .into_iter()
-        .filter(|a| a.framework == fw)
-        .collect();
+/// Get a client's compliance over time for a framework, from `since` (RFC 3339) if given
+#[tauri::command]
+pub async fn get_compliance_trend(
+    db: State<'_, Database>,
+    client_id: String,
+    framework: String,
+    since: Option<String>,
+) -> Result<ComplianceTrend, String> {
+    let fw = parse_framework_param(&framework)?;
+    let since = since
+        .map(|s| chrono::DateTime::parse_from_rfc3339(&s)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|e| format!("Invalid date: {}", e)))
+        .transpose()?;
 
-    // Collect all control assessments across all assessments
-    let mut all_control_assessments: HashMap<String, ControlAssessment> = HashMap::new();
-    for assessment in &framework_assessments {
-        let cas = control_repo.get_by_assessment(&assessment.id).map_err(|e| e.to_string())?;
-        for ca in cas {
-            // Keep the most recent assessment for each control
-            all_control_assessments.insert(ca.control_id.clone(), ca);
-        }
-    }
+    let snapshots = ComplianceSnapshotRepository::new(&db)
+        .list(&client_id, fw, since)
+        .map_err(|e| e.to_string())?;
+    Ok(build_compliance_trend(&client_id, fw, &snapshots))
+}
 
-    // Calculate overall stats
-    let mut total_assessed = 0;
-    let mut total_compliant = 0;
-    let mut total_partial = 0;
-    let mut total_non_compliant = 0;
-    let mut total_na = 0;
+/// Get a client's compliance across every framework it is assessed against
+///
+/// `weights` (framework name to weight) replaces weighting by applicable
+/// assessed controls; frameworks without an entry weigh 1.
+#[tauri::command]
+pub async fn get_client_compliance_overview(
+    db: State<'_, Database>,
+    client_id: String,
+    weights: Option<HashMap<String, f64>>,
+) -> Result<ComplianceOverview, String> {
+    let weights = weights
+        .map(|w| {
+            w.into_iter()
+                .map(|(framework, weight)| {
+                    if !weight.is_finite() || weight < 0.0 {
+                        return Err(format!("Invalid weight for {}: {}", framework, weight));
+                    }
+                    Ok((parse_framework_param(&framework)?, weight))
+                })
+                .collect::<Result<HashMap<_, _>, String>>()
+        })
+        .transpose()?;
 
-    // Build category breakdown
-    let mut category_map: HashMap<String, (usize, usize, usize, usize, usize, usize)> = HashMap::new();
+    build_compliance_overview(&db, &client_id, weights.as_ref()).map_err(|e| e.to_string())
+}
 
-    for control in &controls {
-        let status = all_control_assessments
-            .get(&control.id)
-            .map(|ca| ca.status)
-            .unwrap_or(ComplianceStatus::NotAssessed);
+// ============================================================================
+// Cross-Framework Projection
+// ============================================================================
 
-        let entry = category_map.entry(control.category.clone()).or_insert((0, 0, 0, 0, 0, 0));
-        entry.0 += 1; // total
+/// Get the mappings from one framework's controls onto another's
+#[tauri::command]
+pub async fn get_control_mappings_cmd(
+    from_framework: String,
+    to_framework: String,
+) -> Result<Vec<ControlMapping>, String> {
+    let from = parse_framework_param(&from_framework)?;
+    let to = parse_framework_param(&to_framework)?;
+    Ok(get_control_mappings(from, to))
+}
 
-        match status {
-            ComplianceStatus::NotAssessed => {
-                // Not assessed - counts towards total but not assessed count
-            }
-            ComplianceStatus::Compliant => {
-                total_assessed += 1;
-                total_compliant += 1;
-                entry.1 += 1; // assessed
-                entry.2 += 1; // compliant
-            }
-            ComplianceStatus::PartiallyCompliant => {
-                total_assessed += 1;
-                total_partial += 1;
-                entry.1 += 1;
-                entry.3 += 1; // partial
-            }
-            ComplianceStatus::NonCompliant => {
-                total_assessed += 1;
-                total_non_compliant += 1;
-                entry.1 += 1;
-                entry.4 += 1; // non-compliant
-            }
-            ComplianceStatus::NotApplicable => {
-                total_assessed += 1;
-                total_na += 1;
-                entry.1 += 1;
-                entry.5 += 1; // n/a
-            }
-        }
+/// Project an assessment's control statuses onto another framework
+///
+/// Target controls whose mapped source controls are all compliant are
+/// inherited as compliant; target controls with no mapping are flagged.
+#[tauri::command]
+pub async fn project_assessment_to_framework(
+    db: State<'_, Database>,
+    assessment_id: String,
+    target_framework: String,
+) -> Result<FrameworkProjection, String> {
+    let target = parse_framework_param(&target_framework)?;
+
+    let assessment = AssessmentRepository::new(&db)
+        .get(&assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Assessment not found".to_string())?;
+
+    if assessment.framework == target {
+        return Err("Target framework must differ from the assessment's framework".to_string());
     }
 
-    // Build category breakdown response
-    let category_breakdown: Vec<CategoryComplianceStatus> = categories
+    let control_assessments = ControlAssessmentRepository::new(&db)
+        .get_by_assessment(&assessment_id)
+        .map_err(|e| e.to_string())?;
+
+    // Key source statuses by control code, accepting either id or code
+    let source_controls = get_framework_controls(assessment.framework);
+    let source_statuses: HashMap<String, ComplianceStatus> = control_assessments
         .iter()
-        .map(|cat| {
-            let stats = category_map.get(&cat.code).copied().unwrap_or((0, 0, 0, 0, 0, 0));
-            let (total, assessed, compliant, partial, non_comp, na) = stats;
+        .filter_map(|ca| {
+            source_controls
+                .iter()
+                .find(|c| c.id == ca.control_id || c.code == ca.control_id)
+                .map(|c| (c.code.clone(), ca.status))
+        })
+        .collect();
 
-            let completion_pct = if total > 0 {
-                (assessed as f64 / total as f64) * 100.0
-            } else {
-                0.0
-            };
+    let projected = project_control_statuses(assessment.framework, target, &source_statuses);
+    let target_controls = get_framework_controls(target);
+    let report = build_compliance_report(target, &target_controls, |control| {
+        projected
+            .iter()
+            .find(|p| p.code == control.code)
+            .map(|p| p.status)
+            .unwrap_or(ComplianceStatus::NotAssessed)
+    });
+
+    Ok(FrameworkProjection {
+        assessment_id,
+        source_framework: assessment.framework,
+        report,
+        inherited_compliant_controls: projected.iter().filter(|p| p.inherited_compliant).count(),
+        unmapped_controls: projected
+            .iter()
+            .filter(|p| p.source_controls.is_empty())
+            .map(|p| p.code.clone())
+            .collect(),
+        controls: projected,
+    })
+}
 
-            let applicable = assessed - na;
-            let compliance_pct = if applicable > 0 {
-                ((compliant as f64 + partial as f64 * 0.5) / applicable as f64) * 100.0
-            } else {
-                0.0
-            };
+// ============================================================================
+// Category Grouping Commands
+// ============================================================================
 
-            CategoryComplianceStatus {
-                code: cat.code.clone(),
-                name: cat.name.clone(),
-                description: cat.description.clone(),
-                color: cat.color.clone(),
-                total_controls: total,
-                assessed_controls: assessed,
-                compliant,
-                partially_compliant: partial,
-                non_compliant: non_comp,
-                completion_percentage: (completion_pct * 10.0).round() / 10.0,
-                compliance_percentage: (compliance_pct * 10.0).round() / 10.0,
-            }
-        })
-        .collect();
+/// Request to set a client's category grouping for a framework
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCategoryGroupingRequest {
+    pub client_id: String,
+    pub framework: String,
+    pub groups: Vec<CategoryGroup>,
+}
 
-    let total_controls = controls.len();
-    let completion_percentage = if total_controls > 0 {
-        (total_assessed as f64 / total_controls as f64) * 100.0
-    } else {
-        0.0
+/// Create or replace a client's category grouping for a framework
+#[tauri::command]
+pub async fn set_category_grouping(
+    db: State<'_, Database>,
+    request: SetCategoryGroupingRequest,
+) -> Result<CategoryGrouping, String> {
+    let fw = parse_framework_param(&request.framework)?;
+    validate_category_groups(fw, &request.groups).map_err(|e| e.to_string())?;
+
+    let repo = CategoryGroupingRepository::new(&db);
+    let existing = repo.get(&request.client_id, fw).map_err(|e| e.to_string())?;
+
+    let now = Utc::now();
+    let grouping = CategoryGrouping {
+        id: existing.as_ref().map(|g| g.id.clone()).unwrap_or_else(|| Uuid::new_v4().to_string()),
+        client_id: request.client_id,
+        framework: fw,
+        groups: request.groups,
+        created_at: existing.map(|g| g.created_at).unwrap_or(now),
+        updated_at: now,
     };
 
-    let applicable = total_assessed - total_na;
-    let compliance_percentage = if applicable > 0 {
-        ((total_compliant as f64 + total_partial as f64 * 0.5) / applicable as f64) * 100.0
-    } else {
-        0.0
+    repo.upsert(&grouping).map_err(|e| e.to_string())?;
+
+    tracing::info!("Saved category grouping for client {} ({})", grouping.client_id, fw);
+    Ok(grouping)
+}
+
+/// Get a client's category grouping for a framework, if one has been configured
+#[tauri::command]
+pub async fn get_category_grouping(
+    db: State<'_, Database>,
+    client_id: String,
+    framework: String,
+) -> Result<Option<CategoryGrouping>, String> {
+    let fw = parse_framework_param(&framework)?;
+    let repo = CategoryGroupingRepository::new(&db);
+    repo.get(&client_id, fw).map_err(|e| e.to_string())
+}
+
+/// Remove a client's category grouping, reverting to native categories
+#[tauri::command]
+pub async fn delete_category_grouping(
+    db: State<'_, Database>,
+    client_id: String,
+    framework: String,
+) -> Result<bool, String> {
+    let fw = parse_framework_param(&framework)?;
+    let repo = CategoryGroupingRepository::new(&db);
+    repo.delete(&client_id, fw).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Risk Register Commands
+// ============================================================================
+
+/// Create or update a risk request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveRiskRequest {
+    pub client_id: String,
+    pub assessment_id: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub likelihood: u8,
+    pub impact: u8,
+    pub residual_likelihood: Option<u8>,
+    pub residual_impact: Option<u8>,
+    pub owner: Option<String>,
+    pub status: Option<String>,
+    pub control_ids: Option<Vec<String>>,
+    pub finding_ids: Option<Vec<String>>,
+    pub due_date: Option<String>,
+}
+
+/// Add a risk to a client's register
+#[tauri::command]
+pub async fn create_risk(
+    db: State<'_, Database>,
+    request: SaveRiskRequest,
+) -> Result<RiskItem, String> {
+    let now = Utc::now();
+    let risk = risk_from_request(Uuid::new_v4().to_string(), now, request)?;
+
+    RiskRepository::new(&db).save(&risk).map_err(|e| e.to_string())?;
+    tracing::info!("Created risk {} (score {})", risk.id, risk.inherent_score);
+
+    Ok(risk)
+}
+
+/// List a client's risks, highest inherent score first
+#[tauri::command]
+pub async fn list_risks(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<RiskItem>, String> {
+    RiskRepository::new(&db).list_by_client(&client_id).map_err(|e| e.to_string())
+}
+
+/// Get a risk by ID
+#[tauri::command]
+pub async fn get_risk(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<Option<RiskItem>, String> {
+    RiskRepository::new(&db).get(&id).map_err(|e| e.to_string())
+}
+
+/// Replace a risk's details
+#[tauri::command]
+pub async fn update_risk(
+    db: State<'_, Database>,
+    id: String,
+    request: SaveRiskRequest,
+) -> Result<RiskItem, String> {
+    let repo = RiskRepository::new(&db);
+    let existing = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Risk not found: {}", id))?;
+
+    let mut risk = risk_from_request(id, existing.created_at, request)?;
+    if risk.client_id != existing.client_id {
+        return Err("A risk cannot be moved to another client".to_string());
+    }
+    risk.updated_at = Utc::now();
+
+    repo.save(&risk).map_err(|e| e.to_string())?;
+    Ok(risk)
+}
+
+/// Delete a risk
+#[tauri::command]
+pub async fn delete_risk(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    RiskRepository::new(&db).delete(&id).map_err(|e| e.to_string())
+}
+
+/// Bucket a client's active risks into the 5x5 likelihood/impact heatmap
+#[tauri::command]
+pub async fn get_risk_matrix(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<RiskMatrix, String> {
+    let risks = RiskRepository::new(&db).list_by_client(&client_id).map_err(|e| e.to_string())?;
+    Ok(build_risk_matrix(&client_id, &risks))
+}
+
+fn risk_from_request(id: String, created_at: chrono::DateTime<Utc>, request: SaveRiskRequest) -> Result<RiskItem, String> {
+    validate_rating("Likelihood", request.likelihood)?;
+    validate_rating("Impact", request.impact)?;
+    if let Some(likelihood) = request.residual_likelihood {
+        validate_rating("Residual likelihood", likelihood)?;
+    }
+    if let Some(impact) = request.residual_impact {
+        validate_rating("Residual impact", impact)?;
+    }
+    if request.title.trim().is_empty() {
+        return Err("Risk title is required".to_string());
+    }
+
+    let status = match request.status.as_deref() {
+        Some(s) => parse_risk_status_param(s)?,
+        None => RiskStatus::Open,
     };
+    let due_date = request.due_date
+        .map(|s| chrono::DateTime::parse_from_rfc3339(&s)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|e| format!("Invalid date: {}", e)))
+        .transpose()?;
 
-    Ok(ComplianceStatusReport {
-        framework: fw,
-        completion_percentage: (completion_percentage * 10.0).round() / 10.0,
-        compliance_percentage: (compliance_percentage * 10.0).round() / 10.0,
-        total_controls,
-        assessed_controls: total_assessed,
-        compliant_controls: total_compliant,
-        partially_compliant_controls: total_partial,
-        non_compliant_controls: total_non_compliant,
-        not_applicable_controls: total_na,
-        category_breakdown,
-        network_health_score: None,
-        total_assets: None,
-        last_updated: Utc::now().to_rfc3339(),
+    let mut risk = RiskItem {
+        id,
+        client_id: request.client_id,
+        assessment_id: request.assessment_id,
+        title: request.title,
+        description: request.description,
+        likelihood: request.likelihood,
+        impact: request.impact,
+        inherent_score: 0,
+        residual_likelihood: request.residual_likelihood,
+        residual_impact: request.residual_impact,
+        residual_score: None,
+        owner: request.owner.filter(|o| !o.trim().is_empty()),
+        status,
+        control_ids: request.control_ids.unwrap_or_default(),
+        finding_ids: request.finding_ids.unwrap_or_default(),
+        due_date,
+        created_at,
+        updated_at: created_at,
+    };
+    risk.rescore();
+
+    Ok(risk)
+}
+
+// ============================================================================
+// Records of Processing Commands
+// ============================================================================
+
+/// Create or update a processing activity request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveProcessingActivityRequest {
+    pub client_id: String,
+    pub name: String,
+    pub purpose: String,
+    pub lawful_basis: Option<String>,
+    pub data_categories: Option<Vec<String>>,
+    pub data_subject_categories: Option<Vec<String>>,
+    pub recipients: Option<Vec<String>>,
+    pub transfers: Option<Vec<ThirdCountryTransfer>>,
+    pub retention_period: Option<String>,
+    pub security_measures: Option<String>,
+    /// GDPR control codes the activity evidences (e.g. "Art. 30")
+    pub control_codes: Option<Vec<String>>,
+}
+
+/// A saved processing activity and anything worth following up
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingActivityResult {
+    pub activity: ProcessingActivity,
+    /// Gaps that didn't block saving, such as a transfer with no safeguard
+    pub warnings: Vec<String>,
+}
+
+/// Add an activity to a client's record of processing
+#[tauri::command]
+pub async fn create_processing_activity(
+    db: State<'_, Database>,
+    request: SaveProcessingActivityRequest,
+) -> Result<ProcessingActivityResult, String> {
+    let activity = activity_from_request(Uuid::new_v4().to_string(), Utc::now(), request)?;
+    let warnings = validate_processing_activity(&activity)?;
+
+    ProcessingActivityRepository::new(&db).save(&activity).map_err(|e| e.to_string())?;
+    tracing::info!("Created processing activity {}", activity.id);
+
+    Ok(ProcessingActivityResult { activity, warnings })
+}
+
+/// List a client's processing activities by name
+#[tauri::command]
+pub async fn list_processing_activities(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<ProcessingActivity>, String> {
+    ProcessingActivityRepository::new(&db).list_by_client(&client_id).map_err(|e| e.to_string())
+}
+
+/// Get a processing activity by ID
+#[tauri::command]
+pub async fn get_processing_activity(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<Option<ProcessingActivity>, String> {
+    ProcessingActivityRepository::new(&db).get(&id).map_err(|e| e.to_string())
+}
+
+/// Replace a processing activity's details
+#[tauri::command]
+pub async fn update_processing_activity(
+    db: State<'_, Database>,
+    id: String,
+    request: SaveProcessingActivityRequest,
+) -> Result<ProcessingActivityResult, String> {
+    let repo = ProcessingActivityRepository::new(&db);
+    let existing = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Processing activity not found: {}", id))?;
+
+    let mut activity = activity_from_request(id, existing.created_at, request)?;
+    if activity.client_id != existing.client_id {
+        return Err("A processing activity cannot be moved to another client".to_string());
+    }
+    let warnings = validate_processing_activity(&activity)?;
+    activity.updated_at = Utc::now();
+
+    repo.save(&activity).map_err(|e| e.to_string())?;
+    Ok(ProcessingActivityResult { activity, warnings })
+}
+
+/// Delete a processing activity
+#[tauri::command]
+pub async fn delete_processing_activity(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    ProcessingActivityRepository::new(&db).delete(&id).map_err(|e| e.to_string())
+}
+
+fn activity_from_request(
+    id: String,
+    created_at: chrono::DateTime<Utc>,
+    request: SaveProcessingActivityRequest,
+) -> Result<ProcessingActivity, String> {
+    let lawful_basis = request.lawful_basis.as_deref()
+        .filter(|b| !b.trim().is_empty())
+        .ok_or_else(|| "A lawful basis must be selected".to_string())
+        .and_then(parse_lawful_basis_param)?;
+    let non_empty = |items: Option<Vec<String>>| -> Vec<String> {
+        items.unwrap_or_default()
+            .into_iter()
+            .map(|i| i.trim().to_string())
+            .filter(|i| !i.is_empty())
+            .collect()
+    };
+
+    Ok(ProcessingActivity {
+        id,
+        client_id: request.client_id,
+        name: request.name.trim().to_string(),
+        purpose: request.purpose,
+        lawful_basis,
+        data_categories: non_empty(request.data_categories),
+        data_subject_categories: non_empty(request.data_subject_categories),
+        recipients: non_empty(request.recipients),
+        transfers: request.transfers.unwrap_or_default(),
+        retention_period: request.retention_period.filter(|r| !r.trim().is_empty()),
+        security_measures: request.security_measures.filter(|m| !m.trim().is_empty()),
+        control_codes: non_empty(request.control_codes),
+        created_at,
+        updated_at: created_at,
+    })
+}
+
+// ============================================================================
+// Remediation Plan Commands
+// ============================================================================
+
+/// Build and store the remediation plan for an assessment's gaps
+///
+/// Regenerating replaces the plan but keeps owners, due dates and progress
+/// for controls that are still open.
+#[tauri::command]
+pub async fn generate_remediation_plan(
+    db: State<'_, Database>,
+    assessment_id: String,
+    effort_mapping: Option<EffortMapping>,
+) -> Result<RemediationPlan, String> {
+    let assessment = AssessmentRepository::new(&db)
+        .get(&assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment not found: {}", assessment_id))?;
+    let control_assessments = ControlAssessmentRepository::new(&db)
+        .get_by_assessment(&assessment_id)
+        .map_err(|e| e.to_string())?;
+
+    let repo = RemediationPlanRepository::new(&db);
+    let previous = repo.get_by_assessment(&assessment_id).map_err(|e| e.to_string())?;
+    let plan = build_remediation_plan(
+        &assessment,
+        &control_assessments,
+        &effort_mapping.unwrap_or_default(),
+        previous.as_ref(),
+    );
+    repo.save(&plan).map_err(|e| e.to_string())?;
+
+    tracing::info!("Generated remediation plan for assessment {} ({} items)", assessment_id, plan.items().count());
+    Ok(plan)
+}
+
+/// Get the stored remediation plan for an assessment
+#[tauri::command]
+pub async fn get_remediation_plan(
+    db: State<'_, Database>,
+    assessment_id: String,
+) -> Result<Option<RemediationPlan>, String> {
+    RemediationPlanRepository::new(&db)
+        .get_by_assessment(&assessment_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Remediation item edit request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRemediationItemRequest {
+    pub owner: Option<String>,
+    pub due_date: Option<String>,
+    pub status: String,
+}
+
+/// Assign an owner, due date and status to a remediation item
+#[tauri::command]
+pub async fn update_remediation_item(
+    db: State<'_, Database>,
+    item_id: String,
+    request: UpdateRemediationItemRequest,
+) -> Result<RemediationItem, String> {
+    let repo = RemediationPlanRepository::new(&db);
+    let (plan_id, mut item) = repo.get_item(&item_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Remediation item not found: {}", item_id))?;
+
+    item.owner = request.owner.filter(|o| !o.trim().is_empty());
+    item.status = request.status.parse()?;
+    item.set_due_date(request.due_date
+        .map(|s| chrono::DateTime::parse_from_rfc3339(&s)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|e| format!("Invalid date: {}", e)))
+        .transpose()?);
+    item.updated_at = Utc::now();
+
+    repo.update_item(&plan_id, &item).map_err(|e| e.to_string())?;
+    Ok(item)
+}
+
+/// Remove a remediation item from its plan
+#[tauri::command]
+pub async fn delete_remediation_item(
+    db: State<'_, Database>,
+    item_id: String,
+) -> Result<bool, String> {
+    RemediationPlanRepository::new(&db)
+        .delete_item(&item_id)
+        .map_err(|e| e.to_string())
+}
+
+/// A client's open remediation items that are past their due date, across
+/// all assessments
+#[tauri::command]
+pub async fn list_overdue_remediations(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<ClientRemediationItem>, String> {
+    let now = Utc::now();
+    let mut items: Vec<ClientRemediationItem> = RemediationPlanRepository::new(&db)
+        .get_by_client(&client_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|i| i.item.is_overdue(now))
+        .collect();
+    items.sort_by_key(|i| i.item.due_date);
+    Ok(items)
+}
+
+/// Remediation counts by status and priority for a client, across all
+/// assessments
+#[tauri::command]
+pub async fn get_remediation_stats(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<RemediationStats, String> {
+    let items = RemediationPlanRepository::new(&db)
+        .get_by_client(&client_id)
+        .map_err(|e| e.to_string())?;
+    Ok(RemediationStats::from_items(items.iter().map(|i| &i.item), Utc::now()))
+}
+
+/// Export an assessment's stored remediation plan as CSV or Markdown
+#[tauri::command]
+pub async fn export_remediation_plan(
+    db: State<'_, Database>,
+    assessment_id: String,
+    format: String,
+) -> Result<String, String> {
+    let plan = RemediationPlanRepository::new(&db)
+        .get_by_assessment(&assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Generate a remediation plan for this assessment first".to_string())?;
+
+    match format.to_lowercase().as_str() {
+        "csv" => remediation_plan_to_csv(&plan),
+        "markdown" | "md" => {
+            let assessment = AssessmentRepository::new(&db)
+                .get(&assessment_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Assessment not found: {}", assessment_id))?;
+            Ok(remediation_plan_to_markdown(&assessment.name, &plan))
+        }
+        _ => Err(format!("Unsupported remediation plan format: {}", format)),
+    }
+}
+
+// ============================================================================
+// Assessment Transfer Commands
+// ============================================================================
+
+/// Result of exporting an assessment bundle
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssessmentExportResult {
+    pub file_path: String,
+    pub control_assessments: usize,
+    pub evidence: usize,
+    pub evidence_files: usize,
+    /// Evidence files that could not be included
+    pub warnings: Vec<TransferWarning>,
+}
+
+/// Export an assessment to a bundle for another Optio instance
+///
+/// A path ending in `.zip` gets a zip bundle with evidence files as plain
+/// entries; anything else a single JSON file with the files embedded.
+#[tauri::command]
+pub async fn export_assessment(
+    db: State<'_, Database>,
+    assessment_id: String,
+    path: String,
+    include_evidence_files: Option<bool>,
+) -> Result<AssessmentExportResult, String> {
+    let (bundle, warnings) = export_bundle(&db, &assessment_id, include_evidence_files.unwrap_or(false))
+        .map_err(|e| e.to_string())?;
+
+    if path.to_lowercase().ends_with(".zip") {
+        write_bundle_archive(&bundle, Path::new(&path)).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    } else {
+        let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    tracing::info!("Exported assessment {} to {}", assessment_id, path);
+
+    Ok(AssessmentExportResult {
+        file_path: path,
+        control_assessments: bundle.control_assessments.len(),
+        evidence: bundle.evidence.len(),
+        evidence_files: bundle.evidence_files.len(),
+        warnings,
     })
 }
 
+/// Import an assessment bundle, JSON or zip, exported by another Optio instance
+#[tauri::command]
+pub async fn import_assessment(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    path: String,
+    conflict_strategy: String,
+) -> Result<AssessmentImportSummary, String> {
+    let strategy = parse_conflict_strategy_param(&conflict_strategy)?;
+    let bundle = read_bundle(Path::new(&path)).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let evidence_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("evidence")
+        .join(&bundle.assessment.id);
+    let name = bundle.assessment.name.clone();
+    let exported_by = bundle.exported_by.clone();
+
+    let summary = import_bundle(&db, bundle, strategy, &evidence_dir).map_err(|e| e.to_string())?;
+
+    activity::log(&db, ActivityEvent::new(
+        &summary.client_id,
+        ActivityEventType::AssessmentImported,
+        "assessment",
+        Some(&summary.assessment_id),
+        format!("Imported assessment \"{}\" from {}", name, exported_by),
+    )
+    .with_detail(serde_json::json!({
+        "conflictStrategy": strategy,
+        "created": summary.created,
+        "updated": summary.updated,
+        "skipped": summary.skipped,
+    })));
+
+    Ok(summary)
+}
+
+// ============================================================================
+// Policy Generation Commands
+// ============================================================================
+
+/// Draft policy documents that close an assessment's control gaps
+///
+/// Defaults to every non-compliant or partially compliant control, and to
+/// every policy those gaps map to. Templates in the app data
+/// `policy_templates` directory replace the built-in ones.
+#[tauri::command]
+pub async fn generate_policy_documents(
+    app_handle: AppHandle,
+    db: State<'_, Database>,
+    assessment_id: String,
+    policy_types: Option<Vec<String>>,
+    control_ids: Option<Vec<String>>,
+) -> Result<Vec<PolicyDocument>, String> {
+    let assessment = AssessmentRepository::new(&db)
+        .get(&assessment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assessment not found: {}", assessment_id))?;
+    let control_assessments = ControlAssessmentRepository::new(&db)
+        .get_by_assessment(&assessment_id)
+        .map_err(|e| e.to_string())?;
+    let client_name = ClientRepository::new(&db)
+        .get(&assessment.client_id)
+        .map_err(|e| e.to_string())?
+        .map(|c| c.name)
+        .unwrap_or_else(|| "[Client Name]".to_string());
+
+    let gaps = collect_policy_gaps(&assessment, &control_assessments, control_ids.as_deref());
+
+    let policy_types = match policy_types {
+        Some(types) => types.iter().map(|t| parse_policy_type_param(t)).collect::<Result<Vec<_>, _>>()?,
+        None => PolicyType::all()
+            .into_iter()
+            .filter(|t| gaps.iter().any(|g| g.policy_type == *t))
+            .collect(),
+    };
+    if policy_types.is_empty() {
+        return Err("No control gaps to generate policies for".to_string());
+    }
+
+    let override_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("policy_templates");
+    let generator = PolicyGenerator::new(Some(override_dir));
+
+    let documents = policy_types
+        .iter()
+        .map(|t| generator.generate(*t, &client_name, &assessment, &gaps))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    activity::log(&db, ActivityEvent::new(
+        &assessment.client_id,
+        ActivityEventType::PolicyGenerated,
+        "assessment",
+        Some(&assessment.id),
+        format!("Generated {} policy draft(s) for \"{}\"", documents.len(), assessment.name),
+    )
+    .with_detail(serde_json::json!({
+        "policyTypes": policy_types,
+        "controlCount": gaps.len(),
+    })));
+
+    Ok(documents)
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-fn parse_framework_param(s: &str) -> Result<Framework, String> {
+pub(crate) fn parse_framework_param(s: &str) -> Result<Framework, String> {
     match s.to_uppercase().as_str() {
         "NIST_CSF_2" | "NISTCSF2" | "NIST_CSF2" | "NIST CSF 2.0" => Ok(Framework::NistCsf2),
         "SOC_2_TYPE_II" | "SOC2TYPEII" | "SOC2" | "SOC 2 TYPE II" => Ok(Framework::Soc2TypeII),
         "GDPR" => Ok(Framework::Gdpr),
+        "ISO27001" | "ISO_27001" | "ISO_27001_2022" | "ISO/IEC 27001:2022" => Ok(Framework::Iso27001),
         _ => Err(format!("Unknown framework: {}", s)),
     }
 }
@@ -615,6 +1897,53 @@ fn parse_assessment_status_param(s: &str) -> Result<AssessmentStatus, String> {
     }
 }
 
+fn parse_risk_status_param(s: &str) -> Result<RiskStatus, String> {
+    match s.to_uppercase().as_str() {
+        "OPEN" => Ok(RiskStatus::Open),
+        "MITIGATING" => Ok(RiskStatus::Mitigating),
+        "ACCEPTED" => Ok(RiskStatus::Accepted),
+        "CLOSED" => Ok(RiskStatus::Closed),
+        _ => Err(format!("Unknown risk status: {}", s)),
+    }
+}
+
+fn parse_lawful_basis_param(s: &str) -> Result<LawfulBasis, String> {
+    match s.to_uppercase().as_str() {
+        "CONSENT" => Ok(LawfulBasis::Consent),
+        "CONTRACT" => Ok(LawfulBasis::Contract),
+        "LEGAL_OBLIGATION" => Ok(LawfulBasis::LegalObligation),
+        "VITAL_INTERESTS" => Ok(LawfulBasis::VitalInterests),
+        "PUBLIC_TASK" => Ok(LawfulBasis::PublicTask),
+        "LEGITIMATE_INTERESTS" => Ok(LawfulBasis::LegitimateInterests),
+        _ => Err(format!("Unknown lawful basis: {}", s)),
+    }
+}
+
+fn parse_conflict_strategy_param(s: &str) -> Result<ConflictStrategy, String> {
+    match s.to_uppercase().as_str() {
+        "SKIP_EXISTING" | "SKIP" => Ok(ConflictStrategy::SkipExisting),
+        "OVERWRITE" => Ok(ConflictStrategy::Overwrite),
+        "MERGE_NEWEST" | "MERGE" => Ok(ConflictStrategy::MergeNewest),
+        _ => Err(format!("Unknown conflict strategy: {}", s)),
+    }
+}
+
+fn parse_policy_type_param(s: &str) -> Result<PolicyType, String> {
+    match s.to_uppercase().as_str() {
+        "INFORMATION_SECURITY" => Ok(PolicyType::InformationSecurity),
+        "ACCESS_CONTROL" => Ok(PolicyType::AccessControl),
+        "ASSET_MANAGEMENT" => Ok(PolicyType::AssetManagement),
+        "RISK_MANAGEMENT" => Ok(PolicyType::RiskManagement),
+        "DATA_PROTECTION" => Ok(PolicyType::DataProtection),
+        "INCIDENT_RESPONSE" => Ok(PolicyType::IncidentResponse),
+        "BUSINESS_CONTINUITY" => Ok(PolicyType::BusinessContinuity),
+        "LOGGING_MONITORING" => Ok(PolicyType::LoggingMonitoring),
+        "CHANGE_MANAGEMENT" => Ok(PolicyType::ChangeManagement),
+        "SUPPLIER_SECURITY" => Ok(PolicyType::SupplierSecurity),
+        _ => Err(format!("Unknown policy type: {}", s)),
+    }
+}
+
 fn parse_compliance_status_param(s: &str) -> Result<ComplianceStatus, String> {
     match s.to_uppercase().as_str() {
         "NOT_ASSESSED" | "NOTASSESSED" => Ok(ComplianceStatus::NotAssessed),
@@ -673,5 +2002,14 @@ fn get_category_info(framework: Framework, category: &str) -> (String, String) {
                 _ => (category.to_string(), "#64748b".to_string()),
             }
         }
+        Framework::Iso27001 => {
+            match category {
+                "A.5" => ("Organizational".to_string(), "#3b82f6".to_string()),
+                "A.6" => ("People".to_string(), "#22c55e".to_string()),
+                "A.7" => ("Physical".to_string(), "#f59e0b".to_string()),
+                "A.8" => ("Technological".to_string(), "#8b5cf6".to_string()),
+                _ => (category.to_string(), "#64748b".to_string()),
+            }
+        }
     }
 }
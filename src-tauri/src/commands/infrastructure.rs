@@ -5,11 +5,20 @@
 
 use crate::infrastructure::{
     models::*,
-    cloud_readiness::{get_cloud_readiness_checklist, CloudReadinessAssessor},
-    k8s_hardening::{get_k8s_hardening_checks, K8sHardeningAuditor},
-    finops::{generate_finops_analysis, get_resource_templates, calculate_resource_cost, ResourceTemplate},
+    cloud_readiness::{get_readiness_checklist, new_readiness_assessment, score_readiness, CloudReadinessRepository},
+    k8s_hardening::{self, get_k8s_hardening_checks, k8s_severity_stats, new_k8s_audit, K8sAuditRepository},
+    k8s_manifests::audit_from_manifests,
+    k8s_cluster::{audit_cluster, KubectlTarget},
+    finops::{
+        generate_finops_analysis, get_resource_templates, calculate_resource_cost, CommitmentTerm, ComparisonStrategies,
+        ResourceTemplate,
+    },
+    pricing::{parse_pricing_csv, PricingImportSummary, PricingProfile, PricingProfileRepository},
+    utilization::{client_utilization, import_utilization, UtilizationImportSummary},
 };
+use crate::db::Database;
 use serde::{Deserialize, Serialize};
+use tauri::State;
 use uuid::Uuid;
 
 // ============================================================================
@@ -18,59 +27,110 @@ use uuid::Uuid;
 
 /// Get the full cloud readiness checklist
 #[tauri::command]
-pub async fn get_cloud_readiness_items() -> Result<Vec<CloudReadinessItem>, String> {
-    Ok(get_cloud_readiness_checklist())
+pub async fn get_cloud_readiness_items() -> Result<Vec<ReadinessCheckItem>, String> {
+    Ok(get_readiness_checklist())
 }
 
 /// Get cloud readiness items filtered by category
 #[tauri::command]
 pub async fn get_cloud_readiness_by_category(
     category: String,
-) -> Result<Vec<CloudReadinessItem>, String> {
+) -> Result<Vec<ReadinessCheckItem>, String> {
     let cat = parse_readiness_category(&category)?;
-    let items = get_cloud_readiness_checklist();
+    let items = get_readiness_checklist();
     Ok(items.into_iter().filter(|i| i.category == cat).collect())
 }
 
-/// Perform a cloud readiness assessment
+/// Save cloud readiness answers request
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct PerformReadinessAssessmentRequest {
+pub struct SaveReadinessAnswersRequest {
     pub client_id: String,
-    pub client_name: String,
-    pub target_provider: String,
-    pub item_statuses: Vec<ReadinessItemStatus>,
+    /// Assessment name (default: "Cloud Readiness")
+    pub name: Option<String>,
+    /// Target provider (default: AWS for a new assessment)
+    pub target_provider: Option<String>,
+    pub answers: Vec<ReadinessAnswerInput>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ReadinessItemStatus {
+pub struct ReadinessAnswerInput {
     pub item_id: String,
-    pub status: String,
+    pub answer: String,
     pub notes: Option<String>,
 }
 
-/// Perform cloud readiness assessment and get score
+/// Record answers to the client's readiness checklist
+///
+/// Creates the client's assessment on first save; later saves update only
+/// the items they include.
 #[tauri::command]
-pub async fn assess_cloud_readiness(
-    request: PerformReadinessAssessmentRequest,
+pub async fn save_cloud_readiness_answers(
+    db: State<'_, Database>,
+    request: SaveReadinessAnswersRequest,
 ) -> Result<CloudReadinessAssessment, String> {
-    let provider = parse_cloud_provider(&request.target_provider)?;
-    let assessor = CloudReadinessAssessor::new(provider);
+    let checklist = get_readiness_checklist();
+    let repo = CloudReadinessRepository::new(&db);
+
+    let mut assessment = match repo.get_by_client(&request.client_id).map_err(|e| e.to_string())? {
+        Some(existing) => existing,
+        None => new_readiness_assessment(&request.client_id, "Cloud Readiness", CloudProvider::Aws),
+    };
+    if let Some(name) = request.name.filter(|n| !n.trim().is_empty()) {
+        assessment.name = name;
+    }
+    if let Some(provider) = &request.target_provider {
+        assessment.target_provider = parse_cloud_provider(provider)?;
+    }
 
-    // Build responses from the request
-    let responses: Vec<ReadinessResponse> = request.item_statuses
+    let now = chrono::Utc::now();
+    assessment.answers = request.answers
         .into_iter()
-        .map(|s| ReadinessResponse {
-            item_id: s.item_id,
-            status: parse_readiness_status(&s.status).unwrap_or(ReadinessStatus::NotStarted),
-            notes: s.notes,
-            blockers: None,
+        .map(|a| {
+            if !checklist.iter().any(|i| i.id == a.item_id) {
+                return Err(format!("Unknown readiness item: {}", a.item_id));
+            }
+            Ok(ReadinessItemAnswer {
+                item_id: a.item_id,
+                answer: parse_readiness_answer(&a.answer)?,
+                notes: a.notes,
+                answered_at: now,
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>, String>>()?;
+    assessment.updated_at = now;
+
+    repo.save(&assessment).map_err(|e| e.to_string())?;
+
+    repo.get_by_client(&request.client_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Cloud readiness assessment not found for client: {}", request.client_id))
+}
+
+/// Get the client's cloud readiness assessment and answers
+#[tauri::command]
+pub async fn get_cloud_readiness_assessment(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Option<CloudReadinessAssessment>, String> {
+    CloudReadinessRepository::new(&db)
+        .get_by_client(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Score the client's stored readiness answers
+#[tauri::command]
+pub async fn assess_cloud_readiness(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<ReadinessScore, String> {
+    let assessment = CloudReadinessRepository::new(&db)
+        .get_by_client(&client_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Cloud readiness assessment not found for client: {}", client_id))?;
 
-    let assessment = assessor.assess(&request.client_id, &request.client_name, &responses);
-    Ok(assessment)
+    Ok(score_readiness(&assessment))
 }
 
 // ============================================================================
@@ -99,7 +159,11 @@ pub async fn get_k8s_hardening_by_category(
 pub struct PerformK8sAuditRequest {
     pub client_id: String,
     pub cluster_name: String,
-    pub cluster_version: String,
+    /// Tells apart clusters sharing a name, e.g. "production" and "staging"
+    pub cluster_label: Option<String>,
+    pub cluster_version: Option<String>,
+    pub context_name: Option<String>,
+    pub audited_by: Option<String>,
     pub check_results: Vec<K8sCheckResult>,
 }
 
@@ -108,75 +172,234 @@ pub struct PerformK8sAuditRequest {
 pub struct K8sCheckResult {
     pub check_id: String,
     pub status: String,
-    pub finding: Option<String>,
+    #[serde(alias = "finding")]
+    pub notes: Option<String>,
+    pub evidence_ref: Option<String>,
     pub affected_resources: Option<Vec<String>>,
 }
 
 /// Perform K8s hardening audit
+///
+/// Builds the audit without storing it; see [`save_k8s_audit`].
 #[tauri::command]
 pub async fn audit_k8s_hardening(
     request: PerformK8sAuditRequest,
 ) -> Result<K8sHardeningAudit, String> {
-    let auditor = K8sHardeningAuditor::new();
+    k8s_audit_from_request(request)
+}
 
-    // Build results from request
-    let results: Vec<K8sCheckResultData> = request.check_results
-        .into_iter()
-        .map(|r| K8sCheckResultData {
-            check_id: r.check_id,
-            status: parse_k8s_check_status(&r.status).unwrap_or(K8sCheckStatus::NotChecked),
-            finding: r.finding,
-            affected_resources: r.affected_resources.unwrap_or_default(),
-            remediation_applied: false,
-        })
-        .collect();
+/// Record a K8s hardening audit for a client's cluster
+///
+/// Every save is a new audit, so earlier audits of the cluster stay
+/// available for comparison.
+#[tauri::command]
+pub async fn save_k8s_audit(
+    db: State<'_, Database>,
+    request: PerformK8sAuditRequest,
+) -> Result<K8sHardeningAudit, String> {
+    let audit = k8s_audit_from_request(request)?;
+    K8sAuditRepository::new(&db).save(&audit).map_err(|e| e.to_string())?;
 
-    let audit = auditor.perform_audit(
-        &request.client_id,
-        &request.cluster_name,
-        &request.cluster_version,
-        &results,
+    tracing::info!(
+        "Saved K8s hardening audit {} of {} ({} results)",
+        audit.id,
+        audit.cluster_display_name(),
+        audit.results.len()
+    );
+    Ok(audit)
+}
+
+/// List a client's K8s hardening audits, newest first
+#[tauri::command]
+pub async fn list_k8s_audits(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<K8sHardeningAudit>, String> {
+    K8sAuditRepository::new(&db)
+        .list_by_client(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get a K8s hardening audit with its results
+#[tauri::command]
+pub async fn get_k8s_audit(
+    db: State<'_, Database>,
+    audit_id: String,
+) -> Result<K8sHardeningAudit, String> {
+    load_k8s_audit(&db, &audit_id)
+}
+
+/// Compare a re-audit (`audit_b`) against an earlier audit (`audit_a`)
+#[tauri::command]
+pub async fn compare_k8s_audits(
+    db: State<'_, Database>,
+    audit_a: String,
+    audit_b: String,
+) -> Result<K8sAuditComparison, String> {
+    let baseline = load_k8s_audit(&db, &audit_a)?;
+    let current = load_k8s_audit(&db, &audit_b)?;
+    if baseline.client_id != current.client_id {
+        return Err("Audits belong to different clients".to_string());
+    }
+
+    Ok(k8s_hardening::compare_k8s_audits(&baseline, &current))
+}
+
+/// Audit exported or Helm-rendered Kubernetes manifests for pod-security issues
+///
+/// Accepts files and directories. Documents that can't be evaluated are listed
+/// in the result instead of failing the audit.
+#[tauri::command]
+pub async fn audit_k8s_from_files(paths: Vec<String>) -> Result<K8sManifestAudit, String> {
+    if paths.is_empty() {
+        return Err("No manifest files selected".to_string());
+    }
+
+    let audit = audit_from_manifests(paths);
+    tracing::info!(
+        "Audited {} workloads from {} manifest files ({} findings, {} unparsed)",
+        audit.workloads.len(),
+        audit.files.len(),
+        audit.findings.len(),
+        audit.unparsed.len()
     );
 
     Ok(audit)
 }
 
+/// Scan a directory of rendered manifests (or a single manifest file)
+///
+/// For clients who hand over `helm template` output instead of cluster
+/// access. Unrendered templates are skipped with a warning.
+#[tauri::command]
+pub async fn scan_k8s_manifests(path: String) -> Result<K8sManifestAudit, String> {
+    let path = path.trim().to_string();
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("Manifest path not found: {}", path));
+    }
+
+    let audit = audit_from_manifests(vec![path.clone()]);
+    tracing::info!(
+        "Scanned {} manifest files under {}: {} workloads, {} findings, {} skipped templates",
+        audit.files.len(),
+        path,
+        audit.workloads.len(),
+        audit.findings.len(),
+        audit.skipped.len()
+    );
+
+    Ok(audit)
+}
+
+/// Audit a client's live cluster through kubectl and record the result
+///
+/// Reads workloads with `kubectl get -o json` from the given namespaces, or
+/// all of them, and evaluates the checks that workload specs can answer.
+/// Resources kubectl can't read leave the affected checks for manual review
+/// rather than failing the audit. The stored audit can be completed through
+/// the checklist and used in reports like any other.
+#[tauri::command]
+pub async fn audit_k8s_cluster(
+    db: State<'_, Database>,
+    client_id: String,
+    kubeconfig_path: Option<String>,
+    context: Option<String>,
+    namespaces: Option<Vec<String>>,
+) -> Result<K8sAuditResult, String> {
+    let target = KubectlTarget {
+        kubeconfig: kubeconfig_path.filter(|p| !p.trim().is_empty()),
+        context: context.filter(|c| !c.trim().is_empty()),
+    };
+    let namespaces: Vec<String> = namespaces
+        .unwrap_or_default()
+        .into_iter()
+        .map(|ns| ns.trim().to_string())
+        .filter(|ns| !ns.is_empty())
+        .collect();
+
+    let result = audit_cluster(&client_id, &target, &namespaces).await;
+    K8sAuditRepository::new(&db).save(&result.audit).map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "Audited cluster {} through kubectl: {} workloads, {} results, {} unreadable resources",
+        result.audit.cluster_display_name(),
+        result.workloads,
+        result.audit.results.len(),
+        result.resource_errors.len()
+    );
+    Ok(result)
+}
+
 /// Get K8s hardening severity breakdown
+///
+/// With an audit id, counts that audit's failing checks; without one,
+/// counts the whole checklist.
 #[tauri::command]
-pub async fn get_k8s_severity_stats() -> Result<K8sSeverityStats, String> {
-    let checks = get_k8s_hardening_checks();
+pub async fn get_k8s_severity_stats(
+    db: State<'_, Database>,
+    audit_id: Option<String>,
+) -> Result<K8sSeverityStats, String> {
+    let audit = audit_id.map(|id| load_k8s_audit(&db, &id)).transpose()?;
+    Ok(k8s_severity_stats(audit.as_ref()))
+}
 
-    let mut critical = 0;
-    let mut high = 0;
-    let mut medium = 0;
-    let mut low = 0;
-
-    for check in &checks {
-        match check.severity {
-            Severity::Critical => critical += 1,
-            Severity::High => high += 1,
-            Severity::Medium => medium += 1,
-            Severity::Low => low += 1,
-        }
-    }
+/// Failing checks by severity for each of a client's audits, oldest first
+///
+/// Pass a cluster name to follow one cluster across re-audits.
+#[tauri::command]
+pub async fn get_k8s_severity_trend(
+    db: State<'_, Database>,
+    client_id: String,
+    cluster_name: Option<String>,
+) -> Result<Vec<K8sSeverityStats>, String> {
+    let audits = K8sAuditRepository::new(&db)
+        .list_by_client(&client_id)
+        .map_err(|e| e.to_string())?;
 
-    Ok(K8sSeverityStats {
-        total: checks.len(),
-        critical,
-        high,
-        medium,
-        low,
-    })
+    Ok(audits
+        .iter()
+        .rev()
+        .filter(|a| cluster_name.as_ref().map_or(true, |name| a.cluster_name == *name))
+        .map(|a| k8s_severity_stats(Some(a)))
+        .collect())
 }
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct K8sSeverityStats {
-    pub total: usize,
-    pub critical: usize,
-    pub high: usize,
-    pub medium: usize,
-    pub low: usize,
+fn load_k8s_audit(db: &Database, audit_id: &str) -> Result<K8sHardeningAudit, String> {
+    K8sAuditRepository::new(db)
+        .get(audit_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("K8s audit not found: {}", audit_id))
+}
+
+fn k8s_audit_from_request(request: PerformK8sAuditRequest) -> Result<K8sHardeningAudit, String> {
+    let cluster_name = request.cluster_name.trim();
+    if cluster_name.is_empty() {
+        return Err("Cluster name is required".to_string());
+    }
+
+    let checks = get_k8s_hardening_checks();
+    let mut audit = new_k8s_audit(&request.client_id, cluster_name);
+    audit.cluster_label = request.cluster_label.filter(|l| !l.trim().is_empty());
+    audit.cluster_version = request.cluster_version.filter(|v| !v.trim().is_empty());
+    audit.context_name = request.context_name.filter(|c| !c.trim().is_empty());
+    audit.audited_by = request.audited_by.filter(|a| !a.trim().is_empty());
+    audit.results = request.check_results
+        .into_iter()
+        .map(|r| {
+            if !checks.iter().any(|c| c.id == r.check_id) {
+                return Err(format!("Unknown K8s hardening check: {}", r.check_id));
+            }
+            Ok(K8sCheckResultData {
+                check_id: r.check_id,
+                status: parse_k8s_check_status(&r.status)?,
+                notes: r.notes.filter(|n| !n.trim().is_empty()),
+                evidence_ref: r.evidence_ref.filter(|e| !e.trim().is_empty()),
+                affected_resources: r.affected_resources.unwrap_or_default(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(audit)
 }
 
 // ============================================================================
@@ -218,14 +441,18 @@ pub struct CalculateResourceCostRequest {
     pub bandwidth_gbps: Option<f64>,
     pub iops: Option<u32>,
     pub provider: String,
+    /// Pricing profile overriding the built-in rates
+    pub pricing_profile_id: Option<String>,
 }
 
 #[tauri::command]
 pub async fn calculate_single_resource_cost(
+    db: State<'_, Database>,
     request: CalculateResourceCostRequest,
 ) -> Result<f64, String> {
     let provider = parse_cloud_provider(&request.provider)?;
     let resource_type = parse_resource_type(&request.resource_type)?;
+    let profile = load_pricing_profile(&db, request.pricing_profile_id.as_deref(), &provider)?;
 
     let resource = ResourceCostEstimate {
         resource_type,
@@ -242,7 +469,7 @@ pub async fn calculate_single_resource_cost(
         notes: None,
     };
 
-    let cost = calculate_resource_cost(&resource, &provider);
+    let cost = calculate_resource_cost(&resource, &provider, profile.as_ref());
     Ok((cost * 100.0).round() / 100.0) // Round to 2 decimal places
 }
 
@@ -255,6 +482,10 @@ pub struct GenerateFinOpsAnalysisRequest {
     pub migration_strategy: String,
     pub current_costs: OnPremCostsInput,
     pub resources: Vec<ResourceInput>,
+    /// Pricing profile overriding the built-in rates
+    pub pricing_profile_id: Option<String>,
+    /// Commitment to price compute and databases under (default: on-demand)
+    pub commitment: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -285,55 +516,84 @@ pub struct ResourceInput {
 
 #[tauri::command]
 pub async fn generate_finops_report(
+    db: State<'_, Database>,
     request: GenerateFinOpsAnalysisRequest,
 ) -> Result<FinOpsAnalysis, String> {
     let provider = parse_cloud_provider(&request.target_provider)?;
     let strategy = parse_migration_strategy(&request.migration_strategy)?;
-
-    let current_costs = OnPremiseCosts {
-        hardware_monthly: request.current_costs.hardware_monthly,
-        software_licensing_monthly: request.current_costs.software_licensing_monthly,
-        datacenter_monthly: request.current_costs.datacenter_monthly,
-        personnel_monthly: request.current_costs.personnel_monthly,
-        maintenance_monthly: request.current_costs.maintenance_monthly,
-        power_cooling_monthly: request.current_costs.power_cooling_monthly,
-        network_monthly: request.current_costs.network_monthly,
+    let profile = load_pricing_profile(&db, request.pricing_profile_id.as_deref(), &provider)?;
+    let commitment = match &request.commitment {
+        Some(s) => parse_commitment_term(s)?,
+        None => CommitmentTerm::OnDemand,
     };
 
-    let resources: Result<Vec<ResourceCostEstimate>, String> = request.resources
-        .into_iter()
-        .map(|r| {
-            let resource_type = parse_resource_type(&r.resource_type)?;
-            Ok(ResourceCostEstimate {
-                resource_type,
-                name: r.name,
-                quantity: r.quantity,
-                specs: ResourceSpecs {
-                    vcpus: r.vcpus,
-                    memory_gb: r.memory_gb,
-                    storage_gb: r.storage_gb,
-                    bandwidth_gbps: r.bandwidth_gbps,
-                    iops: r.iops,
-                },
-                monthly_cost: 0.0,
-                notes: r.notes,
-            })
-        })
-        .collect();
-
-    let resources = resources?;
+    let current_costs = request.current_costs.to_costs();
+    let resources = to_resource_estimates(&request.resources)?;
+    let utilization = client_utilization(&db, &request.client_id).map_err(|e| e.to_string())?;
 
-    let mut analysis = generate_finops_analysis(&current_costs, &resources, &provider, &strategy);
+    let mut analysis = generate_finops_analysis(
+        &current_costs,
+        &resources,
+        &provider,
+        &strategy,
+        &commitment,
+        &utilization,
+        profile.as_ref(),
+    );
     analysis.client_id = request.client_id;
 
     Ok(analysis)
 }
 
+/// Multi-year projection settings
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcoProjectionInput {
+    /// Projection horizon in years (1-5)
+    pub horizon_years: u32,
+    pub commitment_strategy: String,
+    pub growth_rates: Vec<GrowthRateInput>,
+    pub current_costs: OnPremCostsInput,
+    pub migration_strategy: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrowthRateInput {
+    pub resource_type: String,
+    pub annual_rate_percent: f64,
+}
+
+/// Generate a multi-year TCO projection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateTcoProjectionRequest {
+    pub client_id: String,
+    pub target_provider: String,
+    pub resources: Vec<ResourceInput>,
+    pub projection: TcoProjectionInput,
+}
+
+#[tauri::command]
+pub async fn generate_tco_projection(
+    request: GenerateTcoProjectionRequest,
+) -> Result<TcoProjection, String> {
+    let provider = parse_cloud_provider(&request.target_provider)?;
+    let resources = to_resource_estimates(&request.resources)?;
+
+    let mut projection = project_tco(&request.projection, &resources, &provider)?;
+    projection.client_id = request.client_id;
+
+    Ok(projection)
+}
+
 /// Compare costs across providers
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompareProvidersRequest {
     pub resources: Vec<ResourceInput>,
+    /// Include a multi-year projection for each provider
+    pub projection: Option<TcoProjectionInput>,
 }
 
 #[derive(Debug, Serialize)]
@@ -342,6 +602,7 @@ pub struct ProviderComparison {
     pub provider: String,
     pub monthly_cost: f64,
     pub annual_cost: f64,
+    pub tco: Option<TcoProjection>,
 }
 
 #[tauri::command]
@@ -350,7 +611,313 @@ pub async fn compare_cloud_providers(
 ) -> Result<Vec<ProviderComparison>, String> {
     let providers = vec![CloudProvider::AWS, CloudProvider::Azure, CloudProvider::GCP];
 
-    let resources: Result<Vec<ResourceCostEstimate>, String> = request.resources
+    let resources = to_resource_estimates(&request.resources)?;
+
+    providers
+        .into_iter()
+        .map(|provider| {
+            let monthly: f64 = resources
+                .iter()
+                .map(|r| calculate_resource_cost(r, &provider, None))
+                .sum();
+
+            let tco = match &request.projection {
+                Some(input) => Some(project_tco(input, &resources, &provider)?),
+                None => None,
+            };
+
+            Ok(ProviderComparison {
+                provider: format!("{:?}", provider),
+                monthly_cost: (monthly * 100.0).round() / 100.0,
+                annual_cost: (monthly * 12.0 * 100.0).round() / 100.0,
+                tco,
+            })
+        })
+        .collect()
+}
+
+/// Compare each resource's cost across providers
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareResourceCostsRequest {
+    pub client_id: String,
+    pub resources: Vec<ResourceInput>,
+    /// Commitment applied to every provider (default: on-demand)
+    pub commitment_strategy: Option<String>,
+    /// With `migration_strategy`, also run a full FinOps analysis per provider
+    pub current_costs: Option<OnPremCostsInput>,
+    pub migration_strategy: Option<String>,
+}
+
+#[tauri::command]
+pub async fn compare_resource_costs(
+    request: CompareResourceCostsRequest,
+) -> Result<crate::infrastructure::finops::ProviderComparison, String> {
+    let resources = to_resource_estimates(&request.resources)?;
+    let commitment = match &request.commitment_strategy {
+        Some(s) => parse_commitment_strategy(s)?,
+        None => CommitmentStrategy::OnDemand,
+    };
+
+    let current_costs = request.current_costs.as_ref().map(OnPremCostsInput::to_costs);
+    let analysis = match (&current_costs, &request.migration_strategy) {
+        (Some(costs), Some(strategy)) => Some((costs, parse_migration_strategy(strategy)?)),
+        (None, None) => None,
+        _ => return Err("Current costs and a migration strategy are needed together".to_string()),
+    };
+
+    let mut comparison = crate::infrastructure::finops::compare_resource_costs(
+        &resources,
+        &ComparisonStrategies { commitment, analysis },
+    );
+    comparison.client_id = request.client_id.clone();
+    for analysis in &mut comparison.analyses {
+        analysis.client_id = request.client_id.clone();
+    }
+
+    Ok(comparison)
+}
+
+fn project_tco(
+    input: &TcoProjectionInput,
+    resources: &[ResourceCostEstimate],
+    provider: &CloudProvider,
+) -> Result<TcoProjection, String> {
+    if !(1..=5).contains(&input.horizon_years) {
+        return Err("Projection horizon must be between 1 and 5 years".to_string());
+    }
+    let strategy = parse_migration_strategy(&input.migration_strategy)?;
+    let commitment = parse_commitment_strategy(&input.commitment_strategy)?;
+    let growth_rates = input.growth_rates
+        .iter()
+        .map(|g| {
+            Ok(ResourceGrowthRate {
+                resource_type: parse_resource_type(&g.resource_type)?,
+                annual_rate_percent: g.annual_rate_percent,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(crate::infrastructure::finops::generate_tco_projection(
+        &input.current_costs.to_costs(),
+        resources,
+        provider,
+        &strategy,
+        &commitment,
+        input.horizon_years,
+        &growth_rates,
+    ))
+}
+
+// ============================================================================
+// Pricing Profile Commands
+// ============================================================================
+
+/// Create or update a pricing profile
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavePricingProfileRequest {
+    pub name: String,
+    pub provider: String,
+    pub region: String,
+    pub compute_per_vcpu: Option<f64>,
+    pub memory_per_gb: Option<f64>,
+    pub storage_per_gb_month: Option<f64>,
+    pub egress_per_gb: Option<f64>,
+    pub managed_db_multiplier: Option<f64>,
+    pub k8s_cluster_base: Option<f64>,
+    pub notes: Option<String>,
+}
+
+/// Create a pricing profile
+#[tauri::command]
+pub async fn create_pricing_profile(
+    db: State<'_, Database>,
+    request: SavePricingProfileRequest,
+) -> Result<PricingProfile, String> {
+    let now = chrono::Utc::now();
+    let mut profile = PricingProfile {
+        id: Uuid::new_v4().to_string(),
+        name: String::new(),
+        provider: parse_cloud_provider(&request.provider)?,
+        region: String::new(),
+        compute_per_vcpu: None,
+        memory_per_gb: None,
+        storage_per_gb_month: None,
+        egress_per_gb: None,
+        managed_db_multiplier: None,
+        k8s_cluster_base: None,
+        notes: None,
+        created_at: now,
+        updated_at: now,
+    };
+    apply_pricing_request(&mut profile, request)?;
+
+    PricingProfileRepository::new(&db).save(&profile).map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+/// List pricing profiles, optionally for one provider
+#[tauri::command]
+pub async fn list_pricing_profiles(
+    db: State<'_, Database>,
+    provider: Option<String>,
+) -> Result<Vec<PricingProfile>, String> {
+    let repo = PricingProfileRepository::new(&db);
+    match provider {
+        Some(provider) => repo.list_by_provider(&parse_cloud_provider(&provider)?),
+        None => repo.list(),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Get a pricing profile by ID
+#[tauri::command]
+pub async fn get_pricing_profile(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<Option<PricingProfile>, String> {
+    PricingProfileRepository::new(&db).get(&id).map_err(|e| e.to_string())
+}
+
+/// Replace a pricing profile's rates; unset rates fall back to the defaults
+#[tauri::command]
+pub async fn update_pricing_profile(
+    db: State<'_, Database>,
+    id: String,
+    request: SavePricingProfileRequest,
+) -> Result<PricingProfile, String> {
+    let repo = PricingProfileRepository::new(&db);
+    let mut profile = repo
+        .get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Pricing profile not found: {}", id))?;
+
+    profile.provider = parse_cloud_provider(&request.provider)?;
+    apply_pricing_request(&mut profile, request)?;
+    profile.updated_at = chrono::Utc::now();
+
+    repo.save(&profile).map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+/// Delete a pricing profile
+#[tauri::command]
+pub async fn delete_pricing_profile(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    PricingProfileRepository::new(&db).delete(&id).map_err(|e| e.to_string())
+}
+
+/// Load pricing profiles from a CSV of vendor rates, one profile per row
+///
+/// Rows that can't be read are skipped and reported in the summary.
+#[tauri::command]
+pub async fn import_pricing_csv(
+    db: State<'_, Database>,
+    path: String,
+) -> Result<PricingImportSummary, String> {
+    let data = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let summary = parse_pricing_csv(&data)?;
+
+    let repo = PricingProfileRepository::new(&db);
+    for profile in &summary.profiles {
+        repo.save(profile).map_err(|e| e.to_string())?;
+    }
+
+    tracing::info!(
+        "Imported {} pricing profiles from {} ({} rows skipped)",
+        summary.profiles.len(),
+        path,
+        summary.warnings.len()
+    );
+    Ok(summary)
+}
+
+/// Store per-server utilization from a CSV export on the client's assets
+///
+/// Rows are matched to assets by IP address or hostname; FinOps analyses for
+/// the client then size virtual machines from the measured load. Rows that
+/// match no asset come back with suggested asset names.
+#[tauri::command]
+pub async fn import_utilization_csv(
+    db: State<'_, Database>,
+    client_id: String,
+    path: String,
+) -> Result<UtilizationImportSummary, String> {
+    let data = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let summary = import_utilization(&db, &client_id, &data).map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "Imported utilization from {}: {} assets matched, {} rows unmatched ({} rows skipped)",
+        path,
+        summary.matched,
+        summary.unmatched.len(),
+        summary.warnings.len()
+    );
+    Ok(summary)
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+impl OnPremCostsInput {
+    fn to_costs(&self) -> OnPremiseCosts {
+        OnPremiseCosts {
+            hardware_monthly: self.hardware_monthly,
+            software_licensing_monthly: self.software_licensing_monthly,
+            datacenter_monthly: self.datacenter_monthly,
+            personnel_monthly: self.personnel_monthly,
+            maintenance_monthly: self.maintenance_monthly,
+            power_cooling_monthly: self.power_cooling_monthly,
+            network_monthly: self.network_monthly,
+        }
+    }
+}
+
+fn apply_pricing_request(profile: &mut PricingProfile, request: SavePricingProfileRequest) -> Result<(), String> {
+    profile.name = request.name.trim().to_string();
+    profile.region = request.region.trim().to_string();
+    profile.compute_per_vcpu = request.compute_per_vcpu;
+    profile.memory_per_gb = request.memory_per_gb;
+    profile.storage_per_gb_month = request.storage_per_gb_month;
+    profile.egress_per_gb = request.egress_per_gb;
+    profile.managed_db_multiplier = request.managed_db_multiplier;
+    profile.k8s_cluster_base = request.k8s_cluster_base;
+    profile.notes = request.notes.filter(|n| !n.trim().is_empty());
+    profile.validate()
+}
+
+/// Look up the profile a request asked for; it has to match the provider
+fn load_pricing_profile(
+    db: &Database,
+    id: Option<&str>,
+    provider: &CloudProvider,
+) -> Result<Option<PricingProfile>, String> {
+    let id = match id {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let profile = PricingProfileRepository::new(db)
+        .get(id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Pricing profile not found: {}", id))?;
+
+    if profile.provider != *provider {
+        return Err(format!(
+            "Pricing profile \"{}\" is for {:?}, not {:?}",
+            profile.name, profile.provider, provider
+        ));
+    }
+    Ok(Some(profile))
+}
+
+fn to_resource_estimates(inputs: &[ResourceInput]) -> Result<Vec<ResourceCostEstimate>, String> {
+    inputs
         .iter()
         .map(|r| {
             let resource_type = parse_resource_type(&r.resource_type)?;
@@ -369,32 +936,28 @@ pub async fn compare_cloud_providers(
                 notes: r.notes.clone(),
             })
         })
-        .collect();
-
-    let resources = resources?;
-
-    let comparisons = providers
-        .into_iter()
-        .map(|provider| {
-            let monthly: f64 = resources
-                .iter()
-                .map(|r| calculate_resource_cost(r, &provider))
-                .sum();
-
-            ProviderComparison {
-                provider: format!("{:?}", provider),
-                monthly_cost: (monthly * 100.0).round() / 100.0,
-                annual_cost: (monthly * 12.0 * 100.0).round() / 100.0,
-            }
-        })
-        .collect();
+        .collect()
+}
 
-    Ok(comparisons)
+fn parse_commitment_strategy(s: &str) -> Result<CommitmentStrategy, String> {
+    match s.to_uppercase().as_str() {
+        "ONDEMAND" | "ON_DEMAND" => Ok(CommitmentStrategy::OnDemand),
+        "RESERVED1YR" | "RESERVED_1YR" | "RI_1YR" => Ok(CommitmentStrategy::Reserved1Yr),
+        "RESERVED3YR" | "RESERVED_3YR" | "RI_3YR" => Ok(CommitmentStrategy::Reserved3Yr),
+        "SAVINGSPLAN" | "SAVINGS_PLAN" => Ok(CommitmentStrategy::SavingsPlan),
+        _ => Err(format!("Unknown commitment strategy: {}", s)),
+    }
 }
 
-// ============================================================================
-// Helper Functions
-// ============================================================================
+fn parse_commitment_term(s: &str) -> Result<CommitmentTerm, String> {
+    match s.to_uppercase().as_str() {
+        "ONDEMAND" | "ON_DEMAND" => Ok(CommitmentTerm::OnDemand),
+        "ONE_YEAR_NO_UPFRONT" | "1YR_NO_UPFRONT" => Ok(CommitmentTerm::OneYearNoUpfront),
+        "ONE_YEAR_ALL_UPFRONT" | "1YR_ALL_UPFRONT" => Ok(CommitmentTerm::OneYearAllUpfront),
+        "THREE_YEAR" | "3YR" => Ok(CommitmentTerm::ThreeYear),
+        _ => Err(format!("Unknown commitment term: {}", s)),
+    }
+}
 
 fn parse_cloud_provider(s: &str) -> Result<CloudProvider, String> {
     match s.to_uppercase().as_str() {
@@ -445,14 +1008,13 @@ fn parse_readiness_category(s: &str) -> Result<ReadinessCategory, String> {
     }
 }
 
-fn parse_readiness_status(s: &str) -> Result<ReadinessStatus, String> {
+fn parse_readiness_answer(s: &str) -> Result<ReadinessAnswer, String> {
     match s.to_uppercase().as_str() {
-        "NOTSTARTED" | "NOT_STARTED" => Ok(ReadinessStatus::NotStarted),
-        "INPROGRESS" | "IN_PROGRESS" => Ok(ReadinessStatus::InProgress),
-        "COMPLETED" | "COMPLETE" => Ok(ReadinessStatus::Completed),
-        "BLOCKED" => Ok(ReadinessStatus::Blocked),
-        "NOTAPPLICABLE" | "NOT_APPLICABLE" | "NA" | "N/A" => Ok(ReadinessStatus::NotApplicable),
-        _ => Err(format!("Unknown readiness status: {}", s)),
+        "YES" => Ok(ReadinessAnswer::Yes),
+        "PARTIAL" => Ok(ReadinessAnswer::Partial),
+        "NO" => Ok(ReadinessAnswer::No),
+        "NOTAPPLICABLE" | "NOT_APPLICABLE" | "NA" | "N/A" => Ok(ReadinessAnswer::NotApplicable),
+        _ => Err(format!("Unknown readiness answer: {}", s)),
     }
 }
 
@@ -475,6 +1037,7 @@ fn parse_k8s_check_status(s: &str) -> Result<K8sCheckStatus, String> {
         "PASS" | "PASSED" => Ok(K8sCheckStatus::Pass),
         "FAIL" | "FAILED" => Ok(K8sCheckStatus::Fail),
         "WARN" | "WARNING" => Ok(K8sCheckStatus::Warn),
+        "NOTAPPLICABLE" | "NOT_APPLICABLE" | "NA" | "N/A" => Ok(K8sCheckStatus::NotApplicable),
         "NOTCHECKED" | "NOT_CHECKED" | "SKIP" | "SKIPPED" => Ok(K8sCheckStatus::NotChecked),
         _ => Err(format!("Unknown K8s check status: {}", s)),
     }
@@ -4,8 +4,17 @@
 
 pub mod factory;
 pub mod clients;
+pub mod dashboard;
 pub mod system;
+pub mod database;
+pub mod onboarding;
+pub mod activity;
+pub mod audit;
 pub mod grc;
 pub mod infrastructure;
 pub mod network;
+pub mod findings;
 pub mod reporting;
+pub mod workspace;
+pub mod search;
+pub mod portfolio;
@@ -6,41 +6,85 @@ use crate::network::{
     models::*,
     scanner::{
         check_nmap_installed, get_scan_types, build_nmap_command, validate_target,
-        get_common_ports, scan_network_native, scan_network_with_ports,
-        NmapInfo, ScanTypeInfo, TargetValidation, CommonPort, ScannedHost,
-        DEFAULT_SCAN_PORTS, EXTENDED_SCAN_PORTS,
+        get_common_ports, scan_network_native, scan_network_with_ports, verify_targets,
+        parse_nmap_file, parse_nmap_output, apply_scan_privileges, detect_scan_privileges, locate_nmap, validate_nmap_binary, NmapInfo, ScanTypeInfo, TargetValidation, CommonPort, ScannedHost,
+        check_custom_args, validate_custom_args, CustomArgsValidation, DEFAULT_SCAN_PORTS, EXTENDED_SCAN_PORTS,
     },
-    inventory::{generate_demo_assets, AssetInventory},
+    inventory::{default_service_policies, generate_demo_assets, AssetInventory, AssetPolicyViolations, ServicePolicy},
+    findings::{detect_findings, evaluate_verification, get_finding_rules, plan_verification_targets, FindingRule},
+    diff::{inventory_results, ScanDiff},
+    batch::{execute_scan_job, validate_batching},
+    targets::{self, check_scan_targets, estimate_scan, ScanEstimate, TargetPlan},
+    repository::{
+        AssetRepository, NetworkFindingRepository, ScanProfileRepository, ScanRepository, ScanScheduleRepository, ScannerSettingsRepository,
+        ServicePolicyRepository, VulnerabilityRepository,
+    },
+    scheduler::{
+        execute_scheduled_job, next_run_after, scheduled_job, should_run, validate_frequency,
+        SCHEDULED_SCAN_COMPLETED_EVENT, SCHEDULER_TICK,
+    },
+    vuln_match::{parse_vuln_json, AssetVulnerabilityReport, VulnDatabase, VulnImportSummary, VulnerabilityMatch},
+    asset_export::{self, AssetExportFormat, AssetExportSummary, AssetImportSummary},
+    agent_listener::{
+        start_listener, AgentEventSink, AgentListenerHandle, AgentListenerStatus, AgentRepository,
+        AgentTelemetry, ConnectedAgent,
+    },
+    agent_tls::tls_acceptor,
 };
+use crate::activity::{self, ActivityEvent, ActivityEventType};
+use crate::audit::{self, AuditEntry, AuditOperation};
+use crate::db::Database;
+use crate::onboarding::{self, Milestone};
+use crate::secrets::OsKeychain;
 use serde::{Deserialize, Serialize};
-use std::sync::Mutex;
-use tauri::State;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
-/// In-memory storage for demo purposes
-/// In production, this would be replaced with database persistence
+/// Network state kept for the session
+/// Findings, scans and the asset inventory are persisted in the database
+#[derive(Default)]
 pub struct NetworkState {
-    pub inventory: Mutex<AssetInventory>,
-    pub scans: Mutex<Vec<ScanJob>>,
+    /// Cancellation flags of the scans running in this session
+    pub running_scans: Mutex<HashMap<String, Arc<AtomicBool>>>,
 }
 
-impl Default for NetworkState {
-    fn default() -> Self {
-        Self {
-            inventory: Mutex::new(AssetInventory::new()),
-            scans: Mutex::new(Vec::new()),
-        }
-    }
-}
+/// Event emitted with the scan record as a running scan makes progress
+pub const SCAN_PROGRESS_EVENT: &str = "scan-progress";
 
 // ============================================================================
 // Scanner Commands
 // ============================================================================
 
+/// The user's Nmap path override, if set
+fn configured_nmap_path(db: &Database) -> Result<Option<String>, String> {
+    ScannerSettingsRepository::new(db).nmap_path().map_err(|e| e.to_string())
+}
+
 /// Check if Nmap is installed and get version info
 #[tauri::command]
-pub async fn check_nmap() -> Result<NmapInfo, String> {
-    check_nmap_installed()
+pub async fn check_nmap(db: State<'_, Database>) -> Result<NmapInfo, String> {
+    let info = check_nmap_installed(configured_nmap_path(&db)?.as_deref())?;
+    if info.installed {
+        onboarding::record(&db, Milestone::NmapValidated);
+    }
+    Ok(info)
+}
+
+/// Set the Nmap binary to use instead of searching PATH; None clears it
+///
+/// The binary is checked by running `--version` before it is saved.
+#[tauri::command]
+pub async fn set_nmap_path(db: State<'_, Database>, path: Option<String>) -> Result<NmapInfo, String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(path) = &path {
+        validate_nmap_binary(path)?;
+    }
+    ScannerSettingsRepository::new(&db).set_nmap_path(path.as_deref()).map_err(|e| e.to_string())?;
+
+    check_nmap(db).await
 }
 
 /// Get available scan types
@@ -61,6 +105,12 @@ pub async fn validate_scan_target(target: String) -> Result<TargetValidation, St
     validate_target(&target)
 }
 
+/// Check custom Nmap arguments before launching a custom scan
+#[tauri::command]
+pub async fn validate_custom_nmap_args(args: String) -> Result<CustomArgsValidation, String> {
+    Ok(validate_custom_args(&args))
+}
+
 /// Create a new scan job request
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -68,33 +118,90 @@ pub struct CreateScanRequest {
     pub client_id: String,
     pub name: String,
     pub targets: Vec<String>,
-    pub scan_type: String,
+    /// Saved profile whose settings the fields below override
+    pub profile_id: Option<String>,
+    /// Required unless a profile is given
+    pub scan_type: Option<String>,
     pub custom_args: Option<String>,
     pub ports: Option<String>,
+    /// Added to the profile's exclusions
     pub exclude_targets: Option<Vec<String>>,
-    pub aggressive: bool,
-    pub skip_discovery: bool,
+    pub aggressive: Option<bool>,
+    pub skip_discovery: Option<bool>,
+    /// Targets per Nmap run; leave unset to scan all targets in one run
+    pub batch_size: Option<usize>,
+    pub batch_concurrency: Option<usize>,
+    pub max_packets_per_second: Option<u32>,
+}
+
+/// The configuration a scan starts from: a saved profile's, or the defaults
+///
+/// Without a profile the scan type must be given. A client's scan can use
+/// global profiles and its own, not another client's.
+fn base_scan_config(
+    db: &Database,
+    profile_id: Option<&str>,
+    client_id: Option<&str>,
+    scan_type: Option<&str>,
+) -> Result<ScanConfig, String> {
+    let mut config = match profile_id {
+        Some(profile_id) => {
+            let profile = ScanProfileRepository::new(db)
+                .get(profile_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Scan profile not found: {}", profile_id))?;
+            if let (Some(owner), Some(client_id)) = (profile.client_id.as_deref(), client_id) {
+                if owner != client_id {
+                    return Err(format!("Scan profile \"{}\" belongs to another client", profile.name));
+                }
+            }
+            profile.config
+        }
+        None if scan_type.is_none() => return Err("A scan type is required".to_string()),
+        None => ScanConfig::default(),
+    };
+    config.output_formats = vec![OutputFormat::Xml];
+    config.privilege_mode = ScanPrivilegeMode::default();
+    Ok(config)
 }
 
 /// Create a new scan job (queued, not executed)
+///
+/// With a profile, the profile's settings are the starting point and any
+/// fields set on the request override them. Scans needing raw packet access
+/// this session lacks are downgraded to a TCP connect scan (with a warning
+/// on the job) or refused with the fix.
 #[tauri::command]
 pub async fn create_scan(
-    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
     request: CreateScanRequest,
 ) -> Result<ScanJob, String> {
-    let scan_type = parse_scan_type(&request.scan_type)?;
-
-    let config = ScanConfig {
-        targets: request.targets,
-        scan_type,
+    let base = base_scan_config(
+        &db,
+        request.profile_id.as_deref(),
+        Some(&request.client_id),
+        request.scan_type.as_deref(),
+    )?;
+    let overrides = ScanOverrides {
+        scan_type: request.scan_type.as_deref().map(parse_scan_type).transpose()?,
         custom_args: request.custom_args,
         ports: request.ports,
         exclude_targets: request.exclude_targets,
         aggressive: request.aggressive,
         skip_discovery: request.skip_discovery,
-        output_formats: vec![OutputFormat::Xml],
+        batch_size: request.batch_size,
+        batch_concurrency: request.batch_concurrency,
+        max_packets_per_second: request.max_packets_per_second,
     };
 
+    let mut config = overrides.apply(base, request.targets);
+    check_scan_targets(&config)?;
+    validate_batching(&config)?;
+    let mut warnings = check_custom_args(&config)?;
+    let nmap = locate_nmap(configured_nmap_path(&db)?.as_deref());
+    let warning = apply_scan_privileges(&mut config, &detect_scan_privileges(nmap.as_ref().map(|n| n.path.as_str())))?;
+    warnings.extend(warning);
+
     let job = ScanJob {
         id: Uuid::new_v4().to_string(),
         client_id: request.client_id,
@@ -106,70 +213,549 @@ pub async fn create_scan(
         completed_at: None,
         error: None,
         progress: 0,
-        raw_output: None,
+        totals: None,
+        verification: None,
+        import: None,
+        warnings,
+        batches: None,
+        live: None,
     };
 
-    let mut scans = state.scans.lock().map_err(|e| e.to_string())?;
-    scans.push(job.clone());
+    ScanRepository::new(&db).save(&job).map_err(|e| e.to_string())?;
+
+    activity::log(&db, ActivityEvent::new(
+        &job.client_id,
+        ActivityEventType::ScanCreated,
+        "scan",
+        Some(&job.id),
+        format!("Queued {} \"{}\"", job.config.scan_type.display_name(), job.name),
+    )
+    .with_detail(serde_json::json!({
+        "scanType": job.config.scan_type,
+        "targets": job.config.targets,
+    })));
 
     Ok(job)
 }
 
 /// Get the Nmap command that would be executed (preview)
+///
+/// Applies the profile, if any, the same way `create_scan` does, and
+/// reflects any downgrade for this session's raw packet access.
 #[tauri::command]
 pub async fn preview_scan_command(
+    db: State<'_, Database>,
     targets: Vec<String>,
-    scan_type: String,
+    scan_type: Option<String>,
     ports: Option<String>,
-    aggressive: bool,
+    aggressive: Option<bool>,
+    max_packets_per_second: Option<u32>,
+    profile_id: Option<String>,
 ) -> Result<String, String> {
-    let st = parse_scan_type(&scan_type)?;
-
-    let config = ScanConfig {
-        targets,
-        scan_type: st,
+    let base = base_scan_config(&db, profile_id.as_deref(), None, scan_type.as_deref())?;
+    let overrides = ScanOverrides {
+        scan_type: scan_type.as_deref().map(parse_scan_type).transpose()?,
         ports,
         aggressive,
+        max_packets_per_second,
         ..Default::default()
     };
 
+    let mut config = overrides.apply(base, targets);
+    check_scan_targets(&config)?;
+    check_custom_args(&config)?;
+    let nmap = locate_nmap(configured_nmap_path(&db)?.as_deref());
+    apply_scan_privileges(&mut config, &detect_scan_privileges(nmap.as_ref().map(|n| n.path.as_str())))?;
+
     let args = build_nmap_command(&config);
     Ok(format!("nmap {}", args.join(" ")))
 }
 
+/// Host count and rough duration of a scan before it is launched
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanTargetPlan {
+    pub plan: TargetPlan,
+    pub estimate: ScanEstimate,
+}
+
+/// Plan a scan's targets: hosts touched after exclusions, overlaps, public
+/// address warnings and an ETA, shown alongside `preview_scan_command`
+///
+/// The profile, if any, is applied the same way `create_scan` does, with
+/// `exclude_targets` added to the profile's exclusions.
+#[tauri::command]
+pub async fn plan_scan_targets(
+    db: State<'_, Database>,
+    targets: Vec<String>,
+    exclude_targets: Option<Vec<String>>,
+    scan_type: Option<String>,
+    ports: Option<String>,
+    aggressive: Option<bool>,
+    max_packets_per_second: Option<u32>,
+    profile_id: Option<String>,
+) -> Result<ScanTargetPlan, String> {
+    let base = base_scan_config(&db, profile_id.as_deref(), None, scan_type.as_deref())?;
+    let overrides = ScanOverrides {
+        scan_type: scan_type.as_deref().map(parse_scan_type).transpose()?,
+        ports,
+        exclude_targets,
+        aggressive,
+        max_packets_per_second,
+        ..Default::default()
+    };
+
+    let mut config = overrides.apply(base, targets);
+    let nmap = locate_nmap(configured_nmap_path(&db)?.as_deref());
+    apply_scan_privileges(&mut config, &detect_scan_privileges(nmap.as_ref().map(|n| n.path.as_str())))?;
+
+    let plan = targets::expand_targets(&config.targets, config.exclude_targets.as_deref().unwrap_or_default())?;
+    let estimate = estimate_scan(plan.total_hosts, &config);
+    Ok(ScanTargetPlan { plan, estimate })
+}
+
 /// List all scans for a client
+///
+/// Only the scan records are loaded; use `get_scan` for hosts and ports.
 #[tauri::command]
 pub async fn list_scans(
-    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
     client_id: String,
 ) -> Result<Vec<ScanJob>, String> {
-    let scans = state.scans.lock().map_err(|e| e.to_string())?;
-    Ok(scans.iter()
-        .filter(|s| s.client_id == client_id)
-        .cloned()
-        .collect())
+    ScanRepository::new(&db).list_by_client(&client_id).map_err(|e| e.to_string())
 }
 
-/// Get a specific scan by ID
+/// Get a scan with its stored results
 #[tauri::command]
 pub async fn get_scan(
-    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
+    scan_id: String,
+) -> Result<Option<ScanDetail>, String> {
+    let repo = ScanRepository::new(&db);
+    let scan = match repo.get(&scan_id).map_err(|e| e.to_string())? {
+        Some(scan) => scan,
+        None => return Ok(None),
+    };
+
+    Ok(Some(ScanDetail {
+        results: repo.get_results(&scan_id).map_err(|e| e.to_string())?,
+        has_raw_xml: repo.has_raw_xml(&scan_id).map_err(|e| e.to_string())?,
+        scan,
+    }))
+}
+
+/// Get the original Nmap XML of a scan, for exporting as evidence
+#[tauri::command]
+pub async fn get_scan_raw_xml(
+    db: State<'_, Database>,
     scan_id: String,
-) -> Result<Option<ScanJob>, String> {
-    let scans = state.scans.lock().map_err(|e| e.to_string())?;
-    Ok(scans.iter().find(|s| s.id == scan_id).cloned())
+) -> Result<Option<String>, String> {
+    ScanRepository::new(&db).get_raw_xml(&scan_id).map_err(|e| e.to_string())
 }
 
-/// Delete a scan
+/// Delete a scan and its stored results
 #[tauri::command]
 pub async fn delete_scan(
+    db: State<'_, Database>,
+    scan_id: String,
+) -> Result<bool, String> {
+    ScanRepository::new(&db).delete(&scan_id).map_err(|e| e.to_string())
+}
+
+/// Run a queued scan, or resume an interrupted one
+///
+/// Batched scans pick up from their first incomplete batch; scans without
+/// a batch size run again from the start. Runs in the background, emitting
+/// [`SCAN_PROGRESS_EVENT`] as Nmap reports progress, after each batch and
+/// when the scan ends.
+#[tauri::command]
+pub async fn resume_scan(
+    app: AppHandle,
+    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
+    scan_id: String,
+) -> Result<ScanJob, String> {
+    let scans = ScanRepository::new(&db);
+    let mut job = scans
+        .get(&scan_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scan not found: {}", scan_id))?;
+
+    if job.import.is_some() || job.verification.is_some() {
+        return Err("Only Nmap scans can be run".to_string());
+    }
+    if job.status == ScanStatus::Completed {
+        return Err("Scan has already completed".to_string());
+    }
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut running = state.running_scans.lock().map_err(|e| e.to_string())?;
+        if running.contains_key(&scan_id) {
+            return Err("Scan is already running".to_string());
+        }
+        running.insert(scan_id.clone(), cancelled.clone());
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    job.status = ScanStatus::Running;
+    job.started_at.get_or_insert(now);
+    job.completed_at = None;
+    job.error = None;
+    if let Err(e) = scans.save(&job) {
+        if let Ok(mut running) = state.running_scans.lock() {
+            running.remove(&scan_id);
+        }
+        return Err(e.to_string());
+    }
+
+    let mut running_job = job.clone();
+    tauri::async_runtime::spawn(async move {
+        run_scan_in_background(&app, &mut running_job, &cancelled).await;
+    });
+
+    Ok(job)
+}
+
+/// Cancel a running scan; its completed batches are kept for resuming
+#[tauri::command]
+pub async fn cancel_scan(
     state: State<'_, NetworkState>,
     scan_id: String,
 ) -> Result<bool, String> {
-    let mut scans = state.scans.lock().map_err(|e| e.to_string())?;
-    let len_before = scans.len();
-    scans.retain(|s| s.id != scan_id);
-    Ok(scans.len() < len_before)
+    let running = state.running_scans.lock().map_err(|e| e.to_string())?;
+    match running.get(&scan_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Audit entry for an Nmap scan about to run, with its exact arguments
+///
+/// Built from the job as fitted to the session's privileges, which may have
+/// turned a SYN scan into a connect scan.
+fn scan_launch_entry(job: &ScanJob) -> AuditEntry {
+    AuditEntry::new(AuditOperation::ScanLaunched, "scan", Some(&job.id)).with_detail(serde_json::json!({
+        "clientId": job.client_id,
+        "name": job.name,
+        "scanner": "nmap",
+        "targets": job.config.targets,
+        "excludeTargets": job.config.exclude_targets,
+        "nmapArgs": build_nmap_command(&job.config),
+    }))
+}
+
+/// Audit entry for a scan by the built-in TCP connect scanner
+fn tcp_scan_entry(client_id: Option<&str>, scan_id: Option<&str>, targets: &[String], ports: &[u16]) -> AuditEntry {
+    AuditEntry::new(AuditOperation::ScanLaunched, "scan", scan_id).with_detail(serde_json::json!({
+        "clientId": client_id,
+        "scanner": "tcp_connect",
+        "targets": targets,
+        "ports": ports,
+    }))
+}
+
+async fn run_scan_in_background(app: &AppHandle, job: &mut ScanJob, cancelled: &AtomicBool) {
+    let db = app.state::<Database>();
+    let emit = |job: &ScanJob| {
+        if let Err(e) = app.emit(SCAN_PROGRESS_EVENT, job) {
+            tracing::warn!("Failed to emit scan progress event: {}", e);
+        }
+    };
+
+    // Recorded before Nmap starts, so a crash mid-scan can't lose the record
+    let launch = |job: &ScanJob| {
+        audit::record(&db, scan_launch_entry(job))
+            .map_err(|e| format!("Not run because it couldn't be recorded in the audit log: {}", e))
+    };
+    let run = execute_scan_job(&db, job, cancelled, launch, emit).await;
+
+    if let Err(e) = ScanRepository::new(&db).save(job) {
+        tracing::warn!("Failed to store scan {}: {}", job.id, e);
+    }
+    if let Ok(mut running) = app.state::<NetworkState>().running_scans.lock() {
+        running.remove(&job.id);
+    }
+
+    match job.status {
+        ScanStatus::Completed => {
+            onboarding::record(&db, Milestone::FirstScan);
+            activity::log(&db, ActivityEvent::new(
+                &job.client_id,
+                ActivityEventType::ScanCreated,
+                "scan",
+                Some(&job.id),
+                format!("Scan \"{}\" found {} live hosts", job.name, run.hosts_up),
+            )
+            .with_detail(serde_json::json!({
+                "targets": job.config.targets,
+                "batches": job.batches,
+                "assetsUpdated": run.assets_updated,
+            })));
+        }
+        ScanStatus::Failed => {
+            tracing::warn!("Scan {} failed: {}", job.id, job.error.as_deref().unwrap_or_default());
+        }
+        _ => tracing::info!("Scan {} cancelled", job.id),
+    }
+    emit(job);
+}
+
+/// Compare an earlier scan with a later scan of the same client
+#[tauri::command]
+pub async fn diff_scans(
+    db: State<'_, Database>,
+    scan_id_a: String,
+    scan_id_b: String,
+) -> Result<ScanDiff, String> {
+    diff_stored_scans(&db, &scan_id_a, &scan_id_b, None)
+}
+
+/// Compare a scan with a baseline scan, or with the client's asset inventory
+///
+/// Without a baseline the comparison is against the active assets in the
+/// inventory, so hosts the inventory knows about that the scan didn't see
+/// show up as disappeared.
+#[tauri::command]
+pub async fn compare_scans(
+    db: State<'_, Database>,
+    scan_id: String,
+    baseline_scan_id: Option<String>,
+) -> Result<ScanDiff, String> {
+    if let Some(baseline) = baseline_scan_id {
+        return diff_stored_scans(&db, &baseline, &scan_id, None);
+    }
+
+    let repo = ScanRepository::new(&db);
+    let job = repo.get(&scan_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scan not found: {}", scan_id))?;
+    let results = stored_scan_results(&repo, &job)?;
+    let assets = AssetInventory::new(&db).get_client_assets(&job.client_id).map_err(|e| e.to_string())?;
+
+    Ok(crate::network::diff::diff_scans(&inventory_results(&assets), &results))
+}
+
+/// Diff two stored scans, optionally requiring they belong to `client_id`
+pub(crate) fn diff_stored_scans(
+    db: &Database,
+    scan_id_a: &str,
+    scan_id_b: &str,
+    client_id: Option<&str>,
+) -> Result<ScanDiff, String> {
+    let repo = ScanRepository::new(db);
+    let find = |id: &str| {
+        repo.get(id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Scan not found: {}", id))
+    };
+    let (job_a, job_b) = (find(scan_id_a)?, find(scan_id_b)?);
+
+    if job_a.client_id != job_b.client_id {
+        return Err("Scans belong to different clients".to_string());
+    }
+    if client_id.is_some_and(|c| c != job_a.client_id) {
+        return Err("Scans do not belong to this client".to_string());
+    }
+
+    let results_a = stored_scan_results(&repo, &job_a)?;
+    let results_b = stored_scan_results(&repo, &job_b)?;
+    Ok(crate::network::diff::diff_scans(&results_a, &results_b))
+}
+
+fn stored_scan_results(repo: &ScanRepository, job: &ScanJob) -> Result<ScanResults, String> {
+    match repo.get_results(&job.id).map_err(|e| e.to_string())? {
+        Some(results) => Ok(results),
+        None => Err(format!("Scan '{}' has no stored results", job.name)),
+    }
+}
+
+// ============================================================================
+// Scan Import Commands
+// ============================================================================
+
+/// Largest imported file whose original XML is kept alongside the results
+const MAX_STORED_IMPORT_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Recorded as the source of imports pasted rather than read from a file
+const PASTED_IMPORT_SOURCE: &str = "Pasted output";
+
+/// Where imported Nmap output came from
+enum NmapImportSource {
+    File(String),
+    Pasted(String),
+}
+
+/// Import an Nmap XML (`-oX`) or greppable (`-oG`) file produced outside the app
+///
+/// See [`import_nmap_xml`].
+#[tauri::command]
+pub async fn import_scan_results(
+    db: State<'_, Database>,
+    client_id: String,
+    path: String,
+    name: Option<String>,
+) -> Result<ScanImportSummary, String> {
+    import_nmap_xml(db, client_id, Some(path), None, name).await
+}
+
+/// Import Nmap output produced outside the app, from a file or pasted text
+///
+/// Accepts XML (`-oX`) and greppable (`-oG`) output. Files are streamed
+/// rather than read into memory. Creates a completed scan flagged as
+/// imported and feeds every live host into the client's asset inventory;
+/// hosts that fail to parse are skipped and reported in the summary.
+/// Importing the same scan twice (same Nmap start time and command line)
+/// is rejected.
+#[tauri::command]
+pub async fn import_nmap_xml(
+    db: State<'_, Database>,
+    client_id: String,
+    path: Option<String>,
+    content: Option<String>,
+    name: Option<String>,
+) -> Result<ScanImportSummary, String> {
+    let (mut results, mut warnings, format, source) = match (path, content) {
+        (Some(path), None) => {
+            let file_path = std::path::PathBuf::from(&path);
+            let (results, warnings, format) = tokio::task::spawn_blocking(move || parse_nmap_file(&file_path))
+                .await
+                .map_err(|e| e.to_string())??;
+            (results, warnings, format, NmapImportSource::File(path))
+        }
+        (None, Some(content)) => {
+            let (results, warnings, format) = parse_nmap_output(&content)?;
+            (results, warnings, format, NmapImportSource::Pasted(content))
+        }
+        _ => return Err("Give either the path of an Nmap output file or the output itself".to_string()),
+    };
+
+    let scans = ScanRepository::new(&db);
+    let existing = scans.list_by_client(&client_id).map_err(|e| e.to_string())?;
+    let duplicate = existing.iter().find(|s| {
+        s.import.as_ref().is_some_and(|i| {
+            i.scan_started == results.start_time && i.command_line == results.command_line
+        })
+    });
+    if let Some(existing) = duplicate {
+        return Err(format!("This scan was already imported as \"{}\"", existing.name));
+    }
+
+    // Keep the original XML as evidence alongside the parsed results
+    let size = match &source {
+        NmapImportSource::File(path) => std::fs::metadata(path).map(|m| m.len()).unwrap_or(u64::MAX),
+        NmapImportSource::Pasted(content) => content.len() as u64,
+    };
+    let raw_xml = if format != NmapOutputFormat::Xml {
+        None
+    } else if size <= MAX_STORED_IMPORT_BYTES {
+        match &source {
+            NmapImportSource::File(path) => match std::fs::read_to_string(path) {
+                Ok(xml) => Some(xml),
+                Err(e) => {
+                    warnings.push(format!("Could not keep the original XML: {}", e));
+                    None
+                }
+            },
+            NmapImportSource::Pasted(content) => Some(content.clone()),
+        }
+    } else {
+        warnings.push(format!(
+            "File is larger than {} MB; only the parsed results were kept, not the original XML",
+            MAX_STORED_IMPORT_BYTES / (1024 * 1024)
+        ));
+        None
+    };
+
+    let (source_file, file_name) = match source {
+        NmapImportSource::File(path) => {
+            let file_name = std::path::Path::new(&path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone());
+            (path, file_name)
+        }
+        NmapImportSource::Pasted(_) => (PASTED_IMPORT_SOURCE.to_string(), "pasted output".to_string()),
+    };
+    let now = chrono::Utc::now().to_rfc3339();
+    results.scan_id = Uuid::new_v4().to_string();
+    let live_hosts: Vec<&DiscoveredHost> = results.hosts.iter().filter(|h| h.status == "up").collect();
+
+    let job = ScanJob {
+        id: results.scan_id.clone(),
+        client_id: client_id.clone(),
+        name: name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| format!("Imported: {}", file_name)),
+        config: ScanConfig {
+            targets: live_hosts.iter().map(|h| h.ip_address.clone()).collect(),
+            scan_type: ScanType::Custom,
+            custom_args: Some(results.command_line.clone()),
+            output_formats: vec![OutputFormat::Xml],
+            ..Default::default()
+        },
+        status: ScanStatus::Completed,
+        created_at: now.clone(),
+        started_at: Some(results.start_time.clone()),
+        completed_at: Some(results.end_time.clone()),
+        error: None,
+        progress: 100,
+        totals: Some(ScanTotals::from_results(&results)),
+        verification: None,
+        import: Some(ScanImportInfo {
+            source_file,
+            format,
+            command_line: results.command_line.clone(),
+            nmap_version: results.nmap_version.clone(),
+            scan_started: results.start_time.clone(),
+            imported_at: now,
+        }),
+        warnings: vec![],
+        batches: None,
+        live: None,
+    };
+
+    let assets = AssetRepository::new(&db);
+    let inventory = AssetInventory::new(&db);
+    let mut new_assets = 0;
+    let mut updated_assets = 0;
+    for host in &live_hosts {
+        let existed = matches!(assets.find_by_ip(&client_id, &host.ip_address), Ok(Some(_)));
+        match inventory.upsert_from_discovery(&client_id, host, &job.id) {
+            Ok(_) if existed => updated_assets += 1,
+            Ok(_) => new_assets += 1,
+            Err(e) => warnings.push(format!("Failed to update asset {}: {}", host.ip_address, e)),
+        }
+    }
+
+    scans.save(&job).map_err(|e| e.to_string())?;
+    scans.save_results(&job.id, &results, raw_xml.as_deref()).map_err(|e| e.to_string())?;
+    onboarding::record(&db, Milestone::FirstScan);
+
+    activity::log(&db, ActivityEvent::new(
+        &client_id,
+        ActivityEventType::ScanImported,
+        "scan",
+        Some(&job.id),
+        format!("Imported {} live host(s) from {}", live_hosts.len(), file_name),
+    )
+    .with_detail(serde_json::json!({
+        "commandLine": results.command_line,
+        "newAssets": new_assets,
+        "updatedAssets": updated_assets,
+    })));
+
+    tracing::info!("Imported scan {} from {} ({} hosts)", job.id, file_name, live_hosts.len());
+
+    Ok(ScanImportSummary {
+        scan_id: job.id,
+        scan_name: job.name,
+        hosts_imported: live_hosts.len(),
+        new_assets,
+        updated_assets,
+        warnings,
+    })
 }
 
 // ============================================================================
@@ -215,6 +801,7 @@ pub struct ScanNetworkResponse {
 /// Extended ports: 24 common service ports
 #[tauri::command]
 pub async fn scan_network(
+    db: State<'_, Database>,
     request: ScanNetworkRequest,
 ) -> Result<ScanNetworkResponse, String> {
     tracing::info!("Starting native TCP scan of: {}", request.cidr);
@@ -230,6 +817,8 @@ pub async fn scan_network(
         DEFAULT_SCAN_PORTS.to_vec()
     };
 
+    audit::record(&db, tcp_scan_entry(None, None, &[request.cidr.clone()], &ports)).map_err(|e| e.to_string())?;
+
     // Perform the scan
     let hosts = scan_network_with_ports(&request.cidr, ports.clone()).await?;
 
@@ -239,6 +828,7 @@ pub async fn scan_network(
     // For hosts_scanned, we need to parse the CIDR to get the count
     let hosts_scanned = estimate_host_count(&request.cidr).unwrap_or(0);
     let hosts_alive = hosts.len();
+    onboarding::record(&db, Milestone::FirstScan);
 
     tracing::info!(
         "Scan complete: {} live hosts found out of ~{} in {} ms",
@@ -260,12 +850,14 @@ pub async fn scan_network(
 /// Quick scan of a single host
 #[tauri::command]
 pub async fn scan_single_host(
+    db: State<'_, Database>,
     ip: String,
     ports: Option<Vec<u16>>,
 ) -> Result<ScannedHost, String> {
     tracing::info!("Scanning single host: {}", ip);
 
     let ports = ports.unwrap_or_else(|| DEFAULT_SCAN_PORTS.to_vec());
+    audit::record(&db, tcp_scan_entry(None, None, &[ip.clone()], &ports)).map_err(|e| e.to_string())?;
 
     let config = crate::network::scanner::TcpScannerConfig {
         ports,
@@ -273,7 +865,9 @@ pub async fn scan_single_host(
     };
 
     let scanner = crate::network::scanner::TcpScanner::with_config(config);
-    scanner.scan_single_host(&ip).await
+    let host = scanner.scan_single_host(&ip).await?;
+    onboarding::record(&db, Milestone::FirstScan);
+    Ok(host)
 }
 
 /// Get the default ports used for scanning
@@ -297,114 +891,1017 @@ fn estimate_host_count(cidr: &str) -> Option<usize> {
 }
 
 // ============================================================================
-// Asset Inventory Commands
+// Findings & Verification Commands
 // ============================================================================
 
-/// Get all assets for a client
+/// Get the built-in finding detection rules
 #[tauri::command]
-pub async fn list_assets(
-    state: State<'_, NetworkState>,
-    client_id: String,
-) -> Result<Vec<Asset>, String> {
-    let inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    Ok(inventory.get_client_assets(&client_id))
+pub async fn get_finding_rule_list() -> Result<Vec<FindingRule>, String> {
+    Ok(get_finding_rules())
 }
 
-/// Get demo assets for development
+/// Run detection rules against scan results and record new findings
+///
+/// A host/port/rule that already has an open finding is not raised twice.
 #[tauri::command]
-pub async fn get_demo_assets(client_id: String) -> Result<Vec<Asset>, String> {
-    Ok(generate_demo_assets(&client_id))
+pub async fn detect_network_findings(
+    db: State<'_, Database>,
+    client_id: String,
+    hosts: Vec<ScannedHost>,
+) -> Result<Vec<NetworkFinding>, String> {
+    NetworkFindingRepository::new(&db)
+        .create_new(detect_findings(&client_id, &hosts))
+        .map_err(|e| e.to_string())
 }
 
-/// Get a specific asset by ID
+/// List findings for a client
 #[tauri::command]
-pub async fn get_asset(
-    state: State<'_, NetworkState>,
-    asset_id: String,
-) -> Result<Option<Asset>, String> {
-    let inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    Ok(inventory.get_asset(&asset_id).cloned())
+pub async fn list_network_findings(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<NetworkFinding>, String> {
+    NetworkFindingRepository::new(&db)
+        .list_by_client(&client_id)
+        .map_err(|e| e.to_string())
 }
 
-/// Update asset details request
+/// Request to verify remediation of findings
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct UpdateAssetRequest {
-    pub id: String,
-    pub name: String,
-    pub category: String,
-    pub criticality: String,
-    pub status: String,
-    pub location: Option<String>,
-    pub owner: Option<String>,
-    pub description: Option<String>,
-    pub tags: Vec<String>,
+pub struct CreateVerificationScanRequest {
+    pub client_id: String,
+    pub finding_ids: Vec<String>,
+    pub name: Option<String>,
 }
 
-/// Update an asset
+/// Re-scan exactly the hosts/ports behind the selected findings and update them
+///
+/// The scan is recorded as a verification scan linked to the findings, and
+/// its summary (fixed / still present / unreachable) is stored on the job.
 #[tauri::command]
-pub async fn update_asset(
-    state: State<'_, NetworkState>,
-    request: UpdateAssetRequest,
-) -> Result<Asset, String> {
-    let mut inventory = state.inventory.lock().map_err(|e| e.to_string())?;
+pub async fn create_verification_scan(
+    db: State<'_, Database>,
+    request: CreateVerificationScanRequest,
+) -> Result<ScanJob, String> {
+    let findings = NetworkFindingRepository::new(&db);
+    let mut selected: Vec<NetworkFinding> = findings
+        .list_by_client(&request.client_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|f| request.finding_ids.contains(&f.id))
+        .collect();
 
-    let existing = inventory.get_asset(&request.id)
-        .ok_or_else(|| "Asset not found".to_string())?
-        .clone();
+    if selected.len() != request.finding_ids.len() {
+        return Err("One or more findings were not found for this client".to_string());
+    }
+    if selected.is_empty() {
+        return Err("No findings selected for verification".to_string());
+    }
 
-    let updated = Asset {
-        id: existing.id,
-        client_id: existing.client_id,
-        name: request.name,
-        ip_address: existing.ip_address,
-        mac_address: existing.mac_address,
-        category: parse_asset_category(&request.category)?,
-        operating_system: existing.operating_system,
-        criticality: parse_criticality(&request.criticality)?,
-        status: parse_asset_status(&request.status)?,
-        location: request.location,
-        owner: request.owner,
-        description: request.description,
-        services: existing.services,
-        tags: request.tags,
-        first_seen: existing.first_seen,
-        last_seen: existing.last_seen,
-        scan_ids: existing.scan_ids,
-        metadata: existing.metadata,
+    let targets = plan_verification_targets(&selected);
+
+    let mut all_hosts: Vec<String> = targets.iter().flat_map(|t| t.hosts.clone()).collect();
+    all_hosts.sort();
+    let mut all_ports: Vec<u16> = targets.iter().flat_map(|t| t.ports.clone()).collect();
+    all_ports.sort_unstable();
+    all_ports.dedup();
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut job = ScanJob {
+        id: Uuid::new_v4().to_string(),
+        client_id: request.client_id.clone(),
+        name: request.name.unwrap_or_else(|| format!("Verification of {} finding(s)", request.finding_ids.len())),
+        config: ScanConfig {
+            targets: all_hosts,
+            scan_type: ScanType::Custom,
+            ports: Some(all_ports.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",")),
+            skip_discovery: true,
+            ..Default::default()
+        },
+        status: ScanStatus::Running,
+        created_at: now.clone(),
+        started_at: Some(now),
+        completed_at: None,
+        error: None,
+        progress: 0,
+        totals: None,
+        verification: Some(VerificationInfo {
+            finding_ids: request.finding_ids.clone(),
+            targets: targets.clone(),
+            summary: None,
+        }),
+        import: None,
+        warnings: vec![],
+        batches: None,
+        live: None,
     };
 
-    inventory.update_asset(updated)
-}
+    audit::record(&db, tcp_scan_entry(Some(&job.client_id), Some(&job.id), &job.config.targets, &all_ports))
+        .map_err(|e| e.to_string())?;
+    let scans = ScanRepository::new(&db);
+    scans.save(&job).map_err(|e| e.to_string())?;
 
-/// Delete an asset
-#[tauri::command]
-pub async fn delete_asset(
-    state: State<'_, NetworkState>,
-    asset_id: String,
-) -> Result<bool, String> {
-    let mut inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    Ok(inventory.delete_asset(&asset_id))
-}
+    tracing::info!("Running verification scan {} over {} batch(es)", job.id, targets.len());
 
-/// Get network statistics for a client
-#[tauri::command]
-pub async fn get_network_stats(
-    state: State<'_, NetworkState>,
-    client_id: String,
-) -> Result<NetworkStats, String> {
-    let inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    Ok(inventory.get_stats(&client_id))
+    let verified = verify_targets(&targets).await.and_then(|hosts| {
+        let summary = evaluate_verification(&mut selected.iter_mut().collect::<Vec<_>>(), &hosts, &job.id);
+        findings
+            .save_all(&selected)
+            .map(|_| summary)
+            .map_err(|e| format!("Failed to store the verified findings: {}", e))
+    });
+    match verified {
+        Ok(summary) => {
+            job.status = ScanStatus::Completed;
+            job.progress = 100;
+            if let Some(ref mut verification) = job.verification {
+                verification.summary = Some(summary);
+            }
+        }
+        Err(e) => {
+            job.status = ScanStatus::Failed;
+            job.error = Some(e);
+        }
+    }
+    job.completed_at = Some(chrono::Utc::now().to_rfc3339());
+
+    scans.save(&job).map_err(|e| e.to_string())?;
+
+    Ok(job)
 }
 
 // ============================================================================
-// Asset Groups Commands
+// Scan Profile Commands
 // ============================================================================
 
-/// Create a new asset group
+/// Create (or edit) a scan profile request
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CreateGroupRequest {
+pub struct ScanProfileRequest {
+    /// Leave unset for a profile every client can use
+    pub client_id: Option<String>,
+    pub name: String,
+    pub scan_type: String,
+    pub custom_args: Option<String>,
+    pub ports: Option<String>,
+    pub exclude_targets: Option<Vec<String>>,
+    #[serde(default)]
+    pub aggressive: bool,
+    #[serde(default)]
+    pub skip_discovery: bool,
+    pub batch_size: Option<usize>,
+    pub batch_concurrency: Option<usize>,
+    pub max_packets_per_second: Option<u32>,
+    pub notes: Option<String>,
+}
+
+/// Validate a profile request and build the scan defaults it saves
+fn profile_scan_config(request: &ScanProfileRequest) -> Result<ScanConfig, String> {
+    if request.name.trim().is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    let excludes: Vec<String> = request
+        .exclude_targets
+        .iter()
+        .flatten()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let config = ScanConfig {
+        targets: vec![],
+        scan_type: parse_scan_type(&request.scan_type)?,
+        custom_args: request.custom_args.clone(),
+        ports: request.ports.clone().filter(|p| !p.trim().is_empty()),
+        exclude_targets: (!excludes.is_empty()).then_some(excludes),
+        aggressive: request.aggressive,
+        skip_discovery: request.skip_discovery,
+        output_formats: vec![OutputFormat::Xml],
+        privilege_mode: ScanPrivilegeMode::default(),
+        batch_size: request.batch_size,
+        batch_concurrency: request.batch_concurrency,
+        max_packets_per_second: request.max_packets_per_second,
+    };
+    check_scan_targets(&config)?;
+    validate_batching(&config)?;
+    check_custom_args(&config)?;
+    Ok(config)
+}
+
+/// Save scan defaults (exclusions, ports, timing) as a named profile
+#[tauri::command]
+pub async fn create_scan_profile(
+    db: State<'_, Database>,
+    request: ScanProfileRequest,
+) -> Result<ScanProfile, String> {
+    let config = profile_scan_config(&request)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let profile = ScanProfile {
+        id: Uuid::new_v4().to_string(),
+        client_id: request.client_id,
+        name: request.name.trim().to_string(),
+        config,
+        notes: request.notes.filter(|n| !n.trim().is_empty()),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    ScanProfileRepository::new(&db).save(&profile).map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+/// List the global scan profiles and, given a client, that client's own
+#[tauri::command]
+pub async fn list_scan_profiles(
+    db: State<'_, Database>,
+    client_id: Option<String>,
+) -> Result<Vec<ScanProfile>, String> {
+    ScanProfileRepository::new(&db)
+        .list(client_id.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Change a scan profile's name, settings or notes
+///
+/// A profile can't move between clients or become global.
+#[tauri::command]
+pub async fn update_scan_profile(
+    db: State<'_, Database>,
+    id: String,
+    request: ScanProfileRequest,
+) -> Result<ScanProfile, String> {
+    let repo = ScanProfileRepository::new(&db);
+    let mut profile = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scan profile not found: {}", id))?;
+    if request.client_id != profile.client_id {
+        return Err("A scan profile can't be moved to another client".to_string());
+    }
+
+    profile.config = profile_scan_config(&request)?;
+    profile.name = request.name.trim().to_string();
+    profile.notes = request.notes.filter(|n| !n.trim().is_empty());
+    profile.updated_at = chrono::Utc::now().to_rfc3339();
+    repo.save(&profile).map_err(|e| e.to_string())?;
+
+    Ok(profile)
+}
+
+/// Delete a scan profile; scans already created from it are unaffected
+#[tauri::command]
+pub async fn delete_scan_profile(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    ScanProfileRepository::new(&db).delete(&id).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Scan Schedule Commands
+// ============================================================================
+
+/// Create (or edit) a recurring scan schedule request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateScanScheduleRequest {
+    pub client_id: String,
+    pub name: String,
+    pub targets: Vec<String>,
+    pub scan_type: String,
+    pub custom_args: Option<String>,
+    pub ports: Option<String>,
+    pub exclude_targets: Option<Vec<String>>,
+    pub aggressive: bool,
+    pub skip_discovery: bool,
+    pub batch_size: Option<usize>,
+    pub batch_concurrency: Option<usize>,
+    pub max_packets_per_second: Option<u32>,
+    pub frequency: ScheduleFrequency,
+    /// Defaults to running a missed scan once when the app is next open
+    #[serde(default)]
+    pub missed_runs: MissedRunPolicy,
+}
+
+/// Validate a schedule request and build the scan it runs
+fn schedule_scan_config(request: &CreateScanScheduleRequest) -> Result<ScanConfig, String> {
+    let scan_type = parse_scan_type(&request.scan_type)?;
+    validate_frequency(&request.frequency)?;
+
+    if request.targets.is_empty() {
+        return Err("At least one target is required".to_string());
+    }
+    let config = ScanConfig {
+        targets: request.targets.clone(),
+        scan_type,
+        custom_args: request.custom_args.clone(),
+        ports: request.ports.clone(),
+        exclude_targets: request.exclude_targets.clone(),
+        aggressive: request.aggressive,
+        skip_discovery: request.skip_discovery,
+        output_formats: vec![OutputFormat::Xml],
+        privilege_mode: ScanPrivilegeMode::default(),
+        batch_size: request.batch_size,
+        batch_concurrency: request.batch_concurrency,
+        max_packets_per_second: request.max_packets_per_second,
+    };
+    check_scan_targets(&config)?;
+    validate_batching(&config)?;
+    check_custom_args(&config)?;
+    Ok(config)
+}
+
+/// Create a recurring scan, run by the background scheduler
+#[tauri::command]
+pub async fn create_scan_schedule(
+    db: State<'_, Database>,
+    request: CreateScanScheduleRequest,
+) -> Result<ScanSchedule, String> {
+    let config = schedule_scan_config(&request)?;
+    let now = chrono::Utc::now();
+
+    let schedule = ScanSchedule {
+        id: Uuid::new_v4().to_string(),
+        client_id: request.client_id,
+        name: request.name,
+        config,
+        frequency: request.frequency,
+        enabled: true,
+        missed_runs: request.missed_runs,
+        next_run: Some(next_run_after(&request.frequency, now).to_rfc3339()),
+        last_run: None,
+        last_status: None,
+        last_error: None,
+        last_scan_id: None,
+        created_at: now.to_rfc3339(),
+    };
+
+    ScanScheduleRepository::new(&db).create(&schedule).map_err(|e| e.to_string())?;
+    tracing::info!("Created scan schedule {} ({:?})", schedule.id, schedule.frequency);
+
+    Ok(schedule)
+}
+
+/// List scan schedules, optionally for a single client
+#[tauri::command]
+pub async fn list_scan_schedules(
+    db: State<'_, Database>,
+    client_id: Option<String>,
+) -> Result<Vec<ScanSchedule>, String> {
+    let repo = ScanScheduleRepository::new(&db);
+    match client_id {
+        Some(client_id) => repo.list_by_client(&client_id),
+        None => repo.list_all(),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Change a scan schedule's name, scan, timing or missed-run handling
+///
+/// The client can't change. An enabled schedule is next due one interval
+/// (or at the next daily time) from now; its run history is kept.
+#[tauri::command]
+pub async fn update_scan_schedule(
+    db: State<'_, Database>,
+    id: String,
+    request: CreateScanScheduleRequest,
+) -> Result<ScanSchedule, String> {
+    let repo = ScanScheduleRepository::new(&db);
+    let mut schedule = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scan schedule not found: {}", id))?;
+    if request.client_id != schedule.client_id {
+        return Err("A scan schedule can't be moved to another client".to_string());
+    }
+
+    schedule.config = schedule_scan_config(&request)?;
+    schedule.name = request.name;
+    schedule.frequency = request.frequency;
+    schedule.missed_runs = request.missed_runs;
+    if schedule.enabled {
+        schedule.next_run = Some(next_run_after(&schedule.frequency, chrono::Utc::now()).to_rfc3339());
+    }
+    repo.update(&schedule).map_err(|e| e.to_string())?;
+    tracing::info!("Updated scan schedule {} ({:?})", schedule.id, schedule.frequency);
+
+    Ok(schedule)
+}
+
+/// Enable or disable a scan schedule
+///
+/// Re-enabling schedules the next run from now rather than catching up on
+/// runs missed while disabled.
+#[tauri::command]
+pub async fn toggle_scan_schedule(
+    db: State<'_, Database>,
+    id: String,
+    enabled: bool,
+) -> Result<ScanSchedule, String> {
+    let repo = ScanScheduleRepository::new(&db);
+    let schedule = repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scan schedule not found: {}", id))?;
+
+    let next_run = enabled.then(|| next_run_after(&schedule.frequency, chrono::Utc::now()).to_rfc3339());
+    repo.set_enabled(&id, enabled, next_run.as_deref()).map_err(|e| e.to_string())?;
+
+    repo.get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scan schedule not found: {}", id))
+}
+
+/// Delete a scan schedule
+#[tauri::command]
+pub async fn delete_scan_schedule(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    ScanScheduleRepository::new(&db).delete(&id).map_err(|e| e.to_string())
+}
+
+/// Background loop that launches due scan schedules
+///
+/// Spawned once at startup. Checks every minute; each due schedule has its
+/// next run advanced before it starts, so a scan that outlasts the tick is
+/// not launched twice. Runs missed while the app was closed go ahead once,
+/// unless the schedule skips missed runs. Runs execute concurrently and their jobs appear in
+/// the scan list like any other scan.
+pub async fn run_scan_scheduler(app: AppHandle) {
+    let mut ticker = tokio::time::interval(SCHEDULER_TICK);
+
+    loop {
+        ticker.tick().await;
+
+        // The database is initialized asynchronously at startup
+        let db = match app.try_state::<Database>() {
+            Some(db) => db,
+            None => continue,
+        };
+        let repo = ScanScheduleRepository::new(&db);
+
+        let now = chrono::Utc::now();
+        let due = match repo.list_due(&now.to_rfc3339()) {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::warn!("Failed to load due scan schedules: {}", e);
+                continue;
+            }
+        };
+
+        for schedule in due {
+            let next_run = next_run_after(&schedule.frequency, now).to_rfc3339();
+            if let Err(e) = repo.set_next_run(&schedule.id, &next_run) {
+                tracing::warn!("Failed to advance scan schedule {}: {}", schedule.id, e);
+                continue;
+            }
+            if !should_run(&schedule, now) {
+                tracing::info!("Skipping missed run of scan schedule \"{}\", next due {}", schedule.name, next_run);
+                continue;
+            }
+
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                run_scheduled_scan(&app, schedule).await;
+            });
+        }
+    }
+}
+
+async fn run_scheduled_scan(app: &AppHandle, schedule: ScanSchedule) {
+    let db = app.state::<Database>();
+    let scans = ScanRepository::new(&db);
+
+    let mut job = scheduled_job(&schedule);
+    tracing::info!("Running scheduled scan \"{}\" as {}", schedule.name, job.id);
+
+    if let Err(e) = scans.save(&job) {
+        tracing::warn!("Failed to store scheduled scan {}: {}", job.id, e);
+    }
+
+    let launch = |job: &ScanJob| {
+        let mut entry = scan_launch_entry(job);
+        entry.detail["scheduleId"] = serde_json::json!(schedule.id);
+        audit::record(&db, entry)
+            .map_err(|e| format!("Not run because it couldn't be recorded in the audit log: {}", e))
+    };
+    let completed = execute_scheduled_job(&db, &schedule, &mut job, launch).await;
+
+    if let Err(e) = scans.save(&job) {
+        tracing::warn!("Failed to store scheduled scan {}: {}", job.id, e);
+    }
+    if let Err(e) = ScanScheduleRepository::new(&db).record_run(&schedule.id, &job) {
+        tracing::warn!("Failed to record run of scan schedule {}: {}", schedule.id, e);
+    }
+
+    if job.status == ScanStatus::Completed {
+        onboarding::record(&db, Milestone::FirstScan);
+        activity::log(&db, ActivityEvent::new(
+            &job.client_id,
+            ActivityEventType::ScanCreated,
+            "scan",
+            Some(&job.id),
+            format!("Scheduled scan \"{}\" found {} live hosts", schedule.name, completed.hosts_up),
+        )
+        .with_detail(serde_json::json!({
+            "scheduleId": schedule.id,
+            "targets": job.config.targets,
+            "assetsUpdated": completed.assets_updated,
+        })));
+    }
+
+    if let Err(e) = app.emit(SCHEDULED_SCAN_COMPLETED_EVENT, &completed) {
+        tracing::warn!("Failed to emit scheduled scan event: {}", e);
+    }
+}
+
+// ============================================================================
+// Asset Inventory Commands
+// ============================================================================
+
+/// Get all assets for a client
+#[tauri::command]
+pub async fn list_assets(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<Asset>, String> {
+    AssetInventory::new(&db)
+        .get_client_assets(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get demo assets for development
+#[tauri::command]
+pub async fn get_demo_assets(client_id: String) -> Result<Vec<Asset>, String> {
+    Ok(generate_demo_assets(&client_id))
+}
+
+/// Default page size for asset queries
+const DEFAULT_ASSET_PAGE_SIZE: usize = 100;
+
+/// Query a client's assets with a server-side filter, one page at a time
+#[tauri::command]
+pub async fn query_assets(
+    db: State<'_, Database>,
+    client_id: String,
+    filter: Option<AssetFilter>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<AssetPage, String> {
+    AssetInventory::new(&db)
+        .query_assets(
+            &client_id,
+            &filter.unwrap_or_default(),
+            limit.unwrap_or(DEFAULT_ASSET_PAGE_SIZE),
+            offset.unwrap_or(0),
+        )
+        .map_err(|e| e.to_string())
+}
+
+/// Get a specific asset by ID
+#[tauri::command]
+pub async fn get_asset(
+    db: State<'_, Database>,
+    asset_id: String,
+) -> Result<Option<Asset>, String> {
+    AssetInventory::new(&db)
+        .get_asset(&asset_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the changes recorded for an asset across discoveries, oldest first
+#[tauri::command]
+pub async fn get_asset_history(
+    db: State<'_, Database>,
+    asset_id: String,
+) -> Result<Vec<AssetChange>, String> {
+    AssetInventory::new(&db)
+        .get_asset_history(&asset_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Update asset details request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAssetRequest {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub criticality: String,
+    pub status: String,
+    pub location: Option<String>,
+    pub owner: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Update an asset
+#[tauri::command]
+pub async fn update_asset(
+    db: State<'_, Database>,
+    request: UpdateAssetRequest,
+) -> Result<Asset, String> {
+    let inventory = AssetInventory::new(&db);
+
+    let existing = inventory.get_asset(&request.id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Asset not found".to_string())?;
+
+    let updated = Asset {
+        id: existing.id,
+        client_id: existing.client_id,
+        name: request.name,
+        ip_address: existing.ip_address,
+        mac_address: existing.mac_address,
+        category: request.category.parse()?,
+        operating_system: existing.operating_system,
+        criticality: request.criticality.parse()?,
+        status: request.status.parse()?,
+        location: request.location,
+        owner: request.owner,
+        description: request.description,
+        services: existing.services,
+        tags: request.tags,
+        first_seen: existing.first_seen,
+        last_seen: existing.last_seen,
+        scan_ids: existing.scan_ids,
+        metadata: existing.metadata,
+    };
+
+    inventory.update_asset(updated).map_err(|e| e.to_string())
+}
+
+/// Set criticality, owner, location, or tags on many assets at once
+#[tauri::command]
+pub async fn bulk_update_assets(
+    db: State<'_, Database>,
+    asset_ids: Vec<String>,
+    patch: AssetPatch,
+) -> Result<usize, String> {
+    AssetInventory::new(&db)
+        .bulk_update(&asset_ids, &patch)
+        .map_err(|e| e.to_string())
+}
+
+/// Export a client's assets to a CSV or JSON file, one row per asset
+///
+/// The filter narrows the export (category, criticality, tags, ...);
+/// `excel_bom` prefixes CSV output with a UTF-8 byte order mark for Excel.
+#[tauri::command]
+pub async fn export_assets(
+    db: State<'_, Database>,
+    client_id: String,
+    format: String,
+    path: String,
+    filter: Option<AssetFilter>,
+    excel_bom: Option<bool>,
+) -> Result<AssetExportSummary, String> {
+    let format: AssetExportFormat = format.parse()?;
+    let summary = asset_export::export_assets(
+        &db,
+        &client_id,
+        &filter.unwrap_or_default(),
+        format,
+        std::path::Path::new(&path),
+        excel_bom.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())?;
+
+    tracing::info!("Exported {} assets to {}", summary.rows, summary.file_path);
+    Ok(summary)
+}
+
+/// Import assets from a CSV or JSON export, upserting by IP address
+///
+/// The format defaults to the file's extension. Rows that can't be read are
+/// skipped and reported in the summary.
+#[tauri::command]
+pub async fn import_assets(
+    db: State<'_, Database>,
+    client_id: String,
+    path: String,
+    format: Option<String>,
+) -> Result<AssetImportSummary, String> {
+    let format = match format {
+        Some(format) => format.parse()?,
+        None => AssetExportFormat::from_path(std::path::Path::new(&path))
+            .ok_or_else(|| format!("Can't tell the format of {}; expected .csv or .json", path))?,
+    };
+    let data = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let summary = asset_export::import_assets(&db, &client_id, &data, format).map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "Imported assets from {}: {} created, {} updated ({} rows skipped)",
+        path,
+        summary.created,
+        summary.updated,
+        summary.warnings.len()
+    );
+    Ok(summary)
+}
+
+/// Add tags to many assets
+#[tauri::command]
+pub async fn add_asset_tags(
+    db: State<'_, Database>,
+    asset_ids: Vec<String>,
+    tags: Vec<String>,
+) -> Result<usize, String> {
+    AssetInventory::new(&db)
+        .add_tags(&asset_ids, &tags)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove tags from many assets
+#[tauri::command]
+pub async fn remove_asset_tags(
+    db: State<'_, Database>,
+    asset_ids: Vec<String>,
+    tags: Vec<String>,
+) -> Result<usize, String> {
+    AssetInventory::new(&db)
+        .remove_tags(&asset_ids, &tags)
+        .map_err(|e| e.to_string())
+}
+
+/// Save a named asset filter for a client, replacing one with the same name
+#[tauri::command]
+pub async fn save_asset_view(
+    db: State<'_, Database>,
+    client_id: String,
+    name: String,
+    filter: AssetFilter,
+) -> Result<AssetView, String> {
+    AssetInventory::new(&db)
+        .save_view(&client_id, &name, filter)
+        .map_err(|e| e.to_string())
+}
+
+/// List a client's saved asset views
+#[tauri::command]
+pub async fn list_asset_views(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<AssetView>, String> {
+    AssetInventory::new(&db)
+        .get_views(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a saved asset view
+#[tauri::command]
+pub async fn delete_asset_view(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    AssetInventory::new(&db)
+        .delete_view(&id)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete an asset
+#[tauri::command]
+pub async fn delete_asset(
+    db: State<'_, Database>,
+    asset_id: String,
+) -> Result<bool, String> {
+    AssetInventory::new(&db)
+        .delete_asset(&asset_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get network statistics for a client
+#[tauri::command]
+pub async fn get_network_stats(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<NetworkStats, String> {
+    AssetInventory::new(&db)
+        .get_stats(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Service Policy Commands
+// ============================================================================
+
+/// Create or update a service policy request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertServicePolicyRequest {
+    /// Existing policy to replace (a default's id overrides that default); new if unset
+    pub id: Option<String>,
+    pub name: String,
+    pub ports: Option<Vec<u16>>,
+    pub services: Option<Vec<String>>,
+    pub categories: Option<Vec<String>>,
+    pub criticalities: Option<Vec<String>>,
+    pub exempt_tags: Option<Vec<String>>,
+    pub severity: String,
+    pub message: String,
+    pub recommendation: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// List the service policies in effect, defaults first
+#[tauri::command]
+pub async fn list_service_policies(db: State<'_, Database>) -> Result<Vec<ServicePolicy>, String> {
+    AssetInventory::new(&db).service_policies().map_err(|e| e.to_string())
+}
+
+/// Add a service policy, or replace one (including a default)
+#[tauri::command]
+pub async fn upsert_service_policy(
+    db: State<'_, Database>,
+    request: UpsertServicePolicyRequest,
+) -> Result<ServicePolicy, String> {
+    if request.name.trim().is_empty() {
+        return Err("Policy name is required".to_string());
+    }
+    if request.message.trim().is_empty() {
+        return Err("Policy message is required".to_string());
+    }
+    let list = |items: Option<Vec<String>>| -> Vec<String> {
+        items.unwrap_or_default()
+            .into_iter()
+            .map(|i| i.trim().to_string())
+            .filter(|i| !i.is_empty())
+            .collect()
+    };
+
+    let id = request.id.filter(|id| !id.trim().is_empty()).unwrap_or_else(|| Uuid::new_v4().to_string());
+    let policy = ServicePolicy {
+        built_in: default_service_policies().iter().any(|p| p.id == id),
+        id,
+        name: request.name.trim().to_string(),
+        ports: request.ports.unwrap_or_default(),
+        services: list(request.services),
+        categories: list(request.categories).iter().map(|c| c.parse()).collect::<Result<_, _>>()?,
+        criticalities: list(request.criticalities).iter().map(|c| c.parse()).collect::<Result<_, _>>()?,
+        exempt_tags: list(request.exempt_tags),
+        severity: request.severity.parse()?,
+        message: request.message.trim().to_string(),
+        recommendation: request.recommendation.filter(|r| !r.trim().is_empty()),
+        enabled: request.enabled.unwrap_or(true),
+    };
+
+    ServicePolicyRepository::new(&db).save(&policy).map_err(|e| e.to_string())?;
+    tracing::info!("Saved service policy {}", policy.id);
+
+    Ok(policy)
+}
+
+/// Delete a user service policy
+///
+/// Deleting the override of a default restores the default; defaults
+/// themselves can only be disabled.
+#[tauri::command]
+pub async fn delete_service_policy(
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    let deleted = ServicePolicyRepository::new(&db).delete(&id).map_err(|e| e.to_string())?;
+    if !deleted && default_service_policies().iter().any(|p| p.id == id) {
+        return Err("Default service policies can't be deleted; disable them instead".to_string());
+    }
+    Ok(deleted)
+}
+
+/// Check a client's assets against the service policies
+#[tauri::command]
+pub async fn evaluate_service_policies(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<AssetPolicyViolations>, String> {
+    AssetInventory::new(&db)
+        .evaluate_service_policies(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Vulnerability Enrichment Commands
+// ============================================================================
+
+/// Import CVEs from an NVD JSON 2.0 file, a CISA KEV catalog or a list in
+/// the bundled format
+///
+/// Imported CVEs are stored and used alongside the bundled ones; an
+/// imported CVE replaces a bundled one with the same ID. KEV entries flag
+/// matches as known exploited; KEV CVEs the dataset has no version data for
+/// are listed as warnings, since they can't be matched until their NVD
+/// records are imported.
+#[tauri::command]
+pub async fn import_vuln_database(
+    db: State<'_, Database>,
+    file_path: String,
+) -> Result<VulnImportSummary, String> {
+    let data = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+    let mut summary = parse_vuln_json(&data)?;
+
+    let vulnerabilities = VulnerabilityRepository::new(&db);
+    vulnerabilities.save_entries(&summary.entries).map_err(|e| e.to_string())?;
+    vulnerabilities.save_known_exploited(&summary.known_exploited).map_err(|e| e.to_string())?;
+
+    if !summary.known_exploited.is_empty() {
+        let dataset = load_vuln_database(&vulnerabilities)?;
+        let unmatchable = summary.known_exploited.iter().filter(|k| !dataset.contains(&k.cve_id)).count();
+        if unmatchable > 0 {
+            summary.warnings.push(format!(
+                "{} of {} known exploited CVEs have no version data yet; import their NVD records to match them",
+                unmatchable,
+                summary.known_exploited.len()
+            ));
+        }
+    }
+
+    tracing::info!(
+        "Imported {} CVEs and {} known exploited CVEs from {} ({} warnings)",
+        summary.entries.len(), summary.known_exploited.len(), file_path, summary.warnings.len()
+    );
+    Ok(summary)
+}
+
+/// Match an asset's service versions against the CVE dataset
+///
+/// The asset's stored matches are replaced with the new results.
+#[tauri::command]
+pub async fn enrich_asset_vulnerabilities(
+    db: State<'_, Database>,
+    asset_id: String,
+) -> Result<AssetVulnerabilityReport, String> {
+    let asset = AssetInventory::new(&db)
+        .get_asset(&asset_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Asset not found".to_string())?;
+
+    let vulnerabilities = VulnerabilityRepository::new(&db);
+    let dataset = load_vuln_database(&vulnerabilities)?;
+
+    let report = dataset.match_asset(&asset);
+    vulnerabilities
+        .replace_asset_matches(&asset.id, &report.matches)
+        .map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
+
+/// Match every asset of a client against the CVE dataset
+#[tauri::command]
+pub async fn enrich_client_assets(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<AssetVulnerabilityReport>, String> {
+    let assets = AssetInventory::new(&db)
+        .get_client_assets(&client_id)
+        .map_err(|e| e.to_string())?;
+
+    let vulnerabilities = VulnerabilityRepository::new(&db);
+    let dataset = load_vuln_database(&vulnerabilities)?;
+
+    let mut reports = Vec::with_capacity(assets.len());
+    for asset in &assets {
+        let report = dataset.match_asset(asset);
+        vulnerabilities
+            .replace_asset_matches(&asset.id, &report.matches)
+            .map_err(|e| e.to_string())?;
+        reports.push(report);
+    }
+
+    tracing::info!(
+        "Enriched {} assets for client {}: {} vulnerabilities matched",
+        reports.len(), client_id, reports.iter().map(|r| r.matches.len()).sum::<usize>()
+    );
+    Ok(reports)
+}
+
+/// An asset's CVE matches from its last enrichment, known exploited first
+#[tauri::command]
+pub async fn get_asset_vulnerabilities(
+    db: State<'_, Database>,
+    asset_id: String,
+) -> Result<Vec<VulnerabilityMatch>, String> {
+    let mut matches = VulnerabilityRepository::new(&db)
+        .list_by_asset(&asset_id)
+        .map_err(|e| e.to_string())?;
+    matches.sort_by_key(|m| !m.kev);
+    Ok(matches)
+}
+
+/// The bundled and imported CVEs, with the imported KEV catalog applied
+fn load_vuln_database(vulnerabilities: &VulnerabilityRepository) -> Result<VulnDatabase, String> {
+    let entries = vulnerabilities.list_entries().map_err(|e| e.to_string())?;
+    let known_exploited = vulnerabilities.known_exploited_ids().map_err(|e| e.to_string())?;
+    Ok(VulnDatabase::with_imported(entries).with_known_exploited(known_exploited))
+}
+
+// ============================================================================
+// Asset Groups Commands
+// ============================================================================
+
+/// Create a new asset group
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGroupRequest {
     pub client_id: String,
     pub name: String,
     pub description: Option<String>,
@@ -412,43 +1909,155 @@ pub struct CreateGroupRequest {
 
 #[tauri::command]
 pub async fn create_asset_group(
-    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
     request: CreateGroupRequest,
 ) -> Result<AssetGroup, String> {
-    let mut inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    Ok(inventory.create_group(&request.client_id, &request.name, request.description))
+    AssetInventory::new(&db)
+        .create_group(&request.client_id, &request.name, request.description)
+        .map_err(|e| e.to_string())
 }
 
 /// Get all groups for a client
 #[tauri::command]
 pub async fn list_asset_groups(
-    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
     client_id: String,
 ) -> Result<Vec<AssetGroup>, String> {
-    let inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    Ok(inventory.get_client_groups(&client_id))
+    AssetInventory::new(&db)
+        .get_client_groups(&client_id)
+        .map_err(|e| e.to_string())
 }
 
 /// Add asset to a group
 #[tauri::command]
 pub async fn add_asset_to_group(
-    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
     group_id: String,
     asset_id: String,
 ) -> Result<(), String> {
-    let mut inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    inventory.add_to_group(&group_id, &asset_id)
+    AssetInventory::new(&db)
+        .add_to_group(&group_id, &asset_id)
+        .map_err(|e| e.to_string())
 }
 
 /// Remove asset from a group
 #[tauri::command]
 pub async fn remove_asset_from_group(
-    state: State<'_, NetworkState>,
+    db: State<'_, Database>,
     group_id: String,
     asset_id: String,
 ) -> Result<(), String> {
-    let mut inventory = state.inventory.lock().map_err(|e| e.to_string())?;
-    inventory.remove_from_group(&group_id, &asset_id)
+    AssetInventory::new(&db)
+        .remove_from_group(&group_id, &asset_id)
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Agent Listener Commands
+// ============================================================================
+
+/// The running agent callback listener, if any
+#[derive(Default)]
+pub struct AgentListenerState {
+    pub listener: tokio::sync::Mutex<Option<AgentListenerHandle>>,
+}
+
+/// Database access for the listener's background tasks, which outlive any one command
+#[derive(Clone)]
+struct ManagedDatabase(AppHandle);
+
+impl AsRef<Database> for ManagedDatabase {
+    fn as_ref(&self) -> &Database {
+        self.0.state::<Database>().inner()
+    }
+}
+
+/// Start listening for agent callbacks on `port`
+///
+/// With `use_tls` (the default, matching agent scripts) agents must connect
+/// over TLS to their engagement's certificate.
+#[tauri::command]
+pub async fn start_agent_listener(
+    app: AppHandle,
+    state: State<'_, AgentListenerState>,
+    port: u16,
+    use_tls: Option<bool>,
+) -> Result<AgentListenerStatus, String> {
+    if app.try_state::<Database>().is_none() {
+        return Err("Database is not initialized yet".to_string());
+    }
+
+    let mut listener = state.listener.lock().await;
+    if let Some(running) = listener.as_ref() {
+        return Err(format!("Agent listener is already running on port {}", running.status().port.unwrap_or(port)));
+    }
+
+    let emitter = app.clone();
+    let events: AgentEventSink = std::sync::Arc::new(move |event, agent| {
+        if let Err(e) = emitter.emit(event, agent) {
+            tracing::warn!("Failed to emit agent event: {}", e);
+        }
+    });
+
+    let tls = if use_tls.unwrap_or(true) {
+        Some(tls_acceptor(OsKeychain).map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+    let handle = start_listener(port, ManagedDatabase(app), events, tls)
+        .await
+        .map_err(|e| format!("Failed to start agent listener on port {}: {}", port, e))?;
+    let status = handle.status();
+    *listener = Some(handle);
+    Ok(status)
+}
+
+/// Stop the agent listener, closing agent connections
+#[tauri::command]
+pub async fn stop_agent_listener(
+    state: State<'_, AgentListenerState>,
+) -> Result<bool, String> {
+    let handle = state.listener.lock().await.take();
+    match handle {
+        Some(handle) => {
+            handle.stop().await;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Whether the agent listener is running
+#[tauri::command]
+pub async fn get_agent_listener_status(
+    state: State<'_, AgentListenerState>,
+) -> Result<AgentListenerStatus, String> {
+    Ok(match state.listener.lock().await.as_ref() {
+        Some(handle) => handle.status(),
+        None => AgentListenerStatus { running: false, port: None, tls: false, started_at: None },
+    })
+}
+
+/// List the agents that have called back for a client
+#[tauri::command]
+pub async fn list_connected_agents(
+    db: State<'_, Database>,
+    client_id: String,
+) -> Result<Vec<ConnectedAgent>, String> {
+    AgentRepository::new(&db)
+        .list_by_client(&client_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the telemetry an agent has sent, newest first
+#[tauri::command]
+pub async fn get_agent_telemetry(
+    db: State<'_, Database>,
+    agent_id: String,
+) -> Result<Vec<AgentTelemetry>, String> {
+    AgentRepository::new(&db)
+        .telemetry(&agent_id)
+        .map_err(|e| e.to_string())
 }
 
 // ============================================================================
@@ -469,41 +2078,3 @@ fn parse_scan_type(s: &str) -> Result<ScanType, String> {
         _ => Err(format!("Unknown scan type: {}", s)),
     }
 }
-
-fn parse_asset_category(s: &str) -> Result<AssetCategory, String> {
-    match s.to_lowercase().as_str() {
-        "server" => Ok(AssetCategory::Server),
-        "workstation" => Ok(AssetCategory::Workstation),
-        "networkdevice" | "network_device" | "network" => Ok(AssetCategory::NetworkDevice),
-        "securitydevice" | "security_device" | "security" => Ok(AssetCategory::SecurityDevice),
-        "printer" => Ok(AssetCategory::Printer),
-        "iot" => Ok(AssetCategory::IoT),
-        "mobile" => Ok(AssetCategory::Mobile),
-        "virtual" | "vm" => Ok(AssetCategory::Virtual),
-        "cloud" => Ok(AssetCategory::Cloud),
-        "unknown" => Ok(AssetCategory::Unknown),
-        _ => Err(format!("Unknown asset category: {}", s)),
-    }
-}
-
-fn parse_criticality(s: &str) -> Result<Criticality, String> {
-    match s.to_lowercase().as_str() {
-        "critical" => Ok(Criticality::Critical),
-        "high" => Ok(Criticality::High),
-        "medium" => Ok(Criticality::Medium),
-        "low" => Ok(Criticality::Low),
-        "informational" | "info" => Ok(Criticality::Informational),
-        _ => Err(format!("Unknown criticality: {}", s)),
-    }
-}
-
-fn parse_asset_status(s: &str) -> Result<AssetStatus, String> {
-    match s.to_lowercase().as_str() {
-        "active" => Ok(AssetStatus::Active),
-        "inactive" => Ok(AssetStatus::Inactive),
-        "decommissioned" => Ok(AssetStatus::Decommissioned),
-        "pending" => Ok(AssetStatus::Pending),
-        "maintenance" => Ok(AssetStatus::Maintenance),
-        _ => Err(format!("Unknown asset status: {}", s)),
-    }
-}
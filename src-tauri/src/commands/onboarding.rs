@@ -0,0 +1,20 @@
+//! Onboarding Commands
+//!
+//! First-run onboarding checklist status and dismissal.
+
+use crate::db::Database;
+use crate::onboarding::{self, OnboardingStatus};
+use tauri::State;
+
+/// Get onboarding milestone states with next-step hints
+#[tauri::command]
+pub async fn get_onboarding_status(db: State<'_, Database>) -> Result<OnboardingStatus, String> {
+    onboarding::get_status(&db).map_err(|e| e.to_string())
+}
+
+/// Permanently hide onboarding
+#[tauri::command]
+pub async fn dismiss_onboarding(db: State<'_, Database>) -> Result<OnboardingStatus, String> {
+    onboarding::dismiss(&db).map_err(|e| e.to_string())?;
+    onboarding::get_status(&db).map_err(|e| e.to_string())
+}
@@ -0,0 +1,32 @@
+//! Portfolio Commands
+//!
+//! Every client's compliance, risk, findings, scanning and remediation in
+//! one table, and its CSV export.
+
+use crate::db::Database;
+use crate::portfolio::{self, portfolio_to_csv, PortfolioFilter, PortfolioOverview, PortfolioSort};
+use tauri::State;
+
+/// Get the portfolio of all clients, optionally filtered by framework or
+/// minimum risk; sorted by health score, worst first, unless told otherwise
+#[tauri::command]
+pub async fn get_portfolio_overview(
+    db: State<'_, Database>,
+    filter: Option<PortfolioFilter>,
+    sort_by: Option<PortfolioSort>,
+) -> Result<PortfolioOverview, String> {
+    portfolio::get_portfolio_overview(&db, &filter.unwrap_or_default(), sort_by.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+/// Export the portfolio table as CSV, with the same filter and order
+#[tauri::command]
+pub async fn export_portfolio_csv(
+    db: State<'_, Database>,
+    filter: Option<PortfolioFilter>,
+    sort_by: Option<PortfolioSort>,
+) -> Result<String, String> {
+    let overview = portfolio::get_portfolio_overview(&db, &filter.unwrap_or_default(), sort_by.unwrap_or_default())
+        .map_err(|e| e.to_string())?;
+    portfolio_to_csv(&overview.clients)
+}
@@ -2,34 +2,65 @@
 //!
 //! Tauri commands for report generation and management.
 
-use crate::db::Database;
+use crate::activity::{self, ActivityEvent, ActivityEventType};
+use crate::audit::{self, AuditEntry, AuditOperation};
+use crate::db::{ClientRepository, Database};
+use crate::deletion::remove_app_data_file;
+use crate::onboarding::{self, Milestone};
 use crate::grc::{
-    models::{AssetCategoryCount, ComplianceStatusReport, ExecutiveFinding, ExecutiveReportData, Framework, RiskSummary, CategoryComplianceStatus},
-    frameworks::{get_framework_controls, get_framework_categories},
-    repository::{AssessmentRepository, ControlAssessmentRepository},
+    models::{Assessment, AssessmentComparison, AssetCategoryCount, ComplianceStatusReport, ExecutiveFinding, ExecutiveReportData, Framework, RiskSummary, CategoryComplianceStatus},
+    frameworks::{apply_maturity, get_framework_controls, get_framework_categories, rollup_category_groups},
+    repository::{AssessmentRepository, CategoryGroupingRepository, ControlAssessmentRepository},
+    remediation::RemediationPlanRepository,
+    risk::{summarize_risk_counts, summarize_risks, RiskRepository},
+    ropa::ProcessingActivityRepository,
+    trend::{build_compliance_trend, ComplianceSnapshotRepository, ComplianceTrend},
+    snapshot::AssessmentSnapshot,
+    overview::{build_compliance_overview, ComplianceOverview},
+};
+use crate::commands::grc::{evidence_coverage, load_snapshot, source_comparison, summarize_assessment};
+use crate::commands::network::diff_stored_scans;
+use crate::infrastructure::cloud_readiness::{score_readiness, CloudReadinessRepository};
+use crate::findings::library::FindingRepository;
+use crate::infrastructure::k8s_hardening::K8sAuditRepository;
+use crate::infrastructure::finops::ProviderComparison;
+use crate::infrastructure::models::TcoProjection;
+use crate::network::{
+    diff::ScanDiff, inventory::AssetInventory, models::ScanJob,
+    repository::{NetworkFindingRepository, ScanRepository, VulnerabilityRepository},
 };
 use crate::reporting::{
     models::*,
-    generator::{ReportGenerator, content_to_html, content_to_markdown},
-    templates::{get_report_templates, get_template_for_type, get_report_type_info, get_export_formats, ReportTypeInfo, ExportFormatInfo},
+    generator::{ReportDataSource, ReportGenerator, charts_to_tables, content_to_csv, content_to_html, content_to_markdown, number_sections_from, place_custom_sections, processing_register_content},
+    docx_generator::content_to_docx,
+    templates::{get_report_templates, get_template_for_type, get_report_type_info, get_export_formats, resolve_section_selection, validate_custom_section, ReportTypeInfo, ExportFormatInfo},
     pdf_generator::{PdfGenerator, generate_demo_executive_report},
+    repository::{CustomSectionRepository, ReportRepository},
+    branding::{normalize_hex_color, BrandingProfile, BrandingRepository, Logo, DEFAULT_PRIMARY_COLOR},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Mutex;
-use tauri::{Manager, State};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
-/// In-memory storage for reports
+/// In-memory cache of reports loaded or generated this session
+///
+/// The `reports` table is the source of truth; this only saves re-reading
+/// report bodies for repeated exports.
 pub struct ReportingState {
     pub reports: Mutex<Vec<Report>>,
+    /// Cancel flags of streaming previews still running, by preview id
+    pub previews: Mutex<HashMap<String, Arc<AtomicBool>>>,
 }
 
 impl Default for ReportingState {
     fn default() -> Self {
         Self {
             reports: Mutex::new(Vec::new()),
+            previews: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -85,96 +116,460 @@ pub struct GenerateReportRequest {
     pub include_charts: bool,
     pub classification: Option<String>,
     pub notes: Option<String>,
+    /// Framework to pull live compliance data for (compliance reports)
+    pub framework: Option<String>,
+    /// Show the client's category groups instead of native categories
+    pub group_categories: Option<bool>,
+    /// Baseline scan for a "changes since last assessment" section
+    pub baseline_scan_id: Option<String>,
+    /// Follow-up scan compared against the baseline
+    pub followup_scan_id: Option<String>,
+    /// Render the built-in sample content instead of client data
+    pub use_demo_data: Option<bool>,
+    /// Cost projections from the FinOps calculator to include
+    pub cost_projections: Option<Vec<TcoProjection>>,
+    /// Per-resource provider comparison from the FinOps calculator to include
+    pub provider_comparison: Option<ProviderComparison>,
+    /// Sources to leave out; entries with `included: false` skip that source type
+    pub data_sources: Option<Vec<DataSource>>,
+    /// Cover page logo; defaults to the organization's branding profile
+    pub logo_path: Option<String>,
+    /// Brand color; defaults to the organization's branding profile
+    pub primary_color: Option<String>,
+    /// Template section ids to include; defaults to the template's defaults
+    pub selected_sections: Option<Vec<String>>,
+    /// Per-section choices; sections left out keep the template's default
+    pub sections: Option<Vec<SectionSelection>>,
+    /// Kubernetes hardening audit for the technical assessment's hardening section
+    pub k8s_audit_id: Option<String>,
+    /// Existing report to regenerate in place, keeping its custom sections
+    pub report_id: Option<String>,
+    /// Assessment snapshot to take the compliance data from instead of the
+    /// live assessment results; its framework replaces `framework`
+    pub snapshot_id: Option<String>,
 }
 
-/// Generate a new report
+/// Generate a new report, or regenerate one in place with `report_id`
 #[tauri::command]
 pub async fn generate_report(
     state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     request: GenerateReportRequest,
 ) -> Result<Report, String> {
-    let report_type = parse_report_type(&request.report_type)?;
-    let format = parse_export_format(&request.format)?;
-
-    let config = ReportConfig {
-        report_type,
-        client_id: request.client_id,
-        client_name: request.client_name,
-        title: request.title,
-        subtitle: request.subtitle,
-        author: request.author,
-        organization: request.organization,
-        format,
-        include_toc: request.include_toc,
-        include_executive_summary: request.include_executive_summary,
-        include_appendices: request.include_appendices,
-        include_charts: request.include_charts,
-        logo_path: None,
-        primary_color: Some("#3B82F6".to_string()),
-        notes: request.notes,
-        classification: request.classification,
-        data_sources: vec![],
-    };
+    let existing = existing_report(&db, &request)?;
+    let mut config = report_config(&db, &request)?;
+    let (data, data_sources) = report_data(&db, &request).await?;
+    config.data_sources = data_sources;
+
+    let mut generator = ReportGenerator::new(config, data);
+    if let Some(existing) = &existing {
+        generator.set_custom_sections(
+            CustomSectionRepository::new(&db).list_by_report(&existing.id).map_err(|e| e.to_string())?,
+        );
+    }
+    let mut report = generator.generate()?;
+    if let Some(existing) = existing {
+        report.id = existing.id;
+        report.created_at = existing.created_at;
+    }
 
-    let generator = ReportGenerator::new(config);
-    let report = generator.generate()?;
+    ReportRepository::new(&db).save(&report).map_err(|e| e.to_string())?;
+    onboarding::advance(&db, &[Milestone::FirstReport]);
+    activity::log(&db, ActivityEvent::new(
+        &report.client_id,
+        ActivityEventType::ReportGenerated,
+        "report",
+        Some(&report.id),
+        format!("Generated {} \"{}\"", report.config.report_type.display_name(), report.config.title),
+    )
+    .by(&report.config.author)
+    .with_detail(serde_json::json!({
+        "reportType": report.config.report_type,
+        "format": report.config.format,
+        "dataSources": report.config.data_sources,
+    })));
 
     let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
+    reports.retain(|r| r.id != report.id);
     reports.push(report.clone());
 
     Ok(report)
 }
 
 /// Preview report content without saving
+///
+/// Full engagement reports are too slow to build in one go; preview those
+/// with `start_report_preview`.
 #[tauri::command]
-pub async fn preview_report(request: GenerateReportRequest) -> Result<ReportContent, String> {
-    let report_type = parse_report_type(&request.report_type)?;
-    let format = parse_export_format(&request.format)?;
+pub async fn preview_report(
+    db: State<'_, Database>,
+    request: GenerateReportRequest,
+) -> Result<ReportContent, String> {
+    if parse_report_type(&request.report_type)? == ReportType::FullEngagement {
+        return Err("Full engagement previews are streamed; use start_report_preview".to_string());
+    }
+    let existing = existing_report(&db, &request)?;
+    let mut config = report_config(&db, &request)?;
+    let (data, data_sources) = report_data(&db, &request).await?;
+    config.data_sources = data_sources;
+
+    let mut generator = ReportGenerator::new(config, data);
+    if let Some(existing) = &existing {
+        generator.set_custom_sections(
+            CustomSectionRepository::new(&db).list_by_report(&existing.id).map_err(|e| e.to_string())?,
+        );
+    }
+    let report = generator.generate()?;
+
+    report.content.ok_or_else(|| "Failed to generate content".to_string())
+}
+
+/// Render a client's GDPR record of processing activities as a report
+///
+/// The report is stored like any other, so it exports through the usual
+/// HTML, Markdown, CSV and DOCX commands.
+#[tauri::command]
+pub async fn generate_ropa_report(
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    client_id: String,
+    author: Option<String>,
+    organization: Option<String>,
+) -> Result<Report, String> {
+    let client = ClientRepository::new(&db)
+        .get(&client_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Client not found: {}", client_id))?;
+    let activities = ProcessingActivityRepository::new(&db)
+        .list_by_client(&client_id)
+        .map_err(|e| e.to_string())?;
+
+    let mut config = get_template_for_type(ReportType::ComplianceReport).default_config;
+    config.client_id = client_id;
+    config.client_name = client.name;
+    config.title = "Record of Processing Activities".to_string();
+    config.subtitle = Some("Article 30 GDPR".to_string());
+    config.author = author.unwrap_or_default();
+    config.organization = organization;
+    config.data_sources = vec![DataSource {
+        source_type: "processing_activities".to_string(),
+        source_id: format!("{} activities", activities.len()),
+        included: true,
+    }];
+    apply_branding(&db, &mut config)?;
+    config.client_contact = client_contact(&db, &config.client_id)?;
 
-    let config = ReportConfig {
+    let now = chrono::Utc::now().to_rfc3339();
+    let report = Report {
+        id: Uuid::new_v4().to_string(),
+        client_id: config.client_id.clone(),
+        content: Some(processing_register_content(&config, &activities)),
+        config,
+        status: ReportStatus::Ready,
+        file_path: None,
+        file_size: None,
+        created_at: now.clone(),
+        updated_at: now,
+        error: None,
+        warnings: vec![],
+    };
+
+    ReportRepository::new(&db).save(&report).map_err(|e| e.to_string())?;
+    tracing::info!("Generated record of processing activities {} ({} activities)", report.id, activities.len());
+
+    let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
+    reports.push(report.clone());
+
+    Ok(report)
+}
+
+/// The report a request regenerates, if it names one
+fn existing_report(db: &Database, request: &GenerateReportRequest) -> Result<Option<Report>, String> {
+    let report_id = match &request.report_id {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let report = ReportRepository::new(db)
+        .get(report_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Report not found".to_string())?;
+    if report.client_id != request.client_id {
+        return Err(format!("Report {} belongs to another client", report_id));
+    }
+    Ok(Some(report))
+}
+
+/// Build the report configuration for a request, with branding applied
+///
+/// Data sources are left empty until the report's data is gathered.
+fn report_config(db: &Database, request: &GenerateReportRequest) -> Result<ReportConfig, String> {
+    let report_type = parse_report_type(&request.report_type)?;
+    let selected_sections = match (&request.sections, &request.selected_sections) {
+        (Some(_), Some(_)) => return Err("Choose sections with either sections or selectedSections, not both".to_string()),
+        (Some(choices), None) => Some(resolve_section_selection(&get_template_for_type(report_type), choices)?),
+        (None, selected) => selected.clone(),
+    };
+    let mut config = ReportConfig {
         report_type,
-        client_id: request.client_id,
-        client_name: request.client_name,
-        title: request.title,
-        subtitle: request.subtitle,
-        author: request.author,
-        organization: request.organization,
-        format,
+        client_id: request.client_id.clone(),
+        client_name: request.client_name.clone(),
+        title: request.title.clone(),
+        subtitle: request.subtitle.clone(),
+        author: request.author.clone(),
+        organization: request.organization.clone(),
+        format: parse_export_format(&request.format)?,
         include_toc: request.include_toc,
         include_executive_summary: request.include_executive_summary,
         include_appendices: request.include_appendices,
         include_charts: request.include_charts,
-        logo_path: None,
-        primary_color: Some("#3B82F6".to_string()),
-        notes: request.notes,
-        classification: request.classification,
+        logo_path: request.logo_path.clone(),
+        primary_color: request.primary_color.clone(),
+        secondary_color: None,
+        footer_text: None,
+        notes: request.notes.clone(),
+        classification: request.classification.clone(),
         data_sources: vec![],
+        selected_sections,
+        client_contact: None,
+        snapshot_id: request.snapshot_id.clone().filter(|_| !request.use_demo_data.unwrap_or(false)),
     };
+    apply_branding(db, &mut config)?;
+    config.client_contact = client_contact(db, &config.client_id)?;
+    Ok(config)
+}
 
-    let generator = ReportGenerator::new(config);
-    let report = generator.generate()?;
+// ============================================================================
+// Streaming Preview Commands
+// ============================================================================
 
-    report.content.ok_or_else(|| "Failed to generate content".to_string())
+/// Emitted as each section of a streaming preview is rendered
+pub const REPORT_PREVIEW_SECTION_EVENT: &str = "report-preview:section";
+/// Emitted once a streaming preview finishes, fails or is cancelled
+pub const REPORT_PREVIEW_DONE_EVENT: &str = "report-preview:done";
+
+/// A rendered section of a streaming preview
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportPreviewSection {
+    pub preview_id: String,
+    /// Position of the section in the report
+    pub index: usize,
+    pub section: ReportSection,
+}
+
+/// The end of a streaming preview
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportPreviewDone {
+    pub preview_id: String,
+    /// Cover page details; absent when the preview failed or was cancelled
+    pub metadata: Option<ReportMetadata>,
+    pub toc: Vec<TocEntry>,
+    pub section_count: usize,
+    pub data_sources: Vec<DataSource>,
+    pub cancelled: bool,
+    pub error: Option<String>,
+}
+
+impl ReportPreviewDone {
+    fn stopped(preview_id: &str, cancelled: bool, error: Option<String>) -> Self {
+        Self {
+            preview_id: preview_id.to_string(),
+            metadata: None,
+            toc: vec![],
+            section_count: 0,
+            data_sources: vec![],
+            cancelled,
+            error,
+        }
+    }
+}
+
+/// Start previewing a report in the background
+///
+/// Sections arrive as `report-preview:section` events as they're rendered,
+/// followed by a single `report-preview:done`. Full engagement reports
+/// gather each part's data just before rendering it, so the first sections
+/// show up without waiting on the rest. Returns the preview id.
+#[tauri::command]
+pub async fn start_report_preview(
+    app: AppHandle,
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    request: GenerateReportRequest,
+) -> Result<String, String> {
+    let generator = ReportGenerator::new(report_config(&db, &request)?, ReportDataSource::default());
+    generator.check_selection()?;
+
+    let preview_id = Uuid::new_v4().to_string();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    state.previews.lock().map_err(|e| e.to_string())?
+        .insert(preview_id.clone(), cancelled.clone());
+
+    let id = preview_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let db = app.state::<Database>();
+        let done = stream_report_preview(&app, &db, &id, &request, generator, &cancelled)
+            .await
+            .unwrap_or_else(|e| ReportPreviewDone::stopped(&id, false, Some(e)));
+
+        if let Ok(mut previews) = app.state::<ReportingState>().previews.lock() {
+            previews.remove(&id);
+        }
+        if let Err(e) = app.emit(REPORT_PREVIEW_DONE_EVENT, &done) {
+            tracing::warn!("Failed to emit report preview event: {}", e);
+        }
+    });
+
+    Ok(preview_id)
+}
+
+/// Stop a streaming preview after the section in progress
+///
+/// Returns false if the preview already finished.
+#[tauri::command]
+pub async fn cancel_report_preview(
+    state: State<'_, ReportingState>,
+    preview_id: String,
+) -> Result<bool, String> {
+    let previews = state.previews.lock().map_err(|e| e.to_string())?;
+    match previews.get(&preview_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Data source types each part of a full engagement report draws on
+fn engagement_part_sources(part: &str) -> &'static [&'static str] {
+    match part {
+        "executive-summary" => &["compliance", "compliance_overview", "asset_inventory", "network_findings", "findings", "risk_register", "cost_projection"],
+        "compliance-assessment" => &["compliance", "evidence", "remediation_plan", "processing_activities"],
+        "network-assessment" => &["asset_inventory", "network_findings", "findings", "scan"],
+        "security-findings" => &["network_findings", "findings", "vulnerabilities", "scan"],
+        "cloud-readiness" => &["cloud_readiness", "cost_projection"],
+        _ => &[],
+    }
+}
+
+/// Render a preview section by section, emitting each as it's done
+async fn stream_report_preview(
+    app: &AppHandle,
+    db: &Database,
+    preview_id: &str,
+    request: &GenerateReportRequest,
+    mut generator: ReportGenerator,
+    cancelled: &AtomicBool,
+) -> Result<ReportPreviewDone, String> {
+    let mut loader = ReportDataLoader::new(request);
+    *generator.data_mut() = loader.initial_data();
+
+    let mut section_count = 0;
+    let mut emit = |section: ReportSection| {
+        let event = ReportPreviewSection {
+            preview_id: preview_id.to_string(),
+            index: section_count,
+            section,
+        };
+        if let Err(e) = app.emit(REPORT_PREVIEW_SECTION_EVENT, &event) {
+            tracing::warn!("Failed to emit report preview event: {}", e);
+        }
+        section_count += 1;
+    };
+
+    let toc = if parse_report_type(&request.report_type)? == ReportType::FullEngagement {
+        let parts: Vec<String> = generator.template().sections.iter()
+            .map(|s| s.id.clone())
+            .filter(|id| generator.includes(id))
+            .collect();
+        let mut counters = Vec::new();
+        let mut toc = Vec::new();
+        let mut finish = |sections: &mut Vec<ReportSection>| {
+            if !request.include_charts {
+                charts_to_tables(sections);
+            }
+            if request.include_toc {
+                toc.extend(number_sections_from(sections, &mut counters));
+            }
+        };
+
+        for part in &parts {
+            if cancelled.load(Ordering::Relaxed) {
+                return Ok(ReportPreviewDone::stopped(preview_id, true, None));
+            }
+            for source_type in engagement_part_sources(part) {
+                loader.load(db, source_type, generator.data_mut()).await?;
+            }
+            let mut sections = generator.build_engagement_part(part);
+            finish(&mut sections);
+            sections.into_iter().for_each(&mut emit);
+        }
+
+        generator.set_data_sources(loader.sources.clone());
+        let mut appendix: Vec<ReportSection> = generator.data_sources_appendix().into_iter().collect();
+        finish(&mut appendix);
+        appendix.into_iter().for_each(&mut emit);
+        toc
+    } else {
+        for source_type in REPORT_SOURCE_TYPES {
+            loader.load(db, source_type, generator.data_mut()).await?;
+        }
+        if cancelled.load(Ordering::Relaxed) {
+            return Ok(ReportPreviewDone::stopped(preview_id, true, None));
+        }
+        generator.set_data_sources(loader.sources.clone());
+        let content = generator
+            .generate()?
+            .content
+            .ok_or_else(|| "Failed to generate content".to_string())?;
+        content.sections.into_iter().for_each(&mut emit);
+        content.toc
+    };
+
+    Ok(ReportPreviewDone {
+        preview_id: preview_id.to_string(),
+        metadata: Some(generator.metadata()),
+        toc,
+        section_count,
+        data_sources: loader.sources,
+        cancelled: false,
+        error: None,
+    })
 }
 
 // ============================================================================
 // Report Export Commands
 // ============================================================================
 
+/// Record a report export in the audit log; the export doesn't go ahead if
+/// it can't be recorded
+fn audit_report_export(db: &Database, report: &Report, format: &str, output_path: Option<&str>) -> Result<(), String> {
+    audit::record(db, AuditEntry::new(AuditOperation::ReportExported, "report", Some(&report.id))
+        .with_detail(serde_json::json!({
+            "clientId": report.client_id,
+            "title": report.config.title,
+            "format": format,
+            "outputPath": output_path,
+        })))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
 /// Export report to HTML
 #[tauri::command]
 pub async fn export_report_html(
     state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     report_id: String,
 ) -> Result<String, String> {
-    let reports = state.reports.lock().map_err(|e| e.to_string())?;
-    let report = reports.iter()
-        .find(|r| r.id == report_id)
-        .ok_or_else(|| "Report not found".to_string())?;
+    let report = load_report(&state, &db, &report_id)?;
 
     let content = report.content.as_ref()
         .ok_or_else(|| "Report has no content".to_string())?;
 
+    audit_report_export(&db, &report, "html", None)?;
     Ok(content_to_html(content))
 }
 
@@ -182,34 +577,238 @@ pub async fn export_report_html(
 #[tauri::command]
 pub async fn export_report_markdown(
     state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     report_id: String,
 ) -> Result<String, String> {
-    let reports = state.reports.lock().map_err(|e| e.to_string())?;
-    let report = reports.iter()
-        .find(|r| r.id == report_id)
-        .ok_or_else(|| "Report not found".to_string())?;
+    let report = load_report(&state, &db, &report_id)?;
 
     let content = report.content.as_ref()
         .ok_or_else(|| "Report has no content".to_string())?;
 
+    audit_report_export(&db, &report, "markdown", None)?;
     Ok(content_to_markdown(content))
 }
 
+/// Export report tables and findings to CSV
+#[tauri::command]
+pub async fn export_report_csv(
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    report_id: String,
+) -> Result<String, String> {
+    let report = load_report(&state, &db, &report_id)?;
+
+    let content = report.content.as_ref()
+        .ok_or_else(|| "Report has no content".to_string())?;
+
+    let csv = content_to_csv(content)?;
+    if csv.is_empty() {
+        return Err("Report has no tables or findings to export".to_string());
+    }
+    audit_report_export(&db, &report, "csv", None)?;
+    Ok(csv)
+}
+
+/// Written export file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFileResult {
+    pub file_path: String,
+    pub file_size: u64,
+}
+
+/// Export report to an editable Word document at `output_path`
+#[tauri::command]
+pub async fn export_report_docx(
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    report_id: String,
+    output_path: String,
+) -> Result<ExportFileResult, String> {
+    let report = load_report(&state, &db, &report_id)?;
+
+    let content = report.content.as_ref()
+        .ok_or_else(|| "Report has no content".to_string())?;
+
+    let bytes = content_to_docx(content)?;
+    audit_report_export(&db, &report, "docx", Some(&output_path))?;
+    std::fs::write(&output_path, &bytes)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    ReportRepository::new(&db)
+        .set_file(&report_id, &output_path, bytes.len() as u64)
+        .map_err(|e| e.to_string())?;
+    let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
+    if let Some(cached) = reports.iter_mut().find(|r| r.id == report_id) {
+        cached.file_path = Some(output_path.clone());
+        cached.file_size = Some(bytes.len() as u64);
+    }
+
+    Ok(ExportFileResult {
+        file_path: output_path,
+        file_size: bytes.len() as u64,
+    })
+}
+
+/// Export report to a PDF at `output_path`, branded from the report's metadata
+#[tauri::command]
+pub async fn export_report_pdf(
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    report_id: String,
+    output_path: String,
+) -> Result<ExportFileResult, String> {
+    let report = load_report(&state, &db, &report_id)?;
+
+    let content = report.content.as_ref()
+        .ok_or_else(|| "Report has no content".to_string())?;
+
+    let metadata = &content.metadata;
+    let mut generator = PdfGenerator::new(metadata.title.clone());
+    if let Some(color) = metadata.primary_color.clone() {
+        generator = generator.with_primary_color(color);
+    }
+    if let Some(path) = metadata.logo_path.clone() {
+        generator = generator.with_logo(path);
+    }
+    if let Some(footer) = metadata.footer_text.clone() {
+        generator = generator.with_footer_text(footer);
+    }
+    if let Some(classification) = metadata.classification.clone() {
+        generator = generator.with_classification(classification);
+    }
+    audit_report_export(&db, &report, "pdf", Some(&output_path))?;
+    let output = generator.generate_report(content, &PathBuf::from(&output_path))?;
+
+    ReportRepository::new(&db)
+        .set_file(&report_id, &output_path, output.file_size)
+        .map_err(|e| e.to_string())?;
+    let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
+    if let Some(cached) = reports.iter_mut().find(|r| r.id == report_id) {
+        cached.file_path = Some(output_path.clone());
+        cached.file_size = Some(output.file_size);
+    }
+
+    Ok(ExportFileResult {
+        file_path: output_path,
+        file_size: output.file_size,
+    })
+}
+
 /// Export report to JSON
 #[tauri::command]
 pub async fn export_report_json(
     state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     report_id: String,
 ) -> Result<String, String> {
-    let reports = state.reports.lock().map_err(|e| e.to_string())?;
-    let report = reports.iter()
-        .find(|r| r.id == report_id)
-        .ok_or_else(|| "Report not found".to_string())?;
+    let report = load_report(&state, &db, &report_id)?;
 
-    serde_json::to_string_pretty(report)
+    audit_report_export(&db, &report, "json", None)?;
+    serde_json::to_string_pretty(&report)
         .map_err(|e| format!("JSON serialization failed: {}", e))
 }
 
+// ============================================================================
+// Custom Section Commands
+// ============================================================================
+
+/// A custom section's title, position and content
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomSectionRequest {
+    pub title: String,
+    pub placement: SectionPlacement,
+    /// Template section to place the custom section before or after
+    pub anchor_section_id: String,
+    /// Paragraph, bullet list, table, callout and code blocks
+    pub blocks: Vec<ContentBlock>,
+}
+
+/// Add a user-written section to a report
+#[tauri::command]
+pub async fn add_custom_section(
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    report_id: String,
+    section: CustomSectionRequest,
+) -> Result<CustomSection, String> {
+    let report = load_report(&state, &db, &report_id)?;
+    let template = get_template_for_type(report.config.report_type);
+    validate_custom_section(&template, &section.title, &section.anchor_section_id, &section.blocks)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let custom = CustomSection {
+        id: Uuid::new_v4().to_string(),
+        report_id,
+        title: section.title.trim().to_string(),
+        placement: section.placement,
+        anchor_section_id: section.anchor_section_id,
+        blocks: section.blocks,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    CustomSectionRepository::new(&db).create(&custom).map_err(|e| e.to_string())?;
+    refresh_custom_sections(&state, &db, report)?;
+
+    Ok(custom)
+}
+
+/// Replace a custom section's title, position and content
+#[tauri::command]
+pub async fn update_custom_section(
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    id: String,
+    section: CustomSectionRequest,
+) -> Result<CustomSection, String> {
+    let repo = CustomSectionRepository::new(&db);
+    let mut custom = repo
+        .get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Custom section not found: {}", id))?;
+    let report = load_report(&state, &db, &custom.report_id)?;
+    let template = get_template_for_type(report.config.report_type);
+    validate_custom_section(&template, &section.title, &section.anchor_section_id, &section.blocks)?;
+
+    custom.title = section.title.trim().to_string();
+    custom.placement = section.placement;
+    custom.anchor_section_id = section.anchor_section_id;
+    custom.blocks = section.blocks;
+    custom.updated_at = chrono::Utc::now().to_rfc3339();
+    repo.update(&custom).map_err(|e| e.to_string())?;
+    refresh_custom_sections(&state, &db, report)?;
+
+    Ok(custom)
+}
+
+/// Delete a custom section from its report
+#[tauri::command]
+pub async fn delete_custom_section(
+    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
+    id: String,
+) -> Result<bool, String> {
+    let repo = CustomSectionRepository::new(&db);
+    let custom = match repo.get(&id).map_err(|e| e.to_string())? {
+        Some(custom) => custom,
+        None => return Ok(false),
+    };
+    let report = load_report(&state, &db, &custom.report_id)?;
+    let deleted = repo.delete(&id).map_err(|e| e.to_string())?;
+    refresh_custom_sections(&state, &db, report)?;
+    Ok(deleted)
+}
+
+/// List a report's custom sections in the order they were added
+#[tauri::command]
+pub async fn list_custom_sections(
+    db: State<'_, Database>,
+    report_id: String,
+) -> Result<Vec<CustomSection>, String> {
+    CustomSectionRepository::new(&db).list_by_report(&report_id).map_err(|e| e.to_string())
+}
+
 // ============================================================================
 // Report Management Commands
 // ============================================================================
@@ -217,68 +816,67 @@ pub async fn export_report_json(
 /// List all reports for a client
 #[tauri::command]
 pub async fn list_reports(
-    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     client_id: Option<String>,
 ) -> Result<Vec<ReportSummary>, String> {
-    let reports = state.reports.lock().map_err(|e| e.to_string())?;
-
-    let filtered: Vec<ReportSummary> = reports.iter()
-        .filter(|r| client_id.as_ref().map_or(true, |cid| &r.client_id == cid))
-        .map(|r| ReportSummary {
-            id: r.id.clone(),
-            title: r.config.title.clone(),
-            report_type: r.config.report_type,
-            client_name: r.config.client_name.clone(),
-            status: r.status,
-            format: r.config.format,
-            created_at: r.created_at.clone(),
-            file_size: r.file_size,
-        })
-        .collect();
-
-    Ok(filtered)
+    ReportRepository::new(&db)
+        .list_summaries(client_id.as_deref())
+        .map_err(|e| e.to_string())
 }
 
 /// Get a specific report by ID
 #[tauri::command]
 pub async fn get_report(
-    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     report_id: String,
 ) -> Result<Option<Report>, String> {
-    let reports = state.reports.lock().map_err(|e| e.to_string())?;
-    Ok(reports.iter().find(|r| r.id == report_id).cloned())
+    ReportRepository::new(&db).get(&report_id).map_err(|e| e.to_string())
 }
 
-/// Delete a report
+/// Delete a report, and its exported file when that lives in the app data dir
 #[tauri::command]
 pub async fn delete_report(
+    app_handle: AppHandle,
     state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     report_id: String,
 ) -> Result<bool, String> {
-    let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
-    let len_before = reports.len();
-    reports.retain(|r| r.id != report_id);
-    Ok(reports.len() < len_before)
+    let repo = ReportRepository::new(&db);
+    let file_path = repo.get(&report_id).map_err(|e| e.to_string())?.and_then(|r| r.file_path);
+    let deleted = repo.delete(&report_id).map_err(|e| e.to_string())?;
+
+    {
+        let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
+        reports.retain(|r| r.id != report_id);
+    }
+
+    if let Some(file_path) = file_path {
+        let app_data_dir = app_handle.path().app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+        // Files the user exported elsewhere are theirs to manage and are left alone
+        remove_app_data_file(&app_data_dir, &PathBuf::from(file_path))
+            .map_err(|e| format!("Failed to delete report file: {}", e))?;
+    }
+
+    Ok(deleted)
 }
 
 /// Get report statistics
 #[tauri::command]
 pub async fn get_report_stats(
-    state: State<'_, ReportingState>,
+    db: State<'_, Database>,
     client_id: Option<String>,
 ) -> Result<ReportStats, String> {
-    let reports = state.reports.lock().map_err(|e| e.to_string())?;
-
-    let filtered: Vec<&Report> = reports.iter()
-        .filter(|r| client_id.as_ref().map_or(true, |cid| &r.client_id == cid))
-        .collect();
+    let filtered = ReportRepository::new(&db)
+        .list_summaries(client_id.as_deref())
+        .map_err(|e| e.to_string())?;
 
     let total_reports = filtered.len();
 
     // Count by type
     let mut type_counts: std::collections::HashMap<ReportType, usize> = std::collections::HashMap::new();
     for r in &filtered {
-        *type_counts.entry(r.config.report_type).or_insert(0) += 1;
+        *type_counts.entry(r.report_type).or_insert(0) += 1;
     }
     let by_type: Vec<ReportTypeCount> = type_counts.into_iter()
         .map(|(report_type, count)| ReportTypeCount { report_type, count })
@@ -293,24 +891,19 @@ pub async fn get_report_stats(
         .map(|(status, count)| ReportStatusCount { status, count })
         .collect();
 
-    // Recent reports
-    let mut recent: Vec<ReportSummary> = filtered.iter()
-        .take(5)
-        .map(|r| ReportSummary {
-            id: r.id.clone(),
-            title: r.config.title.clone(),
-            report_type: r.config.report_type,
-            client_name: r.config.client_name.clone(),
-            status: r.status,
-            format: r.config.format,
-            created_at: r.created_at.clone(),
-            file_size: r.file_size,
-        })
-        .collect();
+    let month_start = chrono::Utc::now().format("%Y-%m-01T00:00:00Z").to_string();
+    let month_start = chrono::DateTime::parse_from_rfc3339(&month_start).map_err(|e| e.to_string())?;
+    let reports_this_month = filtered
+        .iter()
+        .filter(|r| chrono::DateTime::parse_from_rfc3339(&r.created_at).is_ok_and(|created| created >= month_start))
+        .count();
+
+    // Recent reports (summaries are newest first)
+    let recent: Vec<ReportSummary> = filtered.into_iter().take(5).collect();
 
     Ok(ReportStats {
         total_reports,
-        reports_this_month: total_reports, // Simplified
+        reports_this_month,
         by_type,
         by_status,
         recent_reports: recent,
@@ -372,10 +965,128 @@ pub async fn generate_demo_reports(client_id: String, client_name: String) -> Re
     Ok(demo_reports)
 }
 
+// ============================================================================
+// Branding Commands
+// ============================================================================
+
+/// Request to save an organization's branding profile
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveBrandingProfileRequest {
+    pub organization: String,
+    pub firm_name: String,
+    /// PNG or JPEG shown on report cover pages
+    pub logo_path: Option<String>,
+    /// Hex color (default: #3B82F6)
+    pub primary_color: Option<String>,
+    pub secondary_color: Option<String>,
+    pub footer_text: Option<String>,
+}
+
+/// Create or replace an organization's branding profile
+#[tauri::command]
+pub async fn save_branding_profile(
+    db: State<'_, Database>,
+    request: SaveBrandingProfileRequest,
+) -> Result<BrandingProfile, String> {
+    let repo = BrandingRepository::new(&db);
+    let organization = request.organization.trim().to_string();
+    let existing = repo.get(&organization).map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now();
+    let blank_to_none = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    let mut profile = BrandingProfile {
+        organization,
+        firm_name: request.firm_name.trim().to_string(),
+        logo_path: blank_to_none(request.logo_path),
+        primary_color: blank_to_none(request.primary_color)
+            .unwrap_or_else(|| DEFAULT_PRIMARY_COLOR.to_string()),
+        secondary_color: blank_to_none(request.secondary_color),
+        footer_text: blank_to_none(request.footer_text),
+        created_at: existing.map(|p| p.created_at).unwrap_or(now),
+        updated_at: now,
+    };
+    profile.validate()?;
+    if let Some(ref path) = profile.logo_path {
+        Logo::read(path)?;
+    }
+    profile.primary_color = normalize_hex_color(&profile.primary_color).unwrap_or(profile.primary_color);
+    profile.secondary_color = profile.secondary_color.as_deref().and_then(normalize_hex_color);
+
+    repo.save(&profile).map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+/// Get an organization's branding profile
+#[tauri::command]
+pub async fn get_branding_profile(
+    db: State<'_, Database>,
+    organization: String,
+) -> Result<Option<BrandingProfile>, String> {
+    BrandingRepository::new(&db).get(organization.trim()).map_err(|e| e.to_string())
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Load a report from the session cache, falling back to the database
+fn load_report(state: &ReportingState, db: &Database, report_id: &str) -> Result<Report, String> {
+    {
+        let reports = state.reports.lock().map_err(|e| e.to_string())?;
+        if let Some(report) = reports.iter().find(|r| r.id == report_id) {
+            return Ok(report.clone());
+        }
+    }
+
+    let report = ReportRepository::new(db)
+        .get(report_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Report not found".to_string())?;
+
+    let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
+    reports.push(report.clone());
+
+    Ok(report)
+}
+
+/// Re-place a report's custom sections in its stored content after one changes,
+/// so exports pick up the change without regenerating
+fn refresh_custom_sections(state: &ReportingState, db: &Database, mut report: Report) -> Result<(), String> {
+    let custom_sections = CustomSectionRepository::new(db).list_by_report(&report.id).map_err(|e| e.to_string())?;
+    if let Some(content) = report.content.as_mut() {
+        place_custom_sections(content, &custom_sections, report.config.include_toc);
+    }
+    report.updated_at = chrono::Utc::now().to_rfc3339();
+    ReportRepository::new(db).save(&report).map_err(|e| e.to_string())?;
+
+    let mut reports = state.reports.lock().map_err(|e| e.to_string())?;
+    if let Some(cached) = reports.iter_mut().find(|r| r.id == report.id) {
+        *cached = report;
+    }
+    Ok(())
+}
+
+/// Fill in the organization's branding profile for anything the request leaves unset
+fn apply_branding(db: &Database, config: &mut ReportConfig) -> Result<(), String> {
+    let profile = match config.organization.as_deref() {
+        Some(organization) => BrandingRepository::new(db).get(organization).map_err(|e| e.to_string())?,
+        None => None,
+    };
+    if let Some(profile) = profile {
+        profile.apply_to(config);
+    }
+    Ok(())
+}
+
+/// The client's primary contact for cover pages, if it has one
+fn client_contact(db: &Database, client_id: &str) -> Result<Option<String>, String> {
+    Ok(ClientRepository::new(db)
+        .get(client_id)
+        .map_err(|e| e.to_string())?
+        .and_then(|client| client.primary_contact().map(|c| c.display_line())))
+}
+
 fn parse_report_type(s: &str) -> Result<ReportType, String> {
     match s.to_lowercase().replace("-", "_").as_str() {
         "executive_summary" | "executivesummary" | "executive" => Ok(ReportType::ExecutiveSummary),
@@ -396,6 +1107,7 @@ fn parse_export_format(s: &str) -> Result<ExportFormat, String> {
         "markdown" | "md" => Ok(ExportFormat::Markdown),
         "docx" | "word" => Ok(ExportFormat::Docx),
         "json" => Ok(ExportFormat::Json),
+        "csv" => Ok(ExportFormat::Csv),
         _ => Err(format!("Unknown export format: {}", s)),
     }
 }
@@ -414,6 +1126,17 @@ pub struct GenerateExecutivePdfRequest {
     pub framework: Option<String>,
     pub include_network_data: bool,
     pub include_compliance_data: bool,
+    /// Break compliance down by the client's category groups
+    pub group_categories: Option<bool>,
+    /// Organization whose branding profile to apply
+    pub organization: Option<String>,
+    /// Cover page logo; defaults to the branding profile
+    pub logo_path: Option<String>,
+    /// Heading and banner color; defaults to the branding profile
+    pub primary_color: Option<String>,
+    /// Number the sections and add a contents page with page references
+    #[serde(default)]
+    pub include_toc: bool,
 }
 
 /// Response from PDF generation
@@ -448,10 +1171,22 @@ pub async fn generate_executive_pdf(
     );
     let output_path = app_data_dir.join(&file_name);
 
-    // Build executive report data
+    // Build executive report data; without a framework, detail the one
+    // assessed most and show the others in the combined overview
+    let compliance_overview = if request.include_compliance_data {
+        report_compliance_overview(&db, &request.client_id)?
+    } else {
+        None
+    };
     let compliance_status = if request.include_compliance_data {
-        let framework = request.framework.as_deref().unwrap_or("NIST_CSF_2");
-        build_compliance_status(&db, framework, Some(&request.client_id)).await.ok()
+        let framework = request.framework.clone().unwrap_or_else(|| {
+            compliance_overview
+                .as_ref()
+                .and_then(|o| o.primary_framework())
+                .map_or_else(|| "NIST_CSF_2".to_string(), |f| format!("{:?}", f.framework))
+        });
+        let grouped = request.group_categories.unwrap_or(false);
+        build_compliance_status(&db, &framework, Some(&request.client_id), grouped).await.ok()
     } else {
         None
     };
@@ -467,11 +1202,28 @@ pub async fn generate_executive_pdf(
         format!("Executive Security Assessment - {}", request.client_name)
     });
 
+    // Prefer the managed risk register; estimate from compliance gaps without one
+    let risks = RiskRepository::new(&db)
+        .list_by_client(&request.client_id)
+        .map_err(|e| e.to_string())?;
+    let risk_summary = summarize_risks(&risks)
+        .unwrap_or_else(|| calculate_risk_summary(&compliance_status));
+
+    let compliance_trend = match &compliance_status {
+        Some(compliance) => report_compliance_trend(&db, &request.client_id, compliance.framework, None)?
+            .map(|trend| trend.points)
+            .unwrap_or_default(),
+        None => vec![],
+    };
+
     let data = ExecutiveReportData {
         client_name: request.client_name.clone(),
+        client_contact: client_contact(&db, &request.client_id)?,
         title: title.clone(),
         report_date: chrono::Utc::now().format("%B %d, %Y").to_string(),
         compliance_status: compliance_status.clone(),
+        compliance_overview,
+        compliance_trend,
         network_health_score,
         total_assets: compliance_status.as_ref()
             .and_then(|c| c.total_assets)
@@ -483,18 +1235,43 @@ pub async fn generate_executive_pdf(
             AssetCategoryCount { category: "Security Appliances".to_string(), count: 8 },
         ],
         top_findings: generate_findings_from_compliance(&compliance_status),
-        risk_summary: calculate_risk_summary(&compliance_status),
+        risk_summary,
     };
 
-    // Generate PDF
-    let generator = PdfGenerator::new(title);
-    let file_size = generator.generate_executive_report(&data, &output_path)?;
+    // Generate PDF, with explicit branding taking precedence over the profile
+    let branding = match request.organization.as_deref() {
+        Some(organization) => BrandingRepository::new(&db).get(organization).map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let mut generator = PdfGenerator::new(title);
+    if let Some(color) = request.primary_color.or_else(|| branding.as_ref().map(|b| b.primary_color.clone())) {
+        generator = generator.with_primary_color(color);
+    }
+    if let Some(path) = request.logo_path.or_else(|| branding.as_ref().and_then(|b| b.logo_path.clone())) {
+        generator = generator.with_logo(path);
+    }
+    if let Some(footer) = branding.and_then(|b| b.footer_text) {
+        generator = generator.with_footer_text(footer);
+    }
+    if request.include_toc {
+        generator = generator.with_table_of_contents();
+    }
+    audit::record(&db, AuditEntry::new(AuditOperation::ReportExported, "executive_report", None)
+        .with_detail(serde_json::json!({
+            "clientId": request.client_id,
+            "title": data.title,
+            "format": "pdf",
+            "outputPath": output_path.to_string_lossy(),
+        })))
+        .map_err(|e| e.to_string())?;
+    let output = generator.generate_executive_report(&data, &output_path)?;
+    onboarding::record(&db, Milestone::FirstReport);
 
     Ok(PdfGenerationResult {
         success: true,
         file_path: output_path.to_string_lossy().to_string(),
-        file_size,
-        page_count: 5,
+        file_size: output.file_size,
+        page_count: output.page_count,
         message: "Executive PDF report generated successfully".to_string(),
     })
 }
@@ -517,13 +1294,13 @@ pub async fn generate_demo_pdf(
     );
     let output_path = app_data_dir.join(&file_name);
 
-    let file_size = generate_demo_executive_report(&client_name, &output_path)?;
+    let output = generate_demo_executive_report(&client_name, &output_path)?;
 
     Ok(PdfGenerationResult {
         success: true,
         file_path: output_path.to_string_lossy().to_string(),
-        file_size,
-        page_count: 5,
+        file_size: output.file_size,
+        page_count: output.page_count,
         message: "Demo executive PDF generated successfully".to_string(),
     })
 }
@@ -565,15 +1342,401 @@ pub async fn open_pdf_location(file_path: String) -> Result<bool, String> {
 // PDF Helper Functions
 // ============================================================================
 
+/// Data source types a report can draw on, in load order
+///
+/// Evidence, the remediation plan and processing activities follow the
+/// compliance data's framework, so `compliance` loads before them.
+const REPORT_SOURCE_TYPES: &[&str] = &[
+    "compliance",
+    "compliance_overview",
+    "asset_inventory",
+    "network_findings",
+    "findings",
+    "vulnerabilities",
+    "scan",
+    "evidence",
+    "remediation_plan",
+    "processing_activities",
+    "risk_register",
+    "cloud_readiness",
+    "cost_projection",
+    "k8s_audit",
+];
+
+/// Gather the client's assessment data for a report request
+///
+/// Returns the data alongside the list of sources it came from, for the
+/// report's data sources appendix.
+async fn report_data(
+    db: &Database,
+    request: &GenerateReportRequest,
+) -> Result<(ReportDataSource, Vec<DataSource>), String> {
+    let mut loader = ReportDataLoader::new(request);
+    let mut data = loader.initial_data();
+    for source_type in REPORT_SOURCE_TYPES {
+        loader.load(db, source_type, &mut data).await?;
+    }
+    Ok((data, loader.sources))
+}
+
+/// Loads a report request's data one source type at a time
+///
+/// Each source type loads at most once, so sections sharing a source can
+/// each ask for it.
+struct ReportDataLoader<'r> {
+    request: &'r GenerateReportRequest,
+    demo: bool,
+    excluded: Vec<&'r str>,
+    loaded: Vec<&'static str>,
+    sources: Vec<DataSource>,
+    /// Snapshot the compliance data was taken from, once loaded
+    snapshot: Option<AssessmentSnapshot>,
+}
+
+impl<'r> ReportDataLoader<'r> {
+    fn new(request: &'r GenerateReportRequest) -> Self {
+        let demo = request.use_demo_data.unwrap_or(false);
+        let sources = if demo {
+            vec![DataSource {
+                source_type: "demo".to_string(),
+                source_id: "sample-content".to_string(),
+                included: true,
+            }]
+        } else {
+            vec![]
+        };
+        let excluded = request.data_sources.iter()
+            .flatten()
+            .filter(|d| !d.included)
+            .map(|d| d.source_type.as_str())
+            .collect();
+
+        Self { request, demo, excluded, loaded: Vec::new(), sources, snapshot: None }
+    }
+
+    /// Data to start from; demo requests have everything up front
+    fn initial_data(&self) -> ReportDataSource {
+        if self.demo {
+            ReportDataSource::demo()
+        } else {
+            ReportDataSource::default()
+        }
+    }
+
+    fn record(&mut self, source_type: &str, source_id: String) {
+        self.sources.push(DataSource {
+            source_type: source_type.to_string(),
+            source_id,
+            included: true,
+        });
+    }
+
+    /// Load one source type into `data`, unless excluded or already loaded
+    async fn load(
+        &mut self,
+        db: &Database,
+        source_type: &'static str,
+        data: &mut ReportDataSource,
+    ) -> Result<(), String> {
+        if self.demo || self.loaded.contains(&source_type) || self.excluded.contains(&source_type) {
+            return Ok(());
+        }
+        self.loaded.push(source_type);
+        let request = self.request;
+
+        match source_type {
+            "compliance" if request.snapshot_id.is_some() => {
+                let snapshot = report_snapshot(db, request)?;
+                self.record("compliance", snapshot.framework.display_name().to_string());
+                self.record("compliance", snapshot.id.clone());
+                data.compliance = Some(snapshot_compliance_status(db, request, &snapshot)?);
+                data.assessment_comparison = snapshot.summary.source_comparison.clone();
+                data.compliance_trend =
+                    report_compliance_trend(db, &request.client_id, snapshot.framework, Some(snapshot.taken_at))?;
+                if let Some(comparison) = &data.assessment_comparison {
+                    self.record("compliance", comparison.baseline_id.clone());
+                }
+                self.snapshot = Some(snapshot);
+            }
+            "compliance" => {
+                data.compliance = report_compliance_status(db, request).await?;
+                if let Some(compliance) = &data.compliance {
+                    self.record("compliance", compliance.framework.display_name().to_string());
+                    data.assessment_comparison = report_assessment_comparison(db, &request.client_id, compliance.framework)?;
+                    data.compliance_trend = report_compliance_trend(db, &request.client_id, compliance.framework, None)?;
+                }
+                if let Some(comparison) = &data.assessment_comparison {
+                    self.record("compliance", comparison.baseline_id.clone());
+                }
+            }
+            "compliance_overview" => {
+                data.compliance_overview = report_compliance_overview(db, &request.client_id)?;
+                for framework in data.compliance_overview.iter().flat_map(|o| &o.frameworks) {
+                    self.record("compliance_overview", framework.framework.display_name().to_string());
+                }
+            }
+            "asset_inventory" => {
+                let stats = AssetInventory::new(db)
+                    .get_stats(&request.client_id)
+                    .map_err(|e| e.to_string())?;
+                if stats.total_assets > 0 {
+                    self.record("asset_inventory", request.client_id.clone());
+                    data.network_stats = Some(stats);
+                    data.policy_violations = AssetInventory::new(db)
+                        .evaluate_service_policies(&request.client_id)
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+            "network_findings" => {
+                data.network_findings = NetworkFindingRepository::new(db)
+                    .list_by_client(&request.client_id)
+                    .map_err(|e| e.to_string())?;
+                if !data.network_findings.is_empty() {
+                    self.record("network_findings", format!("{} findings", data.network_findings.len()));
+                }
+            }
+            "findings" => {
+                data.findings = FindingRepository::new(db)
+                    .list_by_client(&request.client_id)
+                    .map_err(|e| e.to_string())?;
+                if !data.findings.is_empty() {
+                    self.record("findings", format!("{} library findings", data.findings.len()));
+                }
+            }
+            "vulnerabilities" => {
+                data.vulnerabilities = VulnerabilityRepository::new(db)
+                    .list_by_client(&request.client_id)
+                    .map_err(|e| e.to_string())?;
+                if !data.vulnerabilities.is_empty() {
+                    self.record("vulnerabilities", format!("{} CVE matches", data.vulnerabilities.len()));
+                }
+            }
+            "scan" => {
+                data.verification_scans = client_verification_scans(db, &request.client_id)?;
+                for scan in &data.verification_scans {
+                    self.record("scan", scan.id.clone());
+                }
+                data.scan_diff = report_scan_diff(db, request)?;
+                if let Some(diff) = &data.scan_diff {
+                    self.record("scan", diff.scan_id_a.clone());
+                    self.record("scan", diff.scan_id_b.clone());
+                }
+            }
+            "evidence" => {
+                if let Some(assessment) = compliance_assessment(db, request, data, self.snapshot.as_ref())? {
+                    data.evidence_coverage = Some(evidence_coverage(db, &assessment.id)?);
+                    self.record("evidence", assessment.id);
+                }
+            }
+            "remediation_plan" => {
+                if let Some(assessment) = compliance_assessment(db, request, data, self.snapshot.as_ref())? {
+                    data.remediation_plan = RemediationPlanRepository::new(db)
+                        .get_by_assessment(&assessment.id)
+                        .map_err(|e| e.to_string())?;
+                    if let Some(plan) = &data.remediation_plan {
+                        self.record("remediation_plan", plan.id.clone());
+                    }
+                }
+            }
+            "processing_activities" if data.compliance.as_ref().is_some_and(|c| c.framework == Framework::Gdpr) => {
+                data.processing_activities = ProcessingActivityRepository::new(db)
+                    .list_by_client(&request.client_id)
+                    .map_err(|e| e.to_string())?;
+                if !data.processing_activities.is_empty() {
+                    self.record("processing_activities", format!("{} activities", data.processing_activities.len()));
+                }
+            }
+            "risk_register" => {
+                data.risks = RiskRepository::new(db)
+                    .list_by_client(&request.client_id)
+                    .map_err(|e| e.to_string())?;
+                if !data.risks.is_empty() {
+                    self.record("risk_register", format!("{} risks", data.risks.len()));
+                }
+            }
+            "cloud_readiness" => {
+                let assessment = CloudReadinessRepository::new(db)
+                    .get_by_client(&request.client_id)
+                    .map_err(|e| e.to_string())?;
+                if let Some(assessment) = assessment {
+                    data.cloud_readiness = Some(score_readiness(&assessment));
+                    self.record("cloud_readiness", assessment.id);
+                }
+            }
+            "cost_projection" => {
+                data.cost_projections = request.cost_projections.clone().unwrap_or_default();
+                for projection in &data.cost_projections {
+                    self.record("cost_projection", projection.id.clone());
+                }
+                data.provider_comparison = request.provider_comparison.clone();
+                if let Some(comparison) = &data.provider_comparison {
+                    self.record("cost_projection", comparison.id.clone());
+                }
+            }
+            "k8s_audit" => {
+                if let Some(audit_id) = &request.k8s_audit_id {
+                    let audit = K8sAuditRepository::new(db)
+                        .get(audit_id)
+                        .map_err(|e| e.to_string())?
+                        .filter(|a| a.client_id == request.client_id)
+                        .ok_or_else(|| format!("K8s audit not found: {}", audit_id))?;
+                    self.record("k8s_audit", audit.id.clone());
+                    data.k8s_audit = Some(audit);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// The assessment behind the compliance data: the snapshot's, or else the
+/// client's latest assessment of the framework
+fn compliance_assessment(
+    db: &Database,
+    request: &GenerateReportRequest,
+    data: &ReportDataSource,
+    snapshot: Option<&AssessmentSnapshot>,
+) -> Result<Option<Assessment>, String> {
+    if let Some(snapshot) = snapshot {
+        return AssessmentRepository::new(db).get(&snapshot.assessment_id).map_err(|e| e.to_string());
+    }
+    let compliance = match &data.compliance {
+        Some(compliance) => compliance,
+        None => return Ok(None),
+    };
+    Ok(AssessmentRepository::new(db)
+        .list_by_client(&request.client_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|a| a.framework == compliance.framework))
+}
+
+/// Verification scans recorded for a client
+fn client_verification_scans(db: &Database, client_id: &str) -> Result<Vec<ScanJob>, String> {
+    let scans = ScanRepository::new(db).list_by_client(client_id).map_err(|e| e.to_string())?;
+    Ok(scans.into_iter()
+        .filter(|s| s.verification.is_some())
+        .collect())
+}
+
+/// Scan comparison for a report request, when both scans were selected
+fn report_scan_diff(db: &Database, request: &GenerateReportRequest) -> Result<Option<ScanDiff>, String> {
+    match (&request.baseline_scan_id, &request.followup_scan_id) {
+        (Some(baseline), Some(followup)) => {
+            diff_stored_scans(db, baseline, followup, Some(&request.client_id)).map(Some)
+        }
+        (None, None) => Ok(None),
+        _ => Err("Both a baseline and a follow-up scan are required to report changes".to_string()),
+    }
+}
+
+/// Live compliance data for a report request, when a framework was selected
+async fn report_compliance_status(
+    db: &Database,
+    request: &GenerateReportRequest,
+) -> Result<Option<ComplianceStatusReport>, String> {
+    match &request.framework {
+        Some(framework) => {
+            let client_id = Some(request.client_id.as_str()).filter(|c| !c.is_empty());
+            let grouped = request.group_categories.unwrap_or(false);
+            build_compliance_status(db, framework, client_id, grouped).await.map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+/// The snapshot a report request's compliance data is frozen from
+fn report_snapshot(db: &Database, request: &GenerateReportRequest) -> Result<AssessmentSnapshot, String> {
+    let id = request.snapshot_id.as_deref().unwrap_or_default();
+    let snapshot = load_snapshot(db, id)?;
+    if snapshot.client_id != request.client_id {
+        return Err(format!("Assessment snapshot {} belongs to another client", id));
+    }
+    Ok(snapshot)
+}
+
+/// Compliance data frozen in a snapshot, rolled up into the client's
+/// category groups when the request asks for them
+fn snapshot_compliance_status(
+    db: &Database,
+    request: &GenerateReportRequest,
+    snapshot: &AssessmentSnapshot,
+) -> Result<ComplianceStatusReport, String> {
+    let mut report = snapshot.compliance_report();
+    if request.group_categories.unwrap_or(false) {
+        let groups = CategoryGroupingRepository::new(db)
+            .groups_for(&request.client_id, snapshot.framework)
+            .map_err(|e| e.to_string())?;
+        report.group_breakdown = rollup_category_groups(&report.category_breakdown, &groups);
+    }
+    Ok(report)
+}
+
+/// The client's combined compliance across frameworks, when it has any
+/// active assessment
+fn report_compliance_overview(db: &Database, client_id: &str) -> Result<Option<ComplianceOverview>, String> {
+    if client_id.is_empty() {
+        return Ok(None);
+    }
+    let overview = build_compliance_overview(db, client_id, None).map_err(|e| e.to_string())?;
+    Ok(Some(overview).filter(|o| !o.frameworks.is_empty()))
+}
+
+/// Year-over-year change for the client's latest assessment of `framework`,
+/// when that assessment was cloned from an earlier one
+fn report_assessment_comparison(
+    db: &Database,
+    client_id: &str,
+    framework: Framework,
+) -> Result<Option<AssessmentComparison>, String> {
+    let latest = AssessmentRepository::new(db)
+        .list_by_client(client_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|a| a.framework == framework);
+
+    match latest {
+        Some(assessment) => {
+            let summary = summarize_assessment(db, &assessment)?;
+            source_comparison(db, &assessment, &summary)
+        }
+        None => Ok(None),
+    }
+}
+
+/// The client's compliance snapshots for `framework`, up to `until` if
+/// given, when any were taken
+fn report_compliance_trend(
+    db: &Database,
+    client_id: &str,
+    framework: Framework,
+    until: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Option<ComplianceTrend>, String> {
+    if client_id.is_empty() {
+        return Ok(None);
+    }
+    let mut snapshots = ComplianceSnapshotRepository::new(db)
+        .list(client_id, framework, None)
+        .map_err(|e| e.to_string())?;
+    snapshots.retain(|s| until.map_or(true, |until| s.taken_at <= until));
+    if snapshots.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(build_compliance_trend(client_id, framework, &snapshots)))
+}
+
 async fn build_compliance_status(
     db: &Database,
     framework_str: &str,
     client_id: Option<&str>,
+    grouped: bool,
 ) -> Result<ComplianceStatusReport, String> {
     let fw = match framework_str.to_uppercase().as_str() {
         "NIST_CSF_2" | "NISTCSF2" | "NIST_CSF2" => Framework::NistCsf2,
         "SOC_2_TYPE_II" | "SOC2TYPEII" | "SOC2" => Framework::Soc2TypeII,
         "GDPR" => Framework::Gdpr,
+        "ISO27001" | "ISO_27001" | "ISO_27001_2022" => Framework::Iso27001,
         _ => return Err(format!("Unknown framework: {}", framework_str)),
     };
 
@@ -681,6 +1844,8 @@ async fn build_compliance_status(
                 non_compliant: non_comp,
                 completion_percentage: (completion_pct * 10.0).round() / 10.0,
                 compliance_percentage: (compliance_pct * 10.0).round() / 10.0,
+                average_maturity: None,
+                maturity_rated: 0,
             }
         })
         .collect();
@@ -699,7 +1864,7 @@ async fn build_compliance_status(
         0.0
     };
 
-    Ok(ComplianceStatusReport {
+    let mut report = ComplianceStatusReport {
         framework: fw,
         completion_percentage: (completion_percentage * 10.0).round() / 10.0,
         compliance_percentage: (compliance_percentage * 10.0).round() / 10.0,
@@ -710,10 +1875,24 @@ async fn build_compliance_status(
         non_compliant_controls: total_non_compliant,
         not_applicable_controls: total_na,
         category_breakdown,
+        group_breakdown: vec![],
+        average_maturity: None,
         network_health_score: None,
         total_assets: Some(134),
         last_updated: chrono::Utc::now().to_rfc3339(),
-    })
+    };
+    apply_maturity(&mut report, &controls, |control| {
+        all_control_assessments.get(&control.id).and_then(|ca| ca.rated_maturity())
+    });
+
+    if let Some(cid) = client_id.filter(|_| grouped) {
+        let groups = CategoryGroupingRepository::new(db)
+            .groups_for(cid, fw)
+            .map_err(|e| e.to_string())?;
+        report.group_breakdown = rollup_category_groups(&report.category_breakdown, &groups);
+    }
+
+    Ok(report)
 }
 
 fn calculate_network_health_score(compliance: &Option<ComplianceStatusReport>) -> f64 {
@@ -777,23 +1956,7 @@ fn calculate_risk_summary(compliance: &Option<ComplianceStatusReport>) -> RiskSu
             let medium = c.partially_compliant_controls.min(10);
             let low = (c.total_controls - c.assessed_controls).min(8);
 
-            let rating = if critical > 1 {
-                "Critical"
-            } else if high > 2 {
-                "High"
-            } else if medium > 5 {
-                "Moderate"
-            } else {
-                "Low"
-            };
-
-            RiskSummary {
-                critical_count: critical,
-                high_count: high,
-                medium_count: medium,
-                low_count: low,
-                overall_risk_rating: rating.to_string(),
-            }
+            summarize_risk_counts(critical, high, medium, low)
         }
         None => RiskSummary {
             critical_count: 0,
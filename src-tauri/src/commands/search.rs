@@ -0,0 +1,28 @@
+//! Search Commands
+//!
+//! Global search across clients, assets, controls, control assessments,
+//! evidence and reports.
+
+use crate::db::Database;
+use crate::search::{self, SearchEntityType, SearchHit, DEFAULT_SEARCH_LIMIT};
+use tauri::State;
+
+/// Search everything, best matches first, optionally for one client or
+/// some kinds of record
+#[tauri::command]
+pub async fn search_everything(
+    db: State<'_, Database>,
+    query: String,
+    client_id: Option<String>,
+    entity_types: Option<Vec<SearchEntityType>>,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    search::search_everything(
+        &db,
+        &query,
+        client_id.as_deref(),
+        entity_types.as_deref(),
+        limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+    )
+    .map_err(|e| e.to_string())
+}
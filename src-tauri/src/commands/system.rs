@@ -2,8 +2,13 @@
 //!
 //! System information and utility commands.
 
+use crate::db::{ConsultantIpRepository, Database};
+use crate::factory::consultant_ip_problem;
+use crate::onboarding::{self, Milestone};
+use ipnetwork::IpNetwork;
 use serde::Serialize;
-use std::net::UdpSocket;
+use std::net::{IpAddr, UdpSocket};
+use tauri::State;
 
 /// System information response
 #[derive(Debug, Serialize)]
@@ -38,10 +43,327 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
     })
 }
 
-/// Get the consultant's local IP address for script injection
+/// A network interface on the consultant's machine
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsultantInterface {
+    pub name: String,
+    pub ipv4: Vec<String>,
+    pub ipv6: Vec<String>,
+    /// Attached networks in CIDR notation, e.g. "192.168.1.0/24"
+    pub subnets: Vec<String>,
+    /// VPN tunnel or virtual adapter (Docker, Hyper-V, VirtualBox, ...), by name
+    pub is_virtual: bool,
+    pub is_loopback: bool,
+    /// Carries the route to the internet
+    pub has_default_route: bool,
+}
+
+/// Candidate consultant IPs for script injection
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsultantIpInfo {
+    pub interfaces: Vec<ConsultantInterface>,
+    /// Best guess: the routed address, unless it belongs to a virtual adapter
+    pub suggested_ip: Option<String>,
+    /// Address chosen for the client, if one was persisted
+    pub preferred_ip: Option<String>,
+    /// Interface whose address is suggested by default, if one was chosen
+    pub preferred_interface: Option<String>,
+}
+
+/// Consultant IP that can reach an engagement subnet
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubnetConsultantIp {
+    pub target_subnet: String,
+    pub ip: Option<String>,
+    pub interface: Option<String>,
+    /// Whether the interface is attached to the subnet rather than routing to it
+    pub directly_attached: bool,
+}
+
+/// List the consultant's interfaces and the IP to inject for a client
+#[tauri::command]
+pub async fn get_consultant_ip(
+    db: State<'_, Database>,
+    client_id: Option<String>,
+) -> Result<ConsultantIpInfo, String> {
+    let repo = ConsultantIpRepository::new(&db);
+    let preferred_interface = repo.preferred_interface().map_err(|e| e.to_string())?;
+    let interfaces = list_interfaces();
+    let suggested_ip = suggest_consultant_ip(&interfaces, detect_local_ip(), preferred_interface.as_deref());
+    let preferred_ip = match client_id {
+        Some(id) => repo.get(&id).map_err(|e| e.to_string())?,
+        None => None,
+    };
+
+    Ok(ConsultantIpInfo {
+        interfaces,
+        suggested_ip,
+        preferred_ip,
+        preferred_interface,
+    })
+}
+
+/// List the consultant's non-loopback interfaces with their subnets
 #[tauri::command]
-pub async fn get_consultant_ip() -> Result<String, String> {
-    detect_local_ip().ok_or_else(|| "Could not detect local IP address".to_string())
+pub async fn list_network_interfaces() -> Result<Vec<ConsultantInterface>, String> {
+    Ok(list_interfaces().into_iter().filter(|i| !i.is_loopback).collect())
+}
+
+/// Persist the interface whose address is suggested by default
+///
+/// An empty name clears the preference.
+#[tauri::command]
+pub async fn set_preferred_interface(
+    db: State<'_, Database>,
+    name: Option<String>,
+) -> Result<(), String> {
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if let Some(name) = &name {
+        if !list_interfaces().iter().any(|i| i.name == *name) {
+            return Err(format!("No network interface named {}", name));
+        }
+    }
+
+    ConsultantIpRepository::new(&db)
+        .set_preferred_interface(name.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Suggest the consultant IP for an engagement subnet
+///
+/// Picks the interface attached to the subnet, or else the one the OS
+/// routes it through (a VPN tunnel, typically).
+#[tauri::command]
+pub async fn suggest_consultant_ip_for_subnet(target_subnet: String) -> Result<SubnetConsultantIp, String> {
+    let target: IpNetwork = target_subnet
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid subnet: {}", target_subnet))?;
+
+    let interfaces = list_interfaces();
+    let choice = consultant_ip_for_subnet(&interfaces, target, route_source_ip(target.network()));
+
+    Ok(SubnetConsultantIp {
+        target_subnet: target.to_string(),
+        directly_attached: choice.as_ref().map_or(false, |c| c.2),
+        ip: choice.as_ref().map(|c| c.1.clone()),
+        interface: choice.map(|c| c.0),
+    })
+}
+
+/// Persist the consultant IP to inject into a client's scripts
+#[tauri::command]
+pub async fn set_preferred_consultant_ip(
+    db: State<'_, Database>,
+    client_id: String,
+    ip: String,
+) -> Result<(), String> {
+    let ip = ip.trim().to_string();
+    if let Some(problem) = consultant_ip_problem(&ip) {
+        return Err(problem);
+    }
+
+    ConsultantIpRepository::new(&db)
+        .set(&client_id, &ip)
+        .map_err(|e| e.to_string())?;
+    onboarding::advance(&db, &[Milestone::ConsultantProfile]);
+    Ok(())
+}
+
+/// Consultant IP to embed in a client's scripts
+///
+/// An explicit IP wins, then the client's persisted preference, then the
+/// suggested default.
+pub fn resolve_consultant_ip(db: &Database, client_id: Option<&str>, explicit: Option<&str>) -> String {
+    if let Some(ip) = explicit.map(str::trim).filter(|ip| !ip.is_empty()) {
+        return ip.to_string();
+    }
+
+    if let Some(id) = client_id {
+        match ConsultantIpRepository::new(db).get(id) {
+            Ok(Some(ip)) => return ip,
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to load consultant IP for client {}: {}", id, e),
+        }
+    }
+
+    let preferred_interface = ConsultantIpRepository::new(db).preferred_interface().unwrap_or_else(|e| {
+        tracing::warn!("Failed to load preferred consultant interface: {}", e);
+        None
+    });
+    suggest_consultant_ip(&list_interfaces(), detect_local_ip(), preferred_interface.as_deref())
+        .unwrap_or_else(|| "127.0.0.1".to_string())
+}
+
+/// Why client machines in `target_subnet` may not reach `ip`, if they may not
+///
+/// Compares the IP against the interface this machine would use for the
+/// subnet. Unparseable input is left to the other validations.
+pub fn consultant_ip_route_problem(ip: &str, target_subnet: &str) -> Option<String> {
+    let target: IpNetwork = target_subnet.trim().parse().ok()?;
+    let ip = ip.trim();
+    let interfaces = list_interfaces();
+
+    match consultant_ip_for_subnet(&interfaces, target, route_source_ip(target.network())) {
+        Some((_, reaching_ip, _)) if reaching_ip == ip => None,
+        Some((interface, reaching_ip, _)) => Some(format!(
+            "Consultant IP {} is not on the interface that reaches {} ({}, {}); scripts may not be able to call back",
+            ip, target, interface, reaching_ip
+        )),
+        None => Some(format!(
+            "No interface on this machine reaches {}; check the VPN or network connection before deploying scripts",
+            target
+        )),
+    }
+}
+
+/// Enumerate network interfaces, grouping addresses by interface name
+///
+/// The interface holding the address used to reach the internet is marked
+/// as having the default route.
+pub fn list_interfaces() -> Vec<ConsultantInterface> {
+    let routed = detect_local_ip();
+    let addrs = match if_addrs::get_if_addrs() {
+        Ok(addrs) => addrs,
+        Err(e) => {
+            tracing::warn!("Failed to enumerate network interfaces: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut interfaces: Vec<ConsultantInterface> = Vec::new();
+    for addr in addrs {
+        let index = match interfaces.iter().position(|i| i.name == addr.name) {
+            Some(index) => index,
+            None => {
+                interfaces.push(ConsultantInterface {
+                    is_virtual: is_virtual_interface(&addr.name),
+                    is_loopback: addr.is_loopback(),
+                    name: addr.name.clone(),
+                    ipv4: Vec::new(),
+                    ipv6: Vec::new(),
+                    subnets: Vec::new(),
+                    has_default_route: false,
+                });
+                interfaces.len() - 1
+            }
+        };
+        let interface = &mut interfaces[index];
+        let prefix = match &addr.addr {
+            if_addrs::IfAddr::V4(v4) => v4.prefixlen,
+            if_addrs::IfAddr::V6(v6) => v6.prefixlen,
+        };
+        if let Ok(network) = IpNetwork::new(addr.ip(), prefix) {
+            let subnet = format!("{}/{}", network.network(), network.prefix());
+            if !interface.subnets.contains(&subnet) {
+                interface.subnets.push(subnet);
+            }
+        }
+        if routed.as_deref() == Some(addr.ip().to_string().as_str()) {
+            interface.has_default_route = true;
+        }
+        match addr.ip() {
+            IpAddr::V4(ip) => interface.ipv4.push(ip.to_string()),
+            IpAddr::V6(ip) => interface.ipv6.push(ip.to_string()),
+        }
+    }
+    interfaces
+}
+
+/// Whether an interface name looks like a VPN tunnel or virtual adapter
+pub fn is_virtual_interface(name: &str) -> bool {
+    const PREFIXES: &[&str] = &[
+        "docker", "br-", "bridge", "veth", "virbr", "vmnet", "vboxnet", "tun", "tap", "wg", "utun",
+        "ppp", "ipsec", "gpd", "zt", "tailscale", "vethernet", "cscotun", "lxc", "lxd",
+    ];
+    const KEYWORDS: &[&str] = &[
+        "vpn", "virtual", "hyper-v", "vmware", "virtualbox", "wireguard", "openvpn",
+        "anyconnect", "globalprotect", "fortinet", "tap-windows", "wsl", "loopback",
+    ];
+
+    let name = name.to_lowercase();
+    PREFIXES.iter().any(|p| name.starts_with(p)) || KEYWORDS.iter().any(|k| name.contains(k))
+}
+
+/// Pick the default consultant IP from the interfaces
+///
+/// A usable IPv4 address on the preferred interface wins. Otherwise prefers
+/// the routed address when it's on a physical adapter, then the first usable
+/// IPv4 address on a physical adapter, then whatever is routed.
+pub fn suggest_consultant_ip(
+    interfaces: &[ConsultantInterface],
+    routed: Option<String>,
+    preferred_interface: Option<&str>,
+) -> Option<String> {
+    let preferred = interfaces
+        .iter()
+        .filter(|i| Some(i.name.as_str()) == preferred_interface)
+        .flat_map(|i| i.ipv4.iter())
+        .find(|ip| consultant_ip_problem(ip).is_none());
+    if let Some(ip) = preferred {
+        return Some(ip.clone());
+    }
+
+    let physical = || interfaces.iter().filter(|i| !i.is_virtual && !i.is_loopback);
+
+    if let Some(ip) = &routed {
+        if physical().any(|i| i.ipv4.contains(ip) || i.ipv6.contains(ip)) {
+            return routed;
+        }
+    }
+
+    physical()
+        .flat_map(|i| i.ipv4.iter())
+        .find(|ip| consultant_ip_problem(ip).is_none())
+        .cloned()
+        .or(routed)
+}
+
+/// Interface and address that reach a subnet, and whether it's attached
+///
+/// An interface attached to an overlapping network wins; otherwise the
+/// interface owning `route_source`, the address the OS would send from.
+pub fn consultant_ip_for_subnet(
+    interfaces: &[ConsultantInterface],
+    target: IpNetwork,
+    route_source: Option<IpAddr>,
+) -> Option<(String, String, bool)> {
+    let usable = || interfaces.iter().filter(|i| !i.is_loopback);
+
+    for interface in usable() {
+        for subnet in interface.subnets.iter().filter_map(|s| s.parse::<IpNetwork>().ok()) {
+            let overlaps = subnet.contains(target.network()) || target.contains(subnet.network());
+            if !overlaps {
+                continue;
+            }
+            let ip = interface
+                .ipv4
+                .iter()
+                .chain(interface.ipv6.iter())
+                .find(|ip| ip.parse().map_or(false, |ip| subnet.contains(ip)));
+            if let Some(ip) = ip {
+                return Some((interface.name.clone(), ip.clone(), true));
+            }
+        }
+    }
+
+    let source = route_source?.to_string();
+    usable()
+        .find(|i| i.ipv4.contains(&source) || i.ipv6.contains(&source))
+        .map(|i| (i.name.clone(), source, false))
+}
+
+/// Local address the OS would use to send to `target`
+///
+/// Like [`detect_local_ip`], connecting a UDP socket sends nothing.
+pub fn route_source_ip(target: IpAddr) -> Option<IpAddr> {
+    let bind = if target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect((target, 9)).ok()?;
+    Some(socket.local_addr().ok()?.ip())
 }
 
 /// Detect the local IP address by creating a UDP socket
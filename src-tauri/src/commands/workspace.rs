@@ -0,0 +1,36 @@
+//! Workspace Archive Commands
+//!
+//! Export a client's whole engagement workspace as one archive, and check
+//! a received archive against its manifest.
+
+use crate::db::Database;
+use crate::workspace::{
+    self, WorkspaceArchiveValidation, WorkspaceExportOptions, WorkspaceExportSummary,
+    WORKSPACE_EXPORT_PROGRESS_EVENT,
+};
+use std::path::Path;
+use tauri::{AppHandle, Emitter, State};
+
+/// Export everything held for a client into a zip at `dest_path`
+#[tauri::command]
+pub async fn export_client_workspace(
+    app: AppHandle,
+    db: State<'_, Database>,
+    client_id: String,
+    dest_path: String,
+    options: Option<WorkspaceExportOptions>,
+) -> Result<WorkspaceExportSummary, String> {
+    let options = options.unwrap_or_default();
+    workspace::export_workspace(&db, &client_id, Path::new(&dest_path), &options, |progress| {
+        if let Err(e) = app.emit(WORKSPACE_EXPORT_PROGRESS_EVENT, progress) {
+            tracing::warn!("Failed to emit workspace export progress: {}", e);
+        }
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Check a workspace archive's files against the hashes in its manifest
+#[tauri::command]
+pub async fn validate_workspace_archive(path: String) -> Result<WorkspaceArchiveValidation, String> {
+    workspace::validate_workspace(Path::new(&path)).map_err(|e| e.to_string())
+}
@@ -1,17 +1,26 @@
 //! Database layer with encrypted SQLite storage
 //!
 //! Provides secure, local-first storage for client profiles, audit logs,
-//! and credentials using AES-256 encryption.
+//! and credentials using AES-256 encryption. The whole database can also be
+//! encrypted at rest with SQLCipher; see [`crate::db_encryption`].
 
+use crate::db_encryption::{is_encrypted, DatabaseKey};
 use crate::error::{OptioError, OptioResult};
+use crate::grc::models::Framework;
+use crate::grc::repository::parse_framework;
 use rusqlite::{Connection, params};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+/// Version of the schema [`migrate`] brings a database to, kept in
+/// `PRAGMA user_version`. Bump it with any migration that changes the
+/// schema, so existing databases are backed up before they're migrated.
+pub const SCHEMA_VERSION: i32 = 2;
+
 /// Thread-safe database connection wrapper
 pub struct Database {
     pub conn: Mutex<Connection>,
@@ -30,6 +39,32 @@ impl Database {
         })
     }
 
+    /// Open an encrypted database with its key
+    pub fn open_encrypted(path: &Path, key: &DatabaseKey) -> OptioResult<Self> {
+        let conn = crate::db_encryption::open_with_key(path, key)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")?;
+
+        Ok(Database {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Take the connection out, e.g. to swap it into an open database
+    pub fn into_connection(self) -> Connection {
+        self.conn.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The schema version the database was last migrated to, or None for a
+    /// new database with no tables yet
+    pub fn schema_version(&self) -> OptioResult<Option<i32>> {
+        let conn = self.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tables: i64 = conn.query_row("SELECT count(*) FROM sqlite_master WHERE type = 'table'", [], |row| row.get(0))?;
+        if tables == 0 {
+            return Ok(None);
+        }
+        Ok(Some(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?))
+    }
+
     /// Initialize database schema
     pub fn init_schema(&self) -> OptioResult<()> {
         let conn = self.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
@@ -43,7 +78,13 @@ impl Database {
                 contact_email TEXT,
                 notes TEXT,
                 created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
+                updated_at TEXT NOT NULL,
+                contacts TEXT,
+                industry TEXT,
+                company_size TEXT,
+                engagement_start TEXT,
+                engagement_end TEXT,
+                default_framework TEXT
             );
 
             -- Generated scripts history
@@ -79,18 +120,63 @@ impl Database {
                 FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
             );
 
+            -- Consultant IP chosen per client for script injection
+            CREATE TABLE IF NOT EXISTS consultant_ip_preferences (
+                client_id TEXT PRIMARY KEY,
+                ip TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+            );
+
+            -- Consultant machine settings, one row per key
+            CREATE TABLE IF NOT EXISTS consultant_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
             -- Create indexes for performance
             CREATE INDEX IF NOT EXISTS idx_clients_name ON clients(name);
             CREATE INDEX IF NOT EXISTS idx_script_history_client ON script_history(client_id);
             CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp);
         "#)?;
 
+        // Client profile columns added after the first release; existing
+        // rows load with them empty
+        add_missing_columns(&conn, "clients", &[
+            ("contacts", "TEXT"),
+            ("industry", "TEXT"),
+            ("company_size", "TEXT"),
+            ("engagement_start", "TEXT"),
+            ("engagement_end", "TEXT"),
+            ("default_framework", "TEXT"),
+        ])?;
+
         tracing::info!("Database schema initialized");
         Ok(())
     }
 }
 
+/// Add columns a table created by an older release doesn't have yet
+pub(crate) fn add_missing_columns(conn: &Connection, table: &str, columns: &[(&str, &str)]) -> OptioResult<()> {
+    let existing = conn
+        .prepare(&format!("PRAGMA table_info({})", table))?
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (name, definition) in columns {
+        if !existing.iter().any(|c| c == name) {
+            conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, name, definition))?;
+            tracing::info!("Added column {}.{}", table, name);
+        }
+    }
+    Ok(())
+}
+
 /// Client profile stored in the database
+///
+/// Fields after `updated_at` were added later and are empty for older rows
+/// and bundles.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Client {
     pub id: String,
@@ -100,6 +186,19 @@ pub struct Client {
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub contacts: Vec<ClientContact>,
+    #[serde(default)]
+    pub industry: Option<IndustrySector>,
+    #[serde(default)]
+    pub company_size: Option<CompanySize>,
+    #[serde(default)]
+    pub engagement_start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub engagement_end: Option<DateTime<Utc>>,
+    /// Framework new assessments default to
+    #[serde(default)]
+    pub default_framework: Option<Framework>,
 }
 
 impl Client {
@@ -113,8 +212,184 @@ impl Client {
             notes,
             created_at: now,
             updated_at: now,
+            contacts: vec![],
+            industry: None,
+            company_size: None,
+            engagement_start: None,
+            engagement_end: None,
+            default_framework: None,
         }
     }
+
+    /// The contact marked primary, otherwise the first one
+    pub fn primary_contact(&self) -> Option<&ClientContact> {
+        self.contacts.iter().find(|c| c.primary).or_else(|| self.contacts.first())
+    }
+
+    /// Framework for new assessments: the client's preference, then its
+    /// industry's usual framework
+    pub fn preferred_framework(&self) -> Option<Framework> {
+        self.default_framework.or_else(|| self.industry.map(|i| i.default_framework()))
+    }
+}
+
+/// A person at the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientContact {
+    pub name: String,
+    pub role: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    /// Named on report cover pages
+    #[serde(default)]
+    pub primary: bool,
+}
+
+impl ClientContact {
+    /// "Name, Role (email)" for cover pages
+    pub fn display_line(&self) -> String {
+        let mut line = self.name.clone();
+        if let Some(role) = self.role.as_deref().filter(|r| !r.is_empty()) {
+            line.push_str(&format!(", {}", role));
+        }
+        if let Some(email) = self.email.as_deref().filter(|e| !e.is_empty()) {
+            line.push_str(&format!(" ({})", email));
+        }
+        line
+    }
+}
+
+/// Industry sector a client operates in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IndustrySector {
+    Finance,
+    Healthcare,
+    Government,
+    Education,
+    Retail,
+    Manufacturing,
+    Technology,
+    Energy,
+    Legal,
+    NonProfit,
+    Other,
+}
+
+impl IndustrySector {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            IndustrySector::Finance => "Financial Services",
+            IndustrySector::Healthcare => "Healthcare",
+            IndustrySector::Government => "Government",
+            IndustrySector::Education => "Education",
+            IndustrySector::Retail => "Retail",
+            IndustrySector::Manufacturing => "Manufacturing",
+            IndustrySector::Technology => "Technology",
+            IndustrySector::Energy => "Energy & Utilities",
+            IndustrySector::Legal => "Legal",
+            IndustrySector::NonProfit => "Non-Profit",
+            IndustrySector::Other => "Other",
+        }
+    }
+
+    /// Framework clients in this sector are most often assessed against
+    pub fn default_framework(&self) -> Framework {
+        match self {
+            IndustrySector::Finance | IndustrySector::Technology => Framework::Soc2TypeII,
+            IndustrySector::Retail => Framework::Gdpr,
+            IndustrySector::Manufacturing | IndustrySector::Legal => Framework::Iso27001,
+            IndustrySector::Healthcare
+            | IndustrySector::Government
+            | IndustrySector::Education
+            | IndustrySector::Energy
+            | IndustrySector::NonProfit
+            | IndustrySector::Other => Framework::NistCsf2,
+        }
+    }
+}
+
+/// Client headcount band
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompanySize {
+    /// 1-49 employees
+    Small,
+    /// 50-249 employees
+    Medium,
+    /// 250-999 employees
+    Large,
+    /// 1,000+ employees
+    Enterprise,
+}
+
+impl CompanySize {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CompanySize::Small => "1-49 employees",
+            CompanySize::Medium => "50-249 employees",
+            CompanySize::Large => "250-999 employees",
+            CompanySize::Enterprise => "1,000+ employees",
+        }
+    }
+}
+
+fn parse_industry(s: &str) -> OptioResult<IndustrySector> {
+    match s {
+        "Finance" => Ok(IndustrySector::Finance),
+        "Healthcare" => Ok(IndustrySector::Healthcare),
+        "Government" => Ok(IndustrySector::Government),
+        "Education" => Ok(IndustrySector::Education),
+        "Retail" => Ok(IndustrySector::Retail),
+        "Manufacturing" => Ok(IndustrySector::Manufacturing),
+        "Technology" => Ok(IndustrySector::Technology),
+        "Energy" => Ok(IndustrySector::Energy),
+        "Legal" => Ok(IndustrySector::Legal),
+        "NonProfit" => Ok(IndustrySector::NonProfit),
+        "Other" => Ok(IndustrySector::Other),
+        _ => Err(OptioError::Database(format!("Unknown industry: {}", s))),
+    }
+}
+
+fn parse_company_size(s: &str) -> OptioResult<CompanySize> {
+    match s {
+        "Small" => Ok(CompanySize::Small),
+        "Medium" => Ok(CompanySize::Medium),
+        "Large" => Ok(CompanySize::Large),
+        "Enterprise" => Ok(CompanySize::Enterprise),
+        _ => Err(OptioError::Database(format!("Unknown company size: {}", s))),
+    }
+}
+
+fn parse_timestamp(s: &str) -> OptioResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| OptioError::Database(e.to_string()))
+}
+
+const CLIENT_COLUMNS: &str = "id, name, target_subnet, contact_email, notes, created_at, updated_at, \
+    contacts, industry, company_size, engagement_start, engagement_end, default_framework";
+
+fn parse_client_row(row: &rusqlite::Row) -> OptioResult<Client> {
+    Ok(Client {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        target_subnet: row.get(2)?,
+        contact_email: row.get(3)?,
+        notes: row.get(4)?,
+        created_at: parse_timestamp(&row.get::<_, String>(5)?)?,
+        updated_at: parse_timestamp(&row.get::<_, String>(6)?)?,
+        contacts: match row.get::<_, Option<String>>(7)? {
+            Some(json) => serde_json::from_str(&json)?,
+            None => vec![],
+        },
+        industry: row.get::<_, Option<String>>(8)?.as_deref().map(parse_industry).transpose()?,
+        company_size: row.get::<_, Option<String>>(9)?.as_deref().map(parse_company_size).transpose()?,
+        engagement_start: row.get::<_, Option<String>>(10)?.as_deref().map(parse_timestamp).transpose()?,
+        engagement_end: row.get::<_, Option<String>>(11)?.as_deref().map(parse_timestamp).transpose()?,
+        default_framework: row.get::<_, Option<String>>(12)?.as_deref().map(parse_framework).transpose()?,
+    })
 }
 
 /// Client repository for CRUD operations
@@ -131,7 +406,7 @@ impl<'a> ClientRepository<'a> {
         let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
 
         conn.execute(
-            "INSERT INTO clients (id, name, target_subnet, contact_email, notes, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            &format!("INSERT INTO clients ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)", CLIENT_COLUMNS),
             params![
                 client.id,
                 client.name,
@@ -140,6 +415,12 @@ impl<'a> ClientRepository<'a> {
                 client.notes,
                 client.created_at.to_rfc3339(),
                 client.updated_at.to_rfc3339(),
+                serde_json::to_string(&client.contacts)?,
+                client.industry.map(|i| format!("{:?}", i)),
+                client.company_size.map(|s| format!("{:?}", s)),
+                client.engagement_start.map(|d| d.to_rfc3339()),
+                client.engagement_end.map(|d| d.to_rfc3339()),
+                client.default_framework.map(|f| format!("{:?}", f)),
             ],
         )?;
 
@@ -150,26 +431,12 @@ impl<'a> ClientRepository<'a> {
     pub fn get(&self, id: &str) -> OptioResult<Option<Client>> {
         let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
 
-        let mut stmt = conn.prepare(
-            "SELECT id, name, target_subnet, contact_email, notes, created_at, updated_at FROM clients WHERE id = ?1"
-        )?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM clients WHERE id = ?1", CLIENT_COLUMNS))?;
 
         let mut rows = stmt.query(params![id])?;
 
         if let Some(row) = rows.next()? {
-            Ok(Some(Client {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                target_subnet: row.get(2)?,
-                contact_email: row.get(3)?,
-                notes: row.get(4)?,
-                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
-                    .map_err(|e| OptioError::Database(e.to_string()))?
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
-                    .map_err(|e| OptioError::Database(e.to_string()))?
-                    .with_timezone(&Utc),
-            }))
+            Ok(Some(parse_client_row(row)?))
         } else {
             Ok(None)
         }
@@ -178,27 +445,11 @@ impl<'a> ClientRepository<'a> {
     pub fn list(&self) -> OptioResult<Vec<Client>> {
         let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
 
-        let mut stmt = conn.prepare(
-            "SELECT id, name, target_subnet, contact_email, notes, created_at, updated_at FROM clients ORDER BY name"
-        )?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM clients ORDER BY name", CLIENT_COLUMNS))?;
 
-        let clients = stmt.query_map([], |row| {
-            Ok(Client {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                target_subnet: row.get(2)?,
-                contact_email: row.get(3)?,
-                notes: row.get(4)?,
-                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5).unwrap_or_default())
-                    .unwrap_or_default()
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6).unwrap_or_default())
-                    .unwrap_or_default()
-                    .with_timezone(&Utc),
-            })
-        })?
+        let clients = stmt.query_map([], |row| Ok(parse_client_row(row)))?
         .filter_map(|r| r.ok())
-        .collect();
+        .collect::<OptioResult<Vec<_>>>()?;
 
         Ok(clients)
     }
@@ -207,7 +458,10 @@ impl<'a> ClientRepository<'a> {
         let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
 
         let updated = conn.execute(
-            "UPDATE clients SET name = ?2, target_subnet = ?3, contact_email = ?4, notes = ?5, updated_at = ?6 WHERE id = ?1",
+            "UPDATE clients SET name = ?2, target_subnet = ?3, contact_email = ?4, notes = ?5, updated_at = ?6,
+                contacts = ?7, industry = ?8, company_size = ?9, engagement_start = ?10, engagement_end = ?11,
+                default_framework = ?12
+             WHERE id = ?1",
             params![
                 client.id,
                 client.name,
@@ -215,6 +469,12 @@ impl<'a> ClientRepository<'a> {
                 client.contact_email,
                 client.notes,
                 Utc::now().to_rfc3339(),
+                serde_json::to_string(&client.contacts)?,
+                client.industry.map(|i| format!("{:?}", i)),
+                client.company_size.map(|s| format!("{:?}", s)),
+                client.engagement_start.map(|d| d.to_rfc3339()),
+                client.engagement_end.map(|d| d.to_rfc3339()),
+                client.default_framework.map(|f| format!("{:?}", f)),
             ],
         )?;
 
@@ -226,10 +486,150 @@ impl<'a> ClientRepository<'a> {
         let deleted = conn.execute("DELETE FROM clients WHERE id = ?1", params![id])?;
         Ok(deleted > 0)
     }
+
+    /// Count the records that reference a client, to show before deleting it
+    pub fn dependencies(&self, id: &str) -> OptioResult<ClientDependencies> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let count = |table: &str| count_client_rows(&conn, table, id, "1");
+
+        Ok(ClientDependencies {
+            assessments: count("assessments")?,
+            assets: count("assets")?,
+            scans: count("scans")?,
+            risks: count("risks")?,
+            reports: count("reports")?,
+            generated_scripts: count("generated_scripts")?,
+        })
+    }
+
+    /// Dashboard counts for a client
+    pub fn overview(&self, id: &str) -> OptioResult<ClientOverviewCounts> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        Ok(ClientOverviewCounts {
+            assessments: count_client_rows(&conn, "assessments", id, "1")?,
+            active_assessments: count_client_rows(&conn, "assessments", id, "status NOT IN ('Completed', 'Archived')")?,
+            assets: count_client_rows(&conn, "assets", id, "1")?,
+            reports: count_client_rows(&conn, "reports", id, "1")?,
+            open_risks: count_client_rows(&conn, "risks", id, "status != 'Closed'")?,
+        })
+    }
 }
 
-/// Initialize the database on application startup
-pub async fn initialize(app_handle: &AppHandle) -> OptioResult<()> {
+/// Count a client's rows in `table` matching the SQL `condition`
+fn count_client_rows(conn: &Connection, table: &str, client_id: &str, condition: &str) -> OptioResult<usize> {
+    let count: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM {} WHERE client_id = ?1 AND {}", table, condition),
+        params![client_id],
+        |row| row.get(0),
+    )?;
+    Ok(count as usize)
+}
+
+/// Record counts for a client's dashboard
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientOverviewCounts {
+    pub assessments: usize,
+    /// Assessments not yet completed or archived
+    pub active_assessments: usize,
+    pub assets: usize,
+    pub reports: usize,
+    /// Register risks that aren't closed
+    pub open_risks: usize,
+}
+
+/// Records that reference a client
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDependencies {
+    pub assessments: usize,
+    pub assets: usize,
+    pub scans: usize,
+    pub risks: usize,
+    pub reports: usize,
+    /// Provisioning scripts generated for the client
+    pub generated_scripts: usize,
+}
+
+impl ClientDependencies {
+    pub fn total(&self) -> usize {
+        self.assessments + self.assets + self.scans + self.risks + self.reports + self.generated_scripts
+    }
+}
+
+/// Per-client consultant IP preferences
+pub struct ConsultantIpRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ConsultantIpRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ConsultantIpRepository { db }
+    }
+
+    /// Preferred consultant IP for a client, if one was chosen
+    pub fn get(&self, client_id: &str) -> OptioResult<Option<String>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare("SELECT ip FROM consultant_ip_preferences WHERE client_id = ?1")?;
+        let mut rows = stmt.query(params![client_id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Set (or replace) the preferred consultant IP for a client
+    pub fn set(&self, client_id: &str, ip: &str) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO consultant_ip_preferences (client_id, ip, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(client_id) DO UPDATE SET ip = excluded.ip, updated_at = excluded.updated_at",
+            params![client_id, ip, Utc::now().to_rfc3339()],
+        )?;
+
+        tracing::debug!("Set consultant IP for client {}: {}", client_id, ip);
+        Ok(())
+    }
+
+    /// Interface whose address is suggested by default, if one was chosen
+    pub fn preferred_interface(&self) -> OptioResult<Option<String>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare("SELECT value FROM consultant_settings WHERE key = 'preferred_interface'")?;
+        let mut rows = stmt.query([])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Set the preferred interface, or clear it with `None`
+    pub fn set_preferred_interface(&self, name: Option<&str>) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        match name {
+            Some(name) => conn.execute(
+                "INSERT INTO consultant_settings (key, value, updated_at) VALUES ('preferred_interface', ?1, ?2)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                params![name, Utc::now().to_rfc3339()],
+            )?,
+            None => conn.execute("DELETE FROM consultant_settings WHERE key = 'preferred_interface'", [])?,
+        };
+
+        tracing::debug!("Set preferred consultant interface: {:?}", name);
+        Ok(())
+    }
+}
+
+/// Where the database lives, creating the app data directory if needed
+pub fn database_path(app_handle: &AppHandle) -> OptioResult<PathBuf> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
@@ -238,17 +638,177 @@ pub async fn initialize(app_handle: &AppHandle) -> OptioResult<()> {
     // Ensure the directory exists
     std::fs::create_dir_all(&app_data_dir)?;
 
-    let db_path = app_data_dir.join("optio.db");
-    tracing::info!("Database path: {:?}", db_path);
+    Ok(app_data_dir.join("optio.db"))
+}
 
-    let db = Database::open(&db_path)?;
+/// Bring every table up to the current schema and record [`SCHEMA_VERSION`]
+pub fn migrate(db: &Database) -> OptioResult<()> {
     db.init_schema()?;
 
+    // Initialize the audit log hash chain
+    crate::audit::init_audit_schema(db)?;
+
     // Initialize GRC schema
-    crate::grc::repository::init_grc_schema(&db)?;
+    crate::grc::repository::init_grc_schema(db)?;
+
+    // Initialize network schema
+    crate::network::repository::init_network_schema(db)?;
+
+    // Initialize reporting schema
+    crate::reporting::repository::init_reporting_schema(db)?;
+
+    // Initialize onboarding schema
+    crate::onboarding::init_onboarding_schema(db)?;
+
+    // Initialize activity log schema
+    crate::activity::init_activity_schema(db)?;
+
+    // Initialize risk register schema
+    crate::grc::risk::init_risk_schema(db)?;
+
+    // Initialize records of processing schema
+    crate::grc::ropa::init_ropa_schema(db)?;
+
+    // Initialize remediation plan schema
+    crate::grc::remediation::init_remediation_schema(db)?;
+
+    // Initialize compliance trend schema
+    crate::grc::trend::init_compliance_trend_schema(db)?;
+
+    // Initialize assessment snapshot schema
+    crate::grc::snapshot::init_assessment_snapshot_schema(db)?;
+
+    // Initialize pricing profile schema
+    crate::infrastructure::pricing::init_pricing_schema(db)?;
+
+    // Initialize cloud readiness schema
+    crate::infrastructure::cloud_readiness::init_cloud_readiness_schema(db)?;
+
+    // Initialize K8s hardening audit schema
+    crate::infrastructure::k8s_hardening::init_k8s_audit_schema(db)?;
+
+    // Initialize finding library schema
+    crate::findings::library::init_findings_schema(db)?;
+
+    // Initialize report branding schema
+    crate::reporting::branding::init_branding_schema(db)?;
+
+    // Initialize script history schema
+    crate::factory::history::init_script_history_schema(db)?;
+
+    // Initialize agent token schema
+    crate::factory::tokens::init_agent_token_schema(db)?;
+
+    // Initialize agent listener schema
+    crate::network::agent_listener::init_agent_schema(db)?;
+
+    // Initialize the search index, once every table it covers exists
+    crate::search::init_search_schema(db)?;
+
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    conn.execute_batch(&format!("PRAGMA user_version = {};", SCHEMA_VERSION))?;
+    Ok(())
+}
+
+/// Initialize the database on application startup
+///
+/// An encrypted database needs its key; without one this fails with
+/// `DatabaseLocked` and the database is opened later by unlocking it.
+pub async fn initialize(app_handle: &AppHandle, key: Option<&DatabaseKey>) -> OptioResult<()> {
+    let db_path = database_path(app_handle)?;
+    tracing::info!("Database path: {:?}", db_path);
+
+    // An encrypted database waits for its passphrase
+    let db = if is_encrypted(&db_path) {
+        let key = key.ok_or_else(|| OptioError::DatabaseLocked("A passphrase is required to open the database".to_string()))?;
+        Database::open_encrypted(&db_path, key)?
+    } else {
+        Database::open(&db_path)?
+    };
+
+    // Back up before migrating a database from another schema version
+    match db.schema_version()? {
+        Some(version) if version != SCHEMA_VERSION => {
+            tracing::info!("Migrating the database from schema version {} to {}", version, SCHEMA_VERSION);
+            crate::db_backup::create_backup(&db, Some("pre-migration"), key)?;
+        }
+        _ => {}
+    }
+    migrate(&db)?;
 
     // Store database in app state
     app_handle.manage(db);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_db() -> Database {
+        Database { conn: Mutex::new(Connection::open_in_memory().unwrap()) }
+    }
+
+    #[test]
+    fn test_existing_clients_load_after_migration() {
+        let db = memory_db();
+        db.conn.lock().unwrap().execute_batch(
+            "CREATE TABLE clients (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                target_subnet TEXT,
+                contact_email TEXT,
+                notes TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            INSERT INTO clients (id, name, created_at, updated_at)
+            VALUES ('client-1', 'Acme', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');",
+        ).unwrap();
+
+        db.init_schema().unwrap();
+        // Running it again finds the columns already there
+        db.init_schema().unwrap();
+
+        let client = ClientRepository::new(&db).get("client-1").unwrap().unwrap();
+        assert_eq!(client.name, "Acme");
+        assert!(client.contacts.is_empty());
+        assert!(client.industry.is_none() && client.preferred_framework().is_none());
+    }
+
+    #[test]
+    fn test_client_profile_round_trip() {
+        let db = memory_db();
+        db.init_schema().unwrap();
+        let repo = ClientRepository::new(&db);
+
+        let mut client = Client::new("Acme".to_string(), None, None, None);
+        client.industry = Some(IndustrySector::Finance);
+        client.company_size = Some(CompanySize::Large);
+        client.engagement_start = Some(Utc::now());
+        client.contacts = vec![
+            ClientContact { name: "Sam Lee".to_string(), role: None, email: None, phone: None, primary: false },
+            ClientContact {
+                name: "Jane Doe".to_string(),
+                role: Some("CISO".to_string()),
+                email: Some("jane@acme.example".to_string()),
+                phone: None,
+                primary: true,
+            },
+        ];
+        repo.create(&client).unwrap();
+
+        let mut loaded = repo.get(&client.id).unwrap().unwrap();
+        assert_eq!(loaded.contacts, client.contacts);
+        assert_eq!(loaded.company_size, Some(CompanySize::Large));
+        assert_eq!(loaded.primary_contact().unwrap().display_line(), "Jane Doe, CISO (jane@acme.example)");
+        // The industry default applies until a preference is set
+        assert_eq!(loaded.preferred_framework(), Some(Framework::Soc2TypeII));
+
+        loaded.default_framework = Some(Framework::Iso27001);
+        repo.update(&loaded).unwrap();
+        let loaded = repo.list().unwrap().remove(0);
+        assert_eq!(loaded.preferred_framework(), Some(Framework::Iso27001));
+    }
+}
@@ -0,0 +1,511 @@
+//! Database Backups
+//!
+//! Point-in-time copies of the local database, kept in a `backups` directory
+//! beside it. Copies are taken with SQLite's online backup API, so they are
+//! consistent while the app is running, and each has a JSON manifest with
+//! the app version, schema version and row count of every table.
+//!
+//! An encrypted database is backed up encrypted under its own key, so a
+//! backup is never more readable than the database it came from. Turning
+//! encryption on encrypts the backups taken before it under the new key;
+//! any that can't be encrypted are deleted rather than left readable.
+
+use crate::db::{self, Database};
+use crate::db_encryption::{
+    connection_path, export_encrypted, is_encrypted, open_with_key, remove_if_exists, sibling_path, DatabaseKey,
+};
+use crate::error::{OptioError, OptioResult};
+use chrono::{DateTime, Utc};
+use rusqlite::backup::Backup;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Pages copied per backup step
+const PAGES_PER_STEP: std::os::raw::c_int = 256;
+
+/// Rows in one table of a backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRowCount {
+    pub table: String,
+    pub rows: i64,
+}
+
+/// What a backup holds, written beside it as `<id>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub id: String,
+    /// Why it was taken, e.g. "pre-migration"
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub app_version: String,
+    pub schema_version: i32,
+    pub encrypted: bool,
+    pub size_bytes: u64,
+    pub tables: Vec<TableRowCount>,
+}
+
+/// A restored backup and the copy of the database it replaced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRestore {
+    pub restored: BackupManifest,
+    /// Backup of the database as it was before the restore
+    pub safety_backup: BackupManifest,
+}
+
+/// Where backups of the database at `db_path` are kept
+pub fn backups_dir(db_path: &Path) -> PathBuf {
+    db_path.with_file_name("backups")
+}
+
+fn backup_file(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.db", id))
+}
+
+fn manifest_file(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+/// Ids are generated; anything else could point outside the backups directory
+fn validate_backup_id(id: &str) -> OptioResult<()> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(OptioError::Validation(format!("Invalid backup id: {}", id)));
+    }
+    Ok(())
+}
+
+/// `20240301-101500-pre-migration-1a2b3c4d`
+fn new_backup_id(created_at: DateTime<Utc>, label: Option<&str>) -> String {
+    let slug = label
+        .unwrap_or("manual")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .take(6)
+        .collect::<Vec<_>>()
+        .join("-");
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    if slug.is_empty() {
+        format!("{}-{}", created_at.format("%Y%m%d-%H%M%S"), suffix)
+    } else {
+        format!("{}-{}-{}", created_at.format("%Y%m%d-%H%M%S"), slug, suffix)
+    }
+}
+
+/// The key an encrypted database needs, or None for a plaintext one
+fn database_key<'k>(path: &Path, key: Option<&'k DatabaseKey>) -> OptioResult<Option<&'k DatabaseKey>> {
+    if !is_encrypted(path) {
+        return Ok(None);
+    }
+    key.map(Some)
+        .ok_or_else(|| OptioError::DatabaseLocked("Unlock the database before using its backups".to_string()))
+}
+
+fn open_copy(path: &Path, key: Option<&DatabaseKey>) -> OptioResult<Connection> {
+    match key {
+        Some(key) => open_with_key(path, key),
+        None => Ok(Connection::open(path)?),
+    }
+}
+
+/// Row counts of the ordinary tables, leaving out SQLite's own and the
+/// search index's shadow tables
+fn table_row_counts(conn: &Connection) -> OptioResult<Vec<TableRowCount>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM pragma_table_list
+         WHERE schema = 'main' AND type = 'table' AND name NOT LIKE 'sqlite_%'
+         ORDER BY name",
+    )?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    tables
+        .into_iter()
+        .map(|table| {
+            let rows = conn.query_row(&format!("SELECT count(*) FROM \"{}\"", table.replace('"', "\"\"")), [], |row| row.get(0))?;
+            Ok(TableRowCount { table, rows })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Creating and Listing
+// ============================================================================
+
+/// Back up the database, labelling the copy with why it was taken
+///
+/// `key` is the session key, needed when the database is encrypted.
+pub fn create_backup(db: &Database, label: Option<&str>, key: Option<&DatabaseKey>) -> OptioResult<BackupManifest> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    let path = connection_path(&conn)?;
+    let key = database_key(&path, key)?;
+    let label = label.map(str::trim).filter(|l| !l.is_empty());
+
+    let dir = backups_dir(&path);
+    std::fs::create_dir_all(&dir)?;
+    let created_at = Utc::now();
+    let id = new_backup_id(created_at, label);
+    let file = backup_file(&dir, &id);
+
+    let written = write_backup(&conn, &file, key).and_then(|(schema_version, tables)| {
+        let manifest = BackupManifest {
+            id: id.clone(),
+            label: label.map(String::from),
+            created_at,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            encrypted: key.is_some(),
+            size_bytes: std::fs::metadata(&file)?.len(),
+            tables,
+        };
+        std::fs::write(manifest_file(&dir, &id), serde_json::to_string_pretty(&manifest)?)?;
+        Ok(manifest)
+    });
+
+    match written {
+        Ok(manifest) => {
+            tracing::info!("Database backup created: {} ({} bytes)", manifest.id, manifest.size_bytes);
+            Ok(manifest)
+        }
+        Err(e) => {
+            tracing::error!("Database backup failed: {}", e);
+            let _ = std::fs::remove_file(&file);
+            let _ = std::fs::remove_file(manifest_file(&dir, &id));
+            Err(e)
+        }
+    }
+}
+
+/// Copy the open database to `file`, returning the copy's schema version and row counts
+fn write_backup(conn: &Connection, file: &Path, key: Option<&DatabaseKey>) -> OptioResult<(i32, Vec<TableRowCount>)> {
+    let mut copy = open_copy(file, key)?;
+    Backup::new(conn, &mut copy)?.run_to_completion(PAGES_PER_STEP, Duration::ZERO, None)?;
+
+    let schema_version = copy.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let tables = table_row_counts(&copy)?;
+    Ok((schema_version, tables))
+}
+
+/// Backups of the database at `db_path`, newest first
+pub fn list_backups(db_path: &Path) -> OptioResult<Vec<BackupManifest>> {
+    let dir = backups_dir(db_path);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let manifest = std::fs::read_to_string(&path)
+            .map_err(OptioError::from)
+            .and_then(|json| Ok(serde_json::from_str::<BackupManifest>(&json)?));
+        match manifest {
+            Ok(manifest) if backup_file(&dir, &manifest.id).exists() => backups.push(manifest),
+            Ok(manifest) => tracing::warn!("Backup {} is missing its database file", manifest.id),
+            Err(e) => tracing::warn!("Skipping unreadable backup manifest {:?}: {}", path, e),
+        }
+    }
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+fn load_manifest(dir: &Path, id: &str) -> OptioResult<BackupManifest> {
+    validate_backup_id(id)?;
+    let file = manifest_file(dir, id);
+    if !file.exists() || !backup_file(dir, id).exists() {
+        return Err(OptioError::Validation(format!("Backup not found: {}", id)));
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(file)?)?)
+}
+
+/// Encrypt the plaintext backups of the database at `db_path` under `key`,
+/// once the database itself has been encrypted
+///
+/// Each backup is exported into an encrypted copy that must pass an
+/// integrity check before it replaces the plaintext file. A backup that
+/// can't be encrypted is deleted, so no plaintext copy outlives encryption
+/// being turned on. Returns the backups that were encrypted.
+pub fn encrypt_plaintext_backups(db_path: &Path, key: &DatabaseKey) -> Vec<BackupManifest> {
+    let dir = backups_dir(db_path);
+    let plaintext = match list_backups(db_path) {
+        Ok(backups) => backups.into_iter().filter(|manifest| !manifest.encrypted),
+        Err(e) => {
+            tracing::error!("Could not list backups to encrypt them: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut encrypted = Vec::new();
+    for manifest in plaintext {
+        match encrypt_backup(&dir, manifest.clone(), key) {
+            Ok(manifest) => encrypted.push(manifest),
+            Err(e) => {
+                tracing::warn!("Deleting plaintext backup {} that could not be encrypted: {}", manifest.id, e);
+                let deleted = remove_if_exists(&backup_file(&dir, &manifest.id))
+                    .and_then(|_| remove_if_exists(&manifest_file(&dir, &manifest.id)));
+                if let Err(e) = deleted {
+                    tracing::error!("Plaintext backup {} is still on disk: {}", manifest.id, e);
+                }
+            }
+        }
+    }
+
+    if !encrypted.is_empty() {
+        tracing::info!("Encrypted {} database backups taken before encryption", encrypted.len());
+    }
+    encrypted
+}
+
+fn encrypt_backup(dir: &Path, mut manifest: BackupManifest, key: &DatabaseKey) -> OptioResult<BackupManifest> {
+    let file = backup_file(dir, &manifest.id);
+    let staging = dir.join(format!("{}.encrypting", manifest.id));
+    remove_if_exists(&staging)?;
+
+    let exported = Connection::open(&file)
+        .map_err(OptioError::from)
+        .and_then(|conn| export_encrypted(&conn, &staging, key))
+        .and_then(|_| check_integrity(&staging, Some(key)))
+        .and_then(|_| std::fs::rename(&staging, &file).map_err(OptioError::from));
+    if let Err(e) = exported {
+        let _ = std::fs::remove_file(&staging);
+        return Err(e);
+    }
+
+    manifest.encrypted = true;
+    manifest.size_bytes = std::fs::metadata(&file)?.len();
+    std::fs::write(manifest_file(dir, &manifest.id), serde_json::to_string_pretty(&manifest)?)?;
+    Ok(manifest)
+}
+
+// ============================================================================
+// Restoring and Pruning
+// ============================================================================
+
+/// Replace the database with a backup
+///
+/// The backup is copied aside and must pass `PRAGMA integrity_check` before
+/// anything is touched. The current database is then backed up as a safety
+/// copy, the files are swapped, and the restored database is migrated to
+/// the current schema. If the swap fails the current database is reopened.
+pub fn restore_backup(db: &Database, backup_id: &str, key: Option<&DatabaseKey>) -> OptioResult<BackupRestore> {
+    let path = {
+        let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        connection_path(&conn)?
+    };
+    let key = database_key(&path, key)?;
+    let dir = backups_dir(&path);
+    let restored = load_manifest(&dir, backup_id)?;
+    if restored.encrypted != key.is_some() {
+        return Err(OptioError::Validation(if restored.encrypted {
+            "This backup is encrypted and the database no longer is".to_string()
+        } else {
+            "This backup was taken before the database was encrypted".to_string()
+        }));
+    }
+
+    let staging = sibling_path(&path, "restoring");
+    std::fs::copy(backup_file(&dir, backup_id), &staging)?;
+    if let Err(e) = check_integrity(&staging, key) {
+        tracing::error!("Backup {} failed its integrity check: {}", backup_id, e);
+        let _ = std::fs::remove_file(&staging);
+        return Err(e);
+    }
+
+    let safety_backup = match create_backup(db, Some("pre-restore"), key) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let _ = std::fs::remove_file(&staging);
+            return Err(e);
+        }
+    };
+
+    swap_in(db, &path, &staging, key).map_err(|e| {
+        tracing::error!("Restoring backup {} failed: {}", backup_id, e);
+        e
+    })?;
+    db::migrate(db)?;
+
+    tracing::info!("Restored database backup {} (safety copy {})", backup_id, safety_backup.id);
+    Ok(BackupRestore { restored, safety_backup })
+}
+
+fn check_integrity(file: &Path, key: Option<&DatabaseKey>) -> OptioResult<()> {
+    let conn = open_copy(file, key)?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let problems = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    if problems.len() != 1 || problems[0] != "ok" {
+        return Err(OptioError::Database(format!(
+            "Backup failed its integrity check: {}",
+            problems.into_iter().take(5).collect::<Vec<_>>().join("; ")
+        )));
+    }
+    Ok(())
+}
+
+/// Close the database, move `staging` over it and reopen it
+fn swap_in(db: &Database, path: &Path, staging: &Path, key: Option<&DatabaseKey>) -> OptioResult<()> {
+    let mut conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    let current = std::mem::replace(&mut *conn, Connection::open_in_memory()?);
+    current.close().map_err(|(_, e)| OptioError::from(e))?;
+
+    let open = |path: &Path| match key {
+        Some(key) => Database::open_encrypted(path, key),
+        None => Database::open(&path.to_path_buf()),
+    };
+
+    // Closing checkpoints the WAL; leftovers would be replayed into the restored copy
+    let swapped = ["wal", "shm"]
+        .iter()
+        .try_for_each(|suffix| remove_if_exists(&sibling_path(path, suffix)))
+        .and_then(|_| std::fs::rename(staging, path).map_err(OptioError::from))
+        .and_then(|_| open(path));
+
+    match swapped {
+        Ok(restored) => {
+            *conn = restored.into_connection();
+            Ok(())
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(staging);
+            *conn = open(path)?.into_connection();
+            Err(e)
+        }
+    }
+}
+
+/// Delete all but the newest `keep_last` backups, returning the deleted ones
+pub fn prune_backups(db_path: &Path, keep_last: usize) -> OptioResult<Vec<BackupManifest>> {
+    if keep_last == 0 {
+        return Err(OptioError::Validation("Keep at least one backup".to_string()));
+    }
+
+    let dir = backups_dir(db_path);
+    let pruned: Vec<BackupManifest> = list_backups(db_path)?.into_iter().skip(keep_last).collect();
+    for manifest in &pruned {
+        remove_if_exists(&backup_file(&dir, &manifest.id))?;
+        remove_if_exists(&manifest_file(&dir, &manifest.id))?;
+    }
+
+    if !pruned.is_empty() {
+        tracing::info!("Pruned {} database backups, kept {}", pruned.len(), keep_last);
+    }
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_database() -> (PathBuf, Database) {
+        let dir = std::env::temp_dir().join(format!("optio-backup-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("optio.db");
+        let db = Database::open(&path).unwrap();
+        db.conn
+            .lock()
+            .unwrap()
+            .execute_batch("CREATE TABLE notes (body TEXT); INSERT INTO notes VALUES ('first'), ('second');")
+            .unwrap();
+        (dir, db)
+    }
+
+    fn note_count(db: &Database) -> i64 {
+        db.conn.lock().unwrap().query_row("SELECT count(*) FROM notes", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_backup_restore_and_prune() {
+        let (dir, db) = temp_database();
+        let path = dir.join("optio.db");
+
+        let backup = create_backup(&db, Some("Before cleanup!"), None).unwrap();
+        assert!(backup.id.contains("-before-cleanup-"));
+        assert!(!backup.encrypted);
+        assert_eq!(backup.tables, vec![TableRowCount { table: "notes".to_string(), rows: 2 }]);
+
+        db.conn.lock().unwrap().execute("DELETE FROM notes", []).unwrap();
+        assert_eq!(note_count(&db), 0);
+
+        let restore = restore_backup(&db, &backup.id, None).unwrap();
+        assert_eq!(note_count(&db), 2);
+        assert_eq!(restore.safety_backup.label.as_deref(), Some("pre-restore"));
+        assert!(restore.safety_backup.tables.contains(&TableRowCount { table: "notes".to_string(), rows: 0 }));
+        // The restored copy is migrated to the current schema
+        let version: i32 = db.conn.lock().unwrap().query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, db::SCHEMA_VERSION);
+
+        assert_eq!(list_backups(&path).unwrap().len(), 2);
+        let pruned = prune_backups(&path, 1).unwrap();
+        assert_eq!(pruned.len(), 1);
+        let remaining = list_backups(&path).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(!backup_file(&backups_dir(&path), &pruned[0].id).exists());
+        assert!(prune_backups(&path, 0).is_err());
+
+        drop(db);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_backup_is_not_restored() {
+        let (dir, db) = temp_database();
+        let path = dir.join("optio.db");
+
+        let backup = create_backup(&db, None, None).unwrap();
+        std::fs::write(backup_file(&backups_dir(&path), &backup.id), b"not a database at all").unwrap();
+
+        assert!(restore_backup(&db, &backup.id, None).is_err());
+        assert_eq!(note_count(&db), 2);
+        assert!(!sibling_path(&path, "restoring").exists());
+        // Nothing was replaced, so no safety copy was taken
+        assert_eq!(list_backups(&path).unwrap().len(), 1);
+        assert!(restore_backup(&db, "../optio", None).is_err());
+
+        drop(db);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_encrypting_the_database_encrypts_its_backups() {
+        let (dir, db) = temp_database();
+        let path = dir.join("optio.db");
+
+        let backup = create_backup(&db, Some("before encryption"), None).unwrap();
+        let broken = create_backup(&db, Some("broken"), None).unwrap();
+        std::fs::write(backup_file(&backups_dir(&path), &broken.id), b"not a database at all").unwrap();
+
+        let key = crate::db_encryption::encrypt_existing_database(&db, "long enough passphrase").unwrap();
+
+        // The unreadable one is gone rather than left as plaintext
+        let backups = list_backups(&path).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].id, backup.id);
+        assert!(backups[0].encrypted);
+        assert!(!backup_file(&backups_dir(&path), &broken.id).exists());
+        assert!(!manifest_file(&backups_dir(&path), &broken.id).exists());
+
+        let bytes = std::fs::read(backup_file(&backups_dir(&path), &backup.id)).unwrap();
+        assert!(!bytes.starts_with(b"SQLite format 3\0"));
+        assert!(!bytes.windows(6).any(|w| w == b"second"));
+
+        db.conn.lock().unwrap().execute("DELETE FROM notes", []).unwrap();
+        restore_backup(&db, &backup.id, Some(&key)).unwrap();
+        assert_eq!(note_count(&db), 2);
+
+        drop(db);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,577 @@
+//! Database Encryption at Rest
+//!
+//! The local database can be encrypted with SQLCipher under a random 256-bit
+//! key. Encryption is opt-in: a database stays plaintext until a passphrase
+//! is set, at which point a key is generated and the existing data is
+//! exported into an encrypted copy that replaces the original.
+//!
+//! The key is wrapped with AES-256-GCM under a key derived from the
+//! passphrase with Argon2id. The wrapped key, salt and cost parameters are
+//! kept beside the database in `optio.db.key.json`; that file existing is
+//! what marks the database as encrypted. Neither the passphrase nor the
+//! unwrapped key is ever written to disk; the key is held in memory for the
+//! running session only. Changing the passphrase re-wraps the same key.
+//!
+//! Key files written before keys were wrapped have no wrapped key; their
+//! passphrase-derived key is the database key itself.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rand::{rngs::OsRng, RngCore};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Event emitted when the database becomes ready, locked or fails to open
+pub const DATABASE_STATUS_EVENT: &str = "database-status";
+
+/// Shortest passphrase accepted
+pub const MIN_PASSPHRASE_LENGTH: usize = 12;
+
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// Associated data binding a wrapped key to its purpose
+const WRAP_CONTEXT: &[u8] = b"optio-database-key-v1";
+
+// ============================================================================
+// Key Derivation
+// ============================================================================
+
+/// Argon2id salt and costs the passphrase key is derived with, and the
+/// database key wrapped under it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyDerivation {
+    /// Base64 salt
+    pub salt: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    /// Base64 AES-GCM nonce followed by the encrypted database key; None in
+    /// key files from before keys were wrapped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrapped_key: Option<String>,
+}
+
+impl KeyDerivation {
+    /// Fresh random salt with the default costs (64 MiB, 3 passes)
+    pub fn generate() -> Self {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        KeyDerivation {
+            salt: BASE64.encode(salt),
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+            wrapped_key: None,
+        }
+    }
+
+    /// Wrap `key` under the key this derivation gives for `passphrase`
+    pub fn wrap(mut self, passphrase: &str, key: &DatabaseKey) -> OptioResult<Self> {
+        let wrapping = self.derive(passphrase)?;
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+
+        let sealed = Aes256Gcm::new_from_slice(&wrapping.0)
+            .map_err(|e| OptioError::Encryption(e.to_string()))?
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: &key.0, aad: WRAP_CONTEXT })
+            .map_err(|_| OptioError::Encryption("Failed to wrap the database key".to_string()))?;
+
+        let mut wrapped = nonce.to_vec();
+        wrapped.extend_from_slice(&sealed);
+        self.wrapped_key = Some(BASE64.encode(wrapped));
+        Ok(self)
+    }
+
+    /// The database key for `passphrase`
+    ///
+    /// A wrong passphrase fails with `DatabaseLocked` when the key is
+    /// wrapped; for an older unwrapped key file it only shows when the
+    /// database doesn't open.
+    pub fn unwrap_key(&self, passphrase: &str) -> OptioResult<DatabaseKey> {
+        let wrapping = self.derive(passphrase)?;
+        let wrapped = match &self.wrapped_key {
+            Some(wrapped) => BASE64
+                .decode(wrapped)
+                .map_err(|e| OptioError::Encryption(format!("Invalid wrapped key: {}", e)))?,
+            None => return Ok(wrapping),
+        };
+        if wrapped.len() != NONCE_LENGTH + KEY_LENGTH + 16 {
+            return Err(OptioError::Encryption("Invalid wrapped key".to_string()));
+        }
+
+        let (nonce, sealed) = wrapped.split_at(NONCE_LENGTH);
+        let opened = Aes256Gcm::new_from_slice(&wrapping.0)
+            .map_err(|e| OptioError::Encryption(e.to_string()))?
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad: WRAP_CONTEXT })
+            .map_err(|_| OptioError::DatabaseLocked("Incorrect passphrase".to_string()))?;
+
+        let mut key = [0u8; KEY_LENGTH];
+        key.copy_from_slice(&opened);
+        Ok(DatabaseKey(key))
+    }
+
+    fn derive(&self, passphrase: &str) -> OptioResult<DatabaseKey> {
+        let salt = BASE64
+            .decode(&self.salt)
+            .map_err(|e| OptioError::Encryption(format!("Invalid key salt: {}", e)))?;
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(KEY_LENGTH))
+            .map_err(|e| OptioError::Encryption(format!("Invalid key derivation parameters: {}", e)))?;
+
+        let mut key = [0u8; KEY_LENGTH];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| OptioError::Encryption(format!("Key derivation failed: {}", e)))?;
+        Ok(DatabaseKey(key))
+    }
+
+    /// The derivation for the database at `db_path`; None when it isn't encrypted
+    pub fn load(db_path: &Path) -> OptioResult<Option<Self>> {
+        read_derivation(&key_file_path(db_path))
+    }
+
+    fn save(&self, path: &Path) -> OptioResult<()> {
+        // Write then rename, so a crash never leaves a truncated key file
+        let staging = path.with_extension("json.tmp");
+        std::fs::write(&staging, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&staging, path)?;
+        Ok(())
+    }
+}
+
+fn read_derivation(path: &Path) -> OptioResult<Option<KeyDerivation>> {
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A database key; zeroed when dropped
+#[derive(Clone, PartialEq, Eq)]
+pub struct DatabaseKey([u8; KEY_LENGTH]);
+
+impl DatabaseKey {
+    /// A new random key
+    pub fn generate() -> Self {
+        let mut key = [0u8; KEY_LENGTH];
+        OsRng.fill_bytes(&mut key);
+        DatabaseKey(key)
+    }
+
+    /// SQLCipher raw key literal (`"x'…'"`), which skips SQLCipher's own KDF
+    fn sql_literal(&self) -> String {
+        let hex: String = self.0.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("\"x'{}'\"", hex)
+    }
+}
+
+impl Drop for DatabaseKey {
+    fn drop(&mut self) {
+        self.0.iter_mut().for_each(|b| *b = 0);
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl std::fmt::Debug for DatabaseKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DatabaseKey(..)")
+    }
+}
+
+/// Where the key derivation for `db_path` is kept
+pub fn key_file_path(db_path: &Path) -> PathBuf {
+    sibling_path(db_path, "key.json")
+}
+
+/// A derivation written before a re-key and promoted once it completes;
+/// only passphrase changes from before keys were wrapped re-keyed
+fn pending_key_file_path(db_path: &Path) -> PathBuf {
+    sibling_path(db_path, "key.pending.json")
+}
+
+pub(crate) fn sibling_path(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", suffix));
+    db_path.with_file_name(name)
+}
+
+/// Whether the database at `db_path` is encrypted
+pub fn is_encrypted(db_path: &Path) -> bool {
+    key_file_path(db_path).exists()
+}
+
+pub fn validate_passphrase(passphrase: &str) -> OptioResult<()> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LENGTH {
+        return Err(OptioError::Validation(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Opening and Unlocking
+// ============================================================================
+
+/// Open an encrypted database, failing with `DatabaseLocked` on a wrong key
+pub fn open_with_key(path: &Path, key: &DatabaseKey) -> OptioResult<Connection> {
+    let conn = Connection::open(path)?;
+    conn.execute_batch(&format!("PRAGMA key = {};", key.sql_literal()))?;
+    // SQLCipher only reads the first page, and so checks the key, on first access
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+        .map_err(|_| OptioError::DatabaseLocked("Incorrect passphrase".to_string()))?;
+    Ok(conn)
+}
+
+/// Unwrap the key for the encrypted database at `db_path` and check it opens it
+///
+/// A re-key interrupted before its derivation was promoted leaves the
+/// database under either the old or the new key; both are tried and the
+/// pending derivation is promoted or discarded to match.
+pub fn unlock(db_path: &Path, passphrase: &str) -> OptioResult<DatabaseKey> {
+    let current = KeyDerivation::load(db_path)?
+        .ok_or_else(|| OptioError::Validation("The database is not encrypted".to_string()))?;
+    let pending_path = pending_key_file_path(db_path);
+
+    if let Some(key) = current.unwrap_key(passphrase).ok().filter(|key| open_with_key(db_path, key).is_ok()) {
+        if pending_path.exists() {
+            std::fs::remove_file(&pending_path)?;
+        }
+        return Ok(key);
+    }
+
+    if let Some(pending) = read_derivation(&pending_path)? {
+        let key = pending.unwrap_key(passphrase)?;
+        if open_with_key(db_path, &key).is_ok() {
+            std::fs::rename(&pending_path, key_file_path(db_path))?;
+            tracing::info!("Completed an interrupted database passphrase change");
+            return Ok(key);
+        }
+    }
+
+    Err(OptioError::DatabaseLocked("Incorrect passphrase".to_string()))
+}
+
+fn ensure_sqlcipher(conn: &Connection) -> OptioResult<()> {
+    let version: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()?;
+    if version.is_none() {
+        return Err(OptioError::Encryption("This build does not include SQLCipher".to_string()));
+    }
+    Ok(())
+}
+
+pub(crate) fn connection_path(conn: &Connection) -> OptioResult<PathBuf> {
+    conn.path()
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| OptioError::Database("An in-memory database can't be encrypted".to_string()))
+}
+
+// ============================================================================
+// Encrypting and Re-keying
+// ============================================================================
+
+/// Encrypt a plaintext database in place under a new key wrapped with
+/// `passphrase`
+///
+/// The data is exported into an encrypted copy, which is checked before it
+/// replaces the original. The original is moved aside as a backup before
+/// the swap; if the swap or reopening fails it is moved back and reopened,
+/// and once the encrypted database is open the backup is deleted.
+/// Backups taken while the database was plaintext are then encrypted
+/// under the same key. Returns the key for the session.
+pub fn encrypt_existing_database(db: &Database, passphrase: &str) -> OptioResult<DatabaseKey> {
+    validate_passphrase(passphrase)?;
+    let mut conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    let path = connection_path(&conn)?;
+    if is_encrypted(&path) {
+        return Err(OptioError::Validation("The database is already encrypted".to_string()));
+    }
+    ensure_sqlcipher(&conn)?;
+
+    let key = DatabaseKey::generate();
+    let derivation = KeyDerivation::generate().wrap(passphrase, &key)?;
+
+    // Export into an encrypted copy
+    let staging = sibling_path(&path, "encrypting");
+    remove_if_exists(&staging)?;
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+    if let Err(e) = export_encrypted(&conn, &staging, &key).and_then(|_| verify_copy(&conn, &staging, &key)) {
+        let _ = std::fs::remove_file(&staging);
+        return Err(e);
+    }
+
+    // Swap the copy in, closing the plaintext connection first
+    let backup = sibling_path(&path, "plaintext-backup");
+    let plaintext = std::mem::replace(&mut *conn, Connection::open_in_memory()?);
+    plaintext.close().map_err(|(_, e)| OptioError::from(e))?;
+
+    let swapped = std::fs::rename(&path, &backup)
+        .map_err(OptioError::from)
+        .and_then(|_| std::fs::rename(&staging, &path).map_err(OptioError::from))
+        .and_then(|_| derivation.save(&key_file_path(&path)))
+        .and_then(|_| Database::open_encrypted(&path, &key).map(|db| db.into_connection()));
+
+    match swapped {
+        Ok(encrypted) => {
+            *conn = encrypted;
+            std::fs::remove_file(&backup)?;
+            for suffix in ["wal", "shm"] {
+                remove_if_exists(&sibling_path(&path, suffix))?;
+            }
+            tracing::info!("Database encrypted");
+            crate::db_backup::encrypt_plaintext_backups(&path, &key);
+            Ok(key)
+        }
+        Err(e) => {
+            tracing::error!("Database encryption failed, restoring the plaintext database: {}", e);
+            let _ = std::fs::remove_file(key_file_path(&path));
+            if backup.exists() {
+                remove_if_exists(&path)?;
+                std::fs::rename(&backup, &path)?;
+            }
+            remove_if_exists(&staging)?;
+            *conn = Database::open(&path)?.into_connection();
+            Err(e)
+        }
+    }
+}
+
+/// Export the database open on `conn` into a new copy at `dest` encrypted under `key`
+pub(crate) fn export_encrypted(conn: &Connection, dest: &Path, key: &DatabaseKey) -> OptioResult<()> {
+    let exported = conn
+        .execute(&format!("ATTACH DATABASE ?1 AS encrypted KEY {}", key.sql_literal()), [dest.to_string_lossy().to_string()])
+        .and_then(|_| conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(())))
+        .map_err(OptioError::from);
+    let _ = conn.execute_batch("DETACH DATABASE encrypted;");
+    exported
+}
+
+/// Check an encrypted copy opens with `key` and has every table of the original
+fn verify_copy(original: &Connection, copy: &Path, key: &DatabaseKey) -> OptioResult<()> {
+    let copy = open_with_key(copy, key)?;
+    let tables = |conn: &Connection| -> OptioResult<i64> {
+        Ok(conn.query_row("SELECT count(*) FROM sqlite_master WHERE type = 'table'", [], |row| row.get(0))?)
+    };
+    if tables(original)? != tables(&copy)? {
+        return Err(OptioError::Encryption("The encrypted copy is incomplete".to_string()));
+    }
+    Ok(())
+}
+
+/// Re-wrap the database key under a new passphrase
+///
+/// `current` must be the session's key. The data stays encrypted under the
+/// same key; only the key file is replaced, atomically, so an interrupted
+/// change leaves the old passphrase working. A database whose key file
+/// predates wrapped keys gets a wrapped one here.
+pub fn change_passphrase(db: &Database, current: &DatabaseKey, new_passphrase: &str) -> OptioResult<DatabaseKey> {
+    validate_passphrase(new_passphrase)?;
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    let path = connection_path(&conn)?;
+    if !is_encrypted(&path) {
+        return Err(OptioError::Validation("The database is not encrypted".to_string()));
+    }
+
+    KeyDerivation::generate().wrap(new_passphrase, current)?.save(&key_file_path(&path))?;
+    remove_if_exists(&pending_key_file_path(&path))?;
+
+    tracing::info!("Database passphrase changed");
+    Ok(current.clone())
+}
+
+pub(crate) fn remove_if_exists(path: &Path) -> OptioResult<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+// ============================================================================
+// Session State
+// ============================================================================
+
+/// Where the database is in starting up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DatabaseState {
+    Starting,
+    /// Encrypted and waiting for the passphrase
+    Locked,
+    Ready,
+    Failed,
+}
+
+/// Database state reported to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStatus {
+    pub state: DatabaseState,
+    pub encrypted: bool,
+    /// Why opening failed
+    pub message: Option<String>,
+}
+
+/// Database state and key for the running session
+pub struct DatabaseSession {
+    status: Mutex<DatabaseStatus>,
+    key: Mutex<Option<DatabaseKey>>,
+}
+
+impl Default for DatabaseSession {
+    fn default() -> Self {
+        DatabaseSession {
+            status: Mutex::new(DatabaseStatus { state: DatabaseState::Starting, encrypted: false, message: None }),
+            key: Mutex::new(None),
+        }
+    }
+}
+
+impl DatabaseSession {
+    pub fn status(&self) -> DatabaseStatus {
+        self.status.lock().map(|s| s.clone()).unwrap_or(DatabaseStatus {
+            state: DatabaseState::Failed,
+            encrypted: false,
+            message: Some("Database status unavailable".to_string()),
+        })
+    }
+
+    pub fn set_status(&self, state: DatabaseState, encrypted: bool, message: Option<String>) -> DatabaseStatus {
+        let status = DatabaseStatus { state, encrypted, message };
+        if let Ok(mut current) = self.status.lock() {
+            *current = status.clone();
+        }
+        status
+    }
+
+    /// Keep the key for the rest of the session
+    pub fn set_key(&self, key: DatabaseKey) {
+        if let Ok(mut current) = self.key.lock() {
+            *current = Some(key);
+        }
+    }
+
+    /// The session key, when the database is encrypted and unlocked
+    pub fn key(&self) -> Option<DatabaseKey> {
+        self.key.lock().ok().and_then(|k| k.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cheap_derivation(salt: &str) -> KeyDerivation {
+        KeyDerivation {
+            salt: BASE64.encode(salt),
+            memory_kib: 1024,
+            iterations: 1,
+            parallelism: 1,
+            wrapped_key: None,
+        }
+    }
+
+    #[test]
+    fn test_key_derivation_depends_on_passphrase_and_salt() {
+        let derivation = cheap_derivation("sixteen-byte-slt");
+        let key = derivation.derive("correct horse battery").unwrap();
+
+        assert_eq!(key, derivation.derive("correct horse battery").unwrap());
+        assert_ne!(key, derivation.derive("correct horse battery!").unwrap());
+        assert_ne!(key, cheap_derivation("another-salt-val").derive("correct horse battery").unwrap());
+
+        let literal = key.sql_literal();
+        assert!(literal.starts_with("\"x'") && literal.ends_with("'\""));
+        assert_eq!(literal.len(), KEY_LENGTH * 2 + 5);
+        assert_eq!(format!("{:?}", key), "DatabaseKey(..)");
+    }
+
+    #[test]
+    fn test_key_files_sit_beside_the_database() {
+        let dir = std::env::temp_dir().join(format!("optio-key-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("optio.db");
+
+        assert_eq!(key_file_path(&db_path), dir.join("optio.db.key.json"));
+        assert!(!is_encrypted(&db_path));
+        assert_eq!(KeyDerivation::load(&db_path).unwrap(), None);
+
+        let derivation = KeyDerivation::generate();
+        derivation.save(&key_file_path(&db_path)).unwrap();
+        assert!(is_encrypted(&db_path));
+        assert_eq!(KeyDerivation::load(&db_path).unwrap(), Some(derivation));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_wrapped_key_opens_only_with_its_passphrase() {
+        let key = DatabaseKey::generate();
+        let derivation = cheap_derivation("sixteen-byte-slt").wrap("correct horse battery", &key).unwrap();
+
+        assert_eq!(derivation.unwrap_key("correct horse battery").unwrap(), key);
+        assert!(matches!(
+            derivation.unwrap_key("wrong horse battery"),
+            Err(OptioError::DatabaseLocked(_))
+        ));
+        // A key file from before wrapping yields the derived key itself
+        let legacy = cheap_derivation("sixteen-byte-slt");
+        assert_eq!(legacy.unwrap_key("correct horse battery").unwrap(), legacy.derive("correct horse battery").unwrap());
+    }
+
+    #[test]
+    fn test_encrypted_database_is_not_readable_as_plaintext() {
+        let dir = std::env::temp_dir().join(format!("optio-encrypt-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("optio.db");
+        let marker = "10.20.30.40 Palo Alto config";
+
+        let db = Database::open(&db_path).unwrap();
+        db.conn
+            .lock()
+            .unwrap()
+            .execute_batch(&format!("CREATE TABLE notes (body TEXT); INSERT INTO notes VALUES ('{}');", marker))
+            .unwrap();
+        let key = encrypt_existing_database(&db, "long enough passphrase").unwrap();
+        drop(db);
+
+        let bytes = std::fs::read(&db_path).unwrap();
+        assert!(!bytes.starts_with(b"SQLite format 3\0"));
+        assert!(!bytes.windows(marker.len()).any(|w| w == marker.as_bytes()));
+        let plain = Connection::open(&db_path).unwrap();
+        assert!(plain.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0)).is_err());
+        assert!(!std::fs::read_to_string(key_file_path(&db_path)).unwrap().contains("passphrase"));
+        assert!(!dir.join("optio.db.plaintext-backup").exists());
+
+        assert_eq!(unlock(&db_path, "long enough passphrase").unwrap(), key);
+        assert!(matches!(unlock(&db_path, "not the passphrase"), Err(OptioError::DatabaseLocked(_))));
+
+        let db = Database::open_encrypted(&db_path, &key).unwrap();
+        change_passphrase(&db, &key, "a different passphrase").unwrap();
+        let body: String = db.conn.lock().unwrap().query_row("SELECT body FROM notes", [], |row| row.get(0)).unwrap();
+        assert_eq!(body, marker);
+        drop(db);
+        assert_eq!(unlock(&db_path, "a different passphrase").unwrap(), key);
+        assert!(unlock(&db_path, "long enough passphrase").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_short_passphrases_are_rejected() {
+        assert!(validate_passphrase("too short").is_err());
+        assert!(validate_passphrase("long enough passphrase").is_ok());
+    }
+}
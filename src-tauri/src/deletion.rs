@@ -0,0 +1,289 @@
+//! Client Data Deletion
+//!
+//! Deleting a client removes everything Optio holds for it, for engagements
+//! that promise complete data deletion at off-boarding. Most tables reference
+//! the client (or an assessment, asset or scan of it) with `ON DELETE
+//! CASCADE`; reports, Kubernetes audits, cloud readiness assessments,
+//! remediation plans and the activity log only carry a `client_id` and are
+//! deleted explicitly. All rows go in one transaction.
+//!
+//! Evidence files and exported report files are removed once the
+//! transaction commits, but only those inside the app data dir: files the
+//! user picked from elsewhere are theirs to manage.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Tables with a `client_id` but no foreign key to `clients`
+const UNLINKED_CLIENT_TABLES: [&str; 5] = [
+    "remediation_plans",
+    "reports",
+    "k8s_audits",
+    "cloud_readiness_assessments",
+    "activity_log",
+];
+
+/// Everything that deleting a client removes, by entity type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDeletionPreview {
+    pub assessments: usize,
+    pub control_assessments: usize,
+    pub evidence: usize,
+    /// Evidence rows with a file on disk
+    pub evidence_files: usize,
+    pub remediation_items: usize,
+    pub risks: usize,
+    pub processing_activities: usize,
+    pub findings: usize,
+    pub assets: usize,
+    pub asset_groups: usize,
+    pub scans: usize,
+    pub scan_schedules: usize,
+    pub network_findings: usize,
+    pub reports: usize,
+    /// Reports with an exported file on disk
+    pub report_files: usize,
+    pub generated_scripts: usize,
+    pub agent_tokens: usize,
+    pub k8s_audits: usize,
+    pub cloud_readiness_assessments: usize,
+    pub activity_events: usize,
+}
+
+/// Count what deleting a client would remove
+pub fn preview_client_deletion(db: &Database, client_id: &str) -> OptioResult<ClientDeletionPreview> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    let count = |sql: &str| -> OptioResult<usize> {
+        let n: i64 = conn.query_row(sql, params![client_id], |row| row.get(0))?;
+        Ok(n as usize)
+    };
+    let by_client = |table: &str| count(&format!("SELECT COUNT(*) FROM {} WHERE client_id = ?1", table));
+    let by_assessment = |table: &str, condition: &str| count(&format!(
+        "SELECT COUNT(*) FROM {} t JOIN assessments a ON a.id = t.assessment_id WHERE a.client_id = ?1 AND {}",
+        table, condition
+    ));
+
+    Ok(ClientDeletionPreview {
+        assessments: by_client("assessments")?,
+        control_assessments: by_assessment("control_assessments", "1")?,
+        evidence: by_assessment("evidence", "1")?,
+        evidence_files: by_assessment("evidence", "t.file_path IS NOT NULL")?,
+        remediation_items: count(
+            "SELECT COUNT(*) FROM remediation_items i JOIN remediation_plans p ON p.id = i.plan_id WHERE p.client_id = ?1",
+        )?,
+        risks: by_client("risks")?,
+        processing_activities: by_client("processing_activities")?,
+        findings: by_client("findings")?,
+        assets: by_client("assets")?,
+        asset_groups: by_client("asset_groups")?,
+        scans: by_client("scans")?,
+        scan_schedules: by_client("scan_schedules")?,
+        network_findings: by_client("network_findings")?,
+        reports: by_client("reports")?,
+        report_files: count("SELECT COUNT(*) FROM reports WHERE client_id = ?1 AND file_path IS NOT NULL")?,
+        generated_scripts: by_client("generated_scripts")?,
+        agent_tokens: by_client("agent_tokens")?,
+        k8s_audits: by_client("k8s_audits")?,
+        cloud_readiness_assessments: by_client("cloud_readiness_assessments")?,
+        activity_events: by_client("activity_log")?,
+    })
+}
+
+/// Delete a client and all of its data in one transaction
+///
+/// Returns the evidence and report files the deleted rows pointed at, for
+/// [`remove_app_data_files`], or `None` if there is no such client.
+pub fn delete_client_data(db: &Database, client_id: &str) -> OptioResult<Option<Vec<PathBuf>>> {
+    let mut conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    let tx = conn.transaction()?;
+
+    let files = client_file_paths(&tx, client_id)?;
+    for table in UNLINKED_CLIENT_TABLES {
+        tx.execute(&format!("DELETE FROM {} WHERE client_id = ?1", table), params![client_id])?;
+    }
+    let deleted = tx.execute("DELETE FROM clients WHERE id = ?1", params![client_id])?;
+    if deleted == 0 {
+        // Nothing to delete; leave any stray rows for a client that never existed alone
+        return Ok(None);
+    }
+
+    tx.commit()?;
+    Ok(Some(files))
+}
+
+fn client_file_paths(conn: &Connection, client_id: &str) -> OptioResult<Vec<PathBuf>> {
+    let mut stmt = conn.prepare(
+        "SELECT e.file_path FROM evidence e JOIN assessments a ON a.id = e.assessment_id
+         WHERE a.client_id = ?1 AND e.file_path IS NOT NULL
+         UNION
+         SELECT file_path FROM reports WHERE client_id = ?1 AND file_path IS NOT NULL",
+    )?;
+    let paths = stmt.query_map(params![client_id], |row| row.get::<_, String>(0))?
+        .filter_map(|r| r.ok())
+        .map(PathBuf::from)
+        .collect();
+    Ok(paths)
+}
+
+/// Delete a file if it lives inside the app data dir, returning whether it
+/// was removed
+///
+/// Paths that don't exist, or that lie outside `app_data_dir`, are left alone.
+pub fn remove_app_data_file(app_data_dir: &Path, file_path: &Path) -> std::io::Result<bool> {
+    let (dir, path) = match (app_data_dir.canonicalize(), file_path.canonicalize()) {
+        (Ok(dir), Ok(path)) => (dir, path),
+        // Already gone, or no app data dir to be inside of
+        _ => return Ok(false),
+    };
+    if !path.starts_with(&dir) {
+        tracing::info!("Leaving file outside the app data dir: {}", path.display());
+        return Ok(false);
+    }
+    std::fs::remove_file(&path)?;
+    Ok(true)
+}
+
+/// Remove the files of a deleted client, returning how many were removed
+/// and a warning for each that couldn't be
+///
+/// The rows are already gone, so failures are reported rather than returned.
+pub fn remove_app_data_files(app_data_dir: &Path, files: &[PathBuf]) -> (usize, Vec<String>) {
+    let mut removed = 0;
+    let mut warnings = Vec::new();
+    for file in files {
+        match remove_app_data_file(app_data_dir, file) {
+            Ok(true) => removed += 1,
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!("Failed to delete {}: {}", file.display(), e);
+                warnings.push(format!("Could not delete {}: {}", file.display(), e));
+            }
+        }
+    }
+    (removed, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::activity::{self, ActivityEvent, ActivityEventType};
+    use crate::db::{Client, ClientRepository};
+    use crate::grc::models::{Assessment, AssessmentStatus, Evidence, EvidenceType, Framework};
+    use crate::grc::repository::{AssessmentRepository, EvidenceRepository};
+    use chrono::Utc;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::grc::repository::init_grc_schema(&db).unwrap();
+        crate::grc::risk::init_risk_schema(&db).unwrap();
+        crate::grc::ropa::init_ropa_schema(&db).unwrap();
+        crate::grc::remediation::init_remediation_schema(&db).unwrap();
+        crate::findings::library::init_findings_schema(&db).unwrap();
+        crate::network::repository::init_network_schema(&db).unwrap();
+        crate::reporting::repository::init_reporting_schema(&db).unwrap();
+        crate::factory::history::init_script_history_schema(&db).unwrap();
+        crate::factory::tokens::init_agent_token_schema(&db).unwrap();
+        crate::infrastructure::k8s_hardening::init_k8s_audit_schema(&db).unwrap();
+        crate::infrastructure::cloud_readiness::init_cloud_readiness_schema(&db).unwrap();
+        crate::activity::init_activity_schema(&db).unwrap();
+        db
+    }
+
+    fn seed_client(db: &Database, id: &str, evidence_file: Option<&Path>) {
+        let mut client = Client::new(id.to_string(), None, None, None);
+        client.id = id.to_string();
+        ClientRepository::new(db).create(&client).unwrap();
+
+        let assessment_id = format!("{}-assessment", id);
+        AssessmentRepository::new(db).create(&Assessment {
+            id: assessment_id.clone(),
+            client_id: id.to_string(),
+            name: "SOC 2".to_string(),
+            description: None,
+            framework: Framework::Soc2TypeII,
+            scope: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            lead_assessor: "Jane Auditor".to_string(),
+            status: AssessmentStatus::InProgress,
+            source_assessment_id: None,
+        }).unwrap();
+        EvidenceRepository::new(db).create(&Evidence {
+            id: format!("{}-evidence", id),
+            assessment_id: assessment_id.clone(),
+            control_ids: vec![],
+            title: "Access review".to_string(),
+            description: None,
+            evidence_type: EvidenceType::Document,
+            file_path: evidence_file.map(|p| p.to_string_lossy().into_owned()),
+            url: None,
+            file_hash: None,
+            collected_at: Utc::now(),
+            collected_by: "Jane Auditor".to_string(),
+            notes: None,
+            carried_forward: false,
+        }).unwrap();
+
+        db.conn.lock().unwrap().execute(
+            "INSERT INTO reports (id, client_id, client_name, title, report_type, status, format, config, created_at, updated_at)
+             VALUES (?1, ?2, ?2, 'Report', 'ExecutiveSummary', 'Draft', 'Html', '{}', '2026-01-01', '2026-01-01')",
+            params![format!("{}-report", id), id],
+        ).unwrap();
+        db.conn.lock().unwrap().execute(
+            "INSERT INTO network_findings (id, client_id, rule_id, host, port, service, title, severity, status,
+                                           evidence, detected_at, updated_at)
+             VALUES (?1, ?2, 'telnet-exposed', '10.0.0.5', 23, 'telnet', 'Telnet exposed', 'High', 'Open',
+                     '[]', '2026-01-01', '2026-01-01')",
+            params![format!("{}-network-finding", id), id],
+        ).unwrap();
+        activity::log(db, ActivityEvent::new(id, ActivityEventType::AssessmentCreated, "assessment", Some(&assessment_id), "Created"));
+    }
+
+    #[test]
+    fn test_delete_client_cascades() {
+        let dir = std::env::temp_dir().join(format!("optio-deletion-{}", uuid::Uuid::new_v4()));
+        let outside = std::env::temp_dir().join(format!("optio-deletion-outside-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("evidence")).unwrap();
+        let managed = dir.join("evidence").join("review.pdf");
+        std::fs::write(&managed, b"pdf").unwrap();
+        std::fs::write(&outside, b"user file").unwrap();
+
+        let db = test_db();
+        seed_client(&db, "client-1", Some(&managed));
+        seed_client(&db, "client-2", Some(&outside));
+
+        let preview = preview_client_deletion(&db, "client-1").unwrap();
+        assert_eq!((preview.assessments, preview.evidence, preview.evidence_files), (1, 1, 1));
+        assert_eq!((preview.reports, preview.report_files, preview.activity_events), (1, 0, 1));
+        assert_eq!(preview.network_findings, 1);
+
+        let files = delete_client_data(&db, "client-1").unwrap().unwrap();
+        assert_eq!(files, vec![managed.clone()]);
+        assert_eq!(remove_app_data_files(&dir, &files), (1, vec![]));
+        assert!(!managed.exists());
+
+        // Nothing of client-1 is left, and client-2 is untouched
+        let left = preview_client_deletion(&db, "client-1").unwrap();
+        assert_eq!(serde_json::to_value(&left).unwrap(), serde_json::to_value(ClientDeletionPreview::default()).unwrap());
+        let other = preview_client_deletion(&db, "client-2").unwrap();
+        assert_eq!((other.assessments, other.evidence, other.reports, other.activity_events), (1, 1, 1, 1));
+
+        // Files outside the app data dir are never removed
+        let files = delete_client_data(&db, "client-2").unwrap().unwrap();
+        assert_eq!(remove_app_data_files(&dir, &files), (0, vec![]));
+        assert!(outside.exists());
+
+        assert!(delete_client_data(&db, "client-1").unwrap().is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(&outside);
+    }
+}
@@ -18,6 +18,9 @@ pub enum OptioError {
     #[error("Database error: {0}")]
     Database(String),
 
+    #[error("Database is locked: {0}")]
+    DatabaseLocked(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -52,6 +55,7 @@ impl From<OptioError> for ErrorResponse {
             OptioError::InvalidConfig(_) => "INVALID_CONFIG",
             OptioError::ScriptGeneration(_) => "SCRIPT_GENERATION_FAILED",
             OptioError::Database(_) => "DATABASE_ERROR",
+            OptioError::DatabaseLocked(_) => "DATABASE_LOCKED",
             OptioError::Io(_) => "IO_ERROR",
             OptioError::Serialization(_) => "SERIALIZATION_ERROR",
             OptioError::ClientNotFound(_) => "CLIENT_NOT_FOUND",
@@ -0,0 +1,374 @@
+//! Pre-Change Audit Results
+//!
+//! Audit-only scripts record the current state of every setting a prep script
+//! would change and write it to a JSON file. Importing that file produces a
+//! current vs intended comparison per setting, for attaching to the change
+//! ticket before anything is modified.
+//!
+//! The file carries a `schemaVersion`. Fields added in later versions are
+//! optional, so files from older scripts keep parsing; files from a newer
+//! Optio are rejected rather than half-read.
+
+use super::ScriptConfig;
+use crate::error::{OptioError, OptioResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Schema version written by the current audit templates
+pub const PREP_AUDIT_SCHEMA_VERSION: u32 = 1;
+
+// ============================================================================
+// Audit File
+// ============================================================================
+
+/// State captured by an audit-only script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrepAudit {
+    pub schema_version: u32,
+    /// Script that produced the file, for looking up its recorded config
+    #[serde(default)]
+    pub script_id: String,
+    #[serde(default)]
+    pub client_id: String,
+    #[serde(default)]
+    pub hostname: String,
+    /// When the state was captured, as reported by the host
+    #[serde(default)]
+    pub collected_at: Option<String>,
+    /// What the paired prep script would set
+    #[serde(default)]
+    pub intended: Option<AuditIntendedState>,
+    #[serde(default)]
+    pub winrm: Option<WinRmState>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub dns: Vec<DnsState>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub firewall_logging: Vec<FirewallLoggingState>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub installed_agents: Vec<InstalledAgent>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub custom_commands: Vec<CustomCommandTarget>,
+}
+
+/// Settings the prep script would apply
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AuditIntendedState {
+    pub enable_winrm: bool,
+    pub configure_dns: bool,
+    #[serde(deserialize_with = "one_or_many")]
+    pub dns_servers: Vec<String>,
+    pub install_agent: bool,
+    pub agent_installer: Option<String>,
+    pub enable_firewall_logging: bool,
+    pub consultant_ip: Option<String>,
+}
+
+impl From<&ScriptConfig> for AuditIntendedState {
+    fn from(config: &ScriptConfig) -> Self {
+        AuditIntendedState {
+            enable_winrm: config.enable_winrm,
+            configure_dns: config.configure_dns,
+            dns_servers: config.dns_servers.clone(),
+            install_agent: config.install_agent,
+            agent_installer: config.agent_installer.clone(),
+            enable_firewall_logging: config.enable_firewall_logging,
+            consultant_ip: Some(config.consultant_ip.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WinRmState {
+    /// Service status ("Running", "Stopped", "NotInstalled", ...)
+    pub service_status: String,
+    pub start_type: Option<String>,
+    pub trusted_hosts: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DnsState {
+    pub interface: String,
+    #[serde(deserialize_with = "one_or_many")]
+    pub servers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FirewallLoggingState {
+    pub profile: String,
+    pub log_allowed: bool,
+    pub log_blocked: bool,
+    pub log_file_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InstalledAgent {
+    pub name: String,
+    pub version: Option<String>,
+    pub publisher: Option<String>,
+}
+
+/// A custom command and whether the command it invokes exists on the host
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CustomCommandTarget {
+    pub command: String,
+    /// First word of the command (the cmdlet or program it runs)
+    pub target: String,
+    pub available: bool,
+}
+
+/// ConvertTo-Json writes single-element collections as a bare value and
+/// empty ones as null; accept all three shapes
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let items = match value {
+        serde_json::Value::Null => vec![],
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+    items
+        .into_iter()
+        .map(|item| serde_json::from_value(item).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+/// Parse an audit file, checking its schema version
+pub fn parse_prep_audit(json: &str) -> OptioResult<PrepAudit> {
+    // Windows PowerShell writes UTF-8 with a byte order mark
+    let json = json.trim_start_matches('\u{feff}');
+    let value: serde_json::Value = serde_json::from_str(json)?;
+
+    let version = value
+        .get("schemaVersion")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| OptioError::Validation("Not an Optio prep audit file (no schemaVersion)".to_string()))?;
+    if version == 0 || version > PREP_AUDIT_SCHEMA_VERSION as u64 {
+        return Err(OptioError::Validation(format!(
+            "Unsupported prep audit schema version {} (this version of Optio reads up to {})",
+            version, PREP_AUDIT_SCHEMA_VERSION
+        )));
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+// ============================================================================
+// Pre-Change Report
+// ============================================================================
+
+/// Current vs intended state of one setting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditSettingComparison {
+    pub setting: String,
+    pub current: String,
+    pub intended: String,
+    /// Whether running the prep script would change this setting
+    pub change_required: bool,
+}
+
+/// What a prep script would change on one host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrepAuditReport {
+    pub schema_version: u32,
+    pub script_id: String,
+    pub client_id: String,
+    pub hostname: String,
+    pub collected_at: Option<String>,
+    /// Intended state came from Optio's record of the script rather than the file
+    pub from_recorded_script: bool,
+    pub settings: Vec<AuditSettingComparison>,
+    pub changes_required: usize,
+    pub warnings: Vec<String>,
+}
+
+const UNCHANGED: &str = "Unchanged (not configured)";
+
+/// Compare captured state with what the prep script would set
+pub fn build_prep_audit_report(
+    audit: &PrepAudit,
+    intended: &AuditIntendedState,
+    from_recorded_script: bool,
+) -> PrepAuditReport {
+    let mut settings = Vec::new();
+    let mut warnings = Vec::new();
+    let mut compare = |setting: String, current: String, intended: String, change_required: bool| {
+        settings.push(AuditSettingComparison { setting, current, intended, change_required });
+    };
+
+    // WinRM service and the consultant's trusted host entry
+    let winrm = audit.winrm.clone().unwrap_or_default();
+    let running = winrm.service_status.eq_ignore_ascii_case("running");
+    let status = match (&winrm.service_status, &winrm.start_type) {
+        (s, _) if s.is_empty() => "Not captured".to_string(),
+        (s, Some(start)) => format!("{} ({})", s, start),
+        (s, None) => s.clone(),
+    };
+    if intended.enable_winrm {
+        compare("WinRM service".to_string(), status, "Running".to_string(), !running);
+
+        let consultant_ip = intended.consultant_ip.clone().unwrap_or_default();
+        let trusted = winrm.trusted_hosts.clone().unwrap_or_default();
+        let already_trusted = trusted == "*" || trusted.split(',').any(|h| h.trim() == consultant_ip);
+        compare(
+            "WinRM trusted hosts".to_string(),
+            if trusted.is_empty() { "None".to_string() } else { trusted },
+            consultant_ip,
+            !already_trusted,
+        );
+    } else {
+        compare("WinRM service".to_string(), status, UNCHANGED.to_string(), false);
+    }
+
+    // DNS servers per active interface
+    if audit.dns.is_empty() && intended.configure_dns {
+        warnings.push("No active network interfaces were captured; DNS changes can't be assessed".to_string());
+    }
+    for dns in &audit.dns {
+        let current = if dns.servers.is_empty() { "None (DHCP)".to_string() } else { dns.servers.join(", ") };
+        let setting = format!("DNS servers ({})", dns.interface);
+        if intended.configure_dns && !intended.dns_servers.is_empty() {
+            compare(setting, current, intended.dns_servers.join(", "), dns.servers != intended.dns_servers);
+        } else {
+            compare(setting, current, UNCHANGED.to_string(), false);
+        }
+    }
+
+    // Firewall logging per profile
+    for profile in &audit.firewall_logging {
+        let on_off = |enabled: bool| if enabled { "on" } else { "off" };
+        let current = format!(
+            "Allowed: {}, blocked: {}",
+            on_off(profile.log_allowed),
+            on_off(profile.log_blocked)
+        );
+        let setting = format!("Firewall logging ({})", profile.profile);
+        if intended.enable_firewall_logging {
+            let complete = profile.log_allowed && profile.log_blocked;
+            compare(setting, current, "Allowed: on, blocked: on".to_string(), !complete);
+        } else {
+            compare(setting, current, UNCHANGED.to_string(), false);
+        }
+    }
+
+    // Security agent
+    let agents = if audit.installed_agents.is_empty() {
+        "None detected".to_string()
+    } else {
+        audit.installed_agents
+            .iter()
+            .map(|a| match &a.version {
+                Some(version) => format!("{} {}", a.name, version),
+                None => a.name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if intended.install_agent {
+        let installer = intended.agent_installer.clone().unwrap_or_default();
+        compare("Security agent".to_string(), agents, format!("Install from {}", installer), true);
+    } else {
+        compare("Security agent".to_string(), agents, UNCHANGED.to_string(), false);
+    }
+
+    // Custom commands always run; flag the ones that can't
+    for command in &audit.custom_commands {
+        let current = if command.available {
+            format!("{} available", command.target)
+        } else {
+            warnings.push(format!("Custom command target '{}' was not found on {}", command.target, audit.hostname));
+            format!("{} not found", command.target)
+        };
+        compare("Custom command".to_string(), current, format!("Run: {}", command.command), true);
+    }
+
+    let changes_required = settings.iter().filter(|s| s.change_required).count();
+    PrepAuditReport {
+        schema_version: audit.schema_version,
+        script_id: audit.script_id.clone(),
+        client_id: audit.client_id.clone(),
+        hostname: audit.hostname.clone(),
+        collected_at: audit.collected_at.clone(),
+        from_recorded_script,
+        settings,
+        changes_required,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shape written by ConvertTo-Json, including a BOM and single-element collections
+    const AUDIT_V1: &str = "\u{feff}{
+        \"schemaVersion\": 1,
+        \"scriptId\": \"script-1\",
+        \"clientId\": \"client-1\",
+        \"hostname\": \"WS-042\",
+        \"collectedAt\": \"2026-03-02T09:15:00.0000000Z\",
+        \"intended\": {
+            \"enableWinrm\": true,
+            \"configureDns\": true,
+            \"dnsServers\": [\"10.0.0.10\", \"10.0.0.11\"],
+            \"installAgent\": false,
+            \"agentInstaller\": \"\",
+            \"enableFirewallLogging\": true,
+            \"consultantIp\": \"10.0.0.50\"
+        },
+        \"winrm\": { \"serviceStatus\": \"Stopped\", \"startType\": \"Manual\", \"trustedHosts\": null },
+        \"dns\": { \"interface\": \"Ethernet\", \"servers\": \"10.0.0.10\" },
+        \"firewallLogging\": [
+            { \"profile\": \"Domain\", \"logAllowed\": true, \"logBlocked\": true, \"logFileName\": \"pfirewall.log\" },
+            { \"profile\": \"Public\", \"logAllowed\": false, \"logBlocked\": true }
+        ],
+        \"installedAgents\": null,
+        \"customCommands\": { \"command\": \"Set-MpPreference -DisableRealtimeMonitoring $false\", \"target\": \"Set-MpPreference\", \"available\": false }
+    }";
+
+    #[test]
+    fn test_parse_and_report() {
+        let audit = parse_prep_audit(AUDIT_V1).unwrap();
+        assert_eq!(audit.dns[0].servers, vec!["10.0.0.10"]);
+        assert!(audit.installed_agents.is_empty());
+
+        let intended = audit.intended.clone().unwrap();
+        let report = build_prep_audit_report(&audit, &intended, false);
+        let changed: Vec<&str> = report.settings.iter()
+            .filter(|s| s.change_required)
+            .map(|s| s.setting.as_str())
+            .collect();
+        assert_eq!(changed, vec![
+            "WinRM service",
+            "WinRM trusted hosts",
+            "DNS servers (Ethernet)",
+            "Firewall logging (Public)",
+            "Custom command",
+        ]);
+        assert_eq!(report.changes_required, 5);
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_schema_versions() {
+        // Minimal file: every section is optional
+        let audit = parse_prep_audit(r#"{"schemaVersion": 1, "hostname": "WS-001"}"#).unwrap();
+        let report = build_prep_audit_report(&audit, &AuditIntendedState::default(), false);
+        assert_eq!(report.changes_required, 0);
+
+        let newer = format!(r#"{{"schemaVersion": {}}}"#, PREP_AUDIT_SCHEMA_VERSION + 1);
+        assert!(parse_prep_audit(&newer).unwrap_err().to_string().contains("Unsupported"));
+        assert!(parse_prep_audit(r#"{"hostname": "WS-001"}"#).is_err());
+    }
+}
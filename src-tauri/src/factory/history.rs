@@ -0,0 +1,386 @@
+//! Script Generation History
+//!
+//! Every generated script is recorded with the template name, the full
+//! configuration it was rendered from, the content and its SHA-256 hash. A
+//! stored generation can be replayed through the current template with its
+//! original script ID and timestamp, so any difference in the output comes
+//! from template or generator changes alone.
+
+use super::{ScriptConfig, ScriptGenerator};
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::repository::parse_datetime;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Initialize script history schema
+pub fn init_script_history_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Generated scripts (config and warnings stored as JSON)
+        CREATE TABLE IF NOT EXISTS generated_scripts (
+            script_id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            template_name TEXT NOT NULL,
+            config TEXT NOT NULL,
+            content_hash TEXT NOT NULL,
+            content TEXT,
+            warnings TEXT NOT NULL,
+            output_path TEXT,
+            generated_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_generated_scripts_client ON generated_scripts(client_id, generated_at);
+    "#)?;
+
+    tracing::info!("Script history schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// A recorded script generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRecord {
+    pub script_id: String,
+    pub client_id: String,
+    pub template_name: String,
+    /// Configuration the script was rendered from
+    pub config: ScriptConfig,
+    /// SHA-256 of the content, hex-encoded
+    pub content_hash: String,
+    pub content: Option<String>,
+    pub warnings: Vec<String>,
+    /// Where the script was written
+    pub output_path: Option<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Stored generation without its content, for listings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRecordSummary {
+    pub script_id: String,
+    pub template_name: String,
+    pub target_subnet: String,
+    pub consultant_ip: String,
+    pub content_hash: String,
+    pub warning_count: usize,
+    pub output_path: Option<String>,
+    pub audit_only: bool,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl From<&ScriptRecord> for ScriptRecordSummary {
+    fn from(record: &ScriptRecord) -> Self {
+        ScriptRecordSummary {
+            script_id: record.script_id.clone(),
+            template_name: record.template_name.clone(),
+            target_subnet: record.config.target_subnet.clone(),
+            consultant_ip: record.config.consultant_ip.clone(),
+            content_hash: record.content_hash.clone(),
+            warning_count: record.warnings.len(),
+            output_path: record.output_path.clone(),
+            audit_only: record.config.audit_only,
+            generated_at: record.generated_at,
+        }
+    }
+}
+
+/// Whether a line was added or removed on regeneration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineChangeKind {
+    Added,
+    Removed,
+}
+
+/// One line that differs between the stored and regenerated script
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptLineChange {
+    pub kind: LineChangeKind,
+    /// 1-based line number in the stored script (removed) or the new one (added)
+    pub line: usize,
+    pub text: String,
+}
+
+/// A stored generation replayed through the current template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRegeneration {
+    pub script_id: String,
+    pub template_name: String,
+    pub stored_hash: String,
+    pub content_hash: String,
+    /// The regenerated script is byte-identical to the stored one
+    pub unchanged: bool,
+    pub content: String,
+    pub warnings: Vec<String>,
+    /// Line differences, when the stored content is available
+    pub changes: Vec<ScriptLineChange>,
+}
+
+/// Hex-encoded SHA-256 of a script
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Replay a stored generation through the current template
+///
+/// Unknown placeholders are reported as warnings rather than failing, so a
+/// template that has drifted still produces a comparison.
+pub fn regenerate(generator: ScriptGenerator, record: &ScriptRecord) -> OptioResult<ScriptRegeneration> {
+    let result = generator
+        .with_strict_placeholders(false)
+        .render(&record.template_name, &record.config, &record.script_id, record.generated_at)?;
+    let hash = content_hash(&result.content);
+
+    let changes = match record.content {
+        Some(ref stored) if hash != record.content_hash => diff_lines(stored, &result.content),
+        _ => vec![],
+    };
+
+    Ok(ScriptRegeneration {
+        script_id: record.script_id.clone(),
+        template_name: record.template_name.clone(),
+        unchanged: hash == record.content_hash,
+        stored_hash: record.content_hash.clone(),
+        content_hash: hash,
+        content: result.content,
+        warnings: result.warnings,
+        changes,
+    })
+}
+
+/// Lines removed from `old` and added in `new`, in order
+///
+/// A longest-common-subsequence diff; scripts are a few hundred lines, so
+/// the quadratic table is small.
+pub fn diff_lines(old: &str, new: &str) -> Vec<ScriptLineChange> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j]: common lines between old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let change = |kind, index: usize, text: &str| ScriptLineChange { kind, line: index + 1, text: text.to_string() };
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            changes.push(change(LineChangeKind::Added, j, new[j]));
+            j += 1;
+        } else {
+            changes.push(change(LineChangeKind::Removed, i, old[i]));
+            i += 1;
+        }
+    }
+    changes
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Script history repository
+pub struct ScriptHistoryRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ScriptHistoryRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ScriptHistoryRepository { db }
+    }
+
+    pub fn create(&self, record: &ScriptRecord) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT INTO generated_scripts
+               (script_id, client_id, template_name, config, content_hash, content, warnings,
+                output_path, generated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"#,
+            params![
+                record.script_id,
+                record.client_id,
+                record.template_name,
+                serde_json::to_string(&record.config)?,
+                record.content_hash,
+                record.content,
+                serde_json::to_string(&record.warnings)?,
+                record.output_path,
+                record.generated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, script_id: &str) -> OptioResult<Option<ScriptRecord>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM generated_scripts WHERE script_id = ?1",
+            RECORD_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query(params![script_id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(parse_record_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// A client's generations, newest first
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<ScriptRecord>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM generated_scripts WHERE client_id = ?1 ORDER BY generated_at DESC",
+            RECORD_COLUMNS
+        ))?;
+
+        let records = stmt.query_map(params![client_id], |row| Ok(parse_record_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(records)
+    }
+
+    pub fn count_by_client(&self, client_id: &str) -> OptioResult<usize> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM generated_scripts WHERE client_id = ?1",
+            params![client_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+}
+
+const RECORD_COLUMNS: &str = "script_id, client_id, template_name, config, content_hash, content, warnings, \
+    output_path, generated_at";
+
+fn parse_record_row(row: &rusqlite::Row) -> OptioResult<ScriptRecord> {
+    let config_json: String = row.get(3)?;
+    let warnings_json: String = row.get(6)?;
+
+    Ok(ScriptRecord {
+        script_id: row.get(0)?,
+        client_id: row.get(1)?,
+        template_name: row.get(2)?,
+        config: serde_json::from_str(&config_json)?,
+        content_hash: row.get(4)?,
+        content: row.get(5)?,
+        warnings: serde_json::from_str(&warnings_json)?,
+        output_path: row.get(7)?,
+        generated_at: parse_datetime(&row.get::<_, String>(8)?)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Client, ClientRepository};
+    use rusqlite::Connection;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    fn config(client_id: &str) -> ScriptConfig {
+        ScriptConfig {
+            client_id: client_id.to_string(),
+            client_name: "Test Client".to_string(),
+            target_subnet: "192.168.1.0/24".to_string(),
+            consultant_ip: "10.0.0.1".to_string(),
+            enable_winrm: true,
+            configure_dns: false,
+            dns_servers: vec![],
+            install_agent: false,
+            agent_installer: None,
+            enable_firewall_logging: true,
+            custom_commands: vec![],
+            audit_only: false,
+        }
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert!(diff_lines("a\nb\nc", "a\nb\nc").is_empty());
+
+        let changes = diff_lines("a\nb\nc", "a\nx\nc\nd");
+        assert_eq!(changes, vec![
+            ScriptLineChange { kind: LineChangeKind::Added, line: 2, text: "x".to_string() },
+            ScriptLineChange { kind: LineChangeKind::Removed, line: 2, text: "b".to_string() },
+            ScriptLineChange { kind: LineChangeKind::Added, line: 4, text: "d".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_record_and_regenerate() {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_script_history_schema(&db).unwrap();
+        let client = Client::new("Test Client".to_string(), None, None, None);
+        ClientRepository::new(&db).create(&client).unwrap();
+
+        let generated = ScriptGenerator::new(PathBuf::from("templates"))
+            .generate("smart_prep", &config(&client.id))
+            .unwrap();
+        let mut record = ScriptRecord {
+            script_id: generated.script_id.clone(),
+            client_id: client.id.clone(),
+            template_name: "smart_prep".to_string(),
+            config: config(&client.id),
+            content_hash: content_hash(&generated.content),
+            content: Some(generated.content.clone()),
+            warnings: generated.warnings.clone(),
+            output_path: None,
+            generated_at: generated.generated_at,
+        };
+
+        let repo = ScriptHistoryRepository::new(&db);
+        repo.create(&record).unwrap();
+        assert_eq!(repo.count_by_client(&client.id).unwrap(), 1);
+
+        // Replaying the stored record reproduces the script exactly
+        let stored = repo.get(&record.script_id).unwrap().unwrap();
+        let replay = regenerate(ScriptGenerator::new(PathBuf::from("templates")), &stored).unwrap();
+        assert!(replay.unchanged, "{:?}", replay.changes);
+        assert_eq!(replay.content, generated.content);
+
+        // A stored script that differs from the current output is diffed
+        record.content = Some(generated.content.replacen("Test Client", "Old Name", 1));
+        record.content_hash = content_hash(record.content.as_ref().unwrap());
+        let replay = regenerate(ScriptGenerator::new(PathBuf::from("templates")), &record).unwrap();
+        assert!(!replay.unchanged);
+        assert_eq!(replay.changes.len(), 2);
+        assert!(replay.changes.iter().any(|c| c.kind == LineChangeKind::Removed && c.text.contains("Old Name")));
+    }
+}
@@ -2,15 +2,30 @@
 //!
 //! "The Factory" is Optio's dynamic script generation engine. Instead of static
 //! downloads, it manufactures unique, state-aware PowerShell scripts for each
-//! engagement with identity injection and idempotent operations.
+//! engagement with identity injection and idempotent operations. Every
+//! generation is recorded so it can be inspected and replayed later, and
+//! signed so the client can verify it; an audit-only variant captures the
+//! current state before anything changes.
 
 use crate::error::{OptioError, OptioResult};
+use crate::network::agent_tls::AgentTlsPin;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+use std::net::IpAddr;
 use std::path::PathBuf;
+use std::process::{Command, Stdio};
 use uuid::Uuid;
 
+pub mod audit;
+pub mod history;
+pub mod signing;
+pub mod tokens;
+
+pub use audit::*;
+pub use history::*;
+
 /// Configuration for script generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScriptConfig {
@@ -36,6 +51,10 @@ pub struct ScriptConfig {
     pub enable_firewall_logging: bool,
     /// Custom PowerShell commands
     pub custom_commands: Vec<String>,
+    /// Generate the template's audit-only variant, which records the current
+    /// state of each setting to a JSON file instead of changing it
+    #[serde(default)]
+    pub audit_only: bool,
 }
 
 /// Result of script generation
@@ -70,25 +89,46 @@ pub struct TemplateInfo {
 /// Script generator engine
 pub struct ScriptGenerator {
     templates_dir: PathBuf,
+    /// Fail generation on unknown placeholders instead of warning
+    strict_placeholders: bool,
 }
 
 impl ScriptGenerator {
     /// Create a new script generator
     pub fn new(templates_dir: PathBuf) -> Self {
-        ScriptGenerator { templates_dir }
+        ScriptGenerator { templates_dir, strict_placeholders: true }
+    }
+
+    /// Report unknown placeholders as warnings rather than failing generation
+    pub fn with_strict_placeholders(mut self, strict: bool) -> Self {
+        self.strict_placeholders = strict;
+        self
     }
 
     /// Generate a script from a template with the given configuration
     pub fn generate(&self, template_name: &str, config: &ScriptConfig) -> OptioResult<GeneratedScript> {
-        let template_path = self.templates_dir.join(format!("{}.ps1", template_name));
+        self.render(template_name, config, &Uuid::new_v4().to_string(), Utc::now())
+    }
 
-        // Read the template file
-        let template_content = if template_path.exists() {
-            std::fs::read_to_string(&template_path)?
+    /// Render a template with a fixed script ID and generation time
+    ///
+    /// Replaying a stored generation this way yields byte-identical output
+    /// unless the template or generator has changed since.
+    pub fn render(
+        &self,
+        template_name: &str,
+        config: &ScriptConfig,
+        script_id: &str,
+        generated_at: DateTime<Utc>,
+    ) -> OptioResult<GeneratedScript> {
+        // Audit-only scripts come from the template's `_audit` companion
+        let template_name = if config.audit_only {
+            format!("{}_audit", template_name)
         } else {
-            // Use embedded default template
-            get_default_template(template_name)?
+            template_name.to_string()
         };
+        let template_name = template_name.as_str();
+        let template_content = self.template_content(template_name)?;
 
         // Build variable map for substitution
         let mut vars = HashMap::new();
@@ -96,8 +136,8 @@ impl ScriptGenerator {
         vars.insert("CLIENT_NAME", config.client_name.clone());
         vars.insert("TARGET_SUBNET", config.target_subnet.clone());
         vars.insert("CONSULTANT_IP", config.consultant_ip.clone());
-        vars.insert("SCRIPT_ID", Uuid::new_v4().to_string());
-        vars.insert("GENERATED_AT", Utc::now().to_rfc3339());
+        vars.insert("SCRIPT_ID", script_id.to_string());
+        vars.insert("GENERATED_AT", generated_at.to_rfc3339());
         vars.insert("ENABLE_WINRM", config.enable_winrm.to_string());
         vars.insert("CONFIGURE_DNS", config.configure_dns.to_string());
         vars.insert("DNS_SERVERS", config.dns_servers.join(","));
@@ -116,6 +156,30 @@ impl ScriptGenerator {
         };
         vars.insert("CUSTOM_COMMANDS", custom_section);
 
+        // Custom commands as a PowerShell array body, for audit scripts to inspect without running
+        let command_list = config.custom_commands.iter()
+            .map(|cmd| format!("'{}'", cmd.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        vars.insert("CUSTOM_COMMAND_LIST", command_list);
+        vars.insert("AUDIT_SCHEMA_VERSION", PREP_AUDIT_SCHEMA_VERSION.to_string());
+
+        // Check every placeholder in the template has a value
+        let unknown: Vec<String> = extract_placeholders(&template_content)
+            .into_iter()
+            .filter(|p| !vars.contains_key(p.as_str()))
+            .collect();
+        let unknown_list = unknown.iter()
+            .map(|p| format!("{{{{{}}}}}", p))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !unknown.is_empty() && self.strict_placeholders {
+            return Err(OptioError::Validation(format!(
+                "Template '{}' uses unknown placeholder(s): {}",
+                template_name, unknown_list
+            )));
+        }
+
         // Perform template substitution
         let mut content = template_content;
         for (key, value) in &vars {
@@ -125,21 +189,42 @@ impl ScriptGenerator {
 
         // Generate warnings
         let mut warnings = Vec::new();
-        if config.enable_winrm {
-            warnings.push("WinRM will be enabled - ensure this is authorized for the target environment.".to_string());
+        if !unknown.is_empty() {
+            warnings.push(format!("Unresolved placeholder(s) left in script: {}", unknown_list));
         }
-        if !config.custom_commands.is_empty() {
-            warnings.push(format!("{} custom command(s) will be executed.", config.custom_commands.len()));
+        if let Some(problem) = consultant_ip_problem(&config.consultant_ip) {
+            warnings.push(format!("{} - choose another consultant IP before deploying.", problem));
+        }
+        if config.audit_only {
+            warnings.push("Audit-only script - records current state to JSON and makes no changes.".to_string());
+        } else {
+            if config.enable_winrm {
+                warnings.push("WinRM will be enabled - ensure this is authorized for the target environment.".to_string());
+            }
+            if !config.custom_commands.is_empty() {
+                warnings.push(format!("{} custom command(s) will be executed.", config.custom_commands.len()));
+            }
         }
 
         Ok(GeneratedScript {
-            script_id: vars.get("SCRIPT_ID").unwrap().clone(),
+            script_id: script_id.to_string(),
             content,
-            generated_at: Utc::now(),
+            generated_at,
             warnings,
         })
     }
 
+    /// A template's source: the file in the templates dir, or the embedded
+    /// default of that name
+    pub fn template_content(&self, template_name: &str) -> OptioResult<String> {
+        let template_path = self.templates_dir.join(format!("{}.ps1", template_name));
+        if template_path.exists() {
+            Ok(std::fs::read_to_string(&template_path)?)
+        } else {
+            get_default_template(template_name)
+        }
+    }
+
     /// List all available templates
     pub fn list_templates(&self) -> OptioResult<Vec<TemplateInfo>> {
         let mut templates = vec![
@@ -189,11 +274,15 @@ impl ScriptGenerator {
                             continue;
                         }
 
+                        let required_vars = std::fs::read_to_string(&path)
+                            .map(|content| extract_placeholders(&content))
+                            .unwrap_or_default();
+
                         templates.push(TemplateInfo {
                             name: name.clone(),
                             description: format!("Custom template: {}", name),
                             category: "Custom".to_string(),
-                            required_vars: vec![],
+                            required_vars,
                             path: path.to_string_lossy().to_string(),
                         });
                     }
@@ -205,10 +294,408 @@ impl ScriptGenerator {
     }
 }
 
+/// Why a consultant IP can't be reached from client machines, if it can't
+///
+/// Loopback and link-local addresses only mean something on the consultant's
+/// own machine or segment, so scripts that call back to them silently fail.
+pub fn consultant_ip_problem(ip: &str) -> Option<String> {
+    let addr: IpAddr = match ip.trim().parse() {
+        Ok(addr) => addr,
+        Err(_) => return Some(format!("Consultant IP '{}' is not a valid IP address", ip)),
+    };
+    let link_local = match addr {
+        IpAddr::V4(v4) => v4.is_link_local(),
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xffc0) == 0xfe80,
+    };
+    if addr.is_loopback() {
+        Some(format!("Consultant IP {} is a loopback address and is unreachable from client machines", addr))
+    } else if link_local {
+        Some(format!("Consultant IP {} is a link-local address and is unlikely to be reachable from client machines", addr))
+    } else if addr.is_unspecified() {
+        Some(format!("Consultant IP {} is unspecified", addr))
+    } else {
+        None
+    }
+}
+
+/// Placeholders the generator fills in from a [`ScriptConfig`]
+pub const FACTORY_VARIABLES: [&str; 15] = [
+    "CLIENT_ID",
+    "CLIENT_NAME",
+    "TARGET_SUBNET",
+    "CONSULTANT_IP",
+    "SCRIPT_ID",
+    "GENERATED_AT",
+    "ENABLE_WINRM",
+    "CONFIGURE_DNS",
+    "DNS_SERVERS",
+    "INSTALL_AGENT",
+    "AGENT_INSTALLER",
+    "ENABLE_FIREWALL_LOGGING",
+    "CUSTOM_COMMANDS",
+    "CUSTOM_COMMAND_LIST",
+    "AUDIT_SCHEMA_VERSION",
+];
+
+/// Result of linting a template before it is used for generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateLint {
+    /// No unknown placeholders and no structural errors
+    pub valid: bool,
+    /// Every placeholder used, in order of appearance
+    pub variables: Vec<String>,
+    /// Placeholders the generator has no value for
+    pub unknown_variables: Vec<String>,
+    /// Unbalanced brackets, quotes or comments, or a misplaced param block
+    pub errors: Vec<ScriptSyntaxError>,
+    /// Problems that don't stop generation, like a missing `#Requires` line
+    pub warnings: Vec<String>,
+}
+
+/// Check a template's placeholders and structure
+///
+/// Uses the internal syntax check only: the PowerShell parser would see the
+/// template's placeholders rather than the values that replace them.
+pub fn lint_template(content: &str) -> TemplateLint {
+    let variables = extract_placeholders(content);
+    let unknown_variables: Vec<String> = variables
+        .iter()
+        .filter(|v| !FACTORY_VARIABLES.contains(&v.as_str()))
+        .cloned()
+        .collect();
+    let errors = check_script_syntax(content);
+
+    let mut warnings = Vec::new();
+    if !content.lines().any(|l| l.trim_start().to_lowercase().starts_with("#requires")) {
+        warnings.push("No #Requires line: add '#Requires -RunAsAdministrator' or '#Requires -Version' so the script fails fast on hosts that can't run it".to_string());
+    }
+    if content.trim().is_empty() {
+        warnings.push("Template is empty".to_string());
+    }
+
+    TemplateLint {
+        valid: unknown_variables.is_empty() && errors.is_empty(),
+        variables,
+        unknown_variables,
+        errors,
+        warnings,
+    }
+}
+
+/// Extract the distinct `{{VAR}}` placeholder names from a template, in order of appearance
+///
+/// Names are limited to ASCII letters, digits, and underscores so that
+/// PowerShell script blocks and hashtables are never mistaken for placeholders.
+pub fn extract_placeholders(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = &after[..end];
+                let valid = !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if valid {
+                    if !names.iter().any(|n| n == name) {
+                        names.push(name.to_string());
+                    }
+                    rest = &after[end + 2..];
+                } else {
+                    rest = &rest[start + 1..];
+                }
+            }
+            None => break,
+        }
+    }
+
+    names
+}
+
+/// A syntax error found in a generated script
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptSyntaxError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column number
+    pub column: usize,
+    /// Parser message
+    pub message: String,
+}
+
+impl std::fmt::Display for ScriptSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Result of a script syntax check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptSyntaxReport {
+    /// Whether the script parsed without errors
+    pub valid: bool,
+    /// Which checker ran ("pwsh", "powershell", or "internal")
+    pub checker: String,
+    /// Syntax errors, in script order
+    pub errors: Vec<ScriptSyntaxError>,
+}
+
+/// Parses stdin with the PowerShell language parser and prints one
+/// tab-separated line, column, message triple per error
+const POWERSHELL_PARSE_COMMAND: &str = "$src = [Console]::In.ReadToEnd(); \
+    $tokens = $null; $errors = $null; \
+    [void][System.Management.Automation.Language.Parser]::ParseInput($src, [ref]$tokens, [ref]$errors); \
+    foreach ($e in $errors) { \"{0}`t{1}`t{2}\" -f $e.Extent.StartLineNumber, $e.Extent.StartColumnNumber, $e.Message }";
+
+/// Check a generated script for PowerShell syntax errors
+///
+/// Uses the PowerShell parser when `pwsh` or `powershell` is on PATH and
+/// falls back to a lightweight internal check otherwise.
+pub fn validate_script_syntax(content: &str) -> ScriptSyntaxReport {
+    for shell in ["pwsh", "powershell"] {
+        if let Some(errors) = parse_with_powershell(shell, content) {
+            return ScriptSyntaxReport {
+                valid: errors.is_empty(),
+                checker: shell.to_string(),
+                errors,
+            };
+        }
+    }
+
+    let errors = check_script_syntax(content);
+    ScriptSyntaxReport {
+        valid: errors.is_empty(),
+        checker: "internal".to_string(),
+        errors,
+    }
+}
+
+/// Run the PowerShell parser over the script; None if the shell is unavailable
+fn parse_with_powershell(shell: &str, content: &str) -> Option<Vec<ScriptSyntaxError>> {
+    let mut child = Command::new(shell)
+        .args(["-NoProfile", "-NonInteractive", "-Command", POWERSHELL_PARSE_COMMAND])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    child.stdin.take()?.write_all(content.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        tracing::warn!("{} parser check failed, using internal checker", shell);
+        return None;
+    }
+
+    let errors = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            Some(ScriptSyntaxError {
+                line: parts.next()?.trim().parse().ok()?,
+                column: parts.next()?.trim().parse().ok()?,
+                message: parts.next()?.trim().to_string(),
+            })
+        })
+        .collect();
+
+    Some(errors)
+}
+
+/// Open construct tracked by the internal checker
+enum SyntaxFrame {
+    Bracket(char, usize, usize),
+    DoubleQuoted(usize, usize),
+    SubExpression(usize, usize),
+}
+
+/// Lightweight PowerShell syntax check
+///
+/// Verifies that brackets, quotes, here-strings, and block comments are
+/// balanced and that a script-level param block is the first statement.
+/// It is deliberately conservative: it does not understand the grammar, so
+/// it catches structural breakage rather than every parse error.
+pub fn check_script_syntax(content: &str) -> Vec<ScriptSyntaxError> {
+    let lines: Vec<Vec<char>> = content.lines().map(|l| l.chars().collect()).collect();
+    let mut errors = Vec::new();
+    let mut stack: Vec<SyntaxFrame> = Vec::new();
+    let mut code_lines: BTreeSet<usize> = BTreeSet::new();
+
+    let err = |line: usize, col: usize, message: String| ScriptSyntaxError {
+        line: line + 1,
+        column: col + 1,
+        message,
+    };
+
+    let (mut ln, mut col) = (0usize, 0usize);
+    while ln < lines.len() {
+        let line = &lines[ln];
+        if col >= line.len() {
+            ln += 1;
+            col = 0;
+            continue;
+        }
+        let c = line[col];
+        let next = line.get(col + 1).copied();
+        let rest_blank = |from: usize| line[from.min(line.len())..].iter().all(|ch| ch.is_whitespace());
+
+        if let Some(SyntaxFrame::DoubleQuoted(..)) = stack.last() {
+            match c {
+                '`' => col += 2,
+                '"' if next == Some('"') => col += 2,
+                '"' => {
+                    stack.pop();
+                    col += 1;
+                }
+                '$' if next == Some('(') => {
+                    stack.push(SyntaxFrame::SubExpression(ln, col));
+                    col += 2;
+                }
+                _ => col += 1,
+            }
+            continue;
+        }
+
+        if !c.is_whitespace() && c != '#' && !(c == '<' && next == Some('#')) {
+            code_lines.insert(ln);
+        }
+
+        match c {
+            '#' => col = line.len(),
+            '<' if next == Some('#') => {
+                // Block comment, possibly spanning lines
+                let (start_ln, start_col) = (ln, col);
+                col += 2;
+                let mut closed = false;
+                while ln < lines.len() {
+                    let l = &lines[ln];
+                    while col + 1 < l.len() {
+                        if l[col] == '#' && l[col + 1] == '>' {
+                            closed = true;
+                            break;
+                        }
+                        col += 1;
+                    }
+                    if closed {
+                        col += 2;
+                        break;
+                    }
+                    ln += 1;
+                    col = 0;
+                }
+                if !closed {
+                    errors.push(err(start_ln, start_col, "Unterminated block comment".to_string()));
+                }
+            }
+            '@' if matches!(next, Some('"') | Some('\'')) && rest_blank(col + 2) => {
+                // Here-string: terminator must start its own line
+                let quote = next.unwrap_or('"');
+                let (start_ln, start_col) = (ln, col);
+                ln += 1;
+                while ln < lines.len() && !(lines[ln].first() == Some(&quote) && lines[ln].get(1) == Some(&'@')) {
+                    ln += 1;
+                }
+                if ln >= lines.len() {
+                    errors.push(err(start_ln, start_col, format!("Unterminated here-string (missing {}@ at the start of a line)", quote)));
+                }
+                col = 2;
+            }
+            '\'' => {
+                let (start_ln, start_col) = (ln, col);
+                col += 1;
+                let mut closed = false;
+                while ln < lines.len() {
+                    let l = &lines[ln];
+                    while col < l.len() {
+                        if l[col] == '\'' {
+                            if l.get(col + 1) == Some(&'\'') {
+                                col += 2;
+                                continue;
+                            }
+                            closed = true;
+                            break;
+                        }
+                        col += 1;
+                    }
+                    if closed {
+                        col += 1;
+                        break;
+                    }
+                    ln += 1;
+                    col = 0;
+                }
+                if !closed {
+                    errors.push(err(start_ln, start_col, "Unterminated string literal".to_string()));
+                }
+            }
+            '"' => {
+                stack.push(SyntaxFrame::DoubleQuoted(ln, col));
+                col += 1;
+            }
+            '`' => col += 2,
+            '(' | '{' | '[' => {
+                stack.push(SyntaxFrame::Bracket(c, ln, col));
+                col += 1;
+            }
+            ')' | '}' | ']' => {
+                let expected = match c {
+                    ')' => '(',
+                    '}' => '{',
+                    _ => '[',
+                };
+                match stack.last() {
+                    Some(SyntaxFrame::Bracket(open, ..)) if *open == expected => {
+                        stack.pop();
+                    }
+                    Some(SyntaxFrame::SubExpression(..)) if c == ')' => {
+                        stack.pop();
+                    }
+                    _ => errors.push(err(ln, col, format!("Unexpected '{}'", c))),
+                }
+                col += 1;
+            }
+            _ => col += 1,
+        }
+    }
+
+    for frame in stack {
+        errors.push(match frame {
+            SyntaxFrame::Bracket(open, l, c) => err(l, c, format!("Missing closing bracket for '{}'", open)),
+            SyntaxFrame::DoubleQuoted(l, c) => err(l, c, "Unterminated string literal".to_string()),
+            SyntaxFrame::SubExpression(l, c) => err(l, c, "Unterminated subexpression '$('".to_string()),
+        });
+    }
+
+    // A script-level param block may only be preceded by comments,
+    // attributes such as [CmdletBinding()], and using statements
+    let param_line = lines.iter().position(|l| {
+        let text: String = l.iter().collect::<String>().to_ascii_lowercase();
+        text.strip_prefix("param").map(|r| r.trim_start().starts_with('(')).unwrap_or(false)
+    });
+    if let Some(param_ln) = param_line {
+        let preceded_by_statement = code_lines.range(..param_ln).any(|&l| {
+            let text: String = lines[l].iter().collect::<String>();
+            let text = text.trim_start();
+            !(text.starts_with('[') || text.to_ascii_lowercase().starts_with("using "))
+        });
+        if preceded_by_statement {
+            errors.push(err(param_ln, 0, "param block must be the first statement in the script".to_string()));
+        }
+    }
+
+    errors.sort_by_key(|e| (e.line, e.column));
+    errors
+}
+
 /// Get the default embedded template content
 fn get_default_template(name: &str) -> OptioResult<String> {
     match name {
         "smart_prep" => Ok(SMART_PREP_TEMPLATE.to_string()),
+        "smart_prep_audit" => Ok(SMART_PREP_AUDIT_TEMPLATE.to_string()),
         "winrm_setup" => Ok(WINRM_SETUP_TEMPLATE.to_string()),
         "security_baseline" => Ok(SECURITY_BASELINE_TEMPLATE.to_string()),
         "agent_deploy" => Ok(AGENT_DEPLOY_TEMPLATE.to_string()),
@@ -361,6 +848,124 @@ Write-OptioLog "Log file: $Script:LogPath" "INFO"
 Write-OptioLog "========================================" "INFO"
 "#;
 
+/// Smart Prep audit template - read-only state capture before Smart Prep runs
+const SMART_PREP_AUDIT_TEMPLATE: &str = r#"<#
+.SYNOPSIS
+    Optio Smart Prep Audit - Pre-Change State Capture
+.DESCRIPTION
+    Dynamically generated by Optio Factory for client: {{CLIENT_NAME}}
+    Script ID: {{SCRIPT_ID}}
+    Generated: {{GENERATED_AT}}
+
+    Read-only companion to the Smart Prep script. Records the current state
+    of every setting Smart Prep would change and writes it to a JSON file
+    for import into Optio. Nothing on this system is modified.
+.NOTES
+    Consultant IP: {{CONSULTANT_IP}}
+    Target Subnet: {{TARGET_SUBNET}}
+#>
+
+param(
+    [string]$OutputPath = (Join-Path $PSScriptRoot "optio_prep_audit_$($env:COMPUTERNAME).json")
+)
+
+$ErrorActionPreference = "Continue"
+$customCommands = @({{CUSTOM_COMMAND_LIST}})
+
+function Get-OptioWinRMState {
+    $service = Get-Service WinRM -ErrorAction SilentlyContinue
+    $trustedHosts = $null
+    try {
+        $trustedHosts = (Get-Item WSMan:\localhost\Client\TrustedHosts -ErrorAction Stop).Value
+    } catch {
+        $trustedHosts = $null
+    }
+    return [ordered]@{
+        serviceStatus = if ($service) { "$($service.Status)" } else { "NotInstalled" }
+        startType     = if ($service) { "$($service.StartType)" } else { $null }
+        trustedHosts  = $trustedHosts
+    }
+}
+
+function Get-OptioDnsState {
+    $adapters = Get-NetAdapter -ErrorAction SilentlyContinue | Where-Object { $_.Status -eq 'Up' }
+    foreach ($adapter in $adapters) {
+        $dns = Get-DnsClientServerAddress -InterfaceIndex $adapter.ifIndex -AddressFamily IPv4 -ErrorAction SilentlyContinue
+        [ordered]@{
+            interface = $adapter.Name
+            servers   = @($dns.ServerAddresses)
+        }
+    }
+}
+
+function Get-OptioFirewallLoggingState {
+    foreach ($fwProfile in (Get-NetFirewallProfile -Profile Domain,Private,Public -ErrorAction SilentlyContinue)) {
+        [ordered]@{
+            profile     = "$($fwProfile.Name)"
+            logAllowed  = ("$($fwProfile.LogAllowed)" -eq "True")
+            logBlocked  = ("$($fwProfile.LogBlocked)" -eq "True")
+            logFileName = $fwProfile.LogFileName
+        }
+    }
+}
+
+function Get-OptioInstalledAgents {
+    $uninstallKeys = @(
+        "HKLM:\SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall\*",
+        "HKLM:\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall\*"
+    )
+    $pattern = "Agent|Defender|Sentinel|CrowdStrike|Falcon|Sysmon|Carbon Black|Sophos|Wazuh|Tanium|Qualys|Rapid7|Tenable|Nessus"
+    Get-ItemProperty $uninstallKeys -ErrorAction SilentlyContinue |
+        Where-Object { $_.DisplayName -match $pattern } |
+        ForEach-Object {
+            [ordered]@{
+                name      = $_.DisplayName
+                version   = $_.DisplayVersion
+                publisher = $_.Publisher
+            }
+        }
+}
+
+function Get-OptioCustomCommandTargets {
+    foreach ($command in $customCommands) {
+        $target = ($command.Trim() -split '\s+')[0]
+        [ordered]@{
+            command   = $command
+            target    = $target
+            available = [bool](Get-Command $target -ErrorAction SilentlyContinue)
+        }
+    }
+}
+
+Write-Host "Optio Smart Prep Audit - {{CLIENT_NAME}}"
+Write-Host "Collecting current state (no changes will be made)..."
+
+$audit = [ordered]@{
+    schemaVersion   = {{AUDIT_SCHEMA_VERSION}}
+    scriptId        = "{{SCRIPT_ID}}"
+    clientId        = "{{CLIENT_ID}}"
+    hostname        = $env:COMPUTERNAME
+    collectedAt     = (Get-Date).ToUniversalTime().ToString("o")
+    intended        = [ordered]@{
+        enableWinrm           = ("{{ENABLE_WINRM}}" -eq "true")
+        configureDns          = ("{{CONFIGURE_DNS}}" -eq "true")
+        dnsServers            = @("{{DNS_SERVERS}}" -split "," | Where-Object { $_ -ne "" })
+        installAgent          = ("{{INSTALL_AGENT}}" -eq "true")
+        agentInstaller        = "{{AGENT_INSTALLER}}"
+        enableFirewallLogging = ("{{ENABLE_FIREWALL_LOGGING}}" -eq "true")
+        consultantIp          = "{{CONSULTANT_IP}}"
+    }
+    winrm           = Get-OptioWinRMState
+    dns             = @(Get-OptioDnsState)
+    firewallLogging = @(Get-OptioFirewallLoggingState)
+    installedAgents = @(Get-OptioInstalledAgents)
+    customCommands  = @(Get-OptioCustomCommandTargets)
+}
+
+ConvertTo-Json -InputObject $audit -Depth 6 | Set-Content -Path $OutputPath -Encoding UTF8
+Write-Host "Audit written to $OutputPath - import it into Optio for the pre-change report."
+"#;
+
 /// WinRM Setup template
 const WINRM_SETUP_TEMPLATE: &str = r#"<#
 .SYNOPSIS
@@ -524,8 +1129,8 @@ pub struct AgentScriptConfig {
     pub auth_token: String,
     /// Callback port (default: 443)
     pub callback_port: u16,
-    /// Enable TLS for callback connection
-    pub use_tls: bool,
+    /// Listener certificate to connect over TLS to; None for plain TCP
+    pub tls: Option<AgentTlsPin>,
     /// Heartbeat interval in seconds
     pub heartbeat_interval: u32,
 }
@@ -536,7 +1141,7 @@ impl Default for AgentScriptConfig {
             client_ip: String::new(),
             auth_token: String::new(),
             callback_port: 443,
-            use_tls: true,
+            tls: None,
             heartbeat_interval: 30,
         }
     }
@@ -557,7 +1162,9 @@ pub fn generate_agent_script(config: &AgentScriptConfig) -> OptioResult<Generate
     vars.insert("CLIENT_IP", config.client_ip.clone());
     vars.insert("AUTH_TOKEN", config.auth_token.clone());
     vars.insert("CALLBACK_PORT", config.callback_port.to_string());
-    vars.insert("USE_TLS", if config.use_tls { "true" } else { "false" }.to_string());
+    vars.insert("USE_TLS", if config.tls.is_some() { "true" } else { "false" }.to_string());
+    vars.insert("TLS_SERVER_NAME", config.tls.as_ref().map(|pin| pin.server_name.clone()).unwrap_or_default());
+    vars.insert("TLS_FINGERPRINT", config.tls.as_ref().map(|pin| pin.fingerprint.clone()).unwrap_or_default());
     vars.insert("HEARTBEAT_INTERVAL", config.heartbeat_interval.to_string());
     vars.insert("SCRIPT_ID", Uuid::new_v4().to_string());
     vars.insert("GENERATED_AT", Utc::now().to_rfc3339());
@@ -571,7 +1178,7 @@ pub fn generate_agent_script(config: &AgentScriptConfig) -> OptioResult<Generate
 
     // Generate warnings
     let mut warnings = Vec::new();
-    if !config.use_tls {
+    if config.tls.is_none() {
         warnings.push("TLS is disabled - connection will not be encrypted!".to_string());
     }
     if config.callback_port != 443 && config.callback_port != 8443 {
@@ -618,6 +1225,8 @@ $script:Config = @{
     ServerPort = {{CALLBACK_PORT}}
     AuthToken = "{{AUTH_TOKEN}}"
     UseTLS = ${{USE_TLS}}
+    TlsServerName = "{{TLS_SERVER_NAME}}"
+    TlsFingerprint = "{{TLS_FINGERPRINT}}"
     HeartbeatInterval = {{HEARTBEAT_INTERVAL}}
     ScriptId = "{{SCRIPT_ID}}"
 }
@@ -662,8 +1271,16 @@ function Connect-OptioServer {
 
         if ($Config.UseTLS) {
             Write-AgentLog "Establishing TLS connection..."
-            $sslStream = New-Object System.Net.Security.SslStream($stream, $false)
-            $sslStream.AuthenticateAsClient($Config.ServerIP)
+            # The listener's certificate is self-signed; trust only the one this script was generated for
+            $validate = [System.Net.Security.RemoteCertificateValidationCallback]{
+                param($source, $certificate, $chain, $errors)
+                if ($null -eq $certificate) { return $false }
+                $sha256 = [System.Security.Cryptography.SHA256]::Create()
+                $hash = [System.BitConverter]::ToString($sha256.ComputeHash($certificate.GetRawCertData())) -replace '-', ''
+                return $hash -eq $script:Config.TlsFingerprint
+            }
+            $sslStream = New-Object System.Net.Security.SslStream($stream, $false, $validate)
+            $sslStream.AuthenticateAsClient($Config.TlsServerName, $null, [System.Security.Authentication.SslProtocols]::Tls12, $false)
             $stream = $sslStream
         }
 
@@ -835,6 +1452,7 @@ mod tests {
             agent_installer: None,
             enable_firewall_logging: true,
             custom_commands: vec![],
+            audit_only: false,
         };
 
         let result = generator.generate("smart_prep", &config).unwrap();
@@ -843,6 +1461,161 @@ mod tests {
         assert!(result.content.contains("Test Client"));
         assert!(result.content.contains("192.168.1.0/24"));
         assert!(result.content.contains("10.0.0.1"));
+
+        // Audit-only swaps in the read-only variant, fully rendered
+        let audit = ScriptConfig {
+            custom_commands: vec!["Set-MpPreference -DisableRealtimeMonitoring $false".to_string()],
+            audit_only: true,
+            ..config
+        };
+        let result = generator.generate("smart_prep", &audit).unwrap();
+        assert!(result.content.contains("schemaVersion"));
+        assert!(result.content.contains("'Set-MpPreference -DisableRealtimeMonitoring $false'"));
+        assert!(!result.content.contains("Enable-PSRemoting"));
+        assert!(extract_placeholders(&result.content).is_empty());
+        assert!(check_script_syntax(&result.content).is_empty());
+    }
+
+    #[test]
+    fn test_consultant_ip_problem() {
+        assert_eq!(consultant_ip_problem("10.0.0.1"), None);
+        assert_eq!(consultant_ip_problem("2001:db8::1"), None);
+        assert!(consultant_ip_problem("127.0.0.1").unwrap().contains("loopback"));
+        assert!(consultant_ip_problem("::1").unwrap().contains("loopback"));
+        assert!(consultant_ip_problem("169.254.10.1").unwrap().contains("link-local"));
+        assert!(consultant_ip_problem("fe80::1").unwrap().contains("link-local"));
+        assert!(consultant_ip_problem("not-an-ip").unwrap().contains("not a valid"));
+
+        let generator = ScriptGenerator::new(PathBuf::from("templates"));
+        let config = ScriptConfig {
+            client_id: "test-123".to_string(),
+            client_name: "Test Client".to_string(),
+            target_subnet: "192.168.1.0/24".to_string(),
+            consultant_ip: "127.0.0.1".to_string(),
+            enable_winrm: false,
+            configure_dns: false,
+            dns_servers: vec![],
+            install_agent: false,
+            agent_installer: None,
+            enable_firewall_logging: false,
+            custom_commands: vec![],
+            audit_only: false,
+        };
+        let result = generator.generate("smart_prep", &config).unwrap();
+        assert!(result.warnings.iter().any(|w| w.contains("loopback")));
+    }
+
+    #[test]
+    fn test_unknown_placeholders() {
+        assert_eq!(
+            extract_placeholders("{{CLIENT_NAME}} @{ a = 1 } {{{{ {{NOT_A_VAR}} {{CLIENT_NAME}}"),
+            vec!["CLIENT_NAME".to_string(), "NOT_A_VAR".to_string()]
+        );
+
+        let dir = std::env::temp_dir().join(format!("optio-templates-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("custom.ps1"), "Write-Host \"{{CLIENT_NAME}} {{NOT_A_VAR}}\"").unwrap();
+
+        let config = ScriptConfig {
+            client_id: "test-123".to_string(),
+            client_name: "Test Client".to_string(),
+            target_subnet: "192.168.1.0/24".to_string(),
+            consultant_ip: "10.0.0.1".to_string(),
+            enable_winrm: false,
+            configure_dns: false,
+            dns_servers: vec![],
+            install_agent: false,
+            agent_installer: None,
+            enable_firewall_logging: false,
+            custom_commands: vec![],
+            audit_only: false,
+        };
+
+        let strict = ScriptGenerator::new(dir.clone());
+        match strict.generate("custom", &config) {
+            Err(OptioError::Validation(msg)) => assert!(msg.contains("{{NOT_A_VAR}}")),
+            other => panic!("expected validation error, got {:?}", other.map(|s| s.content)),
+        }
+
+        let lenient = ScriptGenerator::new(dir.clone()).with_strict_placeholders(false);
+        let result = lenient.generate("custom", &config).unwrap();
+        assert!(result.content.contains("Test Client {{NOT_A_VAR}}"));
+        assert!(result.warnings.iter().any(|w| w.contains("{{NOT_A_VAR}}")));
+
+        let templates = strict.list_templates().unwrap();
+        let custom = templates.iter().find(|t| t.name == "custom").unwrap();
+        assert_eq!(custom.required_vars, vec!["CLIENT_NAME".to_string(), "NOT_A_VAR".to_string()]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_internal_syntax_check() {
+        for name in ["smart_prep", "smart_prep_audit", "winrm_setup", "security_baseline", "agent_deploy"] {
+            let template = get_default_template(name).unwrap();
+            assert!(check_script_syntax(&template).is_empty(), "{} should parse", name);
+        }
+
+        let agent = generate_agent_script(&AgentScriptConfig {
+            client_ip: "192.168.1.100".to_string(),
+            auth_token: "token".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(check_script_syntax(&agent.content).is_empty());
+
+        let broken = "function Test-It {\n    Write-Host \"open $(Get-Date\"\n";
+        let errors = check_script_syntax(broken);
+        assert_eq!(errors.first().map(|e| e.line), Some(1));
+        assert!(errors.iter().any(|e| e.message.contains("'{'")));
+
+        let errors = check_script_syntax("Write-Host 'a'\nparam([string]$Name)\n");
+        assert_eq!(errors, vec![ScriptSyntaxError {
+            line: 2,
+            column: 1,
+            message: "param block must be the first statement in the script".to_string(),
+        }]);
+
+        assert_eq!(check_script_syntax("$x = @\"\nhello }\n").len(), 1);
+        assert!(check_script_syntax("[CmdletBinding()]\nparam()\n$x = @'\n} ' \"\n'@\n").is_empty());
+    }
+
+    #[test]
+    fn test_lint_template() {
+        // Every Factory variable is substituted, so a template using them all renders cleanly
+        let dir = std::env::temp_dir().join(format!("optio-templates-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let all_vars = FACTORY_VARIABLES.iter().map(|v| format!("# {{{{{}}}}}", v)).collect::<Vec<_>>().join("\n");
+        std::fs::write(dir.join("all_vars.ps1"), &all_vars).unwrap();
+        let generator = ScriptGenerator::new(dir.clone());
+        let config = ScriptConfig {
+            client_id: "test-123".to_string(),
+            client_name: "Test Client".to_string(),
+            target_subnet: "192.168.1.0/24".to_string(),
+            consultant_ip: "10.0.0.1".to_string(),
+            enable_winrm: false,
+            configure_dns: false,
+            dns_servers: vec![],
+            install_agent: false,
+            agent_installer: None,
+            enable_firewall_logging: false,
+            custom_commands: vec![],
+            audit_only: false,
+        };
+        assert!(extract_placeholders(&generator.generate("all_vars", &config).unwrap().content).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        for name in ["smart_prep", "winrm_setup", "security_baseline", "agent_deploy"] {
+            let lint = lint_template(&generator.template_content(name).unwrap());
+            assert!(lint.valid && lint.warnings.is_empty(), "{} should lint cleanly: {:?}", name, lint);
+        }
+
+        let lint = lint_template("param(\n    [string]$Name = \"{{CLIENT_NAME}}\"\n\nWrite-Host {{CLIENT_NAME}} {{TYPO_VAR}}\n");
+        assert!(!lint.valid);
+        assert_eq!(lint.variables, vec!["CLIENT_NAME".to_string(), "TYPO_VAR".to_string()]);
+        assert_eq!(lint.unknown_variables, vec!["TYPO_VAR".to_string()]);
+        assert!(lint.errors.iter().any(|e| e.line == 1 && e.message.contains("'('")));
+        assert!(lint.warnings[0].contains("#Requires"));
     }
 
     #[test]
@@ -860,7 +1633,10 @@ mod tests {
             client_ip: "192.168.1.100".to_string(),
             auth_token: "secret-token-123".to_string(),
             callback_port: 443,
-            use_tls: true,
+            tls: Some(AgentTlsPin {
+                server_name: "0123abcd.agents.optio.invalid".to_string(),
+                fingerprint: "AB".repeat(32),
+            }),
             heartbeat_interval: 30,
         };
 
@@ -870,7 +1646,11 @@ mod tests {
         assert!(result.content.contains("192.168.1.100"));
         assert!(result.content.contains("secret-token-123"));
         assert!(result.content.contains("443"));
+        assert!(result.content.contains("UseTLS = $true"));
+        assert!(result.content.contains(&format!("TlsFingerprint = \"{}\"", "AB".repeat(32))));
+        assert!(result.content.contains("TlsServerName = \"0123abcd.agents.optio.invalid\""));
         assert!(result.warnings.is_empty()); // Should have no warnings with default port and TLS
+        assert!(check_script_syntax(&result.content).is_empty());
     }
 
     #[test]
@@ -0,0 +1,389 @@
+//! Script Integrity Manifests
+//!
+//! Every generated script is signed so the client's security team can check
+//! it wasn't altered in transit. The manifest carries the SHA-256 of the
+//! script, its ID and generation time, signed with an ed25519 key that is
+//! created on first use and kept in the app data dir. The same fields are
+//! appended to the script as a PowerShell comment footer, so the manifest can
+//! be rebuilt from the script file alone.
+//!
+//! Rotating the key retires the old one but keeps its public half, so
+//! scripts signed before the rotation still verify.
+
+use crate::error::{OptioError, OptioResult};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Private key file, inside the signing dir
+const SIGNING_KEY_FILE: &str = "signing_key";
+
+/// Current and retired public keys, inside the signing dir
+const PUBLIC_KEYS_FILE: &str = "public_keys.json";
+
+/// Version tag at the start of the signed payload
+const MANIFEST_PAYLOAD_VERSION: &str = "optio-script-manifest/v1";
+
+const FOOTER_START: &str = "# ---- Optio integrity manifest ----";
+const FOOTER_END: &str = "# ---- end of manifest ----";
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Signed integrity manifest for a generated script
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptManifest {
+    pub script_id: String,
+    pub generated_at: DateTime<Utc>,
+    /// Hex SHA-256 of the script without its manifest footer
+    pub sha256: String,
+    /// Identifies the signing key
+    pub key_id: String,
+    /// Base64 ed25519 public key of the signer
+    pub public_key: String,
+    /// Base64 ed25519 signature over the script ID, time and hash
+    pub signature: String,
+}
+
+/// A signing public key, current or retired
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningKeyInfo {
+    pub key_id: String,
+    /// Base64 ed25519 public key
+    pub public_key: String,
+    pub created_at: DateTime<Utc>,
+    /// When the key was replaced by a rotation; `None` for the current key
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of checking a script against its manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptVerification {
+    /// Hash and signature both check out
+    pub valid: bool,
+    /// The script's hash matches the manifest
+    pub hash_matches: bool,
+    /// The manifest's signature is valid for its public key
+    pub signature_valid: bool,
+    /// The signing key is one of ours, current or retired
+    pub known_key: bool,
+    /// The manifest checked, from the request or the script's footer
+    pub manifest: Option<ScriptManifest>,
+    pub problems: Vec<String>,
+}
+
+// ============================================================================
+// Signing
+// ============================================================================
+
+/// Signs scripts with the key stored in a directory
+pub struct ScriptSigner {
+    dir: PathBuf,
+    key: SigningKey,
+    keys: Vec<SigningKeyInfo>,
+}
+
+impl ScriptSigner {
+    /// Load the signing key from `dir`, generating one on first use
+    pub fn load_or_create(dir: &Path) -> OptioResult<Self> {
+        std::fs::create_dir_all(dir)?;
+        let keys = read_public_keys(dir)?;
+
+        let key_path = dir.join(SIGNING_KEY_FILE);
+        if key_path.exists() {
+            let encoded = std::fs::read_to_string(&key_path)?;
+            let bytes: [u8; 32] = BASE64
+                .decode(encoded.trim())
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or_else(|| OptioError::Encryption("Signing key file is corrupt".to_string()))?;
+            let mut signer = ScriptSigner { dir: dir.to_path_buf(), key: SigningKey::from_bytes(&bytes), keys };
+            // A key file without its public key entry, e.g. after the list was deleted
+            if !signer.keys.iter().any(|k| k.key_id == signer.key_id()) {
+                signer.keys.push(signer.current_key_info());
+                write_public_keys(dir, &signer.keys)?;
+            }
+            return Ok(signer);
+        }
+
+        let mut signer = ScriptSigner { dir: dir.to_path_buf(), key: SigningKey::generate(&mut OsRng), keys };
+        signer.store_new_key()?;
+        tracing::info!("Generated script signing key {}", signer.key_id());
+        Ok(signer)
+    }
+
+    pub fn key_id(&self) -> String {
+        key_id(&self.key.verifying_key())
+    }
+
+    /// Current and retired public keys, oldest first
+    pub fn public_keys(&self) -> &[SigningKeyInfo] {
+        &self.keys
+    }
+
+    /// Sign a script's content, ID and generation time
+    pub fn sign(&self, content: &str, script_id: &str, generated_at: DateTime<Utc>) -> ScriptManifest {
+        let sha256 = content_sha256(content);
+        let signature = self.key.sign(signed_payload(script_id, generated_at, &sha256).as_bytes());
+
+        ScriptManifest {
+            script_id: script_id.to_string(),
+            generated_at,
+            sha256,
+            key_id: self.key_id(),
+            public_key: BASE64.encode(self.key.verifying_key().as_bytes()),
+            signature: BASE64.encode(signature.to_bytes()),
+        }
+    }
+
+    /// Replace the signing key, keeping the old public key for verification
+    pub fn rotate(&mut self) -> OptioResult<SigningKeyInfo> {
+        let retired = self.key_id();
+        let now = Utc::now();
+        for key in self.keys.iter_mut().filter(|k| k.key_id == retired) {
+            key.retired_at = Some(now);
+        }
+
+        self.key = SigningKey::generate(&mut OsRng);
+        self.store_new_key()?;
+        tracing::info!("Rotated script signing key {} to {}", retired, self.key_id());
+        Ok(self.current_key_info())
+    }
+
+    fn current_key_info(&self) -> SigningKeyInfo {
+        let verifying_key = self.key.verifying_key();
+        SigningKeyInfo {
+            key_id: key_id(&verifying_key),
+            public_key: BASE64.encode(verifying_key.as_bytes()),
+            created_at: Utc::now(),
+            retired_at: None,
+        }
+    }
+
+    /// Write the private key readable by the owner only, then record its public key
+    fn store_new_key(&mut self) -> OptioResult<()> {
+        let encoded = BASE64.encode(self.key.to_bytes());
+        let path = self.dir.join(SIGNING_KEY_FILE);
+        let partial = path.with_extension("partial");
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&partial)?;
+        file.write_all(encoded.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&partial, &path)?;
+
+        self.keys.push(self.current_key_info());
+        write_public_keys(&self.dir, &self.keys)
+    }
+}
+
+fn read_public_keys(dir: &Path) -> OptioResult<Vec<SigningKeyInfo>> {
+    let path = dir.join(PUBLIC_KEYS_FILE);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn write_public_keys(dir: &Path, keys: &[SigningKeyInfo]) -> OptioResult<()> {
+    std::fs::write(dir.join(PUBLIC_KEYS_FILE), serde_json::to_string_pretty(keys)?)?;
+    Ok(())
+}
+
+/// Short identifier of a public key: the first 16 hex digits of its SHA-256
+fn key_id(key: &VerifyingKey) -> String {
+    hex(&Sha256::digest(key.as_bytes()))[..16].to_string()
+}
+
+fn content_sha256(content: &str) -> String {
+    hex(&Sha256::digest(content.as_bytes()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// What gets signed; fixed field order so it doesn't depend on JSON layout
+fn signed_payload(script_id: &str, generated_at: DateTime<Utc>, sha256: &str) -> String {
+    format!("{}\n{}\n{}\n{}", MANIFEST_PAYLOAD_VERSION, script_id, generated_at.to_rfc3339(), sha256)
+}
+
+// ============================================================================
+// Footer
+// ============================================================================
+
+/// The manifest as a PowerShell comment block, to append to the script
+pub fn manifest_footer(manifest: &ScriptManifest) -> String {
+    format!(
+        "\n{}\n# ScriptId: {}\n# Generated: {}\n# SHA256: {}\n# KeyId: {}\n# PublicKey: {}\n# Signature: {}\n{}\n",
+        FOOTER_START,
+        manifest.script_id,
+        manifest.generated_at.to_rfc3339(),
+        manifest.sha256,
+        manifest.key_id,
+        manifest.public_key,
+        manifest.signature,
+        FOOTER_END,
+    )
+}
+
+/// Split a script into its content and the manifest in its footer, if it
+/// has one
+pub fn split_manifest_footer(script: &str) -> (&str, Option<ScriptManifest>) {
+    let start = match script.rfind(&format!("\n{}\n", FOOTER_START)) {
+        Some(start) => start,
+        None => return (script, None),
+    };
+    let footer = &script[start..];
+
+    let field = |name: &str| {
+        footer
+            .lines()
+            .find_map(|l| l.strip_prefix(&format!("# {}: ", name)))
+            .map(|v| v.trim().to_string())
+    };
+    let manifest = (|| {
+        Some(ScriptManifest {
+            script_id: field("ScriptId")?,
+            generated_at: DateTime::parse_from_rfc3339(&field("Generated")?).ok()?.with_timezone(&Utc),
+            sha256: field("SHA256")?,
+            key_id: field("KeyId")?,
+            public_key: field("PublicKey")?,
+            signature: field("Signature")?,
+        })
+    })();
+
+    match manifest {
+        Some(manifest) => (&script[..start], Some(manifest)),
+        None => (script, None),
+    }
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+/// Check a script against a manifest, or against the manifest in its own
+/// footer when none is given
+///
+/// `known_keys` are the public keys to trust; a manifest signed by any
+/// other key can be internally consistent but is not `valid`.
+pub fn verify_script(script: &str, manifest: Option<&ScriptManifest>, known_keys: &[SigningKeyInfo]) -> ScriptVerification {
+    let (content, footer) = split_manifest_footer(script);
+    let manifest = match manifest.cloned().or(footer) {
+        Some(manifest) => manifest,
+        None => {
+            return ScriptVerification {
+                valid: false,
+                hash_matches: false,
+                signature_valid: false,
+                known_key: false,
+                manifest: None,
+                problems: vec!["No manifest given and the script has no manifest footer".to_string()],
+            }
+        }
+    };
+
+    let mut problems = Vec::new();
+    let hash_matches = content_sha256(content) == manifest.sha256;
+    if !hash_matches {
+        problems.push("Script content does not match the manifest hash; it was changed after signing".to_string());
+    }
+
+    let known_key = known_keys.iter().any(|k| k.key_id == manifest.key_id && k.public_key == manifest.public_key);
+    if !known_key {
+        problems.push(format!("Signing key {} is not a known Optio key", manifest.key_id));
+    }
+
+    let signature_valid = match decode_key_and_signature(&manifest) {
+        Some((key, signature)) => {
+            let payload = signed_payload(&manifest.script_id, manifest.generated_at, &manifest.sha256);
+            key.verify(payload.as_bytes(), &signature).is_ok()
+        }
+        None => false,
+    };
+    if !signature_valid {
+        problems.push("Manifest signature is invalid".to_string());
+    }
+
+    ScriptVerification {
+        valid: hash_matches && signature_valid && known_key,
+        hash_matches,
+        signature_valid,
+        known_key,
+        manifest: Some(manifest),
+        problems,
+    }
+}
+
+fn decode_key_and_signature(manifest: &ScriptManifest) -> Option<(VerifyingKey, Signature)> {
+    let key: [u8; 32] = BASE64.decode(&manifest.public_key).ok()?.try_into().ok()?;
+    let signature: [u8; 64] = BASE64.decode(&manifest.signature).ok()?.try_into().ok()?;
+    Some((VerifyingKey::from_bytes(&key).ok()?, Signature::from_bytes(&signature)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_verify_and_rotate() {
+        let dir = std::env::temp_dir().join(format!("optio-signing-{}", uuid::Uuid::new_v4()));
+        let mut signer = ScriptSigner::load_or_create(&dir).unwrap();
+        let script = "#Requires -RunAsAdministrator\nWrite-Host \"Hello\"\n";
+        let manifest = signer.sign(script, "script-1", Utc::now());
+
+        let signed = format!("{}{}", script, manifest_footer(&manifest));
+        let (content, footer) = split_manifest_footer(&signed);
+        assert_eq!(content, script);
+        assert_eq!(footer.as_ref(), Some(&manifest));
+
+        // From the manifest, or from the footer alone
+        assert!(verify_script(script, Some(&manifest), signer.public_keys()).valid);
+        assert!(verify_script(&signed, None, signer.public_keys()).valid);
+
+        let tampered = signed.replace("Hello", "Goodbye");
+        let result = verify_script(&tampered, None, signer.public_keys());
+        assert!(!result.valid && !result.hash_matches && result.signature_valid);
+
+        let mut forged = manifest.clone();
+        forged.sha256 = content_sha256("Write-Host \"Goodbye\"\n");
+        let result = verify_script("Write-Host \"Goodbye\"\n", Some(&forged), signer.public_keys());
+        assert!(result.hash_matches && !result.signature_valid);
+
+        // The key survives a reload, and rotation keeps the old public key
+        let old_key = signer.key_id();
+        let reloaded = ScriptSigner::load_or_create(&dir).unwrap();
+        assert_eq!(reloaded.key_id(), old_key);
+        let new_key = signer.rotate().unwrap();
+        assert_ne!(new_key.key_id, old_key);
+        let keys = ScriptSigner::load_or_create(&dir).unwrap().public_keys().to_vec();
+        assert_eq!(keys.len(), 2);
+        assert!(keys[0].retired_at.is_some() && keys[1].retired_at.is_none());
+        assert!(verify_script(&signed, None, &keys).valid);
+        assert!(!verify_script(&signed, None, &keys[1..]).known_key);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.join(SIGNING_KEY_FILE)).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,490 @@
+//! Agent Auth Tokens
+//!
+//! Tokens that agent callback scripts present to Optio. Each token is
+//! generated from the OS random source; the database keeps only a salted
+//! SHA-256 hash and the last four characters, while the plaintext sits in the
+//! OS credential store until the Factory embeds it in a script. Revoking a
+//! token deletes the plaintext and makes validation reject it; a token past
+//! its expiry is rejected the same way. Successful validations record when the
+//! token was last used.
+//!
+//! Tokens carry a fixed prefix so they can be redacted from any text (previews,
+//! logs, stored script content) without knowing their value.
+
+use crate::db::{add_missing_columns, Database};
+use crate::error::{OptioError, OptioResult};
+use crate::grc::repository::parse_datetime;
+use crate::secrets::SecretStore;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::OsRng, RngCore};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Prefix of every agent token
+pub const AGENT_TOKEN_PREFIX: &str = "optio_at_";
+/// Random bytes per token
+const TOKEN_BYTES: usize = 32;
+const SALT_BYTES: usize = 16;
+/// Characters of a token left visible when redacted
+const VISIBLE_CHARS: usize = 4;
+/// Longest lifetime a token can be issued with
+pub const MAX_TOKEN_TTL_DAYS: u32 = 365;
+
+/// Initialize agent token schema
+pub fn init_agent_token_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Agent auth tokens (salted hash only; the plaintext is in the OS credential store)
+        CREATE TABLE IF NOT EXISTS agent_tokens (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            token_hash TEXT NOT NULL,
+            salt TEXT NOT NULL,
+            last_four TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            revoked_at TEXT,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_agent_tokens_client ON agent_tokens(client_id, created_at);
+    "#)?;
+
+    add_missing_columns(&conn, "agent_tokens", &[
+        ("label", "TEXT"),
+        ("expires_at", "TEXT"),
+        ("last_used_at", "TEXT"),
+    ])?;
+
+    tracing::info!("Agent token schema initialized");
+    Ok(())
+}
+
+/// Agent token metadata; never includes the token itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentToken {
+    pub id: String,
+    pub client_id: String,
+    /// Last four characters, to tell tokens apart
+    pub last_four: String,
+    /// What the token is for, e.g. the site or host group it was issued to
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// None for tokens that never expire
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Last successful agent authentication with this token
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl AgentToken {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Neither revoked nor expired
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        !self.is_revoked() && !self.is_expired(now)
+    }
+
+    /// How the token appears once redacted
+    pub fn redacted(&self) -> String {
+        format!("{}****{}", AGENT_TOKEN_PREFIX, self.last_four)
+    }
+}
+
+/// New random agent token
+fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    format!("{}{}", AGENT_TOKEN_PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hex-encoded SHA-256 of the salt followed by the token
+fn hash_token(salt: &str, token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(token.as_bytes());
+    hex(&hasher.finalize())
+}
+
+/// Compare without stopping at the first difference
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Whether `c` can appear in a token (the URL-safe base64 alphabet)
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Last `VISIBLE_CHARS` characters of a token
+fn visible_suffix(token: &str) -> String {
+    let skip = token.chars().count().saturating_sub(VISIBLE_CHARS);
+    token.chars().skip(skip).collect()
+}
+
+/// Replace every agent token in `text` with its prefix and last four characters
+pub fn redact_agent_tokens(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(AGENT_TOKEN_PREFIX) {
+        let (before, from_prefix) = rest.split_at(start);
+        redacted.push_str(before);
+
+        let body = &from_prefix[AGENT_TOKEN_PREFIX.len()..];
+        let body_len = body
+            .find(|c: char| !is_token_char(c))
+            .unwrap_or(body.len());
+        let visible = &body[body_len.saturating_sub(VISIBLE_CHARS)..body_len];
+
+        redacted.push_str(AGENT_TOKEN_PREFIX);
+        redacted.push_str("****");
+        redacted.push_str(visible);
+        rest = &body[body_len..];
+    }
+
+    redacted.push_str(rest);
+    redacted
+}
+
+/// Repository for agent tokens
+pub struct AgentTokenRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AgentTokenRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Generate a token for a client, keeping the plaintext in `store`
+    ///
+    /// `ttl_days` of None issues a token that never expires.
+    pub fn create(
+        &self,
+        client_id: &str,
+        label: Option<&str>,
+        ttl_days: Option<u32>,
+        store: &dyn SecretStore,
+    ) -> OptioResult<AgentToken> {
+        if let Some(days) = ttl_days {
+            if days == 0 || days > MAX_TOKEN_TTL_DAYS {
+                return Err(OptioError::Validation(format!(
+                    "Token lifetime must be between 1 and {} days", MAX_TOKEN_TTL_DAYS
+                )));
+            }
+        }
+
+        let secret = generate_token();
+        let mut salt = [0u8; SALT_BYTES];
+        OsRng.fill_bytes(&mut salt);
+        let salt = hex(&salt);
+        let now = Utc::now();
+
+        let token = AgentToken {
+            id: Uuid::new_v4().to_string(),
+            client_id: client_id.to_string(),
+            last_four: visible_suffix(&secret),
+            label: label.map(str::trim).filter(|l| !l.is_empty()).map(String::from),
+            created_at: now,
+            expires_at: ttl_days.map(|days| now + Duration::days(days.into())),
+            revoked_at: None,
+            last_used_at: None,
+        };
+
+        store.set(&token.id, &secret)?;
+
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let inserted = conn.execute(
+            "INSERT INTO agent_tokens (id, client_id, token_hash, salt, last_four, label, created_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                token.id,
+                token.client_id,
+                hash_token(&salt, &secret),
+                salt,
+                token.last_four,
+                token.label,
+                token.created_at.to_rfc3339(),
+                token.expires_at.map(|t| t.to_rfc3339()),
+            ],
+        );
+        if let Err(e) = inserted {
+            // Don't leave an orphaned secret behind
+            drop(conn);
+            if let Err(cleanup) = store.delete(&token.id) {
+                tracing::warn!("Failed to remove secret for unsaved agent token {}: {}", token.id, cleanup);
+            }
+            return Err(e.into());
+        }
+
+        Ok(token)
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<AgentToken>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM agent_tokens WHERE id = ?1", TOKEN_COLUMNS))?;
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(parse_token_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// A client's tokens, newest first
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<AgentToken>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM agent_tokens WHERE client_id = ?1 ORDER BY created_at DESC",
+            TOKEN_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query(params![client_id])?;
+        let mut tokens = Vec::new();
+        while let Some(row) = rows.next()? {
+            tokens.push(parse_token_row(row)?);
+        }
+        Ok(tokens)
+    }
+
+    /// Fetch a live token's plaintext for embedding in a script
+    ///
+    /// Fails for revoked or expired tokens and for a stored secret that no
+    /// longer matches the recorded hash.
+    pub fn reveal(&self, id: &str, store: &dyn SecretStore) -> OptioResult<String> {
+        let token = self.get(id)?
+            .ok_or_else(|| OptioError::Validation(format!("Agent token not found: {}", id)))?;
+        if token.is_revoked() {
+            return Err(OptioError::Validation(format!("Agent token {} has been revoked", token.redacted())));
+        }
+        if token.is_expired(Utc::now()) {
+            return Err(OptioError::Validation(format!("Agent token {} has expired", token.redacted())));
+        }
+
+        let secret = store.get(id)?
+            .ok_or_else(|| OptioError::Encryption(format!("Agent token {} is missing from the credential store", token.redacted())))?;
+        let (salt, hash) = self.hash_of(id)?;
+        if !constant_time_eq(&hash_token(&salt, &secret), &hash) {
+            return Err(OptioError::Encryption(format!("Agent token {} does not match its stored hash", token.redacted())));
+        }
+        Ok(secret)
+    }
+
+    /// Revoke a token and delete its plaintext; revoking twice keeps the first time
+    pub fn revoke(&self, id: &str, store: &dyn SecretStore) -> OptioResult<Option<AgentToken>> {
+        {
+            let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+            conn.execute(
+                "UPDATE agent_tokens SET revoked_at = ?2 WHERE id = ?1 AND revoked_at IS NULL",
+                params![id, Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        let token = self.get(id)?;
+        if token.is_some() {
+            store.delete(id)?;
+        }
+        Ok(token)
+    }
+
+    /// The live token matching a presented value, if any
+    ///
+    /// This is the check for agent callbacks: revoked and expired tokens never
+    /// match. A match is recorded as the token's last use.
+    pub fn validate(&self, presented: &str) -> OptioResult<Option<AgentToken>> {
+        // Presented tokens come from the network; anything outside the token
+        // alphabet can't match, so it's turned away before the lock is taken
+        if !presented.starts_with(AGENT_TOKEN_PREFIX) || !presented.chars().all(is_token_char) {
+            return Ok(None);
+        }
+        let last_four = visible_suffix(presented);
+
+        let candidates: Vec<(String, String, String)> = {
+            let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+            let mut stmt = conn.prepare(
+                "SELECT id, salt, token_hash FROM agent_tokens WHERE revoked_at IS NULL AND last_four = ?1",
+            )?;
+            let rows = stmt
+                .query_map(params![last_four], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        let now = Utc::now();
+        for (id, salt, hash) in candidates {
+            if !constant_time_eq(&hash_token(&salt, presented), &hash) {
+                continue;
+            }
+            let mut token = match self.get(&id)? {
+                Some(token) if !token.is_expired(now) => token,
+                _ => return Ok(None),
+            };
+
+            let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+            conn.execute(
+                "UPDATE agent_tokens SET last_used_at = ?2 WHERE id = ?1",
+                params![id, now.to_rfc3339()],
+            )?;
+            token.last_used_at = Some(now);
+            return Ok(Some(token));
+        }
+        Ok(None)
+    }
+
+    fn hash_of(&self, id: &str) -> OptioResult<(String, String)> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.query_row(
+            "SELECT salt, token_hash FROM agent_tokens WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(Into::into)
+    }
+}
+
+const TOKEN_COLUMNS: &str = "id, client_id, last_four, created_at, revoked_at, label, expires_at, last_used_at";
+
+fn parse_token_row(row: &rusqlite::Row) -> OptioResult<AgentToken> {
+    let revoked_at: Option<String> = row.get(4)?;
+    let expires_at: Option<String> = row.get(6)?;
+    let last_used_at: Option<String> = row.get(7)?;
+
+    Ok(AgentToken {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        last_four: row.get(2)?,
+        label: row.get(5)?,
+        created_at: parse_datetime(&row.get::<_, String>(3)?)?,
+        expires_at: expires_at.as_deref().map(parse_datetime).transpose()?,
+        revoked_at: revoked_at.as_deref().map(parse_datetime).transpose()?,
+        last_used_at: last_used_at.as_deref().map(parse_datetime).transpose()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::MemorySecretStore;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_agent_token_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_token_lifecycle() {
+        let db = test_db();
+        let store = MemorySecretStore::default();
+        let repo = AgentTokenRepository::new(&db);
+
+        let token = repo.create("client-1", Some(" Site A "), Some(30), &store).unwrap();
+        assert_eq!(token.label.as_deref(), Some("Site A"));
+        assert!(token.expires_at.unwrap() > Utc::now() + Duration::days(29));
+        let secret = repo.reveal(&token.id, &store).unwrap();
+        assert!(secret.starts_with(AGENT_TOKEN_PREFIX));
+        assert!(secret.ends_with(&token.last_four));
+        assert_ne!(secret, generate_token());
+
+        // Only the salted hash reaches the database
+        let stored: String = db.conn.lock().unwrap()
+            .query_row("SELECT token_hash || salt || last_four FROM agent_tokens", [], |r| r.get(0))
+            .unwrap();
+        assert!(!stored.contains(&secret[AGENT_TOKEN_PREFIX.len()..]));
+
+        assert!(repo.get(&token.id).unwrap().unwrap().last_used_at.is_none());
+        assert_eq!(repo.validate(&secret).unwrap().unwrap().id, token.id);
+        assert!(repo.get(&token.id).unwrap().unwrap().last_used_at.is_some());
+        assert!(repo.validate(&format!("{}x", secret)).unwrap().is_none());
+
+        // A tampered credential store entry is caught
+        store.set(&token.id, &generate_token()).unwrap();
+        assert!(repo.reveal(&token.id, &store).is_err());
+        store.set(&token.id, &secret).unwrap();
+
+        let revoked = repo.revoke(&token.id, &store).unwrap().unwrap();
+        assert!(revoked.is_revoked());
+        assert!(store.get(&token.id).unwrap().is_none());
+        assert!(repo.validate(&secret).unwrap().is_none());
+        assert!(repo.reveal(&token.id, &store).is_err());
+        assert_eq!(repo.list_by_client("client-1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_expired_tokens_fail_auth() {
+        let db = test_db();
+        let store = MemorySecretStore::default();
+        let repo = AgentTokenRepository::new(&db);
+
+        assert!(repo.create("client-1", None, Some(0), &store).is_err());
+        assert!(repo.create("client-1", None, Some(MAX_TOKEN_TTL_DAYS + 1), &store).is_err());
+
+        let token = repo.create("client-1", None, Some(1), &store).unwrap();
+        let secret = repo.reveal(&token.id, &store).unwrap();
+        db.conn.lock().unwrap().execute(
+            "UPDATE agent_tokens SET expires_at = ?1",
+            params![(Utc::now() - Duration::minutes(1)).to_rfc3339()],
+        ).unwrap();
+
+        let expired = repo.get(&token.id).unwrap().unwrap();
+        assert!(expired.is_expired(Utc::now()));
+        assert!(!expired.is_active(Utc::now()));
+        assert!(repo.validate(&secret).unwrap().is_none());
+        assert!(repo.reveal(&token.id, &store).is_err());
+
+        let forever = repo.create("client-1", None, None, &store).unwrap();
+        assert!(forever.is_active(Utc::now() + Duration::days(10_000)));
+    }
+
+    #[test]
+    fn test_malformed_tokens_are_rejected() {
+        let db = test_db();
+        let store = MemorySecretStore::default();
+        let repo = AgentTokenRepository::new(&db);
+        let token = repo.create("client-1", None, None, &store).unwrap();
+
+        for presented in ["optio_at_\u{20ac}\u{20ac}", "optio_at_ab\u{e9}", "optio_at_ abc", "optio_at_"] {
+            assert!(repo.validate(presented).unwrap().is_none());
+        }
+        assert_eq!(visible_suffix("x\u{20ac}\u{20ac}\u{20ac}\u{20ac}"), "\u{20ac}\u{20ac}\u{20ac}\u{20ac}");
+
+        // The database is still usable afterwards
+        let secret = repo.reveal(&token.id, &store).unwrap();
+        assert_eq!(repo.validate(&secret).unwrap().unwrap().id, token.id);
+    }
+
+    #[test]
+    fn test_redact_agent_tokens() {
+        let secret = generate_token();
+        let script = format!("$AuthToken = \"{}\"\n# {}", secret, secret);
+        let redacted = redact_agent_tokens(&script);
+        let expected = format!("optio_at_****{}", &secret[secret.len() - 4..]);
+        assert_eq!(redacted, format!("$AuthToken = \"{}\"\n# {}", expected, expected));
+        assert_eq!(redact_agent_tokens("no tokens here"), "no tokens here");
+    }
+}
@@ -0,0 +1,272 @@
+//! CVSS v3.1 Scoring
+//!
+//! Parses CVSS v3.1 base vectors (e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`)
+//! and calculates the base score with the formulas from the FIRST
+//! specification. Temporal and environmental metrics are not supported.
+
+use crate::network::models::Criticality;
+use serde::{Deserialize, Serialize};
+
+const CVSS_PREFIX: &str = "CVSS:3.1";
+
+/// Base metrics in the order the specification writes them
+const BASE_METRICS: [&str; 8] = ["AV", "AC", "PR", "UI", "S", "C", "I", "A"];
+
+/// A parsed CVSS v3.1 base vector, one value letter per metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CvssVector {
+    pub attack_vector: char,
+    pub attack_complexity: char,
+    pub privileges_required: char,
+    pub user_interaction: char,
+    pub scope: char,
+    pub confidentiality: char,
+    pub integrity: char,
+    pub availability: char,
+}
+
+/// Base score of a vector, with its subscores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CvssScore {
+    /// The vector in canonical metric order
+    pub vector: String,
+    pub base_score: f64,
+    pub impact_subscore: f64,
+    pub exploitability_subscore: f64,
+    /// Qualitative rating; a score of 0.0 ("None") maps to informational
+    pub severity: Criticality,
+}
+
+impl CvssVector {
+    /// Parse a base vector; metrics may appear in any order but each exactly once
+    pub fn parse(vector: &str) -> Result<Self, String> {
+        let mut parts = vector.trim().split('/');
+        if parts.next() != Some(CVSS_PREFIX) {
+            return Err(format!("CVSS vector must start with \"{}/\"", CVSS_PREFIX));
+        }
+
+        let mut values: [Option<char>; 8] = [None; 8];
+        for part in parts {
+            let (metric, value) = part
+                .split_once(':')
+                .ok_or_else(|| format!("Malformed CVSS metric \"{}\"", part))?;
+            let index = BASE_METRICS
+                .iter()
+                .position(|m| *m == metric)
+                .ok_or_else(|| format!("Unsupported CVSS metric \"{}\"", metric))?;
+            let mut chars = value.chars();
+            let letter = match (chars.next(), chars.next()) {
+                (Some(letter), None) if allowed_values(metric).contains(letter) => letter,
+                _ => return Err(format!("Invalid value \"{}\" for CVSS metric {}", value, metric)),
+            };
+            if values[index].replace(letter).is_some() {
+                return Err(format!("CVSS metric {} is given more than once", metric));
+            }
+        }
+
+        let missing: Vec<&str> = BASE_METRICS
+            .iter()
+            .zip(values.iter())
+            .filter(|(_, v)| v.is_none())
+            .map(|(m, _)| *m)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("CVSS vector is missing metrics: {}", missing.join(", ")));
+        }
+
+        let value = |i: usize| values[i].unwrap_or_default();
+        Ok(CvssVector {
+            attack_vector: value(0),
+            attack_complexity: value(1),
+            privileges_required: value(2),
+            user_interaction: value(3),
+            scope: value(4),
+            confidentiality: value(5),
+            integrity: value(6),
+            availability: value(7),
+        })
+    }
+
+    fn letters(&self) -> [char; 8] {
+        [
+            self.attack_vector,
+            self.attack_complexity,
+            self.privileges_required,
+            self.user_interaction,
+            self.scope,
+            self.confidentiality,
+            self.integrity,
+            self.availability,
+        ]
+    }
+
+    /// Whether a successful attack can affect components beyond the vulnerable one
+    pub fn scope_changed(&self) -> bool {
+        self.scope == 'C'
+    }
+
+    pub fn score(&self) -> CvssScore {
+        let changed = self.scope_changed();
+        let attack_vector: f64 = match self.attack_vector {
+            'N' => 0.85,
+            'A' => 0.62,
+            'L' => 0.55,
+            _ => 0.2,
+        };
+        let attack_complexity = if self.attack_complexity == 'L' { 0.77 } else { 0.44 };
+        let privileges_required = match (self.privileges_required, changed) {
+            ('N', _) => 0.85,
+            ('L', false) => 0.62,
+            ('L', true) => 0.68,
+            (_, false) => 0.27,
+            (_, true) => 0.5,
+        };
+        let user_interaction = if self.user_interaction == 'N' { 0.85 } else { 0.62 };
+        let cia = |value: char| match value {
+            'H' => 0.56,
+            'L' => 0.22,
+            _ => 0.0,
+        };
+
+        let iss: f64 = 1.0
+            - (1.0 - cia(self.confidentiality)) * (1.0 - cia(self.integrity)) * (1.0 - cia(self.availability));
+        let impact: f64 = if changed {
+            7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15)
+        } else {
+            6.42 * iss
+        };
+        let exploitability: f64 = 8.22 * attack_vector * attack_complexity * privileges_required * user_interaction;
+
+        let base_score = if impact <= 0.0 {
+            0.0
+        } else if changed {
+            roundup((1.08 * (impact + exploitability)).min(10.0))
+        } else {
+            roundup((impact + exploitability).min(10.0))
+        };
+
+        CvssScore {
+            vector: self.to_string(),
+            base_score,
+            impact_subscore: round1(impact.max(0.0)),
+            exploitability_subscore: round1(exploitability),
+            severity: cvss_severity(base_score),
+        }
+    }
+}
+
+impl std::fmt::Display for CvssVector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", CVSS_PREFIX)?;
+        for (metric, value) in BASE_METRICS.iter().zip(self.letters()) {
+            write!(f, "/{}:{}", metric, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse and score a base vector
+pub fn score_cvss_vector(vector: &str) -> Result<CvssScore, String> {
+    CvssVector::parse(vector).map(|v| v.score())
+}
+
+/// Qualitative severity rating of a base score
+pub fn cvss_severity(score: f64) -> Criticality {
+    if score >= 9.0 {
+        Criticality::Critical
+    } else if score >= 7.0 {
+        Criticality::High
+    } else if score >= 4.0 {
+        Criticality::Medium
+    } else if score > 0.0 {
+        Criticality::Low
+    } else {
+        Criticality::Informational
+    }
+}
+
+fn allowed_values(metric: &str) -> &'static str {
+    match metric {
+        "AV" => "NALP",
+        "AC" => "LH",
+        "PR" | "C" | "I" | "A" => "NLH",
+        "UI" => "NR",
+        "S" => "UC",
+        _ => "",
+    }
+}
+
+/// Round up to one decimal, as defined in CVSS v3.1 Appendix A to avoid
+/// floating point artifacts (e.g. 4.000001 must not become 4.1)
+fn roundup(value: f64) -> f64 {
+    let int_input = (value * 100_000.0).round() as i64;
+    if int_input % 10_000 == 0 {
+        int_input as f64 / 100_000.0
+    } else {
+        ((int_input / 10_000) + 1) as f64 / 10.0
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_scores() {
+        let cases = [
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H", 9.8, Criticality::Critical),
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H", 10.0, Criticality::Critical),
+            ("CVSS:3.1/AV:L/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:H", 7.8, Criticality::High),
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N", 6.1, Criticality::Medium),
+            ("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:N/A:N", 5.9, Criticality::Medium),
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:L/I:N/A:N", 5.3, Criticality::Medium),
+            ("CVSS:3.1/AV:N/AC:L/PR:H/UI:R/S:C/C:L/I:L/A:N", 4.8, Criticality::Medium),
+            ("CVSS:3.1/AV:P/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N", 1.6, Criticality::Low),
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N", 0.0, Criticality::Informational),
+        ];
+
+        for (vector, expected, severity) in cases {
+            let score = score_cvss_vector(vector).unwrap();
+            assert_eq!(score.base_score, expected, "{}", vector);
+            assert_eq!(score.severity, severity, "{}", vector);
+            assert_eq!(score.vector, vector);
+        }
+    }
+
+    #[test]
+    fn test_metric_order_is_normalized() {
+        let score = score_cvss_vector(" CVSS:3.1/A:H/I:H/C:H/S:U/UI:N/PR:N/AC:L/AV:N ").unwrap();
+        assert_eq!(score.vector, "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H");
+        assert_eq!(score.base_score, 9.8);
+    }
+
+    #[test]
+    fn test_malformed_vectors_are_rejected() {
+        for vector in [
+            "",
+            "AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+            "CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H",
+            "CVSS:3.1/AV:X/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:HH",
+            "CVSS:3.1/AV:N/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/E:F",
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A",
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/",
+        ] {
+            assert!(score_cvss_vector(vector).is_err(), "accepted {:?}", vector);
+        }
+    }
+
+    #[test]
+    fn test_roundup() {
+        assert_eq!(roundup(4.000_001), 4.0);
+        assert_eq!(roundup(4.02), 4.1);
+        assert_eq!(roundup(10.0), 10.0);
+    }
+}
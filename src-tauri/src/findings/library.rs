@@ -0,0 +1,745 @@
+//! Finding Library
+//!
+//! Reusable finding templates ("Missing MFA", "Deprecated TLS") and the
+//! client findings instantiated from them. Templates shipped in code can be
+//! overridden by a user template with the same id; a finding copies its
+//! template's text so later template edits don't change delivered reports.
+
+use super::cvss::score_cvss_vector;
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::repository::parse_datetime;
+use crate::network::models::{Criticality, FindingStatus};
+use crate::network::repository::parse_criticality;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Initialize finding library schema
+pub fn init_findings_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- User finding templates and overrides of built-in ones (references and tags as JSON)
+        CREATE TABLE IF NOT EXISTS finding_templates (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            cvss_vector TEXT,
+            description TEXT NOT NULL,
+            impact TEXT NOT NULL,
+            recommendation TEXT NOT NULL,
+            refs TEXT NOT NULL,
+            tags TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Client findings (references, tags and linked asset ids as JSON)
+        CREATE TABLE IF NOT EXISTS findings (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            template_id TEXT,
+            title TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            cvss_vector TEXT,
+            description TEXT NOT NULL,
+            impact TEXT NOT NULL,
+            recommendation TEXT NOT NULL,
+            refs TEXT NOT NULL,
+            tags TEXT NOT NULL,
+            asset_ids TEXT NOT NULL,
+            assessment_id TEXT,
+            status TEXT NOT NULL,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_findings_client ON findings(client_id);
+    "#)?;
+
+    tracing::info!("Finding library schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// A reusable finding write-up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindingTemplate {
+    /// Stable identifier; a user template with a built-in's id replaces it
+    pub id: String,
+    pub title: String,
+    /// Severity given to findings created from the template
+    pub severity: Criticality,
+    /// CVSS v3.1 base vector
+    pub cvss_vector: Option<String>,
+    /// Base score of `cvss_vector`
+    pub cvss_score: Option<f64>,
+    pub description: String,
+    pub impact: String,
+    pub recommendation: String,
+    /// Standards, advisories or articles backing the finding
+    pub references: Vec<String>,
+    pub tags: Vec<String>,
+    /// Shipped in code rather than added by the user
+    #[serde(default)]
+    pub built_in: bool,
+}
+
+/// A finding recorded against a client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Finding {
+    pub id: String,
+    pub client_id: String,
+    /// Template the finding was created from, if any
+    pub template_id: Option<String>,
+    pub title: String,
+    pub severity: Criticality,
+    pub cvss_vector: Option<String>,
+    pub cvss_score: Option<f64>,
+    pub description: String,
+    pub impact: String,
+    pub recommendation: String,
+    pub references: Vec<String>,
+    pub tags: Vec<String>,
+    /// Affected assets from the client's inventory
+    pub asset_ids: Vec<String>,
+    /// Assessment the finding was raised in
+    pub assessment_id: Option<String>,
+    pub status: FindingStatus,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Changes to a finding's details; unset fields keep their current value
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindingOverrides {
+    pub title: Option<String>,
+    pub severity: Option<Criticality>,
+    /// An empty vector clears the CVSS score
+    pub cvss_vector: Option<String>,
+    pub description: Option<String>,
+    pub impact: Option<String>,
+    pub recommendation: Option<String>,
+    pub references: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub asset_ids: Option<Vec<String>>,
+    pub assessment_id: Option<String>,
+    pub status: Option<FindingStatus>,
+    pub notes: Option<String>,
+}
+
+impl Finding {
+    /// Whether the finding still needs fixing
+    pub fn is_open(&self) -> bool {
+        self.status != FindingStatus::Remediated
+    }
+
+    /// Apply overrides to the finding
+    ///
+    /// A new CVSS vector also resets the severity to the vector's rating,
+    /// unless a severity is given alongside it.
+    pub fn apply(&mut self, overrides: FindingOverrides) -> Result<(), String> {
+        if let Some(vector) = overrides.cvss_vector {
+            if vector.trim().is_empty() {
+                self.cvss_vector = None;
+                self.cvss_score = None;
+            } else {
+                let score = score_cvss_vector(&vector)?;
+                self.severity = score.severity;
+                self.cvss_vector = Some(score.vector);
+                self.cvss_score = Some(score.base_score);
+            }
+        }
+        if let Some(title) = overrides.title {
+            if title.trim().is_empty() {
+                return Err("Finding title is required".to_string());
+            }
+            self.title = title.trim().to_string();
+        }
+        if let Some(severity) = overrides.severity {
+            self.severity = severity;
+        }
+        if let Some(description) = overrides.description {
+            self.description = description;
+        }
+        if let Some(impact) = overrides.impact {
+            self.impact = impact;
+        }
+        if let Some(recommendation) = overrides.recommendation {
+            self.recommendation = recommendation;
+        }
+        if let Some(references) = overrides.references {
+            self.references = clean_list(references);
+        }
+        if let Some(tags) = overrides.tags {
+            self.tags = clean_list(tags);
+        }
+        if let Some(asset_ids) = overrides.asset_ids {
+            self.asset_ids = clean_list(asset_ids);
+        }
+        if let Some(assessment_id) = overrides.assessment_id {
+            self.assessment_id = Some(assessment_id).filter(|a| !a.trim().is_empty());
+        }
+        if let Some(status) = overrides.status {
+            self.status = status;
+        }
+        if let Some(notes) = overrides.notes {
+            self.notes = Some(notes).filter(|n| !n.trim().is_empty());
+        }
+        Ok(())
+    }
+}
+
+/// Create a client finding from a template, with any overrides applied
+pub fn instantiate_finding(
+    template: &FindingTemplate,
+    client_id: &str,
+    overrides: FindingOverrides,
+) -> Result<Finding, String> {
+    let now = Utc::now();
+    let mut finding = Finding {
+        id: Uuid::new_v4().to_string(),
+        client_id: client_id.to_string(),
+        template_id: Some(template.id.clone()),
+        title: template.title.clone(),
+        severity: template.severity,
+        cvss_vector: template.cvss_vector.clone(),
+        cvss_score: template.cvss_score,
+        description: template.description.clone(),
+        impact: template.impact.clone(),
+        recommendation: template.recommendation.clone(),
+        references: template.references.clone(),
+        tags: template.tags.clone(),
+        asset_ids: vec![],
+        assessment_id: None,
+        status: FindingStatus::Open,
+        notes: None,
+        created_at: now,
+        updated_at: now,
+    };
+    finding.apply(overrides)?;
+    Ok(finding)
+}
+
+/// Most severe first; within a severity, highest CVSS score first and unscored last
+pub fn sort_findings(findings: &mut [Finding]) {
+    findings.sort_by(|a, b| {
+        a.severity.rank().cmp(&b.severity.rank())
+            .then_with(|| b.cvss_score.unwrap_or(-1.0).total_cmp(&a.cvss_score.unwrap_or(-1.0)))
+    });
+}
+
+fn clean_list(items: Vec<String>) -> Vec<String> {
+    items
+        .into_iter()
+        .map(|i| i.trim().to_string())
+        .filter(|i| !i.is_empty())
+        .collect()
+}
+
+/// Score a template's vector, checking it parses
+pub fn score_template(template: &mut FindingTemplate) -> Result<(), String> {
+    template.cvss_score = match template.cvss_vector.as_deref().filter(|v| !v.trim().is_empty()) {
+        Some(vector) => {
+            let score = score_cvss_vector(vector)?;
+            template.cvss_vector = Some(score.vector);
+            Some(score.base_score)
+        }
+        None => {
+            template.cvss_vector = None;
+            None
+        }
+    };
+    Ok(())
+}
+
+// ============================================================================
+// Built-in Templates
+// ============================================================================
+
+/// Finding templates shipped with Optio
+pub fn default_finding_templates() -> Vec<FindingTemplate> {
+    let template = |id: &str,
+                    title: &str,
+                    severity: Criticality,
+                    cvss_vector: Option<&str>,
+                    description: &str,
+                    impact: &str,
+                    recommendation: &str,
+                    references: &[&str],
+                    tags: &[&str]| {
+        let mut template = FindingTemplate {
+            id: id.to_string(),
+            title: title.to_string(),
+            severity,
+            cvss_vector: cvss_vector.map(|v| v.to_string()),
+            cvss_score: None,
+            description: description.to_string(),
+            impact: impact.to_string(),
+            recommendation: recommendation.to_string(),
+            references: references.iter().map(|r| r.to_string()).collect(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            built_in: true,
+        };
+        // Built-in vectors are covered by tests
+        let _ = score_template(&mut template);
+        template
+    };
+
+    vec![
+        template(
+            "missing-mfa",
+            "Missing Multi-Factor Authentication",
+            Criticality::High,
+            Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:H/A:N"),
+            "Remote access services and administrative portals accept a password as the only authentication factor.",
+            "A phished, reused or brute-forced password gives an attacker full access to the account and the systems it can reach.",
+            "Enforce multi-factor authentication for all remote access, email and privileged accounts, preferring phishing-resistant methods such as FIDO2.",
+            &["CIS Controls v8 6.3-6.5", "NIST SP 800-63B", "OWASP ASVS V2"],
+            &["identity", "authentication"],
+        ),
+        template(
+            "deprecated-tls",
+            "Deprecated TLS Protocols Enabled",
+            Criticality::Medium,
+            Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:N/A:N"),
+            "Services negotiate TLS 1.0 or TLS 1.1, which were formally deprecated in 2021.",
+            "An attacker in a privileged network position may downgrade connections and exploit known protocol weaknesses to read traffic.",
+            "Disable TLS 1.0 and 1.1 and allow only TLS 1.2 and 1.3 with strong cipher suites.",
+            &["RFC 8996", "NIST SP 800-52 Rev. 2"],
+            &["encryption", "network"],
+        ),
+        template(
+            "missing-security-headers",
+            "Missing HTTP Security Headers",
+            Criticality::Medium,
+            Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"),
+            "Web applications do not send Content-Security-Policy, X-Frame-Options or X-Content-Type-Options headers.",
+            "Browsers apply no additional protection against cross-site scripting, clickjacking and MIME sniffing attacks.",
+            "Configure a restrictive Content-Security-Policy, X-Frame-Options: DENY (or frame-ancestors), X-Content-Type-Options: nosniff and Strict-Transport-Security.",
+            &["OWASP Secure Headers Project", "OWASP ASVS V14.4"],
+            &["web"],
+        ),
+        template(
+            "default-credentials",
+            "Default Credentials in Use",
+            Criticality::Critical,
+            Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            "Devices or applications accept the vendor's published default username and password.",
+            "Anyone with network access can log in with administrative rights using publicly documented credentials.",
+            "Change all default credentials before deployment, store them in a password manager and include the check in build procedures.",
+            &["CIS Controls v8 4.7", "OWASP Top 10 A07:2021"],
+            &["identity", "configuration"],
+        ),
+        template(
+            "smbv1-enabled",
+            "SMBv1 Protocol Enabled",
+            Criticality::High,
+            Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            "Windows hosts accept connections over the SMBv1 protocol.",
+            "SMBv1 lacks modern security features and is exploited by wormable malware such as WannaCry and NotPetya.",
+            "Disable SMBv1 on all servers and clients and block SMB at the network perimeter.",
+            &["Microsoft Security Bulletin MS17-010", "CISA Alert TA17-132A"],
+            &["windows", "network"],
+        ),
+        template(
+            "cleartext-protocols",
+            "Cleartext Administrative Protocols",
+            Criticality::High,
+            Some("CVSS:3.1/AV:A/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:N"),
+            "Devices are managed over Telnet, FTP or HTTP, which send credentials and data unencrypted.",
+            "Anyone able to observe the network traffic can capture administrative credentials and session contents.",
+            "Replace Telnet with SSH, FTP with SFTP and HTTP management interfaces with HTTPS, and disable the cleartext services.",
+            &["CIS Controls v8 3.10", "NIST SP 800-53 SC-8"],
+            &["network", "encryption"],
+        ),
+        template(
+            "weak-password-policy",
+            "Weak Password Policy",
+            Criticality::Medium,
+            Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:L/I:L/A:N"),
+            "The password policy allows short passwords and does not check new passwords against known breached passwords.",
+            "Users can choose passwords that are easily guessed or cracked, making credential attacks more likely to succeed.",
+            "Require a minimum length of 14 characters, screen passwords against breached password lists and enable account lockout or throttling.",
+            &["NIST SP 800-63B 5.1.1", "CIS Controls v8 5.2"],
+            &["identity", "authentication"],
+        ),
+        template(
+            "unsupported-software",
+            "Unsupported Software Versions",
+            Criticality::High,
+            Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            "Systems run operating systems or applications that no longer receive security updates from the vendor.",
+            "Newly discovered vulnerabilities will remain unpatched, leaving the systems permanently exposed.",
+            "Upgrade to a supported version, or isolate the systems and purchase extended support until they can be replaced.",
+            &["CIS Controls v8 2.2", "NIST SP 800-40 Rev. 4"],
+            &["patching", "lifecycle"],
+        ),
+        template(
+            "excessive-local-admin",
+            "Excessive Local Administrator Rights",
+            Criticality::High,
+            Some("CVSS:3.1/AV:L/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:H"),
+            "Standard users are members of the local Administrators group on their workstations.",
+            "Malware running as the user gains full control of the machine and can harvest credentials for lateral movement.",
+            "Remove local administrator rights from standard accounts and provide just-in-time elevation where needed, with LAPS for local admin passwords.",
+            &["CIS Controls v8 5.4", "Microsoft LAPS"],
+            &["identity", "endpoint"],
+        ),
+        template(
+            "missing-disk-encryption",
+            "Missing Full Disk Encryption",
+            Criticality::Medium,
+            Some("CVSS:3.1/AV:P/AC:L/PR:N/UI:N/S:U/C:H/I:N/A:N"),
+            "Laptops and workstations store data on unencrypted disks.",
+            "Data on a lost or stolen device can be read by anyone with physical access to it.",
+            "Enable BitLocker or FileVault on all endpoints and escrow recovery keys centrally.",
+            &["CIS Controls v8 3.6", "NIST SP 800-111"],
+            &["encryption", "endpoint"],
+        ),
+        template(
+            "insufficient-logging",
+            "Insufficient Security Logging and Monitoring",
+            Criticality::Medium,
+            None,
+            "Security-relevant events are not collected centrally, retained or reviewed.",
+            "Attacks may go unnoticed, and investigations lack the records needed to establish what happened.",
+            "Forward authentication, administrative and security events to a central log platform, retain them for at least 90 days and alert on suspicious activity.",
+            &["CIS Controls v8 8", "OWASP Top 10 A09:2021"],
+            &["monitoring"],
+        ),
+    ]
+}
+
+/// Merge user templates over the built-in ones: overrides keep the built-in's place, additions follow
+pub fn merge_finding_templates(defaults: Vec<FindingTemplate>, user: Vec<FindingTemplate>) -> Vec<FindingTemplate> {
+    let mut templates: Vec<FindingTemplate> = defaults
+        .into_iter()
+        .map(|default| match user.iter().find(|u| u.id == default.id) {
+            Some(user_template) => FindingTemplate { built_in: true, ..user_template.clone() },
+            None => default,
+        })
+        .collect();
+    for user_template in user {
+        if !templates.iter().any(|t| t.id == user_template.id) {
+            templates.push(FindingTemplate { built_in: false, ..user_template });
+        }
+    }
+    templates
+}
+
+/// Templates in effect: the built-ins with user overrides, then user additions
+pub fn finding_templates(db: &Database) -> OptioResult<Vec<FindingTemplate>> {
+    let user = FindingTemplateRepository::new(db).list()?;
+    Ok(merge_finding_templates(default_finding_templates(), user))
+}
+
+// ============================================================================
+// Repositories
+// ============================================================================
+
+/// User finding template repository
+pub struct FindingTemplateRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> FindingTemplateRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        FindingTemplateRepository { db }
+    }
+
+    /// Insert or replace a template
+    pub fn save(&self, template: &FindingTemplate) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO finding_templates
+               (id, title, severity, cvss_vector, description, impact, recommendation, refs, tags, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#,
+            params![
+                template.id,
+                template.title,
+                format!("{:?}", template.severity),
+                template.cvss_vector,
+                template.description,
+                template.impact,
+                template.recommendation,
+                serde_json::to_string(&template.references)?,
+                serde_json::to_string(&template.tags)?,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// User templates in the order they were first added
+    pub fn list(&self) -> OptioResult<Vec<FindingTemplate>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            r#"SELECT id, title, severity, cvss_vector, description, impact, recommendation, refs, tags
+               FROM finding_templates ORDER BY rowid"#
+        )?;
+
+        let templates = stmt.query_map([], |row| Ok(parse_template_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(templates)
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM finding_templates WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+}
+
+/// Client finding repository
+pub struct FindingRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> FindingRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        FindingRepository { db }
+    }
+
+    /// Insert or replace a finding
+    pub fn save(&self, finding: &Finding) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO findings
+               (id, client_id, template_id, title, severity, cvss_vector, description, impact,
+                recommendation, refs, tags, asset_ids, assessment_id, status, notes, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)"#,
+            params![
+                finding.id,
+                finding.client_id,
+                finding.template_id,
+                finding.title,
+                format!("{:?}", finding.severity),
+                finding.cvss_vector,
+                finding.description,
+                finding.impact,
+                finding.recommendation,
+                serde_json::to_string(&finding.references)?,
+                serde_json::to_string(&finding.tags)?,
+                serde_json::to_string(&finding.asset_ids)?,
+                finding.assessment_id,
+                format!("{:?}", finding.status),
+                finding.notes,
+                finding.created_at.to_rfc3339(),
+                finding.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<Finding>> {
+        let sql = format!("SELECT {} FROM findings WHERE id = ?1", FINDING_COLUMNS);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    /// A client's findings, most severe and highest scored first
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<Finding>> {
+        let sql = format!("SELECT {} FROM findings WHERE client_id = ?1 ORDER BY created_at", FINDING_COLUMNS);
+        let mut findings = self.query(&sql, params![client_id])?;
+        sort_findings(&mut findings);
+        Ok(findings)
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM findings WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Open findings per client and severity, for clients with any
+    pub fn count_open_by_client(&self) -> OptioResult<HashMap<String, HashMap<Criticality, usize>>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT client_id, severity, COUNT(*) FROM findings
+             WHERE status != 'Remediated'
+             GROUP BY client_id, severity",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?))
+        })?;
+
+        let mut counts: HashMap<String, HashMap<Criticality, usize>> = HashMap::new();
+        for row in rows {
+            let (client_id, severity, count) = row?;
+            counts.entry(client_id).or_default().insert(parse_criticality(&severity)?, count as usize);
+        }
+        Ok(counts)
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<Finding>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let findings = stmt.query_map(query_params, |row| Ok(parse_finding_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(findings)
+    }
+}
+
+const FINDING_COLUMNS: &str = "id, client_id, template_id, title, severity, cvss_vector, description, impact, \
+    recommendation, refs, tags, asset_ids, assessment_id, status, notes, created_at, updated_at";
+
+/// Base score of a stored vector; vectors are checked before they are saved
+fn stored_score(vector: Option<&str>) -> Option<f64> {
+    vector.and_then(|v| score_cvss_vector(v).ok()).map(|s| s.base_score)
+}
+
+fn parse_template_row(row: &rusqlite::Row) -> OptioResult<FindingTemplate> {
+    let severity_str: String = row.get(2)?;
+    let cvss_vector: Option<String> = row.get(3)?;
+
+    Ok(FindingTemplate {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        severity: parse_criticality(&severity_str)?,
+        cvss_score: stored_score(cvss_vector.as_deref()),
+        cvss_vector,
+        description: row.get(4)?,
+        impact: row.get(5)?,
+        recommendation: row.get(6)?,
+        references: serde_json::from_str(&row.get::<_, String>(7)?)?,
+        tags: serde_json::from_str(&row.get::<_, String>(8)?)?,
+        built_in: false,
+    })
+}
+
+fn parse_finding_row(row: &rusqlite::Row) -> OptioResult<Finding> {
+    let severity_str: String = row.get(4)?;
+    let cvss_vector: Option<String> = row.get(5)?;
+    let status_str: String = row.get(13)?;
+
+    Ok(Finding {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        template_id: row.get(2)?,
+        title: row.get(3)?,
+        severity: parse_criticality(&severity_str)?,
+        cvss_score: stored_score(cvss_vector.as_deref()),
+        cvss_vector,
+        description: row.get(6)?,
+        impact: row.get(7)?,
+        recommendation: row.get(8)?,
+        references: serde_json::from_str(&row.get::<_, String>(9)?)?,
+        tags: serde_json::from_str(&row.get::<_, String>(10)?)?,
+        asset_ids: serde_json::from_str(&row.get::<_, String>(11)?)?,
+        assessment_id: row.get(12)?,
+        status: parse_finding_status(&status_str)?,
+        notes: row.get(14)?,
+        created_at: parse_datetime(&row.get::<_, String>(15)?)?,
+        updated_at: parse_datetime(&row.get::<_, String>(16)?)?,
+    })
+}
+
+fn parse_finding_status(s: &str) -> OptioResult<FindingStatus> {
+    match s {
+        "Open" => Ok(FindingStatus::Open),
+        "Remediated" => Ok(FindingStatus::Remediated),
+        "Unverifiable" => Ok(FindingStatus::Unverifiable),
+        _ => Err(OptioError::Database(format!("Unknown finding status: {}", s))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_findings_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn template(id: &str) -> FindingTemplate {
+        default_finding_templates().into_iter().find(|t| t.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_default_templates_score_at_their_severity() {
+        for template in default_finding_templates() {
+            let vector = match &template.cvss_vector {
+                Some(vector) => vector,
+                None => continue,
+            };
+            let score = score_cvss_vector(vector).unwrap();
+            assert_eq!(template.cvss_score, Some(score.base_score), "{}", template.id);
+            assert_eq!(template.severity, score.severity, "{}", template.id);
+        }
+    }
+
+    #[test]
+    fn test_instantiate_and_list_by_severity() {
+        let db = test_db();
+        let repo = FindingRepository::new(&db);
+
+        let tls = instantiate_finding(&template("deprecated-tls"), "client-1", FindingOverrides::default()).unwrap();
+        assert_eq!(tls.cvss_score, Some(5.9));
+        assert_eq!(tls.template_id.as_deref(), Some("deprecated-tls"));
+
+        // A new vector rescores the finding and re-rates its severity
+        let mfa = instantiate_finding(&template("missing-mfa"), "client-1", FindingOverrides {
+            cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H".to_string()),
+            asset_ids: Some(vec!["asset-1".to_string(), " ".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(mfa.severity, Criticality::Critical);
+        assert_eq!(mfa.asset_ids, vec!["asset-1".to_string()]);
+
+        assert!(instantiate_finding(&template("missing-mfa"), "client-1", FindingOverrides {
+            cvss_vector: Some("CVSS:3.1/AV:N".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+
+        let logging = instantiate_finding(&template("insufficient-logging"), "client-1", FindingOverrides {
+            severity: Some(Criticality::High),
+            ..Default::default()
+        })
+        .unwrap();
+
+        for finding in [&tls, &logging, &mfa] {
+            repo.save(finding).unwrap();
+        }
+
+        let listed: Vec<String> = repo.list_by_client("client-1").unwrap().into_iter().map(|f| f.id).collect();
+        assert_eq!(listed, vec![mfa.id.clone(), logging.id.clone(), tls.id.clone()]);
+
+        let stored = repo.get(&mfa.id).unwrap().unwrap();
+        assert_eq!(stored.cvss_score, Some(9.8));
+        assert_eq!(stored.status, FindingStatus::Open);
+    }
+}
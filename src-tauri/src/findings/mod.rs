@@ -0,0 +1,10 @@
+//! Findings Library
+//!
+//! Reusable finding templates with CVSS v3.1 scoring, and the client
+//! findings consultants create from them for reports.
+
+pub mod cvss;
+pub mod library;
+
+pub use cvss::*;
+pub use library::*;
@@ -1,8 +1,15 @@
 //! Framework Control Definitions
 //!
-//! Contains the built-in control libraries for NIST CSF 2.0, SOC 2 Type II, and GDPR.
+//! Contains the built-in control libraries for NIST CSF 2.0, SOC 2 Type II, GDPR,
+//! and ISO/IEC 27001:2022 Annex A.
 
-use crate::grc::models::{Control, Framework, NistFunction, Soc2Category, GdprChapter};
+use crate::error::{OptioError, OptioResult};
+use crate::grc::models::{
+    average_maturity, Assessment, CategoryComplianceStatus, CategoryGroup, ComplianceStatus, ComplianceStatusReport, Control,
+    ControlAssessment, ControlEvidenceCoverage, ControlField, ControlMapping, ControlSearchResult, MatchHighlight, Evidence, EvidenceCoverage, Framework, GdprChapter, Iso27001Theme, MappingStrength, NistFunction,
+    MaturityDescriptor, ProjectedControl, Soc2Category, MATURITY_LEVEL_NAMES,
+};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Get all controls for a specific framework
@@ -11,6 +18,7 @@ pub fn get_framework_controls(framework: Framework) -> Vec<Control> {
         Framework::NistCsf2 => get_nist_csf2_controls(),
         Framework::Soc2TypeII => get_soc2_controls(),
         Framework::Gdpr => get_gdpr_controls(),
+        Framework::Iso27001 => get_iso27001_controls(),
     }
 }
 
@@ -47,44 +55,651 @@ pub struct CategoryInfo {
     pub name: String,
     pub description: String,
     pub color: String,
+    /// Canonical position of the category within its framework
+    pub order: u32,
 }
 
-/// Get all categories for a specific framework
+/// Get all categories for a specific framework, in canonical order
 pub fn get_framework_categories(framework: Framework) -> Vec<CategoryInfo> {
     match framework {
         Framework::NistCsf2 => NistFunction::all()
             .into_iter()
-            .map(|f| CategoryInfo {
+            .enumerate()
+            .map(|(i, f)| CategoryInfo {
                 code: f.code().to_string(),
                 name: f.display_name().to_string(),
                 description: f.description().to_string(),
                 color: f.color().to_string(),
+                order: i as u32,
             })
             .collect(),
         Framework::Soc2TypeII => Soc2Category::all()
             .into_iter()
-            .map(|c| CategoryInfo {
+            .enumerate()
+            .map(|(i, c)| CategoryInfo {
                 code: c.code().to_string(),
                 name: c.display_name().to_string(),
                 description: format!("{} trust services criteria", c.display_name()),
                 color: c.color().to_string(),
+                order: i as u32,
             })
             .collect(),
         Framework::Gdpr => GdprChapter::all()
             .into_iter()
-            .map(|c| CategoryInfo {
+            .enumerate()
+            .map(|(i, c)| CategoryInfo {
                 code: c.code().to_string(),
                 name: c.display_name().to_string(),
                 description: format!("GDPR {}", c.display_name()),
                 color: c.color().to_string(),
+                order: i as u32,
             })
             .collect(),
+        Framework::Iso27001 => Iso27001Theme::all()
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| CategoryInfo {
+                code: t.code().to_string(),
+                name: t.display_name().to_string(),
+                description: t.description().to_string(),
+                color: t.color().to_string(),
+                order: i as u32,
+            })
+            .collect(),
+    }
+}
+
+/// Default grouping for a framework: one group per native category
+pub fn default_category_groups(framework: Framework) -> Vec<CategoryGroup> {
+    get_framework_categories(framework)
+        .into_iter()
+        .map(|c| CategoryGroup {
+            name: c.name,
+            order: c.order,
+            category_codes: vec![c.code],
+        })
+        .collect()
+}
+
+/// Validate a category grouping against a framework's categories
+///
+/// Every category must be assigned to exactly one group and no group may
+/// reference a code that the framework does not define.
+pub fn validate_category_groups(framework: Framework, groups: &[CategoryGroup]) -> OptioResult<()> {
+    if groups.is_empty() {
+        return Err(OptioError::Validation("At least one category group is required".to_string()));
+    }
+
+    let known: HashSet<String> = get_framework_categories(framework)
+        .into_iter()
+        .map(|c| c.code)
+        .collect();
+
+    let mut names = HashSet::new();
+    let mut assigned = HashSet::new();
+    for group in groups {
+        let name = group.name.trim();
+        if name.is_empty() {
+            return Err(OptioError::Validation("Category group name is required".to_string()));
+        }
+        if !names.insert(name.to_lowercase()) {
+            return Err(OptioError::Validation(format!("Duplicate category group: {}", name)));
+        }
+        if group.category_codes.is_empty() {
+            return Err(OptioError::Validation(format!("Category group {} has no categories", name)));
+        }
+        for code in &group.category_codes {
+            if !known.contains(code) {
+                return Err(OptioError::Validation(format!(
+                    "Unknown category {} for {}", code, framework
+                )));
+            }
+            if !assigned.insert(code.clone()) {
+                return Err(OptioError::Validation(format!(
+                    "Category {} is assigned to more than one group", code
+                )));
+            }
+        }
+    }
+
+    let mut missing: Vec<&String> = known.iter().filter(|c| !assigned.contains(*c)).collect();
+    if !missing.is_empty() {
+        missing.sort();
+        let missing: Vec<&str> = missing.into_iter().map(|c| c.as_str()).collect();
+        return Err(OptioError::Validation(format!(
+            "Categories not assigned to a group: {}", missing.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Roll per-category compliance numbers up into display groups, in group order
+pub fn rollup_category_groups(
+    breakdown: &[CategoryComplianceStatus],
+    groups: &[CategoryGroup],
+) -> Vec<CategoryComplianceStatus> {
+    let mut ordered: Vec<&CategoryGroup> = groups.iter().collect();
+    ordered.sort_by_key(|g| g.order);
+
+    ordered
+        .into_iter()
+        .map(|group| {
+            let members: Vec<&CategoryComplianceStatus> = group
+                .category_codes
+                .iter()
+                .filter_map(|code| breakdown.iter().find(|c| &c.code == code))
+                .collect();
+
+            let total: usize = members.iter().map(|c| c.total_controls).sum();
+            let assessed: usize = members.iter().map(|c| c.assessed_controls).sum();
+            let compliant: usize = members.iter().map(|c| c.compliant).sum();
+            let partial: usize = members.iter().map(|c| c.partially_compliant).sum();
+            let non_comp: usize = members.iter().map(|c| c.non_compliant).sum();
+            let rated: usize = members.iter().map(|c| c.maturity_rated).sum();
+            let maturity_total: f64 = members
+                .iter()
+                .filter_map(|c| c.average_maturity.map(|m| m * c.maturity_rated as f64))
+                .sum();
+
+            let completion_pct = if total > 0 {
+                (assessed as f64 / total as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            // Not-applicable controls count as assessed but not towards compliance
+            let applicable = compliant + partial + non_comp;
+            let compliance_pct = if applicable > 0 {
+                ((compliant as f64 + partial as f64 * 0.5) / applicable as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            CategoryComplianceStatus {
+                code: group.category_codes.join("+"),
+                name: group.name.clone(),
+                description: members.iter().map(|c| c.name.as_str()).collect::<Vec<_>>().join(", "),
+                color: members.first().map(|c| c.color.clone()).unwrap_or_else(|| "#64748b".to_string()),
+                total_controls: total,
+                assessed_controls: assessed,
+                compliant,
+                partially_compliant: partial,
+                non_compliant: non_comp,
+                completion_percentage: (completion_pct * 10.0).round() / 10.0,
+                compliance_percentage: (compliance_pct * 10.0).round() / 10.0,
+                average_maturity: if rated > 0 {
+                    Some((maturity_total / rated as f64 * 10.0).round() / 10.0)
+                } else {
+                    None
+                },
+                maturity_rated: rated,
+            }
+        })
+        .collect()
+}
+
+/// Aggregate per-control statuses into a compliance report for a framework
+///
+/// The group breakdown is left empty; callers add it when grouping is requested.
+pub fn build_compliance_report(
+    framework: Framework,
+    controls: &[Control],
+    status_of: impl Fn(&Control) -> ComplianceStatus,
+) -> ComplianceStatusReport {
+    let categories = get_framework_categories(framework);
+
+    // Calculate overall stats
+    let mut total_assessed = 0;
+    let mut total_compliant = 0;
+    let mut total_partial = 0;
+    let mut total_non_compliant = 0;
+    let mut total_na = 0;
+
+    // Build category breakdown
+    let mut category_map: HashMap<String, (usize, usize, usize, usize, usize, usize)> = HashMap::new();
+
+    for control in controls {
+        let entry = category_map.entry(control.category.clone()).or_insert((0, 0, 0, 0, 0, 0));
+        entry.0 += 1; // total
+
+        match status_of(control) {
+            ComplianceStatus::NotAssessed => {
+                // Not assessed - counts towards total but not assessed count
+            }
+            ComplianceStatus::Compliant => {
+                total_assessed += 1;
+                total_compliant += 1;
+                entry.1 += 1; // assessed
+                entry.2 += 1; // compliant
+            }
+            ComplianceStatus::PartiallyCompliant => {
+                total_assessed += 1;
+                total_partial += 1;
+                entry.1 += 1;
+                entry.3 += 1; // partial
+            }
+            ComplianceStatus::NonCompliant => {
+                total_assessed += 1;
+                total_non_compliant += 1;
+                entry.1 += 1;
+                entry.4 += 1; // non-compliant
+            }
+            ComplianceStatus::NotApplicable => {
+                total_assessed += 1;
+                total_na += 1;
+                entry.1 += 1;
+                entry.5 += 1; // n/a
+            }
+        }
+    }
+
+    // Build category breakdown response
+    let category_breakdown: Vec<CategoryComplianceStatus> = categories
+        .iter()
+        .map(|cat| {
+            let stats = category_map.get(&cat.code).copied().unwrap_or((0, 0, 0, 0, 0, 0));
+            let (total, assessed, compliant, partial, non_comp, na) = stats;
+
+            let completion_pct = if total > 0 {
+                (assessed as f64 / total as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            let applicable = assessed - na;
+            let compliance_pct = if applicable > 0 {
+                ((compliant as f64 + partial as f64 * 0.5) / applicable as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            CategoryComplianceStatus {
+                code: cat.code.clone(),
+                name: cat.name.clone(),
+                description: cat.description.clone(),
+                color: cat.color.clone(),
+                total_controls: total,
+                assessed_controls: assessed,
+                compliant,
+                partially_compliant: partial,
+                non_compliant: non_comp,
+                completion_percentage: (completion_pct * 10.0).round() / 10.0,
+                compliance_percentage: (compliance_pct * 10.0).round() / 10.0,
+                average_maturity: None,
+                maturity_rated: 0,
+            }
+        })
+        .collect();
+
+    let total_controls = controls.len();
+    let completion_percentage = if total_controls > 0 {
+        (total_assessed as f64 / total_controls as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    let applicable = total_assessed - total_na;
+    let compliance_percentage = if applicable > 0 {
+        ((total_compliant as f64 + total_partial as f64 * 0.5) / applicable as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    ComplianceStatusReport {
+        framework,
+        completion_percentage: (completion_percentage * 10.0).round() / 10.0,
+        compliance_percentage: (compliance_percentage * 10.0).round() / 10.0,
+        total_controls,
+        assessed_controls: total_assessed,
+        compliant_controls: total_compliant,
+        partially_compliant_controls: total_partial,
+        non_compliant_controls: total_non_compliant,
+        not_applicable_controls: total_na,
+        category_breakdown,
+        group_breakdown: vec![],
+        average_maturity: None,
+        network_health_score: None,
+        total_assets: None,
+        last_updated: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Fill in a report's maturity averages, overall and per category
+///
+/// `maturity_of` gives a control's rated maturity level; unrated and
+/// not-applicable controls are left out of the averages.
+pub fn apply_maturity(
+    report: &mut ComplianceStatusReport,
+    controls: &[Control],
+    maturity_of: impl Fn(&Control) -> Option<u8>,
+) {
+    let mut by_category: HashMap<&str, Vec<u8>> = HashMap::new();
+    for control in controls {
+        if let Some(level) = maturity_of(control) {
+            by_category.entry(control.category.as_str()).or_default().push(level);
+        }
+    }
+
+    for category in &mut report.category_breakdown {
+        let levels = by_category.get(category.code.as_str()).cloned().unwrap_or_default();
+        category.maturity_rated = levels.len();
+        category.average_maturity = average_maturity(levels);
+    }
+    report.average_maturity = average_maturity(by_category.into_values().flatten());
+}
+
+/// Suggested remediation for a control with no remediation recorded
+///
+/// Uses the control's implementation guidance where the library has it.
+pub fn default_remediation_action(control: &Control) -> String {
+    match &control.guidance {
+        Some(guidance) => guidance.clone(),
+        None => format!("Implement and document a control that ensures: {}", control.description),
+    }
+}
+
+/// Role that usually owns remediation for a framework category
+pub fn suggested_owner_role(framework: Framework, category: &str) -> &'static str {
+    match (framework, category) {
+        (Framework::NistCsf2, "GV") => "CISO",
+        (Framework::NistCsf2, "ID") => "Risk Manager",
+        (Framework::NistCsf2, "PR") => "IT Operations",
+        (Framework::NistCsf2, "DE") | (Framework::NistCsf2, "RS") => "Security Operations",
+        (Framework::NistCsf2, "RC") => "Business Continuity Lead",
+        (Framework::Soc2TypeII, "A") => "IT Operations",
+        (Framework::Soc2TypeII, "PI") => "Application Owner",
+        (Framework::Soc2TypeII, "P") => "Privacy Officer",
+        (Framework::Soc2TypeII, _) => "Security Lead",
+        (Framework::Gdpr, _) => "Data Protection Officer",
+        (Framework::Iso27001, "A.6") => "HR Manager",
+        (Framework::Iso27001, "A.7") => "Facilities Manager",
+        (Framework::Iso27001, "A.8") => "IT Operations",
+        _ => "Information Security Manager",
+    }
+}
+
+/// Count the evidence linked to each control of an assessment's framework
+///
+/// Control assessments and evidence links may reference a control by ID or
+/// by code. Controls marked compliant with no evidence are flagged.
+pub fn build_evidence_coverage(
+    assessment: &Assessment,
+    control_assessments: &[ControlAssessment],
+    evidence: &[Evidence],
+) -> EvidenceCoverage {
+    let refers_to = |control: &Control, id: &str| control.id == id || control.code == id;
+
+    let controls: Vec<ControlEvidenceCoverage> = get_framework_controls(assessment.framework)
+        .into_iter()
+        .map(|control| {
+            let status = control_assessments
+                .iter()
+                .find(|ca| refers_to(&control, &ca.control_id))
+                .map(|ca| ca.status)
+                .unwrap_or(ComplianceStatus::NotAssessed);
+            let evidence_count = evidence
+                .iter()
+                .filter(|e| e.control_ids.iter().any(|id| refers_to(&control, id)))
+                .count();
+
+            ControlEvidenceCoverage {
+                unsupported: status == ComplianceStatus::Compliant && evidence_count == 0,
+                control_code: control.code,
+                title: control.title,
+                category: control.category,
+                status,
+                evidence_count,
+            }
+        })
+        .collect();
+
+    EvidenceCoverage {
+        assessment_id: assessment.id.clone(),
+        framework: assessment.framework,
+        total_evidence: evidence.len(),
+        controls_with_evidence: controls.iter().filter(|c| c.evidence_count > 0).count(),
+        unsupported_controls: controls.iter().filter(|c| c.unsupported).count(),
+        controls,
+    }
+}
+
+// ============================================================================
+// Cross-Framework Mapping
+// ============================================================================
+
+/// Map each control of one framework onto the controls of another
+///
+/// Two controls are linked when either lists the other in its cross
+/// references. A mapping is Full when the link is one-to-one in both
+/// directions and Partial otherwise. Source controls with no link are omitted.
+pub fn get_control_mappings(from: Framework, to: Framework) -> Vec<ControlMapping> {
+    let sources = get_framework_controls(from);
+    let targets = get_framework_controls(to);
+
+    let links = |source: &Control| -> Vec<String> {
+        targets
+            .iter()
+            .filter(|t| source.cross_references.contains(&t.code) || t.cross_references.contains(&source.code))
+            .map(|t| t.code.clone())
+            .collect()
+    };
+
+    let linked: Vec<(String, Vec<String>)> = sources
+        .iter()
+        .map(|s| (s.code.clone(), links(s)))
+        .filter(|(_, t)| !t.is_empty())
+        .collect();
+
+    // How many source controls map onto each target
+    let mut inbound: HashMap<&str, usize> = HashMap::new();
+    for (_, target_codes) in &linked {
+        for code in target_codes {
+            *inbound.entry(code.as_str()).or_insert(0) += 1;
+        }
+    }
+
+    linked
+        .iter()
+        .map(|(source, target_codes)| {
+            let one_to_one = target_codes.len() == 1
+                && inbound.get(target_codes[0].as_str()).copied() == Some(1);
+            ControlMapping {
+                source_control: source.clone(),
+                target_controls: target_codes.clone(),
+                strength: if one_to_one { MappingStrength::Full } else { MappingStrength::Partial },
+            }
+        })
+        .collect()
+}
+
+/// Project source control statuses (keyed by control code) onto another framework
+///
+/// A target control is inherited compliant when every mapped source control
+/// is compliant. Not-applicable sources are ignored unless all sources are
+/// not applicable. Targets with no mapped source stay NotAssessed.
+pub fn project_control_statuses(
+    from: Framework,
+    to: Framework,
+    source_statuses: &HashMap<String, ComplianceStatus>,
+) -> Vec<ProjectedControl> {
+    let mut sources_by_target: HashMap<String, Vec<(String, MappingStrength)>> = HashMap::new();
+    for mapping in get_control_mappings(from, to) {
+        for target in mapping.target_controls {
+            sources_by_target
+                .entry(target)
+                .or_default()
+                .push((mapping.source_control.clone(), mapping.strength));
+        }
+    }
+
+    get_framework_controls(to)
+        .into_iter()
+        .map(|control| {
+            let sources = sources_by_target.remove(&control.code).unwrap_or_default();
+            let statuses: Vec<ComplianceStatus> = sources
+                .iter()
+                .map(|(code, _)| source_statuses.get(code).copied().unwrap_or(ComplianceStatus::NotAssessed))
+                .filter(|s| *s != ComplianceStatus::NotApplicable)
+                .collect();
+
+            let status = if sources.is_empty() {
+                ComplianceStatus::NotAssessed
+            } else if statuses.is_empty() {
+                ComplianceStatus::NotApplicable
+            } else if statuses.iter().all(|s| *s == ComplianceStatus::Compliant) {
+                ComplianceStatus::Compliant
+            } else if statuses
+                .iter()
+                .any(|s| matches!(s, ComplianceStatus::Compliant | ComplianceStatus::PartiallyCompliant))
+            {
+                ComplianceStatus::PartiallyCompliant
+            } else if statuses.contains(&ComplianceStatus::NonCompliant) {
+                ComplianceStatus::NonCompliant
+            } else {
+                ComplianceStatus::NotAssessed
+            };
+
+            let strength = if sources.is_empty() {
+                None
+            } else if sources.iter().all(|(_, s)| *s == MappingStrength::Full) {
+                Some(MappingStrength::Full)
+            } else {
+                Some(MappingStrength::Partial)
+            };
+
+            ProjectedControl {
+                code: control.code,
+                title: control.title,
+                category: control.category,
+                status,
+                inherited_compliant: status == ComplianceStatus::Compliant,
+                source_controls: sources.into_iter().map(|(code, _)| code).collect(),
+                strength,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Control Search
+// ============================================================================
+
+/// Bonus for a query that is exactly a control's code
+const EXACT_CODE_BONUS: u32 = 20;
+
+/// Search a framework's controls by code, title, description and guidance
+///
+/// Matching is case-insensitive and every whitespace-separated term must
+/// appear somewhere in the control. Each term scores by the most specific
+/// field it is found in (code, then title, description, guidance), plus one
+/// when it starts a word. Results are ranked by score, ties keeping
+/// framework order; an empty query matches every control with score 0.
+///
+/// `category` filters by category code. With `control_assessments` each
+/// result carries its status in that assessment; `status_filter` keeps only
+/// controls with that status, counting unrecorded controls as not assessed.
+pub fn search_framework_controls(
+    framework: Framework,
+    query: &str,
+    category: Option<&str>,
+    status_filter: Option<ComplianceStatus>,
+    control_assessments: Option<&[ControlAssessment]>,
+) -> Vec<ControlSearchResult> {
+    let terms: Vec<Vec<char>> = query.split_whitespace().map(fold_case).collect();
+
+    let mut results: Vec<ControlSearchResult> = get_framework_controls(framework)
+        .into_iter()
+        .filter(|control| category.map_or(true, |cat| control.category.eq_ignore_ascii_case(cat.trim())))
+        .filter_map(|control| {
+            // Control assessments may reference a control by ID or by code
+            let status = control_assessments.map(|cas| {
+                cas.iter()
+                    .find(|ca| ca.control_id == control.id || ca.control_id == control.code)
+                    .map(|ca| ca.status)
+                    .unwrap_or(ComplianceStatus::NotAssessed)
+            });
+            if status_filter.is_some_and(|wanted| status.unwrap_or(ComplianceStatus::NotAssessed) != wanted) {
+                return None;
+            }
+
+            let (score, highlights) = score_control(&control, &terms)?;
+            Some(ControlSearchResult { control, status, score, highlights })
+        })
+        .collect();
+
+    // Stable sort, so equal scores stay in framework order
+    results.sort_by_key(|r| std::cmp::Reverse(r.score));
+    results
+}
+
+/// Score and highlights for `control`, or None unless every term matches
+fn score_control(control: &Control, terms: &[Vec<char>]) -> Option<(u32, Vec<MatchHighlight>)> {
+    let fields = [
+        (ControlField::Code, fold_case(&control.code)),
+        (ControlField::Title, fold_case(&control.title)),
+        (ControlField::Description, fold_case(&control.description)),
+        (ControlField::Guidance, fold_case(control.guidance.as_deref().unwrap_or(""))),
+    ];
+
+    let mut score = 0;
+    let mut highlights = Vec::new();
+    for term in terms {
+        let mut best = None;
+        for (field, text) in &fields {
+            for start in find_term(text, term) {
+                let word_start = start == 0 || !text[start - 1].is_alphanumeric();
+                best = best.max(Some(field_weight(*field) + u32::from(word_start)));
+                highlights.push(MatchHighlight { field: *field, start, end: start + term.len() });
+            }
+        }
+        score += best?;
+    }
+
+    if terms.len() == 1 && fields[0].1 == terms[0] {
+        score += EXACT_CODE_BONUS;
+    }
+
+    highlights.sort_by_key(|h| (h.field, h.start, h.end));
+    highlights.dedup();
+    Some((score, highlights))
+}
+
+fn field_weight(field: ControlField) -> u32 {
+    match field {
+        ControlField::Code => 8,
+        ControlField::Title => 5,
+        ControlField::Description => 2,
+        ControlField::Guidance => 1,
     }
 }
 
+/// Lowercase character by character, so offsets still line up with the original text
+pub(crate) fn fold_case(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| {
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(l), None) => l,
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+/// Character offsets where `term` starts in `text`
+pub(crate) fn find_term(text: &[char], term: &[char]) -> Vec<usize> {
+    if term.is_empty() || term.len() > text.len() {
+        return vec![];
+    }
+    (0..=text.len() - term.len())
+        .filter(|&i| text[i..i + term.len()] == *term)
+        .collect()
+}
+
 /// NIST CSF 2.0 Controls
 fn get_nist_csf2_controls() -> Vec<Control> {
-    vec![
+    let mut controls = vec![
         // GOVERN Function
         Control {
             id: Uuid::new_v4().to_string(),
@@ -97,6 +712,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Document how cybersecurity supports organizational objectives".to_string()),
             cross_references: vec!["CC1.1".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -109,6 +725,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Define risk appetite and tolerance levels".to_string()),
             cross_references: vec!["CC3.1".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -121,6 +738,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Establish RACI matrix for cybersecurity functions".to_string()),
             cross_references: vec!["CC1.3".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -133,6 +751,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Develop comprehensive information security policy".to_string()),
             cross_references: vec!["CC1.1".to_string(), "Art. 24".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
 
         // IDENTIFY Function
@@ -147,6 +766,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement automated asset discovery and maintain CMDB".to_string()),
             cross_references: vec!["CC6.1".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -159,6 +779,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Deploy software asset management tools".to_string()),
             cross_references: vec!["CC6.1".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -171,6 +792,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Create and maintain network diagrams and data flow maps".to_string()),
             cross_references: vec!["CC6.1".to_string(), "Art. 30".to_string()],
             priority: 3,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -183,6 +805,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement vulnerability scanning and penetration testing".to_string()),
             cross_references: vec!["CC4.1".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -195,6 +818,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Subscribe to threat intelligence feeds and participate in ISACs".to_string()),
             cross_references: vec!["CC3.2".to_string()],
             priority: 3,
+            maturity_descriptions: vec![],
         },
 
         // PROTECT Function
@@ -209,6 +833,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement identity governance with lifecycle management".to_string()),
             cross_references: vec!["CC6.1".to_string(), "CC6.2".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -221,6 +846,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement MFA for all privileged and remote access".to_string()),
             cross_references: vec!["CC6.1".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -233,6 +859,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement role-based access control with least privilege".to_string()),
             cross_references: vec!["CC6.2".to_string(), "CC6.3".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -245,6 +872,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement encryption for data at rest".to_string()),
             cross_references: vec!["CC6.1".to_string(), "Art. 32".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -257,6 +885,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement TLS 1.3 for all network communications".to_string()),
             cross_references: vec!["CC6.1".to_string(), "Art. 32".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -269,6 +898,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement configuration baselines and drift detection".to_string()),
             cross_references: vec!["CC6.1".to_string(), "CC7.1".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -281,6 +911,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement 3-2-1 backup strategy with regular testing".to_string()),
             cross_references: vec!["A1.2".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
 
         // DETECT Function
@@ -295,6 +926,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Deploy network detection and response (NDR) solutions".to_string()),
             cross_references: vec!["CC7.2".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -307,6 +939,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement physical access monitoring and environmental controls".to_string()),
             cross_references: vec!["CC6.4".to_string()],
             priority: 3,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -319,6 +952,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Implement user behavior analytics (UBA)".to_string()),
             cross_references: vec!["CC6.2".to_string()],
             priority: 3,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -331,6 +965,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Deploy SIEM with correlation rules".to_string()),
             cross_references: vec!["CC7.2".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
 
         // RESPOND Function
@@ -345,6 +980,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Develop and test incident response procedures".to_string()),
             cross_references: vec!["CC7.4".to_string(), "Art. 33".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -357,6 +993,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Establish incident classification and escalation procedures".to_string()),
             cross_references: vec!["CC7.3".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -369,6 +1006,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Establish notification procedures for various incident types".to_string()),
             cross_references: vec!["CC7.4".to_string(), "Art. 33".to_string(), "Art. 34".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
 
         // RECOVER Function
@@ -383,6 +1021,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Develop and test business continuity and disaster recovery plans".to_string()),
             cross_references: vec!["A1.3".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -395,6 +1034,7 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Conduct post-recovery validation and testing".to_string()),
             cross_references: vec!["A1.3".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -407,8 +1047,219 @@ fn get_nist_csf2_controls() -> Vec<Control> {
             guidance: Some("Establish recovery status reporting procedures".to_string()),
             cross_references: vec!["CC7.5".to_string()],
             priority: 3,
+            maturity_descriptions: vec![],
         },
-    ]
+    ];
+    for control in &mut controls {
+        control.maturity_descriptions = nist_maturity_descriptions(&control.code);
+    }
+    controls
+}
+
+/// NIST CSF 2.0 maturity descriptors: (code, levels 1 to 5)
+const NIST_CSF2_MATURITY: &[(&str, [&str; 5])] = &[
+    ("GV.OC-01", [
+        "Mission's cybersecurity implications are not considered",
+        "Leadership discusses cybersecurity and mission informally",
+        "Documented link between business objectives and cybersecurity priorities",
+        "Priorities reviewed with stakeholders on a set schedule",
+        "Cybersecurity strategy adjusts as the mission and context change",
+    ]),
+    ("GV.RM-01", [
+        "No stated risk objectives",
+        "Risk appetite discussed but not written down",
+        "Approved risk appetite and tolerance statements",
+        "Risk decisions measured against tolerances and reported",
+        "Objectives refined from risk metrics and incidents",
+    ]),
+    ("GV.RR-01", [
+        "Security falls to whoever is available",
+        "Key security roles named informally",
+        "Roles and responsibilities documented and assigned",
+        "Accountability tracked through performance objectives",
+        "Role coverage reviewed and adjusted as the business changes",
+    ]),
+    ("GV.PO-01", [
+        "No written security policy",
+        "Draft or outdated policies exist",
+        "Approved policies communicated to all staff",
+        "Policy adherence measured and exceptions tracked",
+        "Policies revised from audit findings and threat changes",
+    ]),
+    ("ID.AM-01", [
+        "No hardware inventory",
+        "Partial inventory kept by hand",
+        "Complete inventory with owners, kept current by process",
+        "Automated discovery reconciled against the inventory",
+        "Inventory drives lifecycle and risk decisions continuously",
+    ]),
+    ("ID.AM-02", [
+        "No software inventory",
+        "Partial list of key applications",
+        "Complete inventory of software, services and versions",
+        "Automated discovery flags unapproved software",
+        "Inventory feeds vulnerability and licence management in real time",
+    ]),
+    ("ID.AM-03", [
+        "Data flows are unknown",
+        "Some key flows sketched informally",
+        "Data flow diagrams maintained for critical systems",
+        "Flows validated against network and application monitoring",
+        "Flow maps updated automatically as systems change",
+    ]),
+    ("ID.RA-01", [
+        "No vulnerability scanning",
+        "Occasional scans without follow-up",
+        "Scheduled scanning with a documented remediation process",
+        "Remediation times measured against targets",
+        "Continuous scanning with risk-based prioritization",
+    ]),
+    ("ID.RA-02", [
+        "No threat intelligence",
+        "Ad hoc reading of public advisories",
+        "Defined intelligence sources reviewed routinely",
+        "Intelligence mapped to assets and acted on measurably",
+        "Intelligence shared and integrated into automated defences",
+    ]),
+    ("PR.AA-01", [
+        "Accounts created and removed ad hoc",
+        "Basic joiner and leaver steps followed inconsistently",
+        "Documented identity lifecycle with central directory",
+        "Access reviews completed and orphaned accounts tracked",
+        "Identity lifecycle automated from HR and reviewed continuously",
+    ]),
+    ("PR.AA-02", [
+        "Passwords only, with no standard",
+        "MFA on some systems",
+        "MFA required on all remote and privileged access",
+        "Authentication events monitored and MFA coverage measured",
+        "Phishing-resistant, risk-adaptive authentication throughout",
+    ]),
+    ("PR.AA-03", [
+        "Access granted on request without review",
+        "Some role-based access for key systems",
+        "Least privilege defined and enforced through roles",
+        "Periodic access reviews with measured completion",
+        "Just-in-time access with continuous entitlement review",
+    ]),
+    ("PR.DS-01", [
+        "Stored data is not encrypted",
+        "Encryption used on some devices",
+        "Encryption standard applied to all sensitive data stores",
+        "Encryption coverage and key management audited",
+        "Key rotation and coverage monitored automatically",
+    ]),
+    ("PR.DS-02", [
+        "Plaintext protocols in common use",
+        "TLS on public-facing services only",
+        "Encryption in transit required internally and externally",
+        "Protocol and certificate compliance monitored",
+        "Weak protocols detected and retired automatically",
+    ]),
+    ("PR.PS-01", [
+        "Systems configured individually",
+        "Some build standards followed informally",
+        "Documented baselines applied through change control",
+        "Configuration drift detected and reported",
+        "Baselines enforced automatically and improved from findings",
+    ]),
+    ("PR.IR-01", [
+        "No regular backups",
+        "Backups taken but rarely tested",
+        "Scheduled, protected backups with documented restores",
+        "Restore tests measured against recovery objectives",
+        "Immutable backups with automated restore verification",
+    ]),
+    ("DE.CM-01", [
+        "No network monitoring",
+        "Logs collected but rarely reviewed",
+        "Defined monitoring of key network segments",
+        "Alerts triaged against response-time targets",
+        "Detection tuned continuously from incidents and threat intelligence",
+    ]),
+    ("DE.CM-02", [
+        "Physical access is not monitored",
+        "Visitor logs kept inconsistently",
+        "Access logging and CCTV for secure areas",
+        "Physical access events reviewed and exceptions followed up",
+        "Physical and logical monitoring correlated",
+    ]),
+    ("DE.CM-03", [
+        "User activity is not monitored",
+        "Activity reviewed only after incidents",
+        "Defined monitoring of privileged and sensitive activity",
+        "Anomalies alerted on and reviewed against targets",
+        "Behavioural analytics refined from investigations",
+    ]),
+    ("DE.AE-02", [
+        "Events reviewed in isolation, if at all",
+        "Manual correlation during investigations",
+        "Central log platform with correlation rules",
+        "Correlation accuracy measured and rules tuned",
+        "Automated correlation and enrichment across all sources",
+    ]),
+    ("RS.MA-01", [
+        "No incident response plan",
+        "Informal plan known to a few people",
+        "Documented, approved plan with assigned roles",
+        "Plan exercised and response metrics tracked",
+        "Plan improved after every exercise and incident",
+    ]),
+    ("RS.MA-02", [
+        "Incidents handled first come, first served",
+        "Severity judged case by case",
+        "Documented triage and severity criteria",
+        "Triage times and accuracy measured",
+        "Triage partly automated and criteria refined continuously",
+    ]),
+    ("RS.CO-02", [
+        "No defined reporting of incidents",
+        "Stakeholders told informally",
+        "Documented notification paths, including regulators",
+        "Notification timeliness tracked against obligations",
+        "Reporting streamlined from lessons learned",
+    ]),
+    ("RC.RP-01", [
+        "Recovery is improvised",
+        "Recovery steps known to key staff only",
+        "Documented recovery plans for critical services",
+        "Recovery exercised against time objectives",
+        "Recovery automated where possible and refined after tests",
+    ]),
+    ("RC.RP-02", [
+        "Restored systems are not checked",
+        "Informal checks after restoration",
+        "Documented integrity checks before return to service",
+        "Validation results recorded and reviewed",
+        "Validation automated and criteria improved continuously",
+    ]),
+    ("RC.CO-03", [
+        "No communication during recovery",
+        "Updates given ad hoc",
+        "Communication plan for recovery activities",
+        "Communication timeliness and reach reviewed",
+        "Messaging refined from stakeholder feedback",
+    ]),
+];
+
+/// A NIST CSF 2.0 control's maturity descriptors; empty for unknown codes
+fn nist_maturity_descriptions(code: &str) -> Vec<MaturityDescriptor> {
+    NIST_CSF2_MATURITY
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, levels)| {
+            MATURITY_LEVEL_NAMES
+                .iter()
+                .zip(levels)
+                .enumerate()
+                .map(|(i, (name, description))| MaturityDescriptor {
+                    level: i as u8 + 1,
+                    name: name.to_string(),
+                    description: description.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// SOC 2 Type II Controls (Common Criteria)
@@ -426,6 +1277,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Establish and communicate code of conduct".to_string()),
             cross_references: vec!["GV.OC-01".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -438,6 +1290,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Document board oversight of IT and security".to_string()),
             cross_references: vec!["GV.RR-01".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -450,6 +1303,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Define organizational structure and reporting relationships".to_string()),
             cross_references: vec!["GV.RR-01".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -462,6 +1316,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Document IT and security objectives".to_string()),
             cross_references: vec!["GV.RM-01".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -474,6 +1329,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Conduct regular risk assessments".to_string()),
             cross_references: vec!["ID.RA-02".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -486,6 +1342,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement continuous monitoring and periodic assessments".to_string()),
             cross_references: vec!["ID.RA-01".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -498,6 +1355,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Deploy identity and access management controls".to_string()),
             cross_references: vec!["PR.AA-01".to_string(), "PR.AA-02".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -510,6 +1368,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement formal user provisioning process".to_string()),
             cross_references: vec!["PR.AA-01".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -522,6 +1381,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement user deprovisioning process".to_string()),
             cross_references: vec!["PR.AA-03".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -534,6 +1394,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement physical security controls".to_string()),
             cross_references: vec!["DE.CM-02".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -546,6 +1407,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement change detection mechanisms".to_string()),
             cross_references: vec!["PR.PS-01".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -558,6 +1420,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Deploy security monitoring and SIEM".to_string()),
             cross_references: vec!["DE.CM-01".to_string(), "DE.AE-02".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -570,6 +1433,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Establish incident triage procedures".to_string()),
             cross_references: vec!["RS.MA-02".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -582,6 +1446,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Develop and test incident response plan".to_string()),
             cross_references: vec!["RS.MA-01".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -594,6 +1459,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement recovery procedures".to_string()),
             cross_references: vec!["RC.CO-03".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
 
         // Availability
@@ -608,6 +1474,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement capacity planning and monitoring".to_string()),
             cross_references: vec![],
             priority: 3,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -620,6 +1487,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement backup and recovery procedures".to_string()),
             cross_references: vec!["PR.IR-01".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -632,6 +1500,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Conduct regular DR tests".to_string()),
             cross_references: vec!["RC.RP-01".to_string(), "RC.RP-02".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
 
         // Confidentiality
@@ -646,6 +1515,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement data classification".to_string()),
             cross_references: vec!["PR.DS-01".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -658,6 +1528,7 @@ fn get_soc2_controls() -> Vec<Control> {
             guidance: Some("Implement secure data disposal procedures".to_string()),
             cross_references: vec![],
             priority: 3,
+            maturity_descriptions: vec![],
         },
     ]
 }
@@ -677,6 +1548,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Document lawful basis for processing, provide clear privacy notices".to_string()),
             cross_references: vec![],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -689,6 +1561,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Document purposes of processing in ROPA".to_string()),
             cross_references: vec![],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -701,6 +1574,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Review data collection practices, implement data minimization".to_string()),
             cross_references: vec![],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -713,6 +1587,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement technical and organizational security measures".to_string()),
             cross_references: vec!["PR.DS-01".to_string(), "PR.DS-02".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -725,6 +1600,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Document lawful basis for each processing activity".to_string()),
             cross_references: vec![],
             priority: 5,
+            maturity_descriptions: vec![],
         },
 
         // Data Subject Rights (Chapter 3)
@@ -739,6 +1615,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement clear privacy notices and communication procedures".to_string()),
             cross_references: vec![],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -751,6 +1628,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement subject access request (SAR) process".to_string()),
             cross_references: vec![],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -763,6 +1641,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement data deletion process and right to be forgotten".to_string()),
             cross_references: vec![],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -775,6 +1654,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement data export functionality".to_string()),
             cross_references: vec![],
             priority: 3,
+            maturity_descriptions: vec![],
         },
 
         // Controller & Processor (Chapter 4)
@@ -789,6 +1669,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Establish data protection governance framework".to_string()),
             cross_references: vec!["GV.PO-01".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -801,6 +1682,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Integrate privacy into system design and development".to_string()),
             cross_references: vec![],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -813,6 +1695,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement Data Processing Agreements with all processors".to_string()),
             cross_references: vec![],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -825,6 +1708,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Create and maintain Records of Processing Activities (ROPA)".to_string()),
             cross_references: vec!["ID.AM-03".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -837,6 +1721,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement encryption, pseudonymization, and security controls".to_string()),
             cross_references: vec!["PR.DS-01".to_string(), "PR.DS-02".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -849,6 +1734,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement breach detection and 72-hour notification process".to_string()),
             cross_references: vec!["RS.MA-01".to_string(), "RS.CO-02".to_string()],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -861,6 +1747,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement data subject breach notification process".to_string()),
             cross_references: vec!["RS.CO-02".to_string()],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -873,6 +1760,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Conduct DPIAs for new high-risk processing activities".to_string()),
             cross_references: vec![],
             priority: 4,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -885,6 +1773,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Appoint DPO if required, document rationale if not".to_string()),
             cross_references: vec![],
             priority: 4,
+            maturity_descriptions: vec![],
         },
 
         // Transfers (Chapter 5)
@@ -899,6 +1788,7 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Document transfer mechanisms for international data transfers".to_string()),
             cross_references: vec![],
             priority: 5,
+            maturity_descriptions: vec![],
         },
         Control {
             id: Uuid::new_v4().to_string(),
@@ -911,10 +1801,174 @@ fn get_gdpr_controls() -> Vec<Control> {
             guidance: Some("Implement SCCs or other approved transfer mechanisms".to_string()),
             cross_references: vec![],
             priority: 4,
+            maturity_descriptions: vec![],
         },
     ]
 }
 
+/// ISO/IEC 27001:2022 Annex A controls: (code, title, description, priority)
+///
+/// Descriptions summarize each control's objective; the standard's own
+/// wording is not reproduced.
+const ISO27001_ANNEX_A: &[(&str, &str, &str, u8)] = &[
+    // A.5 Organizational controls
+    ("A.5.1", "Policies for Information Security", "An information security policy and topic-specific policies are defined, approved, communicated, and reviewed", 5),
+    ("A.5.2", "Information Security Roles and Responsibilities", "Information security roles and responsibilities are defined and allocated", 5),
+    ("A.5.3", "Segregation of Duties", "Conflicting duties and areas of responsibility are segregated", 4),
+    ("A.5.4", "Management Responsibilities", "Management requires personnel to apply information security in line with policies and procedures", 4),
+    ("A.5.5", "Contact with Authorities", "Contact with relevant authorities is established and maintained", 3),
+    ("A.5.6", "Contact with Special Interest Groups", "Contact with security forums and professional associations is maintained", 2),
+    ("A.5.7", "Threat Intelligence", "Information about threats is collected and analysed to produce threat intelligence", 4),
+    ("A.5.8", "Information Security in Project Management", "Information security is integrated into project management", 3),
+    ("A.5.9", "Inventory of Information and Other Associated Assets", "An inventory of information and associated assets, including owners, is developed and maintained", 5),
+    ("A.5.10", "Acceptable Use of Information and Other Associated Assets", "Rules for acceptable use and handling of information and assets are identified, documented, and implemented", 3),
+    ("A.5.11", "Return of Assets", "Personnel and other parties return organizational assets on change or termination of employment or agreement", 3),
+    ("A.5.12", "Classification of Information", "Information is classified according to confidentiality, integrity, availability, and stakeholder requirements", 4),
+    ("A.5.13", "Labelling of Information", "Procedures for labelling information follow the classification scheme", 3),
+    ("A.5.14", "Information Transfer", "Rules, procedures, or agreements are in place for all types of information transfer", 4),
+    ("A.5.15", "Access Control", "Rules to control physical and logical access are established based on business and security requirements", 5),
+    ("A.5.16", "Identity Management", "The full life cycle of identities is managed", 5),
+    ("A.5.17", "Authentication Information", "Allocation and management of authentication information is controlled, including advice to personnel", 5),
+    ("A.5.18", "Access Rights", "Access rights are provisioned, reviewed, modified, and removed in line with the access control policy", 5),
+    ("A.5.19", "Information Security in Supplier Relationships", "Processes are defined to manage risks associated with the use of supplier products and services", 4),
+    ("A.5.20", "Addressing Information Security Within Supplier Agreements", "Relevant security requirements are established and agreed with each supplier", 4),
+    ("A.5.21", "Managing Information Security in the ICT Supply Chain", "Processes manage security risks associated with the ICT product and service supply chain", 4),
+    ("A.5.22", "Monitoring, Review and Change Management of Supplier Services", "Supplier security practices and service delivery are regularly monitored, reviewed, and changes managed", 3),
+    ("A.5.23", "Information Security for Use of Cloud Services", "Acquisition, use, management, and exit from cloud services follow defined security requirements", 4),
+    ("A.5.24", "Information Security Incident Management Planning and Preparation", "Incident management processes, roles, and responsibilities are planned and established", 5),
+    ("A.5.25", "Assessment and Decision on Information Security Events", "Security events are assessed to decide whether they are categorized as incidents", 4),
+    ("A.5.26", "Response to Information Security Incidents", "Incidents are responded to in accordance with documented procedures", 5),
+    ("A.5.27", "Learning from Information Security Incidents", "Knowledge gained from incidents is used to strengthen controls", 3),
+    ("A.5.28", "Collection of Evidence", "Procedures exist to identify, collect, acquire, and preserve evidence related to security events", 3),
+    ("A.5.29", "Information Security During Disruption", "Information security is maintained at an appropriate level during disruption", 4),
+    ("A.5.30", "ICT Readiness for Business Continuity", "ICT readiness is planned, implemented, maintained, and tested against continuity objectives", 5),
+    ("A.5.31", "Legal, Statutory, Regulatory and Contractual Requirements", "Applicable legal, regulatory, and contractual requirements are identified, documented, and kept up to date", 4),
+    ("A.5.32", "Intellectual Property Rights", "Procedures protect intellectual property rights", 2),
+    ("A.5.33", "Protection of Records", "Records are protected from loss, destruction, falsification, and unauthorized access or release", 3),
+    ("A.5.34", "Privacy and Protection of PII", "Requirements for preserving privacy and protecting PII are identified and met", 5),
+    ("A.5.35", "Independent Review of Information Security", "The approach to managing information security is independently reviewed at planned intervals or on significant change", 3),
+    ("A.5.36", "Compliance with Policies, Rules and Standards for Information Security", "Compliance with the security policy, topic-specific policies, and standards is regularly reviewed", 4),
+    ("A.5.37", "Documented Operating Procedures", "Operating procedures for information processing facilities are documented and made available", 3),
+    // A.6 People controls
+    ("A.6.1", "Screening", "Background verification checks on candidates are carried out prior to joining and on an ongoing basis", 4),
+    ("A.6.2", "Terms and Conditions of Employment", "Employment agreements state personnel and organizational responsibilities for information security", 3),
+    ("A.6.3", "Information Security Awareness, Education and Training", "Personnel receive appropriate awareness, education, training, and regular policy updates", 5),
+    ("A.6.4", "Disciplinary Process", "A disciplinary process is formalized and communicated for policy violations", 2),
+    ("A.6.5", "Responsibilities After Termination or Change of Employment", "Security responsibilities that remain valid after termination or change are defined and enforced", 3),
+    ("A.6.6", "Confidentiality or Non-Disclosure Agreements", "Confidentiality or non-disclosure agreements are identified, documented, reviewed, and signed", 3),
+    ("A.6.7", "Remote Working", "Security measures protect information accessed, processed, or stored outside organizational premises", 4),
+    ("A.6.8", "Information Security Event Reporting", "Personnel have a mechanism to report observed or suspected security events promptly", 4),
+    // A.7 Physical controls
+    ("A.7.1", "Physical Security Perimeters", "Security perimeters are defined and used to protect areas containing information and assets", 4),
+    ("A.7.2", "Physical Entry", "Secure areas are protected by appropriate entry controls and access points", 4),
+    ("A.7.3", "Securing Offices, Rooms and Facilities", "Physical security for offices, rooms, and facilities is designed and implemented", 3),
+    ("A.7.4", "Physical Security Monitoring", "Premises are continuously monitored for unauthorized physical access", 3),
+    ("A.7.5", "Protecting Against Physical and Environmental Threats", "Protection against natural disasters, physical attacks, and environmental threats is designed and implemented", 3),
+    ("A.7.6", "Working in Secure Areas", "Security measures for working in secure areas are designed and implemented", 2),
+    ("A.7.7", "Clear Desk and Clear Screen", "Clear desk rules for papers and media and clear screen rules for facilities are defined and enforced", 3),
+    ("A.7.8", "Equipment Siting and Protection", "Equipment is sited securely and protected", 2),
+    ("A.7.9", "Security of Assets Off-Premises", "Off-site assets are protected", 3),
+    ("A.7.10", "Storage Media", "Storage media are managed through their life cycle of acquisition, use, transport, and disposal", 3),
+    ("A.7.11", "Supporting Utilities", "Processing facilities are protected from power failures and other disruptions caused by utility failures", 3),
+    ("A.7.12", "Cabling Security", "Power, data, and supporting services cables are protected from interception, interference, or damage", 2),
+    ("A.7.13", "Equipment Maintenance", "Equipment is maintained correctly to ensure availability, integrity, and confidentiality of information", 2),
+    ("A.7.14", "Secure Disposal or Re-Use of Equipment", "Sensitive data and licensed software are removed or securely overwritten before disposal or re-use", 4),
+    // A.8 Technological controls
+    ("A.8.1", "User Endpoint Devices", "Information stored on, processed by, or accessible via user endpoint devices is protected", 5),
+    ("A.8.2", "Privileged Access Rights", "The allocation and use of privileged access rights is restricted and managed", 5),
+    ("A.8.3", "Information Access Restriction", "Access to information and assets is restricted in line with the access control policy", 5),
+    ("A.8.4", "Access to Source Code", "Read and write access to source code, development tools, and software libraries is appropriately managed", 3),
+    ("A.8.5", "Secure Authentication", "Secure authentication technologies and procedures are implemented based on access restrictions", 5),
+    ("A.8.6", "Capacity Management", "Resource use is monitored and adjusted in line with current and expected capacity requirements", 2),
+    ("A.8.7", "Protection Against Malware", "Protection against malware is implemented and supported by user awareness", 5),
+    ("A.8.8", "Management of Technical Vulnerabilities", "Information about technical vulnerabilities is obtained, exposure evaluated, and appropriate measures taken", 5),
+    ("A.8.9", "Configuration Management", "Configurations, including security configurations, of hardware, software, services, and networks are established, documented, and monitored", 4),
+    ("A.8.10", "Information Deletion", "Information stored in systems, devices, or other media is deleted when no longer required", 3),
+    ("A.8.11", "Data Masking", "Data masking is used in line with access control and business requirements and applicable legislation", 3),
+    ("A.8.12", "Data Leakage Prevention", "Data leakage prevention measures are applied to systems, networks, and devices handling sensitive information", 4),
+    ("A.8.13", "Information Backup", "Backup copies of information, software, and systems are maintained and regularly tested", 5),
+    ("A.8.14", "Redundancy of Information Processing Facilities", "Processing facilities are implemented with redundancy sufficient to meet availability requirements", 3),
+    ("A.8.15", "Logging", "Logs recording activities, exceptions, faults, and other relevant events are produced, stored, protected, and analysed", 5),
+    ("A.8.16", "Monitoring Activities", "Networks, systems, and applications are monitored for anomalous behaviour and appropriate action taken", 5),
+    ("A.8.17", "Clock Synchronization", "Clocks of information processing systems are synchronized to approved time sources", 2),
+    ("A.8.18", "Use of Privileged Utility Programs", "Use of utility programs that can override system and application controls is restricted and tightly controlled", 3),
+    ("A.8.19", "Installation of Software on Operational Systems", "Procedures and measures securely manage software installation on operational systems", 3),
+    ("A.8.20", "Networks Security", "Networks and network devices are secured, managed, and controlled to protect information in systems and applications", 5),
+    ("A.8.21", "Security of Network Services", "Security mechanisms, service levels, and requirements of network services are identified, implemented, and monitored", 4),
+    ("A.8.22", "Segregation of Networks", "Groups of information services, users, and systems are segregated in the organization's networks", 4),
+    ("A.8.23", "Web Filtering", "Access to external websites is managed to reduce exposure to malicious content", 3),
+    ("A.8.24", "Use of Cryptography", "Rules for the effective use of cryptography, including key management, are defined and implemented", 5),
+    ("A.8.25", "Secure Development Life Cycle", "Rules for the secure development of software and systems are established and applied", 4),
+    ("A.8.26", "Application Security Requirements", "Security requirements are identified, specified, and approved when developing or acquiring applications", 4),
+    ("A.8.27", "Secure System Architecture and Engineering Principles", "Principles for engineering secure systems are established, documented, maintained, and applied", 3),
+    ("A.8.28", "Secure Coding", "Secure coding principles are applied to software development", 4),
+    ("A.8.29", "Security Testing in Development and Acceptance", "Security testing processes are defined and implemented in the development life cycle", 4),
+    ("A.8.30", "Outsourced Development", "Outsourced system development activities are directed, monitored, and reviewed", 3),
+    ("A.8.31", "Separation of Development, Test and Production Environments", "Development, testing, and production environments are separated and secured", 4),
+    ("A.8.32", "Change Management", "Changes to information processing facilities and systems are subject to change management procedures", 4),
+    ("A.8.33", "Test Information", "Test information is appropriately selected, protected, and managed", 2),
+    ("A.8.34", "Protection of Information Systems During Audit Testing", "Audit tests and other assurance activities involving operational systems are planned and agreed", 2),
+];
+
+/// Cross references from Annex A controls to NIST CSF 2.0, SOC 2, and GDPR
+const ISO27001_CROSS_REFERENCES: &[(&str, &[&str])] = &[
+    ("A.5.1", &["GV.PO-01", "CC1.1"]),
+    ("A.5.2", &["GV.RR-01", "CC1.3"]),
+    ("A.5.4", &["CC1.2"]),
+    ("A.5.7", &["ID.RA-02", "CC3.2"]),
+    ("A.5.9", &["ID.AM-01", "ID.AM-02"]),
+    ("A.5.12", &["C1.1"]),
+    ("A.5.14", &["ID.AM-03", "Art. 30"]),
+    ("A.5.15", &["PR.AA-03", "CC6.1"]),
+    ("A.5.16", &["PR.AA-01", "CC6.2"]),
+    ("A.5.17", &["PR.AA-02"]),
+    ("A.5.18", &["CC6.3"]),
+    ("A.5.24", &["RS.MA-01", "CC7.4"]),
+    ("A.5.25", &["RS.MA-02", "CC7.3"]),
+    ("A.5.26", &["RS.CO-02", "Art. 33"]),
+    ("A.5.29", &["RC.RP-01", "CC7.5"]),
+    ("A.5.30", &["RC.RP-02", "A1.3"]),
+    ("A.5.34", &["Art. 5(1)(f)", "Art. 24"]),
+    ("A.6.8", &["DE.CM-03"]),
+    ("A.7.4", &["DE.CM-02", "CC6.4"]),
+    ("A.8.6", &["A1.1"]),
+    ("A.8.8", &["ID.RA-01", "CC4.1"]),
+    ("A.8.9", &["PR.PS-01", "CC7.1"]),
+    ("A.8.10", &["C1.2"]),
+    ("A.8.13", &["PR.IR-01", "A1.2"]),
+    ("A.8.16", &["DE.CM-01", "DE.AE-02", "CC7.2"]),
+    ("A.8.24", &["PR.DS-01", "PR.DS-02", "Art. 32"]),
+    ("A.8.27", &["Art. 25"]),
+];
+
+/// ISO/IEC 27001:2022 Annex A Controls
+fn get_iso27001_controls() -> Vec<Control> {
+    ISO27001_ANNEX_A
+        .iter()
+        .map(|(code, title, description, priority)| {
+            // "A.5.1" belongs to theme "A.5"
+            let theme = code.rsplit_once('.').map(|(t, _)| t).unwrap_or(code);
+            Control {
+                id: Uuid::new_v4().to_string(),
+                framework: Framework::Iso27001,
+                code: code.to_string(),
+                category: theme.to_string(),
+                subcategory: None,
+                title: title.to_string(),
+                description: description.to_string(),
+                guidance: None,
+                cross_references: ISO27001_CROSS_REFERENCES
+                    .iter()
+                    .find(|(c, _)| c == code)
+                    .map(|(_, refs)| refs.iter().map(|r| r.to_string()).collect())
+                    .unwrap_or_default(),
+                priority: *priority,
+                maturity_descriptions: vec![],
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -927,9 +1981,323 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_evidence_coverage() {
+        let now = chrono::Utc::now();
+        let assessment = Assessment {
+            id: "a1".to_string(),
+            client_id: "c1".to_string(),
+            name: "SOC 2".to_string(),
+            description: None,
+            framework: Framework::Soc2TypeII,
+            scope: None,
+            started_at: now,
+            completed_at: None,
+            lead_assessor: "Jane Auditor".to_string(),
+            status: crate::grc::models::AssessmentStatus::InProgress,
+            source_assessment_id: None,
+        };
+        let assessed = |code: &str, status| ControlAssessment {
+            id: code.to_string(),
+            assessment_id: "a1".to_string(),
+            control_id: code.to_string(),
+            status,
+            notes: None,
+            gap_description: None,
+            remediation: None,
+            remediation_target: None,
+            risk_rating: None,
+            maturity_level: None,
+            evidence_ids: vec![],
+            assessed_at: now,
+            assessed_by: "Jane Auditor".to_string(),
+        };
+        let evidence = Evidence {
+            id: "e1".to_string(),
+            assessment_id: "a1".to_string(),
+            control_ids: vec!["CC6.1".to_string()],
+            evidence_type: crate::grc::models::EvidenceType::Screenshot,
+            title: "MFA settings".to_string(),
+            description: None,
+            file_path: None,
+            url: None,
+            file_hash: None,
+            collected_at: now,
+            collected_by: "Jane Auditor".to_string(),
+            notes: None,
+            carried_forward: false,
+        };
+
+        let coverage = build_evidence_coverage(
+            &assessment,
+            &[assessed("CC6.1", ComplianceStatus::Compliant), assessed("CC6.2", ComplianceStatus::Compliant)],
+            &[evidence],
+        );
+        let get = |code: &str| coverage.controls.iter().find(|c| c.control_code == code).unwrap();
+
+        assert_eq!(coverage.controls.len(), get_framework_controls(Framework::Soc2TypeII).len());
+        assert_eq!(get("CC6.1").evidence_count, 1);
+        assert!(!get("CC6.1").unsupported);
+        assert!(get("CC6.2").unsupported);
+        assert_eq!((coverage.controls_with_evidence, coverage.unsupported_controls), (1, 1));
+    }
+
     #[test]
     fn test_available_frameworks() {
         let frameworks = get_available_frameworks();
-        assert_eq!(frameworks.len(), 3);
+        assert_eq!(frameworks.len(), 4);
+    }
+
+    #[test]
+    fn test_iso27001_controls() {
+        let controls = get_framework_controls(Framework::Iso27001);
+        assert_eq!(controls.len(), 93);
+
+        let codes: Vec<String> = get_framework_categories(Framework::Iso27001)
+            .into_iter()
+            .map(|c| c.code)
+            .collect();
+        assert_eq!(codes, vec!["A.5", "A.6", "A.7", "A.8"]);
+
+        let per_theme: Vec<usize> = codes
+            .iter()
+            .map(|code| controls.iter().filter(|c| &c.category == code).count())
+            .collect();
+        assert_eq!(per_theme, vec![37, 8, 14, 34]);
+    }
+
+    #[test]
+    fn test_control_mappings() {
+        let nist_to_soc2 = get_control_mappings(Framework::NistCsf2, Framework::Soc2TypeII);
+        let rm = nist_to_soc2.iter().find(|m| m.source_control == "GV.RM-01").unwrap();
+        assert_eq!(rm.target_controls, vec!["CC3.1"]);
+        assert_eq!(rm.strength, MappingStrength::Full);
+
+        // CC6.1 is referenced by several NIST controls
+        let aa = nist_to_soc2.iter().find(|m| m.source_control == "PR.AA-02").unwrap();
+        assert_eq!(aa.strength, MappingStrength::Partial);
+
+        // Links declared only on the ISO side still map in both directions
+        let soc2_to_iso = get_control_mappings(Framework::Soc2TypeII, Framework::Iso27001);
+        let cc41 = soc2_to_iso.iter().find(|m| m.source_control == "CC4.1").unwrap();
+        assert_eq!(cc41.target_controls, vec!["A.8.8"]);
+
+        let codes: HashSet<String> = get_framework_controls(Framework::Iso27001)
+            .into_iter()
+            .map(|c| c.code)
+            .collect();
+        for m in get_control_mappings(Framework::NistCsf2, Framework::Iso27001) {
+            assert!(m.target_controls.iter().all(|t| codes.contains(t)));
+        }
+    }
+
+    #[test]
+    fn test_project_control_statuses() {
+        let mut statuses = HashMap::new();
+        statuses.insert("GV.RM-01".to_string(), ComplianceStatus::Compliant);
+        statuses.insert("PR.AA-01".to_string(), ComplianceStatus::Compliant);
+        statuses.insert("PR.AA-02".to_string(), ComplianceStatus::NonCompliant);
+
+        let projected = project_control_statuses(Framework::NistCsf2, Framework::Soc2TypeII, &statuses);
+        let get = |code: &str| projected.iter().find(|p| p.code == code).unwrap();
+
+        assert_eq!(get("CC3.1").status, ComplianceStatus::Compliant);
+        assert!(get("CC3.1").inherited_compliant);
+        // CC6.2 maps from PR.AA-01 (compliant) and others that are not assessed
+        assert_eq!(get("CC6.2").status, ComplianceStatus::PartiallyCompliant);
+        assert!(!get("CC6.2").inherited_compliant);
+        // C1.2 has no NIST counterpart
+        assert!(get("C1.2").source_controls.is_empty());
+        assert_eq!(get("C1.2").status, ComplianceStatus::NotAssessed);
+
+        let report = build_compliance_report(
+            Framework::Soc2TypeII,
+            &get_framework_controls(Framework::Soc2TypeII),
+            |c| get(&c.code).status,
+        );
+        assert_eq!(report.compliant_controls, projected.iter().filter(|p| p.inherited_compliant).count());
+    }
+
+    #[test]
+    fn test_category_order_is_canonical() {
+        let codes: Vec<String> = get_framework_categories(Framework::NistCsf2)
+            .into_iter()
+            .map(|c| c.code)
+            .collect();
+        assert_eq!(codes, vec!["GV", "ID", "PR", "DE", "RS", "RC"]);
+
+        for framework in Framework::all() {
+            let orders: Vec<u32> = get_framework_categories(framework).iter().map(|c| c.order).collect();
+            assert!(orders.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    fn test_validate_category_groups() {
+        let group = |name: &str, order: u32, codes: &[&str]| CategoryGroup {
+            name: name.to_string(),
+            order,
+            category_codes: codes.iter().map(|c| c.to_string()).collect(),
+        };
+
+        let valid = vec![
+            group("Security", 0, &["CC"]),
+            group("Operational", 1, &["A", "PI"]),
+            group("Data Protection", 2, &["C", "P"]),
+        ];
+        assert!(validate_category_groups(Framework::Soc2TypeII, &valid).is_ok());
+        assert!(validate_category_groups(Framework::Soc2TypeII, &default_category_groups(Framework::Soc2TypeII)).is_ok());
+
+        let missing = vec![group("Security", 0, &["CC"]), group("Operational", 1, &["A", "PI"])];
+        assert!(validate_category_groups(Framework::Soc2TypeII, &missing).is_err());
+
+        let duplicate = vec![group("Security", 0, &["CC", "A"]), group("Other", 1, &["A", "PI", "C", "P"])];
+        assert!(validate_category_groups(Framework::Soc2TypeII, &duplicate).is_err());
+
+        let unknown = vec![group("All", 0, &["CC", "A", "PI", "C", "P", "GV"])];
+        assert!(validate_category_groups(Framework::Soc2TypeII, &unknown).is_err());
+    }
+
+    #[test]
+    fn test_search_multi_word() {
+        // Every term must match, in any field
+        let results = search_framework_controls(Framework::NistCsf2, "data protected", None, None, None);
+        let codes: Vec<&str> = results.iter().map(|r| r.control.code.as_str()).collect();
+        assert!(codes.contains(&"PR.DS-01") && codes.contains(&"PR.DS-02"));
+        assert!(results.iter().all(|r| {
+            let text = format!("{} {} {} {:?}", r.control.code, r.control.title, r.control.description, r.control.guidance).to_lowercase();
+            text.contains("data") && text.contains("protected")
+        }));
+        assert!(search_framework_controls(Framework::NistCsf2, "data xyzzy", None, None, None).is_empty());
+
+        // Title matches outrank guidance-only matches, and highlights point at the text
+        let results = search_framework_controls(Framework::NistCsf2, "ENCRYPTION", None, None, None);
+        let at_rest = results.iter().find(|r| r.control.code == "PR.DS-01").unwrap();
+        let highlight = at_rest.highlights.iter().find(|h| h.field == ControlField::Guidance).unwrap();
+        let guidance: Vec<char> = at_rest.control.guidance.as_ref().unwrap().chars().collect();
+        assert_eq!(guidance[highlight.start..highlight.end].iter().collect::<String>(), "encryption");
+
+        // An exact code ranks its control first
+        let results = search_framework_controls(Framework::NistCsf2, "pr.ds-02", None, None, None);
+        assert_eq!(results[0].control.code, "PR.DS-02");
+        assert!(results[0].score > EXACT_CODE_BONUS);
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let assessed = |code: &str, status| ControlAssessment {
+            id: code.to_string(),
+            assessment_id: "a1".to_string(),
+            control_id: code.to_string(),
+            status,
+            notes: None,
+            gap_description: None,
+            remediation: None,
+            remediation_target: None,
+            risk_rating: None,
+            maturity_level: None,
+            evidence_ids: vec![],
+            assessed_at: chrono::Utc::now(),
+            assessed_by: "Jane Auditor".to_string(),
+        };
+        let cas = vec![assessed("PR.DS-02", ComplianceStatus::Compliant)];
+
+        // Unassessed PR controls mentioning data
+        let results = search_framework_controls(
+            Framework::NistCsf2, "data", Some("pr"), Some(ComplianceStatus::NotAssessed), Some(&cas),
+        );
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.control.category == "PR" && r.status == Some(ComplianceStatus::NotAssessed)));
+        assert!(results.iter().all(|r| r.control.code != "PR.DS-02"));
+
+        let compliant = search_framework_controls(
+            Framework::NistCsf2, "data", Some("PR"), Some(ComplianceStatus::Compliant), Some(&cas),
+        );
+        assert_eq!(compliant.len(), 1);
+        assert_eq!(compliant[0].control.code, "PR.DS-02");
+
+        // Without an assessment there is no status to report
+        let results = search_framework_controls(Framework::NistCsf2, "data", Some("PR"), None, None);
+        assert!(results.iter().all(|r| r.status.is_none()));
+    }
+
+    #[test]
+    fn test_search_empty_query() {
+        let all = get_framework_controls(Framework::NistCsf2);
+        let results = search_framework_controls(Framework::NistCsf2, "   ", None, None, None);
+        assert_eq!(results.len(), all.len());
+        assert!(results.iter().all(|r| r.score == 0 && r.highlights.is_empty()));
+        // Framework order is kept
+        assert!(results.iter().zip(&all).all(|(r, c)| r.control.code == c.code));
+
+        // Filters still apply
+        let pr = search_framework_controls(Framework::NistCsf2, "", Some("PR"), None, None);
+        assert_eq!(pr.len(), all.iter().filter(|c| c.category == "PR").count());
+        let none_compliant = search_framework_controls(Framework::NistCsf2, "", None, Some(ComplianceStatus::Compliant), Some(&[]));
+        assert!(none_compliant.is_empty());
+    }
+
+    #[test]
+    fn test_rollup_category_groups() {
+        let status = |code: &str, total, assessed, compliant, partial, non_comp| CategoryComplianceStatus {
+            code: code.to_string(),
+            name: code.to_string(),
+            description: String::new(),
+            color: "#000000".to_string(),
+            total_controls: total,
+            assessed_controls: assessed,
+            compliant,
+            partially_compliant: partial,
+            non_compliant: non_comp,
+            completion_percentage: 0.0,
+            compliance_percentage: 0.0,
+            average_maturity: None,
+            maturity_rated: 0,
+        };
+        let mut breakdown = vec![status("A", 4, 4, 2, 0, 1), status("PI", 6, 3, 2, 1, 0)];
+        breakdown[0].average_maturity = Some(2.0);
+        breakdown[0].maturity_rated = 2;
+        breakdown[1].average_maturity = Some(4.0);
+        breakdown[1].maturity_rated = 1;
+        let groups = vec![CategoryGroup {
+            name: "Operational".to_string(),
+            order: 0,
+            category_codes: vec!["A".to_string(), "PI".to_string()],
+        }];
+
+        let rollup = rollup_category_groups(&breakdown, &groups);
+        assert_eq!(rollup.len(), 1);
+        assert_eq!(rollup[0].total_controls, 10);
+        assert_eq!(rollup[0].assessed_controls, 7);
+        assert_eq!(rollup[0].completion_percentage, 70.0);
+        // (4 compliant + 0.5 partial) / 6 applicable (one A control is N/A)
+        assert_eq!(rollup[0].compliance_percentage, 75.0);
+        // Weighted by rated controls: (2 x 2.0 + 1 x 4.0) / 3
+        assert_eq!(rollup[0].average_maturity, Some(2.7));
+        assert_eq!(rollup[0].maturity_rated, 3);
+    }
+
+    #[test]
+    fn test_apply_maturity() {
+        let controls = get_framework_controls(Framework::NistCsf2);
+        let mut report = build_compliance_report(Framework::NistCsf2, &controls, |_| ComplianceStatus::NotAssessed);
+        apply_maturity(&mut report, &controls, |c| match c.code.as_str() {
+            "GV.OC-01" => Some(2),
+            "GV.RM-01" => Some(3),
+            "PR.AA-01" => Some(5),
+            _ => None,
+        });
+
+        assert_eq!(report.average_maturity, Some(3.3));
+        let gv = report.category_breakdown.iter().find(|c| c.code == "GV").unwrap();
+        assert_eq!(gv.average_maturity, Some(2.5));
+        assert_eq!(gv.maturity_rated, 2);
+        let de = report.category_breakdown.iter().find(|c| c.code == "DE").unwrap();
+        assert_eq!(de.average_maturity, None);
+        assert_eq!(de.maturity_rated, 0);
+
+        // Every NIST CSF control describes all five levels
+        assert!(controls.iter().all(|c| c.maturity_descriptions.len() == 5));
+        assert_eq!(controls[0].maturity_scale()[2].name, "Defined");
     }
 }
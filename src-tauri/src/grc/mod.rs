@@ -1,12 +1,30 @@
 //! GRC (Governance, Risk, Compliance) Module
 //!
-//! Provides interactive audit, gap analysis, and policy generation
-//! supporting NIST CSF 2.0, SOC 2 Type II, and GDPR frameworks.
+//! Provides interactive audit, gap analysis, a risk register, remediation
+//! plans, compliance trends, assessment snapshots, a combined multi-framework
+//! overview, GDPR records of processing, assessment export/import, and policy
+//! generation supporting NIST CSF 2.0, SOC 2 Type II, and GDPR frameworks.
 
 pub mod models;
 pub mod frameworks;
 pub mod repository;
+pub mod risk;
+pub mod ropa;
+pub mod remediation;
+pub mod trend;
+pub mod snapshot;
+pub mod overview;
+pub mod transfer;
+pub mod policy_generator;
 
 pub use models::*;
 pub use frameworks::*;
 pub use repository::*;
+pub use risk::*;
+pub use ropa::*;
+pub use remediation::*;
+pub use trend::*;
+pub use snapshot::*;
+pub use overview::*;
+pub use transfer::*;
+pub use policy_generator::*;
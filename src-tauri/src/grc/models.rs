@@ -3,6 +3,8 @@
 //! Core data structures for compliance frameworks, controls, evidence,
 //! and assessments.
 
+use crate::grc::overview::ComplianceOverview;
+use crate::grc::trend::ComplianceTrendPoint;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +18,8 @@ pub enum Framework {
     Soc2TypeII,
     /// General Data Protection Regulation
     Gdpr,
+    /// ISO/IEC 27001:2022 Annex A
+    Iso27001,
 }
 
 impl Framework {
@@ -24,6 +28,7 @@ impl Framework {
             Framework::NistCsf2 => "NIST CSF 2.0",
             Framework::Soc2TypeII => "SOC 2 Type II",
             Framework::Gdpr => "GDPR",
+            Framework::Iso27001 => "ISO/IEC 27001:2022",
         }
     }
 
@@ -32,11 +37,12 @@ impl Framework {
             Framework::NistCsf2 => "NIST Cybersecurity Framework version 2.0 - A voluntary framework for managing cybersecurity risk",
             Framework::Soc2TypeII => "Service Organization Control 2 Type II - Trust Services Criteria for security, availability, processing integrity, confidentiality, and privacy",
             Framework::Gdpr => "General Data Protection Regulation - EU regulation on data protection and privacy",
+            Framework::Iso27001 => "ISO/IEC 27001:2022 Annex A - Information security controls for an ISMS across organizational, people, physical, and technological themes",
         }
     }
 
     pub fn all() -> Vec<Framework> {
-        vec![Framework::NistCsf2, Framework::Soc2TypeII, Framework::Gdpr]
+        vec![Framework::NistCsf2, Framework::Soc2TypeII, Framework::Gdpr, Framework::Iso27001]
     }
 }
 
@@ -226,6 +232,98 @@ impl GdprChapter {
     }
 }
 
+/// ISO/IEC 27001:2022 Annex A themes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Iso27001Theme {
+    Organizational,
+    People,
+    Physical,
+    Technological,
+}
+
+impl Iso27001Theme {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Iso27001Theme::Organizational => "A.5",
+            Iso27001Theme::People => "A.6",
+            Iso27001Theme::Physical => "A.7",
+            Iso27001Theme::Technological => "A.8",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Iso27001Theme::Organizational => "Organizational",
+            Iso27001Theme::People => "People",
+            Iso27001Theme::Physical => "Physical",
+            Iso27001Theme::Technological => "Technological",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Iso27001Theme::Organizational => "Policies, roles, asset management, suppliers, incidents, continuity, and compliance",
+            Iso27001Theme::People => "Screening, employment terms, awareness, and responsibilities of personnel",
+            Iso27001Theme::Physical => "Secure areas, equipment, media, and supporting utilities",
+            Iso27001Theme::Technological => "Endpoint, access, network, logging, development, and cryptographic controls",
+        }
+    }
+
+    pub fn color(&self) -> &'static str {
+        match self {
+            Iso27001Theme::Organizational => "#3b82f6",
+            Iso27001Theme::People => "#22c55e",
+            Iso27001Theme::Physical => "#f59e0b",
+            Iso27001Theme::Technological => "#8b5cf6",
+        }
+    }
+
+    pub fn all() -> Vec<Iso27001Theme> {
+        vec![
+            Iso27001Theme::Organizational,
+            Iso27001Theme::People,
+            Iso27001Theme::Physical,
+            Iso27001Theme::Technological,
+        ]
+    }
+}
+
+/// Highest control maturity level
+pub const MAX_MATURITY_LEVEL: u8 = 5;
+
+/// Names of maturity levels 1 to 5
+pub const MATURITY_LEVEL_NAMES: [&str; 5] = ["Initial", "Developing", "Defined", "Managed", "Optimizing"];
+
+/// What a control looks like at one maturity level
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaturityDescriptor {
+    /// 1 (Initial) to 5 (Optimizing)
+    pub level: u8,
+    pub name: String,
+    pub description: String,
+}
+
+/// Check a maturity level is between 1 and 5
+pub fn validate_maturity_level(level: u8) -> Result<(), String> {
+    if (1..=MAX_MATURITY_LEVEL).contains(&level) {
+        Ok(())
+    } else {
+        Err(format!("Maturity level must be between 1 and {}", MAX_MATURITY_LEVEL))
+    }
+}
+
+/// Mean of the given maturity levels to one decimal; None without any
+pub fn average_maturity(levels: impl IntoIterator<Item = u8>) -> Option<f64> {
+    let (sum, count) = levels.into_iter().fold((0u32, 0u32), |(sum, count), l| (sum + l as u32, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some((sum as f64 / count as f64 * 10.0).round() / 10.0)
+    }
+}
+
 /// A compliance control/requirement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -234,7 +332,7 @@ pub struct Control {
     pub id: String,
     /// Framework this control belongs to
     pub framework: Framework,
-    /// Control code (e.g., "PR.AC-1", "CC6.1", "Art. 25")
+    /// Control code (e.g., "PR.AC-1", "CC6.1", "Art. 25", "A.8.8")
     pub code: String,
     /// Category/Function/Chapter this control belongs to
     pub category: String,
@@ -244,12 +342,42 @@ pub struct Control {
     pub title: String,
     /// Full description of the control
     pub description: String,
-    /// Implementation guidance
+    /// Implementation guidance: what good looks like
     pub guidance: Option<String>,
     /// Related controls in other frameworks
     pub cross_references: Vec<String>,
     /// Priority/importance level (1-5)
     pub priority: u8,
+    /// Control-specific descriptors for maturity levels 1-5; empty where
+    /// the library has none (see `maturity_scale`)
+    #[serde(default)]
+    pub maturity_descriptions: Vec<MaturityDescriptor>,
+}
+
+impl Control {
+    /// The control's maturity descriptors, or the generic scale without them
+    pub fn maturity_scale(&self) -> Vec<MaturityDescriptor> {
+        if !self.maturity_descriptions.is_empty() {
+            return self.maturity_descriptions.clone();
+        }
+        const GENERIC: [&str; 5] = [
+            "Ad hoc or undocumented; depends on individuals",
+            "Partly implemented and repeated informally",
+            "Documented, approved and applied consistently",
+            "Measured and reviewed against defined targets",
+            "Continuously improved from metrics and lessons learned",
+        ];
+        MATURITY_LEVEL_NAMES
+            .iter()
+            .zip(GENERIC)
+            .enumerate()
+            .map(|(i, (name, description))| MaturityDescriptor {
+                level: i as u8 + 1,
+                name: name.to_string(),
+                description: description.to_string(),
+            })
+            .collect()
+    }
 }
 
 /// Compliance status for a control
@@ -362,6 +490,9 @@ pub struct Evidence {
     pub collected_by: String,
     /// Notes about the evidence
     pub notes: Option<String>,
+    /// Referenced from an earlier assessment rather than collected for this one
+    #[serde(default)]
+    pub carried_forward: bool,
 }
 
 /// Assessment of a control within an engagement
@@ -386,6 +517,9 @@ pub struct ControlAssessment {
     pub remediation_target: Option<DateTime<Utc>>,
     /// Risk rating if non-compliant (1-5)
     pub risk_rating: Option<u8>,
+    /// Maturity level (1-5), rated alongside the compliance status
+    #[serde(default)]
+    pub maturity_level: Option<u8>,
     /// Evidence IDs supporting this assessment
     pub evidence_ids: Vec<String>,
     /// When last assessed
@@ -394,6 +528,72 @@ pub struct ControlAssessment {
     pub assessed_by: String,
 }
 
+/// One saved version of a control's assessment
+///
+/// Every save appends a revision; the latest is what `ControlAssessment`
+/// holds. Reverting copies an older revision forward as a new one, so the
+/// history is never rewritten.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlAssessmentRevision {
+    /// 1 for the control's first assessment, counting up with each save
+    pub revision: u32,
+    pub assessment_id: String,
+    pub control_id: String,
+    pub status: ComplianceStatus,
+    pub notes: Option<String>,
+    pub gap_description: Option<String>,
+    pub remediation: Option<String>,
+    pub remediation_target: Option<DateTime<Utc>>,
+    pub risk_rating: Option<u8>,
+    pub maturity_level: Option<u8>,
+    pub assessed_at: DateTime<Utc>,
+    pub assessed_by: String,
+    /// Revision this one restored, when saved by a revert
+    pub reverted_from: Option<u32>,
+}
+
+/// Prefix marking notes carried over from the assessment a clone was made from
+pub const PRIOR_YEAR_PREFIX: &str = "Prior year: ";
+
+impl ControlAssessment {
+    /// Copy of this result for a cloned assessment
+    ///
+    /// With `reset_status` the control starts again as Not Assessed and its
+    /// notes and gap description are kept as prior-year annotations; the
+    /// risk rating is dropped since it rated the old result.
+    pub fn carry_forward(&self, assessment_id: &str, reset_status: bool) -> ControlAssessment {
+        let mut ca = ControlAssessment {
+            id: uuid::Uuid::new_v4().to_string(),
+            assessment_id: assessment_id.to_string(),
+            evidence_ids: vec![],
+            ..self.clone()
+        };
+
+        if reset_status {
+            let annotate = |text: &Option<String>| {
+                text.as_ref()
+                    .filter(|t| !t.trim().is_empty())
+                    .map(|t| if t.starts_with(PRIOR_YEAR_PREFIX) { t.clone() } else { format!("{}{}", PRIOR_YEAR_PREFIX, t) })
+            };
+            ca.status = ComplianceStatus::NotAssessed;
+            ca.notes = annotate(&self.notes);
+            ca.gap_description = annotate(&self.gap_description);
+            ca.risk_rating = None;
+            ca.maturity_level = None;
+        }
+        ca
+    }
+
+    /// The maturity level, unless the control is not applicable
+    pub fn rated_maturity(&self) -> Option<u8> {
+        match self.status {
+            ComplianceStatus::NotApplicable => None,
+            _ => self.maturity_level,
+        }
+    }
+}
+
 /// A compliance assessment/audit engagement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -418,6 +618,9 @@ pub struct Assessment {
     pub lead_assessor: String,
     /// Assessment status
     pub status: AssessmentStatus,
+    /// Assessment this one was cloned from, for year-over-year traceability
+    #[serde(default)]
+    pub source_assessment_id: Option<String>,
 }
 
 /// Status of an assessment
@@ -446,6 +649,48 @@ impl AssessmentStatus {
             AssessmentStatus::Archived => "Archived",
         }
     }
+
+    /// Statuses an assessment can move to from this one
+    ///
+    /// Work moves forward Draft → In Progress → Under Review → Completed →
+    /// Archived; a review can send it back to In Progress. Completed and
+    /// Archived assessments only go back to In Progress by being reopened.
+    pub fn allowed_transitions(&self) -> &'static [AssessmentStatus] {
+        match self {
+            AssessmentStatus::Draft => &[AssessmentStatus::InProgress],
+            AssessmentStatus::InProgress => &[AssessmentStatus::UnderReview],
+            AssessmentStatus::UnderReview => &[AssessmentStatus::InProgress, AssessmentStatus::Completed],
+            AssessmentStatus::Completed => &[AssessmentStatus::Archived],
+            AssessmentStatus::Archived => &[],
+        }
+    }
+
+    /// Check a status change, explaining why it isn't allowed
+    pub fn validate_transition(&self, next: AssessmentStatus) -> Result<(), String> {
+        if self.allowed_transitions().contains(&next) {
+            return Ok(());
+        }
+
+        let allowed: Vec<&str> = self.allowed_transitions().iter().map(|s| s.display_name()).collect();
+        let hint = if self.is_locked() && next == AssessmentStatus::InProgress {
+            " (reopen the assessment instead)".to_string()
+        } else if allowed.is_empty() {
+            String::new()
+        } else {
+            format!(" (allowed: {})", allowed.join(", "))
+        };
+        Err(format!(
+            "Cannot move an assessment from {} to {}{}",
+            self.display_name(),
+            next.display_name(),
+            hint
+        ))
+    }
+
+    /// Whether control results and evidence are frozen
+    pub fn is_locked(&self) -> bool {
+        matches!(self, AssessmentStatus::Completed | AssessmentStatus::Archived)
+    }
 }
 
 /// Summary statistics for a category/function
@@ -472,6 +717,9 @@ pub struct CategoryScore {
     pub not_applicable: usize,
     /// Compliance percentage (0-100)
     pub compliance_percentage: f64,
+    /// Average maturity level of the rated controls, if any were rated
+    #[serde(default)]
+    pub average_maturity: Option<f64>,
 }
 
 /// Overall assessment summary
@@ -492,12 +740,152 @@ pub struct AssessmentSummary {
     pub non_compliant: usize,
     pub not_assessed: usize,
     pub not_applicable: usize,
+    /// Average maturity level of the rated controls, if any were rated
+    #[serde(default)]
+    pub average_maturity: Option<f64>,
     /// Breakdown by category
     pub category_scores: Vec<CategoryScore>,
     /// High-risk gaps (non-compliant with risk >= 4)
     pub high_risk_gaps: usize,
     /// Evidence count
     pub evidence_count: usize,
+    /// Change since the assessment this one was cloned from
+    #[serde(default)]
+    pub source_comparison: Option<AssessmentComparison>,
+}
+
+/// Number of an assessment's control results with one status and risk rating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ControlResultCount {
+    pub status: ComplianceStatus,
+    pub risk_rating: Option<u8>,
+    pub count: usize,
+}
+
+/// An assessment's control results, counted rather than loaded
+#[derive(Debug, Clone)]
+pub struct AssessmentResultCounts {
+    pub assessment_id: String,
+    pub client_id: String,
+    pub framework: Framework,
+    pub started_at: DateTime<Utc>,
+    /// Recorded results other than not assessed
+    pub results: Vec<ControlResultCount>,
+}
+
+impl AssessmentResultCounts {
+    pub fn count(&self, status: ComplianceStatus) -> usize {
+        self.results.iter().filter(|r| r.status == status).map(|r| r.count).sum()
+    }
+
+    /// Overall compliance percentage, scored as in the assessment summary
+    ///
+    /// Controls of the framework without a recorded result are not assessed.
+    pub fn overall_compliance(&self, total_controls: usize) -> f64 {
+        let applicable = total_controls.saturating_sub(self.count(ComplianceStatus::NotApplicable));
+        let compliance = if applicable > 0 {
+            let met = self.count(ComplianceStatus::Compliant) as f64
+                + self.count(ComplianceStatus::PartiallyCompliant) as f64 * 0.5;
+            (met / applicable as f64 * 100.0).min(100.0)
+        } else {
+            100.0
+        };
+        (compliance * 10.0).round() / 10.0
+    }
+}
+
+/// Change in one category between two assessments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryDelta {
+    /// Category identifier
+    pub category: String,
+    /// Display name
+    pub display_name: String,
+    /// Color for visualization
+    pub color: String,
+    /// Compliant controls in the baseline assessment
+    pub baseline_compliant: usize,
+    /// Compliant controls in the current assessment
+    pub current_compliant: usize,
+    /// Change in compliant controls
+    pub compliant_delta: i64,
+    /// Compliance percentage in the baseline assessment
+    pub baseline_percentage: f64,
+    /// Compliance percentage in the current assessment
+    pub current_percentage: f64,
+    /// Change in compliance percentage points
+    pub percentage_delta: f64,
+}
+
+/// Year-over-year comparison of two assessments of the same framework
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssessmentComparison {
+    /// Earlier assessment
+    pub baseline_id: String,
+    /// Later assessment
+    pub current_id: String,
+    pub framework: Framework,
+    pub baseline_compliance: f64,
+    pub current_compliance: f64,
+    /// Change in overall compliance percentage points
+    pub compliance_delta: f64,
+    /// Change in compliant controls
+    pub compliant_delta: i64,
+    /// Change in non-compliant controls
+    pub non_compliant_delta: i64,
+    /// Per-category changes, in the current assessment's category order
+    pub categories: Vec<CategoryDelta>,
+}
+
+impl AssessmentComparison {
+    /// Compare `current` against `baseline`
+    ///
+    /// Categories missing from the baseline count as having had no compliant controls.
+    pub fn between(baseline: &AssessmentSummary, current: &AssessmentSummary) -> Result<Self, String> {
+        if baseline.framework != current.framework {
+            return Err(format!(
+                "Cannot compare a {} assessment with a {} assessment",
+                baseline.framework.display_name(),
+                current.framework.display_name()
+            ));
+        }
+
+        let round = |v: f64| (v * 10.0).round() / 10.0;
+        let categories = current
+            .category_scores
+            .iter()
+            .map(|cat| {
+                let before = baseline.category_scores.iter().find(|b| b.category == cat.category);
+                let baseline_compliant = before.map(|b| b.compliant).unwrap_or(0);
+                let baseline_percentage = before.map(|b| b.compliance_percentage).unwrap_or(0.0);
+                CategoryDelta {
+                    category: cat.category.clone(),
+                    display_name: cat.display_name.clone(),
+                    color: cat.color.clone(),
+                    baseline_compliant,
+                    current_compliant: cat.compliant,
+                    compliant_delta: cat.compliant as i64 - baseline_compliant as i64,
+                    baseline_percentage,
+                    current_percentage: cat.compliance_percentage,
+                    percentage_delta: round(cat.compliance_percentage - baseline_percentage),
+                }
+            })
+            .collect();
+
+        Ok(AssessmentComparison {
+            baseline_id: baseline.assessment_id.clone(),
+            current_id: current.assessment_id.clone(),
+            framework: current.framework,
+            baseline_compliance: baseline.overall_compliance,
+            current_compliance: current.overall_compliance,
+            compliance_delta: round(current.overall_compliance - baseline.overall_compliance),
+            compliant_delta: current.compliant as i64 - baseline.compliant as i64,
+            non_compliant_delta: current.non_compliant as i64 - baseline.non_compliant as i64,
+            categories,
+        })
+    }
 }
 
 /// Compliance status for a framework (aggregate across all client assessments)
@@ -524,6 +912,12 @@ pub struct ComplianceStatusReport {
     pub not_applicable_controls: usize,
     /// Breakdown by category (NIST functions, SOC2 categories, etc.)
     pub category_breakdown: Vec<CategoryComplianceStatus>,
+    /// Rollup by the client's category groups (empty unless grouping was requested)
+    #[serde(default)]
+    pub group_breakdown: Vec<CategoryComplianceStatus>,
+    /// Average maturity level of the rated controls, if any were rated
+    #[serde(default)]
+    pub average_maturity: Option<f64>,
     /// Network health score (if available)
     pub network_health_score: Option<f64>,
     /// Total assets discovered
@@ -558,6 +952,170 @@ pub struct CategoryComplianceStatus {
     pub completion_percentage: f64,
     /// Compliance percentage
     pub compliance_percentage: f64,
+    /// Average maturity level of the rated controls, if any were rated
+    #[serde(default)]
+    pub average_maturity: Option<f64>,
+    /// Controls with a maturity level, to weight rollups by
+    #[serde(default)]
+    pub maturity_rated: usize,
+}
+
+/// Evidence linked to one framework control within an assessment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlEvidenceCoverage {
+    /// Control code (e.g., "CC6.1")
+    pub control_code: String,
+    /// Control title
+    pub title: String,
+    /// Category/Function/Chapter the control belongs to
+    pub category: String,
+    /// Assessed status (NotAssessed when no assessment was recorded)
+    pub status: ComplianceStatus,
+    /// Evidence items linked to the control
+    pub evidence_count: usize,
+    /// Marked compliant without any supporting evidence
+    pub unsupported: bool,
+}
+
+/// Evidence coverage across every control of an assessment's framework
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenceCoverage {
+    pub assessment_id: String,
+    pub framework: Framework,
+    /// Total evidence items in the assessment
+    pub total_evidence: usize,
+    /// Controls with at least one linked evidence item
+    pub controls_with_evidence: usize,
+    /// Controls marked compliant with no evidence
+    pub unsupported_controls: usize,
+    /// One entry per framework control, in framework order
+    pub controls: Vec<ControlEvidenceCoverage>,
+}
+
+/// A named display group that rolls up one or more framework categories
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryGroup {
+    /// Display name of the group (e.g. "Operational")
+    pub name: String,
+    /// Sort order of the group in reports
+    pub order: u32,
+    /// Category codes rolled up into this group
+    pub category_codes: Vec<String>,
+}
+
+/// Client-specific grouping of a framework's categories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryGrouping {
+    /// Unique identifier
+    pub id: String,
+    /// Client this grouping belongs to
+    pub client_id: String,
+    /// Framework the grouping applies to
+    pub framework: Framework,
+    /// Display groups, each category assigned exactly once
+    pub groups: Vec<CategoryGroup>,
+    /// When the grouping was created
+    pub created_at: DateTime<Utc>,
+    /// When the grouping was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+/// How closely a source control covers the controls it maps to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MappingStrength {
+    /// One-to-one: the source and target cover the same requirement
+    Full,
+    /// One-to-many or many-to-one: the source covers part of the target
+    Partial,
+}
+
+/// Mapping of one control onto the controls of another framework
+///
+/// Controls are referenced by code, which is stable across calls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlMapping {
+    /// Source control code
+    pub source_control: String,
+    /// Target framework control codes covered by the source
+    pub target_controls: Vec<String>,
+    /// Mapping strength
+    pub strength: MappingStrength,
+}
+
+/// A target framework control with its status projected from a source assessment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectedControl {
+    /// Target control code
+    pub code: String,
+    /// Target control title
+    pub title: String,
+    /// Target control category
+    pub category: String,
+    /// Projected compliance status
+    pub status: ComplianceStatus,
+    /// Source control codes that map onto this control (empty if unmapped)
+    pub source_controls: Vec<String>,
+    /// Weakest strength among the mappings onto this control
+    pub strength: Option<MappingStrength>,
+    /// Every mapped source control is compliant
+    pub inherited_compliant: bool,
+}
+
+/// Projection of an assessment onto another framework
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkProjection {
+    /// Assessment the projection was built from
+    pub assessment_id: String,
+    /// Framework of the source assessment
+    pub source_framework: Framework,
+    /// Projected compliance status for the target framework
+    pub report: ComplianceStatusReport,
+    /// Per-control projection, in target framework order
+    pub controls: Vec<ProjectedControl>,
+    /// Target controls inherited as compliant
+    pub inherited_compliant_controls: usize,
+    /// Target control codes with no mapped source control
+    pub unmapped_controls: Vec<String>,
+}
+
+/// Control field a search term matched in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ControlField {
+    Code,
+    Title,
+    Description,
+    Guidance,
+}
+
+/// Matched text within a control field, as character offsets (end exclusive)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchHighlight {
+    pub field: ControlField,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A control matching a search, with where it matched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlSearchResult {
+    pub control: Control,
+    /// Status in the assessment searched against, if one was given
+    pub status: Option<ComplianceStatus>,
+    /// Relevance; higher is better, 0 for an empty query
+    pub score: u32,
+    /// Every occurrence of every query term, ordered by field then offset
+    pub highlights: Vec<MatchHighlight>,
 }
 
 /// Data for generating an executive report
@@ -566,12 +1124,21 @@ pub struct CategoryComplianceStatus {
 pub struct ExecutiveReportData {
     /// Client name
     pub client_name: String,
+    /// Client's primary contact
+    #[serde(default)]
+    pub client_contact: Option<String>,
     /// Report title
     pub title: String,
     /// Report date
     pub report_date: String,
     /// Compliance status from GRC
     pub compliance_status: Option<ComplianceStatusReport>,
+    /// Compliance across all the client's assessed frameworks
+    #[serde(default)]
+    pub compliance_overview: Option<ComplianceOverview>,
+    /// Compliance over time from the client's snapshots, oldest first
+    #[serde(default)]
+    pub compliance_trend: Vec<ComplianceTrendPoint>,
     /// Network health score (0-100)
     pub network_health_score: f64,
     /// Total assets discovered
@@ -613,3 +1180,139 @@ pub struct RiskSummary {
     pub low_count: usize,
     pub overall_risk_rating: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATUSES: [AssessmentStatus; 5] = [
+        AssessmentStatus::Draft,
+        AssessmentStatus::InProgress,
+        AssessmentStatus::UnderReview,
+        AssessmentStatus::Completed,
+        AssessmentStatus::Archived,
+    ];
+
+    #[test]
+    fn test_assessment_transition_matrix() {
+        use AssessmentStatus::*;
+        let allowed = [
+            (Draft, InProgress),
+            (InProgress, UnderReview),
+            (UnderReview, InProgress),
+            (UnderReview, Completed),
+            (Completed, Archived),
+        ];
+
+        for from in ALL_STATUSES {
+            for to in ALL_STATUSES {
+                let expected = allowed.contains(&(from, to));
+                assert_eq!(
+                    from.validate_transition(to).is_ok(),
+                    expected,
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_transition_errors() {
+        let err = AssessmentStatus::Draft.validate_transition(AssessmentStatus::Archived).unwrap_err();
+        assert_eq!(err, "Cannot move an assessment from Draft to Archived (allowed: In Progress)");
+
+        let err = AssessmentStatus::Completed.validate_transition(AssessmentStatus::InProgress).unwrap_err();
+        assert!(err.contains("reopen"), "{}", err);
+
+        let locked: Vec<AssessmentStatus> = ALL_STATUSES.into_iter().filter(|s| s.is_locked()).collect();
+        assert_eq!(locked, vec![AssessmentStatus::Completed, AssessmentStatus::Archived]);
+    }
+
+    #[test]
+    fn test_carry_forward_control() {
+        let ca = ControlAssessment {
+            id: "ca-1".to_string(),
+            assessment_id: "2025".to_string(),
+            control_id: "PR.AA-01".to_string(),
+            status: ComplianceStatus::PartiallyCompliant,
+            notes: Some("MFA on VPN only".to_string()),
+            gap_description: Some("No MFA for email".to_string()),
+            remediation: Some("Enforce MFA tenant-wide".to_string()),
+            remediation_target: None,
+            risk_rating: Some(4),
+            maturity_level: Some(3),
+            evidence_ids: vec!["ev-1".to_string()],
+            assessed_at: Utc::now(),
+            assessed_by: "Assessor".to_string(),
+        };
+
+        let kept = ca.carry_forward("2026", false);
+        assert_ne!(kept.id, ca.id);
+        assert_eq!(kept.assessment_id, "2026");
+        assert_eq!(kept.status, ComplianceStatus::PartiallyCompliant);
+        assert_eq!(kept.notes, ca.notes);
+        assert_eq!(kept.risk_rating, Some(4));
+        assert_eq!(kept.maturity_level, Some(3));
+
+        let reset = ca.carry_forward("2026", true);
+        assert_eq!(reset.status, ComplianceStatus::NotAssessed);
+        assert_eq!(reset.notes.as_deref(), Some("Prior year: MFA on VPN only"));
+        assert_eq!(reset.gap_description.as_deref(), Some("Prior year: No MFA for email"));
+        assert_eq!(reset.remediation, ca.remediation);
+        assert_eq!(reset.risk_rating, None);
+        assert_eq!(reset.maturity_level, None);
+
+        // Cloning a clone doesn't stack the annotation
+        let again = reset.carry_forward("2027", true);
+        assert_eq!(again.notes, reset.notes);
+    }
+
+    #[test]
+    fn test_assessment_comparison() {
+        let score = |category: &str, compliant: usize, pct: f64| CategoryScore {
+            category: category.to_string(),
+            display_name: category.to_string(),
+            color: "#000000".to_string(),
+            total_controls: 5,
+            compliant,
+            partially_compliant: 0,
+            non_compliant: 5 - compliant,
+            not_assessed: 0,
+            not_applicable: 0,
+            compliance_percentage: pct,
+            average_maturity: None,
+        };
+        let summary = |id: &str, overall: f64, categories: Vec<CategoryScore>| AssessmentSummary {
+            assessment_id: id.to_string(),
+            framework: Framework::NistCsf2,
+            overall_compliance: overall,
+            total_controls: 10,
+            compliant: categories.iter().map(|c| c.compliant).sum(),
+            partially_compliant: 0,
+            non_compliant: categories.iter().map(|c| c.non_compliant).sum(),
+            not_assessed: 0,
+            not_applicable: 0,
+            average_maturity: None,
+            category_scores: categories,
+            high_risk_gaps: 0,
+            evidence_count: 0,
+            source_comparison: None,
+        };
+
+        let baseline = summary("2025", 40.0, vec![score("GV", 3, 60.0), score("PR", 1, 20.0)]);
+        let current = summary("2026", 70.0, vec![score("GV", 2, 40.0), score("PR", 5, 100.0)]);
+        let comparison = AssessmentComparison::between(&baseline, &current).unwrap();
+
+        assert_eq!(comparison.compliance_delta, 30.0);
+        assert_eq!(comparison.compliant_delta, 3);
+        assert_eq!(comparison.non_compliant_delta, -3);
+        let deltas: Vec<(i64, f64)> = comparison.categories.iter().map(|c| (c.compliant_delta, c.percentage_delta)).collect();
+        assert_eq!(deltas, vec![(-1, -20.0), (4, 80.0)]);
+
+        let mut other = current.clone();
+        other.framework = Framework::Iso27001;
+        assert!(AssessmentComparison::between(&baseline, &other).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Combined Compliance Overview
+//!
+//! One posture view for a client assessed against several frameworks at
+//! once. Each framework's status comes from the client's non-archived
+//! assessments of it; the overall score blends the frameworks, weighted by
+//! their applicable assessed controls unless weights are given. High-risk
+//! gaps on controls mapped to each other across frameworks are reported once.
+
+use crate::db::Database;
+use crate::error::OptioResult;
+use crate::grc::frameworks::{apply_maturity, build_compliance_report, get_control_mappings, get_framework_controls};
+use crate::grc::models::{AssessmentStatus, ComplianceStatus, ComplianceStatusReport, ControlMapping, Framework};
+use crate::grc::repository::{AssessmentRepository, ControlAssessmentRepository};
+use crate::grc::trend::{control_result, latest_control_assessments};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Risk rating from which a non-compliant control counts as a high-risk gap
+const HIGH_RISK_RATING: u8 = 4;
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// How the frameworks were weighted in the overall score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OverviewWeighting {
+    /// By each framework's applicable assessed controls
+    ControlCount,
+    /// By weights given per framework
+    Custom,
+}
+
+/// One framework's current status within the overview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkScore {
+    pub framework: Framework,
+    /// Weight in the overall score; 0 when nothing applicable was assessed
+    pub weight: f64,
+    pub status: ComplianceStatusReport,
+}
+
+/// A control in another framework mapped to a gap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedControl {
+    pub framework: Framework,
+    pub code: String,
+}
+
+/// A non-compliant control rated high risk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverviewGap {
+    pub framework: Framework,
+    pub control_code: String,
+    pub control_title: String,
+    pub risk_rating: u8,
+    pub gap_description: Option<String>,
+    /// Gaps in other frameworks on controls mapped to this one, folded into it
+    pub related_controls: Vec<RelatedControl>,
+}
+
+/// A client's compliance across all the frameworks it is assessed against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceOverview {
+    pub client_id: String,
+    /// Frameworks with at least one active assessment, in framework order
+    pub frameworks: Vec<FrameworkScore>,
+    /// Weighted blend of the framework compliance percentages
+    pub overall_compliance_percentage: f64,
+    /// Share of all the frameworks' controls that have been assessed
+    pub overall_completion_percentage: f64,
+    pub weighting: OverviewWeighting,
+    /// High-risk gaps, highest risk first, de-duplicated across mapped controls
+    pub high_risk_gaps: Vec<OverviewGap>,
+    pub total_controls: usize,
+    /// Controls not yet assessed, summed over the frameworks
+    pub unassessed_controls: usize,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ComplianceOverview {
+    /// Whether more than one framework contributes to the overview
+    pub fn is_multi_framework(&self) -> bool {
+        self.frameworks.len() > 1
+    }
+
+    /// The framework with the most controls assessed, for views that show one in detail
+    pub fn primary_framework(&self) -> Option<&FrameworkScore> {
+        self.frameworks.iter().max_by_key(|f| f.status.assessed_controls)
+    }
+}
+
+// ============================================================================
+// Overview
+// ============================================================================
+
+/// Build a client's compliance overview
+///
+/// With `weights`, each framework is weighted by its entry (1.0 when it
+/// has none) instead of by its applicable assessed controls.
+pub fn build_compliance_overview(
+    db: &Database,
+    client_id: &str,
+    weights: Option<&HashMap<Framework, f64>>,
+) -> OptioResult<ComplianceOverview> {
+    let assessments: Vec<_> = AssessmentRepository::new(db)
+        .list_by_client(client_id)?
+        .into_iter()
+        .filter(|a| a.status != AssessmentStatus::Archived)
+        .collect();
+    let control_repo = ControlAssessmentRepository::new(db);
+
+    let mut frameworks = Vec::new();
+    let mut gaps = Vec::new();
+    for framework in Framework::all() {
+        let active: Vec<_> = assessments.iter().filter(|a| a.framework == framework).collect();
+        if active.is_empty() {
+            continue;
+        }
+        let latest = latest_control_assessments(&control_repo, active)?;
+        let controls = get_framework_controls(framework);
+
+        for control in &controls {
+            if let Some(ca) = control_result(&latest, control) {
+                let risk = ca.risk_rating.unwrap_or(0);
+                if ca.status == ComplianceStatus::NonCompliant && risk >= HIGH_RISK_RATING {
+                    gaps.push(OverviewGap {
+                        framework,
+                        control_code: control.code.clone(),
+                        control_title: control.title.clone(),
+                        risk_rating: risk,
+                        gap_description: ca.gap_description.clone(),
+                        related_controls: vec![],
+                    });
+                }
+            }
+        }
+
+        let mut status = build_compliance_report(framework, &controls, |control| {
+            control_result(&latest, control)
+                .map(|ca| ca.status)
+                .unwrap_or(ComplianceStatus::NotAssessed)
+        });
+        apply_maturity(&mut status, &controls, |control| {
+            control_result(&latest, control).and_then(|ca| ca.rated_maturity())
+        });
+        let weight = weights.map(|w| w.get(&framework).copied().unwrap_or(1.0));
+        frameworks.push((status, weight));
+    }
+
+    Ok(combine_frameworks(client_id, frameworks, gaps))
+}
+
+/// Blend framework statuses and fold gaps on mapped controls together
+///
+/// A framework with nothing applicable assessed has no compliance score,
+/// so it gets no weight whatever weight was asked for.
+pub fn combine_frameworks(
+    client_id: &str,
+    statuses: Vec<(ComplianceStatusReport, Option<f64>)>,
+    gaps: Vec<OverviewGap>,
+) -> ComplianceOverview {
+    let weighting = if statuses.iter().any(|(_, w)| w.is_some()) {
+        OverviewWeighting::Custom
+    } else {
+        OverviewWeighting::ControlCount
+    };
+
+    let frameworks: Vec<FrameworkScore> = statuses
+        .into_iter()
+        .map(|(status, weight)| {
+            let applicable = status.assessed_controls - status.not_applicable_controls;
+            let weight = if applicable == 0 {
+                0.0
+            } else {
+                weight.unwrap_or(applicable as f64).max(0.0)
+            };
+            FrameworkScore { framework: status.framework, weight, status }
+        })
+        .collect();
+
+    let total_weight: f64 = frameworks.iter().map(|f| f.weight).sum();
+    let overall_compliance = if total_weight > 0.0 {
+        frameworks.iter().map(|f| f.status.compliance_percentage * f.weight).sum::<f64>() / total_weight
+    } else {
+        0.0
+    };
+
+    let total_controls: usize = frameworks.iter().map(|f| f.status.total_controls).sum();
+    let assessed_controls: usize = frameworks.iter().map(|f| f.status.assessed_controls).sum();
+    let overall_completion = if total_controls > 0 {
+        assessed_controls as f64 / total_controls as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    ComplianceOverview {
+        client_id: client_id.to_string(),
+        frameworks,
+        overall_compliance_percentage: (overall_compliance * 10.0).round() / 10.0,
+        overall_completion_percentage: (overall_completion * 10.0).round() / 10.0,
+        weighting,
+        high_risk_gaps: dedupe_gaps(gaps),
+        total_controls,
+        unassessed_controls: total_controls - assessed_controls,
+        generated_at: Utc::now(),
+    }
+}
+
+/// Highest risk first; a gap on a control mapped to an already listed gap
+/// in another framework is recorded as related to it instead of repeated
+pub fn dedupe_gaps(mut gaps: Vec<OverviewGap>) -> Vec<OverviewGap> {
+    gaps.sort_by_key(|g| std::cmp::Reverse(g.risk_rating));
+
+    let mut mappings: HashMap<(Framework, Framework), Vec<ControlMapping>> = HashMap::new();
+    let mut kept: Vec<OverviewGap> = Vec::new();
+    for gap in gaps {
+        let mut mapped_to = None;
+        for (i, existing) in kept.iter().enumerate() {
+            if existing.framework == gap.framework {
+                continue;
+            }
+            let pair = mappings
+                .entry((existing.framework, gap.framework))
+                .or_insert_with(|| get_control_mappings(existing.framework, gap.framework));
+            let mapped = pair
+                .iter()
+                .any(|m| m.source_control == existing.control_code && m.target_controls.contains(&gap.control_code));
+            if mapped {
+                mapped_to = Some(i);
+                break;
+            }
+        }
+
+        match mapped_to {
+            Some(i) => kept[i].related_controls.push(RelatedControl {
+                framework: gap.framework,
+                code: gap.control_code,
+            }),
+            None => kept.push(gap),
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(framework: Framework, assessed: usize, compliant: usize) -> ComplianceStatusReport {
+        let controls = get_framework_controls(framework);
+        let mut report = build_compliance_report(framework, &controls, |_| ComplianceStatus::NotAssessed);
+        report.assessed_controls = assessed;
+        report.compliant_controls = compliant;
+        report.non_compliant_controls = assessed - compliant;
+        report.compliance_percentage = if assessed > 0 { compliant as f64 / assessed as f64 * 100.0 } else { 0.0 };
+        report
+    }
+
+    fn gap(framework: Framework, code: &str, risk_rating: u8) -> OverviewGap {
+        OverviewGap {
+            framework,
+            control_code: code.to_string(),
+            control_title: code.to_string(),
+            risk_rating,
+            gap_description: None,
+            related_controls: vec![],
+        }
+    }
+
+    #[test]
+    fn test_blended_score_and_unassessed_count() {
+        let nist = status(Framework::NistCsf2, 30, 24);
+        let soc2 = status(Framework::Soc2TypeII, 10, 5);
+        let iso = status(Framework::Iso27001, 0, 0);
+        let total = nist.total_controls + soc2.total_controls + iso.total_controls;
+
+        let overview = combine_frameworks(
+            "client-1",
+            vec![(nist.clone(), None), (soc2.clone(), None), (iso.clone(), None)],
+            vec![],
+        );
+        assert_eq!(overview.weighting, OverviewWeighting::ControlCount);
+        // (80% x 30 + 50% x 10) / 40
+        assert_eq!(overview.overall_compliance_percentage, 72.5);
+        assert_eq!(overview.frameworks[2].weight, 0.0);
+        assert_eq!(overview.total_controls, total);
+        assert_eq!(overview.unassessed_controls, total - 40);
+
+        let overview = combine_frameworks("client-1", vec![(nist, Some(1.0)), (soc2, Some(3.0)), (iso, Some(5.0))], vec![]);
+        assert_eq!(overview.weighting, OverviewWeighting::Custom);
+        assert_eq!(overview.overall_compliance_percentage, 57.5);
+    }
+
+    #[test]
+    fn test_gaps_on_mapped_controls_are_folded() {
+        let gaps = dedupe_gaps(vec![
+            gap(Framework::Soc2TypeII, "CC3.1", 4),
+            gap(Framework::NistCsf2, "GV.RM-01", 5),
+            gap(Framework::NistCsf2, "PR.AA-02", 4),
+        ]);
+
+        let codes: Vec<&str> = gaps.iter().map(|g| g.control_code.as_str()).collect();
+        assert_eq!(codes, vec!["GV.RM-01", "PR.AA-02"]);
+        assert_eq!(gaps[0].related_controls, vec![RelatedControl {
+            framework: Framework::Soc2TypeII,
+            code: "CC3.1".to_string(),
+        }]);
+        assert!(gaps[1].related_controls.is_empty());
+    }
+}
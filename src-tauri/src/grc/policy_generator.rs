@@ -0,0 +1,973 @@
+//! Policy Generator
+//!
+//! Drafts policy documents from an assessment's gap analysis. Each
+//! non-compliant or partially compliant control maps to the policy that
+//! should close it; a draft is built from that policy's template, filled in
+//! with the client and framework, and cites the controls it addresses.
+//!
+//! Templates are embedded so generation works offline. A firm can replace
+//! any of them by dropping `<template_name>.md` into the override directory.
+//!
+//! Template format: `# ` starts a section and `## ` a subsection, `- ` and
+//! `1. ` lines build lists, `> ` lines become a note, and other lines are
+//! joined into paragraphs. Placeholders use the Factory `{{NAME}}` syntax.
+
+use crate::error::{OptioError, OptioResult};
+use crate::factory::extract_placeholders;
+use crate::grc::frameworks::get_framework_controls;
+use crate::grc::models::*;
+use crate::reporting::generator::{content_to_html, content_to_markdown};
+use crate::reporting::models::{
+    CalloutType, ContentBlock, ReportContent, ReportMetadata, ReportSection,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Policy documents the generator can draft
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PolicyType {
+    InformationSecurity,
+    AccessControl,
+    AssetManagement,
+    RiskManagement,
+    DataProtection,
+    IncidentResponse,
+    BusinessContinuity,
+    LoggingMonitoring,
+    ChangeManagement,
+    SupplierSecurity,
+}
+
+impl PolicyType {
+    pub fn all() -> Vec<PolicyType> {
+        vec![
+            PolicyType::InformationSecurity,
+            PolicyType::AccessControl,
+            PolicyType::AssetManagement,
+            PolicyType::RiskManagement,
+            PolicyType::DataProtection,
+            PolicyType::IncidentResponse,
+            PolicyType::BusinessContinuity,
+            PolicyType::LoggingMonitoring,
+            PolicyType::ChangeManagement,
+            PolicyType::SupplierSecurity,
+        ]
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PolicyType::InformationSecurity => "Information Security Policy",
+            PolicyType::AccessControl => "Access Control Policy",
+            PolicyType::AssetManagement => "Asset Management Policy",
+            PolicyType::RiskManagement => "Risk Management Policy",
+            PolicyType::DataProtection => "Data Protection Policy",
+            PolicyType::IncidentResponse => "Incident Response Plan",
+            PolicyType::BusinessContinuity => "Business Continuity and Disaster Recovery Plan",
+            PolicyType::LoggingMonitoring => "Logging and Monitoring Policy",
+            PolicyType::ChangeManagement => "Configuration and Change Management Policy",
+            PolicyType::SupplierSecurity => "Supplier Security Policy",
+        }
+    }
+
+    /// File name (without `.md`) of the template, embedded or overridden
+    pub fn template_name(&self) -> &'static str {
+        match self {
+            PolicyType::InformationSecurity => "information_security",
+            PolicyType::AccessControl => "access_control",
+            PolicyType::AssetManagement => "asset_management",
+            PolicyType::RiskManagement => "risk_management",
+            PolicyType::DataProtection => "data_protection",
+            PolicyType::IncidentResponse => "incident_response",
+            PolicyType::BusinessContinuity => "business_continuity",
+            PolicyType::LoggingMonitoring => "logging_monitoring",
+            PolicyType::ChangeManagement => "change_management",
+            PolicyType::SupplierSecurity => "supplier_security",
+        }
+    }
+}
+
+/// A control gap a policy is drafted to close
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyControlGap {
+    pub control_code: String,
+    pub title: String,
+    pub status: ComplianceStatus,
+    pub gap_description: Option<String>,
+    pub remediation: Option<String>,
+    pub policy_type: PolicyType,
+}
+
+/// A drafted policy, as structured content and rendered for export
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyDocument {
+    pub policy_type: PolicyType,
+    pub title: String,
+    /// Codes of the controls the policy cites
+    pub control_codes: Vec<String>,
+    /// Whether a firm template from the override directory was used
+    pub custom_template: bool,
+    pub content: ReportContent,
+    pub html: String,
+    pub markdown: String,
+    /// Template problems that didn't stop generation
+    pub warnings: Vec<String>,
+}
+
+// ============================================================================
+// Control Mapping
+// ============================================================================
+
+/// Control code prefixes and the policy that addresses them
+///
+/// The longest matching prefix wins, so a specific control can override its
+/// category (e.g. ISO "A.5.15" within theme "A.5").
+const POLICY_MAPPINGS: &[(&str, PolicyType)] = &[
+    // NIST CSF 2.0 categories
+    ("GV", PolicyType::InformationSecurity),
+    ("GV.RM", PolicyType::RiskManagement),
+    ("ID.AM", PolicyType::AssetManagement),
+    ("ID.RA", PolicyType::RiskManagement),
+    ("PR.AA", PolicyType::AccessControl),
+    ("PR.DS", PolicyType::DataProtection),
+    ("PR.PS", PolicyType::ChangeManagement),
+    ("PR.IR", PolicyType::BusinessContinuity),
+    ("DE", PolicyType::LoggingMonitoring),
+    ("RS", PolicyType::IncidentResponse),
+    ("RC", PolicyType::BusinessContinuity),
+    // SOC 2 criteria
+    ("CC1", PolicyType::InformationSecurity),
+    ("CC3", PolicyType::RiskManagement),
+    ("CC4", PolicyType::LoggingMonitoring),
+    ("CC6", PolicyType::AccessControl),
+    ("CC7", PolicyType::LoggingMonitoring),
+    ("CC7.3", PolicyType::IncidentResponse),
+    ("CC7.4", PolicyType::IncidentResponse),
+    ("CC7.5", PolicyType::BusinessContinuity),
+    ("A1", PolicyType::BusinessContinuity),
+    ("C1", PolicyType::DataProtection),
+    // GDPR articles
+    ("Art", PolicyType::DataProtection),
+    ("Art. 28", PolicyType::SupplierSecurity),
+    ("Art. 33", PolicyType::IncidentResponse),
+    ("Art. 34", PolicyType::IncidentResponse),
+    // ISO/IEC 27001:2022 Annex A themes and controls
+    ("A.5", PolicyType::InformationSecurity),
+    ("A.5.7", PolicyType::RiskManagement),
+    ("A.5.9", PolicyType::AssetManagement),
+    ("A.5.10", PolicyType::AssetManagement),
+    ("A.5.11", PolicyType::AssetManagement),
+    ("A.5.12", PolicyType::DataProtection),
+    ("A.5.13", PolicyType::DataProtection),
+    ("A.5.14", PolicyType::DataProtection),
+    ("A.5.15", PolicyType::AccessControl),
+    ("A.5.16", PolicyType::AccessControl),
+    ("A.5.17", PolicyType::AccessControl),
+    ("A.5.18", PolicyType::AccessControl),
+    ("A.5.19", PolicyType::SupplierSecurity),
+    ("A.5.20", PolicyType::SupplierSecurity),
+    ("A.5.21", PolicyType::SupplierSecurity),
+    ("A.5.22", PolicyType::SupplierSecurity),
+    ("A.5.23", PolicyType::SupplierSecurity),
+    ("A.5.24", PolicyType::IncidentResponse),
+    ("A.5.25", PolicyType::IncidentResponse),
+    ("A.5.26", PolicyType::IncidentResponse),
+    ("A.5.27", PolicyType::IncidentResponse),
+    ("A.5.28", PolicyType::IncidentResponse),
+    ("A.5.29", PolicyType::BusinessContinuity),
+    ("A.5.30", PolicyType::BusinessContinuity),
+    ("A.5.33", PolicyType::DataProtection),
+    ("A.5.34", PolicyType::DataProtection),
+    ("A.6", PolicyType::InformationSecurity),
+    ("A.6.8", PolicyType::IncidentResponse),
+    ("A.7", PolicyType::AccessControl),
+    ("A.7.9", PolicyType::AssetManagement),
+    ("A.7.10", PolicyType::AssetManagement),
+    ("A.7.14", PolicyType::AssetManagement),
+    ("A.8", PolicyType::ChangeManagement),
+    ("A.8.1", PolicyType::AssetManagement),
+    ("A.8.2", PolicyType::AccessControl),
+    ("A.8.3", PolicyType::AccessControl),
+    ("A.8.4", PolicyType::AccessControl),
+    ("A.8.5", PolicyType::AccessControl),
+    ("A.8.8", PolicyType::RiskManagement),
+    ("A.8.10", PolicyType::DataProtection),
+    ("A.8.11", PolicyType::DataProtection),
+    ("A.8.12", PolicyType::DataProtection),
+    ("A.8.13", PolicyType::BusinessContinuity),
+    ("A.8.14", PolicyType::BusinessContinuity),
+    ("A.8.15", PolicyType::LoggingMonitoring),
+    ("A.8.16", PolicyType::LoggingMonitoring),
+    ("A.8.17", PolicyType::LoggingMonitoring),
+    ("A.8.24", PolicyType::DataProtection),
+];
+
+/// Policy that addresses a control, by its code
+pub fn policy_type_for_control(code: &str) -> PolicyType {
+    POLICY_MAPPINGS
+        .iter()
+        .filter(|(prefix, _)| code_has_prefix(code, prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, policy)| *policy)
+        .unwrap_or(PolicyType::InformationSecurity)
+}
+
+/// Whether `prefix` covers `code` at a segment boundary ("A.5.1" covers
+/// "A.5.1" but not "A.5.15"; "Art" covers "Art. 32")
+fn code_has_prefix(code: &str, prefix: &str) -> bool {
+    match code.strip_prefix(prefix) {
+        Some(rest) => !rest.starts_with(|c: char| c.is_ascii_alphanumeric()) || prefix == "Art",
+        None => false,
+    }
+}
+
+/// Non-compliant and partially compliant controls of an assessment
+///
+/// `control_ids` narrows the gaps to a selection (by control id or code).
+pub fn collect_policy_gaps(
+    assessment: &Assessment,
+    control_assessments: &[ControlAssessment],
+    control_ids: Option<&[String]>,
+) -> Vec<PolicyControlGap> {
+    let controls = get_framework_controls(assessment.framework);
+
+    let mut gaps: Vec<PolicyControlGap> = control_assessments
+        .iter()
+        .filter(|ca| matches!(ca.status, ComplianceStatus::NonCompliant | ComplianceStatus::PartiallyCompliant))
+        .filter_map(|ca| {
+            let control = controls.iter().find(|c| c.id == ca.control_id || c.code == ca.control_id)?;
+            if let Some(ids) = control_ids {
+                if !ids.contains(&ca.control_id) && !ids.contains(&control.code) {
+                    return None;
+                }
+            }
+            Some(PolicyControlGap {
+                control_code: control.code.clone(),
+                title: control.title.clone(),
+                status: ca.status,
+                gap_description: ca.gap_description.clone(),
+                remediation: ca.remediation.clone(),
+                policy_type: policy_type_for_control(&control.code),
+            })
+        })
+        .collect();
+
+    // Framework order, not assessment order
+    gaps.sort_by_key(|g| controls.iter().position(|c| c.code == g.control_code));
+    gaps
+}
+
+// ============================================================================
+// Generator
+// ============================================================================
+
+/// Policy document generator
+pub struct PolicyGenerator {
+    override_dir: Option<PathBuf>,
+}
+
+impl PolicyGenerator {
+    /// Create a generator; templates in `override_dir` replace the embedded ones
+    pub fn new(override_dir: Option<PathBuf>) -> Self {
+        PolicyGenerator { override_dir }
+    }
+
+    /// Draft one policy covering `gaps` (those mapped to other policies are ignored)
+    pub fn generate(
+        &self,
+        policy_type: PolicyType,
+        client_name: &str,
+        assessment: &Assessment,
+        gaps: &[PolicyControlGap],
+    ) -> OptioResult<PolicyDocument> {
+        let (template, custom_template) = self.load_template(policy_type)?;
+        let gaps: Vec<&PolicyControlGap> = gaps.iter().filter(|g| g.policy_type == policy_type).collect();
+        let control_codes: Vec<String> = gaps.iter().map(|g| g.control_code.clone()).collect();
+
+        let today = Utc::now();
+        let mut vars = HashMap::new();
+        vars.insert("CLIENT_NAME", client_name.to_string());
+        vars.insert("POLICY_TITLE", policy_type.display_name().to_string());
+        vars.insert("FRAMEWORK", assessment.framework.display_name().to_string());
+        vars.insert("ASSESSMENT_NAME", assessment.name.clone());
+        vars.insert("EFFECTIVE_DATE", today.format("%B %-d, %Y").to_string());
+        vars.insert("REVIEW_DATE", (today + Duration::days(365)).format("%B %-d, %Y").to_string());
+        vars.insert(
+            "CONTROL_REFERENCES",
+            if control_codes.is_empty() {
+                format!("the {} framework", assessment.framework.display_name())
+            } else {
+                format!("{} {}", assessment.framework.display_name(), control_codes.join(", "))
+            },
+        );
+
+        let mut warnings: Vec<String> = extract_placeholders(&template)
+            .into_iter()
+            .filter(|p| !vars.contains_key(p.as_str()))
+            .map(|p| format!("Template uses unknown placeholder {{{{{}}}}}; left as is", p))
+            .collect();
+        if gaps.is_empty() {
+            warnings.push(format!("No assessed gaps map to the {}", policy_type.display_name()));
+        }
+
+        let mut text = template;
+        for (key, value) in &vars {
+            text = text.replace(&format!("{{{{{}}}}}", key), value);
+        }
+
+        let mut sections = parse_policy_template(&text);
+        if sections.is_empty() {
+            return Err(OptioError::Validation(format!(
+                "Template '{}' has no sections",
+                policy_type.template_name()
+            )));
+        }
+        if !gaps.is_empty() {
+            sections.push(control_gaps_section(assessment, &gaps));
+        }
+
+        let content = ReportContent {
+            sections,
+            toc: vec![],
+            anchors: vec![],
+            metadata: ReportMetadata {
+                title: policy_type.display_name().to_string(),
+                subtitle: Some(format!("Draft for {}", client_name)),
+                author: assessment.lead_assessor.clone(),
+                organization: Some(client_name.to_string()),
+                client_name: client_name.to_string(),
+                client_contact: None,
+                report_date: today.format("%Y-%m-%d").to_string(),
+                classification: Some("Internal".to_string()),
+                version: "0.1 (Draft)".to_string(),
+                page_count: None,
+                logo_path: None,
+                primary_color: None,
+                secondary_color: None,
+                footer_text: None,
+            },
+        };
+
+        Ok(PolicyDocument {
+            policy_type,
+            title: policy_type.display_name().to_string(),
+            control_codes,
+            custom_template,
+            html: content_to_html(&content),
+            markdown: content_to_markdown(&content),
+            content,
+            warnings,
+        })
+    }
+
+    /// The firm's template if there is one, else the embedded default
+    fn load_template(&self, policy_type: PolicyType) -> OptioResult<(String, bool)> {
+        if let Some(dir) = &self.override_dir {
+            let path = dir.join(format!("{}.md", policy_type.template_name()));
+            if path.exists() {
+                return Ok((std::fs::read_to_string(&path)?, true));
+            }
+        }
+        Ok((default_policy_template(policy_type).to_string(), false))
+    }
+}
+
+/// Section citing the controls a policy addresses
+fn control_gaps_section(assessment: &Assessment, gaps: &[&PolicyControlGap]) -> ReportSection {
+    let remediation: Vec<String> = gaps
+        .iter()
+        .filter_map(|g| g.remediation.as_ref().map(|r| format!("{}: {}", g.control_code, r)))
+        .collect();
+
+    let mut subsections = Vec::new();
+    if !remediation.is_empty() {
+        subsections.push(ReportSection {
+            id: "control-gaps-remediation".to_string(),
+            title: "Remediation Actions".to_string(),
+            level: 2,
+            blocks: vec![ContentBlock::BulletList { items: remediation }],
+            subsections: vec![],
+        });
+    }
+
+    ReportSection {
+        id: "control-gaps".to_string(),
+        title: "Control Gaps Addressed".to_string(),
+        level: 1,
+        blocks: vec![
+            ContentBlock::Paragraph {
+                text: format!(
+                    "This policy was drafted to close the following gaps identified in the \"{}\" {} assessment.",
+                    assessment.name,
+                    assessment.framework.display_name()
+                ),
+            },
+            ContentBlock::Table {
+                headers: vec!["Control".to_string(), "Title".to_string(), "Status".to_string(), "Gap".to_string()],
+                rows: gaps
+                    .iter()
+                    .map(|g| vec![
+                        g.control_code.clone(),
+                        g.title.clone(),
+                        g.status.display_name().to_string(),
+                        g.gap_description.clone().unwrap_or_default(),
+                    ])
+                    .collect(),
+                caption: Some(format!("{} references", assessment.framework.display_name())),
+            },
+        ],
+        subsections,
+    }
+}
+
+/// Build report sections from a filled-in policy template
+pub fn parse_policy_template(text: &str) -> Vec<ReportSection> {
+    let mut sections: Vec<ReportSection> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    fn current(sections: &mut Vec<ReportSection>) -> &mut ReportSection {
+        if sections.is_empty() {
+            sections.push(new_section("Overview", 1));
+        }
+        let section = sections.last_mut().unwrap();
+        if section.subsections.is_empty() {
+            section
+        } else {
+            section.subsections.last_mut().unwrap()
+        }
+    }
+
+    fn flush(sections: &mut Vec<ReportSection>, paragraph: &mut Vec<&str>) {
+        if !paragraph.is_empty() {
+            let text = paragraph.join(" ");
+            paragraph.clear();
+            current(sections).blocks.push(ContentBlock::Paragraph { text });
+        }
+    }
+
+    for line in text.lines().map(str::trim) {
+        if let Some(title) = line.strip_prefix("## ") {
+            flush(&mut sections, &mut paragraph);
+            if sections.is_empty() {
+                sections.push(new_section("Overview", 1));
+            }
+            sections.last_mut().unwrap().subsections.push(new_section(title, 2));
+        } else if let Some(title) = line.strip_prefix("# ") {
+            flush(&mut sections, &mut paragraph);
+            sections.push(new_section(title, 1));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            flush(&mut sections, &mut paragraph);
+            push_list_item(current(&mut sections), item, false);
+        } else if let Some(item) = numbered_item(line) {
+            flush(&mut sections, &mut paragraph);
+            push_list_item(current(&mut sections), item, true);
+        } else if let Some(note) = line.strip_prefix("> ") {
+            flush(&mut sections, &mut paragraph);
+            current(&mut sections).blocks.push(ContentBlock::Callout {
+                callout_type: CalloutType::Note,
+                title: None,
+                text: note.to_string(),
+            });
+        } else if line.is_empty() {
+            flush(&mut sections, &mut paragraph);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush(&mut sections, &mut paragraph);
+
+    sections
+}
+
+fn new_section(title: &str, level: u8) -> ReportSection {
+    let slug: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    ReportSection {
+        id: format!("policy-{}", slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-")),
+        title: title.trim().to_string(),
+        level,
+        blocks: vec![],
+        subsections: vec![],
+    }
+}
+
+/// "1. Text" -> "Text"
+fn numbered_item(line: &str) -> Option<&str> {
+    let (number, rest) = line.split_once(". ")?;
+    if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// Append to the section's trailing list of the same kind, or start one
+fn push_list_item(section: &mut ReportSection, item: &str, numbered: bool) {
+    match (section.blocks.last_mut(), numbered) {
+        (Some(ContentBlock::BulletList { items }), false) | (Some(ContentBlock::NumberedList { items }), true) => {
+            items.push(item.to_string());
+        }
+        (_, false) => section.blocks.push(ContentBlock::BulletList { items: vec![item.to_string()] }),
+        (_, true) => section.blocks.push(ContentBlock::NumberedList { items: vec![item.to_string()] }),
+    }
+}
+
+// ============================================================================
+// Embedded Templates
+// ============================================================================
+
+fn default_policy_template(policy_type: PolicyType) -> &'static str {
+    match policy_type {
+        PolicyType::InformationSecurity => INFORMATION_SECURITY_TEMPLATE,
+        PolicyType::AccessControl => ACCESS_CONTROL_TEMPLATE,
+        PolicyType::AssetManagement => ASSET_MANAGEMENT_TEMPLATE,
+        PolicyType::RiskManagement => RISK_MANAGEMENT_TEMPLATE,
+        PolicyType::DataProtection => DATA_PROTECTION_TEMPLATE,
+        PolicyType::IncidentResponse => INCIDENT_RESPONSE_TEMPLATE,
+        PolicyType::BusinessContinuity => BUSINESS_CONTINUITY_TEMPLATE,
+        PolicyType::LoggingMonitoring => LOGGING_MONITORING_TEMPLATE,
+        PolicyType::ChangeManagement => CHANGE_MANAGEMENT_TEMPLATE,
+        PolicyType::SupplierSecurity => SUPPLIER_SECURITY_TEMPLATE,
+    }
+}
+
+const INFORMATION_SECURITY_TEMPLATE: &str = r#"
+# Purpose
+This policy sets out how {{CLIENT_NAME}} protects the confidentiality, integrity and availability of its information. It establishes the governance under which all other security policies operate and supports {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy applies to all employees, contractors and third parties with access to {{CLIENT_NAME}} information or systems, in any location and on any device.
+
+# Policy Statements
+## Governance
+- Executive management approves this policy and is accountable for information security.
+- Security roles and responsibilities are assigned, documented and communicated.
+- Conflicting duties are segregated to reduce the risk of misuse.
+
+## Policy Framework
+- Topic-specific policies are derived from this policy and approved by [Policy Owner].
+- All policies are reviewed at least annually and after significant change.
+- Staff acknowledge the policies that apply to them on joining and after each revision.
+
+## Awareness and Training
+- All personnel complete security awareness training on joining and annually thereafter.
+- Personnel in privileged or high-risk roles receive additional role-based training.
+
+# Compliance
+Breaches of this policy may result in disciplinary action. Exceptions must be approved in writing by [Policy Owner] and recorded with a review date.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const ACCESS_CONTROL_TEMPLATE: &str = r#"
+# Purpose
+This policy defines how {{CLIENT_NAME}} grants, reviews and removes access to information and systems so that users have only the access their role requires. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy covers all user, service and privileged accounts on {{CLIENT_NAME}} systems, applications, cloud services and physical premises.
+
+# Policy Statements
+## Identity and Account Management
+- Every user has a unique identity; shared accounts are prohibited unless approved and documented.
+- Accounts are created only on an approved request from the user's line manager.
+- Service accounts have a named owner and are not used for interactive logon.
+
+## Authentication
+- Multi-factor authentication is required for remote access, cloud services and all privileged access.
+- Passwords meet the minimum length and complexity set in the authentication standard and are never shared.
+
+## Least Privilege
+- Access is granted on a least-privilege and need-to-know basis.
+- Privileged access is granted separately from standard accounts, time-limited where possible, and logged.
+
+## Access Reviews and Removal
+1. Line managers review their team's access every quarter.
+2. System owners review privileged access every quarter.
+3. Access is removed within one business day of a leaver's departure and adjusted on role change.
+
+## Physical Access
+- Entry to offices and secure areas is restricted to authorized personnel and visitor access is logged and escorted.
+
+# Compliance
+Unauthorized access attempts and policy violations are investigated under the Incident Response Plan.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const ASSET_MANAGEMENT_TEMPLATE: &str = r#"
+# Purpose
+This policy ensures {{CLIENT_NAME}} knows what information assets it holds, who owns them and how they must be handled. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy applies to hardware, software, cloud services, data stores and removable media used for {{CLIENT_NAME}} business.
+
+# Policy Statements
+## Inventory
+- An inventory of hardware and software assets is maintained and reconciled at least quarterly.
+- Each asset has a named owner responsible for its protection.
+- Unauthorized or unsupported software is identified and removed.
+
+## Acceptable Use
+- Assets are used for authorized business purposes in line with the acceptable use standard.
+- Assets are returned when employment or contracts end.
+
+## Secure Disposal
+- Storage media is securely wiped or destroyed before disposal or reuse, and disposal is recorded.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const RISK_MANAGEMENT_TEMPLATE: &str = r#"
+# Purpose
+This policy describes how {{CLIENT_NAME}} identifies, assesses, treats and monitors information security risk. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy applies to risks arising from {{CLIENT_NAME}} information, systems, suppliers and processes.
+
+# Policy Statements
+## Risk Assessment
+1. Risks are identified through annual assessments, vulnerability scanning, threat intelligence and after significant change.
+2. Each risk is scored for likelihood and impact on a 5x5 scale and recorded in the risk register.
+3. Each risk has a named owner.
+
+## Risk Treatment
+- Risks above the approved appetite are mitigated, transferred, avoided or formally accepted.
+- Risk acceptance is approved by [Policy Owner] and reviewed at least annually.
+
+## Vulnerability Management
+- Systems are scanned for vulnerabilities at least monthly.
+- Critical vulnerabilities are remediated within 14 days and high vulnerabilities within 30 days.
+
+## Monitoring
+- The risk register is reviewed by management quarterly.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const DATA_PROTECTION_TEMPLATE: &str = r#"
+# Purpose
+This policy sets out how {{CLIENT_NAME}} classifies, handles and protects data, including personal data, throughout its lifecycle. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy applies to all data created, received, stored or processed by or for {{CLIENT_NAME}}, in any format.
+
+# Policy Statements
+## Classification and Handling
+- Data is classified as Public, Internal, Confidential or Restricted and handled according to its classification.
+- Confidential and Restricted data is shared only with authorized recipients over approved channels.
+
+## Encryption
+- Confidential and Restricted data is encrypted at rest and in transit using approved algorithms.
+- Encryption keys are managed separately from the data they protect.
+
+## Personal Data
+- Personal data is processed lawfully, fairly and transparently, for specified purposes only.
+- Only the personal data needed for the purpose is collected, and it is kept no longer than necessary.
+- Records of processing activities are maintained.
+- Data protection impact assessments are completed before high-risk processing begins.
+- Data subject requests are answered within one month.
+
+## Retention and Disposal
+- Data is retained according to the retention schedule and securely deleted when no longer required.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const INCIDENT_RESPONSE_TEMPLATE: &str = r#"
+# Purpose
+This plan defines how {{CLIENT_NAME}} detects, reports, contains and recovers from information security incidents. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This plan applies to all suspected or confirmed security incidents affecting {{CLIENT_NAME}} information, systems or personnel.
+
+# Roles and Responsibilities
+- Incident Manager: [Incident Manager] leads the response and coordinates communication.
+- Technical Lead: investigates, contains and eradicates the threat.
+- Communications Lead: manages internal, customer and regulator communication.
+
+# Response Process
+## Detection and Reporting
+- All personnel report suspected incidents immediately to [Reporting Contact].
+- Security alerts are triaged within one hour during business hours.
+
+## Triage and Classification
+1. Confirm whether a security incident has occurred.
+2. Classify severity as Low, Medium, High or Critical based on impact.
+3. Escalate High and Critical incidents to executive management.
+
+## Containment, Eradication and Recovery
+1. Isolate affected systems and preserve evidence.
+2. Remove the cause of the incident and restore from known-good sources.
+3. Monitor restored systems for recurrence.
+
+## Notification
+- Personal data breaches are assessed promptly and, where required, reported to the supervisory authority within 72 hours.
+- Affected individuals and customers are notified without undue delay where required.
+
+# Post-Incident Review
+A review is held within two weeks of closing any High or Critical incident, and lessons learned are tracked to completion.
+
+# Testing
+This plan is tested at least annually through a tabletop exercise.
+
+# Document Control
+- Plan owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const BUSINESS_CONTINUITY_TEMPLATE: &str = r#"
+# Purpose
+This plan ensures {{CLIENT_NAME}} can continue critical operations during a disruption and recover systems and data within agreed timeframes. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This plan covers the critical business processes, systems and data identified in the business impact analysis.
+
+# Policy Statements
+## Business Impact Analysis
+- Critical processes and their recovery time (RTO) and recovery point (RPO) objectives are identified and approved by management.
+
+## Backup
+- Critical data is backed up according to its RPO, with at least one copy held offline or immutable.
+- Backups are encrypted and monitored for failures.
+
+## Recovery
+1. Recovery procedures are documented for each critical system.
+2. Recovery is prioritized according to the business impact analysis.
+3. Stakeholders are kept informed during recovery through the agreed communication plan.
+
+## Testing
+- Backup restoration is tested at least quarterly.
+- The full plan is exercised at least annually and updated with the results.
+
+# Document Control
+- Plan owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const LOGGING_MONITORING_TEMPLATE: &str = r#"
+# Purpose
+This policy defines how {{CLIENT_NAME}} records and monitors activity so that security events are detected and investigated promptly. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy applies to servers, endpoints, network devices, cloud services and applications that process {{CLIENT_NAME}} information.
+
+# Policy Statements
+## Logging
+- Security-relevant events, including authentication, privileged activity and configuration changes, are logged.
+- Logs are sent to a central platform, protected from tampering and retained for at least 12 months.
+- System clocks are synchronized to an approved time source.
+
+## Monitoring
+- Network traffic and security events are monitored continuously for anomalies.
+- Alerts are correlated across sources and triaged according to severity.
+- Monitoring coverage is reviewed at least annually.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const CHANGE_MANAGEMENT_TEMPLATE: &str = r#"
+# Purpose
+This policy ensures changes to {{CLIENT_NAME}} systems are made securely and consistently, from approved configuration baselines. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy applies to changes to infrastructure, applications, cloud configuration and security tooling.
+
+# Policy Statements
+## Configuration Baselines
+- Hardened configuration baselines are defined for each system type and applied at build.
+- Deviations from baselines are detected and either corrected or approved as exceptions.
+
+## Change Control
+1. Changes are requested, risk-assessed and approved before implementation.
+2. Changes are tested in a non-production environment where one exists.
+3. Emergency changes are approved retrospectively within two business days.
+4. Changes are recorded with their outcome and a rollback plan.
+
+## Secure Development
+- Source code changes are peer reviewed before release.
+- Development, test and production environments are separated.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+const SUPPLIER_SECURITY_TEMPLATE: &str = r#"
+# Purpose
+This policy manages the information security risk of suppliers and processors that access, store or process {{CLIENT_NAME}} information. It addresses {{CONTROL_REFERENCES}}.
+
+# Scope
+This policy applies to all third parties, including cloud service providers, with access to {{CLIENT_NAME}} information or systems.
+
+# Policy Statements
+## Due Diligence
+- Suppliers are risk-assessed before engagement and tiered by the sensitivity of the data they handle.
+- High-risk suppliers provide independent assurance such as a SOC 2 report or ISO 27001 certificate.
+
+## Contracts
+- Contracts include security, confidentiality, breach notification and audit requirements.
+- Processors of personal data are bound by a data processing agreement.
+
+## Ongoing Management
+- High-risk suppliers are reviewed at least annually.
+- Supplier access is removed and data returned or destroyed when the relationship ends.
+
+# Document Control
+- Policy owner: [Policy Owner]
+- Effective date: {{EFFECTIVE_DATE}}
+- Next review: {{REVIEW_DATE}}
+> This draft was generated from the {{ASSESSMENT_NAME}} gap analysis and must be reviewed before adoption.
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assessment() -> Assessment {
+        Assessment {
+            id: "assessment-1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "FY26 Readiness".to_string(),
+            description: None,
+            framework: Framework::NistCsf2,
+            scope: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            lead_assessor: "J. Auditor".to_string(),
+            status: AssessmentStatus::InProgress,
+            source_assessment_id: None,
+        }
+    }
+
+    fn control_assessment(control_id: &str, status: ComplianceStatus) -> ControlAssessment {
+        ControlAssessment {
+            id: format!("ca-{}", control_id),
+            assessment_id: "assessment-1".to_string(),
+            control_id: control_id.to_string(),
+            status,
+            notes: None,
+            gap_description: Some("No formal process".to_string()),
+            remediation: Some("Adopt and enforce the policy".to_string()),
+            remediation_target: None,
+            risk_rating: Some(4),
+            maturity_level: None,
+            evidence_ids: vec![],
+            assessed_at: Utc::now(),
+            assessed_by: "J. Auditor".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_policy_type_for_control() {
+        assert_eq!(policy_type_for_control("PR.AA-02"), PolicyType::AccessControl);
+        assert_eq!(policy_type_for_control("RS.MA-01"), PolicyType::IncidentResponse);
+        assert_eq!(policy_type_for_control("CC7.4"), PolicyType::IncidentResponse);
+        assert_eq!(policy_type_for_control("CC7.1"), PolicyType::LoggingMonitoring);
+        assert_eq!(policy_type_for_control("Art. 33"), PolicyType::IncidentResponse);
+        assert_eq!(policy_type_for_control("Art. 32"), PolicyType::DataProtection);
+        assert_eq!(policy_type_for_control("A.5.15"), PolicyType::AccessControl);
+        assert_eq!(policy_type_for_control("A.5.1"), PolicyType::InformationSecurity);
+        assert_eq!(policy_type_for_control("A.8.13"), PolicyType::BusinessContinuity);
+    }
+
+    #[test]
+    fn test_generate_policy_documents() {
+        let assessment = assessment();
+        let control_assessments = vec![
+            control_assessment("PR.AA-02", ComplianceStatus::NonCompliant),
+            control_assessment("PR.AA-03", ComplianceStatus::PartiallyCompliant),
+            control_assessment("RS.MA-01", ComplianceStatus::NonCompliant),
+            control_assessment("PR.AA-01", ComplianceStatus::Compliant),
+        ];
+        let gaps = collect_policy_gaps(&assessment, &control_assessments, None);
+        assert_eq!(gaps.len(), 3);
+
+        let generator = PolicyGenerator::new(None);
+        let policy = generator.generate(PolicyType::AccessControl, "Acme Corp", &assessment, &gaps).unwrap();
+
+        assert_eq!(policy.control_codes, vec!["PR.AA-02", "PR.AA-03"]);
+        assert!(!policy.custom_template);
+        assert!(policy.warnings.is_empty());
+        assert!(policy.markdown.contains("Acme Corp"));
+        assert!(policy.markdown.contains("NIST CSF 2.0 PR.AA-02, PR.AA-03"));
+        assert!(!policy.markdown.contains("{{"));
+        assert!(policy.html.contains("Control Gaps Addressed"));
+
+        let gap_section = policy.content.sections.last().unwrap();
+        assert_eq!(gap_section.id, "control-gaps");
+        match &gap_section.blocks[1] {
+            ContentBlock::Table { rows, .. } => assert_eq!(rows.len(), 2),
+            other => panic!("expected table, got {:?}", other),
+        }
+
+        let selected = collect_policy_gaps(&assessment, &control_assessments, Some(&["RS.MA-01".to_string()]));
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].policy_type, PolicyType::IncidentResponse);
+    }
+
+    #[test]
+    fn test_policy_template_override() {
+        let dir = std::env::temp_dir().join(format!("optio-policy-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("incident_response.md"),
+            "# Firm IR Plan\nFor {{CLIENT_NAME}} owned by {{OWNER}}.\n## Steps\n1. Report\n2. Contain\n",
+        )
+        .unwrap();
+
+        let policy = PolicyGenerator::new(Some(dir.clone()))
+            .generate(PolicyType::IncidentResponse, "Acme Corp", &assessment(), &[])
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(policy.custom_template);
+        assert_eq!(policy.content.sections[0].title, "Firm IR Plan");
+        assert!(matches!(
+            &policy.content.sections[0].subsections[0].blocks[0],
+            ContentBlock::NumberedList { items } if items.len() == 2
+        ));
+        // Unknown placeholder and no mapped gaps are reported, not fatal
+        assert_eq!(policy.warnings.len(), 2);
+    }
+}
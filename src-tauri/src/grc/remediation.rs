@@ -0,0 +1,895 @@
+//! Remediation Plans
+//!
+//! Consolidates an assessment's gaps into a plan: every non-compliant or
+//! partially compliant control becomes an item with a recommended action, an
+//! effort estimate, a suggested owner and a target quarter, grouped by
+//! category and ordered by urgency. Plans are stored so owners, due dates and
+//! progress can be tracked; regenerating a plan keeps those edits for
+//! controls that are still open. A client's items across all assessments
+//! roll up into overdue lists and counts by status and priority.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::frameworks::{default_remediation_action, get_framework_categories, suggested_owner_role};
+use crate::grc::models::{Assessment, ComplianceStatus, Control, ControlAssessment, Framework};
+use crate::grc::repository::{parse_compliance_status, parse_datetime, parse_framework};
+use chrono::{DateTime, Datelike, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Initialize remediation plan schema
+pub fn init_remediation_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- One plan per assessment, replaced when regenerated
+        CREATE TABLE IF NOT EXISTS remediation_plans (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            assessment_id TEXT NOT NULL UNIQUE,
+            framework TEXT NOT NULL,
+            generated_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (assessment_id) REFERENCES assessments(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS remediation_items (
+            id TEXT PRIMARY KEY,
+            plan_id TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            control_id TEXT NOT NULL,
+            control_code TEXT NOT NULL,
+            control_title TEXT NOT NULL,
+            category TEXT NOT NULL,
+            category_name TEXT NOT NULL,
+            compliance_status TEXT NOT NULL,
+            risk_rating INTEGER,
+            gap TEXT,
+            recommended_action TEXT NOT NULL,
+            effort TEXT NOT NULL,
+            owner_role TEXT NOT NULL,
+            owner TEXT,
+            due_date TEXT,
+            status TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (plan_id) REFERENCES remediation_plans(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_remediation_items_plan ON remediation_items(plan_id);
+    "#)?;
+
+    tracing::info!("Remediation plan schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Rough size of a remediation item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EffortEstimate {
+    Small,
+    Medium,
+    Large,
+}
+
+impl EffortEstimate {
+    /// Short form used in tables ("S", "M", "L")
+    pub fn code(&self) -> &'static str {
+        match self {
+            EffortEstimate::Small => "S",
+            EffortEstimate::Medium => "M",
+            EffortEstimate::Large => "L",
+        }
+    }
+}
+
+/// Progress of a remediation item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RemediationItemStatus {
+    Open,
+    InProgress,
+    Blocked,
+    Done,
+    /// The client accepted the risk instead of closing the gap
+    AcceptedRisk,
+}
+
+impl RemediationItemStatus {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RemediationItemStatus::Open => "Open",
+            RemediationItemStatus::InProgress => "In Progress",
+            RemediationItemStatus::Blocked => "Blocked",
+            RemediationItemStatus::Done => "Done",
+            RemediationItemStatus::AcceptedRisk => "Accepted Risk",
+        }
+    }
+
+    /// Done or accepted, so no longer counted as outstanding
+    pub fn is_closed(&self) -> bool {
+        matches!(self, RemediationItemStatus::Done | RemediationItemStatus::AcceptedRisk)
+    }
+}
+
+impl std::str::FromStr for RemediationItemStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().replace([' ', '-'], "_").as_str() {
+            "OPEN" => Ok(RemediationItemStatus::Open),
+            "IN_PROGRESS" | "INPROGRESS" => Ok(RemediationItemStatus::InProgress),
+            "BLOCKED" => Ok(RemediationItemStatus::Blocked),
+            "DONE" => Ok(RemediationItemStatus::Done),
+            "ACCEPTED_RISK" | "ACCEPTEDRISK" => Ok(RemediationItemStatus::AcceptedRisk),
+            _ => Err(format!("Invalid remediation status: {}", s)),
+        }
+    }
+}
+
+/// Urgency of a remediation item, from its risk rating
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RemediationPriority {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl RemediationPriority {
+    /// Priority of a control gap from its risk rating; unrated non-compliant
+    /// controls are high
+    pub fn from_gap(risk_rating: Option<u8>, status: ComplianceStatus) -> Self {
+        match (risk_rating, status) {
+            (Some(5), _) => RemediationPriority::Critical,
+            (Some(4), _) | (None, ComplianceStatus::NonCompliant) => RemediationPriority::High,
+            (Some(3), _) | (None, _) => RemediationPriority::Medium,
+            (Some(_), _) => RemediationPriority::Low,
+        }
+    }
+}
+
+/// How gaps map to effort estimates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EffortMapping {
+    /// Effort to close a partially compliant control
+    pub partially_compliant: EffortEstimate,
+    /// Effort to implement a non-compliant control
+    pub non_compliant: EffortEstimate,
+    /// Effort for a non-compliant control at or above `high_priority`
+    pub non_compliant_high_priority: EffortEstimate,
+    /// Control priority (1-5) treated as high
+    pub high_priority: u8,
+    /// Fixed estimates by control code, taking precedence over the rules above
+    pub overrides: HashMap<String, EffortEstimate>,
+}
+
+impl Default for EffortMapping {
+    fn default() -> Self {
+        EffortMapping {
+            partially_compliant: EffortEstimate::Small,
+            non_compliant: EffortEstimate::Medium,
+            non_compliant_high_priority: EffortEstimate::Large,
+            high_priority: 5,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl EffortMapping {
+    pub fn estimate(&self, control: &Control, status: ComplianceStatus) -> EffortEstimate {
+        if let Some(effort) = self.overrides.get(&control.code) {
+            return *effort;
+        }
+        match status {
+            ComplianceStatus::NonCompliant if control.priority >= self.high_priority => self.non_compliant_high_priority,
+            ComplianceStatus::NonCompliant => self.non_compliant,
+            _ => self.partially_compliant,
+        }
+    }
+}
+
+/// One gap to close
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationItem {
+    pub id: String,
+    pub control_id: String,
+    pub control_code: String,
+    pub control_title: String,
+    pub category: String,
+    pub compliance_status: ComplianceStatus,
+    /// Assessor's risk rating (1-5), if given
+    pub risk_rating: Option<u8>,
+    pub gap: Option<String>,
+    /// Stored remediation, or the library's suggestion for the control
+    pub recommended_action: String,
+    pub effort: EffortEstimate,
+    pub owner_role: String,
+    /// Named owner, once assigned
+    pub owner: Option<String>,
+    pub due_date: Option<DateTime<Utc>>,
+    /// Quarter of the due date, e.g. "Q3 2026"
+    pub target_quarter: Option<String>,
+    pub status: RemediationItemStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RemediationItem {
+    /// Set the due date and the target quarter derived from it
+    pub fn set_due_date(&mut self, due_date: Option<DateTime<Utc>>) {
+        self.due_date = due_date;
+        self.target_quarter = due_date.map(|d| format!("Q{} {}", d.month0() / 3 + 1, d.year()));
+    }
+
+    /// Priority from the risk rating; unrated non-compliant controls are high
+    pub fn priority(&self) -> RemediationPriority {
+        RemediationPriority::from_gap(self.risk_rating, self.compliance_status)
+    }
+
+    /// Past its due date and not yet closed
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        !self.status.is_closed() && self.due_date.is_some_and(|d| d < now)
+    }
+}
+
+/// A remediation item with the assessment it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientRemediationItem {
+    pub assessment_id: String,
+    pub assessment_name: String,
+    #[serde(flatten)]
+    pub item: RemediationItem,
+}
+
+/// Roll-up of a client's remediation items across assessments
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationStats {
+    pub total: usize,
+    /// Items neither done nor accepted
+    pub outstanding: usize,
+    pub overdue: usize,
+    pub by_status: HashMap<RemediationItemStatus, usize>,
+    /// Outstanding items only
+    pub by_priority: HashMap<RemediationPriority, usize>,
+}
+
+impl RemediationStats {
+    pub fn from_items<'i>(items: impl IntoIterator<Item = &'i RemediationItem>, now: DateTime<Utc>) -> Self {
+        let mut stats = RemediationStats::default();
+        for item in items {
+            stats.total += 1;
+            *stats.by_status.entry(item.status).or_insert(0) += 1;
+            if item.status.is_closed() {
+                continue;
+            }
+            stats.outstanding += 1;
+            *stats.by_priority.entry(item.priority()).or_insert(0) += 1;
+            if item.is_overdue(now) {
+                stats.overdue += 1;
+            }
+        }
+        stats
+    }
+}
+
+/// A category's items, most urgent first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationCategory {
+    pub category: String,
+    pub display_name: String,
+    pub items: Vec<RemediationItem>,
+}
+
+/// Remediation plan for an assessment
+///
+/// Categories are ordered by their most urgent item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationPlan {
+    pub id: String,
+    pub client_id: String,
+    pub assessment_id: String,
+    pub framework: Framework,
+    pub categories: Vec<RemediationCategory>,
+    pub generated_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RemediationPlan {
+    pub fn items(&self) -> impl Iterator<Item = &RemediationItem> {
+        self.categories.iter().flat_map(|c| c.items.iter())
+    }
+
+    /// Number of items with each effort estimate, as (small, medium, large)
+    pub fn effort_counts(&self) -> (usize, usize, usize) {
+        self.items().fold((0, 0, 0), |(s, m, l), item| match item.effort {
+            EffortEstimate::Small => (s + 1, m, l),
+            EffortEstimate::Medium => (s, m + 1, l),
+            EffortEstimate::Large => (s, m, l + 1),
+        })
+    }
+}
+
+/// Build a remediation plan from an assessment's non-compliant and partially
+/// compliant controls
+///
+/// Items are ranked by risk rating, then non-compliant before partially
+/// compliant, then control priority. Owner, due date and status edits from
+/// `previous` carry over to items for the same control.
+pub fn build_remediation_plan(
+    assessment: &Assessment,
+    control_assessments: &[ControlAssessment],
+    mapping: &EffortMapping,
+    previous: Option<&RemediationPlan>,
+) -> RemediationPlan {
+    let now = Utc::now();
+    let controls = crate::grc::frameworks::get_framework_controls(assessment.framework);
+    let category_names: HashMap<String, String> = get_framework_categories(assessment.framework)
+        .into_iter()
+        .map(|c| (c.code, c.name))
+        .collect();
+    let previous_items: HashMap<&str, &RemediationItem> = previous
+        .map(|p| p.items().map(|i| (i.control_id.as_str(), i)).collect())
+        .unwrap_or_default();
+
+    let mut ranked: Vec<(u8, u8, u8, RemediationItem)> = Vec::new();
+    for ca in control_assessments {
+        if !matches!(ca.status, ComplianceStatus::NonCompliant | ComplianceStatus::PartiallyCompliant) {
+            continue;
+        }
+        // Control assessments may reference a control by ID or by code
+        let control = match controls.iter().find(|c| c.id == ca.control_id || c.code == ca.control_id) {
+            Some(control) => control,
+            None => continue,
+        };
+
+        let mut item = RemediationItem {
+            id: uuid::Uuid::new_v4().to_string(),
+            control_id: ca.control_id.clone(),
+            control_code: control.code.clone(),
+            control_title: control.title.clone(),
+            category: control.category.clone(),
+            compliance_status: ca.status,
+            risk_rating: ca.risk_rating,
+            gap: ca.gap_description.clone().filter(|g| !g.trim().is_empty()),
+            recommended_action: ca.remediation.clone()
+                .filter(|r| !r.trim().is_empty())
+                .unwrap_or_else(|| default_remediation_action(control)),
+            effort: mapping.estimate(control, ca.status),
+            owner_role: suggested_owner_role(assessment.framework, &control.category).to_string(),
+            owner: None,
+            due_date: None,
+            target_quarter: None,
+            status: RemediationItemStatus::Open,
+            updated_at: now,
+        };
+        item.set_due_date(ca.remediation_target);
+
+        if let Some(existing) = previous_items.get(ca.control_id.as_str()) {
+            item.id = existing.id.clone();
+            item.owner = existing.owner.clone();
+            item.status = existing.status;
+            item.set_due_date(existing.due_date.or(ca.remediation_target));
+        }
+
+        let status_rank = u8::from(ca.status == ComplianceStatus::NonCompliant);
+        ranked.push((ca.risk_rating.unwrap_or(0), status_rank, control.priority, item));
+    }
+    ranked.sort_by(|a, b| {
+        (b.0, b.1, b.2)
+            .cmp(&(a.0, a.1, a.2))
+            .then_with(|| a.3.control_code.cmp(&b.3.control_code))
+    });
+
+    let mut categories: Vec<RemediationCategory> = Vec::new();
+    for (_, _, _, item) in ranked {
+        match categories.iter_mut().find(|c| c.category == item.category) {
+            Some(category) => category.items.push(item),
+            None => categories.push(RemediationCategory {
+                category: item.category.clone(),
+                display_name: category_names.get(&item.category).cloned().unwrap_or_else(|| item.category.clone()),
+                items: vec![item],
+            }),
+        }
+    }
+
+    RemediationPlan {
+        id: previous.map(|p| p.id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        client_id: assessment.client_id.clone(),
+        assessment_id: assessment.id.clone(),
+        framework: assessment.framework,
+        categories,
+        generated_at: now,
+        updated_at: now,
+    }
+}
+
+/// Standalone CSV export of a plan, one row per item
+pub fn remediation_plan_to_csv(plan: &RemediationPlan) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "Category", "Control", "Title", "Compliance Status", "Risk Rating", "Gap",
+        "Recommended Action", "Effort", "Owner Role", "Owner", "Due Date", "Target Quarter", "Status",
+    ]).map_err(|e| e.to_string())?;
+
+    for category in &plan.categories {
+        for item in &category.items {
+            writer.write_record([
+                category.display_name.as_str(),
+                &item.control_code,
+                &item.control_title,
+                item.compliance_status.display_name(),
+                &item.risk_rating.map(|r| r.to_string()).unwrap_or_default(),
+                item.gap.as_deref().unwrap_or(""),
+                &item.recommended_action,
+                item.effort.code(),
+                &item.owner_role,
+                item.owner.as_deref().unwrap_or(""),
+                &item.due_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+                item.target_quarter.as_deref().unwrap_or(""),
+                item.status.display_name(),
+            ]).map_err(|e| e.to_string())?;
+        }
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Remediation plan repository
+pub struct RemediationPlanRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> RemediationPlanRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        RemediationPlanRepository { db }
+    }
+
+    /// Store a plan, replacing the assessment's previous plan and its items
+    pub fn save(&self, plan: &RemediationPlan) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        tx.execute("DELETE FROM remediation_plans WHERE assessment_id = ?1", params![plan.assessment_id])?;
+        tx.execute(
+            r#"INSERT INTO remediation_plans (id, client_id, assessment_id, framework, generated_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6)"#,
+            params![
+                plan.id,
+                plan.client_id,
+                plan.assessment_id,
+                format!("{:?}", plan.framework),
+                plan.generated_at.to_rfc3339(),
+                plan.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        for (position, (category, item)) in plan.categories.iter()
+            .flat_map(|c| c.items.iter().map(move |i| (c, i)))
+            .enumerate()
+        {
+            tx.execute(
+                r#"INSERT INTO remediation_items
+                   (id, plan_id, position, control_id, control_code, control_title, category, category_name,
+                    compliance_status, risk_rating, gap, recommended_action, effort, owner_role, owner,
+                    due_date, status, updated_at)
+                   VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)"#,
+                params![
+                    item.id,
+                    plan.id,
+                    position as i64,
+                    item.control_id,
+                    item.control_code,
+                    item.control_title,
+                    category.category,
+                    category.display_name,
+                    format!("{:?}", item.compliance_status),
+                    item.risk_rating,
+                    item.gap,
+                    item.recommended_action,
+                    format!("{:?}", item.effort),
+                    item.owner_role,
+                    item.owner,
+                    item.due_date.map(|d| d.to_rfc3339()),
+                    format!("{:?}", item.status),
+                    item.updated_at.to_rfc3339(),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// An assessment's plan with its items in ranked order
+    pub fn get_by_assessment(&self, assessment_id: &str) -> OptioResult<Option<RemediationPlan>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, client_id, assessment_id, framework, generated_at, updated_at
+             FROM remediation_plans WHERE assessment_id = ?1",
+        )?;
+        let mut plans = stmt.query_map(params![assessment_id], |row| Ok(parse_plan_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+        let mut plan = match plans.pop() {
+            Some(plan) => plan,
+            None => return Ok(None),
+        };
+
+        let sql = format!("SELECT {} FROM remediation_items WHERE plan_id = ?1 ORDER BY position", ITEM_COLUMNS);
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![plan.id], |row| Ok(parse_item_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        for (display_name, item) in rows {
+            match plan.categories.iter_mut().find(|c| c.category == item.category) {
+                Some(category) => category.items.push(item),
+                None => plan.categories.push(RemediationCategory {
+                    category: item.category.clone(),
+                    display_name,
+                    items: vec![item],
+                }),
+            }
+        }
+
+        Ok(Some(plan))
+    }
+
+    /// An item with the ID of the plan it belongs to
+    pub fn get_item(&self, id: &str) -> OptioResult<Option<(String, RemediationItem)>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let sql = format!("SELECT plan_id, {} FROM remediation_items WHERE id = ?1", ITEM_COLUMNS);
+        let mut stmt = conn.prepare(&sql)?;
+        let mut items = stmt.query_map(params![id], |row| Ok(parse_item_with_plan_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(items.pop())
+    }
+
+    /// Save an item's owner, due date and status
+    pub fn update_item(&self, plan_id: &str, item: &RemediationItem) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let updated = conn.execute(
+            "UPDATE remediation_items SET owner = ?1, due_date = ?2, status = ?3, updated_at = ?4 WHERE id = ?5",
+            params![
+                item.owner,
+                item.due_date.map(|d| d.to_rfc3339()),
+                format!("{:?}", item.status),
+                item.updated_at.to_rfc3339(),
+                item.id,
+            ],
+        )?;
+        conn.execute(
+            "UPDATE remediation_plans SET updated_at = ?1 WHERE id = ?2",
+            params![item.updated_at.to_rfc3339(), plan_id],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    /// Remove an item from its plan
+    pub fn delete_item(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM remediation_items WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Every item in a client's plans, by assessment then ranked order
+    pub fn get_by_client(&self, client_id: &str) -> OptioResult<Vec<ClientRemediationItem>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let columns = ITEM_COLUMNS.split(", ").map(|c| format!("i.{}", c.trim())).collect::<Vec<_>>().join(", ");
+        let sql = format!(
+            "SELECT p.assessment_id, a.name, {} FROM remediation_items i
+             JOIN remediation_plans p ON p.id = i.plan_id
+             JOIN assessments a ON a.id = p.assessment_id
+             WHERE p.client_id = ?1
+             ORDER BY a.started_at, p.assessment_id, i.position",
+            columns
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let items = stmt.query_map(params![client_id], |row| Ok(parse_client_item_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    /// Number of overdue items per client, for clients with any
+    ///
+    /// Overdue as in `RemediationItem::is_overdue`: past the due date and
+    /// neither done nor accepted.
+    pub fn count_overdue_by_client(&self, now: DateTime<Utc>) -> OptioResult<HashMap<String, usize>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT p.client_id, COUNT(*) FROM remediation_items i
+             JOIN remediation_plans p ON p.id = i.plan_id
+             WHERE i.status NOT IN ('Done', 'AcceptedRisk')
+               AND i.due_date IS NOT NULL AND julianday(i.due_date) < julianday(?1)
+             GROUP BY p.client_id",
+        )?;
+        let counts = stmt
+            .query_map(params![now.to_rfc3339()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(counts)
+    }
+}
+
+const ITEM_COLUMNS: &str = "id, control_id, control_code, control_title, category, category_name, compliance_status, \
+    risk_rating, gap, recommended_action, effort, owner_role, owner, due_date, status, updated_at";
+
+fn parse_plan_row(row: &rusqlite::Row) -> OptioResult<RemediationPlan> {
+    Ok(RemediationPlan {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        assessment_id: row.get(2)?,
+        framework: parse_framework(&row.get::<_, String>(3)?)?,
+        categories: vec![],
+        generated_at: parse_datetime(&row.get::<_, String>(4)?)?,
+        updated_at: parse_datetime(&row.get::<_, String>(5)?)?,
+    })
+}
+
+/// Item and its category's display name
+fn parse_item_row(row: &rusqlite::Row) -> OptioResult<(String, RemediationItem)> {
+    parse_item_row_at(row, 0)
+}
+
+/// Plan ID followed by the item columns
+fn parse_item_with_plan_row(row: &rusqlite::Row) -> OptioResult<(String, RemediationItem)> {
+    let (_, item) = parse_item_row_at(row, 1)?;
+    Ok((row.get(0)?, item))
+}
+
+/// Assessment ID and name followed by the item columns
+fn parse_client_item_row(row: &rusqlite::Row) -> OptioResult<ClientRemediationItem> {
+    let (_, item) = parse_item_row_at(row, 2)?;
+    Ok(ClientRemediationItem {
+        assessment_id: row.get(0)?,
+        assessment_name: row.get(1)?,
+        item,
+    })
+}
+
+fn parse_item_row_at(row: &rusqlite::Row, offset: usize) -> OptioResult<(String, RemediationItem)> {
+    let col = |i: usize| offset + i;
+
+    let mut item = RemediationItem {
+        id: row.get(col(0))?,
+        control_id: row.get(col(1))?,
+        control_code: row.get(col(2))?,
+        control_title: row.get(col(3))?,
+        category: row.get(col(4))?,
+        compliance_status: parse_compliance_status(&row.get::<_, String>(col(6))?)?,
+        risk_rating: row.get(col(7))?,
+        gap: row.get(col(8))?,
+        recommended_action: row.get(col(9))?,
+        effort: parse_effort(&row.get::<_, String>(col(10))?)?,
+        owner_role: row.get(col(11))?,
+        owner: row.get(col(12))?,
+        due_date: None,
+        target_quarter: None,
+        status: parse_item_status(&row.get::<_, String>(col(14))?)?,
+        updated_at: parse_datetime(&row.get::<_, String>(col(15))?)?,
+    };
+    item.set_due_date(row.get::<_, Option<String>>(col(13))?
+        .map(|s| parse_datetime(&s))
+        .transpose()?);
+
+    Ok((row.get(col(5))?, item))
+}
+
+fn parse_effort(s: &str) -> OptioResult<EffortEstimate> {
+    match s {
+        "Small" => Ok(EffortEstimate::Small),
+        "Medium" => Ok(EffortEstimate::Medium),
+        "Large" => Ok(EffortEstimate::Large),
+        _ => Err(OptioError::Database(format!("Unknown effort estimate: {}", s))),
+    }
+}
+
+fn parse_item_status(s: &str) -> OptioResult<RemediationItemStatus> {
+    match s {
+        "Open" => Ok(RemediationItemStatus::Open),
+        "InProgress" => Ok(RemediationItemStatus::InProgress),
+        "Blocked" => Ok(RemediationItemStatus::Blocked),
+        "Done" => Ok(RemediationItemStatus::Done),
+        "AcceptedRisk" => Ok(RemediationItemStatus::AcceptedRisk),
+        _ => Err(OptioError::Database(format!("Unknown remediation status: {}", s))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grc::models::AssessmentStatus;
+    use crate::grc::repository::{init_grc_schema, AssessmentRepository};
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_grc_schema(&db).unwrap();
+        init_remediation_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn assessment() -> Assessment {
+        Assessment {
+            id: "a1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "NIST CSF 2026".to_string(),
+            description: None,
+            framework: Framework::NistCsf2,
+            scope: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            lead_assessor: "Assessor".to_string(),
+            status: AssessmentStatus::InProgress,
+            source_assessment_id: None,
+        }
+    }
+
+    fn control(code: &str, status: ComplianceStatus, risk_rating: Option<u8>) -> ControlAssessment {
+        ControlAssessment {
+            id: uuid::Uuid::new_v4().to_string(),
+            assessment_id: "a1".to_string(),
+            control_id: code.to_string(),
+            status,
+            notes: None,
+            gap_description: Some(format!("Gap in {}", code)),
+            remediation: None,
+            remediation_target: None,
+            risk_rating,
+            maturity_level: None,
+            evidence_ids: vec![],
+            assessed_at: Utc::now(),
+            assessed_by: "Assessor".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_remediation_plan() {
+        let mut governance = control("GV.RR-01", ComplianceStatus::PartiallyCompliant, Some(2));
+        governance.remediation = Some("Publish a RACI for security roles".to_string());
+        governance.remediation_target = Some(parse_datetime("2026-08-14T00:00:00Z").unwrap());
+        let cas = vec![
+            governance,
+            control("GV.OC-01", ComplianceStatus::NonCompliant, Some(5)),
+            control("GV.RM-01", ComplianceStatus::Compliant, None),
+            control("NOT-A-CONTROL", ComplianceStatus::NonCompliant, Some(5)),
+        ];
+
+        let plan = build_remediation_plan(&assessment(), &cas, &EffortMapping::default(), None);
+        assert_eq!(plan.categories.len(), 1);
+        assert_eq!(plan.categories[0].display_name, "Govern");
+        let items = &plan.categories[0].items;
+        assert_eq!(items.iter().map(|i| i.control_code.as_str()).collect::<Vec<_>>(), vec!["GV.OC-01", "GV.RR-01"]);
+
+        // Priority 5 non-compliant control is large; stored remediation wins over the library
+        assert_eq!(items[0].effort, EffortEstimate::Large);
+        assert_eq!(items[0].recommended_action, "Document how cybersecurity supports organizational objectives");
+        assert_eq!(items[0].owner_role, "CISO");
+        assert_eq!(items[1].effort, EffortEstimate::Small);
+        assert_eq!(items[1].recommended_action, "Publish a RACI for security roles");
+        assert_eq!(items[1].target_quarter.as_deref(), Some("Q3 2026"));
+
+        let mapping = EffortMapping {
+            overrides: HashMap::from([("GV.OC-01".to_string(), EffortEstimate::Medium)]),
+            ..Default::default()
+        };
+        let plan = build_remediation_plan(&assessment(), &cas, &mapping, None);
+        assert_eq!(plan.effort_counts(), (1, 1, 0));
+
+        let csv = remediation_plan_to_csv(&plan).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.lines().nth(2).unwrap().contains("Q3 2026"));
+    }
+
+    #[test]
+    fn test_plan_persistence_keeps_edits() {
+        let db = test_db();
+        AssessmentRepository::new(&db).create(&assessment()).unwrap();
+        let repo = RemediationPlanRepository::new(&db);
+
+        let cas = vec![
+            control("PR.AA-01", ComplianceStatus::NonCompliant, Some(4)),
+            control("GV.OC-01", ComplianceStatus::PartiallyCompliant, None),
+        ];
+        let plan = build_remediation_plan(&assessment(), &cas, &EffortMapping::default(), None);
+        repo.save(&plan).unwrap();
+
+        let stored = repo.get_by_assessment("a1").unwrap().unwrap();
+        assert_eq!(stored.items().count(), 2);
+        assert_eq!(stored.categories[0].category, plan.categories[0].category);
+
+        // Assign an owner and mark progress
+        let item_id = stored.categories[0].items[0].id.clone();
+        let (plan_id, mut item) = repo.get_item(&item_id).unwrap().unwrap();
+        assert_eq!(plan_id, stored.id);
+        item.owner = Some("J. Smith".to_string());
+        item.status = RemediationItemStatus::InProgress;
+        item.set_due_date(Some(parse_datetime("2027-01-05T00:00:00Z").unwrap()));
+        assert!(repo.update_item(&plan_id, &item).unwrap());
+
+        // Regenerating keeps the edits for controls that are still open
+        let regenerated = build_remediation_plan(&assessment(), &cas, &EffortMapping::default(), Some(&repo.get_by_assessment("a1").unwrap().unwrap()));
+        repo.save(&regenerated).unwrap();
+        let stored = repo.get_by_assessment("a1").unwrap().unwrap();
+        let kept = stored.items().find(|i| i.id == item_id).unwrap();
+        assert_eq!(kept.owner.as_deref(), Some("J. Smith"));
+        assert_eq!(kept.status, RemediationItemStatus::InProgress);
+        assert_eq!(kept.target_quarter.as_deref(), Some("Q1 2027"));
+    }
+
+    #[test]
+    fn test_client_items_and_stats() {
+        let db = test_db();
+        AssessmentRepository::new(&db).create(&assessment()).unwrap();
+        let repo = RemediationPlanRepository::new(&db);
+
+        let cas = vec![
+            control("GV.OC-01", ComplianceStatus::NonCompliant, Some(5)),
+            control("PR.AA-01", ComplianceStatus::NonCompliant, None),
+            control("GV.RR-01", ComplianceStatus::PartiallyCompliant, Some(2)),
+        ];
+        let mut plan = build_remediation_plan(&assessment(), &cas, &EffortMapping::default(), None);
+        let past = parse_datetime("2020-01-01T00:00:00Z").unwrap();
+        for item in plan.categories.iter_mut().flat_map(|c| c.items.iter_mut()) {
+            item.set_due_date(Some(past));
+            if item.control_code == "GV.RR-01" {
+                item.status = RemediationItemStatus::AcceptedRisk;
+            }
+        }
+        repo.save(&plan).unwrap();
+
+        let items = repo.get_by_client("client-1").unwrap();
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(|i| i.assessment_id == "a1" && i.assessment_name == "NIST CSF 2026"));
+        assert!(repo.get_by_client("client-2").unwrap().is_empty());
+
+        // Accepted risk is closed, so neither outstanding nor overdue
+        let stats = RemediationStats::from_items(items.iter().map(|i| &i.item), Utc::now());
+        assert_eq!((stats.total, stats.outstanding, stats.overdue), (3, 2, 2));
+        assert_eq!(stats.by_status.get(&RemediationItemStatus::AcceptedRisk), Some(&1));
+        assert_eq!(stats.by_priority.get(&RemediationPriority::Critical), Some(&1));
+        assert_eq!(stats.by_priority.get(&RemediationPriority::High), Some(&1));
+        assert_eq!(stats.by_priority.get(&RemediationPriority::Low), None);
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["byStatus"]["ACCEPTED_RISK"], 1);
+
+        assert_eq!("accepted-risk".parse::<RemediationItemStatus>(), Ok(RemediationItemStatus::AcceptedRisk));
+        assert!(repo.delete_item(&items[0].item.id).unwrap());
+        assert_eq!(repo.get_by_client("client-1").unwrap().len(), 2);
+    }
+}
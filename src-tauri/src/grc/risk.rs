@@ -0,0 +1,496 @@
+//! Risk Register
+//!
+//! Managed risks for a client, with owners, due dates and a lifecycle, linked
+//! back to the control assessments and network findings that raised them.
+//! Risks are scored on a 5x5 likelihood x impact grid; the residual score
+//! reflects the position once mitigations are in place.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::models::RiskSummary;
+use crate::grc::repository::parse_datetime;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+/// Initialize risk register schema
+pub fn init_risk_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Risk register (linked control and finding ids stored as JSON)
+        CREATE TABLE IF NOT EXISTS risks (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            assessment_id TEXT,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL,
+            likelihood INTEGER NOT NULL,
+            impact INTEGER NOT NULL,
+            residual_likelihood INTEGER,
+            residual_impact INTEGER,
+            owner TEXT,
+            status TEXT NOT NULL,
+            control_ids TEXT NOT NULL,
+            finding_ids TEXT NOT NULL,
+            due_date TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_risks_client ON risks(client_id);
+    "#)?;
+
+    tracing::info!("Risk register schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Lifecycle of a managed risk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RiskStatus {
+    Open,
+    Mitigating,
+    Accepted,
+    Closed,
+}
+
+impl RiskStatus {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RiskStatus::Open => "Open",
+            RiskStatus::Mitigating => "Mitigating",
+            RiskStatus::Accepted => "Accepted",
+            RiskStatus::Closed => "Closed",
+        }
+    }
+}
+
+/// Band of a 1-25 risk score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl RiskLevel {
+    pub fn from_score(score: u8) -> Self {
+        match score {
+            15.. => RiskLevel::Critical,
+            10..=14 => RiskLevel::High,
+            5..=9 => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    /// Overall level of a set of issues counted by severity
+    ///
+    /// More than one critical issue is Critical, more than two high ones
+    /// High, more than five medium ones Medium, and anything less Low.
+    pub fn from_counts(critical: usize, high: usize, medium: usize) -> Self {
+        if critical > 1 {
+            RiskLevel::Critical
+        } else if high > 2 {
+            RiskLevel::High
+        } else if medium > 5 {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RiskLevel::Low => "Low",
+            RiskLevel::Medium => "Medium",
+            RiskLevel::High => "High",
+            RiskLevel::Critical => "Critical",
+        }
+    }
+
+    /// Name used for the overall rating in reports
+    pub fn rating_name(&self) -> &'static str {
+        match self {
+            RiskLevel::Medium => "Moderate",
+            level => level.display_name(),
+        }
+    }
+}
+
+/// An entry in a client's risk register
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskItem {
+    pub id: String,
+    pub client_id: String,
+    /// Assessment the risk was raised from, if any
+    pub assessment_id: Option<String>,
+    pub title: String,
+    pub description: String,
+    /// Likelihood before mitigation (1-5)
+    pub likelihood: u8,
+    /// Impact before mitigation (1-5)
+    pub impact: u8,
+    /// Likelihood x impact
+    pub inherent_score: u8,
+    pub residual_likelihood: Option<u8>,
+    pub residual_impact: Option<u8>,
+    /// Residual likelihood x impact, once both are set
+    pub residual_score: Option<u8>,
+    pub owner: Option<String>,
+    pub status: RiskStatus,
+    /// Linked control ids (e.g. "PR.AC-01")
+    pub control_ids: Vec<String>,
+    /// Linked network finding ids
+    pub finding_ids: Vec<String>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RiskItem {
+    /// Recompute the derived scores from likelihood and impact
+    pub fn rescore(&mut self) {
+        self.inherent_score = self.likelihood * self.impact;
+        self.residual_score = match (self.residual_likelihood, self.residual_impact) {
+            (Some(likelihood), Some(impact)) => Some(likelihood * impact),
+            _ => None,
+        };
+    }
+
+    /// Current exposure: the residual score where assessed, else inherent
+    pub fn current_score(&self) -> u8 {
+        self.residual_score.unwrap_or(self.inherent_score)
+    }
+
+    pub fn level(&self) -> RiskLevel {
+        RiskLevel::from_score(self.current_score())
+    }
+
+    /// Whether the risk still needs managing
+    pub fn is_active(&self) -> bool {
+        self.status != RiskStatus::Closed
+    }
+}
+
+/// Check a likelihood or impact rating is on the 1-5 scale
+pub fn validate_rating(name: &str, value: u8) -> Result<(), String> {
+    if (1..=5).contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("{} must be between 1 and 5, got {}", name, value))
+    }
+}
+
+/// One cell of the 5x5 risk heatmap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskMatrixCell {
+    pub likelihood: u8,
+    pub impact: u8,
+    pub score: u8,
+    pub level: RiskLevel,
+    pub risk_ids: Vec<String>,
+}
+
+/// Active risks bucketed by inherent likelihood and impact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskMatrix {
+    pub client_id: String,
+    /// 25 cells, highest likelihood first, then by increasing impact
+    pub cells: Vec<RiskMatrixCell>,
+    pub total_risks: usize,
+    pub critical_count: usize,
+    pub high_count: usize,
+    pub medium_count: usize,
+    pub low_count: usize,
+}
+
+/// Build the heatmap for a client's register; closed risks are left out
+pub fn build_risk_matrix(client_id: &str, risks: &[RiskItem]) -> RiskMatrix {
+    let active: Vec<&RiskItem> = risks.iter().filter(|r| r.is_active()).collect();
+
+    let mut cells = Vec::with_capacity(25);
+    for likelihood in (1..=5u8).rev() {
+        for impact in 1..=5u8 {
+            let score = likelihood * impact;
+            cells.push(RiskMatrixCell {
+                likelihood,
+                impact,
+                score,
+                level: RiskLevel::from_score(score),
+                risk_ids: active
+                    .iter()
+                    .filter(|r| r.likelihood == likelihood && r.impact == impact)
+                    .map(|r| r.id.clone())
+                    .collect(),
+            });
+        }
+    }
+
+    let count = |level: RiskLevel| active.iter().filter(|r| RiskLevel::from_score(r.inherent_score) == level).count();
+
+    RiskMatrix {
+        client_id: client_id.to_string(),
+        cells,
+        total_risks: active.len(),
+        critical_count: count(RiskLevel::Critical),
+        high_count: count(RiskLevel::High),
+        medium_count: count(RiskLevel::Medium),
+        low_count: count(RiskLevel::Low),
+    }
+}
+
+/// Executive risk summary from the register's current (residual) exposure
+///
+/// `None` when the register has no active risks, so callers can fall back
+/// to a compliance-derived estimate.
+pub fn summarize_risks(risks: &[RiskItem]) -> Option<RiskSummary> {
+    let active: Vec<&RiskItem> = risks.iter().filter(|r| r.is_active()).collect();
+    if active.is_empty() {
+        return None;
+    }
+
+    let count = |level: RiskLevel| active.iter().filter(|r| r.level() == level).count();
+    let overall = active.iter().map(|r| r.level()).max().unwrap_or(RiskLevel::Low);
+
+    Some(RiskSummary {
+        critical_count: count(RiskLevel::Critical),
+        high_count: count(RiskLevel::High),
+        medium_count: count(RiskLevel::Medium),
+        low_count: count(RiskLevel::Low),
+        overall_risk_rating: overall.rating_name().to_string(),
+    })
+}
+
+/// Executive risk summary from issues counted by severity, rated with
+/// `RiskLevel::from_counts`
+pub fn summarize_risk_counts(critical: usize, high: usize, medium: usize, low: usize) -> RiskSummary {
+    RiskSummary {
+        critical_count: critical,
+        high_count: high,
+        medium_count: medium,
+        low_count: low,
+        overall_risk_rating: RiskLevel::from_counts(critical, high, medium).rating_name().to_string(),
+    }
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Risk register repository
+pub struct RiskRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> RiskRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        RiskRepository { db }
+    }
+
+    /// Insert or replace a risk
+    pub fn save(&self, risk: &RiskItem) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO risks
+               (id, client_id, assessment_id, title, description, likelihood, impact,
+                residual_likelihood, residual_impact, owner, status, control_ids, finding_ids,
+                due_date, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"#,
+            params![
+                risk.id,
+                risk.client_id,
+                risk.assessment_id,
+                risk.title,
+                risk.description,
+                risk.likelihood,
+                risk.impact,
+                risk.residual_likelihood,
+                risk.residual_impact,
+                risk.owner,
+                format!("{:?}", risk.status),
+                serde_json::to_string(&risk.control_ids)?,
+                serde_json::to_string(&risk.finding_ids)?,
+                risk.due_date.map(|d| d.to_rfc3339()),
+                risk.created_at.to_rfc3339(),
+                risk.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<RiskItem>> {
+        let sql = format!("SELECT {} FROM risks WHERE id = ?1", RISK_COLUMNS);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    /// A client's risks, highest inherent score first
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<RiskItem>> {
+        let sql = format!(
+            "SELECT {} FROM risks WHERE client_id = ?1 ORDER BY likelihood * impact DESC, created_at",
+            RISK_COLUMNS
+        );
+        self.query(&sql, params![client_id])
+    }
+
+    /// Active risk already linked to a control, to avoid raising duplicates
+    pub fn find_active_for_control(&self, client_id: &str, control_id: &str) -> OptioResult<Option<RiskItem>> {
+        Ok(self
+            .list_by_client(client_id)?
+            .into_iter()
+            .find(|r| r.is_active() && r.control_ids.iter().any(|c| c == control_id)))
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM risks WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<RiskItem>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let risks = stmt.query_map(query_params, |row| Ok(parse_risk_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(risks)
+    }
+}
+
+const RISK_COLUMNS: &str = "id, client_id, assessment_id, title, description, likelihood, impact, \
+    residual_likelihood, residual_impact, owner, status, control_ids, finding_ids, due_date, created_at, updated_at";
+
+fn parse_risk_row(row: &rusqlite::Row) -> OptioResult<RiskItem> {
+    let status_str: String = row.get(10)?;
+    let control_ids_json: String = row.get(11)?;
+    let finding_ids_json: String = row.get(12)?;
+
+    let mut risk = RiskItem {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        assessment_id: row.get(2)?,
+        title: row.get(3)?,
+        description: row.get(4)?,
+        likelihood: row.get(5)?,
+        impact: row.get(6)?,
+        inherent_score: 0,
+        residual_likelihood: row.get(7)?,
+        residual_impact: row.get(8)?,
+        residual_score: None,
+        owner: row.get(9)?,
+        status: parse_risk_status(&status_str)?,
+        control_ids: serde_json::from_str(&control_ids_json)?,
+        finding_ids: serde_json::from_str(&finding_ids_json)?,
+        due_date: row.get::<_, Option<String>>(13)?
+            .map(|s| parse_datetime(&s))
+            .transpose()?,
+        created_at: parse_datetime(&row.get::<_, String>(14)?)?,
+        updated_at: parse_datetime(&row.get::<_, String>(15)?)?,
+    };
+    risk.rescore();
+
+    Ok(risk)
+}
+
+fn parse_risk_status(s: &str) -> OptioResult<RiskStatus> {
+    match s {
+        "Open" => Ok(RiskStatus::Open),
+        "Mitigating" => Ok(RiskStatus::Mitigating),
+        "Accepted" => Ok(RiskStatus::Accepted),
+        "Closed" => Ok(RiskStatus::Closed),
+        _ => Err(OptioError::Database(format!("Unknown risk status: {}", s))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_risk_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn risk(id: &str, likelihood: u8, impact: u8, status: RiskStatus) -> RiskItem {
+        let mut risk = RiskItem {
+            id: id.to_string(),
+            client_id: "client-1".to_string(),
+            assessment_id: None,
+            title: format!("Risk {}", id),
+            description: String::new(),
+            likelihood,
+            impact,
+            inherent_score: 0,
+            residual_likelihood: None,
+            residual_impact: None,
+            residual_score: None,
+            owner: None,
+            status,
+            control_ids: vec!["PR.AC-01".to_string()],
+            finding_ids: vec![],
+            due_date: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        risk.rescore();
+        risk
+    }
+
+    #[test]
+    fn test_register_matrix_and_summary() {
+        let db = test_db();
+        let repo = RiskRepository::new(&db);
+
+        let mut mitigated = risk("r1", 5, 4, RiskStatus::Mitigating);
+        mitigated.residual_likelihood = Some(2);
+        mitigated.residual_impact = Some(3);
+        mitigated.rescore();
+        repo.save(&mitigated).unwrap();
+        repo.save(&risk("r2", 5, 4, RiskStatus::Open)).unwrap();
+        repo.save(&risk("r3", 1, 2, RiskStatus::Closed)).unwrap();
+
+        let risks = repo.list_by_client("client-1").unwrap();
+        assert_eq!(risks.len(), 3);
+        let stored = risks.iter().find(|r| r.id == "r1").unwrap();
+        assert_eq!((stored.inherent_score, stored.residual_score), (20, Some(6)));
+        assert_eq!(stored.control_ids, vec!["PR.AC-01"]);
+
+        let matrix = build_risk_matrix("client-1", &risks);
+        assert_eq!(matrix.cells.len(), 25);
+        assert_eq!(matrix.total_risks, 2);
+        let hot = matrix.cells.iter().find(|c| c.likelihood == 5 && c.impact == 4).unwrap();
+        assert_eq!(hot.risk_ids.len(), 2);
+        assert_eq!(hot.level, RiskLevel::Critical);
+
+        // The summary reflects residual exposure
+        let summary = summarize_risks(&risks).unwrap();
+        assert_eq!((summary.critical_count, summary.medium_count), (1, 1));
+        assert_eq!(summary.overall_risk_rating, "Critical");
+
+        assert!(repo.find_active_for_control("client-1", "PR.AC-01").unwrap().is_some());
+        assert!(summarize_risks(&[risk("r4", 1, 1, RiskStatus::Closed)]).is_none());
+    }
+}
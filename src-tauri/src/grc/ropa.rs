@@ -0,0 +1,388 @@
+//! Records of Processing Activities
+//!
+//! The GDPR Article 30 register: what personal data a client processes, why,
+//! on which lawful basis, who receives it and how long it is kept. Activities
+//! link back to the GDPR controls (by article code) they help evidence, so
+//! compliance reports can cite which activities cover which articles.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::frameworks::get_framework_controls;
+use crate::grc::models::Framework;
+use crate::grc::repository::parse_datetime;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+/// Initialize processing activity schema
+pub fn init_ropa_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Article 30 register (list fields and transfers stored as JSON)
+        CREATE TABLE IF NOT EXISTS processing_activities (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            purpose TEXT NOT NULL,
+            lawful_basis TEXT NOT NULL,
+            data_categories TEXT NOT NULL,
+            data_subject_categories TEXT NOT NULL,
+            recipients TEXT NOT NULL,
+            transfers TEXT NOT NULL,
+            retention_period TEXT,
+            security_measures TEXT,
+            control_codes TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_processing_activities_client ON processing_activities(client_id);
+    "#)?;
+
+    tracing::info!("Processing activity schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Article 6(1) lawful basis for processing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LawfulBasis {
+    Consent,
+    Contract,
+    LegalObligation,
+    VitalInterests,
+    PublicTask,
+    LegitimateInterests,
+}
+
+impl LawfulBasis {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LawfulBasis::Consent => "Consent",
+            LawfulBasis::Contract => "Contract",
+            LawfulBasis::LegalObligation => "Legal Obligation",
+            LawfulBasis::VitalInterests => "Vital Interests",
+            LawfulBasis::PublicTask => "Public Task",
+            LawfulBasis::LegitimateInterests => "Legitimate Interests",
+        }
+    }
+
+    /// The Article 6(1) point establishing the basis
+    pub fn article(&self) -> &'static str {
+        match self {
+            LawfulBasis::Consent => "Art. 6(1)(a)",
+            LawfulBasis::Contract => "Art. 6(1)(b)",
+            LawfulBasis::LegalObligation => "Art. 6(1)(c)",
+            LawfulBasis::VitalInterests => "Art. 6(1)(d)",
+            LawfulBasis::PublicTask => "Art. 6(1)(e)",
+            LawfulBasis::LegitimateInterests => "Art. 6(1)(f)",
+        }
+    }
+}
+
+/// Chapter V safeguard covering a transfer outside the EEA
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferSafeguard {
+    AdequacyDecision,
+    StandardContractualClauses,
+    BindingCorporateRules,
+    CodeOfConduct,
+    Certification,
+    Derogation,
+}
+
+impl TransferSafeguard {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TransferSafeguard::AdequacyDecision => "Adequacy Decision (Art. 45)",
+            TransferSafeguard::StandardContractualClauses => "Standard Contractual Clauses (Art. 46)",
+            TransferSafeguard::BindingCorporateRules => "Binding Corporate Rules (Art. 47)",
+            TransferSafeguard::CodeOfConduct => "Code of Conduct (Art. 46)",
+            TransferSafeguard::Certification => "Certification (Art. 46)",
+            TransferSafeguard::Derogation => "Derogation (Art. 49)",
+        }
+    }
+}
+
+/// Personal data sent to a recipient in a third country
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThirdCountryTransfer {
+    /// Destination country or international organisation
+    pub country: String,
+    pub recipient: Option<String>,
+    /// None until a safeguard has been documented
+    pub safeguard: Option<TransferSafeguard>,
+}
+
+/// An entry in a client's record of processing activities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingActivity {
+    pub id: String,
+    pub client_id: String,
+    pub name: String,
+    pub purpose: String,
+    pub lawful_basis: LawfulBasis,
+    /// Categories of personal data (e.g. "Contact details", "Payroll")
+    pub data_categories: Vec<String>,
+    /// Categories of data subjects (e.g. "Employees", "Customers")
+    pub data_subject_categories: Vec<String>,
+    /// Categories of recipients the data is disclosed to
+    pub recipients: Vec<String>,
+    pub transfers: Vec<ThirdCountryTransfer>,
+    /// Time limit for erasure, as recorded (e.g. "7 years after contract end")
+    pub retention_period: Option<String>,
+    /// General description of technical and organisational measures
+    pub security_measures: Option<String>,
+    /// Linked GDPR control codes (e.g. "Art. 30")
+    pub control_codes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProcessingActivity {
+    /// Transfers with no documented safeguard
+    pub fn unsafeguarded_transfers(&self) -> impl Iterator<Item = &ThirdCountryTransfer> {
+        self.transfers.iter().filter(|t| t.safeguard.is_none())
+    }
+}
+
+/// Check an activity is complete enough to record
+///
+/// Returns warnings for gaps that don't block saving, such as a third-country
+/// transfer with no safeguard.
+pub fn validate_processing_activity(activity: &ProcessingActivity) -> Result<Vec<String>, String> {
+    if activity.name.trim().is_empty() {
+        return Err("Processing activity name is required".to_string());
+    }
+    if activity.purpose.trim().is_empty() {
+        return Err("Processing purpose is required".to_string());
+    }
+    if let Some(transfer) = activity.transfers.iter().find(|t| t.country.trim().is_empty()) {
+        return Err(format!(
+            "Third-country transfer{} needs a destination country",
+            transfer.recipient.as_deref().map(|r| format!(" to {}", r)).unwrap_or_default()
+        ));
+    }
+
+    let gdpr_codes: Vec<String> = get_framework_controls(Framework::Gdpr).into_iter().map(|c| c.code).collect();
+    let unknown: Vec<&str> = activity.control_codes.iter()
+        .filter(|code| !gdpr_codes.contains(code))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!("Unknown GDPR control(s): {}", unknown.join(", ")));
+    }
+
+    Ok(activity
+        .unsafeguarded_transfers()
+        .map(|t| format!("Transfer to {} has no safeguard documented", t.country))
+        .collect())
+}
+
+/// Activities linked to each GDPR control code, in control order
+pub fn activities_by_control(activities: &[ProcessingActivity]) -> Vec<(String, Vec<&ProcessingActivity>)> {
+    get_framework_controls(Framework::Gdpr)
+        .into_iter()
+        .map(|control| {
+            let linked: Vec<&ProcessingActivity> = activities.iter().filter(|a| a.control_codes.contains(&control.code)).collect();
+            (control.code, linked)
+        })
+        .filter(|(_, linked)| !linked.is_empty())
+        .collect()
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Processing activity repository
+pub struct ProcessingActivityRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ProcessingActivityRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ProcessingActivityRepository { db }
+    }
+
+    /// Insert or replace an activity
+    pub fn save(&self, activity: &ProcessingActivity) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO processing_activities
+               (id, client_id, name, purpose, lawful_basis, data_categories, data_subject_categories,
+                recipients, transfers, retention_period, security_measures, control_codes,
+                created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)"#,
+            params![
+                activity.id,
+                activity.client_id,
+                activity.name,
+                activity.purpose,
+                format!("{:?}", activity.lawful_basis),
+                serde_json::to_string(&activity.data_categories)?,
+                serde_json::to_string(&activity.data_subject_categories)?,
+                serde_json::to_string(&activity.recipients)?,
+                serde_json::to_string(&activity.transfers)?,
+                activity.retention_period,
+                activity.security_measures,
+                serde_json::to_string(&activity.control_codes)?,
+                activity.created_at.to_rfc3339(),
+                activity.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<ProcessingActivity>> {
+        let sql = format!("SELECT {} FROM processing_activities WHERE id = ?1", ACTIVITY_COLUMNS);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    /// A client's register, by name
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<ProcessingActivity>> {
+        let sql = format!(
+            "SELECT {} FROM processing_activities WHERE client_id = ?1 ORDER BY name COLLATE NOCASE",
+            ACTIVITY_COLUMNS
+        );
+        self.query(&sql, params![client_id])
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM processing_activities WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<ProcessingActivity>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let activities = stmt.query_map(query_params, |row| Ok(parse_activity_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(activities)
+    }
+}
+
+const ACTIVITY_COLUMNS: &str = "id, client_id, name, purpose, lawful_basis, data_categories, \
+    data_subject_categories, recipients, transfers, retention_period, security_measures, control_codes, \
+    created_at, updated_at";
+
+fn parse_activity_row(row: &rusqlite::Row) -> OptioResult<ProcessingActivity> {
+    let lawful_basis_str: String = row.get(4)?;
+
+    Ok(ProcessingActivity {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        name: row.get(2)?,
+        purpose: row.get(3)?,
+        lawful_basis: parse_lawful_basis(&lawful_basis_str)?,
+        data_categories: serde_json::from_str(&row.get::<_, String>(5)?)?,
+        data_subject_categories: serde_json::from_str(&row.get::<_, String>(6)?)?,
+        recipients: serde_json::from_str(&row.get::<_, String>(7)?)?,
+        transfers: serde_json::from_str(&row.get::<_, String>(8)?)?,
+        retention_period: row.get(9)?,
+        security_measures: row.get(10)?,
+        control_codes: serde_json::from_str(&row.get::<_, String>(11)?)?,
+        created_at: parse_datetime(&row.get::<_, String>(12)?)?,
+        updated_at: parse_datetime(&row.get::<_, String>(13)?)?,
+    })
+}
+
+fn parse_lawful_basis(s: &str) -> OptioResult<LawfulBasis> {
+    match s {
+        "Consent" => Ok(LawfulBasis::Consent),
+        "Contract" => Ok(LawfulBasis::Contract),
+        "LegalObligation" => Ok(LawfulBasis::LegalObligation),
+        "VitalInterests" => Ok(LawfulBasis::VitalInterests),
+        "PublicTask" => Ok(LawfulBasis::PublicTask),
+        "LegitimateInterests" => Ok(LawfulBasis::LegitimateInterests),
+        _ => Err(OptioError::Database(format!("Unknown lawful basis: {}", s))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_ropa_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn activity(id: &str, name: &str) -> ProcessingActivity {
+        ProcessingActivity {
+            id: id.to_string(),
+            client_id: "client-1".to_string(),
+            name: name.to_string(),
+            purpose: "Pay staff".to_string(),
+            lawful_basis: LawfulBasis::Contract,
+            data_categories: vec!["Bank details".to_string()],
+            data_subject_categories: vec!["Employees".to_string()],
+            recipients: vec!["Payroll provider".to_string()],
+            transfers: vec![],
+            retention_period: Some("7 years".to_string()),
+            security_measures: None,
+            control_codes: vec!["Art. 5(1)(a)".to_string()],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_register_round_trip_and_validation() {
+        let db = test_db();
+        let repo = ProcessingActivityRepository::new(&db);
+
+        let mut payroll = activity("a1", "Payroll");
+        payroll.transfers = vec![
+            ThirdCountryTransfer {
+                country: "United States".to_string(),
+                recipient: Some("Payroll provider".to_string()),
+                safeguard: Some(TransferSafeguard::StandardContractualClauses),
+            },
+            ThirdCountryTransfer { country: "India".to_string(), recipient: None, safeguard: None },
+        ];
+        let warnings = validate_processing_activity(&payroll).unwrap();
+        assert_eq!(warnings, vec!["Transfer to India has no safeguard documented"]);
+        repo.save(&payroll).unwrap();
+        repo.save(&activity("a2", "CRM")).unwrap();
+
+        let register = repo.list_by_client("client-1").unwrap();
+        assert_eq!(register.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), vec!["CRM", "Payroll"]);
+        let stored = repo.get("a1").unwrap().unwrap();
+        assert_eq!(stored.lawful_basis, LawfulBasis::Contract);
+        assert_eq!(stored.transfers[0].safeguard, Some(TransferSafeguard::StandardContractualClauses));
+
+        let coverage = activities_by_control(&register);
+        assert_eq!(coverage.len(), 1);
+        assert_eq!((coverage[0].0.as_str(), coverage[0].1.len()), ("Art. 5(1)(a)", 2));
+
+        let mut unlinked = activity("a3", "Marketing");
+        unlinked.control_codes = vec!["PR.AC-01".to_string()];
+        assert!(validate_processing_activity(&unlinked).unwrap_err().contains("PR.AC-01"));
+        assert!(validate_processing_activity(&activity("a4", " ")).is_err());
+    }
+}
@@ -0,0 +1,404 @@
+//! Assessment Snapshots
+//!
+//! Assessments keep changing after an interim report has gone out, so the
+//! figures in that report stop matching the database. A snapshot freezes an
+//! assessment's summary and every control's result under a label ("Interim
+//! report", "Final report"); reports can be generated from a snapshot, and
+//! two snapshots compared to show what changed in between.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::frameworks::{apply_maturity, build_compliance_report, get_framework_controls};
+use crate::grc::models::{
+    Assessment, AssessmentComparison, AssessmentSummary, ComplianceStatus, ComplianceStatusReport, ControlAssessment,
+    Framework,
+};
+use crate::grc::repository::{parse_datetime, parse_framework};
+use crate::grc::trend::control_result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Initialize assessment snapshot schema
+pub fn init_assessment_snapshot_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Frozen assessment results (AssessmentSummary and SnapshotControl JSON)
+        CREATE TABLE IF NOT EXISTS assessment_snapshots (
+            id TEXT PRIMARY KEY,
+            assessment_id TEXT NOT NULL,
+            client_id TEXT NOT NULL,
+            framework TEXT NOT NULL,
+            label TEXT NOT NULL,
+            taken_at TEXT NOT NULL,
+            summary TEXT NOT NULL,
+            controls TEXT NOT NULL,
+            FOREIGN KEY (assessment_id) REFERENCES assessments(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_assessment_snapshots_assessment ON assessment_snapshots(assessment_id, taken_at);
+    "#)?;
+
+    tracing::info!("Assessment snapshot schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// One framework control's result when the snapshot was taken
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotControl {
+    pub code: String,
+    pub title: String,
+    pub category: String,
+    /// Not assessed when the control had no result yet
+    pub status: ComplianceStatus,
+    pub risk_rating: Option<u8>,
+    /// Maturity level counted towards averages; None for not applicable controls
+    pub maturity_level: Option<u8>,
+}
+
+/// An assessment's summary and control results frozen at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssessmentSnapshot {
+    pub id: String,
+    pub assessment_id: String,
+    pub client_id: String,
+    pub framework: Framework,
+    pub label: String,
+    pub taken_at: DateTime<Utc>,
+    pub summary: AssessmentSummary,
+    /// Every control of the framework, in framework order
+    pub controls: Vec<SnapshotControl>,
+}
+
+impl AssessmentSnapshot {
+    /// Freeze `summary` and the assessment's control results
+    pub fn new(
+        assessment: &Assessment,
+        label: &str,
+        summary: AssessmentSummary,
+        control_assessments: Vec<ControlAssessment>,
+        taken_at: DateTime<Utc>,
+    ) -> Self {
+        let results: HashMap<String, ControlAssessment> = control_assessments
+            .into_iter()
+            .map(|ca| (ca.control_id.clone(), ca))
+            .collect();
+        let controls = get_framework_controls(assessment.framework)
+            .iter()
+            .map(|control| {
+                let result = control_result(&results, control);
+                SnapshotControl {
+                    code: control.code.clone(),
+                    title: control.title.clone(),
+                    category: control.category.clone(),
+                    status: result.map(|ca| ca.status).unwrap_or(ComplianceStatus::NotAssessed),
+                    risk_rating: result.and_then(|ca| ca.risk_rating),
+                    maturity_level: result.and_then(|ca| ca.rated_maturity()),
+                }
+            })
+            .collect();
+
+        AssessmentSnapshot {
+            id: Uuid::new_v4().to_string(),
+            assessment_id: assessment.id.clone(),
+            client_id: assessment.client_id.clone(),
+            framework: assessment.framework,
+            label: label.trim().to_string(),
+            taken_at,
+            summary,
+            controls,
+        }
+    }
+
+    /// The frozen results as a compliance status report, dated when the
+    /// snapshot was taken
+    pub fn compliance_report(&self) -> ComplianceStatusReport {
+        let results: HashMap<&str, &SnapshotControl> = self.controls.iter().map(|c| (c.code.as_str(), c)).collect();
+        let controls = get_framework_controls(self.framework);
+
+        let mut report = build_compliance_report(self.framework, &controls, |control| {
+            results.get(control.code.as_str()).map_or(ComplianceStatus::NotAssessed, |c| c.status)
+        });
+        apply_maturity(&mut report, &controls, |control| {
+            results.get(control.code.as_str()).and_then(|c| c.maturity_level)
+        });
+        report.last_updated = self.taken_at.to_rfc3339();
+        report
+    }
+}
+
+/// A control whose status differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlStatusChange {
+    pub code: String,
+    pub title: String,
+    pub category: String,
+    pub baseline_status: ComplianceStatus,
+    pub current_status: ComplianceStatus,
+}
+
+/// What changed between two snapshots of the same framework
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotComparison {
+    pub baseline_snapshot_id: String,
+    pub baseline_label: String,
+    pub baseline_taken_at: DateTime<Utc>,
+    pub current_snapshot_id: String,
+    pub current_label: String,
+    pub current_taken_at: DateTime<Utc>,
+    /// Overall and per-category compliance deltas
+    pub comparison: AssessmentComparison,
+    /// Controls whose status changed, in framework order
+    pub changed_controls: Vec<ControlStatusChange>,
+}
+
+impl SnapshotComparison {
+    /// Compare `current` against `baseline`
+    ///
+    /// Controls missing from the baseline count as not assessed there.
+    pub fn between(baseline: &AssessmentSnapshot, current: &AssessmentSnapshot) -> Result<Self, String> {
+        let comparison = AssessmentComparison::between(&baseline.summary, &current.summary)?;
+
+        let before: HashMap<&str, ComplianceStatus> = baseline.controls.iter().map(|c| (c.code.as_str(), c.status)).collect();
+        let changed_controls = current
+            .controls
+            .iter()
+            .filter_map(|control| {
+                let baseline_status = before.get(control.code.as_str()).copied().unwrap_or(ComplianceStatus::NotAssessed);
+                (baseline_status != control.status).then(|| ControlStatusChange {
+                    code: control.code.clone(),
+                    title: control.title.clone(),
+                    category: control.category.clone(),
+                    baseline_status,
+                    current_status: control.status,
+                })
+            })
+            .collect();
+
+        Ok(SnapshotComparison {
+            baseline_snapshot_id: baseline.id.clone(),
+            baseline_label: baseline.label.clone(),
+            baseline_taken_at: baseline.taken_at,
+            current_snapshot_id: current.id.clone(),
+            current_label: current.label.clone(),
+            current_taken_at: current.taken_at,
+            comparison,
+            changed_controls,
+        })
+    }
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Repository for assessment snapshots
+pub struct AssessmentSnapshotRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AssessmentSnapshotRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    pub fn create(&self, snapshot: &AssessmentSnapshot) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO assessment_snapshots (id, assessment_id, client_id, framework, label, taken_at, summary, controls)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                snapshot.id,
+                snapshot.assessment_id,
+                snapshot.client_id,
+                format!("{:?}", snapshot.framework),
+                snapshot.label,
+                snapshot.taken_at.to_rfc3339(),
+                serde_json::to_string(&snapshot.summary)?,
+                serde_json::to_string(&snapshot.controls)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<AssessmentSnapshot>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM assessment_snapshots WHERE id = ?1", SNAPSHOT_COLUMNS))?;
+        let mut rows = stmt.query(params![id])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(parse_snapshot_row(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// An assessment's snapshots, oldest first
+    pub fn list_by_assessment(&self, assessment_id: &str) -> OptioResult<Vec<AssessmentSnapshot>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM assessment_snapshots WHERE assessment_id = ?1 ORDER BY taken_at, rowid",
+            SNAPSHOT_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query(params![assessment_id])?;
+        let mut snapshots = Vec::new();
+        while let Some(row) = rows.next()? {
+            snapshots.push(parse_snapshot_row(row)?);
+        }
+        Ok(snapshots)
+    }
+}
+
+const SNAPSHOT_COLUMNS: &str = "id, assessment_id, client_id, framework, label, taken_at, summary, controls";
+
+fn parse_snapshot_row(row: &rusqlite::Row) -> OptioResult<AssessmentSnapshot> {
+    let summary: String = row.get(6)?;
+    let controls: String = row.get(7)?;
+
+    Ok(AssessmentSnapshot {
+        id: row.get(0)?,
+        assessment_id: row.get(1)?,
+        client_id: row.get(2)?,
+        framework: parse_framework(&row.get::<_, String>(3)?)?,
+        label: row.get(4)?,
+        taken_at: parse_datetime(&row.get::<_, String>(5)?)?,
+        summary: serde_json::from_str(&summary)?,
+        controls: serde_json::from_str(&controls)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grc::models::AssessmentStatus;
+    use crate::grc::repository::{init_grc_schema, AssessmentRepository};
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_grc_schema(&db).unwrap();
+        init_assessment_snapshot_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn assessment() -> Assessment {
+        Assessment {
+            id: "a1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "NIST CSF 2026".to_string(),
+            description: None,
+            framework: Framework::NistCsf2,
+            scope: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            lead_assessor: "Assessor".to_string(),
+            status: AssessmentStatus::InProgress,
+            source_assessment_id: None,
+        }
+    }
+
+    fn control(code: &str, status: ComplianceStatus) -> ControlAssessment {
+        ControlAssessment {
+            id: Uuid::new_v4().to_string(),
+            assessment_id: "a1".to_string(),
+            control_id: code.to_string(),
+            status,
+            notes: None,
+            gap_description: None,
+            remediation: None,
+            remediation_target: None,
+            risk_rating: Some(4),
+            maturity_level: Some(2),
+            evidence_ids: vec![],
+            assessed_at: Utc::now(),
+            assessed_by: "Assessor".to_string(),
+        }
+    }
+
+    fn summary(overall: f64, compliant: usize) -> AssessmentSummary {
+        AssessmentSummary {
+            assessment_id: "a1".to_string(),
+            framework: Framework::NistCsf2,
+            overall_compliance: overall,
+            total_controls: get_framework_controls(Framework::NistCsf2).len(),
+            compliant,
+            partially_compliant: 0,
+            non_compliant: 0,
+            not_assessed: 0,
+            not_applicable: 0,
+            average_maturity: None,
+            category_scores: vec![],
+            high_risk_gaps: 0,
+            evidence_count: 0,
+            source_comparison: None,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_freezes_control_results() {
+        let db = test_db();
+        AssessmentRepository::new(&db).create(&assessment()).unwrap();
+        let repo = AssessmentSnapshotRepository::new(&db);
+
+        let interim = AssessmentSnapshot::new(
+            &assessment(),
+            " Interim report ",
+            summary(25.0, 1),
+            vec![control("GV.OC-01", ComplianceStatus::Compliant), control("GV.RR-01", ComplianceStatus::NonCompliant)],
+            parse_datetime("2026-03-31T09:00:00Z").unwrap(),
+        );
+        repo.create(&interim).unwrap();
+        let final_report = AssessmentSnapshot::new(
+            &assessment(),
+            "Final report",
+            summary(75.0, 3),
+            vec![
+                control("GV.OC-01", ComplianceStatus::Compliant),
+                control("GV.RR-01", ComplianceStatus::Compliant),
+                control("GV.RM-01", ComplianceStatus::Compliant),
+            ],
+            parse_datetime("2026-06-30T09:00:00Z").unwrap(),
+        );
+        repo.create(&final_report).unwrap();
+
+        let stored = repo.get(&interim.id).unwrap().unwrap();
+        assert_eq!(stored.label, "Interim report");
+        assert_eq!(stored.controls, interim.controls);
+        assert_eq!(stored.controls.len(), get_framework_controls(Framework::NistCsf2).len());
+        let listed: Vec<String> = repo.list_by_assessment("a1").unwrap().into_iter().map(|s| s.label).collect();
+        assert_eq!(listed, ["Interim report", "Final report"]);
+        assert!(repo.get("missing").unwrap().is_none());
+
+        let report = stored.compliance_report();
+        assert_eq!((report.compliant_controls, report.non_compliant_controls, report.assessed_controls), (1, 1, 2));
+        assert_eq!(report.last_updated, stored.taken_at.to_rfc3339());
+
+        let comparison = SnapshotComparison::between(&interim, &final_report).unwrap();
+        assert_eq!(comparison.comparison.compliance_delta, 50.0);
+        let changed: Vec<(&str, ComplianceStatus)> = comparison
+            .changed_controls
+            .iter()
+            .map(|c| (c.code.as_str(), c.baseline_status))
+            .collect();
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains(&("GV.RR-01", ComplianceStatus::NonCompliant)));
+        assert!(changed.contains(&("GV.RM-01", ComplianceStatus::NotAssessed)));
+    }
+}
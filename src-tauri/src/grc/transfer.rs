@@ -0,0 +1,817 @@
+//! Assessment Transfer
+//!
+//! Export an assessment as a self-contained JSON bundle and import it into
+//! another Optio instance, so consultants splitting an engagement across
+//! machines can merge their work. A bundle carries the client record, the
+//! assessment, its control assessments, evidence metadata, the client's
+//! category grouping for the framework and, optionally, the evidence files.
+//!
+//! Bundles are a single JSON file, with any evidence files embedded, or a zip
+//! holding `bundle.json` and the evidence files as plain entries. Records keep
+//! their IDs so re-importing a bundle updates what the last import created;
+//! an ID already used by another client's assessment or another assessment's
+//! evidence is replaced with one derived from it, which stays the same on
+//! every import.
+
+use crate::db::{Client, ClientRepository, Database};
+use crate::error::{OptioError, OptioResult};
+use crate::grc::models::*;
+use crate::grc::repository::{
+    AssessmentRepository, CategoryGroupingRepository, ControlAssessmentRepository, EvidenceRepository,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Identifies a file as an Optio assessment bundle
+pub const BUNDLE_FORMAT: &str = "optio-assessment";
+
+/// Current bundle version; older versions are accepted, newer ones rejected
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// The bundle inside a zip bundle
+const ARCHIVE_BUNDLE_FILE: &str = "bundle.json";
+/// Folder of a zip bundle holding evidence files, as `evidence/{evidence_id}/{file_name}`
+const ARCHIVE_EVIDENCE_DIR: &str = "evidence/";
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Portable snapshot of one assessment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssessmentBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub exported_by: String,
+    /// Optio version that wrote the bundle
+    #[serde(default)]
+    pub app_version: String,
+    pub client: Client,
+    pub assessment: Assessment,
+    pub control_assessments: Vec<ControlAssessment>,
+    pub evidence: Vec<Evidence>,
+    #[serde(default)]
+    pub category_grouping: Option<CategoryGrouping>,
+    /// Evidence files, present only when exported with files
+    #[serde(default)]
+    pub evidence_files: Vec<BundledEvidenceFile>,
+}
+
+/// An evidence file embedded in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledEvidenceFile {
+    pub evidence_id: String,
+    pub file_name: String,
+    /// Base64-encoded file content
+    pub data: String,
+}
+
+/// How to treat records that already exist on import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ConflictStrategy {
+    /// Keep the local record
+    SkipExisting,
+    /// Replace the local record with the bundle's
+    Overwrite,
+    /// Keep whichever record was assessed (or changed) most recently
+    MergeNewest,
+}
+
+/// What happened to one bundle record on import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferAction {
+    Created,
+    Updated,
+    Skipped,
+}
+
+/// Import outcome for one record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferRecord {
+    /// client, assessment, control_assessment, evidence or category_grouping
+    pub record_type: String,
+    pub id: String,
+    /// Human-readable label (name, title or control)
+    pub label: String,
+    pub action: TransferAction,
+    /// Why the record was skipped
+    pub reason: Option<String>,
+}
+
+/// A problem with one record that did not stop an export or import
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferWarning {
+    /// Same values as `TransferRecord::record_type`
+    pub record_type: String,
+    pub id: String,
+    pub label: String,
+    pub message: String,
+}
+
+/// Result of importing a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssessmentImportSummary {
+    pub assessment_id: String,
+    pub client_id: String,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub records: Vec<TransferRecord>,
+    /// Problems that did not stop the import
+    pub warnings: Vec<TransferWarning>,
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// Snapshot an assessment into a bundle
+///
+/// With `include_files`, local evidence files are embedded; files that
+/// cannot be read are reported as warnings and left out.
+pub fn export_bundle(
+    db: &Database,
+    assessment_id: &str,
+    include_files: bool,
+) -> OptioResult<(AssessmentBundle, Vec<TransferWarning>)> {
+    let assessment = AssessmentRepository::new(db)
+        .get(assessment_id)?
+        .ok_or_else(|| OptioError::Validation(format!("Assessment not found: {}", assessment_id)))?;
+    let client = ClientRepository::new(db)
+        .get(&assessment.client_id)?
+        .ok_or_else(|| OptioError::ClientNotFound(assessment.client_id.clone()))?;
+
+    let control_assessments = ControlAssessmentRepository::new(db).get_by_assessment(assessment_id)?;
+    let evidence = EvidenceRepository::new(db).get_by_assessment(assessment_id)?;
+    let category_grouping = CategoryGroupingRepository::new(db).get(&client.id, assessment.framework)?;
+
+    let mut warnings = Vec::new();
+    let mut evidence_files = Vec::new();
+    if include_files {
+        for item in &evidence {
+            let path = match &item.file_path {
+                Some(path) => Path::new(path),
+                None => continue,
+            };
+            match std::fs::read(path) {
+                Ok(bytes) => evidence_files.push(BundledEvidenceFile {
+                    evidence_id: item.id.clone(),
+                    file_name: path.file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| item.id.clone()),
+                    data: BASE64.encode(bytes),
+                }),
+                Err(e) => warnings.push(warning(
+                    "evidence",
+                    &item.id,
+                    &item.title,
+                    format!("Could not read {}: {}", path.display(), e),
+                )),
+            }
+        }
+    }
+
+    let bundle = AssessmentBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        exported_by: whoami::username(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        client,
+        assessment,
+        control_assessments,
+        evidence,
+        category_grouping,
+        evidence_files,
+    };
+
+    Ok((bundle, warnings))
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Parse and validate a bundle
+///
+/// Checks the format marker, that the version is not newer than this build
+/// understands, and that the assessment's framework is available here.
+pub fn parse_bundle(json: &str) -> OptioResult<AssessmentBundle> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| OptioError::Validation(format!("Bundle is not valid JSON: {}", e)))?;
+
+    if value["format"].as_str() != Some(BUNDLE_FORMAT) {
+        return Err(OptioError::Validation("Not an Optio assessment bundle".to_string()));
+    }
+    let version = value["version"].as_u64().unwrap_or(0);
+    if version == 0 || version > BUNDLE_VERSION as u64 {
+        return Err(OptioError::Validation(format!(
+            "Unsupported bundle version {} (this version of Optio reads up to {})",
+            version, BUNDLE_VERSION
+        )));
+    }
+
+    let framework = &value["assessment"]["framework"];
+    let available = serde_json::from_value::<Framework>(framework.clone())
+        .map(|f| !crate::grc::frameworks::get_framework_controls(f).is_empty())
+        .unwrap_or(false);
+    if !available {
+        return Err(OptioError::Validation(format!(
+            "Framework {} is not available in this version of Optio",
+            framework
+        )));
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| OptioError::Validation(format!("Invalid assessment bundle: {}", e)))
+}
+
+/// Read a bundle file, either JSON or a zip bundle
+///
+/// Evidence files in a zip are embedded in the returned bundle as if it had
+/// been exported as JSON.
+pub fn read_bundle(path: &Path) -> OptioResult<AssessmentBundle> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 4];
+    let is_zip = file.read(&mut magic)? == 4 && magic == *b"PK\x03\x04";
+    file.rewind()?;
+    if !is_zip {
+        let mut json = String::new();
+        file.read_to_string(&mut json)?;
+        return parse_bundle(&json);
+    }
+
+    let mut zip = ZipArchive::new(file).map_err(zip_error)?;
+    let mut json = String::new();
+    zip.by_name(ARCHIVE_BUNDLE_FILE)
+        .map_err(|_| OptioError::Validation(format!("Zip has no {}; not an Optio assessment bundle", ARCHIVE_BUNDLE_FILE)))?
+        .read_to_string(&mut json)?;
+    let mut bundle = parse_bundle(&json)?;
+
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(zip_error)?;
+        let (evidence_id, file_name) = match entry.name().strip_prefix(ARCHIVE_EVIDENCE_DIR).and_then(|rest| rest.split_once('/')) {
+            Some((id, name)) if !id.is_empty() && !name.is_empty() && !entry.is_dir() => (id.to_string(), name.to_string()),
+            _ => continue,
+        };
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        bundle.evidence_files.push(BundledEvidenceFile { evidence_id, file_name, data: BASE64.encode(bytes) });
+    }
+    Ok(bundle)
+}
+
+/// Write a bundle as a zip: `bundle.json` plus its evidence files as entries
+///
+/// The zip is written next to `dest` and moved into place once complete.
+pub fn write_bundle_archive(bundle: &AssessmentBundle, dest: &Path) -> OptioResult<()> {
+    let partial = dest.with_extension("zip.partial");
+    let result = write_archive(bundle, File::create(&partial)?).and_then(|file| {
+        file.sync_all()?;
+        std::fs::rename(&partial, dest)?;
+        Ok(())
+    });
+    if result.is_err() {
+        std::fs::remove_file(&partial).ok();
+    }
+    result
+}
+
+fn write_archive<W: Write + Seek>(bundle: &AssessmentBundle, writer: W) -> OptioResult<W> {
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+    let mut zip = ZipWriter::new(writer);
+
+    for file in &bundle.evidence_files {
+        let bytes = BASE64
+            .decode(&file.data)
+            .map_err(|e| OptioError::Validation(format!("invalid file data: {}", e)))?;
+        let path = format!("{}{}/{}", ARCHIVE_EVIDENCE_DIR, safe_id(&file.evidence_id), safe_file_name(&file.file_name));
+        zip.start_file(path, options).map_err(zip_error)?;
+        zip.write_all(&bytes)?;
+    }
+
+    // The files travel as entries, not inline
+    let manifest = AssessmentBundle { evidence_files: Vec::new(), ..bundle.clone() };
+    zip.start_file(ARCHIVE_BUNDLE_FILE, options).map_err(zip_error)?;
+    serde_json::to_writer_pretty(&mut zip, &manifest)?;
+    zip.finish().map_err(zip_error)
+}
+
+fn zip_error(e: zip::result::ZipError) -> OptioError {
+    match e {
+        zip::result::ZipError::Io(e) => OptioError::Io(e),
+        other => OptioError::Validation(format!("Bundle archive error: {}", other)),
+    }
+}
+
+/// Restore a bundle into this database
+///
+/// The client is created if missing and never overwritten. Embedded evidence
+/// files are written to `evidence_dir` and the evidence repointed at them.
+/// An assessment ID taken by another client's assessment, or an evidence ID
+/// taken by another assessment's evidence, is swapped for `remapped_id`.
+/// A completed or archived local assessment is left untouched, with its
+/// control assessments and evidence, until it is reopened.
+pub fn import_bundle(
+    db: &Database,
+    mut bundle: AssessmentBundle,
+    strategy: ConflictStrategy,
+    evidence_dir: &Path,
+) -> OptioResult<AssessmentImportSummary> {
+    let mut records = Vec::new();
+    let mut warnings = Vec::new();
+    let client_id = bundle.client.id.clone();
+
+    if bundle.assessment.client_id != client_id {
+        return Err(OptioError::Validation("Bundle assessment does not belong to its client".to_string()));
+    }
+
+    // Client
+    let clients = ClientRepository::new(db);
+    if clients.get(&client_id)?.is_none() {
+        clients.create(&bundle.client)?;
+        records.push(record("client", &client_id, &bundle.client.name, TransferAction::Created, None));
+    } else {
+        records.push(record("client", &client_id, &bundle.client.name, TransferAction::Skipped, Some("Client already exists")));
+    }
+
+    // Assessment
+    let assessments = AssessmentRepository::new(db);
+    let label = bundle.assessment.name.clone();
+    let mut local_assessment = assessments.get(&bundle.assessment.id)?;
+    if local_assessment.as_ref().is_some_and(|local| local.client_id != client_id) {
+        let remapped = remapped_id(&client_id, &bundle.assessment.id);
+        let original = std::mem::replace(&mut bundle.assessment.id, remapped);
+        warnings.push(warning(
+            "assessment",
+            &original,
+            &label,
+            format!("ID is used by another client's assessment here; imported as {}", bundle.assessment.id),
+        ));
+        // Control assessment IDs would collide with that assessment's too
+        for ca in &mut bundle.control_assessments {
+            ca.id = remapped_id(&bundle.assessment.id, &ca.id);
+        }
+        local_assessment = assessments.get(&bundle.assessment.id)?;
+    }
+    let assessment_id = bundle.assessment.id.clone();
+    let locked = local_assessment
+        .as_ref()
+        .filter(|local| local.client_id == client_id && local.status.is_locked())
+        .map(|local| format!("Assessment is {}; reopen it to import changes", local.status.display_name()));
+    if let (Some(reason), Some(local)) = (&locked, &local_assessment) {
+        warnings.push(warning("assessment", &assessment_id, &local.name, reason.clone()));
+    }
+    match local_assessment {
+        None => {
+            assessments.create(&bundle.assessment)?;
+            records.push(record("assessment", &assessment_id, &label, TransferAction::Created, None));
+        }
+        Some(local) if local.client_id != client_id => {
+            return Err(OptioError::Validation(format!(
+                "Assessment \"{}\" belongs to a different client in this database",
+                local.name
+            )));
+        }
+        Some(_) if locked.is_some() => {
+            records.push(record("assessment", &assessment_id, &label, TransferAction::Skipped, locked.as_deref()));
+        }
+        Some(local) => {
+            let replace = match strategy {
+                ConflictStrategy::SkipExisting => false,
+                ConflictStrategy::Overwrite => true,
+                ConflictStrategy::MergeNewest => bundle.assessment.completed_at > local.completed_at,
+            };
+            if replace {
+                assessments.update(&bundle.assessment)?;
+                records.push(record("assessment", &assessment_id, &label, TransferAction::Updated, None));
+            } else {
+                records.push(record("assessment", &assessment_id, &label, TransferAction::Skipped, Some(skip_reason(strategy))));
+            }
+        }
+    }
+
+    // Control assessments, keyed by control within the assessment
+    let control_assessments = ControlAssessmentRepository::new(db);
+    for mut ca in bundle.control_assessments {
+        if locked.is_some() {
+            records.push(record("control_assessment", &ca.id, &ca.control_id, TransferAction::Skipped, locked.as_deref()));
+            continue;
+        }
+        ca.assessment_id = assessment_id.clone();
+        let local = control_assessments.get_by_control(&assessment_id, &ca.control_id)?;
+        let action = resolve(strategy, local.map(|l| l.assessed_at), ca.assessed_at);
+        if action != TransferAction::Skipped {
+            control_assessments.upsert(&ca)?;
+        }
+        let reason = (action == TransferAction::Skipped).then(|| skip_reason(strategy));
+        records.push(record("control_assessment", &ca.id, &ca.control_id, action, reason));
+    }
+
+    // Evidence, keyed by ID
+    let evidence_repo = EvidenceRepository::new(db);
+    for mut item in bundle.evidence {
+        if locked.is_some() {
+            records.push(record("evidence", &item.id, &item.title, TransferAction::Skipped, locked.as_deref()));
+            continue;
+        }
+        let original_id = item.id.clone();
+        let mut local = evidence_repo.get(&item.id)?;
+        if local.as_ref().is_some_and(|l| l.assessment_id != assessment_id) {
+            item.id = remapped_id(&assessment_id, &item.id);
+            warnings.push(warning(
+                "evidence",
+                &original_id,
+                &item.title,
+                format!("ID is used by another assessment's evidence here; imported as {}", item.id),
+            ));
+            local = evidence_repo.get(&item.id)?;
+        }
+        if let Some(local) = local.as_ref().filter(|l| l.assessment_id != assessment_id) {
+            warnings.push(warning("evidence", &item.id, &item.title, format!("\"{}\" belongs to another assessment here", local.title)));
+            records.push(record("evidence", &item.id, &item.title, TransferAction::Skipped, Some("Belongs to another assessment")));
+            continue;
+        }
+
+        let action = resolve(strategy, local.map(|l| l.collected_at), item.collected_at);
+        if action == TransferAction::Skipped {
+            records.push(record("evidence", &item.id, &item.title, action, Some(skip_reason(strategy))));
+            continue;
+        }
+
+        match bundle.evidence_files.iter().find(|f| f.evidence_id == original_id) {
+            Some(file) => match write_evidence_file(evidence_dir, &item.id, file) {
+                Ok(path) => item.file_path = Some(path),
+                Err(e) => warnings.push(warning("evidence", &item.id, &item.title, format!("File not restored: {}", e))),
+            },
+            // A file path from the exporting machine points nowhere here
+            None if item.file_path.is_some() && !bundle.evidence_files.is_empty() => {
+                warnings.push(warning("evidence", &item.id, &item.title, "File missing from the bundle".to_string()));
+                item.file_path = None;
+            }
+            None => {}
+        }
+
+        item.assessment_id = assessment_id.clone();
+        if action == TransferAction::Updated {
+            evidence_repo.delete(&item.id)?;
+        }
+        evidence_repo.create(&item)?;
+        records.push(record("evidence", &item.id, &item.title, action, None));
+    }
+
+    // Category grouping for the client and framework
+    if let Some(mut grouping) = bundle.category_grouping {
+        grouping.client_id = client_id.clone();
+        let groupings = CategoryGroupingRepository::new(db);
+        let local = groupings.get(&client_id, grouping.framework)?;
+        let action = resolve(strategy, local.map(|l| l.updated_at), grouping.updated_at);
+        if action != TransferAction::Skipped {
+            groupings.upsert(&grouping)?;
+        }
+        let reason = (action == TransferAction::Skipped).then(|| skip_reason(strategy));
+        records.push(record("category_grouping", &grouping.id, grouping.framework.display_name(), action, reason));
+    }
+
+    let count = |action| records.iter().filter(|r| r.action == action).count();
+    Ok(AssessmentImportSummary {
+        assessment_id,
+        client_id,
+        created: count(TransferAction::Created),
+        updated: count(TransferAction::Updated),
+        skipped: count(TransferAction::Skipped),
+        records,
+        warnings,
+    })
+}
+
+/// Decide what to do with a bundle record given the local copy's timestamp
+fn resolve(strategy: ConflictStrategy, local: Option<DateTime<Utc>>, incoming: DateTime<Utc>) -> TransferAction {
+    match (local, strategy) {
+        (None, _) => TransferAction::Created,
+        (Some(_), ConflictStrategy::SkipExisting) => TransferAction::Skipped,
+        (Some(_), ConflictStrategy::Overwrite) => TransferAction::Updated,
+        (Some(local), ConflictStrategy::MergeNewest) if incoming > local => TransferAction::Updated,
+        (Some(_), ConflictStrategy::MergeNewest) => TransferAction::Skipped,
+    }
+}
+
+fn skip_reason(strategy: ConflictStrategy) -> &'static str {
+    match strategy {
+        ConflictStrategy::MergeNewest => "Local copy is as recent or newer",
+        _ => "Already exists",
+    }
+}
+
+/// Stable stand-in for an ID that collides with another record here
+///
+/// Hashing `scope` with the ID gives the same replacement on every import,
+/// so re-importing a bundle updates the remapped records instead of copying
+/// them again.
+pub fn remapped_id(scope: &str, id: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", scope, id).as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_random_bytes(bytes).into_uuid().to_string()
+}
+
+fn warning(record_type: &str, id: &str, label: &str, message: String) -> TransferWarning {
+    TransferWarning {
+        record_type: record_type.to_string(),
+        id: id.to_string(),
+        label: label.to_string(),
+        message,
+    }
+}
+
+fn record(record_type: &str, id: &str, label: &str, action: TransferAction, reason: Option<&str>) -> TransferRecord {
+    TransferRecord {
+        record_type: record_type.to_string(),
+        id: id.to_string(),
+        label: label.to_string(),
+        action,
+        reason: reason.map(str::to_string),
+    }
+}
+
+/// Write an embedded evidence file for evidence `evidence_id`, returning its new path
+fn write_evidence_file(dir: &Path, evidence_id: &str, file: &BundledEvidenceFile) -> OptioResult<String> {
+    let bytes = BASE64
+        .decode(&file.data)
+        .map_err(|e| OptioError::Validation(format!("invalid file data: {}", e)))?;
+
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}-{}", safe_id(evidence_id), safe_file_name(&file.file_name)));
+    std::fs::write(&path, bytes)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Bundle file names are untrusted; keep only a plain name
+fn safe_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    name.trim_start_matches('.').to_string()
+}
+
+fn safe_id(id: &str) -> String {
+    id.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grc::repository::init_grc_schema;
+    use chrono::Duration;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_grc_schema(&db).unwrap();
+        db
+    }
+
+    fn control_assessment(control_id: &str, status: ComplianceStatus, assessed_at: DateTime<Utc>) -> ControlAssessment {
+        ControlAssessment {
+            id: format!("ca-{}", control_id),
+            assessment_id: "assessment-1".to_string(),
+            control_id: control_id.to_string(),
+            status,
+            notes: None,
+            gap_description: None,
+            remediation: None,
+            remediation_target: None,
+            risk_rating: None,
+            maturity_level: None,
+            evidence_ids: vec![],
+            assessed_at,
+            assessed_by: "Jane Auditor".to_string(),
+        }
+    }
+
+    fn seeded_db() -> Database {
+        let db = test_db();
+        let mut client = Client::new("Acme".to_string(), None, None, None);
+        client.id = "client-1".to_string();
+        ClientRepository::new(&db).create(&client).unwrap();
+        AssessmentRepository::new(&db).create(&Assessment {
+            id: "assessment-1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "Acme SOC 2".to_string(),
+            description: None,
+            framework: Framework::Soc2TypeII,
+            scope: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            lead_assessor: "Jane Auditor".to_string(),
+            status: AssessmentStatus::InProgress,
+            source_assessment_id: None,
+        }).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_bundle_round_trip_and_conflicts() {
+        let source = seeded_db();
+        let earlier = Utc::now() - Duration::days(1);
+        let cas = ControlAssessmentRepository::new(&source);
+        cas.upsert(&control_assessment("CC6.1", ComplianceStatus::NonCompliant, earlier)).unwrap();
+        cas.upsert(&control_assessment("CC7.2", ComplianceStatus::Compliant, earlier)).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("optio-transfer-test-{}", uuid::Uuid::new_v4()));
+        let evidence_file = dir.join("mfa policy.pdf");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&evidence_file, b"%PDF-1.4").unwrap();
+        EvidenceRepository::new(&source).create(&Evidence {
+            id: "evidence-1".to_string(),
+            assessment_id: "assessment-1".to_string(),
+            control_ids: vec!["CC6.1".to_string()],
+            evidence_type: EvidenceType::Document,
+            title: "MFA policy".to_string(),
+            description: None,
+            file_path: Some(evidence_file.to_string_lossy().into_owned()),
+            url: None,
+            file_hash: None,
+            collected_at: earlier,
+            collected_by: "Jane Auditor".to_string(),
+            notes: None,
+            carried_forward: false,
+        }).unwrap();
+
+        let (bundle, warnings) = export_bundle(&source, "assessment-1", true).unwrap();
+        assert!(warnings.is_empty());
+        let bundle = parse_bundle(&serde_json::to_string(&bundle).unwrap()).unwrap();
+
+        // Fresh instance: everything is created, evidence file restored
+        let target = test_db();
+        let restored = dir.join("restored");
+        let summary = import_bundle(&target, bundle.clone(), ConflictStrategy::SkipExisting, &restored).unwrap();
+        assert_eq!((summary.created, summary.updated, summary.skipped), (5, 0, 0));
+        let evidence = EvidenceRepository::new(&target).get("evidence-1").unwrap().unwrap();
+        assert!(evidence.file_path.unwrap().starts_with(restored.to_str().unwrap()));
+
+        // The second consultant reassesses one control locally
+        let local_cas = ControlAssessmentRepository::new(&target);
+        local_cas.upsert(&control_assessment("CC7.2", ComplianceStatus::PartiallyCompliant, Utc::now())).unwrap();
+
+        let summary = import_bundle(&target, bundle.clone(), ConflictStrategy::MergeNewest, &restored).unwrap();
+        assert_eq!(summary.created, 0);
+        assert_eq!(local_cas.get_by_control("assessment-1", "CC7.2").unwrap().unwrap().status, ComplianceStatus::PartiallyCompliant);
+
+        let summary = import_bundle(&target, bundle, ConflictStrategy::Overwrite, &restored).unwrap();
+        assert_eq!(summary.skipped, 1); // the client is never overwritten
+        assert_eq!(local_cas.get_by_control("assessment-1", "CC7.2").unwrap().unwrap().status, ComplianceStatus::Compliant);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_leaves_locked_assessments_alone() {
+        let source = seeded_db();
+        let cas = ControlAssessmentRepository::new(&source);
+        cas.upsert(&control_assessment("CC6.1", ComplianceStatus::Compliant, Utc::now())).unwrap();
+        EvidenceRepository::new(&source).create(&evidence("evidence-1", "assessment-1", None)).unwrap();
+        let (mut bundle, _) = export_bundle(&source, "assessment-1", false).unwrap();
+        bundle.assessment.completed_at = Some(Utc::now());
+
+        let target = seeded_db();
+        let earlier = Utc::now() - Duration::days(1);
+        let local_cas = ControlAssessmentRepository::new(&target);
+        local_cas.upsert(&control_assessment("CC6.1", ComplianceStatus::NonCompliant, earlier)).unwrap();
+        let assessments = AssessmentRepository::new(&target);
+        let mut local = assessments.get("assessment-1").unwrap().unwrap();
+        local.status = AssessmentStatus::Completed;
+        local.completed_at = Some(earlier);
+        assessments.update(&local).unwrap();
+
+        let restored = std::env::temp_dir().join(format!("optio-transfer-locked-test-{}", uuid::Uuid::new_v4()));
+        for strategy in [ConflictStrategy::Overwrite, ConflictStrategy::MergeNewest] {
+            let summary = import_bundle(&target, bundle.clone(), strategy, &restored).unwrap();
+            assert_eq!((summary.created, summary.updated), (0, 0));
+            assert_eq!(summary.warnings.len(), 1);
+            assert!(summary.records.iter().skip(1).all(|r| r.reason.as_deref().is_some_and(|r| r.contains("reopen"))));
+        }
+        assert_eq!(assessments.get("assessment-1").unwrap().unwrap().completed_at, Some(earlier));
+        assert_eq!(local_cas.get_by_control("assessment-1", "CC6.1").unwrap().unwrap().status, ComplianceStatus::NonCompliant);
+        assert!(EvidenceRepository::new(&target).get("evidence-1").unwrap().is_none());
+
+        // Once reopened, the import goes through
+        local.status = AssessmentStatus::InProgress;
+        assessments.update(&local).unwrap();
+        let summary = import_bundle(&target, bundle, ConflictStrategy::Overwrite, &restored).unwrap();
+        assert_eq!((summary.created, summary.updated), (1, 2));
+        std::fs::remove_dir_all(&restored).ok();
+    }
+
+    fn evidence(id: &str, assessment_id: &str, file_path: Option<String>) -> Evidence {
+        Evidence {
+            id: id.to_string(),
+            assessment_id: assessment_id.to_string(),
+            control_ids: vec!["CC6.1".to_string()],
+            evidence_type: EvidenceType::Document,
+            title: format!("Evidence {}", id),
+            description: None,
+            file_path,
+            url: None,
+            file_hash: None,
+            collected_at: Utc::now() - Duration::days(1),
+            collected_by: "Jane Auditor".to_string(),
+            notes: None,
+            carried_forward: false,
+        }
+    }
+
+    #[test]
+    fn test_zip_bundle_with_id_collisions() {
+        let dir = std::env::temp_dir().join(format!("optio-transfer-zip-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let policy = dir.join("policy.pdf");
+        std::fs::write(&policy, b"%PDF-1.4").unwrap();
+
+        let source = seeded_db();
+        let evidence_repo = EvidenceRepository::new(&source);
+        evidence_repo.create(&evidence("evidence-1", "assessment-1", Some(policy.to_string_lossy().into_owned()))).unwrap();
+        let gone = dir.join("gone.png").to_string_lossy().into_owned();
+        evidence_repo.create(&evidence("evidence-2", "assessment-1", Some(gone))).unwrap();
+
+        // The unreadable file is reported and left out; the rest travels in the zip
+        let (bundle, warnings) = export_bundle(&source, "assessment-1", true).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].record_type.as_str(), warnings[0].id.as_str()), ("evidence", "evidence-2"));
+        let archive = dir.join("bundle.zip");
+        write_bundle_archive(&bundle, &archive).unwrap();
+        let read = read_bundle(&archive).unwrap();
+        assert_eq!(read.app_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(read.evidence_files.len(), 1);
+        assert_eq!(read.evidence_files[0].file_name, "policy.pdf");
+        assert_eq!(BASE64.decode(&read.evidence_files[0].data).unwrap(), b"%PDF-1.4");
+
+        // Here the assessment and evidence IDs already belong to another client
+        let target = test_db();
+        let mut other = Client::new("Globex".to_string(), None, None, None);
+        other.id = "client-2".to_string();
+        ClientRepository::new(&target).create(&other).unwrap();
+        let mut taken = AssessmentRepository::new(&seeded_db()).get("assessment-1").unwrap().unwrap();
+        taken.client_id = "client-2".to_string();
+        AssessmentRepository::new(&target).create(&taken).unwrap();
+        EvidenceRepository::new(&target).create(&evidence("evidence-1", "assessment-1", None)).unwrap();
+
+        let restored = dir.join("restored");
+        let summary = import_bundle(&target, read.clone(), ConflictStrategy::MergeNewest, &restored).unwrap();
+        let new_assessment = remapped_id("client-1", "assessment-1");
+        assert_eq!(summary.assessment_id, new_assessment);
+        assert_eq!(summary.created, 4);
+        assert!(summary.warnings.iter().any(|w| w.record_type == "assessment" && w.id == "assessment-1"));
+        let new_evidence = remapped_id(&new_assessment, "evidence-1");
+        let imported = EvidenceRepository::new(&target).get(&new_evidence).unwrap().unwrap();
+        assert!(imported.file_path.unwrap().starts_with(restored.to_str().unwrap()));
+        // The missing file is reported and its foreign path dropped
+        assert!(summary.warnings.iter().any(|w| w.id == "evidence-2" && w.message.contains("missing")));
+        assert_eq!(EvidenceRepository::new(&target).get("evidence-2").unwrap().unwrap().file_path, None);
+        // The other client's records are untouched
+        assert_eq!(AssessmentRepository::new(&target).get("assessment-1").unwrap().unwrap().client_id, "client-2");
+
+        // Importing again lands on the same remapped records
+        let again = import_bundle(&target, read, ConflictStrategy::MergeNewest, &restored).unwrap();
+        assert_eq!((again.assessment_id.as_str(), again.created), (new_assessment.as_str(), 0));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parse_bundle_validation() {
+        let (bundle, _) = export_bundle(&seeded_db(), "assessment-1", false).unwrap();
+        let mut value = serde_json::to_value(&bundle).unwrap();
+
+        value["version"] = serde_json::json!(BUNDLE_VERSION + 1);
+        assert!(parse_bundle(&value.to_string()).unwrap_err().to_string().contains("version"));
+
+        value["version"] = serde_json::json!(BUNDLE_VERSION);
+        value["assessment"]["framework"] = serde_json::json!("PCI_DSS");
+        assert!(parse_bundle(&value.to_string()).unwrap_err().to_string().contains("PCI_DSS"));
+
+        assert!(parse_bundle(r#"{"format":"something-else","version":1}"#).is_err());
+    }
+}
@@ -0,0 +1,381 @@
+//! Compliance Trends
+//!
+//! Point-in-time compliance reports are snapshotted per client and framework
+//! so progress can be shown over time ("62% in Q1, 81% now"). Snapshots are
+//! taken on request and whenever an assessment is completed; a second
+//! snapshot within the same hour replaces the first rather than adding a
+//! near-duplicate point.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::frameworks::{apply_maturity, build_compliance_report, get_framework_controls};
+use crate::grc::models::{Assessment, ComplianceStatus, ComplianceStatusReport, Control, ControlAssessment, Framework};
+use crate::grc::repository::{parse_datetime, parse_framework, AssessmentRepository, ControlAssessmentRepository};
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Initialize compliance trend schema
+pub fn init_compliance_trend_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Compliance report snapshots (full ComplianceStatusReport JSON), at most one per hour
+        CREATE TABLE IF NOT EXISTS compliance_snapshots (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            framework TEXT NOT NULL,
+            taken_hour TEXT NOT NULL,
+            taken_at TEXT NOT NULL,
+            report TEXT NOT NULL,
+            UNIQUE(client_id, framework, taken_hour),
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_compliance_snapshots_client ON compliance_snapshots(client_id, framework, taken_at);
+    "#)?;
+
+    tracing::info!("Compliance trend schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// A client's compliance report for one framework at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceSnapshot {
+    pub id: String,
+    pub client_id: String,
+    pub framework: Framework,
+    pub taken_at: DateTime<Utc>,
+    pub report: ComplianceStatusReport,
+}
+
+/// Completion and compliance at one snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceTrendPoint {
+    pub taken_at: DateTime<Utc>,
+    pub completion_percentage: f64,
+    pub compliance_percentage: f64,
+}
+
+/// Trend series for a single category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTrend {
+    pub code: String,
+    pub name: String,
+    pub color: String,
+    pub points: Vec<ComplianceTrendPoint>,
+}
+
+/// Compliance over time for a client and framework, oldest point first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceTrend {
+    pub client_id: String,
+    pub framework: Framework,
+    pub points: Vec<ComplianceTrendPoint>,
+    /// Per-category series, in the framework's category order
+    pub categories: Vec<CategoryTrend>,
+}
+
+impl ComplianceTrend {
+    /// Change in compliance percentage from the first point to the last
+    pub fn compliance_change(&self) -> Option<f64> {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) if self.points.len() > 1 => {
+                Some(((last.compliance_percentage - first.compliance_percentage) * 10.0).round() / 10.0)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Turn snapshots (oldest first) into overall and per-category series
+///
+/// Categories follow the latest snapshot's breakdown. Every report carries
+/// all of its framework's categories, so each series has a point per snapshot.
+pub fn build_compliance_trend(client_id: &str, framework: Framework, snapshots: &[ComplianceSnapshot]) -> ComplianceTrend {
+    let points = snapshots
+        .iter()
+        .map(|s| ComplianceTrendPoint {
+            taken_at: s.taken_at,
+            completion_percentage: s.report.completion_percentage,
+            compliance_percentage: s.report.compliance_percentage,
+        })
+        .collect();
+
+    let categories = snapshots
+        .last()
+        .map(|latest| {
+            latest
+                .report
+                .category_breakdown
+                .iter()
+                .map(|cat| CategoryTrend {
+                    code: cat.code.clone(),
+                    name: cat.name.clone(),
+                    color: cat.color.clone(),
+                    points: snapshots
+                        .iter()
+                        .filter_map(|s| {
+                            s.report.category_breakdown.iter().find(|c| c.code == cat.code).map(|c| ComplianceTrendPoint {
+                                taken_at: s.taken_at,
+                                completion_percentage: c.completion_percentage,
+                                compliance_percentage: c.compliance_percentage,
+                            })
+                        })
+                        .collect(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ComplianceTrend {
+        client_id: client_id.to_string(),
+        framework,
+        points,
+        categories,
+    }
+}
+
+/// Compliance status of a framework across assessments, for one client or all
+///
+/// The latest result recorded for each control counts. No category groups
+/// are rolled up; callers add them when grouping is requested.
+pub fn current_compliance_status(
+    db: &Database,
+    framework: Framework,
+    client_id: Option<&str>,
+) -> OptioResult<ComplianceStatusReport> {
+    let controls = get_framework_controls(framework);
+
+    let assessment_repo = AssessmentRepository::new(db);
+    let control_repo = ControlAssessmentRepository::new(db);
+
+    let assessments = match client_id {
+        Some(cid) => assessment_repo.list_by_client(cid)?,
+        None => assessment_repo.list_all()?,
+    };
+
+    let latest = latest_control_assessments(
+        &control_repo,
+        assessments.iter().filter(|a| a.framework == framework),
+    )?;
+
+    let mut report = build_compliance_report(framework, &controls, |control| {
+        control_result(&latest, control)
+            .map(|ca| ca.status)
+            .unwrap_or(ComplianceStatus::NotAssessed)
+    });
+    apply_maturity(&mut report, &controls, |control| {
+        control_result(&latest, control).and_then(|ca| ca.rated_maturity())
+    });
+    Ok(report)
+}
+
+/// The latest result recorded for each control across assessments, by control ID
+pub fn latest_control_assessments<'a>(
+    control_repo: &ControlAssessmentRepository,
+    assessments: impl IntoIterator<Item = &'a Assessment>,
+) -> OptioResult<HashMap<String, ControlAssessment>> {
+    let mut latest: HashMap<String, ControlAssessment> = HashMap::new();
+    for assessment in assessments {
+        for ca in control_repo.get_by_assessment(&assessment.id)? {
+            let newer = latest
+                .get(&ca.control_id)
+                .map_or(true, |existing| ca.assessed_at >= existing.assessed_at);
+            if newer {
+                latest.insert(ca.control_id.clone(), ca);
+            }
+        }
+    }
+    Ok(latest)
+}
+
+/// A control's latest result, recorded against either its ID or its code
+pub fn control_result<'a>(
+    latest: &'a HashMap<String, ControlAssessment>,
+    control: &Control,
+) -> Option<&'a ControlAssessment> {
+    latest.get(&control.id).or_else(|| latest.get(&control.code))
+}
+
+/// Snapshot a client's current compliance status for a framework
+pub fn snapshot_compliance(db: &Database, client_id: &str, framework: Framework) -> OptioResult<ComplianceSnapshot> {
+    let report = current_compliance_status(db, framework, Some(client_id))?;
+    ComplianceSnapshotRepository::new(db).record(client_id, &report, Utc::now())
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Repository for compliance snapshots
+pub struct ComplianceSnapshotRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ComplianceSnapshotRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Store a snapshot taken at `taken_at`, replacing any from the same hour
+    pub fn record(
+        &self,
+        client_id: &str,
+        report: &ComplianceStatusReport,
+        taken_at: DateTime<Utc>,
+    ) -> OptioResult<ComplianceSnapshot> {
+        let framework = format!("{:?}", report.framework);
+        let taken_hour = taken_at.format("%Y-%m-%dT%H").to_string();
+
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO compliance_snapshots (id, client_id, framework, taken_hour, taken_at, report)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(client_id, framework, taken_hour) DO UPDATE SET
+                taken_at = excluded.taken_at,
+                report = excluded.report",
+            params![
+                Uuid::new_v4().to_string(),
+                client_id,
+                framework,
+                taken_hour,
+                taken_at.to_rfc3339(),
+                serde_json::to_string(report)?,
+            ],
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM compliance_snapshots WHERE client_id = ?1 AND framework = ?2 AND taken_hour = ?3",
+            SNAPSHOT_COLUMNS
+        ))?;
+        let mut rows = stmt.query(params![client_id, framework, taken_hour])?;
+        match rows.next()? {
+            Some(row) => parse_snapshot_row(row),
+            None => Err(OptioError::Database("Compliance snapshot was not stored".to_string())),
+        }
+    }
+
+    /// Snapshots for a client and framework, oldest first, optionally from `since` on
+    pub fn list(
+        &self,
+        client_id: &str,
+        framework: Framework,
+        since: Option<DateTime<Utc>>,
+    ) -> OptioResult<Vec<ComplianceSnapshot>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM compliance_snapshots WHERE client_id = ?1 AND framework = ?2 ORDER BY taken_at",
+            SNAPSHOT_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query(params![client_id, format!("{:?}", framework)])?;
+        let mut snapshots = Vec::new();
+        while let Some(row) = rows.next()? {
+            let snapshot = parse_snapshot_row(row)?;
+            // Timestamps are compared parsed; stored offsets may differ
+            if since.map_or(true, |since| snapshot.taken_at >= since) {
+                snapshots.push(snapshot);
+            }
+        }
+        Ok(snapshots)
+    }
+}
+
+const SNAPSHOT_COLUMNS: &str = "id, client_id, framework, taken_at, report";
+
+fn parse_snapshot_row(row: &rusqlite::Row) -> OptioResult<ComplianceSnapshot> {
+    let report: String = row.get(4)?;
+
+    Ok(ComplianceSnapshot {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        framework: parse_framework(&row.get::<_, String>(2)?)?,
+        taken_at: parse_datetime(&row.get::<_, String>(3)?)?,
+        report: serde_json::from_str(&report)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grc::repository::init_grc_schema;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_grc_schema(&db).unwrap();
+        init_compliance_trend_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    /// NIST report with the first `compliant` controls compliant and the rest unassessed
+    fn report(compliant: usize) -> ComplianceStatusReport {
+        let controls = get_framework_controls(Framework::NistCsf2);
+        let ids: Vec<&str> = controls.iter().take(compliant).map(|c| c.id.as_str()).collect();
+        build_compliance_report(Framework::NistCsf2, &controls, |c| {
+            if ids.contains(&c.id.as_str()) { ComplianceStatus::Compliant } else { ComplianceStatus::NotAssessed }
+        })
+    }
+
+    #[test]
+    fn test_snapshots_dedupe_within_hour() {
+        let db = test_db();
+        let repo = ComplianceSnapshotRepository::new(&db);
+
+        let q1 = parse_datetime("2026-03-31T09:05:00Z").unwrap();
+        repo.record("client-1", &report(5), q1).unwrap();
+        // Same hour: replaces the earlier snapshot
+        let replaced = repo.record("client-1", &report(10), parse_datetime("2026-03-31T09:55:00Z").unwrap()).unwrap();
+        repo.record("client-1", &report(20), parse_datetime("2026-06-30T14:00:00Z").unwrap()).unwrap();
+
+        let all = repo.list("client-1", Framework::NistCsf2, None).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, replaced.id);
+        assert_eq!(all[0].report.compliant_controls, 10);
+        assert!(all[0].taken_at < all[1].taken_at);
+
+        let recent = repo.list("client-1", Framework::NistCsf2, Some(parse_datetime("2026-04-01T00:00:00Z").unwrap())).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert!(repo.list("client-1", Framework::Gdpr, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_build_compliance_trend() {
+        let db = test_db();
+        let repo = ComplianceSnapshotRepository::new(&db);
+        repo.record("client-1", &report(5), parse_datetime("2026-03-31T09:00:00Z").unwrap()).unwrap();
+        repo.record("client-1", &report(20), parse_datetime("2026-06-30T09:00:00Z").unwrap()).unwrap();
+
+        let snapshots = repo.list("client-1", Framework::NistCsf2, None).unwrap();
+        let trend = build_compliance_trend("client-1", Framework::NistCsf2, &snapshots);
+
+        assert_eq!(trend.points.len(), 2);
+        assert!(trend.points[0].completion_percentage < trend.points[1].completion_percentage);
+        assert_eq!(trend.categories.len(), snapshots[1].report.category_breakdown.len());
+        assert!(trend.categories.iter().all(|c| c.points.len() == 2));
+        // All assessed controls are compliant, so compliance stays flat
+        assert_eq!(trend.compliance_change(), Some(0.0));
+
+        let empty = build_compliance_trend("client-1", Framework::NistCsf2, &[]);
+        assert!(empty.points.is_empty() && empty.categories.is_empty());
+        assert_eq!(empty.compliance_change(), None);
+    }
+}
@@ -2,8 +2,18 @@
 //!
 //! Interactive checklist for cloud migration readiness based on
 //! industry best practices and the 15-step cloud migration guide.
+//!
+//! Answers are persisted per client and scored with weights: items by their
+//! priority, categories by [`CATEGORY_WEIGHTS`], so a missing disaster
+//! recovery plan costs far more than a missing communication plan.
 
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::repository::parse_datetime;
 use crate::infrastructure::models::*;
+use crate::infrastructure::pricing::{parse_pricing_provider, provider_key};
+use chrono::Utc;
+use rusqlite::params;
 use uuid::Uuid;
 
 /// Get all cloud readiness checklist items
@@ -11,7 +21,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
     vec![
         // Business Alignment
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "BA-01".to_string(),
             category: ReadinessCategory::BusinessAlignment,
             title: "Executive Sponsorship".to_string(),
             description: "Secure executive sponsorship and establish governance structure for cloud migration".to_string(),
@@ -20,7 +30,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 1,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "BA-02".to_string(),
             category: ReadinessCategory::BusinessAlignment,
             title: "Business Case Development".to_string(),
             description: "Develop comprehensive business case with ROI analysis and TCO comparison".to_string(),
@@ -29,7 +39,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 2,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "BA-03".to_string(),
             category: ReadinessCategory::BusinessAlignment,
             title: "Migration Goals Definition".to_string(),
             description: "Define clear, measurable migration goals and success criteria".to_string(),
@@ -38,7 +48,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 3,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "BA-04".to_string(),
             category: ReadinessCategory::BusinessAlignment,
             title: "Stakeholder Alignment".to_string(),
             description: "Align all stakeholders on migration timeline, expectations, and responsibilities".to_string(),
@@ -49,7 +59,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
 
         // Technical Readiness
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "TR-01".to_string(),
             category: ReadinessCategory::TechnicalReadiness,
             title: "Application Portfolio Discovery".to_string(),
             description: "Complete inventory of all applications, dependencies, and integrations".to_string(),
@@ -58,7 +68,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 1,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "TR-02".to_string(),
             category: ReadinessCategory::TechnicalReadiness,
             title: "Application Assessment (6 Rs)".to_string(),
             description: "Assess each application using the 6 Rs framework: Rehost, Replatform, Repurchase, Refactor, Retire, Retain".to_string(),
@@ -67,7 +77,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 2,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "TR-03".to_string(),
             category: ReadinessCategory::TechnicalReadiness,
             title: "Infrastructure Assessment".to_string(),
             description: "Document current infrastructure: compute, storage, network, databases".to_string(),
@@ -76,7 +86,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 3,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "TR-04".to_string(),
             category: ReadinessCategory::TechnicalReadiness,
             title: "Dependency Mapping".to_string(),
             description: "Map all application and infrastructure dependencies".to_string(),
@@ -85,7 +95,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 4,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "TR-05".to_string(),
             category: ReadinessCategory::TechnicalReadiness,
             title: "Network Architecture Review".to_string(),
             description: "Review and document current network architecture and connectivity requirements".to_string(),
@@ -94,7 +104,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 5,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "TR-06".to_string(),
             category: ReadinessCategory::TechnicalReadiness,
             title: "Cloud Provider Selection".to_string(),
             description: "Evaluate and select appropriate cloud provider(s) based on requirements".to_string(),
@@ -103,7 +113,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 6,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "TR-07".to_string(),
             category: ReadinessCategory::TechnicalReadiness,
             title: "Landing Zone Design".to_string(),
             description: "Design cloud landing zone with account structure, networking, and security foundations".to_string(),
@@ -114,7 +124,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
 
         // Security & Compliance
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "SC-01".to_string(),
             category: ReadinessCategory::SecurityCompliance,
             title: "Compliance Requirements".to_string(),
             description: "Identify all regulatory and compliance requirements (HIPAA, PCI-DSS, SOC 2, GDPR)".to_string(),
@@ -123,7 +133,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 1,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "SC-02".to_string(),
             category: ReadinessCategory::SecurityCompliance,
             title: "Security Architecture".to_string(),
             description: "Design cloud security architecture aligned with zero-trust principles".to_string(),
@@ -132,7 +142,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 2,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "SC-03".to_string(),
             category: ReadinessCategory::SecurityCompliance,
             title: "Identity & Access Management".to_string(),
             description: "Plan IAM strategy including SSO, MFA, and privileged access management".to_string(),
@@ -141,7 +151,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 3,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "SC-04".to_string(),
             category: ReadinessCategory::SecurityCompliance,
             title: "Data Classification".to_string(),
             description: "Classify data by sensitivity and define handling requirements".to_string(),
@@ -150,7 +160,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 4,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "SC-05".to_string(),
             category: ReadinessCategory::SecurityCompliance,
             title: "Encryption Strategy".to_string(),
             description: "Define encryption requirements for data at rest and in transit".to_string(),
@@ -159,7 +169,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 5,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "SC-06".to_string(),
             category: ReadinessCategory::SecurityCompliance,
             title: "Security Monitoring Plan".to_string(),
             description: "Plan cloud security monitoring, SIEM integration, and incident response".to_string(),
@@ -170,7 +180,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
 
         // Operational Readiness
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "OR-01".to_string(),
             category: ReadinessCategory::OperationalReadiness,
             title: "Operations Model".to_string(),
             description: "Define cloud operations model: CloudOps, DevOps, SRE".to_string(),
@@ -179,7 +189,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 1,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "OR-02".to_string(),
             category: ReadinessCategory::OperationalReadiness,
             title: "Monitoring & Observability".to_string(),
             description: "Plan monitoring, logging, and observability strategy".to_string(),
@@ -188,7 +198,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 2,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "OR-03".to_string(),
             category: ReadinessCategory::OperationalReadiness,
             title: "Backup & Disaster Recovery".to_string(),
             description: "Design backup and disaster recovery strategy for cloud".to_string(),
@@ -197,7 +207,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 3,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "OR-04".to_string(),
             category: ReadinessCategory::OperationalReadiness,
             title: "Change Management".to_string(),
             description: "Establish change management process for cloud environment".to_string(),
@@ -206,7 +216,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 4,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "OR-05".to_string(),
             category: ReadinessCategory::OperationalReadiness,
             title: "Automation Strategy".to_string(),
             description: "Define Infrastructure as Code (IaC) and automation approach".to_string(),
@@ -217,7 +227,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
 
         // Financial Planning
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "FP-01".to_string(),
             category: ReadinessCategory::FinancialPlanning,
             title: "Cost Baseline".to_string(),
             description: "Establish current infrastructure cost baseline".to_string(),
@@ -226,7 +236,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 1,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "FP-02".to_string(),
             category: ReadinessCategory::FinancialPlanning,
             title: "Cloud Cost Estimation".to_string(),
             description: "Estimate cloud costs using provider pricing calculators".to_string(),
@@ -235,7 +245,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 2,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "FP-03".to_string(),
             category: ReadinessCategory::FinancialPlanning,
             title: "FinOps Practices".to_string(),
             description: "Establish FinOps practices for cloud cost optimization".to_string(),
@@ -244,7 +254,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 3,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "FP-04".to_string(),
             category: ReadinessCategory::FinancialPlanning,
             title: "Migration Budget".to_string(),
             description: "Define migration project budget including tools, training, and consulting".to_string(),
@@ -255,7 +265,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
 
         // People & Process
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "PP-01".to_string(),
             category: ReadinessCategory::PeopleProcess,
             title: "Skills Assessment".to_string(),
             description: "Assess current team cloud skills and identify gaps".to_string(),
@@ -264,7 +274,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 1,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "PP-02".to_string(),
             category: ReadinessCategory::PeopleProcess,
             title: "Training Plan".to_string(),
             description: "Develop cloud training and certification plan".to_string(),
@@ -273,7 +283,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 2,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "PP-03".to_string(),
             category: ReadinessCategory::PeopleProcess,
             title: "Migration Team".to_string(),
             description: "Establish dedicated migration team with clear roles".to_string(),
@@ -282,7 +292,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 3,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "PP-04".to_string(),
             category: ReadinessCategory::PeopleProcess,
             title: "Organizational Change".to_string(),
             description: "Plan organizational change management for cloud adoption".to_string(),
@@ -293,7 +303,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
 
         // Data Management
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "DM-01".to_string(),
             category: ReadinessCategory::DataManagement,
             title: "Data Inventory".to_string(),
             description: "Complete inventory of all data stores and volumes".to_string(),
@@ -302,7 +312,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 1,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "DM-02".to_string(),
             category: ReadinessCategory::DataManagement,
             title: "Data Migration Strategy".to_string(),
             description: "Define data migration approach: online, offline, or hybrid".to_string(),
@@ -311,7 +321,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 2,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "DM-03".to_string(),
             category: ReadinessCategory::DataManagement,
             title: "Data Residency".to_string(),
             description: "Identify data residency requirements and select appropriate regions".to_string(),
@@ -320,7 +330,7 @@ pub fn get_readiness_checklist() -> Vec<ReadinessCheckItem> {
             order: 3,
         },
         ReadinessCheckItem {
-            id: Uuid::new_v4().to_string(),
+            id: "DM-04".to_string(),
             category: ReadinessCategory::DataManagement,
             title: "Data Validation".to_string(),
             description: "Plan data validation and integrity verification post-migration".to_string(),
@@ -355,9 +365,271 @@ pub fn get_checklist_by_category() -> Vec<(ReadinessCategory, Vec<ReadinessCheck
     result
 }
 
+// ============================================================================
+// Scoring
+// ============================================================================
+
+/// Relative weight of each category in the overall readiness score
+///
+/// Security and technical readiness block a migration outright; the rest
+/// slow it down.
+pub const CATEGORY_WEIGHTS: &[(ReadinessCategory, f64)] = &[
+    (ReadinessCategory::BusinessAlignment, 1.0),
+    (ReadinessCategory::TechnicalReadiness, 1.5),
+    (ReadinessCategory::SecurityCompliance, 1.5),
+    (ReadinessCategory::OperationalReadiness, 1.25),
+    (ReadinessCategory::FinancialPlanning, 0.75),
+    (ReadinessCategory::PeopleProcess, 0.75),
+    (ReadinessCategory::DataManagement, 1.25),
+];
+
+pub fn category_weight(category: ReadinessCategory) -> f64 {
+    CATEGORY_WEIGHTS
+        .iter()
+        .find(|(c, _)| *c == category)
+        .map(|(_, w)| *w)
+        .unwrap_or(1.0)
+}
+
+/// Score an assessment's answers against the checklist
+///
+/// Each category scores the priority-weighted credit of its items, with
+/// unanswered items counting as "No" and N/A items left out. The overall
+/// score is the category-weighted mean over categories with applicable items.
+pub fn score_readiness(assessment: &CloudReadinessAssessment) -> ReadinessScore {
+    let checklist = get_readiness_checklist();
+    let answer_for = |item_id: &str| {
+        assessment.answers.iter().find(|a| a.item_id == item_id).map(|a| a.answer)
+    };
+
+    let mut category_scores = Vec::new();
+    let (mut weighted_sum, mut weight_total) = (0.0, 0.0);
+
+    for category in ReadinessCategory::all() {
+        let items: Vec<&ReadinessCheckItem> = checklist.iter().filter(|i| i.category == category).collect();
+        let (mut earned, mut possible, mut applicable, mut answered) = (0.0, 0.0, 0, 0);
+
+        for item in &items {
+            let answer = answer_for(&item.id);
+            if answer.is_some() {
+                answered += 1;
+            }
+            if let Some(credit) = answer.unwrap_or(ReadinessAnswer::No).score() {
+                earned += credit * f64::from(item.priority);
+                possible += f64::from(item.priority);
+                applicable += 1;
+            }
+        }
+
+        let weight = category_weight(category);
+        let percentage = if possible > 0.0 { earned / possible * 100.0 } else { 0.0 };
+        if applicable > 0 {
+            weighted_sum += percentage * weight;
+            weight_total += weight;
+        }
+
+        category_scores.push(CategoryReadinessScore {
+            category,
+            display_name: category.display_name().to_string(),
+            color: category.color().to_string(),
+            total: applicable,
+            completed: answered,
+            percentage,
+            weight,
+        });
+    }
+
+    let overall_score = if weight_total > 0.0 { weighted_sum / weight_total } else { 0.0 };
+
+    ReadinessScore {
+        assessment_id: assessment.id.clone(),
+        overall_score,
+        tier: ReadinessTier::from_score(overall_score),
+        category_scores,
+        total_items: checklist.len(),
+        answered_items: checklist.iter().filter(|i| answer_for(&i.id).is_some()).count(),
+    }
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Initialize cloud readiness schema
+pub fn init_cloud_readiness_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- One readiness assessment per client
+        CREATE TABLE IF NOT EXISTS cloud_readiness_assessments (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL UNIQUE,
+            name TEXT NOT NULL,
+            target_provider TEXT NOT NULL,
+            target_date TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Answers by checklist item id
+        CREATE TABLE IF NOT EXISTS cloud_readiness_answers (
+            assessment_id TEXT NOT NULL,
+            item_id TEXT NOT NULL,
+            answer TEXT NOT NULL,
+            notes TEXT,
+            answered_at TEXT NOT NULL,
+            PRIMARY KEY (assessment_id, item_id),
+            FOREIGN KEY (assessment_id) REFERENCES cloud_readiness_assessments(id) ON DELETE CASCADE
+        );
+    "#)?;
+
+    tracing::info!("Cloud readiness schema initialized");
+    Ok(())
+}
+
+/// Cloud readiness assessment repository
+pub struct CloudReadinessRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> CloudReadinessRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        CloudReadinessRepository { db }
+    }
+
+    /// The client's assessment with its answers
+    pub fn get_by_client(&self, client_id: &str) -> OptioResult<Option<CloudReadinessAssessment>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, client_id, name, target_provider, target_date, created_at, updated_at
+             FROM cloud_readiness_assessments WHERE client_id = ?1",
+        )?;
+        let mut rows = stmt.query(params![client_id])?;
+        let row = match rows.next()? {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let provider: String = row.get(3)?;
+        let target_date: Option<String> = row.get(4)?;
+        let mut assessment = CloudReadinessAssessment {
+            id: row.get(0)?,
+            client_id: row.get(1)?,
+            name: row.get(2)?,
+            target_provider: parse_pricing_provider(&provider).map_err(OptioError::Database)?,
+            target_date: target_date.as_deref().map(parse_datetime).transpose()?,
+            answers: Vec::new(),
+            created_at: parse_datetime(&row.get::<_, String>(5)?)?,
+            updated_at: parse_datetime(&row.get::<_, String>(6)?)?,
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT item_id, answer, notes, answered_at FROM cloud_readiness_answers
+             WHERE assessment_id = ?1 ORDER BY item_id",
+        )?;
+        assessment.answers = stmt
+            .query_map(params![assessment.id], |row| Ok(parse_answer_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(Some(assessment))
+    }
+
+    /// Insert or update an assessment and its answers
+    ///
+    /// Answers are upserted by item id; answers already stored for items not
+    /// in `assessment.answers` are kept.
+    pub fn save(&self, assessment: &CloudReadinessAssessment) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            r#"INSERT INTO cloud_readiness_assessments
+               (id, client_id, name, target_provider, target_date, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+               ON CONFLICT(id) DO UPDATE SET name = excluded.name, target_provider = excluded.target_provider,
+                   target_date = excluded.target_date, updated_at = excluded.updated_at"#,
+            params![
+                assessment.id,
+                assessment.client_id,
+                assessment.name,
+                provider_key(&assessment.target_provider)?,
+                assessment.target_date.map(|d| d.to_rfc3339()),
+                assessment.created_at.to_rfc3339(),
+                assessment.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        for answer in &assessment.answers {
+            tx.execute(
+                "INSERT OR REPLACE INTO cloud_readiness_answers (assessment_id, item_id, answer, notes, answered_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    assessment.id,
+                    answer.item_id,
+                    format!("{:?}", answer.answer),
+                    answer.notes,
+                    answer.answered_at.to_rfc3339(),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn parse_answer_row(row: &rusqlite::Row) -> OptioResult<ReadinessItemAnswer> {
+    let answer: String = row.get(1)?;
+
+    Ok(ReadinessItemAnswer {
+        item_id: row.get(0)?,
+        answer: parse_answer(&answer)?,
+        notes: row.get(2)?,
+        answered_at: parse_datetime(&row.get::<_, String>(3)?)?,
+    })
+}
+
+fn parse_answer(s: &str) -> OptioResult<ReadinessAnswer> {
+    match s {
+        "Yes" => Ok(ReadinessAnswer::Yes),
+        "Partial" => Ok(ReadinessAnswer::Partial),
+        "No" => Ok(ReadinessAnswer::No),
+        "NotApplicable" => Ok(ReadinessAnswer::NotApplicable),
+        _ => Err(OptioError::Database(format!("Unknown readiness answer: {}", s))),
+    }
+}
+
+/// A new, unanswered assessment for a client
+pub fn new_readiness_assessment(client_id: &str, name: &str, target_provider: CloudProvider) -> CloudReadinessAssessment {
+    let now = Utc::now();
+    CloudReadinessAssessment {
+        id: Uuid::new_v4().to_string(),
+        client_id: client_id.to_string(),
+        name: name.to_string(),
+        target_provider,
+        target_date: None,
+        answers: Vec::new(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn answer(item_id: &str, answer: ReadinessAnswer) -> ReadinessItemAnswer {
+        ReadinessItemAnswer {
+            item_id: item_id.to_string(),
+            answer,
+            notes: None,
+            answered_at: Utc::now(),
+        }
+    }
 
     #[test]
     fn test_checklist_not_empty() {
@@ -374,4 +646,90 @@ mod tests {
             assert!(count > 0, "Category {:?} has no items", category);
         }
     }
+
+    #[test]
+    fn test_item_ids_stable_and_unique() {
+        let first: Vec<String> = get_readiness_checklist().into_iter().map(|i| i.id).collect();
+        let second: Vec<String> = get_readiness_checklist().into_iter().map(|i| i.id).collect();
+        assert_eq!(first, second);
+
+        let mut unique = first.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), first.len());
+    }
+
+    #[test]
+    fn test_weighted_scoring() {
+        let checklist = get_readiness_checklist();
+        let mut assessment = new_readiness_assessment("c1", "Migration", CloudProvider::Aws);
+
+        let empty = score_readiness(&assessment);
+        assert_eq!(empty.overall_score, 0.0);
+        assert_eq!(empty.tier, ReadinessTier::NotReady);
+        assert_eq!(empty.answered_items, 0);
+
+        assessment.answers = checklist.iter().map(|i| answer(&i.id, ReadinessAnswer::Yes)).collect();
+        let full = score_readiness(&assessment);
+        assert!((full.overall_score - 100.0).abs() < 1e-9);
+        assert_eq!(full.tier, ReadinessTier::Optimized);
+
+        // A "No" on a priority 5 item costs more than on a priority 3 item
+        let low = checklist.iter().find(|i| i.priority == 3).unwrap();
+        let high = checklist.iter().find(|i| i.priority == 5 && i.category == low.category).unwrap();
+        let score_without = |item_id: &str| {
+            let mut a = assessment.clone();
+            a.answers.iter_mut().find(|x| x.item_id == item_id).unwrap().answer = ReadinessAnswer::No;
+            score_readiness(&a).overall_score
+        };
+        assert!(score_without(&high.id) < score_without(&low.id));
+
+        // N/A items drop out instead of counting against the category
+        let security: Vec<&ReadinessCheckItem> = checklist
+            .iter()
+            .filter(|i| i.category == ReadinessCategory::SecurityCompliance)
+            .collect();
+        for a in assessment.answers.iter_mut() {
+            if a.item_id == security[0].id {
+                a.answer = ReadinessAnswer::NotApplicable;
+            } else if security.iter().any(|i| i.id == a.item_id) {
+                a.answer = ReadinessAnswer::Partial;
+            }
+        }
+        let partial = score_readiness(&assessment);
+        let category = partial
+            .category_scores
+            .iter()
+            .find(|c| c.category == ReadinessCategory::SecurityCompliance)
+            .unwrap();
+        assert!((category.percentage - 50.0).abs() < 1e-9);
+        assert_eq!(category.total, security.len() - 1);
+        assert_eq!(category.weight, category_weight(ReadinessCategory::SecurityCompliance));
+        assert_eq!(partial.tier, ReadinessTier::Optimized);
+    }
+
+    #[test]
+    fn test_readiness_repository() {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        init_cloud_readiness_schema(&db).unwrap();
+        let repo = CloudReadinessRepository::new(&db);
+        assert!(repo.get_by_client("c1").unwrap().is_none());
+
+        let mut assessment = new_readiness_assessment("c1", "Migration", CloudProvider::Azure);
+        assessment.answers = vec![answer("BA-01", ReadinessAnswer::Yes), answer("SC-01", ReadinessAnswer::No)];
+        repo.save(&assessment).unwrap();
+
+        assessment.answers = vec![answer("SC-01", ReadinessAnswer::Partial)];
+        assessment.target_provider = CloudProvider::Gcp;
+        repo.save(&assessment).unwrap();
+
+        let stored = repo.get_by_client("c1").unwrap().unwrap();
+        assert_eq!(stored.id, assessment.id);
+        assert_eq!(stored.target_provider, CloudProvider::Gcp);
+        assert_eq!(stored.answers.len(), 2);
+        assert_eq!(stored.answers[1].item_id, "SC-01");
+        assert_eq!(stored.answers[1].answer, ReadinessAnswer::Partial);
+    }
 }
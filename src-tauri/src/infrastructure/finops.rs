@@ -4,6 +4,12 @@
 //! Provides TCO calculations, cost comparisons, and optimization recommendations.
 
 use super::models::*;
+use super::pricing::PricingProfile;
+use super::utilization::{ServerUtilization, SIZING_HEADROOM};
+use serde::{Deserialize, Serialize};
+
+/// Providers compared side by side, in display order
+const COMPARED_PROVIDERS: [CloudProvider; 3] = [CloudProvider::AWS, CloudProvider::Azure, CloudProvider::GCP];
 
 /// Default pricing data for major cloud providers (per hour, simplified)
 pub struct CloudPricing {
@@ -13,6 +19,128 @@ pub struct CloudPricing {
     pub egress_per_gb: f64,
     pub managed_db_multiplier: f64,
     pub k8s_cluster_base: f64,
+    /// Compute discount for a 1-year reservation / committed use
+    pub reserved_1yr_discount: f64,
+    /// Compute discount for a 3-year reservation / committed use
+    pub reserved_3yr_discount: f64,
+    /// Discount for a flexible compute commitment (Savings Plan / flexible CUD)
+    pub savings_plan_discount: f64,
+    /// Compute discounts by commitment term and payment option
+    pub compute_commitment: CommitmentDiscounts,
+    /// Managed database discounts by commitment term and payment option
+    pub database_commitment: CommitmentDiscounts,
+}
+
+/// Discount off on-demand rates for each commitment term
+#[derive(Debug, Clone, Copy)]
+pub struct CommitmentDiscounts {
+    pub one_year_no_upfront: f64,
+    pub one_year_all_upfront: f64,
+    pub three_year: f64,
+}
+
+impl CommitmentDiscounts {
+    pub fn for_term(&self, term: &CommitmentTerm) -> f64 {
+        match term {
+            CommitmentTerm::OnDemand => 0.0,
+            CommitmentTerm::OneYearNoUpfront => self.one_year_no_upfront,
+            CommitmentTerm::OneYearAllUpfront => self.one_year_all_upfront,
+            CommitmentTerm::ThreeYear => self.three_year,
+        }
+    }
+}
+
+/// Commitment a FinOps analysis prices compute and databases under
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CommitmentTerm {
+    #[default]
+    OnDemand,
+    #[serde(rename = "ONE_YEAR_NO_UPFRONT")]
+    OneYearNoUpfront,
+    #[serde(rename = "ONE_YEAR_ALL_UPFRONT")]
+    OneYearAllUpfront,
+    #[serde(rename = "THREE_YEAR")]
+    ThreeYear,
+}
+
+impl CommitmentTerm {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CommitmentTerm::OnDemand => "On-Demand",
+            CommitmentTerm::OneYearNoUpfront => "1-Year, No Upfront",
+            CommitmentTerm::OneYearAllUpfront => "1-Year, All Upfront",
+            CommitmentTerm::ThreeYear => "3-Year",
+        }
+    }
+
+    pub fn term_months(&self) -> u32 {
+        match self {
+            CommitmentTerm::OnDemand => 0,
+            CommitmentTerm::OneYearNoUpfront | CommitmentTerm::OneYearAllUpfront => 12,
+            CommitmentTerm::ThreeYear => 36,
+        }
+    }
+
+    /// Whether the whole term is paid when the commitment is bought
+    pub fn is_upfront(&self) -> bool {
+        matches!(self, CommitmentTerm::OneYearAllUpfront)
+    }
+}
+
+/// How a resource type can be committed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CommitmentClass {
+    Compute,
+    Database,
+    /// Storage, network, serverless and the rest stay on-demand
+    OnDemandOnly,
+}
+
+impl CommitmentClass {
+    pub fn for_resource(resource_type: &ResourceType) -> Self {
+        match resource_type {
+            ResourceType::VirtualMachine | ResourceType::Container | ResourceType::Kubernetes => CommitmentClass::Compute,
+            ResourceType::Database => CommitmentClass::Database,
+            _ => CommitmentClass::OnDemandOnly,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CommitmentClass::Compute => "Compute",
+            CommitmentClass::Database => "Managed Databases",
+            CommitmentClass::OnDemandOnly => "Storage, Network & Other",
+        }
+    }
+}
+
+/// On-demand vs committed monthly cost for one resource class
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitmentClassCost {
+    pub class: CommitmentClass,
+    pub on_demand_monthly: f64,
+    pub committed_monthly: f64,
+    pub discount_percentage: f64,
+}
+
+/// What a commitment saves and what it locks the client into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitmentAnalysis {
+    pub term: CommitmentTerm,
+    pub by_class: Vec<CommitmentClassCost>,
+    pub on_demand_monthly: f64,
+    pub committed_monthly: f64,
+    /// Paid when the commitment is bought; zero for monthly payment
+    pub upfront_payment: f64,
+    /// Utilization of committed capacity below which on-demand would be cheaper
+    pub break_even_utilization_percentage: f64,
+    /// Commitment still owed if the committed workload is shut down halfway
+    /// through the term
+    pub downsize_exposure: f64,
 }
 
 impl CloudPricing {
@@ -26,6 +154,19 @@ impl CloudPricing {
                 egress_per_gb: 0.09,            // First 10TB
                 managed_db_multiplier: 1.5,     // RDS premium
                 k8s_cluster_base: 0.10,         // EKS per hour
+                reserved_1yr_discount: 0.36,    // Standard RI, no upfront
+                reserved_3yr_discount: 0.57,
+                savings_plan_discount: 0.28,    // Compute Savings Plan, 1 year
+                compute_commitment: CommitmentDiscounts {
+                    one_year_no_upfront: 0.36,
+                    one_year_all_upfront: 0.40,
+                    three_year: 0.57,
+                },
+                database_commitment: CommitmentDiscounts { // RDS reserved instances
+                    one_year_no_upfront: 0.31,
+                    one_year_all_upfront: 0.35,
+                    three_year: 0.52,
+                },
             },
             CloudProvider::Azure => Self {
                 compute_per_vcpu: 0.04,
@@ -34,6 +175,20 @@ impl CloudPricing {
                 egress_per_gb: 0.087,
                 managed_db_multiplier: 1.45,
                 k8s_cluster_base: 0.10,         // AKS control plane
+                reserved_1yr_discount: 0.35,
+                reserved_3yr_discount: 0.55,
+                savings_plan_discount: 0.30,    // Azure savings plan for compute
+                // Azure reservations cost the same paid monthly or upfront
+                compute_commitment: CommitmentDiscounts {
+                    one_year_no_upfront: 0.35,
+                    one_year_all_upfront: 0.35,
+                    three_year: 0.55,
+                },
+                database_commitment: CommitmentDiscounts { // Azure SQL reserved capacity
+                    one_year_no_upfront: 0.33,
+                    one_year_all_upfront: 0.33,
+                    three_year: 0.55,
+                },
             },
             CloudProvider::GCP => Self {
                 compute_per_vcpu: 0.038,
@@ -42,17 +197,90 @@ impl CloudPricing {
                 egress_per_gb: 0.085,
                 managed_db_multiplier: 1.4,
                 k8s_cluster_base: 0.10,         // GKE autopilot base
+                reserved_1yr_discount: 0.37,    // Resource-based CUD
+                reserved_3yr_discount: 0.55,
+                savings_plan_discount: 0.28,    // Flexible CUD
+                // Committed use discounts are billed monthly; prepaying earns nothing extra
+                compute_commitment: CommitmentDiscounts {
+                    one_year_no_upfront: 0.37,
+                    one_year_all_upfront: 0.37,
+                    three_year: 0.55,
+                },
+                database_commitment: CommitmentDiscounts { // Cloud SQL CUDs
+                    one_year_no_upfront: 0.25,
+                    one_year_all_upfront: 0.25,
+                    three_year: 0.52,
+                },
             },
         }
     }
+
+    /// Provider pricing with a profile's rates laid over the defaults
+    ///
+    /// Each rate the profile sets wins; unset rates keep the built-in value.
+    /// Commitment discounts always come from the defaults.
+    pub fn resolve(provider: &CloudProvider, profile: Option<&PricingProfile>) -> Self {
+        let mut pricing = Self::for_provider(provider);
+        if let Some(profile) = profile {
+            let overrides = [
+                (&mut pricing.compute_per_vcpu, profile.compute_per_vcpu),
+                (&mut pricing.memory_per_gb, profile.memory_per_gb),
+                (&mut pricing.storage_per_gb_month, profile.storage_per_gb_month),
+                (&mut pricing.egress_per_gb, profile.egress_per_gb),
+                (&mut pricing.managed_db_multiplier, profile.managed_db_multiplier),
+                (&mut pricing.k8s_cluster_base, profile.k8s_cluster_base),
+            ];
+            for (rate, value) in overrides {
+                if let Some(value) = value {
+                    *rate = value;
+                }
+            }
+        }
+        pricing
+    }
+
+    /// Discount a commitment term earns on a resource type
+    ///
+    /// Compute and managed databases have their own discount curves; other
+    /// resource types are always on-demand.
+    pub fn term_discount(&self, term: &CommitmentTerm, resource_type: &ResourceType) -> f64 {
+        match CommitmentClass::for_resource(resource_type) {
+            CommitmentClass::Compute => self.compute_commitment.for_term(term),
+            CommitmentClass::Database => self.database_commitment.for_term(term),
+            CommitmentClass::OnDemandOnly => 0.0,
+        }
+    }
+
+    /// Discount a commitment earns on a resource type
+    ///
+    /// Reservations cover instance-backed compute; savings plans also cover
+    /// serverless. Storage, network and load balancers are always on-demand.
+    pub fn commitment_discount(&self, commitment: &CommitmentStrategy, resource_type: &ResourceType) -> f64 {
+        let reservable = matches!(
+            resource_type,
+            ResourceType::VirtualMachine | ResourceType::Container | ResourceType::Database | ResourceType::Kubernetes
+        );
+        match commitment {
+            CommitmentStrategy::OnDemand => 0.0,
+            CommitmentStrategy::Reserved1Yr if reservable => self.reserved_1yr_discount,
+            CommitmentStrategy::Reserved3Yr if reservable => self.reserved_3yr_discount,
+            CommitmentStrategy::SavingsPlan if reservable || matches!(resource_type, ResourceType::Serverless) => {
+                self.savings_plan_discount
+            }
+            _ => 0.0,
+        }
+    }
 }
 
 /// Calculate monthly cost for a resource
+///
+/// Rates come from `profile` where it sets them, else the provider defaults.
 pub fn calculate_resource_cost(
     resource: &ResourceCostEstimate,
     provider: &CloudProvider,
+    profile: Option<&PricingProfile>,
 ) -> f64 {
-    let pricing = CloudPricing::for_provider(provider);
+    let pricing = CloudPricing::resolve(provider, profile);
     let hours_per_month = 730.0;
 
     match resource.resource_type {
@@ -144,35 +372,45 @@ pub fn calculate_resource_cost(
 }
 
 /// Generate a complete FinOps analysis
+///
+/// Compute and databases are priced under `commitment`; each resource in the
+/// breakdown carries its committed cost. On-demand analyses have no
+/// commitment section. When `utilization` has measured servers, they replace
+/// the virtual machine resources, sized from their p95 load.
 pub fn generate_finops_analysis(
     current_costs: &OnPremiseCosts,
     resources: &[ResourceCostEstimate],
     provider: &CloudProvider,
     migration_strategy: &MigrationStrategy,
+    commitment: &CommitmentTerm,
+    utilization: &[ServerUtilization],
+    profile: Option<&PricingProfile>,
 ) -> FinOpsAnalysis {
+    let pricing = CloudPricing::resolve(provider, profile);
+    let sized = right_sized_resources(resources, utilization);
+
     // Calculate cloud costs
     let mut resource_estimates = Vec::new();
+    let mut on_demand_costs = Vec::new();
     let mut total_monthly_cloud = 0.0;
 
-    for resource in resources {
-        let monthly_cost = calculate_resource_cost(resource, provider);
+    for resource in &sized {
+        let on_demand = calculate_resource_cost(resource, provider, profile);
+        let monthly_cost = on_demand * (1.0 - pricing.term_discount(commitment, &resource.resource_type));
         let mut estimate = resource.clone();
         estimate.monthly_cost = monthly_cost;
         total_monthly_cloud += monthly_cost;
+        on_demand_costs.push(on_demand);
         resource_estimates.push(estimate);
     }
 
-    // Apply strategy-based adjustments
-    let strategy_multiplier = match migration_strategy {
-        MigrationStrategy::Rehost => 1.0,           // Lift and shift, no optimization
-        MigrationStrategy::Replatform => 0.85,      // Some optimization
-        MigrationStrategy::Refactor => 0.70,        // Cloud-native optimization
-        MigrationStrategy::Repurchase => 0.90,      // SaaS replacement
-        MigrationStrategy::Retire => 0.0,           // Removing
-        MigrationStrategy::Retain => 1.0,           // Keeping as-is
+    let commitment_analysis = match commitment {
+        CommitmentTerm::OnDemand => None,
+        _ => Some(analyze_commitment(commitment, &resource_estimates, &on_demand_costs)),
     };
 
-    let optimized_monthly = total_monthly_cloud * strategy_multiplier;
+    // Apply strategy-based adjustments
+    let optimized_monthly = total_monthly_cloud * strategy_multiplier(migration_strategy);
 
     // Calculate annual costs
     let annual_cloud_cost = optimized_monthly * 12.0;
@@ -207,6 +445,8 @@ pub fn generate_finops_analysis(
         &resource_estimates,
         provider,
         migration_strategy,
+        commitment,
+        utilization,
     );
 
     FinOpsAnalysis {
@@ -222,15 +462,436 @@ pub fn generate_finops_analysis(
         roi_months: payback_months,
         resource_breakdown: resource_estimates,
         recommendations,
-        assumptions: vec![
-            "Pricing based on on-demand rates; reserved instances can reduce costs by 30-60%".to_string(),
-            "Network egress estimated at 30% of provisioned bandwidth".to_string(),
-            "Managed services include high availability configuration".to_string(),
-            "Migration costs include planning, execution, and 3-month parallel run".to_string(),
-        ],
+        assumptions: finops_assumptions(provider, commitment, utilization, profile),
+        commitment_analysis,
+    }
+}
+
+/// Resources with virtual machines replaced by the measured servers
+///
+/// Servers are grouped by their right-sized shape, one estimate per shape.
+/// Without utilization the resources are returned as they are.
+fn right_sized_resources(resources: &[ResourceCostEstimate], utilization: &[ServerUtilization]) -> Vec<ResourceCostEstimate> {
+    if utilization.is_empty() {
+        return resources.to_vec();
+    }
+
+    let mut shapes: Vec<((u32, f64), u32)> = Vec::new();
+    for server in utilization {
+        let shape = server.right_size();
+        match shapes.iter_mut().find(|(s, _)| *s == shape) {
+            Some((_, count)) => *count += 1,
+            None => shapes.push((shape, 1)),
+        }
+    }
+    shapes.sort_by(|a, b| a.0.0.cmp(&b.0.0).then(a.0.1.total_cmp(&b.0.1)));
+
+    resources
+        .iter()
+        .filter(|r| !matches!(r.resource_type, ResourceType::VirtualMachine))
+        .cloned()
+        .chain(shapes.into_iter().map(|((vcpus, memory_gb), count)| {
+            let mut estimate = vm_estimate(format!("Right-sized VMs ({} vCPU / {} GB)", vcpus, memory_gb), vcpus, memory_gb);
+            estimate.quantity = count;
+            estimate.notes = Some(format!(
+                "Sized from p95 utilization plus {:.0}% headroom",
+                SIZING_HEADROOM * 100.0
+            ));
+            estimate
+        }))
+        .collect()
+}
+
+/// A single virtual machine of the given shape
+fn vm_estimate(name: String, vcpus: u32, memory_gb: f64) -> ResourceCostEstimate {
+    ResourceCostEstimate {
+        resource_type: ResourceType::VirtualMachine,
+        name,
+        quantity: 1,
+        specs: ResourceSpecs {
+            vcpus: Some(vcpus),
+            memory_gb: Some(memory_gb),
+            storage_gb: None,
+            bandwidth_gbps: None,
+            iops: None,
+        },
+        monthly_cost: 0.0,
+        notes: None,
     }
 }
 
+/// Committed vs on-demand cost per resource class
+///
+/// `on_demand_costs` holds the on-demand cost of each resource in `committed`.
+fn analyze_commitment(
+    term: &CommitmentTerm,
+    committed: &[ResourceCostEstimate],
+    on_demand_costs: &[f64],
+) -> CommitmentAnalysis {
+    let by_class: Vec<CommitmentClassCost> = [CommitmentClass::Compute, CommitmentClass::Database, CommitmentClass::OnDemandOnly]
+        .into_iter()
+        .filter_map(|class| {
+            let (on_demand, committed): (f64, f64) = committed
+                .iter()
+                .zip(on_demand_costs)
+                .filter(|(r, _)| CommitmentClass::for_resource(&r.resource_type) == class)
+                .fold((0.0, 0.0), |(od, c), (r, on_demand)| (od + on_demand, c + r.monthly_cost));
+            if on_demand <= 0.0 {
+                return None;
+            }
+            Some(CommitmentClassCost {
+                class,
+                on_demand_monthly: round_currency(on_demand),
+                committed_monthly: round_currency(committed),
+                discount_percentage: round_currency((1.0 - committed / on_demand) * 100.0),
+            })
+        })
+        .collect();
+
+    // Only the committed classes are paid whether used or not
+    let (locked_on_demand, locked_committed) = by_class
+        .iter()
+        .filter(|c| c.class != CommitmentClass::OnDemandOnly)
+        .fold((0.0, 0.0), |(od, c), class| (od + class.on_demand_monthly, c + class.committed_monthly));
+    let term_months = term.term_months() as f64;
+
+    CommitmentAnalysis {
+        term: *term,
+        on_demand_monthly: round_currency(by_class.iter().map(|c| c.on_demand_monthly).sum()),
+        committed_monthly: round_currency(by_class.iter().map(|c| c.committed_monthly).sum()),
+        upfront_payment: if term.is_upfront() { round_currency(locked_committed * term_months) } else { 0.0 },
+        break_even_utilization_percentage: if locked_on_demand > 0.0 {
+            round_currency(locked_committed / locked_on_demand * 100.0)
+        } else {
+            0.0
+        },
+        downsize_exposure: round_currency(locked_committed * term_months / 2.0),
+        by_class,
+    }
+}
+
+/// Assumptions behind an analysis, starting with where its rates came from
+fn finops_assumptions(
+    provider: &CloudProvider,
+    commitment: &CommitmentTerm,
+    utilization: &[ServerUtilization],
+    profile: Option<&PricingProfile>,
+) -> Vec<String> {
+    let mut assumptions = match profile {
+        Some(profile) => {
+            let mut source = vec![format!(
+                "Rates from pricing profile \"{}\" ({}), last updated {}",
+                profile.name,
+                profile.region,
+                profile.updated_at.format("%Y-%m-%d")
+            )];
+            let unset = profile.unset_rates();
+            if !unset.is_empty() {
+                source.push(format!(
+                    "Rates not set in the profile use built-in {} list prices: {}",
+                    provider_label(provider),
+                    unset.join(", ")
+                ));
+            }
+            source
+        }
+        None => vec![format!("Rates from built-in {} list prices (simplified baseline)", provider_label(provider))],
+    };
+
+    assumptions.push(match commitment {
+        CommitmentTerm::OnDemand => {
+            "Pricing based on on-demand rates; reserved instances can reduce costs by 30-60%".to_string()
+        }
+        _ => format!(
+            "Compute and managed databases priced under a {} commitment; other resources on-demand",
+            commitment.display_name()
+        ),
+    });
+    if !utilization.is_empty() {
+        assumptions.push(format!(
+            "Virtual machines sized from the p95 utilization of {} measured servers plus {:.0}% headroom",
+            utilization.len(),
+            SIZING_HEADROOM * 100.0
+        ));
+    }
+    assumptions.extend([
+        "Network egress estimated at 30% of provisioned bandwidth".to_string(),
+        "Managed services include high availability configuration".to_string(),
+        "Migration costs include planning, execution, and 3-month parallel run".to_string(),
+    ]);
+    assumptions
+}
+
+/// Generate a multi-year TCO projection
+///
+/// Each resource's cloud cost grows at its type's annual rate (compounded per
+/// year) and the on-premises run rate grows with the workload as a whole.
+/// `horizon_years` is clamped to 1-5.
+pub fn generate_tco_projection(
+    current_costs: &OnPremiseCosts,
+    resources: &[ResourceCostEstimate],
+    provider: &CloudProvider,
+    migration_strategy: &MigrationStrategy,
+    commitment: &CommitmentStrategy,
+    horizon_years: u32,
+    growth_rates: &[ResourceGrowthRate],
+) -> TcoProjection {
+    let horizon_years = horizon_years.clamp(1, 5);
+    let pricing = CloudPricing::for_provider(provider);
+    let multiplier = strategy_multiplier(migration_strategy);
+    let migration_cost = estimate_migration_cost(resources, migration_strategy);
+    let current_annual = current_costs.annual_total();
+
+    let growth_for = |resource_type: &ResourceType| {
+        growth_rates
+            .iter()
+            .find(|g| g.resource_type == *resource_type)
+            .map(|g| g.annual_rate_percent / 100.0)
+            .unwrap_or(0.0)
+    };
+
+    // Base monthly cost per resource, before growth
+    let base: Vec<(f64, f64, f64)> = resources
+        .iter()
+        .map(|r| {
+            let monthly = calculate_resource_cost(r, provider, None);
+            let discount = pricing.commitment_discount(commitment, &r.resource_type);
+            (monthly, monthly * (1.0 - discount) * multiplier, growth_for(&r.resource_type))
+        })
+        .collect();
+    let base_monthly: f64 = base.iter().map(|(monthly, _, _)| monthly).sum();
+
+    let mut years = Vec::new();
+    let mut cumulative = 0.0;
+    let mut break_even_month = None;
+
+    for year in 1..=horizon_years {
+        let exponent = (year - 1) as i32;
+        let grown_monthly: f64 = base.iter().map(|(monthly, _, rate)| monthly * (1.0 + rate).powi(exponent)).sum();
+        let cloud_monthly: f64 = base.iter().map(|(_, committed, rate)| committed * (1.0 + rate).powi(exponent)).sum();
+
+        // On-prem capacity has to grow with the same workload
+        let workload_growth = if base_monthly > 0.0 { grown_monthly / base_monthly } else { 1.0 };
+        let on_prem_monthly = current_annual / 12.0 * workload_growth;
+        let year_migration = if year == 1 { migration_cost } else { 0.0 };
+
+        for month in 1..=12 {
+            cumulative += on_prem_monthly - cloud_monthly;
+            if month == 1 {
+                cumulative -= year_migration;
+            }
+            if break_even_month.is_none() && cumulative >= 0.0 && on_prem_monthly > cloud_monthly {
+                break_even_month = Some((year - 1) * 12 + month);
+            }
+        }
+
+        let on_prem_cost = on_prem_monthly * 12.0;
+        let cloud_cost = cloud_monthly * 12.0;
+        years.push(TcoYear {
+            year,
+            on_prem_cost: round_currency(on_prem_cost),
+            cloud_cost: round_currency(cloud_cost),
+            migration_cost: round_currency(year_migration),
+            net_savings: round_currency(on_prem_cost - cloud_cost - year_migration),
+            cumulative_savings: round_currency(cumulative),
+        });
+    }
+
+    let mut assumptions = vec![
+        format!("{} pricing for {} compute", commitment.display_name(), provider_label(provider)),
+        "Growth compounds annually; on-premises costs grow with total workload".to_string(),
+        "Storage, network, and load balancer costs are billed on-demand".to_string(),
+        "Migration costs are incurred in the first month".to_string(),
+    ];
+    if matches!(commitment, CommitmentStrategy::Reserved3Yr) && horizon_years < 3 {
+        assumptions.push("3-year commitment extends beyond the projection horizon".to_string());
+    }
+
+    TcoProjection {
+        id: uuid::Uuid::new_v4().to_string(),
+        client_id: String::new(), // Set by caller
+        provider: *provider,
+        migration_strategy: *migration_strategy,
+        commitment: *commitment,
+        horizon_years,
+        total_on_prem_cost: round_currency(years.iter().map(|y| y.on_prem_cost).sum()),
+        total_cloud_cost: round_currency(years.iter().map(|y| y.cloud_cost).sum()),
+        cumulative_savings: round_currency(cumulative),
+        years,
+        break_even_month,
+        assumptions,
+    }
+}
+
+/// Pricing choices for a provider comparison
+#[derive(Debug, Clone, Copy)]
+pub struct ComparisonStrategies<'a> {
+    /// Commitment applied to every provider's rates
+    pub commitment: CommitmentStrategy,
+    /// Current costs and migration strategy; when set, a full FinOps analysis
+    /// is run for each provider as well
+    pub analysis: Option<(&'a OnPremiseCosts, MigrationStrategy)>,
+}
+
+impl Default for ComparisonStrategies<'_> {
+    fn default() -> Self {
+        Self { commitment: CommitmentStrategy::OnDemand, analysis: None }
+    }
+}
+
+/// One resource's monthly cost on each provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceProviderCosts {
+    pub name: String,
+    pub resource_type: ResourceType,
+    pub aws: f64,
+    pub azure: f64,
+    pub gcp: f64,
+}
+
+impl ResourceProviderCosts {
+    pub fn cost_for(&self, provider: &CloudProvider) -> f64 {
+        match provider {
+            CloudProvider::AWS => self.aws,
+            CloudProvider::Azure => self.azure,
+            CloudProvider::GCP => self.gcp,
+        }
+    }
+}
+
+/// A provider's total cost across all compared resources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCostTotal {
+    pub provider: CloudProvider,
+    pub monthly_cost: f64,
+    pub annual_cost: f64,
+    /// Monthly cost above the cheapest provider; zero for the cheapest
+    pub delta_vs_cheapest: f64,
+}
+
+/// Side-by-side cost of a set of resources on each provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderComparison {
+    pub id: String,
+    pub client_id: String,
+    pub commitment: CommitmentStrategy,
+    pub per_resource: Vec<ResourceProviderCosts>,
+    pub totals_by_provider: Vec<ProviderCostTotal>,
+    pub cheapest_provider: CloudProvider,
+    /// One analysis per provider, when current costs were given
+    pub analyses: Vec<FinOpsAnalysis>,
+}
+
+impl ProviderComparison {
+    pub fn total_for(&self, provider: &CloudProvider) -> Option<&ProviderCostTotal> {
+        self.totals_by_provider.iter().find(|t| t.provider == *provider)
+    }
+}
+
+/// Compare the monthly cost of each resource across AWS, Azure and GCP
+///
+/// Costs use each provider's built-in rates with the commitment discount
+/// applied. Ties for cheapest go to the provider listed first.
+pub fn compare_resource_costs(
+    resources: &[ResourceCostEstimate],
+    strategies: &ComparisonStrategies,
+) -> ProviderComparison {
+    let commitment = &strategies.commitment;
+    let pricing: Vec<CloudPricing> = COMPARED_PROVIDERS.iter().map(CloudPricing::for_provider).collect();
+
+    let per_resource: Vec<ResourceProviderCosts> = resources
+        .iter()
+        .map(|r| {
+            let costs: Vec<f64> = COMPARED_PROVIDERS
+                .iter()
+                .zip(&pricing)
+                .map(|(provider, pricing)| {
+                    let discount = pricing.commitment_discount(commitment, &r.resource_type);
+                    round_currency(calculate_resource_cost(r, provider, None) * (1.0 - discount))
+                })
+                .collect();
+            ResourceProviderCosts {
+                name: r.name.clone(),
+                resource_type: r.resource_type.clone(),
+                aws: costs[0],
+                azure: costs[1],
+                gcp: costs[2],
+            }
+        })
+        .collect();
+
+    let monthly: Vec<(CloudProvider, f64)> = COMPARED_PROVIDERS
+        .iter()
+        .map(|provider| (*provider, per_resource.iter().map(|r| r.cost_for(provider)).sum::<f64>()))
+        .collect();
+    let (cheapest_provider, cheapest_monthly) = monthly
+        .iter()
+        .fold(monthly[0], |best, candidate| if candidate.1 < best.1 { *candidate } else { best });
+
+    let totals_by_provider = monthly
+        .iter()
+        .map(|(provider, monthly_cost)| ProviderCostTotal {
+            provider: *provider,
+            monthly_cost: round_currency(*monthly_cost),
+            annual_cost: round_currency(monthly_cost * 12.0),
+            delta_vs_cheapest: round_currency(monthly_cost - cheapest_monthly),
+        })
+        .collect();
+
+    let analyses = match strategies.analysis {
+        Some((current_costs, migration_strategy)) => COMPARED_PROVIDERS
+            .iter()
+            .map(|provider| generate_finops_analysis(
+                current_costs,
+                resources,
+                provider,
+                &migration_strategy,
+                &CommitmentTerm::OnDemand,
+                &[],
+                None,
+            ))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    ProviderComparison {
+        id: uuid::Uuid::new_v4().to_string(),
+        client_id: String::new(), // Set by caller
+        commitment: *commitment,
+        per_resource,
+        totals_by_provider,
+        cheapest_provider,
+        analyses,
+    }
+}
+
+/// Cost multiplier for the optimization a migration strategy buys
+fn strategy_multiplier(strategy: &MigrationStrategy) -> f64 {
+    match strategy {
+        MigrationStrategy::Rehost => 1.0,           // Lift and shift, no optimization
+        MigrationStrategy::Replatform => 0.85,      // Some optimization
+        MigrationStrategy::Refactor => 0.70,        // Cloud-native optimization
+        MigrationStrategy::Repurchase => 0.90,      // SaaS replacement
+        MigrationStrategy::Retire => 0.0,           // Removing
+        MigrationStrategy::Retain => 1.0,           // Keeping as-is
+    }
+}
+
+fn provider_label(provider: &CloudProvider) -> &'static str {
+    match provider {
+        CloudProvider::AWS => "AWS",
+        CloudProvider::Azure => "Azure",
+        CloudProvider::GCP => "GCP",
+    }
+}
+
+fn round_currency(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
 /// Estimate migration project costs
 fn estimate_migration_cost(
     resources: &[ResourceCostEstimate],
@@ -265,12 +926,23 @@ fn generate_cost_recommendations(
     resources: &[ResourceCostEstimate],
     provider: &CloudProvider,
     strategy: &MigrationStrategy,
+    commitment: &CommitmentTerm,
+    utilization: &[ServerUtilization],
 ) -> Vec<CostRecommendation> {
     let mut recommendations = Vec::new();
 
-    // Reserved instance recommendation
+    // Reserved instance recommendation, unless already committed
     let total_monthly: f64 = resources.iter().map(|r| r.monthly_cost).sum();
-    if total_monthly > 1000.0 {
+    let pricing = CloudPricing::for_provider(provider);
+    let one_year = CommitmentTerm::OneYearNoUpfront;
+    let committable_monthly: f64 = resources.iter()
+        .filter(|r| CommitmentClass::for_resource(&r.resource_type) != CommitmentClass::OnDemandOnly)
+        .map(|r| r.monthly_cost)
+        .sum();
+    let one_year_savings: f64 = resources.iter()
+        .map(|r| r.monthly_cost * pricing.term_discount(&one_year, &r.resource_type))
+        .sum();
+    if total_monthly > 1000.0 && committable_monthly > 0.0 && matches!(commitment, CommitmentTerm::OnDemand) {
         recommendations.push(CostRecommendation {
             category: "Commitment Discounts".to_string(),
             title: format!("Consider {} Reserved Instances", match provider {
@@ -278,18 +950,50 @@ fn generate_cost_recommendations(
                 CloudProvider::Azure => "Azure",
                 CloudProvider::GCP => "GCP Committed Use",
             }),
-            description: "For predictable workloads, 1-3 year commitments can reduce compute costs by 30-60%".to_string(),
-            estimated_savings: total_monthly * 0.35 * 12.0,
+            description: format!(
+                "For predictable workloads, a 1-year no-upfront commitment cuts compute and database costs by {:.0}% \
+                 ({:.0}% for 3 years)",
+                one_year_savings / committable_monthly * 100.0,
+                resources.iter()
+                    .map(|r| r.monthly_cost * pricing.term_discount(&CommitmentTerm::ThreeYear, &r.resource_type))
+                    .sum::<f64>() / committable_monthly * 100.0,
+            ),
+            estimated_savings: one_year_savings * 12.0,
             effort: "Low".to_string(),
             priority: 1,
         });
     }
 
-    // Right-sizing recommendation
+    // Right-sizing recommendation, from measured utilization where there is some
     let vm_resources: Vec<_> = resources.iter()
         .filter(|r| matches!(r.resource_type, ResourceType::VirtualMachine | ResourceType::Container))
         .collect();
-    if !vm_resources.is_empty() {
+    let oversized: Vec<(f64, f64)> = utilization
+        .iter()
+        .map(|server| {
+            let (vcpus, memory_gb) = server.right_size();
+            let current = vm_estimate(String::new(), server.provisioned_vcpus, server.provisioned_memory_gb);
+            let sized = vm_estimate(String::new(), vcpus, memory_gb);
+            (calculate_resource_cost(&current, provider, None), calculate_resource_cost(&sized, provider, None))
+        })
+        .filter(|(current, sized)| sized < current)
+        .collect();
+    if !oversized.is_empty() {
+        recommendations.push(CostRecommendation {
+            category: "Right-sizing".to_string(),
+            title: "Right-size over-provisioned servers".to_string(),
+            description: format!(
+                "{} of {} measured servers are larger than their p95 load plus {:.0}% headroom needs. \
+                 The virtual machine estimates above already use the smaller sizes.",
+                oversized.len(),
+                utilization.len(),
+                SIZING_HEADROOM * 100.0
+            ),
+            estimated_savings: oversized.iter().map(|(current, sized)| current - sized).sum::<f64>() * 12.0,
+            effort: "Medium".to_string(),
+            priority: 2,
+        });
+    } else if !vm_resources.is_empty() && utilization.is_empty() {
         recommendations.push(CostRecommendation {
             category: "Right-sizing".to_string(),
             title: "Implement resource right-sizing".to_string(),
@@ -534,7 +1238,7 @@ mod tests {
             notes: None,
         };
 
-        let cost = calculate_resource_cost(&resource, &CloudProvider::AWS);
+        let cost = calculate_resource_cost(&resource, &CloudProvider::AWS, None);
         assert!(cost > 0.0);
         assert!(cost < 500.0); // Sanity check
     }
@@ -573,10 +1277,349 @@ mod tests {
             &resources,
             &CloudProvider::AWS,
             &MigrationStrategy::Replatform,
+            &CommitmentTerm::OnDemand,
+            &[],
+            None,
         );
 
         assert!(!analysis.id.is_empty());
         assert!(!analysis.recommendations.is_empty());
         assert!(analysis.projected_monthly_cost > 0.0);
+        assert!(analysis.commitment_analysis.is_none());
+    }
+
+    #[test]
+    fn test_finops_commitment_analysis() {
+        let current_costs = OnPremiseCosts {
+            hardware_monthly: 20000.0,
+            software_licensing_monthly: 0.0,
+            datacenter_monthly: 0.0,
+            personnel_monthly: 0.0,
+            maintenance_monthly: 0.0,
+            power_cooling_monthly: 0.0,
+            network_monthly: 0.0,
+        };
+        let specs = |vcpus, memory_gb, storage_gb| ResourceSpecs {
+            vcpus,
+            memory_gb,
+            storage_gb,
+            bandwidth_gbps: None,
+            iops: None,
+        };
+        let resources = vec![
+            ResourceCostEstimate {
+                resource_type: ResourceType::VirtualMachine,
+                name: "App Servers".to_string(),
+                quantity: 10,
+                specs: specs(Some(4), Some(16.0), None),
+                monthly_cost: 0.0,
+                notes: None,
+            },
+            ResourceCostEstimate {
+                resource_type: ResourceType::Database,
+                name: "Orders DB".to_string(),
+                quantity: 1,
+                specs: specs(Some(8), Some(64.0), Some(500.0)),
+                monthly_cost: 0.0,
+                notes: None,
+            },
+            ResourceCostEstimate {
+                resource_type: ResourceType::Storage,
+                name: "Backups".to_string(),
+                quantity: 1,
+                specs: specs(None, None, Some(5000.0)),
+                monthly_cost: 0.0,
+                notes: None,
+            },
+        ];
+        let analyze = |term: CommitmentTerm| generate_finops_analysis(
+            &current_costs, &resources, &CloudProvider::AWS, &MigrationStrategy::Rehost, &term, &[], None,
+        );
+
+        let on_demand = analyze(CommitmentTerm::OnDemand);
+        let one_year = analyze(CommitmentTerm::OneYearNoUpfront);
+        let upfront = analyze(CommitmentTerm::OneYearAllUpfront);
+        let three_year = analyze(CommitmentTerm::ThreeYear);
+        assert!(one_year.projected_monthly_cost < on_demand.projected_monthly_cost);
+        assert!(upfront.projected_monthly_cost < one_year.projected_monthly_cost);
+        assert!(three_year.projected_monthly_cost < upfront.projected_monthly_cost);
+
+        // The on-demand recommendation quotes the real discount, committed analyses drop it
+        let pitch = on_demand.recommendations.iter().find(|r| r.category == "Commitment Discounts").unwrap();
+        assert!(!pitch.description.contains("30-60%"));
+        assert!(one_year.recommendations.iter().all(|r| r.category != "Commitment Discounts"));
+
+        let commitment = one_year.commitment_analysis.unwrap();
+        let class = |class| commitment.by_class.iter().find(|c| c.class == class).unwrap();
+        let pricing = CloudPricing::for_provider(&CloudProvider::AWS);
+        assert!((class(CommitmentClass::Compute).discount_percentage - pricing.compute_commitment.one_year_no_upfront * 100.0).abs() < 0.01);
+        assert!((class(CommitmentClass::Database).discount_percentage - pricing.database_commitment.one_year_no_upfront * 100.0).abs() < 0.01);
+        let storage = class(CommitmentClass::OnDemandOnly);
+        assert_eq!(storage.on_demand_monthly, storage.committed_monthly);
+
+        // Break-even sits between the two class discounts; storage doesn't count
+        assert!(commitment.break_even_utilization_percentage > 100.0 - 36.0);
+        assert!(commitment.break_even_utilization_percentage < 100.0 - 31.0);
+        let locked = class(CommitmentClass::Compute).committed_monthly + class(CommitmentClass::Database).committed_monthly;
+        assert!((commitment.downsize_exposure - locked * 6.0).abs() < 0.05);
+        assert_eq!(commitment.upfront_payment, 0.0);
+
+        let upfront = upfront.commitment_analysis.unwrap();
+        assert!(upfront.upfront_payment > 0.0);
+        let three_year = three_year.commitment_analysis.unwrap();
+        assert!(three_year.downsize_exposure > commitment.downsize_exposure);
+    }
+
+    #[test]
+    fn test_finops_analysis_sized_from_utilization() {
+        let current_costs = OnPremiseCosts {
+            hardware_monthly: 5000.0,
+            software_licensing_monthly: 0.0,
+            datacenter_monthly: 0.0,
+            personnel_monthly: 0.0,
+            maintenance_monthly: 0.0,
+            power_cooling_monthly: 0.0,
+            network_monthly: 0.0,
+        };
+        let resources = vec![vm_estimate("App Servers".to_string(), 8, 32.0)];
+        let server = |p95_cpu_percent, avg_memory_percent| ServerUtilization {
+            avg_cpu_percent: p95_cpu_percent / 2.0,
+            p95_cpu_percent,
+            avg_memory_percent,
+            p95_memory_percent: None,
+            provisioned_vcpus: 8,
+            provisioned_memory_gb: 32.0,
+            imported_at: chrono::Utc::now(),
+        };
+        // Two idle servers fit 4 vCPU / 16 GB; a busy one keeps its size
+        let utilization = vec![server(20.0, 30.0), server(25.0, 35.0), server(70.0, 70.0)];
+        let analyze = |utilization: &[ServerUtilization]| generate_finops_analysis(
+            &current_costs, &resources, &CloudProvider::AWS, &MigrationStrategy::Rehost,
+            &CommitmentTerm::OnDemand, utilization, None,
+        );
+
+        let provisioned = analyze(&[]);
+        let sized = analyze(&utilization);
+        let shapes: Vec<_> = sized.resource_breakdown
+            .iter()
+            .map(|r| (r.specs.vcpus.unwrap(), r.specs.memory_gb.unwrap(), r.quantity))
+            .collect();
+        assert_eq!(shapes, vec![(4, 16.0, 2), (8, 32.0, 1)]);
+        assert!(sized.assumptions.iter().any(|a| a.contains("3 measured servers")));
+
+        let right_sizing = sized.recommendations.iter().find(|r| r.category == "Right-sizing").unwrap();
+        assert!(right_sizing.description.starts_with("2 of 3"));
+        let vm = |vcpus, memory_gb| calculate_resource_cost(&vm_estimate(String::new(), vcpus, memory_gb), &CloudProvider::AWS, None);
+        assert!((right_sizing.estimated_savings - 2.0 * (vm(8, 32.0) - vm(4, 16.0)) * 12.0).abs() < 0.01);
+
+        // Without measurements the old rule of thumb applies
+        let estimate = provisioned.recommendations.iter().find(|r| r.category == "Right-sizing").unwrap();
+        assert!(estimate.description.contains("40%"));
+    }
+
+    #[test]
+    fn test_tco_projection() {
+        let current_costs = OnPremiseCosts {
+            hardware_monthly: 5000.0,
+            software_licensing_monthly: 2000.0,
+            datacenter_monthly: 1500.0,
+            personnel_monthly: 8000.0,
+            maintenance_monthly: 1000.0,
+            power_cooling_monthly: 500.0,
+            network_monthly: 300.0,
+        };
+
+        let resources = vec![
+            ResourceCostEstimate {
+                resource_type: ResourceType::VirtualMachine,
+                name: "App Servers".to_string(),
+                quantity: 20,
+                specs: ResourceSpecs {
+                    vcpus: Some(8),
+                    memory_gb: Some(32.0),
+                    storage_gb: None,
+                    bandwidth_gbps: None,
+                    iops: None,
+                },
+                monthly_cost: 0.0,
+                notes: None,
+            },
+        ];
+        let growth = vec![ResourceGrowthRate { resource_type: ResourceType::VirtualMachine, annual_rate_percent: 20.0 }];
+
+        let on_demand = generate_tco_projection(
+            &current_costs, &resources, &CloudProvider::AWS, &MigrationStrategy::Rehost,
+            &CommitmentStrategy::OnDemand, 3, &growth,
+        );
+        let reserved = generate_tco_projection(
+            &current_costs, &resources, &CloudProvider::AWS, &MigrationStrategy::Rehost,
+            &CommitmentStrategy::Reserved3Yr, 3, &growth,
+        );
+
+        assert_eq!(on_demand.years.len(), 3);
+        // 20% growth compounds into year 3 for both cloud and on-prem
+        let ratio = on_demand.years[2].cloud_cost / on_demand.years[0].cloud_cost;
+        assert!((ratio - 1.44).abs() < 0.01);
+        assert!((on_demand.years[2].on_prem_cost / on_demand.years[0].on_prem_cost - 1.44).abs() < 0.01);
+
+        assert!(reserved.total_cloud_cost < on_demand.total_cloud_cost);
+        assert!(reserved.cumulative_savings > on_demand.cumulative_savings);
+        let break_even = reserved.break_even_month.unwrap();
+        assert!((1..=36).contains(&break_even));
+        assert_eq!(on_demand.years[0].migration_cost, reserved.years[0].migration_cost);
+        assert_eq!(reserved.years[1].migration_cost, 0.0);
+    }
+
+    #[test]
+    fn test_compare_resource_costs() {
+        let resources = vec![
+            ResourceCostEstimate {
+                resource_type: ResourceType::VirtualMachine,
+                name: "App Servers".to_string(),
+                quantity: 4,
+                specs: ResourceSpecs {
+                    vcpus: Some(4),
+                    memory_gb: Some(16.0),
+                    storage_gb: None,
+                    bandwidth_gbps: None,
+                    iops: None,
+                },
+                monthly_cost: 0.0,
+                notes: None,
+            },
+            ResourceCostEstimate {
+                resource_type: ResourceType::Storage,
+                name: "File Shares".to_string(),
+                quantity: 1,
+                specs: ResourceSpecs {
+                    vcpus: None,
+                    memory_gb: None,
+                    storage_gb: Some(2000.0),
+                    bandwidth_gbps: None,
+                    iops: None,
+                },
+                monthly_cost: 0.0,
+                notes: None,
+            },
+        ];
+
+        let comparison = compare_resource_costs(&resources, &ComparisonStrategies::default());
+        assert_eq!(comparison.per_resource.len(), 2);
+        let vm = &comparison.per_resource[0];
+        assert_eq!(vm.aws, round_currency(calculate_resource_cost(&resources[0], &CloudProvider::AWS, None)));
+        assert!(vm.gcp < vm.aws);
+
+        // GCP has the lowest compute and storage rates
+        assert_eq!(comparison.cheapest_provider, CloudProvider::GCP);
+        let gcp = comparison.total_for(&CloudProvider::GCP).unwrap();
+        let aws = comparison.total_for(&CloudProvider::AWS).unwrap();
+        assert_eq!(gcp.delta_vs_cheapest, 0.0);
+        assert!((aws.delta_vs_cheapest - (aws.monthly_cost - gcp.monthly_cost)).abs() < 0.01);
+        assert!((aws.annual_cost - aws.monthly_cost * 12.0).abs() < 0.1);
+        assert!(comparison.analyses.is_empty());
+
+        // Commitments discount compute but not storage
+        let current_costs = OnPremiseCosts {
+            hardware_monthly: 5000.0,
+            software_licensing_monthly: 0.0,
+            datacenter_monthly: 0.0,
+            personnel_monthly: 0.0,
+            maintenance_monthly: 0.0,
+            power_cooling_monthly: 0.0,
+            network_monthly: 0.0,
+        };
+        let reserved = compare_resource_costs(&resources, &ComparisonStrategies {
+            commitment: CommitmentStrategy::Reserved3Yr,
+            analysis: Some((&current_costs, MigrationStrategy::Rehost)),
+        });
+        assert!(reserved.per_resource[0].aws < vm.aws);
+        assert_eq!(reserved.per_resource[1].aws, comparison.per_resource[1].aws);
+
+        // One analysis per provider, with provider-specific recommendations
+        let providers: Vec<_> = reserved.analyses.iter().map(|a| a.target_provider).collect();
+        assert_eq!(providers, COMPARED_PROVIDERS.to_vec());
+        let spot_titles: Vec<_> = reserved.analyses
+            .iter()
+            .map(|a| a.recommendations.iter().find(|r| r.category == "Spot Instances").unwrap().title.clone())
+            .collect();
+        assert_ne!(spot_titles[0], spot_titles[2]);
+    }
+
+    fn profile(compute: Option<f64>, memory: Option<f64>) -> PricingProfile {
+        PricingProfile {
+            id: "profile-1".to_string(),
+            name: "EA quote".to_string(),
+            provider: CloudProvider::AWS,
+            region: "eu-west-2".to_string(),
+            compute_per_vcpu: compute,
+            memory_per_gb: memory,
+            storage_per_gb_month: None,
+            egress_per_gb: None,
+            managed_db_multiplier: None,
+            k8s_cluster_base: None,
+            notes: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_pricing_profile_overrides() {
+        let defaults = CloudPricing::for_provider(&CloudProvider::AWS);
+
+        // Set rates win over the defaults
+        let full = profile(Some(0.02), Some(0.001));
+        let pricing = CloudPricing::resolve(&CloudProvider::AWS, Some(&full));
+        assert_eq!(pricing.compute_per_vcpu, 0.02);
+        assert_eq!(pricing.memory_per_gb, 0.001);
+
+        // Unset rates fall back one field at a time
+        let partial = profile(Some(0.02), None);
+        let pricing = CloudPricing::resolve(&CloudProvider::AWS, Some(&partial));
+        assert_eq!(pricing.compute_per_vcpu, 0.02);
+        assert_eq!(pricing.memory_per_gb, defaults.memory_per_gb);
+        assert_eq!(pricing.storage_per_gb_month, defaults.storage_per_gb_month);
+        assert_eq!(pricing.reserved_3yr_discount, defaults.reserved_3yr_discount);
+
+        let vm = ResourceCostEstimate {
+            resource_type: ResourceType::VirtualMachine,
+            name: "App Server".to_string(),
+            quantity: 1,
+            specs: ResourceSpecs {
+                vcpus: Some(4),
+                memory_gb: Some(16.0),
+                storage_gb: None,
+                bandwidth_gbps: None,
+                iops: None,
+            },
+            monthly_cost: 0.0,
+            notes: None,
+        };
+        let expected = 4.0 * 0.02 * 730.0 + 16.0 * defaults.memory_per_gb * 730.0;
+        let cost = calculate_resource_cost(&vm, &CloudProvider::AWS, Some(&partial));
+        assert!((cost - expected).abs() < 0.01);
+        assert!(cost < calculate_resource_cost(&vm, &CloudProvider::AWS, None));
+
+        let current_costs = OnPremiseCosts {
+            hardware_monthly: 5000.0,
+            software_licensing_monthly: 0.0,
+            datacenter_monthly: 0.0,
+            personnel_monthly: 0.0,
+            maintenance_monthly: 0.0,
+            power_cooling_monthly: 0.0,
+            network_monthly: 0.0,
+        };
+        let analysis = generate_finops_analysis(
+            &current_costs,
+            &[vm],
+            &CloudProvider::AWS,
+            &MigrationStrategy::Rehost,
+            &CommitmentTerm::OnDemand,
+            &[],
+            Some(&partial),
+        );
+        assert!(analysis.assumptions[0].contains("\"EA quote\" (eu-west-2)"));
+        assert!(analysis.assumptions[1].contains("memory_per_gb"));
+        assert!(!analysis.assumptions[1].contains("compute_per_vcpu"));
     }
 }
@@ -0,0 +1,524 @@
+//! Live Kubernetes Cluster Auditor
+//!
+//! Evaluates the hardening checklist against a running cluster by shelling
+//! out to `kubectl get -o json`, the way the scanner drives Nmap, so no
+//! Kubernetes client library is needed. Pod-security checks reuse the
+//! manifest evaluator; checks that need more than workload specs are left
+//! for manual review.
+//!
+//! Failures are kept per resource type: a missing kubectl, a bad kubeconfig
+//! or RBAC denying one resource turns the affected checks to Manual with
+//! the reason instead of failing the whole audit.
+
+use crate::infrastructure::k8s_hardening::*;
+use crate::infrastructure::k8s_manifests::{container_name, evaluate_check, pod_containers, pod_template_spec, MANIFEST_CHECKS};
+use crate::infrastructure::models::*;
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+const KUBECTL: &str = "kubectl";
+
+/// Workload resource types read from the cluster, with the kind of their items
+const WORKLOAD_RESOURCES: [(&str, &str); 6] = [
+    ("pods", "Pod"),
+    ("deployments", "Deployment"),
+    ("statefulsets", "StatefulSet"),
+    ("daemonsets", "DaemonSet"),
+    ("jobs", "Job"),
+    ("cronjobs", "CronJob"),
+];
+
+/// Owners whose pods are audited through the owner's template instead
+const TEMPLATE_OWNERS: [&str; 4] = ["ReplicaSet", "StatefulSet", "DaemonSet", "Job"];
+
+/// Which cluster kubectl talks to; unset fields fall back to kubectl's defaults
+#[derive(Debug, Clone, Default)]
+pub struct KubectlTarget {
+    pub kubeconfig: Option<String>,
+    pub context: Option<String>,
+}
+
+/// Audit a client's cluster through kubectl
+///
+/// With no namespaces, all namespaces are read.
+pub async fn audit_cluster(
+    client_id: &str,
+    target: &KubectlTarget,
+    namespaces: &[String],
+) -> K8sAuditResult {
+    let resources = fetch_cluster_resources(target, namespaces).await;
+
+    let context = match &target.context {
+        Some(context) => Some(context.clone()),
+        None => kubectl(target, &["config", "current-context"]).await.ok().map(|s| s.trim().to_string()),
+    };
+    let cluster_name = context.clone().unwrap_or_else(|| "kubectl default".to_string());
+
+    let checks = evaluate_cluster(&resources);
+    let mut audit = cluster_audit(client_id, &cluster_name, &checks);
+    audit.context_name = context;
+    audit.cluster_version = server_version(target).await;
+
+    K8sAuditResult {
+        audit,
+        checks,
+        workloads: resources.audited_workloads().count(),
+        resource_errors: resources.errors,
+    }
+}
+
+// ============================================================================
+// kubectl
+// ============================================================================
+
+/// Why a kubectl call failed
+#[derive(Debug)]
+enum KubectlError {
+    /// kubectl couldn't be run at all; every later call would fail the same way
+    Unavailable(String),
+    Failed(String),
+}
+
+impl KubectlError {
+    fn message(&self) -> &str {
+        match self {
+            KubectlError::Unavailable(message) | KubectlError::Failed(message) => message,
+        }
+    }
+}
+
+/// Run kubectl against the target and return its standard output
+async fn kubectl(target: &KubectlTarget, args: &[&str]) -> Result<String, KubectlError> {
+    let mut command = tokio::process::Command::new(KUBECTL);
+    if let Some(kubeconfig) = &target.kubeconfig {
+        command.arg("--kubeconfig").arg(kubeconfig);
+    }
+    if let Some(context) = &target.context {
+        command.arg("--context").arg(context);
+    }
+
+    let output = command
+        .args(args)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => KubectlError::Unavailable(
+                "kubectl was not found on PATH. Install kubectl to audit a live cluster.".to_string(),
+            ),
+            _ => KubectlError::Unavailable(format!("Failed to execute kubectl: {}", e)),
+        })?;
+
+    if !output.status.success() {
+        return Err(KubectlError::Failed(describe_kubectl_error(&String::from_utf8_lossy(&output.stderr))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Turn kubectl's error output into a reason a consultant can act on
+fn describe_kubectl_error(stderr: &str) -> String {
+    let message = stderr
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("kubectl exited without an error message")
+        .trim_start_matches("error: ")
+        .trim_start_matches("Error from server ")
+        .to_string();
+    let lower = message.to_lowercase();
+
+    if lower.contains("forbidden") {
+        format!("Access denied by RBAC: {}", message)
+    } else if lower.contains("kubeconfig")
+        || lower.contains("invalid configuration")
+        || (lower.contains("context") && lower.contains("does not exist"))
+    {
+        format!("Invalid kubeconfig: {}", message)
+    } else if lower.contains("unable to connect") || lower.contains("connection refused") {
+        format!("Cluster unreachable: {}", message)
+    } else {
+        message
+    }
+}
+
+/// The API server's version, if the cluster answers
+async fn server_version(target: &KubectlTarget) -> Option<String> {
+    let output = kubectl(target, &["version", "-o", "json"]).await.ok()?;
+    let version: serde_json::Value = serde_json::from_str(&output).ok()?;
+    version["serverVersion"]["gitVersion"].as_str().map(str::to_string)
+}
+
+/// Read the workloads and service accounts of the given namespaces (or all)
+async fn fetch_cluster_resources(target: &KubectlTarget, namespaces: &[String]) -> ClusterResources {
+    let scopes: Vec<Option<&str>> = if namespaces.is_empty() {
+        vec![None]
+    } else {
+        namespaces.iter().map(|ns| Some(ns.as_str())).collect()
+    };
+    let resources = WORKLOAD_RESOURCES.iter().map(|(resource, _)| *resource).chain(["serviceaccounts"]);
+
+    let mut cluster = ClusterResources::default();
+    let mut unavailable: Option<String> = None;
+    for resource in resources {
+        for namespace in &scopes {
+            if let Some(reason) = &unavailable {
+                cluster.record_error(resource, *namespace, reason.clone());
+                continue;
+            }
+
+            let mut args = vec!["get", resource, "-o", "json"];
+            match namespace {
+                Some(ns) => args.extend(["-n", *ns]),
+                None => args.push("--all-namespaces"),
+            }
+
+            match kubectl(target, &args).await {
+                Ok(output) => match serde_json::from_str::<serde_json::Value>(&output) {
+                    Ok(list) => cluster.add_list(resource, *namespace, &list),
+                    Err(e) => cluster.record_error(resource, *namespace, format!("Unreadable kubectl output: {}", e)),
+                },
+                Err(e) => {
+                    if let KubectlError::Unavailable(reason) = &e {
+                        unavailable = Some(reason.clone());
+                    }
+                    cluster.record_error(resource, *namespace, e.message().to_string());
+                }
+            }
+        }
+    }
+
+    cluster
+}
+
+// ============================================================================
+// Evaluation
+// ============================================================================
+
+/// Resources read from a cluster, and the ones that couldn't be
+#[derive(Debug, Default)]
+pub struct ClusterResources {
+    /// (kind, resource) of each workload read
+    pub workloads: Vec<(String, Value)>,
+    pub service_accounts: Vec<Value>,
+    pub errors: Vec<K8sResourceError>,
+}
+
+impl ClusterResources {
+    /// Add the items of a `kubectl get <resource> -o json` list
+    pub fn add_list(&mut self, resource: &str, namespace: Option<&str>, list: &serde_json::Value) {
+        let value = match serde_yaml::to_value(list) {
+            Ok(value) => value,
+            Err(e) => return self.record_error(resource, namespace, format!("Unreadable kubectl output: {}", e)),
+        };
+        let items = value["items"].as_sequence().cloned().unwrap_or_default();
+
+        if resource == "serviceaccounts" {
+            self.service_accounts.extend(items);
+            return;
+        }
+
+        let default_kind = WORKLOAD_RESOURCES
+            .iter()
+            .find(|(r, _)| *r == resource)
+            .map(|(_, kind)| *kind)
+            .unwrap_or_default();
+        for item in items {
+            let kind = item["kind"].as_str().unwrap_or(default_kind).to_string();
+            self.workloads.push((kind, item));
+        }
+    }
+
+    pub fn record_error(&mut self, resource: &str, namespace: Option<&str>, error: String) {
+        self.errors.push(K8sResourceError {
+            resource: resource.to_string(),
+            namespace: namespace.map(str::to_string),
+            error,
+        });
+    }
+
+    /// Workloads audited in their own right; pods and jobs run by a
+    /// controller are covered by the controller's template
+    fn audited_workloads(&self) -> impl Iterator<Item = &(String, Value)> {
+        self.workloads.iter().filter(|(kind, resource)| {
+            let owners: Vec<&str> = resource["metadata"]["ownerReferences"]
+                .as_sequence()
+                .map(|refs| refs.iter().filter_map(|r| r["kind"].as_str()).collect())
+                .unwrap_or_default();
+            match kind.as_str() {
+                "Pod" => !owners.iter().any(|o| TEMPLATE_OWNERS.contains(o)),
+                "Job" => !owners.contains(&"CronJob"),
+                _ => true,
+            }
+        })
+    }
+
+    /// Errors that stop `check_id` from being fully evaluated
+    fn errors_for(&self, check_id: &str) -> Vec<&K8sResourceError> {
+        self.errors
+            .iter()
+            .filter(|e| e.resource != "serviceaccounts" || check_id == CHECK_SA_TOKEN_AUTOMOUNT)
+            .collect()
+    }
+}
+
+/// Evaluate every checklist item against the resources read from a cluster
+///
+/// A check fails when any workload violates it, passes only when every
+/// resource it depends on was read, and is otherwise left Manual.
+pub fn evaluate_cluster(resources: &ClusterResources) -> Vec<K8sClusterCheck> {
+    let automount_by_account: HashMap<(String, String), bool> = resources
+        .service_accounts
+        .iter()
+        .filter_map(|sa| {
+            let namespace = sa["metadata"]["namespace"].as_str()?;
+            let name = sa["metadata"]["name"].as_str()?;
+            Some(((namespace.to_string(), name.to_string()), sa["automountServiceAccountToken"].as_bool() != Some(false)))
+        })
+        .collect();
+
+    get_k8s_hardening_checks()
+        .into_iter()
+        .map(|check| {
+            let automatic = MANIFEST_CHECKS.contains(&check.id.as_str())
+                || check.id == CHECK_NO_DEFAULT_NAMESPACE
+                || check.id == CHECK_SA_TOKEN_AUTOMOUNT;
+            if !automatic {
+                return K8sClusterCheck {
+                    check_id: check.id,
+                    title: check.title,
+                    severity: check.severity,
+                    outcome: K8sClusterCheckOutcome::Manual,
+                    evidence: Vec::new(),
+                    error: None,
+                };
+            }
+
+            let evidence: Vec<String> = resources
+                .audited_workloads()
+                .filter_map(|(kind, resource)| {
+                    let pod_spec = pod_template_spec(kind, resource)?;
+                    let namespace = resource["metadata"]["namespace"].as_str().unwrap_or("default");
+                    let name = resource["metadata"]["name"].as_str().unwrap_or("<unnamed>");
+                    let problem = match check.id.as_str() {
+                        CHECK_NO_DEFAULT_NAMESPACE => (namespace == "default").then(|| "runs in the default namespace".to_string()),
+                        CHECK_SA_TOKEN_AUTOMOUNT => mounted_token(pod_spec, namespace, &automount_by_account),
+                        _ => evaluate_check(&check.id, pod_spec, &pod_containers(pod_spec)).err(),
+                    }?;
+                    Some(format!("{}/{}/{}: {}", namespace, kind, name, problem))
+                })
+                .collect();
+
+            let errors = resources.errors_for(&check.id);
+            let error = (!errors.is_empty()).then(|| {
+                errors
+                    .iter()
+                    .map(|e| match &e.namespace {
+                        Some(ns) => format!("{} in {}: {}", e.resource, ns, e.error),
+                        None => format!("{}: {}", e.resource, e.error),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            });
+
+            let outcome = if !evidence.is_empty() {
+                K8sClusterCheckOutcome::Fail
+            } else if error.is_some() {
+                K8sClusterCheckOutcome::Manual
+            } else {
+                K8sClusterCheckOutcome::Pass
+            };
+
+            K8sClusterCheck {
+                check_id: check.id,
+                title: check.title,
+                severity: check.severity,
+                outcome,
+                evidence,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// Describe the service account token a pod mounts, if it mounts one
+///
+/// The pod's own setting wins; otherwise its service account's applies. A
+/// service account that wasn't read counts as not mounting, so an RBAC gap
+/// doesn't produce findings it can't back up.
+fn mounted_token(pod_spec: &Value, namespace: &str, automount_by_account: &HashMap<(String, String), bool>) -> Option<String> {
+    let account = pod_spec["serviceAccountName"]
+        .as_str()
+        .or_else(|| pod_spec["serviceAccount"].as_str())
+        .unwrap_or("default");
+    let mounts = match pod_spec["automountServiceAccountToken"].as_bool() {
+        Some(mounts) => mounts,
+        None => *automount_by_account.get(&(namespace.to_string(), account.to_string())).unwrap_or(&false),
+    };
+    let names: Vec<&str> = pod_containers(pod_spec).into_iter().map(container_name).collect();
+    mounts.then(|| format!("mounts the {} service account token into {}", account, names.join(", ")))
+}
+
+/// Hardening audit recording the automatic outcomes
+///
+/// Manual checks are left unchecked; the ones that couldn't be evaluated
+/// keep the reason as their notes.
+pub fn cluster_audit(client_id: &str, cluster_name: &str, checks: &[K8sClusterCheck]) -> K8sHardeningAudit {
+    let mut audit = new_k8s_audit(client_id, cluster_name);
+    audit.results = checks
+        .iter()
+        .filter_map(|check| {
+            let status = match (check.outcome, &check.error) {
+                (K8sClusterCheckOutcome::Pass, _) => K8sCheckStatus::Pass,
+                (K8sClusterCheckOutcome::Fail, _) => K8sCheckStatus::Fail,
+                (K8sClusterCheckOutcome::Manual, Some(_)) => K8sCheckStatus::NotChecked,
+                (K8sClusterCheckOutcome::Manual, None) => return None,
+            };
+            Some(K8sCheckResultData {
+                check_id: check.check_id.clone(),
+                status,
+                notes: check.error.clone(),
+                evidence_ref: Some("kubectl".to_string()),
+                affected_resources: check.evidence.clone(),
+            })
+        })
+        .collect();
+    audit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPLOYMENTS: &str = r#"{
+        "apiVersion": "v1",
+        "kind": "List",
+        "items": [
+            {
+                "kind": "Deployment",
+                "metadata": {"name": "web", "namespace": "default"},
+                "spec": {"template": {"spec": {
+                    "hostPID": true,
+                    "containers": [{"name": "web", "image": "nginx", "securityContext": {"privileged": true}}]
+                }}}
+            },
+            {
+                "kind": "Deployment",
+                "metadata": {"name": "api", "namespace": "prod"},
+                "spec": {"template": {"spec": {
+                    "serviceAccountName": "api",
+                    "securityContext": {"runAsNonRoot": true},
+                    "containers": [{
+                        "name": "api",
+                        "image": "api:1.4",
+                        "securityContext": {
+                            "allowPrivilegeEscalation": false,
+                            "readOnlyRootFilesystem": true,
+                            "capabilities": {"drop": ["ALL"]}
+                        },
+                        "resources": {"limits": {"cpu": "500m", "memory": "256Mi"}}
+                    }]
+                }}}
+            }
+        ]
+    }"#;
+
+    const PODS: &str = r#"{
+        "kind": "List",
+        "items": [
+            {
+                "kind": "Pod",
+                "metadata": {"name": "api-7d9f-x2x", "namespace": "prod", "ownerReferences": [{"kind": "ReplicaSet", "name": "api-7d9f"}]},
+                "spec": {"containers": [{"name": "api", "securityContext": {"privileged": true}}]}
+            }
+        ]
+    }"#;
+
+    const SERVICE_ACCOUNTS: &str = r#"{
+        "kind": "List",
+        "items": [
+            {"kind": "ServiceAccount", "metadata": {"name": "default", "namespace": "default"}},
+            {"kind": "ServiceAccount", "metadata": {"name": "api", "namespace": "prod"}, "automountServiceAccountToken": false}
+        ]
+    }"#;
+
+    fn resources() -> ClusterResources {
+        let mut resources = ClusterResources::default();
+        for (resource, json) in [("deployments", DEPLOYMENTS), ("pods", PODS), ("serviceaccounts", SERVICE_ACCOUNTS)] {
+            resources.add_list(resource, None, &serde_json::from_str(json).unwrap());
+        }
+        resources
+    }
+
+    fn check<'c>(checks: &'c [K8sClusterCheck], id: &str) -> &'c K8sClusterCheck {
+        checks.iter().find(|c| c.check_id == id).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_cluster() {
+        let resources = resources();
+        assert_eq!(resources.audited_workloads().count(), 2, "ReplicaSet pods are audited through their Deployment");
+
+        let checks = evaluate_cluster(&resources);
+        assert_eq!(checks.len(), get_k8s_hardening_checks().len());
+
+        let privileged = check(&checks, CHECK_NOT_PRIVILEGED);
+        assert_eq!(privileged.outcome, K8sClusterCheckOutcome::Fail);
+        assert_eq!(privileged.evidence, vec!["default/Deployment/web: Containers running privileged: web"]);
+
+        let host = check(&checks, CHECK_HOST_NAMESPACES);
+        assert_eq!(host.evidence, vec!["default/Deployment/web: Pod shares host namespaces: hostPID"]);
+
+        let default_ns = check(&checks, CHECK_NO_DEFAULT_NAMESPACE);
+        assert_eq!(default_ns.outcome, K8sClusterCheckOutcome::Fail);
+        assert_eq!(default_ns.evidence.len(), 1);
+
+        // web mounts the default account's token; api's account opts out
+        let automount = check(&checks, CHECK_SA_TOKEN_AUTOMOUNT);
+        assert_eq!(automount.evidence.len(), 1);
+        assert!(automount.evidence[0].starts_with("default/Deployment/web"));
+
+        assert_eq!(check(&checks, "rbac-no-wildcards").outcome, K8sClusterCheckOutcome::Manual);
+    }
+
+    #[test]
+    fn test_resource_errors_are_per_check() {
+        let mut resources = resources();
+        resources.service_accounts.clear();
+        resources.workloads.retain(|(_, w)| w["metadata"]["name"].as_str() == Some("api"));
+        resources.record_error(
+            "serviceaccounts",
+            Some("prod"),
+            describe_kubectl_error(
+                "Error from server (Forbidden): serviceaccounts is forbidden: User \"auditor\" cannot list resource \"serviceaccounts\"",
+            ),
+        );
+
+        let checks = evaluate_cluster(&resources);
+        let automount = check(&checks, CHECK_SA_TOKEN_AUTOMOUNT);
+        assert_eq!(automount.outcome, K8sClusterCheckOutcome::Manual);
+        assert!(automount.error.as_deref().unwrap().contains("Access denied by RBAC"));
+
+        // Workload checks don't depend on service accounts
+        let privileged = check(&checks, CHECK_NOT_PRIVILEGED);
+        assert_eq!(privileged.outcome, K8sClusterCheckOutcome::Pass);
+        assert!(privileged.error.is_none());
+
+        let audit = cluster_audit("client-1", "prod-cluster", &checks);
+        assert_eq!(audit.status_of(CHECK_NOT_PRIVILEGED), K8sCheckStatus::Pass);
+        assert_eq!(audit.status_of(CHECK_SA_TOKEN_AUTOMOUNT), K8sCheckStatus::NotChecked);
+        assert!(audit.results.iter().any(|r| r.check_id == CHECK_SA_TOKEN_AUTOMOUNT && r.notes.is_some()));
+        assert!(!audit.results.iter().any(|r| r.check_id == "rbac-no-wildcards"));
+    }
+
+    #[test]
+    fn test_describe_kubectl_error() {
+        assert_eq!(
+            describe_kubectl_error("error: context \"prod\" does not exist\n"),
+            "Invalid kubeconfig: context \"prod\" does not exist"
+        );
+        assert!(describe_kubectl_error("error: error loading config file \"/tmp/x\": invalid configuration")
+            .starts_with("Invalid kubeconfig"));
+        assert!(describe_kubectl_error("Unable to connect to the server: dial tcp 10.0.0.1:6443: i/o timeout")
+            .starts_with("Cluster unreachable"));
+    }
+}
@@ -2,16 +2,40 @@
 //!
 //! Security checks based on NSA/CISA Kubernetes Hardening Guide
 //! and CIS Kubernetes Benchmark.
+//!
+//! Audits are persisted per client and cluster, so a re-audit can be
+//! compared against the previous one.
 
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::repository::parse_datetime;
 use crate::infrastructure::models::*;
+use chrono::Utc;
+use rusqlite::params;
 use uuid::Uuid;
 
+// Stable ids for the checks that are also evaluated automatically from
+// manifests or a live cluster
+pub const CHECK_NON_ROOT: &str = "pod-non-root";
+pub const CHECK_READ_ONLY_ROOT_FS: &str = "pod-read-only-root-fs";
+pub const CHECK_NO_PRIVILEGE_ESCALATION: &str = "pod-no-privilege-escalation";
+pub const CHECK_NOT_PRIVILEGED: &str = "pod-not-privileged";
+pub const CHECK_CAPABILITIES_DROPPED: &str = "pod-capabilities-dropped";
+pub const CHECK_HOST_NAMESPACES: &str = "pod-host-namespaces";
+pub const CHECK_RESOURCE_LIMITS: &str = "pod-resource-limits";
+pub const CHECK_NO_HOST_PATH: &str = "pod-no-host-path";
+pub const CHECK_NO_DEFAULT_NAMESPACE: &str = "pod-no-default-namespace";
+pub const CHECK_NO_LATEST_TAG: &str = "supply-no-latest-tag";
+pub const CHECK_SA_TOKEN_AUTOMOUNT: &str = "authn-sa-token-automount";
+
 /// Get all K8s hardening checks
+///
+/// Check ids are stable so audit results and findings can refer back to them.
 pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
     vec![
         // Pod Security
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_NON_ROOT.to_string(),
             category: K8sHardeningCategory::PodSecurity,
             title: "Non-root containers".to_string(),
             description: "Containers should run as non-root user".to_string(),
@@ -22,7 +46,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Pod Security".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_READ_ONLY_ROOT_FS.to_string(),
             category: K8sHardeningCategory::PodSecurity,
             title: "Read-only root filesystem".to_string(),
             description: "Container root filesystem should be read-only".to_string(),
@@ -33,7 +57,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Pod Security".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_NO_PRIVILEGE_ESCALATION.to_string(),
             category: K8sHardeningCategory::PodSecurity,
             title: "Privilege escalation disabled".to_string(),
             description: "Containers should not allow privilege escalation".to_string(),
@@ -44,7 +68,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Pod Security".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_NOT_PRIVILEGED.to_string(),
             category: K8sHardeningCategory::PodSecurity,
             title: "No privileged containers".to_string(),
             description: "Containers should not run in privileged mode".to_string(),
@@ -55,7 +79,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Pod Security".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_CAPABILITIES_DROPPED.to_string(),
             category: K8sHardeningCategory::PodSecurity,
             title: "Capabilities dropped".to_string(),
             description: "All unnecessary Linux capabilities should be dropped".to_string(),
@@ -66,7 +90,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Pod Security".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_HOST_NAMESPACES.to_string(),
             category: K8sHardeningCategory::PodSecurity,
             title: "Host namespaces not shared".to_string(),
             description: "Pods should not share host PID, IPC, or network namespaces".to_string(),
@@ -77,7 +101,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Pod Security".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_RESOURCE_LIMITS.to_string(),
             category: K8sHardeningCategory::PodSecurity,
             title: "Resource limits defined".to_string(),
             description: "CPU and memory limits should be set for all containers".to_string(),
@@ -87,10 +111,32 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             cis_benchmark: Some("5.4.1".to_string()),
             nsa_reference: Some("Pod Security".to_string()),
         },
+        K8sHardeningCheck {
+            id: CHECK_NO_HOST_PATH.to_string(),
+            category: K8sHardeningCategory::PodSecurity,
+            title: "No hostPath volumes".to_string(),
+            description: "Pods should not mount directories from the host filesystem".to_string(),
+            rationale: "hostPath volumes expose node files such as the container runtime socket and kubelet credentials".to_string(),
+            remediation: "Replace hostPath volumes with persistent volumes, configMaps or emptyDir".to_string(),
+            severity: Severity::High,
+            cis_benchmark: Some("5.2.12".to_string()),
+            nsa_reference: Some("Pod Security".to_string()),
+        },
+        K8sHardeningCheck {
+            id: CHECK_NO_DEFAULT_NAMESPACE.to_string(),
+            category: K8sHardeningCategory::PodSecurity,
+            title: "No workloads in the default namespace".to_string(),
+            description: "Workloads should run in dedicated namespaces rather than default".to_string(),
+            rationale: "Namespaces are the boundary for RBAC, network policies and quotas; default usually has none".to_string(),
+            remediation: "Move workloads into application namespaces and keep default empty".to_string(),
+            severity: Severity::Low,
+            cis_benchmark: Some("5.7.4".to_string()),
+            nsa_reference: Some("Pod Security".to_string()),
+        },
 
         // Network Policies
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "net-default-deny-ingress".to_string(),
             category: K8sHardeningCategory::NetworkPolicies,
             title: "Default deny ingress policy".to_string(),
             description: "Default deny ingress network policy should exist in each namespace".to_string(),
@@ -101,7 +147,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Network Separation".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "net-default-deny-egress".to_string(),
             category: K8sHardeningCategory::NetworkPolicies,
             title: "Default deny egress policy".to_string(),
             description: "Default deny egress network policy should exist in each namespace".to_string(),
@@ -112,7 +158,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Network Separation".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "net-cni-policy-support".to_string(),
             category: K8sHardeningCategory::NetworkPolicies,
             title: "CNI supports network policies".to_string(),
             description: "Container Network Interface (CNI) should support NetworkPolicy enforcement".to_string(),
@@ -125,7 +171,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
 
         // Authentication
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "authn-anonymous-disabled".to_string(),
             category: K8sHardeningCategory::Authentication,
             title: "Anonymous authentication disabled".to_string(),
             description: "API server should have anonymous authentication disabled".to_string(),
@@ -136,7 +182,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Authentication".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "authn-strong-method".to_string(),
             category: K8sHardeningCategory::Authentication,
             title: "Strong authentication method".to_string(),
             description: "Use OIDC or client certificates for user authentication".to_string(),
@@ -147,7 +193,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Authentication".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_SA_TOKEN_AUTOMOUNT.to_string(),
             category: K8sHardeningCategory::Authentication,
             title: "Service account token automount disabled".to_string(),
             description: "Disable automatic mounting of service account tokens when not needed".to_string(),
@@ -160,7 +206,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
 
         // Authorization (RBAC)
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "rbac-enabled".to_string(),
             category: K8sHardeningCategory::Authorization,
             title: "RBAC enabled".to_string(),
             description: "Role-Based Access Control should be enabled".to_string(),
@@ -171,7 +217,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Authorization".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "rbac-no-cluster-admin-users".to_string(),
             category: K8sHardeningCategory::Authorization,
             title: "No cluster-admin for users".to_string(),
             description: "cluster-admin role should not be bound to regular users".to_string(),
@@ -182,7 +228,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Authorization".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "rbac-no-wildcards".to_string(),
             category: K8sHardeningCategory::Authorization,
             title: "Minimize wildcard permissions".to_string(),
             description: "Roles should not use wildcard (*) permissions".to_string(),
@@ -193,7 +239,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Authorization".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "rbac-default-sa-restricted".to_string(),
             category: K8sHardeningCategory::Authorization,
             title: "Default service account restricted".to_string(),
             description: "Default service account should have minimal permissions".to_string(),
@@ -206,7 +252,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
 
         // Logging & Monitoring
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "log-audit-enabled".to_string(),
             category: K8sHardeningCategory::Logging,
             title: "Audit logging enabled".to_string(),
             description: "Kubernetes audit logging should be enabled".to_string(),
@@ -217,7 +263,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Logging".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "log-audit-retention".to_string(),
             category: K8sHardeningCategory::Logging,
             title: "Audit log retention".to_string(),
             description: "Audit logs should be retained for at least 30 days".to_string(),
@@ -228,7 +274,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Logging".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "log-centralized".to_string(),
             category: K8sHardeningCategory::Logging,
             title: "Centralized logging".to_string(),
             description: "Container logs should be shipped to centralized logging system".to_string(),
@@ -241,7 +287,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
 
         // Threat Detection
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "threat-runtime-monitoring".to_string(),
             category: K8sHardeningCategory::ThreatDetection,
             title: "Runtime security monitoring".to_string(),
             description: "Deploy runtime security tool for threat detection".to_string(),
@@ -252,7 +298,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Threat Detection".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "threat-admission-controller".to_string(),
             category: K8sHardeningCategory::ThreatDetection,
             title: "Admission controller for security".to_string(),
             description: "Deploy admission controller to enforce security policies".to_string(),
@@ -265,7 +311,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
 
         // Supply Chain Security
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "supply-image-scanning".to_string(),
             category: K8sHardeningCategory::SupplyChain,
             title: "Image scanning enabled".to_string(),
             description: "Container images should be scanned for vulnerabilities".to_string(),
@@ -276,7 +322,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Supply Chain".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "supply-image-signing".to_string(),
             category: K8sHardeningCategory::SupplyChain,
             title: "Image signature verification".to_string(),
             description: "Container images should be signed and verified".to_string(),
@@ -287,7 +333,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Supply Chain".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "supply-private-registry".to_string(),
             category: K8sHardeningCategory::SupplyChain,
             title: "Private registry only".to_string(),
             description: "Images should only be pulled from trusted private registries".to_string(),
@@ -298,7 +344,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Supply Chain".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: CHECK_NO_LATEST_TAG.to_string(),
             category: K8sHardeningCategory::SupplyChain,
             title: "No latest tag".to_string(),
             description: "Container images should use specific version tags, not 'latest'".to_string(),
@@ -311,7 +357,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
 
         // Secrets Management
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "secrets-encryption-at-rest".to_string(),
             category: K8sHardeningCategory::Secrets,
             title: "Encryption at rest for secrets".to_string(),
             description: "Kubernetes Secrets should be encrypted at rest".to_string(),
@@ -322,7 +368,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Secrets".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "secrets-external-manager".to_string(),
             category: K8sHardeningCategory::Secrets,
             title: "External secrets manager".to_string(),
             description: "Use external secrets manager for sensitive data".to_string(),
@@ -333,7 +379,7 @@ pub fn get_k8s_hardening_checks() -> Vec<K8sHardeningCheck> {
             nsa_reference: Some("Secrets".to_string()),
         },
         K8sHardeningCheck {
-            id: Uuid::new_v4().to_string(),
+            id: "secrets-no-env-vars".to_string(),
             category: K8sHardeningCategory::Secrets,
             title: "No secrets in environment variables".to_string(),
             description: "Avoid passing secrets as environment variables".to_string(),
@@ -366,6 +412,349 @@ pub fn get_checks_by_category() -> Vec<(K8sHardeningCategory, Vec<K8sHardeningCh
     result
 }
 
+// ============================================================================
+// Audit Results
+// ============================================================================
+
+/// A new audit of a client's cluster, with no results yet
+pub fn new_k8s_audit(client_id: &str, cluster_name: &str) -> K8sHardeningAudit {
+    K8sHardeningAudit {
+        id: Uuid::new_v4().to_string(),
+        client_id: client_id.to_string(),
+        cluster_name: cluster_name.to_string(),
+        cluster_label: None,
+        cluster_version: None,
+        context_name: None,
+        audited_by: None,
+        audited_at: Utc::now(),
+        results: Vec::new(),
+    }
+}
+
+/// Pass/fail counts per category; N/A and unchecked checks don't score
+pub fn summarize_k8s_audit(audit: &K8sHardeningAudit) -> K8sAuditSummary {
+    let checks = get_k8s_hardening_checks();
+    let failing: Vec<&K8sHardeningCheck> = checks
+        .iter()
+        .filter(|c| audit.status_of(&c.id).is_failing())
+        .collect();
+
+    let category_results = K8sHardeningCategory::all()
+        .into_iter()
+        .map(|category| {
+            let statuses: Vec<K8sCheckStatus> = checks
+                .iter()
+                .filter(|c| c.category == category)
+                .map(|c| audit.status_of(&c.id))
+                .collect();
+            let passed = statuses.iter().filter(|s| **s == K8sCheckStatus::Pass).count();
+            let failed = statuses.iter().filter(|s| s.is_failing()).count();
+            K8sCategoryResult {
+                category,
+                display_name: category.display_name().to_string(),
+                color: category.color().to_string(),
+                total: statuses.len(),
+                passed,
+                failed,
+                score_percentage: if passed + failed > 0 { passed as f64 / (passed + failed) as f64 * 100.0 } else { 0.0 },
+            }
+        })
+        .collect();
+
+    K8sAuditSummary {
+        audit_id: audit.id.clone(),
+        total_checks: checks.len(),
+        passed: checks.iter().filter(|c| audit.status_of(&c.id) == K8sCheckStatus::Pass).count(),
+        failed: failing.len(),
+        warnings: checks.iter().filter(|c| audit.status_of(&c.id) == K8sCheckStatus::Warn).count(),
+        critical_findings: failing.iter().filter(|c| c.severity == Severity::Critical).count(),
+        high_findings: failing.iter().filter(|c| c.severity == Severity::High).count(),
+        category_results,
+    }
+}
+
+/// Severity breakdown of the checklist, or of an audit's failing checks
+pub fn k8s_severity_stats(audit: Option<&K8sHardeningAudit>) -> K8sSeverityStats {
+    let checks = get_k8s_hardening_checks();
+    let status_of = |check: &K8sHardeningCheck| audit.map(|a| a.status_of(&check.id));
+    let counted: Vec<&K8sHardeningCheck> = checks
+        .iter()
+        .filter(|c| status_of(c).map_or(true, |s| s.is_failing()))
+        .collect();
+    let count = |severity: Severity| counted.iter().filter(|c| c.severity == severity).count();
+
+    K8sSeverityStats {
+        audit_id: audit.map(|a| a.id.clone()),
+        cluster_name: audit.map(|a| a.cluster_display_name()),
+        audited_at: audit.map(|a| a.audited_at),
+        total: checks.len(),
+        critical: count(Severity::Critical),
+        high: count(Severity::High),
+        medium: count(Severity::Medium),
+        low: count(Severity::Low),
+        info: count(Severity::Info),
+        passed: checks.iter().filter(|c| status_of(c) == Some(K8sCheckStatus::Pass)).count(),
+        failed: if audit.is_some() { counted.len() } else { 0 },
+        not_checked: checks.iter().filter(|c| status_of(c) == Some(K8sCheckStatus::NotChecked)).count(),
+    }
+}
+
+/// Compare a re-audit against a baseline audit, check by check
+///
+/// A check is newly passing when it passes now and didn't before, and newly
+/// failing when it fails or warns now and did neither before.
+pub fn compare_k8s_audits(baseline: &K8sHardeningAudit, current: &K8sHardeningAudit) -> K8sAuditComparison {
+    let severity_order = Severity::all();
+    let mut checks = get_k8s_hardening_checks();
+    checks.sort_by_key(|c| severity_order.iter().position(|s| *s == c.severity));
+
+    let mut categories: Vec<K8sCategoryComparison> = K8sHardeningCategory::all()
+        .into_iter()
+        .map(|category| K8sCategoryComparison {
+            category,
+            display_name: category.display_name().to_string(),
+            newly_passing: Vec::new(),
+            newly_failing: Vec::new(),
+            unchanged: Vec::new(),
+            other_changes: Vec::new(),
+        })
+        .collect();
+    let mut severities: Vec<K8sSeverityComparison> = severity_order
+        .iter()
+        .map(|severity| K8sSeverityComparison {
+            severity: *severity,
+            newly_passing: 0,
+            newly_failing: 0,
+            unchanged: 0,
+        })
+        .collect();
+
+    for check in &checks {
+        let change = K8sCheckChange {
+            check_id: check.id.clone(),
+            title: check.title.clone(),
+            severity: check.severity,
+            previous: baseline.status_of(&check.id),
+            current: current.status_of(&check.id),
+        };
+        let category = match categories.iter_mut().find(|c| c.category == check.category) {
+            Some(category) => category,
+            None => continue,
+        };
+        let by_severity = match severities.iter_mut().find(|s| s.severity == check.severity) {
+            Some(by_severity) => by_severity,
+            None => continue,
+        };
+
+        if change.current == K8sCheckStatus::Pass && change.previous != K8sCheckStatus::Pass {
+            by_severity.newly_passing += 1;
+            category.newly_passing.push(change);
+        } else if change.current.is_failing() && !change.previous.is_failing() {
+            by_severity.newly_failing += 1;
+            category.newly_failing.push(change);
+        } else if change.current == change.previous {
+            by_severity.unchanged += 1;
+            category.unchanged.push(change);
+        } else {
+            category.other_changes.push(change);
+        }
+    }
+
+    K8sAuditComparison {
+        baseline_id: baseline.id.clone(),
+        current_id: current.id.clone(),
+        baseline_cluster: baseline.cluster_display_name(),
+        current_cluster: current.cluster_display_name(),
+        newly_passing: categories.iter().map(|c| c.newly_passing.len()).sum(),
+        newly_failing: categories.iter().map(|c| c.newly_failing.len()).sum(),
+        unchanged: categories.iter().map(|c| c.unchanged.len()).sum(),
+        categories,
+        severities,
+    }
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Initialize K8s hardening audit schema
+pub fn init_k8s_audit_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Hardening audits, any number per client and cluster
+        CREATE TABLE IF NOT EXISTS k8s_audits (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            cluster_name TEXT NOT NULL,
+            cluster_label TEXT,
+            cluster_version TEXT,
+            context_name TEXT,
+            audited_by TEXT,
+            audited_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_k8s_audits_client ON k8s_audits(client_id, audited_at);
+
+        -- Results by check id
+        CREATE TABLE IF NOT EXISTS k8s_audit_results (
+            audit_id TEXT NOT NULL,
+            check_id TEXT NOT NULL,
+            status TEXT NOT NULL,
+            notes TEXT,
+            evidence_ref TEXT,
+            affected_resources TEXT NOT NULL DEFAULT '[]',
+            PRIMARY KEY (audit_id, check_id),
+            FOREIGN KEY (audit_id) REFERENCES k8s_audits(id) ON DELETE CASCADE
+        );
+    "#)?;
+
+    tracing::info!("K8s audit schema initialized");
+    Ok(())
+}
+
+const AUDIT_COLUMNS: &str =
+    "id, client_id, cluster_name, cluster_label, cluster_version, context_name, audited_by, audited_at";
+
+/// K8s hardening audit repository
+pub struct K8sAuditRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> K8sAuditRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        K8sAuditRepository { db }
+    }
+
+    /// Insert or update an audit, replacing its results
+    pub fn save(&self, audit: &K8sHardeningAudit) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            r#"INSERT INTO k8s_audits
+               (id, client_id, cluster_name, cluster_label, cluster_version, context_name, audited_by, audited_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+               ON CONFLICT(id) DO UPDATE SET cluster_name = excluded.cluster_name,
+                   cluster_label = excluded.cluster_label, cluster_version = excluded.cluster_version,
+                   context_name = excluded.context_name, audited_by = excluded.audited_by,
+                   audited_at = excluded.audited_at"#,
+            params![
+                audit.id,
+                audit.client_id,
+                audit.cluster_name,
+                audit.cluster_label,
+                audit.cluster_version,
+                audit.context_name,
+                audit.audited_by,
+                audit.audited_at.to_rfc3339(),
+            ],
+        )?;
+
+        tx.execute("DELETE FROM k8s_audit_results WHERE audit_id = ?1", params![audit.id])?;
+        for result in &audit.results {
+            tx.execute(
+                "INSERT INTO k8s_audit_results (audit_id, check_id, status, notes, evidence_ref, affected_resources)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    audit.id,
+                    result.check_id,
+                    format!("{:?}", result.status),
+                    result.notes,
+                    result.evidence_ref,
+                    serde_json::to_string(&result.affected_resources)?,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<K8sHardeningAudit>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM k8s_audits WHERE id = ?1", AUDIT_COLUMNS))?;
+        let mut rows = stmt.query(params![id])?;
+        let mut audit = match rows.next()? {
+            Some(row) => parse_audit_row(row)?,
+            None => return Ok(None),
+        };
+
+        audit.results = load_results(&conn, &audit.id)?;
+        Ok(Some(audit))
+    }
+
+    /// A client's audits, newest first
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<K8sHardeningAudit>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM k8s_audits WHERE client_id = ?1 ORDER BY audited_at DESC",
+            AUDIT_COLUMNS
+        ))?;
+        let mut audits = stmt
+            .query_map(params![client_id], |row| Ok(parse_audit_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        for audit in audits.iter_mut() {
+            audit.results = load_results(&conn, &audit.id)?;
+        }
+        Ok(audits)
+    }
+}
+
+fn load_results(conn: &rusqlite::Connection, audit_id: &str) -> OptioResult<Vec<K8sCheckResultData>> {
+    let mut stmt = conn.prepare(
+        "SELECT check_id, status, notes, evidence_ref, affected_resources FROM k8s_audit_results
+         WHERE audit_id = ?1 ORDER BY check_id",
+    )?;
+    let results = stmt
+        .query_map(params![audit_id], |row| Ok(parse_result_row(row)))?
+        .filter_map(|r| r.ok())
+        .collect::<OptioResult<Vec<_>>>()?;
+    Ok(results)
+}
+
+fn parse_audit_row(row: &rusqlite::Row) -> OptioResult<K8sHardeningAudit> {
+    Ok(K8sHardeningAudit {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        cluster_name: row.get(2)?,
+        cluster_label: row.get(3)?,
+        cluster_version: row.get(4)?,
+        context_name: row.get(5)?,
+        audited_by: row.get(6)?,
+        audited_at: parse_datetime(&row.get::<_, String>(7)?)?,
+        results: Vec::new(),
+    })
+}
+
+fn parse_result_row(row: &rusqlite::Row) -> OptioResult<K8sCheckResultData> {
+    let status: String = row.get(1)?;
+    let affected: String = row.get(4)?;
+
+    Ok(K8sCheckResultData {
+        check_id: row.get(0)?,
+        status: parse_check_status(&status)?,
+        notes: row.get(2)?,
+        evidence_ref: row.get(3)?,
+        affected_resources: serde_json::from_str(&affected)?,
+    })
+}
+
+fn parse_check_status(s: &str) -> OptioResult<K8sCheckStatus> {
+    match s {
+        "Pass" => Ok(K8sCheckStatus::Pass),
+        "Fail" => Ok(K8sCheckStatus::Fail),
+        "Warn" => Ok(K8sCheckStatus::Warn),
+        "NotApplicable" => Ok(K8sCheckStatus::NotApplicable),
+        "NotChecked" => Ok(K8sCheckStatus::NotChecked),
+        _ => Err(OptioError::Database(format!("Unknown K8s check status: {}", s))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -385,4 +774,98 @@ mod tests {
             assert!(count > 0, "Category {:?} has no checks", category);
         }
     }
+
+    #[test]
+    fn test_check_ids_stable_and_unique() {
+        let ids: Vec<String> = get_k8s_hardening_checks().into_iter().map(|c| c.id).collect();
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len());
+        assert_eq!(ids, get_k8s_hardening_checks().into_iter().map(|c| c.id).collect::<Vec<_>>());
+    }
+
+    fn result(check_id: &str, status: K8sCheckStatus) -> K8sCheckResultData {
+        K8sCheckResultData {
+            check_id: check_id.to_string(),
+            status,
+            notes: None,
+            evidence_ref: None,
+            affected_resources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_compare_audits() {
+        let mut baseline = new_k8s_audit("c1", "prod");
+        baseline.results = vec![
+            result(CHECK_NON_ROOT, K8sCheckStatus::Fail),
+            result(CHECK_NOT_PRIVILEGED, K8sCheckStatus::Pass),
+            result(CHECK_READ_ONLY_ROOT_FS, K8sCheckStatus::Fail),
+            result(CHECK_RESOURCE_LIMITS, K8sCheckStatus::Warn),
+        ];
+        let mut current = new_k8s_audit("c1", "prod");
+        current.results = vec![
+            result(CHECK_NON_ROOT, K8sCheckStatus::Pass),
+            result(CHECK_NOT_PRIVILEGED, K8sCheckStatus::Fail),
+            result(CHECK_READ_ONLY_ROOT_FS, K8sCheckStatus::Fail),
+            result(CHECK_RESOURCE_LIMITS, K8sCheckStatus::Fail),
+        ];
+
+        let comparison = compare_k8s_audits(&baseline, &current);
+        assert_eq!(comparison.newly_passing, 1);
+        assert_eq!(comparison.newly_failing, 1);
+
+        let pod = &comparison.categories[0];
+        assert_eq!(pod.category, K8sHardeningCategory::PodSecurity);
+        assert_eq!(pod.newly_passing[0].check_id, CHECK_NON_ROOT);
+        assert_eq!(pod.newly_failing[0].check_id, CHECK_NOT_PRIVILEGED);
+        assert!(pod.unchanged.iter().any(|c| c.check_id == CHECK_READ_ONLY_ROOT_FS));
+        // Warn to Fail is still failing, so it's neither new nor unchanged
+        assert_eq!(pod.other_changes[0].check_id, CHECK_RESOURCE_LIMITS);
+
+        // Checks without results on either side are unchanged
+        let total = get_k8s_hardening_checks().len();
+        assert_eq!(comparison.unchanged, total - 3);
+        let critical = comparison.severities.iter().find(|s| s.severity == Severity::Critical).unwrap();
+        assert_eq!(critical.newly_failing, 1);
+
+        let stats = k8s_severity_stats(Some(&current));
+        assert_eq!(stats.failed, 3);
+        assert_eq!(stats.passed, 1);
+        assert_eq!(stats.not_checked, total - 4);
+        let checklist = k8s_severity_stats(None);
+        assert_eq!(checklist.critical + checklist.high + checklist.medium + checklist.low + checklist.info, total);
+    }
+
+    #[test]
+    fn test_audit_repository() {
+        let db = Database {
+            conn: std::sync::Mutex::new(rusqlite::Connection::open_in_memory().unwrap()),
+        };
+        init_k8s_audit_schema(&db).unwrap();
+        let repo = K8sAuditRepository::new(&db);
+
+        let mut prod = new_k8s_audit("c1", "main");
+        prod.cluster_label = Some("production".to_string());
+        prod.results = vec![result(CHECK_NON_ROOT, K8sCheckStatus::Fail)];
+        prod.results[0].evidence_ref = Some("kubectl-get-pods.txt".to_string());
+        repo.save(&prod).unwrap();
+
+        let mut staging = new_k8s_audit("c1", "main");
+        staging.cluster_label = Some("staging".to_string());
+        staging.audited_at = prod.audited_at + chrono::Duration::days(30);
+        repo.save(&staging).unwrap();
+
+        prod.results = vec![result(CHECK_NON_ROOT, K8sCheckStatus::NotApplicable)];
+        repo.save(&prod).unwrap();
+
+        let audits = repo.list_by_client("c1").unwrap();
+        assert_eq!(audits.len(), 2);
+        assert_eq!(audits[0].cluster_display_name(), "main (staging)");
+
+        let stored = repo.get(&prod.id).unwrap().unwrap();
+        assert_eq!(stored.results.len(), 1);
+        assert_eq!(stored.status_of(CHECK_NON_ROOT), K8sCheckStatus::NotApplicable);
+        assert_eq!(stored.status_of(CHECK_NOT_PRIVILEGED), K8sCheckStatus::NotChecked);
+        assert!(repo.get("missing").unwrap().is_none());
+    }
 }
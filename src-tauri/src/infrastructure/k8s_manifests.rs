@@ -0,0 +1,794 @@
+//! Kubernetes Manifest Auditor
+//!
+//! Evaluates the pod-security hardening checks against Kubernetes manifests:
+//! resources exported with `kubectl get -o yaml`, hand-written manifests, or
+//! `helm template` output. Each workload's pod template is checked and
+//! failures become findings against the existing check ids, grouped by file
+//! and by check.
+//!
+//! Documents that cannot be parsed or evaluated are reported back rather than
+//! failing the audit, so one bad template doesn't hide the rest of a chart.
+//! YAML that isn't a Kubernetes resource (no apiVersion or kind, such as
+//! `values.yaml`) is ignored, and unrendered Helm templates are skipped with
+//! a warning.
+
+use crate::infrastructure::k8s_hardening::*;
+use crate::infrastructure::models::*;
+use chrono::Utc;
+use serde_yaml::Value;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Checks that can be evaluated from a pod template
+pub(crate) const MANIFEST_CHECKS: [&str; 9] = [
+    CHECK_NON_ROOT,
+    CHECK_READ_ONLY_ROOT_FS,
+    CHECK_NO_PRIVILEGE_ESCALATION,
+    CHECK_NOT_PRIVILEGED,
+    CHECK_CAPABILITIES_DROPPED,
+    CHECK_HOST_NAMESPACES,
+    CHECK_RESOURCE_LIMITS,
+    CHECK_NO_HOST_PATH,
+    CHECK_NO_LATEST_TAG,
+];
+
+/// Audit manifest files (or directories of them) for pod-security issues
+pub fn audit_from_manifests(paths: Vec<String>) -> K8sManifestAudit {
+    let mut auditor = ManifestAuditor::new();
+    let mut files = Vec::new();
+
+    for path in &paths {
+        collect_manifest_files(Path::new(path), &mut files, &mut auditor.unparsed);
+    }
+
+    for file in &files {
+        match std::fs::read_to_string(file) {
+            Ok(text) => auditor.audit_text(file, &text),
+            Err(e) => auditor.unparsed.push(K8sUnparsedResource {
+                source: file.clone(),
+                document: None,
+                kind: None,
+                name: None,
+                reason: format!("Failed to read file: {}", e),
+            }),
+        }
+    }
+
+    auditor.finish(files)
+}
+
+/// Expand directories into the manifest files they contain
+fn collect_manifest_files(path: &Path, files: &mut Vec<String>, unparsed: &mut Vec<K8sUnparsedResource>) {
+    if path.is_dir() {
+        let mut entries: Vec<_> = match std::fs::read_dir(path) {
+            Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(e) => {
+                unparsed.push(K8sUnparsedResource {
+                    source: path.display().to_string(),
+                    document: None,
+                    kind: None,
+                    name: None,
+                    reason: format!("Failed to read directory: {}", e),
+                });
+                return;
+            }
+        };
+        entries.sort();
+
+        for entry in entries {
+            let is_manifest = entry
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| matches!(ext.to_lowercase().as_str(), "yaml" | "yml" | "json"))
+                .unwrap_or(false);
+
+            if entry.is_dir() || is_manifest {
+                collect_manifest_files(&entry, files, unparsed);
+            }
+        }
+    } else if path.is_file() {
+        files.push(path.display().to_string());
+    } else {
+        unparsed.push(K8sUnparsedResource {
+            source: path.display().to_string(),
+            document: None,
+            kind: None,
+            name: None,
+            reason: "File not found".to_string(),
+        });
+    }
+}
+
+/// Split a multi-document YAML stream on `---` separators
+///
+/// Done by hand rather than through the YAML deserializer so that a single
+/// malformed document (e.g. an unrendered Helm template) doesn't prevent the
+/// others from being read. Comment-only documents, such as the `# Source:`
+/// headers Helm emits for empty templates, are dropped.
+fn split_documents(text: &str) -> Vec<String> {
+    let mut documents = vec![String::new()];
+
+    for line in text.lines() {
+        if line == "---" || line.starts_with("--- ") || line.starts_with("---\t") {
+            documents.push(String::new());
+            continue;
+        }
+        if line == "..." {
+            continue;
+        }
+        if let Some(document) = documents.last_mut() {
+            document.push_str(line);
+            document.push('\n');
+        }
+    }
+
+    documents
+        .into_iter()
+        .filter(|doc| {
+            doc.lines().any(|line| {
+                let line = line.trim();
+                !line.is_empty() && !line.starts_with('#')
+            })
+        })
+        .collect()
+}
+
+/// Whether a document still holds Helm template actions
+///
+/// Actions on their own line (`{{- if ... }}`) or as unquoted values
+/// (`image: {{ .Values.image }}`) never survive `helm template`; quoted
+/// strings are left alone since rendered resources can legitimately carry
+/// Go templates, e.g. in alerting rules.
+fn is_unrendered_template(document: &str) -> bool {
+    document.lines().any(|line| {
+        let line = line.trim();
+        !line.starts_with('#') && (line.starts_with("{{") || line.contains(": {{") || line.contains("- {{"))
+    })
+}
+
+// ============================================================================
+// Auditor
+// ============================================================================
+
+struct ManifestAuditor {
+    audit_id: String,
+    checks: Vec<K8sHardeningCheck>,
+    workloads: Vec<K8sWorkload>,
+    findings: Vec<K8sHardeningFinding>,
+    /// File each finding was read from, by position in `findings`
+    finding_sources: Vec<String>,
+    unparsed: Vec<K8sUnparsedResource>,
+    skipped: Vec<K8sUnparsedResource>,
+}
+
+impl ManifestAuditor {
+    fn new() -> Self {
+        let checks = get_k8s_hardening_checks()
+            .into_iter()
+            .filter(|c| MANIFEST_CHECKS.contains(&c.id.as_str()))
+            .collect();
+
+        Self {
+            audit_id: Uuid::new_v4().to_string(),
+            checks,
+            workloads: Vec::new(),
+            findings: Vec::new(),
+            finding_sources: Vec::new(),
+            unparsed: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+
+    fn audit_text(&mut self, source: &str, text: &str) {
+        for (index, document) in split_documents(text).iter().enumerate() {
+            if is_unrendered_template(document) {
+                self.skipped.push(K8sUnparsedResource {
+                    source: source.to_string(),
+                    document: Some(index + 1),
+                    kind: None,
+                    name: None,
+                    reason: "Unrendered Helm template; run `helm template` and audit its output".to_string(),
+                });
+                continue;
+            }
+
+            match serde_yaml::from_str::<Value>(document) {
+                Ok(Value::Null) => {}
+                // Not a Kubernetes resource, e.g. values.yaml or Chart.yaml
+                Ok(value) if value["apiVersion"].as_str().is_none() || value["kind"].as_str().is_none() => {}
+                Ok(value) => self.audit_resource(&value, source, index + 1),
+                Err(e) => self.unparsed.push(K8sUnparsedResource {
+                    source: source.to_string(),
+                    document: Some(index + 1),
+                    kind: None,
+                    name: None,
+                    reason: format!("Invalid YAML: {}", e),
+                }),
+            }
+        }
+    }
+
+    fn audit_resource(&mut self, resource: &Value, source: &str, document: usize) {
+        let kind = resource["kind"].as_str().unwrap_or_default().to_string();
+
+        // `kubectl get -o yaml` wraps multiple resources in a List
+        if let Some(items) = resource["items"].as_sequence() {
+            if kind == "List" || kind.ends_with("List") {
+                for item in items {
+                    self.audit_resource(item, source, document);
+                }
+                return;
+            }
+        }
+
+        let pod_spec = match pod_template_spec(&kind, resource) {
+            Some(spec) => spec,
+            // Services, ConfigMaps etc. carry no pod template
+            None => return,
+        };
+
+        let name = resource["metadata"]["name"].as_str().map(str::to_string);
+        let namespace = resource["metadata"]["namespace"].as_str().map(str::to_string);
+
+        let unparsed = |reason: &str| K8sUnparsedResource {
+            source: source.to_string(),
+            document: Some(document),
+            kind: Some(kind.clone()),
+            name: name.clone(),
+            reason: reason.to_string(),
+        };
+
+        let name = match &name {
+            Some(name) => name.clone(),
+            None => {
+                self.unparsed.push(unparsed("Resource has no metadata.name"));
+                return;
+            }
+        };
+
+        let containers = pod_containers(pod_spec);
+        if containers.is_empty() {
+            self.unparsed.push(unparsed("No containers found in pod template"));
+            return;
+        }
+
+        let mut failed_checks = 0;
+        for check in &self.checks {
+            match evaluate_check(&check.id, pod_spec, &containers) {
+                Ok(()) => {}
+                Err(details) => {
+                    failed_checks += 1;
+                    self.findings.push(K8sHardeningFinding {
+                        id: Uuid::new_v4().to_string(),
+                        audit_id: self.audit_id.clone(),
+                        check_id: check.id.clone(),
+                        result: K8sCheckResult::Fail,
+                        severity: check.severity,
+                        resource_type: Some(kind.clone()),
+                        resource_name: Some(name.clone()),
+                        namespace: namespace.clone(),
+                        details: Some(details),
+                        found_at: Utc::now(),
+                    });
+                    self.finding_sources.push(source.to_string());
+                }
+            }
+        }
+
+        self.workloads.push(K8sWorkload {
+            kind,
+            name,
+            namespace,
+            source: source.to_string(),
+            containers: containers.iter().map(|c| container_name(c).to_string()).collect(),
+            failed_checks,
+        });
+    }
+
+    fn finish(self, files: Vec<String>) -> K8sManifestAudit {
+        let evaluated = self.workloads.len();
+        let failures_of = |check: &K8sHardeningCheck| self.findings.iter().filter(|f| f.check_id == check.id).count();
+
+        let by_check: Vec<K8sCheckFindings> = self
+            .checks
+            .iter()
+            .map(|check| K8sCheckFindings {
+                check_id: check.id.clone(),
+                title: check.title.clone(),
+                severity: check.severity,
+                passed: evaluated - failures_of(check),
+                failed: failures_of(check),
+                affected: self
+                    .findings
+                    .iter()
+                    .zip(&self.finding_sources)
+                    .filter(|(f, _)| f.check_id == check.id)
+                    .map(|(f, source)| {
+                        format!(
+                            "{}: {}/{}/{}",
+                            source,
+                            f.namespace.as_deref().unwrap_or("default"),
+                            f.resource_type.as_deref().unwrap_or_default(),
+                            f.resource_name.as_deref().unwrap_or_default()
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut by_file: Vec<K8sFileFindings> = Vec::new();
+        for workload in &self.workloads {
+            match by_file.iter_mut().find(|f| f.source == workload.source) {
+                Some(file) => file.workloads += 1,
+                None => by_file.push(K8sFileFindings { source: workload.source.clone(), workloads: 1, findings: Vec::new() }),
+            }
+        }
+        for (finding, source) in self.findings.iter().zip(&self.finding_sources) {
+            if let Some(file) = by_file.iter_mut().find(|f| f.source == *source) {
+                file.findings.push(finding.clone());
+            }
+        }
+
+        // Evaluations (workload x check) per category
+        let mut category_counts: Vec<(K8sHardeningCategory, usize, usize)> = Vec::new();
+        for check in &self.checks {
+            let failed = failures_of(check);
+            match category_counts.iter_mut().find(|(c, _, _)| *c == check.category) {
+                Some((_, passed_total, failed_total)) => {
+                    *passed_total += evaluated - failed;
+                    *failed_total += failed;
+                }
+                None => category_counts.push((check.category, evaluated - failed, failed)),
+            }
+        }
+        let category_results: Vec<K8sCategoryResult> = category_counts
+            .into_iter()
+            .map(|(category, passed, failed)| K8sCategoryResult {
+                category,
+                display_name: category.display_name().to_string(),
+                color: category.color().to_string(),
+                total: passed + failed,
+                passed,
+                failed,
+                score_percentage: if passed + failed > 0 { passed as f64 / (passed + failed) as f64 * 100.0 } else { 0.0 },
+            })
+            .collect();
+
+        let audited_at = Utc::now();
+        let severity_stats = manifest_severity_stats(&self.audit_id, audited_at, &self.checks, &by_check, evaluated);
+
+        let failed = self.findings.len();
+        let passed = evaluated * self.checks.len() - failed;
+        let critical_findings = self.findings.iter().filter(|f| f.severity == Severity::Critical).count();
+        let high_findings = self.findings.iter().filter(|f| f.severity == Severity::High).count();
+
+        K8sManifestAudit {
+            id: self.audit_id.clone(),
+            audited_at,
+            files,
+            workloads: self.workloads,
+            findings: self.findings,
+            unparsed: self.unparsed,
+            skipped: self.skipped,
+            by_file,
+            by_check,
+            severity_stats,
+            summary: K8sAuditSummary {
+                audit_id: self.audit_id,
+                total_checks: passed + failed,
+                passed,
+                failed,
+                warnings: 0,
+                critical_findings,
+                high_findings,
+                category_results,
+            },
+        }
+    }
+}
+
+/// Severity breakdown of the checks failed by at least one workload, in the
+/// same shape as [`k8s_severity_stats`]; with no workloads nothing was checked
+fn manifest_severity_stats(
+    audit_id: &str,
+    audited_at: chrono::DateTime<Utc>,
+    checks: &[K8sHardeningCheck],
+    by_check: &[K8sCheckFindings],
+    evaluated: usize,
+) -> K8sSeverityStats {
+    let count = |severity: Severity| by_check.iter().filter(|c| c.failed > 0 && c.severity == severity).count();
+    let failed = by_check.iter().filter(|c| c.failed > 0).count();
+
+    K8sSeverityStats {
+        audit_id: Some(audit_id.to_string()),
+        cluster_name: None,
+        audited_at: Some(audited_at),
+        total: checks.len(),
+        critical: count(Severity::Critical),
+        high: count(Severity::High),
+        medium: count(Severity::Medium),
+        low: count(Severity::Low),
+        info: count(Severity::Info),
+        passed: if evaluated > 0 { checks.len() - failed } else { 0 },
+        failed,
+        not_checked: if evaluated > 0 { 0 } else { checks.len() },
+    }
+}
+
+// ============================================================================
+// Pod Security Checks
+// ============================================================================
+
+/// Pod spec of a workload, or `None` for kinds without a pod template
+pub(crate) fn pod_template_spec<'v>(kind: &str, resource: &'v Value) -> Option<&'v Value> {
+    match kind {
+        "Pod" => Some(&resource["spec"]),
+        "Deployment" | "StatefulSet" | "DaemonSet" | "ReplicaSet" | "ReplicationController" | "Job" => {
+            Some(&resource["spec"]["template"]["spec"])
+        }
+        "CronJob" => Some(&resource["spec"]["jobTemplate"]["spec"]["template"]["spec"]),
+        _ => None,
+    }
+}
+
+/// Regular and init containers of a pod spec
+pub(crate) fn pod_containers(pod_spec: &Value) -> Vec<&Value> {
+    ["containers", "initContainers"]
+        .iter()
+        .filter_map(|key| pod_spec[*key].as_sequence())
+        .flatten()
+        .collect()
+}
+
+pub(crate) fn container_name(container: &Value) -> &str {
+    container["name"].as_str().unwrap_or("<unnamed>")
+}
+
+/// Evaluate one check against a workload; `Err` carries the failure details
+pub(crate) fn evaluate_check(check_id: &str, pod_spec: &Value, containers: &[&Value]) -> Result<(), String> {
+    let pod_context = &pod_spec["securityContext"];
+
+    match check_id {
+        CHECK_NON_ROOT => failing_containers(containers, "not guaranteed to run as non-root", |c| {
+            let context = &c["securityContext"];
+            let run_as_user = context["runAsUser"].as_i64().or_else(|| pod_context["runAsUser"].as_i64());
+            let run_as_non_root = context["runAsNonRoot"].as_bool().or_else(|| pod_context["runAsNonRoot"].as_bool());
+            match run_as_user {
+                Some(uid) => uid != 0,
+                None => run_as_non_root == Some(true),
+            }
+        }),
+        CHECK_READ_ONLY_ROOT_FS => failing_containers(containers, "with a writable root filesystem", |c| {
+            c["securityContext"]["readOnlyRootFilesystem"].as_bool() == Some(true)
+        }),
+        CHECK_NO_PRIVILEGE_ESCALATION => failing_containers(containers, "allowing privilege escalation", |c| {
+            let context = &c["securityContext"];
+            context["allowPrivilegeEscalation"].as_bool() == Some(false) && context["privileged"].as_bool() != Some(true)
+        }),
+        CHECK_NOT_PRIVILEGED => failing_containers(containers, "running privileged", |c| {
+            c["securityContext"]["privileged"].as_bool() != Some(true)
+        }),
+        CHECK_CAPABILITIES_DROPPED => failing_containers(containers, "not dropping ALL capabilities", |c| {
+            c["securityContext"]["capabilities"]["drop"]
+                .as_sequence()
+                .map(|drop| drop.iter().any(|cap| cap.as_str().map(|s| s.eq_ignore_ascii_case("ALL")).unwrap_or(false)))
+                .unwrap_or(false)
+        }),
+        CHECK_HOST_NAMESPACES => {
+            let shared: Vec<&str> = ["hostNetwork", "hostPID", "hostIPC"]
+                .into_iter()
+                .filter(|key| pod_spec[*key].as_bool() == Some(true))
+                .collect();
+            if shared.is_empty() {
+                Ok(())
+            } else {
+                Err(format!("Pod shares host namespaces: {}", shared.join(", ")))
+            }
+        }
+        CHECK_RESOURCE_LIMITS => failing_containers(containers, "without CPU and memory limits", |c| {
+            let limits = &c["resources"]["limits"];
+            !limits["cpu"].is_null() && !limits["memory"].is_null()
+        }),
+        CHECK_NO_HOST_PATH => {
+            let mounts: Vec<String> = pod_spec["volumes"]
+                .as_sequence()
+                .map(|volumes| {
+                    volumes
+                        .iter()
+                        .filter_map(|v| {
+                            let path = v["hostPath"]["path"].as_str()?;
+                            Some(format!("{} ({})", v["name"].as_str().unwrap_or("<unnamed>"), path))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            if mounts.is_empty() {
+                Ok(())
+            } else {
+                Err(format!("Pod mounts host paths: {}", mounts.join(", ")))
+            }
+        }
+        CHECK_NO_LATEST_TAG => failing_containers(containers, "using the latest or no image tag", |c| {
+            c["image"].as_str().map(is_pinned_image).unwrap_or(true)
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Whether an image reference names a digest or a tag other than latest
+fn is_pinned_image(image: &str) -> bool {
+    if image.contains('@') {
+        return true;
+    }
+    // A colon before the last slash belongs to a registry port
+    let name = image.rsplit('/').next().unwrap_or(image);
+    match name.split_once(':') {
+        Some((_, tag)) => tag != "latest",
+        None => false,
+    }
+}
+
+/// Fail with the names of containers that don't satisfy `passes`
+fn failing_containers(containers: &[&Value], problem: &str, passes: impl Fn(&Value) -> bool) -> Result<(), String> {
+    let failing: Vec<&str> = containers
+        .iter()
+        .filter(|c| !passes(c))
+        .map(|c| container_name(c))
+        .collect();
+
+    if failing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Containers {}: {}", problem, failing.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELM_OUTPUT: &str = r#"---
+# Source: app/templates/serviceaccount.yaml
+---
+# Source: app/templates/service.yaml
+apiVersion: v1
+kind: Service
+metadata:
+  name: web
+spec:
+  ports:
+    - port: 80
+---
+# Source: app/templates/deployment.yaml
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+  namespace: prod
+spec:
+  template:
+    spec:
+      hostNetwork: true
+      securityContext:
+        runAsNonRoot: true
+      containers:
+        - name: web
+          image: nginx:1.27
+          securityContext:
+            privileged: true
+        - name: sidecar
+          image: envoy:1.30
+          securityContext:
+            allowPrivilegeEscalation: false
+            readOnlyRootFilesystem: true
+            capabilities:
+              drop: ["ALL"]
+          resources:
+            limits:
+              cpu: 100m
+              memory: 128Mi
+---
+# Source: app/templates/job.yaml
+kind: Job
+metadata:
+  name: migrate
+spec:
+  template:
+    spec:
+      containers:
+        - name: migrate
+          image: {{ .Values.image }
+"#;
+
+    fn audit(text: &str) -> K8sManifestAudit {
+        let mut auditor = ManifestAuditor::new();
+        auditor.audit_text("chart.yaml", text);
+        auditor.finish(vec!["chart.yaml".to_string()])
+    }
+
+    fn failed_checks(audit: &K8sManifestAudit, name: &str) -> Vec<String> {
+        audit.findings.iter()
+            .filter(|f| f.resource_name.as_deref() == Some(name))
+            .map(|f| f.check_id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_helm_output() {
+        let audit = audit(HELM_OUTPUT);
+
+        assert_eq!(audit.workloads.len(), 1);
+        let workload = &audit.workloads[0];
+        assert_eq!(workload.namespace.as_deref(), Some("prod"));
+        assert_eq!(workload.containers, vec!["web", "sidecar"]);
+
+        let failed = failed_checks(&audit, "web");
+        assert!(failed.contains(&CHECK_NOT_PRIVILEGED.to_string()));
+        assert!(failed.contains(&CHECK_HOST_NAMESPACES.to_string()));
+        assert!(!failed.contains(&CHECK_NON_ROOT.to_string()));
+
+        let privileged = audit.findings.iter().find(|f| f.check_id == CHECK_NOT_PRIVILEGED).unwrap();
+        assert_eq!(privileged.severity, Severity::Critical);
+        assert_eq!(privileged.details.as_deref(), Some("Containers running privileged: web"));
+
+        // The unrendered job template is skipped with a warning, not fatal
+        assert!(audit.unparsed.is_empty());
+        assert_eq!(audit.skipped.len(), 1);
+        assert_eq!(audit.skipped[0].document, Some(3));
+        assert_eq!(audit.summary.total_checks, MANIFEST_CHECKS.len());
+    }
+
+    /// A pod passing every manifest check
+    const HARDENED_POD: &str = r#"apiVersion: v1
+kind: Pod
+metadata:
+  name: app
+  namespace: prod
+spec:
+  securityContext:
+    runAsNonRoot: true
+  containers:
+    - name: app
+      image: registry.local:5000/app:1.2.3
+      securityContext:
+        allowPrivilegeEscalation: false
+        readOnlyRootFilesystem: true
+        capabilities:
+          drop: ["ALL"]
+      resources:
+        limits:
+          cpu: 500m
+          memory: 256Mi
+"#;
+
+    #[test]
+    fn test_each_check_passes_and_fails() {
+        assert!(failed_checks(&audit(HARDENED_POD), "app").is_empty());
+
+        let cases: [(&str, &str, &str, &[&str]); 9] = [
+            (CHECK_NON_ROOT, "runAsNonRoot: true", "runAsNonRoot: false", &[]),
+            (CHECK_READ_ONLY_ROOT_FS, "readOnlyRootFilesystem: true", "readOnlyRootFilesystem: false", &[]),
+            (CHECK_NO_PRIVILEGE_ESCALATION, "allowPrivilegeEscalation: false", "allowPrivilegeEscalation: true", &[]),
+            (
+                CHECK_NOT_PRIVILEGED,
+                "allowPrivilegeEscalation: false",
+                "allowPrivilegeEscalation: false\n        privileged: true",
+                &[CHECK_NO_PRIVILEGE_ESCALATION],
+            ),
+            (CHECK_CAPABILITIES_DROPPED, r#"drop: ["ALL"]"#, r#"drop: ["NET_RAW"]"#, &[]),
+            (CHECK_HOST_NAMESPACES, "spec:\n  securityContext:", "spec:\n  hostIPC: true\n  securityContext:", &[]),
+            (CHECK_RESOURCE_LIMITS, "          memory: 256Mi\n", "", &[]),
+            (
+                CHECK_NO_HOST_PATH,
+                "          memory: 256Mi\n",
+                "          memory: 256Mi\n  volumes:\n    - name: docker\n      hostPath:\n        path: /var/run/docker.sock\n",
+                &[],
+            ),
+            (CHECK_NO_LATEST_TAG, "app:1.2.3", "app", &[]),
+        ];
+
+        for (check, from, to, also) in cases {
+            let manifest = HARDENED_POD.replace(from, to);
+            assert_ne!(manifest, HARDENED_POD, "fixture for {} didn't change", check);
+
+            let mut expected: Vec<String> = std::iter::once(check).chain(also.iter().copied()).map(str::to_string).collect();
+            let mut failed = failed_checks(&audit(&manifest), "app");
+            expected.sort();
+            failed.sort();
+            assert_eq!(failed, expected, "{}", check);
+        }
+
+        assert!(is_pinned_image("nginx@sha256:0123abcd"));
+        assert!(!is_pinned_image("nginx:latest"));
+        assert!(!is_pinned_image("registry.local:5000/nginx"));
+    }
+
+    #[test]
+    fn test_grouped_by_file_and_check() {
+        let dir = std::env::temp_dir().join(format!("optio-k8s-manifests-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("templates")).unwrap();
+        std::fs::write(dir.join("values.yaml"), "image:\n  tag: latest\nreplicas: 2\n").unwrap();
+        std::fs::write(dir.join("rendered.yaml"), HARDENED_POD.replace("app:1.2.3", "app:latest")).unwrap();
+        std::fs::write(
+            dir.join("templates").join("pod.yaml"),
+            "{{- if .Values.enabled }}\napiVersion: v1\nkind: Pod\n{{- end }}\n",
+        )
+        .unwrap();
+
+        let audit = audit_from_manifests(vec![dir.display().to_string()]);
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(audit.files.len(), 3);
+        assert!(audit.unparsed.is_empty(), "values.yaml is ignored: {:?}", audit.unparsed);
+        assert_eq!(audit.skipped.len(), 1);
+        assert!(audit.skipped[0].source.ends_with("pod.yaml"));
+
+        assert_eq!(audit.by_file.len(), 1);
+        assert!(audit.by_file[0].source.ends_with("rendered.yaml"));
+        assert_eq!(audit.by_file[0].findings.len(), 1);
+
+        let latest = audit.by_check.iter().find(|c| c.check_id == CHECK_NO_LATEST_TAG).unwrap();
+        assert_eq!((latest.passed, latest.failed), (0, 1));
+        assert!(latest.affected[0].ends_with("rendered.yaml: prod/Pod/app"));
+
+        assert_eq!(audit.severity_stats.total, MANIFEST_CHECKS.len());
+        assert_eq!(audit.severity_stats.failed, 1);
+        assert_eq!(audit.severity_stats.low, 1);
+        assert_eq!(audit.severity_stats.passed, MANIFEST_CHECKS.len() - 1);
+    }
+
+    #[test]
+    fn test_exported_list() {
+        let audit = audit(r#"
+apiVersion: v1
+kind: List
+items:
+  - kind: Pod
+    metadata:
+      name: hardened
+    spec:
+      securityContext:
+        runAsUser: 1000
+      containers:
+        - name: app
+          securityContext:
+            allowPrivilegeEscalation: false
+            readOnlyRootFilesystem: true
+            capabilities:
+              drop: ["ALL"]
+          resources:
+            limits:
+              cpu: "1"
+              memory: 1Gi
+  - kind: CronJob
+    metadata:
+      name: backup
+    spec:
+      jobTemplate:
+        spec:
+          template:
+            spec:
+              containers:
+                - name: backup
+                  securityContext:
+                    runAsUser: 0
+                    runAsNonRoot: true
+  - kind: DaemonSet
+    metadata:
+      name: empty
+    spec: {}
+"#);
+
+        assert_eq!(audit.workloads.len(), 2);
+        assert!(failed_checks(&audit, "hardened").is_empty());
+        assert!(failed_checks(&audit, "backup").contains(&CHECK_NON_ROOT.to_string()));
+        assert_eq!(audit.unparsed[0].name.as_deref(), Some("empty"));
+    }
+
+    #[test]
+    fn test_missing_file_reported() {
+        let audit = audit_from_manifests(vec!["/nonexistent/manifest.yaml".to_string()]);
+        assert!(audit.files.is_empty());
+        assert_eq!(audit.unparsed.len(), 1);
+    }
+}
@@ -1,14 +1,23 @@
 //! Infrastructure & Migration Module
 //!
 //! Provides cloud readiness assessment, Kubernetes hardening audits,
-//! and FinOps cost calculations for migration planning.
+//! and FinOps cost calculations for migration planning, right-sized from
+//! imported server utilization where it exists.
 
 pub mod models;
 pub mod cloud_readiness;
 pub mod k8s_hardening;
+pub mod k8s_manifests;
+pub mod k8s_cluster;
 pub mod finops;
+pub mod pricing;
+pub mod utilization;
 
 pub use models::*;
 pub use cloud_readiness::*;
 pub use k8s_hardening::*;
+pub use k8s_manifests::*;
+pub use k8s_cluster::*;
 pub use finops::*;
+pub use pricing::*;
+pub use utilization::*;
@@ -179,7 +179,48 @@ pub struct ReadinessCheckAssessment {
     pub assessed_by: String,
 }
 
-/// Cloud readiness assessment
+/// Consultant's answer to a readiness checklist item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReadinessAnswer {
+    Yes,
+    Partial,
+    No,
+    NotApplicable,
+}
+
+impl ReadinessAnswer {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReadinessAnswer::Yes => "Yes",
+            ReadinessAnswer::Partial => "Partial",
+            ReadinessAnswer::No => "No",
+            ReadinessAnswer::NotApplicable => "N/A",
+        }
+    }
+
+    /// Credit towards readiness (0-1); None for items that don't apply
+    pub fn score(&self) -> Option<f64> {
+        match self {
+            ReadinessAnswer::Yes => Some(1.0),
+            ReadinessAnswer::Partial => Some(0.5),
+            ReadinessAnswer::No => Some(0.0),
+            ReadinessAnswer::NotApplicable => None,
+        }
+    }
+}
+
+/// Answer recorded against one checklist item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessItemAnswer {
+    pub item_id: String,
+    pub answer: ReadinessAnswer,
+    pub notes: Option<String>,
+    pub answered_at: DateTime<Utc>,
+}
+
+/// Cloud readiness assessment, one per client
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CloudReadinessAssessment {
@@ -188,10 +229,59 @@ pub struct CloudReadinessAssessment {
     pub name: String,
     pub target_provider: CloudProvider,
     pub target_date: Option<DateTime<Utc>>,
+    /// Answers by checklist item; unanswered items are absent
+    pub answers: Vec<ReadinessItemAnswer>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Maturity tier for an overall readiness score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReadinessTier {
+    NotReady,
+    Emerging,
+    Ready,
+    Optimized,
+}
+
+impl ReadinessTier {
+    /// Tier for a 0-100 score
+    pub fn from_score(score: f64) -> Self {
+        if score >= 85.0 {
+            ReadinessTier::Optimized
+        } else if score >= 65.0 {
+            ReadinessTier::Ready
+        } else if score >= 40.0 {
+            ReadinessTier::Emerging
+        } else {
+            ReadinessTier::NotReady
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReadinessTier::NotReady => "Not Ready",
+            ReadinessTier::Emerging => "Emerging",
+            ReadinessTier::Ready => "Ready",
+            ReadinessTier::Optimized => "Optimized",
+        }
+    }
+}
+
+/// Weighted readiness score of an assessment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessScore {
+    pub assessment_id: String,
+    /// Weighted score across categories (0-100)
+    pub overall_score: f64,
+    pub tier: ReadinessTier,
+    pub category_scores: Vec<CategoryReadinessScore>,
+    pub total_items: usize,
+    pub answered_items: usize,
+}
+
 /// Summary of readiness assessment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -215,6 +305,8 @@ pub struct CategoryReadinessScore {
     pub total: usize,
     pub completed: usize,
     pub percentage: f64,
+    /// Relative weight of the category in the overall score
+    pub weight: f64,
 }
 
 // ============================================================================
@@ -307,6 +399,10 @@ impl Severity {
             Severity::Info => "#64748b",
         }
     }
+
+    pub fn all() -> Vec<Severity> {
+        vec![Severity::Critical, Severity::High, Severity::Medium, Severity::Low, Severity::Info]
+    }
 }
 
 /// A K8s hardening check
@@ -351,18 +447,84 @@ pub struct K8sHardeningFinding {
     pub found_at: DateTime<Utc>,
 }
 
-/// K8s hardening audit
+/// Status recorded for a check in a K8s hardening audit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum K8sCheckStatus {
+    Pass,
+    Fail,
+    Warn,
+    NotApplicable,
+    NotChecked,
+}
+
+impl K8sCheckStatus {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            K8sCheckStatus::Pass => "Pass",
+            K8sCheckStatus::Fail => "Fail",
+            K8sCheckStatus::Warn => "Warning",
+            K8sCheckStatus::NotApplicable => "N/A",
+            K8sCheckStatus::NotChecked => "Not checked",
+        }
+    }
+
+    /// Warnings count as failing: the check isn't met
+    pub fn is_failing(&self) -> bool {
+        matches!(self, K8sCheckStatus::Fail | K8sCheckStatus::Warn)
+    }
+}
+
+/// Recorded result of one check in a K8s hardening audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sCheckResultData {
+    pub check_id: String,
+    pub status: K8sCheckStatus,
+    pub notes: Option<String>,
+    /// Where the supporting evidence lives (file, ticket, screenshot)
+    pub evidence_ref: Option<String>,
+    #[serde(default)]
+    pub affected_resources: Vec<String>,
+}
+
+/// K8s hardening audit of one cluster
+///
+/// A client can have audits of several clusters; the name and optional
+/// label tell them apart. Re-auditing a cluster records a new audit so
+/// earlier ones stay comparable.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct K8sHardeningAudit {
     pub id: String,
     pub client_id: String,
     pub cluster_name: String,
+    /// Free-form label such as "production" or "eu-west"
+    pub cluster_label: Option<String>,
     pub cluster_version: Option<String>,
     pub context_name: Option<String>,
-    pub started_at: DateTime<Utc>,
-    pub completed_at: Option<DateTime<Utc>>,
-    pub status: AuditStatus,
+    pub audited_by: Option<String>,
+    pub audited_at: DateTime<Utc>,
+    /// Results by check id; checks without a result were not checked
+    pub results: Vec<K8sCheckResultData>,
+}
+
+impl K8sHardeningAudit {
+    /// Cluster name with its label, if any
+    pub fn cluster_display_name(&self) -> String {
+        match &self.cluster_label {
+            Some(label) => format!("{} ({})", self.cluster_name, label),
+            None => self.cluster_name.clone(),
+        }
+    }
+
+    pub fn status_of(&self, check_id: &str) -> K8sCheckStatus {
+        self.results
+            .iter()
+            .find(|r| r.check_id == check_id)
+            .map(|r| r.status)
+            .unwrap_or(K8sCheckStatus::NotChecked)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -400,6 +562,188 @@ pub struct K8sCategoryResult {
     pub score_percentage: f64,
 }
 
+/// Severity breakdown of the hardening checks, or of one audit's failing checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sSeverityStats {
+    /// Audit the counts come from; `None` counts the whole checklist
+    pub audit_id: Option<String>,
+    pub cluster_name: Option<String>,
+    pub audited_at: Option<DateTime<Utc>>,
+    pub total: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub info: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub not_checked: usize,
+}
+
+/// A check whose status was compared between two audits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sCheckChange {
+    pub check_id: String,
+    pub title: String,
+    pub severity: Severity,
+    pub previous: K8sCheckStatus,
+    pub current: K8sCheckStatus,
+}
+
+/// Audit comparison for one check category, most severe checks first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sCategoryComparison {
+    pub category: K8sHardeningCategory,
+    pub display_name: String,
+    pub newly_passing: Vec<K8sCheckChange>,
+    pub newly_failing: Vec<K8sCheckChange>,
+    pub unchanged: Vec<K8sCheckChange>,
+    /// Other status changes, e.g. from failing to not checked
+    pub other_changes: Vec<K8sCheckChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sSeverityComparison {
+    pub severity: Severity,
+    pub newly_passing: usize,
+    pub newly_failing: usize,
+    pub unchanged: usize,
+}
+
+/// Change between a baseline audit and a re-audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sAuditComparison {
+    pub baseline_id: String,
+    pub current_id: String,
+    pub baseline_cluster: String,
+    pub current_cluster: String,
+    pub newly_passing: usize,
+    pub newly_failing: usize,
+    pub unchanged: usize,
+    pub categories: Vec<K8sCategoryComparison>,
+    pub severities: Vec<K8sSeverityComparison>,
+}
+
+/// A workload found in a manifest and evaluated against the pod-security checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sWorkload {
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    /// File the workload was read from
+    pub source: String,
+    pub containers: Vec<String>,
+    pub failed_checks: usize,
+}
+
+/// A manifest document that could not be evaluated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sUnparsedResource {
+    pub source: String,
+    /// 1-based document position within a multi-document file
+    pub document: Option<usize>,
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+/// Findings of the workloads read from one manifest file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sFileFindings {
+    pub source: String,
+    pub workloads: usize,
+    pub findings: Vec<K8sHardeningFinding>,
+}
+
+/// How the audited workloads fared against one check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sCheckFindings {
+    pub check_id: String,
+    pub title: String,
+    pub severity: Severity,
+    pub passed: usize,
+    pub failed: usize,
+    /// Failing workloads as "file: namespace/Kind/name"
+    pub affected: Vec<String>,
+}
+
+/// Result of auditing exported or rendered Kubernetes manifests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sManifestAudit {
+    pub id: String,
+    pub audited_at: DateTime<Utc>,
+    pub files: Vec<String>,
+    pub workloads: Vec<K8sWorkload>,
+    pub findings: Vec<K8sHardeningFinding>,
+    pub unparsed: Vec<K8sUnparsedResource>,
+    /// Unrendered Helm templates, left out with a warning
+    #[serde(default)]
+    pub skipped: Vec<K8sUnparsedResource>,
+    #[serde(default)]
+    pub by_file: Vec<K8sFileFindings>,
+    #[serde(default)]
+    pub by_check: Vec<K8sCheckFindings>,
+    /// Failing checks by severity, in the shape of an audit's severity stats
+    pub severity_stats: K8sSeverityStats,
+    pub summary: K8sAuditSummary,
+}
+
+/// Outcome of a check evaluated against a live cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum K8sClusterCheckOutcome {
+    Pass,
+    Fail,
+    /// Can't be decided from workload specs, or the resources couldn't be read
+    Manual,
+}
+
+/// One checklist item evaluated against a live cluster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sClusterCheck {
+    pub check_id: String,
+    pub title: String,
+    pub severity: Severity,
+    pub outcome: K8sClusterCheckOutcome,
+    /// Offending workloads as "namespace/Kind/name: details"
+    pub evidence: Vec<String>,
+    /// Why the check couldn't be fully evaluated (kubectl missing, RBAC denied...)
+    pub error: Option<String>,
+}
+
+/// A resource type kubectl could not list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sResourceError {
+    pub resource: String,
+    pub namespace: Option<String>,
+    pub error: String,
+}
+
+/// Result of auditing a live cluster through kubectl
+///
+/// `audit` is the stored hardening audit; manual checks are left unchecked
+/// in it so they can be completed through the checklist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct K8sAuditResult {
+    pub audit: K8sHardeningAudit,
+    pub checks: Vec<K8sClusterCheck>,
+    pub workloads: usize,
+    pub resource_errors: Vec<K8sResourceError>,
+}
+
 // ============================================================================
 // FinOps Calculator Models
 // ============================================================================
@@ -519,3 +863,66 @@ pub struct ProviderEstimate {
     pub reserved_annual: f64,
     pub savings_vs_onprem_percent: f64,
 }
+
+/// Pricing commitment used for multi-year projections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CommitmentStrategy {
+    OnDemand,
+    #[serde(rename = "RESERVED_1YR")]
+    Reserved1Yr,
+    #[serde(rename = "RESERVED_3YR")]
+    Reserved3Yr,
+    SavingsPlan,
+}
+
+impl CommitmentStrategy {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CommitmentStrategy::OnDemand => "On-Demand",
+            CommitmentStrategy::Reserved1Yr => "1-Year Reserved",
+            CommitmentStrategy::Reserved3Yr => "3-Year Reserved",
+            CommitmentStrategy::SavingsPlan => "Savings Plan",
+        }
+    }
+}
+
+/// Expected annual growth for one resource type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceGrowthRate {
+    pub resource_type: ResourceType,
+    pub annual_rate_percent: f64,
+}
+
+/// One year of a TCO projection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcoYear {
+    pub year: u32,
+    pub on_prem_cost: f64,
+    pub cloud_cost: f64,
+    /// One-off migration spend, charged in year 1
+    pub migration_cost: f64,
+    pub net_savings: f64,
+    pub cumulative_savings: f64,
+}
+
+/// Multi-year on-premises vs cloud cost projection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcoProjection {
+    pub id: String,
+    pub client_id: String,
+    pub provider: CloudProvider,
+    pub migration_strategy: MigrationStrategy,
+    pub commitment: CommitmentStrategy,
+    pub horizon_years: u32,
+    pub years: Vec<TcoYear>,
+    pub total_on_prem_cost: f64,
+    pub total_cloud_cost: f64,
+    pub cumulative_savings: f64,
+    /// First month in which cumulative savings cover the migration cost
+    pub break_even_month: Option<u32>,
+    pub assumptions: Vec<String>,
+}
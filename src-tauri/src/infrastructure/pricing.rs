@@ -0,0 +1,395 @@
+//! Pricing Profiles
+//!
+//! Region-specific or negotiated cloud rates that override the built-in
+//! FinOps list prices. Every rate is optional: anything a profile leaves
+//! unset falls back to the provider default, so a vendor quote that only
+//! covers compute and storage is still usable.
+
+use super::models::CloudProvider;
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::repository::parse_datetime;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Initialize pricing profile schema
+pub fn init_pricing_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Custom cloud rates (NULL rate = use the built-in default)
+        CREATE TABLE IF NOT EXISTS pricing_profiles (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            region TEXT NOT NULL,
+            compute_per_vcpu REAL,
+            memory_per_gb REAL,
+            storage_per_gb_month REAL,
+            egress_per_gb REAL,
+            managed_db_multiplier REAL,
+            k8s_cluster_base REAL,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_pricing_profiles_provider ON pricing_profiles(provider);
+    "#)?;
+
+    tracing::info!("Pricing profile schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Custom rates for one provider and region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingProfile {
+    pub id: String,
+    pub name: String,
+    pub provider: CloudProvider,
+    /// Free-form region label (e.g. "eu-west-2", "EA discount")
+    pub region: String,
+    /// Per vCPU-hour
+    pub compute_per_vcpu: Option<f64>,
+    /// Per GB-hour of memory
+    pub memory_per_gb: Option<f64>,
+    pub storage_per_gb_month: Option<f64>,
+    pub egress_per_gb: Option<f64>,
+    /// Premium applied to compute for managed databases
+    pub managed_db_multiplier: Option<f64>,
+    /// Per hour for a managed Kubernetes control plane
+    pub k8s_cluster_base: Option<f64>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PricingProfile {
+    /// Each rate with its field name, in display order
+    pub fn rates(&self) -> [(&'static str, Option<f64>); 6] {
+        [
+            ("compute_per_vcpu", self.compute_per_vcpu),
+            ("memory_per_gb", self.memory_per_gb),
+            ("storage_per_gb_month", self.storage_per_gb_month),
+            ("egress_per_gb", self.egress_per_gb),
+            ("managed_db_multiplier", self.managed_db_multiplier),
+            ("k8s_cluster_base", self.k8s_cluster_base),
+        ]
+    }
+
+    /// Rates the profile leaves to the built-in defaults
+    pub fn unset_rates(&self) -> Vec<&'static str> {
+        self.rates()
+            .into_iter()
+            .filter(|(_, value)| value.is_none())
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Check the profile has a name and no negative or non-numeric rates
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Pricing profile name is required".to_string());
+        }
+        for (name, value) in self.rates() {
+            if let Some(value) = value {
+                if !value.is_finite() || value < 0.0 {
+                    return Err(format!("{} must be a non-negative number, got {}", name, value));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Profiles loaded from a CSV file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingImportSummary {
+    pub profiles: Vec<PricingProfile>,
+    /// Rows that were skipped, with the reason
+    pub warnings: Vec<String>,
+}
+
+/// Parse a provider name as used in the API ("AWS", "azure", ...)
+pub fn parse_pricing_provider(s: &str) -> Result<CloudProvider, String> {
+    serde_json::from_value(serde_json::Value::String(s.trim().to_uppercase()))
+        .map_err(|_| format!("Unknown cloud provider: {}", s))
+}
+
+pub(crate) fn provider_key(provider: &CloudProvider) -> OptioResult<String> {
+    match serde_json::to_value(provider)? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Err(OptioError::Database(format!("Unexpected provider value: {}", other))),
+    }
+}
+
+// ============================================================================
+// CSV Import
+// ============================================================================
+
+/// Parse pricing profiles from CSV, one profile per row
+///
+/// The header row names the columns: `provider` is required, and `name`,
+/// `region`, `notes` and the six rate columns are optional and may come in
+/// any order. Blank rate cells are left unset; currency symbols and
+/// thousands separators are stripped. Bad rows are skipped with a warning.
+pub fn parse_pricing_csv(data: &str) -> Result<PricingImportSummary, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data.as_bytes());
+
+    let columns: HashMap<String, usize> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (h.to_lowercase().replace([' ', '-'], "_"), i))
+        .collect();
+    if !columns.contains_key("provider") {
+        return Err("CSV must have a \"provider\" column".to_string());
+    }
+
+    let now = Utc::now();
+    let mut profiles = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (index, record) in reader.records().enumerate() {
+        // Header is line 1
+        let line = index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                warnings.push(format!("Line {}: {}", line, e));
+                continue;
+            }
+        };
+        match parse_pricing_row(&record, &columns, now) {
+            Ok(profile) => profiles.push(profile),
+            Err(e) => warnings.push(format!("Line {}: {}", line, e)),
+        }
+    }
+
+    if profiles.is_empty() {
+        let detail = warnings.first().map(|w| format!(" ({})", w)).unwrap_or_default();
+        return Err(format!("No pricing profiles found in CSV{}", detail));
+    }
+
+    Ok(PricingImportSummary { profiles, warnings })
+}
+
+/// One CSV row as a profile; columns are looked up by normalized header
+fn parse_pricing_row(
+    record: &csv::StringRecord,
+    columns: &HashMap<String, usize>,
+    now: DateTime<Utc>,
+) -> Result<PricingProfile, String> {
+    let cell = |column: &str| {
+        columns
+            .get(column)
+            .and_then(|&i| record.get(i))
+            .filter(|value| !value.is_empty())
+    };
+    let rate = |column: &str| -> Result<Option<f64>, String> {
+        match cell(column) {
+            Some(value) => value
+                .replace(['$', '€', '£', ','], "")
+                .parse::<f64>()
+                .map(Some)
+                .map_err(|_| format!("invalid {} \"{}\"", column, value)),
+            None => Ok(None),
+        }
+    };
+
+    let provider = parse_pricing_provider(cell("provider").unwrap_or_default())?;
+    let region = cell("region").unwrap_or("Default").to_string();
+    let profile = PricingProfile {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: match cell("name") {
+            Some(name) => name.to_string(),
+            None => format!("{} {}", provider_key(&provider).map_err(|e| e.to_string())?, region),
+        },
+        provider,
+        region,
+        compute_per_vcpu: rate("compute_per_vcpu")?,
+        memory_per_gb: rate("memory_per_gb")?,
+        storage_per_gb_month: rate("storage_per_gb_month")?,
+        egress_per_gb: rate("egress_per_gb")?,
+        managed_db_multiplier: rate("managed_db_multiplier")?,
+        k8s_cluster_base: rate("k8s_cluster_base")?,
+        notes: cell("notes").map(String::from),
+        created_at: now,
+        updated_at: now,
+    };
+    profile.validate()?;
+
+    Ok(profile)
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Pricing profile repository
+pub struct PricingProfileRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> PricingProfileRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        PricingProfileRepository { db }
+    }
+
+    /// Insert or replace a profile
+    pub fn save(&self, profile: &PricingProfile) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO pricing_profiles
+               (id, name, provider, region, compute_per_vcpu, memory_per_gb, storage_per_gb_month,
+                egress_per_gb, managed_db_multiplier, k8s_cluster_base, notes, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"#,
+            params![
+                profile.id,
+                profile.name,
+                provider_key(&profile.provider)?,
+                profile.region,
+                profile.compute_per_vcpu,
+                profile.memory_per_gb,
+                profile.storage_per_gb_month,
+                profile.egress_per_gb,
+                profile.managed_db_multiplier,
+                profile.k8s_cluster_base,
+                profile.notes,
+                profile.created_at.to_rfc3339(),
+                profile.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<PricingProfile>> {
+        let sql = format!("SELECT {} FROM pricing_profiles WHERE id = ?1", PROFILE_COLUMNS);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    /// All profiles, by provider then name
+    pub fn list(&self) -> OptioResult<Vec<PricingProfile>> {
+        let sql = format!("SELECT {} FROM pricing_profiles ORDER BY provider, name", PROFILE_COLUMNS);
+        self.query(&sql, [])
+    }
+
+    pub fn list_by_provider(&self, provider: &CloudProvider) -> OptioResult<Vec<PricingProfile>> {
+        let sql = format!("SELECT {} FROM pricing_profiles WHERE provider = ?1 ORDER BY name", PROFILE_COLUMNS);
+        self.query(&sql, params![provider_key(provider)?])
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM pricing_profiles WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<PricingProfile>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let profiles = stmt.query_map(query_params, |row| Ok(parse_profile_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(profiles)
+    }
+}
+
+const PROFILE_COLUMNS: &str = "id, name, provider, region, compute_per_vcpu, memory_per_gb, \
+    storage_per_gb_month, egress_per_gb, managed_db_multiplier, k8s_cluster_base, notes, created_at, updated_at";
+
+fn parse_profile_row(row: &rusqlite::Row) -> OptioResult<PricingProfile> {
+    let provider_str: String = row.get(2)?;
+
+    Ok(PricingProfile {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        provider: parse_pricing_provider(&provider_str).map_err(OptioError::Database)?,
+        region: row.get(3)?,
+        compute_per_vcpu: row.get(4)?,
+        memory_per_gb: row.get(5)?,
+        storage_per_gb_month: row.get(6)?,
+        egress_per_gb: row.get(7)?,
+        managed_db_multiplier: row.get(8)?,
+        k8s_cluster_base: row.get(9)?,
+        notes: row.get(10)?,
+        created_at: parse_datetime(&row.get::<_, String>(11)?)?,
+        updated_at: parse_datetime(&row.get::<_, String>(12)?)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_parse_pricing_csv() {
+        let csv = "Name,Provider,Region,Compute per vCPU,Storage_per_GB_month,Notes\n\
+                   EA quote,aws,eu-west-2,$0.031,0.08,Signed 2026\n\
+                   ,azure,uksouth,,0.09,\n\
+                   Bad,oracle,,,,\n\
+                   Typo,gcp,,cheap,,\n";
+        let summary = parse_pricing_csv(csv).unwrap();
+
+        assert_eq!(summary.profiles.len(), 2);
+        assert_eq!(summary.warnings.len(), 2);
+        assert!(summary.warnings[0].starts_with("Line 4"));
+
+        let quote = &summary.profiles[0];
+        assert_eq!(quote.compute_per_vcpu, Some(0.031));
+        assert_eq!(quote.storage_per_gb_month, Some(0.08));
+        assert_eq!(quote.notes.as_deref(), Some("Signed 2026"));
+        assert_eq!(quote.unset_rates(), vec!["memory_per_gb", "egress_per_gb", "managed_db_multiplier", "k8s_cluster_base"]);
+
+        let azure = &summary.profiles[1];
+        assert_eq!(azure.name, "AZURE uksouth");
+        assert_eq!(azure.compute_per_vcpu, None);
+
+        assert!(parse_pricing_csv("region,compute_per_vcpu\neu,0.1\n").is_err());
+    }
+
+    #[test]
+    fn test_pricing_profile_repository() {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        init_pricing_schema(&db).unwrap();
+        let repo = PricingProfileRepository::new(&db);
+
+        let mut profile = parse_pricing_csv("provider,region,egress_per_gb\ngcp,europe-west2,0.05\n")
+            .unwrap()
+            .profiles
+            .remove(0);
+        repo.save(&profile).unwrap();
+
+        let stored = repo.get(&profile.id).unwrap().unwrap();
+        assert_eq!(stored.provider, profile.provider);
+        assert_eq!(stored.egress_per_gb, Some(0.05));
+        assert_eq!(stored.memory_per_gb, None);
+        assert_eq!(repo.list_by_provider(&profile.provider).unwrap().len(), 1);
+
+        profile.egress_per_gb = Some(-1.0);
+        assert!(profile.validate().is_err());
+
+        assert!(repo.delete(&profile.id).unwrap());
+        assert!(repo.list().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,495 @@
+//! Server Utilization
+//!
+//! Per-server CPU and memory metrics imported from a CSV export (vCenter,
+//! RVTools and most monitoring tools produce one), stored on the matching
+//! inventory asset under `metadata.utilization`. The FinOps analysis sizes
+//! measured servers from their p95 load plus headroom instead of from what
+//! they are provisioned with today.
+//!
+//! Rows are matched to assets by IP address, then by hostname (ignoring case
+//! and any domain suffix). Rows that match nothing come back with the closest
+//! asset names as suggestions.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::network::models::Asset;
+use crate::network::repository::AssetRepository;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Key under which utilization is kept in asset metadata
+pub const UTILIZATION_METADATA_KEY: &str = "utilization";
+/// Capacity added on top of the p95 load when sizing
+pub const SIZING_HEADROOM: f64 = 0.30;
+/// vCPU counts instance families are commonly offered in
+const VCPU_STEPS: [u32; 11] = [1, 2, 4, 8, 12, 16, 24, 32, 48, 64, 96];
+/// Memory sizes (GB) instance families are commonly offered in
+const MEMORY_STEPS_GB: [f64; 12] = [1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 48.0, 64.0, 96.0, 128.0, 192.0, 256.0];
+/// Suggestions offered for a row that matched no asset
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Measured load of one server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerUtilization {
+    pub avg_cpu_percent: f64,
+    pub p95_cpu_percent: f64,
+    pub avg_memory_percent: f64,
+    /// Falls back to the average when the export has no p95 column
+    pub p95_memory_percent: Option<f64>,
+    pub provisioned_vcpus: u32,
+    pub provisioned_memory_gb: f64,
+    pub imported_at: DateTime<Utc>,
+}
+
+impl ServerUtilization {
+    /// vCPUs and memory this server needs, from its p95 load plus headroom
+    pub fn right_size(&self) -> (u32, f64) {
+        size_from_utilization(
+            self.provisioned_vcpus,
+            self.provisioned_memory_gb,
+            self.p95_cpu_percent,
+            self.p95_memory_percent.unwrap_or(self.avg_memory_percent),
+        )
+    }
+
+    /// Utilization stored on an asset, if any
+    pub fn from_asset(asset: &Asset) -> Option<Self> {
+        asset
+            .metadata
+            .as_ref()?
+            .get(UTILIZATION_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+/// Size a server from its peak load: p95 usage plus [`SIZING_HEADROOM`],
+/// rounded up to the next common vCPU count and memory size
+///
+/// Busy servers can come out larger than they are today; anything past the
+/// largest step stays at that step.
+pub fn size_from_utilization(
+    provisioned_vcpus: u32,
+    provisioned_memory_gb: f64,
+    p95_cpu_percent: f64,
+    p95_memory_percent: f64,
+) -> (u32, f64) {
+    let needed_vcpus = provisioned_vcpus as f64 * p95_cpu_percent.clamp(0.0, 100.0) / 100.0 * (1.0 + SIZING_HEADROOM);
+    let needed_memory = provisioned_memory_gb * p95_memory_percent.clamp(0.0, 100.0) / 100.0 * (1.0 + SIZING_HEADROOM);
+
+    let vcpus = VCPU_STEPS
+        .iter()
+        .copied()
+        .find(|step| *step as f64 >= needed_vcpus)
+        .unwrap_or(VCPU_STEPS[VCPU_STEPS.len() - 1]);
+    let memory_gb = MEMORY_STEPS_GB
+        .iter()
+        .copied()
+        .find(|step| *step >= needed_memory)
+        .unwrap_or(MEMORY_STEPS_GB[MEMORY_STEPS_GB.len() - 1]);
+    (vcpus, memory_gb)
+}
+
+/// One parsed CSV row
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtilizationRow {
+    pub hostname: Option<String>,
+    pub ip_address: Option<String>,
+    pub utilization: ServerUtilization,
+}
+
+impl UtilizationRow {
+    /// How the row names its server in messages
+    fn label(&self) -> &str {
+        self.hostname.as_deref().or(self.ip_address.as_deref()).unwrap_or("?")
+    }
+}
+
+/// A row that matched no asset, with the closest asset names
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmatchedUtilizationRow {
+    pub line: usize,
+    pub server: String,
+    pub suggestions: Vec<String>,
+}
+
+/// Outcome of a utilization import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtilizationImportSummary {
+    /// Assets that now carry utilization
+    pub matched: usize,
+    pub unmatched: Vec<UnmatchedUtilizationRow>,
+    /// Rows that were skipped, with the reason
+    pub warnings: Vec<String>,
+}
+
+/// Accepted header names for each column, after normalization
+const HOSTNAME_COLUMNS: [&str; 5] = ["hostname", "host", "vm", "server", "name"];
+const IP_COLUMNS: [&str; 4] = ["ip", "ip_address", "primary_ip_address", "ipaddress"];
+const AVG_CPU_COLUMNS: [&str; 4] = ["avg_cpu", "avg_cpu_percent", "cpu_avg", "average_cpu"];
+const P95_CPU_COLUMNS: [&str; 4] = ["p95_cpu", "p95_cpu_percent", "cpu_p95", "95th_percentile_cpu"];
+const AVG_MEMORY_COLUMNS: [&str; 6] = ["avg_memory", "avg_memory_percent", "avg_mem", "avg_mem_percent", "memory_avg", "average_memory"];
+const P95_MEMORY_COLUMNS: [&str; 4] = ["p95_memory", "p95_memory_percent", "p95_mem", "memory_p95"];
+const VCPU_COLUMNS: [&str; 6] = ["provisioned_vcpu", "provisioned_vcpus", "vcpu", "vcpus", "cpus", "num_cpu"];
+const MEMORY_GB_COLUMNS: [&str; 5] = ["provisioned_ram", "provisioned_ram_gb", "provisioned_memory_gb", "memory_gb", "ram_gb"];
+/// RVTools reports memory in MB
+const MEMORY_MB_COLUMNS: [&str; 3] = ["memory", "memory_mb", "provisioned_memory_mb"];
+
+/// Header as a lookup key: lowercase, with runs of other characters as "_"
+/// and "%" spelled out, so "Avg CPU %" becomes "avg_cpu_percent"
+fn normalize_header(header: &str) -> String {
+    let spelled = header.trim().to_lowercase().replace('%', " percent");
+    spelled
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Parse a utilization CSV into rows with their line numbers
+///
+/// Columns are found by header name in any order. A row needs a hostname or
+/// IP, p95 CPU, average memory and provisioned vCPU and memory; average CPU
+/// defaults to the p95 figure. Percent signs and thousands separators are
+/// stripped. Bad rows are skipped with a warning.
+pub fn parse_utilization_csv(data: &str) -> Result<(Vec<(usize, UtilizationRow)>, Vec<String>), String> {
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data.as_bytes());
+
+    let columns: HashMap<String, usize> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (normalize_header(h), i))
+        .collect();
+    let find = |names: &[&str]| names.iter().find_map(|name| columns.get(*name).copied());
+
+    let hostname = find(&HOSTNAME_COLUMNS);
+    let ip = find(&IP_COLUMNS);
+    if hostname.is_none() && ip.is_none() {
+        return Err("CSV must have a hostname or IP address column".to_string());
+    }
+    let layout = ColumnLayout {
+        hostname,
+        ip,
+        avg_cpu: find(&AVG_CPU_COLUMNS),
+        p95_cpu: find(&P95_CPU_COLUMNS).ok_or("CSV must have a p95 CPU column")?,
+        avg_memory: find(&AVG_MEMORY_COLUMNS).ok_or("CSV must have an average memory column")?,
+        p95_memory: find(&P95_MEMORY_COLUMNS),
+        vcpus: find(&VCPU_COLUMNS).ok_or("CSV must have a provisioned vCPU column")?,
+        memory: match (find(&MEMORY_GB_COLUMNS), find(&MEMORY_MB_COLUMNS)) {
+            (Some(gb), _) => (gb, 1.0),
+            (None, Some(mb)) => (mb, 1.0 / 1024.0),
+            (None, None) => return Err("CSV must have a provisioned memory column".to_string()),
+        },
+    };
+
+    let now = Utc::now();
+    let mut rows = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (index, record) in reader.records().enumerate() {
+        // Header is line 1
+        let line = index + 2;
+        let parsed = record
+            .map_err(|e| e.to_string())
+            .and_then(|record| layout.parse_row(&record, now));
+        match parsed {
+            Ok(row) => rows.push((line, row)),
+            Err(e) => warnings.push(format!("Line {}: {}", line, e)),
+        }
+    }
+
+    Ok((rows, warnings))
+}
+
+/// Where each field sits in the CSV
+struct ColumnLayout {
+    hostname: Option<usize>,
+    ip: Option<usize>,
+    avg_cpu: Option<usize>,
+    p95_cpu: usize,
+    avg_memory: usize,
+    p95_memory: Option<usize>,
+    vcpus: usize,
+    /// Column and the factor converting it to GB
+    memory: (usize, f64),
+}
+
+impl ColumnLayout {
+    fn parse_row(&self, record: &csv::StringRecord, now: DateTime<Utc>) -> Result<UtilizationRow, String> {
+        let cell = |index: Option<usize>| index.and_then(|i| record.get(i)).filter(|value| !value.is_empty());
+        let number = |index: Option<usize>, what: &str| -> Result<Option<f64>, String> {
+            match cell(index) {
+                Some(value) => value
+                    .replace(['%', ','], "")
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|n| n.is_finite() && *n >= 0.0)
+                    .map(Some)
+                    .ok_or_else(|| format!("invalid {} \"{}\"", what, value)),
+                None => Ok(None),
+            }
+        };
+        let required = |index: usize, what: &str| -> Result<f64, String> {
+            number(Some(index), what)?.ok_or_else(|| format!("missing {}", what))
+        };
+
+        let hostname = cell(self.hostname).map(String::from);
+        let ip_address = cell(self.ip).map(String::from);
+        if hostname.is_none() && ip_address.is_none() {
+            return Err("no hostname or IP address".to_string());
+        }
+
+        let p95_cpu_percent = required(self.p95_cpu, "p95 CPU")?;
+        let vcpus = required(self.vcpus, "provisioned vCPU")?;
+        if vcpus < 1.0 {
+            return Err("provisioned vCPU must be at least 1".to_string());
+        }
+
+        Ok(UtilizationRow {
+            hostname,
+            ip_address,
+            utilization: ServerUtilization {
+                avg_cpu_percent: number(self.avg_cpu, "average CPU")?.unwrap_or(p95_cpu_percent),
+                p95_cpu_percent,
+                avg_memory_percent: required(self.avg_memory, "average memory")?,
+                p95_memory_percent: number(self.p95_memory, "p95 memory")?,
+                provisioned_vcpus: vcpus.round() as u32,
+                provisioned_memory_gb: required(self.memory.0, "provisioned memory")? * self.memory.1,
+                imported_at: now,
+            },
+        })
+    }
+}
+
+/// Hostname without any domain suffix, lowercased
+fn short_name(name: &str) -> String {
+    name.trim().split('.').next().unwrap_or_default().to_lowercase()
+}
+
+/// The asset a row describes: by IP first, then by hostname
+fn match_asset<'a>(assets: &'a [Asset], row: &UtilizationRow) -> Option<&'a Asset> {
+    if let Some(ip) = &row.ip_address {
+        if let Some(asset) = assets.iter().find(|a| a.ip_address == *ip) {
+            return Some(asset);
+        }
+    }
+    let hostname = row.hostname.as_deref()?;
+    // A hostname column holding an IP still matches by address
+    if let Some(asset) = assets.iter().find(|a| a.ip_address == hostname) {
+        return Some(asset);
+    }
+    let wanted = short_name(hostname);
+    assets.iter().find(|a| !a.name.is_empty() && short_name(&a.name) == wanted)
+}
+
+/// Edit distance between two strings, in characters
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Asset names close to `server`, closest first
+///
+/// A name is close when its short form contains the server's (or the other
+/// way round) or is within a third of its length in edits.
+pub fn suggest_assets(assets: &[Asset], server: &str) -> Vec<String> {
+    let wanted = short_name(server);
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let max_distance = (wanted.chars().count() / 3).max(1);
+
+    let mut scored: Vec<(usize, &str)> = assets
+        .iter()
+        .filter(|a| !a.name.is_empty())
+        .filter_map(|a| {
+            let candidate = short_name(&a.name);
+            let distance = levenshtein(&wanted, &candidate);
+            let contains = candidate.contains(&wanted) || wanted.contains(&candidate);
+            (distance <= max_distance || (contains && !candidate.is_empty())).then_some((distance, a.name.as_str()))
+        })
+        .collect();
+    scored.sort();
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, name)| name.to_string()).collect()
+}
+
+/// Match utilization rows to a client's assets and store them as metadata
+///
+/// A later row for the same asset replaces an earlier one.
+pub fn import_utilization(db: &Database, client_id: &str, data: &str) -> OptioResult<UtilizationImportSummary> {
+    let (rows, warnings) = parse_utilization_csv(data).map_err(OptioError::Validation)?;
+    let repo = AssetRepository::new(db);
+    let assets = repo.list_by_client(client_id)?;
+
+    let mut summary = UtilizationImportSummary { warnings, ..Default::default() };
+    let mut updated: HashMap<String, Asset> = HashMap::new();
+
+    for (line, row) in rows {
+        let asset = match match_asset(&assets, &row) {
+            Some(asset) => asset,
+            None => {
+                summary.unmatched.push(UnmatchedUtilizationRow {
+                    line,
+                    server: row.label().to_string(),
+                    suggestions: row.hostname.as_deref().map(|h| suggest_assets(&assets, h)).unwrap_or_default(),
+                });
+                continue;
+            }
+        };
+
+        let target = updated.entry(asset.id.clone()).or_insert_with(|| asset.clone());
+        let mut metadata = match target.metadata.take() {
+            Some(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        metadata.insert(UTILIZATION_METADATA_KEY.to_string(), serde_json::to_value(&row.utilization)?);
+        target.metadata = Some(serde_json::Value::Object(metadata));
+    }
+
+    for asset in updated.values() {
+        repo.save(asset)?;
+    }
+    summary.matched = updated.len();
+    Ok(summary)
+}
+
+/// Utilization stored on a client's assets
+pub fn client_utilization(db: &Database, client_id: &str) -> OptioResult<Vec<ServerUtilization>> {
+    Ok(AssetRepository::new(db)
+        .list_by_client(client_id)?
+        .iter()
+        .filter_map(ServerUtilization::from_asset)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::models::{AssetCategory, AssetStatus, Criticality};
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::network::repository::init_network_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn asset(name: &str, ip_address: &str) -> Asset {
+        Asset {
+            id: uuid::Uuid::new_v4().to_string(),
+            client_id: "client-1".to_string(),
+            name: name.to_string(),
+            ip_address: ip_address.to_string(),
+            mac_address: None,
+            category: AssetCategory::Server,
+            operating_system: None,
+            criticality: Criticality::Medium,
+            status: AssetStatus::Active,
+            location: None,
+            owner: None,
+            description: None,
+            services: vec![],
+            tags: vec![],
+            first_seen: "2024-01-01T00:00:00Z".to_string(),
+            last_seen: "2024-01-01T00:00:00Z".to_string(),
+            scan_ids: vec![],
+            metadata: Some(serde_json::json!({"rack": "B4"})),
+        }
+    }
+
+    #[test]
+    fn test_size_from_utilization() {
+        // 8 vCPU at 30% p95 needs 3.12 with headroom, so 4; 32 GB at 50% needs 20.8, so 32
+        assert_eq!(size_from_utilization(8, 32.0, 30.0, 50.0), (4, 32.0));
+        // Idle servers still get the smallest step
+        assert_eq!(size_from_utilization(16, 64.0, 0.0, 0.0), (1, 1.0));
+        // Busy servers can grow past what they have today
+        assert_eq!(size_from_utilization(4, 8.0, 95.0, 90.0), (8, 16.0));
+        // Steps aren't all powers of two: 10 vCPU * 80% * 1.3 = 10.4 -> 12
+        assert_eq!(size_from_utilization(10, 10.0, 80.0, 100.0), (12, 16.0));
+        // Nothing past the largest step
+        assert_eq!(size_from_utilization(128, 512.0, 100.0, 100.0), (96, 256.0));
+    }
+
+    #[test]
+    fn test_parse_utilization_csv() {
+        let csv = "\u{feff}VM,Primary IP Address,Avg CPU %,P95 CPU %,Avg Memory %,CPUs,Memory\n\
+                   web01.acme.local,10.0.0.5,12%,35%,40%,4,\"16,384\"\n\
+                   ,,10,20,30,2,4096\n\
+                   db01,,,80,x,8,65536\n";
+        let (rows, warnings) = parse_utilization_csv(csv).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        let (line, row) = &rows[0];
+        assert_eq!(*line, 2);
+        assert_eq!(row.hostname.as_deref(), Some("web01.acme.local"));
+        assert_eq!(row.ip_address.as_deref(), Some("10.0.0.5"));
+        assert_eq!(row.utilization.p95_cpu_percent, 35.0);
+        assert_eq!(row.utilization.provisioned_vcpus, 4);
+        // RVTools memory is in MB
+        assert_eq!(row.utilization.provisioned_memory_gb, 16.0);
+
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("Line 3: no hostname"));
+        assert!(warnings[1].contains("invalid average memory"));
+
+        assert!(parse_utilization_csv("hostname,avg cpu\nweb01,10\n").is_err());
+    }
+
+    #[test]
+    fn test_import_utilization() {
+        let db = test_db();
+        let repo = AssetRepository::new(&db);
+        let web = asset("WEB01", "10.0.0.5");
+        let db_server = asset("db-prod-01.acme.local", "10.0.0.9");
+        repo.save(&web).unwrap();
+        repo.save(&db_server).unwrap();
+
+        let csv = "hostname,ip,p95 cpu,avg memory,provisioned vcpu,provisioned ram gb\n\
+                   web01.acme.local,,40,50,4,16\n\
+                   DB-PROD-01,,70,80,8,64\n\
+                   db-prod-1,,10,10,2,4\n";
+        let summary = import_utilization(&db, "client-1", csv).unwrap();
+
+        assert_eq!(summary.matched, 2);
+        assert_eq!(summary.unmatched.len(), 1);
+        assert_eq!(summary.unmatched[0].line, 4);
+        assert_eq!(summary.unmatched[0].suggestions, vec!["db-prod-01.acme.local".to_string()]);
+
+        // Stored alongside existing metadata
+        let stored = repo.get(&web.id).unwrap().unwrap();
+        assert_eq!(stored.metadata.as_ref().unwrap()["rack"], "B4");
+        let utilization = ServerUtilization::from_asset(&stored).unwrap();
+        assert_eq!(utilization.p95_cpu_percent, 40.0);
+        assert_eq!(utilization.right_size(), (4, 16.0));
+
+        assert_eq!(client_utilization(&db, "client-1").unwrap().len(), 2);
+    }
+}
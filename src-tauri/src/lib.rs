@@ -8,11 +8,22 @@ pub mod factory;
 pub mod grc;
 pub mod infrastructure;
 pub mod network;
+pub mod findings;
 pub mod reporting;
 pub mod error;
 pub mod db;
+pub mod db_encryption;
+pub mod db_backup;
+pub mod onboarding;
+pub mod activity;
+pub mod audit;
+pub mod secrets;
+pub mod workspace;
+pub mod deletion;
+pub mod search;
+pub mod portfolio;
 
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 /// Initialize the Tauri application with all plugins and commands
@@ -33,16 +44,33 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(commands::network::NetworkState::default())
+        .manage(commands::network::AgentListenerState::default())
         .manage(commands::reporting::ReportingState::default())
+        .manage(db_encryption::DatabaseSession::default())
         .setup(|app| {
             // Initialize the database on startup
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                if let Err(e) = db::initialize(&app_handle).await {
-                    tracing::error!("Failed to initialize database: {}", e);
+                let session = app_handle.state::<db_encryption::DatabaseSession>();
+                let status = match db::initialize(&app_handle, None).await {
+                    Ok(()) => session.set_status(db_encryption::DatabaseState::Ready, false, None),
+                    Err(error::OptioError::DatabaseLocked(reason)) => {
+                        tracing::info!("Database is encrypted, waiting to be unlocked");
+                        session.set_status(db_encryption::DatabaseState::Locked, true, Some(reason))
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to initialize database: {}", e);
+                        session.set_status(db_encryption::DatabaseState::Failed, false, Some(e.to_string()))
+                    }
+                };
+                if let Err(e) = app_handle.emit(db_encryption::DATABASE_STATUS_EVENT, status) {
+                    tracing::warn!("Failed to emit database status: {}", e);
                 }
             });
 
+            // Run recurring scans in the background
+            tauri::async_runtime::spawn(commands::network::run_scan_scheduler(app.handle().clone()));
+
             #[cfg(debug_assertions)]
             {
                 let window = app.get_webview_window("main").unwrap();
@@ -51,76 +79,248 @@ pub fn run() {
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(commands::database::require_open_database(tauri::generate_handler![
             // Factory commands
             commands::factory::generate_client_script,
+            commands::factory::list_generated_scripts,
+            commands::factory::get_generated_script,
+            commands::factory::regenerate_script,
             commands::factory::list_templates,
             commands::factory::get_script_preview,
             commands::factory::validate_config,
+            commands::factory::validate_generated_script,
+            commands::factory::lint_template,
+            commands::factory::verify_script,
+            commands::factory::list_signing_keys,
+            commands::factory::rotate_signing_key,
+            commands::factory::parse_prep_audit_result,
             commands::factory::generate_agent_script,
+            commands::factory::issue_agent_token,
+            commands::factory::list_agent_tokens,
+            commands::factory::revoke_agent_token,
             // Client management commands
             commands::clients::create_client,
             commands::clients::list_clients,
             commands::clients::get_client,
             commands::clients::update_client,
+            commands::clients::preview_client_deletion,
             commands::clients::delete_client,
+            commands::clients::get_client_dependencies,
+            commands::clients::get_client_overview,
+            commands::dashboard::get_client_dashboard,
+            commands::portfolio::get_portfolio_overview,
+            commands::portfolio::export_portfolio_csv,
             // System commands
             commands::system::get_system_info,
             commands::system::get_consultant_ip,
+            commands::system::set_preferred_consultant_ip,
+            commands::system::list_network_interfaces,
+            commands::system::set_preferred_interface,
+            commands::system::suggest_consultant_ip_for_subnet,
+            // Database commands
+            commands::database::get_database_status,
+            commands::database::unlock_database,
+            commands::database::set_database_passphrase,
+            commands::database::change_database_passphrase,
+            commands::database::create_database_backup,
+            commands::database::list_database_backups,
+            commands::database::restore_database_backup,
+            commands::database::prune_database_backups,
+            // Onboarding commands
+            commands::onboarding::get_onboarding_status,
+            commands::onboarding::dismiss_onboarding,
+            // Activity commands
+            commands::activity::get_client_timeline,
+            commands::activity::get_recent_activity,
+            commands::activity::purge_activity_log,
+            // Audit log commands
+            commands::audit::export_audit_log,
+            commands::audit::verify_audit_chain,
+            commands::search::search_everything,
             // GRC commands
             commands::grc::list_frameworks,
             commands::grc::get_framework_controls_cmd,
+            commands::grc::search_controls,
             commands::grc::create_assessment,
             commands::grc::get_assessment,
             commands::grc::list_client_assessments,
             commands::grc::list_assessments,
             commands::grc::update_assessment_status,
+            commands::grc::reopen_assessment,
+            commands::grc::clone_assessment,
             commands::grc::delete_assessment,
             commands::grc::update_control_assessment,
             commands::grc::get_control_assessments,
+            commands::grc::get_control_history,
+            commands::grc::revert_control_assessment,
             commands::grc::batch_update_controls,
             commands::grc::create_evidence,
             commands::grc::get_assessment_evidence,
             commands::grc::delete_evidence,
+            commands::grc::get_evidence_for_control,
+            commands::grc::get_evidence_coverage,
+            commands::grc::link_evidence_to_controls,
+            commands::grc::unlink_evidence_from_controls,
             commands::grc::get_assessment_summary,
+            commands::grc::compare_assessments,
+            commands::grc::snapshot_assessment,
+            commands::grc::list_snapshots,
+            commands::grc::get_snapshot,
+            commands::grc::compare_snapshots,
             commands::grc::get_compliance_status,
+            commands::grc::record_compliance_snapshot,
+            commands::grc::get_compliance_trend,
+            commands::grc::get_client_compliance_overview,
+            commands::grc::get_control_mappings_cmd,
+            commands::grc::project_assessment_to_framework,
+            commands::grc::set_category_grouping,
+            commands::grc::get_category_grouping,
+            commands::grc::delete_category_grouping,
+            // Records of processing commands
+            commands::grc::create_processing_activity,
+            commands::grc::list_processing_activities,
+            commands::grc::get_processing_activity,
+            commands::grc::update_processing_activity,
+            commands::grc::delete_processing_activity,
+            // Risk register commands
+            commands::grc::create_risk,
+            commands::grc::list_risks,
+            commands::grc::get_risk,
+            commands::grc::update_risk,
+            commands::grc::delete_risk,
+            commands::grc::get_risk_matrix,
+            commands::grc::generate_remediation_plan,
+            commands::grc::get_remediation_plan,
+            commands::grc::update_remediation_item,
+            commands::grc::delete_remediation_item,
+            commands::grc::list_overdue_remediations,
+            commands::grc::get_remediation_stats,
+            commands::grc::export_remediation_plan,
+            // Assessment transfer commands
+            commands::grc::export_assessment,
+            commands::grc::import_assessment,
+            // Policy generation commands
+            commands::grc::generate_policy_documents,
             // Infrastructure commands
             commands::infrastructure::get_cloud_readiness_items,
             commands::infrastructure::get_cloud_readiness_by_category,
             commands::infrastructure::assess_cloud_readiness,
+            commands::infrastructure::save_cloud_readiness_answers,
+            commands::infrastructure::get_cloud_readiness_assessment,
             commands::infrastructure::get_k8s_hardening_checklist,
             commands::infrastructure::get_k8s_hardening_by_category,
             commands::infrastructure::audit_k8s_hardening,
+            commands::infrastructure::audit_k8s_from_files,
+            commands::infrastructure::scan_k8s_manifests,
+            commands::infrastructure::audit_k8s_cluster,
             commands::infrastructure::get_k8s_severity_stats,
+            commands::infrastructure::save_k8s_audit,
+            commands::infrastructure::list_k8s_audits,
+            commands::infrastructure::get_k8s_audit,
+            commands::infrastructure::compare_k8s_audits,
+            commands::infrastructure::get_k8s_severity_trend,
             commands::infrastructure::get_finops_templates,
             commands::infrastructure::calculate_single_resource_cost,
             commands::infrastructure::generate_finops_report,
             commands::infrastructure::compare_cloud_providers,
+            commands::infrastructure::compare_resource_costs,
+            commands::infrastructure::generate_tco_projection,
+            // Pricing profile commands
+            commands::infrastructure::create_pricing_profile,
+            commands::infrastructure::list_pricing_profiles,
+            commands::infrastructure::get_pricing_profile,
+            commands::infrastructure::update_pricing_profile,
+            commands::infrastructure::delete_pricing_profile,
+            commands::infrastructure::import_pricing_csv,
+            commands::infrastructure::import_utilization_csv,
             // Network Intelligence commands
             commands::network::check_nmap,
+            commands::network::set_nmap_path,
             commands::network::get_scan_type_list,
             commands::network::get_common_port_list,
             commands::network::validate_scan_target,
+            commands::network::validate_custom_nmap_args,
             commands::network::create_scan,
             commands::network::preview_scan_command,
+            commands::network::plan_scan_targets,
             commands::network::list_scans,
             commands::network::get_scan,
+            commands::network::get_scan_raw_xml,
             commands::network::delete_scan,
+            commands::network::resume_scan,
+            commands::network::cancel_scan,
+            commands::network::diff_scans,
+            commands::network::compare_scans,
+            commands::network::import_scan_results,
+            commands::network::import_nmap_xml,
             // Native TCP Scanner commands
             commands::network::scan_network,
             commands::network::scan_single_host,
             commands::network::get_default_scan_ports,
             commands::network::get_extended_scan_ports,
+            // Findings & verification commands
+            commands::network::get_finding_rule_list,
+            commands::network::detect_network_findings,
+            commands::network::list_network_findings,
+            commands::network::create_verification_scan,
+            // Scan profile commands
+            commands::network::create_scan_profile,
+            commands::network::list_scan_profiles,
+            commands::network::update_scan_profile,
+            commands::network::delete_scan_profile,
+            // Scan schedule commands
+            commands::network::create_scan_schedule,
+            commands::network::list_scan_schedules,
+            commands::network::update_scan_schedule,
+            commands::network::toggle_scan_schedule,
+            commands::network::delete_scan_schedule,
+            // Asset inventory commands
             commands::network::list_assets,
+            commands::network::query_assets,
             commands::network::get_demo_assets,
             commands::network::get_asset,
+            commands::network::get_asset_history,
             commands::network::update_asset,
             commands::network::delete_asset,
+            commands::network::bulk_update_assets,
+            commands::network::export_assets,
+            commands::network::import_assets,
+            commands::network::add_asset_tags,
+            commands::network::remove_asset_tags,
+            commands::network::save_asset_view,
+            commands::network::list_asset_views,
+            commands::network::delete_asset_view,
             commands::network::get_network_stats,
             commands::network::create_asset_group,
             commands::network::list_asset_groups,
             commands::network::add_asset_to_group,
             commands::network::remove_asset_from_group,
+            // Service policy commands
+            commands::network::list_service_policies,
+            commands::network::upsert_service_policy,
+            commands::network::delete_service_policy,
+            commands::network::evaluate_service_policies,
+            // Vulnerability enrichment commands
+            commands::network::import_vuln_database,
+            commands::network::enrich_asset_vulnerabilities,
+            commands::network::get_asset_vulnerabilities,
+            commands::network::enrich_client_assets,
+            // Agent listener commands
+            commands::network::start_agent_listener,
+            commands::network::stop_agent_listener,
+            commands::network::get_agent_listener_status,
+            commands::network::list_connected_agents,
+            commands::network::get_agent_telemetry,
+            // Findings library commands
+            commands::findings::list_finding_templates,
+            commands::findings::upsert_finding_template,
+            commands::findings::delete_finding_template,
+            commands::findings::calculate_cvss_score,
+            commands::findings::instantiate_finding,
+            commands::findings::list_findings,
+            commands::findings::get_finding,
+            commands::findings::update_finding,
+            commands::findings::delete_finding,
             // Reporting commands
             commands::reporting::get_report_template_list,
             commands::reporting::get_template_by_type,
@@ -128,19 +328,34 @@ pub fn run() {
             commands::reporting::get_export_format_list,
             commands::reporting::generate_report,
             commands::reporting::preview_report,
+            commands::reporting::start_report_preview,
+            commands::reporting::cancel_report_preview,
+            commands::reporting::generate_ropa_report,
             commands::reporting::export_report_html,
             commands::reporting::export_report_markdown,
+            commands::reporting::export_report_csv,
+            commands::reporting::export_report_docx,
+            commands::reporting::export_report_pdf,
             commands::reporting::export_report_json,
+            commands::reporting::add_custom_section,
+            commands::reporting::update_custom_section,
+            commands::reporting::delete_custom_section,
+            commands::reporting::list_custom_sections,
             commands::reporting::list_reports,
             commands::reporting::get_report,
             commands::reporting::delete_report,
             commands::reporting::get_report_stats,
             commands::reporting::generate_demo_reports,
+            commands::reporting::save_branding_profile,
+            commands::reporting::get_branding_profile,
             // PDF generation commands
             commands::reporting::generate_executive_pdf,
             commands::reporting::generate_demo_pdf,
             commands::reporting::open_pdf_location,
-        ])
+            // Workspace archive commands
+            commands::workspace::export_client_workspace,
+            commands::workspace::validate_workspace_archive,
+        ]))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,1016 @@
+//! Agent Callback Listener
+//!
+//! Receives the reverse connections made by Factory agent scripts. Agents send
+//! newline-delimited JSON: an AUTH message carrying their token, then
+//! HEARTBEAT messages for liveness and TELEMETRY payloads describing the host.
+//! The token is checked against the stored agent tokens (revoked tokens are
+//! refused), the script the agent names must have been generated for the
+//! token's client, and each authenticated connection is recorded as a
+//! connected agent of that client.
+//!
+//! Connections are untrusted: a message is capped in size and must arrive in
+//! full shortly after it starts, malformed messages are logged and skipped,
+//! and a connection sending nothing but garbage is dropped. Nothing a client
+//! sends can stop the listener itself. A source IP whose connections keep
+//! failing to authenticate is turned away for a while without being read.
+//!
+//! TELEMETRY payloads are kept as sent, and the host details in them (domain,
+//! OS version, IP addresses, memory) update the agent's record.
+//!
+//! The listener speaks either plain TCP or TLS, presenting each client
+//! engagement's certificate (see [`crate::network::agent_tls`]). A TLS
+//! handshake that fails or stalls counts as a failed authentication.
+
+use crate::db::{add_missing_columns, Database};
+use crate::error::{OptioError, OptioResult};
+use crate::factory::tokens::{AgentToken, AgentTokenRepository};
+use crate::grc::repository::parse_datetime;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_rustls::TlsAcceptor;
+use uuid::Uuid;
+
+/// Event emitted to the frontend when an agent authenticates
+pub const AGENT_CONNECTED_EVENT: &str = "agent-connected";
+/// Event emitted to the frontend when an agent's connection ends
+pub const AGENT_DISCONNECTED_EVENT: &str = "agent-disconnected";
+
+/// Longest accepted message, in bytes
+const MAX_LINE_BYTES: usize = 64 * 1024;
+/// Time allowed from connecting to a complete AUTH message
+const AUTH_TIMEOUT: Duration = Duration::from_secs(15);
+/// Time allowed to finish a message once its first byte arrives
+const LINE_TIMEOUT: Duration = Duration::from_secs(15);
+/// Silence tolerated between messages; above the longest heartbeat interval
+const IDLE_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+/// Consecutive malformed messages before the connection is dropped
+const MAX_MALFORMED: u32 = 5;
+/// Connections handled at once; further ones are closed straight away
+const MAX_CONNECTIONS: usize = 256;
+/// Failed authentications from one IP before it is turned away
+const MAX_AUTH_FAILURES: u32 = 5;
+/// How long failed authentications count against an IP
+const AUTH_FAILURE_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// Initialize agent listener schema
+pub fn init_agent_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Agent scripts generated for each client, which agents must name
+        CREATE TABLE IF NOT EXISTS agent_scripts (
+            script_id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            token_id TEXT NOT NULL,
+            generated_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        -- Agents that have called back, one per deployed script and host
+        CREATE TABLE IF NOT EXISTS connected_agents (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            token_id TEXT NOT NULL,
+            script_id TEXT NOT NULL,
+            hostname TEXT NOT NULL,
+            username TEXT,
+            os_version TEXT,
+            remote_ip TEXT NOT NULL,
+            connected_at TEXT NOT NULL,
+            last_heartbeat TEXT NOT NULL,
+            disconnected_at TEXT,
+            UNIQUE(script_id, hostname),
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        -- Telemetry payloads as sent (JSON)
+        CREATE TABLE IF NOT EXISTS agent_telemetry (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            agent_id TEXT NOT NULL,
+            received_at TEXT NOT NULL,
+            data TEXT NOT NULL,
+            FOREIGN KEY (agent_id) REFERENCES connected_agents(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_connected_agents_client ON connected_agents(client_id);
+        CREATE INDEX IF NOT EXISTS idx_agent_telemetry_agent ON agent_telemetry(agent_id, received_at);
+    "#)?;
+
+    add_missing_columns(&conn, "connected_agents", &[
+        ("domain", "TEXT"),
+        ("ip_addresses", "TEXT NOT NULL DEFAULT '[]'"),
+        ("total_memory_mb", "INTEGER"),
+        ("last_telemetry_at", "TEXT"),
+    ])?;
+
+    tracing::info!("Agent listener schema initialized");
+    Ok(())
+}
+
+/// An agent that has called back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectedAgent {
+    pub id: String,
+    pub client_id: String,
+    /// Agent token it authenticated with
+    pub token_id: String,
+    /// Factory script it runs
+    pub script_id: String,
+    pub hostname: String,
+    pub username: Option<String>,
+    pub os_version: Option<String>,
+    pub remote_ip: String,
+    /// Host details from the latest telemetry
+    pub domain: Option<String>,
+    pub ip_addresses: Vec<String>,
+    pub total_memory_mb: Option<u64>,
+    pub last_telemetry_at: Option<DateTime<Utc>>,
+    /// Start of the current (or last) connection
+    pub connected_at: DateTime<Utc>,
+    pub last_heartbeat: DateTime<Utc>,
+    /// Set when the connection ends
+    pub disconnected_at: Option<DateTime<Utc>>,
+}
+
+impl ConnectedAgent {
+    pub fn is_online(&self) -> bool {
+        self.disconnected_at.is_none()
+    }
+}
+
+/// A telemetry payload sent by an agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentTelemetry {
+    pub id: i64,
+    pub agent_id: String,
+    pub received_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+/// Messages sent by the agent script, one JSON object per line
+#[derive(Debug, Deserialize)]
+#[serde(tag = "Type", rename_all = "SCREAMING_SNAKE_CASE")]
+enum AgentMessage {
+    #[serde(rename_all = "PascalCase")]
+    Auth {
+        token: String,
+        script_id: String,
+        hostname: String,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(rename = "OSVersion", default)]
+        os_version: Option<String>,
+    },
+    Heartbeat {},
+    #[serde(rename_all = "PascalCase")]
+    Telemetry { data: serde_json::Value },
+}
+
+/// Who an authenticated agent says it is
+#[derive(Debug, Clone)]
+pub struct AgentHello {
+    pub script_id: String,
+    pub hostname: String,
+    pub username: Option<String>,
+    pub os_version: Option<String>,
+}
+
+/// Repository for connected agents and their telemetry
+pub struct AgentRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AgentRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Record an agent script generated with `token`
+    pub fn record_script(&self, script_id: &str, token: &AgentToken, generated_at: DateTime<Utc>) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO agent_scripts (script_id, client_id, token_id, generated_at) VALUES (?1, ?2, ?3, ?4)",
+            params![script_id, token.client_id, token.id, generated_at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Record an authenticated connection, reusing the agent's record on reconnect
+    ///
+    /// The agent's script must have been generated for the token's client, so
+    /// one client's token can't report hosts under another client's script.
+    pub fn register(&self, token: &AgentToken, hello: &AgentHello, remote_ip: IpAddr) -> OptioResult<ConnectedAgent> {
+        let now = Utc::now().to_rfc3339();
+        {
+            let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+            let script_client: Option<String> = conn
+                .query_row(
+                    "SELECT client_id FROM agent_scripts WHERE script_id = ?1",
+                    params![hello.script_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if script_client.as_deref() != Some(token.client_id.as_str()) {
+                return Err(OptioError::Validation(format!(
+                    "script {} was not generated for the token's client",
+                    hello.script_id
+                )));
+            }
+
+            conn.execute(
+                "INSERT INTO connected_agents (
+                    id, client_id, token_id, script_id, hostname, username, os_version,
+                    remote_ip, connected_at, last_heartbeat, disconnected_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9, NULL)
+                ON CONFLICT(script_id, hostname) DO UPDATE SET
+                    token_id = excluded.token_id,
+                    username = excluded.username,
+                    os_version = excluded.os_version,
+                    remote_ip = excluded.remote_ip,
+                    connected_at = excluded.connected_at,
+                    last_heartbeat = excluded.last_heartbeat,
+                    disconnected_at = NULL",
+                params![
+                    Uuid::new_v4().to_string(),
+                    token.client_id,
+                    token.id,
+                    hello.script_id,
+                    hello.hostname,
+                    hello.username,
+                    hello.os_version,
+                    remote_ip.to_string(),
+                    now,
+                ],
+            )?;
+        }
+
+        self.find(&hello.script_id, &hello.hostname)?
+            .ok_or_else(|| OptioError::Database(format!("Agent {} was not recorded", hello.hostname)))
+    }
+
+    pub fn heartbeat(&self, agent_id: &str) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            "UPDATE connected_agents SET last_heartbeat = ?2 WHERE id = ?1",
+            params![agent_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Store a telemetry payload and update the agent's host details from it
+    ///
+    /// Details missing from the payload keep their previous values.
+    pub fn record_telemetry(&self, agent_id: &str, data: &serde_json::Value) -> OptioResult<()> {
+        let now = Utc::now().to_rfc3339();
+        let host = HostDetails::from_telemetry(data);
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO agent_telemetry (agent_id, received_at, data) VALUES (?1, ?2, ?3)",
+            params![agent_id, now, serde_json::to_string(data)?],
+        )?;
+        tx.execute(
+            "UPDATE connected_agents SET
+                domain = COALESCE(?2, domain),
+                os_version = COALESCE(?3, os_version),
+                ip_addresses = COALESCE(?4, ip_addresses),
+                total_memory_mb = COALESCE(?5, total_memory_mb),
+                last_telemetry_at = ?6
+             WHERE id = ?1",
+            params![
+                agent_id,
+                host.domain,
+                host.os_version,
+                host.ip_addresses.map(|ips| serde_json::to_string(&ips)).transpose()?,
+                host.total_memory_mb.map(|mb| mb as i64),
+                now,
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Mark an agent's connection as ended
+    pub fn disconnect(&self, agent_id: &str) -> OptioResult<Option<ConnectedAgent>> {
+        {
+            let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+            conn.execute(
+                "UPDATE connected_agents SET disconnected_at = ?2 WHERE id = ?1 AND disconnected_at IS NULL",
+                params![agent_id, Utc::now().to_rfc3339()],
+            )?;
+        }
+        self.get(agent_id)
+    }
+
+    /// Mark every agent offline, e.g. connections left open by a previous run
+    pub fn disconnect_all(&self) -> OptioResult<usize> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let count = conn.execute(
+            "UPDATE connected_agents SET disconnected_at = ?1 WHERE disconnected_at IS NULL",
+            params![Utc::now().to_rfc3339()],
+        )?;
+        Ok(count)
+    }
+
+    pub fn get(&self, agent_id: &str) -> OptioResult<Option<ConnectedAgent>> {
+        self.query_one(&format!("SELECT {} FROM connected_agents WHERE id = ?1", AGENT_COLUMNS), params![agent_id])
+    }
+
+    fn find(&self, script_id: &str, hostname: &str) -> OptioResult<Option<ConnectedAgent>> {
+        self.query_one(
+            &format!("SELECT {} FROM connected_agents WHERE script_id = ?1 AND hostname = ?2", AGENT_COLUMNS),
+            params![script_id, hostname],
+        )
+    }
+
+    fn query_one(&self, sql: &str, params: &[&dyn rusqlite::ToSql]) -> OptioResult<Option<ConnectedAgent>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(sql)?;
+        let mut rows = stmt.query(params)?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(parse_agent_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// A client's agents, online first, then most recently seen
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<ConnectedAgent>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM connected_agents WHERE client_id = ?1
+             ORDER BY disconnected_at IS NOT NULL, last_heartbeat DESC",
+            AGENT_COLUMNS
+        ))?;
+
+        let mut rows = stmt.query(params![client_id])?;
+        let mut agents = Vec::new();
+        while let Some(row) = rows.next()? {
+            agents.push(parse_agent_row(row)?);
+        }
+        Ok(agents)
+    }
+
+    /// An agent's telemetry, newest first
+    pub fn telemetry(&self, agent_id: &str) -> OptioResult<Vec<AgentTelemetry>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, agent_id, received_at, data FROM agent_telemetry
+             WHERE agent_id = ?1 ORDER BY received_at DESC, id DESC",
+        )?;
+
+        let mut rows = stmt.query(params![agent_id])?;
+        let mut telemetry = Vec::new();
+        while let Some(row) = rows.next()? {
+            let data: String = row.get(3)?;
+            telemetry.push(AgentTelemetry {
+                id: row.get(0)?,
+                agent_id: row.get(1)?,
+                received_at: parse_datetime(&row.get::<_, String>(2)?)?,
+                data: serde_json::from_str(&data)?,
+            });
+        }
+        Ok(telemetry)
+    }
+}
+
+const AGENT_COLUMNS: &str = "id, client_id, token_id, script_id, hostname, username, os_version, \
+    remote_ip, connected_at, last_heartbeat, disconnected_at, domain, ip_addresses, total_memory_mb, \
+    last_telemetry_at";
+
+fn parse_agent_row(row: &rusqlite::Row) -> OptioResult<ConnectedAgent> {
+    let disconnected_at: Option<String> = row.get(10)?;
+    let ip_addresses: String = row.get(12)?;
+    let total_memory_mb: Option<i64> = row.get(13)?;
+    let last_telemetry_at: Option<String> = row.get(14)?;
+
+    Ok(ConnectedAgent {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        token_id: row.get(2)?,
+        script_id: row.get(3)?,
+        hostname: row.get(4)?,
+        username: row.get(5)?,
+        os_version: row.get(6)?,
+        remote_ip: row.get(7)?,
+        domain: row.get(11)?,
+        ip_addresses: serde_json::from_str(&ip_addresses)?,
+        total_memory_mb: total_memory_mb.map(|mb| mb as u64),
+        last_telemetry_at: last_telemetry_at.as_deref().map(parse_datetime).transpose()?,
+        connected_at: parse_datetime(&row.get::<_, String>(8)?)?,
+        last_heartbeat: parse_datetime(&row.get::<_, String>(9)?)?,
+        disconnected_at: disconnected_at.as_deref().map(parse_datetime).transpose()?,
+    })
+}
+
+/// Host details an agent reports in its TELEMETRY payload
+#[derive(Debug, Default, PartialEq)]
+struct HostDetails {
+    domain: Option<String>,
+    os_version: Option<String>,
+    ip_addresses: Option<Vec<String>>,
+    total_memory_mb: Option<u64>,
+}
+
+impl HostDetails {
+    /// Pick the known fields out of a payload, ignoring any of the wrong type
+    ///
+    /// PowerShell serializes a single IP address as a string rather than a
+    /// one-element array, so both are accepted.
+    fn from_telemetry(data: &serde_json::Value) -> Self {
+        let text = |key: &str| {
+            data.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        let ip_addresses = match data.get("IPAddresses") {
+            Some(serde_json::Value::String(ip)) => Some(vec![ip.clone()]),
+            Some(serde_json::Value::Array(ips)) => {
+                Some(ips.iter().filter_map(|ip| ip.as_str()).map(String::from).collect())
+            }
+            _ => None,
+        };
+
+        Self {
+            domain: text("Domain"),
+            os_version: text("OSVersion"),
+            ip_addresses,
+            total_memory_mb: data.get("TotalMemoryMB")
+                .and_then(|v| v.as_f64())
+                .filter(|mb| *mb >= 0.0)
+                .map(|mb| mb.round() as u64),
+        }
+    }
+}
+
+/// Failed authentications per source IP, shared by a listener's connections
+///
+/// An IP with [`MAX_AUTH_FAILURES`] failures, each within
+/// [`AUTH_FAILURE_WINDOW`] of the last, is blocked until the window passes.
+#[derive(Default)]
+struct AuthFailures {
+    by_ip: Mutex<HashMap<IpAddr, (u32, Instant)>>,
+}
+
+impl AuthFailures {
+    fn is_blocked(&self, ip: IpAddr, now: Instant) -> bool {
+        let by_ip = self.by_ip.lock().unwrap_or_else(|e| e.into_inner());
+        by_ip.get(&ip).is_some_and(|(count, last)| {
+            *count >= MAX_AUTH_FAILURES && now.duration_since(*last) < AUTH_FAILURE_WINDOW
+        })
+    }
+
+    fn record_failure(&self, ip: IpAddr, now: Instant) {
+        let mut by_ip = self.by_ip.lock().unwrap_or_else(|e| e.into_inner());
+        // Forget stale entries so scanners passing through don't pile up
+        by_ip.retain(|_, (_, last)| now.duration_since(*last) < AUTH_FAILURE_WINDOW);
+        let entry = by_ip.entry(ip).or_insert((0, now));
+        entry.0 += 1;
+        entry.1 = now;
+    }
+
+    fn clear(&self, ip: IpAddr) {
+        self.by_ip.lock().unwrap_or_else(|e| e.into_inner()).remove(&ip);
+    }
+}
+
+/// Receives listener events (event name, agent) for the frontend
+pub type AgentEventSink = Arc<dyn Fn(&'static str, &ConnectedAgent) + Send + Sync>;
+
+/// Whether the listener is running, and where
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentListenerStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    /// Whether agents must connect over TLS
+    pub tls: bool,
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+/// A running listener
+pub struct AgentListenerHandle {
+    port: u16,
+    tls: bool,
+    started_at: DateTime<Utc>,
+    shutdown: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl AgentListenerHandle {
+    pub fn status(&self) -> AgentListenerStatus {
+        AgentListenerStatus {
+            running: true,
+            port: Some(self.port),
+            tls: self.tls,
+            started_at: Some(self.started_at),
+        }
+    }
+
+    /// Stop accepting connections and close the open ones
+    pub async fn stop(self) {
+        // Send only fails once every receiver is gone, i.e. nothing is left to stop
+        let _ = self.shutdown.send(true);
+        if let Err(e) = self.task.await {
+            tracing::warn!("Agent listener task ended abnormally: {}", e);
+        }
+    }
+}
+
+/// Bind the listener on all interfaces and start accepting agents
+///
+/// `port` 0 picks a free port. With `tls`, every connection must complete
+/// a TLS handshake first. Agents still marked online from a previous run
+/// are marked offline first.
+pub async fn start_listener<D>(
+    port: u16,
+    db: D,
+    events: AgentEventSink,
+    tls: Option<TlsAcceptor>,
+) -> OptioResult<AgentListenerHandle>
+where
+    D: AsRef<Database> + Clone + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    let port = listener.local_addr()?.port();
+    AgentRepository::new(db.as_ref()).disconnect_all()?;
+
+    let (shutdown, shutdown_rx) = watch::channel(false);
+    let uses_tls = tls.is_some();
+    let task = tokio::spawn(accept_loop(listener, db, events, tls, shutdown_rx));
+    tracing::info!("Agent listener started on port {}{}", port, if uses_tls { " with TLS" } else { "" });
+
+    Ok(AgentListenerHandle {
+        port,
+        tls: uses_tls,
+        started_at: Utc::now(),
+        shutdown,
+        task,
+    })
+}
+
+async fn accept_loop<D>(
+    listener: TcpListener,
+    db: D,
+    events: AgentEventSink,
+    tls: Option<TlsAcceptor>,
+    mut shutdown: watch::Receiver<bool>,
+) where
+    D: AsRef<Database> + Clone + Send + Sync + 'static,
+{
+    let slots = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+    let failures = Arc::new(AuthFailures::default());
+
+    loop {
+        let (stream, remote) = tokio::select! {
+            _ = shutdown.changed() => break,
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // Usually out of file descriptors; back off rather than spin
+                    tracing::warn!("Agent listener failed to accept a connection: {}", e);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            },
+        };
+
+        if failures.is_blocked(remote.ip(), Instant::now()) {
+            tracing::debug!("Refusing agent connection from {}: too many failed authentications", remote);
+            continue;
+        }
+
+        let slot = match slots.clone().try_acquire_owned() {
+            Ok(slot) => slot,
+            Err(_) => {
+                tracing::warn!("Refusing agent connection from {}: {} connections open", remote, MAX_CONNECTIONS);
+                continue;
+            }
+        };
+
+        let (db, events, failures, shutdown) = (db.clone(), events.clone(), failures.clone(), shutdown.clone());
+        let tls = tls.clone();
+        tokio::spawn(async move {
+            match tls {
+                None => handle_connection(stream, remote, db, events, &failures, shutdown).await,
+                Some(tls) => match timeout(AUTH_TIMEOUT, tls.accept(stream)).await {
+                    Ok(Ok(stream)) => handle_connection(stream, remote, db, events, &failures, shutdown).await,
+                    Ok(Err(e)) => {
+                        tracing::warn!("Rejected agent connection from {}: TLS handshake failed: {}", remote, e);
+                        failures.record_failure(remote.ip(), Instant::now());
+                    }
+                    Err(_) => {
+                        tracing::warn!("Rejected agent connection from {}: TLS handshake timed out", remote);
+                        failures.record_failure(remote.ip(), Instant::now());
+                    }
+                },
+            }
+            drop(slot);
+        });
+    }
+
+    tracing::info!("Agent listener stopped");
+}
+
+async fn handle_connection<S, D>(
+    stream: S,
+    remote: SocketAddr,
+    db: D,
+    events: AgentEventSink,
+    failures: &AuthFailures,
+    mut shutdown: watch::Receiver<bool>,
+) where
+    S: AsyncRead + Unpin,
+    D: AsRef<Database>,
+{
+    let mut reader = BufReader::new(stream);
+
+    let agent = match authenticate(&mut reader, db.as_ref(), remote).await {
+        Ok(agent) => agent,
+        Err(reason) => {
+            tracing::warn!("Rejected agent connection from {}: {}", remote, reason);
+            failures.record_failure(remote.ip(), Instant::now());
+            return;
+        }
+    };
+    failures.clear(remote.ip());
+    tracing::info!("Agent {} connected from {}", agent.hostname, remote);
+    events(AGENT_CONNECTED_EVENT, &agent);
+
+    let agents = AgentRepository::new(db.as_ref());
+    let mut malformed = 0;
+    loop {
+        let line = tokio::select! {
+            _ = shutdown.changed() => break,
+            line = read_line(&mut reader, IDLE_TIMEOUT, LINE_TIMEOUT) => line,
+        };
+
+        let line = match line {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!("Dropping agent {} ({}): {}", agent.hostname, remote, e);
+                break;
+            }
+        };
+        if line.is_empty() {
+            continue;
+        }
+
+        let result = match serde_json::from_str::<AgentMessage>(&line) {
+            Ok(AgentMessage::Heartbeat {}) => agents.heartbeat(&agent.id),
+            Ok(AgentMessage::Telemetry { data }) => agents.record_telemetry(&agent.id, &data),
+            Ok(AgentMessage::Auth { .. }) => {
+                tracing::warn!("Ignoring repeated AUTH from agent {}", agent.hostname);
+                Ok(())
+            }
+            Err(e) => {
+                malformed += 1;
+                tracing::warn!("Malformed message from agent {} ({}): {}", agent.hostname, remote, e);
+                if malformed >= MAX_MALFORMED {
+                    tracing::warn!("Dropping agent {} after {} malformed messages", agent.hostname, malformed);
+                    break;
+                }
+                continue;
+            }
+        };
+        malformed = 0;
+        if let Err(e) = result {
+            tracing::warn!("Failed to store message from agent {}: {}", agent.hostname, e);
+        }
+    }
+
+    match agents.disconnect(&agent.id) {
+        Ok(Some(agent)) => {
+            tracing::info!("Agent {} disconnected", agent.hostname);
+            events(AGENT_DISCONNECTED_EVENT, &agent);
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to record disconnect of agent {}: {}", agent.hostname, e),
+    }
+}
+
+/// Read the AUTH message and register the agent; Err explains a rejection
+async fn authenticate<R>(reader: &mut R, db: &Database, remote: SocketAddr) -> Result<ConnectedAgent, String>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let line = read_line(reader, AUTH_TIMEOUT, LINE_TIMEOUT)
+        .await?
+        .ok_or_else(|| "closed before authenticating".to_string())?;
+
+    let (token, hello) = match serde_json::from_str::<AgentMessage>(&line) {
+        Ok(AgentMessage::Auth { token, script_id, hostname, username, os_version }) => {
+            (token, AgentHello { script_id, hostname, username, os_version })
+        }
+        Ok(_) => return Err("first message was not AUTH".to_string()),
+        Err(e) => return Err(format!("malformed AUTH message: {}", e)),
+    };
+
+    let token = AgentTokenRepository::new(db)
+        .validate(&token)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("unknown, revoked or expired token from host {}", hello.hostname))?;
+
+    AgentRepository::new(db)
+        .register(&token, &hello, remote.ip())
+        .map_err(|e| e.to_string())
+}
+
+/// Read one newline-terminated message; Ok(None) when the connection closes
+///
+/// The wait for a message to start is bounded by `idle`, and once its first
+/// byte arrives the rest must follow within `line_timeout`, so a client
+/// trickling bytes can't hold the connection open. Messages over
+/// [`MAX_LINE_BYTES`] are an error.
+async fn read_line<R>(reader: &mut R, idle: Duration, line_timeout: Duration) -> Result<Option<String>, String>
+where
+    R: AsyncBufRead + Unpin,
+{
+    match timeout(idle, reader.fill_buf()).await {
+        Err(_) => return Err(format!("no message for {}s", idle.as_secs())),
+        Ok(Err(e)) => return Err(e.to_string()),
+        Ok(Ok([])) => return Ok(None),
+        Ok(Ok(_)) => {}
+    }
+
+    let mut line = Vec::new();
+    let mut limited = (&mut *reader).take(MAX_LINE_BYTES as u64 + 1);
+    match timeout(line_timeout, limited.read_until(b'\n', &mut line)).await {
+        Err(_) => Err(format!("message incomplete after {}s", line_timeout.as_secs())),
+        Ok(Err(e)) => Err(e.to_string()),
+        Ok(Ok(_)) if line.len() > MAX_LINE_BYTES => Err(format!("message over {} bytes", MAX_LINE_BYTES)),
+        // Closed partway through a message
+        Ok(Ok(_)) if !line.ends_with(b"\n") => Ok(None),
+        Ok(Ok(_)) => Ok(Some(String::from_utf8_lossy(&line).trim().to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factory::tokens::init_agent_token_schema;
+    use crate::secrets::{MemorySecretStore, SecretStore};
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpStream;
+
+    fn test_db() -> Arc<Database> {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_agent_token_schema(&db).unwrap();
+        init_agent_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES
+                ('client-1', 'Acme', '2024-01-01', '2024-01-01'),
+                ('client-2', 'Beta', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        Arc::new(db)
+    }
+
+    /// A client-1 token, with script-1 generated for it
+    fn issue_token(db: &Database, store: &MemorySecretStore) -> (AgentToken, String) {
+        let token = AgentTokenRepository::new(db).create("client-1", None, None, store).unwrap();
+        AgentRepository::new(db).record_script("script-1", &token, Utc::now()).unwrap();
+        let secret = store.get(&token.id).unwrap().unwrap();
+        (token, secret)
+    }
+
+    fn auth_line(token: &str) -> String {
+        format!(
+            "{{\"Type\":\"AUTH\",\"Token\":\"{}\",\"ScriptId\":\"script-1\",\"Hostname\":\"WS01\",\
+            \"Username\":\"admin\",\"OSVersion\":\"Microsoft Windows NT 10.0\",\"Timestamp\":\"2026-01-01T00:00:00Z\"}}\n",
+            token
+        )
+    }
+
+    /// Poll until `check` holds, failing after two seconds
+    async fn wait_for(mut check: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if check() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("condition not reached");
+    }
+
+    #[tokio::test]
+    async fn test_agent_session() {
+        let db = test_db();
+        let store = MemorySecretStore::default();
+        let (token, secret) = issue_token(&db, &store);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let events: AgentEventSink = Arc::new(move |event, agent| {
+            sink.lock().unwrap().push((event, agent.hostname.clone()));
+        });
+        let listener = start_listener(0, db.clone(), events, None).await.unwrap();
+        let addr = ("127.0.0.1", listener.status().port.unwrap());
+
+        // A bad token is turned away without registering anything
+        let mut rejected = TcpStream::connect(addr).await.unwrap();
+        rejected.write_all(auth_line("optio_at_wrong").as_bytes()).await.unwrap();
+        let mut buf = [0u8; 1];
+        assert_eq!(rejected.read(&mut buf).await.unwrap(), 0);
+
+        let mut agent = TcpStream::connect(addr).await.unwrap();
+        agent.write_all(auth_line(&secret).as_bytes()).await.unwrap();
+        agent.write_all(b"{not json\n").await.unwrap();
+        agent.write_all(b"{\"Type\":\"HEARTBEAT\",\"ScriptId\":\"script-1\",\"Memory\":41.5}\n").await.unwrap();
+        agent.write_all(b"{\"Type\":\"TELEMETRY\",\"ScriptId\":\"script-1\",\"Data\":{\"Hostname\":\"WS01\",\"Domain\":\"ACME\",\"Is64Bit\":true,\"TotalMemoryMB\":16384,\"IPAddresses\":\"10.0.0.5\"}}\n").await.unwrap();
+
+        let agents = AgentRepository::new(&db);
+        wait_for(|| agents.list_by_client("client-1").unwrap().first()
+            .is_some_and(|a| !agents.telemetry(&a.id).unwrap().is_empty())).await;
+        let connected = agents.list_by_client("client-1").unwrap().remove(0);
+        assert!(connected.is_online());
+        assert_eq!(connected.os_version.as_deref(), Some("Microsoft Windows NT 10.0"));
+        assert_eq!(connected.remote_ip, "127.0.0.1");
+        assert_eq!(connected.domain.as_deref(), Some("ACME"));
+        assert_eq!(connected.ip_addresses, vec!["10.0.0.5".to_string()]);
+        assert_eq!(connected.total_memory_mb, Some(16384));
+        assert!(connected.last_telemetry_at.is_some());
+        assert_eq!(agents.telemetry(&connected.id).unwrap()[0].data["Is64Bit"], true);
+
+        drop(agent);
+        wait_for(|| !agents.get(&connected.id).unwrap().unwrap().is_online()).await;
+        assert_eq!(*seen.lock().unwrap(), vec![
+            (AGENT_CONNECTED_EVENT, "WS01".to_string()),
+            (AGENT_DISCONNECTED_EVENT, "WS01".to_string()),
+        ]);
+
+        // Revoked tokens can no longer connect
+        AgentTokenRepository::new(&db).revoke(&token.id, &store).unwrap();
+        let mut revoked = TcpStream::connect(addr).await.unwrap();
+        revoked.write_all(auth_line(&secret).as_bytes()).await.unwrap();
+        assert_eq!(revoked.read(&mut buf).await.unwrap(), 0);
+
+        listener.stop().await;
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_agent_session_over_tls() {
+        use crate::network::agent_tls::{tls_acceptor, EngagementCertificate};
+        use rustls::pki_types::{CertificateDer, ServerName};
+
+        let db = test_db();
+        let store = MemorySecretStore::default();
+        let (_, secret) = issue_token(&db, &store);
+        let certificate = EngagementCertificate::load_or_create("client-1", &store).unwrap();
+        let pin = certificate.pin();
+        let der = CertificateDer::from(certificate.certificate().to_vec());
+
+        let events: AgentEventSink = Arc::new(|_, _| {});
+        let listener = start_listener(0, db.clone(), events, Some(tls_acceptor(store).unwrap())).await.unwrap();
+        assert!(listener.status().tls);
+        let addr = ("127.0.0.1", listener.status().port.unwrap());
+
+        // The agent trusts exactly its engagement's certificate
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(der).unwrap();
+        let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+
+        // Plain TCP and unknown server names get no further than the handshake
+        let mut plain = TcpStream::connect(addr).await.unwrap();
+        plain.write_all(auth_line(&secret).as_bytes()).await.unwrap();
+        // At most a TLS alert comes back before the connection closes
+        let _ = plain.read_to_end(&mut Vec::new()).await;
+        let unknown = ServerName::try_from("0123.agents.optio.invalid").unwrap();
+        assert!(connector.connect(unknown, TcpStream::connect(addr).await.unwrap()).await.is_err());
+        assert!(AgentRepository::new(&db).list_by_client("client-1").unwrap().is_empty());
+
+        let server_name = ServerName::try_from(pin.server_name.clone()).unwrap();
+        let mut agent = connector.connect(server_name, TcpStream::connect(addr).await.unwrap()).await.unwrap();
+        agent.write_all(auth_line(&secret).as_bytes()).await.unwrap();
+        agent.flush().await.unwrap();
+
+        let agents = AgentRepository::new(&db);
+        wait_for(|| agents.list_by_client("client-1").unwrap().first().is_some_and(|a| a.is_online())).await;
+
+        drop(agent);
+        listener.stop().await;
+    }
+
+    #[test]
+    fn test_register_checks_script_client() {
+        let db = test_db();
+        let store = MemorySecretStore::default();
+        let (token, _) = issue_token(&db, &store);
+        let other = AgentTokenRepository::new(&db).create("client-2", None, None, &store).unwrap();
+        let agents = AgentRepository::new(&db);
+        let hello = |script_id: &str| AgentHello {
+            script_id: script_id.to_string(),
+            hostname: "WS01".to_string(),
+            username: None,
+            os_version: None,
+        };
+        let ip = IpAddr::from([127, 0, 0, 1]);
+
+        // Another client's token can't claim script-1, and unknown scripts are refused
+        assert!(agents.register(&other, &hello("script-1"), ip).is_err());
+        assert!(agents.register(&token, &hello("script-9"), ip).is_err());
+        assert!(agents.list_by_client("client-2").unwrap().is_empty());
+
+        assert_eq!(agents.register(&token, &hello("script-1"), ip).unwrap().client_id, "client-1");
+        agents.record_script("script-2", &other, Utc::now()).unwrap();
+        assert_eq!(agents.register(&other, &hello("script-2"), ip).unwrap().client_id, "client-2");
+        // The first client's agent stays with it
+        assert_eq!(agents.list_by_client("client-1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_host_details_from_telemetry() {
+        let host = HostDetails::from_telemetry(&serde_json::json!({
+            "Domain": "ACME",
+            "OSVersion": "Microsoft Windows NT 10.0.19045.0",
+            "IPAddresses": ["10.0.0.5", "192.168.1.20"],
+            "TotalMemoryMB": 8191.6,
+        }));
+        assert_eq!(host, HostDetails {
+            domain: Some("ACME".to_string()),
+            os_version: Some("Microsoft Windows NT 10.0.19045.0".to_string()),
+            ip_addresses: Some(vec!["10.0.0.5".to_string(), "192.168.1.20".to_string()]),
+            total_memory_mb: Some(8192),
+        });
+
+        // Missing or mistyped fields are left out rather than failing the payload
+        let host = HostDetails::from_telemetry(&serde_json::json!({"Domain": "", "TotalMemoryMB": "lots"}));
+        assert_eq!(host, HostDetails::default());
+    }
+
+    #[test]
+    fn test_auth_failures_block_per_ip() {
+        let failures = AuthFailures::default();
+        let (attacker, agent): (IpAddr, IpAddr) = ("10.0.0.66".parse().unwrap(), "10.0.0.5".parse().unwrap());
+        let start = Instant::now();
+
+        for _ in 0..MAX_AUTH_FAILURES - 1 {
+            failures.record_failure(attacker, start);
+        }
+        assert!(!failures.is_blocked(attacker, start));
+        failures.record_failure(attacker, start);
+        assert!(failures.is_blocked(attacker, start));
+        assert!(!failures.is_blocked(agent, start));
+
+        // The block lifts once the window passes
+        assert!(!failures.is_blocked(attacker, start + AUTH_FAILURE_WINDOW));
+
+        // A successful authentication forgets earlier failures
+        failures.record_failure(agent, start);
+        failures.clear(agent);
+        assert!(failures.by_ip.lock().unwrap().get(&agent).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_line_limits() {
+        let short = Duration::from_millis(50);
+
+        // A message that starts but never finishes times out
+        let (mut client, server) = tokio::io::duplex(1024);
+        client.write_all(b"{\"Type\":\"HEART").await.unwrap();
+        let mut reader = BufReader::new(server);
+        assert!(read_line(&mut reader, short, short).await.unwrap_err().contains("incomplete"));
+
+        // So does silence
+        let (_client, server) = tokio::io::duplex(1024);
+        assert!(read_line(&mut BufReader::new(server), short, short).await.is_err());
+
+        // Oversized messages are refused rather than buffered
+        let (mut client, server) = tokio::io::duplex(MAX_LINE_BYTES * 2);
+        client.write_all(&vec![b'a'; MAX_LINE_BYTES + 10]).await.unwrap();
+        assert!(read_line(&mut BufReader::new(server), short, short).await.unwrap_err().contains("bytes"));
+
+        let (mut client, server) = tokio::io::duplex(1024);
+        client.write_all(b"one\r\ntwo").await.unwrap();
+        drop(client);
+        let mut reader = BufReader::new(server);
+        assert_eq!(read_line(&mut reader, short, short).await.unwrap().as_deref(), Some("one"));
+        assert_eq!(read_line(&mut reader, short, short).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Agent Listener TLS
+//!
+//! Agent callbacks can run over TLS with a self-signed certificate per client
+//! engagement, generated the first time a TLS agent script is made for the
+//! client. A script pins its certificate's SHA-256 fingerprint instead of
+//! trusting a CA, and sends its engagement's server name in SNI, which is
+//! how the listener picks the certificate to present. Certificates and their
+//! private keys live in the OS credential store beside the agent tokens.
+
+use crate::error::{OptioError, OptioResult};
+use crate::secrets::SecretStore;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use rustls::ServerConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio_rustls::TlsAcceptor;
+
+/// Domain engagement server names are made under; never resolved
+const SERVER_NAME_SUFFIX: &str = "agents.optio.invalid";
+
+/// What an agent script needs to reach its engagement's listener over TLS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentTlsPin {
+    /// Name the agent sends in SNI
+    pub server_name: String,
+    /// SHA-256 of the certificate, upper-case hex
+    pub fingerprint: String,
+}
+
+/// A certificate as kept in the credential store
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredCertificate {
+    /// DER, base64-encoded
+    certificate: String,
+    /// PKCS#8 DER, base64-encoded
+    private_key: String,
+}
+
+/// Server name of a client's engagement; a hash, so any client ID makes a
+/// valid DNS name
+pub fn engagement_server_name(client_id: &str) -> String {
+    let label: String = Sha256::digest(client_id.as_bytes())[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("{}.{}", label, SERVER_NAME_SUFFIX)
+}
+
+fn secret_key(server_name: &str) -> String {
+    format!("agent-tls-{}", server_name)
+}
+
+/// A client engagement's listener certificate and key
+pub struct EngagementCertificate {
+    server_name: String,
+    certificate: Vec<u8>,
+    private_key: Vec<u8>,
+}
+
+impl EngagementCertificate {
+    /// The client's certificate, generated and stored the first time
+    pub fn load_or_create(client_id: &str, store: &dyn SecretStore) -> OptioResult<Self> {
+        let server_name = engagement_server_name(client_id);
+        if let Some(existing) = Self::load(&server_name, store)? {
+            return Ok(existing);
+        }
+
+        let generated = rcgen::generate_simple_self_signed(vec![server_name.clone()])
+            .map_err(|e| OptioError::Encryption(format!("Failed to generate an agent listener certificate: {}", e)))?;
+        let certificate = Self {
+            server_name,
+            certificate: generated.cert.der().to_vec(),
+            private_key: generated.key_pair.serialize_der(),
+        };
+        let stored = StoredCertificate {
+            certificate: BASE64.encode(&certificate.certificate),
+            private_key: BASE64.encode(&certificate.private_key),
+        };
+        store.set(&secret_key(&certificate.server_name), &serde_json::to_string(&stored)?)?;
+
+        tracing::info!("Generated agent listener certificate for client {}", client_id);
+        Ok(certificate)
+    }
+
+    fn load(server_name: &str, store: &dyn SecretStore) -> OptioResult<Option<Self>> {
+        let stored: StoredCertificate = match store.get(&secret_key(server_name))? {
+            Some(json) => serde_json::from_str(&json)?,
+            None => return Ok(None),
+        };
+        let decode = |encoded: &str| {
+            BASE64
+                .decode(encoded)
+                .map_err(|e| OptioError::Encryption(format!("Stored agent listener certificate is corrupt: {}", e)))
+        };
+
+        Ok(Some(Self {
+            server_name: server_name.to_string(),
+            certificate: decode(&stored.certificate)?,
+            private_key: decode(&stored.private_key)?,
+        }))
+    }
+
+    /// The certificate, DER-encoded
+    pub fn certificate(&self) -> &[u8] {
+        &self.certificate
+    }
+
+    pub fn pin(&self) -> AgentTlsPin {
+        AgentTlsPin {
+            server_name: self.server_name.clone(),
+            fingerprint: Sha256::digest(&self.certificate).iter().map(|b| format!("{:02X}", b)).collect(),
+        }
+    }
+
+    fn certified_key(&self) -> OptioResult<CertifiedKey> {
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(self.private_key.clone()));
+        let signing_key = rustls::crypto::ring::sign::any_supported_type(&key).map_err(tls_error)?;
+        Ok(CertifiedKey::new(vec![CertificateDer::from(self.certificate.clone())], signing_key))
+    }
+}
+
+/// Presents the engagement certificate named in SNI, read from the store
+/// the first time it is asked for
+struct EngagementCertificates {
+    store: Box<dyn SecretStore + Send + Sync>,
+    loaded: Mutex<HashMap<String, Arc<CertifiedKey>>>,
+}
+
+impl std::fmt::Debug for EngagementCertificates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EngagementCertificates").finish_non_exhaustive()
+    }
+}
+
+impl ResolvesServerCert for EngagementCertificates {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        // Agents always send SNI; anything else is not one of ours
+        let server_name = client_hello.server_name()?;
+        if !server_name.ends_with(SERVER_NAME_SUFFIX) {
+            return None;
+        }
+
+        let mut loaded = self.loaded.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(key) = loaded.get(server_name) {
+            return Some(key.clone());
+        }
+        let store: &dyn SecretStore = self.store.as_ref();
+        match EngagementCertificate::load(server_name, store).and_then(|c| c.map(|c| c.certified_key()).transpose()) {
+            Ok(Some(key)) => {
+                let key = Arc::new(key);
+                loaded.insert(server_name.to_string(), key.clone());
+                Some(key)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Failed to load the agent listener certificate for {}: {}", server_name, e);
+                None
+            }
+        }
+    }
+}
+
+/// TLS acceptor presenting each engagement's certificate from `store`
+pub fn tls_acceptor(store: impl SecretStore + Send + Sync + 'static) -> OptioResult<TlsAcceptor> {
+    let resolver = EngagementCertificates {
+        store: Box::new(store),
+        loaded: Mutex::new(HashMap::new()),
+    };
+    let config = ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .with_no_client_auth()
+        .with_cert_resolver(Arc::new(resolver));
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+fn tls_error(e: rustls::Error) -> OptioError {
+    OptioError::Encryption(format!("Agent listener TLS setup failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::MemorySecretStore;
+
+    #[test]
+    fn test_engagement_certificates() {
+        let store = MemorySecretStore::default();
+        let first = EngagementCertificate::load_or_create("client-1", &store).unwrap();
+        let pin = first.pin();
+        assert_eq!(pin.server_name, engagement_server_name("client-1"));
+        assert!(pin.server_name.ends_with(".agents.optio.invalid"));
+        assert_eq!(pin.fingerprint.len(), 64);
+        assert!(first.certified_key().is_ok());
+
+        // The same engagement keeps its certificate, so deployed scripts keep working
+        assert_eq!(EngagementCertificate::load_or_create("client-1", &store).unwrap().pin(), pin);
+
+        // Another engagement gets its own
+        let other = EngagementCertificate::load_or_create("client 2/with odd chars", &store).unwrap().pin();
+        assert_ne!(other.server_name, pin.server_name);
+        assert_ne!(other.fingerprint, pin.fingerprint);
+
+        assert!(tls_acceptor(store).is_ok());
+    }
+}
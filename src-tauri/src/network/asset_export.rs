@@ -0,0 +1,558 @@
+//! Asset Inventory Export and Import
+//!
+//! Writes a client's assets to CSV or JSON, one row per asset with the
+//! services flattened into a single column ("22/tcp ssh OpenSSH 8.9; 443/tcp
+//! https"), and reads the same files back so owners, criticality and tags
+//! can be bulk-edited in a spreadsheet. Imported rows are matched to
+//! existing assets by IP address; unmatched rows become new assets.
+
+use super::inventory::normalize_tags;
+use super::models::*;
+use super::repository::AssetRepository;
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Column headers, in export order
+pub const ASSET_EXPORT_COLUMNS: [&str; 14] = [
+    "IP Address",
+    "Name",
+    "MAC Address",
+    "Category",
+    "Criticality",
+    "Status",
+    "Operating System",
+    "Location",
+    "Owner",
+    "Description",
+    "Tags",
+    "Services",
+    "First Seen",
+    "Last Seen",
+];
+
+/// Byte order mark Excel needs to open a CSV as UTF-8
+const UTF8_BOM: &str = "\u{feff}";
+
+/// File format for asset export and import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetExportFormat {
+    Csv,
+    Json,
+}
+
+impl AssetExportFormat {
+    /// The format named by a file's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(AssetExportFormat::Csv),
+            "json" => Some(AssetExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for AssetExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(AssetExportFormat::Csv),
+            "json" => Ok(AssetExportFormat::Json),
+            _ => Err(format!("Unknown export format: {}", s)),
+        }
+    }
+}
+
+/// One asset as an exported row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetRecord {
+    pub ip_address: String,
+    pub name: String,
+    pub mac_address: Option<String>,
+    pub category: String,
+    pub criticality: String,
+    pub status: String,
+    pub operating_system: Option<String>,
+    pub location: Option<String>,
+    pub owner: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    /// Services flattened to "port/protocol name version", separated by "; "
+    pub services: String,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+impl From<&Asset> for AssetRecord {
+    fn from(asset: &Asset) -> Self {
+        AssetRecord {
+            ip_address: asset.ip_address.clone(),
+            name: asset.name.clone(),
+            mac_address: asset.mac_address.clone(),
+            category: format!("{:?}", asset.category),
+            criticality: format!("{:?}", asset.criticality),
+            status: format!("{:?}", asset.status),
+            operating_system: asset.operating_system.clone(),
+            location: asset.location.clone(),
+            owner: asset.owner.clone(),
+            description: asset.description.clone(),
+            tags: asset.tags.clone(),
+            services: flatten_services(&asset.services),
+            first_seen: asset.first_seen.clone(),
+            last_seen: asset.last_seen.clone(),
+        }
+    }
+}
+
+/// Where an export was written and how many assets it holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetExportSummary {
+    pub file_path: String,
+    pub rows: usize,
+}
+
+/// Outcome of an asset import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetImportSummary {
+    pub created: usize,
+    pub updated: usize,
+    /// Rows that were skipped, with the reason
+    pub warnings: Vec<String>,
+}
+
+/// Services as one cell, e.g. "22/tcp ssh OpenSSH 8.9; 443/tcp https"
+pub fn flatten_services(services: &[AssetService]) -> String {
+    services
+        .iter()
+        .map(|service| {
+            let mut entry = format!("{}/{:?} {}", service.port, service.protocol, service.name).to_lowercase();
+            if let Some(version) = service.version.as_deref().filter(|v| !v.is_empty()) {
+                entry.push(' ');
+                entry.push_str(version);
+            }
+            entry
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Parse a flattened services cell back into open services
+pub fn parse_services(cell: &str) -> Result<Vec<AssetService>, String> {
+    let mut services = Vec::new();
+    for entry in cell.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let mut parts = entry.splitn(3, ' ');
+        let endpoint = parts.next().unwrap_or_default();
+        let (port, protocol) = endpoint.split_once('/').unwrap_or((endpoint, "tcp"));
+        let port = port.parse::<u16>().map_err(|_| format!("invalid service \"{}\"", entry))?;
+        let protocol = match protocol.to_lowercase().as_str() {
+            "tcp" => Protocol::Tcp,
+            "udp" => Protocol::Udp,
+            "sctp" => Protocol::Sctp,
+            _ => return Err(format!("invalid protocol in service \"{}\"", entry)),
+        };
+        services.push(AssetService {
+            port,
+            protocol,
+            name: parts.next().unwrap_or("unknown").to_string(),
+            version: parts.next().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()),
+            state: PortState::Open,
+        });
+    }
+    Ok(services)
+}
+
+/// Render assets as CSV, optionally prefixed with a UTF-8 BOM for Excel
+pub fn assets_to_csv(assets: &[Asset], excel_bom: bool) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(ASSET_EXPORT_COLUMNS).map_err(|e| e.to_string())?;
+
+    for record in assets.iter().map(AssetRecord::from) {
+        writer.write_record([
+            record.ip_address.as_str(),
+            &record.name,
+            record.mac_address.as_deref().unwrap_or_default(),
+            &record.category,
+            &record.criticality,
+            &record.status,
+            record.operating_system.as_deref().unwrap_or_default(),
+            record.location.as_deref().unwrap_or_default(),
+            record.owner.as_deref().unwrap_or_default(),
+            record.description.as_deref().unwrap_or_default(),
+            &record.tags.join("; "),
+            &record.services,
+            &record.first_seen,
+            &record.last_seen,
+        ]).map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    let csv = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+    Ok(if excel_bom { format!("{}{}", UTF8_BOM, csv) } else { csv })
+}
+
+/// Render assets as a pretty-printed JSON array of records
+pub fn assets_to_json(assets: &[Asset]) -> Result<String, String> {
+    let records: Vec<AssetRecord> = assets.iter().map(AssetRecord::from).collect();
+    serde_json::to_string_pretty(&records).map_err(|e| e.to_string())
+}
+
+/// Write a client's assets matching a filter to a CSV or JSON file
+pub fn export_assets(
+    db: &Database,
+    client_id: &str,
+    filter: &AssetFilter,
+    format: AssetExportFormat,
+    dest: &Path,
+    excel_bom: bool,
+) -> OptioResult<AssetExportSummary> {
+    let (assets, _) = AssetRepository::new(db).query_filtered(client_id, filter, i64::MAX as usize, 0)?;
+    let data = match format {
+        AssetExportFormat::Csv => assets_to_csv(&assets, excel_bom),
+        AssetExportFormat::Json => assets_to_json(&assets),
+    }
+    .map_err(|e| OptioError::Validation(format!("Asset export failed: {}", e)))?;
+    std::fs::write(dest, data)?;
+
+    Ok(AssetExportSummary {
+        file_path: dest.to_string_lossy().to_string(),
+        rows: assets.len(),
+    })
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// One imported row: cells keyed by normalized column name ("ip_address")
+///
+/// A column that is absent leaves the asset's field untouched; a column that
+/// is present but blank clears an optional field.
+type ImportRow = HashMap<String, String>;
+
+/// Rows labelled by where they came from ("Line 4", "Record 2")
+type LabelledRows = Vec<(String, Result<ImportRow, String>)>;
+
+fn normalize_column(name: &str) -> String {
+    let mut column = String::new();
+    for c in name.trim().chars() {
+        if c == ' ' || c == '-' {
+            column.push('_');
+        } else if c.is_uppercase() {
+            // camelCase JSON keys
+            if !column.is_empty() && !column.ends_with('_') {
+                column.push('_');
+            }
+            column.extend(c.to_lowercase());
+        } else {
+            column.push(c);
+        }
+    }
+    column
+}
+
+/// Read CSV rows as written by `assets_to_csv`, in any column order
+fn csv_rows(data: &str) -> Result<LabelledRows, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data.as_bytes());
+
+    let columns: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.to_lowercase().replace([' ', '-'], "_"))
+        .collect();
+    if !columns.iter().any(|c| c == "ip_address") {
+        return Err("CSV must have an \"IP Address\" column".to_string());
+    }
+
+    Ok(reader
+        .records()
+        .enumerate()
+        .map(|(index, record)| match record {
+            Ok(record) => {
+                // Quoted cells can span lines, so label rows by where they start
+                let line = record.position().map(|p| p.line()).unwrap_or(index as u64 + 2);
+                let row = columns.iter().cloned().zip(record.iter().map(String::from)).collect();
+                (format!("Line {}", line), Ok(row))
+            }
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or(index as u64 + 2);
+                (format!("Line {}", line), Err(e.to_string()))
+            }
+        })
+        .collect())
+}
+
+/// Read a JSON array of records as written by `assets_to_json`
+fn json_rows(data: &str) -> Result<LabelledRows, String> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_str(data).map_err(|e| format!("Invalid asset JSON: {}", e))?;
+
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| (format!("Record {}", index + 1), json_row(value)))
+        .collect())
+}
+
+fn json_row(value: serde_json::Value) -> Result<ImportRow, String> {
+    let object = match value {
+        serde_json::Value::Object(object) => object,
+        _ => return Err("expected an object".to_string()),
+    };
+    Ok(object
+        .into_iter()
+        .map(|(key, value)| {
+            let cell = match value {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(s) => s,
+                serde_json::Value::Array(items) => items
+                    .iter()
+                    .map(|item| item.as_str().map(String::from).unwrap_or_else(|| item.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                other => other.to_string(),
+            };
+            (normalize_column(&key), cell.trim().to_string())
+        })
+        .collect())
+}
+
+/// Apply one row to the inventory, returning whether it created an asset
+fn import_row(assets: &AssetRepository, client_id: &str, row: &ImportRow, now: &str) -> Result<bool, String> {
+    let cell = |column: &str| row.get(column).map(|value| value.trim());
+    let text = |column: &str| cell(column).map(|value| Some(value.to_string()).filter(|v| !v.is_empty()));
+
+    let ip_address = cell("ip_address").unwrap_or_default();
+    if ip_address.parse::<std::net::IpAddr>().is_err() {
+        return Err(format!("invalid IP address \"{}\"", ip_address));
+    }
+
+    let existing = assets.find_by_ip(client_id, ip_address).map_err(|e| e.to_string())?;
+    let created = existing.is_none();
+    let mut asset = match existing {
+        Some(asset) => asset,
+        None => Asset {
+            id: Uuid::new_v4().to_string(),
+            client_id: client_id.to_string(),
+            name: ip_address.to_string(),
+            ip_address: ip_address.to_string(),
+            mac_address: None,
+            category: AssetCategory::Unknown,
+            operating_system: None,
+            criticality: Criticality::Medium,
+            status: AssetStatus::Active,
+            location: None,
+            owner: None,
+            description: None,
+            // Discovered services come from scans; a file only seeds new assets
+            services: parse_services(cell("services").unwrap_or_default())?,
+            tags: vec![],
+            first_seen: text("first_seen").flatten().unwrap_or_else(|| now.to_string()),
+            last_seen: text("last_seen").flatten().unwrap_or_else(|| now.to_string()),
+            scan_ids: vec![],
+            metadata: None,
+        },
+    };
+
+    if let Some(name) = text("name").flatten() {
+        asset.name = name;
+    }
+    if let Some(category) = cell("category").filter(|v| !v.is_empty()) {
+        asset.category = category.replace(' ', "_").parse()?;
+    }
+    if let Some(criticality) = cell("criticality").filter(|v| !v.is_empty()) {
+        asset.criticality = criticality.parse()?;
+    }
+    if let Some(status) = cell("status").filter(|v| !v.is_empty()) {
+        asset.status = status.parse()?;
+    }
+    if let Some(mac_address) = text("mac_address") {
+        asset.mac_address = mac_address;
+    }
+    if let Some(operating_system) = text("operating_system") {
+        asset.operating_system = operating_system;
+    }
+    if let Some(location) = text("location") {
+        asset.location = location;
+    }
+    if let Some(owner) = text("owner") {
+        asset.owner = owner;
+    }
+    if let Some(description) = text("description") {
+        asset.description = description;
+    }
+    if let Some(tags) = cell("tags") {
+        let tags: Vec<String> = tags.split(';').map(String::from).collect();
+        asset.tags = normalize_tags(&tags);
+    }
+
+    assets.save(&asset).map_err(|e| e.to_string())?;
+    Ok(created)
+}
+
+/// Upsert assets from CSV or JSON data, matching existing assets by IP
+///
+/// Bad rows are skipped with a warning; a leading UTF-8 BOM is ignored.
+pub fn import_assets(
+    db: &Database,
+    client_id: &str,
+    data: &str,
+    format: AssetExportFormat,
+) -> OptioResult<AssetImportSummary> {
+    let data = data.strip_prefix(UTF8_BOM).unwrap_or(data);
+    let rows = match format {
+        AssetExportFormat::Csv => csv_rows(data),
+        AssetExportFormat::Json => json_rows(data),
+    }
+    .map_err(OptioError::Validation)?;
+
+    let assets = AssetRepository::new(db);
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut summary = AssetImportSummary::default();
+
+    for (line, row) in rows {
+        match row.and_then(|row| import_row(&assets, client_id, &row, &now)) {
+            Ok(true) => summary.created += 1,
+            Ok(false) => summary.updated += 1,
+            Err(e) => summary.warnings.push(format!("{}: {}", line, e)),
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::network::repository::init_network_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn asset(ip_address: &str, owner: Option<&str>) -> Asset {
+        Asset {
+            id: Uuid::new_v4().to_string(),
+            client_id: "client-1".to_string(),
+            name: "web, \"primary\"".to_string(),
+            ip_address: ip_address.to_string(),
+            mac_address: None,
+            category: AssetCategory::NetworkDevice,
+            operating_system: Some("Linux".to_string()),
+            criticality: Criticality::Low,
+            status: AssetStatus::Active,
+            location: None,
+            owner: owner.map(String::from),
+            description: Some("Line one\nline two".to_string()),
+            services: vec![
+                AssetService {
+                    port: 22,
+                    protocol: Protocol::Tcp,
+                    name: "ssh".to_string(),
+                    version: Some("OpenSSH 8.9".to_string()),
+                    state: PortState::Open,
+                },
+                AssetService {
+                    port: 443,
+                    protocol: Protocol::Tcp,
+                    name: "https".to_string(),
+                    version: None,
+                    state: PortState::Open,
+                },
+            ],
+            tags: vec!["dmz".to_string()],
+            first_seen: "2024-01-01T00:00:00Z".to_string(),
+            last_seen: "2024-01-02T00:00:00Z".to_string(),
+            scan_ids: vec![],
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_services_round_trip() {
+        let services = asset("10.0.0.1", None).services;
+        let flattened = flatten_services(&services);
+        assert_eq!(flattened, "22/tcp ssh OpenSSH 8.9; 443/tcp https");
+
+        let parsed = parse_services(&flattened).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].version.as_deref(), Some("OpenSSH 8.9"));
+        assert_eq!(parsed[1].name, "https");
+        assert!(parse_services("ssh/tcp").is_err());
+    }
+
+    #[test]
+    fn test_csv_round_trip_updates_by_ip() {
+        let db = test_db();
+        let repo = AssetRepository::new(&db);
+        repo.save(&asset("10.0.0.1", Some("IT"))).unwrap();
+        repo.save(&asset("10.0.0.2", None)).unwrap();
+
+        let csv = assets_to_csv(&repo.list_by_client("client-1").unwrap(), true).unwrap();
+        assert!(csv.starts_with(UTF8_BOM));
+        assert!(csv.contains("\"web, \"\"primary\"\"\""));
+
+        // Bulk-edit in a spreadsheet: new owner and criticality, one new host, one bad row
+        let edited = csv
+            .replace(",Low,", ",High,")
+            .replace(",IT,", ",Finance,")
+            + "10.0.0.3,printer-1,,Printer,Medium,Active,,,,,,9100/tcp jetdirect,,\n"
+            + "not-an-ip,bad,,,,,,,,,,,,\n";
+
+        let summary = import_assets(&db, "client-1", &edited, AssetExportFormat::Csv).unwrap();
+        assert_eq!(summary.updated, 2);
+        assert_eq!(summary.created, 1);
+        assert_eq!(summary.warnings.len(), 1);
+        assert!(summary.warnings[0].starts_with("Line 7"));
+
+        let first = repo.find_by_ip("client-1", "10.0.0.1").unwrap().unwrap();
+        assert_eq!(first.owner.as_deref(), Some("Finance"));
+        assert_eq!(first.criticality, Criticality::High);
+        assert_eq!(first.name, "web, \"primary\"");
+        assert_eq!(first.description.as_deref(), Some("Line one\nline two"));
+        assert_eq!(first.services.len(), 2);
+
+        let printer = repo.find_by_ip("client-1", "10.0.0.3").unwrap().unwrap();
+        assert_eq!(printer.category, AssetCategory::Printer);
+        assert_eq!(printer.services[0].port, 9100);
+    }
+
+    #[test]
+    fn test_json_import_leaves_missing_fields() {
+        let db = test_db();
+        let repo = AssetRepository::new(&db);
+        repo.save(&asset("10.0.0.1", Some("IT"))).unwrap();
+
+        let json = r#"[{"ipAddress": "10.0.0.1", "criticality": "critical", "tags": ["pci", "dmz"]}]"#;
+        let summary = import_assets(&db, "client-1", json, AssetExportFormat::Json).unwrap();
+        assert_eq!(summary.updated, 1);
+
+        let updated = repo.find_by_ip("client-1", "10.0.0.1").unwrap().unwrap();
+        assert_eq!(updated.criticality, Criticality::Critical);
+        assert_eq!(updated.owner.as_deref(), Some("IT"));
+        assert_eq!(updated.tags, vec!["pci".to_string(), "dmz".to_string()]);
+
+        let exported: Vec<AssetRecord> = serde_json::from_str(&assets_to_json(&[updated]).unwrap()).unwrap();
+        assert_eq!(exported[0].category, "NetworkDevice");
+    }
+}
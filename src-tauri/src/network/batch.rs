@@ -0,0 +1,537 @@
+//! Batched Scans
+//!
+//! Large target lists are scanned in batches rather than one Nmap run: IPv4
+//! CIDRs are expanded to single addresses, split into fixed-size batches and
+//! run one batch (or a few) at a time. Each finished batch is stored as soon
+//! as it completes, so a scan that crashed or was cancelled resumes from the
+//! batches still outstanding. The batch results are merged into one set of
+//! scan results, with hosts from overlapping target specs listed once.
+
+use super::inventory::AssetInventory;
+use super::models::*;
+use super::repository::{ScanRepository, ScannerSettingsRepository};
+use super::scanner::{apply_scan_privileges, detect_scan_privileges, locate_nmap, run_nmap_scan};
+use crate::db::Database;
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use ipnetwork::IpNetwork;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Most addresses a batched scan may expand to (a /12)
+pub const MAX_EXPANDED_TARGETS: usize = 1 << 20;
+
+/// Most batches that may run at once
+pub const MAX_BATCH_CONCURRENCY: usize = 8;
+
+/// How often a running scan checks whether it was cancelled
+const CANCEL_POLL: Duration = Duration::from_millis(250);
+
+/// Check a scan's batching and rate settings
+pub fn validate_batching(config: &ScanConfig) -> Result<(), String> {
+    if config.batch_size == Some(0) {
+        return Err("Batch size must be at least 1".to_string());
+    }
+    if let Some(concurrency) = config.batch_concurrency {
+        if concurrency == 0 || concurrency > MAX_BATCH_CONCURRENCY {
+            return Err(format!("Batch concurrency must be between 1 and {}", MAX_BATCH_CONCURRENCY));
+        }
+    }
+    if config.max_packets_per_second == Some(0) {
+        return Err("Maximum packet rate must be at least 1 per second".to_string());
+    }
+    Ok(())
+}
+
+/// Expand IPv4 CIDRs to single addresses, dropping repeated targets
+///
+/// Like Nmap, a CIDR includes its network and broadcast addresses. Ranges,
+/// hostnames and IPv6 networks are kept as they are, in their original order.
+pub fn expand_targets(targets: &[String]) -> Result<Vec<String>, String> {
+    let mut expanded = Vec::new();
+    let mut seen = HashSet::new();
+
+    for target in targets.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        match target.parse::<IpNetwork>() {
+            Ok(IpNetwork::V4(net)) if target.contains('/') => {
+                for ip in net.iter() {
+                    push_unique(&mut expanded, &mut seen, ip.to_string());
+                    if seen.len() > MAX_EXPANDED_TARGETS {
+                        return Err(format!(
+                            "Targets expand to more than {} addresses; split the scan",
+                            MAX_EXPANDED_TARGETS
+                        ));
+                    }
+                }
+            }
+            _ => push_unique(&mut expanded, &mut seen, target.to_string()),
+        }
+    }
+
+    Ok(expanded)
+}
+
+fn push_unique(expanded: &mut Vec<String>, seen: &mut HashSet<String>, target: String) {
+    if seen.insert(target.clone()) {
+        expanded.push(target);
+    }
+}
+
+/// Number of batches `target_count` targets split into
+pub fn batch_count(target_count: usize, batch_size: usize) -> usize {
+    if batch_size == 0 {
+        return 0;
+    }
+    (target_count + batch_size - 1) / batch_size
+}
+
+/// Split a scan's targets into batches
+///
+/// Without a batch size the targets form a single batch, unexpanded.
+pub fn plan_batches(scan_id: &str, config: &ScanConfig) -> Result<Vec<ScanBatch>, String> {
+    let groups: Vec<Vec<String>> = match config.batch_size {
+        Some(size) if size > 0 => expand_targets(&config.targets)?
+            .chunks(size)
+            .map(|chunk| chunk.to_vec())
+            .collect(),
+        _ if config.targets.is_empty() => vec![],
+        _ => vec![config.targets.clone()],
+    };
+
+    Ok(groups
+        .into_iter()
+        .enumerate()
+        .map(|(index, targets)| ScanBatch {
+            scan_id: scan_id.to_string(),
+            index: index as u32,
+            targets,
+            status: ScanStatus::Queued,
+            results: None,
+            error: None,
+            completed_at: None,
+        })
+        .collect())
+}
+
+/// Merge batch results, in batch order, into one scan's results
+///
+/// A host found by more than one batch is listed once, with the ports,
+/// scripts and details each batch found. The duration is the sum of the
+/// batch durations; start and end times come from the first and last batch.
+pub fn merge_batch_results(scan_id: &str, parts: &[ScanResults]) -> ScanResults {
+    let mut hosts: Vec<DiscoveredHost> = Vec::new();
+    let mut by_ip: HashMap<String, usize> = HashMap::new();
+    let mut repeated = 0u32;
+
+    for host in parts.iter().flat_map(|p| &p.hosts) {
+        match by_ip.get(&host.ip_address) {
+            Some(&i) => {
+                repeated += 1;
+                merge_host(&mut hosts[i], host);
+            }
+            None => {
+                by_ip.insert(host.ip_address.clone(), hosts.len());
+                hosts.push(host.clone());
+            }
+        }
+    }
+
+    let command_line = match parts {
+        [] => String::new(),
+        [only] => only.command_line.clone(),
+        [first, rest @ ..] => format!("{} (+{} more batches)", first.command_line, rest.len()),
+    };
+
+    ScanResults {
+        scan_id: scan_id.to_string(),
+        hosts_scanned: parts.iter().map(|p| p.hosts_scanned).sum::<u32>().saturating_sub(repeated),
+        hosts_up: hosts.iter().filter(|h| h.status == "up").count() as u32,
+        duration_seconds: parts.iter().map(|p| p.duration_seconds).sum(),
+        nmap_version: parts.iter().find_map(|p| p.nmap_version.clone()),
+        command_line,
+        start_time: parts.first().map(|p| p.start_time.clone()).unwrap_or_default(),
+        end_time: parts.last().map(|p| p.end_time.clone()).unwrap_or_default(),
+        hosts,
+    }
+}
+
+fn merge_host(host: &mut DiscoveredHost, other: &DiscoveredHost) {
+    if other.status == "up" {
+        host.status = other.status.clone();
+    }
+    if host.mac_address.is_none() {
+        host.mac_address = other.mac_address.clone();
+    }
+    if host.hostname.is_none() {
+        host.hostname = other.hostname.clone();
+    }
+    if host.vendor.is_none() {
+        host.vendor = other.vendor.clone();
+    }
+    for port in &other.ports {
+        if !host.ports.iter().any(|p| p.port == port.port && p.protocol == port.protocol) {
+            host.ports.push(port.clone());
+        }
+    }
+    host.ports.sort_by_key(|p| p.port);
+    if host.os_matches.is_empty() {
+        host.os_matches = other.os_matches.clone();
+    }
+    for script in &other.host_scripts {
+        if !host.host_scripts.iter().any(|s| s.id == script.id) {
+            host.host_scripts.push(script.clone());
+        }
+    }
+    if host.extra_ports.is_empty() {
+        host.extra_ports = other.extra_ports.clone();
+    }
+}
+
+/// Run a scan's outstanding batches and merge the results of all of them
+///
+/// Batches are planned and stored on the first run; later runs pick up the
+/// batches not yet completed. The job's progress is saved, and passed to
+/// `on_progress`, after each batch and whenever a running batch's Nmap
+/// reports progress. Stops at the first failed batch, or when `cancelled`
+/// is set, killing the batches still running. Returns the merged results
+/// and, for a single-batch scan, Nmap's XML.
+pub async fn run_scan_batches(
+    db: &Database,
+    nmap: &str,
+    job: &mut ScanJob,
+    cancelled: &AtomicBool,
+    mut on_progress: impl FnMut(&ScanJob),
+) -> Result<(ScanResults, Option<String>), String> {
+    let repo = ScanRepository::new(db);
+    let mut batches = repo.list_batches(&job.id).map_err(|e| e.to_string())?;
+    if batches.is_empty() {
+        batches = plan_batches(&job.id, &job.config)?;
+        repo.save_batches(&batches).map_err(|e| e.to_string())?;
+    }
+    if batches.is_empty() {
+        return Err("No scan targets specified".to_string());
+    }
+
+    let total = batches.len() as u32;
+    let pending: Vec<ScanBatch> = batches.iter().filter(|b| b.status != ScanStatus::Completed).cloned().collect();
+    let mut completed = total - pending.len() as u32;
+    let mut xml = None;
+    tracing::info!("Running scan {}: {} of {} batches outstanding", job.id, pending.len(), total);
+
+    let config = job.config.clone();
+    let concurrency = config.batch_concurrency.unwrap_or(1).clamp(1, MAX_BATCH_CONCURRENCY);
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel::<(u32, ScanLiveProgress)>();
+    let mut runs = stream::iter(pending)
+        .map(|batch| {
+            let config = ScanConfig { targets: batch.targets.clone(), ..config.clone() };
+            let progress_tx = progress_tx.clone();
+            async move {
+                let index = batch.index;
+                let outcome = run_nmap_scan(nmap, &config, |progress| {
+                    let _ = progress_tx.send((index, progress.clone()));
+                })
+                .await;
+                (batch, outcome)
+            }
+        })
+        .buffer_unordered(concurrency);
+
+    // Nmap's latest report for each running batch, and hosts finished by the others
+    let mut running: HashMap<u32, ScanLiveProgress> = HashMap::new();
+    let mut finished: HashSet<u32> = HashSet::new();
+    let mut hosts_done = 0;
+
+    loop {
+        let next = tokio::select! {
+            next = runs.next() => next,
+            Some((index, progress)) = progress_rx.recv() => {
+                // A report can trail its batch's completion
+                if !finished.contains(&index) {
+                    let task = progress.task.clone();
+                    running.insert(index, progress);
+                    let live = overall_progress(completed, total, hosts_done, &running, task);
+                    job.progress = job.progress.max(live.percent_done as u8).min(99);
+                    job.live = Some(live);
+                    if let Err(e) = repo.save(job) {
+                        tracing::warn!("Failed to store progress of scan {}: {}", job.id, e);
+                    }
+                    on_progress(job);
+                }
+                continue;
+            }
+            _ = wait_for_cancel(cancelled) => return Err("Scan cancelled".to_string()),
+        };
+        let (mut batch, outcome) = match next {
+            Some(run) => run,
+            None => break,
+        };
+
+        batch.completed_at = Some(Utc::now().to_rfc3339());
+        finished.insert(batch.index);
+        running.remove(&batch.index);
+        let failure = match outcome {
+            Ok((results, batch_xml)) => {
+                hosts_done += results.hosts.len() as u32;
+                batch.status = ScanStatus::Completed;
+                batch.results = Some(results);
+                if total == 1 {
+                    xml = Some(batch_xml);
+                }
+                None
+            }
+            Err(e) => {
+                batch.status = ScanStatus::Failed;
+                batch.error = Some(e.clone());
+                Some(format!("Batch {} of {} failed: {}", batch.index + 1, total, e))
+            }
+        };
+        repo.save_batches(std::slice::from_ref(&batch)).map_err(|e| e.to_string())?;
+        if let Some(failure) = failure {
+            return Err(failure);
+        }
+
+        completed += 1;
+        let task = job.live.as_ref().and_then(|l| l.task.clone());
+        let live = overall_progress(completed, total, hosts_done, &running, task);
+        job.progress = job.progress.max((completed * 100 / total) as u8).min(99);
+        job.live = Some(live);
+        job.batches = Some(ScanBatchProgress { completed, total });
+        if let Err(e) = repo.save(job) {
+            tracing::warn!("Failed to store progress of scan {}: {}", job.id, e);
+        }
+        on_progress(job);
+    }
+
+    let parts: Vec<ScanResults> = repo
+        .list_batches(&job.id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter_map(|b| b.results)
+        .collect();
+    Ok((merge_batch_results(&job.id, &parts), xml))
+}
+
+/// Progress across a scan's batches, counting each running batch by how far
+/// Nmap is through its current phase
+fn overall_progress(
+    completed: u32,
+    total: u32,
+    hosts_done: u32,
+    running: &HashMap<u32, ScanLiveProgress>,
+    task: Option<String>,
+) -> ScanLiveProgress {
+    let in_flight: f64 = running.values().map(|p| p.percent_done.clamp(0.0, 100.0) / 100.0).sum();
+    ScanLiveProgress {
+        percent_done: ((completed as f64 + in_flight) * 100.0 / total.max(1) as f64).min(100.0),
+        hosts_completed: hosts_done + running.values().map(|p| p.hosts_completed).sum::<u32>(),
+        eta_seconds: running.values().filter_map(|p| p.eta_seconds).max(),
+        task,
+    }
+}
+
+async fn wait_for_cancel(cancelled: &AtomicBool) {
+    while !cancelled.load(Ordering::Relaxed) {
+        tokio::time::sleep(CANCEL_POLL).await;
+    }
+}
+
+/// Live hosts and asset updates from running a scan job
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScanJobRun {
+    pub hosts_up: usize,
+    pub assets_updated: usize,
+}
+
+/// Run a saved scan job to completion, or resume it
+///
+/// The scan is fitted to this session's raw packet access first, since
+/// privileges may have changed since it was created, and then passed to
+/// `on_launch` before Nmap runs, so the launch can be audited with the
+/// arguments Nmap actually gets; an error from it fails the scan. Live hosts
+/// are upserted into the client's asset inventory and the merged results are
+/// stored against the job, which is Parsing meanwhile. The job ends up Completed,
+/// Failed or Cancelled; the caller saves it.
+pub async fn execute_scan_job(
+    db: &Database,
+    job: &mut ScanJob,
+    cancelled: &AtomicBool,
+    on_launch: impl FnOnce(&ScanJob) -> Result<(), String>,
+    mut on_progress: impl FnMut(&ScanJob),
+) -> ScanJobRun {
+    let mut run = ScanJobRun::default();
+
+    let configured = ScannerSettingsRepository::new(db).nmap_path().unwrap_or_else(|e| {
+        tracing::warn!("Failed to load the configured Nmap path: {}", e);
+        None
+    });
+    let outcome = match locate_nmap(configured.as_deref()) {
+        Some(nmap) => match apply_scan_privileges(&mut job.config, &detect_scan_privileges(Some(&nmap.path))) {
+            Ok(warning) => {
+                // A resumed scan already carries its warning
+                if let Some(warning) = warning.filter(|w| !job.warnings.contains(w)) {
+                    job.warnings.push(warning);
+                }
+                match on_launch(job) {
+                    Ok(()) => run_scan_batches(db, &nmap.path, job, cancelled, &mut on_progress).await,
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        },
+        None => Err("Nmap was not found on PATH or in its standard install locations".to_string()),
+    };
+
+    job.live = None;
+    match outcome {
+        Ok((mut results, xml)) => {
+            job.status = ScanStatus::Parsing;
+            if let Err(e) = ScanRepository::new(db).save(job) {
+                tracing::warn!("Failed to store progress of scan {}: {}", job.id, e);
+            }
+            on_progress(job);
+
+            let inventory = AssetInventory::new(db);
+            for host in results.hosts.iter().filter(|h| h.status == "up") {
+                run.hosts_up += 1;
+                match inventory.upsert_from_discovery(&job.client_id, host, &job.id) {
+                    Ok(_) => run.assets_updated += 1,
+                    Err(e) => tracing::warn!("Failed to update asset {} from scan: {}", host.ip_address, e),
+                }
+            }
+
+            results.scan_id = job.id.clone();
+            if let Err(e) = ScanRepository::new(db).save_results(&job.id, &results, xml.as_deref()) {
+                tracing::warn!("Failed to store results of scan {}: {}", job.id, e);
+            }
+
+            job.status = ScanStatus::Completed;
+            job.progress = 100;
+            job.error = None;
+            job.totals = Some(ScanTotals::from_results(&results));
+        }
+        Err(_) if cancelled.load(Ordering::Relaxed) => {
+            job.status = ScanStatus::Cancelled;
+        }
+        Err(e) => {
+            job.status = ScanStatus::Failed;
+            job.error = Some(e);
+        }
+    }
+    job.completed_at = Some(Utc::now().to_rfc3339());
+
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(ip: &str, ports: &[u16]) -> DiscoveredHost {
+        DiscoveredHost {
+            ip_address: ip.to_string(),
+            mac_address: None,
+            hostname: None,
+            vendor: None,
+            status: "up".to_string(),
+            ports: ports
+                .iter()
+                .map(|&port| DiscoveredPort {
+                    port,
+                    protocol: Protocol::Tcp,
+                    state: PortState::Open,
+                    service: None,
+                    product: None,
+                    version: None,
+                    extra_info: None,
+                    scripts: vec![],
+                })
+                .collect(),
+            os_matches: vec![],
+            host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
+        }
+    }
+
+    fn results(hosts: Vec<DiscoveredHost>, scanned: u32) -> ScanResults {
+        ScanResults {
+            scan_id: String::new(),
+            hosts_up: hosts.len() as u32,
+            hosts,
+            hosts_scanned: scanned,
+            duration_seconds: 10.0,
+            nmap_version: Some("7.94".to_string()),
+            command_line: "nmap -sS".to_string(),
+            start_time: "start".to_string(),
+            end_time: "end".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_expand_targets() {
+        let targets = vec![
+            "10.0.0.0/30".to_string(),
+            "10.0.0.2".to_string(),
+            "scanme.example.com".to_string(),
+            "10.0.1.1-20".to_string(),
+            "10.0.0.3/32".to_string(),
+        ];
+        assert_eq!(
+            expand_targets(&targets).unwrap(),
+            vec!["10.0.0.0", "10.0.0.1", "10.0.0.2", "10.0.0.3", "scanme.example.com", "10.0.1.1-20"]
+        );
+
+        assert_eq!(expand_targets(&["192.168.0.0/16".to_string()]).unwrap().len(), 65536);
+        assert!(expand_targets(&["10.0.0.0/8".to_string()]).is_err());
+        assert_eq!(expand_targets(&["fd00::/64".to_string()]).unwrap(), vec!["fd00::/64"]);
+    }
+
+    #[test]
+    fn test_plan_batches() {
+        assert_eq!(batch_count(256, 100), 3);
+        assert_eq!(batch_count(200, 100), 2);
+        assert_eq!(batch_count(0, 100), 0);
+
+        let mut config = ScanConfig {
+            targets: vec!["10.0.0.0/24".to_string(), "10.0.0.10".to_string()],
+            batch_size: Some(100),
+            ..Default::default()
+        };
+        let batches = plan_batches("scan-1", &config).unwrap();
+        assert_eq!(batches.len(), batch_count(256, 100));
+        assert_eq!(batches.iter().map(|b| b.targets.len()).collect::<Vec<_>>(), vec![100, 100, 56]);
+        assert_eq!(batches[2].index, 2);
+        assert_eq!(batches[1].targets[0], "10.0.0.100");
+
+        // Unbatched scans run their targets as given
+        config.batch_size = None;
+        let single = plan_batches("scan-1", &config).unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].targets, config.targets);
+
+        config.batch_size = Some(0);
+        assert!(validate_batching(&config).is_err());
+    }
+
+    #[test]
+    fn test_merge_batch_results() {
+        let first = results(vec![host("10.0.0.1", &[22]), host("10.0.0.2", &[80])], 100);
+        let mut second = results(vec![host("10.0.0.1", &[443, 22]), host("10.0.0.3", &[])], 100);
+        second.hosts[0].hostname = Some("gw.local".to_string());
+
+        let merged = merge_batch_results("scan-1", &[first, second]);
+        assert_eq!(merged.scan_id, "scan-1");
+        assert_eq!(merged.hosts.len(), 3);
+        assert_eq!(merged.hosts_up, 3);
+        assert_eq!(merged.hosts_scanned, 199);
+        assert_eq!(merged.duration_seconds, 20.0);
+        assert_eq!(merged.command_line, "nmap -sS (+1 more batches)");
+
+        let gateway = &merged.hosts[0];
+        assert_eq!(gateway.ports.iter().map(|p| p.port).collect::<Vec<_>>(), vec![22, 443]);
+        assert_eq!(gateway.hostname.as_deref(), Some("gw.local"));
+    }
+}
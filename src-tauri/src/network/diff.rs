@@ -0,0 +1,524 @@
+//! Scan Diffing
+//!
+//! Compares two scans of the same client and reports what changed between
+//! them: hosts that appeared or disappeared, ports opened or closed, service
+//! version changes, and OS fingerprint changes. A scan can also be compared
+//! with the client's asset inventory, as what was known before it.
+
+use super::models::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// ============================================================================
+// Diff Types
+// ============================================================================
+
+/// Differences between an earlier scan (A) and a later scan (B)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanDiff {
+    /// Earlier scan
+    pub scan_id_a: String,
+    /// Later scan
+    pub scan_id_b: String,
+    /// Hosts only seen in the later scan
+    pub hosts_appeared: Vec<DiffHost>,
+    /// Hosts only seen in the earlier scan
+    pub hosts_disappeared: Vec<DiffHost>,
+    /// Hosts seen in both scans that changed
+    pub hosts_changed: Vec<HostDiff>,
+    /// Hosts seen in both scans with no changes
+    pub hosts_unchanged: usize,
+}
+
+impl ScanDiff {
+    /// Whether the two scans saw exactly the same thing
+    pub fn is_empty(&self) -> bool {
+        self.hosts_appeared.is_empty() && self.hosts_disappeared.is_empty() && self.hosts_changed.is_empty()
+    }
+
+    /// Total ports opened across changed hosts
+    pub fn ports_opened(&self) -> usize {
+        self.hosts_changed.iter().map(|h| h.ports_opened.len()).sum()
+    }
+
+    /// Total ports closed across changed hosts
+    pub fn ports_closed(&self) -> usize {
+        self.hosts_changed.iter().map(|h| h.ports_closed.len()).sum()
+    }
+}
+
+/// A host that appeared or disappeared between scans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHost {
+    pub ip_address: String,
+    pub mac_address: Option<String>,
+    pub hostname: Option<String>,
+    /// Open ports at the time the host was seen
+    pub open_ports: Vec<PortRef>,
+}
+
+/// Changes to a host present in both scans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostDiff {
+    /// Address in the later scan
+    pub ip_address: String,
+    /// Address in the earlier scan, when it changed (same MAC, new IP)
+    pub previous_ip_address: Option<String>,
+    pub mac_address: Option<String>,
+    pub hostname: Option<String>,
+    pub ports_opened: Vec<PortRef>,
+    pub ports_closed: Vec<PortRef>,
+    pub service_changes: Vec<ServiceChange>,
+    pub os_change: Option<OsChange>,
+}
+
+impl HostDiff {
+    fn has_changes(&self) -> bool {
+        self.previous_ip_address.is_some()
+            || !self.ports_opened.is_empty()
+            || !self.ports_closed.is_empty()
+            || !self.service_changes.is_empty()
+            || self.os_change.is_some()
+    }
+}
+
+/// An open port referenced in a diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortRef {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub service: Option<String>,
+}
+
+/// A port open in both scans whose detected service changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceChange {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub old_service: Option<String>,
+    pub new_service: Option<String>,
+}
+
+/// Best OS match changed between scans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OsChange {
+    pub old_os: String,
+    pub new_os: String,
+}
+
+/// Stands in for a scan ID when one side of a diff is the asset inventory
+pub const INVENTORY_DIFF_ID: &str = "inventory";
+
+// ============================================================================
+// Diffing
+// ============================================================================
+
+/// Compare an earlier scan with a later one
+///
+/// Hosts are matched by MAC address when both scans recorded one, so a
+/// device that picked up a new DHCP lease is reported as a changed host and
+/// an IP handed to a different device is reported as one host leaving and
+/// another appearing. Hosts without a MAC on either side fall back to IP.
+pub fn diff_scans(a: &ScanResults, b: &ScanResults) -> ScanDiff {
+    let hosts_a: Vec<&DiscoveredHost> = a.hosts.iter().filter(|h| h.status != "down").collect();
+    let hosts_b: Vec<&DiscoveredHost> = b.hosts.iter().filter(|h| h.status != "down").collect();
+
+    let pairs = match_hosts(&hosts_a, &hosts_b);
+    let matched_a: HashSet<usize> = pairs.iter().map(|(i, _)| *i).collect();
+    let matched_b: HashSet<usize> = pairs.iter().map(|(_, j)| *j).collect();
+
+    let mut hosts_changed = Vec::new();
+    let mut hosts_unchanged = 0;
+    for (i, j) in &pairs {
+        let diff = diff_host(hosts_a[*i], hosts_b[*j]);
+        if diff.has_changes() {
+            hosts_changed.push(diff);
+        } else {
+            hosts_unchanged += 1;
+        }
+    }
+
+    ScanDiff {
+        scan_id_a: a.scan_id.clone(),
+        scan_id_b: b.scan_id.clone(),
+        hosts_appeared: hosts_b
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| !matched_b.contains(j))
+            .map(|(_, h)| diff_host_entry(h))
+            .collect(),
+        hosts_disappeared: hosts_a
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !matched_a.contains(i))
+            .map(|(_, h)| diff_host_entry(h))
+            .collect(),
+        hosts_changed,
+        hosts_unchanged,
+    }
+}
+
+/// A client's asset inventory as scan results, to diff a scan against
+///
+/// Only active assets are included, since the others aren't expected to
+/// answer a scan. Each asset's recorded services are its ports.
+pub fn inventory_results(assets: &[Asset]) -> ScanResults {
+    let hosts: Vec<DiscoveredHost> = assets
+        .iter()
+        .filter(|a| a.status == AssetStatus::Active)
+        .map(|a| DiscoveredHost {
+            ip_address: a.ip_address.clone(),
+            mac_address: a.mac_address.clone(),
+            hostname: Some(a.name.clone()).filter(|n| *n != a.ip_address),
+            vendor: None,
+            status: "up".to_string(),
+            ports: a
+                .services
+                .iter()
+                .map(|s| DiscoveredPort {
+                    port: s.port,
+                    protocol: s.protocol,
+                    state: s.state,
+                    service: Some(s.name.clone()).filter(|n| n != "unknown"),
+                    product: s.version.clone(),
+                    version: None,
+                    extra_info: None,
+                    scripts: vec![],
+                })
+                .collect(),
+            os_matches: a
+                .operating_system
+                .iter()
+                .map(|os| OsMatch { name: os.clone(), accuracy: 100, os_family: None, os_gen: None, device_type: None })
+                .collect(),
+            host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
+        })
+        .collect();
+
+    ScanResults {
+        scan_id: INVENTORY_DIFF_ID.to_string(),
+        hosts_scanned: hosts.len() as u32,
+        hosts_up: hosts.len() as u32,
+        hosts,
+        duration_seconds: 0.0,
+        nmap_version: None,
+        command_line: String::new(),
+        start_time: String::new(),
+        end_time: String::new(),
+    }
+}
+
+/// Pair up hosts from both scans: MAC first, then IP where a MAC is missing
+fn match_hosts(hosts_a: &[&DiscoveredHost], hosts_b: &[&DiscoveredHost]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    let mut used_a = HashSet::new();
+    let mut used_b = HashSet::new();
+
+    let macs_a: HashMap<String, usize> = hosts_a
+        .iter()
+        .enumerate()
+        .filter_map(|(i, h)| normalize_mac(h).map(|mac| (mac, i)))
+        .collect();
+    for (j, host) in hosts_b.iter().enumerate() {
+        if let Some(i) = normalize_mac(host).and_then(|mac| macs_a.get(&mac)) {
+            if used_a.insert(*i) {
+                used_b.insert(j);
+                pairs.push((*i, j));
+            }
+        }
+    }
+
+    for (j, host_b) in hosts_b.iter().enumerate() {
+        if used_b.contains(&j) {
+            continue;
+        }
+        let candidate = hosts_a.iter().enumerate().find(|(i, host_a)| {
+            !used_a.contains(i)
+                && host_a.ip_address == host_b.ip_address
+                && (normalize_mac(host_a).is_none() || normalize_mac(host_b).is_none())
+        });
+        if let Some((i, _)) = candidate {
+            used_a.insert(i);
+            used_b.insert(j);
+            pairs.push((i, j));
+        }
+    }
+
+    pairs.sort_unstable_by_key(|(_, j)| *j);
+    pairs
+}
+
+fn normalize_mac(host: &DiscoveredHost) -> Option<String> {
+    host.mac_address
+        .as_ref()
+        .map(|m| m.trim().to_uppercase().replace('-', ":"))
+        .filter(|m| !m.is_empty())
+}
+
+fn diff_host(a: &DiscoveredHost, b: &DiscoveredHost) -> HostDiff {
+    let ports_a = open_ports(a);
+    let ports_b = open_ports(b);
+
+    let mut ports_opened = Vec::new();
+    let mut service_changes = Vec::new();
+    for (key, port_b) in &ports_b {
+        match ports_a.get(key) {
+            None => ports_opened.push(port_ref(port_b)),
+            Some(port_a) => {
+                let old_service = service_label(port_a);
+                let new_service = service_label(port_b);
+                // A scan without version detection says nothing about versions
+                let comparable = has_version_info(port_a) == has_version_info(port_b) || port_a.service != port_b.service;
+                if old_service != new_service && comparable {
+                    service_changes.push(ServiceChange {
+                        port: port_b.port,
+                        protocol: port_b.protocol,
+                        old_service,
+                        new_service,
+                    });
+                }
+            }
+        }
+    }
+    let ports_closed: Vec<PortRef> = ports_a
+        .iter()
+        .filter(|(key, _)| !ports_b.contains_key(*key))
+        .map(|(_, p)| port_ref(p))
+        .collect();
+
+    // Only a change when both scans fingerprinted the host; a scan without -O
+    // says nothing about the OS
+    let os_change = match (best_os(a), best_os(b)) {
+        (Some(old_os), Some(new_os)) if old_os != new_os => Some(OsChange { old_os, new_os }),
+        _ => None,
+    };
+
+    HostDiff {
+        ip_address: b.ip_address.clone(),
+        previous_ip_address: Some(a.ip_address.clone()).filter(|ip| *ip != b.ip_address),
+        mac_address: b.mac_address.clone().or_else(|| a.mac_address.clone()),
+        hostname: b.hostname.clone().or_else(|| a.hostname.clone()),
+        ports_opened,
+        ports_closed,
+        service_changes,
+        os_change,
+    }
+}
+
+/// Open ports keyed by (port, protocol), ordered for stable output
+fn open_ports(host: &DiscoveredHost) -> BTreeMap<(u16, String), &DiscoveredPort> {
+    host.ports
+        .iter()
+        .filter(|p| p.state == PortState::Open)
+        .map(|p| ((p.port, format!("{:?}", p.protocol)), p))
+        .collect()
+}
+
+fn port_ref(port: &DiscoveredPort) -> PortRef {
+    PortRef {
+        port: port.port,
+        protocol: port.protocol,
+        service: port.service.clone(),
+    }
+}
+
+/// Service name plus product and version, e.g. "ssh OpenSSH 8.9p1"
+fn service_label(port: &DiscoveredPort) -> Option<String> {
+    let parts: Vec<&str> = [&port.service, &port.product, &port.version]
+        .iter()
+        .filter_map(|p| p.as_deref())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+fn has_version_info(port: &DiscoveredPort) -> bool {
+    port.product.is_some() || port.version.is_some()
+}
+
+fn best_os(host: &DiscoveredHost) -> Option<String> {
+    host.os_matches.iter().max_by_key(|m| m.accuracy).map(|m| m.name.clone())
+}
+
+fn diff_host_entry(host: &DiscoveredHost) -> DiffHost {
+    DiffHost {
+        ip_address: host.ip_address.clone(),
+        mac_address: host.mac_address.clone(),
+        hostname: host.hostname.clone(),
+        open_ports: open_ports(host).values().map(|p| port_ref(p)).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(ip: &str, mac: Option<&str>, ports: &[(u16, Option<&str>)]) -> DiscoveredHost {
+        DiscoveredHost {
+            ip_address: ip.to_string(),
+            mac_address: mac.map(String::from),
+            hostname: None,
+            vendor: None,
+            status: "up".to_string(),
+            ports: ports
+                .iter()
+                .map(|(port, version)| DiscoveredPort {
+                    port: *port,
+                    protocol: Protocol::Tcp,
+                    state: PortState::Open,
+                    service: Some("svc".to_string()),
+                    product: None,
+                    version: version.map(String::from),
+                    extra_info: None,
+                    scripts: vec![],
+                })
+                .collect(),
+            os_matches: vec![],
+            host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
+        }
+    }
+
+    fn scan(id: &str, hosts: Vec<DiscoveredHost>) -> ScanResults {
+        ScanResults {
+            scan_id: id.to_string(),
+            hosts_scanned: hosts.len() as u32,
+            hosts_up: hosts.len() as u32,
+            hosts,
+            duration_seconds: 1.0,
+            nmap_version: None,
+            command_line: String::new(),
+            start_time: String::new(),
+            end_time: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_port_and_service_changes() {
+        let a = scan("a", vec![host("10.0.0.1", None, &[(22, Some("8.9")), (80, None)]), host("10.0.0.2", None, &[])]);
+        let mut b_host = host("10.0.0.1", None, &[(22, Some("9.6")), (443, None)]);
+        b_host.os_matches.push(OsMatch {
+            name: "Linux 6.x".to_string(),
+            accuracy: 98,
+            os_family: None,
+            os_gen: None,
+            device_type: None,
+        });
+        let b = scan("b", vec![b_host, host("10.0.0.3", None, &[(3389, None)])]);
+
+        let diff = diff_scans(&a, &b);
+        assert_eq!(diff.hosts_appeared.len(), 1);
+        assert_eq!(diff.hosts_appeared[0].ip_address, "10.0.0.3");
+        assert_eq!(diff.hosts_disappeared[0].ip_address, "10.0.0.2");
+
+        let changed = &diff.hosts_changed[0];
+        assert_eq!(changed.ports_opened.iter().map(|p| p.port).collect::<Vec<_>>(), vec![443]);
+        assert_eq!(changed.ports_closed.iter().map(|p| p.port).collect::<Vec<_>>(), vec![80]);
+        assert_eq!(changed.service_changes[0].new_service.as_deref(), Some("svc 9.6"));
+        // Earlier scan had no OS detection, so nothing to compare against
+        assert!(changed.os_change.is_none());
+    }
+
+    #[test]
+    fn test_hosts_matched_by_mac() {
+        let a = scan("a", vec![
+            host("10.0.0.10", Some("aa:bb:cc:00:00:01"), &[(22, None)]),
+            host("10.0.0.20", Some("aa:bb:cc:00:00:02"), &[(22, None)]),
+        ]);
+        let b = scan("b", vec![
+            // Same device, new lease
+            host("10.0.0.11", Some("AA-BB-CC-00-00-01"), &[(22, None)]),
+            // Old address now belongs to a different device
+            host("10.0.0.20", Some("aa:bb:cc:00:00:03"), &[(22, None)]),
+        ]);
+
+        let diff = diff_scans(&a, &b);
+        assert_eq!(diff.hosts_changed.len(), 1);
+        assert_eq!(diff.hosts_changed[0].previous_ip_address.as_deref(), Some("10.0.0.10"));
+        assert_eq!(diff.hosts_appeared[0].mac_address.as_deref(), Some("aa:bb:cc:00:00:03"));
+        assert_eq!(diff.hosts_disappeared[0].mac_address.as_deref(), Some("aa:bb:cc:00:00:02"));
+        assert_eq!(diff.hosts_unchanged, 0);
+
+        // No MAC on one side falls back to the IP
+        let c = scan("c", vec![host("10.0.0.20", None, &[(22, None)])]);
+        let diff = diff_scans(&a, &c);
+        assert_eq!(diff.hosts_unchanged, 1);
+        assert!(diff.hosts_appeared.is_empty());
+    }
+
+    #[test]
+    fn test_filtered_ports_and_renamed_hosts() {
+        let mut before = host("10.0.0.5", None, &[(22, None), (3389, None)]);
+        before.hostname = Some("fs01".to_string());
+        before.ports[1].state = PortState::Filtered;
+        let mut after = host("10.0.0.5", None, &[(22, None), (3389, None)]);
+        after.hostname = Some("fs01.corp.example".to_string());
+        after.ports[0].state = PortState::Filtered;
+
+        let diff = diff_scans(&scan("a", vec![before]), &scan("b", vec![after]));
+        assert!(diff.hosts_appeared.is_empty() && diff.hosts_disappeared.is_empty());
+        let changed = &diff.hosts_changed[0];
+        assert_eq!(changed.hostname.as_deref(), Some("fs01.corp.example"));
+        assert_eq!(changed.ports_closed.iter().map(|p| p.port).collect::<Vec<_>>(), vec![22]);
+        assert_eq!(changed.ports_opened.iter().map(|p| p.port).collect::<Vec<_>>(), vec![3389]);
+    }
+
+    #[test]
+    fn test_scan_against_inventory() {
+        let asset = |ip: &str, status: AssetStatus, version: Option<&str>| Asset {
+            id: ip.to_string(),
+            client_id: "client-1".to_string(),
+            name: ip.to_string(),
+            ip_address: ip.to_string(),
+            mac_address: None,
+            category: AssetCategory::Server,
+            operating_system: None,
+            criticality: Criticality::Medium,
+            status,
+            location: None,
+            owner: None,
+            description: None,
+            services: vec![AssetService {
+                port: 22,
+                protocol: Protocol::Tcp,
+                name: "svc".to_string(),
+                version: version.map(String::from),
+                state: PortState::Open,
+            }],
+            tags: vec![],
+            first_seen: String::new(),
+            last_seen: String::new(),
+            scan_ids: vec![],
+            metadata: None,
+        };
+        let inventory = inventory_results(&[
+            asset("10.0.0.1", AssetStatus::Active, Some("OpenSSH 8.9")),
+            asset("10.0.0.2", AssetStatus::Active, None),
+            asset("10.0.0.3", AssetStatus::Decommissioned, None),
+        ]);
+        assert_eq!(inventory.scan_id, INVENTORY_DIFF_ID);
+        assert!(inventory.hosts[0].hostname.is_none());
+
+        // A scan without -sV sees 10.0.0.1 as unchanged
+        let diff = diff_scans(&inventory, &scan("b", vec![host("10.0.0.1", None, &[(22, None)])]));
+        assert_eq!(diff.hosts_unchanged, 1);
+        assert_eq!(diff.hosts_disappeared.iter().map(|h| h.ip_address.as_str()).collect::<Vec<_>>(), vec!["10.0.0.2"]);
+    }
+}
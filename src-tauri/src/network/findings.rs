@@ -0,0 +1,337 @@
+//! Network Findings
+//!
+//! Rule-based detection of exposed services in scan results, and
+//! verification of reported findings against fresh scan data. Detection and
+//! verification share the same rule definitions so a re-test always checks
+//! exactly the condition that raised the finding.
+
+use super::models::*;
+use super::scanner::ScannedHost;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// A detection rule for an exposed service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindingRule {
+    /// Stable rule identifier, stored on findings
+    pub id: String,
+    /// Title given to findings raised by this rule
+    pub title: String,
+    /// Severity of findings raised by this rule
+    pub severity: Criticality,
+    /// Ports whose exposure triggers the rule
+    pub ports: Vec<u16>,
+    /// What the exposure means
+    pub description: String,
+    /// How to remediate
+    pub recommendation: String,
+}
+
+impl FindingRule {
+    /// Detection condition: the rule holds for a host/port when the port is
+    /// one of the rule's ports and the host reports it open
+    pub fn holds(&self, host: &ScannedHost, port: u16) -> bool {
+        self.ports.contains(&port) && host.open_ports.iter().any(|p| p.open && p.port == port)
+    }
+}
+
+/// Get all built-in detection rules
+pub fn get_finding_rules() -> Vec<FindingRule> {
+    vec![
+        FindingRule {
+            id: "telnet-exposed".to_string(),
+            title: "Telnet Service Exposed".to_string(),
+            severity: Criticality::High,
+            ports: vec![23],
+            description: "Telnet transmits credentials and session data in cleartext.".to_string(),
+            recommendation: "Disable Telnet and use SSH for remote administration.".to_string(),
+        },
+        FindingRule {
+            id: "ftp-exposed".to_string(),
+            title: "FTP Service Exposed".to_string(),
+            severity: Criticality::Medium,
+            ports: vec![21],
+            description: "FTP transmits credentials and files in cleartext.".to_string(),
+            recommendation: "Replace FTP with SFTP or FTPS.".to_string(),
+        },
+        FindingRule {
+            id: "smb-exposed".to_string(),
+            title: "SMB Service Exposed".to_string(),
+            severity: Criticality::High,
+            ports: vec![445],
+            description: "SMB is a common lateral movement and ransomware propagation vector.".to_string(),
+            recommendation: "Restrict SMB to required hosts and disable SMBv1.".to_string(),
+        },
+        FindingRule {
+            id: "netbios-exposed".to_string(),
+            title: "NetBIOS Service Exposed".to_string(),
+            severity: Criticality::Medium,
+            ports: vec![139],
+            description: "NetBIOS can leak host, domain, and share information.".to_string(),
+            recommendation: "Disable NetBIOS over TCP/IP where not required.".to_string(),
+        },
+        FindingRule {
+            id: "msrpc-exposed".to_string(),
+            title: "MSRPC Endpoint Mapper Exposed".to_string(),
+            severity: Criticality::Medium,
+            ports: vec![135],
+            description: "The RPC endpoint mapper exposes service enumeration and remote management interfaces.".to_string(),
+            recommendation: "Restrict RPC access to management networks with host firewall rules.".to_string(),
+        },
+        FindingRule {
+            id: "rdp-exposed".to_string(),
+            title: "RDP Service Exposed".to_string(),
+            severity: Criticality::High,
+            ports: vec![3389],
+            description: "Exposed RDP is a frequent target for brute force and remote exploits.".to_string(),
+            recommendation: "Place RDP behind a VPN or gateway and enforce NLA and MFA.".to_string(),
+        },
+        FindingRule {
+            id: "vnc-exposed".to_string(),
+            title: "VNC Service Exposed".to_string(),
+            severity: Criticality::High,
+            ports: vec![5900],
+            description: "VNC often uses weak authentication and unencrypted sessions.".to_string(),
+            recommendation: "Disable VNC or tunnel it over SSH/VPN with strong authentication.".to_string(),
+        },
+        FindingRule {
+            id: "database-exposed".to_string(),
+            title: "Database Service Exposed".to_string(),
+            severity: Criticality::High,
+            ports: vec![1433, 1521, 3306, 5432, 27017],
+            description: "Database listeners reachable from the network widen the attack surface for data theft.".to_string(),
+            recommendation: "Bind databases to application hosts only and firewall the listener.".to_string(),
+        },
+        FindingRule {
+            id: "redis-exposed".to_string(),
+            title: "Redis Service Exposed".to_string(),
+            severity: Criticality::Critical,
+            ports: vec![6379],
+            description: "Redis has no authentication by default and can be abused for remote code execution.".to_string(),
+            recommendation: "Bind Redis to localhost, require authentication, and firewall the port.".to_string(),
+        },
+    ]
+}
+
+/// Look up a detection rule by identifier
+pub fn get_finding_rule(id: &str) -> Option<FindingRule> {
+    get_finding_rules().into_iter().find(|r| r.id == id)
+}
+
+/// Run every rule against scan results and raise a finding per matching host/port
+pub fn detect_findings(client_id: &str, hosts: &[ScannedHost]) -> Vec<NetworkFinding> {
+    let rules = get_finding_rules();
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut findings = Vec::new();
+
+    for host in hosts {
+        for port in &host.open_ports {
+            for rule in rules.iter().filter(|r| r.holds(host, port.port)) {
+                findings.push(NetworkFinding {
+                    id: Uuid::new_v4().to_string(),
+                    client_id: client_id.to_string(),
+                    rule_id: rule.id.clone(),
+                    host: host.ip_address.clone(),
+                    port: port.port,
+                    service: port.service.clone(),
+                    title: rule.title.clone(),
+                    severity: rule.severity,
+                    status: FindingStatus::Open,
+                    evidence: vec![format!("{} {}/tcp ({}) open", now, port.port, port.service)],
+                    notes: None,
+                    detected_at: now.clone(),
+                    updated_at: now.clone(),
+                    verification_scan_id: None,
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+/// Group the hosts/ports implicated by findings into probe batches
+///
+/// Hosts that need exactly the same ports are batched together, so the
+/// verification scan touches nothing beyond what the findings reference.
+pub fn plan_verification_targets(findings: &[NetworkFinding]) -> Vec<VerificationTarget> {
+    let mut ports_by_host: BTreeMap<&str, Vec<u16>> = BTreeMap::new();
+    for finding in findings {
+        let ports = ports_by_host.entry(finding.host.as_str()).or_default();
+        if !ports.contains(&finding.port) {
+            ports.push(finding.port);
+        }
+    }
+
+    let mut hosts_by_ports: BTreeMap<Vec<u16>, Vec<String>> = BTreeMap::new();
+    for (host, mut ports) in ports_by_host {
+        ports.sort_unstable();
+        hosts_by_ports.entry(ports).or_default().push(host.to_string());
+    }
+
+    hosts_by_ports
+        .into_iter()
+        .map(|(ports, hosts)| VerificationTarget { hosts, ports })
+        .collect()
+}
+
+/// Evaluate findings against verification results and update their status
+///
+/// Hosts missing from `hosts`, or present but not alive, are treated as
+/// unreachable and their findings become Unverifiable.
+pub fn evaluate_verification(
+    findings: &mut [&mut NetworkFinding],
+    hosts: &[ScannedHost],
+    scan_id: &str,
+) -> VerificationSummary {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut summary = VerificationSummary {
+        evaluated_at: now.clone(),
+        ..Default::default()
+    };
+
+    for finding in findings.iter_mut() {
+        let host = hosts.iter().find(|h| h.ip_address == finding.host && h.is_alive);
+        let rule = get_finding_rule(&finding.rule_id);
+
+        match (host, rule) {
+            (Some(host), Some(rule)) if rule.holds(host, finding.port) => {
+                finding.status = FindingStatus::Open;
+                finding.notes = Some(format!(
+                    "Verification failed: {}/tcp ({}) is still open",
+                    finding.port, finding.service
+                ));
+                finding.evidence.push(format!(
+                    "{} verification: {}/tcp ({}) open",
+                    now, finding.port, finding.service
+                ));
+                summary.still_present += 1;
+            }
+            (Some(_), Some(_)) => {
+                finding.status = FindingStatus::Remediated;
+                finding.notes = Some("Verified fixed by re-scan".to_string());
+                finding.evidence.push(format!(
+                    "{} verification: {}/tcp closed",
+                    now, finding.port
+                ));
+                summary.verified_fixed += 1;
+            }
+            (None, _) => {
+                finding.status = FindingStatus::Unverifiable;
+                finding.notes = Some(format!("Host {} did not respond to the verification scan", finding.host));
+                summary.unreachable += 1;
+            }
+            (Some(_), None) => {
+                finding.status = FindingStatus::Unverifiable;
+                finding.notes = Some(format!("Detection rule {} no longer exists", finding.rule_id));
+                summary.unreachable += 1;
+            }
+        }
+
+        finding.verification_scan_id = Some(scan_id.to_string());
+        finding.updated_at = now.clone();
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::scanner::ScannedPort;
+
+    fn host(ip: &str, open: &[u16], alive: bool) -> ScannedHost {
+        ScannedHost {
+            ip_address: ip.to_string(),
+            open_ports: open
+                .iter()
+                .map(|p| ScannedPort { port: *p, open: true, service: format!("port-{}", p) })
+                .collect(),
+            is_alive: alive,
+            hostname: None,
+        }
+    }
+
+    #[test]
+    fn test_detect_and_plan_verification() {
+        let hosts = vec![
+            host("10.0.0.1", &[22, 23], true),
+            host("10.0.0.2", &[23], true),
+            host("10.0.0.3", &[445, 3306], true),
+        ];
+        let findings = detect_findings("client-1", &hosts);
+        assert_eq!(findings.len(), 4);
+        assert!(findings.iter().all(|f| f.status == FindingStatus::Open));
+
+        let targets = plan_verification_targets(&findings);
+        assert_eq!(targets.len(), 2);
+        assert!(targets.contains(&VerificationTarget {
+            hosts: vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()],
+            ports: vec![23],
+        }));
+        assert!(targets.contains(&VerificationTarget {
+            hosts: vec!["10.0.0.3".to_string()],
+            ports: vec![445, 3306],
+        }));
+    }
+
+    #[test]
+    fn test_evaluate_verification() {
+        let mut findings = detect_findings(
+            "client-1",
+            &[host("10.0.0.1", &[23], true), host("10.0.0.2", &[23], true), host("10.0.0.3", &[23], true)],
+        );
+
+        // .1 fixed (host answers, port closed), .2 still open, .3 unreachable
+        let rescan = vec![host("10.0.0.1", &[], true), host("10.0.0.2", &[23], true), host("10.0.0.3", &[], false)];
+        let mut refs: Vec<&mut NetworkFinding> = findings.iter_mut().collect();
+        let summary = evaluate_verification(&mut refs, &rescan, "scan-1");
+
+        assert_eq!(summary.verified_fixed, 1);
+        assert_eq!(summary.still_present, 1);
+        assert_eq!(summary.unreachable, 1);
+        assert_eq!(findings[0].status, FindingStatus::Remediated);
+        assert_eq!(findings[1].status, FindingStatus::Open);
+        assert_eq!(findings[1].evidence.len(), 2);
+        assert_eq!(findings[2].status, FindingStatus::Unverifiable);
+        assert!(findings.iter().all(|f| f.verification_scan_id.as_deref() == Some("scan-1")));
+    }
+
+    #[test]
+    fn test_findings_are_stored() {
+        use crate::db::{Client, ClientRepository, Database};
+        use crate::network::repository::{init_network_schema, NetworkFindingRepository};
+
+        let db = Database {
+            conn: std::sync::Mutex::new(rusqlite::Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_network_schema(&db).unwrap();
+        let client = Client::new("Acme".to_string(), None, None, None);
+        ClientRepository::new(&db).create(&client).unwrap();
+        let repo = NetworkFindingRepository::new(&db);
+
+        let hosts = [host("10.0.0.1", &[23], true), host("10.0.0.2", &[23], true)];
+        assert_eq!(repo.create_new(detect_findings(&client.id, &hosts)).unwrap().len(), 2);
+        // Still open, so not raised again
+        assert!(repo.create_new(detect_findings(&client.id, &hosts)).unwrap().is_empty());
+
+        let mut stored = repo.list_by_client(&client.id).unwrap();
+        let mut refs: Vec<&mut NetworkFinding> = stored.iter_mut().collect();
+        evaluate_verification(&mut refs, &[host("10.0.0.1", &[], true), host("10.0.0.2", &[23], true)], "scan-1");
+        repo.save_all(&stored).unwrap();
+
+        let reloaded = repo.list_by_client(&client.id).unwrap();
+        assert_eq!(reloaded[0].status, FindingStatus::Remediated);
+        assert_eq!(reloaded[1].status, FindingStatus::Open);
+        assert_eq!(reloaded[1].evidence.len(), 2);
+        assert_eq!(reloaded[1].verification_scan_id.as_deref(), Some("scan-1"));
+
+        // A remediated finding that comes back is raised again
+        let counts = repo.count_unremediated_by_client().unwrap();
+        assert_eq!(counts[&client.id].values().sum::<usize>(), 1);
+        assert_eq!(repo.create_new(detect_findings(&client.id, &hosts)).unwrap().len(), 1);
+    }
+}
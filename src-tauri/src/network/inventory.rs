@@ -1,133 +1,236 @@
 //! Asset Inventory Management
 //!
 //! Manages discovered network assets, tracks changes over time,
-//! and provides asset organization capabilities.
+//! and provides asset organization capabilities. Assets, groups, and change
+//! history are persisted in the SQLite database.
+//!
+//! Service policies flag exposures that break the client's rules rather than
+//! just counting services: Telnet anywhere, RDP off the jump hosts, databases
+//! on workstations. Default rules ship in code; user rules are persisted and
+//! override a default with the same id.
 
 use super::models::*;
+use super::repository::{
+    AssetGroupRepository, AssetHistoryRepository, AssetRepository, AssetViewRepository, ServicePolicyRepository,
+    VulnerabilityRepository,
+};
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-/// Asset inventory manager for in-memory asset storage
-/// In production, this would be backed by the SQLite database
-pub struct AssetInventory {
-    assets: HashMap<String, Asset>,
-    groups: HashMap<String, AssetGroup>,
+/// Asset inventory backed by the SQLite database
+pub struct AssetInventory<'a> {
+    db: &'a Database,
 }
 
-impl AssetInventory {
-    pub fn new() -> Self {
-        Self {
-            assets: HashMap::new(),
-            groups: HashMap::new(),
-        }
+impl<'a> AssetInventory<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
     }
 
     /// Add or update an asset from scan results
-    pub fn upsert_from_discovery(&mut self, client_id: &str, host: &DiscoveredHost, scan_id: &str) -> Asset {
+    ///
+    /// When an existing asset is updated, every detected change (ports
+    /// opened or closed, OS, hostname) is recorded in the asset's history.
+    pub fn upsert_from_discovery(&self, client_id: &str, host: &DiscoveredHost, scan_id: &str) -> OptioResult<Asset> {
         let now = chrono::Utc::now().to_rfc3339();
+        let assets = AssetRepository::new(self.db);
+
+        let services: Vec<AssetService> = host.ports.iter()
+            .filter(|p| matches!(p.state, PortState::Open))
+            .map(|p| AssetService {
+                port: p.port,
+                protocol: p.protocol,
+                name: p.service.clone().unwrap_or_else(|| "unknown".to_string()),
+                version: service_version(p),
+                state: p.state,
+            })
+            .collect();
 
         // Check if asset already exists by IP
-        let existing = self.assets.values().find(|a| {
-            a.client_id == client_id && a.ip_address == host.ip_address
-        });
+        if let Some(existing) = assets.find_by_ip(client_id, &host.ip_address)? {
+            return self.update_from_discovery(existing, host, services, scan_id, &now);
+        }
 
-        if let Some(existing_asset) = existing {
-            // Update existing asset
-            let mut updated = existing_asset.clone();
-            updated.last_seen = now;
-            updated.mac_address = host.mac_address.clone().or(updated.mac_address);
-            updated.name = host.hostname.clone().unwrap_or(updated.name);
-
-            // Update services from discovered ports
-            updated.services = host.ports.iter()
-                .filter(|p| matches!(p.state, PortState::Open))
-                .map(|p| AssetService {
-                    port: p.port,
-                    protocol: p.protocol,
-                    name: p.service.clone().unwrap_or_else(|| "unknown".to_string()),
-                    version: p.product.clone(),
-                    state: p.state,
-                })
-                .collect();
+        // Create new asset
+        let asset = Asset {
+            id: Uuid::new_v4().to_string(),
+            client_id: client_id.to_string(),
+            name: host.hostname.clone().unwrap_or_else(|| host.ip_address.clone()),
+            ip_address: host.ip_address.clone(),
+            mac_address: host.mac_address.clone(),
+            category: infer_category(host),
+            operating_system: host.os_matches.first().map(|o| o.name.clone()),
+            criticality: Criticality::Medium,
+            status: AssetStatus::Active,
+            location: None,
+            owner: None,
+            description: None,
+            services: services.clone(),
+            tags: vec![],
+            first_seen: now.clone(),
+            last_seen: now.clone(),
+            scan_ids: vec![scan_id.to_string()],
+            metadata: None,
+        };
 
-            // Update OS if detected
-            if let Some(os) = host.os_matches.first() {
-                updated.operating_system = Some(os.name.clone());
-            }
+        match assets.save(&asset) {
+            Ok(()) => Ok(asset),
+            // A concurrent scan or import created the asset after the lookup;
+            // the unique (client, IP) key rejected ours, so update theirs
+            Err(e) => match assets.find_by_ip(client_id, &host.ip_address)? {
+                Some(existing) => self.update_from_discovery(existing, host, services, scan_id, &now),
+                None => Err(e),
+            },
+        }
+    }
 
-            // Add scan ID
-            if !updated.scan_ids.contains(&scan_id.to_string()) {
-                updated.scan_ids.push(scan_id.to_string());
-            }
+    /// Apply a discovery to an existing asset, recording what changed
+    fn update_from_discovery(
+        &self,
+        existing: Asset,
+        host: &DiscoveredHost,
+        services: Vec<AssetService>,
+        scan_id: &str,
+        now: &str,
+    ) -> OptioResult<Asset> {
+        let mut updated = existing.clone();
+        updated.last_seen = now.to_string();
+        updated.mac_address = host.mac_address.clone().or(updated.mac_address);
+        updated.name = host.hostname.clone().unwrap_or(updated.name);
+        updated.services = merge_services(&existing.services, services);
 
-            self.assets.insert(updated.id.clone(), updated.clone());
-            updated
-        } else {
-            // Create new asset
-            let asset = Asset {
+        // Update OS if detected
+        if let Some(os) = host.os_matches.first() {
+            updated.operating_system = Some(os.name.clone());
+        }
+
+        // Add scan ID
+        if !updated.scan_ids.contains(&scan_id.to_string()) {
+            updated.scan_ids.push(scan_id.to_string());
+        }
+
+        AssetRepository::new(self.db).save(&updated)?;
+
+        let history = AssetHistoryRepository::new(self.db);
+        for (change_type, old_value, new_value) in diff_assets(&existing, &updated) {
+            history.create(&AssetChange {
                 id: Uuid::new_v4().to_string(),
-                client_id: client_id.to_string(),
-                name: host.hostname.clone().unwrap_or_else(|| host.ip_address.clone()),
-                ip_address: host.ip_address.clone(),
-                mac_address: host.mac_address.clone(),
-                category: infer_category(host),
-                operating_system: host.os_matches.first().map(|o| o.name.clone()),
-                criticality: Criticality::Medium,
-                status: AssetStatus::Active,
-                location: None,
-                owner: None,
-                description: None,
-                services: host.ports.iter()
-                    .filter(|p| matches!(p.state, PortState::Open))
-                    .map(|p| AssetService {
-                        port: p.port,
-                        protocol: p.protocol,
-                        name: p.service.clone().unwrap_or_else(|| "unknown".to_string()),
-                        version: p.product.clone(),
-                        state: p.state,
-                    })
-                    .collect(),
-                tags: vec![],
-                first_seen: now.clone(),
-                last_seen: now,
-                scan_ids: vec![scan_id.to_string()],
-                metadata: None,
-            };
-
-            self.assets.insert(asset.id.clone(), asset.clone());
-            asset
+                asset_id: updated.id.clone(),
+                scan_id: scan_id.to_string(),
+                change_type,
+                old_value,
+                new_value,
+                changed_at: now.to_string(),
+            })?;
         }
+
+        Ok(updated)
     }
 
     /// Get all assets for a client
-    pub fn get_client_assets(&self, client_id: &str) -> Vec<Asset> {
-        self.assets.values()
-            .filter(|a| a.client_id == client_id)
-            .cloned()
-            .collect()
+    pub fn get_client_assets(&self, client_id: &str) -> OptioResult<Vec<Asset>> {
+        AssetRepository::new(self.db).list_by_client(client_id)
+    }
+
+    /// Query a client's assets, one page at a time
+    pub fn query_assets(&self, client_id: &str, filter: &AssetFilter, limit: usize, offset: usize) -> OptioResult<AssetPage> {
+        let (assets, total) = AssetRepository::new(self.db).query_filtered(client_id, filter, limit, offset)?;
+        Ok(AssetPage { assets, total, limit, offset })
+    }
+
+    /// Set criticality, owner, location, or tags on many assets at once
+    ///
+    /// Returns the number of assets updated.
+    pub fn bulk_update(&self, asset_ids: &[String], patch: &AssetPatch) -> OptioResult<usize> {
+        let mut patch = patch.clone();
+        patch.tags = patch.tags.map(|tags| normalize_tags(&tags));
+        AssetRepository::new(self.db).apply_patch(asset_ids, &patch)
+    }
+
+    /// Add tags to many assets, returning how many gained a tag
+    pub fn add_tags(&self, asset_ids: &[String], tags: &[String]) -> OptioResult<usize> {
+        let tags = normalize_tags(tags);
+        AssetRepository::new(self.db).update_tags(asset_ids, |existing| {
+            for tag in &tags {
+                if !existing.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    existing.push(tag.clone());
+                }
+            }
+        })
+    }
+
+    /// Remove tags (case-insensitive) from many assets, returning how many lost a tag
+    pub fn remove_tags(&self, asset_ids: &[String], tags: &[String]) -> OptioResult<usize> {
+        let tags = normalize_tags(tags);
+        AssetRepository::new(self.db).update_tags(asset_ids, |existing| {
+            existing.retain(|t| !tags.iter().any(|r| r.eq_ignore_ascii_case(t)));
+        })
+    }
+
+    /// Save a named filter for a client, replacing any view with the same name
+    pub fn save_view(&self, client_id: &str, name: &str, filter: AssetFilter) -> OptioResult<AssetView> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(OptioError::Validation("View name is required".to_string()));
+        }
+
+        let views = AssetViewRepository::new(self.db);
+        let now = chrono::Utc::now().to_rfc3339();
+        let view = match views.find_by_name(client_id, name)? {
+            Some(existing) => AssetView { filter, updated_at: now, ..existing },
+            None => AssetView {
+                id: Uuid::new_v4().to_string(),
+                client_id: client_id.to_string(),
+                name: name.to_string(),
+                filter,
+                created_at: now.clone(),
+                updated_at: now,
+            },
+        };
+        views.save(&view)?;
+        Ok(view)
+    }
+
+    /// Get a client's saved views by name
+    pub fn get_views(&self, client_id: &str) -> OptioResult<Vec<AssetView>> {
+        AssetViewRepository::new(self.db).list_by_client(client_id)
+    }
+
+    /// Delete a saved view
+    pub fn delete_view(&self, id: &str) -> OptioResult<bool> {
+        AssetViewRepository::new(self.db).delete(id)
     }
 
     /// Get asset by ID
-    pub fn get_asset(&self, id: &str) -> Option<&Asset> {
-        self.assets.get(id)
+    pub fn get_asset(&self, id: &str) -> OptioResult<Option<Asset>> {
+        AssetRepository::new(self.db).get(id)
     }
 
     /// Update asset details
-    pub fn update_asset(&mut self, asset: Asset) -> Result<Asset, String> {
-        if !self.assets.contains_key(&asset.id) {
-            return Err("Asset not found".to_string());
+    pub fn update_asset(&self, asset: Asset) -> OptioResult<Asset> {
+        let assets = AssetRepository::new(self.db);
+        if assets.get(&asset.id)?.is_none() {
+            return Err(OptioError::Validation("Asset not found".to_string()));
         }
-        self.assets.insert(asset.id.clone(), asset.clone());
+        assets.save(&asset)?;
         Ok(asset)
     }
 
     /// Delete an asset
-    pub fn delete_asset(&mut self, id: &str) -> bool {
-        self.assets.remove(id).is_some()
+    pub fn delete_asset(&self, id: &str) -> OptioResult<bool> {
+        AssetRepository::new(self.db).delete(id)
+    }
+
+    /// Get the recorded changes for an asset, oldest first
+    pub fn get_asset_history(&self, asset_id: &str) -> OptioResult<Vec<AssetChange>> {
+        AssetHistoryRepository::new(self.db).list_by_asset(asset_id)
     }
 
     /// Create a new asset group
-    pub fn create_group(&mut self, client_id: &str, name: &str, description: Option<String>) -> AssetGroup {
+    pub fn create_group(&self, client_id: &str, name: &str, description: Option<String>) -> OptioResult<AssetGroup> {
         let group = AssetGroup {
             id: Uuid::new_v4().to_string(),
             client_id: client_id.to_string(),
@@ -136,48 +239,42 @@ impl AssetInventory {
             asset_ids: vec![],
             color: None,
         };
-        self.groups.insert(group.id.clone(), group.clone());
-        group
+        AssetGroupRepository::new(self.db).create(&group)?;
+        Ok(group)
     }
 
     /// Add asset to group
-    pub fn add_to_group(&mut self, group_id: &str, asset_id: &str) -> Result<(), String> {
-        let group = self.groups.get_mut(group_id)
-            .ok_or_else(|| "Group not found".to_string())?;
-
-        if !self.assets.contains_key(asset_id) {
-            return Err("Asset not found".to_string());
+    pub fn add_to_group(&self, group_id: &str, asset_id: &str) -> OptioResult<()> {
+        let groups = AssetGroupRepository::new(self.db);
+        if !groups.exists(group_id)? {
+            return Err(OptioError::Validation("Group not found".to_string()));
         }
 
-        if !group.asset_ids.contains(&asset_id.to_string()) {
-            group.asset_ids.push(asset_id.to_string());
+        if AssetRepository::new(self.db).get(asset_id)?.is_none() {
+            return Err(OptioError::Validation("Asset not found".to_string()));
         }
 
-        Ok(())
+        groups.add_member(group_id, asset_id)
     }
 
     /// Remove asset from group
-    pub fn remove_from_group(&mut self, group_id: &str, asset_id: &str) -> Result<(), String> {
-        let group = self.groups.get_mut(group_id)
-            .ok_or_else(|| "Group not found".to_string())?;
+    pub fn remove_from_group(&self, group_id: &str, asset_id: &str) -> OptioResult<()> {
+        let groups = AssetGroupRepository::new(self.db);
+        if !groups.exists(group_id)? {
+            return Err(OptioError::Validation("Group not found".to_string()));
+        }
 
-        group.asset_ids.retain(|id| id != asset_id);
-        Ok(())
+        groups.remove_member(group_id, asset_id)
     }
 
     /// Get groups for a client
-    pub fn get_client_groups(&self, client_id: &str) -> Vec<AssetGroup> {
-        self.groups.values()
-            .filter(|g| g.client_id == client_id)
-            .cloned()
-            .collect()
+    pub fn get_client_groups(&self, client_id: &str) -> OptioResult<Vec<AssetGroup>> {
+        AssetGroupRepository::new(self.db).list_by_client(client_id)
     }
 
     /// Get network statistics for a client
-    pub fn get_stats(&self, client_id: &str) -> NetworkStats {
-        let assets: Vec<&Asset> = self.assets.values()
-            .filter(|a| a.client_id == client_id)
-            .collect();
+    pub fn get_stats(&self, client_id: &str) -> OptioResult<NetworkStats> {
+        let assets = self.get_client_assets(client_id)?;
 
         let total_assets = assets.len();
         let active_assets = assets.iter()
@@ -215,7 +312,26 @@ impl AssetInventory {
         top_services.sort_by(|a, b| b.count.cmp(&a.count));
         top_services.truncate(10);
 
-        NetworkStats {
+        // Known vulnerabilities from the last enrichment of each asset
+        let vulnerabilities = VulnerabilityRepository::new(self.db).list_by_client(client_id)?;
+        let vulnerable_assets = vulnerabilities.iter()
+            .map(|v| v.asset_id.as_str())
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        let mut severity_counts: HashMap<Criticality, usize> = HashMap::new();
+        for vulnerability in &vulnerabilities {
+            *severity_counts.entry(vulnerability.severity).or_insert(0) += 1;
+        }
+        let by_vulnerability_severity: Vec<CriticalityCount> = severity_counts.into_iter()
+            .map(|(criticality, count)| CriticalityCount { criticality, count })
+            .collect();
+
+        let policy_violations = find_policy_violations(&self.service_policies()?, &assets)
+            .iter()
+            .map(|a| a.violations.len())
+            .sum();
+
+        Ok(NetworkStats {
             total_assets,
             active_assets,
             total_scans: 0, // Would come from scan history
@@ -223,14 +339,310 @@ impl AssetInventory {
             by_criticality,
             top_services,
             recent_scans: vec![],
+            vulnerable_assets,
+            known_vulnerabilities: vulnerabilities.len(),
+            by_vulnerability_severity,
+            policy_violations,
+        })
+    }
+
+    /// Service policies in effect: the defaults with user overrides, then user additions
+    pub fn service_policies(&self) -> OptioResult<Vec<ServicePolicy>> {
+        let user = ServicePolicyRepository::new(self.db).list()?;
+        Ok(merge_service_policies(default_service_policies(), user))
+    }
+
+    /// Check a client's assets against the service policies in effect
+    pub fn evaluate_service_policies(&self, client_id: &str) -> OptioResult<Vec<AssetPolicyViolations>> {
+        let assets = self.get_client_assets(client_id)?;
+        Ok(find_policy_violations(&self.service_policies()?, &assets))
+    }
+}
+
+// ============================================================================
+// Service Policies
+// ============================================================================
+
+/// A rule about which services may be exposed where
+///
+/// A service matches when its port or name is listed (both lists empty
+/// match any service) on an asset of a listed category and criticality
+/// (empty lists match any), unless the asset carries an exempt tag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServicePolicy {
+    /// Stable rule identifier; a user rule with a default's id replaces it
+    pub id: String,
+    pub name: String,
+    pub ports: Vec<u16>,
+    /// Service names, matched case-insensitively
+    pub services: Vec<String>,
+    pub categories: Vec<AssetCategory>,
+    pub criticalities: Vec<Criticality>,
+    /// Asset tags that exempt an asset from the rule (e.g. "jump-host")
+    pub exempt_tags: Vec<String>,
+    pub severity: Criticality,
+    /// What a violation means, shown on each violation
+    pub message: String,
+    pub recommendation: Option<String>,
+    pub enabled: bool,
+    /// Shipped in code rather than added by the user
+    #[serde(default)]
+    pub built_in: bool,
+}
+
+impl ServicePolicy {
+    /// Whether an open service on an asset breaks this rule
+    pub fn matches(&self, asset: &Asset, service: &AssetService) -> bool {
+        let targets_service = (self.ports.is_empty() && self.services.is_empty())
+            || self.ports.contains(&service.port)
+            || self.services.iter().any(|s| s.eq_ignore_ascii_case(&service.name));
+
+        self.enabled
+            && service.state == PortState::Open
+            && targets_service
+            && (self.categories.is_empty() || self.categories.contains(&asset.category))
+            && (self.criticalities.is_empty() || self.criticalities.contains(&asset.criticality))
+            && !self.exempt_tags.iter().any(|t| asset.tags.iter().any(|tag| tag.eq_ignore_ascii_case(t)))
+    }
+}
+
+/// A service on an asset that breaks a policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyViolation {
+    pub policy_id: String,
+    pub policy_name: String,
+    pub port: u16,
+    pub service: String,
+    pub severity: Criticality,
+    pub message: String,
+    pub recommendation: Option<String>,
+}
+
+/// An asset's policy violations, most severe first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetPolicyViolations {
+    pub asset_id: String,
+    pub asset_name: String,
+    pub ip_address: String,
+    pub category: AssetCategory,
+    pub criticality: Criticality,
+    pub violations: Vec<PolicyViolation>,
+}
+
+/// Service policies shipped with Optio
+pub fn default_service_policies() -> Vec<ServicePolicy> {
+    let policy = |id: &str, name: &str, severity: Criticality, message: &str, recommendation: &str| ServicePolicy {
+        id: id.to_string(),
+        name: name.to_string(),
+        ports: vec![],
+        services: vec![],
+        categories: vec![],
+        criticalities: vec![],
+        exempt_tags: vec![],
+        severity,
+        message: message.to_string(),
+        recommendation: Some(recommendation.to_string()),
+        enabled: true,
+        built_in: true,
+    };
+
+    vec![
+        ServicePolicy {
+            ports: vec![23],
+            services: vec!["telnet".to_string()],
+            ..policy(
+                "telnet-anywhere",
+                "Telnet Exposed",
+                Criticality::High,
+                "Telnet is open; credentials and sessions cross the network in cleartext",
+                "Disable Telnet and manage the device over SSH",
+            )
+        },
+        ServicePolicy {
+            ports: vec![3389],
+            services: vec!["ms-wbt-server".to_string(), "rdp".to_string()],
+            exempt_tags: vec!["jump-host".to_string()],
+            ..policy(
+                "rdp-outside-jump-hosts",
+                "RDP Outside Jump Hosts",
+                Criticality::High,
+                "RDP is exposed on an asset that isn't a designated jump host",
+                "Restrict RDP to jump hosts (tag them \"jump-host\") and require NLA and MFA",
+            )
+        },
+        ServicePolicy {
+            ports: vec![137, 138, 139],
+            services: vec!["netbios-ns".to_string(), "netbios-dgm".to_string(), "netbios-ssn".to_string()],
+            criticalities: vec![Criticality::Critical, Criticality::High],
+            ..policy(
+                "legacy-smb-critical-assets",
+                "Legacy SMB on Critical Assets",
+                Criticality::High,
+                "NetBIOS ports associated with SMBv1 are open on a critical asset",
+                "Disable SMBv1 and NetBIOS over TCP/IP; serve file shares over SMB 3 on port 445",
+            )
+        },
+        ServicePolicy {
+            ports: vec![1433, 1521, 3306, 5432, 6379, 27017],
+            services: vec![
+                "ms-sql-s".to_string(),
+                "oracle".to_string(),
+                "mysql".to_string(),
+                "postgresql".to_string(),
+                "redis".to_string(),
+                "mongodb".to_string(),
+            ],
+            categories: vec![AssetCategory::Workstation],
+            ..policy(
+                "database-on-workstation",
+                "Database on Workstation",
+                Criticality::Medium,
+                "A database is listening on a workstation",
+                "Move the database to a managed server segment or bind it to localhost",
+            )
+        },
+    ]
+}
+
+/// Combine the default policies with the user's
+///
+/// A user policy with a default's id takes its place; the rest follow the
+/// defaults in the order given.
+pub fn merge_service_policies(defaults: Vec<ServicePolicy>, user: Vec<ServicePolicy>) -> Vec<ServicePolicy> {
+    let mut policies: Vec<ServicePolicy> = defaults
+        .into_iter()
+        .map(|default| match user.iter().find(|u| u.id == default.id) {
+            Some(user_policy) => ServicePolicy { built_in: true, ..user_policy.clone() },
+            None => default,
+        })
+        .collect();
+    for user_policy in user {
+        if !policies.iter().any(|p| p.id == user_policy.id) {
+            policies.push(ServicePolicy { built_in: false, ..user_policy });
         }
     }
+    policies
+}
+
+/// Check assets against policies; assets without violations are left out
+///
+/// Each open service yields at most one violation, from the most severe
+/// matching policy; between equally severe policies the earlier one wins.
+pub fn find_policy_violations(policies: &[ServicePolicy], assets: &[Asset]) -> Vec<AssetPolicyViolations> {
+    assets
+        .iter()
+        .filter_map(|asset| {
+            let mut violations: Vec<PolicyViolation> = asset
+                .services
+                .iter()
+                .filter_map(|service| {
+                    policies
+                        .iter()
+                        .filter(|p| p.matches(asset, service))
+                        .min_by_key(|p| p.severity.rank())
+                        .map(|policy| PolicyViolation {
+                            policy_id: policy.id.clone(),
+                            policy_name: policy.name.clone(),
+                            port: service.port,
+                            service: service.name.clone(),
+                            severity: policy.severity,
+                            message: policy.message.clone(),
+                            recommendation: policy.recommendation.clone(),
+                        })
+                })
+                .collect();
+            if violations.is_empty() {
+                return None;
+            }
+            violations.sort_by_key(|v| v.severity.rank());
+
+            Some(AssetPolicyViolations {
+                asset_id: asset.id.clone(),
+                asset_name: asset.name.clone(),
+                ip_address: asset.ip_address.clone(),
+                category: asset.category,
+                criticality: asset.criticality,
+                violations,
+            })
+        })
+        .collect()
+}
+
+/// Trim tags and drop empty and case-insensitive duplicates, keeping order
+pub(crate) fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !normalized.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// Product and version as one string, e.g. "OpenSSH 8.9p1"
+///
+/// Nmap reports them separately; both are kept so services can be matched
+/// against the vulnerability dataset.
+fn service_version(port: &DiscoveredPort) -> Option<String> {
+    match (&port.product, &port.version) {
+        (Some(product), Some(version)) => Some(format!("{} {}", product, version)),
+        (Some(product), None) => Some(product.clone()),
+        (None, Some(version)) => Some(version.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Take a discovery's services, keeping what earlier scans identified
+///
+/// A scan without version detection reports only the port table's guess,
+/// so the name and version from an earlier `-sV` scan are kept for ports
+/// that are still open.
+fn merge_services(previous: &[AssetService], discovered: Vec<AssetService>) -> Vec<AssetService> {
+    discovered
+        .into_iter()
+        .map(|mut service| {
+            if let Some(old) = previous.iter().find(|o| o.port == service.port && o.protocol == service.protocol) {
+                if service.version.is_none() {
+                    service.version = old.version.clone();
+                }
+                if service.name == "unknown" {
+                    service.name = old.name.clone();
+                }
+            }
+            service
+        })
+        .collect()
 }
 
-impl Default for AssetInventory {
-    fn default() -> Self {
-        Self::new()
+/// Compare an asset before and after a discovery: (change, old value, new value)
+fn diff_assets(before: &Asset, after: &Asset) -> Vec<(AssetChangeType, Option<String>, Option<String>)> {
+    let mut changes = Vec::new();
+    let port_label = |s: &AssetService| format!("{}/{:?} ({})", s.port, s.protocol, s.name).to_lowercase();
+    let same_port = |a: &AssetService, b: &AssetService| a.port == b.port && a.protocol == b.protocol;
+
+    for service in &after.services {
+        if !before.services.iter().any(|s| same_port(s, service)) {
+            changes.push((AssetChangeType::PortOpened, None, Some(port_label(service))));
+        }
     }
+    for service in &before.services {
+        if !after.services.iter().any(|s| same_port(s, service)) {
+            changes.push((AssetChangeType::PortClosed, Some(port_label(service)), None));
+        }
+    }
+
+    if before.operating_system != after.operating_system {
+        changes.push((AssetChangeType::OsChanged, before.operating_system.clone(), after.operating_system.clone()));
+    }
+
+    if before.name != after.name {
+        changes.push((AssetChangeType::HostnameChanged, Some(before.name.clone()), Some(after.name.clone())));
+    }
+
+    changes
 }
 
 /// Infer asset category from discovered host data
@@ -487,10 +899,39 @@ pub fn generate_demo_assets(client_id: &str) -> Vec<Asset> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::network::repository::init_network_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "PRAGMA foreign_keys=ON;
+             INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    fn port(port: u16, service: &str) -> DiscoveredPort {
+        DiscoveredPort {
+            port,
+            protocol: Protocol::Tcp,
+            state: PortState::Open,
+            service: Some(service.to_string()),
+            product: None,
+            version: None,
+            extra_info: None,
+            scripts: vec![],
+        }
+    }
 
     #[test]
     fn test_asset_inventory_add() {
-        let mut inventory = AssetInventory::new();
+        let db = test_db();
+        let inventory = AssetInventory::new(&db);
 
         let host = DiscoveredHost {
             ip_address: "192.168.1.100".to_string(),
@@ -512,13 +953,257 @@ mod tests {
             ],
             os_matches: vec![],
             host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
         };
 
-        let asset = inventory.upsert_from_discovery("client-1", &host, "scan-1");
+        let asset = inventory.upsert_from_discovery("client-1", &host, "scan-1").unwrap();
 
         assert_eq!(asset.name, "test-server");
         assert_eq!(asset.ip_address, "192.168.1.100");
         assert_eq!(asset.services.len(), 1);
+
+        // Persisted, services included
+        let stored = inventory.get_asset(&asset.id).unwrap().unwrap();
+        assert_eq!(stored.services.len(), 1);
+        assert_eq!(stored.scan_ids, vec!["scan-1".to_string()]);
+        assert!(inventory.get_asset_history(&asset.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_discovery_change_history() {
+        let db = test_db();
+        let inventory = AssetInventory::new(&db);
+
+        let mut host = DiscoveredHost {
+            ip_address: "10.0.0.5".to_string(),
+            mac_address: None,
+            hostname: Some("web01".to_string()),
+            vendor: None,
+            status: "up".to_string(),
+            ports: vec![port(22, "ssh"), port(80, "http")],
+            os_matches: vec![],
+            host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
+        };
+        let first = inventory.upsert_from_discovery("client-1", &host, "scan-1").unwrap();
+
+        // Unchanged re-scan records nothing
+        inventory.upsert_from_discovery("client-1", &host, "scan-2").unwrap();
+        assert!(inventory.get_asset_history(&first.id).unwrap().is_empty());
+
+        host.hostname = Some("web01.corp.local".to_string());
+        host.ports = vec![port(22, "ssh"), port(443, "https")];
+        host.os_matches = vec![OsMatch {
+            name: "Linux 5.15".to_string(),
+            accuracy: 98,
+            os_family: Some("Linux".to_string()),
+            os_gen: None,
+            device_type: None,
+        }];
+        let updated = inventory.upsert_from_discovery("client-1", &host, "scan-3").unwrap();
+        assert_eq!(updated.id, first.id);
+        assert_eq!(updated.scan_ids.len(), 3);
+
+        let history = inventory.get_asset_history(&first.id).unwrap();
+        let types: Vec<AssetChangeType> = history.iter().map(|c| c.change_type).collect();
+        assert_eq!(types, vec![
+            AssetChangeType::PortOpened,
+            AssetChangeType::PortClosed,
+            AssetChangeType::OsChanged,
+            AssetChangeType::HostnameChanged,
+        ]);
+        assert!(history.iter().all(|c| c.scan_id == "scan-3"));
+        assert_eq!(history[0].new_value.as_deref(), Some("443/tcp (https)"));
+        assert_eq!(history[3].old_value.as_deref(), Some("web01"));
+
+        // Groups persist membership and drop deleted assets
+        let group = inventory.create_group("client-1", "DMZ", None).unwrap();
+        inventory.add_to_group(&group.id, &first.id).unwrap();
+        assert_eq!(inventory.get_client_groups("client-1").unwrap()[0].asset_ids, vec![first.id.clone()]);
+        assert!(inventory.delete_asset(&first.id).unwrap());
+        assert!(inventory.get_client_groups("client-1").unwrap()[0].asset_ids.is_empty());
+        assert!(inventory.get_asset_history(&first.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_discovery_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("optio-inventory-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("optio.db");
+        let open = || {
+            let db = Database::open(&path).unwrap();
+            db.init_schema().unwrap();
+            crate::network::repository::init_network_schema(&db).unwrap();
+            db
+        };
+
+        let mut ssh = port(22, "ssh");
+        ssh.product = Some("OpenSSH".to_string());
+        ssh.version = Some("9.6".to_string());
+        let mut host = DiscoveredHost {
+            ip_address: "10.0.0.7".to_string(),
+            mac_address: None,
+            hostname: Some("bastion".to_string()),
+            vendor: None,
+            status: "up".to_string(),
+            ports: vec![ssh],
+            os_matches: vec![],
+            host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
+        };
+
+        let first = {
+            let db = open();
+            db.conn.lock().unwrap().execute_batch(
+                "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+            ).unwrap();
+            let inventory = AssetInventory::new(&db);
+            let asset = inventory.upsert_from_discovery("client-1", &host, "scan-1").unwrap();
+            let mut annotated = asset.clone();
+            annotated.criticality = Criticality::Critical;
+            annotated.owner = Some("Infra team".to_string());
+            inventory.update_asset(annotated).unwrap();
+            asset
+        };
+
+        // A later scan without version detection, after the app restarted
+        host.ports = vec![port(22, "ssh"), port(443, "https")];
+        let db = open();
+        let inventory = AssetInventory::new(&db);
+        let updated = inventory.upsert_from_discovery("client-1", &host, "scan-2").unwrap();
+
+        assert_eq!(updated.id, first.id);
+        assert_eq!(updated.first_seen, first.first_seen);
+        assert_eq!(updated.scan_ids, vec!["scan-1".to_string(), "scan-2".to_string()]);
+        assert_eq!(updated.criticality, Criticality::Critical);
+        assert_eq!(updated.owner.as_deref(), Some("Infra team"));
+        assert_eq!(updated.services.len(), 2);
+        assert_eq!(updated.services[0].version.as_deref(), Some("OpenSSH 9.6"));
+        assert_eq!(updated.services[1].version, None);
+        let stored = inventory.get_asset(&first.id).unwrap().unwrap();
+        assert_eq!(stored.services[0].version.as_deref(), Some("OpenSSH 9.6"));
+
+        drop(db);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_discovery_per_client() {
+        let db = test_db();
+        db.conn.lock().unwrap().execute(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-2', 'Globex', '2024-01-01', '2024-01-01')",
+            [],
+        ).unwrap();
+
+        // Both clients use the same address space; each IP is discovered by
+        // several scans at once so creates race on the (client, IP) key
+        let host = |ip: u8, service: &str| DiscoveredHost {
+            ip_address: format!("10.0.0.{}", ip),
+            mac_address: None,
+            hostname: None,
+            vendor: None,
+            status: "up".to_string(),
+            ports: vec![port(22, service)],
+            os_matches: vec![],
+            host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
+        };
+        std::thread::scope(|scope| {
+            for worker in 0..8 {
+                let (client_id, service) = if worker % 2 == 0 { ("client-1", "ssh") } else { ("client-2", "sftp") };
+                let db = &db;
+                scope.spawn(move || {
+                    let inventory = AssetInventory::new(db);
+                    for ip in 1..=20 {
+                        let scan_id = format!("scan-{}", worker);
+                        inventory.upsert_from_discovery(client_id, &host(ip, service), &scan_id).unwrap();
+                    }
+                });
+            }
+        });
+
+        let inventory = AssetInventory::new(&db);
+        for (client_id, service) in [("client-1", "ssh"), ("client-2", "sftp")] {
+            let assets = inventory.get_client_assets(client_id).unwrap();
+            assert_eq!(assets.len(), 20);
+            assert!(assets.iter().all(|a| a.client_id == client_id));
+            assert!(assets.iter().all(|a| a.services.len() == 1 && a.services[0].name == service));
+        }
+    }
+
+    #[test]
+    fn test_query_and_bulk_update() {
+        let db = test_db();
+        let inventory = AssetInventory::new(&db);
+        let repo = AssetRepository::new(&db);
+        let demo = generate_demo_assets("client-1");
+        for asset in &demo {
+            repo.save(asset).unwrap();
+        }
+        let names = |page: &AssetPage| page.assets.iter().map(|a| a.name.clone()).collect::<Vec<_>>();
+
+        // "PCI-scope Linux servers"
+        let filter = AssetFilter {
+            categories: vec![AssetCategory::Server],
+            tags: vec!["PCI".to_string()],
+            open_port: Some(22),
+            ..Default::default()
+        };
+        let page = inventory.query_assets("client-1", &filter, 50, 0).unwrap();
+        assert_eq!(names(&page), vec!["db-prod-01"]);
+
+        let filter = AssetFilter { service: Some("nginx".to_string()), ..Default::default() };
+        assert_eq!(names(&inventory.query_assets("client-1", &filter, 50, 0).unwrap()), vec!["web-prod-01"]);
+
+        let filter = AssetFilter { operating_system: Some("windows".to_string()), ..Default::default() };
+        assert_eq!(inventory.query_assets("client-1", &filter, 50, 0).unwrap().total, 2);
+
+        // Sorting and pagination
+        let filter = AssetFilter { sort_by: Some(AssetSortField::Criticality), ..Default::default() };
+        let page = inventory.query_assets("client-1", &filter, 2, 1).unwrap();
+        assert_eq!(page.total, demo.len());
+        assert_eq!(page.assets.len(), 2);
+        assert_eq!(page.assets[0].criticality, Criticality::Critical);
+
+        let filter = AssetFilter { last_seen_before: Some("2000-01-01T00:00:00Z".to_string()), ..Default::default() };
+        assert_eq!(inventory.query_assets("client-1", &filter, 50, 0).unwrap().total, 0);
+        let filter = AssetFilter { last_seen_after: Some("yesterday".to_string()), ..Default::default() };
+        assert!(inventory.query_assets("client-1", &filter, 50, 0).is_err());
+
+        // Bulk updates
+        let ids: Vec<String> = demo.iter().take(2).map(|a| a.id.clone()).collect();
+        let patch = AssetPatch { criticality: Some(Criticality::Low), owner: Some("IT Ops".to_string()), ..Default::default() };
+        assert_eq!(inventory.bulk_update(&ids, &patch).unwrap(), 2);
+        let updated = inventory.get_asset(&ids[0]).unwrap().unwrap();
+        assert_eq!((updated.criticality, updated.owner.as_deref()), (Criticality::Low, Some("IT Ops")));
+        assert_eq!(updated.tags, demo[0].tags);
+
+        assert_eq!(inventory.add_tags(&ids, &[" pci ".to_string(), "Critical".to_string()]).unwrap(), 2);
+        assert_eq!(inventory.get_asset(&ids[0]).unwrap().unwrap().tags, vec!["domain-controller", "critical", "pci"]);
+        assert_eq!(inventory.remove_tags(&ids, &["PCI".to_string()]).unwrap(), 2);
+        assert_eq!(inventory.remove_tags(&ids, &["PCI".to_string()]).unwrap(), 0);
+
+        // Saved views replace by name
+        let view = inventory.save_view("client-1", "PCI scope", AssetFilter::default()).unwrap();
+        let filter = AssetFilter { tags: vec!["pci".to_string()], ..Default::default() };
+        let resaved = inventory.save_view("client-1", "PCI scope", filter).unwrap();
+        assert_eq!(resaved.id, view.id);
+        let views = inventory.get_views("client-1").unwrap();
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].filter.tags, vec!["pci"]);
+        assert!(inventory.save_view("client-1", " ", AssetFilter::default()).is_err());
     }
 
     #[test]
@@ -543,8 +1228,117 @@ mod tests {
             ],
             os_matches: vec![],
             host_scripts: vec![],
+            extra_ports: vec![],
+            uptime_seconds: None,
+            last_boot: None,
+            distance: None,
         };
 
         assert_eq!(infer_category(&host), AssetCategory::Server);
     }
+
+    fn policy_asset(name: &str, category: AssetCategory, criticality: Criticality, services: &[(u16, &str)]) -> Asset {
+        Asset {
+            id: name.to_string(),
+            client_id: "client-1".to_string(),
+            name: name.to_string(),
+            ip_address: "10.0.0.1".to_string(),
+            mac_address: None,
+            category,
+            operating_system: None,
+            criticality,
+            status: AssetStatus::Active,
+            location: None,
+            owner: None,
+            description: None,
+            services: services
+                .iter()
+                .map(|(port, name)| AssetService {
+                    port: *port,
+                    protocol: Protocol::Tcp,
+                    name: name.to_string(),
+                    version: None,
+                    state: PortState::Open,
+                })
+                .collect(),
+            tags: vec![],
+            first_seen: "2024-01-01".to_string(),
+            last_seen: "2024-01-01".to_string(),
+            scan_ids: vec![],
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_service_policy_conditions() {
+        let policies = default_service_policies();
+        let violations = |asset: &Asset| -> Vec<String> {
+            find_policy_violations(&policies, std::slice::from_ref(asset))
+                .into_iter()
+                .flat_map(|a| a.violations)
+                .map(|v| v.policy_id)
+                .collect()
+        };
+
+        // Databases only break policy on workstations
+        let workstation = policy_asset("ws", AssetCategory::Workstation, Criticality::Low, &[(5432, "postgresql")]);
+        let server = policy_asset("db", AssetCategory::Server, Criticality::Low, &[(5432, "postgresql")]);
+        assert_eq!(violations(&workstation), vec!["database-on-workstation"]);
+        assert!(violations(&server).is_empty());
+
+        // Legacy SMB only on critical and high criticality assets
+        let file_server = policy_asset("fs", AssetCategory::Server, Criticality::Critical, &[(139, "netbios-ssn")]);
+        let printer = policy_asset("pr", AssetCategory::Printer, Criticality::Low, &[(139, "netbios-ssn")]);
+        assert_eq!(violations(&file_server), vec!["legacy-smb-critical-assets"]);
+        assert!(violations(&printer).is_empty());
+
+        // Jump hosts are exempt from the RDP rule; Telnet matches by name on any port
+        let mut jump_host = policy_asset("jump", AssetCategory::Server, Criticality::High, &[(3389, "ms-wbt-server")]);
+        assert_eq!(violations(&jump_host), vec!["rdp-outside-jump-hosts"]);
+        jump_host.tags.push("Jump-Host".to_string());
+        assert!(violations(&jump_host).is_empty());
+        let switch = policy_asset("sw", AssetCategory::NetworkDevice, Criticality::Medium, &[(2323, "telnet")]);
+        assert_eq!(violations(&switch), vec!["telnet-anywhere"]);
+    }
+
+    #[test]
+    fn test_service_policy_precedence() {
+        let user = |id: &str, severity: Criticality, enabled: bool| ServicePolicy {
+            id: id.to_string(),
+            name: id.to_string(),
+            ports: vec![5432],
+            services: vec![],
+            categories: vec![],
+            criticalities: vec![],
+            exempt_tags: vec![],
+            severity,
+            message: "Database exposed".to_string(),
+            recommendation: None,
+            enabled,
+            built_in: false,
+        };
+        let workstation = policy_asset("ws", AssetCategory::Workstation, Criticality::Low, &[(5432, "postgresql")]);
+
+        // A user rule with a default's id replaces it in place
+        let policies = merge_service_policies(
+            default_service_policies(),
+            vec![user("database-on-workstation", Criticality::Low, false), user("any-database", Criticality::Low, true)],
+        );
+        assert_eq!(policies.len(), default_service_policies().len() + 1);
+        let overridden = policies.iter().find(|p| p.id == "database-on-workstation").unwrap();
+        assert!(overridden.built_in && !overridden.enabled);
+        assert!(!policies.last().unwrap().built_in);
+        let found = find_policy_violations(&policies, std::slice::from_ref(&workstation));
+        assert_eq!(found[0].violations.len(), 1);
+        assert_eq!(found[0].violations[0].policy_id, "any-database");
+
+        // One violation per service, from the most severe matching rule
+        let policies = merge_service_policies(default_service_policies(), vec![user("any-database", Criticality::Critical, true)]);
+        let found = find_policy_violations(&policies, std::slice::from_ref(&workstation));
+        assert_eq!(found[0].violations.len(), 1);
+        assert_eq!(found[0].violations[0].policy_id, "any-database");
+        let policies = merge_service_policies(default_service_policies(), vec![user("any-database", Criticality::Medium, true)]);
+        let found = find_policy_violations(&policies, std::slice::from_ref(&workstation));
+        assert_eq!(found[0].violations[0].policy_id, "database-on-workstation");
+    }
 }
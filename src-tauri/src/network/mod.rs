@@ -1,12 +1,38 @@
 //! Network Intelligence Module
 //!
-//! Provides network discovery, Nmap integration, and asset inventory management.
+//! Provides network discovery, Nmap integration, asset inventory management,
+//! rule-based findings with re-scan verification, scan-to-scan diffing, and
+//! recurring scheduled scans, batched and resumable scans of large target
+//! lists, host counts and ETAs for a target spec before it is scanned, and matching of detected service versions
+//! against a local CVE dataset, a listener for Factory agent callbacks over
+//! plain TCP or TLS, and CSV/JSON export and re-import of the asset inventory.
 //! Enables consultants to map client networks and track discovered assets.
 
 pub mod models;
 pub mod scanner;
 pub mod inventory;
+pub mod findings;
+pub mod repository;
+pub mod diff;
+pub mod scheduler;
+pub mod batch;
+pub mod targets;
+pub mod vuln_match;
+pub mod agent_listener;
+pub mod agent_tls;
+pub mod asset_export;
 
 pub use models::*;
 pub use scanner::*;
 pub use inventory::*;
+pub use findings::*;
+pub use repository::*;
+pub use diff::*;
+pub use scheduler::*;
+pub use batch::*;
+// `targets::expand_targets` shares its name with the batch expansion above
+pub use targets::{estimate_scan, parse_target, ScanEstimate, TargetCount, TargetKind, TargetOverlap, TargetPlan};
+pub use vuln_match::*;
+pub use agent_listener::*;
+pub use agent_tls::*;
+pub use asset_export::*;
@@ -102,6 +102,8 @@ pub enum ScanStatus {
     Queued,
     /// Scan is currently running
     Running,
+    /// Nmap has finished; results are being merged and stored
+    Parsing,
     /// Scan completed successfully
     Completed,
     /// Scan failed with an error
@@ -155,6 +157,19 @@ pub struct ScanConfig {
     pub skip_discovery: bool,
     /// Output format preferences
     pub output_formats: Vec<OutputFormat>,
+    /// How Nmap gets raw packet access, decided when the scan is created
+    #[serde(default)]
+    pub privilege_mode: ScanPrivilegeMode,
+    /// Targets per Nmap run; CIDRs are expanded first. `None` scans all
+    /// targets in one run
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+    /// Batches run at once (default 1, one after another)
+    #[serde(default)]
+    pub batch_concurrency: Option<usize>,
+    /// Packet rate cap, passed to Nmap as `--max-rate`
+    #[serde(default)]
+    pub max_packets_per_second: Option<u32>,
 }
 
 impl Default for ScanConfig {
@@ -168,10 +183,27 @@ impl Default for ScanConfig {
             aggressive: false,
             skip_discovery: false,
             output_formats: vec![OutputFormat::Normal, OutputFormat::Xml],
+            privilege_mode: ScanPrivilegeMode::default(),
+            batch_size: None,
+            batch_concurrency: None,
+            max_packets_per_second: None,
         }
     }
 }
 
+/// How Nmap is run with respect to raw packet access
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ScanPrivilegeMode {
+    /// Running as root/Administrator; scan types are used as defined
+    #[default]
+    Elevated,
+    /// Nmap holds raw socket capabilities and is told so with `--privileged`
+    Capabilities,
+    /// No raw packet access; SYN scans run as TCP connect scans (-sT)
+    Unprivileged,
+}
+
 /// Output format for scan results
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -210,8 +242,62 @@ pub struct ScanJob {
     pub error: Option<String>,
     /// Progress percentage (0-100)
     pub progress: u8,
-    /// Raw Nmap output
-    pub raw_output: Option<String>,
+    /// Host and port counts from the stored results, for list views
+    #[serde(default)]
+    pub totals: Option<ScanTotals>,
+    /// Set when this scan re-tests previously reported findings
+    #[serde(default)]
+    pub verification: Option<VerificationInfo>,
+    /// Set when the scan was imported from an Nmap XML file
+    #[serde(default)]
+    pub import: Option<ScanImportInfo>,
+    /// Notes about how the scan was run, e.g. a downgraded scan type
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Batch completion, once a batched scan has been planned
+    #[serde(default)]
+    pub batches: Option<ScanBatchProgress>,
+    /// Nmap's own progress while the scan runs; sent with progress events,
+    /// never stored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live: Option<ScanLiveProgress>,
+}
+
+/// Progress Nmap reports while scanning (`--stats-every`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanLiveProgress {
+    /// Overall completion, across batches
+    pub percent_done: f64,
+    /// Hosts Nmap has finished with
+    pub hosts_completed: u32,
+    /// Nmap's estimate of the time left in its current phase
+    pub eta_seconds: Option<u64>,
+    /// Current phase, e.g. "SYN Stealth Scan" or "Service scan"
+    pub task: Option<String>,
+}
+
+/// Completed and total batches of a batched scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanBatchProgress {
+    pub completed: u32,
+    pub total: u32,
+}
+
+/// One Nmap run of a batched scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanBatch {
+    pub scan_id: String,
+    pub index: u32,
+    pub targets: Vec<String>,
+    /// Queued until run, then Completed or Failed
+    pub status: ScanStatus,
+    /// Parsed results, once completed
+    pub results: Option<ScanResults>,
+    pub error: Option<String>,
+    pub completed_at: Option<String>,
 }
 
 /// Results from a completed scan
@@ -238,6 +324,43 @@ pub struct ScanResults {
     pub end_time: String,
 }
 
+/// Headline counts of a scan's results, kept on the scan record
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanTotals {
+    pub hosts_scanned: u32,
+    pub hosts_up: u32,
+    /// Open ports across all hosts
+    pub open_ports: u32,
+    pub duration_seconds: f64,
+}
+
+impl ScanTotals {
+    pub fn from_results(results: &ScanResults) -> Self {
+        ScanTotals {
+            hosts_scanned: results.hosts_scanned,
+            hosts_up: results.hosts_up,
+            open_ports: results.hosts
+                .iter()
+                .flat_map(|h| &h.ports)
+                .filter(|p| p.state == PortState::Open)
+                .count() as u32,
+            duration_seconds: results.duration_seconds,
+        }
+    }
+}
+
+/// A scan together with its stored results
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanDetail {
+    pub scan: ScanJob,
+    /// Parsed hosts, ports and scripts; `None` until the scan has completed
+    pub results: Option<ScanResults>,
+    /// Whether the original Nmap XML is available for export
+    pub has_raw_xml: bool,
+}
+
 /// A discovered host from a scan
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -258,6 +381,18 @@ pub struct DiscoveredHost {
     pub os_matches: Vec<OsMatch>,
     /// Additional host scripts output
     pub host_scripts: Vec<ScriptResult>,
+    /// Summaries of ports Nmap did not list individually
+    #[serde(default)]
+    pub extra_ports: Vec<ExtraPorts>,
+    /// Uptime guessed from TCP timestamps during OS detection
+    #[serde(default)]
+    pub uptime_seconds: Option<u64>,
+    /// Last boot time as reported by Nmap
+    #[serde(default)]
+    pub last_boot: Option<String>,
+    /// Network distance in hops
+    #[serde(default)]
+    pub distance: Option<u32>,
 }
 
 /// A discovered port on a host
@@ -308,6 +443,16 @@ pub struct ScriptResult {
     pub output: String,
 }
 
+/// Ports Nmap collapsed into a single `<extraports>` summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraPorts {
+    /// Shared port state (usually filtered or closed)
+    pub state: String,
+    /// Number of ports in this state
+    pub count: u32,
+}
+
 // ============================================================================
 // Asset Inventory
 // ============================================================================
@@ -345,8 +490,28 @@ impl AssetCategory {
     }
 }
 
+impl std::str::FromStr for AssetCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "server" => Ok(AssetCategory::Server),
+            "workstation" => Ok(AssetCategory::Workstation),
+            "networkdevice" | "network_device" | "network" => Ok(AssetCategory::NetworkDevice),
+            "securitydevice" | "security_device" | "security" => Ok(AssetCategory::SecurityDevice),
+            "printer" => Ok(AssetCategory::Printer),
+            "iot" => Ok(AssetCategory::IoT),
+            "mobile" => Ok(AssetCategory::Mobile),
+            "virtual" | "vm" => Ok(AssetCategory::Virtual),
+            "cloud" => Ok(AssetCategory::Cloud),
+            "unknown" => Ok(AssetCategory::Unknown),
+            _ => Err(format!("Unknown asset category: {}", s)),
+        }
+    }
+}
+
 /// Asset criticality level
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Criticality {
     Critical,
@@ -356,6 +521,34 @@ pub enum Criticality {
     Informational,
 }
 
+impl Criticality {
+    /// Position in severity order, 0 for critical through 4 for informational
+    pub fn rank(self) -> u8 {
+        match self {
+            Criticality::Critical => 0,
+            Criticality::High => 1,
+            Criticality::Medium => 2,
+            Criticality::Low => 3,
+            Criticality::Informational => 4,
+        }
+    }
+}
+
+impl std::str::FromStr for Criticality {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "critical" => Ok(Criticality::Critical),
+            "high" => Ok(Criticality::High),
+            "medium" => Ok(Criticality::Medium),
+            "low" => Ok(Criticality::Low),
+            "informational" | "info" => Ok(Criticality::Informational),
+            _ => Err(format!("Unknown criticality: {}", s)),
+        }
+    }
+}
+
 /// Asset status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -367,6 +560,21 @@ pub enum AssetStatus {
     Maintenance,
 }
 
+impl std::str::FromStr for AssetStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(AssetStatus::Active),
+            "inactive" => Ok(AssetStatus::Inactive),
+            "decommissioned" => Ok(AssetStatus::Decommissioned),
+            "pending" => Ok(AssetStatus::Pending),
+            "maintenance" => Ok(AssetStatus::Maintenance),
+            _ => Err(format!("Unknown asset status: {}", s)),
+        }
+    }
+}
+
 /// An asset in the inventory
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -443,6 +651,243 @@ pub struct AssetGroup {
     pub color: Option<String>,
 }
 
+/// Kind of change detected on an asset between discoveries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetChangeType {
+    PortOpened,
+    PortClosed,
+    OsChanged,
+    HostnameChanged,
+}
+
+/// A change recorded when a discovery modified an existing asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetChange {
+    /// Unique change identifier
+    pub id: String,
+    /// Asset that changed
+    pub asset_id: String,
+    /// Scan that observed the change
+    pub scan_id: String,
+    /// What changed
+    pub change_type: AssetChangeType,
+    /// Previous value (None for newly opened ports)
+    pub old_value: Option<String>,
+    /// New value (None for closed ports)
+    pub new_value: Option<String>,
+    /// When the change was recorded
+    pub changed_at: String,
+}
+
+// ============================================================================
+// Asset Queries
+// ============================================================================
+
+/// Server-side asset filter; every condition that is set must hold
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AssetFilter {
+    /// Any of these categories
+    pub categories: Vec<AssetCategory>,
+    /// Any of these criticality levels
+    pub criticalities: Vec<Criticality>,
+    /// Any of these statuses
+    pub statuses: Vec<AssetStatus>,
+    /// All of these tags (case-insensitive)
+    pub tags: Vec<String>,
+    /// An open service on this port
+    pub open_port: Option<u16>,
+    /// Substring of a service name or product/version
+    pub service: Option<String>,
+    /// Substring of the operating system
+    pub operating_system: Option<String>,
+    /// Last seen at or after this time (RFC 3339)
+    pub last_seen_after: Option<String>,
+    /// Last seen before this time (RFC 3339)
+    pub last_seen_before: Option<String>,
+    /// Sort field (default: IP address)
+    pub sort_by: Option<AssetSortField>,
+    pub descending: bool,
+}
+
+/// Field to sort asset query results by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetSortField {
+    Name,
+    IpAddress,
+    Category,
+    /// Critical first when ascending
+    Criticality,
+    Status,
+    FirstSeen,
+    LastSeen,
+}
+
+/// One page of an asset query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetPage {
+    pub assets: Vec<Asset>,
+    /// Assets matching the filter across all pages
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// Fields set on many assets at once; unset fields are left alone
+///
+/// An empty owner or location clears it; tags replace the asset's tags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AssetPatch {
+    pub criticality: Option<Criticality>,
+    pub owner: Option<String>,
+    pub location: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+/// A named asset filter saved for a client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetView {
+    pub id: String,
+    pub client_id: String,
+    pub name: String,
+    pub filter: AssetFilter,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ============================================================================
+// Findings & Verification
+// ============================================================================
+
+/// Lifecycle status of a network finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingStatus {
+    Open,
+    Remediated,
+    Unverifiable,
+}
+
+/// A finding raised by a detection rule against scan results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkFinding {
+    /// Unique finding identifier
+    pub id: String,
+    /// Client this finding belongs to
+    pub client_id: String,
+    /// Detection rule that raised the finding
+    pub rule_id: String,
+    /// Affected host IP address
+    pub host: String,
+    /// Affected port
+    pub port: u16,
+    /// Service name on the port
+    pub service: String,
+    /// Finding title
+    pub title: String,
+    /// Severity, taken from the rule
+    pub severity: Criticality,
+    /// Current status
+    pub status: FindingStatus,
+    /// Observations supporting the current status, oldest first
+    pub evidence: Vec<String>,
+    /// Consultant or verification notes
+    pub notes: Option<String>,
+    /// When the finding was first detected
+    pub detected_at: String,
+    /// When the finding was last updated
+    pub updated_at: String,
+    /// Verification scan that last evaluated this finding
+    pub verification_scan_id: Option<String>,
+}
+
+/// Hosts sharing the same set of ports to re-test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationTarget {
+    /// Host IP addresses
+    pub hosts: Vec<String>,
+    /// Ports to probe on every host in the batch
+    pub ports: Vec<u16>,
+}
+
+/// Verification metadata attached to a scan job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationInfo {
+    /// Findings being verified
+    pub finding_ids: Vec<String>,
+    /// Host/port batches probed by the scan
+    pub targets: Vec<VerificationTarget>,
+    /// Outcome, set once the scan completes
+    pub summary: Option<VerificationSummary>,
+}
+
+/// Nmap output formats that can be imported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NmapOutputFormat {
+    /// `-oX`
+    #[default]
+    Xml,
+    /// `-oG`
+    Greppable,
+}
+
+/// Provenance of a scan imported from Nmap output run outside the app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanImportInfo {
+    /// File the results were imported from ("Pasted output" if pasted)
+    pub source_file: String,
+    #[serde(default)]
+    pub format: NmapOutputFormat,
+    /// Nmap command line recorded in the XML
+    pub command_line: String,
+    /// Nmap version recorded in the XML
+    pub nmap_version: Option<String>,
+    /// When Nmap started the scan, as recorded in the XML
+    pub scan_started: String,
+    /// When the file was imported
+    pub imported_at: String,
+}
+
+/// Outcome of importing Nmap output
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanImportSummary {
+    /// Scan created for the import
+    pub scan_id: String,
+    pub scan_name: String,
+    /// Live hosts fed into the asset inventory
+    pub hosts_imported: usize,
+    pub new_assets: usize,
+    pub updated_assets: usize,
+    /// Problems that did not stop the import
+    pub warnings: Vec<String>,
+}
+
+/// Outcome counts of a verification scan
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationSummary {
+    /// Findings confirmed fixed
+    pub verified_fixed: usize,
+    /// Findings whose condition still holds
+    pub still_present: usize,
+    /// Findings on hosts that did not respond
+    pub unreachable: usize,
+    /// When the results were evaluated
+    pub evaluated_at: String,
+}
+
 // ============================================================================
 // Statistics
 // ============================================================================
@@ -465,6 +910,15 @@ pub struct NetworkStats {
     pub top_services: Vec<ServiceCount>,
     /// Recent scan activity
     pub recent_scans: Vec<ScanSummary>,
+    /// Assets with at least one known vulnerability
+    pub vulnerable_assets: usize,
+    /// Known vulnerabilities across all assets
+    pub known_vulnerabilities: usize,
+    /// Known vulnerabilities by CVSS severity
+    pub by_vulnerability_severity: Vec<CriticalityCount>,
+    /// Services breaking a service policy, across all assets
+    #[serde(default)]
+    pub policy_violations: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -499,3 +953,122 @@ pub struct ScanSummary {
     pub hosts_found: u32,
     pub completed_at: Option<String>,
 }
+
+// ============================================================================
+// Scan Schedules
+// ============================================================================
+
+/// How often a scheduled scan repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleFrequency {
+    /// Every N minutes
+    Interval { minutes: u32 },
+    /// Once a day at a fixed local time
+    Daily { hour: u32, minute: u32 },
+}
+
+/// What a schedule does about a run that came due while the app was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedRunPolicy {
+    /// Run once as soon as the app is open again
+    #[default]
+    RunOnce,
+    /// Wait for the next scheduled time
+    Skip,
+}
+
+/// Saved scan defaults (exclusions, ports, timing) reused across scans
+///
+/// Profiles without a client are available to every client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanProfile {
+    pub id: String,
+    pub client_id: Option<String>,
+    pub name: String,
+    /// Defaults for scans using this profile; targets are left empty
+    pub config: ScanConfig,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Per-scan settings; unset fields fall back to the base configuration
+#[derive(Debug, Clone, Default)]
+pub struct ScanOverrides {
+    pub scan_type: Option<ScanType>,
+    pub custom_args: Option<String>,
+    pub ports: Option<String>,
+    /// Added to the base exclusions rather than replacing them
+    pub exclude_targets: Option<Vec<String>>,
+    pub aggressive: Option<bool>,
+    pub skip_discovery: Option<bool>,
+    pub batch_size: Option<usize>,
+    pub batch_concurrency: Option<usize>,
+    pub max_packets_per_second: Option<u32>,
+}
+
+impl ScanOverrides {
+    /// Layer these settings over `base` for a scan of `targets`
+    pub fn apply(self, mut base: ScanConfig, targets: Vec<String>) -> ScanConfig {
+        base.targets = targets;
+        if let Some(scan_type) = self.scan_type {
+            base.scan_type = scan_type;
+        }
+        if self.custom_args.is_some() {
+            base.custom_args = self.custom_args;
+        }
+        if self.ports.is_some() {
+            base.ports = self.ports;
+        }
+        if let Some(excludes) = self.exclude_targets {
+            let merged = base.exclude_targets.get_or_insert_with(Vec::new);
+            for exclude in excludes {
+                if !merged.contains(&exclude) {
+                    merged.push(exclude);
+                }
+            }
+        }
+        if let Some(aggressive) = self.aggressive {
+            base.aggressive = aggressive;
+        }
+        if let Some(skip_discovery) = self.skip_discovery {
+            base.skip_discovery = skip_discovery;
+        }
+        if self.batch_size.is_some() {
+            base.batch_size = self.batch_size;
+        }
+        if self.batch_concurrency.is_some() {
+            base.batch_concurrency = self.batch_concurrency;
+        }
+        if self.max_packets_per_second.is_some() {
+            base.max_packets_per_second = self.max_packets_per_second;
+        }
+        base
+    }
+}
+
+/// A recurring scan run by the background scheduler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanSchedule {
+    pub id: String,
+    pub client_id: String,
+    pub name: String,
+    /// Scan configuration used for every run
+    pub config: ScanConfig,
+    pub frequency: ScheduleFrequency,
+    pub enabled: bool,
+    #[serde(default)]
+    pub missed_runs: MissedRunPolicy,
+    /// When the scan is next due (None while disabled)
+    pub next_run: Option<String>,
+    pub last_run: Option<String>,
+    pub last_status: Option<ScanStatus>,
+    /// Why the last run failed (e.g. Nmap missing, target unreachable)
+    pub last_error: Option<String>,
+    pub last_scan_id: Option<String>,
+    pub created_at: String,
+}
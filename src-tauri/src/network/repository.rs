@@ -0,0 +1,1924 @@
+//! Network Repository
+//!
+//! Database persistence for the asset inventory: assets and their services,
+//! filtered asset queries and saved views, asset groups, the change history
+//! recorded between discoveries,
+//! recurring scan schedules, scans with their stored results, and the
+//! imported CVE dataset with the vulnerabilities matched to each asset,
+//! scanner settings such as the Nmap path override, user service
+//! policies, and the findings raised by detection rules.
+
+use crate::db::{add_missing_columns, Database};
+use crate::error::{OptioError, OptioResult};
+use crate::network::inventory::ServicePolicy;
+use crate::network::models::*;
+use crate::network::vuln_match::{cvss_severity, KnownExploitedVuln, VulnEntry, VulnerabilityMatch};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use rusqlite::{params, types::Value, OptionalExtension};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+/// Initialize network database schema
+pub fn init_network_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Discovered assets (tags, scan ids, and metadata stored as JSON)
+        CREATE TABLE IF NOT EXISTS assets (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            ip_address TEXT NOT NULL,
+            mac_address TEXT,
+            category TEXT NOT NULL,
+            operating_system TEXT,
+            criticality TEXT NOT NULL,
+            status TEXT NOT NULL,
+            location TEXT,
+            owner TEXT,
+            description TEXT,
+            tags TEXT NOT NULL,
+            scan_ids TEXT NOT NULL,
+            metadata TEXT,
+            first_seen TEXT NOT NULL,
+            last_seen TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE,
+            UNIQUE(client_id, ip_address)
+        );
+
+        -- Services observed on each asset
+        CREATE TABLE IF NOT EXISTS asset_services (
+            asset_id TEXT NOT NULL,
+            port INTEGER NOT NULL,
+            protocol TEXT NOT NULL,
+            name TEXT NOT NULL,
+            version TEXT,
+            state TEXT NOT NULL,
+            PRIMARY KEY (asset_id, port, protocol),
+            FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE
+        );
+
+        -- Saved asset filters (filter stored as JSON)
+        CREATE TABLE IF NOT EXISTS asset_views (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            filter TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE,
+            UNIQUE(client_id, name)
+        );
+
+        -- Asset groups
+        CREATE TABLE IF NOT EXISTS asset_groups (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            description TEXT,
+            color TEXT,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        -- Group membership (many-to-many)
+        CREATE TABLE IF NOT EXISTS asset_group_members (
+            group_id TEXT NOT NULL,
+            asset_id TEXT NOT NULL,
+            PRIMARY KEY (group_id, asset_id),
+            FOREIGN KEY (group_id) REFERENCES asset_groups(id) ON DELETE CASCADE,
+            FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE
+        );
+
+        -- Changes detected when a discovery updates an existing asset
+        CREATE TABLE IF NOT EXISTS asset_history (
+            id TEXT PRIMARY KEY,
+            asset_id TEXT NOT NULL,
+            scan_id TEXT NOT NULL,
+            change_type TEXT NOT NULL,
+            old_value TEXT,
+            new_value TEXT,
+            changed_at TEXT NOT NULL,
+            FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE
+        );
+
+        -- Recurring scans (config and frequency stored as JSON)
+        CREATE TABLE IF NOT EXISTS scan_schedules (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            config TEXT NOT NULL,
+            frequency TEXT NOT NULL,
+            enabled INTEGER NOT NULL,
+            missed_runs TEXT NOT NULL DEFAULT 'RunOnce',
+            next_run TEXT,
+            last_run TEXT,
+            last_status TEXT,
+            last_error TEXT,
+            last_scan_id TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        -- Saved scan defaults (config stored as JSON); no client means global
+        CREATE TABLE IF NOT EXISTS scan_profiles (
+            id TEXT PRIMARY KEY,
+            client_id TEXT,
+            name TEXT NOT NULL,
+            config TEXT NOT NULL,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        -- Scan records (config, verification and import info stored as JSON;
+        -- result counts kept as columns so lists never load the results)
+        CREATE TABLE IF NOT EXISTS scans (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            config TEXT NOT NULL,
+            status TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            started_at TEXT,
+            completed_at TEXT,
+            error TEXT,
+            progress INTEGER NOT NULL,
+            verification TEXT,
+            import TEXT,
+            hosts_scanned INTEGER,
+            hosts_up INTEGER,
+            open_ports INTEGER,
+            duration_seconds REAL,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        -- Notes on how a scan was run (JSON array), e.g. a downgraded scan type
+        CREATE TABLE IF NOT EXISTS scan_warnings (
+            scan_id TEXT PRIMARY KEY,
+            warnings TEXT NOT NULL,
+            FOREIGN KEY (scan_id) REFERENCES scans(id) ON DELETE CASCADE
+        );
+
+        -- Parsed results as JSON and the original Nmap XML, gzip-compressed
+        CREATE TABLE IF NOT EXISTS scan_results (
+            scan_id TEXT PRIMARY KEY,
+            results TEXT NOT NULL,
+            raw_xml BLOB,
+            raw_xml_size INTEGER,
+            FOREIGN KEY (scan_id) REFERENCES scans(id) ON DELETE CASCADE
+        );
+
+        -- Batches of a batched scan (targets and parsed results as JSON)
+        CREATE TABLE IF NOT EXISTS scan_batches (
+            scan_id TEXT NOT NULL,
+            batch_index INTEGER NOT NULL,
+            targets TEXT NOT NULL,
+            status TEXT NOT NULL,
+            results TEXT,
+            error TEXT,
+            completed_at TEXT,
+            PRIMARY KEY (scan_id, batch_index),
+            FOREIGN KEY (scan_id) REFERENCES scans(id) ON DELETE CASCADE
+        );
+
+        -- Imported CVEs (affected products stored as JSON); bundled CVEs are
+        -- not stored
+        CREATE TABLE IF NOT EXISTS vuln_entries (
+            cve_id TEXT PRIMARY KEY,
+            summary TEXT NOT NULL,
+            cvss_score REAL NOT NULL,
+            affected TEXT NOT NULL,
+            imported_at TEXT NOT NULL
+        );
+
+        -- CVEs from an imported CISA Known Exploited Vulnerabilities catalog
+        CREATE TABLE IF NOT EXISTS known_exploited_vulns (
+            cve_id TEXT PRIMARY KEY,
+            vendor_project TEXT NOT NULL,
+            product TEXT NOT NULL,
+            name TEXT NOT NULL,
+            date_added TEXT,
+            due_date TEXT,
+            ransomware INTEGER NOT NULL DEFAULT 0,
+            imported_at TEXT NOT NULL
+        );
+
+        -- CVEs matched to asset services by the last enrichment
+        CREATE TABLE IF NOT EXISTS asset_vulnerabilities (
+            asset_id TEXT NOT NULL,
+            cve_id TEXT NOT NULL,
+            port INTEGER NOT NULL,
+            protocol TEXT NOT NULL,
+            product TEXT NOT NULL,
+            version TEXT NOT NULL,
+            cvss_score REAL NOT NULL,
+            summary TEXT NOT NULL,
+            matched_range TEXT NOT NULL,
+            matched_at TEXT NOT NULL,
+            PRIMARY KEY (asset_id, cve_id, port, protocol),
+            FOREIGN KEY (asset_id) REFERENCES assets(id) ON DELETE CASCADE
+        );
+
+        -- Scanner settings, one row per key
+        CREATE TABLE IF NOT EXISTS scanner_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- User service policies (list conditions stored as JSON)
+        CREATE TABLE IF NOT EXISTS service_policies (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            ports TEXT NOT NULL,
+            services TEXT NOT NULL,
+            categories TEXT NOT NULL,
+            criticalities TEXT NOT NULL,
+            exempt_tags TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            message TEXT NOT NULL,
+            recommendation TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Findings raised by detection rules (evidence stored as JSON)
+        CREATE TABLE IF NOT EXISTS network_findings (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            rule_id TEXT NOT NULL,
+            host TEXT NOT NULL,
+            port INTEGER NOT NULL,
+            service TEXT NOT NULL,
+            title TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            status TEXT NOT NULL,
+            evidence TEXT NOT NULL,
+            notes TEXT,
+            detected_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            verification_scan_id TEXT,
+            FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_assets_client ON assets(client_id);
+        CREATE INDEX IF NOT EXISTS idx_assets_last_seen ON assets(client_id, last_seen);
+        CREATE INDEX IF NOT EXISTS idx_asset_services_port ON asset_services(port, state);
+        CREATE INDEX IF NOT EXISTS idx_asset_groups_client ON asset_groups(client_id);
+        CREATE INDEX IF NOT EXISTS idx_asset_history_asset ON asset_history(asset_id);
+        CREATE INDEX IF NOT EXISTS idx_scan_schedules_due ON scan_schedules(enabled, next_run);
+        CREATE INDEX IF NOT EXISTS idx_scans_client ON scans(client_id);
+        CREATE INDEX IF NOT EXISTS idx_asset_vulnerabilities_asset ON asset_vulnerabilities(asset_id);
+        CREATE INDEX IF NOT EXISTS idx_network_findings_client ON network_findings(client_id, rule_id, host, port);
+    "#)?;
+
+    // Missed-run handling was added after scheduling shipped
+    add_missing_columns(&conn, "scan_schedules", &[("missed_runs", "TEXT NOT NULL DEFAULT 'RunOnce'")])?;
+
+    tracing::info!("Network schema initialized");
+    Ok(())
+}
+
+/// Asset repository
+pub struct AssetRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AssetRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        AssetRepository { db }
+    }
+
+    /// Insert or replace an asset together with its services
+    pub fn save(&self, asset: &Asset) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT INTO assets
+               (id, client_id, name, ip_address, mac_address, category, operating_system,
+                criticality, status, location, owner, description, tags, scan_ids, metadata,
+                first_seen, last_seen)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+               ON CONFLICT(id) DO UPDATE SET
+                   name = excluded.name,
+                   mac_address = excluded.mac_address,
+                   category = excluded.category,
+                   operating_system = excluded.operating_system,
+                   criticality = excluded.criticality,
+                   status = excluded.status,
+                   location = excluded.location,
+                   owner = excluded.owner,
+                   description = excluded.description,
+                   tags = excluded.tags,
+                   scan_ids = excluded.scan_ids,
+                   metadata = excluded.metadata,
+                   last_seen = excluded.last_seen"#,
+            params![
+                asset.id,
+                asset.client_id,
+                asset.name,
+                asset.ip_address,
+                asset.mac_address,
+                format!("{:?}", asset.category),
+                asset.operating_system,
+                format!("{:?}", asset.criticality),
+                format!("{:?}", asset.status),
+                asset.location,
+                asset.owner,
+                asset.description,
+                serde_json::to_string(&asset.tags)?,
+                serde_json::to_string(&asset.scan_ids)?,
+                asset.metadata.as_ref().map(serde_json::to_string).transpose()?,
+                asset.first_seen,
+                asset.last_seen,
+            ],
+        )?;
+
+        // Replace services
+        conn.execute("DELETE FROM asset_services WHERE asset_id = ?1", params![asset.id])?;
+        for service in &asset.services {
+            conn.execute(
+                r#"INSERT OR REPLACE INTO asset_services (asset_id, port, protocol, name, version, state)
+                   VALUES (?1, ?2, ?3, ?4, ?5, ?6)"#,
+                params![
+                    asset.id,
+                    service.port,
+                    format!("{:?}", service.protocol),
+                    service.name,
+                    service.version,
+                    format!("{:?}", service.state),
+                ],
+            )?;
+        }
+
+        tracing::debug!("Saved asset: {}", asset.id);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<Asset>> {
+        let sql = format!("SELECT {} FROM assets WHERE id = ?1", ASSET_COLUMNS);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    /// Find a client's asset by IP address
+    pub fn find_by_ip(&self, client_id: &str, ip_address: &str) -> OptioResult<Option<Asset>> {
+        let sql = format!("SELECT {} FROM assets WHERE client_id = ?1 AND ip_address = ?2", ASSET_COLUMNS);
+        Ok(self.query(&sql, params![client_id, ip_address])?.pop())
+    }
+
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<Asset>> {
+        let sql = format!("SELECT {} FROM assets WHERE client_id = ?1 ORDER BY ip_address", ASSET_COLUMNS);
+        self.query(&sql, params![client_id])
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM assets WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    /// A page of a client's assets matching a filter, and the total match count
+    pub fn query_filtered(
+        &self,
+        client_id: &str,
+        filter: &AssetFilter,
+        limit: usize,
+        offset: usize,
+    ) -> OptioResult<(Vec<Asset>, usize)> {
+        let (where_clause, mut values) = filter_clause(client_id, filter)?;
+
+        let total: i64 = {
+            let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM assets WHERE {}", where_clause),
+                rusqlite::params_from_iter(values.iter()),
+                |row| row.get(0),
+            )?
+        };
+
+        let direction = if filter.descending { "DESC" } else { "ASC" };
+        let sql = format!(
+            "SELECT {} FROM assets WHERE {} ORDER BY {} {}, ip_address, id LIMIT ? OFFSET ?",
+            ASSET_COLUMNS,
+            where_clause,
+            sort_expression(filter.sort_by.unwrap_or(AssetSortField::IpAddress)),
+            direction
+        );
+        values.push(Value::Integer(limit as i64));
+        values.push(Value::Integer(offset as i64));
+
+        let assets = self.query(&sql, rusqlite::params_from_iter(values.iter()))?;
+        Ok((assets, total as usize))
+    }
+
+    /// Apply a patch to many assets in one transaction, returning how many exist
+    pub fn apply_patch(&self, ids: &[String], patch: &AssetPatch) -> OptioResult<usize> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        let tags = patch.tags.as_ref().map(serde_json::to_string).transpose()?;
+
+        let mut updated = 0;
+        for id in ids {
+            updated += tx.execute(
+                r#"UPDATE assets SET
+                       criticality = COALESCE(?2, criticality),
+                       owner = CASE WHEN ?3 IS NULL THEN owner ELSE NULLIF(?3, '') END,
+                       location = CASE WHEN ?4 IS NULL THEN location ELSE NULLIF(?4, '') END,
+                       tags = COALESCE(?5, tags)
+                   WHERE id = ?1"#,
+                params![
+                    id,
+                    patch.criticality.map(|c| format!("{:?}", c)),
+                    patch.owner.as_deref().map(str::trim),
+                    patch.location.as_deref().map(str::trim),
+                    tags,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    /// Rewrite the tags of many assets in one transaction
+    ///
+    /// Returns how many assets' tags actually changed.
+    pub fn update_tags<F>(&self, ids: &[String], mut update: F) -> OptioResult<usize>
+    where
+        F: FnMut(&mut Vec<String>),
+    {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        let mut changed = 0;
+        for id in ids {
+            let tags_json: Option<String> = tx
+                .query_row("SELECT tags FROM assets WHERE id = ?1", params![id], |row| row.get(0))
+                .optional()?;
+            let tags_json = match tags_json {
+                Some(tags_json) => tags_json,
+                None => continue,
+            };
+
+            let before: Vec<String> = serde_json::from_str(&tags_json)?;
+            let mut tags = before.clone();
+            update(&mut tags);
+            if tags != before {
+                tx.execute(
+                    "UPDATE assets SET tags = ?2 WHERE id = ?1",
+                    params![id, serde_json::to_string(&tags)?],
+                )?;
+                changed += 1;
+            }
+        }
+
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    /// Run an asset query and load each asset's services
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<Asset>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let assets = stmt.query_map(query_params, |row| Ok(parse_asset_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        // Load services
+        let mut result = Vec::new();
+        for mut asset in assets {
+            let mut svc_stmt = conn.prepare(
+                "SELECT port, protocol, name, version, state FROM asset_services WHERE asset_id = ?1 ORDER BY port"
+            )?;
+            asset.services = svc_stmt
+                .query_map(params![asset.id], |row| Ok(parse_service_row(row)))?
+                .filter_map(|r| r.ok())
+                .collect::<OptioResult<Vec<_>>>()?;
+            result.push(asset);
+        }
+
+        Ok(result)
+    }
+}
+
+/// WHERE clause and its values for an asset filter
+fn filter_clause(client_id: &str, filter: &AssetFilter) -> OptioResult<(String, Vec<Value>)> {
+    let mut clauses = vec!["client_id = ?".to_string()];
+    let mut values = vec![Value::Text(client_id.to_string())];
+
+    let mut any_of = |column: &str, options: Vec<String>| {
+        if !options.is_empty() {
+            clauses.push(format!("{} IN ({})", column, vec!["?"; options.len()].join(", ")));
+            values.extend(options.into_iter().map(Value::Text));
+        }
+    };
+    any_of("category", filter.categories.iter().map(|c| format!("{:?}", c)).collect());
+    any_of("criticality", filter.criticalities.iter().map(|c| format!("{:?}", c)).collect());
+    any_of("status", filter.statuses.iter().map(|s| format!("{:?}", s)).collect());
+
+    for tag in filter.tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        clauses.push("EXISTS (SELECT 1 FROM json_each(assets.tags) t WHERE lower(t.value) = lower(?))".to_string());
+        values.push(Value::Text(tag.to_string()));
+    }
+
+    if let Some(port) = filter.open_port {
+        clauses.push(
+            "EXISTS (SELECT 1 FROM asset_services s WHERE s.asset_id = assets.id AND s.port = ? AND s.state = 'Open')"
+                .to_string(),
+        );
+        values.push(Value::Integer(port as i64));
+    }
+
+    if let Some(pattern) = like_pattern(filter.service.as_deref()) {
+        clauses.push(
+            "EXISTS (SELECT 1 FROM asset_services s WHERE s.asset_id = assets.id \
+             AND (s.name LIKE ? ESCAPE '\\' OR s.version LIKE ? ESCAPE '\\'))"
+                .to_string(),
+        );
+        values.push(Value::Text(pattern.clone()));
+        values.push(Value::Text(pattern));
+    }
+
+    if let Some(pattern) = like_pattern(filter.operating_system.as_deref()) {
+        clauses.push("operating_system LIKE ? ESCAPE '\\'".to_string());
+        values.push(Value::Text(pattern));
+    }
+
+    for (bound, op) in [(&filter.last_seen_after, ">="), (&filter.last_seen_before, "<")] {
+        if let Some(bound) = bound {
+            let time = chrono::DateTime::parse_from_rfc3339(bound)
+                .map_err(|e| OptioError::Validation(format!("Invalid date {}: {}", bound, e)))?;
+            clauses.push(format!("last_seen {} ?", op));
+            values.push(Value::Text(time.with_timezone(&chrono::Utc).to_rfc3339()));
+        }
+    }
+
+    Ok((clauses.join(" AND "), values))
+}
+
+/// Case-insensitive substring pattern for LIKE, with wildcards escaped
+fn like_pattern(text: Option<&str>) -> Option<String> {
+    let text = text.map(str::trim).filter(|t| !t.is_empty())?;
+    let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    Some(format!("%{}%", escaped))
+}
+
+fn sort_expression(field: AssetSortField) -> &'static str {
+    match field {
+        AssetSortField::Name => "name COLLATE NOCASE",
+        AssetSortField::IpAddress => "ip_address",
+        AssetSortField::Category => "category",
+        AssetSortField::Criticality => {
+            "CASE criticality WHEN 'Critical' THEN 0 WHEN 'High' THEN 1 WHEN 'Medium' THEN 2 \
+             WHEN 'Low' THEN 3 ELSE 4 END"
+        }
+        AssetSortField::Status => "status",
+        AssetSortField::FirstSeen => "first_seen",
+        AssetSortField::LastSeen => "last_seen",
+    }
+}
+
+/// Saved asset view repository
+pub struct AssetViewRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AssetViewRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        AssetViewRepository { db }
+    }
+
+    /// Insert a view or update its name and filter
+    pub fn save(&self, view: &AssetView) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT INTO asset_views (id, client_id, name, filter, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+               ON CONFLICT(id) DO UPDATE SET
+                   name = excluded.name, filter = excluded.filter, updated_at = excluded.updated_at"#,
+            params![
+                view.id,
+                view.client_id,
+                view.name,
+                serde_json::to_string(&view.filter)?,
+                view.created_at,
+                view.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn find_by_name(&self, client_id: &str, name: &str) -> OptioResult<Option<AssetView>> {
+        let sql = format!("SELECT {} FROM asset_views WHERE client_id = ?1 AND name = ?2", VIEW_COLUMNS);
+        Ok(self.query(&sql, params![client_id, name])?.pop())
+    }
+
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<AssetView>> {
+        let sql = format!("SELECT {} FROM asset_views WHERE client_id = ?1 ORDER BY name COLLATE NOCASE", VIEW_COLUMNS);
+        self.query(&sql, params![client_id])
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM asset_views WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<AssetView>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let views = stmt.query_map(query_params, |row| Ok(parse_view_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(views)
+    }
+}
+
+const VIEW_COLUMNS: &str = "id, client_id, name, filter, created_at, updated_at";
+
+/// Asset group repository
+pub struct AssetGroupRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AssetGroupRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        AssetGroupRepository { db }
+    }
+
+    pub fn create(&self, group: &AssetGroup) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT INTO asset_groups (id, client_id, name, description, color)
+               VALUES (?1, ?2, ?3, ?4, ?5)"#,
+            params![group.id, group.client_id, group.name, group.description, group.color],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn exists(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM asset_groups WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<AssetGroup>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, client_id, name, description, color FROM asset_groups WHERE client_id = ?1 ORDER BY name"
+        )?;
+
+        let groups: Vec<AssetGroup> = stmt.query_map(params![client_id], |row| {
+            Ok(AssetGroup {
+                id: row.get(0)?,
+                client_id: row.get(1)?,
+                name: row.get(2)?,
+                description: row.get(3)?,
+                asset_ids: vec![],
+                color: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+        // Load members
+        let mut result = Vec::new();
+        for mut group in groups {
+            let mut member_stmt = conn.prepare(
+                "SELECT asset_id FROM asset_group_members WHERE group_id = ?1"
+            )?;
+            group.asset_ids = member_stmt
+                .query_map(params![group.id], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect();
+            result.push(group);
+        }
+
+        Ok(result)
+    }
+
+    pub fn add_member(&self, group_id: &str, asset_id: &str) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO asset_group_members (group_id, asset_id) VALUES (?1, ?2)",
+            params![group_id, asset_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_member(&self, group_id: &str, asset_id: &str) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM asset_group_members WHERE group_id = ?1 AND asset_id = ?2",
+            params![group_id, asset_id],
+        )?;
+        Ok(())
+    }
+}
+
+/// Asset change history repository
+pub struct AssetHistoryRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> AssetHistoryRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        AssetHistoryRepository { db }
+    }
+
+    pub fn create(&self, change: &AssetChange) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT INTO asset_history
+               (id, asset_id, scan_id, change_type, old_value, new_value, changed_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+            params![
+                change.id,
+                change.asset_id,
+                change.scan_id,
+                format!("{:?}", change.change_type),
+                change.old_value,
+                change.new_value,
+                change.changed_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// List an asset's changes, oldest first
+    pub fn list_by_asset(&self, asset_id: &str) -> OptioResult<Vec<AssetChange>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            r#"SELECT id, asset_id, scan_id, change_type, old_value, new_value, changed_at
+               FROM asset_history WHERE asset_id = ?1 ORDER BY changed_at, rowid"#
+        )?;
+
+        let changes = stmt.query_map(params![asset_id], |row| Ok(parse_change_row(row)))?
+        .filter_map(|r| r.ok())
+        .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(changes)
+    }
+}
+
+/// Scanner settings key for the user's Nmap path
+const NMAP_PATH_SETTING: &str = "nmap_path";
+
+/// Scanner settings repository
+pub struct ScannerSettingsRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ScannerSettingsRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ScannerSettingsRepository { db }
+    }
+
+    /// The user's Nmap path, if one is set
+    pub fn nmap_path(&self) -> OptioResult<Option<String>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let path = conn
+            .query_row(
+                "SELECT value FROM scanner_settings WHERE key = ?1",
+                params![NMAP_PATH_SETTING],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(path)
+    }
+
+    /// Set the user's Nmap path; None clears it
+    pub fn set_nmap_path(&self, path: Option<&str>) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        match path {
+            Some(path) => conn.execute(
+                "INSERT INTO scanner_settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                params![NMAP_PATH_SETTING, path, chrono::Utc::now().to_rfc3339()],
+            )?,
+            None => conn.execute("DELETE FROM scanner_settings WHERE key = ?1", params![NMAP_PATH_SETTING])?,
+        };
+
+        Ok(())
+    }
+}
+
+/// User service policy repository
+///
+/// Only user policies are stored; defaults live in code and a stored policy
+/// with a default's id overrides it.
+pub struct ServicePolicyRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ServicePolicyRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ServicePolicyRepository { db }
+    }
+
+    /// Insert or replace a policy
+    pub fn save(&self, policy: &ServicePolicy) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO service_policies
+               (id, name, ports, services, categories, criticalities, exempt_tags, severity,
+                message, recommendation, enabled, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"#,
+            params![
+                policy.id,
+                policy.name,
+                serde_json::to_string(&policy.ports)?,
+                serde_json::to_string(&policy.services)?,
+                serde_json::to_string(&policy.categories)?,
+                serde_json::to_string(&policy.criticalities)?,
+                serde_json::to_string(&policy.exempt_tags)?,
+                format!("{:?}", policy.severity),
+                policy.message,
+                policy.recommendation,
+                policy.enabled,
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// User policies in the order they were first added
+    pub fn list(&self) -> OptioResult<Vec<ServicePolicy>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            r#"SELECT id, name, ports, services, categories, criticalities, exempt_tags, severity,
+                      message, recommendation, enabled
+               FROM service_policies ORDER BY rowid"#
+        )?;
+
+        let policies = stmt.query_map([], |row| Ok(parse_service_policy_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(policies)
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM service_policies WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+}
+
+/// Scan schedule repository
+pub struct ScanScheduleRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ScanScheduleRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ScanScheduleRepository { db }
+    }
+
+    pub fn create(&self, schedule: &ScanSchedule) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT INTO scan_schedules
+               (id, client_id, name, config, frequency, enabled, next_run, last_run,
+                last_status, last_error, last_scan_id, created_at, missed_runs)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"#,
+            params![
+                schedule.id,
+                schedule.client_id,
+                schedule.name,
+                serde_json::to_string(&schedule.config)?,
+                serde_json::to_string(&schedule.frequency)?,
+                schedule.enabled,
+                schedule.next_run,
+                schedule.last_run,
+                schedule.last_status.map(|s| format!("{:?}", s)),
+                schedule.last_error,
+                schedule.last_scan_id,
+                schedule.created_at,
+                format!("{:?}", schedule.missed_runs),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Save a schedule's name, scan, timing and missed-run handling
+    pub fn update(&self, schedule: &ScanSchedule) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let updated = conn.execute(
+            r#"UPDATE scan_schedules
+               SET name = ?2, config = ?3, frequency = ?4, missed_runs = ?5, next_run = ?6
+               WHERE id = ?1"#,
+            params![
+                schedule.id,
+                schedule.name,
+                serde_json::to_string(&schedule.config)?,
+                serde_json::to_string(&schedule.frequency)?,
+                format!("{:?}", schedule.missed_runs),
+                schedule.next_run,
+            ],
+        )?;
+        Ok(updated > 0)
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<ScanSchedule>> {
+        let sql = format!("SELECT {} FROM scan_schedules WHERE id = ?1", SCHEDULE_COLUMNS);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<ScanSchedule>> {
+        let sql = format!("SELECT {} FROM scan_schedules WHERE client_id = ?1 ORDER BY created_at", SCHEDULE_COLUMNS);
+        self.query(&sql, params![client_id])
+    }
+
+    pub fn list_all(&self) -> OptioResult<Vec<ScanSchedule>> {
+        let sql = format!("SELECT {} FROM scan_schedules ORDER BY created_at", SCHEDULE_COLUMNS);
+        self.query(&sql, [])
+    }
+
+    /// Enabled schedules whose next run is at or before `now` (RFC 3339)
+    pub fn list_due(&self, now: &str) -> OptioResult<Vec<ScanSchedule>> {
+        let sql = format!(
+            "SELECT {} FROM scan_schedules WHERE enabled = 1 AND next_run IS NOT NULL AND next_run <= ?1 ORDER BY next_run",
+            SCHEDULE_COLUMNS
+        );
+        self.query(&sql, params![now])
+    }
+
+    /// Enable or disable a schedule, setting when it next runs
+    pub fn set_enabled(&self, id: &str, enabled: bool, next_run: Option<&str>) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let updated = conn.execute(
+            "UPDATE scan_schedules SET enabled = ?2, next_run = ?3 WHERE id = ?1",
+            params![id, enabled, next_run],
+        )?;
+        Ok(updated > 0)
+    }
+
+    pub fn set_next_run(&self, id: &str, next_run: &str) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute("UPDATE scan_schedules SET next_run = ?2 WHERE id = ?1", params![id, next_run])?;
+        Ok(())
+    }
+
+    /// Record the outcome of a scheduled run
+    pub fn record_run(&self, id: &str, scan: &ScanJob) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            r#"UPDATE scan_schedules
+               SET last_run = ?2, last_status = ?3, last_error = ?4, last_scan_id = ?5
+               WHERE id = ?1"#,
+            params![
+                id,
+                scan.completed_at.as_ref().or(scan.started_at.as_ref()),
+                format!("{:?}", scan.status),
+                scan.error,
+                scan.id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM scan_schedules WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<ScanSchedule>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let schedules = stmt.query_map(query_params, |row| Ok(parse_schedule_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(schedules)
+    }
+}
+
+/// Scan profile repository
+pub struct ScanProfileRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ScanProfileRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ScanProfileRepository { db }
+    }
+
+    /// Insert or replace a profile
+    pub fn save(&self, profile: &ScanProfile) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO scan_profiles
+               (id, client_id, name, config, notes, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+            params![
+                profile.id,
+                profile.client_id,
+                profile.name,
+                serde_json::to_string(&profile.config)?,
+                profile.notes,
+                profile.created_at,
+                profile.updated_at,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<ScanProfile>> {
+        let sql = format!("SELECT {} FROM scan_profiles WHERE id = ?1", PROFILE_COLUMNS);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    /// Global profiles and, given a client, that client's own, by name
+    pub fn list(&self, client_id: Option<&str>) -> OptioResult<Vec<ScanProfile>> {
+        let sql = format!(
+            "SELECT {} FROM scan_profiles WHERE client_id IS NULL OR client_id = ?1 ORDER BY name COLLATE NOCASE",
+            PROFILE_COLUMNS
+        );
+        self.query(&sql, params![client_id])
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM scan_profiles WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<ScanProfile>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let profiles = stmt.query_map(query_params, |row| Ok(parse_profile_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(profiles)
+    }
+}
+
+/// Scan repository
+pub struct ScanRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ScanRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ScanRepository { db }
+    }
+
+    /// Insert a scan or update its record, keeping any stored results
+    pub fn save(&self, scan: &ScanJob) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let totals = scan.totals.as_ref();
+
+        conn.execute(
+            r#"INSERT INTO scans
+               (id, client_id, name, config, status, created_at, started_at, completed_at, error,
+                progress, verification, import, hosts_scanned, hosts_up, open_ports, duration_seconds)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+               ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name, config = excluded.config, status = excluded.status,
+                started_at = excluded.started_at, completed_at = excluded.completed_at,
+                error = excluded.error, progress = excluded.progress,
+                verification = excluded.verification, import = excluded.import,
+                hosts_scanned = excluded.hosts_scanned, hosts_up = excluded.hosts_up,
+                open_ports = excluded.open_ports, duration_seconds = excluded.duration_seconds"#,
+            params![
+                scan.id,
+                scan.client_id,
+                scan.name,
+                serde_json::to_string(&scan.config)?,
+                format!("{:?}", scan.status),
+                scan.created_at,
+                scan.started_at,
+                scan.completed_at,
+                scan.error,
+                scan.progress,
+                scan.verification.as_ref().map(serde_json::to_string).transpose()?,
+                scan.import.as_ref().map(serde_json::to_string).transpose()?,
+                totals.map(|t| t.hosts_scanned),
+                totals.map(|t| t.hosts_up),
+                totals.map(|t| t.open_ports),
+                totals.map(|t| t.duration_seconds),
+            ],
+        )?;
+
+        if scan.warnings.is_empty() {
+            conn.execute("DELETE FROM scan_warnings WHERE scan_id = ?1", params![scan.id])?;
+        } else {
+            conn.execute(
+                "INSERT OR REPLACE INTO scan_warnings (scan_id, warnings) VALUES (?1, ?2)",
+                params![scan.id, serde_json::to_string(&scan.warnings)?],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<ScanJob>> {
+        let sql = format!("SELECT {} FROM {} WHERE id = ?1", SCAN_COLUMNS, SCAN_FROM);
+        Ok(self.query(&sql, params![id])?.pop())
+    }
+
+    /// A client's scans, newest first (records only, never the results)
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<ScanJob>> {
+        let sql = format!("SELECT {} FROM {} WHERE client_id = ?1 ORDER BY created_at DESC", SCAN_COLUMNS, SCAN_FROM);
+        self.query(&sql, params![client_id])
+    }
+
+    /// When each client's latest completed scan finished, for clients with any
+    pub fn last_completed_by_client(&self) -> OptioResult<HashMap<String, String>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        // With MAX(), SQLite takes the bare column from the row holding the maximum
+        let mut stmt = conn.prepare(
+            "SELECT client_id, COALESCE(completed_at, created_at), MAX(julianday(COALESCE(completed_at, created_at)))
+             FROM scans WHERE status = 'Completed'
+             GROUP BY client_id",
+        )?;
+        let last = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<_, _>>()?;
+
+        Ok(last)
+    }
+
+    /// Store a scan's parsed results and, if given, its original Nmap XML
+    ///
+    /// Also updates the counts on the scan record.
+    pub fn save_results(&self, scan_id: &str, results: &ScanResults, raw_xml: Option<&str>) -> OptioResult<()> {
+        let results_json = serde_json::to_string(results)?;
+        let compressed = raw_xml.map(compress).transpose()?;
+        let totals = ScanTotals::from_results(results);
+
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            r#"INSERT OR REPLACE INTO scan_results (scan_id, results, raw_xml, raw_xml_size)
+               VALUES (?1, ?2, ?3, ?4)"#,
+            params![scan_id, results_json, compressed, raw_xml.map(|x| x.len() as i64)],
+        )?;
+        tx.execute(
+            r#"UPDATE scans SET hosts_scanned = ?2, hosts_up = ?3, open_ports = ?4, duration_seconds = ?5
+               WHERE id = ?1"#,
+            params![scan_id, totals.hosts_scanned, totals.hosts_up, totals.open_ports, totals.duration_seconds],
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn get_results(&self, scan_id: &str) -> OptioResult<Option<ScanResults>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let json: Option<String> = conn
+            .query_row("SELECT results FROM scan_results WHERE scan_id = ?1", params![scan_id], |row| row.get(0))
+            .optional()?;
+
+        Ok(json.map(|j| serde_json::from_str(&j)).transpose()?)
+    }
+
+    /// The original Nmap XML, decompressed
+    pub fn get_raw_xml(&self, scan_id: &str) -> OptioResult<Option<String>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let compressed: Option<Vec<u8>> = conn
+            .query_row("SELECT raw_xml FROM scan_results WHERE scan_id = ?1", params![scan_id], |row| row.get(0))
+            .optional()?
+            .flatten();
+        drop(conn);
+
+        compressed.map(|bytes| decompress(&bytes)).transpose()
+    }
+
+    pub fn has_raw_xml(&self, scan_id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM scan_results WHERE scan_id = ?1 AND raw_xml IS NOT NULL",
+            params![scan_id],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Delete a scan and its stored results
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM scan_results WHERE scan_id = ?1", params![id])?;
+        tx.execute("DELETE FROM scan_batches WHERE scan_id = ?1", params![id])?;
+        let deleted = tx.execute("DELETE FROM scans WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(deleted > 0)
+    }
+
+    /// A batched scan's batches in order; empty until the scan is planned
+    pub fn list_batches(&self, scan_id: &str) -> OptioResult<Vec<ScanBatch>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT scan_id, batch_index, targets, status, results, error, completed_at
+             FROM scan_batches WHERE scan_id = ?1 ORDER BY batch_index",
+        )?;
+        let batches = stmt.query_map(params![scan_id], |row| Ok(parse_batch_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(batches)
+    }
+
+    /// Insert or update batches
+    pub fn save_batches(&self, batches: &[ScanBatch]) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        for batch in batches {
+            tx.execute(
+                r#"INSERT OR REPLACE INTO scan_batches
+                   (scan_id, batch_index, targets, status, results, error, completed_at)
+                   VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+                params![
+                    batch.scan_id,
+                    batch.index,
+                    serde_json::to_string(&batch.targets)?,
+                    format!("{:?}", batch.status),
+                    batch.results.as_ref().map(serde_json::to_string).transpose()?,
+                    batch.error,
+                    batch.completed_at,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<ScanJob>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let scans = stmt.query_map(query_params, |row| Ok(parse_scan_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(scans)
+    }
+}
+
+/// Vulnerability repository: imported CVEs and per-asset matches
+pub struct VulnerabilityRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> VulnerabilityRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        VulnerabilityRepository { db }
+    }
+
+    /// Store imported CVEs, replacing any with the same ID
+    pub fn save_entries(&self, entries: &[VulnEntry]) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+
+        for entry in entries {
+            tx.execute(
+                r#"INSERT OR REPLACE INTO vuln_entries (cve_id, summary, cvss_score, affected, imported_at)
+                   VALUES (?1, ?2, ?3, ?4, ?5)"#,
+                params![
+                    entry.cve_id,
+                    entry.summary,
+                    entry.cvss_score,
+                    serde_json::to_string(&entry.affected)?,
+                    now,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn list_entries(&self) -> OptioResult<Vec<VulnEntry>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT cve_id, summary, cvss_score, affected FROM vuln_entries ORDER BY cve_id"
+        )?;
+
+        let entries = stmt.query_map([], |row| Ok(parse_vuln_entry_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+
+    /// Store KEV catalog entries, replacing any with the same ID
+    pub fn save_known_exploited(&self, entries: &[KnownExploitedVuln]) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+
+        for entry in entries {
+            tx.execute(
+                r#"INSERT OR REPLACE INTO known_exploited_vulns
+                   (cve_id, vendor_project, product, name, date_added, due_date, ransomware, imported_at)
+                   VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+                params![
+                    entry.cve_id,
+                    entry.vendor_project,
+                    entry.product,
+                    entry.name,
+                    entry.date_added,
+                    entry.due_date,
+                    entry.ransomware,
+                    now,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// IDs of the CVEs in the imported KEV catalog
+    pub fn known_exploited_ids(&self) -> OptioResult<Vec<String>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare("SELECT cve_id FROM known_exploited_vulns ORDER BY cve_id")?;
+        let ids = stmt.query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// Replace an asset's matches with the results of a new enrichment
+    pub fn replace_asset_matches(&self, asset_id: &str, matches: &[VulnerabilityMatch]) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM asset_vulnerabilities WHERE asset_id = ?1", params![asset_id])?;
+
+        for m in matches {
+            tx.execute(
+                r#"INSERT OR REPLACE INTO asset_vulnerabilities
+                   (asset_id, cve_id, port, protocol, product, version, cvss_score, summary,
+                    matched_range, matched_at)
+                   VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#,
+                params![
+                    asset_id,
+                    m.cve_id,
+                    m.port,
+                    format!("{:?}", m.protocol),
+                    m.product,
+                    m.version,
+                    m.cvss_score,
+                    m.summary,
+                    m.matched_range,
+                    m.matched_at,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// An asset's matches, highest CVSS first
+    pub fn list_by_asset(&self, asset_id: &str) -> OptioResult<Vec<VulnerabilityMatch>> {
+        self.query(
+            &format!("{} WHERE v.asset_id = ?1 ORDER BY v.cvss_score DESC, v.cve_id", VULN_MATCH_SELECT),
+            params![asset_id],
+        )
+    }
+
+    /// Matches across a client's assets, highest CVSS first
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<VulnerabilityMatch>> {
+        self.query(
+            &format!("{} WHERE a.client_id = ?1 ORDER BY v.cvss_score DESC, a.ip_address, v.cve_id", VULN_MATCH_SELECT),
+            params![client_id],
+        )
+    }
+
+    fn query<P: rusqlite::Params>(&self, sql: &str, query_params: P) -> OptioResult<Vec<VulnerabilityMatch>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(sql)?;
+        let matches = stmt.query_map(query_params, |row| Ok(parse_vuln_match_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(matches)
+    }
+}
+
+// KEV status is looked up rather than stored, so matches made before a
+// catalog import are flagged too
+const VULN_MATCH_SELECT: &str = "SELECT v.asset_id, a.name, a.ip_address, v.cve_id, v.port, v.protocol, \
+    v.product, v.version, v.cvss_score, v.summary, v.matched_range, v.matched_at, \
+    EXISTS(SELECT 1 FROM known_exploited_vulns k WHERE k.cve_id = v.cve_id) \
+    FROM asset_vulnerabilities v JOIN assets a ON a.id = v.asset_id";
+
+const SCAN_COLUMNS: &str = "id, client_id, name, config, status, created_at, started_at, completed_at, error, \
+    progress, verification, import, hosts_scanned, hosts_up, open_ports, duration_seconds, warnings, \
+    (SELECT COUNT(*) FROM scan_batches b WHERE b.scan_id = scans.id AND b.status = 'Completed'), \
+    (SELECT COUNT(*) FROM scan_batches b WHERE b.scan_id = scans.id)";
+const SCAN_FROM: &str = "scans LEFT JOIN scan_warnings ON scan_warnings.scan_id = scans.id";
+
+/// Network finding repository
+pub struct NetworkFindingRepository<'a> {
+    db: &'a Database,
+}
+
+const NETWORK_FINDING_COLUMNS: &str = "id, client_id, rule_id, host, port, service, title, severity, status, \
+     evidence, notes, detected_at, updated_at, verification_scan_id";
+
+impl<'a> NetworkFindingRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        NetworkFindingRepository { db }
+    }
+
+    /// Record newly detected findings, returning those recorded
+    ///
+    /// A finding whose host, port and rule already have an open finding for
+    /// the client is not raised twice.
+    pub fn create_new(&self, findings: Vec<NetworkFinding>) -> OptioResult<Vec<NetworkFinding>> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        let mut created = Vec::new();
+        for finding in findings {
+            let open: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM network_findings
+                 WHERE client_id = ?1 AND rule_id = ?2 AND host = ?3 AND port = ?4 AND status = 'Open')",
+                params![finding.client_id, finding.rule_id, finding.host, finding.port],
+                |row| row.get(0),
+            )?;
+            if !open {
+                write_network_finding(&tx, &finding)?;
+                created.push(finding);
+            }
+        }
+
+        tx.commit()?;
+        Ok(created)
+    }
+
+    /// Store findings' updated status, notes and evidence
+    pub fn save_all(&self, findings: &[NetworkFinding]) -> OptioResult<()> {
+        let mut conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        for finding in findings {
+            write_network_finding(&tx, finding)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// A client's findings in the order they were detected
+    pub fn list_by_client(&self, client_id: &str) -> OptioResult<Vec<NetworkFinding>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM network_findings WHERE client_id = ?1 ORDER BY detected_at, rowid",
+            NETWORK_FINDING_COLUMNS
+        ))?;
+        let findings = stmt.query_map(params![client_id], |row| Ok(parse_network_finding_row(row)))?
+            .filter_map(|r| r.ok())
+            .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(findings)
+    }
+
+    /// Count of findings not yet remediated, by client and severity
+    pub fn count_unremediated_by_client(&self) -> OptioResult<HashMap<String, HashMap<Criticality, usize>>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT client_id, severity, COUNT(*) FROM network_findings
+             WHERE status != 'Remediated'
+             GROUP BY client_id, severity",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?))
+        })?;
+
+        let mut counts: HashMap<String, HashMap<Criticality, usize>> = HashMap::new();
+        for row in rows {
+            let (client_id, severity, count) = row?;
+            counts.entry(client_id).or_default().insert(parse_criticality(&severity)?, count as usize);
+        }
+        Ok(counts)
+    }
+}
+
+/// Insert or replace one finding
+fn write_network_finding(conn: &rusqlite::Connection, finding: &NetworkFinding) -> OptioResult<()> {
+    conn.execute(
+        &format!(
+            "INSERT OR REPLACE INTO network_findings ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            NETWORK_FINDING_COLUMNS
+        ),
+        params![
+            finding.id,
+            finding.client_id,
+            finding.rule_id,
+            finding.host,
+            finding.port,
+            finding.service,
+            finding.title,
+            format!("{:?}", finding.severity),
+            format!("{:?}", finding.status),
+            serde_json::to_string(&finding.evidence)?,
+            finding.notes,
+            finding.detected_at,
+            finding.updated_at,
+            finding.verification_scan_id,
+        ],
+    )?;
+    Ok(())
+}
+
+fn compress(text: &str) -> OptioResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(text.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+fn decompress(bytes: &[u8]) -> OptioResult<String> {
+    let mut text = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut text)?;
+    Ok(text)
+}
+
+// Helper functions for parsing rows
+
+const ASSET_COLUMNS: &str = "id, client_id, name, ip_address, mac_address, category, operating_system, \
+    criticality, status, location, owner, description, tags, scan_ids, metadata, first_seen, last_seen";
+
+fn parse_asset_row(row: &rusqlite::Row) -> OptioResult<Asset> {
+    let category_str: String = row.get(5)?;
+    let criticality_str: String = row.get(7)?;
+    let status_str: String = row.get(8)?;
+    let tags_json: String = row.get(12)?;
+    let scan_ids_json: String = row.get(13)?;
+    let metadata_json: Option<String> = row.get(14)?;
+
+    Ok(Asset {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        name: row.get(2)?,
+        ip_address: row.get(3)?,
+        mac_address: row.get(4)?,
+        category: parse_asset_category(&category_str)?,
+        operating_system: row.get(6)?,
+        criticality: parse_criticality(&criticality_str)?,
+        status: parse_asset_status(&status_str)?,
+        location: row.get(9)?,
+        owner: row.get(10)?,
+        description: row.get(11)?,
+        services: vec![],
+        tags: serde_json::from_str(&tags_json)?,
+        first_seen: row.get(15)?,
+        last_seen: row.get(16)?,
+        scan_ids: serde_json::from_str(&scan_ids_json)?,
+        metadata: metadata_json.map(|m| serde_json::from_str(&m)).transpose()?,
+    })
+}
+
+fn parse_view_row(row: &rusqlite::Row) -> OptioResult<AssetView> {
+    let filter_json: String = row.get(3)?;
+
+    Ok(AssetView {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        name: row.get(2)?,
+        filter: serde_json::from_str(&filter_json)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn parse_service_row(row: &rusqlite::Row) -> OptioResult<AssetService> {
+    let protocol_str: String = row.get(1)?;
+    let state_str: String = row.get(4)?;
+
+    Ok(AssetService {
+        port: row.get(0)?,
+        protocol: parse_protocol(&protocol_str)?,
+        name: row.get(2)?,
+        version: row.get(3)?,
+        state: parse_port_state(&state_str)?,
+    })
+}
+
+fn parse_vuln_entry_row(row: &rusqlite::Row) -> OptioResult<VulnEntry> {
+    let affected_json: String = row.get(3)?;
+
+    Ok(VulnEntry {
+        cve_id: row.get(0)?,
+        summary: row.get(1)?,
+        cvss_score: row.get(2)?,
+        affected: serde_json::from_str(&affected_json)?,
+    })
+}
+
+fn parse_vuln_match_row(row: &rusqlite::Row) -> OptioResult<VulnerabilityMatch> {
+    let protocol_str: String = row.get(5)?;
+    let cvss_score: f64 = row.get(8)?;
+
+    Ok(VulnerabilityMatch {
+        asset_id: row.get(0)?,
+        asset_name: row.get(1)?,
+        ip_address: row.get(2)?,
+        cve_id: row.get(3)?,
+        port: row.get(4)?,
+        protocol: parse_protocol(&protocol_str)?,
+        product: row.get(6)?,
+        version: row.get(7)?,
+        cvss_score,
+        severity: cvss_severity(cvss_score),
+        summary: row.get(9)?,
+        matched_range: row.get(10)?,
+        matched_at: row.get(11)?,
+        kev: row.get(12)?,
+    })
+}
+
+fn parse_change_row(row: &rusqlite::Row) -> OptioResult<AssetChange> {
+    let change_type_str: String = row.get(3)?;
+
+    Ok(AssetChange {
+        id: row.get(0)?,
+        asset_id: row.get(1)?,
+        scan_id: row.get(2)?,
+        change_type: parse_asset_change_type(&change_type_str)?,
+        old_value: row.get(4)?,
+        new_value: row.get(5)?,
+        changed_at: row.get(6)?,
+    })
+}
+
+const SCHEDULE_COLUMNS: &str = "id, client_id, name, config, frequency, enabled, next_run, last_run, \
+    last_status, last_error, last_scan_id, created_at, missed_runs";
+
+fn parse_schedule_row(row: &rusqlite::Row) -> OptioResult<ScanSchedule> {
+    let config_json: String = row.get(3)?;
+    let frequency_json: String = row.get(4)?;
+    let last_status_str: Option<String> = row.get(8)?;
+    let missed_runs_str: String = row.get(12)?;
+
+    Ok(ScanSchedule {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        name: row.get(2)?,
+        config: serde_json::from_str(&config_json)?,
+        frequency: serde_json::from_str(&frequency_json)?,
+        enabled: row.get(5)?,
+        missed_runs: parse_missed_run_policy(&missed_runs_str)?,
+        next_run: row.get(6)?,
+        last_run: row.get(7)?,
+        last_status: last_status_str.as_deref().map(parse_scan_status).transpose()?,
+        last_error: row.get(9)?,
+        last_scan_id: row.get(10)?,
+        created_at: row.get(11)?,
+    })
+}
+
+const PROFILE_COLUMNS: &str = "id, client_id, name, config, notes, created_at, updated_at";
+
+fn parse_profile_row(row: &rusqlite::Row) -> OptioResult<ScanProfile> {
+    let config_json: String = row.get(3)?;
+
+    Ok(ScanProfile {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        name: row.get(2)?,
+        config: serde_json::from_str(&config_json)?,
+        notes: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+fn parse_scan_row(row: &rusqlite::Row) -> OptioResult<ScanJob> {
+    let config_json: String = row.get(3)?;
+    let status_str: String = row.get(4)?;
+    let verification_json: Option<String> = row.get(10)?;
+    let import_json: Option<String> = row.get(11)?;
+    let hosts_scanned: Option<u32> = row.get(12)?;
+    let warnings_json: Option<String> = row.get(16)?;
+
+    Ok(ScanJob {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        name: row.get(2)?,
+        config: serde_json::from_str(&config_json)?,
+        status: parse_scan_status(&status_str)?,
+        created_at: row.get(5)?,
+        started_at: row.get(6)?,
+        completed_at: row.get(7)?,
+        error: row.get(8)?,
+        progress: row.get(9)?,
+        verification: verification_json.map(|j| serde_json::from_str(&j)).transpose()?,
+        import: import_json.map(|j| serde_json::from_str(&j)).transpose()?,
+        totals: match hosts_scanned {
+            Some(hosts_scanned) => Some(ScanTotals {
+                hosts_scanned,
+                hosts_up: row.get::<_, Option<u32>>(13)?.unwrap_or(0),
+                open_ports: row.get::<_, Option<u32>>(14)?.unwrap_or(0),
+                duration_seconds: row.get::<_, Option<f64>>(15)?.unwrap_or(0.0),
+            }),
+            None => None,
+        },
+        warnings: warnings_json.map(|j| serde_json::from_str(&j)).transpose()?.unwrap_or_default(),
+        batches: match row.get::<_, u32>(18)? {
+            0 => None,
+            total => Some(ScanBatchProgress { completed: row.get(17)?, total }),
+        },
+        live: None,
+    })
+}
+
+fn parse_network_finding_row(row: &rusqlite::Row) -> OptioResult<NetworkFinding> {
+    let severity_str: String = row.get(7)?;
+    let status_str: String = row.get(8)?;
+    let evidence_json: String = row.get(9)?;
+
+    Ok(NetworkFinding {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        rule_id: row.get(2)?,
+        host: row.get(3)?,
+        port: row.get(4)?,
+        service: row.get(5)?,
+        title: row.get(6)?,
+        severity: parse_criticality(&severity_str)?,
+        status: parse_finding_status(&status_str)?,
+        evidence: serde_json::from_str(&evidence_json)?,
+        notes: row.get(10)?,
+        detected_at: row.get(11)?,
+        updated_at: row.get(12)?,
+        verification_scan_id: row.get(13)?,
+    })
+}
+
+fn parse_batch_row(row: &rusqlite::Row) -> OptioResult<ScanBatch> {
+    let targets_json: String = row.get(2)?;
+    let status_str: String = row.get(3)?;
+    let results_json: Option<String> = row.get(4)?;
+
+    Ok(ScanBatch {
+        scan_id: row.get(0)?,
+        index: row.get(1)?,
+        targets: serde_json::from_str(&targets_json)?,
+        status: parse_scan_status(&status_str)?,
+        results: results_json.map(|j| serde_json::from_str(&j)).transpose()?,
+        error: row.get(5)?,
+        completed_at: row.get(6)?,
+    })
+}
+
+fn parse_scan_status(s: &str) -> OptioResult<ScanStatus> {
+    match s {
+        "Queued" => Ok(ScanStatus::Queued),
+        "Running" => Ok(ScanStatus::Running),
+        "Parsing" => Ok(ScanStatus::Parsing),
+        "Completed" => Ok(ScanStatus::Completed),
+        "Failed" => Ok(ScanStatus::Failed),
+        "Cancelled" => Ok(ScanStatus::Cancelled),
+        _ => Err(OptioError::Database(format!("Unknown scan status: {}", s))),
+    }
+}
+
+fn parse_finding_status(s: &str) -> OptioResult<FindingStatus> {
+    match s {
+        "Open" => Ok(FindingStatus::Open),
+        "Remediated" => Ok(FindingStatus::Remediated),
+        "Unverifiable" => Ok(FindingStatus::Unverifiable),
+        _ => Err(OptioError::Database(format!("Unknown finding status: {}", s))),
+    }
+}
+
+fn parse_missed_run_policy(s: &str) -> OptioResult<MissedRunPolicy> {
+    match s {
+        "RunOnce" => Ok(MissedRunPolicy::RunOnce),
+        "Skip" => Ok(MissedRunPolicy::Skip),
+        _ => Err(OptioError::Database(format!("Unknown missed run policy: {}", s))),
+    }
+}
+
+fn parse_asset_category(s: &str) -> OptioResult<AssetCategory> {
+    match s {
+        "Server" => Ok(AssetCategory::Server),
+        "Workstation" => Ok(AssetCategory::Workstation),
+        "NetworkDevice" => Ok(AssetCategory::NetworkDevice),
+        "SecurityDevice" => Ok(AssetCategory::SecurityDevice),
+        "Printer" => Ok(AssetCategory::Printer),
+        "IoT" => Ok(AssetCategory::IoT),
+        "Mobile" => Ok(AssetCategory::Mobile),
+        "Virtual" => Ok(AssetCategory::Virtual),
+        "Cloud" => Ok(AssetCategory::Cloud),
+        "Unknown" => Ok(AssetCategory::Unknown),
+        _ => Err(OptioError::Database(format!("Unknown asset category: {}", s))),
+    }
+}
+
+fn parse_service_policy_row(row: &rusqlite::Row) -> OptioResult<ServicePolicy> {
+    let severity_str: String = row.get(7)?;
+
+    Ok(ServicePolicy {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        ports: serde_json::from_str(&row.get::<_, String>(2)?)?,
+        services: serde_json::from_str(&row.get::<_, String>(3)?)?,
+        categories: serde_json::from_str(&row.get::<_, String>(4)?)?,
+        criticalities: serde_json::from_str(&row.get::<_, String>(5)?)?,
+        exempt_tags: serde_json::from_str(&row.get::<_, String>(6)?)?,
+        severity: parse_criticality(&severity_str)?,
+        message: row.get(8)?,
+        recommendation: row.get(9)?,
+        enabled: row.get(10)?,
+        built_in: false,
+    })
+}
+
+pub(crate) fn parse_criticality(s: &str) -> OptioResult<Criticality> {
+    match s {
+        "Critical" => Ok(Criticality::Critical),
+        "High" => Ok(Criticality::High),
+        "Medium" => Ok(Criticality::Medium),
+        "Low" => Ok(Criticality::Low),
+        "Informational" => Ok(Criticality::Informational),
+        _ => Err(OptioError::Database(format!("Unknown criticality: {}", s))),
+    }
+}
+
+fn parse_asset_status(s: &str) -> OptioResult<AssetStatus> {
+    match s {
+        "Active" => Ok(AssetStatus::Active),
+        "Inactive" => Ok(AssetStatus::Inactive),
+        "Decommissioned" => Ok(AssetStatus::Decommissioned),
+        "Pending" => Ok(AssetStatus::Pending),
+        "Maintenance" => Ok(AssetStatus::Maintenance),
+        _ => Err(OptioError::Database(format!("Unknown asset status: {}", s))),
+    }
+}
+
+fn parse_protocol(s: &str) -> OptioResult<Protocol> {
+    match s {
+        "Tcp" => Ok(Protocol::Tcp),
+        "Udp" => Ok(Protocol::Udp),
+        "Sctp" => Ok(Protocol::Sctp),
+        _ => Err(OptioError::Database(format!("Unknown protocol: {}", s))),
+    }
+}
+
+fn parse_port_state(s: &str) -> OptioResult<PortState> {
+    match s {
+        "Open" => Ok(PortState::Open),
+        "Closed" => Ok(PortState::Closed),
+        "Filtered" => Ok(PortState::Filtered),
+        "Unfiltered" => Ok(PortState::Unfiltered),
+        "OpenFiltered" => Ok(PortState::OpenFiltered),
+        "ClosedFiltered" => Ok(PortState::ClosedFiltered),
+        _ => Err(OptioError::Database(format!("Unknown port state: {}", s))),
+    }
+}
+
+fn parse_asset_change_type(s: &str) -> OptioResult<AssetChangeType> {
+    match s {
+        "PortOpened" => Ok(AssetChangeType::PortOpened),
+        "PortClosed" => Ok(AssetChangeType::PortClosed),
+        "OsChanged" => Ok(AssetChangeType::OsChanged),
+        "HostnameChanged" => Ok(AssetChangeType::HostnameChanged),
+        _ => Err(OptioError::Database(format!("Unknown asset change type: {}", s))),
+    }
+}
@@ -5,8 +5,12 @@
 //! Handles command construction, execution, and result parsing.
 
 use super::models::*;
+use super::targets::{parse_target, split_target_list};
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
 use tokio::net::TcpStream;
@@ -14,25 +18,26 @@ use tokio::time::timeout;
 use uuid::Uuid;
 use futures::stream::{self, StreamExt};
 use ipnetwork::IpNetwork;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
 
 /// Check if Nmap is installed and available
-pub fn check_nmap_installed() -> Result<NmapInfo, String> {
-    let output = Command::new("nmap")
-        .arg("--version")
-        .output()
-        .map_err(|e| format!("Failed to execute nmap: {}. Is Nmap installed?", e))?;
-
-    if !output.status.success() {
-        return Err("Nmap command failed".to_string());
-    }
-
-    let version_output = String::from_utf8_lossy(&output.stdout);
-    let version = parse_nmap_version(&version_output);
+///
+/// A configured path takes precedence over PATH and the standard install
+/// locations (see [`locate_nmap`]).
+pub fn check_nmap_installed(configured: Option<&str>) -> Result<NmapInfo, String> {
+    let location = locate_nmap(configured).ok_or_else(|| {
+        "Nmap was not found on PATH or in its standard install locations. Install Nmap or set its path in the scanner settings.".to_string()
+    })?;
+    let version = validate_nmap_binary(&location.path)?;
 
     Ok(NmapInfo {
         installed: true,
-        version,
-        path: which_nmap(),
+        version: Some(version),
+        privileges: detect_scan_privileges(Some(&location.path)),
+        path: Some(location.path),
+        path_source: Some(location.source),
+        npcap_installed: npcap_installed(),
     })
 }
 
@@ -43,6 +48,481 @@ pub struct NmapInfo {
     pub installed: bool,
     pub version: Option<String>,
     pub path: Option<String>,
+    /// How `path` was found
+    pub path_source: Option<NmapSource>,
+    /// Whether Npcap (or WinPcap) is installed; None off Windows, where it isn't needed
+    pub npcap_installed: Option<bool>,
+    /// Whether scans needing raw packets can run from this session
+    pub privileges: ScanPrivileges,
+}
+
+/// How the Nmap binary was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NmapSource {
+    /// Path set by the user
+    Configured,
+    /// Found on PATH
+    Path,
+    /// Found in a standard install directory
+    KnownLocation,
+}
+
+/// The Nmap binary to run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NmapLocation {
+    pub path: String,
+    pub source: NmapSource,
+}
+
+#[cfg(windows)]
+const NMAP_BINARY: &str = "nmap.exe";
+#[cfg(not(windows))]
+const NMAP_BINARY: &str = "nmap";
+
+/// Find the Nmap binary: the configured path if it exists, then PATH, then
+/// the platform's standard install directories
+pub fn locate_nmap(configured: Option<&str>) -> Option<NmapLocation> {
+    locate_nmap_in(configured, std::env::var_os("PATH").as_deref(), &known_nmap_locations())
+}
+
+fn locate_nmap_in(
+    configured: Option<&str>,
+    path_var: Option<&std::ffi::OsStr>,
+    known: &[PathBuf],
+) -> Option<NmapLocation> {
+    let found = |path: &Path, source| NmapLocation { path: path.to_string_lossy().into_owned(), source };
+
+    if let Some(configured) = configured.map(str::trim).filter(|p| !p.is_empty()) {
+        if Path::new(configured).is_file() {
+            return Some(found(Path::new(configured), NmapSource::Configured));
+        }
+        tracing::warn!("Configured Nmap path {} no longer exists; searching PATH", configured);
+    }
+
+    let on_path = path_var
+        .into_iter()
+        .flat_map(std::env::split_paths)
+        .map(|dir| dir.join(NMAP_BINARY))
+        .find(|candidate| candidate.is_file());
+    if let Some(path) = on_path {
+        return Some(found(&path, NmapSource::Path));
+    }
+
+    known.iter().find(|p| p.is_file()).map(|p| found(p, NmapSource::KnownLocation))
+}
+
+/// Where the official installers and package managers put Nmap
+fn known_nmap_locations() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        ["ProgramFiles(x86)", "ProgramFiles"]
+            .iter()
+            .filter_map(std::env::var_os)
+            .map(|dir| PathBuf::from(dir).join("Nmap").join(NMAP_BINARY))
+            .chain([
+                PathBuf::from(r"C:\Program Files (x86)\Nmap\nmap.exe"),
+                PathBuf::from(r"C:\Program Files\Nmap\nmap.exe"),
+            ])
+            .collect()
+    } else if cfg!(target_os = "macos") {
+        ["/usr/local/bin/nmap", "/opt/homebrew/bin/nmap", "/opt/local/bin/nmap"].iter().map(PathBuf::from).collect()
+    } else {
+        ["/usr/bin/nmap", "/usr/local/bin/nmap", "/snap/bin/nmap"].iter().map(PathBuf::from).collect()
+    }
+}
+
+/// Check that `path` is an Nmap binary, returning its version
+pub fn validate_nmap_binary(path: &str) -> Result<String, String> {
+    if !Path::new(path).is_file() {
+        return Err(format!("{} is not a file", path));
+    }
+
+    let output = Command::new(path)
+        .arg("--version")
+        .output()
+        .map_err(|e| format!("Failed to execute {}: {}", path, e))?;
+    if !output.status.success() {
+        return Err(format!("{} --version failed with {}", path, output.status));
+    }
+
+    parse_nmap_version(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| format!("{} does not look like Nmap: no version in its --version output", path))
+}
+
+/// Whether Npcap (or WinPcap) is installed; None off Windows, where it isn't needed
+#[cfg(windows)]
+fn npcap_installed() -> Option<bool> {
+    let system32 = std::env::var("SystemRoot")
+        .map(|root| Path::new(&root).join("System32"))
+        .unwrap_or_else(|_| Path::new(r"C:\Windows\System32").to_path_buf());
+    Some(system32.join("Npcap").is_dir() || system32.join("wpcap.dll").is_file())
+}
+
+#[cfg(not(windows))]
+fn npcap_installed() -> Option<bool> {
+    None
+}
+
+/// Raw packet access available to Nmap from this session
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanPrivileges {
+    /// Running as root (Unix) or with an Administrator token (Windows)
+    pub elevated: bool,
+    /// The Nmap binary holds CAP_NET_RAW (Linux)
+    pub nmap_capabilities: bool,
+    /// A packet capture driver is installed (Npcap/WinPcap; always true off Windows)
+    pub packet_driver: bool,
+    /// How to get raw packet access, when it's missing
+    pub remedy: Option<String>,
+}
+
+impl ScanPrivileges {
+    /// Whether SYN, UDP and OS detection scans can run
+    pub fn raw_packets(&self) -> bool {
+        self.packet_driver && (self.elevated || self.nmap_capabilities)
+    }
+}
+
+/// Detect this session's raw packet access for the Nmap binary at `nmap`
+#[cfg(unix)]
+pub fn detect_scan_privileges(nmap: Option<&str>) -> ScanPrivileges {
+    let elevated = Command::new("id")
+        .arg("-u")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .is_some_and(|o| String::from_utf8_lossy(&o.stdout).trim() == "0");
+
+    // Nmap only uses file capabilities when told to with --privileged
+    let nmap_capabilities = cfg!(target_os = "linux")
+        && nmap.is_some_and(|path| {
+            Command::new("getcap")
+                .arg(path)
+                .output()
+                .ok()
+                .is_some_and(|o| String::from_utf8_lossy(&o.stdout).contains("cap_net_raw"))
+        });
+
+    let remedy = if elevated || nmap_capabilities {
+        None
+    } else if cfg!(target_os = "linux") {
+        Some(format!(
+            "Run Optio as root, or grant Nmap raw socket access with: sudo setcap cap_net_raw,cap_net_admin,cap_net_bind_service+eip {}",
+            nmap.unwrap_or("$(which nmap)")
+        ))
+    } else {
+        Some("Run Optio as root (sudo) to use SYN, UDP and OS detection scans".to_string())
+    };
+
+    ScanPrivileges { elevated, nmap_capabilities, packet_driver: true, remedy }
+}
+
+/// Detect this session's raw packet access for the Nmap binary at `nmap`
+#[cfg(windows)]
+pub fn detect_scan_privileges(_nmap: Option<&str>) -> ScanPrivileges {
+    // `net session` only succeeds from an elevated token
+    let elevated = Command::new("net")
+        .arg("session")
+        .output()
+        .is_ok_and(|o| o.status.success());
+
+    let packet_driver = npcap_installed().unwrap_or(false);
+
+    let remedy = match (elevated, packet_driver) {
+        (true, true) => None,
+        (false, true) => Some("Run Optio as Administrator to use SYN, UDP and OS detection scans".to_string()),
+        (_, false) => Some("Install Npcap (https://npcap.com) and run Optio as Administrator to use SYN, UDP and OS detection scans".to_string()),
+    };
+
+    ScanPrivileges { elevated, nmap_capabilities: false, packet_driver, remedy }
+}
+
+/// Nmap options that need raw packets and have no unprivileged equivalent
+const RAW_PACKET_ARGS: &[&str] = &[
+    "-sU", "-sA", "-sW", "-sM", "-sN", "-sF", "-sX", "-sO", "-sY", "-sZ", "-sI", "-O", "-A",
+];
+
+/// Fit a scan to the raw packet access available
+///
+/// With access, the config records how Nmap gets it. Without, SYN scans are
+/// downgraded to TCP connect scans (returning a warning to record on the
+/// job), and scans that can't run at all are refused with the fix.
+pub fn apply_scan_privileges(config: &mut ScanConfig, privileges: &ScanPrivileges) -> Result<Option<String>, String> {
+    if privileges.raw_packets() {
+        config.privilege_mode = if privileges.elevated {
+            ScanPrivilegeMode::Elevated
+        } else {
+            ScanPrivilegeMode::Capabilities
+        };
+        return Ok(None);
+    }
+
+    config.privilege_mode = ScanPrivilegeMode::Unprivileged;
+    let args = scan_type_args(config);
+    let remedy = privileges.remedy.clone().unwrap_or_default();
+
+    if let Some(arg) = args.iter().find(|a| RAW_PACKET_ARGS.contains(&a.as_str())) {
+        let what = match config.scan_type {
+            ScanType::Custom => format!("Nmap option {}", arg),
+            scan_type => scan_type.display_name().to_string(),
+        };
+        return Err(format!("{} needs raw packet access, which this session doesn't have. {}", what, remedy).trim_end().to_string());
+    }
+
+    if args.iter().any(|a| a == "-sS") {
+        return Ok(Some(format!(
+            "No raw packet access: SYN scan (-sS) downgraded to TCP connect scan (-sT), which is slower and shows up in target logs. {}",
+            remedy
+        ).trim_end().to_string()));
+    }
+
+    Ok(None)
+}
+
+/// Result of checking a custom scan's Nmap arguments
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomArgsValidation {
+    pub valid: bool,
+    /// The arguments as they will be passed to Nmap, denied options removed
+    pub normalized_args: Vec<String>,
+    /// Options that clash with ones Optio adds
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// A denied option, the shortest prefix of it Nmap accepts, whether it takes
+/// a value, and why it is denied
+type DeniedOption = (&'static str, usize, bool, &'static str);
+
+/// Options that read or write arbitrary files, or pick targets at random,
+/// with the shortest prefix Nmap reads as each one and whether it takes a
+/// value. Nmap also reads an unambiguous prefix of a long option, with one
+/// dash or two, so `-datad` is `--datadir`; two-letter options like `-oN`
+/// take their value stuck to them.
+const DENIED_NMAP_OPTIONS: &[DeniedOption] = &[
+    ("-oN", 2, true, "writes output to a file"),
+    ("-oG", 2, true, "writes output to a file"),
+    ("-oA", 2, true, "writes output to files"),
+    ("-oS", 2, true, "writes output to a file"),
+    ("-oM", 2, true, "writes output to a file"),
+    ("--append-output", 2, false, "appends to output files"),
+    ("--resume", 4, true, "reads a file"),
+    ("-iL", 2, true, "reads targets from a file"),
+    ("--excludefile", 8, true, "reads exclusions from a file"),
+    ("-iR", 2, true, "scans random hosts on the internet"),
+    ("--datadir", 5, true, "loads Nmap data files from another directory"),
+    ("--servicedb", 3, true, "loads a services file"),
+    ("--versiondb", 8, true, "loads a version probes file"),
+    ("--script-args-file", 12, true, "reads a file"),
+];
+
+/// An argument as Nmap reads a long option: its name, without dashes, and
+/// the value given with `=`, if any
+fn long_option(arg: &str) -> Option<(&str, Option<&str>)> {
+    let body = arg.strip_prefix("--").or_else(|| arg.strip_prefix('-'))?;
+    if body.is_empty() {
+        return None;
+    }
+    Some(match body.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (body, None),
+    })
+}
+
+/// The denied option an argument selects, and whether its value is the
+/// next argument
+fn denied_option(arg: &str) -> Option<(&'static DeniedOption, bool)> {
+    let (name, value) = long_option(arg)?;
+    DENIED_NMAP_OPTIONS.iter().find_map(|denied| {
+        let (option, min_prefix, takes_value, _) = denied;
+        let full = option.trim_start_matches('-');
+        let (matches, value_follows) = if full.len() == 2 {
+            (name.starts_with(full), name == full && value.is_none())
+        } else {
+            (name.len() >= *min_prefix && full.starts_with(name), value.is_none())
+        };
+        matches.then_some((denied, *takes_value && value_follows))
+    })
+}
+
+/// The value of a two-letter option such as `-oX`, given as `-oX value`,
+/// `-oXvalue` or `-oX=value`, with one dash or two, and whether it was the
+/// next argument
+fn short_option_value(arg: &str, option: &str, next: Option<&String>) -> Option<(String, bool)> {
+    let body = arg.strip_prefix("--").or_else(|| arg.strip_prefix('-'))?;
+    let rest = body.strip_prefix(option)?;
+    if rest.is_empty() {
+        return Some((next.cloned().unwrap_or_default(), next.is_some()));
+    }
+    Some((rest.strip_prefix('=').unwrap_or(rest).to_string(), false))
+}
+
+/// The value of a long option such as `--script`, with one dash or two, and
+/// whether it was the next argument
+fn long_option_value(arg: &str, option: &str, next: Option<&String>) -> Option<(String, bool)> {
+    match long_option(arg)? {
+        (name, _) if name != option => None,
+        (_, Some(value)) => Some((value.to_string(), false)),
+        (_, None) => Some((next.cloned().unwrap_or_default(), next.is_some())),
+    }
+}
+
+/// Split an argument string like a shell: whitespace separates arguments,
+/// quotes group them and a backslash escapes the next character
+pub fn split_custom_args(args: &str) -> Result<Vec<String>, String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = args.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                current.push(chars.next().ok_or_else(|| "Trailing backslash in Nmap arguments".to_string())?);
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    parts.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+                continue;
+            }
+            (None, c) => current.push(c),
+        }
+        in_arg = true;
+    }
+
+    if let Some(q) = quote {
+        return Err(format!("Unclosed {} quote in Nmap arguments", q));
+    }
+    if in_arg {
+        parts.push(current);
+    }
+    Ok(parts)
+}
+
+/// Check custom Nmap arguments before a scan runs
+///
+/// Options that touch the filesystem (output files, target lists, data
+/// directories, scripts outside Nmap's script directory) or scan random
+/// hosts are errors and are removed from the normalized arguments. `-oX -`
+/// is dropped with a warning since Optio already reads XML from stdout,
+/// and `--exclude` is kept with a warning that the scan's exclusions
+/// replace it. Nmap allows only one `-p`.
+pub fn validate_custom_args(args: &str) -> CustomArgsValidation {
+    let mut normalized_args = Vec::new();
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+
+    let parsed = match split_custom_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            errors.push(e);
+            args.split_whitespace().map(String::from).collect()
+        }
+    };
+
+    let mut port_flags = 0;
+    let mut i = 0;
+    while i < parsed.len() {
+        let arg = &parsed[i];
+        let next = parsed.get(i + 1);
+        i += 1;
+
+        if let Some(((option, _, _, why), value_follows)) = denied_option(arg) {
+            if value_follows && next.is_some() {
+                i += 1;
+            }
+            errors.push(format!("{} is not allowed: it {}", option, why));
+            continue;
+        }
+
+        if let Some((value, consumed)) = short_option_value(arg, "oX", next) {
+            if consumed {
+                i += 1;
+            }
+            if value == "-" {
+                warnings.push("-oX - is already added by Optio and was removed".to_string());
+            } else {
+                errors.push("-oX is not allowed: it writes output to a file".to_string());
+            }
+            continue;
+        }
+
+        if let Some((value, consumed)) = long_option_value(arg, "script", next) {
+            let outside = value.split(',').find(|script| {
+                let script = script.trim();
+                script.contains('/') || script.contains('\\') || script.starts_with('.') || script.starts_with('~')
+            });
+            if let Some(script) = outside {
+                if consumed {
+                    i += 1;
+                }
+                errors.push(format!(
+                    "--script {} is not allowed: scripts must come from Nmap's script directory",
+                    script.trim()
+                ));
+                continue;
+            }
+        }
+
+        if arg.starts_with("-p") && !arg.starts_with("-P") {
+            port_flags += 1;
+            if port_flags == 2 {
+                errors.push("-p is given more than once; Nmap allows one -p, with ranges separated by commas".to_string());
+            }
+        }
+
+        if arg == "--exclude" || arg.starts_with("--exclude=") {
+            warnings.push("--exclude is replaced by the scan's exclusions when it has any".to_string());
+        }
+
+        normalized_args.push(arg.clone());
+    }
+
+    CustomArgsValidation {
+        valid: errors.is_empty(),
+        normalized_args,
+        warnings,
+        errors,
+    }
+}
+
+/// Check a custom scan's arguments, returning any warnings
+///
+/// Other scan types have nothing to check.
+pub fn check_custom_args(config: &ScanConfig) -> Result<Vec<String>, String> {
+    match (config.scan_type, config.custom_args.as_deref()) {
+        (ScanType::Custom, Some(custom)) => {
+            let validation = validate_custom_args(custom);
+            if validation.valid {
+                Ok(validation.warnings)
+            } else {
+                Err(validation.errors.join("; "))
+            }
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// Arguments selecting the scan technique, before privilege adjustments
+///
+/// Custom arguments go through [`validate_custom_args`], so denied options
+/// are dropped even from configurations saved before validation existed.
+fn scan_type_args(config: &ScanConfig) -> Vec<String> {
+    match config.scan_type {
+        ScanType::Custom => config.custom_args
+            .as_deref()
+            .map(|custom| validate_custom_args(custom).normalized_args)
+            .unwrap_or_default(),
+        scan_type => scan_type.to_nmap_args().into_iter().map(|s| s.to_string()).collect(),
+    }
 }
 
 fn parse_nmap_version(output: &str) -> Option<String> {
@@ -61,33 +541,22 @@ fn parse_nmap_version(output: &str) -> Option<String> {
         })
 }
 
-fn which_nmap() -> Option<String> {
-    Command::new("which")
-        .arg("nmap")
-        .output()
-        .ok()
-        .filter(|o| o.status.success())
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-}
+/// How often Nmap reports progress during a scan
+pub const NMAP_STATS_INTERVAL: &str = "5s";
 
 /// Build Nmap command from scan configuration
 pub fn build_nmap_command(config: &ScanConfig) -> Vec<String> {
     let mut args = Vec::new();
 
-    // Add scan type arguments
-    match config.scan_type {
-        ScanType::Custom => {
-            if let Some(ref custom) = config.custom_args {
-                args.extend(custom.split_whitespace().map(|s| s.to_string()));
-            }
+    // Add scan type arguments, adjusted to the raw packet access available
+    match config.privilege_mode {
+        ScanPrivilegeMode::Elevated => args.extend(scan_type_args(config)),
+        ScanPrivilegeMode::Capabilities => {
+            args.push("--privileged".to_string());
+            args.extend(scan_type_args(config));
         }
-        _ => {
-            args.extend(
-                config.scan_type
-                    .to_nmap_args()
-                    .into_iter()
-                    .map(|s| s.to_string())
-            );
+        ScanPrivilegeMode::Unprivileged => {
+            args.extend(scan_type_args(config).into_iter().map(|a| if a == "-sS" { "-sT".to_string() } else { a }));
         }
     }
 
@@ -96,6 +565,12 @@ pub fn build_nmap_command(config: &ScanConfig) -> Vec<String> {
         args.push("-T5".to_string());
     }
 
+    // Cap the packet rate, to stay under the client's IDS thresholds
+    if let Some(rate) = config.max_packets_per_second {
+        args.push("--max-rate".to_string());
+        args.push(rate.to_string());
+    }
+
     // Skip host discovery if requested
     if config.skip_discovery {
         args.push("-Pn".to_string());
@@ -115,16 +590,128 @@ pub fn build_nmap_command(config: &ScanConfig) -> Vec<String> {
         }
     }
 
+    // Progress reports, written into the XML as <taskprogress> elements
+    args.push("--stats-every".to_string());
+    args.push(NMAP_STATS_INTERVAL.to_string());
+
     // Add output format for XML parsing
     args.push("-oX".to_string());
     args.push("-".to_string()); // Output to stdout
 
-    // Add targets
+    // Add targets, after "--" so none can be read as an option
+    args.push("--".to_string());
     args.extend(config.targets.clone());
 
     args
 }
 
+/// Run Nmap for a scan configuration and parse its XML output
+///
+/// The XML is read as Nmap writes it, and `on_progress` is called whenever
+/// a progress report or a finished host comes through; its percentage is
+/// that of Nmap's current phase. Dropping the future kills Nmap. Returns
+/// the parsed results together with the raw XML, which is kept on the scan
+/// job so later scans can be diffed against it.
+pub async fn run_nmap_scan(
+    nmap: &str,
+    config: &ScanConfig,
+    mut on_progress: impl FnMut(&ScanLiveProgress),
+) -> Result<(ScanResults, String), String> {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    if config.targets.is_empty() {
+        return Err("No scan targets specified".to_string());
+    }
+
+    let mut child = tokio::process::Command::new(nmap)
+        .args(build_nmap_command(config))
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => format!("Nmap was not found at {}", nmap),
+            _ => format!("Failed to execute nmap: {}", e),
+        })?;
+
+    // Drain stderr alongside stdout so a chatty Nmap can't block on a full pipe
+    let mut stderr = child.stderr.take().ok_or("Failed to capture Nmap's error output")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text).await;
+        text
+    });
+
+    let stdout = child.stdout.take().ok_or("Failed to capture Nmap's output")?;
+    let mut lines = tokio::io::BufReader::new(stdout).lines();
+    let mut xml = String::new();
+    let mut progress = ScanLiveProgress::default();
+    while let Some(line) = lines.next_line().await.map_err(|e| format!("Failed to read Nmap output: {}", e))? {
+        if track_nmap_progress(&line, &mut progress) {
+            on_progress(&progress);
+        }
+        xml.push_str(&line);
+        xml.push('\n');
+    }
+
+    let status = child.wait().await.map_err(|e| format!("Failed to execute nmap: {}", e))?;
+    let stderr = stderr_task.await.unwrap_or_default();
+    if !status.success() {
+        return Err(format!("Nmap exited with {}: {}", status, stderr.trim()));
+    }
+
+    let results = parse_nmap_xml(&xml)?;
+    Ok((results, xml))
+}
+
+/// Update progress from one line of Nmap's XML output; true if it changed
+///
+/// Nmap writes `<taskbegin>`, `<taskprogress>` and `<taskend>` for each
+/// phase of the scan, and each `<host>` once it is done with it, each on
+/// a line of its own.
+pub fn track_nmap_progress(line: &str, progress: &mut ScanLiveProgress) -> bool {
+    let mut reader = Reader::from_str(line);
+    reader.config_mut().allow_unmatched_ends = true;
+    let mut changed = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let attrs = match xml_attrs(&e) {
+                    Ok(attrs) => attrs,
+                    Err(_) => break,
+                };
+                match e.name().as_ref() {
+                    b"taskbegin" => {
+                        progress.task = attrs.get("task").cloned();
+                        progress.percent_done = 0.0;
+                        progress.eta_seconds = None;
+                    }
+                    b"taskprogress" => {
+                        progress.task = attrs.get("task").cloned();
+                        progress.percent_done = attrs.get("percent").and_then(|v| v.parse().ok()).unwrap_or(0.0);
+                        progress.eta_seconds = attrs.get("remaining").and_then(|v| v.parse().ok());
+                    }
+                    b"taskend" => {
+                        progress.percent_done = 100.0;
+                        progress.eta_seconds = Some(0);
+                    }
+                    _ => continue,
+                }
+                changed = true;
+            }
+            Ok(Event::End(e)) if e.name().as_ref() == b"host" => {
+                progress.hosts_completed += 1;
+                changed = true;
+            }
+            // Elements split across lines are left to the full parse
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+    changed
+}
+
 /// Get available scan types with their descriptions
 pub fn get_scan_types() -> Vec<ScanTypeInfo> {
     vec![
@@ -198,335 +785,643 @@ pub struct ScanTypeInfo {
 }
 
 /// Parse Nmap XML output into structured results
+///
+/// Streams the document with quick-xml, so attribute order and quoting do
+/// not matter and large scans are never held as a DOM. Totals and timing
+/// come from `<runstats>`; `<extraports>` summaries are recorded per host
+/// rather than expanded into individual ports.
 pub fn parse_nmap_xml(xml: &str) -> Result<ScanResults, String> {
-    // Simple XML parsing - in production would use a proper XML parser
-    // For now, we'll create a demo result structure
-
-    let scan_id = Uuid::new_v4().to_string();
-    let now = chrono::Utc::now().to_rfc3339();
-
-    // Extract basic info from XML
-    let hosts = parse_hosts_from_xml(xml);
-    let hosts_up = hosts.iter().filter(|h| h.status == "up").count() as u32;
-
-    // Try to extract nmap version
-    let nmap_version = extract_xml_attr(xml, "scanner", "version");
-
-    // Extract timing info
-    let start_time = extract_xml_attr(xml, "nmaprun", "startstr")
-        .unwrap_or_else(|| now.clone());
-
-    Ok(ScanResults {
-        scan_id,
-        hosts,
-        hosts_scanned: hosts_up, // Simplified
-        hosts_up,
-        duration_seconds: 0.0, // Would parse from XML
-        nmap_version,
-        command_line: extract_xml_attr(xml, "nmaprun", "args").unwrap_or_default(),
-        start_time,
-        end_time: now,
-    })
+    parse_nmap_reader(xml.as_bytes()).map(|(results, _)| results)
 }
 
-fn parse_hosts_from_xml(xml: &str) -> Vec<DiscoveredHost> {
-    let mut hosts = Vec::new();
-
-    // Simple regex-free parsing for demonstration
-    // In production, use quick-xml or roxmltree
-
-    let mut current_pos = 0;
-    while let Some(host_start) = xml[current_pos..].find("<host") {
-        let abs_start = current_pos + host_start;
-        if let Some(host_end) = xml[abs_start..].find("</host>") {
-            let host_xml = &xml[abs_start..abs_start + host_end + 7];
-
-            if let Some(host) = parse_single_host(host_xml) {
-                hosts.push(host);
-            }
-
-            current_pos = abs_start + host_end + 7;
-        } else {
-            break;
-        }
+/// Parse an Nmap XML file from disk without reading it into memory
+///
+/// Returns the results along with warnings about anything that was parsed
+/// but is incomplete (an interrupted scan, hosts without an address).
+/// Normal (`-oN`) and greppable (`-oG`) output are rejected with an error
+/// explaining how to produce XML instead.
+pub fn parse_nmap_xml_file(path: &Path) -> Result<(ScanResults, Vec<String>), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut reader = BufReader::with_capacity(64 * 1024, file);
+
+    let head = reader.fill_buf().map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let head = String::from_utf8_lossy(head);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    if head.starts_with("# Nmap") {
+        let format = if head.contains("\nHost: ") { "greppable (-oG)" } else { "normal (-oN)" };
+        return Err(format!(
+            "{} looks like Nmap {} output. Only XML output can be imported; re-run the scan with -oX <file>.",
+            path.display(),
+            format
+        ));
+    }
+    if head.is_empty() {
+        return Err(format!("{} is empty", path.display()));
     }
 
-    hosts
+    parse_nmap_reader(reader)
 }
 
-fn parse_single_host(host_xml: &str) -> Option<DiscoveredHost> {
-    // Extract IP address
-    let ip = extract_address(host_xml, "ipv4")
-        .or_else(|| extract_address(host_xml, "ipv6"))?;
-
-    // Extract MAC if present
-    let mac = extract_address(host_xml, "mac");
-
-    // Extract vendor
-    let vendor = extract_xml_attr(host_xml, "address", "vendor");
-
-    // Extract hostname
-    let hostname = extract_hostname(host_xml);
-
-    // Extract status
-    let status = extract_xml_attr(host_xml, "status", "state")
-        .unwrap_or_else(|| "unknown".to_string());
-
-    // Parse ports
-    let ports = parse_ports(host_xml);
+/// Which Nmap output format a file or pasted text holds, from its first bytes
+///
+/// Normal (`-oN`) output has no reliable structure and is rejected.
+fn sniff_nmap_format(head: &str) -> Result<NmapOutputFormat, String> {
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    if head.is_empty() {
+        return Err("Nmap output is empty".to_string());
+    }
+    if head.starts_with("Host: ") || (head.starts_with("# Nmap") && (head.contains("\nHost: ") || head.contains(" -oG "))) {
+        return Ok(NmapOutputFormat::Greppable);
+    }
+    if head.starts_with("# Nmap") || head.starts_with("Starting Nmap") {
+        return Err(
+            "This looks like Nmap normal (-oN) output. Only XML (-oX) and greppable (-oG) output can be imported."
+                .to_string(),
+        );
+    }
+    Ok(NmapOutputFormat::Xml)
+}
 
-    // Parse OS matches
-    let os_matches = parse_os_matches(host_xml);
+/// Parse an Nmap XML or greppable file from disk without reading it into memory
+pub fn parse_nmap_file(path: &Path) -> Result<(ScanResults, Vec<String>, NmapOutputFormat), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut reader = BufReader::with_capacity(64 * 1024, file);
 
-    Some(DiscoveredHost {
-        ip_address: ip,
-        mac_address: mac,
-        hostname,
-        vendor,
-        status,
-        ports,
-        os_matches,
-        host_scripts: vec![],
-    })
+    let head = reader.fill_buf().map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let format = sniff_nmap_format(&String::from_utf8_lossy(head)).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let (results, warnings) = match format {
+        NmapOutputFormat::Xml => parse_nmap_reader(reader)?,
+        NmapOutputFormat::Greppable => parse_nmap_gnmap(reader)?,
+    };
+    Ok((results, warnings, format))
 }
 
-fn extract_address(xml: &str, addr_type: &str) -> Option<String> {
-    // Look for <address addr="..." addrtype="ipv4"/>
-    let pattern = format!(r#"addrtype="{}""#, addr_type);
+/// Parse Nmap XML or greppable output held in memory, e.g. pasted text
+pub fn parse_nmap_output(content: &str) -> Result<(ScanResults, Vec<String>, NmapOutputFormat), String> {
+    let content = content.trim_start_matches('\u{feff}');
+    let format = sniff_nmap_format(content)?;
+    let (results, warnings) = match format {
+        NmapOutputFormat::Xml => parse_nmap_reader(content.as_bytes())?,
+        NmapOutputFormat::Greppable => parse_nmap_gnmap(content.as_bytes())?,
+    };
+    Ok((results, warnings, format))
+}
 
-    if let Some(pos) = xml.find(&pattern) {
-        // Find the addr= attribute before this
-        let before = &xml[..pos];
-        if let Some(addr_start) = before.rfind(r#"addr=""#) {
-            let start = addr_start + 6;
-            if let Some(end) = xml[start..].find('"') {
-                return Some(xml[start..start + end].to_string());
+/// Parse greppable (`-oG`) Nmap output line by line
+///
+/// Greppable output carries less than XML: the product and version come as
+/// one string, OS detection gives only the best guess, and there are no
+/// script results. A line that can't be read is skipped with a warning.
+pub fn parse_nmap_gnmap<R: BufRead>(source: R) -> Result<(ScanResults, Vec<String>), String> {
+    let mut results = ScanResults {
+        scan_id: Uuid::new_v4().to_string(),
+        hosts: Vec::new(),
+        hosts_scanned: 0,
+        hosts_up: 0,
+        duration_seconds: 0.0,
+        nmap_version: None,
+        command_line: String::new(),
+        start_time: String::new(),
+        end_time: String::new(),
+    };
+    let mut warnings = Vec::new();
+    let mut host_index: HashMap<String, usize> = HashMap::new();
+    let mut finished = false;
+
+    for (index, line) in source.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read Nmap output: {}", e))?;
+        let line = line.trim_start_matches('\u{feff}').trim_end();
+
+        if let Some(header) = line.strip_prefix("# Nmap ") {
+            if let Some((version, rest)) = header.split_once(" scan initiated ") {
+                results.nmap_version = Some(version.to_string());
+                let (started, command) = rest.split_once(" as: ").unwrap_or((rest, ""));
+                results.start_time = started.to_string();
+                results.command_line = command.to_string();
+            } else if let Some(done) = header.strip_prefix("done at ") {
+                finished = true;
+                parse_gnmap_footer(done, &mut results);
             }
+            continue;
         }
-    }
-    None
-}
 
-fn extract_hostname(xml: &str) -> Option<String> {
-    // Look for <hostname name="..." type="PTR"/>
-    if let Some(pos) = xml.find("<hostname") {
-        let after = &xml[pos..];
-        if let Some(name_pos) = after.find(r#"name=""#) {
-            let start = name_pos + 6;
-            if let Some(end) = after[start..].find('"') {
-                return Some(after[start..start + end].to_string());
+        let host_line = match line.strip_prefix("Host: ") {
+            Some(host_line) => host_line,
+            None => continue,
+        };
+        match parse_gnmap_host(host_line) {
+            Ok((host, port_warnings)) => {
+                warnings.extend(port_warnings.into_iter().map(|w| format!("Line {}: {}", index + 1, w)));
+                match host_index.get(&host.ip_address) {
+                    // Nmap writes a Status line and a Ports line for the same host
+                    Some(&existing) => merge_gnmap_host(&mut results.hosts[existing], host),
+                    None => {
+                        host_index.insert(host.ip_address.clone(), results.hosts.len());
+                        results.hosts.push(host);
+                    }
+                }
             }
+            Err(e) => warnings.push(format!("Line {}: {}", index + 1, e)),
         }
     }
-    None
-}
 
-fn extract_xml_attr(xml: &str, element: &str, attr: &str) -> Option<String> {
-    let element_pattern = format!("<{}", element);
-    if let Some(elem_pos) = xml.find(&element_pattern) {
-        let after = &xml[elem_pos..];
-        let attr_pattern = format!(r#"{}=""#, attr);
-        if let Some(attr_pos) = after.find(&attr_pattern) {
-            let start = attr_pos + attr.len() + 2;
-            if let Some(end) = after[start..].find('"') {
-                return Some(after[start..start + end].to_string());
-            }
-        }
+    if results.hosts.is_empty() && results.command_line.is_empty() {
+        return Err("Not Nmap greppable output: no header or Host lines".to_string());
     }
-    None
+    if !finished {
+        warnings.push("Scan did not finish (no \"Nmap done\" line); results may be incomplete".to_string());
+        results.hosts_scanned = results.hosts.len() as u32;
+        results.hosts_up = results.hosts.iter().filter(|h| h.status == "up").count() as u32;
+    }
+    if results.start_time.is_empty() {
+        results.start_time = chrono::Utc::now().to_rfc3339();
+    }
+    if results.end_time.is_empty() {
+        results.end_time = chrono::Utc::now().to_rfc3339();
+    }
+
+    Ok((results, warnings))
 }
 
-fn parse_ports(xml: &str) -> Vec<DiscoveredPort> {
-    let mut ports = Vec::new();
+/// "Tue Mar  4 10:12:03 2025 -- 8 IP addresses (3 hosts up) scanned in 2.05 seconds"
+fn parse_gnmap_footer(done: &str, results: &mut ScanResults) {
+    let (finished, summary) = done.split_once(" -- ").unwrap_or((done, ""));
+    results.end_time = finished.trim().to_string();
 
-    let mut current_pos = 0;
-    while let Some(port_start) = xml[current_pos..].find("<port ") {
-        let abs_start = current_pos + port_start;
+    let number_before = |marker: &str| -> Option<&str> {
+        let before = &summary[..summary.find(marker)?];
+        before.rsplit([' ', '(']).find(|w| !w.is_empty())
+    };
+    results.hosts_scanned = number_before(" IP address").and_then(|n| n.parse().ok()).unwrap_or(0);
+    results.hosts_up = number_before(" host").and_then(|n| n.parse().ok()).unwrap_or(0);
+    results.duration_seconds = summary
+        .split_once("scanned in ")
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0.0);
+}
 
-        // Find the end of this port element
-        let end_pos = if let Some(end) = xml[abs_start..].find("</port>") {
-            abs_start + end + 7
-        } else if let Some(end) = xml[abs_start..].find("/>") {
-            abs_start + end + 2
-        } else {
-            break;
-        };
+/// One "Host:" line (without the prefix), and warnings about ports skipped in it
+fn parse_gnmap_host(line: &str) -> Result<(DiscoveredHost, Vec<String>), String> {
+    let mut fields = line.split('\t');
+    let address = fields.next().unwrap_or_default();
+    let (ip, name) = match address.split_once(" (") {
+        Some((ip, name)) => (ip.trim(), name.trim_end_matches(')').trim()),
+        None => (address.trim(), ""),
+    };
+    if ip.parse::<IpAddr>().is_err() {
+        return Err(format!("invalid host address \"{}\"", ip));
+    }
 
-        let port_xml = &xml[abs_start..end_pos];
+    let mut host = DiscoveredHost {
+        ip_address: ip.to_string(),
+        mac_address: None,
+        hostname: Some(name.to_string()).filter(|n| !n.is_empty()),
+        vendor: None,
+        status: "unknown".to_string(),
+        ports: Vec::new(),
+        os_matches: Vec::new(),
+        host_scripts: Vec::new(),
+        extra_ports: Vec::new(),
+        uptime_seconds: None,
+        last_boot: None,
+        distance: None,
+    };
+    let mut warnings = Vec::new();
 
-        // Extract port number
-        if let Some(port_num) = extract_xml_attr(port_xml, "port", "portid")
-            .and_then(|p| p.parse::<u16>().ok())
-        {
-            // Extract protocol
-            let protocol = match extract_xml_attr(port_xml, "port", "protocol").as_deref() {
-                Some("udp") => Protocol::Udp,
-                Some("sctp") => Protocol::Sctp,
-                _ => Protocol::Tcp,
-            };
+    for field in fields {
+        let (key, value) = match field.split_once(": ") {
+            Some(pair) => pair,
+            None => continue,
+        };
+        match key.trim() {
+            "Status" => host.status = value.trim().to_lowercase(),
+            "Ports" => {
+                // Listed ports mean the host answered, even without a Status line
+                host.status = "up".to_string();
+                for entry in split_gnmap_ports(value) {
+                    match parse_gnmap_port(entry) {
+                        Some(port) => host.ports.push(port),
+                        None => warnings.push(format!("skipped unreadable port \"{}\" on {}", entry, ip)),
+                    }
+                }
+            }
+            "Ignored State" => {
+                let (state, count) = value.split_once(" (").unwrap_or((value, ""));
+                host.extra_ports.push(ExtraPorts {
+                    state: state.trim().to_string(),
+                    count: count.trim_end_matches(')').parse().unwrap_or(0),
+                });
+            }
+            "OS" => host.os_matches.push(OsMatch {
+                name: value.trim().to_string(),
+                accuracy: 100,
+                os_family: None,
+                os_gen: None,
+                device_type: None,
+            }),
+            _ => {}
+        }
+    }
 
-            // Extract state
-            let state = match extract_xml_attr(port_xml, "state", "state").as_deref() {
-                Some("open") => PortState::Open,
-                Some("closed") => PortState::Closed,
-                Some("filtered") => PortState::Filtered,
-                Some("unfiltered") => PortState::Unfiltered,
-                Some("open|filtered") => PortState::OpenFiltered,
-                Some("closed|filtered") => PortState::ClosedFiltered,
-                _ => PortState::Filtered,
-            };
+    Ok((host, warnings))
+}
 
-            // Extract service info
-            let service = extract_xml_attr(port_xml, "service", "name");
-            let product = extract_xml_attr(port_xml, "service", "product");
-            let version = extract_xml_attr(port_xml, "service", "version");
-            let extra_info = extract_xml_attr(port_xml, "service", "extrainfo");
-
-            ports.push(DiscoveredPort {
-                port: port_num,
-                protocol,
-                state,
-                service,
-                product,
-                version,
-                extra_info,
-                scripts: vec![],
-            });
+/// Split the Ports field on the ", " between entries, not inside versions
+fn split_gnmap_ports(value: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut start = 0;
+    for (i, _) in value.match_indices(", ") {
+        let next = &value[i + 2..];
+        if next.split_once('/').is_some_and(|(port, _)| !port.is_empty() && !port.contains(char::is_whitespace)) {
+            entries.push(value[start..i].trim());
+            start = i + 2;
         }
+    }
+    entries.push(value[start..].trim());
+    entries.into_iter().filter(|e| !e.is_empty()).collect()
+}
 
-        current_pos = end_pos;
+/// "22/open/tcp//ssh//OpenSSH 8.9p1 Ubuntu 3ubuntu0.6/"
+fn parse_gnmap_port(entry: &str) -> Option<DiscoveredPort> {
+    let fields: Vec<&str> = entry.split('/').collect();
+    if fields.len() < 3 {
+        return None;
     }
+    let non_empty = |i: usize| fields.get(i).map(|f| f.trim()).filter(|f| !f.is_empty()).map(String::from);
+
+    Some(DiscoveredPort {
+        port: fields[0].trim().parse().ok()?,
+        protocol: match fields[2] {
+            "tcp" => Protocol::Tcp,
+            "udp" => Protocol::Udp,
+            "sctp" => Protocol::Sctp,
+            _ => return None,
+        },
+        state: parse_port_state(Some(fields[1])),
+        service: non_empty(4),
+        product: non_empty(6),
+        version: None,
+        extra_info: None,
+        scripts: Vec::new(),
+    })
+}
 
-    ports
+/// Fold a second line for the same host into the first
+fn merge_gnmap_host(existing: &mut DiscoveredHost, host: DiscoveredHost) {
+    if host.status != "unknown" {
+        existing.status = host.status;
+    }
+    if existing.hostname.is_none() {
+        existing.hostname = host.hostname;
+    }
+    existing.ports.extend(host.ports);
+    existing.extra_ports.extend(host.extra_ports);
+    existing.os_matches.extend(host.os_matches);
 }
 
-fn parse_os_matches(xml: &str) -> Vec<OsMatch> {
-    let mut matches = Vec::new();
+/// Stream Nmap XML from any buffered source
+fn parse_nmap_reader<R: BufRead>(source: R) -> Result<(ScanResults, Vec<String>), String> {
+    let mut reader = Reader::from_reader(source);
+    reader.config_mut().trim_text(true);
+
+    let mut results = ScanResults {
+        scan_id: Uuid::new_v4().to_string(),
+        hosts: Vec::new(),
+        hosts_scanned: 0,
+        hosts_up: 0,
+        duration_seconds: 0.0,
+        nmap_version: None,
+        command_line: String::new(),
+        start_time: String::new(),
+        end_time: String::new(),
+    };
+    let mut state = NmapXmlState::default();
+    let mut buf = Vec::new();
+    let mut warnings = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => state.open_or_skip(&e, &mut results)?,
+            Ok(Event::Empty(e)) => {
+                state.open_or_skip(&e, &mut results)?;
+                state.close(e.name().as_ref(), &mut results);
+            }
+            Ok(Event::End(e)) => state.close(e.name().as_ref(), &mut results),
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            // A file cut off or corrupted part-way keeps the hosts before the damage
+            Err(e) if !results.hosts.is_empty() => {
+                warnings.push(format!(
+                    "Invalid Nmap XML at position {}: {}; hosts after this point were not imported",
+                    reader.error_position(),
+                    e
+                ));
+                break;
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Invalid Nmap XML at position {}: {}",
+                    reader.error_position(),
+                    e
+                ))
+            }
+        }
+        buf.clear();
+    }
+
+    if !state.seen_nmaprun {
+        return Err("Not an Nmap XML document: missing <nmaprun> element".to_string());
+    }
 
-    let mut current_pos = 0;
-    while let Some(os_start) = xml[current_pos..].find("<osmatch ") {
-        let abs_start = current_pos + os_start;
+    warnings.append(&mut state.host_warnings);
+    if state.skipped_hosts > 0 {
+        warnings.push(format!("Skipped {} host(s) with no IP address", state.skipped_hosts));
+    }
 
-        let end_pos = if let Some(end) = xml[abs_start..].find("/>") {
-            abs_start + end + 2
-        } else if let Some(end) = xml[abs_start..].find("</osmatch>") {
-            abs_start + end + 10
-        } else {
-            break;
-        };
+    // Interrupted scans have no <runstats>; fall back to what was parsed
+    if !state.seen_runstats {
+        warnings.push("Scan did not finish (no <runstats>); results may be incomplete".to_string());
+        results.hosts_scanned = results.hosts.len() as u32;
+        results.hosts_up = results.hosts.iter().filter(|h| h.status == "up").count() as u32;
+    }
+    if results.start_time.is_empty() {
+        results.start_time = chrono::Utc::now().to_rfc3339();
+    }
+    if results.end_time.is_empty() {
+        results.end_time = chrono::Utc::now().to_rfc3339();
+    }
 
-        let os_xml = &xml[abs_start..end_pos];
+    Ok((results, warnings))
+}
 
-        if let Some(name) = extract_xml_attr(os_xml, "osmatch", "name") {
-            let accuracy = extract_xml_attr(os_xml, "osmatch", "accuracy")
-                .and_then(|a| a.parse::<u8>().ok())
-                .unwrap_or(0);
+/// Element context while streaming Nmap XML
+#[derive(Default)]
+struct NmapXmlState {
+    seen_nmaprun: bool,
+    seen_runstats: bool,
+    host: Option<DiscoveredHost>,
+    hostname_is_user: bool,
+    port: Option<DiscoveredPort>,
+    os_match: Option<OsMatch>,
+    in_hostscript: bool,
+    skipped_hosts: usize,
+    /// Why the current host can't be used, if something in it failed to parse
+    host_error: Option<String>,
+    host_warnings: Vec<String>,
+}
 
-            matches.push(OsMatch {
-                name,
-                accuracy,
-                os_family: extract_xml_attr(os_xml, "osclass", "osfamily"),
-                os_gen: extract_xml_attr(os_xml, "osclass", "osgen"),
-                device_type: extract_xml_attr(os_xml, "osclass", "type"),
-            });
+impl NmapXmlState {
+    /// Open an element; a bad element inside a host discards just that host
+    fn open_or_skip(&mut self, e: &BytesStart, results: &mut ScanResults) -> Result<(), String> {
+        match self.open(e, results) {
+            Err(err) if self.host.is_some() => {
+                self.host_error.get_or_insert(err);
+                Ok(())
+            }
+            other => other,
         }
-
-        current_pos = end_pos;
     }
 
-    // Sort by accuracy descending
-    matches.sort_by(|a, b| b.accuracy.cmp(&a.accuracy));
+    fn open(&mut self, e: &BytesStart, results: &mut ScanResults) -> Result<(), String> {
+        let attrs = xml_attrs(e)?;
+        let attr = |name: &str| attrs.get(name).cloned();
 
-    matches
-}
+        match e.name().as_ref() {
+            b"nmaprun" => {
+                self.seen_nmaprun = true;
+                results.nmap_version = attr("version");
+                results.command_line = attr("args").unwrap_or_default();
+                results.start_time = attr("startstr").unwrap_or_default();
+            }
+            b"finished" => {
+                results.end_time = attr("timestr").unwrap_or_default();
+                results.duration_seconds = attr("elapsed")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.0);
+            }
+            b"runstats" => self.seen_runstats = true,
+            b"hosts" if self.seen_runstats => {
+                results.hosts_up = attr("up").and_then(|v| v.parse().ok()).unwrap_or(0);
+                results.hosts_scanned = attr("total").and_then(|v| v.parse().ok()).unwrap_or(0);
+            }
+            b"host" => {
+                self.hostname_is_user = false;
+                self.host = Some(DiscoveredHost {
+                    ip_address: String::new(),
+                    mac_address: None,
+                    hostname: None,
+                    vendor: None,
+                    status: "unknown".to_string(),
+                    ports: Vec::new(),
+                    os_matches: Vec::new(),
+                    host_scripts: Vec::new(),
+                    extra_ports: Vec::new(),
+                    uptime_seconds: None,
+                    last_boot: None,
+                    distance: None,
+                });
+            }
+            _ => {}
+        }
 
-/// Validate target specification
-pub fn validate_target(target: &str) -> Result<TargetValidation, String> {
-    let target = target.trim();
-
-    if target.is_empty() {
-        return Ok(TargetValidation {
-            valid: false,
-            target_type: None,
-            normalized: None,
-            error: Some("Target cannot be empty".to_string()),
-        });
-    }
+        let host = match self.host.as_mut() {
+            Some(host) => host,
+            None => return Ok(()),
+        };
 
-    // Check for CIDR notation
-    if target.contains('/') {
-        let parts: Vec<&str> = target.split('/').collect();
-        if parts.len() == 2 {
-            if let Ok(prefix) = parts[1].parse::<u8>() {
-                if prefix <= 32 {
-                    return Ok(TargetValidation {
-                        valid: true,
-                        target_type: Some("CIDR".to_string()),
-                        normalized: Some(target.to_string()),
-                        error: None,
+        match e.name().as_ref() {
+            b"status" => host.status = attr("state").unwrap_or_else(|| "unknown".to_string()),
+            b"address" => match attr("addrtype").as_deref() {
+                Some("ipv4") | Some("ipv6") if host.ip_address.is_empty() => {
+                    host.ip_address = attr("addr").unwrap_or_default();
+                }
+                Some("mac") => {
+                    host.mac_address = attr("addr");
+                    host.vendor = attr("vendor");
+                }
+                _ => {}
+            },
+            // Prefer the name the user supplied as a target over reverse DNS
+            b"hostname" => {
+                let is_user = attr("type").as_deref() == Some("user");
+                if host.hostname.is_none() || (is_user && !self.hostname_is_user) {
+                    host.hostname = attr("name");
+                    self.hostname_is_user = is_user;
+                }
+            }
+            b"extraports" => {
+                let count = attr("count").and_then(|v| v.parse().ok()).unwrap_or(0);
+                if let Some(state) = attr("state") {
+                    host.extra_ports.push(ExtraPorts { state, count });
+                }
+            }
+            b"port" => {
+                self.port = attr("portid")
+                    .and_then(|p| p.parse::<u16>().ok())
+                    .map(|port| DiscoveredPort {
+                        port,
+                        protocol: match attr("protocol").as_deref() {
+                            Some("udp") => Protocol::Udp,
+                            Some("sctp") => Protocol::Sctp,
+                            _ => Protocol::Tcp,
+                        },
+                        state: PortState::Filtered,
+                        service: None,
+                        product: None,
+                        version: None,
+                        extra_info: None,
+                        scripts: Vec::new(),
                     });
+            }
+            b"state" => {
+                if let Some(port) = self.port.as_mut() {
+                    port.state = parse_port_state(attr("state").as_deref());
+                }
+            }
+            b"service" => {
+                if let Some(port) = self.port.as_mut() {
+                    port.service = attr("name");
+                    port.product = attr("product");
+                    port.version = attr("version");
+                    port.extra_info = attr("extrainfo");
+                }
+            }
+            b"uptime" => {
+                host.uptime_seconds = attr("seconds").and_then(|v| v.parse().ok());
+                host.last_boot = attr("lastboot");
+            }
+            b"distance" => host.distance = attr("value").and_then(|v| v.parse().ok()),
+            b"hostscript" => self.in_hostscript = true,
+            b"script" => {
+                let script = ScriptResult {
+                    id: attr("id").unwrap_or_default(),
+                    output: attr("output").unwrap_or_default(),
+                };
+                if let Some(port) = self.port.as_mut() {
+                    port.scripts.push(script);
+                } else if self.in_hostscript {
+                    host.host_scripts.push(script);
+                }
+            }
+            b"osmatch" => {
+                self.os_match = attr("name").map(|name| OsMatch {
+                    name,
+                    accuracy: attr("accuracy").and_then(|a| a.parse().ok()).unwrap_or(0),
+                    os_family: None,
+                    os_gen: None,
+                    device_type: None,
+                });
+            }
+            // The first (most specific) class describes the match
+            b"osclass" => {
+                if let Some(os) = self.os_match.as_mut() {
+                    if os.os_family.is_none() {
+                        os.os_family = attr("osfamily");
+                        os.os_gen = attr("osgen");
+                        os.device_type = attr("type");
+                    }
                 }
             }
+            _ => {}
         }
-        return Ok(TargetValidation {
-            valid: false,
-            target_type: None,
-            normalized: None,
-            error: Some("Invalid CIDR notation".to_string()),
-        });
-    }
 
-    // Check for IP range (192.168.1.1-100)
-    if target.contains('-') && !target.contains(':') {
-        return Ok(TargetValidation {
-            valid: true,
-            target_type: Some("IP Range".to_string()),
-            normalized: Some(target.to_string()),
-            error: None,
-        });
+        Ok(())
     }
 
-    // Check for IPv4 address
-    let ipv4_parts: Vec<&str> = target.split('.').collect();
-    if ipv4_parts.len() == 4 {
-        let valid = ipv4_parts.iter().all(|p| {
-            p.parse::<u8>().is_ok() || *p == "*"
-        });
-        if valid {
-            return Ok(TargetValidation {
-                valid: true,
-                target_type: Some("IPv4".to_string()),
-                normalized: Some(target.to_string()),
-                error: None,
-            });
+    fn close(&mut self, name: &[u8], results: &mut ScanResults) {
+        match name {
+            b"port" => {
+                if let (Some(host), Some(port)) = (self.host.as_mut(), self.port.take()) {
+                    host.ports.push(port);
+                }
+            }
+            b"osmatch" => {
+                if let (Some(host), Some(os)) = (self.host.as_mut(), self.os_match.take()) {
+                    host.os_matches.push(os);
+                }
+            }
+            b"hostscript" => self.in_hostscript = false,
+            b"host" => {
+                if let Some(mut host) = self.host.take() {
+                    if let Some(err) = self.host_error.take() {
+                        let address = if host.ip_address.is_empty() { "with no address" } else { &host.ip_address };
+                        self.host_warnings.push(format!("Skipped host {}: {}", address, err));
+                    } else if host.ip_address.is_empty() {
+                        self.skipped_hosts += 1;
+                    } else {
+                        host.os_matches.sort_by(|a, b| b.accuracy.cmp(&a.accuracy));
+                        results.hosts.push(host);
+                    }
+                }
+            }
+            _ => {}
         }
     }
+}
 
-    // Check for IPv6 address
-    if target.contains(':') {
-        return Ok(TargetValidation {
-            valid: true,
-            target_type: Some("IPv6".to_string()),
-            normalized: Some(target.to_string()),
-            error: None,
-        });
+/// Collect an element's attributes with entities unescaped
+fn xml_attrs(e: &BytesStart) -> Result<HashMap<String, String>, String> {
+    let mut attrs = HashMap::new();
+    for attr in e.attributes() {
+        let attr = attr.map_err(|err| format!("Invalid Nmap XML attribute: {}", err))?;
+        let value = attr
+            .unescape_value()
+            .map_err(|err| format!("Invalid Nmap XML attribute value: {}", err))?;
+        attrs.insert(
+            String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+            value.into_owned(),
+        );
     }
+    Ok(attrs)
+}
 
-    // Assume hostname
-    if target.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
-        return Ok(TargetValidation {
-            valid: true,
-            target_type: Some("Hostname".to_string()),
-            normalized: Some(target.to_string()),
-            error: None,
-        });
+fn parse_port_state(state: Option<&str>) -> PortState {
+    match state {
+        Some("open") => PortState::Open,
+        Some("closed") => PortState::Closed,
+        Some("filtered") => PortState::Filtered,
+        Some("unfiltered") => PortState::Unfiltered,
+        Some("open|filtered") => PortState::OpenFiltered,
+        Some("closed|filtered") => PortState::ClosedFiltered,
+        _ => PortState::Filtered,
     }
+}
 
-    Ok(TargetValidation {
+/// Validate target specification
+///
+/// A comma-separated list is valid when every entry is. Addresses, CIDRs
+/// and ranges are parsed in full, so `999.999.999.999/24` is rejected.
+pub fn validate_target(target: &str) -> Result<TargetValidation, String> {
+    let parts = split_target_list(target);
+    let invalid = |error: String| TargetValidation {
         valid: false,
         target_type: None,
         normalized: None,
-        error: Some("Invalid target format".to_string()),
+        error: Some(error),
+    };
+
+    if parts.is_empty() {
+        return Ok(invalid("Target cannot be empty".to_string()));
+    }
+
+    let mut parsed = Vec::with_capacity(parts.len());
+    for part in &parts {
+        match parse_target(part) {
+            Ok(target) => parsed.push(target),
+            Err(e) if parts.len() > 1 => return Ok(invalid(format!("{}: {}", part, e))),
+            Err(e) => return Ok(invalid(e)),
+        }
+    }
+
+    Ok(TargetValidation {
+        valid: true,
+        target_type: Some(match parsed.as_slice() {
+            [single] => single.kind.label().to_string(),
+            _ => "List".to_string(),
+        }),
+        normalized: Some(parsed.iter().map(|t| t.normalized.as_str()).collect::<Vec<_>>().join(",")),
+        error: None,
     })
 }
 
@@ -686,6 +1581,18 @@ impl TcpScanner {
         }
     }
 
+    /// Probe a port, telling a refused connection (host up, port closed)
+    /// apart from no response at all
+    async fn probe_port(ip: Ipv4Addr, port: u16, timeout_duration: Duration) -> Option<bool> {
+        let addr = SocketAddr::new(IpAddr::V4(ip), port);
+
+        match timeout(timeout_duration, TcpStream::connect(addr)).await {
+            Ok(Ok(_stream)) => Some(true),
+            Ok(Err(e)) if e.kind() == std::io::ErrorKind::ConnectionRefused => Some(false),
+            _ => None,
+        }
+    }
+
     /// Get service name for a port number
     fn get_service_name(port: u16) -> String {
         match port {
@@ -789,6 +1696,42 @@ impl TcpScanner {
 
         Ok(self.scan_host(ip_addr).await)
     }
+
+    /// Re-test a host for finding verification
+    ///
+    /// Unlike `scan_host`, the host counts as alive when any probed port
+    /// answers, even with a refusal, so a closed port on a responsive host
+    /// can be told apart from a host that is down.
+    pub async fn verify_host(&self, ip: &str) -> Result<ScannedHost, String> {
+        let ip_addr: Ipv4Addr = ip.parse()
+            .map_err(|_| format!("Invalid IP address: {}", ip))?;
+
+        let probes: Vec<(u16, Option<bool>)> = stream::iter(self.config.ports.clone())
+            .map(|port| {
+                let timeout = self.config.timeout;
+                async move { (port, Self::probe_port(ip_addr, port, timeout).await) }
+            })
+            .buffer_unordered(self.config.concurrency)
+            .collect()
+            .await;
+
+        let open_ports = probes
+            .iter()
+            .filter(|(_, probe)| *probe == Some(true))
+            .map(|(port, _)| ScannedPort {
+                port: *port,
+                open: true,
+                service: Self::get_service_name(*port),
+            })
+            .collect();
+
+        Ok(ScannedHost {
+            ip_address: ip_addr.to_string(),
+            open_ports,
+            is_alive: probes.iter().any(|(_, probe)| probe.is_some()),
+            hostname: None,
+        })
+    }
 }
 
 impl Default for TcpScanner {
@@ -814,10 +1757,179 @@ pub async fn scan_network_with_ports(cidr: &str, ports: Vec<u16>) -> Result<Vec<
     scanner.scan_network(cidr).await
 }
 
+/// Probe each verification batch, returning one result per host
+pub async fn verify_targets(targets: &[VerificationTarget]) -> Result<Vec<ScannedHost>, String> {
+    let mut hosts = Vec::new();
+    for target in targets {
+        let scanner = TcpScanner::with_config(TcpScannerConfig {
+            ports: target.ports.clone(),
+            ..Default::default()
+        });
+        for ip in &target.hosts {
+            hosts.push(scanner.verify_host(ip).await?);
+        }
+    }
+    Ok(hosts)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn unprivileged() -> ScanPrivileges {
+        ScanPrivileges {
+            packet_driver: true,
+            remedy: Some("Run Optio as root".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_scan_privileges() {
+        let mut config = ScanConfig { scan_type: ScanType::QuickScan, ..Default::default() };
+        let warning = apply_scan_privileges(&mut config, &unprivileged()).unwrap().unwrap();
+        assert_eq!(config.privilege_mode, ScanPrivilegeMode::Unprivileged);
+        assert!(warning.contains("-sT"));
+        assert!(warning.ends_with("Run Optio as root"));
+
+        let mut config = ScanConfig { scan_type: ScanType::UdpScan, ..Default::default() };
+        let err = apply_scan_privileges(&mut config, &unprivileged()).unwrap_err();
+        assert!(err.contains("Run Optio as root"));
+
+        let mut config = ScanConfig {
+            scan_type: ScanType::Custom,
+            custom_args: Some("-sV -O".to_string()),
+            ..Default::default()
+        };
+        assert!(apply_scan_privileges(&mut config, &unprivileged()).unwrap_err().contains("-O"));
+
+        let mut config = ScanConfig { scan_type: ScanType::PingSweep, ..Default::default() };
+        assert_eq!(apply_scan_privileges(&mut config, &unprivileged()).unwrap(), None);
+
+        // Capabilities without a packet driver still means no raw access
+        let no_driver = ScanPrivileges { nmap_capabilities: true, ..Default::default() };
+        let mut config = ScanConfig { scan_type: ScanType::QuickScan, ..Default::default() };
+        assert!(apply_scan_privileges(&mut config, &no_driver).unwrap().is_some());
+
+        let caps = ScanPrivileges { nmap_capabilities: true, packet_driver: true, ..Default::default() };
+        let mut config = ScanConfig { scan_type: ScanType::UdpScan, ..Default::default() };
+        assert_eq!(apply_scan_privileges(&mut config, &caps).unwrap(), None);
+        assert_eq!(config.privilege_mode, ScanPrivilegeMode::Capabilities);
+    }
+
+    #[test]
+    fn test_build_command_privilege_modes() {
+        let mut config = ScanConfig {
+            targets: vec!["10.0.0.1".to_string()],
+            scan_type: ScanType::QuickScan,
+            privilege_mode: ScanPrivilegeMode::Unprivileged,
+            ..Default::default()
+        };
+        let args = build_nmap_command(&config);
+        assert!(args.contains(&"-sT".to_string()));
+        assert!(!args.contains(&"-sS".to_string()));
+
+        config.scan_type = ScanType::Custom;
+        config.custom_args = Some("-sS -sV".to_string());
+        let args = build_nmap_command(&config);
+        assert!(args.contains(&"-sT".to_string()) && args.contains(&"-sV".to_string()));
+
+        config.privilege_mode = ScanPrivilegeMode::Capabilities;
+        let args = build_nmap_command(&config);
+        assert!(args.contains(&"--privileged".to_string()) && args.contains(&"-sS".to_string()));
+    }
+
+    #[test]
+    fn test_custom_args_quoting() {
+        let args = split_custom_args(r#"-sV --script-args 'http.useragent="Mozilla 5"' --script "default and safe" a\ b"#).unwrap();
+        assert_eq!(args, vec![
+            "-sV",
+            "--script-args",
+            "http.useragent=\"Mozilla 5\"",
+            "--script",
+            "default and safe",
+            "a b",
+        ]);
+        assert!(split_custom_args("-sV --script 'vuln").is_err());
+
+        let validation = validate_custom_args(r#"-sV --script "default and safe""#);
+        assert!(validation.valid);
+        assert_eq!(validation.normalized_args, vec!["-sV", "--script", "default and safe"]);
+
+        let config = ScanConfig {
+            targets: vec!["10.0.0.1".to_string()],
+            scan_type: ScanType::Custom,
+            custom_args: Some(r#"-sV --script "default and safe""#.to_string()),
+            ..Default::default()
+        };
+        assert!(build_nmap_command(&config).contains(&"default and safe".to_string()));
+    }
+
+    #[test]
+    fn test_custom_args_duplicate_ports() {
+        assert!(validate_custom_args("-sS -p 22,80").valid);
+        assert!(validate_custom_args("-sS -Pn -p22").valid);
+
+        let validation = validate_custom_args("-sS -p 22 -p80");
+        assert!(!validation.valid);
+        assert_eq!(validation.errors.len(), 1);
+        assert!(validation.errors[0].contains("more than once"));
+    }
+
+    #[test]
+    fn test_custom_args_denylist() {
+        for args in [
+            "-sV -oN /etc/cron.d/x",
+            "-oA out",
+            "-oG/tmp/grep",
+            "-iL /etc/passwd",
+            "-iR 1000",
+            "--datadir /tmp/evil",
+            "--datadir=/tmp/evil",
+            "--script /tmp/evil.nse",
+            "--script=default,../evil",
+            "-oX /tmp/out.xml",
+            // Nmap reads single-dash long options and unambiguous prefixes
+            "-datadir /tmp/evil",
+            "-script=/tmp/evil.nse",
+            "-script /tmp/evil.nse",
+            "--datad /tmp/evil",
+            "--excludef x",
+            "-excludefile=x",
+            "--oN /tmp/x",
+            "-resum /tmp/x",
+            "-servicedb=/tmp/x",
+        ] {
+            let validation = validate_custom_args(args);
+            assert!(!validation.valid, "{} should be rejected", args);
+        }
+
+        let validation = validate_custom_args("-sV -oN /etc/cron.d/x --script vuln");
+        assert_eq!(validation.normalized_args, vec!["-sV", "--script", "vuln"]);
+
+        // Legitimate options sharing a first letter or a prefix still pass
+        let validation = validate_custom_args("-d -v -sS --exclude 10.0.0.5 --script-args x=1 --data-length 20 --version-light");
+        assert!(validation.valid, "{:?}", validation.errors);
+        let validation = validate_custom_args("-datad /tmp/evil -sV");
+        assert_eq!(validation.normalized_args, vec!["-sV"]);
+
+        let validation = validate_custom_args("-sS -oX - --exclude 10.0.0.5");
+        assert!(validation.valid);
+        assert_eq!(validation.warnings.len(), 2);
+        assert_eq!(validation.normalized_args, vec!["-sS", "--exclude", "10.0.0.5"]);
+
+        // A saved config with a denied option never reaches Nmap
+        let config = ScanConfig {
+            targets: vec!["10.0.0.1".to_string()],
+            scan_type: ScanType::Custom,
+            custom_args: Some("-sS -oN /etc/x".to_string()),
+            ..Default::default()
+        };
+        assert!(check_custom_args(&config).is_err());
+        let args = build_nmap_command(&config);
+        assert!(!args.contains(&"-oN".to_string()) && !args.contains(&"/etc/x".to_string()));
+    }
+
     #[test]
     fn test_build_command_quick_scan() {
         let config = ScanConfig {
@@ -828,7 +1940,40 @@ mod tests {
 
         let args = build_nmap_command(&config);
         assert!(args.contains(&"-sS".to_string()));
-        assert!(args.contains(&"192.168.1.0/24".to_string()));
+        assert_eq!(args[args.len() - 2..], ["--", "192.168.1.0/24"]);
+        assert!(!args.contains(&"--max-rate".to_string()));
+
+        let config = ScanConfig { max_packets_per_second: Some(300), ..config };
+        let args = build_nmap_command(&config);
+        let rate = args.iter().position(|a| a == "--max-rate").unwrap();
+        assert_eq!(args[rate + 1], "300");
+    }
+
+    #[test]
+    fn test_build_command_from_profile() {
+        let profile = ScanConfig {
+            scan_type: ScanType::ServiceDetection,
+            ports: Some("22,443".to_string()),
+            exclude_targets: Some(vec!["10.0.50.0/24".to_string()]),
+            max_packets_per_second: Some(100),
+            ..Default::default()
+        };
+        let overrides = ScanOverrides {
+            ports: Some("80".to_string()),
+            exclude_targets: Some(vec!["10.0.0.1".to_string(), "10.0.50.0/24".to_string()]),
+            ..Default::default()
+        };
+
+        let config = overrides.apply(profile, vec!["10.0.0.0/16".to_string()]);
+        assert_eq!(config.scan_type, ScanType::ServiceDetection);
+        assert_eq!(config.max_packets_per_second, Some(100));
+
+        let args = build_nmap_command(&config);
+        let ports = args.iter().position(|a| a == "-p").unwrap();
+        assert_eq!(args[ports + 1], "80");
+        let exclude = args.iter().position(|a| a == "--exclude").unwrap();
+        assert_eq!(args[exclude + 1], "10.0.50.0/24,10.0.0.1");
+        assert!(args.contains(&"10.0.0.0/16".to_string()));
     }
 
     #[test]
@@ -852,6 +1997,17 @@ mod tests {
         assert_eq!(result.target_type, Some("Hostname".to_string()));
     }
 
+    #[test]
+    fn test_validate_target_rejects_bad_addresses() {
+        for target in ["999.999.999.999/24", "10.0.0.0/33", "300.1.1.1", "10.0.0.1,bad_host!", ""] {
+            assert!(!validate_target(target).unwrap().valid, "{} should be invalid", target);
+        }
+        let result = validate_target("10.0.0.1, 192.168.1.1-100").unwrap();
+        assert!(result.valid);
+        assert_eq!(result.target_type, Some("List".to_string()));
+        assert_eq!(result.normalized, Some("10.0.0.1,192.168.1.1-100".to_string()));
+    }
+
     #[test]
     fn test_tcp_scanner_parse_cidr() {
         let ips = TcpScanner::parse_cidr("192.168.1.0/30").unwrap();
@@ -881,4 +2037,244 @@ mod tests {
         assert_eq!(config.ports, vec![22, 80, 443, 3389]);
         assert_eq!(config.concurrency, 100);
     }
+
+    #[test]
+    fn test_parse_nmap_xml_ping_sweep() {
+        let results = parse_nmap_xml(include_str!("../../tests/fixtures/nmap/ping_sweep.xml")).unwrap();
+
+        assert_eq!(results.hosts.len(), 3);
+        assert_eq!(results.hosts_scanned, 8);
+        assert_eq!(results.hosts_up, 3);
+        assert_eq!(results.duration_seconds, 2.05);
+        assert_eq!(results.nmap_version.as_deref(), Some("7.94SVN"));
+        assert_eq!(results.command_line, "nmap -sn -oX ping_sweep.xml 192.168.56.0/29");
+        assert_eq!(results.end_time, "Tue Mar  4 10:12:03 2025");
+
+        let dc = &results.hosts[1];
+        assert_eq!(dc.ip_address, "192.168.56.100");
+        assert_eq!(dc.mac_address.as_deref(), Some("08:00:27:5D:1A:3F"));
+        assert_eq!(dc.vendor.as_deref(), Some("Oracle VirtualBox virtual NIC"));
+        assert_eq!(dc.hostname.as_deref(), Some("dc01.lab.example"));
+        assert!(results.hosts.iter().all(|h| h.status == "up" && h.ports.is_empty()));
+    }
+
+    #[test]
+    fn test_parse_nmap_xml_services_and_scripts() {
+        let results = parse_nmap_xml(include_str!("../../tests/fixtures/nmap/service_scripts.xml")).unwrap();
+        let host = &results.hosts[0];
+
+        assert_eq!(host.hostname.as_deref(), Some("web01.lab.example"));
+        assert_eq!(host.ports.len(), 4);
+        assert_eq!(host.ports.iter().filter(|p| p.state == PortState::Open).count(), 3);
+        assert_eq!(host.extra_ports.len(), 1);
+        assert_eq!(host.extra_ports[0].state, "closed");
+        assert_eq!(host.extra_ports[0].count, 996);
+
+        let ssh = &host.ports[0];
+        assert_eq!(ssh.product.as_deref(), Some("OpenSSH"));
+        assert_eq!(ssh.version.as_deref(), Some("8.9p1 Ubuntu 3ubuntu0.6"));
+        assert_eq!(ssh.scripts.len(), 1);
+        assert_eq!(ssh.scripts[0].id, "ssh-hostkey");
+        assert!(ssh.scripts[0].output.starts_with("\n  256 3f:1c"));
+
+        let http = &host.ports[1];
+        assert_eq!(http.scripts.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["http-title", "http-server-header"]);
+        assert_eq!(host.ports[2].state, PortState::Filtered);
+        assert!(host.ports[2].scripts.is_empty());
+
+        assert_eq!(host.host_scripts.len(), 2);
+        assert_eq!(host.host_scripts[1].output, "NetBIOS name: WEB01, NetBIOS user: <unknown>, NetBIOS MAC: <unknown> (unknown)");
+
+        assert_eq!(host.os_matches.len(), 2);
+        assert_eq!(host.os_matches[0].name, "Linux 5.0 - 5.5");
+        assert_eq!(host.os_matches[1].os_gen.as_deref(), Some("4.X"));
+        assert_eq!(host.uptime_seconds, Some(1_096_213));
+        assert_eq!(host.last_boot.as_deref(), Some("Sat Feb 19 17:51:17 2025"));
+        assert_eq!(host.distance, Some(1));
+        assert_eq!(results.duration_seconds, 58.31);
+
+        // Markup inside script output is attribute text, not a new element
+        let smb = &host.ports[3];
+        assert_eq!(smb.scripts[0].id, "smb-protocols");
+        assert!(smb.scripts[0].output.contains("<host name=\"web01\">"));
+        assert_eq!(results.hosts.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_nmap_xml_ipv6() {
+        let results = parse_nmap_xml(include_str!("../../tests/fixtures/nmap/ipv6_host.xml")).unwrap();
+        let host = &results.hosts[0];
+
+        assert_eq!(host.ip_address, "2001:db8:10::25");
+        assert_eq!(host.hostname.as_deref(), Some("mail.lab.example"));
+        assert_eq!(host.ports[0].port, 22);
+        assert_eq!(host.ports[0].state, PortState::Open);
+        assert_eq!(host.ports[1].state, PortState::Closed);
+        assert!(host.extra_ports.is_empty());
+    }
+
+    #[test]
+    fn test_parse_nmap_xml_rejects_invalid() {
+        assert!(parse_nmap_xml("<html><body/></html>").is_err());
+        assert!(parse_nmap_xml("<nmaprun><host></nmaprun>").is_err());
+    }
+
+    #[test]
+    fn test_track_nmap_progress() {
+        let mut progress = ScanLiveProgress::default();
+        assert!(!track_nmap_progress("<verbose level=\"0\"/>", &mut progress));
+
+        let lines = [
+            r#"<taskbegin task="SYN Stealth Scan" time="1741083646"/>"#,
+            r#"<taskprogress task="SYN Stealth Scan" time="1741083651" percent="42.17" remaining="7" etc="1741083658"/>"#,
+        ];
+        for line in lines {
+            assert!(track_nmap_progress(line, &mut progress));
+        }
+        assert_eq!(progress.task.as_deref(), Some("SYN Stealth Scan"));
+        assert_eq!(progress.percent_done, 42.17);
+        assert_eq!(progress.eta_seconds, Some(7));
+
+        // Attribute order and quoting don't matter
+        assert!(track_nmap_progress("<taskprogress remaining='12' percent='8.50' task='Service scan'/>", &mut progress));
+        assert_eq!((progress.percent_done, progress.eta_seconds), (8.5, Some(12)));
+
+        // A host's closing tag arrives without its opening tag
+        assert!(!track_nmap_progress(r#"<times srtt="512" rttvar="140" to="100000"/>"#, &mut progress));
+        assert!(track_nmap_progress("</host>", &mut progress));
+        assert_eq!(progress.hosts_completed, 1);
+        assert!(track_nmap_progress(r#"<taskend task="Service scan" time="1741083700"/>"#, &mut progress));
+        assert_eq!(progress.percent_done, 100.0);
+    }
+
+    #[test]
+    fn test_parse_nmap_xml_file() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/nmap");
+
+        let (results, warnings) = parse_nmap_xml_file(&fixtures.join("ping_sweep.xml")).unwrap();
+        assert_eq!(results.hosts_up, 3);
+        assert_eq!(results.command_line, "nmap -sn -oX ping_sweep.xml 192.168.56.0/29");
+        assert!(warnings.is_empty());
+
+        let err = parse_nmap_xml_file(&fixtures.join("ping_sweep.gnmap")).unwrap_err();
+        assert!(err.contains("greppable") && err.contains("-oX"));
+
+        let (_, warnings) = parse_nmap_reader("<nmaprun><host><status state=\"up\"/></host></nmaprun>".as_bytes()).unwrap();
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_nmap_gnmap() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/nmap");
+
+        let (results, warnings, format) = parse_nmap_file(&fixtures.join("ping_sweep.gnmap")).unwrap();
+        assert_eq!(format, NmapOutputFormat::Greppable);
+        assert!(warnings.is_empty());
+        assert_eq!((results.hosts_scanned, results.hosts_up), (8, 3));
+        assert_eq!(results.nmap_version.as_deref(), Some("7.94SVN"));
+        assert_eq!(results.command_line, "nmap -sn -oG ping_sweep.gnmap 192.168.56.0/29");
+        assert_eq!(results.hosts[1].hostname.as_deref(), Some("dc01.lab.example"));
+        assert!(results.hosts.iter().all(|h| h.status == "up"));
+
+        let (results, warnings, _) = parse_nmap_file(&fixtures.join("service_scan.gnmap")).unwrap();
+        assert_eq!(results.duration_seconds, 105.23);
+        // The Status and Ports lines of each host are merged
+        assert_eq!(results.hosts.len(), 2);
+        let dc = &results.hosts[0];
+        assert_eq!(dc.ports.len(), 4);
+        assert_eq!(dc.ports[2].product.as_deref(), Some("Microsoft Windows Active Directory LDAP (Domain: lab.example, Site: Default)"));
+        assert_eq!(dc.ports[3].state, PortState::OpenFiltered);
+        assert_eq!(dc.os_matches[0].name, "Microsoft Windows Server 2019");
+        assert_eq!(dc.extra_ports[0].count, 996);
+        let linux = &results.hosts[1];
+        assert_eq!(linux.ports[1].protocol, Protocol::Udp);
+        assert_eq!(linux.ports[1].service.as_deref(), Some("snmp"));
+
+        // A bad port and a bad host are reported, not fatal
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("bogus/open/tcp"));
+        assert!(warnings[1].contains("not-an-address"));
+
+        // Pasted text is sniffed the same way; normal output is refused
+        let pasted = std::fs::read_to_string(fixtures.join("ping_sweep.gnmap")).unwrap();
+        assert_eq!(parse_nmap_output(&pasted).unwrap().2, NmapOutputFormat::Greppable);
+        let err = parse_nmap_output("# Nmap 7.94 scan initiated Tue Mar  4 as: nmap -oN x 10.0.0.1\nNmap scan report for 10.0.0.1\n").unwrap_err();
+        assert!(err.contains("normal (-oN)"));
+    }
+
+    #[test]
+    fn test_parse_nmap_xml_skips_broken_hosts() {
+        let xml = r#"<nmaprun args="nmap -sV 10.0.0.0/30">
+            <host><status state="up"/><address addr="10.0.0.1" addrtype="ipv4"/>
+              <ports><port protocol="tcp" portid="22"><state state="open"/></port></ports></host>
+            <host><status state="up"/><address addr="10.0.0.2" addrtype="ipv4"/>
+              <ports><port protocol="tcp" portid="80"><service name="http&bogus;"/></port></ports></host>
+            <host><status state="up"/><address addr="10.0.0.3" addrtype="ipv4"/></host>
+            <host><status state="up"/><address addr="10.0.0.4" addrtype="ipv4"/><ports><port protocol="tcp" portid="443"><st"#;
+
+        let (results, warnings, format) = parse_nmap_output(xml).unwrap();
+        assert_eq!(format, NmapOutputFormat::Xml);
+        let ips: Vec<&str> = results.hosts.iter().map(|h| h.ip_address.as_str()).collect();
+        assert_eq!(ips, vec!["10.0.0.1", "10.0.0.3"]);
+        assert!(warnings.iter().any(|w| w.starts_with("Skipped host 10.0.0.2")));
+        assert!(warnings.iter().any(|w| w.contains("hosts after this point were not imported")));
+    }
+
+    #[test]
+    fn test_locate_nmap_precedence() {
+        let dir = std::env::temp_dir().join(format!("optio-nmap-{}", Uuid::new_v4()));
+        let (configured, on_path, known) = (dir.join("configured"), dir.join("path"), dir.join("known"));
+        for sub in [&configured, &on_path, &known] {
+            std::fs::create_dir_all(sub).unwrap();
+            std::fs::write(sub.join(NMAP_BINARY), "").unwrap();
+        }
+        let configured_bin = configured.join(NMAP_BINARY).to_string_lossy().into_owned();
+        let path_var = std::env::join_paths([dir.join("empty"), on_path.clone()]).unwrap();
+        let known = [known.join(NMAP_BINARY)];
+
+        let found = locate_nmap_in(Some(&configured_bin), Some(&path_var), &known).unwrap();
+        assert_eq!(found, NmapLocation { path: configured_bin.clone(), source: NmapSource::Configured });
+
+        // A configured path that has gone away falls back to PATH
+        let missing = dir.join("missing").join(NMAP_BINARY).to_string_lossy().into_owned();
+        let found = locate_nmap_in(Some(&missing), Some(&path_var), &known).unwrap();
+        assert_eq!(found.source, NmapSource::Path);
+        assert_eq!(Path::new(&found.path), on_path.join(NMAP_BINARY));
+
+        let found = locate_nmap_in(None, None, &known).unwrap();
+        assert_eq!(found.source, NmapSource::KnownLocation);
+        assert!(locate_nmap_in(None, None, &[]).is_none());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_validate_nmap_binary_rejects_bogus_path() {
+        let dir = std::env::temp_dir().join(format!("optio-nmap-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir_str = dir.to_string_lossy().into_owned();
+
+        assert!(validate_nmap_binary(&format!("{}/nmap-missing", dir_str)).unwrap_err().contains("not a file"));
+        assert!(validate_nmap_binary(&dir_str).unwrap_err().contains("not a file"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let script = |name: &str, body: &str| {
+                let path = dir.join(name);
+                std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+                path.to_string_lossy().into_owned()
+            };
+
+            let impostor = script("impostor", "echo 'Python 3.12.1'");
+            assert!(validate_nmap_binary(&impostor).unwrap_err().contains("does not look like Nmap"));
+
+            let fake = script("nmap", "echo 'Nmap version 7.94 ( https://nmap.org )'");
+            assert_eq!(validate_nmap_binary(&fake).unwrap(), "7.94");
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,345 @@
+//! Scheduled Scans
+//!
+//! Timing and execution for recurring scans. Each run goes through Nmap,
+//! feeds every live host into the asset inventory (so asset history builds
+//! up between runs), stores its results and records its outcome on the
+//! schedule. A failed run is recorded, never propagated: the scheduler keeps
+//! going.
+
+use super::batch::execute_scan_job;
+use super::models::*;
+use crate::db::Database;
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
+use uuid::Uuid;
+
+/// Event emitted to the frontend when a scheduled scan finishes
+pub const SCHEDULED_SCAN_COMPLETED_EVENT: &str = "scheduled-scan-completed";
+
+/// How often the scheduler looks for due schedules
+pub const SCHEDULER_TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Shortest allowed interval, to keep recurring scans from flooding a network
+pub const MIN_INTERVAL_MINUTES: u32 = 15;
+
+/// How late a run can start and still count as on time. The scheduler
+/// checks every minute, so a run later than this came due while the app
+/// was closed or the machine was asleep.
+pub const MISSED_RUN_GRACE_MINUTES: i64 = 5;
+
+/// Payload of [`SCHEDULED_SCAN_COMPLETED_EVENT`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledScanCompleted {
+    pub schedule_id: String,
+    pub schedule_name: String,
+    pub client_id: String,
+    pub scan_id: String,
+    pub status: ScanStatus,
+    pub hosts_up: usize,
+    pub assets_updated: usize,
+    pub error: Option<String>,
+}
+
+/// Validate a schedule frequency
+pub fn validate_frequency(frequency: &ScheduleFrequency) -> Result<(), String> {
+    match *frequency {
+        ScheduleFrequency::Interval { minutes } if minutes < MIN_INTERVAL_MINUTES => Err(format!(
+            "Scan interval must be at least {} minutes",
+            MIN_INTERVAL_MINUTES
+        )),
+        ScheduleFrequency::Daily { hour, minute } if hour > 23 || minute > 59 => {
+            Err(format!("Invalid time of day: {:02}:{:02}", hour, minute))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// When a schedule is next due after `after`
+///
+/// Daily schedules use local time, so a nightly scan stays at the same
+/// wall-clock time across DST changes.
+pub fn next_run_after(frequency: &ScheduleFrequency, after: DateTime<Utc>) -> DateTime<Utc> {
+    match *frequency {
+        ScheduleFrequency::Interval { minutes } => after + Duration::minutes(minutes.max(1) as i64),
+        ScheduleFrequency::Daily { hour, minute } => {
+            let today = after.with_timezone(&Local).date_naive();
+            for days in 0..=2 {
+                // A time skipped by a DST change has no local instant; try the next day
+                let candidate = (today + Duration::days(days))
+                    .and_hms_opt(hour, minute, 0)
+                    .and_then(|t| Local.from_local_datetime(&t).earliest())
+                    .map(|t| t.with_timezone(&Utc));
+
+                if let Some(candidate) = candidate {
+                    if candidate > after {
+                        return candidate;
+                    }
+                }
+            }
+            after + Duration::days(1)
+        }
+    }
+}
+
+/// Whether a due schedule should run now, or its run was missed and skipped
+pub fn should_run(schedule: &ScanSchedule, now: DateTime<Utc>) -> bool {
+    if schedule.missed_runs == MissedRunPolicy::RunOnce {
+        return true;
+    }
+    match schedule.next_run.as_deref().and_then(|t| DateTime::parse_from_rfc3339(t).ok()) {
+        Some(due) => now - due.with_timezone(&Utc) <= Duration::minutes(MISSED_RUN_GRACE_MINUTES),
+        None => true,
+    }
+}
+
+/// New running scan job for one run of a schedule
+pub fn scheduled_job(schedule: &ScanSchedule) -> ScanJob {
+    let now = Utc::now();
+    ScanJob {
+        id: Uuid::new_v4().to_string(),
+        client_id: schedule.client_id.clone(),
+        name: format!("{} ({})", schedule.name, now.with_timezone(&Local).format("%Y-%m-%d %H:%M")),
+        config: schedule.config.clone(),
+        status: ScanStatus::Running,
+        created_at: now.to_rfc3339(),
+        started_at: Some(now.to_rfc3339()),
+        completed_at: None,
+        error: None,
+        progress: 0,
+        totals: None,
+        verification: None,
+        import: None,
+        warnings: vec![],
+        batches: None,
+        live: None,
+    }
+}
+
+/// Run a scheduled scan job to completion
+///
+/// The job must already be saved; see [`execute_scan_job`], which also
+/// describes `on_launch`. A failed run is logged and recorded on the job.
+/// Returns the completion event for the frontend.
+pub async fn execute_scheduled_job(
+    db: &Database,
+    schedule: &ScanSchedule,
+    job: &mut ScanJob,
+    on_launch: impl FnOnce(&ScanJob) -> Result<(), String>,
+) -> ScheduledScanCompleted {
+    let run = execute_scan_job(db, job, &AtomicBool::new(false), on_launch, |_| {}).await;
+    if let Some(e) = &job.error {
+        tracing::warn!("Scheduled scan \"{}\" failed: {}", schedule.name, e);
+    }
+
+    ScheduledScanCompleted {
+        schedule_id: schedule.id.clone(),
+        schedule_name: schedule.name.clone(),
+        client_id: schedule.client_id.clone(),
+        scan_id: job.id.clone(),
+        status: job.status,
+        hosts_up: run.hosts_up,
+        assets_updated: run.assets_updated,
+        error: job.error.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::repository::{init_network_schema, ScanProfileRepository, ScanRepository, ScanScheduleRepository};
+    use chrono::Timelike;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        init_network_schema(&db).unwrap();
+        db.conn.lock().unwrap().execute_batch(
+            "INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-1', 'Acme', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_next_run_after() {
+        let after = Utc::now();
+        let interval = next_run_after(&ScheduleFrequency::Interval { minutes: 90 }, after);
+        assert_eq!(interval - after, Duration::minutes(90));
+
+        let daily = next_run_after(&ScheduleFrequency::Daily { hour: 2, minute: 30 }, after);
+        assert!(daily > after && daily - after <= Duration::hours(25));
+        let local = daily.with_timezone(&Local);
+        assert_eq!((local.hour(), local.minute()), (2, 30));
+
+        assert!(validate_frequency(&ScheduleFrequency::Interval { minutes: 5 }).is_err());
+        assert!(validate_frequency(&ScheduleFrequency::Daily { hour: 24, minute: 0 }).is_err());
+    }
+
+    #[test]
+    fn test_due_schedules_and_run_recording() {
+        let db = test_db();
+        let repo = ScanScheduleRepository::new(&db);
+        let now = Utc::now();
+
+        let schedule = ScanSchedule {
+            id: "sched-1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "Nightly core".to_string(),
+            config: ScanConfig { targets: vec!["10.0.0.0/24".to_string()], ..Default::default() },
+            frequency: ScheduleFrequency::Daily { hour: 1, minute: 0 },
+            enabled: true,
+            missed_runs: MissedRunPolicy::RunOnce,
+            next_run: Some((now - Duration::minutes(1)).to_rfc3339()),
+            last_run: None,
+            last_status: None,
+            last_error: None,
+            last_scan_id: None,
+            created_at: now.to_rfc3339(),
+        };
+        repo.create(&schedule).unwrap();
+        assert_eq!(repo.list_due(&now.to_rfc3339()).unwrap().len(), 1);
+
+        let mut job = scheduled_job(&schedule);
+        job.status = ScanStatus::Failed;
+        job.error = Some("Nmap is not installed or not on PATH".to_string());
+        repo.record_run(&schedule.id, &job).unwrap();
+        repo.set_next_run(&schedule.id, &next_run_after(&schedule.frequency, now).to_rfc3339()).unwrap();
+        assert!(repo.list_due(&now.to_rfc3339()).unwrap().is_empty());
+
+        let stored = repo.get("sched-1").unwrap().unwrap();
+        assert_eq!(stored.last_status, Some(ScanStatus::Failed));
+        assert_eq!(stored.last_scan_id.as_deref(), Some(job.id.as_str()));
+        assert_eq!(stored.frequency, schedule.frequency);
+
+        repo.set_enabled("sched-1", false, None).unwrap();
+        let disabled = repo.get("sched-1").unwrap().unwrap();
+        assert!(!disabled.enabled && disabled.next_run.is_none());
+        assert_eq!(disabled.missed_runs, MissedRunPolicy::RunOnce);
+
+        let mut edited = disabled.clone();
+        edited.name = "Nightly core and DMZ".to_string();
+        edited.missed_runs = MissedRunPolicy::Skip;
+        edited.config.targets.push("10.0.1.0/24".to_string());
+        assert!(repo.update(&edited).unwrap());
+        let stored = repo.get("sched-1").unwrap().unwrap();
+        assert_eq!(stored.name, edited.name);
+        assert_eq!(stored.missed_runs, MissedRunPolicy::Skip);
+        assert_eq!(stored.config.targets.len(), 2);
+        assert_eq!(stored.last_scan_id.as_deref(), Some(job.id.as_str()));
+    }
+
+    #[test]
+    fn test_missed_run_policy() {
+        let now = Utc::now();
+        let mut schedule = ScanSchedule {
+            id: "sched-1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "Nightly core".to_string(),
+            config: ScanConfig::default(),
+            frequency: ScheduleFrequency::Daily { hour: 1, minute: 0 },
+            enabled: true,
+            missed_runs: MissedRunPolicy::Skip,
+            next_run: Some((now - Duration::minutes(1)).to_rfc3339()),
+            last_run: None,
+            last_status: None,
+            last_error: None,
+            last_scan_id: None,
+            created_at: now.to_rfc3339(),
+        };
+        assert!(should_run(&schedule, now));
+
+        // Due overnight while the app was closed
+        schedule.next_run = Some((now - Duration::hours(7)).to_rfc3339());
+        assert!(!should_run(&schedule, now));
+        schedule.missed_runs = MissedRunPolicy::RunOnce;
+        assert!(should_run(&schedule, now));
+    }
+
+    #[test]
+    fn test_scan_results_storage() {
+        let db = test_db();
+        let scans = ScanRepository::new(&db);
+        let schedule = ScanSchedule {
+            id: "sched-1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "Weekly DMZ".to_string(),
+            config: ScanConfig { targets: vec!["192.168.1.0/24".to_string()], ..Default::default() },
+            frequency: ScheduleFrequency::Interval { minutes: 60 },
+            enabled: true,
+            missed_runs: MissedRunPolicy::RunOnce,
+            next_run: None,
+            last_run: None,
+            last_status: None,
+            last_error: None,
+            last_scan_id: None,
+            created_at: Utc::now().to_rfc3339(),
+        };
+        let mut job = scheduled_job(&schedule);
+        scans.save(&job).unwrap();
+
+        let xml = include_str!("../../tests/fixtures/nmap/service_scripts.xml");
+        let mut results = super::super::scanner::parse_nmap_xml(xml).unwrap();
+        results.scan_id = job.id.clone();
+        scans.save_results(&job.id, &results, Some(xml)).unwrap();
+
+        // The list view gets the counts from the record, not the results
+        let listed = scans.list_by_client("client-1").unwrap();
+        assert_eq!(listed[0].totals, Some(ScanTotals::from_results(&results)));
+
+        // Updating the record keeps the stored results
+        job.status = ScanStatus::Completed;
+        job.totals = Some(ScanTotals::from_results(&results));
+        scans.save(&job).unwrap();
+        let stored = scans.get_results(&job.id).unwrap().unwrap();
+        assert_eq!(stored.hosts.len(), results.hosts.len());
+        assert_eq!(stored.hosts[0].ports.len(), results.hosts[0].ports.len());
+        assert_eq!(scans.get_raw_xml(&job.id).unwrap().as_deref(), Some(xml));
+        assert_eq!(scans.get(&job.id).unwrap().unwrap().status, ScanStatus::Completed);
+
+        assert!(scans.delete(&job.id).unwrap());
+        assert!(scans.get(&job.id).unwrap().is_none());
+        assert!(scans.get_results(&job.id).unwrap().is_none());
+        assert!(!scans.has_raw_xml(&job.id).unwrap());
+    }
+
+    #[test]
+    fn test_client_scan_profiles_are_deleted_with_the_client() {
+        let db = test_db();
+        db.conn.lock().unwrap().execute_batch(
+            "PRAGMA foreign_keys=ON;
+             INSERT INTO clients (id, name, created_at, updated_at) VALUES ('client-2', 'Globex', '2024-01-01', '2024-01-01');"
+        ).unwrap();
+        let profiles = ScanProfileRepository::new(&db);
+        let profile = |id: &str, client_id: Option<&str>| ScanProfile {
+            id: id.to_string(),
+            client_id: client_id.map(String::from),
+            name: id.to_string(),
+            config: ScanConfig { exclude_targets: Some(vec!["10.9.0.0/16".to_string()]), ..Default::default() },
+            notes: None,
+            created_at: Utc::now().to_rfc3339(),
+            updated_at: Utc::now().to_rfc3339(),
+        };
+        profiles.save(&profile("global", None)).unwrap();
+        profiles.save(&profile("acme", Some("client-1"))).unwrap();
+        profiles.save(&profile("globex", Some("client-2"))).unwrap();
+
+        let names = |client_id: Option<&str>| -> Vec<String> {
+            profiles.list(client_id).unwrap().into_iter().map(|p| p.name).collect()
+        };
+        assert_eq!(names(Some("client-1")), vec!["acme", "global"]);
+        assert_eq!(names(None), vec!["global"]);
+        assert_eq!(
+            profiles.get("acme").unwrap().unwrap().config.exclude_targets,
+            Some(vec!["10.9.0.0/16".to_string()])
+        );
+
+        db.conn.lock().unwrap().execute("DELETE FROM clients WHERE id = 'client-1'", []).unwrap();
+        assert!(profiles.get("acme").unwrap().is_none());
+        assert_eq!(names(Some("client-2")), vec!["global", "globex"]);
+    }
+}
@@ -0,0 +1,724 @@
+//! Scan Target Planning
+//!
+//! Works out, before a scan is launched, how many hosts its target spec
+//! touches. Targets may be CIDRs, dashed ranges (`192.168.1.1-100`,
+//! `10.0.0.1-10.0.0.50`, `10.0-3.*.1`), single addresses, hostnames and
+//! comma-separated lists of those. IPv4 targets become address ranges, so
+//! exclusions are subtracted and overlaps counted without expanding them;
+//! hostnames are counted as one host each and left unresolved. Ranges
+//! reaching public address space are flagged, as scanning those without the
+//! owner's authorization is a legal problem.
+
+use super::models::{ScanConfig, ScanType};
+use super::scanner::build_nmap_command;
+use ipnetwork::{IpNetwork, Ipv6Network};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Most address ranges one octet pattern (e.g. `10.*.*.1`) may produce
+const MAX_PATTERN_RANGES: u64 = 1 << 16;
+
+/// RFC 1918 private networks
+const RFC1918: [(u32, u32); 3] = [
+    (0x0A00_0000, 0x0AFF_FFFF), // 10.0.0.0/8
+    (0xAC10_0000, 0xAC1F_FFFF), // 172.16.0.0/12
+    (0xC0A8_0000, 0xC0A8_FFFF), // 192.168.0.0/16
+];
+
+/// Non-RFC 1918 space that still isn't someone's public host: "this"
+/// network, shared CGNAT space, loopback, link-local, multicast and reserved
+const SPECIAL_USE: [(u32, u32); 5] = [
+    (0x0000_0000, 0x00FF_FFFF), // 0.0.0.0/8
+    (0x6440_0000, 0x647F_FFFF), // 100.64.0.0/10
+    (0x7F00_0000, 0x7FFF_FFFF), // 127.0.0.0/8
+    (0xA9FE_0000, 0xA9FE_FFFF), // 169.254.0.0/16
+    (0xE000_0000, 0xFFFF_FFFF), // 224.0.0.0/3
+];
+
+/// What form a target takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TargetKind {
+    Ipv4,
+    Ipv6,
+    Cidr,
+    Range,
+    Hostname,
+}
+
+impl TargetKind {
+    /// Label shown when validating a target
+    pub fn label(&self) -> &'static str {
+        match self {
+            TargetKind::Ipv4 => "IPv4",
+            TargetKind::Ipv6 => "IPv6",
+            TargetKind::Cidr => "CIDR",
+            TargetKind::Range => "IP Range",
+            TargetKind::Hostname => "Hostname",
+        }
+    }
+}
+
+/// Hosts one target (or exclusion) covers
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TargetHosts {
+    /// Sorted, non-overlapping inclusive address ranges
+    V4(Vec<(u32, u32)>),
+    V6(Ipv6Network),
+    /// Lowercased, unresolved
+    Hostname(String),
+}
+
+/// A single parsed target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedTarget {
+    pub kind: TargetKind,
+    pub normalized: String,
+    hosts: TargetHosts,
+}
+
+impl ParsedTarget {
+    /// Number of hosts the target covers (saturating for huge IPv6 networks)
+    pub fn host_count(&self) -> u64 {
+        match &self.hosts {
+            TargetHosts::V4(ranges) => range_count(ranges),
+            TargetHosts::V6(net) => ipv6_host_count(net),
+            TargetHosts::Hostname(_) => 1,
+        }
+    }
+}
+
+/// Host count of one target in a plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetCount {
+    pub target: String,
+    pub kind: TargetKind,
+    /// Hosts the target covers before exclusions
+    pub hosts: u64,
+    /// Of those, hosts removed by the exclusions
+    pub excluded: u64,
+    /// Whether any of the remaining hosts are public addresses
+    pub public: bool,
+}
+
+/// A target and an exclusion that share hosts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetOverlap {
+    pub target: String,
+    pub exclude: String,
+    pub hosts: u64,
+}
+
+/// How many hosts a set of targets touches once exclusions are removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetPlan {
+    /// Distinct hosts scanned, after exclusions
+    pub total_hosts: u64,
+    /// Distinct hosts removed by the exclusions
+    pub excluded_hosts: u64,
+    /// Remaining hosts outside private and special-use address space
+    pub public_hosts: u64,
+    pub per_target_counts: Vec<TargetCount>,
+    pub overlaps: Vec<TargetOverlap>,
+    /// Every remaining host is an RFC 1918 address (false when hostnames or
+    /// IPv6 targets, which can't be checked, are present)
+    pub rfc1918_only: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Split a target entry into its comma- or whitespace-separated parts
+pub fn split_target_list(entry: &str) -> Vec<&str> {
+    entry
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Parse a single target: an address, CIDR, dashed range or hostname
+pub fn parse_target(target: &str) -> Result<ParsedTarget, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Target cannot be empty".to_string());
+    }
+    // Nmap would read it as an option
+    if target.starts_with('-') {
+        return Err("Target cannot start with '-'".to_string());
+    }
+
+    if target.contains('/') {
+        return match target.parse::<IpNetwork>() {
+            Ok(IpNetwork::V4(net)) => Ok(ParsedTarget {
+                kind: TargetKind::Cidr,
+                normalized: net.to_string(),
+                hosts: TargetHosts::V4(vec![(u32::from(net.network()), u32::from(net.broadcast()))]),
+            }),
+            Ok(IpNetwork::V6(net)) => Ok(ParsedTarget {
+                kind: TargetKind::Cidr,
+                normalized: net.to_string(),
+                hosts: TargetHosts::V6(net),
+            }),
+            Err(_) => Err("Invalid CIDR notation".to_string()),
+        };
+    }
+
+    match target.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            let ip = u32::from(ip);
+            return Ok(ParsedTarget {
+                kind: TargetKind::Ipv4,
+                normalized: target.to_string(),
+                hosts: TargetHosts::V4(vec![(ip, ip)]),
+            });
+        }
+        Ok(IpAddr::V6(ip)) => {
+            return Ok(ParsedTarget {
+                kind: TargetKind::Ipv6,
+                normalized: ip.to_string(),
+                hosts: TargetHosts::V6(Ipv6Network::new(ip, 128).map_err(|e| e.to_string())?),
+            });
+        }
+        Err(_) if target.contains(':') => return Err("Invalid IPv6 address".to_string()),
+        Err(_) => {}
+    }
+
+    if let Some((start, end)) = target.split_once('-') {
+        if let (Ok(start), Ok(end)) = (start.parse::<Ipv4Addr>(), end.parse::<Ipv4Addr>()) {
+            let (start, end) = (u32::from(start), u32::from(end));
+            if start > end {
+                return Err("Range start is after its end".to_string());
+            }
+            return Ok(ParsedTarget {
+                kind: TargetKind::Range,
+                normalized: target.to_string(),
+                hosts: TargetHosts::V4(vec![(start, end)]),
+            });
+        }
+    }
+
+    if target.chars().all(|c| c.is_ascii_digit() || c == '.' || c == '-' || c == '*') {
+        return Ok(ParsedTarget {
+            kind: TargetKind::Range,
+            normalized: target.to_string(),
+            hosts: TargetHosts::V4(parse_octet_pattern(target)?),
+        });
+    }
+
+    if is_hostname(target) {
+        return Ok(ParsedTarget {
+            kind: TargetKind::Hostname,
+            normalized: target.to_ascii_lowercase(),
+            hosts: TargetHosts::Hostname(target.to_ascii_lowercase()),
+        });
+    }
+
+    Err("Invalid target format".to_string())
+}
+
+/// Parse an Nmap octet pattern such as `192.168.1.1-100` or `10.0-3.*.1`
+fn parse_octet_pattern(target: &str) -> Result<Vec<(u32, u32)>, String> {
+    let octets: Vec<&str> = target.split('.').collect();
+    if octets.len() != 4 {
+        return Err("Invalid IPv4 address".to_string());
+    }
+    let mut spans = Vec::with_capacity(4);
+    for octet in octets {
+        let span = match octet {
+            "*" => (0u32, 255u32),
+            _ => match octet.split_once('-') {
+                Some((lo, hi)) => (parse_octet(lo)?, parse_octet(hi)?),
+                None => {
+                    let value = parse_octet(octet)?;
+                    (value, value)
+                }
+            },
+        };
+        if span.0 > span.1 {
+            return Err(format!("Octet range {} runs backwards", octet));
+        }
+        spans.push(span);
+    }
+
+    // Octets after the last partial one are full, so each combination of
+    // the octets before it is one contiguous range
+    let split = spans.iter().rposition(|&(lo, hi)| lo != 0 || hi != 255).unwrap_or(0);
+    let combinations: u64 = spans[..split].iter().map(|&(lo, hi)| (hi - lo + 1) as u64).product();
+    if combinations > MAX_PATTERN_RANGES {
+        return Err("Range pattern is too fragmented; use CIDRs instead".to_string());
+    }
+
+    let shift = |index: usize| 8 * (3 - index as u32);
+    let tail_mask = if split == 3 { 0 } else { (1u32 << shift(split)) - 1 };
+    let mut prefixes = vec![0u32];
+    for (index, &(lo, hi)) in spans[..split].iter().enumerate() {
+        prefixes = prefixes
+            .iter()
+            .flat_map(|prefix| (lo..=hi).map(move |value| prefix | (value << shift(index))))
+            .collect();
+    }
+    let (lo, hi) = spans[split];
+    Ok(prefixes
+        .into_iter()
+        .map(|prefix| (prefix | (lo << shift(split)), prefix | (hi << shift(split)) | tail_mask))
+        .collect())
+}
+
+fn parse_octet(value: &str) -> Result<u32, String> {
+    value
+        .parse::<u8>()
+        .map(u32::from)
+        .map_err(|_| format!("Invalid IPv4 octet: {}", if value.is_empty() { "(empty)" } else { value }))
+}
+
+/// RFC 1123 hostname whose last label isn't purely numeric
+fn is_hostname(target: &str) -> bool {
+    let target = target.strip_suffix('.').unwrap_or(target);
+    if target.is_empty() || target.len() > 253 {
+        return false;
+    }
+    let labels: Vec<&str> = target.split('.').collect();
+    labels.iter().all(|label| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    }) && !labels.last().is_some_and(|l| l.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Plan a scan of `targets` with `excludes` removed
+///
+/// Each entry may itself be a comma-separated list. The first invalid
+/// target or exclusion is reported as an error.
+pub fn expand_targets(targets: &[String], excludes: &[String]) -> Result<TargetPlan, String> {
+    let targets = parse_entries(targets, "target")?;
+    let excludes = parse_entries(excludes, "exclusion")?;
+
+    let excluded_v4 = merge_ranges(excludes.iter().flat_map(|e| v4_ranges(e)).collect());
+    let excluded_names: HashSet<&str> = excludes.iter().filter_map(hostname).collect();
+    let excluded_v6: Vec<&Ipv6Network> = excludes.iter().filter_map(v6_network).collect();
+    let is_v6_excluded = |net: &Ipv6Network| excluded_v6.iter().any(|e| e.contains(net.network()) && e.prefix() <= net.prefix());
+
+    let mut per_target_counts = Vec::new();
+    let mut overlaps = Vec::new();
+    for target in &targets {
+        let hosts = target.host_count();
+        let excluded = match &target.hosts {
+            TargetHosts::V4(ranges) => intersection_count(ranges, &excluded_v4),
+            TargetHosts::V6(net) if is_v6_excluded(net) => hosts,
+            TargetHosts::Hostname(name) if excluded_names.contains(name.as_str()) => 1,
+            _ => 0,
+        };
+        let remaining = subtract_ranges(v4_ranges(target), &excluded_v4);
+        per_target_counts.push(TargetCount {
+            target: target.normalized.clone(),
+            kind: target.kind,
+            hosts,
+            excluded,
+            public: range_count(&subtract_ranges(remaining, &non_public_ranges())) > 0,
+        });
+
+        for exclude in &excludes {
+            let shared = match (&target.hosts, &exclude.hosts) {
+                (TargetHosts::V4(a), TargetHosts::V4(b)) => intersection_count(a, b),
+                (TargetHosts::V6(net), TargetHosts::V6(e)) if e.contains(net.network()) && e.prefix() <= net.prefix() => hosts,
+                (TargetHosts::Hostname(a), TargetHosts::Hostname(b)) if a == b => 1,
+                _ => 0,
+            };
+            if shared > 0 {
+                overlaps.push(TargetOverlap {
+                    target: target.normalized.clone(),
+                    exclude: exclude.normalized.clone(),
+                    hosts: shared,
+                });
+            }
+        }
+    }
+
+    let all_v4 = merge_ranges(targets.iter().flat_map(|t| v4_ranges(t)).collect());
+    let remaining_v4 = subtract_ranges(all_v4.clone(), &excluded_v4);
+    let mut names: HashSet<&str> = targets.iter().filter_map(hostname).collect();
+    let listed_names = names.len() as u64;
+    names.retain(|name| !excluded_names.contains(name));
+    let mut networks: Vec<&Ipv6Network> = targets.iter().filter_map(v6_network).collect();
+    networks.sort_by_key(|net| (net.network(), net.prefix()));
+    networks.dedup();
+    let listed_v6: u64 = networks.iter().fold(0, |sum, net| sum.saturating_add(ipv6_host_count(net)));
+    networks.retain(|net| !is_v6_excluded(net));
+    let remaining_v6: u64 = networks.iter().fold(0, |sum, net| sum.saturating_add(ipv6_host_count(net)));
+
+    let total_hosts = range_count(&remaining_v4)
+        .saturating_add(names.len() as u64)
+        .saturating_add(remaining_v6);
+    let listed_hosts = range_count(&all_v4).saturating_add(listed_names).saturating_add(listed_v6);
+    let public_hosts = range_count(&subtract_ranges(remaining_v4.clone(), &non_public_ranges()));
+    let rfc1918_only = !remaining_v4.is_empty()
+        && names.is_empty()
+        && networks.is_empty()
+        && range_count(&subtract_ranges(remaining_v4, &RFC1918)) == 0;
+
+    let mut warnings = Vec::new();
+    if public_hosts > 0 {
+        let public_targets: Vec<&str> = per_target_counts
+            .iter()
+            .filter(|c| c.public)
+            .map(|c| c.target.as_str())
+            .collect();
+        warnings.push(format!(
+            "PUBLIC ADDRESSES: {} host{} ({}) {} outside private address space. Scanning systems without \
+             written authorization from their owner is illegal in many jurisdictions; confirm they are in scope",
+            group_thousands(public_hosts),
+            if public_hosts == 1 { "" } else { "s" },
+            public_targets.join(", "),
+            if public_hosts == 1 { "is" } else { "are" },
+        ));
+    }
+    let listed_separately: u64 = targets
+        .iter()
+        .filter(|t| v6_network(t).is_none())
+        .map(ParsedTarget::host_count)
+        .sum();
+    let listed_once = range_count(&all_v4).saturating_add(listed_names);
+    if listed_separately > listed_once {
+        warnings.push(format!(
+            "{} hosts are covered by more than one target; each is scanned once",
+            group_thousands(listed_separately - listed_once)
+        ));
+    }
+    for exclude in &excludes {
+        if !overlaps.iter().any(|o| o.exclude == exclude.normalized) {
+            warnings.push(format!("Exclusion {} doesn't overlap any target", exclude.normalized));
+        }
+    }
+    if !targets.is_empty() && total_hosts == 0 {
+        warnings.push("Every target is excluded; the scan would touch no hosts".to_string());
+    }
+    if !names.is_empty() {
+        warnings.push(format!(
+            "{} hostname{} counted as one host each; they are resolved when the scan runs",
+            names.len(),
+            if names.len() == 1 { " is" } else { "s are" }
+        ));
+    }
+
+    Ok(TargetPlan {
+        total_hosts,
+        excluded_hosts: listed_hosts.saturating_sub(total_hosts),
+        public_hosts,
+        per_target_counts,
+        overlaps,
+        rfc1918_only,
+        warnings,
+    })
+}
+
+/// Check every target and exclusion of a scan before it reaches Nmap
+///
+/// Each entry may be a comma-separated list; the first invalid target or
+/// exclusion is reported.
+pub fn check_scan_targets(config: &ScanConfig) -> Result<(), String> {
+    parse_entries(&config.targets, "target")?;
+    parse_entries(config.exclude_targets.as_deref().unwrap_or_default(), "exclusion")?;
+    Ok(())
+}
+
+fn parse_entries(entries: &[String], what: &str) -> Result<Vec<ParsedTarget>, String> {
+    entries
+        .iter()
+        .flat_map(|entry| split_target_list(entry))
+        .map(|target| parse_target(target).map_err(|e| format!("Invalid {} \"{}\": {}", what, target, e)))
+        .collect()
+}
+
+fn v4_ranges(target: &ParsedTarget) -> Vec<(u32, u32)> {
+    match &target.hosts {
+        TargetHosts::V4(ranges) => ranges.clone(),
+        _ => vec![],
+    }
+}
+
+fn hostname(target: &ParsedTarget) -> Option<&str> {
+    match &target.hosts {
+        TargetHosts::Hostname(name) => Some(name),
+        _ => None,
+    }
+}
+
+fn v6_network(target: &ParsedTarget) -> Option<&Ipv6Network> {
+    match &target.hosts {
+        TargetHosts::V6(net) => Some(net),
+        _ => None,
+    }
+}
+
+fn ipv6_host_count(net: &Ipv6Network) -> u64 {
+    match 128 - net.prefix() as u32 {
+        bits if bits >= 64 => u64::MAX,
+        bits => 1u64 << bits,
+    }
+}
+
+fn non_public_ranges() -> Vec<(u32, u32)> {
+    merge_ranges(RFC1918.iter().chain(SPECIAL_USE.iter()).copied().collect())
+}
+
+/// Sort ranges and join those that overlap or touch
+fn merge_ranges(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start as u64 <= last.1 as u64 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Remove `remove` from `ranges`
+fn subtract_ranges(ranges: Vec<(u32, u32)>, remove: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let remove = merge_ranges(remove.to_vec());
+    let mut remaining = Vec::new();
+    for (start, end) in merge_ranges(ranges) {
+        let mut next = start as u64;
+        for &(r_start, r_end) in remove.iter().filter(|&&(s, e)| e >= start && s <= end) {
+            if (r_start as u64) > next {
+                remaining.push((next as u32, r_start - 1));
+            }
+            next = next.max(r_end as u64 + 1);
+        }
+        if next <= end as u64 {
+            remaining.push((next as u32, end));
+        }
+    }
+    remaining
+}
+
+fn intersection_count(a: &[(u32, u32)], b: &[(u32, u32)]) -> u64 {
+    let a = merge_ranges(a.to_vec());
+    range_count(&a).saturating_sub(range_count(&subtract_ranges(a, b)))
+}
+
+fn range_count(ranges: &[(u32, u32)]) -> u64 {
+    ranges.iter().map(|&(start, end)| (end - start) as u64 + 1).sum()
+}
+
+/// Rough scan duration for a target plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanEstimate {
+    pub hosts: u64,
+    /// Nmap timing template (0-5) the scan runs at
+    pub timing_template: u8,
+    pub eta_seconds: u64,
+    /// e.g. "This will touch 4,094 hosts, ETA ~40 min at T3"
+    pub summary: String,
+}
+
+/// Timing template a scan's Nmap command uses (Nmap's default is T3)
+pub fn timing_template(config: &ScanConfig) -> u8 {
+    build_nmap_command(config)
+        .iter()
+        .rev()
+        .filter_map(|arg| arg.strip_prefix("-T"))
+        .find_map(|level| match level {
+            "0" | "paranoid" => Some(0),
+            "1" | "sneaky" => Some(1),
+            "2" | "polite" => Some(2),
+            "3" | "normal" => Some(3),
+            "4" | "aggressive" => Some(4),
+            "5" | "insane" => Some(5),
+            _ => None,
+        })
+        .unwrap_or(3)
+}
+
+/// Ports probed per host, from the port list or the scan type's default
+fn ports_per_host(config: &ScanConfig) -> u64 {
+    if let Some(ports) = config.ports.as_deref().filter(|p| !p.trim().is_empty()) {
+        let count: u64 = ports
+            .split(',')
+            .map(|part| part.trim().trim_start_matches(|c: char| c.is_ascii_alphabetic() || c == ':'))
+            .map(|part| match part.split_once('-') {
+                Some((lo, hi)) => {
+                    let lo = lo.parse::<u64>().unwrap_or(1);
+                    let hi = hi.parse::<u64>().unwrap_or(65535);
+                    hi.saturating_sub(lo) + 1
+                }
+                None => 1,
+            })
+            .sum();
+        return count.max(1);
+    }
+    match config.scan_type {
+        ScanType::PingSweep => 2,
+        ScanType::QuickScan | ScanType::UdpScan => 100,
+        ScanType::FullScan => 65535,
+        _ => 1000,
+    }
+}
+
+/// Estimate how long a scan of `hosts` hosts takes
+///
+/// A heuristic: seconds per host at T3 for the scan type, scaled by the
+/// ports scanned and the timing template, and never faster than the packet
+/// rate cap allows. Good for "minutes or days?", not for scheduling.
+pub fn estimate_scan(hosts: u64, config: &ScanConfig) -> ScanEstimate {
+    let (per_host, default_ports) = match config.scan_type {
+        ScanType::PingSweep => (0.05, 2.0),
+        ScanType::QuickScan => (0.6, 100.0),
+        ScanType::StandardScan => (2.0, 1000.0),
+        ScanType::FullScan => (40.0, 65535.0),
+        ScanType::ServiceDetection => (6.0, 1000.0),
+        ScanType::OsDetection => (4.0, 1000.0),
+        ScanType::VulnerabilityScan => (20.0, 1000.0),
+        ScanType::UdpScan => (15.0, 100.0),
+        ScanType::Custom => (2.0, 1000.0),
+    };
+    let ports = ports_per_host(config);
+    let per_host = per_host * (ports as f64 / default_ports).max(0.05);
+    let timing = timing_template(config);
+    let factor = [600.0, 30.0, 4.0, 1.0, 0.6, 0.4][timing as usize];
+
+    let mut seconds = hosts as f64 * per_host * factor;
+    if let Some(rate) = config.max_packets_per_second.filter(|r| *r > 0) {
+        seconds = seconds.max(hosts as f64 * ports as f64 / rate as f64);
+    }
+    let eta_seconds = seconds.ceil().min(u64::MAX as f64) as u64;
+
+    ScanEstimate {
+        hosts,
+        timing_template: timing,
+        eta_seconds,
+        summary: format!(
+            "This will touch {} host{}, ETA {} at T{}",
+            group_thousands(hosts),
+            if hosts == 1 { "" } else { "s" },
+            format_eta(eta_seconds),
+            timing
+        ),
+    }
+}
+
+fn format_eta(seconds: u64) -> String {
+    match seconds {
+        0..=59 => "under a minute".to_string(),
+        60..=7199 => format!("~{} min", (seconds + 30) / 60),
+        7200..=172_799 => format!("~{} h", (seconds + 1800) / 3600),
+        _ => format!("~{} days", (seconds + 43_200) / 86_400),
+    }
+}
+
+/// Format a number with thousands separators (4094 -> "4,094")
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(targets: &[&str], excludes: &[&str]) -> TargetPlan {
+        let targets: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+        let excludes: Vec<String> = excludes.iter().map(|t| t.to_string()).collect();
+        expand_targets(&targets, &excludes).unwrap()
+    }
+
+    #[test]
+    fn test_parse_target_forms() {
+        assert_eq!(parse_target("10.0.0.0/24").unwrap().host_count(), 256);
+        assert_eq!(parse_target("192.168.1.1-100").unwrap().host_count(), 100);
+        assert_eq!(parse_target("10.0.0.250-10.0.1.5").unwrap().host_count(), 12);
+        assert_eq!(parse_target("10.0-1.*.1").unwrap().host_count(), 512);
+        assert_eq!(parse_target("10.0.*.*").unwrap().host_count(), 65536);
+        assert_eq!(parse_target("fd00::1").unwrap().kind, TargetKind::Ipv6);
+        assert_eq!(parse_target("Host-1.Example.com").unwrap().normalized, "host-1.example.com");
+
+        assert!(parse_target("999.999.999.999/24").is_err());
+        assert!(parse_target("10.0.0.0/33").is_err());
+        assert!(parse_target("999.1.1.1").is_err());
+        assert!(parse_target("10.0.0.100-1").is_err());
+        assert!(parse_target("1.2.3").is_err());
+        assert!(parse_target("*.*.*.1").is_err());
+        assert!(parse_target("bad_host!").is_err());
+
+        // Nothing Nmap could take for an option
+        for option in ["-oN/etc/cron.d/x", "-iL/etc/passwd", "--datadir=/tmp/x", "-sS", "-1.2.3.4"] {
+            assert!(parse_target(option).is_err(), "{} should be rejected", option);
+        }
+        let config = ScanConfig {
+            targets: vec!["10.0.0.0/24, fd00::1".to_string()],
+            exclude_targets: Some(vec!["10.0.0.1,-iL/etc/passwd".to_string()]),
+            ..Default::default()
+        };
+        assert!(check_scan_targets(&config).unwrap_err().contains("exclusion"));
+        assert!(check_scan_targets(&ScanConfig { exclude_targets: None, ..config }).is_ok());
+    }
+
+    #[test]
+    fn test_exclusions_are_subtracted() {
+        let result = plan(&["192.168.0.0/20"], &["192.168.0.0/24", "192.168.15.255", "10.0.0.1"]);
+        assert_eq!(result.total_hosts, 4096 - 257);
+        assert_eq!(result.excluded_hosts, 257);
+        assert_eq!(result.per_target_counts[0].excluded, 257);
+        assert_eq!(result.overlaps.len(), 2);
+        assert!(result.rfc1918_only);
+        assert!(result.warnings.iter().any(|w| w.contains("10.0.0.1 doesn't overlap")));
+    }
+
+    #[test]
+    fn test_lists_overlaps_and_hostnames() {
+        let result = plan(&["10.0.0.1,10.0.0.2 10.0.0.0/30", "db.corp.local"], &["DB.corp.local"]);
+        assert_eq!(result.per_target_counts.len(), 4);
+        assert_eq!(result.total_hosts, 4);
+        assert!(result.rfc1918_only);
+        assert!(result.warnings.iter().any(|w| w.contains("2 hosts are covered by more than one target")));
+
+        let result = plan(&["10.0.0.0/24"], &["10.0.0.0/24"]);
+        assert_eq!(result.total_hosts, 0);
+        assert!(result.warnings.iter().any(|w| w.contains("Every target is excluded")));
+    }
+
+    #[test]
+    fn test_public_ranges_are_flagged() {
+        let result = plan(&["10.0.0.1", "8.8.8.0/30", "127.0.0.1"], &[]);
+        assert_eq!(result.public_hosts, 4);
+        assert!(!result.rfc1918_only);
+        assert!(result.per_target_counts[1].public);
+        assert!(!result.per_target_counts[2].public);
+        assert!(result.warnings[0].starts_with("PUBLIC ADDRESSES: 4 hosts (8.8.8.0/30)"));
+
+        assert_eq!(plan(&["8.8.8.8"], &["8.8.8.8"]).public_hosts, 0);
+        assert!(expand_targets(&["8.8.8.8/40".to_string()], &[]).is_err());
+    }
+
+    #[test]
+    fn test_estimate_scan() {
+        let config = ScanConfig {
+            scan_type: ScanType::QuickScan,
+            custom_args: Some("-T3".to_string()),
+            ..ScanConfig::default()
+        };
+        assert_eq!(timing_template(&ScanConfig::default()), 4);
+
+        let custom = ScanConfig { scan_type: ScanType::Custom, ..config };
+        let estimate = estimate_scan(4094, &ScanConfig { ports: Some("1-100".to_string()), ..custom.clone() });
+        assert_eq!(estimate.timing_template, 3);
+        assert_eq!(estimate.summary, "This will touch 4,094 hosts, ETA ~14 min at T3");
+
+        let capped = estimate_scan(10, &ScanConfig { max_packets_per_second: Some(1), ports: Some("1-100".to_string()), ..custom });
+        assert_eq!(capped.eta_seconds, 1000);
+        assert_eq!(group_thousands(1_234_567), "1,234,567");
+    }
+}
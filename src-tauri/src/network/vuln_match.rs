@@ -0,0 +1,749 @@
+//! Vulnerability Matching
+//!
+//! Matches the product versions detected on asset services against an
+//! offline CVE dataset. A small set of well-known CVEs is bundled; NVD JSON
+//! feed slices (or lists in the bundled format) can be imported to extend it,
+//! and a CISA Known Exploited Vulnerabilities (KEV) catalog can be imported
+//! to flag the matches that are being exploited in the wild.
+//!
+//! Service strings like "OpenSSH 8.9p1 Ubuntu 3ubuntu0.6" are reduced to a
+//! CPE-style product and version before matching. Versions compare by
+//! numeric and alphabetic runs, and a trailing letter suffix is treated as
+//! the same release as its base, so "8.9p1" matches an entry for "8.9".
+//! Services that can't be fingerprinted, or whose product the dataset
+//! doesn't know, are reported as unmatched rather than silently skipped.
+
+use super::models::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+/// CVEs bundled with the application, in the native import format
+const BUNDLED_VULN_DATA: &str = include_str!("vuln_data.json");
+
+/// Nmap product names mapped to CPE vendor and product
+const PRODUCT_ALIASES: &[(&str, &str, &str)] = &[
+    ("openssh", "openbsd", "openssh"),
+    ("nginx", "f5", "nginx"),
+    ("apache httpd", "apache", "http_server"),
+    ("apache tomcat", "apache", "tomcat"),
+    ("apache tomcat/coyote jsp engine", "apache", "tomcat"),
+    ("vsftpd", "beasts", "vsftpd"),
+    ("proftpd", "proftpd", "proftpd"),
+    ("exim smtpd", "exim", "exim"),
+    ("postfix smtpd", "postfix", "postfix"),
+    ("microsoft iis httpd", "microsoft", "internet_information_services"),
+    ("mysql", "oracle", "mysql"),
+    ("postgresql db", "postgresql", "postgresql"),
+    ("samba smbd", "samba", "samba"),
+    ("isc bind", "isc", "bind"),
+    ("dropbear sshd", "dropbear_ssh_project", "dropbear_ssh"),
+    ("lighttpd", "lighttpd", "lighttpd"),
+];
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// A CVE and the product versions it affects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VulnEntry {
+    pub cve_id: String,
+    pub summary: String,
+    /// CVSS base score (v3.x where available)
+    pub cvss_score: f64,
+    pub affected: Vec<AffectedProduct>,
+}
+
+/// Affected versions of one product: an exact version, or a range
+///
+/// A product with neither an exact version nor bounds is affected in every
+/// version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedProduct {
+    pub vendor: String,
+    pub product: String,
+    pub version: Option<String>,
+    pub start_including: Option<String>,
+    pub start_excluding: Option<String>,
+    pub end_including: Option<String>,
+    pub end_excluding: Option<String>,
+}
+
+impl AffectedProduct {
+    /// Whether `version` falls within the affected versions
+    pub fn matches(&self, version: &str) -> bool {
+        if let Some(ref exact) = self.version {
+            return compare_versions(version, exact) == Ordering::Equal;
+        }
+        let bounds = [
+            (&self.start_including, [Ordering::Greater, Ordering::Equal]),
+            (&self.start_excluding, [Ordering::Greater, Ordering::Greater]),
+            (&self.end_including, [Ordering::Less, Ordering::Equal]),
+            (&self.end_excluding, [Ordering::Less, Ordering::Less]),
+        ];
+        bounds.iter().all(|(bound, allowed)| match bound {
+            Some(bound) => allowed.contains(&compare_versions(version, bound)),
+            None => true,
+        })
+    }
+
+    /// The affected versions, e.g. ">= 8.5p1, < 9.8p1"
+    pub fn describe(&self) -> String {
+        if let Some(ref exact) = self.version {
+            return format!("= {}", exact);
+        }
+        let parts: Vec<String> = [
+            (">=", &self.start_including),
+            (">", &self.start_excluding),
+            ("<=", &self.end_including),
+            ("<", &self.end_excluding),
+        ]
+        .iter()
+        .filter_map(|(op, bound)| bound.as_ref().map(|b| format!("{} {}", op, b)))
+        .collect();
+
+        if parts.is_empty() {
+            "all versions".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// A service reduced to a CPE-style product and version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceFingerprint {
+    pub vendor: String,
+    pub product: String,
+    pub version: String,
+}
+
+/// A CVE matched to a service on an asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VulnerabilityMatch {
+    pub asset_id: String,
+    pub asset_name: String,
+    pub ip_address: String,
+    pub cve_id: String,
+    pub port: u16,
+    pub protocol: Protocol,
+    pub product: String,
+    pub version: String,
+    pub cvss_score: f64,
+    pub severity: Criticality,
+    pub summary: String,
+    /// Affected version range the service version fell in
+    pub matched_range: String,
+    pub matched_at: String,
+    /// Listed in the CISA Known Exploited Vulnerabilities catalog
+    #[serde(default)]
+    pub kev: bool,
+}
+
+/// A CVE from the CISA Known Exploited Vulnerabilities catalog
+///
+/// The catalog carries no version data, so it only flags CVEs that the
+/// dataset can match by version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownExploitedVuln {
+    pub cve_id: String,
+    pub vendor_project: String,
+    pub product: String,
+    pub name: String,
+    pub date_added: Option<String>,
+    /// Remediation due date set for US federal agencies
+    pub due_date: Option<String>,
+    pub ransomware: bool,
+}
+
+/// A service that couldn't be checked against the dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmatchedService {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub name: String,
+    /// Product/version string as detected, if any
+    pub detail: Option<String>,
+    pub reason: String,
+}
+
+/// Vulnerability matches for one asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetVulnerabilityReport {
+    pub asset_id: String,
+    pub asset_name: String,
+    pub ip_address: String,
+    /// Matches, highest CVSS first
+    pub matches: Vec<VulnerabilityMatch>,
+    pub unmatched: Vec<UnmatchedService>,
+    /// Open services that were fingerprinted and checked
+    pub services_checked: usize,
+}
+
+/// Entries loaded from an import file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VulnImportSummary {
+    pub entries: Vec<VulnEntry>,
+    #[serde(default)]
+    pub known_exploited: Vec<KnownExploitedVuln>,
+    /// CVEs that were skipped, with the reason
+    pub warnings: Vec<String>,
+}
+
+/// Severity band for a CVSS base score
+pub fn cvss_severity(score: f64) -> Criticality {
+    if score >= 9.0 {
+        Criticality::Critical
+    } else if score >= 7.0 {
+        Criticality::High
+    } else if score >= 4.0 {
+        Criticality::Medium
+    } else if score > 0.0 {
+        Criticality::Low
+    } else {
+        Criticality::Informational
+    }
+}
+
+// ============================================================================
+// Dataset
+// ============================================================================
+
+/// CVE dataset used for matching
+pub struct VulnDatabase {
+    entries: Vec<VulnEntry>,
+    products: HashSet<String>,
+    known_exploited: HashSet<String>,
+}
+
+impl VulnDatabase {
+    /// Build from entries; a later entry replaces an earlier one with the same CVE ID
+    pub fn new(entries: impl IntoIterator<Item = VulnEntry>) -> Self {
+        let by_id: BTreeMap<String, VulnEntry> = entries
+            .into_iter()
+            .map(|e| (e.cve_id.clone(), e))
+            .collect();
+        let entries: Vec<VulnEntry> = by_id.into_values().collect();
+        let products = entries
+            .iter()
+            .flat_map(|e| e.affected.iter().map(|a| a.product.clone()))
+            .collect();
+        VulnDatabase { entries, products, known_exploited: HashSet::new() }
+    }
+
+    /// The bundled CVEs overlaid with imported ones
+    pub fn with_imported(imported: Vec<VulnEntry>) -> Self {
+        Self::new(bundled_vuln_entries().into_iter().chain(imported))
+    }
+
+    /// Flag matches of these CVEs as known exploited
+    pub fn with_known_exploited(mut self, cve_ids: impl IntoIterator<Item = String>) -> Self {
+        self.known_exploited.extend(cve_ids);
+        self
+    }
+
+    /// Whether the dataset can match a CVE by version
+    pub fn contains(&self, cve_id: &str) -> bool {
+        self.entries.iter().any(|e| e.cve_id == cve_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check an asset's open services against the dataset
+    pub fn match_asset(&self, asset: &Asset) -> AssetVulnerabilityReport {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut matches = Vec::new();
+        let mut unmatched = Vec::new();
+        let mut services_checked = 0;
+
+        for service in asset.services.iter().filter(|s| matches!(s.state, PortState::Open)) {
+            let unmatched_service = |reason: &str| UnmatchedService {
+                port: service.port,
+                protocol: service.protocol,
+                name: service.name.clone(),
+                detail: service.version.clone(),
+                reason: reason.to_string(),
+            };
+
+            let fingerprint = match fingerprint_service(service) {
+                Some(fingerprint) => fingerprint,
+                None => {
+                    unmatched.push(unmatched_service("No product version detected"));
+                    continue;
+                }
+            };
+            if !self.products.contains(&fingerprint.product) {
+                unmatched.push(unmatched_service("Product not in vulnerability database"));
+                continue;
+            }
+            services_checked += 1;
+
+            for entry in &self.entries {
+                let hit = entry
+                    .affected
+                    .iter()
+                    .find(|a| a.product == fingerprint.product && a.matches(&fingerprint.version));
+                if let Some(affected) = hit {
+                    matches.push(VulnerabilityMatch {
+                        asset_id: asset.id.clone(),
+                        asset_name: asset.name.clone(),
+                        ip_address: asset.ip_address.clone(),
+                        cve_id: entry.cve_id.clone(),
+                        port: service.port,
+                        protocol: service.protocol,
+                        product: fingerprint.product.clone(),
+                        version: fingerprint.version.clone(),
+                        cvss_score: entry.cvss_score,
+                        severity: cvss_severity(entry.cvss_score),
+                        summary: entry.summary.clone(),
+                        matched_range: affected.describe(),
+                        matched_at: now.clone(),
+                        kev: self.known_exploited.contains(&entry.cve_id),
+                    });
+                }
+            }
+        }
+
+        // Known exploited first, then by CVSS
+        matches.sort_by(|a, b| {
+            b.kev.cmp(&a.kev).then(b.cvss_score.partial_cmp(&a.cvss_score).unwrap_or(Ordering::Equal))
+        });
+
+        AssetVulnerabilityReport {
+            asset_id: asset.id.clone(),
+            asset_name: asset.name.clone(),
+            ip_address: asset.ip_address.clone(),
+            matches,
+            unmatched,
+            services_checked,
+        }
+    }
+}
+
+/// The CVEs shipped with the application
+pub fn bundled_vuln_entries() -> Vec<VulnEntry> {
+    serde_json::from_str(BUNDLED_VULN_DATA).unwrap_or_else(|e| {
+        tracing::warn!("Failed to parse bundled vulnerability data: {}", e);
+        vec![]
+    })
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Parse a vulnerability dataset
+///
+/// Accepts an NVD CVE API 2.0 response or feed slice (an object with a
+/// `vulnerabilities` array), a CISA KEV catalog (whose `vulnerabilities`
+/// carry `cveID`), or a list of entries in the bundled format (bare, or
+/// under an `entries` key). From NVD data only vulnerable application CPEs
+/// are kept; CVEs without one or without a CVSS score are skipped with a
+/// warning.
+pub fn parse_vuln_json(data: &str) -> Result<VulnImportSummary, String> {
+    let value: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| format!("Invalid vulnerability data: {}", e))?;
+
+    let is_kev_catalog = value.get("catalogVersion").is_some()
+        || value.pointer("/vulnerabilities/0/cveID").is_some();
+
+    let summary = if is_kev_catalog {
+        parse_kev_catalog(&value)
+    } else if let Some(items) = value.get("vulnerabilities").and_then(|v| v.as_array()) {
+        let mut entries = Vec::new();
+        let mut warnings = Vec::new();
+        for item in items {
+            match parse_nvd_cve(item.get("cve").unwrap_or(item)) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => warnings.push(e),
+            }
+        }
+        VulnImportSummary { entries, known_exploited: vec![], warnings }
+    } else {
+        let list = value.get("entries").cloned().unwrap_or(value);
+        let entries: Vec<VulnEntry> = serde_json::from_value(list)
+            .map_err(|e| format!("Unrecognized vulnerability data: {}", e))?;
+        VulnImportSummary { entries, known_exploited: vec![], warnings: vec![] }
+    };
+
+    if summary.entries.is_empty() && summary.known_exploited.is_empty() {
+        let detail = summary.warnings.first().map(|w| format!(" ({})", w)).unwrap_or_default();
+        return Err(format!("No usable CVEs found{}", detail));
+    }
+    Ok(summary)
+}
+
+/// CVEs listed in a CISA KEV catalog
+fn parse_kev_catalog(value: &serde_json::Value) -> VulnImportSummary {
+    let mut known_exploited = Vec::new();
+    let mut warnings = Vec::new();
+    let items = value.get("vulnerabilities").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+
+    for item in &items {
+        let field = |key: &str| item.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        match field("cveID") {
+            Some(cve_id) => known_exploited.push(KnownExploitedVuln {
+                cve_id,
+                vendor_project: field("vendorProject").unwrap_or_default(),
+                product: field("product").unwrap_or_default(),
+                name: field("vulnerabilityName").unwrap_or_default(),
+                date_added: field("dateAdded"),
+                due_date: field("dueDate"),
+                ransomware: field("knownRansomwareCampaignUse").as_deref() == Some("Known"),
+            }),
+            None => warnings.push("KEV record without a cveID".to_string()),
+        }
+    }
+
+    VulnImportSummary { entries: vec![], known_exploited, warnings }
+}
+
+/// One CVE record from NVD JSON 2.0
+fn parse_nvd_cve(cve: &serde_json::Value) -> Result<VulnEntry, String> {
+    let cve_id = cve
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "CVE record without an id".to_string())?
+        .to_string();
+
+    let summary = cve
+        .get("descriptions")
+        .and_then(|d| d.as_array())
+        .and_then(|d| {
+            d.iter()
+                .find(|d| d.get("lang").and_then(|l| l.as_str()) == Some("en"))
+                .or_else(|| d.first())
+        })
+        .and_then(|d| d.get("value"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    // Prefer the newest CVSS version present
+    let cvss_score = ["cvssMetricV40", "cvssMetricV31", "cvssMetricV30", "cvssMetricV2"]
+        .iter()
+        .filter_map(|key| cve.pointer(&format!("/metrics/{}/0/cvssData/baseScore", key)))
+        .find_map(|score| score.as_f64())
+        .ok_or_else(|| format!("{}: no CVSS score", cve_id))?;
+
+    let mut affected = Vec::new();
+    let configurations = cve.get("configurations").and_then(|c| c.as_array()).cloned().unwrap_or_default();
+    for node in configurations.iter().flat_map(|c| c.get("nodes").and_then(|n| n.as_array()).cloned().unwrap_or_default()) {
+        for cpe_match in node.get("cpeMatch").and_then(|m| m.as_array()).cloned().unwrap_or_default() {
+            if cpe_match.get("vulnerable").and_then(|v| v.as_bool()) != Some(true) {
+                continue;
+            }
+            let criteria = cpe_match.get("criteria").and_then(|c| c.as_str()).unwrap_or_default();
+            let bound = |key: &str| cpe_match.get(key).and_then(|v| v.as_str()).map(str::to_string);
+            if let Some(mut product) = parse_cpe(criteria) {
+                product.start_including = bound("versionStartIncluding");
+                product.start_excluding = bound("versionStartExcluding");
+                product.end_including = bound("versionEndIncluding");
+                product.end_excluding = bound("versionEndExcluding");
+                affected.push(product);
+            }
+        }
+    }
+
+    if affected.is_empty() {
+        return Err(format!("{}: no vulnerable application CPEs", cve_id));
+    }
+
+    Ok(VulnEntry { cve_id, summary, cvss_score, affected })
+}
+
+/// Vendor, product and exact version from a CPE 2.3 application name
+///
+/// The CPE update field is appended to the version ("8.9" + "p1"), matching
+/// how services report it.
+fn parse_cpe(criteria: &str) -> Option<AffectedProduct> {
+    let fields: Vec<&str> = criteria.split(':').collect();
+    if fields.len() < 7 || fields[0] != "cpe" || fields[2] != "a" {
+        return None;
+    }
+    let concrete = |field: &str| field != "*" && field != "-";
+
+    let version = if concrete(fields[5]) {
+        let update = if concrete(fields[6]) { fields[6] } else { "" };
+        Some(format!("{}{}", fields[5].replace('\\', ""), update))
+    } else {
+        None
+    };
+
+    Some(AffectedProduct {
+        vendor: fields[3].to_string(),
+        product: fields[4].to_string(),
+        version,
+        start_including: None,
+        start_excluding: None,
+        end_including: None,
+        end_excluding: None,
+    })
+}
+
+// ============================================================================
+// Fingerprinting and Version Comparison
+// ============================================================================
+
+/// Product and version of a service, or None if no version was detected
+///
+/// The version is the first token of the detected string that starts with
+/// a digit; the words before it name the product (falling back to the
+/// service name), mapped to its CPE name where known.
+pub fn fingerprint_service(service: &AssetService) -> Option<ServiceFingerprint> {
+    let detail = service.version.as_deref()?;
+    let tokens: Vec<&str> = detail.split_whitespace().collect();
+    let index = tokens.iter().position(|t| t.starts_with(|c: char| c.is_ascii_digit()))?;
+    let version = tokens[index]
+        .trim_end_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_string();
+
+    let name = if index == 0 {
+        service.name.to_lowercase()
+    } else {
+        tokens[..index].join(" ").to_lowercase()
+    };
+
+    let (vendor, product) = PRODUCT_ALIASES
+        .iter()
+        .find(|(alias, _, _)| *alias == name)
+        .map(|(_, vendor, product)| (vendor.to_string(), product.to_string()))
+        .unwrap_or_else(|| {
+            let product = name.replace([' ', '-'], "_");
+            (product.clone(), product)
+        });
+
+    Some(ServiceFingerprint { vendor, product, version })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VersionPart<'a> {
+    Number(u64),
+    Text(&'a str),
+}
+
+/// Split a version into runs of digits and letters, dropping separators
+fn version_parts(version: &str) -> Vec<VersionPart<'_>> {
+    let mut parts = Vec::new();
+    let mut start = None;
+    let chars: Vec<(usize, char)> = version.char_indices().collect();
+
+    for (i, &(offset, c)) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            continue;
+        }
+        let begin = *start.get_or_insert(offset);
+        let next = chars.get(i + 1).map(|&(_, n)| n);
+        let run_ends = match next {
+            Some(n) => !n.is_ascii_alphanumeric() || n.is_ascii_digit() != c.is_ascii_digit(),
+            None => true,
+        };
+        if run_ends {
+            let run = &version[begin..offset + c.len_utf8()];
+            parts.push(match run.parse() {
+                Ok(n) => VersionPart::Number(n),
+                Err(_) => VersionPart::Text(run),
+            });
+            start = None;
+        }
+    }
+    parts
+}
+
+/// Compare two version strings
+///
+/// Numeric runs compare as numbers and letter runs case-insensitively.
+/// When one version is a prefix of the other, trailing zeros ("1.20.0" vs
+/// "1.20") and a trailing letter suffix ("8.9p1" vs "8.9") compare equal.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (version_parts(a), version_parts(b));
+
+    for i in 0..a.len().max(b.len()) {
+        match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => {
+                let ordering = compare_parts(*x, *y);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(_), None) => return tail_ordering(&a[i..]),
+            (None, Some(_)) => return tail_ordering(&b[i..]).reverse(),
+            (None, None) => break,
+        }
+    }
+    Ordering::Equal
+}
+
+fn compare_parts(a: VersionPart, b: VersionPart) -> Ordering {
+    match (a, b) {
+        (VersionPart::Number(x), VersionPart::Number(y)) => x.cmp(&y),
+        (VersionPart::Text(x), VersionPart::Text(y)) => x.to_lowercase().cmp(&y.to_lowercase()),
+        (VersionPart::Number(_), VersionPart::Text(_)) => Ordering::Greater,
+        (VersionPart::Text(_), VersionPart::Number(_)) => Ordering::Less,
+    }
+}
+
+/// How a version with extra trailing parts compares to its prefix
+fn tail_ordering(tail: &[VersionPart]) -> Ordering {
+    match tail.iter().find(|p| **p != VersionPart::Number(0)) {
+        None | Some(VersionPart::Text(_)) => Ordering::Equal,
+        Some(VersionPart::Number(_)) => Ordering::Greater,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(port: u16, name: &str, version: Option<&str>) -> AssetService {
+        AssetService {
+            port,
+            protocol: Protocol::Tcp,
+            name: name.to_string(),
+            version: version.map(str::to_string),
+            state: PortState::Open,
+        }
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("8.9p1", "8.9"), Ordering::Equal);
+        assert_eq!(compare_versions("8.9p1", "8.9p2"), Ordering::Less);
+        assert_eq!(compare_versions("8.9p1", "9.8p1"), Ordering::Less);
+        assert_eq!(compare_versions("1.20.0", "1.20"), Ordering::Equal);
+        assert_eq!(compare_versions("1.20.1", "1.20"), Ordering::Greater);
+        assert_eq!(compare_versions("2.4.10", "2.4.9"), Ordering::Greater);
+        assert_eq!(compare_versions("9.0.0.M1", "9.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_fingerprint_service() {
+        let ssh = fingerprint_service(&service(22, "ssh", Some("OpenSSH 8.9p1 Ubuntu 3ubuntu0.6"))).unwrap();
+        assert_eq!((ssh.vendor.as_str(), ssh.product.as_str(), ssh.version.as_str()), ("openbsd", "openssh", "8.9p1"));
+
+        let httpd = fingerprint_service(&service(80, "http", Some("Apache httpd 2.4.49 ((Unix))"))).unwrap();
+        assert_eq!((httpd.product.as_str(), httpd.version.as_str()), ("http_server", "2.4.49"));
+
+        // Version only: the service name stands in for the product
+        let pg = fingerprint_service(&service(5432, "PostgreSQL", Some("15.4"))).unwrap();
+        assert_eq!((pg.product.as_str(), pg.version.as_str()), ("postgresql", "15.4"));
+
+        assert!(fingerprint_service(&service(443, "https", Some("nginx"))).is_none());
+        assert!(fingerprint_service(&service(443, "https", None)).is_none());
+    }
+
+    #[test]
+    fn test_match_asset() {
+        let mut asset = crate::network::generate_demo_assets("client-1").remove(0);
+        asset.services = vec![
+            service(22, "SSH", Some("OpenSSH 8.9p1 Ubuntu 3ubuntu0.6")),
+            service(21, "FTP", Some("vsftpd 2.3.4")),
+            service(80, "HTTP", Some("nginx 1.24.0")),
+            service(443, "HTTPS", None),
+            service(8080, "http-proxy", Some("Squid http proxy 5.7")),
+        ];
+
+        let report = VulnDatabase::with_imported(vec![]).match_asset(&asset);
+        let cves: Vec<&str> = report.matches.iter().map(|m| m.cve_id.as_str()).collect();
+
+        assert!(cves.contains(&"CVE-2024-6387"));
+        assert!(cves.contains(&"CVE-2023-38408"));
+        assert!(cves.contains(&"CVE-2011-2523"));
+        assert!(!cves.contains(&"CVE-2021-23017"), "nginx 1.24 is past the fixed release");
+        assert!(!cves.contains(&"CVE-2018-15473"), "8.9p1 is past 7.7");
+        assert_eq!(report.matches[0].severity, Criticality::Critical);
+        assert_eq!(report.services_checked, 3);
+
+        let unmatched: Vec<(u16, &str)> = report.unmatched.iter().map(|u| (u.port, u.reason.as_str())).collect();
+        assert_eq!(unmatched, vec![
+            (443, "No product version detected"),
+            (8080, "Product not in vulnerability database"),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_nvd_json() {
+        let nvd = r#"{
+            "resultsPerPage": 2,
+            "vulnerabilities": [
+                {"cve": {
+                    "id": "CVE-2099-0001",
+                    "descriptions": [{"lang": "es", "value": "Descripcion"}, {"lang": "en", "value": "Test flaw"}],
+                    "metrics": {"cvssMetricV31": [{"cvssData": {"baseScore": 8.8}}]},
+                    "configurations": [{"nodes": [{"cpeMatch": [
+                        {"vulnerable": true, "criteria": "cpe:2.3:a:openbsd:openssh:*:*:*:*:*:*:*:*", "versionEndExcluding": "9.0"},
+                        {"vulnerable": true, "criteria": "cpe:2.3:a:openbsd:openssh:9.1:p1:*:*:*:*:*:*"},
+                        {"vulnerable": false, "criteria": "cpe:2.3:o:linux:linux_kernel:*:*:*:*:*:*:*:*"}
+                    ]}]}]
+                }},
+                {"cve": {"id": "CVE-2099-0002", "descriptions": [], "metrics": {}}}
+            ]
+        }"#;
+        let summary = parse_vuln_json(nvd).unwrap();
+        assert_eq!(summary.entries.len(), 1);
+        assert_eq!(summary.warnings, vec!["CVE-2099-0002: no CVSS score".to_string()]);
+
+        let entry = &summary.entries[0];
+        assert_eq!(entry.summary, "Test flaw");
+        assert_eq!(entry.affected.len(), 2);
+        assert_eq!(entry.affected[0].describe(), "< 9.0");
+        assert_eq!(entry.affected[1].version.as_deref(), Some("9.1p1"));
+        assert!(entry.affected[1].matches("9.1p1"));
+
+        // Native format, as bundled
+        let native = parse_vuln_json(r#"{"entries": [{"cveId": "CVE-2099-0003", "summary": "x", "cvssScore": 5.0,
+            "affected": [{"vendor": "f5", "product": "nginx", "startIncluding": "1.0"}]}]}"#).unwrap();
+        assert_eq!(native.entries[0].affected[0].describe(), ">= 1.0");
+
+        assert!(parse_vuln_json("[]").is_err());
+        assert!(bundled_vuln_entries().len() >= 10);
+    }
+
+    #[test]
+    fn test_kev_catalog_flags_matches() {
+        let kev = r#"{
+            "title": "CISA Catalog of Known Exploited Vulnerabilities",
+            "catalogVersion": "2024.07.10",
+            "vulnerabilities": [
+                {"cveID": "CVE-2011-2523", "vendorProject": "vsftpd", "product": "vsftpd",
+                 "vulnerabilityName": "vsftpd Backdoor", "dateAdded": "2023-01-01", "dueDate": "2023-01-22",
+                 "knownRansomwareCampaignUse": "Unknown"},
+                {"cveID": "CVE-2099-9999", "vendorProject": "Acme", "product": "Widget",
+                 "knownRansomwareCampaignUse": "Known"}
+            ]
+        }"#;
+        let summary = parse_vuln_json(kev).unwrap();
+        assert!(summary.entries.is_empty());
+        assert_eq!(summary.known_exploited.len(), 2);
+        assert!(summary.known_exploited[1].ransomware);
+
+        let dataset = VulnDatabase::with_imported(vec![])
+            .with_known_exploited(summary.known_exploited.iter().map(|k| k.cve_id.clone()));
+        assert!(!dataset.contains("CVE-2099-9999"));
+
+        let mut asset = crate::network::generate_demo_assets("client-1").remove(0);
+        asset.services = vec![
+            service(22, "SSH", Some("OpenSSH 8.9p1")),
+            service(21, "FTP", Some("vsftpd 2.3.4")),
+        ];
+        let report = dataset.match_asset(&asset);
+        assert_eq!(report.matches[0].cve_id, "CVE-2011-2523", "known exploited CVEs sort first");
+        assert!(report.matches[0].kev);
+        assert!(report.matches.iter().skip(1).all(|m| !m.kev));
+    }
+}
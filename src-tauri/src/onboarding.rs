@@ -0,0 +1,466 @@
+//! First-Run Onboarding
+//!
+//! Backend-driven onboarding checklist. Each milestone is defined once in
+//! [`get_milestone_definitions`] together with the check that completes it,
+//! so adding a milestone is a contained change to this module.
+//!
+//! Milestones backed by persisted data are evaluated with cheap existence
+//! queries; milestones for actions that leave no durable trace (nmap checks,
+//! scans) are recorded by the commands that perform them. Any milestone can
+//! also be recorded directly, e.g. a report exported straight to PDF. Either way a milestone latches once reached, so deleting the client
+//! that completed it does not put onboarding back.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// Initialize onboarding schema
+pub fn init_onboarding_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Completed onboarding milestones
+        CREATE TABLE IF NOT EXISTS onboarding_progress (
+            milestone_id TEXT PRIMARY KEY,
+            completed_at TEXT NOT NULL
+        );
+
+        -- Single-row onboarding state (dismissal flag)
+        CREATE TABLE IF NOT EXISTS onboarding_state (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            dismissed_at TEXT
+        );
+    "#)?;
+
+    tracing::info!("Onboarding schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Milestone Definitions
+// ============================================================================
+
+/// Onboarding milestones, in the order a new consultant should reach them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Milestone {
+    /// Created the first client profile
+    FirstClient,
+    /// Resolved the consultant's identity/callback address
+    ConsultantProfile,
+    /// Confirmed nmap is installed
+    NmapValidated,
+    /// Ran (or imported) the first network scan
+    FirstScan,
+    /// Created the first GRC assessment
+    FirstAssessment,
+    /// Assessed at least ten controls
+    TenControlsAssessed,
+    /// Generated the first report
+    FirstReport,
+}
+
+impl Milestone {
+    /// Stable identifier stored in `onboarding_progress`
+    pub fn id(&self) -> &'static str {
+        match self {
+            Milestone::FirstClient => "first_client",
+            Milestone::ConsultantProfile => "consultant_profile",
+            Milestone::NmapValidated => "nmap_validated",
+            Milestone::FirstScan => "first_scan",
+            Milestone::FirstAssessment => "first_assessment",
+            Milestone::TenControlsAssessed => "ten_controls_assessed",
+            Milestone::FirstReport => "first_report",
+        }
+    }
+}
+
+/// How a milestone is completed
+#[derive(Debug, Clone, Copy)]
+pub enum MilestoneCheck {
+    /// Evaluated from persisted data with a cheap existence query
+    Query(fn(&Connection) -> rusqlite::Result<bool>),
+    /// Recorded by the command that performs the action
+    Event,
+}
+
+/// Definition of an onboarding milestone
+#[derive(Debug, Clone)]
+pub struct MilestoneDefinition {
+    pub milestone: Milestone,
+    pub title: &'static str,
+    /// Short next-step hint shown while the milestone is incomplete
+    pub hint: &'static str,
+    /// Frontend view the hint deep-links to
+    pub link: &'static str,
+    pub check: MilestoneCheck,
+}
+
+/// Number of assessed controls required for [`Milestone::TenControlsAssessed`]
+pub const CONTROLS_ASSESSED_TARGET: i64 = 10;
+
+/// Get all onboarding milestone definitions, in display order
+pub fn get_milestone_definitions() -> Vec<MilestoneDefinition> {
+    vec![
+        MilestoneDefinition {
+            milestone: Milestone::FirstClient,
+            title: "Create your first client",
+            hint: "Add a client profile with its target subnet and contact details.",
+            link: "clients",
+            check: MilestoneCheck::Query(has_client),
+        },
+        MilestoneDefinition {
+            milestone: Milestone::ConsultantProfile,
+            title: "Configure your consultant profile",
+            hint: "Choose the consultant IP that generated scripts report back to.",
+            link: "clients",
+            check: MilestoneCheck::Query(has_consultant_ip),
+        },
+        MilestoneDefinition {
+            milestone: Milestone::NmapValidated,
+            title: "Validate nmap",
+            hint: "Check that nmap is installed and on the PATH.",
+            link: "network",
+            check: MilestoneCheck::Event,
+        },
+        MilestoneDefinition {
+            milestone: Milestone::FirstScan,
+            title: "Run your first scan",
+            hint: "Scan a client subnet, or import existing scan results.",
+            link: "network",
+            check: MilestoneCheck::Event,
+        },
+        MilestoneDefinition {
+            milestone: Milestone::FirstAssessment,
+            title: "Create your first assessment",
+            hint: "Start a compliance assessment against a framework for a client.",
+            link: "grc",
+            check: MilestoneCheck::Query(has_assessment),
+        },
+        MilestoneDefinition {
+            milestone: Milestone::TenControlsAssessed,
+            title: "Assess 10 controls",
+            hint: "Record a compliance status for at least ten controls.",
+            link: "grc",
+            check: MilestoneCheck::Query(has_assessed_controls),
+        },
+        MilestoneDefinition {
+            milestone: Milestone::FirstReport,
+            title: "Generate your first report",
+            hint: "Generate a report or executive PDF from the Reporting view.",
+            link: "reporting",
+            check: MilestoneCheck::Query(has_report),
+        },
+    ]
+}
+
+fn has_client(conn: &Connection) -> rusqlite::Result<bool> {
+    conn.query_row("SELECT EXISTS(SELECT 1 FROM clients)", [], |row| row.get(0))
+}
+
+fn has_consultant_ip(conn: &Connection) -> rusqlite::Result<bool> {
+    conn.query_row("SELECT EXISTS(SELECT 1 FROM consultant_ip_preferences)", [], |row| row.get(0))
+}
+
+fn has_assessment(conn: &Connection) -> rusqlite::Result<bool> {
+    conn.query_row("SELECT EXISTS(SELECT 1 FROM assessments)", [], |row| row.get(0))
+}
+
+fn has_report(conn: &Connection) -> rusqlite::Result<bool> {
+    conn.query_row("SELECT EXISTS(SELECT 1 FROM reports)", [], |row| row.get(0))
+}
+
+fn has_assessed_controls(conn: &Connection) -> rusqlite::Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM (SELECT 1 FROM control_assessments WHERE status != 'NotAssessed' LIMIT ?1)",
+        params![CONTROLS_ASSESSED_TARGET],
+        |row| row.get(0),
+    )?;
+    Ok(count >= CONTROLS_ASSESSED_TARGET)
+}
+
+// ============================================================================
+// Status
+// ============================================================================
+
+/// State of a single milestone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneStatus {
+    /// Milestone identifier
+    pub id: String,
+    /// Display title
+    pub title: String,
+    /// Next-step hint
+    pub hint: String,
+    /// Frontend view to deep-link to
+    pub link: String,
+    /// Whether the milestone has been reached
+    pub completed: bool,
+    /// When the milestone was reached
+    pub completed_at: Option<String>,
+}
+
+/// Overall onboarding status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStatus {
+    /// Milestones in display order
+    pub milestones: Vec<MilestoneStatus>,
+    /// Number of completed milestones
+    pub completed_count: usize,
+    /// Total number of milestones
+    pub total_count: usize,
+    /// First incomplete milestone, if any
+    pub next_milestone: Option<String>,
+    /// Whether the user dismissed onboarding
+    pub dismissed: bool,
+    /// Whether onboarding should be shown (not dismissed and not finished)
+    pub visible: bool,
+}
+
+fn completed_at(conn: &Connection, milestone: Milestone) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT completed_at FROM onboarding_progress WHERE milestone_id = ?1",
+        params![milestone.id()],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+fn latch(conn: &Connection, milestone: Milestone) -> rusqlite::Result<String> {
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT OR IGNORE INTO onboarding_progress (milestone_id, completed_at) VALUES (?1, ?2)",
+        params![milestone.id(), now],
+    )?;
+    Ok(completed_at(conn, milestone)?.unwrap_or(now))
+}
+
+/// Evaluate a milestone, latching it if its check now passes
+fn evaluate(conn: &Connection, def: &MilestoneDefinition) -> rusqlite::Result<Option<String>> {
+    if let Some(at) = completed_at(conn, def.milestone)? {
+        return Ok(Some(at));
+    }
+
+    match def.check {
+        MilestoneCheck::Query(check) if check(conn)? => Ok(Some(latch(conn, def.milestone)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Evaluate every milestone and return the onboarding status
+pub fn get_status(db: &Database) -> OptioResult<OnboardingStatus> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    let mut milestones = Vec::new();
+    for def in get_milestone_definitions() {
+        let completed_at = evaluate(&conn, &def)?;
+        milestones.push(MilestoneStatus {
+            id: def.milestone.id().to_string(),
+            title: def.title.to_string(),
+            hint: def.hint.to_string(),
+            link: def.link.to_string(),
+            completed: completed_at.is_some(),
+            completed_at,
+        });
+    }
+
+    let dismissed: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM onboarding_state WHERE id = 1 AND dismissed_at IS NOT NULL)",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let completed_count = milestones.iter().filter(|m| m.completed).count();
+    let total_count = milestones.len();
+    let next_milestone = milestones.iter().find(|m| !m.completed).map(|m| m.id.clone());
+
+    Ok(OnboardingStatus {
+        visible: !dismissed && completed_count < total_count,
+        milestones,
+        completed_count,
+        total_count,
+        next_milestone,
+        dismissed,
+    })
+}
+
+/// Permanently hide onboarding
+pub fn dismiss(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    conn.execute(
+        "INSERT INTO onboarding_state (id, dismissed_at) VALUES (1, ?1)
+         ON CONFLICT(id) DO UPDATE SET dismissed_at = COALESCE(dismissed_at, excluded.dismissed_at)",
+        params![Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+// ============================================================================
+// Advancement
+// ============================================================================
+
+/// Re-check data-backed milestones after a command that may complete them
+///
+/// Advancement is best-effort: failures are logged and never fail the
+/// calling command.
+pub fn advance(db: &Database, milestones: &[Milestone]) {
+    if let Err(e) = try_advance(db, milestones) {
+        tracing::warn!("Failed to update onboarding progress: {}", e);
+    }
+}
+
+fn try_advance(db: &Database, milestones: &[Milestone]) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+    for def in get_milestone_definitions()
+        .iter()
+        .filter(|d| milestones.contains(&d.milestone))
+    {
+        evaluate(&conn, def)?;
+    }
+    Ok(())
+}
+
+/// Record an event milestone performed by a command
+pub fn record(db: &Database, milestone: Milestone) {
+    let result = db
+        .conn
+        .lock()
+        .map_err(|e| OptioError::Database(e.to_string()))
+        .and_then(|conn| latch(&conn, milestone).map_err(OptioError::from));
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to record onboarding milestone {}: {}", milestone.id(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn seeded_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::grc::repository::init_grc_schema(&db).unwrap();
+        crate::reporting::repository::init_reporting_schema(&db).unwrap();
+        init_onboarding_schema(&db).unwrap();
+        db
+    }
+
+    fn seed(db: &Database, sql: &str) {
+        db.conn.lock().unwrap().execute_batch(sql).unwrap();
+    }
+
+    fn seed_controls(db: &Database, count: usize, status: &str) {
+        let conn = db.conn.lock().unwrap();
+        for i in 0..count {
+            conn.execute(
+                "INSERT INTO control_assessments (id, assessment_id, control_id, status, assessed_at, assessed_by)
+                 VALUES (?1, 'a1', ?2, ?3, '2026-01-01T00:00:00Z', 'tester')",
+                params![format!("{}-{}", status, i), format!("{}-{}", status, i), status],
+            )
+            .unwrap();
+        }
+    }
+
+    fn completed(status: &OnboardingStatus) -> Vec<&str> {
+        status.milestones.iter().filter(|m| m.completed).map(|m| m.id.as_str()).collect()
+    }
+
+    const CLIENT: &str = "INSERT INTO clients (id, name, created_at, updated_at)
+        VALUES ('c1', 'Acme', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z');";
+    const CONSULTANT_IP: &str = "INSERT INTO consultant_ip_preferences (client_id, ip, updated_at)
+        VALUES ('c1', '10.0.0.5', '2026-01-01T00:00:00Z');";
+    const ASSESSMENT: &str = "INSERT INTO assessments (id, client_id, name, framework, started_at, lead_assessor, status)
+        VALUES ('a1', 'c1', 'Baseline', 'NistCsf2', '2026-01-01T00:00:00Z', 'tester', 'Draft');";
+    const REPORT: &str = "INSERT INTO reports (id, client_id, report_type, title, client_name, status, format, config, created_at, updated_at)
+        VALUES ('r1', 'c1', 'ExecutiveSummary', 'Summary', 'Acme', 'Ready', 'Pdf', '{}', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z');";
+
+    #[test]
+    fn test_fresh_database() {
+        let db = seeded_db();
+        let status = get_status(&db).unwrap();
+        assert_eq!(status.completed_count, 0);
+        assert_eq!(status.total_count, get_milestone_definitions().len());
+        assert_eq!(status.next_milestone.as_deref(), Some("first_client"));
+        assert!(status.visible);
+    }
+
+    #[test]
+    fn test_query_milestones_follow_data() {
+        let db = seeded_db();
+        seed(&db, CLIENT);
+        seed(&db, ASSESSMENT);
+        seed_controls(&db, 9, "Compliant");
+        seed_controls(&db, 5, "NotAssessed");
+        assert_eq!(completed(&get_status(&db).unwrap()), vec!["first_client", "first_assessment"]);
+
+        seed_controls(&db, 1, "NonCompliant");
+        let status = get_status(&db).unwrap();
+        assert_eq!(completed(&status), vec!["first_client", "first_assessment", "ten_controls_assessed"]);
+        assert_eq!(status.next_milestone.as_deref(), Some("consultant_profile"));
+
+        seed(&db, CONSULTANT_IP);
+        assert_eq!(get_status(&db).unwrap().next_milestone.as_deref(), Some("nmap_validated"));
+    }
+
+    #[test]
+    fn test_milestones_latch() {
+        let db = seeded_db();
+        seed(&db, CLIENT);
+        advance(&db, &[Milestone::FirstClient]);
+        seed(&db, "DELETE FROM clients;");
+        assert_eq!(completed(&get_status(&db).unwrap()), vec!["first_client"]);
+    }
+
+    #[test]
+    fn test_advance_only_checks_requested_milestones() {
+        let db = seeded_db();
+        seed(&db, CLIENT);
+        seed(&db, ASSESSMENT);
+        advance(&db, &[Milestone::FirstAssessment]);
+        seed(&db, "DELETE FROM clients;");
+        assert_eq!(completed(&get_status(&db).unwrap()), vec!["first_assessment"]);
+    }
+
+    #[test]
+    fn test_events_and_dismissal() {
+        let db = seeded_db();
+        record(&db, Milestone::NmapValidated);
+        record(&db, Milestone::NmapValidated);
+        let status = get_status(&db).unwrap();
+        assert_eq!(completed(&status), vec!["nmap_validated"]);
+        assert!(status.visible);
+
+        dismiss(&db).unwrap();
+        let status = get_status(&db).unwrap();
+        assert!(status.dismissed);
+        assert!(!status.visible);
+    }
+
+    #[test]
+    fn test_hidden_once_complete() {
+        let db = seeded_db();
+        seed(&db, CLIENT);
+        seed(&db, CONSULTANT_IP);
+        seed(&db, ASSESSMENT);
+        seed_controls(&db, 10, "Compliant");
+        seed(&db, REPORT);
+        for def in get_milestone_definitions() {
+            if let MilestoneCheck::Event = def.check {
+                record(&db, def.milestone);
+            }
+        }
+        let status = get_status(&db).unwrap();
+        assert_eq!(status.completed_count, status.total_count);
+        assert_eq!(status.next_milestone, None);
+        assert!(!status.dismissed);
+        assert!(!status.visible);
+    }
+}
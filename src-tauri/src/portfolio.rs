@@ -0,0 +1,540 @@
+//! Client Portfolio
+//!
+//! One row per client for a view across every engagement: the latest
+//! compliance of each framework, an overall risk rating, open critical and
+//! high findings, time since the last scan, overdue remediation and a
+//! composite health score. The figures come from a few aggregate queries
+//! covering all clients at once, not from loading each client's dashboard.
+//!
+//! # Health score
+//!
+//! A score from 0 to 100, higher is healthier, adding up four parts that
+//! never go below zero:
+//!
+//! - Compliance, up to 40: the average latest compliance across frameworks
+//!   times 0.4; nothing for a client without assessments
+//! - Findings, up to 30: 30, less 10 per open critical and 5 per open high
+//!   finding
+//! - Scanning, up to 15: 15 while the last completed scan is at most 30 days
+//!   old, falling evenly to nothing at 120 days; nothing if never scanned
+//! - Remediation, up to 15: 15, less 3 per overdue remediation item
+
+use crate::db::{ClientRepository, Database};
+use crate::error::OptioResult;
+use crate::findings::library::FindingRepository;
+use crate::grc::frameworks::get_framework_controls;
+use crate::grc::models::{AssessmentResultCounts, ComplianceStatus, Framework, RiskSummary};
+use crate::grc::remediation::{RemediationPlanRepository, RemediationPriority};
+use crate::grc::repository::{parse_datetime, AssessmentRepository};
+use crate::grc::risk::{summarize_risk_counts, RiskLevel};
+use crate::network::models::Criticality;
+use crate::network::repository::{NetworkFindingRepository, ScanRepository};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+const COMPLIANCE_WEIGHT: f64 = 0.4;
+const FINDINGS_POINTS: f64 = 30.0;
+const CRITICAL_FINDING_PENALTY: f64 = 10.0;
+const HIGH_FINDING_PENALTY: f64 = 5.0;
+const SCAN_POINTS: f64 = 15.0;
+/// Days a scan stays fully current
+const SCAN_FRESH_DAYS: i64 = 30;
+/// Days after which a scan counts for nothing
+const SCAN_STALE_DAYS: i64 = 120;
+const REMEDIATION_POINTS: f64 = 15.0;
+const OVERDUE_ITEM_PENALTY: f64 = 3.0;
+
+/// Latest compliance of one framework for a client
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioFramework {
+    pub framework: Framework,
+    pub assessment_id: String,
+    pub started_at: DateTime<Utc>,
+    pub overall_compliance: f64,
+}
+
+/// A client's row in the portfolio
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioClient {
+    pub client_id: String,
+    pub client_name: String,
+    /// Latest non-archived assessment of each framework
+    pub frameworks: Vec<PortfolioFramework>,
+    /// Mean compliance of `frameworks`; None without assessments
+    pub average_compliance: Option<f64>,
+    /// Open findings and compliance gaps of the latest assessments, by severity
+    pub risk: RiskSummary,
+    /// None when the client has no assessment, finding or completed scan yet
+    pub overall_risk: Option<RiskLevel>,
+    pub open_critical_findings: usize,
+    pub open_high_findings: usize,
+    /// When the latest completed scan finished
+    pub last_scan_at: Option<String>,
+    pub days_since_last_scan: Option<i64>,
+    pub overdue_remediation: usize,
+    /// See the module documentation for the formula
+    pub health_score: f64,
+}
+
+/// Order of the portfolio; every order but the name puts the clients
+/// needing attention first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PortfolioSort {
+    Name,
+    /// Lowest score first
+    #[default]
+    HealthScore,
+    /// Highest overall risk first, unrated clients last
+    Risk,
+    /// Lowest average compliance first, unassessed clients last
+    Compliance,
+    /// Longest since the last scan first, never scanned before all
+    LastScan,
+    /// Most overdue remediation items first
+    OverdueRemediation,
+}
+
+/// Which clients the portfolio lists
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PortfolioFilter {
+    /// Only clients with an assessment of this framework
+    pub framework: Option<Framework>,
+    /// Only clients rated at this overall risk or above
+    pub min_risk: Option<RiskLevel>,
+}
+
+impl PortfolioFilter {
+    pub fn matches(&self, client: &PortfolioClient) -> bool {
+        let has_framework = self
+            .framework
+            .map_or(true, |framework| client.frameworks.iter().any(|f| f.framework == framework));
+        let at_risk = self
+            .min_risk
+            .map_or(true, |min| client.overall_risk.is_some_and(|risk| risk >= min));
+        has_framework && at_risk
+    }
+}
+
+/// The portfolio of every client, filtered and sorted
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioOverview {
+    pub generated_at: String,
+    pub clients: Vec<PortfolioClient>,
+}
+
+/// Build the portfolio as of now
+pub fn get_portfolio_overview(
+    db: &Database,
+    filter: &PortfolioFilter,
+    sort: PortfolioSort,
+) -> OptioResult<PortfolioOverview> {
+    let now = Utc::now();
+    let mut clients: Vec<PortfolioClient> = build_portfolio(db, now)?
+        .into_iter()
+        .filter(|client| filter.matches(client))
+        .collect();
+    sort_portfolio(&mut clients, sort);
+
+    Ok(PortfolioOverview {
+        generated_at: now.to_rfc3339(),
+        clients,
+    })
+}
+
+/// Every client's portfolio row, by client name
+pub fn build_portfolio(db: &Database, now: DateTime<Utc>) -> OptioResult<Vec<PortfolioClient>> {
+    let clients = ClientRepository::new(db).list()?;
+    let mut latest = latest_assessments(AssessmentRepository::new(db).count_results()?);
+    let mut open_findings = FindingRepository::new(db).count_open_by_client()?;
+    let mut open_network_findings = NetworkFindingRepository::new(db).count_unremediated_by_client()?;
+    let mut last_scans = ScanRepository::new(db).last_completed_by_client()?;
+    let overdue = RemediationPlanRepository::new(db).count_overdue_by_client(now)?;
+    let control_counts: HashMap<Framework, usize> = Framework::all()
+        .into_iter()
+        .map(|f| (f, get_framework_controls(f).len()))
+        .collect();
+
+    clients
+        .into_iter()
+        .map(|client| -> OptioResult<PortfolioClient> {
+            let assessments = latest.remove(&client.id).unwrap_or_default();
+            let mut findings = open_findings.remove(&client.id).unwrap_or_default();
+            for (severity, count) in open_network_findings.remove(&client.id).unwrap_or_default() {
+                *findings.entry(severity).or_insert(0) += count;
+            }
+            let last_scan_at = last_scans.remove(&client.id);
+            let last_scan = last_scan_at.as_deref().map(parse_datetime).transpose()?;
+
+            let mut frameworks: Vec<PortfolioFramework> = assessments
+                .iter()
+                .map(|a| PortfolioFramework {
+                    framework: a.framework,
+                    assessment_id: a.assessment_id.clone(),
+                    started_at: a.started_at,
+                    overall_compliance: a.overall_compliance(control_counts.get(&a.framework).copied().unwrap_or(0)),
+                })
+                .collect();
+            frameworks.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+            let average_compliance = (!frameworks.is_empty()).then(|| {
+                let mean = frameworks.iter().map(|f| f.overall_compliance).sum::<f64>() / frameworks.len() as f64;
+                (mean * 10.0).round() / 10.0
+            });
+
+            let gaps = gap_counts(&assessments);
+            let count = |severity: Criticality, priority: RemediationPriority| {
+                findings.get(&severity).copied().unwrap_or(0) + gaps.get(&priority).copied().unwrap_or(0)
+            };
+            let (critical, high, medium, low) = (
+                count(Criticality::Critical, RemediationPriority::Critical),
+                count(Criticality::High, RemediationPriority::High),
+                count(Criticality::Medium, RemediationPriority::Medium),
+                count(Criticality::Low, RemediationPriority::Low),
+            );
+            let rated = !assessments.is_empty() || !findings.is_empty() || last_scan.is_some();
+            let overall_risk = rated.then(|| RiskLevel::from_counts(critical, high, medium));
+            let mut risk = summarize_risk_counts(critical, high, medium, low);
+            if !rated {
+                risk.overall_risk_rating = "Unknown".to_string();
+            }
+
+            let open_critical_findings = findings.get(&Criticality::Critical).copied().unwrap_or(0);
+            let open_high_findings = findings.get(&Criticality::High).copied().unwrap_or(0);
+            let days_since_last_scan = last_scan.map(|at| (now - at).num_days().max(0));
+            let overdue_remediation = overdue.get(&client.id).copied().unwrap_or(0);
+
+            Ok(PortfolioClient {
+                client_id: client.id,
+                client_name: client.name,
+                health_score: health_score(
+                    average_compliance,
+                    open_critical_findings,
+                    open_high_findings,
+                    days_since_last_scan,
+                    overdue_remediation,
+                ),
+                frameworks,
+                average_compliance,
+                risk,
+                overall_risk,
+                open_critical_findings,
+                open_high_findings,
+                last_scan_at,
+                days_since_last_scan,
+                overdue_remediation,
+            })
+        })
+        .collect()
+}
+
+/// Each client's latest assessment of each framework
+fn latest_assessments(assessments: Vec<AssessmentResultCounts>) -> HashMap<String, Vec<AssessmentResultCounts>> {
+    let mut latest: HashMap<(String, Framework), AssessmentResultCounts> = HashMap::new();
+    for assessment in assessments {
+        let key = (assessment.client_id.clone(), assessment.framework);
+        let is_newer = latest
+            .get(&key)
+            .map_or(true, |current| assessment.started_at >= current.started_at);
+        if is_newer {
+            latest.insert(key, assessment);
+        }
+    }
+
+    let mut by_client: HashMap<String, Vec<AssessmentResultCounts>> = HashMap::new();
+    for ((client_id, _), assessment) in latest {
+        by_client.entry(client_id).or_default().push(assessment);
+    }
+    by_client
+}
+
+/// Non-compliant and partially compliant controls by remediation priority
+fn gap_counts(assessments: &[AssessmentResultCounts]) -> HashMap<RemediationPriority, usize> {
+    let mut gaps = HashMap::new();
+    for result in assessments.iter().flat_map(|a| &a.results) {
+        if matches!(result.status, ComplianceStatus::NonCompliant | ComplianceStatus::PartiallyCompliant) {
+            *gaps.entry(RemediationPriority::from_gap(result.risk_rating, result.status)).or_insert(0) += result.count;
+        }
+    }
+    gaps
+}
+
+/// Composite health score, as described in the module documentation
+pub fn health_score(
+    average_compliance: Option<f64>,
+    open_critical_findings: usize,
+    open_high_findings: usize,
+    days_since_last_scan: Option<i64>,
+    overdue_remediation: usize,
+) -> f64 {
+    let compliance = average_compliance.unwrap_or(0.0) * COMPLIANCE_WEIGHT;
+    let findings = (FINDINGS_POINTS
+        - open_critical_findings as f64 * CRITICAL_FINDING_PENALTY
+        - open_high_findings as f64 * HIGH_FINDING_PENALTY)
+        .max(0.0);
+    let scanning = match days_since_last_scan {
+        None => 0.0,
+        Some(days) if days <= SCAN_FRESH_DAYS => SCAN_POINTS,
+        Some(days) if days >= SCAN_STALE_DAYS => 0.0,
+        Some(days) => SCAN_POINTS * (SCAN_STALE_DAYS - days) as f64 / (SCAN_STALE_DAYS - SCAN_FRESH_DAYS) as f64,
+    };
+    let remediation = (REMEDIATION_POINTS - overdue_remediation as f64 * OVERDUE_ITEM_PENALTY).max(0.0);
+
+    ((compliance + findings + scanning + remediation) * 10.0).round() / 10.0
+}
+
+/// Sort the portfolio, breaking ties by client name
+pub fn sort_portfolio(clients: &mut [PortfolioClient], sort: PortfolioSort) {
+    clients.sort_by(|a, b| {
+        let order = match sort {
+            PortfolioSort::Name => std::cmp::Ordering::Equal,
+            PortfolioSort::HealthScore => a.health_score.total_cmp(&b.health_score),
+            PortfolioSort::Risk => Reverse(a.overall_risk).cmp(&Reverse(b.overall_risk)),
+            PortfolioSort::Compliance => match (a.average_compliance, b.average_compliance) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            },
+            PortfolioSort::LastScan => (a.days_since_last_scan.is_some(), Reverse(a.days_since_last_scan))
+                .cmp(&(b.days_since_last_scan.is_some(), Reverse(b.days_since_last_scan))),
+            PortfolioSort::OverdueRemediation => b.overdue_remediation.cmp(&a.overdue_remediation),
+        };
+        order.then_with(|| a.client_name.to_lowercase().cmp(&b.client_name.to_lowercase()))
+    });
+}
+
+/// CSV export of the portfolio table, one row per client
+///
+/// Each framework gets a compliance column, empty where the client has no
+/// assessment of it.
+pub fn portfolio_to_csv(clients: &[PortfolioClient]) -> Result<String, String> {
+    let frameworks = Framework::all();
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    let mut header = vec!["Client".to_string()];
+    header.extend(frameworks.iter().map(|f| format!("{} Compliance %", f.display_name())));
+    header.extend([
+        "Average Compliance %", "Overall Risk", "Open Critical Findings", "Open High Findings",
+        "Last Scan", "Days Since Last Scan", "Overdue Remediation", "Health Score",
+    ].map(String::from));
+    writer.write_record(&header).map_err(|e| e.to_string())?;
+
+    for client in clients {
+        let mut record = vec![client.client_name.clone()];
+        record.extend(frameworks.iter().map(|framework| {
+            client
+                .frameworks
+                .iter()
+                .find(|f| f.framework == *framework)
+                .map(|f| format!("{:.1}", f.overall_compliance))
+                .unwrap_or_default()
+        }));
+        record.extend([
+            client.average_compliance.map(|c| format!("{:.1}", c)).unwrap_or_default(),
+            client.risk.overall_risk_rating.clone(),
+            client.open_critical_findings.to_string(),
+            client.open_high_findings.to_string(),
+            client.last_scan_at.clone().unwrap_or_default(),
+            client.days_since_last_scan.map(|d| d.to_string()).unwrap_or_default(),
+            client.overdue_remediation.to_string(),
+            format!("{:.1}", client.health_score),
+        ]);
+        writer.write_record(&record).map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Client;
+    use crate::network::models::{FindingStatus, NetworkFinding};
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::grc::repository::init_grc_schema(&db).unwrap();
+        crate::grc::remediation::init_remediation_schema(&db).unwrap();
+        crate::network::repository::init_network_schema(&db).unwrap();
+        crate::findings::library::init_findings_schema(&db).unwrap();
+        db
+    }
+
+    fn create_client(db: &Database, name: &str) -> Client {
+        let client = Client::new(name.to_string(), None, None, None);
+        ClientRepository::new(db).create(&client).unwrap();
+        client
+    }
+
+    /// An assessed, scanned client with open findings and an overdue item
+    fn seed(db: &Database, client_id: &str) {
+        let sql = r#"
+            INSERT INTO assessments (id, client_id, name, framework, started_at, lead_assessor, status) VALUES
+                ('a0', '{c}', 'Baseline', 'NistCsf2', '2026-01-01T00:00:00Z', 'Jane', 'Completed'),
+                ('a1', '{c}', 'Follow-up', 'NistCsf2', '2026-05-01T00:00:00Z', 'Jane', 'InProgress'),
+                ('a2', '{c}', 'Old copy', 'NistCsf2', '2026-05-20T00:00:00Z', 'Jane', 'Archived');
+            INSERT INTO control_assessments (id, assessment_id, control_id, status, risk_rating, assessed_at, assessed_by) VALUES
+                ('r0', 'a0', 'GV.OC-01', 'NonCompliant', 5, '2026-01-02T00:00:00Z', 'Jane'),
+                ('r1', 'a1', 'GV.OC-01', 'Compliant', NULL, '2026-05-02T00:00:00Z', 'Jane'),
+                ('r2', 'a1', 'GV.OC-02', 'PartiallyCompliant', NULL, '2026-05-02T00:00:00Z', 'Jane'),
+                ('r3', 'a1', 'PR.AA-01', 'NonCompliant', 5, '2026-05-02T00:00:00Z', 'Jane'),
+                ('r4', 'a1', 'PR.AA-02', 'NonCompliant', NULL, '2026-05-02T00:00:00Z', 'Jane'),
+                ('r5', 'a2', 'GV.OC-01', 'Compliant', NULL, '2026-05-21T00:00:00Z', 'Jane');
+            INSERT INTO findings (id, client_id, title, severity, description, impact, recommendation,
+                                  refs, tags, asset_ids, status, created_at, updated_at) VALUES
+                ('f1', '{c}', 'No MFA', 'Critical', '', '', '', '[]', '[]', '[]', 'Open', '2026-05-03T00:00:00Z', '2026-05-03T00:00:00Z'),
+                ('f2', '{c}', 'Default creds', 'Critical', '', '', '', '[]', '[]', '[]', 'Unverifiable', '2026-05-03T00:00:00Z', '2026-05-03T00:00:00Z'),
+                ('f3', '{c}', 'Old TLS', 'Critical', '', '', '', '[]', '[]', '[]', 'Remediated', '2026-05-03T00:00:00Z', '2026-05-03T00:00:00Z');
+            INSERT INTO scans (id, client_id, name, config, status, created_at, completed_at, progress) VALUES
+                ('s1', '{c}', 'April', '{}', 'Completed', '2026-04-01T00:00:00Z', '2026-04-01T01:00:00Z', 100),
+                ('s2', '{c}', 'May', '{}', 'Completed', '2026-05-22T00:00:00Z', '2026-05-22T01:00:00Z', 100),
+                ('s3', '{c}', 'Retry', '{}', 'Failed', '2026-05-31T00:00:00Z', NULL, 10);
+            INSERT INTO remediation_plans (id, client_id, assessment_id, framework, generated_at, updated_at) VALUES
+                ('p1', '{c}', 'a1', 'NistCsf2', '2026-05-02T00:00:00Z', '2026-05-02T00:00:00Z');
+            INSERT INTO remediation_items (id, plan_id, position, control_id, control_code, control_title, category,
+                                           category_name, compliance_status, recommended_action, effort, owner_role,
+                                           due_date, status, updated_at) VALUES
+                ('i1', 'p1', 0, 'PR.AA-01', 'PR.AA-01', 'Identities', 'PR', 'Protect', 'NonCompliant', 'Fix', 'MEDIUM', 'IT',
+                 '2026-05-01T00:00:00+00:00', 'Open', '2026-05-02T00:00:00Z'),
+                ('i2', 'p1', 1, 'PR.AA-02', 'PR.AA-02', 'Proofing', 'PR', 'Protect', 'NonCompliant', 'Fix', 'MEDIUM', 'IT',
+                 '2026-05-01T00:00:00+00:00', 'Done', '2026-05-02T00:00:00Z'),
+                ('i3', 'p1', 2, 'GV.OC-02', 'GV.OC-02', 'Context', 'GV', 'Govern', 'PartiallyCompliant', 'Fix', 'LOW', 'IT',
+                 '2026-07-01T00:00:00+00:00', 'Open', '2026-05-02T00:00:00Z');
+        "#;
+        db.conn.lock().unwrap().execute_batch(&sql.replace("{c}", client_id)).unwrap();
+    }
+
+    fn network_finding(client_id: &str, severity: Criticality, status: FindingStatus) -> NetworkFinding {
+        NetworkFinding {
+            id: format!("nf-{:?}-{:?}", severity, status),
+            client_id: client_id.to_string(),
+            rule_id: "telnet-exposed".to_string(),
+            host: "10.0.0.5".to_string(),
+            port: 23,
+            service: "telnet".to_string(),
+            title: "Telnet exposed".to_string(),
+            severity,
+            status,
+            evidence: vec![],
+            notes: None,
+            detected_at: "2026-05-22T01:00:00Z".to_string(),
+            updated_at: "2026-05-22T01:00:00Z".to_string(),
+            verification_scan_id: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        parse_datetime("2026-06-01T00:00:00Z").unwrap()
+    }
+
+    #[test]
+    fn test_portfolio_aggregates_each_client() {
+        let db = test_db();
+        let acme = create_client(&db, "Acme");
+        let beta = create_client(&db, "Beta");
+        seed(&db, &acme.id);
+        NetworkFindingRepository::new(&db)
+            .save_all(&[
+                network_finding(&acme.id, Criticality::High, FindingStatus::Open),
+                network_finding(&acme.id, Criticality::High, FindingStatus::Remediated),
+            ])
+            .unwrap();
+
+        let portfolio = build_portfolio(&db, now()).unwrap();
+        assert_eq!(portfolio.len(), 2);
+        let (acme_row, beta_row) = (&portfolio[0], &portfolio[1]);
+
+        // Only the latest non-archived assessment counts
+        let controls = get_framework_controls(Framework::NistCsf2).len() as f64;
+        let expected = ((1.5 / controls * 100.0) * 10.0).round() / 10.0;
+        assert_eq!(acme_row.frameworks.len(), 1);
+        assert_eq!(acme_row.frameworks[0].assessment_id, "a1");
+        assert_eq!(acme_row.frameworks[0].overall_compliance, expected);
+        assert_eq!(acme_row.average_compliance, Some(expected));
+
+        // Two open critical findings plus one critical gap; one open high
+        // network finding plus the unrated non-compliant gap
+        assert_eq!((acme_row.open_critical_findings, acme_row.open_high_findings), (2, 1));
+        assert_eq!(
+            (acme_row.risk.critical_count, acme_row.risk.high_count, acme_row.risk.medium_count),
+            (3, 2, 1)
+        );
+        assert_eq!(acme_row.overall_risk, Some(RiskLevel::Critical));
+        assert_eq!(acme_row.risk.overall_risk_rating, "Critical");
+
+        assert_eq!(acme_row.last_scan_at.as_deref(), Some("2026-05-22T01:00:00Z"));
+        assert_eq!(acme_row.days_since_last_scan, Some(9));
+        assert_eq!(acme_row.overdue_remediation, 1);
+        assert_eq!(acme_row.health_score, health_score(Some(expected), 2, 1, Some(9), 1));
+
+        assert_eq!(beta_row.client_id, beta.id);
+        assert!(beta_row.frameworks.is_empty() && beta_row.average_compliance.is_none());
+        assert_eq!(beta_row.overall_risk, None);
+        assert_eq!(beta_row.risk.overall_risk_rating, "Unknown");
+        assert_eq!(beta_row.days_since_last_scan, None);
+        assert_eq!(beta_row.health_score, 45.0);
+    }
+
+    #[test]
+    fn test_health_score_formula() {
+        assert_eq!(health_score(Some(100.0), 0, 0, Some(0), 0), 100.0);
+        assert_eq!(health_score(None, 0, 0, None, 0), 45.0);
+        // 80% compliance, one critical and one high finding, a 75-day-old
+        // scan halfway to stale, two overdue items
+        assert_eq!(health_score(Some(80.0), 1, 1, Some(75), 2), 32.0 + 15.0 + 7.5 + 9.0);
+        // Parts bottom out at zero
+        assert_eq!(health_score(Some(50.0), 4, 0, Some(400), 10), 20.0);
+    }
+
+    #[test]
+    fn test_filter_sort_and_csv() {
+        let db = test_db();
+        let acme = create_client(&db, "Acme");
+        create_client(&db, "Beta");
+        create_client(&db, "Cobalt");
+        seed(&db, &acme.id);
+        let mut portfolio = build_portfolio(&db, now()).unwrap();
+
+        let names = |clients: &[PortfolioClient]| clients.iter().map(|c| c.client_name.clone()).collect::<Vec<_>>();
+        let filter = PortfolioFilter {
+            framework: Some(Framework::NistCsf2),
+            min_risk: None,
+        };
+        assert_eq!(names(&portfolio.iter().filter(|c| filter.matches(c)).cloned().collect::<Vec<_>>()), ["Acme"]);
+        let filter = PortfolioFilter {
+            framework: None,
+            min_risk: Some(RiskLevel::Low),
+        };
+        assert_eq!(portfolio.iter().filter(|c| filter.matches(c)).count(), 1);
+        let filter = PortfolioFilter {
+            framework: Some(Framework::Gdpr),
+            min_risk: None,
+        };
+        assert_eq!(portfolio.iter().filter(|c| filter.matches(c)).count(), 0);
+
+        sort_portfolio(&mut portfolio, PortfolioSort::HealthScore);
+        assert_eq!(names(&portfolio), ["Acme", "Beta", "Cobalt"]);
+        sort_portfolio(&mut portfolio, PortfolioSort::LastScan);
+        assert_eq!(names(&portfolio), ["Beta", "Cobalt", "Acme"]);
+        sort_portfolio(&mut portfolio, PortfolioSort::Compliance);
+        assert_eq!(names(&portfolio), ["Acme", "Beta", "Cobalt"]);
+
+        let csv = portfolio_to_csv(&portfolio).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("Client,NIST CSF 2.0 Compliance %,"));
+        assert!(lines[0].ends_with(",Health Score"));
+        assert!(lines[1].starts_with("Acme,"));
+        assert!(lines[2].contains(",Unknown,0,0,,,0,45.0"));
+    }
+}
@@ -0,0 +1,363 @@
+//! Report Branding
+//!
+//! Firm name, logo, theme colors and footer text applied to generated
+//! deliverables. Profiles are stored per organization; reports and executive
+//! PDFs pick up the profile for their organization unless the request sets
+//! explicit values, so white-labeled work for a partner firm needs no
+//! per-report setup.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::repository::parse_datetime;
+use crate::reporting::models::ReportConfig;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+/// Brand color used when neither the request nor a profile sets one
+pub const DEFAULT_PRIMARY_COLOR: &str = "#3B82F6";
+
+/// Initialize branding profile schema
+pub fn init_branding_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Report branding, one profile per organization
+        CREATE TABLE IF NOT EXISTS branding_profiles (
+            organization TEXT PRIMARY KEY,
+            firm_name TEXT NOT NULL,
+            logo_path TEXT,
+            primary_color TEXT NOT NULL,
+            secondary_color TEXT,
+            footer_text TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+    "#)?;
+
+    tracing::info!("Branding schema initialized");
+    Ok(())
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Branding applied to an organization's reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrandingProfile {
+    /// Organization the profile belongs to (matches the report's organization)
+    pub organization: String,
+    /// Name shown on deliverables
+    pub firm_name: String,
+    /// PNG or JPEG shown on the cover page
+    pub logo_path: Option<String>,
+    /// Hex color for headings and the classification banner
+    pub primary_color: String,
+    pub secondary_color: Option<String>,
+    pub footer_text: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BrandingProfile {
+    /// Check the profile has a firm name and well-formed colors
+    pub fn validate(&self) -> Result<(), String> {
+        if self.organization.trim().is_empty() {
+            return Err("Organization is required".to_string());
+        }
+        if self.firm_name.trim().is_empty() {
+            return Err("Firm name is required".to_string());
+        }
+        let colors = [("Primary", Some(&self.primary_color)), ("Secondary", self.secondary_color.as_ref())];
+        for (name, color) in colors {
+            if let Some(color) = color {
+                if parse_hex_color(color).is_none() {
+                    return Err(format!("{} color must be a hex color like #1E40AF, got {}", name, color));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fill in whatever branding the report config doesn't set explicitly
+    pub fn apply_to(&self, config: &mut ReportConfig) {
+        config.organization = Some(self.firm_name.clone());
+        if config.logo_path.is_none() {
+            config.logo_path = self.logo_path.clone();
+        }
+        if config.primary_color.is_none() {
+            config.primary_color = Some(self.primary_color.clone());
+        }
+        if config.secondary_color.is_none() {
+            config.secondary_color = self.secondary_color.clone();
+        }
+        if config.footer_text.is_none() {
+            config.footer_text = self.footer_text.clone();
+        }
+    }
+}
+
+/// Parse `#RRGGBB` or `#RGB` (the `#` is optional)
+pub fn parse_hex_color(s: &str) -> Option<(u8, u8, u8)> {
+    let hex = s.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize, len: usize| u8::from_str_radix(&hex[i..i + len], 16).ok();
+
+    match hex.len() {
+        6 => Some((channel(0, 2)?, channel(2, 2)?, channel(4, 2)?)),
+        3 => {
+            let (r, g, b) = (channel(0, 1)?, channel(1, 1)?, channel(2, 1)?);
+            Some((r * 17, g * 17, b * 17))
+        }
+        _ => None,
+    }
+}
+
+/// A color as `#RRGGBB`, or None if it isn't a hex color
+pub fn normalize_hex_color(s: &str) -> Option<String> {
+    parse_hex_color(s).map(|(r, g, b)| format!("#{:02X}{:02X}{:02X}", r, g, b))
+}
+
+/// Series colors that follow the brand color in chart palettes
+const CHART_COLORS: [&str; 8] = [
+    "#3B82F6", "#F59E0B", "#10B981", "#EF4444", "#8B5CF6", "#06B6D4", "#EC4899", "#84CC16",
+];
+
+/// Chart series colors: the brand color, then a fixed set of contrasting
+/// colors, so every export format colors a chart the same way
+///
+/// An invalid or missing brand color falls back to the default one.
+pub fn chart_palette(primary_color: Option<&str>) -> Vec<String> {
+    let primary = primary_color
+        .and_then(normalize_hex_color)
+        .unwrap_or_else(|| DEFAULT_PRIMARY_COLOR.to_string());
+    let rest = CHART_COLORS.iter().filter(|c| **c != primary).map(|c| c.to_string());
+    std::iter::once(primary.clone()).chain(rest).collect()
+}
+
+// ============================================================================
+// Logos
+// ============================================================================
+
+/// Image formats accepted for logos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogoFormat {
+    Png,
+    Jpeg,
+}
+
+impl LogoFormat {
+    /// Detect the format from the file's magic bytes
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(LogoFormat::Png)
+        } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(LogoFormat::Jpeg)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            LogoFormat::Png => "image/png",
+            LogoFormat::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+/// A logo read from disk
+#[derive(Debug, Clone)]
+pub struct Logo {
+    pub format: LogoFormat,
+    pub bytes: Vec<u8>,
+}
+
+impl Logo {
+    /// Read a PNG or JPEG logo
+    pub fn read(path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Failed to read logo {}: {}", path, e))?;
+        let format = LogoFormat::detect(&bytes)
+            .ok_or_else(|| format!("Logo {} is not a PNG or JPEG image", path))?;
+        Ok(Logo { format, bytes })
+    }
+
+    /// Read a logo for a report, logging a warning instead of failing
+    ///
+    /// A moved or corrupt logo shouldn't stop a deliverable from exporting;
+    /// the report is rendered without it.
+    pub fn load(path: &str) -> Option<Self> {
+        match Self::read(path) {
+            Ok(logo) => Some(logo),
+            Err(e) => {
+                tracing::warn!("Skipping report logo: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Base64 `data:` URI for embedding in HTML
+    pub fn data_uri(&self) -> String {
+        format!(
+            "data:{};base64,{}",
+            self.format.mime_type(),
+            base64::engine::general_purpose::STANDARD.encode(&self.bytes)
+        )
+    }
+}
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// Branding profile repository
+pub struct BrandingRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> BrandingRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        BrandingRepository { db }
+    }
+
+    /// Insert or replace an organization's profile
+    pub fn save(&self, profile: &BrandingProfile) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        conn.execute(
+            r#"INSERT OR REPLACE INTO branding_profiles
+               (organization, firm_name, logo_path, primary_color, secondary_color, footer_text,
+                created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+            params![
+                profile.organization,
+                profile.firm_name,
+                profile.logo_path,
+                profile.primary_color,
+                profile.secondary_color,
+                profile.footer_text,
+                profile.created_at.to_rfc3339(),
+                profile.updated_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, organization: &str) -> OptioResult<Option<BrandingProfile>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            r#"SELECT organization, firm_name, logo_path, primary_color, secondary_color,
+                      footer_text, created_at, updated_at
+               FROM branding_profiles WHERE organization = ?1"#
+        )?;
+
+        let mut rows = stmt.query(params![organization])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(parse_profile_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn parse_profile_row(row: &rusqlite::Row) -> OptioResult<BrandingProfile> {
+    Ok(BrandingProfile {
+        organization: row.get(0)?,
+        firm_name: row.get(1)?,
+        logo_path: row.get(2)?,
+        primary_color: row.get(3)?,
+        secondary_color: row.get(4)?,
+        footer_text: row.get(5)?,
+        created_at: parse_datetime(&row.get::<_, String>(6)?)?,
+        updated_at: parse_datetime(&row.get::<_, String>(7)?)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#1E40AF"), Some((0x1E, 0x40, 0xAF)));
+        assert_eq!(parse_hex_color("1e40af"), Some((0x1E, 0x40, 0xAF)));
+        assert_eq!(parse_hex_color("#fff"), Some((255, 255, 255)));
+        assert_eq!(parse_hex_color("#12345"), None);
+        assert_eq!(parse_hex_color("red"), None);
+        assert_eq!(parse_hex_color("#12345G"), None);
+        assert_eq!(normalize_hex_color(" #0af ").as_deref(), Some("#00AAFF"));
+    }
+
+    #[test]
+    fn test_chart_palette() {
+        let palette = chart_palette(Some("0f766e"));
+        assert_eq!(palette[0], "#0F766E");
+        assert_eq!(palette.len(), CHART_COLORS.len() + 1);
+
+        // The default brand color isn't repeated
+        let palette = chart_palette(Some("not-a-color"));
+        assert_eq!(palette[0], DEFAULT_PRIMARY_COLOR);
+        assert_eq!(palette.iter().filter(|c| *c == DEFAULT_PRIMARY_COLOR).count(), 1);
+        assert_eq!(chart_palette(None), palette);
+    }
+
+    #[test]
+    fn test_logo_read() {
+        let dir = std::env::temp_dir().join(format!("optio-logo-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let png = dir.join("logo.png");
+        std::fs::write(&png, b"\x89PNG\r\n\x1a\nrest").unwrap();
+        let logo = Logo::read(png.to_str().unwrap()).unwrap();
+        assert_eq!(logo.format, LogoFormat::Png);
+        assert!(logo.data_uri().starts_with("data:image/png;base64,iVBORw0KGg"));
+
+        let svg = dir.join("logo.svg");
+        std::fs::write(&svg, b"<svg/>").unwrap();
+        assert!(Logo::read(svg.to_str().unwrap()).is_err());
+        assert!(Logo::load(dir.join("missing.png").to_str().unwrap()).is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_branding_repository() {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        init_branding_schema(&db).unwrap();
+        let repo = BrandingRepository::new(&db);
+
+        let now = Utc::now();
+        let mut profile = BrandingProfile {
+            organization: "Northwind Partners".to_string(),
+            firm_name: "Northwind Security".to_string(),
+            logo_path: None,
+            primary_color: "#0F766E".to_string(),
+            secondary_color: None,
+            footer_text: Some("Prepared by Northwind Security".to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(profile.validate().is_ok());
+        repo.save(&profile).unwrap();
+
+        let stored = repo.get("Northwind Partners").unwrap().unwrap();
+        assert_eq!(stored.firm_name, "Northwind Security");
+        assert_eq!(stored.footer_text, profile.footer_text);
+        assert!(repo.get("Other").unwrap().is_none());
+
+        profile.secondary_color = Some("teal".to_string());
+        assert!(profile.validate().is_err());
+    }
+}
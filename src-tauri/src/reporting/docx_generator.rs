@@ -0,0 +1,435 @@
+//! DOCX Export Module
+//!
+//! Renders report content as an editable Word document using docx-rs.
+//! Tables, key/value blocks and findings become real Word tables; charts are
+//! written as a table of their figures.
+
+use std::io::Cursor;
+
+use docx_rs::{
+    AbstractNumbering, AlignmentType, BreakType, Docx, IndentLevel, Level, LevelJc, LevelOverride,
+    LevelText, NumberFormat, Numbering, NumberingId, Paragraph, Run, RunFonts, Shading, ShdType,
+    SpecialIndentType, Start, Style, StyleType, Table, TableCell, TableRow, WidthType,
+};
+
+use super::generator::chart_to_table;
+use super::models::*;
+
+/// Abstract numbering definitions; numbered lists each get their own instance
+const BULLET_ABSTRACT_ID: usize = 1;
+const DECIMAL_ABSTRACT_ID: usize = 2;
+/// Numbering instance shared by every bullet list
+const BULLET_NUMBERING_ID: usize = 1;
+
+/// Fill for table header rows and key cells
+const HEADER_FILL: &str = "DBEAFE";
+/// Full page width for tables, in fiftieths of a percent
+const FULL_WIDTH_PCT: usize = 5000;
+/// Table of contents indent per section level, in twentieths of a point
+const TOC_INDENT: i32 = 360;
+
+/// Convert report content to a Word document
+pub fn content_to_docx(content: &ReportContent) -> Result<Vec<u8>, String> {
+    let mut buffer = Cursor::new(Vec::new());
+    build_docx(content)
+        .build()
+        .pack(&mut buffer)
+        .map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    Ok(buffer.into_inner())
+}
+
+pub(crate) fn build_docx(content: &ReportContent) -> Docx {
+    let mut writer = DocxWriter {
+        docx: document_styles(Docx::new()),
+        next_numbering_id: BULLET_NUMBERING_ID + 1,
+    };
+
+    writer.cover_page(&content.metadata);
+    if !content.toc.is_empty() {
+        writer.table_of_contents(&content.toc);
+    }
+    for section in &content.sections {
+        writer.section(section);
+    }
+
+    writer.docx
+}
+
+/// Heading, title and caption styles plus list numbering definitions
+fn document_styles(docx: Docx) -> Docx {
+    let heading = |level: usize, size: usize| {
+        Style::new(format!("Heading{}", level), StyleType::Paragraph)
+            .name(format!("Heading {}", level))
+            .based_on("Normal")
+            .next("Normal")
+            .size(size)
+            .bold()
+            .color("1E3A8A")
+            .outline_lvl(level - 1)
+    };
+
+    docx.add_style(
+        Style::new("Title", StyleType::Paragraph)
+            .name("Title")
+            .based_on("Normal")
+            .size(56)
+            .bold()
+            .color("1E3A8A"),
+    )
+    .add_style(
+        Style::new("Subtitle", StyleType::Paragraph)
+            .name("Subtitle")
+            .based_on("Normal")
+            .size(32)
+            .color("4B5563"),
+    )
+    .add_style(heading(1, 32))
+    .add_style(heading(2, 26))
+    .add_style(heading(3, 24))
+    .add_style(
+        Style::new("Caption", StyleType::Paragraph)
+            .name("Caption")
+            .based_on("Normal")
+            .size(18)
+            .italic()
+            .color("4B5563"),
+    )
+    .add_abstract_numbering(
+        AbstractNumbering::new(BULLET_ABSTRACT_ID).add_level(
+            Level::new(
+                0,
+                Start::new(1),
+                NumberFormat::new("bullet"),
+                LevelText::new("•"),
+                LevelJc::new("left"),
+            )
+            .indent(Some(720), Some(SpecialIndentType::Hanging(360)), None, None),
+        ),
+    )
+    .add_abstract_numbering(
+        AbstractNumbering::new(DECIMAL_ABSTRACT_ID).add_level(
+            Level::new(
+                0,
+                Start::new(1),
+                NumberFormat::new("decimal"),
+                LevelText::new("%1."),
+                LevelJc::new("left"),
+            )
+            .indent(Some(720), Some(SpecialIndentType::Hanging(360)), None, None),
+        ),
+    )
+    .add_numbering(Numbering::new(BULLET_NUMBERING_ID, BULLET_ABSTRACT_ID))
+}
+
+struct DocxWriter {
+    docx: Docx,
+    /// Numbering instance for the next numbered list, so each restarts at 1
+    next_numbering_id: usize,
+}
+
+impl DocxWriter {
+    fn paragraph(&mut self, paragraph: Paragraph) {
+        let docx = std::mem::take(&mut self.docx);
+        self.docx = docx.add_paragraph(paragraph);
+    }
+
+    fn table(&mut self, table: Table) {
+        let docx = std::mem::take(&mut self.docx);
+        self.docx = docx.add_table(table);
+    }
+
+    fn cover_page(&mut self, metadata: &ReportMetadata) {
+        self.paragraph(Paragraph::new().style("Title").add_run(Run::new().add_text(&metadata.title)));
+        if let Some(ref subtitle) = metadata.subtitle {
+            self.paragraph(Paragraph::new().style("Subtitle").add_run(Run::new().add_text(subtitle)));
+        }
+
+        let mut details = vec![
+            ("Prepared for", metadata.client_name.clone()),
+        ];
+        if let Some(ref contact) = metadata.client_contact {
+            details.push(("Attention", contact.clone()));
+        }
+        details.push(("Author", metadata.author.clone()));
+        if let Some(ref organization) = metadata.organization {
+            details.push(("Organization", organization.clone()));
+        }
+        details.push(("Date", metadata.report_date.clone()));
+        details.push(("Version", metadata.version.clone()));
+
+        for (label, value) in details {
+            self.paragraph(
+                Paragraph::new()
+                    .add_run(Run::new().add_text(format!("{}: ", label)).bold())
+                    .add_run(Run::new().add_text(value)),
+            );
+        }
+
+        if let Some(ref classification) = metadata.classification {
+            self.paragraph(
+                Paragraph::new()
+                    .align(AlignmentType::Center)
+                    .add_run(Run::new().add_text(classification.to_uppercase()).bold().color("DC2626")),
+            );
+        }
+
+        self.paragraph(page_break());
+    }
+
+    /// Numbered section list, indented by level, on its own page
+    fn table_of_contents(&mut self, toc: &[TocEntry]) {
+        self.paragraph(heading("Table of Contents", 1));
+        for entry in toc {
+            let indent = TOC_INDENT * entry.level.saturating_sub(1) as i32;
+            self.paragraph(
+                Paragraph::new()
+                    .indent(Some(indent), None, None, None)
+                    .add_run(Run::new().add_text(format!("{} {}", entry.number, entry.title))),
+            );
+        }
+        self.paragraph(page_break());
+    }
+
+    fn section(&mut self, section: &ReportSection) {
+        if !section.title.is_empty() {
+            self.paragraph(heading(&section.title, section.level));
+        }
+
+        for block in &section.blocks {
+            self.block(block);
+        }
+
+        for subsection in &section.subsections {
+            self.section(subsection);
+        }
+    }
+
+    fn block(&mut self, block: &ContentBlock) {
+        match block {
+            ContentBlock::Paragraph { text } => self.paragraph(Paragraph::new().add_run(text_run(text))),
+            ContentBlock::Heading { text, level } => self.paragraph(heading(text, *level)),
+            ContentBlock::BulletList { items } => {
+                for item in items {
+                    self.paragraph(
+                        Paragraph::new()
+                            .numbering(NumberingId::new(BULLET_NUMBERING_ID), IndentLevel::new(0))
+                            .add_run(text_run(item)),
+                    );
+                }
+            }
+            ContentBlock::NumberedList { items } => {
+                let id = self.next_numbering_id;
+                self.next_numbering_id += 1;
+                let docx = std::mem::take(&mut self.docx);
+                self.docx = docx.add_numbering(
+                    Numbering::new(id, DECIMAL_ABSTRACT_ID).add_override(LevelOverride::new(0).start(1)),
+                );
+
+                for item in items {
+                    self.paragraph(
+                        Paragraph::new()
+                            .numbering(NumberingId::new(id), IndentLevel::new(0))
+                            .add_run(text_run(item)),
+                    );
+                }
+            }
+            ContentBlock::Table { headers, rows, caption } => {
+                let mut table_rows = vec![TableRow::new(
+                    headers.iter().map(|h| header_cell(h)).collect(),
+                )];
+                table_rows.extend(rows.iter().map(|row| {
+                    TableRow::new(row.iter().map(|cell| text_cell(cell)).collect())
+                }));
+                self.table(Table::new(table_rows).width(FULL_WIDTH_PCT, WidthType::Pct));
+
+                if let Some(caption) = caption {
+                    self.paragraph(Paragraph::new().style("Caption").add_run(Run::new().add_text(caption)));
+                } else {
+                    self.paragraph(Paragraph::new());
+                }
+            }
+            ContentBlock::Chart { title, data, .. } => self.block(&chart_to_table(Some(title.clone()), data)),
+            ContentBlock::KeyValue { items } => {
+                let rows = items
+                    .iter()
+                    .map(|item| TableRow::new(vec![header_cell(&item.key), text_cell(&item.value)]))
+                    .collect();
+                self.table(Table::new(rows).width(FULL_WIDTH_PCT, WidthType::Pct));
+                self.paragraph(Paragraph::new());
+            }
+            ContentBlock::Callout { callout_type, title, text } => {
+                let fill = callout_fill(*callout_type);
+                if let Some(title) = title {
+                    self.paragraph(shaded(
+                        Paragraph::new().keep_next(true).add_run(Run::new().add_text(title).bold()),
+                        fill,
+                    ));
+                }
+                self.paragraph(shaded(Paragraph::new().add_run(text_run(text)), fill));
+            }
+            ContentBlock::Code { content, .. } => {
+                let fonts = RunFonts::new().ascii("Courier New").hi_ansi("Courier New");
+                self.paragraph(shaded(
+                    Paragraph::new().add_run(text_run(content).fonts(fonts).size(18)),
+                    "F3F4F6",
+                ));
+            }
+            ContentBlock::Finding { id, title, severity, description, impact, recommendation } => {
+                let title_cell = TableCell::new()
+                    .grid_span(2)
+                    .shading(fill_shading(severity_fill(severity)))
+                    .add_paragraph(
+                        Paragraph::new()
+                            .add_run(Run::new().add_text(format!("{} - {}", id, title)).bold().color("FFFFFF")),
+                    );
+
+                let rows = vec![
+                    TableRow::new(vec![title_cell]),
+                    TableRow::new(vec![header_cell("Severity"), text_cell(severity)]),
+                    TableRow::new(vec![header_cell("Description"), text_cell(description)]),
+                    TableRow::new(vec![header_cell("Impact"), text_cell(impact)]),
+                    TableRow::new(vec![header_cell("Recommendation"), text_cell(recommendation)]),
+                ];
+                self.table(
+                    Table::new(rows)
+                        .set_grid(vec![2000, 7000])
+                        .width(FULL_WIDTH_PCT, WidthType::Pct),
+                );
+                self.paragraph(Paragraph::new());
+            }
+            ContentBlock::Metric { label, value, change, .. } => {
+                let mut paragraph = Paragraph::new()
+                    .add_run(Run::new().add_text(format!("{}: ", label)).bold())
+                    .add_run(Run::new().add_text(value).size(28));
+                if let Some(change) = change {
+                    paragraph = paragraph.add_run(Run::new().add_text(format!(" ({})", change)).italic());
+                }
+                self.paragraph(paragraph);
+            }
+            ContentBlock::PageBreak => self.paragraph(page_break()),
+            // Markup has no Word equivalent
+            ContentBlock::RawHtml { .. } => {}
+        }
+    }
+}
+
+fn heading(text: &str, level: u8) -> Paragraph {
+    Paragraph::new()
+        .style(&format!("Heading{}", level.clamp(1, 3)))
+        .keep_next(true)
+        .add_run(Run::new().add_text(text))
+}
+
+fn page_break() -> Paragraph {
+    Paragraph::new().add_run(Run::new().add_break(BreakType::Page))
+}
+
+/// Run for free text, keeping embedded line breaks
+fn text_run(text: &str) -> Run {
+    let mut run = Run::new();
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            run = run.add_break(BreakType::TextWrapping);
+        }
+        run = run.add_text(line);
+    }
+    run
+}
+
+fn text_cell(text: &str) -> TableCell {
+    TableCell::new().add_paragraph(Paragraph::new().add_run(text_run(text)))
+}
+
+fn header_cell(text: &str) -> TableCell {
+    TableCell::new()
+        .shading(fill_shading(HEADER_FILL))
+        .add_paragraph(Paragraph::new().add_run(Run::new().add_text(text).bold()))
+}
+
+fn fill_shading(fill: &str) -> Shading {
+    Shading::new().shd_type(ShdType::Clear).color("auto").fill(fill)
+}
+
+fn shaded(mut paragraph: Paragraph, fill: &str) -> Paragraph {
+    paragraph.property = paragraph.property.shading(fill_shading(fill));
+    paragraph
+}
+
+fn callout_fill(callout_type: CalloutType) -> &'static str {
+    match callout_type {
+        CalloutType::Info => "DBEAFE",
+        CalloutType::Warning => "FEF3C7",
+        CalloutType::Critical => "FEE2E2",
+        CalloutType::Success => "DCFCE7",
+        CalloutType::Note => "F3F4F6",
+    }
+}
+
+fn severity_fill(severity: &str) -> &'static str {
+    match severity.to_uppercase().as_str() {
+        "CRITICAL" => "DC2626",
+        "HIGH" => "EA580C",
+        "MEDIUM" => "CA8A04",
+        "LOW" => "2563EB",
+        _ => "6B7280",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_to_docx() {
+        let content = ReportContent {
+            metadata: ReportMetadata {
+                title: "Security Assessment".to_string(),
+                subtitle: Some("Q1 2026".to_string()),
+                client_name: "Acme".to_string(),
+                client_contact: None,
+                author: "Tester".to_string(),
+                organization: None,
+                report_date: "2026-01-01".to_string(),
+                version: "1.0".to_string(),
+                classification: Some("Confidential".to_string()),
+                page_count: None,
+                logo_path: None,
+                primary_color: None,
+                secondary_color: None,
+                footer_text: None,
+            },
+            toc: vec![],
+            anchors: vec![],
+            sections: vec![ReportSection {
+                id: "findings".to_string(),
+                title: "Findings".to_string(),
+                level: 1,
+                blocks: vec![
+                    ContentBlock::NumberedList { items: vec!["First".to_string(), "Second".to_string()] },
+                    ContentBlock::Table {
+                        headers: vec!["Host".to_string(), "Ports".to_string()],
+                        rows: vec![vec!["10.0.0.1".to_string(), "22, 443".to_string()]],
+                        caption: Some("Exposure".to_string()),
+                    },
+                    ContentBlock::Finding {
+                        id: "F-1".to_string(),
+                        title: "Weak TLS".to_string(),
+                        severity: "High".to_string(),
+                        description: "TLS 1.0 enabled".to_string(),
+                        impact: "Interception".to_string(),
+                        recommendation: "Disable TLS 1.0, 1.1".to_string(),
+                    },
+                ],
+                subsections: vec![],
+            }],
+        };
+
+        let document = String::from_utf8(build_docx(&content).build().document).unwrap();
+        assert_eq!(document.matches("<w:tbl>").count(), 2);
+        assert!(document.contains("Heading1"));
+        assert!(document.contains("Disable TLS 1.0, 1.1"));
+
+        let bytes = content_to_docx(&content).unwrap();
+        assert!(bytes.starts_with(b"PK"));
+    }
+}
@@ -4,23 +4,233 @@
 //! Uses structured content blocks to build professional reports.
 
 use super::models::*;
+use super::branding::{chart_palette, normalize_hex_color, Logo};
+use super::svg_charts::chart_svg;
+use super::templates::{get_template_for_type, section_included, validate_section_selection};
+use crate::findings::library::Finding;
+use crate::grc::models::{AssessmentComparison, ComplianceStatusReport, EvidenceCoverage, Framework, MAX_MATURITY_LEVEL};
+use crate::grc::remediation::{RemediationItem, RemediationItemStatus, RemediationPlan, RemediationPriority, RemediationStats};
+use crate::grc::overview::ComplianceOverview;
+use crate::grc::trend::ComplianceTrend;
+use crate::grc::risk::RiskItem;
+use crate::grc::ropa::{activities_by_control, ProcessingActivity};
+use crate::infrastructure::finops::{compare_resource_costs, get_resource_templates, ComparisonStrategies, ProviderComparison};
+use crate::infrastructure::k8s_hardening::{get_k8s_hardening_checks, summarize_k8s_audit};
+use crate::infrastructure::models::{K8sHardeningAudit, ReadinessScore, Severity, TcoProjection};
+use crate::network::diff::{PortRef, ScanDiff};
+use crate::network::findings::{get_finding_rules, FindingRule};
+use crate::network::inventory::AssetPolicyViolations;
+use crate::network::models::{Criticality, FindingStatus, NetworkFinding, NetworkStats, Protocol, ScanJob};
+use crate::network::vuln_match::VulnerabilityMatch;
 use uuid::Uuid;
 
+/// Live data a report is rendered from
+///
+/// Populated by the command layer. Modules that weren't used leave their
+/// fields empty and the matching sections say so rather than show numbers.
+#[derive(Debug, Clone, Default)]
+pub struct ReportDataSource {
+    /// Render the built-in sample content instead of live data
+    pub use_demo_data: bool,
+    /// Compliance status for the selected framework
+    pub compliance: Option<ComplianceStatusReport>,
+    /// Asset inventory statistics
+    pub network_stats: Option<NetworkStats>,
+    /// Assets exposing services their service policies forbid
+    pub policy_violations: Vec<AssetPolicyViolations>,
+    /// Findings raised against scan results
+    pub network_findings: Vec<NetworkFinding>,
+    /// Findings recorded from the findings library
+    pub findings: Vec<Finding>,
+    /// Known CVEs matched to asset service versions, highest CVSS first
+    pub vulnerabilities: Vec<VulnerabilityMatch>,
+    /// Verification scans, for the remediation progress section
+    pub verification_scans: Vec<ScanJob>,
+    /// Baseline vs follow-up scan comparison
+    pub scan_diff: Option<ScanDiff>,
+    /// Multi-year cost projections, one per provider/commitment option
+    pub cost_projections: Vec<TcoProjection>,
+    /// Per-resource cost comparison across providers
+    pub provider_comparison: Option<ProviderComparison>,
+    /// Weighted score of the client's cloud readiness answers
+    pub cloud_readiness: Option<ReadinessScore>,
+    /// The client's risk register
+    pub risks: Vec<RiskItem>,
+    /// Evidence coverage of the assessment behind the compliance data
+    pub evidence_coverage: Option<EvidenceCoverage>,
+    /// Change since the previous assessment, when the latest one was cloned from it
+    pub assessment_comparison: Option<AssessmentComparison>,
+    /// Compliance snapshots over time for the selected framework
+    pub compliance_trend: Option<ComplianceTrend>,
+    /// Compliance across all the client's assessed frameworks
+    pub compliance_overview: Option<ComplianceOverview>,
+    /// Stored remediation plan for the assessment behind the compliance data
+    pub remediation_plan: Option<RemediationPlan>,
+    /// The client's GDPR record of processing activities
+    pub processing_activities: Vec<ProcessingActivity>,
+    /// Kubernetes hardening audit chosen for the technical assessment
+    pub k8s_audit: Option<K8sHardeningAudit>,
+}
+
+impl ReportDataSource {
+    /// Sample content for demos and template previews
+    pub fn demo() -> Self {
+        Self { use_demo_data: true, ..Default::default() }
+    }
+
+    /// Library and rule findings still open or unverifiable, most severe
+    /// first and by CVSS score within a severity
+    fn open_findings(&self) -> Vec<ListedFinding> {
+        let rules = get_finding_rules();
+        let mut findings: Vec<ListedFinding> = self
+            .findings
+            .iter()
+            .filter(|f| f.is_open())
+            .map(ListedFinding::from_library)
+            .chain(
+                self.network_findings
+                    .iter()
+                    .filter(|f| f.status != FindingStatus::Remediated)
+                    .map(|f| ListedFinding::from_rule(f, &rules)),
+            )
+            .collect();
+        findings.sort_by(|a, b| {
+            a.severity.rank().cmp(&b.severity.rank())
+                .then_with(|| b.cvss_score.unwrap_or(-1.0).total_cmp(&a.cvss_score.unwrap_or(-1.0)))
+        });
+        findings
+    }
+
+    /// Non-compliant and partially compliant controls from the remediation
+    /// plan that aren't done or accepted yet, most severe first
+    fn control_gaps(&self) -> Vec<ListedFinding> {
+        let mut gaps: Vec<ListedFinding> = self
+            .remediation_plan
+            .iter()
+            .flat_map(|plan| plan.items())
+            .filter(|item| !item.status.is_closed())
+            .map(ListedFinding::from_control_gap)
+            .collect();
+        gaps.sort_by_key(|g| g.severity.rank());
+        gaps
+    }
+
+    /// Whether any findings were recorded, open or not
+    fn has_findings(&self) -> bool {
+        !self.findings.is_empty() || !self.network_findings.is_empty()
+    }
+}
+
+/// A finding as listed in reports, written up in the findings library or
+/// raised by a detection rule
+struct ListedFinding {
+    title: String,
+    severity: Criticality,
+    cvss_score: Option<f64>,
+    description: String,
+    impact: String,
+    recommendation: String,
+    /// Where the finding applies, for the remediation roadmap
+    affected: String,
+    /// Observations supporting the finding, for the evidence appendix
+    evidence: Vec<String>,
+}
+
+impl ListedFinding {
+    fn from_library(finding: &Finding) -> Self {
+        let mut description = finding.description.clone();
+        if let (Some(vector), Some(score)) = (&finding.cvss_vector, finding.cvss_score) {
+            description.push_str(&format!(" CVSS v3.1 base score {:.1} ({}).", score, vector));
+        }
+        let mut recommendation = finding.recommendation.clone();
+        if !finding.references.is_empty() {
+            recommendation.push_str(&format!(" References: {}.", finding.references.join("; ")));
+        }
+        ListedFinding {
+            title: finding.title.clone(),
+            severity: finding.severity,
+            cvss_score: finding.cvss_score,
+            description,
+            impact: finding.impact.clone(),
+            recommendation,
+            affected: match finding.asset_ids.len() {
+                0 => "-".to_string(),
+                1 => "1 asset".to_string(),
+                n => format!("{} assets", n),
+            },
+            evidence: vec![],
+        }
+    }
+
+    fn from_rule(finding: &NetworkFinding, rules: &[FindingRule]) -> Self {
+        let rule = rules.iter().find(|r| r.id == finding.rule_id);
+        ListedFinding {
+            title: finding.title.clone(),
+            severity: finding.severity,
+            cvss_score: None,
+            description: rule.map(|r| r.description.clone()).unwrap_or_else(|| finding.title.clone()),
+            impact: format!("Observed on {}:{} ({})", finding.host, finding.port, finding.service),
+            recommendation: rule.map(|r| r.recommendation.clone()).unwrap_or_default(),
+            affected: format!("{}:{}", finding.host, finding.port),
+            evidence: finding.evidence.clone(),
+        }
+    }
+}
+
+impl ListedFinding {
+    /// A control gap as a finding, rated from the assessor's risk rating or,
+    /// when unrated, from how far the control falls short
+    fn from_control_gap(item: &RemediationItem) -> Self {
+        let severity = match item.priority() {
+            RemediationPriority::Critical => Criticality::Critical,
+            RemediationPriority::High => Criticality::High,
+            RemediationPriority::Medium => Criticality::Medium,
+            RemediationPriority::Low => Criticality::Low,
+        };
+        ListedFinding {
+            title: format!("{} {}", item.control_code, item.control_title),
+            severity,
+            cvss_score: None,
+            description: item.gap.clone().unwrap_or_else(|| {
+                format!("Control assessed as {}.", item.compliance_status.display_name().to_lowercase())
+            }),
+            impact: String::new(),
+            recommendation: item.recommended_action.clone(),
+            affected: "Control gap".to_string(),
+            evidence: vec![],
+        }
+    }
+}
+
 /// Report generator for creating structured reports
+///
+/// Sections are built per template section: a section deselected in
+/// `ReportConfig::selected_sections` is never built.
 pub struct ReportGenerator {
     config: ReportConfig,
+    data: ReportDataSource,
+    template: ReportTemplate,
+    custom_sections: Vec<CustomSection>,
 }
 
 impl ReportGenerator {
-    pub fn new(config: ReportConfig) -> Self {
-        Self { config }
+    pub fn new(config: ReportConfig, data: ReportDataSource) -> Self {
+        let template = get_template_for_type(config.report_type);
+        Self { config, data, template, custom_sections: vec![] }
     }
 
     /// Generate a complete report
+    ///
+    /// Fails if the section selection doesn't fit the report's template.
     pub fn generate(&self) -> Result<Report, String> {
+        self.check_selection()?;
         let now = chrono::Utc::now().to_rfc3339();
 
         let content = self.build_content()?;
+        let warnings = self.branding_warnings();
+        for warning in &warnings {
+            tracing::warn!("{}", warning);
+        }
 
         Ok(Report {
             id: Uuid::new_v4().to_string(),
@@ -33,38 +243,1062 @@ impl ReportGenerator {
             created_at: now.clone(),
             updated_at: now,
             error: None,
+            warnings,
         })
     }
 
+    /// Branding that can't be applied; the report is rendered without it
+    pub fn branding_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(path) = self.config.logo_path.as_deref() {
+            if let Err(e) = Logo::read(path) {
+                warnings.push(format!("{}; the report has no logo", e));
+            }
+        }
+        if let Some(color) = self.config.primary_color.as_deref() {
+            if normalize_hex_color(color).is_none() {
+                warnings.push(format!("Brand color {} is not a hex color; the default is used", color));
+            }
+        }
+        warnings
+    }
+
     fn build_content(&self) -> Result<ReportContent, String> {
-        let sections = match self.config.report_type {
-            ReportType::ExecutiveSummary => self.build_executive_summary(),
-            ReportType::TechnicalAssessment => self.build_technical_assessment(),
-            ReportType::ComplianceReport => self.build_compliance_report(),
-            ReportType::NetworkAssessment => self.build_network_assessment(),
-            ReportType::CloudReadiness => self.build_cloud_readiness(),
-            ReportType::SecurityFindings => self.build_security_findings(),
-            ReportType::FullEngagement => self.build_full_engagement(),
-        };
-
-        let metadata = ReportMetadata {
-            title: self.config.title.clone(),
-            subtitle: self.config.subtitle.clone(),
-            author: self.config.author.clone(),
-            organization: self.config.organization.clone(),
-            client_name: self.config.client_name.clone(),
-            report_date: chrono::Utc::now().format("%B %d, %Y").to_string(),
-            classification: self.config.classification.clone(),
-            version: "1.0".to_string(),
-            page_count: None,
-        };
-
-        Ok(ReportContent { sections, metadata })
-    }
-
-    fn build_executive_summary(&self) -> Vec<ReportSection> {
-        vec![
-            ReportSection {
+        let mut sections = Vec::new();
+        let mut anchors = Vec::new();
+        for section in &self.template.sections {
+            let built = if self.includes(&section.id) {
+                self.build_section(self.config.report_type, &section.id)
+            } else {
+                vec![]
+            };
+            anchors.push(SectionAnchor { section_id: section.id.clone(), section_count: built.len() });
+            sections.extend(built);
+        }
+
+        sections.extend(self.data_sources_appendix());
+        if !self.config.include_charts {
+            charts_to_tables(&mut sections);
+        }
+
+        let mut content = ReportContent { sections, metadata: self.metadata(), toc: vec![], anchors };
+        place_custom_sections(&mut content, &self.custom_sections, self.config.include_toc);
+        Ok(content)
+    }
+
+    /// Sections of a full engagement part's report type, built per template
+    /// section in template order
+    ///
+    /// Those section ids aren't in the engagement template, so every one of
+    /// them is kept.
+    fn build_sections(&self, report_type: ReportType) -> Vec<ReportSection> {
+        get_template_for_type(report_type)
+            .sections
+            .iter()
+            .filter(|section| self.includes(&section.id))
+            .flat_map(|section| self.build_section(report_type, &section.id))
+            .collect()
+    }
+
+    /// Sections for one template section; empty when it has nothing to show
+    fn build_section(&self, report_type: ReportType, section_id: &str) -> Vec<ReportSection> {
+        let demo = self.data.use_demo_data;
+        match report_type {
+            ReportType::ExecutiveSummary if demo => self.demo_executive_section(section_id),
+            ReportType::ExecutiveSummary => self.executive_section(section_id),
+            ReportType::TechnicalAssessment if demo => self.demo_technical_section(section_id),
+            ReportType::TechnicalAssessment => self.technical_section(section_id),
+            ReportType::ComplianceReport if demo => self.demo_compliance_section(section_id),
+            ReportType::ComplianceReport => self.compliance_section(section_id),
+            ReportType::NetworkAssessment if demo => self.demo_network_section(section_id),
+            ReportType::NetworkAssessment => self.network_section(section_id),
+            ReportType::CloudReadiness if demo => self.demo_cloud_section(section_id),
+            ReportType::CloudReadiness => self.cloud_section(section_id),
+            ReportType::SecurityFindings if demo => self.demo_security_section(section_id),
+            ReportType::SecurityFindings => self.security_section(section_id),
+            ReportType::FullEngagement => self.build_engagement_part(section_id),
+        }
+    }
+
+    /// Check the section selection against the report's template
+    pub fn check_selection(&self) -> Result<(), String> {
+        match &self.config.selected_sections {
+            Some(selected) => validate_section_selection(&self.template, selected),
+            None => Ok(()),
+        }
+    }
+
+    /// The template the report is built from
+    pub fn template(&self) -> &ReportTemplate {
+        &self.template
+    }
+
+    /// Data the report is built from, for filling in between sections
+    pub fn data_mut(&mut self) -> &mut ReportDataSource {
+        &mut self.data
+    }
+
+    /// User-written sections to place around the template sections
+    pub fn set_custom_sections(&mut self, custom_sections: Vec<CustomSection>) {
+        self.custom_sections = custom_sections;
+    }
+
+    /// Record the sources behind the data, for the data sources appendix
+    pub fn set_data_sources(&mut self, data_sources: Vec<DataSource>) {
+        self.config.data_sources = data_sources;
+    }
+
+    /// Cover page and header details
+    pub fn metadata(&self) -> ReportMetadata {
+        report_metadata(&self.config)
+    }
+
+    /// Data sources appendix, when appendices are wanted and sources were recorded
+    pub fn data_sources_appendix(&self) -> Option<ReportSection> {
+        (self.config.include_appendices && self.includes("appendices") && !self.config.data_sources.is_empty())
+            .then(|| self.build_data_sources_appendix())
+    }
+
+    /// Whether the template section with this id is selected
+    pub fn includes(&self, section_id: &str) -> bool {
+        section_included(&self.template, section_id, self.config.selected_sections.as_deref())
+    }
+
+    fn executive_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let section = match section_id {
+            "exec-overview" => ReportSection {
+                id: "exec-overview".to_string(),
+                title: "Executive Overview".to_string(),
+                level: 1,
+                blocks: vec![
+                    ContentBlock::Paragraph {
+                        text: format!(
+                            "This executive summary provides a high-level overview of the security assessment \
+                            conducted for {}. The assessment evaluated the organization's security posture \
+                            across multiple domains including infrastructure, compliance, and risk management.",
+                            self.config.client_name
+                        ),
+                    },
+                    ContentBlock::KeyValue {
+                        items: vec![
+                            KeyValueItem { key: "Assessment Date".to_string(), value: chrono::Utc::now().format("%B %Y").to_string() },
+                            KeyValueItem { key: "Scope".to_string(), value: self.scope_summary() },
+                            KeyValueItem { key: "Classification".to_string(), value: self.config.classification.clone().unwrap_or_else(|| "Confidential".to_string()) },
+                        ],
+                    },
+                ],
+                subsections: vec![],
+            },
+            "key-findings" => ReportSection {
+                id: "key-findings".to_string(),
+                title: "Key Findings".to_string(),
+                level: 1,
+                blocks: self.key_findings_blocks(),
+                subsections: vec![],
+            },
+            "risk-summary" => ReportSection {
+                id: "risk-summary".to_string(),
+                title: "Risk Summary".to_string(),
+                level: 1,
+                blocks: vec![self.risk_summary_block()],
+                subsections: vec![],
+            },
+            "recommendations" => {
+                let recommendations = self.recommendations();
+                ReportSection {
+                    id: "recommendations".to_string(),
+                    title: "Strategic Recommendations".to_string(),
+                    level: 1,
+                    blocks: vec![if recommendations.is_empty() {
+                        ContentBlock::Paragraph {
+                            text: "No open findings or compliance gaps were recorded, so there are no recommendations yet.".to_string(),
+                        }
+                    } else {
+                        ContentBlock::NumberedList { items: recommendations }
+                    }],
+                    subsections: vec![],
+                }
+            }
+            _ => return vec![],
+        };
+        vec![section]
+    }
+
+    /// Urgent findings and compliance scores for the executive summary
+    fn key_findings_blocks(&self) -> Vec<ContentBlock> {
+        let open = self.data.open_findings();
+        let counts = severity_counts(&open);
+
+        let mut blocks = Vec::new();
+        if counts[0] + counts[1] > 0 {
+            blocks.push(ContentBlock::Callout {
+                callout_type: CalloutType::Critical,
+                title: Some("Critical Findings".to_string()),
+                text: format!(
+                    "{} critical and {} high severity findings remain open and require attention",
+                    counts[0], counts[1]
+                ),
+            });
+        } else if self.data.has_findings() {
+            blocks.push(ContentBlock::Callout {
+                callout_type: CalloutType::Success,
+                title: Some("No Urgent Findings".to_string()),
+                text: "No critical or high severity findings remain open".to_string(),
+            });
+        }
+        if let Some(overview) = self.multi_framework_overview() {
+            blocks.push(ContentBlock::Metric {
+                label: "Overall Compliance".to_string(),
+                value: format!("{:.1}%", overview.overall_compliance_percentage),
+                change: None,
+                trend: None,
+            });
+            for framework in &overview.frameworks {
+                blocks.push(ContentBlock::Metric {
+                    label: format!("{} Compliance", framework.framework.display_name()),
+                    value: format!("{:.1}%", framework.status.compliance_percentage),
+                    change: None,
+                    trend: None,
+                });
+            }
+            if overview.unassessed_controls > 0 {
+                blocks.push(ContentBlock::Callout {
+                    callout_type: CalloutType::Info,
+                    title: Some("Assessment Coverage".to_string()),
+                    text: format!(
+                        "{} of {} controls across {} frameworks have not been assessed yet",
+                        overview.unassessed_controls,
+                        overview.total_controls,
+                        overview.frameworks.len()
+                    ),
+                });
+            }
+        } else if let Some(compliance) = &self.data.compliance {
+            blocks.push(ContentBlock::Metric {
+                label: format!("{} Compliance", compliance.framework.display_name()),
+                value: format!("{:.1}%", compliance.compliance_percentage),
+                change: None,
+                trend: None,
+            });
+        }
+        if !open.is_empty() {
+            blocks.push(severity_chart(ChartType::Pie, &counts));
+        }
+        if blocks.is_empty() {
+            blocks.push(no_data("finding or compliance", "Run a network scan or record a compliance assessment."));
+        }
+        blocks
+    }
+
+    fn technical_section(&self, section_id: &str) -> Vec<ReportSection> {
+        match section_id {
+            "tech-overview" => vec![ReportSection {
+                id: "tech-overview".to_string(),
+                title: "Technical Assessment Overview".to_string(),
+                level: 1,
+                blocks: vec![
+                    ContentBlock::Paragraph {
+                        text: "This technical assessment provides detailed analysis of security vulnerabilities, \
+                              misconfigurations, and areas for improvement identified during the engagement.".to_string(),
+                    },
+                ],
+                subsections: vec![],
+            }],
+            "methodology" => vec![methodology_section()],
+            "findings-summary" => {
+                let open = self.data.open_findings();
+                let blocks = if open.is_empty() {
+                    vec![self.no_open_findings()]
+                } else {
+                    findings_summary_blocks(&severity_counts(&open))
+                };
+                vec![findings_summary_section(blocks)]
+            }
+            "detailed-findings" => {
+                let mut blocks: Vec<ContentBlock> = self
+                    .data
+                    .open_findings()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| finding_block(i, f))
+                    .collect();
+                if blocks.is_empty() {
+                    blocks.push(self.no_open_findings());
+                }
+                vec![ReportSection {
+                    id: "findings".to_string(),
+                    title: "Detailed Findings".to_string(),
+                    level: 1,
+                    blocks,
+                    subsections: vec![],
+                }]
+            }
+            "k8s-hardening" => self.data.k8s_audit.iter().map(k8s_hardening_section).collect(),
+            "remediation" => {
+                let open = self.data.open_findings();
+                if open.is_empty() {
+                    return vec![];
+                }
+                vec![ReportSection {
+                    id: "remediation".to_string(),
+                    title: "Remediation Roadmap".to_string(),
+                    level: 1,
+                    blocks: vec![
+                        ContentBlock::Table {
+                            headers: vec!["Finding".to_string(), "Priority".to_string(), "Affected".to_string(), "Timeline".to_string()],
+                            rows: open
+                                .iter()
+                                .enumerate()
+                                .map(|(i, f)| {
+                                    vec![
+                                        finding_id(i),
+                                        format!("{:?}", f.severity),
+                                        f.affected.clone(),
+                                        remediation_timeline(f.severity).to_string(),
+                                    ]
+                                })
+                                .collect(),
+                            caption: Some("Recommended remediation timeline".to_string()),
+                        },
+                    ],
+                    subsections: vec![],
+                }]
+            }
+            "appendix-tools" if self.config.include_appendices => vec![tools_appendix(self.tools_used())],
+            "appendix-evidence" if self.config.include_appendices => vec![self.evidence_appendix()],
+            _ => vec![],
+        }
+    }
+
+    /// Tools behind the data attached to the report, with what each was used for
+    fn tools_used(&self) -> Vec<Vec<String>> {
+        let data = &self.data;
+        let scanned = data.network_stats.as_ref().map_or(false, |s| s.total_assets > 0) || !data.network_findings.is_empty();
+        [
+            (scanned, "Nmap", "Host discovery, port scanning and service version detection"),
+            (!data.network_findings.is_empty(), "Detection rules", "Findings raised against scan results"),
+            (!data.vulnerabilities.is_empty(), "CVE matching", "Published CVEs matched to detected service versions"),
+            (!data.verification_scans.is_empty(), "Verification scans", "Targeted re-tests of reported findings"),
+            (data.k8s_audit.is_some(), "Kubernetes hardening checklist", "Cluster configuration review"),
+            (!data.findings.is_empty(), "Findings library", "Manually validated and rated findings"),
+        ]
+        .into_iter()
+        .filter(|(used, _, _)| *used)
+        .map(|(_, tool, purpose)| vec![tool.to_string(), purpose.to_string()])
+        .collect()
+    }
+
+    /// Observations recorded against open findings, numbered like the detailed findings
+    fn evidence_appendix(&self) -> ReportSection {
+        let rows: Vec<Vec<String>> = self
+            .data
+            .open_findings()
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !f.evidence.is_empty())
+            .map(|(i, f)| vec![finding_id(i), f.title.clone(), f.affected.clone(), f.evidence.join("; ")])
+            .collect();
+        let block = if rows.is_empty() {
+            no_data("evidence", "Detection rules and verification scans record evidence against the findings they raise.")
+        } else {
+            evidence_table(rows)
+        };
+        evidence_appendix_section(block)
+    }
+
+    fn compliance_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let compliance = match &self.data.compliance {
+            Some(c) => c,
+            None if section_id == "compliance-overview" => return vec![ReportSection {
+                id: "compliance-overview".to_string(),
+                title: "Compliance Assessment Overview".to_string(),
+                level: 1,
+                blocks: vec![no_data("compliance", "Select a framework with a recorded assessment for this client.")],
+                subsections: vec![],
+            }],
+            None => return vec![],
+        };
+
+        match section_id {
+            "compliance-overview" => vec![ReportSection {
+                id: "compliance-overview".to_string(),
+                title: "Compliance Assessment Overview".to_string(),
+                level: 1,
+                blocks: vec![
+                    ContentBlock::Paragraph {
+                        text: format!(
+                            "This report presents the findings from the compliance assessment against {}.",
+                            compliance.framework.display_name()
+                        ),
+                    },
+                    ContentBlock::Chart {
+                        chart_type: ChartType::Gauge,
+                        title: "Overall Compliance Score".to_string(),
+                        data: ChartData {
+                            labels: vec!["Compliance".to_string()],
+                            datasets: vec![ChartDataset {
+                                label: "Score".to_string(),
+                                data: vec![(compliance.compliance_percentage * 10.0).round() / 10.0],
+                                color: Some("#22C55E".to_string()),
+                            }],
+                        },
+                    },
+                    ContentBlock::KeyValue {
+                        items: {
+                            let mut items = vec![
+                                KeyValueItem { key: "Controls Assessed".to_string(), value: format!("{}/{}", compliance.assessed_controls, compliance.total_controls) },
+                                KeyValueItem { key: "Compliant".to_string(), value: compliance.compliant_controls.to_string() },
+                                KeyValueItem { key: "Partially Compliant".to_string(), value: compliance.partially_compliant_controls.to_string() },
+                                KeyValueItem { key: "Non-Compliant".to_string(), value: compliance.non_compliant_controls.to_string() },
+                            ];
+                            if let Some(maturity) = compliance.average_maturity {
+                                items.push(KeyValueItem {
+                                    key: "Average Maturity".to_string(),
+                                    value: format!("{:.1} / {}", maturity, MAX_MATURITY_LEVEL),
+                                });
+                            }
+                            items
+                        },
+                    },
+                ],
+                subsections: vec![],
+            }],
+            "framework-status" => {
+                let mut sections = vec![ReportSection {
+                    id: "framework-status".to_string(),
+                    title: "Framework Compliance Status".to_string(),
+                    level: 1,
+                    blocks: vec![self.framework_status_table(), category_compliance_chart(compliance)],
+                    subsections: vec![],
+                }];
+                if let Some(comparison) = &self.data.assessment_comparison {
+                    sections.push(assessment_comparison_section(comparison));
+                }
+                // A single snapshot is not a trend
+                if let Some(trend) = self.data.compliance_trend.as_ref().filter(|t| t.points.len() > 1) {
+                    sections.push(compliance_trend_section(trend));
+                }
+                sections
+            }
+            "control-matrix" if compliance.framework == Framework::Gdpr && !self.data.processing_activities.is_empty() => {
+                vec![processing_coverage_section(&self.data.processing_activities)]
+            }
+            "gap-analysis" => vec![ReportSection {
+                id: "gaps".to_string(),
+                title: "Gap Analysis".to_string(),
+                level: 1,
+                blocks: gap_analysis_blocks(compliance),
+                subsections: vec![],
+            }],
+            "remediation-plan" => self.data.remediation_plan.iter().map(remediation_plan_section).collect(),
+            "evidence-summary" => self.data.evidence_coverage.iter().map(evidence_summary_section).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn network_section(&self, section_id: &str) -> Vec<ReportSection> {
+        if section_id == "security-posture" {
+            let mut sections = Vec::new();
+            if !self.data.policy_violations.is_empty() {
+                sections.push(policy_violations_section(&self.data.policy_violations));
+            }
+            if let Some(diff) = &self.data.scan_diff {
+                sections.push(scan_diff_section(diff));
+            }
+            return sections;
+        }
+
+        let stats = match &self.data.network_stats {
+            Some(stats) if stats.total_assets > 0 => stats,
+            _ if section_id == "network-overview" => return vec![ReportSection {
+                id: "network-overview".to_string(),
+                title: "Network Assessment Overview".to_string(),
+                level: 1,
+                blocks: vec![no_data("asset inventory", "Run discovery or import scan results to build the inventory.")],
+                subsections: vec![],
+            }],
+            _ => return vec![],
+        };
+
+        let section = match section_id {
+            "network-overview" => {
+                let critical_assets = stats
+                    .by_criticality
+                    .iter()
+                    .filter(|c| c.criticality == Criticality::Critical)
+                    .map(|c| c.count)
+                    .sum::<usize>();
+                ReportSection {
+                    id: "network-overview".to_string(),
+                    title: "Network Assessment Overview".to_string(),
+                    level: 1,
+                    blocks: vec![
+                        ContentBlock::Paragraph {
+                            text: "This report documents the network infrastructure assessment including \
+                                  asset discovery, topology mapping, and security posture analysis.".to_string(),
+                        },
+                        ContentBlock::KeyValue {
+                            items: vec![
+                                KeyValueItem { key: "Total Assets Discovered".to_string(), value: stats.total_assets.to_string() },
+                                KeyValueItem { key: "Active Assets".to_string(), value: stats.active_assets.to_string() },
+                                KeyValueItem { key: "Critical Systems".to_string(), value: critical_assets.to_string() },
+                                KeyValueItem { key: "Open Findings".to_string(), value: self.data.open_findings().len().to_string() },
+                                KeyValueItem { key: "Known Vulnerabilities".to_string(), value: stats.known_vulnerabilities.to_string() },
+                            ],
+                        },
+                    ],
+                    subsections: vec![],
+                }
+            }
+            "asset-inventory" => ReportSection {
+                id: "asset-inventory".to_string(),
+                title: "Asset Inventory Summary".to_string(),
+                level: 1,
+                blocks: vec![assets_by_category_chart(stats)],
+                subsections: vec![],
+            },
+            "service-analysis" => {
+                let rules = get_finding_rules();
+                ReportSection {
+                    id: "services".to_string(),
+                    title: "Service Analysis".to_string(),
+                    level: 1,
+                    blocks: vec![
+                        ContentBlock::Table {
+                            headers: vec!["Service".to_string(), "Port".to_string(), "Instances".to_string(), "Risk Level".to_string()],
+                            rows: stats
+                                .top_services
+                                .iter()
+                                .map(|s| {
+                                    let risk = rules
+                                        .iter()
+                                        .filter(|r| r.ports.contains(&s.port))
+                                        .map(|r| r.severity)
+                                        .min_by_key(|sev| sev.rank())
+                                        .map(|sev| format!("{:?}", sev))
+                                        .unwrap_or_else(|| "Not flagged".to_string());
+                                    vec![s.service.clone(), s.port.to_string(), s.count.to_string(), risk]
+                                })
+                                .collect(),
+                            caption: Some("Top services discovered across the network".to_string()),
+                        },
+                    ],
+                    subsections: vec![],
+                }
+            }
+            _ => return vec![],
+        };
+        vec![section]
+    }
+
+    fn cloud_section(&self, section_id: &str) -> Vec<ReportSection> {
+        match section_id {
+            "cloud-overview" => vec![ReportSection {
+                id: "cloud-overview".to_string(),
+                title: "Cloud Readiness Assessment".to_string(),
+                level: 1,
+                blocks: vec![
+                    ContentBlock::Paragraph {
+                        text: "This assessment evaluates the organization's readiness for cloud migration \
+                              and provides recommendations for a successful transition.".to_string(),
+                    },
+                    match &self.data.cloud_readiness {
+                        Some(score) => ContentBlock::Metric {
+                            label: "Cloud Readiness Score".to_string(),
+                            value: format!("{:.0}% ({})", score.overall_score, score.tier.display_name()),
+                            change: None,
+                            trend: None,
+                        },
+                        None => no_data("readiness scoring", "Complete the cloud readiness checklist to score this assessment."),
+                    },
+                ],
+                subsections: vec![],
+            }],
+            "readiness-score" => self
+                .data
+                .cloud_readiness
+                .iter()
+                .map(|score| ReportSection {
+                    id: "readiness-by-area".to_string(),
+                    title: "Readiness by Area".to_string(),
+                    level: 1,
+                    blocks: readiness_by_area_blocks(score),
+                    subsections: vec![],
+                })
+                .collect(),
+            "cost-analysis" => vec![ReportSection {
+                id: "cost-analysis".to_string(),
+                title: "Cost Analysis".to_string(),
+                level: 1,
+                blocks: self.cost_analysis_blocks(),
+                subsections: vec![],
+            }],
+            _ => vec![],
+        }
+    }
+
+    /// Provider comparison and multi-year projections attached to the report
+    fn cost_analysis_blocks(&self) -> Vec<ContentBlock> {
+        let mut blocks = self.data.provider_comparison.as_ref().map(provider_comparison_blocks).unwrap_or_default();
+
+        let projections = &self.data.cost_projections;
+        let best = match projections.iter().max_by(|a, b| a.cumulative_savings.total_cmp(&b.cumulative_savings)) {
+            Some(p) => p,
+            None if blocks.is_empty() => {
+                return vec![no_data(
+                    "cost",
+                    "Compare providers or run a TCO projection in the FinOps calculator and include it in the report.",
+                )]
+            }
+            None => return blocks,
+        };
+
+        blocks.extend([
+            ContentBlock::Table {
+                headers: vec![
+                    "Provider".to_string(),
+                    "Commitment".to_string(),
+                    "Year 1 Cloud Cost".to_string(),
+                    "Final Year Cloud Cost".to_string(),
+                    "Cumulative Savings".to_string(),
+                    "Break-even".to_string(),
+                ],
+                rows: projections
+                    .iter()
+                    .map(|p| {
+                        vec![
+                            p.provider.short_name().to_string(),
+                            p.commitment.display_name().to_string(),
+                            p.years.first().map(|y| currency(y.cloud_cost)).unwrap_or_default(),
+                            p.years.last().map(|y| currency(y.cloud_cost)).unwrap_or_default(),
+                            currency(p.cumulative_savings),
+                            p.break_even_month
+                                .map(|m| format!("Month {}", m))
+                                .unwrap_or_else(|| "Not within horizon".to_string()),
+                        ]
+                    })
+                    .collect(),
+                caption: Some("Projected cloud costs by provider".to_string()),
+            },
+            ContentBlock::Table {
+                headers: vec![
+                    "Year".to_string(),
+                    "On-Premises".to_string(),
+                    "Cloud".to_string(),
+                    "Net Savings".to_string(),
+                    "Cumulative".to_string(),
+                ],
+                rows: best
+                    .years
+                    .iter()
+                    .map(|y| {
+                        vec![
+                            format!("Year {}", y.year),
+                            currency(y.on_prem_cost),
+                            currency(y.cloud_cost + y.migration_cost),
+                            currency(y.net_savings),
+                            currency(y.cumulative_savings),
+                        ]
+                    })
+                    .collect(),
+                caption: Some(format!(
+                    "{}-year projection for {} ({}); cloud costs include migration",
+                    best.horizon_years,
+                    best.provider.short_name(),
+                    best.commitment.display_name()
+                )),
+            },
+        ]);
+        blocks
+    }
+
+    fn security_section(&self, section_id: &str) -> Vec<ReportSection> {
+        match section_id {
+            "findings-overview" => {
+                let open = self.data.open_findings();
+                let mut blocks = vec![ContentBlock::Paragraph {
+                    text: "This report presents security findings identified during the assessment, \
+                          organized by severity and including remediation recommendations.".to_string(),
+                }];
+                if open.is_empty() {
+                    blocks.push(self.no_open_findings());
+                } else {
+                    blocks.push(severity_chart(ChartType::Donut, &severity_counts(&open)));
+                }
+                vec![ReportSection {
+                    id: "findings-overview".to_string(),
+                    title: "Security Findings Overview".to_string(),
+                    level: 1,
+                    blocks,
+                    subsections: vec![],
+                }]
+            }
+            // High findings are listed with the critical ones
+            "critical-findings" => {
+                let urgent = self.findings_of(&[Criticality::Critical, Criticality::High]);
+                if urgent.is_empty() {
+                    return vec![];
+                }
+                let mut blocks = vec![ContentBlock::Callout {
+                    callout_type: CalloutType::Critical,
+                    title: Some("Immediate Action Required".to_string()),
+                    text: "The following findings require immediate attention due to their severity.".to_string(),
+                }];
+                blocks.extend(urgent);
+                vec![ReportSection {
+                    id: "critical-findings".to_string(),
+                    title: "Critical Findings".to_string(),
+                    level: 1,
+                    blocks,
+                    subsections: vec![],
+                }]
+            }
+            // Low and informational findings are listed with the medium ones,
+            // followed by the CVEs matched to service versions
+            "medium-findings" => {
+                let mut sections = Vec::new();
+                let other = self.findings_of(&[Criticality::Medium, Criticality::Low, Criticality::Informational]);
+                if !other.is_empty() {
+                    sections.push(ReportSection {
+                        id: "other-findings".to_string(),
+                        title: "Additional Findings".to_string(),
+                        level: 1,
+                        blocks: other,
+                        subsections: vec![],
+                    });
+                }
+                if !self.data.vulnerabilities.is_empty() {
+                    sections.push(self.known_vulnerabilities_section());
+                }
+                sections
+            }
+            "remediation-priority" => vec![self.remediation_priority_section(&self.data.open_findings())],
+            "remediation-progress" => self.build_remediation_progress().into_iter().collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Open findings of these severities whose template sections are selected
+    ///
+    /// Numbered across all open findings so ids match the other report types.
+    fn findings_of(&self, severities: &[Criticality]) -> Vec<ContentBlock> {
+        self.data
+            .open_findings()
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| severities.contains(&f.severity) && self.includes(severity_section(f.severity)))
+            .map(|(i, f)| finding_block(i, f))
+            .collect()
+    }
+
+    /// Open findings and outstanding control gaps in the order to fix them
+    fn remediation_priority_section(&self, open: &[ListedFinding]) -> ReportSection {
+        let gaps = self.data.control_gaps();
+        let mut rows: Vec<(Criticality, Vec<String>)> = open
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (f.severity, vec![finding_id(i), f.title.clone(), f.affected.clone()]))
+            .chain(gaps.iter().map(|g| (g.severity, vec!["-".to_string(), g.title.clone(), g.affected.clone()])))
+            .collect();
+        // Stable, so findings stay ahead of control gaps of the same severity
+        rows.sort_by_key(|(severity, _)| severity.rank());
+
+        let block = if rows.is_empty() {
+            if self.data.has_findings() || self.data.remediation_plan.is_some() {
+                ContentBlock::Callout {
+                    callout_type: CalloutType::Success,
+                    title: Some("Nothing To Remediate".to_string()),
+                    text: "All recorded findings and control gaps have been remediated.".to_string(),
+                }
+            } else {
+                no_data("finding or remediation plan", "Record findings or generate a remediation plan for the client's assessment.")
+            }
+        } else {
+            ContentBlock::Table {
+                headers: ["ID", "Issue", "Affected", "Severity", "Fix Within"].iter().map(|h| h.to_string()).collect(),
+                rows: rows
+                    .into_iter()
+                    .map(|(severity, mut row)| {
+                        row.push(format!("{:?}", severity));
+                        row.push(remediation_timeline(severity).to_string());
+                        row
+                    })
+                    .collect(),
+                caption: (!gaps.is_empty())
+                    .then(|| "Control gaps come from non-compliant and partially compliant controls in the remediation plan".to_string()),
+            }
+        };
+
+        ReportSection {
+            id: "remediation-priority".to_string(),
+            title: "Remediation Priority".to_string(),
+            level: 1,
+            blocks: vec![block],
+            subsections: vec![],
+        }
+    }
+
+    /// CVEs matched to detected service versions, one finding per CVE with
+    /// known exploited CVEs first
+    fn known_vulnerabilities_section(&self) -> ReportSection {
+        let vulnerabilities = &self.data.vulnerabilities;
+        let assets = vulnerabilities
+            .iter()
+            .map(|v| v.asset_id.as_str())
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+
+        let mut by_cve: Vec<(&str, Vec<&VulnerabilityMatch>)> = Vec::new();
+        for v in vulnerabilities {
+            match by_cve.iter_mut().find(|(cve, _)| *cve == v.cve_id) {
+                Some((_, matches)) => matches.push(v),
+                None => by_cve.push((&v.cve_id, vec![v])),
+            }
+        }
+        by_cve.sort_by(|(_, a), (_, b)| {
+            b[0].kev.cmp(&a[0].kev).then(b[0].cvss_score.partial_cmp(&a[0].cvss_score).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        let known_exploited = by_cve.iter().filter(|(_, m)| m[0].kev).count();
+
+        let mut intro = format!(
+            "{} published CVE(s) affect the service versions detected on {} asset(s). \
+             Matches are based on version banners and should be confirmed, as vendors \
+             often backport fixes without changing the reported version.",
+            by_cve.len(),
+            assets
+        );
+        if known_exploited > 0 {
+            intro.push_str(&format!(
+                " {} of them are listed in the CISA Known Exploited Vulnerabilities catalog and should be treated first.",
+                known_exploited
+            ));
+        }
+
+        let mut blocks = vec![ContentBlock::Paragraph { text: intro }];
+        blocks.extend(by_cve.iter().map(|(cve, matches)| {
+            let first = matches[0];
+            let affected: Vec<String> = matches
+                .iter()
+                .map(|v| format!("{} ({}) {}/{:?}", v.asset_name, v.ip_address, v.port, v.protocol).to_lowercase())
+                .collect();
+            let mut impact = format!(
+                "{} {} is in the affected range ({}) on: {}.",
+                first.product,
+                first.version,
+                first.matched_range,
+                affected.join(", ")
+            );
+            if first.kev {
+                impact.push_str(" This CVE is being actively exploited in the wild.");
+            }
+
+            ContentBlock::Finding {
+                id: cve.to_string(),
+                title: format!("{} in {}", cve, first.product),
+                severity: format!("{:?}", first.severity),
+                description: format!("{} (CVSS {:.1})", first.summary, first.cvss_score),
+                impact,
+                recommendation: format!(
+                    "Upgrade {} to a release outside the affected range ({}), or confirm with the vendor that the \
+                     installed build includes the fix.",
+                    first.product, first.matched_range
+                ),
+            }
+        }));
+
+        ReportSection {
+            id: "known-vulnerabilities".to_string(),
+            title: "Known Vulnerabilities".to_string(),
+            level: 1,
+            blocks,
+            subsections: vec![],
+        }
+    }
+
+    /// Callout for when no findings are open: either everything was fixed or nothing was scanned
+    fn no_open_findings(&self) -> ContentBlock {
+        if !self.data.has_findings() {
+            no_data("finding", "Record findings from the findings library or run detection rules against scan results.")
+        } else {
+            ContentBlock::Callout {
+                callout_type: CalloutType::Success,
+                title: Some("No Open Findings".to_string()),
+                text: "All recorded findings have been verified as remediated.".to_string(),
+            }
+        }
+    }
+
+    /// Active register risks by current exposure, falling back to compliance
+    /// categories ranked from weakest to strongest when the register is empty
+    fn risk_summary_block(&self) -> ContentBlock {
+        let mut risks: Vec<&RiskItem> = self.data.risks.iter().filter(|r| r.is_active()).collect();
+        if !risks.is_empty() {
+            risks.sort_by_key(|r| std::cmp::Reverse(r.current_score()));
+            return ContentBlock::Table {
+                headers: ["Risk", "Owner", "Score", "Level", "Status", "Due"].iter().map(|h| h.to_string()).collect(),
+                rows: risks
+                    .iter()
+                    .map(|r| {
+                        vec![
+                            r.title.clone(),
+                            r.owner.clone().unwrap_or_else(|| "Unassigned".to_string()),
+                            match r.residual_score {
+                                Some(residual) => format!("{} (inherent {})", residual, r.inherent_score),
+                                None => r.inherent_score.to_string(),
+                            },
+                            r.level().display_name().to_string(),
+                            r.status.display_name().to_string(),
+                            r.due_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string()),
+                        ]
+                    })
+                    .collect(),
+                caption: Some(format!("{} active risks from the risk register", risks.len())),
+            };
+        }
+
+        let compliance = match &self.data.compliance {
+            Some(c) => c,
+            None => return no_data("compliance", "Record a compliance assessment to summarize risk by area."),
+        };
+
+        let mut assessed: Vec<_> = compliance
+            .category_breakdown
+            .iter()
+            .filter(|c| c.assessed_controls > 0)
+            .collect();
+        if assessed.is_empty() {
+            return no_data("assessed control", "Assess controls in the selected framework to summarize risk by area.");
+        }
+        assessed.sort_by(|a, b| a.compliance_percentage.total_cmp(&b.compliance_percentage));
+
+        ContentBlock::Table {
+            headers: vec!["Risk Area".to_string(), "Controls Assessed".to_string(), "Compliance".to_string(), "Priority".to_string()],
+            rows: assessed
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.name.clone(),
+                        format!("{}/{}", c.assessed_controls, c.total_controls),
+                        format!("{:.1}%", c.compliance_percentage),
+                        gap_priority(c.compliance_percentage).to_string(),
+                    ]
+                })
+                .collect(),
+            caption: Some(format!("Risk summary by {} category", compliance.framework.display_name())),
+        }
+    }
+
+    /// Recommendations from open findings (most severe first), then the weakest compliance areas
+    fn recommendations(&self) -> Vec<String> {
+        let mut items: Vec<String> = Vec::new();
+
+        for finding in self.data.open_findings().iter().filter(|f| !f.recommendation.is_empty()) {
+            let item = format!("{}: {}", finding.title, finding.recommendation);
+            if !items.contains(&item) {
+                items.push(item);
+            }
+        }
+        items.truncate(5);
+
+        if let Some(overview) = self.multi_framework_overview() {
+            items.extend(overview.high_risk_gaps.iter().take(3).map(|gap| {
+                let mut item = format!(
+                    "Remediate {} {} ({}, risk {}/5)",
+                    gap.control_code,
+                    gap.control_title,
+                    gap.framework.display_name(),
+                    gap.risk_rating
+                );
+                if !gap.related_controls.is_empty() {
+                    let related: Vec<String> = gap
+                        .related_controls
+                        .iter()
+                        .map(|c| format!("{} {}", c.framework.display_name(), c.code))
+                        .collect();
+                    item.push_str(&format!("; also closes {}", related.join(", ")));
+                }
+                item
+            }));
+        }
+
+        if let Some(compliance) = &self.data.compliance {
+            let mut gaps: Vec<_> = compliance
+                .category_breakdown
+                .iter()
+                .filter(|c| c.non_compliant + c.partially_compliant > 0)
+                .collect();
+            gaps.sort_by(|a, b| a.compliance_percentage.total_cmp(&b.compliance_percentage));
+            items.extend(gaps.iter().take(3).map(|c| {
+                format!(
+                    "Close control gaps in {} ({} non-compliant, {} partially compliant)",
+                    c.name, c.non_compliant, c.partially_compliant
+                )
+            }));
+        }
+
+        items
+    }
+
+    /// The combined compliance overview, when the client is assessed against
+    /// more than one framework
+    fn multi_framework_overview(&self) -> Option<&ComplianceOverview> {
+        self.data.compliance_overview.as_ref().filter(|o| o.is_multi_framework())
+    }
+
+    /// Modules that contributed data to this report
+    fn scope_summary(&self) -> String {
+        let mut scope = Vec::new();
+        if let Some(overview) = self.multi_framework_overview() {
+            let frameworks: Vec<&str> = overview.frameworks.iter().map(|f| f.framework.display_name()).collect();
+            scope.push(format!("Compliance ({})", frameworks.join(", ")));
+        } else if let Some(compliance) = &self.data.compliance {
+            scope.push(format!("Compliance ({})", compliance.framework.display_name()));
+        }
+        if let Some(stats) = &self.data.network_stats {
+            scope.push(format!("Network ({} assets)", stats.total_assets));
+        }
+        if self.data.has_findings() {
+            scope.push("Security Findings".to_string());
+        }
+        if !self.data.cost_projections.is_empty() || self.data.provider_comparison.is_some() {
+            scope.push("Cloud Costs".to_string());
+        }
+        if scope.is_empty() {
+            "No assessment data recorded".to_string()
+        } else {
+            scope.join(", ")
+        }
+    }
+
+    /// Appendix listing the data each part of the report was built from
+    fn build_data_sources_appendix(&self) -> ReportSection {
+        ReportSection {
+            id: "data-sources".to_string(),
+            title: "Appendix: Data Sources".to_string(),
+            level: 1,
+            blocks: vec![ContentBlock::Table {
+                headers: vec!["Source".to_string(), "Reference".to_string(), "Included".to_string()],
+                rows: self
+                    .config
+                    .data_sources
+                    .iter()
+                    .map(|d| {
+                        vec![
+                            d.source_type.clone(),
+                            d.source_id.clone(),
+                            if d.included { "Yes" } else { "No" }.to_string(),
+                        ]
+                    })
+                    .collect(),
+                caption: Some("Data sources used to generate this report".to_string()),
+            }],
+            subsections: vec![],
+        }
+    }
+
+    fn demo_executive_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let section = match section_id {
+            "exec-overview" => ReportSection {
                 id: "exec-overview".to_string(),
                 title: "Executive Overview".to_string(),
                 level: 1,
@@ -87,7 +1321,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "key-findings" => ReportSection {
                 id: "key-findings".to_string(),
                 title: "Key Findings".to_string(),
                 level: 1,
@@ -118,7 +1352,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "risk-summary" => ReportSection {
                 id: "risk-summary".to_string(),
                 title: "Risk Summary".to_string(),
                 level: 1,
@@ -136,7 +1370,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "recommendations" => ReportSection {
                 id: "recommendations".to_string(),
                 title: "Strategic Recommendations".to_string(),
                 level: 1,
@@ -153,12 +1387,14 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-        ]
+            _ => return vec![],
+        };
+        vec![section]
     }
 
-    fn build_technical_assessment(&self) -> Vec<ReportSection> {
-        vec![
-            ReportSection {
+    fn demo_technical_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let section = match section_id {
+            "tech-overview" => ReportSection {
                 id: "tech-overview".to_string(),
                 title: "Technical Assessment Overview".to_string(),
                 level: 1,
@@ -170,27 +1406,9 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
-                id: "methodology".to_string(),
-                title: "Methodology".to_string(),
-                level: 1,
-                blocks: vec![
-                    ContentBlock::Paragraph {
-                        text: "The assessment followed industry-standard methodologies including OWASP, NIST, and PTES.".to_string(),
-                    },
-                    ContentBlock::BulletList {
-                        items: vec![
-                            "Network reconnaissance and enumeration".to_string(),
-                            "Vulnerability scanning and validation".to_string(),
-                            "Configuration review and hardening analysis".to_string(),
-                            "Access control and authentication testing".to_string(),
-                            "Security architecture review".to_string(),
-                        ],
-                    },
-                ],
-                subsections: vec![],
-            },
-            ReportSection {
+            "methodology" => methodology_section(),
+            "findings-summary" => findings_summary_section(findings_summary_blocks(&[0, 1, 1, 0, 0])),
+            "detailed-findings" => ReportSection {
                 id: "findings".to_string(),
                 title: "Detailed Findings".to_string(),
                 level: 1,
@@ -214,7 +1432,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "remediation" => ReportSection {
                 id: "remediation".to_string(),
                 title: "Remediation Roadmap".to_string(),
                 level: 1,
@@ -230,12 +1448,32 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-        ]
+            "appendix-tools" if self.config.include_appendices => tools_appendix(vec![
+                vec!["Nmap".to_string(), "Host discovery, port scanning and service version detection".to_string()],
+                vec!["Detection rules".to_string(), "Findings raised against scan results".to_string()],
+            ]),
+            "appendix-evidence" if self.config.include_appendices => evidence_appendix_section(evidence_table(vec![
+                vec![
+                    "FIND-001".to_string(),
+                    "Outdated SSL/TLS Configuration".to_string(),
+                    "3 assets".to_string(),
+                    "TLSv1.0 and TLSv1.1 accepted on 443/tcp".to_string(),
+                ],
+                vec![
+                    "FIND-002".to_string(),
+                    "Missing Security Headers".to_string(),
+                    "2 assets".to_string(),
+                    "No Content-Security-Policy or X-Frame-Options header in HTTP responses".to_string(),
+                ],
+            ])),
+            _ => return vec![],
+        };
+        vec![section]
     }
 
-    fn build_compliance_report(&self) -> Vec<ReportSection> {
-        vec![
-            ReportSection {
+    fn demo_compliance_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let section = match section_id {
+            "compliance-overview" => ReportSection {
                 id: "compliance-overview".to_string(),
                 title: "Compliance Assessment Overview".to_string(),
                 level: 1,
@@ -259,24 +1497,14 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
-                id: "framework-status".to_string(),
-                title: "Framework Compliance Status".to_string(),
-                level: 1,
-                blocks: vec![
-                    ContentBlock::Table {
-                        headers: vec!["Framework".to_string(), "Controls Assessed".to_string(), "Compliant".to_string(), "Score".to_string()],
-                        rows: vec![
-                            vec!["NIST CSF 2.0".to_string(), "106".to_string(), "84".to_string(), "79.2%".to_string()],
-                            vec!["SOC 2 Type II".to_string(), "64".to_string(), "52".to_string(), "81.3%".to_string()],
-                            vec!["GDPR".to_string(), "42".to_string(), "31".to_string(), "73.8%".to_string()],
-                        ],
-                        caption: Some("Compliance status by framework".to_string()),
-                    },
-                ],
-                subsections: vec![],
-            },
-            ReportSection {
+            "framework-status" => ReportSection {
+                id: "framework-status".to_string(),
+                title: "Framework Compliance Status".to_string(),
+                level: 1,
+                blocks: vec![self.framework_status_table()],
+                subsections: vec![],
+            },
+            "gap-analysis" => ReportSection {
                 id: "gaps".to_string(),
                 title: "Gap Analysis".to_string(),
                 level: 1,
@@ -296,12 +1524,74 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-        ]
+            _ => return vec![],
+        };
+        vec![section]
+    }
+
+    /// Framework status table: the client's category groups or native categories
+    /// when compliance data is attached, otherwise the framework overview
+    fn framework_status_table(&self) -> ContentBlock {
+        let compliance = match &self.data.compliance {
+            Some(c) => c,
+            None => return ContentBlock::Table {
+                headers: vec!["Framework".to_string(), "Controls Assessed".to_string(), "Compliant".to_string(), "Score".to_string()],
+                rows: vec![
+                    vec!["NIST CSF 2.0".to_string(), "106".to_string(), "84".to_string(), "79.2%".to_string()],
+                    vec!["SOC 2 Type II".to_string(), "64".to_string(), "52".to_string(), "81.3%".to_string()],
+                    vec!["GDPR".to_string(), "42".to_string(), "31".to_string(), "73.8%".to_string()],
+                ],
+                caption: Some("Compliance status by framework".to_string()),
+            },
+        };
+
+        let grouped = !compliance.group_breakdown.is_empty();
+        let breakdown = if grouped {
+            &compliance.group_breakdown
+        } else {
+            &compliance.category_breakdown
+        };
+
+        // Maturity column only once some control has been rated
+        let rated = compliance.average_maturity.is_some();
+        let mut headers = vec![
+            if grouped { "Group" } else { "Category" }.to_string(),
+            "Controls Assessed".to_string(),
+            "Compliant".to_string(),
+            "Score".to_string(),
+        ];
+        if rated {
+            headers.push("Avg. Maturity".to_string());
+        }
+
+        ContentBlock::Table {
+            headers,
+            rows: breakdown
+                .iter()
+                .map(|c| {
+                    let mut row = vec![
+                        c.name.clone(),
+                        format!("{}/{}", c.assessed_controls, c.total_controls),
+                        c.compliant.to_string(),
+                        format!("{:.1}%", c.compliance_percentage),
+                    ];
+                    if rated {
+                        row.push(c.average_maturity.map(|m| format!("{:.1}", m)).unwrap_or_else(|| "-".to_string()));
+                    }
+                    row
+                })
+                .collect(),
+            caption: Some(format!(
+                "{} compliance status by {}",
+                compliance.framework.display_name(),
+                if grouped { "category group" } else { "category" }
+            )),
+        }
     }
 
-    fn build_network_assessment(&self) -> Vec<ReportSection> {
-        vec![
-            ReportSection {
+    fn demo_network_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let section = match section_id {
+            "network-overview" => ReportSection {
                 id: "network-overview".to_string(),
                 title: "Network Assessment Overview".to_string(),
                 level: 1,
@@ -320,7 +1610,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "asset-inventory" => ReportSection {
                 id: "asset-inventory".to_string(),
                 title: "Asset Inventory Summary".to_string(),
                 level: 1,
@@ -340,7 +1630,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "service-analysis" => ReportSection {
                 id: "services".to_string(),
                 title: "Service Analysis".to_string(),
                 level: 1,
@@ -359,12 +1649,14 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-        ]
+            _ => return vec![],
+        };
+        vec![section]
     }
 
-    fn build_cloud_readiness(&self) -> Vec<ReportSection> {
-        vec![
-            ReportSection {
+    fn demo_cloud_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let section = match section_id {
+            "cloud-overview" => ReportSection {
                 id: "cloud-overview".to_string(),
                 title: "Cloud Readiness Assessment".to_string(),
                 level: 1,
@@ -382,7 +1674,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "readiness-score" => ReportSection {
                 id: "readiness-by-area".to_string(),
                 title: "Readiness by Area".to_string(),
                 level: 1,
@@ -405,29 +1697,29 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "cost-analysis" => ReportSection {
                 id: "cost-analysis".to_string(),
                 title: "Cost Analysis".to_string(),
                 level: 1,
-                blocks: vec![
-                    ContentBlock::Table {
-                        headers: vec!["Provider".to_string(), "Monthly Estimate".to_string(), "Annual Estimate".to_string(), "Savings".to_string()],
-                        rows: vec![
-                            vec!["AWS".to_string(), "$12,450".to_string(), "$149,400".to_string(), "18%".to_string()],
-                            vec!["Azure".to_string(), "$11,890".to_string(), "$142,680".to_string(), "22%".to_string()],
-                            vec!["GCP".to_string(), "$11,200".to_string(), "$134,400".to_string(), "26%".to_string()],
-                        ],
-                        caption: Some("Estimated cloud costs by provider".to_string()),
-                    },
-                ],
+                blocks: get_resource_templates()
+                    .first()
+                    .map(|template| {
+                        provider_comparison_blocks(&compare_resource_costs(
+                            &template.resources,
+                            &ComparisonStrategies::default(),
+                        ))
+                    })
+                    .unwrap_or_default(),
                 subsections: vec![],
             },
-        ]
+            _ => return vec![],
+        };
+        vec![section]
     }
 
-    fn build_security_findings(&self) -> Vec<ReportSection> {
-        vec![
-            ReportSection {
+    fn demo_security_section(&self, section_id: &str) -> Vec<ReportSection> {
+        let section = match section_id {
+            "findings-overview" => ReportSection {
                 id: "findings-overview".to_string(),
                 title: "Security Findings Overview".to_string(),
                 level: 1,
@@ -451,7 +1743,7 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-            ReportSection {
+            "critical-findings" => ReportSection {
                 id: "critical-findings".to_string(),
                 title: "Critical Findings".to_string(),
                 level: 1,
@@ -472,43 +1764,1349 @@ impl ReportGenerator {
                 ],
                 subsections: vec![],
             },
-        ]
+            _ => return vec![],
+        };
+        vec![section]
+    }
+
+    /// Remediation progress from verification scans, if any have completed
+    fn build_remediation_progress(&self) -> Option<ReportSection> {
+        // Only completed verification scans say anything about progress
+        let summaries: Vec<(&ScanJob, &crate::network::models::VerificationSummary)> = self
+            .data
+            .verification_scans
+            .iter()
+            .filter_map(|s| s.verification.as_ref().and_then(|v| v.summary.as_ref()).map(|sum| (s, sum)))
+            .collect();
+        if summaries.is_empty() {
+            return None;
+        }
+
+        let fixed: usize = summaries.iter().map(|(_, s)| s.verified_fixed).sum();
+        let present: usize = summaries.iter().map(|(_, s)| s.still_present).sum();
+        let unreachable: usize = summaries.iter().map(|(_, s)| s.unreachable).sum();
+
+        Some(ReportSection {
+            id: "remediation-progress".to_string(),
+            title: "Remediation Progress".to_string(),
+            level: 1,
+            blocks: vec![
+                ContentBlock::Paragraph {
+                    text: "Reported findings were re-tested with targeted verification scans. \
+                          Findings are closed only when the original detection condition no longer holds.".to_string(),
+                },
+                ContentBlock::Metric {
+                    label: "Verified Fixed".to_string(),
+                    value: fixed.to_string(),
+                    change: None,
+                    trend: None,
+                },
+                ContentBlock::Metric {
+                    label: "Still Present".to_string(),
+                    value: present.to_string(),
+                    change: None,
+                    trend: None,
+                },
+                ContentBlock::Metric {
+                    label: "Unreachable".to_string(),
+                    value: unreachable.to_string(),
+                    change: None,
+                    trend: None,
+                },
+                ContentBlock::Table {
+                    headers: vec![
+                        "Verification Scan".to_string(),
+                        "Evaluated".to_string(),
+                        "Fixed".to_string(),
+                        "Still Present".to_string(),
+                        "Unreachable".to_string(),
+                    ],
+                    rows: summaries
+                        .iter()
+                        .map(|(scan, sum)| {
+                            vec![
+                                scan.name.clone(),
+                                sum.evaluated_at.chars().take(10).collect(),
+                                sum.verified_fixed.to_string(),
+                                sum.still_present.to_string(),
+                                sum.unreachable.to_string(),
+                            ]
+                        })
+                        .collect(),
+                    caption: Some("Finding verification results".to_string()),
+                },
+            ],
+            subsections: vec![],
+        })
+    }
+
+    /// Sections for one top-level part of a full engagement report
+    ///
+    /// Empty when the part is deselected or has no content of its own, like
+    /// the appendices, which follow the last part.
+    pub fn build_engagement_part(&self, part: &str) -> Vec<ReportSection> {
+        if !self.includes(part) {
+            return vec![];
+        }
+        match part {
+            "engagement-overview" => vec![ReportSection {
+                id: "engagement-overview".to_string(),
+                title: "Engagement Overview".to_string(),
+                level: 1,
+                blocks: vec![
+                    ContentBlock::Paragraph {
+                        text: format!(
+                            "This comprehensive report documents the full security engagement conducted for {}. \
+                            The assessment covered multiple domains including compliance, network security, \
+                            cloud readiness, and vulnerability analysis.",
+                            self.config.client_name
+                        ),
+                    },
+                ],
+                subsections: vec![],
+            }],
+            "executive-summary" => {
+                let mut sections = self.build_sections(ReportType::ExecutiveSummary);
+                sections.push(ReportSection {
+                    id: "page-break-1".to_string(),
+                    title: String::new(),
+                    level: 0,
+                    blocks: vec![ContentBlock::PageBreak],
+                    subsections: vec![],
+                });
+                sections
+            }
+            "compliance-assessment" => self.build_sections(ReportType::ComplianceReport),
+            "network-assessment" => self.build_sections(ReportType::NetworkAssessment),
+            "security-findings" => self.build_sections(ReportType::SecurityFindings),
+            "cloud-readiness" => self.build_sections(ReportType::CloudReadiness),
+            _ => vec![],
+        }
+    }
+}
+
+/// Cover page and header details for a report configuration
+fn report_metadata(config: &ReportConfig) -> ReportMetadata {
+    ReportMetadata {
+        title: config.title.clone(),
+        subtitle: config.subtitle.clone(),
+        author: config.author.clone(),
+        organization: config.organization.clone(),
+        client_name: config.client_name.clone(),
+        client_contact: config.client_contact.clone(),
+        report_date: chrono::Utc::now().format("%B %d, %Y").to_string(),
+        classification: config.classification.clone(),
+        version: "1.0".to_string(),
+        page_count: None,
+        logo_path: config.logo_path.clone(),
+        primary_color: config.primary_color.clone(),
+        secondary_color: config.secondary_color.clone(),
+        footer_text: config.footer_text.clone(),
+    }
+}
+
+fn methodology_section() -> ReportSection {
+    ReportSection {
+        id: "methodology".to_string(),
+        title: "Methodology".to_string(),
+        level: 1,
+        blocks: vec![
+            ContentBlock::Paragraph {
+                text: "The assessment followed industry-standard methodologies including OWASP, NIST, and PTES.".to_string(),
+            },
+            ContentBlock::BulletList {
+                items: vec![
+                    "Network reconnaissance and enumeration".to_string(),
+                    "Vulnerability scanning and validation".to_string(),
+                    "Configuration review and hardening analysis".to_string(),
+                    "Access control and authentication testing".to_string(),
+                    "Security architecture review".to_string(),
+                ],
+            },
+        ],
+        subsections: vec![],
+    }
+}
+
+fn findings_summary_section(blocks: Vec<ContentBlock>) -> ReportSection {
+    ReportSection {
+        id: "findings-summary".to_string(),
+        title: "Findings Summary".to_string(),
+        level: 1,
+        blocks,
+        subsections: vec![],
+    }
+}
+
+/// Open finding counts per severity, as a chart and a table
+fn findings_summary_blocks(counts: &[usize; 5]) -> Vec<ContentBlock> {
+    let severities = [
+        Criticality::Critical,
+        Criticality::High,
+        Criticality::Medium,
+        Criticality::Low,
+        Criticality::Informational,
+    ];
+    vec![
+        severity_chart(ChartType::Bar, counts),
+        ContentBlock::Table {
+            headers: vec!["Severity".to_string(), "Open Findings".to_string(), "Fix Within".to_string()],
+            rows: severities
+                .iter()
+                .zip(counts)
+                .map(|(severity, count)| {
+                    vec![format!("{:?}", severity), count.to_string(), remediation_timeline(*severity).to_string()]
+                })
+                .collect(),
+            caption: Some(format!("{} open findings by severity", counts.iter().sum::<usize>())),
+        },
+    ]
+}
+
+fn tools_appendix(tools: Vec<Vec<String>>) -> ReportSection {
+    ReportSection {
+        id: "appendix-tools".to_string(),
+        title: "Appendix: Tools & Techniques".to_string(),
+        level: 1,
+        blocks: vec![if tools.is_empty() {
+            no_data("tool", "Run a network scan or record findings to list the tools behind them.")
+        } else {
+            ContentBlock::Table {
+                headers: vec!["Tool".to_string(), "Used For".to_string()],
+                rows: tools,
+                caption: Some("Tools and techniques behind this assessment".to_string()),
+            }
+        }],
+        subsections: vec![],
+    }
+}
+
+fn evidence_appendix_section(block: ContentBlock) -> ReportSection {
+    ReportSection {
+        id: "appendix-evidence".to_string(),
+        title: "Appendix: Evidence".to_string(),
+        level: 1,
+        blocks: vec![block],
+        subsections: vec![],
+    }
+}
+
+fn evidence_table(rows: Vec<Vec<String>>) -> ContentBlock {
+    ContentBlock::Table {
+        headers: vec!["ID".to_string(), "Finding".to_string(), "Affected".to_string(), "Evidence".to_string()],
+        rows,
+        caption: Some("Observations recorded against open findings".to_string()),
+    }
+}
+
+/// Gaps by category, weakest first
+fn gap_analysis_blocks(compliance: &ComplianceStatusReport) -> Vec<ContentBlock> {
+    let mut gaps: Vec<_> = compliance
+        .category_breakdown
+        .iter()
+        .filter(|c| c.non_compliant + c.partially_compliant > 0)
+        .collect();
+    gaps.sort_by(|a, b| a.compliance_percentage.total_cmp(&b.compliance_percentage));
+
+    if gaps.is_empty() {
+        return vec![ContentBlock::Callout {
+            callout_type: CalloutType::Success,
+            title: Some("No Gaps Identified".to_string()),
+            text: "No assessed control is non-compliant or partially compliant".to_string(),
+        }];
+    }
+    vec![
+        ContentBlock::Callout {
+            callout_type: CalloutType::Warning,
+            title: Some("Key Gaps Identified".to_string()),
+            text: format!(
+                "{} non-compliant and {} partially compliant controls require remediation",
+                compliance.non_compliant_controls, compliance.partially_compliant_controls
+            ),
+        },
+        ContentBlock::BulletList {
+            items: gaps
+                .iter()
+                .map(|c| format!(
+                    "{}: {} non-compliant, {} partially compliant ({:.1}% compliant)",
+                    c.name, c.non_compliant, c.partially_compliant, c.compliance_percentage
+                ))
+                .collect(),
+        },
+    ]
+}
+
+/// Radar chart and table of weighted category scores
+fn readiness_by_area_blocks(score: &ReadinessScore) -> Vec<ContentBlock> {
+    vec![
+        ContentBlock::Chart {
+            chart_type: ChartType::Radar,
+            title: "Readiness Assessment".to_string(),
+            data: ChartData {
+                labels: score.category_scores.iter().map(|c| c.display_name.clone()).collect(),
+                datasets: vec![ChartDataset {
+                    label: "Current".to_string(),
+                    data: score.category_scores.iter().map(|c| (c.percentage * 10.0).round() / 10.0).collect(),
+                    color: Some("#3B82F6".to_string()),
+                }],
+            },
+        },
+        ContentBlock::Table {
+            headers: vec!["Area".to_string(), "Weight".to_string(), "Answered".to_string(), "Score".to_string()],
+            rows: score
+                .category_scores
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.display_name.clone(),
+                        format!("{:.2}", c.weight),
+                        format!("{}/{}", c.completed, c.total),
+                        format!("{:.0}%", c.percentage),
+                    ]
+                })
+                .collect(),
+            caption: Some(format!(
+                "{} of {} checklist items answered; unanswered items score as not ready",
+                score.answered_items, score.total_items
+            )),
+        },
+    ]
+}
+
+/// Per-resource cost table, provider totals and a cost chart for a comparison
+pub fn provider_comparison_blocks(comparison: &ProviderComparison) -> Vec<ContentBlock> {
+    let providers: Vec<_> = comparison.totals_by_provider.iter().map(|t| t.provider).collect();
+    let mut headers = vec!["Resource".to_string()];
+    headers.extend(providers.iter().map(|p| p.short_name().to_string()));
+
+    let mut rows: Vec<Vec<String>> = comparison
+        .per_resource
+        .iter()
+        .map(|r| {
+            let mut row = vec![r.name.clone()];
+            row.extend(providers.iter().map(|p| currency(r.cost_for(p))));
+            row
+        })
+        .collect();
+    let mut total_row = vec!["Total".to_string()];
+    total_row.extend(comparison.totals_by_provider.iter().map(|t| currency(t.monthly_cost)));
+    rows.push(total_row);
+
+    vec![
+        ContentBlock::Table {
+            headers,
+            rows,
+            caption: Some(format!(
+                "Monthly cost per resource ({} pricing)",
+                comparison.commitment.display_name()
+            )),
+        },
+        ContentBlock::Table {
+            headers: vec![
+                "Provider".to_string(),
+                "Monthly Estimate".to_string(),
+                "Annual Estimate".to_string(),
+                "vs Cheapest".to_string(),
+            ],
+            rows: comparison
+                .totals_by_provider
+                .iter()
+                .map(|t| {
+                    vec![
+                        t.provider.short_name().to_string(),
+                        currency(t.monthly_cost),
+                        currency(t.annual_cost),
+                        if t.provider == comparison.cheapest_provider {
+                            "Cheapest".to_string()
+                        } else {
+                            format!("+{}/month", currency(t.delta_vs_cheapest))
+                        },
+                    ]
+                })
+                .collect(),
+            caption: Some("Estimated cloud costs by provider".to_string()),
+        },
+        ContentBlock::Chart {
+            chart_type: ChartType::Bar,
+            title: "Monthly Cost by Resource".to_string(),
+            data: ChartData {
+                labels: comparison.per_resource.iter().map(|r| r.name.clone()).collect(),
+                datasets: providers
+                    .iter()
+                    .map(|p| ChartDataset {
+                        label: p.short_name().to_string(),
+                        data: comparison.per_resource.iter().map(|r| r.cost_for(p)).collect(),
+                        color: None,
+                    })
+                    .collect(),
+            },
+        },
+    ]
+}
+
+/// Callout shown in place of a section whose module wasn't used
+fn no_data(what: &str, hint: &str) -> ContentBlock {
+    ContentBlock::Callout {
+        callout_type: CalloutType::Info,
+        title: Some("No Data Available".to_string()),
+        text: format!("No {} data was recorded for this engagement. {}", what, hint),
+    }
+}
+
+/// Security findings template section listing findings of this severity
+fn severity_section(severity: Criticality) -> &'static str {
+    match severity {
+        Criticality::Critical => "critical-findings",
+        Criticality::High => "high-findings",
+        Criticality::Medium => "medium-findings",
+        Criticality::Low | Criticality::Informational => "low-findings",
+    }
+}
+
+/// Finding counts indexed by severity rank (critical first)
+fn severity_counts(findings: &[ListedFinding]) -> [usize; 5] {
+    let mut counts = [0; 5];
+    for finding in findings {
+        counts[finding.severity.rank() as usize] += 1;
+    }
+    counts
+}
+
+fn severity_chart(chart_type: ChartType, counts: &[usize; 5]) -> ContentBlock {
+    ContentBlock::Chart {
+        chart_type,
+        title: "Findings by Severity".to_string(),
+        data: ChartData {
+            labels: vec!["Critical".to_string(), "High".to_string(), "Medium".to_string(), "Low".to_string(), "Info".to_string()],
+            datasets: vec![ChartDataset {
+                label: "Findings".to_string(),
+                data: counts.iter().map(|c| *c as f64).collect(),
+                color: None,
+            }],
+        },
+    }
+}
+
+/// Bar chart of asset counts per category, largest first
+pub fn assets_by_category_chart(stats: &NetworkStats) -> ContentBlock {
+    let mut by_category: Vec<_> = stats.by_category.iter().collect();
+    by_category.sort_by_key(|c| std::cmp::Reverse(c.count));
+
+    ContentBlock::Chart {
+        chart_type: ChartType::Bar,
+        title: "Assets by Category".to_string(),
+        data: ChartData {
+            labels: by_category.iter().map(|c| c.category.display_name().to_string()).collect(),
+            datasets: vec![ChartDataset {
+                label: "Count".to_string(),
+                data: by_category.iter().map(|c| c.count as f64).collect(),
+                color: Some("#3B82F6".to_string()),
+            }],
+        },
+    }
+}
+
+/// Bar chart of compliance per category, or per category group when grouped
+pub fn category_compliance_chart(compliance: &ComplianceStatusReport) -> ContentBlock {
+    let breakdown = if compliance.group_breakdown.is_empty() {
+        &compliance.category_breakdown
+    } else {
+        &compliance.group_breakdown
+    };
+
+    ContentBlock::Chart {
+        chart_type: ChartType::Bar,
+        title: "Compliance by Category".to_string(),
+        data: ChartData {
+            labels: breakdown.iter().map(|c| c.name.clone()).collect(),
+            datasets: vec![
+                ChartDataset {
+                    label: "Compliance %".to_string(),
+                    data: breakdown.iter().map(|c| c.compliance_percentage).collect(),
+                    color: Some("#22C55E".to_string()),
+                },
+                ChartDataset {
+                    label: "Completion %".to_string(),
+                    data: breakdown.iter().map(|c| c.completion_percentage).collect(),
+                    color: Some("#3B82F6".to_string()),
+                },
+            ],
+        },
+    }
+}
+
+/// Line chart of compliance and completion across the trend's snapshots
+pub fn compliance_trend_chart(trend: &ComplianceTrend) -> ContentBlock {
+    ContentBlock::Chart {
+        chart_type: ChartType::Line,
+        title: "Compliance Over Time".to_string(),
+        data: ChartData {
+            labels: trend.points.iter().map(|p| p.taken_at.format("%Y-%m-%d").to_string()).collect(),
+            datasets: vec![
+                ChartDataset {
+                    label: "Compliance %".to_string(),
+                    data: trend.points.iter().map(|p| p.compliance_percentage).collect(),
+                    color: Some("#22C55E".to_string()),
+                },
+                ChartDataset {
+                    label: "Completion %".to_string(),
+                    data: trend.points.iter().map(|p| p.completion_percentage).collect(),
+                    color: Some("#3B82F6".to_string()),
+                },
+            ],
+        },
+    }
+}
+
+fn finding_id(index: usize) -> String {
+    format!("FIND-{:03}", index + 1)
+}
+
+fn finding_block(index: usize, finding: &ListedFinding) -> ContentBlock {
+    ContentBlock::Finding {
+        id: finding_id(index),
+        title: finding.title.clone(),
+        severity: format!("{:?}", finding.severity),
+        description: finding.description.clone(),
+        impact: finding.impact.clone(),
+        recommendation: finding.recommendation.clone(),
+    }
+}
+
+fn remediation_timeline(severity: Criticality) -> &'static str {
+    match severity {
+        Criticality::Critical => "7 days",
+        Criticality::High => "30 days",
+        Criticality::Medium => "60 days",
+        Criticality::Low => "90 days",
+        Criticality::Informational => "Next review",
+    }
+}
+
+fn gap_priority(compliance_percentage: f64) -> &'static str {
+    if compliance_percentage < 50.0 {
+        "Critical"
+    } else if compliance_percentage < 70.0 {
+        "High"
+    } else if compliance_percentage < 85.0 {
+        "Medium"
+    } else {
+        "Low"
+    }
+}
+
+/// Whole-dollar amount with thousands separators, e.g. "-$12,450"
+fn currency(amount: f64) -> String {
+    let rounded = amount.round().abs() as u64;
+    let digits = rounded.to_string();
+    let mut grouped = String::new();
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    if amount.round() < 0.0 {
+        format!("-${}", grouped)
+    } else {
+        format!("${}", grouped)
+    }
+}
+
+/// Evidence Summary section of a compliance report
+///
+/// Calls out controls marked compliant without evidence, which auditors
+/// will ask about first.
+pub fn evidence_summary_section(coverage: &EvidenceCoverage) -> ReportSection {
+    let mut blocks = vec![ContentBlock::Paragraph {
+        text: format!(
+            "{} evidence items support {} of the {} {} controls.",
+            coverage.total_evidence,
+            coverage.controls_with_evidence,
+            coverage.controls.len(),
+            coverage.framework.display_name()
+        ),
+    }];
+
+    let unsupported: Vec<_> = coverage.controls.iter().filter(|c| c.unsupported).collect();
+    if unsupported.is_empty() {
+        blocks.push(ContentBlock::Callout {
+            callout_type: CalloutType::Success,
+            title: Some("Compliance Fully Evidenced".to_string()),
+            text: "Every control marked compliant has supporting evidence".to_string(),
+        });
+    } else {
+        blocks.push(ContentBlock::Callout {
+            callout_type: CalloutType::Warning,
+            title: Some("Compliant Controls Without Evidence".to_string()),
+            text: format!(
+                "{} controls are marked compliant but have no supporting evidence",
+                unsupported.len()
+            ),
+        });
+        blocks.push(ContentBlock::Table {
+            headers: vec!["Control".to_string(), "Title".to_string(), "Category".to_string()],
+            rows: unsupported
+                .iter()
+                .map(|c| vec![c.control_code.clone(), c.title.clone(), c.category.clone()])
+                .collect(),
+            caption: None,
+        });
+    }
+
+    // Per-category coverage, in framework order
+    let mut categories: Vec<(&str, usize, usize, usize)> = Vec::new();
+    for control in &coverage.controls {
+        let index = match categories.iter().position(|c| c.0 == control.category) {
+            Some(index) => index,
+            None => {
+                categories.push((&control.category, 0, 0, 0));
+                categories.len() - 1
+            }
+        };
+        let entry = &mut categories[index];
+        entry.1 += 1;
+        entry.2 += usize::from(control.evidence_count > 0);
+        entry.3 += control.evidence_count;
+    }
+    blocks.push(ContentBlock::Table {
+        headers: vec![
+            "Category".to_string(),
+            "Controls".to_string(),
+            "With Evidence".to_string(),
+            "Evidence Links".to_string(),
+        ],
+        rows: categories
+            .into_iter()
+            .map(|(category, total, covered, links)| {
+                vec![category.to_string(), total.to_string(), covered.to_string(), links.to_string()]
+            })
+            .collect(),
+        caption: Some("Evidence coverage by category".to_string()),
+    });
+
+    ReportSection {
+        id: "evidence-summary".to_string(),
+        title: "Evidence Summary".to_string(),
+        level: 1,
+        blocks,
+        subsections: vec![],
+    }
+}
+
+pub fn compliance_trend_section(trend: &ComplianceTrend) -> ReportSection {
+    let labels: Vec<String> = trend.points.iter().map(|p| p.taken_at.format("%Y-%m-%d").to_string()).collect();
+    let first = &trend.points[0];
+    let last = &trend.points[trend.points.len() - 1];
+
+    ReportSection {
+        id: "compliance-trend".to_string(),
+        title: "Compliance Trend".to_string(),
+        level: 1,
+        blocks: vec![
+            ContentBlock::Paragraph {
+                text: format!(
+                    "{} compliance moved from {:.1}% on {} to {:.1}% on {}, across {} snapshots.",
+                    trend.framework.display_name(),
+                    first.compliance_percentage,
+                    labels[0],
+                    last.compliance_percentage,
+                    labels[labels.len() - 1],
+                    trend.points.len()
+                ),
+            },
+            compliance_trend_chart(trend),
+            ContentBlock::Chart {
+                chart_type: ChartType::Line,
+                title: "Compliance by Category Over Time".to_string(),
+                data: ChartData {
+                    labels,
+                    datasets: trend
+                        .categories
+                        .iter()
+                        .map(|c| ChartDataset {
+                            label: c.name.clone(),
+                            data: c.points.iter().map(|p| p.compliance_percentage).collect(),
+                            color: Some(c.color.clone()),
+                        })
+                        .collect(),
+                },
+            },
+        ],
+        subsections: vec![],
+    }
+}
+
+pub fn assessment_comparison_section(comparison: &AssessmentComparison) -> ReportSection {
+    let signed = |v: i64| if v > 0 { format!("+{}", v) } else { v.to_string() };
+    let points = |v: f64| if v > 0.0 { format!("+{:.1}", v) } else { format!("{:.1}", v) };
+
+    let (callout_type, title) = if comparison.compliance_delta > 0.0 {
+        (CalloutType::Success, "Compliance Improved")
+    } else if comparison.compliance_delta < 0.0 {
+        (CalloutType::Warning, "Compliance Declined")
+    } else {
+        (CalloutType::Info, "Compliance Unchanged")
+    };
+
+    ReportSection {
+        id: "year-over-year".to_string(),
+        title: "Year-over-Year Comparison".to_string(),
+        level: 1,
+        blocks: vec![
+            ContentBlock::Paragraph {
+                text: format!(
+                    "Overall {} compliance moved from {:.1}% to {:.1}% since the previous assessment.",
+                    comparison.framework.display_name(),
+                    comparison.baseline_compliance,
+                    comparison.current_compliance
+                ),
+            },
+            ContentBlock::Callout {
+                callout_type,
+                title: Some(title.to_string()),
+                text: format!(
+                    "{} percentage points; {} compliant and {} non-compliant controls",
+                    points(comparison.compliance_delta),
+                    signed(comparison.compliant_delta),
+                    signed(comparison.non_compliant_delta)
+                ),
+            },
+            ContentBlock::Table {
+                headers: vec![
+                    "Category".to_string(),
+                    "Previous".to_string(),
+                    "Current".to_string(),
+                    "Compliant Controls".to_string(),
+                    "Change".to_string(),
+                ],
+                rows: comparison
+                    .categories
+                    .iter()
+                    .map(|c| vec![
+                        c.display_name.clone(),
+                        format!("{:.1}%", c.baseline_percentage),
+                        format!("{:.1}%", c.current_percentage),
+                        format!("{} → {}", c.baseline_compliant, c.current_compliant),
+                        points(c.percentage_delta),
+                    ])
+                    .collect(),
+                caption: Some("Compliance by category against the previous assessment".to_string()),
+            },
+        ],
+        subsections: vec![],
+    }
+}
+
+/// Remediation Plan section: one table of gaps per category, most urgent first
+pub fn remediation_plan_section(plan: &RemediationPlan) -> ReportSection {
+    let total = plan.items().count();
+    if total == 0 {
+        return ReportSection {
+            id: "remediation-plan".to_string(),
+            title: "Remediation Plan".to_string(),
+            level: 1,
+            blocks: vec![ContentBlock::Callout {
+                callout_type: CalloutType::Success,
+                title: Some("No Open Gaps".to_string()),
+                text: "No controls were assessed as non-compliant or partially compliant".to_string(),
+            }],
+            subsections: vec![],
+        };
+    }
+
+    let (small, medium, large) = plan.effort_counts();
+    let stats = RemediationStats::from_items(plan.items(), chrono::Utc::now());
+    let count = |status: RemediationItemStatus| stats.by_status.get(&status).copied().unwrap_or(0);
+    let mut progress = format!(
+        "{} of {} items done, {} in progress",
+        count(RemediationItemStatus::Done),
+        total,
+        count(RemediationItemStatus::InProgress)
+    );
+    for (status, label) in [
+        (RemediationItemStatus::Blocked, "blocked"),
+        (RemediationItemStatus::AcceptedRisk, "risk accepted"),
+    ] {
+        if count(status) > 0 {
+            progress.push_str(&format!(", {} {}", count(status), label));
+        }
+    }
+    if stats.overdue > 0 {
+        progress.push_str(&format!(", {} overdue", stats.overdue));
+    }
+
+    let subsections = plan
+        .categories
+        .iter()
+        .map(|category| ReportSection {
+            id: format!("remediation-{}", category.category.to_lowercase().replace('.', "-")),
+            title: category.display_name.clone(),
+            level: 2,
+            blocks: vec![ContentBlock::Table {
+                headers: vec![
+                    "Control".to_string(),
+                    "Gap".to_string(),
+                    "Recommended Action".to_string(),
+                    "Effort".to_string(),
+                    "Owner".to_string(),
+                    "Target".to_string(),
+                    "Status".to_string(),
+                ],
+                rows: category
+                    .items
+                    .iter()
+                    .map(|item| vec![
+                        format!("{} {}", item.control_code, item.control_title),
+                        item.gap.clone().unwrap_or_else(|| item.compliance_status.display_name().to_string()),
+                        item.recommended_action.clone(),
+                        item.effort.code().to_string(),
+                        item.owner.clone().unwrap_or_else(|| item.owner_role.clone()),
+                        item.target_quarter.clone().unwrap_or_else(|| "-".to_string()),
+                        item.status.display_name().to_string(),
+                    ])
+                    .collect(),
+                caption: None,
+            }],
+            subsections: vec![],
+        })
+        .collect();
+
+    ReportSection {
+        id: "remediation-plan".to_string(),
+        title: "Remediation Plan".to_string(),
+        level: 1,
+        blocks: vec![
+            ContentBlock::Paragraph {
+                text: format!(
+                    "{} gaps need remediation across {} categories: {} small, {} medium and {} large.",
+                    total,
+                    plan.categories.len(),
+                    small,
+                    medium,
+                    large
+                ),
+            },
+            ContentBlock::Callout {
+                callout_type: CalloutType::Info,
+                title: Some("Progress".to_string()),
+                text: progress,
+            },
+        ],
+        subsections,
+    }
+}
+
+/// Article 30 record of processing activities as a standalone report
+///
+/// A summary table of every activity, then one table per activity.
+pub fn processing_register_content(config: &ReportConfig, activities: &[ProcessingActivity]) -> ReportContent {
+    let summary = ReportSection {
+        id: "ropa-summary".to_string(),
+        title: "Register Summary".to_string(),
+        level: 1,
+        blocks: if activities.is_empty() {
+            vec![no_data("processing activity", "Record the client's processing activities in the GDPR register.")]
+        } else {
+            vec![
+                ContentBlock::Paragraph {
+                    text: format!(
+                        "{} processing activities are recorded for {} under Article 30 GDPR.",
+                        activities.len(),
+                        config.client_name
+                    ),
+                },
+                ContentBlock::Table {
+                    headers: vec![
+                        "Activity".to_string(),
+                        "Purpose".to_string(),
+                        "Lawful Basis".to_string(),
+                        "Third-Country Transfers".to_string(),
+                        "Retention".to_string(),
+                    ],
+                    rows: activities
+                        .iter()
+                        .map(|a| vec![
+                            a.name.clone(),
+                            a.purpose.clone(),
+                            a.lawful_basis.display_name().to_string(),
+                            a.transfers.len().to_string(),
+                            a.retention_period.clone().unwrap_or_else(|| "-".to_string()),
+                        ])
+                        .collect(),
+                    caption: Some("Processing activities".to_string()),
+                },
+            ]
+        },
+        subsections: vec![],
+    };
+
+    let mut sections = vec![summary];
+    if !activities.is_empty() {
+        sections.push(ReportSection {
+            id: "ropa-activities".to_string(),
+            title: "Processing Activities".to_string(),
+            level: 1,
+            blocks: vec![],
+            subsections: activities.iter().enumerate().map(|(i, a)| processing_activity_section(i, a)).collect(),
+        });
+    }
+    if activities.iter().any(|a| !a.control_codes.is_empty()) {
+        sections.push(processing_coverage_section(activities));
+    }
+
+    let toc = if config.include_toc {
+        number_sections(&mut sections)
+    } else {
+        vec![]
+    };
+
+    ReportContent { sections, metadata: report_metadata(config), toc, anchors: vec![] }
+}
+
+fn processing_activity_section(index: usize, activity: &ProcessingActivity) -> ReportSection {
+    let list = |items: &[String]| if items.is_empty() { "-".to_string() } else { items.join(", ") };
+    let transfers = if activity.transfers.is_empty() {
+        "None".to_string()
+    } else {
+        activity.transfers
+            .iter()
+            .map(|t| format!(
+                "{}{} ({})",
+                t.country,
+                t.recipient.as_deref().map(|r| format!(", {}", r)).unwrap_or_default(),
+                t.safeguard.map_or("no safeguard documented", |s| s.display_name())
+            ))
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+
+    let mut blocks = vec![ContentBlock::Table {
+        headers: vec!["Field".to_string(), "Record".to_string()],
+        rows: vec![
+            vec!["Purpose".to_string(), activity.purpose.clone()],
+            vec![
+                "Lawful Basis".to_string(),
+                format!("{} ({})", activity.lawful_basis.display_name(), activity.lawful_basis.article()),
+            ],
+            vec!["Categories of Data".to_string(), list(&activity.data_categories)],
+            vec!["Data Subjects".to_string(), list(&activity.data_subject_categories)],
+            vec!["Recipients".to_string(), list(&activity.recipients)],
+            vec!["Third-Country Transfers".to_string(), transfers],
+            vec!["Retention Period".to_string(), activity.retention_period.clone().unwrap_or_else(|| "-".to_string())],
+            vec!["Security Measures".to_string(), activity.security_measures.clone().unwrap_or_else(|| "-".to_string())],
+            vec!["Linked Articles".to_string(), list(&activity.control_codes)],
+        ],
+        caption: None,
+    }];
+    if activity.unsafeguarded_transfers().next().is_some() {
+        blocks.push(ContentBlock::Callout {
+            callout_type: CalloutType::Warning,
+            title: Some("Transfer Safeguard Missing".to_string()),
+            text: "At least one third-country transfer has no Chapter V safeguard documented".to_string(),
+        });
+    }
+
+    ReportSection {
+        id: format!("ropa-activity-{}", index + 1),
+        title: activity.name.clone(),
+        level: 2,
+        blocks,
+        subsections: vec![],
+    }
+}
+
+/// GDPR articles evidenced by the client's processing activities
+pub fn processing_coverage_section(activities: &[ProcessingActivity]) -> ReportSection {
+    let coverage = activities_by_control(activities);
+
+    ReportSection {
+        id: "ropa-coverage".to_string(),
+        title: "Records of Processing Coverage".to_string(),
+        level: 1,
+        blocks: vec![
+            ContentBlock::Paragraph {
+                text: format!(
+                    "{} processing activities are recorded, evidencing {} GDPR articles.",
+                    activities.len(),
+                    coverage.len()
+                ),
+            },
+            ContentBlock::Table {
+                headers: vec!["Article".to_string(), "Processing Activities".to_string()],
+                rows: coverage
+                    .iter()
+                    .map(|(code, linked)| vec![
+                        code.clone(),
+                        linked.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", "),
+                    ])
+                    .collect(),
+                caption: Some("Articles covered by the record of processing activities".to_string()),
+            },
+        ],
+        subsections: vec![],
+    }
+}
+
+/// Service policy violations as findings, most severe first
+pub fn policy_violations_section(assets: &[AssetPolicyViolations]) -> ReportSection {
+    let mut violations: Vec<_> = assets
+        .iter()
+        .flat_map(|asset| asset.violations.iter().map(move |v| (asset, v)))
+        .collect();
+    violations.sort_by_key(|(_, v)| v.severity.rank());
+
+    let mut blocks = vec![ContentBlock::Paragraph {
+        text: format!(
+            "{} exposed services on {} assets break the engagement's service policies.",
+            violations.len(),
+            assets.len()
+        ),
+    }];
+    blocks.extend(violations.iter().enumerate().map(|(i, (asset, violation))| ContentBlock::Finding {
+        id: format!("POL-{:03}", i + 1),
+        title: format!("{}: {}", violation.policy_name, asset.asset_name),
+        severity: format!("{:?}", violation.severity),
+        description: violation.message.clone(),
+        impact: format!(
+            "Observed on {} ({}:{}, {}), a {} criticality {}",
+            asset.asset_name,
+            asset.ip_address,
+            violation.port,
+            violation.service,
+            format!("{:?}", asset.criticality).to_lowercase(),
+            asset.category.display_name().to_lowercase()
+        ),
+        recommendation: violation.recommendation.clone().unwrap_or_default(),
+    }));
+
+    ReportSection {
+        id: "policy-violations".to_string(),
+        title: "Service Policy Violations".to_string(),
+        level: 1,
+        blocks,
+        subsections: vec![],
+    }
+}
+
+/// Category scores and failing checks of a Kubernetes hardening audit
+pub fn k8s_hardening_section(audit: &K8sHardeningAudit) -> ReportSection {
+    let summary = summarize_k8s_audit(audit);
+    let severity_order = Severity::all();
+    let mut failing: Vec<_> = get_k8s_hardening_checks()
+        .into_iter()
+        .filter_map(|check| {
+            let result = audit.results.iter().find(|r| r.check_id == check.id && r.status.is_failing())?;
+            Some((check, result))
+        })
+        .collect();
+    failing.sort_by_key(|(check, _)| severity_order.iter().position(|s| *s == check.severity));
+
+    let mut audited = format!("audited {}", audit.audited_at.format("%B %d, %Y"));
+    if let Some(auditor) = &audit.audited_by {
+        audited.push_str(&format!(" by {}", auditor));
+    }
+    let mut blocks = vec![
+        ContentBlock::Paragraph {
+            text: format!(
+                "Cluster {} was {} against the NSA/CISA Kubernetes Hardening Guide and CIS Kubernetes Benchmark: \
+                {} of {} checks passed and {} failed, {} of them critical and {} high severity.",
+                audit.cluster_display_name(),
+                audited,
+                summary.passed,
+                summary.total_checks,
+                summary.failed,
+                summary.critical_findings,
+                summary.high_findings
+            ),
+        },
+        ContentBlock::Table {
+            headers: vec!["Category".to_string(), "Passed".to_string(), "Failed".to_string(), "Score".to_string()],
+            rows: summary
+                .category_results
+                .iter()
+                .map(|c| vec![
+                    c.display_name.clone(),
+                    format!("{}/{}", c.passed, c.total),
+                    c.failed.to_string(),
+                    if c.passed + c.failed > 0 { format!("{:.0}%", c.score_percentage) } else { "-".to_string() },
+                ])
+                .collect(),
+            caption: Some("Scores exclude checks that were not applicable or not checked".to_string()),
+        },
+    ];
+    blocks.extend(failing.iter().enumerate().map(|(i, (check, result))| {
+        let mut impact = check.rationale.clone();
+        if !result.affected_resources.is_empty() {
+            impact.push_str(&format!(". Affected: {}", result.affected_resources.join(", ")));
+        }
+        ContentBlock::Finding {
+            id: format!("K8S-{:03}", i + 1),
+            title: check.title.clone(),
+            severity: check.severity.display_name().to_string(),
+            description: result.notes.clone().unwrap_or_else(|| check.description.clone()),
+            impact,
+            recommendation: check.remediation.clone(),
+        }
+    }));
+
+    ReportSection {
+        id: "k8s-hardening".to_string(),
+        title: "Kubernetes Hardening".to_string(),
+        level: 1,
+        blocks,
+        subsections: vec![],
+    }
+}
+
+/// "Changes since last assessment" section for a baseline vs follow-up scan
+pub fn scan_diff_section(diff: &ScanDiff) -> ReportSection {
+    let mut blocks = vec![ContentBlock::Paragraph {
+        text: "Comparison of the baseline scan with the follow-up scan. Hosts are matched by \
+              MAC address where available, so address changes from DHCP are not reported as \
+              new devices.".to_string(),
+    }];
+    blocks.extend(scan_diff_blocks(diff));
+
+    ReportSection {
+        id: "scan-changes".to_string(),
+        title: "Changes Since Last Assessment".to_string(),
+        level: 1,
+        blocks,
+        subsections: vec![],
+    }
+}
+
+/// Summary and tables of a scan diff, for use in any report section
+pub fn scan_diff_blocks(diff: &ScanDiff) -> Vec<ContentBlock> {
+    let port_id = |port: u16, protocol: Protocol| format!("{}/{}", port, format!("{:?}", protocol).to_lowercase());
+    let port_list = |ports: &[PortRef]| {
+        ports
+            .iter()
+            .map(|p| match &p.service {
+                Some(service) => format!("{} ({})", port_id(p.port, p.protocol), service),
+                None => port_id(p.port, p.protocol),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let host_label = |ip: &str, hostname: &Option<String>| match hostname {
+        Some(name) => format!("{} ({})", ip, name),
+        None => ip.to_string(),
+    };
+
+    let mut blocks = vec![
+        ContentBlock::KeyValue {
+            items: vec![
+                KeyValueItem { key: "New Hosts".to_string(), value: diff.hosts_appeared.len().to_string() },
+                KeyValueItem { key: "Hosts No Longer Seen".to_string(), value: diff.hosts_disappeared.len().to_string() },
+                KeyValueItem { key: "Hosts Changed".to_string(), value: diff.hosts_changed.len().to_string() },
+                KeyValueItem { key: "Ports Opened".to_string(), value: diff.ports_opened().to_string() },
+                KeyValueItem { key: "Ports Closed".to_string(), value: diff.ports_closed().to_string() },
+            ],
+        },
+    ];
+
+    if diff.is_empty() {
+        blocks.push(ContentBlock::Callout {
+            callout_type: CalloutType::Success,
+            title: Some("No Changes".to_string()),
+            text: "No hosts, ports or services changed.".to_string(),
+        });
+    }
+
+    if !diff.hosts_appeared.is_empty() || !diff.hosts_disappeared.is_empty() {
+        let appeared = diff.hosts_appeared.iter().map(|h| ("New", h));
+        let disappeared = diff.hosts_disappeared.iter().map(|h| ("No longer seen", h));
+        blocks.push(ContentBlock::Table {
+            headers: vec!["Host".to_string(), "MAC Address".to_string(), "Change".to_string(), "Open Ports".to_string()],
+            rows: appeared
+                .chain(disappeared)
+                .map(|(change, h)| {
+                    vec![
+                        host_label(&h.ip_address, &h.hostname),
+                        h.mac_address.clone().unwrap_or_default(),
+                        change.to_string(),
+                        port_list(&h.open_ports),
+                    ]
+                })
+                .collect(),
+            caption: Some("Hosts added to or removed from the network".to_string()),
+        });
+    }
+
+    if !diff.hosts_changed.is_empty() {
+        blocks.push(ContentBlock::Table {
+            headers: vec!["Host".to_string(), "Change".to_string(), "Details".to_string()],
+            rows: diff
+                .hosts_changed
+                .iter()
+                .flat_map(|h| {
+                    let host = host_label(&h.ip_address, &h.hostname);
+                    let mut rows = Vec::new();
+                    if let Some(previous) = &h.previous_ip_address {
+                        rows.push(vec![host.clone(), "Address changed".to_string(), format!("Previously {}", previous)]);
+                    }
+                    if !h.ports_opened.is_empty() {
+                        rows.push(vec![host.clone(), "Ports opened".to_string(), port_list(&h.ports_opened)]);
+                    }
+                    if !h.ports_closed.is_empty() {
+                        rows.push(vec![host.clone(), "Ports closed".to_string(), port_list(&h.ports_closed)]);
+                    }
+                    for change in &h.service_changes {
+                        rows.push(vec![
+                            host.clone(),
+                            format!("Service on {}", port_id(change.port, change.protocol)),
+                            format!(
+                                "{} -> {}",
+                                change.old_service.as_deref().unwrap_or("unknown"),
+                                change.new_service.as_deref().unwrap_or("unknown")
+                            ),
+                        ]);
+                    }
+                    if let Some(os) = &h.os_change {
+                        rows.push(vec![host.clone(), "OS fingerprint".to_string(), format!("{} -> {}", os.old_os, os.new_os)]);
+                    }
+                    rows
+                })
+                .collect(),
+            caption: Some("Changes on hosts present in both scans".to_string()),
+        });
+    }
+
+    blocks
+}
+
+/// Replace each chart with a table of its figures, for reports configured without charts
+pub fn charts_to_tables(sections: &mut [ReportSection]) {
+    for section in sections {
+        for block in &mut section.blocks {
+            if let ContentBlock::Chart { title, data, .. } = block {
+                let table = chart_to_table(Some(title.clone()), data);
+                *block = table;
+            }
+        }
+        charts_to_tables(&mut section.subsections);
+    }
+}
+
+/// A chart's figures as a table: a row per label, a column per series
+pub(crate) fn chart_to_table(caption: Option<String>, data: &ChartData) -> ContentBlock {
+    let mut headers = vec![String::new()];
+    headers.extend(data.datasets.iter().map(|d| d.label.clone()));
+    let rows = data
+        .labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let mut row = vec![label.clone()];
+            row.extend(
+                data.datasets
+                    .iter()
+                    .map(|d| d.data.get(i).map(|v| chart_value(*v)).unwrap_or_else(|| "-".to_string())),
+            );
+            row
+        })
+        .collect();
+    ContentBlock::Table { headers, rows, caption }
+}
+
+/// Place custom sections before or after the template sections they're
+/// anchored to
+///
+/// Custom sections already in the content are taken out first, so this also
+/// re-places them after one is added, edited or deleted. Sections anchored to
+/// a template section the content has no record of go at the end. With a
+/// table of contents, everything is renumbered.
+pub fn place_custom_sections(content: &mut ReportContent, custom_sections: &[CustomSection], include_toc: bool) {
+    let mut toc = std::mem::take(&mut content.toc).into_iter();
+    unnumber_sections(&mut content.sections, &mut toc);
+
+    let mut generated = std::mem::take(&mut content.sections)
+        .into_iter()
+        .filter(|s| !s.id.starts_with(CUSTOM_SECTION_ID_PREFIX));
+    let mut sections = Vec::new();
+    for anchor in &content.anchors {
+        sections.extend(custom_sections_at(custom_sections, &anchor.section_id, SectionPlacement::Before));
+        sections.extend(generated.by_ref().take(anchor.section_count));
+        sections.extend(custom_sections_at(custom_sections, &anchor.section_id, SectionPlacement::After));
+    }
+    sections.extend(generated);
+    sections.extend(
+        custom_sections
+            .iter()
+            .filter(|c| !content.anchors.iter().any(|a| a.section_id == c.anchor_section_id))
+            .map(CustomSection::to_section),
+    );
+
+    content.sections = sections;
+    if include_toc {
+        content.toc = number_sections(&mut content.sections);
+    }
+}
+
+fn custom_sections_at<'a>(
+    custom_sections: &'a [CustomSection],
+    anchor: &'a str,
+    placement: SectionPlacement,
+) -> impl Iterator<Item = ReportSection> + 'a {
+    custom_sections
+        .iter()
+        .filter(move |c| c.anchor_section_id == anchor && c.placement == placement)
+        .map(CustomSection::to_section)
+}
+
+/// Put back the titles numbering replaced, walking sections in the order
+/// they were numbered
+fn unnumber_sections(sections: &mut [ReportSection], toc: &mut impl Iterator<Item = TocEntry>) {
+    for section in sections {
+        if !section.title.is_empty() && section.level > 0 {
+            if let Some(entry) = toc.next() {
+                section.title = entry.title;
+            }
+        }
+        unnumber_sections(&mut section.subsections, toc);
+    }
+}
+
+/// Number titled sections in document order and list them for the table of contents
+///
+/// Numbers follow each section's `level` (1, 1.1, 1.2, 2 ...) and are prefixed
+/// to the titles so every export format shows them. Untitled sections, like the
+/// page break wrapper, are skipped.
+pub fn number_sections(sections: &mut [ReportSection]) -> Vec<TocEntry> {
+    number_sections_from(sections, &mut Vec::new())
+}
+
+/// Number sections continuing from earlier ones, for reports built in parts
+///
+/// `counters` carries the numbering from one call to the next.
+pub fn number_sections_from(sections: &mut [ReportSection], counters: &mut Vec<u32>) -> Vec<TocEntry> {
+    let mut toc = Vec::new();
+    for section in sections {
+        number_section(section, counters, &mut toc);
     }
+    toc
+}
 
-    fn build_full_engagement(&self) -> Vec<ReportSection> {
-        let mut sections = vec![
-            ReportSection {
-                id: "engagement-overview".to_string(),
-                title: "Engagement Overview".to_string(),
-                level: 1,
-                blocks: vec![
-                    ContentBlock::Paragraph {
-                        text: format!(
-                            "This comprehensive report documents the full security engagement conducted for {}. \
-                            The assessment covered multiple domains including compliance, network security, \
-                            cloud readiness, and vulnerability analysis.",
-                            self.config.client_name
-                        ),
-                    },
-                ],
-                subsections: vec![],
-            },
-        ];
+fn number_section(section: &mut ReportSection, counters: &mut Vec<u32>, toc: &mut Vec<TocEntry>) {
+    if !section.title.is_empty() && section.level > 0 {
+        let depth = section.level as usize;
+        counters.resize(depth, 0);
+        counters[depth - 1] += 1;
+        let number = counters.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(".");
 
-        // Add sections from each report type
-        sections.extend(self.build_executive_summary());
-        sections.push(ReportSection {
-            id: "page-break-1".to_string(),
-            title: String::new(),
-            level: 0,
-            blocks: vec![ContentBlock::PageBreak],
-            subsections: vec![],
+        toc.push(TocEntry {
+            number: number.clone(),
+            title: section.title.clone(),
+            section_id: section.id.clone(),
+            level: section.level,
         });
-        sections.extend(self.build_compliance_report());
-        sections.extend(self.build_network_assessment());
-        sections.extend(self.build_security_findings());
+        section.title = format!("{} {}", number, section.title);
+    }
 
-        sections
+    for subsection in &mut section.subsections {
+        number_section(subsection, counters, toc);
     }
 }
 
@@ -520,86 +3118,156 @@ pub fn content_to_html(content: &ReportContent) -> String {
     html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
     html.push_str("<meta charset=\"UTF-8\">\n");
     html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n");
-    html.push_str(&format!("<title>{}</title>\n", content.metadata.title));
+    html.push_str(&format!("<title>{}</title>\n", escape_html(&content.metadata.title)));
     html.push_str("<style>\n");
     html.push_str(include_str!("report_styles.css"));
+    html.push_str(&theme_css(&content.metadata));
     html.push_str("</style>\n</head>\n<body>\n");
 
     // Cover page
     html.push_str("<div class=\"cover-page\">\n");
-    html.push_str(&format!("<h1 class=\"title\">{}</h1>\n", content.metadata.title));
+    if let Some(logo) = content.metadata.logo_path.as_deref().and_then(Logo::load) {
+        html.push_str(&format!(
+            "<img class=\"logo\" src=\"{}\" alt=\"{}\">\n",
+            logo.data_uri(),
+            escape_html(content.metadata.organization.as_deref().unwrap_or("Logo"))
+        ));
+    }
+    html.push_str(&format!("<h1 class=\"title\">{}</h1>\n", escape_html(&content.metadata.title)));
     if let Some(ref subtitle) = content.metadata.subtitle {
-        html.push_str(&format!("<h2 class=\"subtitle\">{}</h2>\n", subtitle));
+        html.push_str(&format!("<h2 class=\"subtitle\">{}</h2>\n", escape_html(subtitle)));
+    }
+    html.push_str(&format!("<p class=\"client\">Prepared for: {}</p>\n", escape_html(&content.metadata.client_name)));
+    if let Some(ref contact) = content.metadata.client_contact {
+        html.push_str(&format!("<p class=\"client-contact\">Attention: {}</p>\n", escape_html(contact)));
     }
-    html.push_str(&format!("<p class=\"client\">Prepared for: {}</p>\n", content.metadata.client_name));
-    html.push_str(&format!("<p class=\"author\">Prepared by: {}</p>\n", content.metadata.author));
-    html.push_str(&format!("<p class=\"date\">{}</p>\n", content.metadata.report_date));
+    html.push_str(&format!("<p class=\"author\">Prepared by: {}</p>\n", escape_html(&content.metadata.author)));
+    html.push_str(&format!("<p class=\"date\">{}</p>\n", escape_html(&content.metadata.report_date)));
     if let Some(ref classification) = content.metadata.classification {
-        html.push_str(&format!("<p class=\"classification\">{}</p>\n", classification));
+        html.push_str(&format!("<p class=\"classification\">{}</p>\n", escape_html(classification)));
     }
     html.push_str("</div>\n");
 
+    if !content.toc.is_empty() {
+        html.push_str("<nav class=\"toc\">\n<h1>Table of Contents</h1>\n<ul>\n");
+        for entry in &content.toc {
+            html.push_str(&format!(
+                "<li class=\"toc-level-{}\"><a href=\"#{}\">{} {}</a></li>\n",
+                entry.level.min(6),
+                escape_html(&entry.section_id),
+                escape_html(&entry.number),
+                escape_html(&entry.title)
+            ));
+        }
+        html.push_str("</ul>\n</nav>\n");
+    }
+
     // Sections
+    let palette = chart_palette(content.metadata.primary_color.as_deref());
     for section in &content.sections {
-        html.push_str(&section_to_html(section));
+        html.push_str(&section_to_html(section, &palette));
+    }
+
+    let footer: Vec<String> = [
+        content.metadata.footer_text.as_deref().map(escape_html),
+        content
+            .metadata
+            .classification
+            .as_deref()
+            .map(|label| format!("<span class=\"classification-label\">{}</span>", escape_html(&label.to_uppercase()))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !footer.is_empty() {
+        html.push_str(&format!("<footer class=\"report-footer\">{}</footer>\n", footer.join(" | ")));
     }
 
     html.push_str("</body>\n</html>");
     html
 }
 
-fn section_to_html(section: &ReportSection) -> String {
+/// CSS variable overrides for the report's brand colors
+///
+/// Colors that aren't valid hex are skipped (with a warning) so they can't
+/// inject into the stylesheet.
+fn theme_css(metadata: &ReportMetadata) -> String {
+    let colors = [
+        ("--primary-color", &metadata.primary_color),
+        ("--secondary-color", &metadata.secondary_color),
+    ];
+
+    let mut vars = String::new();
+    for (var, color) in colors {
+        if let Some(color) = color {
+            match normalize_hex_color(color) {
+                Some(hex) => vars.push_str(&format!("  {}: {};\n", var, hex)),
+                None => tracing::warn!("Ignoring invalid report color: {}", color),
+            }
+        }
+    }
+
+    if vars.is_empty() {
+        String::new()
+    } else {
+        format!(":root {{\n{}}}\n", vars)
+    }
+}
+
+fn section_to_html(section: &ReportSection, palette: &[String]) -> String {
     let mut html = String::new();
 
     if !section.title.is_empty() {
         let tag = format!("h{}", section.level.min(6));
-        html.push_str(&format!("<{} id=\"{}\">{}</{}>\n", tag, section.id, section.title, tag));
+        html.push_str(&format!("<{} id=\"{}\">{}</{}>\n", tag, escape_html(&section.id), escape_html(&section.title), tag));
     }
 
-    for block in &section.blocks {
-        html.push_str(&block_to_html(block));
+    for (i, block) in section.blocks.iter().enumerate() {
+        html.push_str(&block_to_html(block, &format!("chart-{}-{}", section.id, i), palette));
     }
 
     for subsection in &section.subsections {
-        html.push_str(&section_to_html(subsection));
+        html.push_str(&section_to_html(subsection, palette));
     }
 
     html
 }
 
-fn block_to_html(block: &ContentBlock) -> String {
+/// One block as HTML; `chart_id` is the stable element id a chart gets and
+/// `palette` the colors its series are drawn in
+fn block_to_html(block: &ContentBlock, chart_id: &str, palette: &[String]) -> String {
     match block {
-        ContentBlock::Paragraph { text } => format!("<p>{}</p>\n", text),
+        ContentBlock::Paragraph { text } => format!("<p>{}</p>\n", escape_html(text)),
 
         ContentBlock::Heading { text, level } => {
             let tag = format!("h{}", level.min(&6));
-            format!("<{0}>{1}</{0}>\n", tag, text)
+            format!("<{0}>{1}</{0}>\n", tag, escape_html(text))
         }
 
         ContentBlock::BulletList { items } => {
-            let items_html: String = items.iter().map(|i| format!("<li>{}</li>", i)).collect();
+            let items_html: String = items.iter().map(|i| format!("<li>{}</li>", escape_html(i))).collect();
             format!("<ul>{}</ul>\n", items_html)
         }
 
         ContentBlock::NumberedList { items } => {
-            let items_html: String = items.iter().map(|i| format!("<li>{}</li>", i)).collect();
+            let items_html: String = items.iter().map(|i| format!("<li>{}</li>", escape_html(i))).collect();
             format!("<ol>{}</ol>\n", items_html)
         }
 
         ContentBlock::Table { headers, rows, caption } => {
             let mut html = String::from("<table class=\"report-table\">\n");
             if let Some(cap) = caption {
-                html.push_str(&format!("<caption>{}</caption>\n", cap));
+                html.push_str(&format!("<caption>{}</caption>\n", escape_html(cap)));
             }
             html.push_str("<thead><tr>");
             for h in headers {
-                html.push_str(&format!("<th>{}</th>", h));
+                html.push_str(&format!("<th>{}</th>", escape_html(h)));
             }
             html.push_str("</tr></thead>\n<tbody>\n");
             for row in rows {
                 html.push_str("<tr>");
                 for cell in row {
-                    html.push_str(&format!("<td>{}</td>", cell));
+                    html.push_str(&format!("<td>{}</td>", escape_html(cell)));
                 }
                 html.push_str("</tr>\n");
             }
@@ -607,14 +3275,12 @@ fn block_to_html(block: &ContentBlock) -> String {
             html
         }
 
-        ContentBlock::Chart { chart_type, title, data: _ } => {
-            format!("<div class=\"chart-placeholder\" data-type=\"{:?}\"><p>{}</p><p>[Chart: {:?}]</p></div>\n", chart_type, title, chart_type)
-        }
+        ContentBlock::Chart { chart_type, title, data } => chart_to_html(chart_id, *chart_type, title, data, palette),
 
         ContentBlock::KeyValue { items } => {
             let mut html = String::from("<dl class=\"key-value\">\n");
             for item in items {
-                html.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", item.key, item.value));
+                html.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", escape_html(&item.key), escape_html(&item.value)));
             }
             html.push_str("</dl>\n");
             html
@@ -630,15 +3296,15 @@ fn block_to_html(block: &ContentBlock) -> String {
             };
             let mut html = format!("<div class=\"callout {}\">\n", class);
             if let Some(t) = title {
-                html.push_str(&format!("<strong>{}</strong>\n", t));
+                html.push_str(&format!("<strong>{}</strong>\n", escape_html(t)));
             }
-            html.push_str(&format!("<p>{}</p>\n</div>\n", text));
+            html.push_str(&format!("<p>{}</p>\n</div>\n", escape_html(text)));
             html
         }
 
         ContentBlock::Code { language, content } => {
             let lang = language.as_deref().unwrap_or("text");
-            format!("<pre><code class=\"language-{}\">{}</code></pre>\n", lang, content)
+            format!("<pre><code class=\"language-{}\">{}</code></pre>\n", escape_html(lang), escape_html(content))
         }
 
         ContentBlock::Finding { id, title, severity, description, impact, recommendation } => {
@@ -656,22 +3322,78 @@ fn block_to_html(block: &ContentBlock) -> String {
                 <p><strong>Impact:</strong> {}</p>\n\
                 <p><strong>Recommendation:</strong> {}</p>\n\
                 </div></div>\n",
-                severity_class, id, title, severity, description, impact, recommendation
+                severity_class,
+                escape_html(id),
+                escape_html(title),
+                escape_html(severity),
+                escape_html(description),
+                escape_html(impact),
+                escape_html(recommendation)
             )
         }
 
         ContentBlock::Metric { label, value, change, trend } => {
-            let mut html = format!("<div class=\"metric\"><span class=\"metric-value\">{}</span><span class=\"metric-label\">{}</span>", value, label);
+            let mut html = format!("<div class=\"metric\"><span class=\"metric-value\">{}</span><span class=\"metric-label\">{}</span>", escape_html(value), escape_html(label));
             if let Some(c) = change {
                 let trend_class = if trend.as_deref() == Some("improving") { "trend-up" } else { "trend-down" };
-                html.push_str(&format!("<span class=\"metric-change {}\">{}</span>", trend_class, c));
+                html.push_str(&format!("<span class=\"metric-change {}\">{}</span>", trend_class, escape_html(c)));
             }
             html.push_str("</div>\n");
             html
         }
 
         ContentBlock::PageBreak => "<div class=\"page-break\"></div>\n".to_string(),
+
+        ContentBlock::RawHtml { html } => format!("{}\n", html),
+    }
+}
+
+/// A chart as inline SVG plus its data as inline JSON
+///
+/// Radar and heatmap charts, and charts with nothing to draw, show a table
+/// of their figures instead. The JSON sits in
+/// `<script type="application/json" id="{id}-data">` for anything that wants
+/// the source figures; nothing in it is executed.
+fn chart_to_html(id: &str, chart_type: ChartType, title: &str, data: &ChartData, palette: &[String]) -> String {
+    let visual = chart_svg(chart_type, title, data, palette)
+        .unwrap_or_else(|| block_to_html(&chart_to_table(None, data), id, palette));
+    let id = escape_html(id);
+    let spec = serde_json::json!({
+        "type": chart_type,
+        "title": title,
+        "data": data,
+    });
+    format!(
+        "<figure class=\"chart\" id=\"{id}\" data-type=\"{}\">\n{}\
+         <script type=\"application/json\" id=\"{id}-data\">{}</script>\n<figcaption>{title}</figcaption>\n</figure>\n",
+        spec["type"].as_str().unwrap_or_default(),
+        visual,
+        escape_script_json(&spec.to_string()),
+        id = id,
+        title = escape_html(title),
+    )
+}
+
+/// Keep inline JSON from closing its script element: `<` is written as
+/// its JSON escape, which parses back to the same string
+fn escape_script_json(json: &str) -> String {
+    json.replace('<', "\\u003c")
+}
+
+/// Escape text for interpolation into HTML element content or attributes
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
     }
+    escaped
 }
 
 /// Convert report content to Markdown
@@ -679,16 +3401,28 @@ pub fn content_to_markdown(content: &ReportContent) -> String {
     let mut md = String::new();
 
     // Title
-    md.push_str(&format!("# {}\n\n", content.metadata.title));
+    md.push_str(&format!("# {}\n\n", escape_markdown(&content.metadata.title)));
     if let Some(ref subtitle) = content.metadata.subtitle {
-        md.push_str(&format!("## {}\n\n", subtitle));
+        md.push_str(&format!("## {}\n\n", escape_markdown(subtitle)));
     }
 
-    md.push_str(&format!("**Client:** {}\n\n", content.metadata.client_name));
-    md.push_str(&format!("**Author:** {}\n\n", content.metadata.author));
-    md.push_str(&format!("**Date:** {}\n\n", content.metadata.report_date));
+    md.push_str(&format!("**Client:** {}\n\n", escape_markdown(&content.metadata.client_name)));
+    if let Some(ref contact) = content.metadata.client_contact {
+        md.push_str(&format!("**Attention:** {}\n\n", escape_markdown(contact)));
+    }
+    md.push_str(&format!("**Author:** {}\n\n", escape_markdown(&content.metadata.author)));
+    md.push_str(&format!("**Date:** {}\n\n", escape_markdown(&content.metadata.report_date)));
     md.push_str("---\n\n");
 
+    if !content.toc.is_empty() {
+        md.push_str("## Table of Contents\n\n");
+        for entry in &content.toc {
+            let indent = "  ".repeat(entry.level.saturating_sub(1) as usize);
+            md.push_str(&format!("{}- {} {}\n", indent, entry.number, escape_markdown(&entry.title)));
+        }
+        md.push_str("\n---\n\n");
+    }
+
     // Sections
     for section in &content.sections {
         md.push_str(&section_to_markdown(section));
@@ -697,12 +3431,22 @@ pub fn content_to_markdown(content: &ReportContent) -> String {
     md
 }
 
+/// Standalone Markdown export of a remediation plan
+pub fn remediation_plan_to_markdown(assessment_name: &str, plan: &RemediationPlan) -> String {
+    let mut section = remediation_plan_section(plan);
+    section.title = format!("Remediation Plan: {}", assessment_name);
+
+    let mut md = section_to_markdown(&section);
+    md.push_str(&format!("*Generated {}*\n", plan.generated_at.format("%Y-%m-%d")));
+    md
+}
+
 fn section_to_markdown(section: &ReportSection) -> String {
     let mut md = String::new();
 
     if !section.title.is_empty() {
         let prefix = "#".repeat(section.level as usize);
-        md.push_str(&format!("{} {}\n\n", prefix, section.title));
+        md.push_str(&format!("{} {}\n\n", prefix, escape_markdown(&section.title)));
     }
 
     for block in &section.blocks {
@@ -719,32 +3463,32 @@ fn section_to_markdown(section: &ReportSection) -> String {
 
 fn block_to_markdown(block: &ContentBlock) -> String {
     match block {
-        ContentBlock::Paragraph { text } => format!("{}\n", text),
+        ContentBlock::Paragraph { text } => format!("{}\n", escape_markdown(text)),
         ContentBlock::Heading { text, level } => {
             let prefix = "#".repeat(*level as usize);
-            format!("{} {}\n", prefix, text)
+            format!("{} {}\n", prefix, escape_markdown(text))
         }
         ContentBlock::BulletList { items } => {
-            items.iter().map(|i| format!("- {}\n", i)).collect()
+            items.iter().map(|i| format!("- {}\n", escape_markdown(i))).collect()
         }
         ContentBlock::NumberedList { items } => {
-            items.iter().enumerate().map(|(n, i)| format!("{}. {}\n", n + 1, i)).collect()
+            items.iter().enumerate().map(|(n, i)| format!("{}. {}\n", n + 1, escape_markdown(i))).collect()
         }
         ContentBlock::Table { headers, rows, caption } => {
             let mut md = String::new();
             if let Some(cap) = caption {
-                md.push_str(&format!("*{}*\n\n", cap));
+                md.push_str(&format!("*{}*\n\n", escape_markdown(cap)));
             }
-            md.push_str(&format!("| {} |\n", headers.join(" | ")));
+            md.push_str(&format!("| {} |\n", table_row_to_markdown(headers)));
             md.push_str(&format!("| {} |\n", headers.iter().map(|_| "---").collect::<Vec<_>>().join(" | ")));
             for row in rows {
-                md.push_str(&format!("| {} |\n", row.join(" | ")));
+                md.push_str(&format!("| {} |\n", table_row_to_markdown(row)));
             }
             md
         }
-        ContentBlock::Chart { title, .. } => format!("[Chart: {}]\n", title),
+        ContentBlock::Chart { title, data, .. } => chart_to_markdown(title, data),
         ContentBlock::KeyValue { items } => {
-            items.iter().map(|i| format!("- **{}:** {}\n", i.key, i.value)).collect()
+            items.iter().map(|i| format!("- **{}:** {}\n", escape_markdown(&i.key), escape_markdown(&i.value))).collect()
         }
         ContentBlock::Callout { callout_type, title, text } => {
             let prefix = match callout_type {
@@ -756,21 +3500,1070 @@ fn block_to_markdown(block: &ContentBlock) -> String {
             };
             let mut md = String::new();
             if let Some(t) = title {
-                md.push_str(&format!("{}**{}**\n", prefix, t));
+                md.push_str(&format!("{}**{}**\n", prefix, escape_markdown(t)));
+            }
+            for line in text.lines() {
+                md.push_str(&format!("{}{}\n", prefix, escape_markdown(line)));
             }
-            md.push_str(&format!("{}{}\n", prefix, text));
             md
         }
         ContentBlock::Code { language, content } => {
-            format!("```{}\n{}\n```\n", language.as_deref().unwrap_or(""), content)
+            // The fence must be longer than any backtick run in the content
+            let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+            let fence = "`".repeat(longest_run.max(2) + 1);
+            let language: String = language
+                .as_deref()
+                .unwrap_or("")
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || "-+_.#".contains(*c))
+                .collect();
+            format!("{0}{1}\n{2}\n{0}\n", fence, language, content)
         }
         ContentBlock::Finding { id, title, severity, description, impact, recommendation } => {
             format!(
                 "### {} - {} [{}]\n\n**Description:** {}\n\n**Impact:** {}\n\n**Recommendation:** {}\n",
-                id, title, severity, description, impact, recommendation
+                escape_markdown(id),
+                escape_markdown(title),
+                escape_markdown(severity),
+                escape_markdown(description),
+                escape_markdown(impact),
+                escape_markdown(recommendation)
             )
         }
-        ContentBlock::Metric { label, value, .. } => format!("**{}:** {}\n", label, value),
+        ContentBlock::Metric { label, value, .. } => format!("**{}:** {}\n", escape_markdown(label), escape_markdown(value)),
         ContentBlock::PageBreak => "\n---\n".to_string(),
+        ContentBlock::RawHtml { html } => format!("{}\n", html),
+    }
+}
+
+/// A chart's numbers as a table, with a text bar per row for a single series
+fn chart_to_markdown(title: &str, data: &ChartData) -> String {
+    const BAR_WIDTH: f64 = 20.0;
+
+    let mut md = format!("*{}*\n\n", escape_markdown(title));
+    if data.labels.is_empty() {
+        md.push_str("No data\n");
+        return md;
+    }
+
+    let single = data.datasets.len() == 1;
+    let max = data
+        .datasets
+        .iter()
+        .flat_map(|d| d.data.iter().copied())
+        .fold(0.0_f64, f64::max);
+
+    let mut headers = vec![String::new()];
+    headers.extend(data.datasets.iter().map(|d| d.label.clone()));
+    if single {
+        headers.push(String::new());
+    }
+    md.push_str(&format!("| {} |\n", table_row_to_markdown(&headers)));
+    md.push_str(&format!("| {} |\n", headers.iter().map(|_| "---").collect::<Vec<_>>().join(" | ")));
+
+    for (i, label) in data.labels.iter().enumerate() {
+        let values: Vec<Option<f64>> = data.datasets.iter().map(|d| d.data.get(i).copied()).collect();
+        let mut row = vec![label.clone()];
+        row.extend(values.iter().map(|v| v.map(chart_value).unwrap_or_else(|| "-".to_string())));
+        if single {
+            let value = values[0].unwrap_or(0.0).max(0.0);
+            let width = if max > 0.0 { (value / max * BAR_WIDTH).round() as usize } else { 0 };
+            row.push("#".repeat(width));
+        }
+        md.push_str(&format!("| {} |\n", table_row_to_markdown(&row)));
+    }
+    md
+}
+
+/// Whole numbers without decimals, anything else to one decimal place
+pub(crate) fn chart_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+/// Neutralise Markdown syntax in text that may start a line
+///
+/// Besides the inline escapes, a `#` opening any line of the text is escaped
+/// so a paragraph or list item can't turn itself into a heading.
+fn escape_markdown(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let line = escape_markdown_inline(line);
+            let indent = line.len() - line.trim_start().len();
+            if line[indent..].starts_with('#') {
+                format!("{}\\{}", &line[..indent], &line[indent..])
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Neutralise inline HTML and code spans in Markdown text
+///
+/// Markdown viewers render embedded tags; writing `<` as an entity is enough
+/// to make them display literally. Backslashes and backticks are escaped so
+/// text can't swallow the next character or open a code span.
+fn escape_markdown_inline(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('`', "\\`").replace('<', "&lt;")
+}
+
+/// Escape cells for a Markdown table row, where `|` would start a new
+/// column and a newline would end the table
+///
+/// A cell never starts a line, so a leading `#` is left alone; the text bars
+/// of chart tables rely on that.
+fn table_row_to_markdown(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|cell| {
+            escape_markdown_inline(cell)
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace(['\r', '\n'], "<br>")
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Convert report content to CSV
+///
+/// Every table and finding in the report is emitted; other block types have
+/// no tabular form and are skipped. Each table becomes its own section,
+/// introduced by a single-cell label row (`Section - Caption`) and separated
+/// from the next by a blank line. Consecutive findings within a section are
+/// flattened into one table with id, title, severity, description, impact
+/// and recommendation columns.
+pub fn content_to_csv(content: &ReportContent) -> Result<String, String> {
+    let mut tables = Vec::new();
+    for section in &content.sections {
+        collect_csv_tables(section, &mut tables);
+    }
+
+    let mut chunks = Vec::with_capacity(tables.len());
+    for table in &tables {
+        let mut writer = csv::WriterBuilder::new()
+            .flexible(true)
+            .from_writer(Vec::new());
+
+        writer.write_record([table.label.as_str()]).map_err(|e| e.to_string())?;
+        writer.write_record(&table.headers).map_err(|e| e.to_string())?;
+        for row in &table.rows {
+            writer.write_record(row).map_err(|e| e.to_string())?;
+        }
+
+        let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+        chunks.push(String::from_utf8(bytes).map_err(|e| e.to_string())?);
+    }
+
+    Ok(chunks.join("\n"))
+}
+
+/// A table flattened for CSV output
+struct CsvTable {
+    label: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+const FINDING_CSV_HEADERS: [&str; 6] = ["ID", "Title", "Severity", "Description", "Impact", "Recommendation"];
+
+fn collect_csv_tables(section: &ReportSection, tables: &mut Vec<CsvTable>) {
+    let mut findings: Vec<Vec<String>> = Vec::new();
+
+    for block in &section.blocks {
+        match block {
+            ContentBlock::Finding { id, title, severity, description, impact, recommendation } => {
+                findings.push(vec![
+                    id.clone(),
+                    title.clone(),
+                    severity.clone(),
+                    description.clone(),
+                    impact.clone(),
+                    recommendation.clone(),
+                ]);
+            }
+            ContentBlock::Table { headers, rows, caption } => {
+                flush_csv_findings(section, &mut findings, tables);
+                let label = match caption {
+                    Some(caption) => format!("{} - {}", section.title, caption),
+                    None => section.title.clone(),
+                };
+                tables.push(CsvTable {
+                    label,
+                    headers: headers.clone(),
+                    rows: rows.clone(),
+                });
+            }
+            _ => flush_csv_findings(section, &mut findings, tables),
+        }
+    }
+    flush_csv_findings(section, &mut findings, tables);
+
+    for subsection in &section.subsections {
+        collect_csv_tables(subsection, tables);
+    }
+}
+
+fn flush_csv_findings(section: &ReportSection, findings: &mut Vec<Vec<String>>, tables: &mut Vec<CsvTable>) {
+    if findings.is_empty() {
+        return;
+    }
+    tables.push(CsvTable {
+        label: format!("{} - Findings", section.title),
+        headers: FINDING_CSV_HEADERS.iter().map(|h| h.to_string()).collect(),
+        rows: std::mem::take(findings),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(title: &str, blocks: Vec<ContentBlock>) -> ReportSection {
+        ReportSection {
+            id: title.to_lowercase(),
+            title: title.to_string(),
+            level: 1,
+            blocks,
+            subsections: vec![],
+        }
+    }
+
+    fn finding(id: &str, description: &str) -> ContentBlock {
+        ContentBlock::Finding {
+            id: id.to_string(),
+            title: "Weak TLS".to_string(),
+            severity: "High".to_string(),
+            description: description.to_string(),
+            impact: "Interception".to_string(),
+            recommendation: "Disable TLS 1.0, 1.1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_content_to_csv() {
+        let mut content = ReportContent {
+            metadata: ReportMetadata {
+                title: "Test".to_string(),
+                subtitle: None,
+                client_name: "Acme".to_string(),
+                client_contact: None,
+                author: "Tester".to_string(),
+                organization: None,
+                report_date: "2026-01-01".to_string(),
+                version: "1.0".to_string(),
+                classification: None,
+                page_count: None,
+                logo_path: None,
+                primary_color: None,
+                secondary_color: None,
+                footer_text: None,
+            },
+            toc: vec![],
+            anchors: vec![],
+            sections: vec![
+                section("Findings", vec![
+                    finding("F-1", "Line one\nline two"),
+                    finding("F-2", "Says \"legacy\""),
+                    ContentBlock::Paragraph { text: "Not tabular".to_string() },
+                    ContentBlock::Table {
+                        headers: vec!["Host".to_string(), "Ports".to_string()],
+                        rows: vec![vec!["10.0.0.1".to_string(), "22, 443".to_string()]],
+                        caption: Some("Exposure".to_string()),
+                    },
+                ]),
+            ],
+        };
+        content.sections[0].subsections.push(section("Nested", vec![finding("F-3", "Plain")]));
+
+        let csv = content_to_csv(&content).unwrap();
+        let expected = "Findings - Findings\n\
+            ID,Title,Severity,Description,Impact,Recommendation\n\
+            F-1,Weak TLS,High,\"Line one\nline two\",Interception,\"Disable TLS 1.0, 1.1\"\n\
+            F-2,Weak TLS,High,\"Says \"\"legacy\"\"\",Interception,\"Disable TLS 1.0, 1.1\"\n\
+            \n\
+            Findings - Exposure\n\
+            Host,Ports\n\
+            10.0.0.1,\"22, 443\"\n\
+            \n\
+            Nested - Findings\n\
+            ID,Title,Severity,Description,Impact,Recommendation\n\
+            F-3,Weak TLS,High,Plain,Interception,\"Disable TLS 1.0, 1.1\"\n";
+        assert_eq!(csv, expected);
+    }
+
+    fn config(report_type: ReportType) -> ReportConfig {
+        ReportConfig {
+            report_type,
+            client_id: "client-1".to_string(),
+            client_name: "Acme".to_string(),
+            title: "Test".to_string(),
+            subtitle: None,
+            author: "Tester".to_string(),
+            organization: None,
+            format: ExportFormat::Html,
+            include_toc: false,
+            include_executive_summary: false,
+            include_appendices: false,
+            include_charts: true,
+            logo_path: None,
+            primary_color: None,
+            secondary_color: None,
+            footer_text: None,
+            notes: None,
+            classification: None,
+            data_sources: vec![],
+            selected_sections: None,
+            client_contact: None,
+            snapshot_id: None,
+        }
+    }
+
+    fn has_no_data_callout(content: &ReportContent) -> bool {
+        content.sections.iter().flat_map(|s| &s.blocks).any(|b| matches!(
+            b,
+            ContentBlock::Callout { title: Some(t), .. } if t == "No Data Available"
+        ))
+    }
+
+    #[test]
+    fn test_live_report_without_data() {
+        for report_type in [ReportType::ExecutiveSummary, ReportType::ComplianceReport, ReportType::CloudReadiness] {
+            let report = ReportGenerator::new(config(report_type), ReportDataSource::default())
+                .generate()
+                .unwrap();
+            let content = report.content.unwrap();
+            assert!(has_no_data_callout(&content), "{:?} should flag missing data", report_type);
+        }
+
+        let demo = ReportGenerator::new(config(ReportType::ExecutiveSummary), ReportDataSource::demo())
+            .generate()
+            .unwrap();
+        assert!(!has_no_data_callout(&demo.content.unwrap()));
+    }
+
+    #[test]
+    fn test_cost_analysis_from_provider_comparison() {
+        let template = get_resource_templates().remove(0);
+        let comparison = compare_resource_costs(&template.resources, &ComparisonStrategies::default());
+        let data = ReportDataSource { provider_comparison: Some(comparison.clone()), ..Default::default() };
+
+        let report = ReportGenerator::new(config(ReportType::CloudReadiness), data).generate().unwrap();
+        let content = report.content.unwrap();
+        let cost = content.sections.iter().find(|s| s.id == "cost-analysis").unwrap();
+        let (rows, totals) = match (&cost.blocks[0], &cost.blocks[1]) {
+            (ContentBlock::Table { rows, .. }, ContentBlock::Table { rows: totals, .. }) => (rows, totals),
+            other => panic!("expected cost tables, got {:?}", other),
+        };
+
+        // One row per resource plus the total, priced from the comparison
+        assert_eq!(rows.len(), template.resources.len() + 1);
+        assert_eq!(rows[0][1], currency(comparison.per_resource[0].aws));
+        let cheapest = totals.iter().find(|r| r[3] == "Cheapest").unwrap();
+        assert_eq!(cheapest[0], comparison.cheapest_provider.short_name());
+        assert!(matches!(&cost.blocks[2], ContentBlock::Chart { chart_type: ChartType::Bar, .. }));
+
+        // The demo report prices its sample workload the same way
+        let demo = ReportGenerator::new(config(ReportType::CloudReadiness), ReportDataSource::demo())
+            .generate()
+            .unwrap();
+        let demo_cost = demo.content.unwrap().sections.into_iter().find(|s| s.id == "cost-analysis").unwrap();
+        assert_eq!(demo_cost.blocks.len(), 3);
+    }
+
+    #[test]
+    fn test_library_findings_ordered_by_severity_and_cvss() {
+        use crate::findings::library::{default_finding_templates, instantiate_finding, FindingOverrides};
+
+        let templates = default_finding_templates();
+        let finding = |id: &str| {
+            let template = templates.iter().find(|t| t.id == id).unwrap();
+            instantiate_finding(template, "client-1", FindingOverrides::default()).unwrap()
+        };
+        let mut remediated = finding("default-credentials");
+        remediated.status = FindingStatus::Remediated;
+        let data = ReportDataSource {
+            findings: vec![finding("deprecated-tls"), finding("missing-security-headers"), finding("smbv1-enabled"), remediated],
+            ..Default::default()
+        };
+
+        let report = ReportGenerator::new(config(ReportType::TechnicalAssessment), data).generate().unwrap();
+        let content = report.content.unwrap();
+        let findings = content.sections.iter().find(|s| s.id == "findings").unwrap();
+        let listed: Vec<(&str, &str)> = findings
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Finding { id, title, .. } => Some((id.as_str(), title.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(listed, vec![
+            ("FIND-001", "SMBv1 Protocol Enabled"),
+            ("FIND-002", "Missing HTTP Security Headers"),
+            ("FIND-003", "Deprecated TLS Protocols Enabled"),
+        ]);
+    }
+
+    #[test]
+    fn test_known_vulnerabilities_become_findings() {
+        let vulnerability = |asset: &str, cve: &str, cvss: f64, kev: bool| VulnerabilityMatch {
+            asset_id: asset.to_string(),
+            asset_name: asset.to_string(),
+            ip_address: "10.0.0.5".to_string(),
+            cve_id: cve.to_string(),
+            port: 22,
+            protocol: Protocol::Tcp,
+            product: "openssh".to_string(),
+            version: "8.9p1".to_string(),
+            cvss_score: cvss,
+            severity: crate::network::vuln_match::cvss_severity(cvss),
+            summary: "Test flaw".to_string(),
+            matched_range: "< 9.8p1".to_string(),
+            matched_at: "2024-07-10T00:00:00Z".to_string(),
+            kev,
+        };
+        let data = ReportDataSource {
+            vulnerabilities: vec![
+                vulnerability("web-1", "CVE-2023-38408", 9.8, false),
+                vulnerability("web-1", "CVE-2024-6387", 8.1, true),
+                vulnerability("web-2", "CVE-2024-6387", 8.1, true),
+            ],
+            ..Default::default()
+        };
+
+        let content = ReportGenerator::new(config(ReportType::SecurityFindings), data).generate().unwrap().content.unwrap();
+        let section = content.sections.iter().find(|s| s.id == "known-vulnerabilities").unwrap();
+        let findings: Vec<(&str, &str)> = section
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Finding { id, impact, .. } => Some((id.as_str(), impact.as_str())),
+                _ => None,
+            })
+            .collect();
+
+        // One finding per CVE, the known exploited one first
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].0, "CVE-2024-6387");
+        assert!(findings[0].1.contains("web-1") && findings[0].1.contains("web-2"));
+        assert!(findings[0].1.contains("actively exploited"));
+        assert!(!findings[1].1.contains("actively exploited"));
+    }
+
+    #[test]
+    fn test_remediation_priority_lists_control_gaps() {
+        use crate::grc::models::ComplianceStatus;
+        use crate::grc::remediation::{EffortEstimate, RemediationCategory};
+
+        let gap = |code: &str, status: ComplianceStatus, risk_rating: Option<u8>, item_status: RemediationItemStatus| RemediationItem {
+            id: code.to_string(),
+            control_id: code.to_string(),
+            control_code: code.to_string(),
+            control_title: "Control".to_string(),
+            category: "GV".to_string(),
+            compliance_status: status,
+            risk_rating,
+            gap: None,
+            recommended_action: "Fix it".to_string(),
+            effort: EffortEstimate::Small,
+            owner_role: "CISO".to_string(),
+            owner: None,
+            due_date: None,
+            target_quarter: None,
+            status: item_status,
+            updated_at: chrono::Utc::now(),
+        };
+        let plan = RemediationPlan {
+            id: "plan-1".to_string(),
+            client_id: "client-1".to_string(),
+            assessment_id: "a1".to_string(),
+            framework: Framework::NistCsf2,
+            categories: vec![RemediationCategory {
+                category: "GV".to_string(),
+                display_name: "Govern".to_string(),
+                items: vec![
+                    gap("GV.RR-01", ComplianceStatus::PartiallyCompliant, None, RemediationItemStatus::Open),
+                    gap("GV.OC-01", ComplianceStatus::NonCompliant, Some(5), RemediationItemStatus::InProgress),
+                    gap("GV.PO-01", ComplianceStatus::NonCompliant, None, RemediationItemStatus::Done),
+                ],
+            }],
+            generated_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let data = ReportDataSource { remediation_plan: Some(plan), ..Default::default() };
+
+        let content = ReportGenerator::new(config(ReportType::SecurityFindings), data).generate().unwrap().content.unwrap();
+        let section = content.sections.iter().find(|s| s.id == "remediation-priority").unwrap();
+        let rows = match &section.blocks[0] {
+            ContentBlock::Table { rows, .. } => rows,
+            other => panic!("expected a table, got {:?}", other),
+        };
+        let listed: Vec<(&str, &str)> = rows.iter().map(|r| (r[1].as_str(), r[3].as_str())).collect();
+        assert_eq!(listed, vec![("GV.OC-01 Control", "Critical"), ("GV.RR-01 Control", "Medium")]);
+
+        let empty = ReportGenerator::new(config(ReportType::SecurityFindings), ReportDataSource::default())
+            .generate()
+            .unwrap();
+        assert!(has_no_data_callout(&empty.content.unwrap()));
+    }
+
+    #[test]
+    fn test_deselected_section_is_omitted() {
+        let render = |selected: Option<Vec<&str>>| {
+            let mut config = config(ReportType::FullEngagement);
+            config.selected_sections = selected.map(|ids| ids.into_iter().map(str::to_string).collect());
+            let content = ReportGenerator::new(config, ReportDataSource::demo()).generate().unwrap().content.unwrap();
+            (content_to_html(&content), content_to_markdown(&content))
+        };
+        let required = ["engagement-overview", "executive-summary", "security-findings", "remediation-roadmap"];
+
+        let (html, markdown) = render(None);
+        assert!(html.contains("Network Assessment Overview") && markdown.contains("Network Assessment Overview"));
+        // Cloud readiness is off by default in full engagement reports
+        assert!(!html.contains("Cloud Readiness Assessment"));
+
+        let mut selected = required.to_vec();
+        selected.extend(["compliance-assessment", "cloud-readiness"]);
+        let (html, markdown) = render(Some(selected));
+        assert!(!html.contains("Network Assessment Overview") && !markdown.contains("Network Assessment Overview"));
+        assert!(html.contains("Compliance Assessment Overview") && markdown.contains("Compliance Assessment Overview"));
+        assert!(html.contains("Cloud Readiness Assessment") && markdown.contains("Cloud Readiness Assessment"));
+    }
+
+    #[test]
+    fn test_section_selection_validation() {
+        let generate = |selected: &[&str]| {
+            let mut config = config(ReportType::ExecutiveSummary);
+            config.selected_sections = Some(selected.iter().map(|id| id.to_string()).collect());
+            ReportGenerator::new(config, ReportDataSource::demo()).generate()
+        };
+
+        let err = generate(&["exec-overview", "key-findings", "risk-summary"]).unwrap_err();
+        assert!(err.contains("Strategic Recommendations") && err.contains("required"));
+
+        let err = generate(&["exec-overview", "key-findings", "risk-summary", "recommendations", "appendix-tools"]).unwrap_err();
+        assert!(err.contains("appendix-tools") && err.contains("next-steps"));
+
+        let content = generate(&["exec-overview", "key-findings", "risk-summary", "recommendations"]).unwrap().content.unwrap();
+        assert_eq!(content.sections.len(), 4);
+    }
+
+    #[test]
+    fn test_excluded_appendices_left_out_of_every_format() {
+        use crate::reporting::docx_generator::build_docx;
+        use crate::reporting::templates::resolve_section_selection;
+
+        let template = get_template_for_type(ReportType::TechnicalAssessment);
+        let exclude = |id: &str| SectionSelection { id: id.to_string(), included: false };
+        let render = |choices: &[SectionSelection]| {
+            let mut config = config(ReportType::TechnicalAssessment);
+            config.include_appendices = true;
+            config.selected_sections = Some(resolve_section_selection(&template, choices).unwrap());
+            let content = ReportGenerator::new(config, ReportDataSource::demo()).generate().unwrap().content.unwrap();
+            let ids: Vec<String> = content.sections.iter().map(|s| s.id.clone()).collect();
+            let outputs = [
+                content_to_html(&content),
+                content_to_markdown(&content),
+                content_to_csv(&content).unwrap(),
+                String::from_utf8(build_docx(&content).build().document).unwrap(),
+                serde_json::to_string(&content).unwrap(),
+            ];
+            (ids, outputs)
+        };
+
+        // Built in template order
+        let (ids, outputs) = render(&[]);
+        assert_eq!(
+            ids,
+            ["tech-overview", "methodology", "findings-summary", "findings", "remediation", "appendix-tools", "appendix-evidence"]
+        );
+        for output in &outputs {
+            assert!(output.contains("Appendix: Tools") && output.contains("Appendix: Evidence"));
+        }
+
+        let (ids, outputs) = render(&[exclude("appendix-tools"), exclude("appendix-evidence")]);
+        assert!(!ids.iter().any(|id| id.starts_with("appendix")));
+        for output in &outputs {
+            assert!(!output.contains("Appendix:"), "appendix left in {}", &output[..output.len().min(80)]);
+            assert!(!output.contains("accepted on 443/tcp"));
+        }
+
+        let err = resolve_section_selection(&template, &[exclude("findings-summary")]).unwrap_err();
+        assert!(err.contains("Findings Summary") && err.contains("required"));
+        let err = resolve_section_selection(&template, &[exclude("appendix-screenshots")]).unwrap_err();
+        assert!(err.contains("appendix-screenshots") && err.contains("appendix-evidence"));
+    }
+
+    #[test]
+    fn test_custom_sections_placed_around_template_sections() {
+        use crate::reporting::templates::validate_custom_section;
+
+        let custom = |title: &str, placement: SectionPlacement, anchor: &str| CustomSection {
+            id: title.to_lowercase(),
+            report_id: "report-1".to_string(),
+            title: title.to_string(),
+            placement,
+            anchor_section_id: anchor.to_string(),
+            blocks: vec![ContentBlock::Paragraph { text: format!("{} notes", title) }],
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let scope = custom("Scope Caveats", SectionPlacement::Before, "tech-overview");
+        let interviews = custom("Interviews", SectionPlacement::After, "methodology");
+        let titles = |content: &ReportContent| content.sections.iter().map(|s| s.title.clone()).collect::<Vec<_>>();
+
+        let mut config = config(ReportType::TechnicalAssessment);
+        config.include_toc = true;
+        let mut generator = ReportGenerator::new(config, ReportDataSource::demo());
+        generator.set_custom_sections(vec![scope.clone(), interviews.clone()]);
+        let mut content = generator.generate().unwrap().content.unwrap();
+        assert_eq!(
+            titles(&content)[..4],
+            ["1 Scope Caveats", "2 Technical Assessment Overview", "3 Methodology", "4 Interviews"]
+        );
+        assert!(content_to_markdown(&content).contains("Interviews notes"));
+
+        // Re-placing after an edit neither duplicates nor double-numbers
+        let mut moved = interviews.clone();
+        moved.placement = SectionPlacement::Before;
+        moved.anchor_section_id = "remediation".to_string();
+        place_custom_sections(&mut content, &[moved], true);
+        let titles = titles(&content);
+        assert_eq!(titles[..3], ["1 Technical Assessment Overview", "2 Methodology", "3 Findings Summary"]);
+        let position = titles.iter().position(|t| t.ends_with("Interviews")).unwrap();
+        assert_eq!(titles.iter().filter(|t| t.ends_with("Interviews")).count(), 1);
+        assert!(titles[position + 1].ends_with("Remediation Roadmap"));
+        assert!(!titles.iter().any(|t| t.contains("Scope")));
+        let top_level: Vec<String> = content
+            .toc
+            .iter()
+            .filter(|entry| entry.level == 1)
+            .map(|entry| format!("{} {}", entry.number, entry.title))
+            .collect();
+        assert_eq!(top_level, titles);
+
+        let template = get_template_for_type(ReportType::TechnicalAssessment);
+        assert!(validate_custom_section(&template, "Notes", "methodology", &scope.blocks).is_ok());
+        let err = validate_custom_section(&template, "Notes", "exec-overview", &scope.blocks).unwrap_err();
+        assert!(err.contains("exec-overview") && err.contains("appendix-evidence"));
+        let raw = [ContentBlock::RawHtml { html: "<b>bold</b>".to_string() }];
+        assert!(validate_custom_section(&template, "Notes", "methodology", &raw).unwrap_err().contains("raw HTML"));
+        assert!(validate_custom_section(&template, " ", "methodology", &scope.blocks).is_err());
+    }
+
+    #[test]
+    fn test_engagement_parts_match_full_report() {
+        let mut config = config(ReportType::FullEngagement);
+        config.include_toc = true;
+        config.include_appendices = true;
+        config.data_sources = vec![DataSource {
+            source_type: "demo".to_string(),
+            source_id: "sample-content".to_string(),
+            included: true,
+        }];
+        let generator = ReportGenerator::new(config, ReportDataSource::demo());
+        let full = generator.generate().unwrap().content.unwrap();
+
+        let mut counters = Vec::new();
+        let mut toc = Vec::new();
+        let mut titles = Vec::new();
+        let parts: Vec<String> = generator.template().sections.iter().map(|s| s.id.clone()).collect();
+        for part in &parts {
+            let mut sections = generator.build_engagement_part(part);
+            toc.extend(number_sections_from(&mut sections, &mut counters));
+            titles.extend(sections.into_iter().map(|s| s.title));
+        }
+        let mut appendix: Vec<ReportSection> = generator.data_sources_appendix().into_iter().collect();
+        toc.extend(number_sections_from(&mut appendix, &mut counters));
+        titles.extend(appendix.into_iter().map(|s| s.title));
+
+        assert_eq!(titles, full.sections.iter().map(|s| s.title.clone()).collect::<Vec<_>>());
+        assert_eq!(toc.len(), full.toc.len());
+        assert_eq!(toc.last().map(|t| &t.number), full.toc.last().map(|t| &t.number));
+    }
+
+    #[test]
+    fn test_number_sections() {
+        let mut overview = section("Overview", vec![]);
+        let mut scope = section("Scope", vec![]);
+        scope.level = 2;
+        let mut method = section("Method", vec![]);
+        method.level = 2;
+        let mut tools = section("Tools", vec![]);
+        tools.level = 3;
+        method.subsections.push(tools);
+        overview.subsections = vec![scope, method];
+
+        let mut page_break = section("", vec![ContentBlock::PageBreak]);
+        page_break.level = 0;
+        let mut findings = section("Findings", vec![]);
+        let mut critical = section("Critical", vec![]);
+        critical.level = 2;
+        findings.subsections.push(critical);
+
+        let mut sections = vec![overview, page_break, findings];
+        let toc = number_sections(&mut sections);
+
+        let numbered: Vec<(&str, &str)> = toc.iter().map(|e| (e.number.as_str(), e.title.as_str())).collect();
+        assert_eq!(numbered, vec![
+            ("1", "Overview"),
+            ("1.1", "Scope"),
+            ("1.2", "Method"),
+            ("1.2.1", "Tools"),
+            ("2", "Findings"),
+            ("2.1", "Critical"),
+        ]);
+        assert_eq!(sections[0].subsections[1].subsections[0].title, "1.2.1 Tools");
+        assert_eq!(sections[1].title, "");
+        assert_eq!(sections[2].subsections[0].title, "2.1 Critical");
+
+        let mut content = hostile_content();
+        content.sections = sections;
+        content.toc = toc;
+        let html = content_to_html(&content);
+        assert!(html.contains("<li class=\"toc-level-3\"><a href=\"#tools\">1.2.1 Tools</a></li>"));
+        assert!(html.contains("<h3 id=\"tools\">1.2.1 Tools</h3>"));
+        assert!(content_to_markdown(&content).contains("\n    - 1.2.1 Tools\n"));
+
+        // Without a TOC, titles stay as they are
+        let mut config = config(ReportType::FullEngagement);
+        let plain = ReportGenerator::new(config.clone(), ReportDataSource::demo()).generate().unwrap().content.unwrap();
+        assert!(plain.toc.is_empty());
+        assert_eq!(plain.sections[0].title, "Engagement Overview");
+        config.include_toc = true;
+        let numbered = ReportGenerator::new(config, ReportDataSource::demo()).generate().unwrap().content.unwrap();
+        assert_eq!(numbered.sections[0].title, "1 Engagement Overview");
+        assert!(!content_to_html(&plain).contains("class=\"toc\""));
+    }
+
+    const HOSTILE: &str = "<img src=x onerror=alert(1)> | \"q\" & 'a'\nnext";
+
+    fn hostile_content() -> ReportContent {
+        ReportContent {
+            metadata: ReportMetadata {
+                title: HOSTILE.to_string(),
+                subtitle: Some(HOSTILE.to_string()),
+                client_name: HOSTILE.to_string(),
+                client_contact: Some(HOSTILE.to_string()),
+                author: HOSTILE.to_string(),
+                organization: None,
+                report_date: "2026-01-01".to_string(),
+                version: "1.0".to_string(),
+                classification: Some(HOSTILE.to_string()),
+                page_count: None,
+                logo_path: Some(HOSTILE.to_string()),
+                primary_color: Some("red;}</style><script>alert(1)</script>".to_string()),
+                secondary_color: None,
+                footer_text: Some(HOSTILE.to_string()),
+            },
+            toc: vec![],
+            anchors: vec![],
+            sections: vec![section(HOSTILE, vec![
+                ContentBlock::Paragraph { text: HOSTILE.to_string() },
+                ContentBlock::BulletList { items: vec![HOSTILE.to_string()] },
+                ContentBlock::Table {
+                    headers: vec!["Host".to_string(), "Notes".to_string()],
+                    rows: vec![vec!["10.0.0.1".to_string(), HOSTILE.to_string()]],
+                    caption: Some(HOSTILE.to_string()),
+                },
+                ContentBlock::KeyValue {
+                    items: vec![KeyValueItem { key: HOSTILE.to_string(), value: HOSTILE.to_string() }],
+                },
+                ContentBlock::Callout {
+                    callout_type: CalloutType::Warning,
+                    title: Some(HOSTILE.to_string()),
+                    text: HOSTILE.to_string(),
+                },
+                ContentBlock::Code { language: Some("\"><script>".to_string()), content: "```\n</code><b>".to_string() },
+                finding(HOSTILE, HOSTILE),
+                ContentBlock::Metric {
+                    label: HOSTILE.to_string(),
+                    value: HOSTILE.to_string(),
+                    change: Some(HOSTILE.to_string()),
+                    trend: None,
+                },
+                ContentBlock::Chart {
+                    chart_type: ChartType::Bar,
+                    title: HOSTILE.to_string(),
+                    data: ChartData {
+                        labels: vec!["</script><script>alert(1)</script>".to_string()],
+                        datasets: vec![ChartDataset { label: HOSTILE.to_string(), data: vec![1.0], color: None }],
+                    },
+                },
+            ])],
+        }
+    }
+
+    #[test]
+    fn test_html_escapes_text() {
+        let html = content_to_html(&hostile_content());
+        assert!(!html.contains("<img"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("class=\"language-&quot;&gt;&lt;script&gt;\""));
+        assert!(html.contains("&lt;img src=x onerror=alert(1)&gt; | &quot;q&quot; &amp; &#39;a&#39;"));
+
+        let mut content = hostile_content();
+        content.sections[0].blocks = vec![ContentBlock::RawHtml { html: "<hr class=\"divider\">".to_string() }];
+        assert!(content_to_html(&content).contains("<hr class=\"divider\">\n"));
+
+        // Only hex brand colors make it into the stylesheet
+        assert!(!html.contains("--primary-color: red"));
+        content.metadata.primary_color = Some("#0f766e".to_string());
+        assert!(content_to_html(&content).contains("--primary-color: #0F766E;"));
+    }
+
+    #[test]
+    fn test_markdown_escapes_text() {
+        let md = content_to_markdown(&hostile_content());
+        assert!(!md.contains("<img"));
+        assert!(!md.contains("<script>"));
+
+        // The hostile cell stays within its row and column
+        let row = md.lines().find(|l| l.starts_with("| 10.0.0.1")).unwrap();
+        assert_eq!(row, "| 10.0.0.1 | &lt;img src=x onerror=alert(1)> \\| \"q\" & 'a'<br>next |");
+
+        // Code content can't close its fence
+        assert!(md.contains("````script\n```\n</code><b>\n````\n"));
+    }
+
+    #[test]
+    fn test_hostile_data_escaped_in_text_exports() {
+        use crate::network::inventory::PolicyViolation;
+        use crate::network::models::AssetCategory;
+
+        const CLIENT: &str = "Acme <script>alert(1)</script>";
+        const ASSET: &str = "db01 </td><img src=x onerror=alert(1)> | `whoami`\n# owned";
+        const NOTE: &str = "Banner: </td></tr><script>x</script> | ```\n## pwned \\";
+
+        let mut network = config(ReportType::NetworkAssessment);
+        network.client_name = CLIENT.to_string();
+        let data = ReportDataSource {
+            policy_violations: vec![AssetPolicyViolations {
+                asset_id: "asset-1".to_string(),
+                asset_name: ASSET.to_string(),
+                ip_address: "10.0.0.5".to_string(),
+                category: AssetCategory::Server,
+                criticality: Criticality::High,
+                violations: vec![PolicyViolation {
+                    policy_id: "no-telnet".to_string(),
+                    policy_name: "No Telnet".to_string(),
+                    port: 23,
+                    service: "telnet".to_string(),
+                    severity: Criticality::High,
+                    message: ASSET.to_string(),
+                    recommendation: None,
+                }],
+            }],
+            ..Default::default()
+        };
+        let network = ReportGenerator::new(network, data).generate().unwrap().content.unwrap();
+
+        let mut technical = config(ReportType::TechnicalAssessment);
+        technical.client_name = CLIENT.to_string();
+        technical.include_appendices = true;
+        let data = ReportDataSource {
+            network_findings: vec![NetworkFinding {
+                id: "finding-1".to_string(),
+                client_id: "client-1".to_string(),
+                rule_id: "telnet-exposed".to_string(),
+                host: "10.0.0.5".to_string(),
+                port: 23,
+                service: "telnet".to_string(),
+                title: ASSET.to_string(),
+                severity: Criticality::High,
+                status: FindingStatus::Open,
+                evidence: vec![NOTE.to_string()],
+                notes: Some(NOTE.to_string()),
+                detected_at: "2026-01-01T00:00:00Z".to_string(),
+                updated_at: "2026-01-01T00:00:00Z".to_string(),
+                verification_scan_id: None,
+            }],
+            ..Default::default()
+        };
+        let technical = ReportGenerator::new(technical, data).generate().unwrap().content.unwrap();
+
+        for content in [&network, &technical] {
+            let html = content_to_html(content);
+            assert!(!html.contains("<script>") && !html.contains("<img") && !html.contains("</td></tr><script"));
+            assert!(html.contains("Prepared for: Acme &lt;script&gt;alert(1)&lt;/script&gt;"));
+
+            let md = content_to_markdown(content);
+            assert!(!md.contains("<script>") && !md.contains("<img"));
+            assert!(md.contains("\\`whoami\\`") && !md.contains(" `whoami`"));
+            assert!(!md.lines().any(|l| l.starts_with("# owned") || l.starts_with("## pwned")));
+
+            // Every row of a Markdown table keeps the header's column count
+            let columns = |line: &str| line.replace("\\\\", "").replace("\\|", "").matches('|').count();
+            let mut header = None;
+            for line in md.lines() {
+                match (line.starts_with('|'), header) {
+                    (true, None) => header = Some(columns(line)),
+                    (true, Some(count)) => assert_eq!(columns(line), count, "{}", line),
+                    (false, _) => header = None,
+                }
+            }
+
+            // CSV quotes the text rather than altering it
+            let csv = content_to_csv(content).unwrap();
+            let mut reader = csv::ReaderBuilder::new().has_headers(false).flexible(true).from_reader(csv.as_bytes());
+            let cells: Vec<String> = reader
+                .records()
+                .flat_map(|record| record.unwrap().iter().map(str::to_string).collect::<Vec<_>>())
+                .collect();
+            assert!(cells.iter().any(|cell| cell.contains(ASSET)));
+        }
+        let csv = content_to_csv(&technical).unwrap();
+        assert!(csv.contains(&format!("\"{}\"", NOTE)));
+    }
+
+    /// The inline JSON of the chart with this id
+    fn chart_json(html: &str, id: &str) -> serde_json::Value {
+        let open = format!("<script type=\"application/json\" id=\"{}-data\">", escape_html(id));
+        let start = html.find(&open).unwrap() + open.len();
+        let end = start + html[start..].find("</script>").unwrap();
+        serde_json::from_str(&html[start..end]).unwrap()
+    }
+
+    #[test]
+    fn test_chart_html_embeds_source_data() {
+        use crate::grc::frameworks::{build_compliance_report, get_framework_controls};
+        use crate::grc::models::ComplianceStatus;
+
+        let controls = get_framework_controls(Framework::NistCsf2);
+        let report = build_compliance_report(Framework::NistCsf2, &controls, |c| match c.category.as_str() {
+            "GV" => ComplianceStatus::Compliant,
+            "PR" => ComplianceStatus::PartiallyCompliant,
+            _ => ComplianceStatus::NotAssessed,
+        });
+        let chart = category_compliance_chart(&report);
+
+        let mut content = hostile_content();
+        content.sections = vec![section("Status", vec![ContentBlock::Paragraph { text: "Intro".to_string() }, chart.clone()])];
+        let html = content_to_html(&content);
+
+        assert!(html.contains("<figure class=\"chart\" id=\"chart-status-1\" data-type=\"bar\">"));
+        assert!(html.contains("<figure class=\"chart\" id=\"chart-status-1\" data-type=\"bar\">\n<svg class=\"chart-svg\""));
+        assert!(!html.contains("<canvas"));
+        let json = chart_json(&html, "chart-status-1");
+        assert_eq!(json["type"], "bar");
+        let data = match chart {
+            ContentBlock::Chart { data, .. } => data,
+            _ => unreachable!(),
+        };
+        assert_eq!(json["data"], serde_json::to_value(&data).unwrap());
+
+        // The numbers are the report's own
+        let names: Vec<&str> = report.category_breakdown.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(json["data"]["labels"], serde_json::json!(names));
+        let scores: Vec<f64> = report.category_breakdown.iter().map(|c| c.compliance_percentage).collect();
+        assert_eq!(json["data"]["datasets"][0]["data"], serde_json::json!(scores));
+        assert_eq!(scores[0], 100.0);
+
+        // Text in the JSON can't close the script element
+        let html = content_to_html(&hostile_content());
+        let json = chart_json(&html, &format!("chart-{}-8", HOSTILE.to_lowercase()));
+        assert_eq!(json["data"]["labels"][0], "</script><script>alert(1)</script>");
+    }
+
+    #[test]
+    fn test_chart_markdown_fallback() {
+        let chart = ContentBlock::Chart {
+            chart_type: ChartType::Pie,
+            title: "Findings by Severity".to_string(),
+            data: ChartData {
+                labels: vec!["Critical".to_string(), "High".to_string(), "Medium".to_string()],
+                datasets: vec![ChartDataset { label: "Findings".to_string(), data: vec![2.0, 5.0, 10.0], color: None }],
+            },
+        };
+        assert_eq!(
+            block_to_markdown(&chart),
+            "*Findings by Severity*\n\n\
+             |  | Findings |  |\n\
+             | --- | --- | --- |\n\
+             | Critical | 2 | #### |\n\
+             | High | 5 | ########## |\n\
+             | Medium | 10 | #################### |\n"
+        );
+
+        // Several series get a column each and no bars
+        let trend = ContentBlock::Chart {
+            chart_type: ChartType::Line,
+            title: "Compliance Over Time".to_string(),
+            data: ChartData {
+                labels: vec!["2026-01-01".to_string(), "2026-04-01".to_string()],
+                datasets: vec![
+                    ChartDataset { label: "Compliance %".to_string(), data: vec![62.5, 71.0], color: None },
+                    ChartDataset { label: "Completion %".to_string(), data: vec![80.0], color: None },
+                ],
+            },
+        };
+        let md = block_to_markdown(&trend);
+        assert!(md.contains("|  | Compliance % | Completion % |\n"));
+        assert!(md.contains("| 2026-04-01 | 71 | - |\n"));
+    }
+
+    #[test]
+    fn test_charts_become_tables_when_not_wanted() {
+        let has_chart = |content: &ReportContent| {
+            content.sections.iter().flat_map(|s| &s.blocks).any(|b| matches!(b, ContentBlock::Chart { .. }))
+        };
+        let mut config = config(ReportType::SecurityFindings);
+        let with = ReportGenerator::new(config.clone(), ReportDataSource::demo()).generate().unwrap().content.unwrap();
+        assert!(has_chart(&with));
+
+        config.include_charts = false;
+        let without = ReportGenerator::new(config, ReportDataSource::demo()).generate().unwrap().content.unwrap();
+        assert!(!has_chart(&without));
+        assert!(!content_to_html(&without).contains("<svg"));
+
+        // The figures stay, as a table under the chart's title
+        let table = without
+            .sections
+            .iter()
+            .flat_map(|s| &s.blocks)
+            .find_map(|b| match b {
+                ContentBlock::Table { headers, rows, caption } if caption.as_deref() == Some("Findings by Severity") => {
+                    Some((headers, rows))
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(table.0, &vec![String::new(), "Findings".to_string()]);
+        assert_eq!(table.1[0][0], "Critical");
+    }
+
+    #[test]
+    fn test_branding_warnings_and_footer() {
+        let mut config = config(ReportType::ExecutiveSummary);
+        config.classification = Some("Confidential".to_string());
+        config.footer_text = Some("Northwind Security".to_string());
+        let report = ReportGenerator::new(config.clone(), ReportDataSource::demo()).generate().unwrap();
+        assert!(report.warnings.is_empty());
+        let html = content_to_html(report.content.as_ref().unwrap());
+        assert!(html.contains(
+            "<footer class=\"report-footer\">Northwind Security | <span class=\"classification-label\">CONFIDENTIAL</span></footer>"
+        ));
+
+        // A missing logo and a bad color are reported without failing the report
+        config.logo_path = Some("/nonexistent/logo.png".to_string());
+        config.primary_color = Some("blue".to_string());
+        let report = ReportGenerator::new(config, ReportDataSource::demo()).generate().unwrap();
+        assert_eq!(report.status, ReportStatus::Ready);
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings[0].contains("/nonexistent/logo.png"));
+        assert!(report.warnings[1].contains("blue"));
+    }
+
+    #[test]
+    fn test_currency() {
+        assert_eq!(currency(12450.4), "$12,450");
+        assert_eq!(currency(-1234567.0), "-$1,234,567");
+        assert_eq!(currency(999.0), "$999");
     }
 }
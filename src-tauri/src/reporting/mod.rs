@@ -1,14 +1,23 @@
 //! Intelligent Reporting Module
 //!
 //! Provides comprehensive report generation including executive summaries,
-//! technical assessments, compliance reports, and PDF export.
+//! technical assessments, compliance reports, and PDF/DOCX export with
+//! per-organization branding.
 
 pub mod models;
 pub mod generator;
 pub mod templates;
 pub mod pdf_generator;
+mod pdf_charts;
+mod svg_charts;
+pub mod docx_generator;
+pub mod repository;
+pub mod branding;
 
 pub use models::*;
 pub use generator::*;
 pub use templates::*;
 pub use pdf_generator::*;
+pub use docx_generator::*;
+pub use repository::*;
+pub use branding::*;
@@ -75,6 +75,7 @@ pub enum ExportFormat {
     Markdown,
     Docx,
     Json,
+    Csv,
 }
 
 impl ExportFormat {
@@ -85,6 +86,7 @@ impl ExportFormat {
             ExportFormat::Markdown => "md",
             ExportFormat::Docx => "docx",
             ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
         }
     }
 
@@ -95,6 +97,7 @@ impl ExportFormat {
             ExportFormat::Markdown => "text/markdown",
             ExportFormat::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
             ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv",
         }
     }
 }
@@ -145,12 +148,25 @@ pub struct ReportConfig {
     pub logo_path: Option<String>,
     /// Primary brand color
     pub primary_color: Option<String>,
+    /// Secondary brand color
+    pub secondary_color: Option<String>,
+    /// Text shown at the foot of every page
+    pub footer_text: Option<String>,
     /// Additional notes
     pub notes: Option<String>,
     /// Classification level
     pub classification: Option<String>,
     /// Data sources to include
     pub data_sources: Vec<DataSource>,
+    /// Template section ids to include; None uses the template's defaults
+    #[serde(default)]
+    pub selected_sections: Option<Vec<String>>,
+    /// Client's primary contact for the cover page
+    #[serde(default)]
+    pub client_contact: Option<String>,
+    /// Assessment snapshot the compliance data was frozen from
+    #[serde(default)]
+    pub snapshot_id: Option<String>,
 }
 
 impl Default for ReportConfig {
@@ -170,9 +186,14 @@ impl Default for ReportConfig {
             include_charts: true,
             logo_path: None,
             primary_color: Some("#3B82F6".to_string()),
+            secondary_color: None,
+            footer_text: None,
             notes: None,
             classification: Some("Confidential".to_string()),
             data_sources: vec![],
+            selected_sections: None,
+            client_contact: None,
+            snapshot_id: None,
         }
     }
 }
@@ -217,6 +238,10 @@ pub struct Report {
     pub updated_at: String,
     /// Generation error message
     pub error: Option<String>,
+    /// Problems that didn't stop generation, such as a logo that couldn't be
+    /// loaded; reported when the report is generated, not stored
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 /// Report content structure
@@ -227,6 +252,38 @@ pub struct ReportContent {
     pub sections: Vec<ReportSection>,
     /// Report metadata
     pub metadata: ReportMetadata,
+    /// Table of contents; empty when `include_toc` is off
+    #[serde(default)]
+    pub toc: Vec<TocEntry>,
+    /// Template sections in order with the generated sections each produced,
+    /// for placing custom sections
+    #[serde(default)]
+    pub anchors: Vec<SectionAnchor>,
+}
+
+/// The run of generated sections built for one template section
+///
+/// Runs follow each other from the first section, so counting generated
+/// sections finds them however many custom sections sit in between.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionAnchor {
+    /// Template section id
+    pub section_id: String,
+    pub section_count: usize,
+}
+
+/// Table of contents entry for a numbered section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TocEntry {
+    /// Hierarchical section number (1, 1.1, 1.2, 2 ...)
+    pub number: String,
+    /// Section title without its number
+    pub title: String,
+    /// Section ID, the HTML anchor
+    pub section_id: String,
+    pub level: u8,
 }
 
 /// Report metadata
@@ -238,10 +295,18 @@ pub struct ReportMetadata {
     pub author: String,
     pub organization: Option<String>,
     pub client_name: String,
+    /// Client's primary contact
+    #[serde(default)]
+    pub client_contact: Option<String>,
     pub report_date: String,
     pub classification: Option<String>,
     pub version: String,
     pub page_count: Option<u32>,
+    /// Cover page logo (PNG or JPEG)
+    pub logo_path: Option<String>,
+    pub primary_color: Option<String>,
+    pub secondary_color: Option<String>,
+    pub footer_text: Option<String>,
 }
 
 /// A section within a report
@@ -324,6 +389,11 @@ pub enum ContentBlock {
 
     #[serde(rename = "page_break")]
     PageBreak,
+
+    /// Markup embedded verbatim in HTML output, bypassing escaping. Never
+    /// put user-controlled text here. Formats without HTML skip it.
+    #[serde(rename = "raw_html")]
+    RawHtml { html: String },
 }
 
 /// Chart type
@@ -347,6 +417,22 @@ pub struct ChartData {
     pub datasets: Vec<ChartDataset>,
 }
 
+impl ChartData {
+    /// Value a full-length bar stands for: 100 when every series is a
+    /// percentage (labelled with "%"), otherwise the largest value
+    pub fn scale_max(&self) -> f64 {
+        if !self.datasets.is_empty() && self.datasets.iter().all(|d| d.label.contains('%')) {
+            return 100.0;
+        }
+        self.datasets.iter().flat_map(|d| d.data.iter().copied()).fold(0.0, f64::max)
+    }
+
+    /// Whether there's at least one value to draw
+    pub fn has_values(&self) -> bool {
+        !self.labels.is_empty() && self.datasets.iter().any(|d| !d.data.is_empty())
+    }
+}
+
 /// Chart dataset
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -356,6 +442,17 @@ pub struct ChartDataset {
     pub color: Option<String>,
 }
 
+impl ChartDataset {
+    /// The series' own color when it's valid hex, otherwise the palette
+    /// color for its position
+    pub fn color_or(&self, palette: &[String], index: usize) -> String {
+        self.color
+            .as_deref()
+            .and_then(super::branding::normalize_hex_color)
+            .unwrap_or_else(|| palette[index % palette.len()].clone())
+    }
+}
+
 /// Key-value item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -375,6 +472,51 @@ pub enum CalloutType {
     Note,
 }
 
+// ============================================================================
+// Custom Sections
+// ============================================================================
+
+/// Where a custom section goes relative to its template section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionPlacement {
+    Before,
+    After,
+}
+
+/// A section written by the user, kept with its report across regeneration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomSection {
+    pub id: String,
+    pub report_id: String,
+    pub title: String,
+    pub placement: SectionPlacement,
+    /// Template section the custom section is placed before or after
+    pub anchor_section_id: String,
+    /// Paragraph, bullet list, table, callout and code blocks only
+    pub blocks: Vec<ContentBlock>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Custom sections' ids in report content start with this, so they can be
+/// told apart from generated sections
+pub const CUSTOM_SECTION_ID_PREFIX: &str = "custom-";
+
+impl CustomSection {
+    /// The custom section as a top-level report section
+    pub fn to_section(&self) -> ReportSection {
+        ReportSection {
+            id: format!("{}{}", CUSTOM_SECTION_ID_PREFIX, self.id),
+            title: self.title.clone(),
+            level: 1,
+            blocks: self.blocks.clone(),
+            subsections: vec![],
+        }
+    }
+}
+
 // ============================================================================
 // Report Templates
 // ============================================================================
@@ -408,6 +550,15 @@ pub struct TemplateSectionDef {
     pub default_included: bool,
 }
 
+/// Whether one template section goes into a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionSelection {
+    /// Template section id
+    pub id: String,
+    pub included: bool,
+}
+
 // ============================================================================
 // Report History
 // ============================================================================
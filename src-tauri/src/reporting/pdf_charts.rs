@@ -0,0 +1,306 @@
+//! PDF Chart Primitives
+//!
+//! Bars, donuts and gauges drawn as filled vector polygons on a printpdf layer,
+//! and trend lines drawn as stroked paths.
+//! Geometry is computed separately from drawing so the math can be tested
+//! without rendering a document, and reused by the SVG charts of HTML exports.
+//! Coordinates are in mm from the bottom-left corner of the page; angles are
+//! in degrees, counter-clockwise from 3 o'clock.
+
+use printpdf::path::{PaintMode, WindingOrder};
+use printpdf::*;
+
+/// Angular resolution used when flattening arcs into polygon edges
+const ARC_STEP_DEGREES: f32 = 4.0;
+/// Unfilled part of bars, rings and gauges
+const TRACK_COLOR: (f32, f32, f32) = (0.9, 0.91, 0.93);
+
+/// Length in mm of a bar showing `percentage` of a `max_length` track
+///
+/// Values outside 0-100 are clamped so a bad figure never draws past the track.
+pub(crate) fn bar_length(percentage: f64, max_length: f32) -> f32 {
+    if percentage.is_nan() {
+        return 0.0;
+    }
+    (percentage.clamp(0.0, 100.0) / 100.0) as f32 * max_length
+}
+
+/// One slice of a donut
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DonutSegment {
+    /// Position of the value in the input slice
+    pub index: usize,
+    /// Angle the slice starts at
+    pub start: f32,
+    /// Clockwise extent of the slice
+    pub sweep: f32,
+}
+
+/// Divide a full circle between `values`, starting at 12 o'clock and running clockwise
+///
+/// Zero, negative and NaN values get no slice; when none is positive there is
+/// nothing to draw.
+pub(crate) fn donut_segments(values: &[f64]) -> Vec<DonutSegment> {
+    let share = |v: f64| if v > 0.0 { v } else { 0.0 };
+    let total: f64 = values.iter().map(|&v| share(v)).sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut start = 90.0;
+    let mut segments = Vec::new();
+    for (index, &value) in values.iter().enumerate() {
+        if share(value) == 0.0 {
+            continue;
+        }
+        let sweep = (share(value) / total * 360.0) as f32;
+        segments.push(DonutSegment { index, start, sweep });
+        start -= sweep;
+    }
+    segments
+}
+
+/// Clockwise extent of a half-circle gauge showing `score` out of 100
+pub(crate) fn gauge_sweep(score: f64) -> f32 {
+    bar_length(score, 180.0)
+}
+
+/// Points along a circular arc running `sweep` degrees clockwise from `start`
+pub(crate) fn arc_points(center: (f32, f32), radius: f32, start: f32, sweep: f32) -> Vec<(f32, f32)> {
+    let steps = ((sweep.abs() / ARC_STEP_DEGREES).ceil() as usize).max(1);
+    (0..=steps)
+        .map(|i| {
+            let angle = (start - sweep * i as f32 / steps as f32).to_radians();
+            (center.0 + radius * angle.cos(), center.1 + radius * angle.sin())
+        })
+        .collect()
+}
+
+/// Outline of a ring slice: along the outer arc, then back along the inner arc
+///
+/// An inner radius of zero gives a pie wedge closed at the center.
+pub(crate) fn ring_slice(center: (f32, f32), outer: f32, inner: f32, start: f32, sweep: f32) -> Vec<(f32, f32)> {
+    let mut points = arc_points(center, outer, start, sweep);
+    if inner > 0.0 {
+        points.extend(arc_points(center, inner, start, sweep).into_iter().rev());
+    } else {
+        points.push(center);
+    }
+    points
+}
+
+/// Points of a line chart of percentages spread evenly across `width`
+///
+/// 0% sits on `bottom` and 100% at `bottom + height`; values are clamped like
+/// bars. A single value is drawn as a flat line across the whole width.
+pub(crate) fn line_points(values: &[f64], x: f32, bottom: f32, width: f32, height: f32) -> Vec<(f32, f32)> {
+    let y = |v: f64| bottom + bar_length(v, height);
+    match values {
+        [] => vec![],
+        [only] => vec![(x, y(*only)), (x + width, y(*only))],
+        _ => {
+            let step = width / (values.len() - 1) as f32;
+            values.iter().enumerate().map(|(i, &v)| (x + step * i as f32, y(v))).collect()
+        }
+    }
+}
+
+/// Line chart of percentages over a shaded 0-100% band; `bottom` is the lower edge
+pub(crate) fn draw_trend_line(
+    layer: &PdfLayerReference,
+    x: f32,
+    bottom: f32,
+    width: f32,
+    height: f32,
+    values: &[f64],
+    color: Color,
+) {
+    fill_polygon(layer, &rect_points(x, bottom, width, height), track_color());
+    draw_line(layer, &line_points(values, x, bottom, width, height), color);
+}
+
+/// Stroked line through `points`, for further series over a trend band
+pub(crate) fn draw_line(layer: &PdfLayerReference, points: &[(f32, f32)], color: Color) {
+    if points.is_empty() {
+        return;
+    }
+    layer.set_outline_color(color);
+    layer.set_outline_thickness(1.5);
+    layer.add_line(Line {
+        points: points.iter().map(|&(x, y)| (Point::new(Mm(x), Mm(y)), false)).collect(),
+        is_closed: false,
+    });
+    layer.set_outline_color(black());
+    layer.set_outline_thickness(1.0);
+    layer.set_fill_color(black());
+}
+
+/// Horizontal bar over a full-length track; `bottom` is the lower edge
+pub(crate) fn draw_bar(
+    layer: &PdfLayerReference,
+    x: f32,
+    bottom: f32,
+    track_length: f32,
+    height: f32,
+    percentage: f64,
+    color: Color,
+) {
+    fill_polygon(layer, &rect_points(x, bottom, track_length, height), track_color());
+    let length = bar_length(percentage, track_length);
+    if length > 0.0 {
+        fill_polygon(layer, &rect_points(x, bottom, length, height), color);
+    }
+    layer.set_fill_color(black());
+}
+
+/// Donut with one colored slice per non-zero value
+///
+/// With nothing to show, an empty track ring is drawn so the layout stays intact.
+pub(crate) fn draw_donut(
+    layer: &PdfLayerReference,
+    center: (f32, f32),
+    outer: f32,
+    inner: f32,
+    slices: &[(f64, Color)],
+) {
+    let values: Vec<f64> = slices.iter().map(|(value, _)| *value).collect();
+    let segments = donut_segments(&values);
+    if segments.is_empty() {
+        fill_polygon(layer, &ring_slice(center, outer, inner, 90.0, 360.0), track_color());
+    }
+    for segment in segments {
+        let points = ring_slice(center, outer, inner, segment.start, segment.sweep);
+        fill_polygon(layer, &points, slices[segment.index].1.clone());
+    }
+    layer.set_fill_color(black());
+}
+
+/// Half-circle gauge filled clockwise from 9 o'clock in proportion to `score`
+pub(crate) fn draw_gauge(
+    layer: &PdfLayerReference,
+    center: (f32, f32),
+    outer: f32,
+    inner: f32,
+    score: f64,
+    color: Color,
+) {
+    fill_polygon(layer, &ring_slice(center, outer, inner, 180.0, 180.0), track_color());
+    let sweep = gauge_sweep(score);
+    if sweep > 0.0 {
+        fill_polygon(layer, &ring_slice(center, outer, inner, 180.0, sweep), color);
+    }
+    layer.set_fill_color(black());
+}
+
+fn rect_points(x: f32, bottom: f32, width: f32, height: f32) -> [(f32, f32); 4] {
+    [(x, bottom), (x + width, bottom), (x + width, bottom + height), (x, bottom + height)]
+}
+
+fn fill_polygon(layer: &PdfLayerReference, points: &[(f32, f32)], color: Color) {
+    layer.set_fill_color(color);
+    layer.add_polygon(Polygon {
+        rings: vec![points.iter().map(|&(x, y)| (Point::new(Mm(x), Mm(y)), false)).collect()],
+        mode: PaintMode::Fill,
+        winding_order: WindingOrder::NonZero,
+    });
+}
+
+fn track_color() -> Color {
+    let (r, g, b) = TRACK_COLOR;
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+fn black() -> Color {
+    Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn test_bar_length() {
+        assert!(close(bar_length(50.0, 80.0), 40.0));
+        assert!(close(bar_length(100.0, 80.0), 80.0));
+        assert_eq!(bar_length(0.0, 80.0), 0.0);
+        // Out-of-range and missing figures stay on the track
+        assert!(close(bar_length(140.0, 80.0), 80.0));
+        assert_eq!(bar_length(-5.0, 80.0), 0.0);
+        assert_eq!(bar_length(f64::NAN, 80.0), 0.0);
+    }
+
+    #[test]
+    fn test_donut_segments() {
+        let segments = donut_segments(&[2.0, 0.0, 1.0, 1.0]);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments.iter().map(|s| s.index).collect::<Vec<_>>(), vec![0, 2, 3]);
+
+        // Proportional, contiguous and covering the whole circle from 12 o'clock
+        assert!(close(segments[0].start, 90.0));
+        assert!(close(segments[0].sweep, 180.0));
+        assert!(close(segments[1].start, -90.0));
+        assert!(close(segments[1].sweep, 90.0));
+        assert!(close(segments[2].start, -180.0));
+        let total: f32 = segments.iter().map(|s| s.sweep).sum();
+        assert!(close(total, 360.0));
+
+        // A single non-zero value fills the ring
+        let single = donut_segments(&[0.0, 0.0, 7.0, 0.0]);
+        assert_eq!(single.len(), 1);
+        assert!(close(single[0].sweep, 360.0));
+
+        // Nothing to draw when every count is zero
+        assert!(donut_segments(&[0.0, 0.0, 0.0, 0.0]).is_empty());
+        assert!(donut_segments(&[]).is_empty());
+
+        // Fractional shares; negative values get no slice
+        let shares = donut_segments(&[1.5, -2.0, 0.5]);
+        assert_eq!(shares.iter().map(|s| s.index).collect::<Vec<_>>(), vec![0, 2]);
+        assert!(close(shares[0].sweep, 270.0));
+        assert!(donut_segments(&[f64::NAN, -1.0]).is_empty());
+    }
+
+    #[test]
+    fn test_line_points() {
+        let points = line_points(&[0.0, 50.0, 100.0], 10.0, 20.0, 60.0, 30.0);
+        assert_eq!(points.len(), 3);
+        assert!(close(points[0].0, 10.0) && close(points[0].1, 20.0));
+        assert!(close(points[1].0, 40.0) && close(points[1].1, 35.0));
+        assert!(close(points[2].0, 70.0) && close(points[2].1, 50.0));
+
+        // One value spans the width; out-of-range values stay in the band
+        let flat = line_points(&[150.0], 0.0, 0.0, 40.0, 10.0);
+        assert_eq!(flat, vec![(0.0, 10.0), (40.0, 10.0)]);
+        assert!(line_points(&[], 0.0, 0.0, 40.0, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_arc_geometry() {
+        // Quarter turn clockwise from 12 o'clock ends at 3 o'clock
+        let points = arc_points((10.0, 10.0), 5.0, 90.0, 90.0);
+        let (first, last) = (points[0], points[points.len() - 1]);
+        assert!(close(first.0, 10.0) && close(first.1, 15.0));
+        assert!(close(last.0, 15.0) && close(last.1, 10.0));
+        assert!(points.iter().all(|&(x, y)| close(((x - 10.0).powi(2) + (y - 10.0).powi(2)).sqrt(), 5.0)));
+
+        // A zero sweep still yields a degenerate two-point arc
+        assert_eq!(arc_points((0.0, 0.0), 5.0, 0.0, 0.0).len(), 2);
+
+        // Ring slices return along the inner arc; pie wedges close at the center
+        let ring = ring_slice((0.0, 0.0), 10.0, 6.0, 90.0, 90.0);
+        assert_eq!(ring.len(), points.len() * 2);
+        let inner_start = ring[ring.len() - 1];
+        assert!(close(inner_start.0, 0.0) && close(inner_start.1, 6.0));
+        let wedge = ring_slice((0.0, 0.0), 10.0, 0.0, 90.0, 90.0);
+        assert_eq!(wedge[wedge.len() - 1], (0.0, 0.0));
+
+        // Gauge runs over the upper half-circle
+        assert!(close(gauge_sweep(50.0), 90.0));
+        assert!(close(gauge_sweep(100.0), 180.0));
+        assert_eq!(gauge_sweep(0.0), 0.0);
+    }
+}
@@ -1,28 +1,101 @@
 //! PDF Generation Module
 //!
 //! Generates PDF reports using the printpdf library.
-//! Supports executive summaries with Network Health Score, Compliance Status, and Assets.
+//! Supports executive summaries with Network Health Score, Compliance Status, and Assets,
+//! as well as any generated report's content blocks, branded with an optional cover
+//! logo, heading color and footer text. Scores and breakdowns are drawn as vector
+//! charts alongside their figures. Pages are numbered "Page N of M" once the whole
+//! document is laid out.
 
 use printpdf::*;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufWriter;
 use std::path::PathBuf;
 
-use crate::grc::models::{ComplianceStatusReport, ExecutiveReportData};
+use crate::grc::models::{ComplianceStatusReport, ExecutiveReportData, MAX_MATURITY_LEVEL};
+use crate::reporting::branding::{chart_palette, parse_hex_color, Logo, LogoFormat, DEFAULT_PRIMARY_COLOR};
+use crate::reporting::generator::{chart_to_table, chart_value};
+use crate::reporting::models::{CalloutType, ChartData, ChartDataset, ChartType, ContentBlock, ReportContent, ReportMetadata, ReportSection, TocEntry};
+use crate::reporting::pdf_charts;
 
-/// PDF Generator for executive reports
+/// Letter page size in millimetres
+const PAGE_WIDTH: f32 = 215.9;
+const PAGE_HEIGHT: f32 = 279.4;
+/// Left and right content margin
+const MARGIN_X: f32 = 25.0;
+/// Baseline of the first line on a page
+const CONTENT_TOP: f32 = 265.0;
+/// Content never goes below this; the footer sits underneath
+const CONTENT_BOTTOM: f32 = 30.0;
+const FOOTER_Y: f32 = 15.0;
+/// Millimetres per typographic point
+const PT_TO_MM: f32 = 0.352_778;
+/// Box the cover logo is scaled to fit, anchored at the top-left margin
+const LOGO_MAX_WIDTH: f32 = 60.0;
+const LOGO_MAX_HEIGHT: f32 = 25.0;
+const LOGO_TOP: f32 = 265.0;
+/// Bar chart columns: wrapped label, bar track, then the value text
+const CHART_LABEL_X: f32 = 30.0;
+const CHART_LABEL_WIDTH: f32 = 55.0;
+const BAR_X: f32 = CHART_LABEL_X + CHART_LABEL_WIDTH + 4.0;
+const BAR_VALUE_WIDTH: f32 = 28.0;
+const BAR_TRACK: f32 = PAGE_WIDTH - MARGIN_X - BAR_VALUE_WIDTH - BAR_X;
+const BAR_HEIGHT: f32 = 4.0;
+/// Height of the compliance trend line's 0-100% band
+const TREND_HEIGHT: f32 = 18.0;
+/// Cover page charts sit in the right-hand column beside their figures
+const COVER_CHART_X: f32 = 165.0;
+const COVER_CHART_RADIUS: f32 = 18.0;
+/// Radius of pie, donut and gauge charts in report bodies
+const CHART_RADIUS: f32 = 20.0;
+/// Contents entries: text size, indent per level below the top, space
+/// kept clear for page numbers and the gap after each entry
+const CONTENTS_SIZE: f32 = 12.0;
+const CONTENTS_INDENT: f32 = 8.0;
+const CONTENTS_NUMBER_WIDTH: f32 = 20.0;
+const CONTENTS_GAP: f32 = 2.0;
+/// Table cell text size and the space kept between columns
+const TABLE_TEXT_SIZE: f32 = 9.0;
+const TABLE_CELL_PADDING: f32 = 3.0;
+/// Risk donut colors, critical to low
+const RISK_COLORS: [&str; 4] = ["#dc2626", "#f97316", "#f59e0b", "#3b82f6"];
+
+/// Result of writing a PDF to disk
+#[derive(Debug, Clone, Copy)]
+pub struct PdfOutput {
+    pub file_size: u64,
+    pub page_count: u32,
+}
+
+/// PDF Generator for executive and generated reports
 pub struct PdfGenerator {
     /// Document title
     title: String,
     /// Primary brand color (hex)
     primary_color: String,
+    /// Cover page logo (PNG or JPEG)
+    logo_path: Option<String>,
+    /// Text shown at the foot of every page
+    footer_text: Option<String>,
+    /// Confidentiality label shown at the foot of every page after the cover
+    classification: Option<String>,
+    /// Number the sections and list them on a contents page after the cover
+    include_toc: bool,
 }
 
+/// Executive report sections in page order
+type SectionDrawer = fn(&PdfGenerator, &mut PageCursor, &ExecutiveReportData);
+
 impl PdfGenerator {
     pub fn new(title: String) -> Self {
         Self {
             title,
-            primary_color: "#3B82F6".to_string(),
+            primary_color: DEFAULT_PRIMARY_COLOR.to_string(),
+            logo_path: None,
+            footer_text: None,
+            classification: None,
+            include_toc: false,
         }
     }
 
@@ -31,17 +104,115 @@ impl PdfGenerator {
         self
     }
 
+    pub fn with_logo(mut self, path: String) -> Self {
+        self.logo_path = Some(path);
+        self
+    }
+
+    pub fn with_footer_text(mut self, text: String) -> Self {
+        self.footer_text = Some(text);
+        self
+    }
+
+    pub fn with_classification(mut self, label: String) -> Self {
+        self.classification = Some(label);
+        self
+    }
+
+    pub fn with_table_of_contents(mut self) -> Self {
+        self.include_toc = true;
+        self
+    }
+
     /// Generate an executive report PDF
     pub fn generate_executive_report(
         &self,
         data: &ExecutiveReportData,
         output_path: &PathBuf,
-    ) -> Result<u64, String> {
+    ) -> Result<PdfOutput, String> {
+        let sections: [(&str, SectionDrawer); 4] = [
+            ("Executive Summary", Self::draw_executive_summary),
+            ("Compliance Status", Self::draw_compliance_status),
+            ("Network Assets", Self::draw_network_assets),
+            ("Recommendations", Self::draw_recommendations),
+        ];
+        let headings: Vec<String> = sections
+            .iter()
+            .enumerate()
+            .map(|(number, (title, _))| {
+                if self.include_toc {
+                    format!("{} {}", number + 1, title)
+                } else {
+                    title.to_string()
+                }
+            })
+            .collect();
+        let contents: Vec<(u8, &str)> = if self.include_toc {
+            headings.iter().map(|h| (1, h.as_str())).collect()
+        } else {
+            vec![]
+        };
+
+        self.write_document(
+            output_path,
+            &contents,
+            |cursor| self.draw_cover_page(cursor, data),
+            |cursor| {
+                let mut pages = Vec::new();
+                for ((title, draw), heading) in sections.iter().zip(&headings) {
+                    cursor.start_page(title);
+                    cursor.heading(heading, 22.0);
+                    cursor.gap(8.0);
+                    pages.push(cursor.page_count);
+                    draw(self, cursor, data);
+                }
+                pages
+            },
+        )
+    }
+
+    /// Render a generated report: a cover page from its metadata, a contents
+    /// page when it has a table of contents, then each top-level section
+    /// starting on a new page
+    pub fn generate_report(&self, content: &ReportContent, output_path: &PathBuf) -> Result<PdfOutput, String> {
+        // Contents list the top two levels; deeper entries would crowd it
+        let toc: Vec<&TocEntry> = content.toc.iter().filter(|e| e.level <= 2).collect();
+        let titles: Vec<String> = toc.iter().map(|e| format!("{} {}", e.number, e.title)).collect();
+        let contents: Vec<(u8, &str)> = toc.iter().zip(&titles).map(|(e, t)| (e.level, t.as_str())).collect();
+
+        self.write_document(
+            output_path,
+            &contents,
+            |cursor| self.draw_content_cover(cursor, &content.metadata),
+            |cursor| {
+                let mut section_pages = HashMap::new();
+                for section in &content.sections {
+                    cursor.start_page(if section.title.is_empty() { "Section" } else { &section.title });
+                    cursor.section(section, &mut section_pages);
+                }
+                toc.iter().map(|e| section_pages.get(e.section_id.as_str()).copied().unwrap_or(0)).collect()
+            },
+        )
+    }
+
+    /// Lay out a document in two passes and save it
+    ///
+    /// Contents pages are reserved after the cover for `contents` (indent
+    /// level and title per entry), `draw_body` lays out the sections and
+    /// returns the page each entry starts on, then the reserved pages are
+    /// filled in and every page after the cover gets a "Page N of M" footer.
+    fn write_document(
+        &self,
+        output_path: &PathBuf,
+        contents: &[(u8, &str)],
+        draw_cover: impl FnOnce(&mut PageCursor),
+        draw_body: impl FnOnce(&mut PageCursor) -> Vec<u32>,
+    ) -> Result<PdfOutput, String> {
         // Create PDF document (Letter size: 215.9mm x 279.4mm)
         let (doc, page1, layer1) = PdfDocument::new(
             &self.title,
-            Mm(215.9),
-            Mm(279.4),
+            Mm(PAGE_WIDTH),
+            Mm(PAGE_HEIGHT),
             "Cover",
         );
 
@@ -50,29 +221,53 @@ impl PdfGenerator {
         let font_regular = doc.add_builtin_font(BuiltinFont::Helvetica)
             .map_err(|e| format!("Failed to add font: {}", e))?;
 
-        // Cover page
-        let cover_layer = doc.get_page(page1).get_layer(layer1);
-        self.draw_cover_page(&cover_layer, &font, &font_regular, data)?;
+        let brand_color = match parse_hex_color(&self.primary_color) {
+            Some(rgb) => rgb_color(rgb),
+            None => {
+                tracing::warn!("Ignoring invalid report color: {}", self.primary_color);
+                rgb_color(parse_hex_color(DEFAULT_PRIMARY_COLOR).unwrap_or_default())
+            }
+        };
 
-        // Executive Summary page
-        let (page2, layer2) = doc.add_page(Mm(215.9), Mm(279.4), "Executive Summary");
-        let summary_layer = doc.get_page(page2).get_layer(layer2);
-        self.draw_executive_summary(&summary_layer, &font, &font_regular, data)?;
+        let layer = doc.get_page(page1).get_layer(layer1);
+        let mut cursor = PageCursor {
+            doc: &doc,
+            font_bold: &font,
+            font_regular: &font_regular,
+            layer: layer.clone(),
+            pages: vec![layer],
+            y: CONTENT_TOP,
+            page_count: 1,
+            section: "Cover".to_string(),
+            brand_color,
+            palette: chart_palette(Some(&self.primary_color)),
+            footer_text: self.footer_text.as_deref(),
+            classification: self.classification.as_ref().map(|label| label.to_uppercase()),
+        };
 
-        // Compliance Status page
-        let (page3, layer3) = doc.add_page(Mm(215.9), Mm(279.4), "Compliance Status");
-        let compliance_layer = doc.get_page(page3).get_layer(layer3);
-        self.draw_compliance_status(&compliance_layer, &font, &font_regular, data)?;
+        draw_cover(&mut cursor);
 
-        // Network & Assets page
-        let (page4, layer4) = doc.add_page(Mm(215.9), Mm(279.4), "Network Assets");
-        let assets_layer = doc.get_page(page4).get_layer(layer4);
-        self.draw_network_assets(&assets_layer, &font, &font_regular, data)?;
+        // Reserve the contents pages; they're filled in once section pages are known
+        let contents_layers: Vec<PdfLayerReference> = (0..contents_page_count(contents))
+            .map(|_| {
+                cursor.start_page("Contents");
+                cursor.layer.clone()
+            })
+            .collect();
 
-        // Recommendations page
-        let (page5, layer5) = doc.add_page(Mm(215.9), Mm(279.4), "Recommendations");
-        let recommendations_layer = doc.get_page(page5).get_layer(layer5);
-        self.draw_recommendations(&recommendations_layer, &font, &font_regular, data)?;
+        let pages = draw_body(&mut cursor);
+
+        if !contents_layers.is_empty() {
+            let entries: Vec<(u8, &str, u32)> = contents
+                .iter()
+                .zip(pages)
+                .map(|(&(level, title), page)| (level, title, page))
+                .collect();
+            cursor.draw_contents(contents_layers, &entries);
+        }
+        cursor.number_pages();
+
+        let page_count = cursor.page_count;
 
         // Save PDF
         let file = File::create(output_path)
@@ -85,162 +280,217 @@ impl PdfGenerator {
         let metadata = std::fs::metadata(output_path)
             .map_err(|e| format!("Failed to get file metadata: {}", e))?;
 
-        Ok(metadata.len())
+        Ok(PdfOutput {
+            file_size: metadata.len(),
+            page_count,
+        })
     }
 
-    fn draw_cover_page(
-        &self,
-        layer: &PdfLayerReference,
-        font_bold: &IndirectFontRef,
-        font_regular: &IndirectFontRef,
-        data: &ExecutiveReportData,
-    ) -> Result<(), String> {
+    /// Cover page of a generated report: title, who it's for and the
+    /// classification banner
+    fn draw_content_cover(&self, cursor: &mut PageCursor, metadata: &ReportMetadata) {
+        if let Some(ref path) = self.logo_path {
+            draw_logo(&cursor.layer, path);
+        }
+
+        cursor.y = 230.0;
+        cursor.heading(&metadata.title, 28.0);
+        if let Some(ref subtitle) = metadata.subtitle {
+            cursor.gap(2.0);
+            cursor.text(subtitle, 16.0, MARGIN_X, false);
+        }
+        cursor.gap(12.0);
+
+        let mut details = vec![format!("Prepared for: {}", metadata.client_name)];
+        if let Some(ref contact) = metadata.client_contact {
+            details.push(format!("Attention: {}", contact));
+        }
+        details.push(format!("Author: {}", metadata.author));
+        if let Some(ref organization) = metadata.organization {
+            details.push(format!("Organization: {}", organization));
+        }
+        details.push(format!("Date: {}", metadata.report_date));
+        details.push(format!("Version: {}", metadata.version));
+        for line in details {
+            cursor.text(&line, 12.0, MARGIN_X, false);
+            cursor.gap(4.0);
+        }
+
+        if let Some(ref classification) = metadata.classification {
+            cursor.banner(&classification.to_uppercase());
+        }
+        cursor.footer();
+    }
+
+    fn draw_cover_page(&self, cursor: &mut PageCursor, data: &ExecutiveReportData) {
+        if let Some(ref path) = self.logo_path {
+            draw_logo(&cursor.layer, path);
+        }
+
         // Title
-        layer.use_text(&data.title, 28.0, Mm(25.0), Mm(230.0), font_bold);
-
-        // Client name
-        layer.use_text(
-            &format!("Prepared for: {}", data.client_name),
-            14.0,
-            Mm(25.0),
-            Mm(200.0),
-            font_regular,
-        );
+        cursor.y = 230.0;
+        cursor.heading(&data.title, 28.0);
+        cursor.gap(12.0);
 
-        // Date
-        layer.use_text(
-            &format!("Date: {}", data.report_date),
-            12.0,
-            Mm(25.0),
-            Mm(185.0),
-            font_regular,
-        );
+        // Client name and date
+        cursor.text(&format!("Prepared for: {}", data.client_name), 14.0, MARGIN_X, false);
+        cursor.gap(4.0);
+        if let Some(ref contact) = data.client_contact {
+            cursor.text(&format!("Attention: {}", contact), 12.0, MARGIN_X, false);
+            cursor.gap(4.0);
+        }
+        cursor.text(&format!("Date: {}", data.report_date), 12.0, MARGIN_X, false);
+        cursor.gap(20.0);
 
         // Key metrics box
-        layer.use_text("Executive Overview", 18.0, Mm(25.0), Mm(150.0), font_bold);
+        cursor.heading("Executive Overview", 18.0);
+        cursor.gap(6.0);
 
-        // Network Health Score
-        layer.use_text(
-            &format!("Network Health Score: {:.0}%", data.network_health_score),
-            14.0,
-            Mm(30.0),
-            Mm(130.0),
-            font_regular,
+        // Health gauge beside the metrics, score printed inside the arc
+        let gauge_center = (COVER_CHART_X, cursor.y - COVER_CHART_RADIUS - 2.0);
+        let health_color = if data.network_health_score >= 80.0 {
+            rgb_color((34, 197, 94))
+        } else if data.network_health_score >= 60.0 {
+            rgb_color((245, 158, 11))
+        } else {
+            rgb_color((239, 68, 68))
+        };
+        pdf_charts::draw_gauge(
+            &cursor.layer,
+            gauge_center,
+            COVER_CHART_RADIUS,
+            COVER_CHART_RADIUS * 0.65,
+            data.network_health_score,
+            health_color,
+        );
+        let score = format!("{:.0}", data.network_health_score);
+        cursor.layer.use_text(
+            score.as_str(),
+            16.0,
+            Mm(gauge_center.0 - text_width(&score, 16.0, true) / 2.0),
+            Mm(gauge_center.1 + 1.0),
+            cursor.font_bold,
+        );
+        let caption = "Network Health";
+        cursor.layer.use_text(
+            caption,
+            9.0,
+            Mm(gauge_center.0 - text_width(caption, 9.0, false) / 2.0),
+            Mm(gauge_center.1 - 5.0),
+            cursor.font_regular,
         );
 
-        // Compliance Score
-        if let Some(ref compliance) = data.compliance_status {
-            layer.use_text(
-                &format!("Compliance Score: {:.1}%", compliance.compliance_percentage),
+        // Network Health Score
+        cursor.text(&format!("Network Health Score: {:.0}%", data.network_health_score), 14.0, 30.0, false);
+        cursor.gap(6.0);
+
+        // Compliance Score, blended with a line per framework when several are assessed
+        if let Some(overview) = data.compliance_overview.as_ref().filter(|o| o.is_multi_framework()) {
+            cursor.text(
+                &format!(
+                    "Overall Compliance: {:.1}% across {} frameworks",
+                    overview.overall_compliance_percentage,
+                    overview.frameworks.len()
+                ),
                 14.0,
-                Mm(30.0),
-                Mm(115.0),
-                font_regular,
+                30.0,
+                false,
             );
+            cursor.gap(5.0);
+            for framework in &overview.frameworks {
+                cursor.text(
+                    &format!(
+                        "{}: {:.1}% ({}/{} controls assessed)",
+                        framework.framework.display_name(),
+                        framework.status.compliance_percentage,
+                        framework.status.assessed_controls,
+                        framework.status.total_controls
+                    ),
+                    10.0,
+                    36.0,
+                    false,
+                );
+                cursor.gap(4.5);
+            }
+            cursor.gap(1.5);
+        } else if let Some(ref compliance) = data.compliance_status {
+            cursor.text(&format!("Compliance Score: {:.1}%", compliance.compliance_percentage), 14.0, 30.0, false);
+            cursor.gap(6.0);
         }
 
         // Total Assets
-        layer.use_text(
-            &format!("Total Assets Discovered: {}", data.total_assets),
-            14.0,
-            Mm(30.0),
-            Mm(100.0),
-            font_regular,
-        );
+        cursor.text(&format!("Total Assets Discovered: {}", data.total_assets), 14.0, 30.0, false);
+        cursor.gap(20.0);
 
-        // Risk Summary
-        layer.use_text("Risk Summary", 18.0, Mm(25.0), Mm(70.0), font_bold);
-        layer.use_text(
-            &format!(
-                "Critical: {} | High: {} | Medium: {} | Low: {}",
-                data.risk_summary.critical_count,
-                data.risk_summary.high_count,
-                data.risk_summary.medium_count,
-                data.risk_summary.low_count
-            ),
-            12.0,
-            Mm(30.0),
-            Mm(55.0),
-            font_regular,
-        );
-        layer.use_text(
-            &format!("Overall Risk Rating: {}", data.risk_summary.overall_risk_rating),
-            12.0,
-            Mm(30.0),
-            Mm(42.0),
-            font_regular,
-        );
+        // Risk Summary: a legend of counts beside the severity donut
+        cursor.heading("Risk Summary", 18.0);
+        cursor.gap(6.0);
 
-        // Classification footer
-        layer.use_text(
-            "CONFIDENTIAL",
-            10.0,
-            Mm(90.0),
-            Mm(15.0),
-            font_bold,
+        let risk = &data.risk_summary;
+        let slices: Vec<(f64, Color)> = [risk.critical_count, risk.high_count, risk.medium_count, risk.low_count]
+            .iter()
+            .zip(RISK_COLORS)
+            .map(|(&count, hex)| (count as f64, hex_color(hex)))
+            .collect();
+        pdf_charts::draw_donut(
+            &cursor.layer,
+            (COVER_CHART_X, cursor.y - COVER_CHART_RADIUS),
+            COVER_CHART_RADIUS,
+            COVER_CHART_RADIUS * 0.55,
+            &slices,
         );
 
-        Ok(())
-    }
+        let labels = ["Critical", "High", "Medium", "Low"];
+        for (row, row_labels) in slices.chunks(2).zip(labels.chunks(2)) {
+            cursor.ensure_space(line_height(12.0));
+            let baseline = cursor.y - 12.0 * PT_TO_MM;
+            for (column, ((count, color), label)) in row.iter().zip(row_labels).enumerate() {
+                let x = 30.0 + column as f32 * 50.0;
+                cursor.swatch(color.clone(), x, 12.0);
+                cursor.layer.use_text(format!("{}: {}", label, count), 12.0, Mm(x + 6.0), Mm(baseline), cursor.font_regular);
+            }
+            cursor.gap(line_height(12.0) + 2.0);
+        }
+        cursor.gap(4.0);
+        cursor.text(&format!("Overall Risk Rating: {}", data.risk_summary.overall_risk_rating), 12.0, 30.0, false);
 
-    fn draw_executive_summary(
-        &self,
-        layer: &PdfLayerReference,
-        font_bold: &IndirectFontRef,
-        font_regular: &IndirectFontRef,
-        data: &ExecutiveReportData,
-    ) -> Result<(), String> {
-        // Header
-        layer.use_text("Executive Summary", 22.0, Mm(25.0), Mm(265.0), font_bold);
+        cursor.banner("CONFIDENTIAL");
+        cursor.footer();
+    }
 
+    fn draw_executive_summary(&self, cursor: &mut PageCursor, data: &ExecutiveReportData) {
         // Introduction
-        layer.use_text("Assessment Overview", 14.0, Mm(25.0), Mm(245.0), font_bold);
-        layer.use_text(
+        cursor.heading("Assessment Overview", 14.0);
+        cursor.gap(2.0);
+        cursor.text(
             &format!(
-                "This assessment was conducted for {} to evaluate the organization's",
+                "This assessment was conducted for {} to evaluate the organization's \
+                cybersecurity posture, compliance status, and network infrastructure.",
                 data.client_name
             ),
             11.0,
-            Mm(25.0),
-            Mm(232.0),
-            font_regular,
-        );
-        layer.use_text(
-            "cybersecurity posture, compliance status, and network infrastructure.",
-            11.0,
-            Mm(25.0),
-            Mm(222.0),
-            font_regular,
+            MARGIN_X,
+            false,
         );
+        cursor.gap(12.0);
 
         // Key Findings
-        layer.use_text("Key Findings", 14.0, Mm(25.0), Mm(200.0), font_bold);
-
-        let mut y_pos = 185.0;
+        cursor.heading("Key Findings", 14.0);
+        cursor.gap(2.0);
         for (i, finding) in data.top_findings.iter().take(5).enumerate() {
-            layer.use_text(
-                &format!("{}. [{}] {}", i + 1, finding.severity, finding.title),
-                10.0,
-                Mm(30.0),
-                Mm(y_pos),
-                font_regular,
-            );
-            y_pos -= 12.0;
+            cursor.text(&format!("{}. [{}] {}", i + 1, finding.severity, finding.title), 10.0, 30.0, false);
+            cursor.gap(3.0);
         }
+        cursor.gap(12.0);
 
         // Metrics summary
-        layer.use_text("Security Metrics", 14.0, Mm(25.0), Mm(100.0), font_bold);
-
-        layer.use_text(
-            &format!("- Network Health Score: {:.0}/100", data.network_health_score),
-            11.0,
-            Mm(30.0),
-            Mm(85.0),
-            font_regular,
-        );
+        cursor.heading("Security Metrics", 14.0);
+        cursor.gap(2.0);
+        cursor.text(&format!("- Network Health Score: {:.0}/100", data.network_health_score), 11.0, 30.0, false);
+        cursor.gap(3.0);
 
         if let Some(ref compliance) = data.compliance_status {
-            layer.use_text(
+            cursor.text(
                 &format!(
                     "- Framework Compliance: {:.1}% ({} of {} controls assessed)",
                     compliance.compliance_percentage,
@@ -248,240 +498,895 @@ impl PdfGenerator {
                     compliance.total_controls
                 ),
                 11.0,
-                Mm(30.0),
-                Mm(72.0),
-                font_regular,
+                30.0,
+                false,
             );
+            cursor.gap(3.0);
         }
 
-        layer.use_text(
+        cursor.text(
             &format!("- Assets Discovered: {} across {} categories",
                 data.total_assets,
                 data.assets_by_category.len()
             ),
             11.0,
-            Mm(30.0),
-            Mm(59.0),
-            font_regular,
+            30.0,
+            false,
         );
+    }
 
-        // Page footer
-        layer.use_text("Page 2", 10.0, Mm(100.0), Mm(15.0), font_regular);
+    fn draw_compliance_status(&self, cursor: &mut PageCursor, data: &ExecutiveReportData) {
+        let compliance = match data.compliance_status {
+            Some(ref c) => c,
+            None => {
+                cursor.text("No compliance assessment data available.", 12.0, MARGIN_X, false);
+                return;
+            }
+        };
 
-        Ok(())
-    }
+        cursor.heading(&format!("Framework: {}", compliance.framework.display_name()), 14.0);
+        cursor.gap(8.0);
 
-    fn draw_compliance_status(
-        &self,
-        layer: &PdfLayerReference,
-        font_bold: &IndirectFontRef,
-        font_regular: &IndirectFontRef,
-        data: &ExecutiveReportData,
-    ) -> Result<(), String> {
-        layer.use_text("Compliance Status", 22.0, Mm(25.0), Mm(265.0), font_bold);
+        // Overall metrics
+        cursor.heading("Overall Compliance Metrics", 12.0);
+        cursor.gap(2.0);
+        let mut lines = vec![
+            format!("Completion: {:.1}%", compliance.completion_percentage),
+            format!("Compliance: {:.1}%", compliance.compliance_percentage),
+            format!("Total Controls: {}", compliance.total_controls),
+        ];
+        if let Some(maturity) = compliance.average_maturity {
+            lines.push(format!("Average Maturity: {:.1} / {}", maturity, MAX_MATURITY_LEVEL));
+        }
+        for line in lines {
+            cursor.text(&line, 11.0, 30.0, false);
+            cursor.gap(3.0);
+        }
+        cursor.text(
+            &format!("Assessed: {} | Compliant: {} | Partial: {} | Non-Compliant: {}",
+                compliance.assessed_controls,
+                compliance.compliant_controls,
+                compliance.partially_compliant_controls,
+                compliance.non_compliant_controls
+            ),
+            10.0,
+            30.0,
+            false,
+        );
+        cursor.gap(12.0);
 
-        if let Some(ref compliance) = data.compliance_status {
-            layer.use_text(
-                &format!("Framework: {}", compliance.framework.display_name()),
-                14.0,
-                Mm(25.0),
-                Mm(245.0),
-                font_bold,
+        // Trend from the client's compliance snapshots; one point is not a trend
+        if data.compliance_trend.len() > 1 {
+            let first = &data.compliance_trend[0];
+            let last = &data.compliance_trend[data.compliance_trend.len() - 1];
+            cursor.heading("Compliance Trend", 12.0);
+            cursor.gap(2.0);
+            let values: Vec<f64> = data.compliance_trend.iter().map(|p| p.compliance_percentage).collect();
+            let color = cursor.brand_color.clone();
+            cursor.trend_line(&values, color);
+            cursor.gap(2.0);
+            cursor.text(
+                &format!(
+                    "{:.1}% on {} to {:.1}% on {} ({} snapshots)",
+                    first.compliance_percentage,
+                    first.taken_at.format("%b %d, %Y"),
+                    last.compliance_percentage,
+                    last.taken_at.format("%b %d, %Y"),
+                    data.compliance_trend.len()
+                ),
+                10.0,
+                30.0,
+                false,
             );
+            cursor.gap(12.0);
+        }
 
-            // Overall metrics
-            layer.use_text("Overall Compliance Metrics", 12.0, Mm(25.0), Mm(225.0), font_bold);
-            layer.use_text(
-                &format!("Completion: {:.1}%", compliance.completion_percentage),
-                11.0,
-                Mm(30.0),
-                Mm(212.0),
-                font_regular,
-            );
-            layer.use_text(
-                &format!("Compliance: {:.1}%", compliance.compliance_percentage),
-                11.0,
-                Mm(30.0),
-                Mm(200.0),
-                font_regular,
-            );
-            layer.use_text(
-                &format!("Total Controls: {}", compliance.total_controls),
-                11.0,
-                Mm(30.0),
-                Mm(188.0),
-                font_regular,
-            );
-            layer.use_text(
-                &format!("Assessed: {} | Compliant: {} | Partial: {} | Non-Compliant: {}",
-                    compliance.assessed_controls,
-                    compliance.compliant_controls,
-                    compliance.partially_compliant_controls,
-                    compliance.non_compliant_controls
+        // Category breakdown (client category groups take precedence when present)
+        let (breakdown_title, breakdown) = if compliance.group_breakdown.is_empty() {
+            ("Compliance by Category", &compliance.category_breakdown)
+        } else {
+            ("Compliance by Category Group", &compliance.group_breakdown)
+        };
+        cursor.heading(breakdown_title, 12.0);
+        cursor.gap(2.0);
+
+        for cat in breakdown {
+            // Fall back to the brand color for categories without a usable color
+            let color = parse_hex_color(&cat.color)
+                .map(rgb_color)
+                .unwrap_or_else(|| cursor.brand_color.clone());
+            // The value column is narrow, so maturity goes with the label
+            let label = match cat.average_maturity {
+                Some(maturity) => format!("{} ({}) - maturity {:.1}", cat.name, cat.code, maturity),
+                None => format!("{} ({})", cat.name, cat.code),
+            };
+            cursor.bar_row(
+                &label,
+                cat.compliance_percentage,
+                &format!(
+                    "{:.1}% ({}/{})",
+                    cat.compliance_percentage,
+                    cat.compliant + cat.partially_compliant,
+                    cat.total_controls
                 ),
-                10.0,
-                Mm(30.0),
-                Mm(176.0),
-                font_regular,
+                color,
             );
+            cursor.gap(3.0);
+        }
+    }
 
-            // Category breakdown
-            layer.use_text("Compliance by Category", 12.0, Mm(25.0), Mm(155.0), font_bold);
+    fn draw_network_assets(&self, cursor: &mut PageCursor, data: &ExecutiveReportData) {
+        cursor.heading(&format!("Total Assets Discovered: {}", data.total_assets), 14.0);
+        cursor.gap(4.0);
+        cursor.text(&format!("Network Health Score: {:.0}%", data.network_health_score), 12.0, MARGIN_X, false);
+        cursor.gap(12.0);
 
-            let mut y_pos = 140.0;
-            for cat in &compliance.category_breakdown {
-                layer.use_text(
-                    &format!(
-                        "{} ({}): {:.1}% compliance ({}/{} controls)",
-                        cat.name,
-                        cat.code,
-                        cat.compliance_percentage,
-                        cat.compliant + cat.partially_compliant,
-                        cat.total_controls
-                    ),
-                    10.0,
-                    Mm(30.0),
-                    Mm(y_pos),
-                    font_regular,
-                );
-                y_pos -= 11.0;
-                if y_pos < 30.0 {
-                    break;
+        // Assets by category
+        cursor.heading("Assets by Category", 12.0);
+        cursor.gap(2.0);
+        for cat in &data.assets_by_category {
+            cursor.text(&format!("{}: {} assets", cat.category, cat.count), 11.0, 30.0, false);
+            cursor.gap(3.0);
+        }
+    }
+
+    fn draw_recommendations(&self, cursor: &mut PageCursor, data: &ExecutiveReportData) {
+        cursor.heading("Strategic Recommendations", 14.0);
+        cursor.gap(4.0);
+
+        for (i, finding) in data.top_findings.iter().enumerate() {
+            // Keep the title, severity and first recommendation line together
+            cursor.ensure_space(line_height(11.0) + line_height(10.0) * 2.0 + 3.0);
+
+            cursor.text(&format!("{}. {}", i + 1, finding.title), 11.0, MARGIN_X, true);
+            cursor.gap(1.0);
+            cursor.text(&format!("Severity: {}", finding.severity), 10.0, 30.0, false);
+            cursor.gap(1.0);
+            cursor.text(&finding.recommendation, 10.0, 30.0, false);
+            cursor.gap(8.0);
+        }
+    }
+}
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+/// Write position within the document
+///
+/// Text is word-wrapped to the right margin and flows onto a new page
+/// whenever it would cross the bottom margin.
+struct PageCursor<'a> {
+    doc: &'a PdfDocumentReference,
+    font_bold: &'a IndirectFontRef,
+    font_regular: &'a IndirectFontRef,
+    layer: PdfLayerReference,
+    /// Every page's layer, cover first, for numbering once the total is known
+    pages: Vec<PdfLayerReference>,
+    /// Baseline of the next line, in mm from the bottom of the page
+    y: f32,
+    page_count: u32,
+    /// Layer name for the current section, reused for continuation pages
+    section: String,
+    /// Heading color
+    brand_color: Color,
+    /// Chart series colors, brand color first
+    palette: Vec<String>,
+    footer_text: Option<&'a str>,
+    /// Upper-cased confidentiality label for the right of the footer
+    classification: Option<String>,
+}
+
+impl PageCursor<'_> {
+    fn start_page(&mut self, section: &str) {
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), section);
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.page_count += 1;
+        self.y = CONTENT_TOP;
+        self.section = section.to_string();
+        self.pages.push(self.layer.clone());
+        self.footer();
+    }
+
+    /// "Page N of M" on every page after the cover
+    fn number_pages(&self) {
+        let total = self.pages.len();
+        for (i, layer) in self.pages.iter().enumerate().skip(1) {
+            let label = format!("Page {} of {}", i + 1, total);
+            let x = (PAGE_WIDTH - text_width(&label, 10.0, false)) / 2.0;
+            layer.use_text(label, 10.0, Mm(x), Mm(FOOTER_Y), self.font_regular);
+        }
+    }
+
+    /// Full-width band across the foot of the page with `label` centered in white
+    fn banner(&mut self, label: &str) {
+        self.layer.set_fill_color(self.brand_color.clone());
+        self.layer.add_rect(Rect::new(Mm(0.0), Mm(FOOTER_Y - 3.0), Mm(PAGE_WIDTH), Mm(FOOTER_Y + 6.0)));
+        self.layer.set_fill_color(rgb_color((255, 255, 255)));
+        self.layer.use_text(
+            label,
+            10.0,
+            Mm((PAGE_WIDTH - text_width(label, 10.0, true)) / 2.0),
+            Mm(FOOTER_Y),
+            self.font_bold,
+        );
+        self.layer.set_fill_color(rgb_color((0, 0, 0)));
+    }
+
+    /// Branding footer text, cut to one line, under the page number, with
+    /// the confidentiality label in the brand color at the right margin
+    fn footer(&self) {
+        let mut width = PAGE_WIDTH - 2.0 * MARGIN_X;
+        if let Some(ref label) = self.classification {
+            let label_width = text_width(label, 8.0, true);
+            self.layer.set_fill_color(self.brand_color.clone());
+            self.layer.use_text(
+                label.as_str(),
+                8.0,
+                Mm(PAGE_WIDTH - MARGIN_X - label_width),
+                Mm(FOOTER_Y - 8.0),
+                self.font_bold,
+            );
+            self.layer.set_fill_color(rgb_color((0, 0, 0)));
+            width -= label_width + 4.0;
+        }
+        let text = match self.footer_text {
+            Some(text) => text,
+            None => return,
+        };
+        if let Some(line) = wrap_text(text, 8.0, width, false).into_iter().next() {
+            self.layer.use_text(line, 8.0, Mm(MARGIN_X), Mm(FOOTER_Y - 8.0), self.font_regular);
+        }
+    }
+
+    /// Fill the reserved contents pages with titles, dot leaders and page numbers
+    ///
+    /// Laid out exactly as `contents_page_count` measured, so the entries
+    /// always fit the reserved pages.
+    fn draw_contents(&mut self, layers: Vec<PdfLayerReference>, entries: &[(u8, &str, u32)]) {
+        let current = std::mem::replace(&mut self.layer, layers[0].clone());
+        let mut layers = layers.into_iter().skip(1);
+        self.y = CONTENT_TOP;
+        self.heading("Table of Contents", 22.0);
+        self.gap(8.0);
+
+        for &(level, title, page) in entries {
+            let lines = contents_lines(level, title);
+            if self.y - contents_entry_height(lines.len()) < CONTENT_BOTTOM {
+                if let Some(layer) = layers.next() {
+                    self.layer = layer;
+                    self.y = CONTENT_TOP;
                 }
             }
-        } else {
-            layer.use_text(
-                "No compliance assessment data available.",
-                12.0,
-                Mm(25.0),
-                Mm(245.0),
-                font_regular,
-            );
+
+            let x = contents_indent(level);
+            let page = page.to_string();
+            let number_x = PAGE_WIDTH - MARGIN_X - text_width(&page, CONTENTS_SIZE, false);
+            for (i, line) in lines.iter().enumerate() {
+                let baseline = self.y - CONTENTS_SIZE * PT_TO_MM;
+                self.layer.use_text(line.as_str(), CONTENTS_SIZE, Mm(x), Mm(baseline), self.font_regular);
+                if i + 1 == lines.len() {
+                    let start = x + text_width(line, CONTENTS_SIZE, false) + 2.0;
+                    let leader = dot_leader(number_x - 2.0 - start);
+                    let leader_x = number_x - 2.0 - text_width(&leader, CONTENTS_SIZE, false);
+                    self.layer.use_text(leader, CONTENTS_SIZE, Mm(leader_x), Mm(baseline), self.font_regular);
+                    self.layer.use_text(page.as_str(), CONTENTS_SIZE, Mm(number_x), Mm(baseline), self.font_regular);
+                }
+                self.y -= line_height(CONTENTS_SIZE);
+            }
+            self.gap(CONTENTS_GAP);
         }
 
-        layer.use_text("Page 3", 10.0, Mm(100.0), Mm(15.0), font_regular);
+        self.layer = current;
+    }
 
-        Ok(())
+    /// Draw a report section and its subsections, noting the page each starts on
+    fn section(&mut self, section: &ReportSection, pages: &mut HashMap<String, u32>) {
+        if !section.title.is_empty() {
+            if section.level > 1 {
+                self.gap(4.0);
+            }
+            self.heading(&section.title, section_heading_size(section.level));
+            self.gap(if section.level <= 1 { 8.0 } else { 3.0 });
+        }
+        pages.insert(section.id.clone(), self.page_count);
+
+        for block in &section.blocks {
+            self.block(block);
+        }
+        for subsection in &section.subsections {
+            self.section(subsection, pages);
+        }
     }
 
-    fn draw_network_assets(
-        &self,
-        layer: &PdfLayerReference,
-        font_bold: &IndirectFontRef,
-        font_regular: &IndirectFontRef,
-        data: &ExecutiveReportData,
-    ) -> Result<(), String> {
-        layer.use_text("Network Assets", 22.0, Mm(25.0), Mm(265.0), font_bold);
-
-        layer.use_text(
-            &format!("Total Assets Discovered: {}", data.total_assets),
-            14.0,
-            Mm(25.0),
-            Mm(245.0),
-            font_bold,
-        );
+    fn block(&mut self, block: &ContentBlock) {
+        match block {
+            ContentBlock::Paragraph { text } => {
+                self.text(text, 11.0, MARGIN_X, false);
+                self.gap(4.0);
+            }
+            ContentBlock::Heading { text, level } => {
+                self.gap(2.0);
+                self.heading(text, section_heading_size(level.saturating_add(1)));
+                self.gap(2.0);
+            }
+            ContentBlock::BulletList { items } => {
+                for item in items {
+                    self.text(&format!("- {}", item), 10.5, 30.0, false);
+                    self.gap(1.5);
+                }
+                self.gap(3.0);
+            }
+            ContentBlock::NumberedList { items } => {
+                for (i, item) in items.iter().enumerate() {
+                    self.text(&format!("{}. {}", i + 1, item), 10.5, 30.0, false);
+                    self.gap(1.5);
+                }
+                self.gap(3.0);
+            }
+            ContentBlock::Table { headers, rows, caption } => {
+                self.table(headers, rows);
+                if let Some(caption) = caption {
+                    self.text(caption, 9.0, MARGIN_X, false);
+                }
+                self.gap(5.0);
+            }
+            ContentBlock::Chart { chart_type, title, data } => self.chart(*chart_type, title, data),
+            ContentBlock::KeyValue { items } => {
+                let rows: Vec<Vec<String>> = items.iter().map(|i| vec![i.key.clone(), i.value.clone()]).collect();
+                self.table(&[], &rows);
+                self.gap(5.0);
+            }
+            ContentBlock::Callout { callout_type, title, text } => {
+                // Info callouts take the brand color, like the HTML export's
+                let color = match callout_type {
+                    CalloutType::Info => self.brand_color.clone(),
+                    _ => callout_color(*callout_type),
+                };
+                let title = title.as_deref().unwrap_or(match callout_type {
+                    CalloutType::Info => "Info",
+                    CalloutType::Note => "Note",
+                    CalloutType::Warning => "Warning",
+                    CalloutType::Critical => "Critical",
+                    CalloutType::Success => "Success",
+                });
+                // Keep the title with the first line of text
+                self.ensure_space(line_height(11.0) + line_height(10.5));
+                self.swatch(color.clone(), MARGIN_X, 11.0);
+                self.draw_text(title, 11.0, MARGIN_X + 6.0, true, Some(color));
+                self.text(text, 10.5, MARGIN_X + 6.0, false);
+                self.gap(5.0);
+            }
+            ContentBlock::Code { content, .. } => {
+                self.text(content, 9.0, 30.0, false);
+                self.gap(4.0);
+            }
+            ContentBlock::Finding { id, title, severity, description, impact, recommendation } => {
+                // Keep the title, severity and first description line together
+                self.ensure_space(line_height(12.0) + line_height(10.0) * 2.0 + 2.0);
+                self.draw_text(&format!("{} - {}", id, title), 12.0, MARGIN_X, true, Some(severity_color(severity)));
+                self.gap(1.0);
+                for (label, value) in [("Severity", severity), ("Description", description), ("Impact", impact), ("Recommendation", recommendation)] {
+                    if !value.is_empty() {
+                        self.text(&format!("{}: {}", label, value), 10.0, 30.0, false);
+                        self.gap(1.0);
+                    }
+                }
+                self.gap(5.0);
+            }
+            ContentBlock::Metric { label, value, change, .. } => {
+                let text = match change {
+                    Some(change) => format!("{}: {} ({})", label, value, change),
+                    None => format!("{}: {}", label, value),
+                };
+                self.text(&text, 12.0, MARGIN_X, true);
+                self.gap(3.0);
+            }
+            ContentBlock::PageBreak => {
+                let section = self.section.clone();
+                self.start_page(&section);
+            }
+            // Markup has no PDF equivalent
+            ContentBlock::RawHtml { .. } => {}
+        }
+    }
 
-        layer.use_text(
-            &format!("Network Health Score: {:.0}%", data.network_health_score),
-            12.0,
-            Mm(25.0),
-            Mm(230.0),
-            font_regular,
-        );
+    /// Table with equal-width columns, each cell word-wrapped within its column
+    fn table(&mut self, headers: &[String], rows: &[Vec<String>]) {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0).max(headers.len());
+        if columns == 0 {
+            return;
+        }
+        let column_width = (PAGE_WIDTH - 2.0 * MARGIN_X) / columns as f32;
+        if !headers.is_empty() {
+            self.table_row(headers, column_width, true);
+        }
+        for row in rows {
+            self.table_row(row, column_width, false);
+        }
+    }
 
-        // Assets by category
-        layer.use_text("Assets by Category", 12.0, Mm(25.0), Mm(210.0), font_bold);
+    /// One table row, drawn a line at a time so a tall row continues on the next page
+    ///
+    /// Bold rows are header rows, drawn in the brand color.
+    fn table_row(&mut self, cells: &[String], column_width: f32, bold: bool) {
+        let size = TABLE_TEXT_SIZE;
+        let wrapped: Vec<Vec<String>> = cells
+            .iter()
+            .map(|cell| wrap_text(cell, size, column_width - TABLE_CELL_PADDING, bold))
+            .collect();
+        let line_count = wrapped.iter().map(Vec::len).max().unwrap_or(1);
+        let font = if bold { self.font_bold } else { self.font_regular };
 
-        let mut y_pos = 195.0;
-        for cat in &data.assets_by_category {
-            layer.use_text(
-                &format!("{}: {} assets", cat.category, cat.count),
-                11.0,
-                Mm(30.0),
-                Mm(y_pos),
-                font_regular,
+        for i in 0..line_count {
+            self.ensure_space(line_height(size));
+            let baseline = self.y - size * PT_TO_MM;
+            if bold {
+                self.layer.set_fill_color(self.brand_color.clone());
+            }
+            for (column, lines) in wrapped.iter().enumerate() {
+                if let Some(line) = lines.get(i) {
+                    let x = MARGIN_X + column as f32 * column_width;
+                    self.layer.use_text(line.as_str(), size, Mm(x), Mm(baseline), font);
+                }
+            }
+            self.layer.set_fill_color(rgb_color((0, 0, 0)));
+            self.y -= line_height(size);
+        }
+
+        // Rule under the row
+        self.gap(1.0);
+        self.layer.set_outline_color(rgb_color((209, 213, 219)));
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN_X), Mm(self.y)), false),
+                (Point::new(Mm(PAGE_WIDTH - MARGIN_X), Mm(self.y)), false),
+            ],
+            is_closed: false,
+        });
+        self.layer.set_outline_color(rgb_color((0, 0, 0)));
+        self.gap(1.5);
+    }
+
+    /// Chart drawn as vector shapes in the report palette
+    ///
+    /// Pie and donut charts get a ring with a legend of values and shares,
+    /// gauges a half-circle arc, line charts a line per series and bar charts
+    /// a bar per label and series. Radar and heatmap charts, and charts with
+    /// nothing to draw, show a table of their figures instead.
+    fn chart(&mut self, chart_type: ChartType, title: &str, data: &ChartData) {
+        if !data.has_values() || matches!(chart_type, ChartType::Radar | ChartType::Heatmap) {
+            self.block(&chart_to_table(Some(title.to_string()), data));
+            return;
+        }
+
+        self.ensure_space(line_height(11.0) + BAR_HEIGHT * 2.0);
+        self.text(title, 11.0, MARGIN_X, true);
+        self.gap(2.0);
+        match chart_type {
+            ChartType::Pie => self.pie_chart(data, 0.0),
+            ChartType::Donut => self.pie_chart(data, 0.55),
+            ChartType::Gauge => self.gauge_chart(data),
+            ChartType::Line => self.line_chart(data),
+            _ => self.bar_chart(data),
+        }
+        self.gap(4.0);
+    }
+
+    /// Ring of the first series in palette order beside a legend; `hole` is
+    /// the inner radius as a fraction of the outer
+    fn pie_chart(&mut self, data: &ChartData, hole: f32) {
+        let values = &first_series(data).data;
+        let rows = data.labels.len().min(values.len());
+        let size = 10.0;
+        let height = (CHART_RADIUS * 2.0).max(line_height(size) * rows as f32);
+        self.ensure_space(height);
+
+        let top = self.y;
+        let slices: Vec<(f64, Color)> = values[..rows]
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| (value, self.palette_color(i)))
+            .collect();
+        let center = (MARGIN_X + CHART_RADIUS, top - CHART_RADIUS);
+        pdf_charts::draw_donut(&self.layer, center, CHART_RADIUS, CHART_RADIUS * hole, &slices);
+
+        let total: f64 = slices.iter().map(|(value, _)| value.max(0.0)).sum();
+        let legend_x = MARGIN_X + CHART_RADIUS * 2.0 + 10.0;
+        for (label, (value, color)) in data.labels.iter().zip(slices) {
+            let share = if total > 0.0 { value.max(0.0) / total * 100.0 } else { 0.0 };
+            self.swatch(color, legend_x, size);
+            let baseline = self.y - size * PT_TO_MM;
+            let line = format!("{}: {} ({:.0}%)", label, chart_value(value), share);
+            self.layer.use_text(line, size, Mm(legend_x + 6.0), Mm(baseline), self.font_regular);
+            self.gap(line_height(size));
+        }
+        self.y = top - height;
+    }
+
+    /// Half-circle gauge of the first value out of 100, value printed inside the arc
+    fn gauge_chart(&mut self, data: &ChartData) {
+        let dataset = first_series(data);
+        let value = dataset.data[0];
+        self.ensure_space(CHART_RADIUS + 8.0);
+
+        let center = (MARGIN_X + CHART_RADIUS, self.y - CHART_RADIUS);
+        let color = self.series_color(dataset, 0);
+        pdf_charts::draw_gauge(&self.layer, center, CHART_RADIUS, CHART_RADIUS * 0.65, value, color);
+        let score = chart_value(value);
+        self.layer.use_text(
+            score.as_str(),
+            16.0,
+            Mm(center.0 - text_width(&score, 16.0, true) / 2.0),
+            Mm(center.1 + 1.0),
+            self.font_bold,
+        );
+        if let Some(label) = data.labels.first() {
+            self.layer.use_text(
+                label.as_str(),
+                9.0,
+                Mm(center.0 - text_width(label, 9.0, false) / 2.0),
+                Mm(center.1 - 5.0),
+                self.font_regular,
             );
-            y_pos -= 12.0;
-            if y_pos < 30.0 {
-                break;
+        }
+        self.y = center.1 - 8.0;
+    }
+
+    /// A line per series over one band, then the label range and a legend
+    fn line_chart(&mut self, data: &ChartData) {
+        let scale = data.scale_max();
+        let scaled = |values: &[f64]| -> Vec<f64> {
+            values.iter().map(|v| if scale > 0.0 { v / scale * 100.0 } else { 0.0 }).collect()
+        };
+        self.ensure_space(TREND_HEIGHT);
+        let (x, bottom, width) = (CHART_LABEL_X, self.y - TREND_HEIGHT, BAR_X + BAR_TRACK - CHART_LABEL_X);
+        for (i, dataset) in data.datasets.iter().enumerate() {
+            let color = self.series_color(dataset, i);
+            let values = scaled(&dataset.data);
+            if i == 0 {
+                pdf_charts::draw_trend_line(&self.layer, x, bottom, width, TREND_HEIGHT, &values, color);
+            } else {
+                pdf_charts::draw_line(&self.layer, &pdf_charts::line_points(&values, x, bottom, width, TREND_HEIGHT), color);
             }
         }
+        self.y = bottom;
+        self.gap(1.0);
 
-        layer.use_text("Page 4", 10.0, Mm(100.0), Mm(15.0), font_regular);
+        if let (Some(first), Some(last)) = (data.labels.first(), data.labels.last()) {
+            let range = if data.labels.len() > 1 { format!("{} to {}", first, last) } else { first.clone() };
+            self.text(&range, 9.0, CHART_LABEL_X, false);
+        }
+        if data.datasets.len() > 1 {
+            self.legend(data);
+        }
+    }
 
-        Ok(())
+    /// A bar per label and series, scaled to `ChartData::scale_max`
+    fn bar_chart(&mut self, data: &ChartData) {
+        let scale = data.scale_max();
+        for (i, label) in data.labels.iter().enumerate() {
+            for (d, dataset) in data.datasets.iter().enumerate() {
+                let value = match dataset.data.get(i) {
+                    Some(value) => *value,
+                    None => continue,
+                };
+                let percentage = if scale > 0.0 { value / scale * 100.0 } else { 0.0 };
+                let color = self.series_color(dataset, d);
+                self.bar_row(if d == 0 { label } else { "" }, percentage, &chart_value(value), color);
+                self.gap(1.0);
+            }
+            self.gap(1.0);
+        }
+        if data.datasets.len() > 1 {
+            self.legend(data);
+        }
     }
 
-    fn draw_recommendations(
-        &self,
-        layer: &PdfLayerReference,
-        font_bold: &IndirectFontRef,
-        font_regular: &IndirectFontRef,
-        data: &ExecutiveReportData,
-    ) -> Result<(), String> {
-        layer.use_text("Recommendations", 22.0, Mm(25.0), Mm(265.0), font_bold);
+    /// Swatch and label per series, one per line
+    fn legend(&mut self, data: &ChartData) {
+        for (i, dataset) in data.datasets.iter().enumerate() {
+            let color = self.series_color(dataset, i);
+            self.swatch(color, CHART_LABEL_X, 9.0);
+            let baseline = self.y - 9.0 * PT_TO_MM;
+            self.layer.use_text(dataset.label.as_str(), 9.0, Mm(CHART_LABEL_X + 5.0), Mm(baseline), self.font_regular);
+            self.gap(line_height(9.0));
+        }
+    }
 
-        layer.use_text("Strategic Recommendations", 14.0, Mm(25.0), Mm(245.0), font_bold);
+    /// A series' own color, else its place in the report palette
+    fn series_color(&self, dataset: &ChartDataset, index: usize) -> Color {
+        hex_color(&dataset.color_or(&self.palette, index))
+    }
 
-        let mut y_pos = 228.0;
-        for (i, finding) in data.top_findings.iter().enumerate() {
-            if y_pos < 50.0 {
-                break;
+    fn palette_color(&self, index: usize) -> Color {
+        hex_color(&self.palette[index % self.palette.len()])
+    }
+
+    /// Start a new page unless `height` mm still fits above the bottom margin
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < CONTENT_BOTTOM {
+            let section = self.section.clone();
+            self.start_page(&section);
+        }
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Bold heading in the brand color, kept on the same page as the line that follows it
+    fn heading(&mut self, text: &str, size: f32) {
+        self.ensure_space(line_height(size) + line_height(11.0));
+        let color = self.brand_color.clone();
+        self.draw_text(text, size, MARGIN_X, true, Some(color));
+    }
+
+    /// Draw word-wrapped text starting at `x`, advancing past the last line
+    fn text(&mut self, text: &str, size: f32, x: f32, bold: bool) {
+        self.draw_text(text, size, x, bold, None);
+    }
+
+    /// Colored square sized to sit on the next line of `size` text at `x`
+    fn swatch(&mut self, color: Color, x: f32, size: f32) {
+        let side = size * PT_TO_MM * 0.75;
+        self.ensure_space(line_height(size));
+        let bottom = self.y - size * PT_TO_MM;
+        self.layer.set_fill_color(color);
+        self.layer.add_rect(Rect::new(Mm(x), Mm(bottom), Mm(x + side), Mm(bottom + side)));
+        self.layer.set_fill_color(rgb_color((0, 0, 0)));
+    }
+
+    /// Bar chart row: label wrapped within its own column, then the bar and value
+    ///
+    /// Long labels wrap rather than run into the bar, and the row grows to fit them.
+    fn bar_row(&mut self, label: &str, percentage: f64, value: &str, color: Color) {
+        let size = 10.0;
+        let lines = wrap_text(label, size, CHART_LABEL_WIDTH, false);
+        let row_height = (line_height(size) * lines.len() as f32).max(BAR_HEIGHT);
+        self.ensure_space(row_height);
+
+        let top = self.y;
+        let first_baseline = top - size * PT_TO_MM;
+        for (i, line) in lines.into_iter().enumerate() {
+            let baseline = first_baseline - line_height(size) * i as f32;
+            self.layer.use_text(line, size, Mm(CHART_LABEL_X), Mm(baseline), self.font_regular);
+        }
+
+        // Bar lines up with the first label line
+        let bar_bottom = top - (line_height(size) + BAR_HEIGHT) / 2.0;
+        pdf_charts::draw_bar(&self.layer, BAR_X, bar_bottom, BAR_TRACK, BAR_HEIGHT, percentage, color);
+        self.layer.use_text(value, 9.0, Mm(BAR_X + BAR_TRACK + 2.0), Mm(first_baseline), self.font_regular);
+
+        self.y = top - row_height;
+    }
+
+    /// Small line chart of percentages spanning the bar column
+    fn trend_line(&mut self, values: &[f64], color: Color) {
+        self.ensure_space(TREND_HEIGHT);
+        pdf_charts::draw_trend_line(&self.layer, CHART_LABEL_X, self.y - TREND_HEIGHT, BAR_X + BAR_TRACK - CHART_LABEL_X, TREND_HEIGHT, values, color);
+        self.y -= TREND_HEIGHT;
+    }
+
+    fn draw_text(&mut self, text: &str, size: f32, x: f32, bold: bool, color: Option<Color>) {
+        let max_width = PAGE_WIDTH - MARGIN_X - x;
+        let height = line_height(size);
+
+        for line in wrap_text(text, size, max_width, bold) {
+            self.ensure_space(height);
+            // Baseline sits below the top of the line box
+            let baseline = self.y - size * PT_TO_MM;
+            let font = if bold { self.font_bold } else { self.font_regular };
+            // Set per line: a page break starts a fresh layer in black
+            if let Some(ref color) = color {
+                self.layer.set_fill_color(color.clone());
+            }
+            self.layer.use_text(line, size, Mm(x), Mm(baseline), font);
+            if color.is_some() {
+                self.layer.set_fill_color(rgb_color((0, 0, 0)));
             }
+            self.y -= height;
+        }
+    }
+}
 
-            layer.use_text(
-                &format!("{}. {}", i + 1, finding.title),
-                11.0,
-                Mm(25.0),
-                Mm(y_pos),
-                font_bold,
-            );
-            y_pos -= 12.0;
+/// Color for a palette entry; entries are validated hex, so black is only a fallback
+fn hex_color(hex: &str) -> Color {
+    rgb_color(parse_hex_color(hex).unwrap_or_default())
+}
 
-            layer.use_text(
-                &format!("Severity: {}", finding.severity),
-                10.0,
-                Mm(30.0),
-                Mm(y_pos),
-                font_regular,
-            );
-            y_pos -= 10.0;
+/// The first series with values; `ChartData::has_values` guarantees one
+fn first_series(data: &ChartData) -> &ChartDataset {
+    data.datasets.iter().find(|d| !d.data.is_empty()).unwrap_or(&data.datasets[0])
+}
 
-            // Wrap recommendation text
-            let rec_text = &finding.recommendation;
-            if rec_text.len() > 80 {
-                layer.use_text(
-                    &rec_text[..80],
-                    10.0,
-                    Mm(30.0),
-                    Mm(y_pos),
-                    font_regular,
-                );
-                y_pos -= 10.0;
-                if rec_text.len() > 80 {
-                    layer.use_text(
-                        &rec_text[80..rec_text.len().min(160)],
-                        10.0,
-                        Mm(30.0),
-                        Mm(y_pos),
-                        font_regular,
-                    );
-                }
+fn rgb_color((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb(Rgb::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, None))
+}
+
+/// Place the logo in the top-left corner of the cover page
+///
+/// A logo that can't be read or decoded is skipped with a warning so the
+/// export still succeeds.
+fn draw_logo(layer: &PdfLayerReference, path: &str) {
+    let logo = match Logo::load(path) {
+        Some(logo) => logo,
+        None => return,
+    };
+    let format = match logo.format {
+        LogoFormat::Png => image_crate::ImageFormat::Png,
+        LogoFormat::Jpeg => image_crate::ImageFormat::Jpeg,
+    };
+    let decoded = match image_crate::load_from_memory_with_format(&logo.bytes, format) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            tracing::warn!("Skipping report logo {}: {}", path, e);
+            return;
+        }
+    };
+
+    let image = Image::from_dynamic_image(&decoded);
+    // Size in mm at printpdf's default 300 dpi
+    let width = image.image.width.0 as f32 / 300.0 * 25.4;
+    let height = image.image.height.0 as f32 / 300.0 * 25.4;
+    if width <= 0.0 || height <= 0.0 {
+        return;
+    }
+    let scale = (LOGO_MAX_WIDTH / width).min(LOGO_MAX_HEIGHT / height);
+
+    image.add_to_layer(layer.clone(), ImageTransform {
+        translate_x: Some(Mm(MARGIN_X)),
+        translate_y: Some(Mm(LOGO_TOP - height * scale)),
+        scale_x: Some(scale),
+        scale_y: Some(scale),
+        ..Default::default()
+    });
+}
+
+fn line_height(size: f32) -> f32 {
+    size * PT_TO_MM * 1.4
+}
+
+fn section_heading_size(level: u8) -> f32 {
+    match level {
+        0 | 1 => 22.0,
+        2 => 16.0,
+        _ => 13.0,
+    }
+}
+
+fn callout_color(callout_type: CalloutType) -> Color {
+    rgb_color(match callout_type {
+        CalloutType::Info => (37, 99, 235),
+        CalloutType::Warning => (217, 119, 6),
+        CalloutType::Critical => (220, 38, 38),
+        CalloutType::Success => (22, 163, 74),
+        CalloutType::Note => (75, 85, 99),
+    })
+}
+
+fn severity_color(severity: &str) -> Color {
+    rgb_color(match severity.to_lowercase().as_str() {
+        "critical" => (220, 38, 38),
+        "high" => (234, 88, 12),
+        "medium" => (217, 119, 6),
+        "low" => (37, 99, 235),
+        _ => (75, 85, 99),
+    })
+}
+
+fn contents_indent(level: u8) -> f32 {
+    MARGIN_X + CONTENTS_INDENT * level.saturating_sub(1) as f32
+}
+
+/// A contents entry's title wrapped clear of the page number column
+fn contents_lines(level: u8, title: &str) -> Vec<String> {
+    let width = PAGE_WIDTH - MARGIN_X - CONTENTS_NUMBER_WIDTH - contents_indent(level);
+    wrap_text(title, CONTENTS_SIZE, width, false)
+}
+
+fn contents_entry_height(lines: usize) -> f32 {
+    line_height(CONTENTS_SIZE) * lines as f32 + CONTENTS_GAP
+}
+
+/// Pages needed for the contents entries (none without entries)
+///
+/// Measures the same layout `PageCursor::draw_contents` draws, so the pages
+/// can be reserved before the sections they point to are laid out.
+fn contents_page_count(entries: &[(u8, &str)]) -> usize {
+    if entries.is_empty() {
+        return 0;
+    }
+    let mut pages = 1;
+    let mut y = CONTENT_TOP - line_height(22.0) - 8.0;
+    for &(level, title) in entries {
+        let height = contents_entry_height(contents_lines(level, title).len());
+        if y - height < CONTENT_BOTTOM {
+            pages += 1;
+            y = CONTENT_TOP;
+        }
+        y -= height;
+    }
+    pages
+}
+
+/// Dots spaced to fill at most `width` mm between a title and its page number
+fn dot_leader(width: f32) -> String {
+    let dot = text_width(" .", CONTENTS_SIZE, false);
+    let count = if width > 0.0 { (width / dot) as usize } else { 0 };
+    " .".repeat(count)
+}
+
+/// Rendered width of `text` in mm using the builtin Helvetica metrics
+fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+    let units: u32 = text.chars().map(|c| helvetica_advance(c, bold) as u32).sum();
+    units as f32 / 1000.0 * size * PT_TO_MM
+}
+
+/// Split `text` into lines no wider than `max_width` mm
+///
+/// Breaks at whitespace; a single word wider than the line is split between
+/// characters so it never overflows the page.
+fn wrap_text(text: &str, size: f32, max_width: f32, bold: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
             } else {
-                layer.use_text(
-                    rec_text,
-                    10.0,
-                    Mm(30.0),
-                    Mm(y_pos),
-                    font_regular,
-                );
+                format!("{} {}", current, word)
+            };
+            if text_width(&candidate, size, bold) <= max_width {
+                current = candidate;
+                continue;
+            }
+
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            for ch in word.chars() {
+                current.push(ch);
+                if text_width(&current, size, bold) > max_width && current.chars().count() > 1 {
+                    current.pop();
+                    lines.push(std::mem::replace(&mut current, ch.to_string()));
+                }
             }
-            y_pos -= 18.0;
         }
+        lines.push(current);
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
 
-        layer.use_text("Page 5", 10.0, Mm(100.0), Mm(15.0), font_regular);
+/// Advance width of `c` in 1/1000 em, from the Adobe Helvetica AFM files
+fn helvetica_advance(c: char, bold: bool) -> u16 {
+    const REGULAR: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+        1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+        333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+        556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+    ];
+    const BOLD: [u16; 95] = [
+        278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+        975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+        333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+        611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+    ];
 
-        Ok(())
+    let table = if bold { &BOLD } else { &REGULAR };
+    match c as u32 {
+        code @ 32..=126 => table[(code - 32) as usize],
+        // Not in the ASCII tables: assume the width of a typical glyph
+        _ => 556,
     }
 }
 
@@ -489,11 +1394,12 @@ impl PdfGenerator {
 pub fn generate_demo_executive_report(
     client_name: &str,
     output_path: &PathBuf,
-) -> Result<u64, String> {
+) -> Result<PdfOutput, String> {
     use crate::grc::models::{
         AssetCategoryCount, CategoryComplianceStatus, ExecutiveFinding,
         Framework, RiskSummary,
     };
+    use crate::grc::trend::ComplianceTrendPoint;
 
     let compliance_status = ComplianceStatusReport {
         framework: Framework::NistCsf2,
@@ -518,6 +1424,8 @@ pub fn generate_demo_executive_report(
                 non_compliant: 0,
                 completion_percentage: 75.0,
                 compliance_percentage: 83.3,
+                average_maturity: Some(2.7),
+                maturity_rated: 3,
             },
             CategoryComplianceStatus {
                 code: "ID".to_string(),
@@ -531,6 +1439,8 @@ pub fn generate_demo_executive_report(
                 non_compliant: 0,
                 completion_percentage: 80.0,
                 compliance_percentage: 87.5,
+                average_maturity: Some(3.0),
+                maturity_rated: 4,
             },
             CategoryComplianceStatus {
                 code: "PR".to_string(),
@@ -544,6 +1454,8 @@ pub fn generate_demo_executive_report(
                 non_compliant: 1,
                 completion_percentage: 71.4,
                 compliance_percentage: 70.0,
+                average_maturity: Some(2.4),
+                maturity_rated: 5,
             },
             CategoryComplianceStatus {
                 code: "DE".to_string(),
@@ -557,6 +1469,8 @@ pub fn generate_demo_executive_report(
                 non_compliant: 0,
                 completion_percentage: 75.0,
                 compliance_percentage: 83.3,
+                average_maturity: Some(2.7),
+                maturity_rated: 3,
             },
             CategoryComplianceStatus {
                 code: "RS".to_string(),
@@ -570,6 +1484,8 @@ pub fn generate_demo_executive_report(
                 non_compliant: 0,
                 completion_percentage: 66.7,
                 compliance_percentage: 100.0,
+                average_maturity: Some(3.5),
+                maturity_rated: 2,
             },
             CategoryComplianceStatus {
                 code: "RC".to_string(),
@@ -583,8 +1499,12 @@ pub fn generate_demo_executive_report(
                 non_compliant: 1,
                 completion_percentage: 100.0,
                 compliance_percentage: 66.7,
+                average_maturity: Some(2.0),
+                maturity_rated: 3,
             },
         ],
+        group_breakdown: vec![],
+        average_maturity: Some(2.7),
         network_health_score: Some(78.0),
         total_assets: Some(247),
         last_updated: chrono::Utc::now().to_rfc3339(),
@@ -592,9 +1512,19 @@ pub fn generate_demo_executive_report(
 
     let data = ExecutiveReportData {
         client_name: client_name.to_string(),
+        client_contact: None,
         title: format!("Security Assessment Report - {}", client_name),
         report_date: chrono::Utc::now().format("%B %d, %Y").to_string(),
         compliance_status: Some(compliance_status),
+        compliance_overview: None,
+        compliance_trend: [(270, 61.8, 54.0), (180, 68.5, 71.0), (90, 76.2, 88.0), (0, 82.5, 100.0)]
+            .into_iter()
+            .map(|(days_ago, compliance, completion)| ComplianceTrendPoint {
+                taken_at: chrono::Utc::now() - chrono::Duration::days(days_ago),
+                completion_percentage: completion,
+                compliance_percentage: compliance,
+            })
+            .collect(),
         network_health_score: 78.0,
         total_assets: 247,
         assets_by_category: vec![
@@ -646,3 +1576,243 @@ pub fn generate_demo_executive_report(
     let generator = PdfGenerator::new(data.title.clone());
     generator.generate_executive_report(&data, output_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grc::models::{ExecutiveFinding, RiskSummary};
+
+    #[test]
+    fn test_wrap_text() {
+        let lines = wrap_text("Disable TLS 1.0 and 1.1 on every externally reachable service", 10.0, 50.0, false);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| text_width(l, 10.0, false) <= 50.0));
+
+        // Multi-byte text and unbroken words are split on char boundaries
+        let lines = wrap_text(&"Überprüfung".repeat(20), 10.0, 40.0, true);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.concat(), "Überprüfung".repeat(20));
+    }
+
+    #[test]
+    fn test_many_findings_paginate() {
+        let data = ExecutiveReportData {
+            client_name: "Acme".to_string(),
+            client_contact: Some("Jane Doe, CISO (jane@acme.example)".to_string()),
+            title: "Security Assessment Report - Acme".to_string(),
+            report_date: "January 01, 2026".to_string(),
+            compliance_status: None,
+            compliance_overview: None,
+            compliance_trend: vec![],
+            network_health_score: 70.0,
+            total_assets: 0,
+            assets_by_category: vec![],
+            top_findings: (1..=40)
+                .map(|i| ExecutiveFinding {
+                    id: format!("FIND-{:03}", i),
+                    title: format!("Finding {} – service exposed on a very long list of externally reachable hosts", i),
+                    severity: "High".to_string(),
+                    description: "Exposed service".to_string(),
+                    recommendation: "Restrict access to the service with host firewalls and network ACLs, \
+                        require authentication, and remove it entirely where the business no longer needs it."
+                        .to_string(),
+                })
+                .collect(),
+            risk_summary: RiskSummary {
+                critical_count: 0,
+                high_count: 40,
+                medium_count: 0,
+                low_count: 0,
+                overall_risk_rating: "High".to_string(),
+            },
+        };
+
+        let path = std::env::temp_dir().join(format!("optio-pdf-test-{}.pdf", uuid::Uuid::new_v4()));
+        let output = PdfGenerator::new(data.title.clone())
+            .generate_executive_report(&data, &path)
+            .unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(output.page_count > 5, "got {} pages", output.page_count);
+        assert!(output.file_size > 0);
+    }
+
+    #[test]
+    fn test_demo_report_charts() {
+        // Long category names wrap inside the label column, clear of the bars
+        let name = "Supply Chain Risk Management and Third-Party Oversight (GV.SC)";
+        let lines = wrap_text(name, 10.0, CHART_LABEL_WIDTH, false);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| CHART_LABEL_X + text_width(l, 10.0, false) < BAR_X));
+
+        let path = std::env::temp_dir().join(format!("optio-pdf-charts-{}.pdf", uuid::Uuid::new_v4()));
+        let output = generate_demo_executive_report("Acme", &path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(output.page_count, 5, "got {} pages", output.page_count);
+    }
+
+    fn sample_data() -> ExecutiveReportData {
+        ExecutiveReportData {
+            client_name: "Acme".to_string(),
+            client_contact: Some("Jane Doe, CISO (jane@acme.example)".to_string()),
+            title: "Branded".to_string(),
+            report_date: "January 01, 2026".to_string(),
+            compliance_status: None,
+            compliance_overview: None,
+            compliance_trend: vec![],
+            network_health_score: 70.0,
+            total_assets: 0,
+            assets_by_category: vec![],
+            top_findings: vec![],
+            risk_summary: RiskSummary {
+                critical_count: 0,
+                high_count: 0,
+                medium_count: 0,
+                low_count: 0,
+                overall_risk_rating: "Low".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_table_of_contents_page() {
+        let data = sample_data();
+        let path = std::env::temp_dir().join(format!("optio-pdf-toc-{}.pdf", uuid::Uuid::new_v4()));
+        let output = PdfGenerator::new(data.title.clone())
+            .with_table_of_contents()
+            .generate_executive_report(&data, &path)
+            .unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(output.page_count, 6, "got {} pages", output.page_count);
+    }
+
+    #[test]
+    fn test_generated_report_layout() {
+        use crate::reporting::models::KeyValueItem;
+
+        // Long contents lists spill onto further reserved pages
+        let titles: Vec<String> = (1..=60).map(|i| format!("{} Section {}", i, i)).collect();
+        let entries: Vec<(u8, &str)> = titles.iter().map(|t| (1, t.as_str())).collect();
+        assert_eq!(contents_page_count(&[]), 0);
+        assert_eq!(contents_page_count(&entries[..5]), 1);
+        assert!(contents_page_count(&entries) > 1);
+
+        // Leaders stop short of the page number column
+        assert!(text_width(&dot_leader(40.0), CONTENTS_SIZE, false) <= 40.0);
+        assert!(dot_leader(-5.0).is_empty());
+
+        let section = |id: &str, title: &str, level: u8, blocks: Vec<ContentBlock>, subsections: Vec<ReportSection>| ReportSection {
+            id: id.to_string(),
+            title: title.to_string(),
+            level,
+            blocks,
+            subsections,
+        };
+        let hosts = ContentBlock::Table {
+            headers: vec!["Host".to_string(), "Service".to_string(), "Notes".to_string()],
+            rows: (0..120)
+                .map(|i| vec![format!("10.0.0.{}", i), "ssh".to_string(), "Überprüfung erforderlich ".repeat(3)])
+                .collect(),
+            caption: Some("Hosts in scope".to_string()),
+        };
+        let notes = vec![
+            ContentBlock::Callout { callout_type: CalloutType::Warning, title: None, text: "Review before release.".to_string() },
+            ContentBlock::KeyValue { items: vec![KeyValueItem { key: "Owner".to_string(), value: "IT".to_string() }] },
+            ContentBlock::Finding {
+                id: "FIND-001".to_string(),
+                title: "SMBv1 Enabled".to_string(),
+                severity: "High".to_string(),
+                description: "Legacy protocol enabled.".to_string(),
+                impact: String::new(),
+                recommendation: "Disable SMBv1.".to_string(),
+            },
+        ];
+        let summary = vec![
+            ContentBlock::Chart {
+                chart_type: ChartType::Donut,
+                title: "Findings by Severity".to_string(),
+                data: ChartData {
+                    labels: vec!["High".to_string(), "Low".to_string()],
+                    datasets: vec![crate::reporting::models::ChartDataset { label: "Findings".to_string(), data: vec![3.0, 1.0], color: None }],
+                },
+            },
+            ContentBlock::Metric { label: "Open Findings".to_string(), value: "4".to_string(), change: None, trend: None },
+        ];
+        let toc_entry = |number: &str, title: &str, section_id: &str, level: u8| TocEntry {
+            number: number.to_string(),
+            title: title.to_string(),
+            section_id: section_id.to_string(),
+            level,
+        };
+        let content = ReportContent {
+            sections: vec![
+                section("hosts", "1 Hosts", 1, vec![ContentBlock::Paragraph { text: "Hosts reviewed.".to_string() }, hosts], vec![
+                    section("notes", "1.1 Notes", 2, notes, vec![]),
+                ]),
+                section("summary", "2 Summary", 1, summary, vec![]),
+            ],
+            metadata: ReportMetadata {
+                title: "Network Assessment".to_string(),
+                subtitle: Some("Internal network".to_string()),
+                author: "Assessor".to_string(),
+                organization: None,
+                client_name: "Acme".to_string(),
+                client_contact: None,
+                report_date: "January 01, 2026".to_string(),
+                classification: Some("Confidential".to_string()),
+                version: "1.0".to_string(),
+                page_count: None,
+                logo_path: None,
+                primary_color: None,
+                secondary_color: None,
+                footer_text: None,
+            },
+            toc: vec![
+                toc_entry("1", "Hosts", "hosts", 1),
+                toc_entry("1.1", "Notes", "notes", 2),
+                toc_entry("2", "Summary", "summary", 1),
+            ],
+            anchors: vec![],
+        };
+
+        let path = std::env::temp_dir().join(format!("optio-pdf-content-{}.pdf", uuid::Uuid::new_v4()));
+        let output = PdfGenerator::new("Network Assessment".to_string())
+            .generate_report(&content, &path)
+            .unwrap();
+        std::fs::remove_file(&path).ok();
+
+        // Cover, contents, the host table over several pages, then the summary
+        assert!(output.page_count > 5, "got {} pages", output.page_count);
+        assert!(output.file_size > 0);
+    }
+
+    #[test]
+    fn test_branding_degrades_gracefully() {
+        let data = sample_data();
+
+        let dir = std::env::temp_dir().join(format!("optio-pdf-brand-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let logo = dir.join("logo.png");
+        image_crate::RgbImage::from_pixel(120, 40, image_crate::Rgb([15, 118, 110]))
+            .save(&logo)
+            .unwrap();
+        let corrupt = dir.join("corrupt.png");
+        std::fs::write(&corrupt, b"\x89PNG\r\n\x1a\nnot really").unwrap();
+
+        let render = |logo: &std::path::Path, color: &str| {
+            PdfGenerator::new(data.title.clone())
+                .with_primary_color(color.to_string())
+                .with_logo(logo.to_string_lossy().to_string())
+                .with_footer_text("Northwind Security | Confidential".to_string())
+                .generate_executive_report(&data, &dir.join("report.pdf"))
+                .unwrap()
+        };
+
+        let branded = render(&logo, "#0F766E");
+        let missing = render(&dir.join("missing.png"), "#0F766E");
+        render(&corrupt, "not-a-color");
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert!(branded.file_size > missing.file_size);
+    }
+}
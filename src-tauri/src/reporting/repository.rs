@@ -0,0 +1,354 @@
+//! Report Repository
+//!
+//! Database persistence for generated reports. Summary columns are stored
+//! alongside the serialized config and content so list views never have to
+//! deserialize report bodies.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::reporting::models::*;
+use rusqlite::params;
+
+/// Initialize reporting database schema
+pub fn init_reporting_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    conn.execute_batch(r#"
+        -- Generated reports (config and content stored as JSON)
+        CREATE TABLE IF NOT EXISTS reports (
+            id TEXT PRIMARY KEY,
+            client_id TEXT NOT NULL,
+            report_type TEXT NOT NULL,
+            title TEXT NOT NULL,
+            client_name TEXT NOT NULL,
+            status TEXT NOT NULL,
+            format TEXT NOT NULL,
+            config TEXT NOT NULL,
+            content TEXT,
+            file_path TEXT,
+            file_size INTEGER,
+            error TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_reports_client ON reports(client_id);
+        CREATE INDEX IF NOT EXISTS idx_reports_created ON reports(created_at);
+
+        -- User-written sections kept with their report (blocks stored as JSON)
+        CREATE TABLE IF NOT EXISTS report_custom_sections (
+            id TEXT PRIMARY KEY,
+            report_id TEXT NOT NULL,
+            title TEXT NOT NULL,
+            placement TEXT NOT NULL,
+            anchor_section_id TEXT NOT NULL,
+            blocks TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (report_id) REFERENCES reports(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_report_custom_sections_report ON report_custom_sections(report_id);
+    "#)?;
+
+    tracing::info!("Reporting schema initialized");
+    Ok(())
+}
+
+/// Report repository
+pub struct ReportRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> ReportRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        ReportRepository { db }
+    }
+
+    /// Insert or replace a report
+    pub fn save(&self, report: &Report) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let content = report.content.as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        conn.execute(
+            r#"INSERT INTO reports
+               (id, client_id, report_type, title, client_name, status, format, config,
+                content, file_path, file_size, error, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+               ON CONFLICT(id) DO UPDATE SET
+                   report_type = excluded.report_type,
+                   title = excluded.title,
+                   client_name = excluded.client_name,
+                   status = excluded.status,
+                   format = excluded.format,
+                   config = excluded.config,
+                   content = excluded.content,
+                   file_path = excluded.file_path,
+                   file_size = excluded.file_size,
+                   error = excluded.error,
+                   updated_at = excluded.updated_at"#,
+            params![
+                report.id,
+                report.client_id,
+                format!("{:?}", report.config.report_type),
+                report.config.title,
+                report.config.client_name,
+                format!("{:?}", report.status),
+                format!("{:?}", report.config.format),
+                serde_json::to_string(&report.config)?,
+                content,
+                report.file_path,
+                report.file_size.map(|s| s as i64),
+                report.error,
+                report.created_at,
+                report.updated_at,
+            ],
+        )?;
+
+        tracing::debug!("Saved report: {}", report.id);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<Report>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            r#"SELECT id, client_id, status, config, content, file_path, file_size,
+                      error, created_at, updated_at
+               FROM reports WHERE id = ?1"#
+        )?;
+
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(parse_report_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List report summaries, newest first, without loading report bodies
+    pub fn list_summaries(&self, client_id: Option<&str>) -> OptioResult<Vec<ReportSummary>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            r#"SELECT id, title, report_type, client_name, status, format, created_at, file_size
+               FROM reports WHERE ?1 IS NULL OR client_id = ?1
+               ORDER BY created_at DESC"#
+        )?;
+
+        let summaries = stmt.query_map(params![client_id], |row| {
+            Ok(parse_summary_row(row))
+        })?
+        .filter_map(|r| r.ok())
+        .collect::<OptioResult<Vec<_>>>()?;
+
+        Ok(summaries)
+    }
+
+    /// Record the file a report was exported to
+    pub fn set_file(&self, id: &str, file_path: &str, file_size: u64) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let updated = conn.execute(
+            "UPDATE reports SET file_path = ?2, file_size = ?3, updated_at = ?4 WHERE id = ?1",
+            params![id, file_path, file_size as i64, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(updated > 0)
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM reports WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+}
+
+/// Custom section repository
+pub struct CustomSectionRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> CustomSectionRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        CustomSectionRepository { db }
+    }
+
+    pub fn create(&self, section: &CustomSection) -> OptioResult<()> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        conn.execute(
+            r#"INSERT INTO report_custom_sections
+               (id, report_id, title, placement, anchor_section_id, blocks, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+            params![
+                section.id,
+                section.report_id,
+                section.title,
+                placement_str(section.placement),
+                section.anchor_section_id,
+                serde_json::to_string(&section.blocks)?,
+                section.created_at,
+                section.updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Replace a custom section's title, placement and blocks
+    pub fn update(&self, section: &CustomSection) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let updated = conn.execute(
+            r#"UPDATE report_custom_sections
+               SET title = ?2, placement = ?3, anchor_section_id = ?4, blocks = ?5, updated_at = ?6
+               WHERE id = ?1"#,
+            params![
+                section.id,
+                section.title,
+                placement_str(section.placement),
+                section.anchor_section_id,
+                serde_json::to_string(&section.blocks)?,
+                section.updated_at,
+            ],
+        )?;
+        Ok(updated > 0)
+    }
+
+    pub fn get(&self, id: &str) -> OptioResult<Option<CustomSection>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            r#"SELECT id, report_id, title, placement, anchor_section_id, blocks, created_at, updated_at
+               FROM report_custom_sections WHERE id = ?1"#
+        )?;
+        let mut rows = stmt.query(params![id])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(parse_custom_section_row(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// A report's custom sections in the order they were added
+    pub fn list_by_report(&self, report_id: &str) -> OptioResult<Vec<CustomSection>> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            r#"SELECT id, report_id, title, placement, anchor_section_id, blocks, created_at, updated_at
+               FROM report_custom_sections WHERE report_id = ?1
+               ORDER BY created_at, rowid"#
+        )?;
+        let mut rows = stmt.query(params![report_id])?;
+        let mut sections = Vec::new();
+        while let Some(row) = rows.next()? {
+            sections.push(parse_custom_section_row(row)?);
+        }
+        Ok(sections)
+    }
+
+    pub fn delete(&self, id: &str) -> OptioResult<bool> {
+        let conn = self.db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        let deleted = conn.execute("DELETE FROM report_custom_sections WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+}
+
+// Helper functions for parsing rows
+
+fn parse_report_row(row: &rusqlite::Row) -> OptioResult<Report> {
+    let status_str: String = row.get(2)?;
+    let config_json: String = row.get(3)?;
+    let content_json: Option<String> = row.get(4)?;
+
+    Ok(Report {
+        id: row.get(0)?,
+        client_id: row.get(1)?,
+        config: serde_json::from_str(&config_json)?,
+        status: parse_report_status(&status_str)?,
+        content: content_json.map(|c| serde_json::from_str(&c)).transpose()?,
+        file_path: row.get(5)?,
+        file_size: row.get::<_, Option<i64>>(6)?.map(|s| s as u64),
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+        error: row.get(7)?,
+        warnings: vec![],
+    })
+}
+
+fn parse_summary_row(row: &rusqlite::Row) -> OptioResult<ReportSummary> {
+    let type_str: String = row.get(2)?;
+    let status_str: String = row.get(4)?;
+    let format_str: String = row.get(5)?;
+
+    Ok(ReportSummary {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        report_type: parse_report_type(&type_str)?,
+        client_name: row.get(3)?,
+        status: parse_report_status(&status_str)?,
+        format: parse_export_format(&format_str)?,
+        created_at: row.get(6)?,
+        file_size: row.get::<_, Option<i64>>(7)?.map(|s| s as u64),
+    })
+}
+
+fn parse_report_type(s: &str) -> OptioResult<ReportType> {
+    match s {
+        "ExecutiveSummary" => Ok(ReportType::ExecutiveSummary),
+        "TechnicalAssessment" => Ok(ReportType::TechnicalAssessment),
+        "ComplianceReport" => Ok(ReportType::ComplianceReport),
+        "NetworkAssessment" => Ok(ReportType::NetworkAssessment),
+        "CloudReadiness" => Ok(ReportType::CloudReadiness),
+        "SecurityFindings" => Ok(ReportType::SecurityFindings),
+        "FullEngagement" => Ok(ReportType::FullEngagement),
+        _ => Err(OptioError::Database(format!("Unknown report type: {}", s))),
+    }
+}
+
+fn parse_report_status(s: &str) -> OptioResult<ReportStatus> {
+    match s {
+        "Draft" => Ok(ReportStatus::Draft),
+        "Generating" => Ok(ReportStatus::Generating),
+        "Ready" => Ok(ReportStatus::Ready),
+        "Failed" => Ok(ReportStatus::Failed),
+        "Archived" => Ok(ReportStatus::Archived),
+        _ => Err(OptioError::Database(format!("Unknown report status: {}", s))),
+    }
+}
+
+fn parse_export_format(s: &str) -> OptioResult<ExportFormat> {
+    match s {
+        "Pdf" => Ok(ExportFormat::Pdf),
+        "Html" => Ok(ExportFormat::Html),
+        "Markdown" => Ok(ExportFormat::Markdown),
+        "Docx" => Ok(ExportFormat::Docx),
+        "Json" => Ok(ExportFormat::Json),
+        "Csv" => Ok(ExportFormat::Csv),
+        _ => Err(OptioError::Database(format!("Unknown export format: {}", s))),
+    }
+}
+
+fn placement_str(placement: SectionPlacement) -> &'static str {
+    match placement {
+        SectionPlacement::Before => "before",
+        SectionPlacement::After => "after",
+    }
+}
+
+fn parse_custom_section_row(row: &rusqlite::Row) -> OptioResult<CustomSection> {
+    let placement: String = row.get(3)?;
+    let blocks_json: String = row.get(5)?;
+
+    Ok(CustomSection {
+        id: row.get(0)?,
+        report_id: row.get(1)?,
+        title: row.get(2)?,
+        placement: match placement.as_str() {
+            "before" => SectionPlacement::Before,
+            "after" => SectionPlacement::After,
+            _ => return Err(OptioError::Database(format!("Unknown section placement: {}", placement))),
+        },
+        anchor_section_id: row.get(4)?,
+        blocks: serde_json::from_str(&blocks_json)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
@@ -0,0 +1,295 @@
+//! SVG Charts
+//!
+//! Report charts as inline SVG for HTML exports, so an exported report is
+//! self-contained and renders without scripts. Pie, donut and gauge shapes
+//! reuse the PDF chart geometry, flipped from its bottom-up coordinates into
+//! SVG's top-down ones; everything else is laid out top-down directly.
+
+use super::generator::{chart_value, escape_html};
+use super::models::{ChartData, ChartDataset, ChartType};
+use super::pdf_charts::{bar_length, donut_segments, gauge_sweep, line_points, ring_slice};
+
+/// Width of every chart's viewBox; heights follow the content
+const WIDTH: f32 = 480.0;
+const TRACK_COLOR: &str = "#E5E7EB";
+const TEXT_COLOR: &str = "#374151";
+/// Pie and donut radius, and the donut's hole as a fraction of it
+const PIE_RADIUS: f32 = 80.0;
+const DONUT_HOLE: f32 = 0.55;
+/// Height of a legend row
+const LEGEND_ROW: f32 = 22.0;
+/// Bar chart columns: label, bar track, then the value
+const BAR_LABEL_WIDTH: f32 = 150.0;
+const BAR_TRACK: f32 = 240.0;
+const BAR_HEIGHT: f32 = 14.0;
+/// Longest bar label, in characters, before it's shortened
+const BAR_LABEL_CHARS: usize = 24;
+/// Gauge arc radii
+const GAUGE_OUTER: f32 = 90.0;
+const GAUGE_INNER: f32 = 60.0;
+/// Line chart plot area
+const PLOT_X: f32 = 40.0;
+const PLOT_TOP: f32 = 10.0;
+const PLOT_HEIGHT: f32 = 120.0;
+
+/// A chart as inline SVG
+///
+/// None for chart types shown as a data table instead (radar, heatmap) and
+/// for charts without values.
+pub fn chart_svg(chart_type: ChartType, title: &str, data: &ChartData, palette: &[String]) -> Option<String> {
+    if !data.has_values() || palette.is_empty() {
+        return None;
+    }
+    let (height, body) = match chart_type {
+        ChartType::Pie => pie(data, palette, 0.0),
+        ChartType::Donut => pie(data, palette, PIE_RADIUS * DONUT_HOLE),
+        ChartType::Bar => bars(data, palette),
+        ChartType::Gauge => gauge(data, palette),
+        ChartType::Line => line(data, palette),
+        ChartType::Radar | ChartType::Heatmap => return None,
+    };
+    Some(format!(
+        "<svg class=\"chart-svg\" xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" role=\"img\" \
+         aria-label=\"{}\" font-family=\"sans-serif\" font-size=\"12\" fill=\"{}\">\n{}</svg>\n",
+        WIDTH,
+        height,
+        escape_html(title),
+        TEXT_COLOR,
+        body
+    ))
+}
+
+/// The first series with values; `ChartData::has_values` guarantees one
+fn first_series(data: &ChartData) -> &ChartDataset {
+    data.datasets.iter().find(|d| !d.data.is_empty()).unwrap_or(&data.datasets[0])
+}
+
+/// Slices of the first series in palette order, beside a legend of values and shares
+fn pie(data: &ChartData, palette: &[String], hole: f32) -> (f32, String) {
+    let values = &first_series(data).data;
+    let rows = data.labels.len().min(values.len());
+    let height = (PIE_RADIUS * 2.0 + 20.0).max(LEGEND_ROW * rows as f32 + 20.0);
+    let center = (PIE_RADIUS + 10.0, height / 2.0);
+
+    let mut svg = String::new();
+    let segments = donut_segments(&values[..rows]);
+    if segments.is_empty() {
+        svg.push_str(&polygon(&ring_slice(center, PIE_RADIUS, hole, 90.0, 360.0), TRACK_COLOR, height));
+    }
+    for segment in &segments {
+        let points = ring_slice(center, PIE_RADIUS, hole, segment.start, segment.sweep);
+        svg.push_str(&polygon(&points, &palette[segment.index % palette.len()], height));
+    }
+
+    let total: f64 = values[..rows].iter().filter(|v| **v > 0.0).sum();
+    let legend_x = PIE_RADIUS * 2.0 + 40.0;
+    let legend_top = (height - LEGEND_ROW * rows as f32) / 2.0;
+    for (i, (label, &value)) in data.labels.iter().zip(values).enumerate() {
+        let y = legend_top + LEGEND_ROW * i as f32;
+        let share = if total > 0.0 { value.max(0.0) / total * 100.0 } else { 0.0 };
+        svg.push_str(&rect(legend_x, y + 2.0, 12.0, 12.0, &palette[i % palette.len()]));
+        svg.push_str(&text(
+            legend_x + 18.0,
+            y + 12.0,
+            &format!("{}: {} ({:.0}%)", label, chart_value(value), share),
+            12.0,
+            "start",
+        ));
+    }
+    (height, svg)
+}
+
+/// A row per label with a bar per series, scaled to `ChartData::scale_max`
+fn bars(data: &ChartData, palette: &[String]) -> (f32, String) {
+    let series = data.datasets.len();
+    let legend = if series > 1 { LEGEND_ROW } else { 0.0 };
+    let row = BAR_HEIGHT * series as f32 + 10.0;
+    let height = legend + row * data.labels.len() as f32;
+    let scale = data.scale_max();
+
+    let mut svg = if series > 1 { legend_row(data, palette, 0.0) } else { String::new() };
+    for (i, label) in data.labels.iter().enumerate() {
+        let top = legend + row * i as f32 + 5.0;
+        svg.push_str(&text(0.0, top + BAR_HEIGHT * series as f32 / 2.0 + 4.0, &shorten(label, BAR_LABEL_CHARS), 12.0, "start"));
+        for (d, dataset) in data.datasets.iter().enumerate() {
+            let value = match dataset.data.get(i) {
+                Some(value) => *value,
+                None => continue,
+            };
+            let y = top + BAR_HEIGHT * d as f32;
+            let length = if scale > 0.0 { bar_length(value / scale * 100.0, BAR_TRACK) } else { 0.0 };
+            svg.push_str(&rect(BAR_LABEL_WIDTH, y, BAR_TRACK, BAR_HEIGHT - 2.0, TRACK_COLOR));
+            if length > 0.0 {
+                svg.push_str(&rect(BAR_LABEL_WIDTH, y, length, BAR_HEIGHT - 2.0, &dataset.color_or(palette, d)));
+            }
+            svg.push_str(&text(BAR_LABEL_WIDTH + BAR_TRACK + 8.0, y + BAR_HEIGHT - 3.0, &chart_value(value), 11.0, "start"));
+        }
+    }
+    (height, svg)
+}
+
+/// Half-circle gauge of the first value out of 100, with the value inside the arc
+fn gauge(data: &ChartData, palette: &[String]) -> (f32, String) {
+    let dataset = first_series(data);
+    let value = dataset.data[0];
+    let height = 140.0;
+    // Bottom-up, like the PDF geometry
+    let center = (WIDTH / 2.0, 30.0);
+
+    let mut svg = polygon(&ring_slice(center, GAUGE_OUTER, GAUGE_INNER, 180.0, 180.0), TRACK_COLOR, height);
+    let sweep = gauge_sweep(value);
+    if sweep > 0.0 {
+        let points = ring_slice(center, GAUGE_OUTER, GAUGE_INNER, 180.0, sweep);
+        svg.push_str(&polygon(&points, &dataset.color_or(palette, 0), height));
+    }
+    svg.push_str(&text(WIDTH / 2.0, height - center.1 - 4.0, &chart_value(value), 26.0, "middle"));
+    if let Some(label) = data.labels.first() {
+        svg.push_str(&text(WIDTH / 2.0, height - 8.0, label, 12.0, "middle"));
+    }
+    (height, svg)
+}
+
+/// A line per series over a shaded band, with the first and last labels under it
+fn line(data: &ChartData, palette: &[String]) -> (f32, String) {
+    let series = data.datasets.len();
+    let legend = if series > 1 { LEGEND_ROW } else { 0.0 };
+    let height = PLOT_TOP + PLOT_HEIGHT + 24.0 + legend;
+    let width = WIDTH - PLOT_X - 20.0;
+    let scale = data.scale_max();
+
+    let mut svg = rect(PLOT_X, PLOT_TOP, width, PLOT_HEIGHT, "#F3F4F6");
+    svg.push_str(&text(PLOT_X - 6.0, PLOT_TOP + 8.0, &chart_value(scale), 10.0, "end"));
+    svg.push_str(&text(PLOT_X - 6.0, PLOT_TOP + PLOT_HEIGHT, "0", 10.0, "end"));
+
+    let bottom = height - PLOT_TOP - PLOT_HEIGHT;
+    for (d, dataset) in data.datasets.iter().enumerate() {
+        let scaled: Vec<f64> = dataset
+            .data
+            .iter()
+            .map(|v| if scale > 0.0 { v / scale * 100.0 } else { 0.0 })
+            .collect();
+        let points: Vec<String> = line_points(&scaled, PLOT_X, bottom, width, PLOT_HEIGHT)
+            .iter()
+            .map(|&(x, y)| format!("{:.1},{:.1}", x, height - y))
+            .collect();
+        if !points.is_empty() {
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+                points.join(" "),
+                dataset.color_or(palette, d)
+            ));
+        }
+    }
+
+    let labels_y = PLOT_TOP + PLOT_HEIGHT + 16.0;
+    if let Some(first) = data.labels.first() {
+        svg.push_str(&text(PLOT_X, labels_y, first, 10.0, "start"));
+    }
+    if data.labels.len() > 1 {
+        svg.push_str(&text(PLOT_X + width, labels_y, &data.labels[data.labels.len() - 1], 10.0, "end"));
+    }
+    if series > 1 {
+        svg.push_str(&legend_row(data, palette, height - LEGEND_ROW));
+    }
+    (height, svg)
+}
+
+/// Swatch and label per series along one row starting at `top`
+fn legend_row(data: &ChartData, palette: &[String], top: f32) -> String {
+    let mut svg = String::new();
+    let mut x = 0.0;
+    for (d, dataset) in data.datasets.iter().enumerate() {
+        svg.push_str(&rect(x, top + 4.0, 12.0, 12.0, &dataset.color_or(palette, d)));
+        svg.push_str(&text(x + 16.0, top + 14.0, &dataset.label, 12.0, "start"));
+        x += 16.0 + dataset.label.chars().count() as f32 * 7.0 + 16.0;
+    }
+    svg
+}
+
+/// Filled polygon through points given bottom-up, as the PDF geometry returns them
+fn polygon(points: &[(f32, f32)], fill: &str, height: f32) -> String {
+    let path: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y))| format!("{}{:.1} {:.1}", if i == 0 { "M" } else { "L" }, x, height - y))
+        .collect();
+    format!("<path d=\"{} Z\" fill=\"{}\"/>\n", path.join(" "), fill)
+}
+
+fn rect(x: f32, y: f32, width: f32, height: f32, fill: &str) -> String {
+    format!(
+        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+        x, y, width, height, fill
+    )
+}
+
+fn text(x: f32, y: f32, content: &str, size: f32, anchor: &str) -> String {
+    format!(
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{}\" text-anchor=\"{}\">{}</text>\n",
+        x,
+        y,
+        size,
+        anchor,
+        escape_html(content)
+    )
+}
+
+/// `label` cut to `max` characters, ending in an ellipsis when shortened
+fn shorten(label: &str, max: usize) -> String {
+    if label.chars().count() <= max {
+        return label.to_string();
+    }
+    let mut short: String = label.chars().take(max - 1).collect();
+    short.push('…');
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporting::branding::chart_palette;
+
+    fn data(labels: &[&str], series: &[(&str, &[f64], Option<&str>)]) -> ChartData {
+        ChartData {
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            datasets: series
+                .iter()
+                .map(|(label, values, color)| ChartDataset {
+                    label: label.to_string(),
+                    data: values.to_vec(),
+                    color: color.map(str::to_string),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_chart_svg() {
+        let palette = chart_palette(Some("#0F766E"));
+
+        // A slice per non-zero value in palette order, with shares in the legend
+        let severity = data(&["Critical", "High", "Low"], &[("Findings", &[1.0, 3.0, 0.0], None)]);
+        let pie = chart_svg(ChartType::Donut, "Findings by Severity", &severity, &palette).unwrap();
+        assert!(pie.starts_with("<svg class=\"chart-svg\""));
+        assert_eq!(pie.matches("<path").count(), 2);
+        assert!(pie.contains("fill=\"#0F766E\""));
+        assert!(pie.contains(">High: 3 (75%)</text>"));
+
+        // Dataset colors win over the palette; percentages scale to 100
+        let compliance = data(&["Govern", "Protect"], &[("Compliance %", &[50.0, 100.0], Some("#22c55e"))]);
+        let bars = chart_svg(ChartType::Bar, "Compliance", &compliance, &palette).unwrap();
+        assert!(bars.contains(&format!("width=\"{:.1}\" height=\"12.0\" fill=\"#22C55E\"", BAR_TRACK / 2.0)));
+        assert!(!bars.contains("#0F766E"));
+
+        let score = data(&["Compliance"], &[("Score", &[78.5], None)]);
+        let gauge = chart_svg(ChartType::Gauge, "Score", &score, &palette).unwrap();
+        assert_eq!(gauge.matches("<path").count(), 2);
+        assert!(gauge.contains(">78.5</text>"));
+
+        // Text is escaped; radar charts and empty charts fall back to tables
+        let hostile = data(&["<script>"], &[("Count", &[1.0], None)]);
+        assert!(!chart_svg(ChartType::Bar, "\"><b>", &hostile, &palette).unwrap().contains("<script>"));
+        assert!(chart_svg(ChartType::Radar, "Readiness", &score, &palette).is_none());
+        assert!(chart_svg(ChartType::Pie, "Empty", &data(&[], &[]), &palette).is_none());
+    }
+}
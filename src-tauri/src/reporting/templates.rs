@@ -31,6 +31,123 @@ pub fn get_template_for_type(report_type: ReportType) -> ReportTemplate {
     }
 }
 
+/// Check a section selection against a template
+///
+/// Every id must name one of the template's sections, and every required
+/// section must be selected.
+pub fn validate_section_selection(template: &ReportTemplate, selected: &[String]) -> Result<(), String> {
+    if let Some(unknown) = selected.iter().find(|id| !template.sections.iter().any(|s| &s.id == *id)) {
+        return Err(unknown_section(template, unknown));
+    }
+
+    if let Some(missing) = template.sections.iter().find(|s| s.required && !selected.contains(&s.id)) {
+        return Err(format!(
+            "The {} section is required in {} reports and can't be removed",
+            missing.title, template.name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Resolve per-section choices into the selected section ids, in template order
+///
+/// Sections without a choice keep their default. Unknown ids and excluded
+/// required sections are rejected.
+pub fn resolve_section_selection(template: &ReportTemplate, choices: &[SectionSelection]) -> Result<Vec<String>, String> {
+    for choice in choices {
+        let section = template
+            .sections
+            .iter()
+            .find(|s| s.id == choice.id)
+            .ok_or_else(|| unknown_section(template, &choice.id))?;
+        if section.required && !choice.included {
+            return Err(format!(
+                "The {} section is required in {} reports and can't be removed",
+                section.title, template.name
+            ));
+        }
+    }
+
+    Ok(template
+        .sections
+        .iter()
+        .filter(|section| {
+            section.required
+                || choices
+                    .iter()
+                    .rev()
+                    .find(|choice| choice.id == section.id)
+                    .map_or(section.default_included, |choice| choice.included)
+        })
+        .map(|section| section.id.clone())
+        .collect())
+}
+
+/// Check a custom section's title, anchor and blocks against the template
+/// of the report it belongs to
+pub fn validate_custom_section(
+    template: &ReportTemplate,
+    title: &str,
+    anchor_section_id: &str,
+    blocks: &[ContentBlock],
+) -> Result<(), String> {
+    if title.trim().is_empty() {
+        return Err("Custom sections need a title".to_string());
+    }
+    if !template.sections.iter().any(|s| s.id == anchor_section_id) {
+        return Err(unknown_section(template, anchor_section_id));
+    }
+
+    let unsupported = blocks.iter().find_map(|block| match block {
+        ContentBlock::Paragraph { .. }
+        | ContentBlock::BulletList { .. }
+        | ContentBlock::Table { .. }
+        | ContentBlock::Callout { .. }
+        | ContentBlock::Code { .. } => None,
+        ContentBlock::Heading { .. } => Some("headings"),
+        ContentBlock::NumberedList { .. } => Some("numbered lists"),
+        ContentBlock::Chart { .. } => Some("charts"),
+        ContentBlock::KeyValue { .. } => Some("key/value lists"),
+        ContentBlock::Finding { .. } => Some("findings"),
+        ContentBlock::Metric { .. } => Some("metrics"),
+        ContentBlock::PageBreak => Some("page breaks"),
+        ContentBlock::RawHtml { .. } => Some("raw HTML"),
+    });
+    match unsupported {
+        Some(kind) => Err(format!(
+            "Custom sections can contain paragraphs, bullet lists, tables, callouts and code, not {}",
+            kind
+        )),
+        None => Ok(()),
+    }
+}
+
+fn unknown_section(template: &ReportTemplate, section_id: &str) -> String {
+    let valid: Vec<&str> = template.sections.iter().map(|s| s.id.as_str()).collect();
+    format!(
+        "Unknown section \"{}\" for the {} template; valid sections are: {}",
+        section_id,
+        template.name,
+        valid.join(", ")
+    )
+}
+
+/// Whether a report includes the section with this id
+///
+/// Without a selection the template's defaults apply. Ids that aren't
+/// sections of this template are always included, so report types built
+/// from other types' sections only filter on their own.
+pub fn section_included(template: &ReportTemplate, section_id: &str, selected: Option<&[String]>) -> bool {
+    match template.sections.iter().find(|s| s.id == section_id) {
+        Some(section) => match selected {
+            Some(selected) => section.required || selected.iter().any(|id| id == section_id),
+            None => section.default_included,
+        },
+        None => true,
+    }
+}
+
 fn get_executive_summary_template() -> ReportTemplate {
     ReportTemplate {
         id: "exec-summary-v1".to_string(),
@@ -121,6 +238,13 @@ fn get_technical_assessment_template() -> ReportTemplate {
                 required: true,
                 default_included: true,
             },
+            TemplateSectionDef {
+                id: "k8s-hardening".to_string(),
+                title: "Kubernetes Hardening".to_string(),
+                description: "Results of a Kubernetes hardening audit, when one is selected".to_string(),
+                required: false,
+                default_included: true,
+            },
             TemplateSectionDef {
                 id: "remediation".to_string(),
                 title: "Remediation Roadmap".to_string(),
@@ -397,6 +521,13 @@ fn get_security_findings_template() -> ReportTemplate {
                 required: true,
                 default_included: true,
             },
+            TemplateSectionDef {
+                id: "remediation-progress".to_string(),
+                title: "Remediation Progress".to_string(),
+                description: "Verification scan results for previously reported findings".to_string(),
+                required: false,
+                default_included: true,
+            },
         ],
         default_config: ReportConfig {
             report_type: ReportType::SecurityFindings,
@@ -579,6 +710,12 @@ pub fn get_export_formats() -> Vec<ExportFormatInfo> {
             description: "Structured JSON data".to_string(),
             icon: "file-json".to_string(),
         },
+        ExportFormatInfo {
+            format: ExportFormat::Csv,
+            name: "CSV".to_string(),
+            description: "Tables and findings as spreadsheet rows".to_string(),
+            icon: "file-spreadsheet".to_string(),
+        },
     ]
 }
 
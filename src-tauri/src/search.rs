@@ -0,0 +1,606 @@
+//! Global Search
+//!
+//! One search across everything recorded for an engagement: clients,
+//! assets, control assessments, evidence and reports, plus the framework
+//! control catalog. Database records are indexed in an FTS5 table kept
+//! current by triggers on their tables; when SQLite was built without FTS5
+//! the same records are searched with LIKE instead. Every hit is re-scored
+//! the same way, so results from the index and the catalog rank together,
+//! and carries a snippet of about 80 characters around the first match.
+
+use crate::db::Database;
+use crate::error::{OptioError, OptioResult};
+use crate::grc::frameworks::{find_term, fold_case, get_framework_controls};
+use crate::grc::models::Framework;
+use rusqlite::{params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Characters of context in a snippet
+const SNIPPET_WIDTH: usize = 80;
+
+/// Candidate rows read from the database per search
+const CANDIDATE_LIMIT: usize = 500;
+
+/// Hits returned when no limit is given
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// Kind of record a search hit points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchEntityType {
+    Client,
+    Asset,
+    /// A framework control from the catalog
+    Control,
+    /// Notes and gap description recorded against a control
+    ControlAssessment,
+    Evidence,
+    Report,
+}
+
+impl SearchEntityType {
+    fn code(&self) -> &'static str {
+        match self {
+            SearchEntityType::Client => "CLIENT",
+            SearchEntityType::Asset => "ASSET",
+            SearchEntityType::Control => "CONTROL",
+            SearchEntityType::ControlAssessment => "CONTROL_ASSESSMENT",
+            SearchEntityType::Evidence => "EVIDENCE",
+            SearchEntityType::Report => "REPORT",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        INDEXED_SOURCES
+            .iter()
+            .map(|s| s.entity)
+            .chain([SearchEntityType::Control])
+            .find(|e| e.code() == code)
+    }
+}
+
+/// Matched text within a snippet, as character offsets (end exclusive)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetHighlight {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A record matching a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub entity_type: SearchEntityType,
+    pub id: String,
+    pub title: String,
+    /// Text around the first match, with "…" where it was cut
+    pub snippet: String,
+    pub highlights: Vec<SnippetHighlight>,
+    /// None for catalog controls
+    pub client_id: Option<String>,
+    /// Relevance; higher is better
+    pub score: u32,
+}
+
+/// A table whose rows are searchable
+///
+/// The SQL expressions use `{r}` for the row, so the same source builds the
+/// index triggers (`NEW.`), the index rebuild and the LIKE fallback (`t.`).
+struct IndexedSource {
+    entity: SearchEntityType,
+    table: &'static str,
+    title: &'static str,
+    body: &'static str,
+    client_id: &'static str,
+}
+
+const INDEXED_SOURCES: [IndexedSource; 5] = [
+    IndexedSource {
+        entity: SearchEntityType::Client,
+        table: "clients",
+        title: "{r}name",
+        body: "COALESCE({r}notes, '') || ' ' || COALESCE({r}industry, '') || ' ' || COALESCE({r}target_subnet, '')",
+        client_id: "{r}id",
+    },
+    IndexedSource {
+        entity: SearchEntityType::Asset,
+        table: "assets",
+        title: "{r}name || ' (' || {r}ip_address || ')'",
+        body: "COALESCE({r}description, '') || ' ' || COALESCE({r}operating_system, '') || ' ' || \
+               COALESCE({r}owner, '') || ' ' || REPLACE(REPLACE(REPLACE({r}tags, '[', ''), ']', ''), '\"', '')",
+        client_id: "{r}client_id",
+    },
+    IndexedSource {
+        entity: SearchEntityType::ControlAssessment,
+        table: "control_assessments",
+        title: "{r}control_id",
+        body: "COALESCE({r}notes, '') || ' ' || COALESCE({r}gap_description, '')",
+        client_id: "(SELECT client_id FROM assessments WHERE id = {r}assessment_id)",
+    },
+    IndexedSource {
+        entity: SearchEntityType::Evidence,
+        table: "evidence",
+        title: "{r}title",
+        body: "COALESCE({r}description, '') || ' ' || COALESCE({r}notes, '')",
+        client_id: "(SELECT client_id FROM assessments WHERE id = {r}assessment_id)",
+    },
+    IndexedSource {
+        entity: SearchEntityType::Report,
+        table: "reports",
+        title: "{r}title",
+        body: "{r}client_name",
+        client_id: "{r}client_id",
+    },
+];
+
+impl IndexedSource {
+    fn columns(&self, row: &str) -> (String, String, String) {
+        (
+            self.title.replace("{r}", row),
+            self.body.replace("{r}", row),
+            self.client_id.replace("{r}", row),
+        )
+    }
+
+    /// Statements adding a row to the index (replacing any earlier entry)
+    fn index_row_sql(&self, row: &str) -> String {
+        let (title, body, client_id) = self.columns(row);
+        format!(
+            "DELETE FROM search_index WHERE entity_type = '{entity}' AND entity_id = {row}id;
+             INSERT INTO search_index (entity_type, entity_id, client_id, title, body)
+             VALUES ('{entity}', {row}id, {client_id}, {title}, {body});",
+            entity = self.entity.code(),
+        )
+    }
+
+    fn triggers_sql(&self) -> String {
+        let name = format!("search_index_{}", self.table);
+        format!(
+            "CREATE TRIGGER IF NOT EXISTS {name}_insert AFTER INSERT ON {table} BEGIN {insert} END;
+             CREATE TRIGGER IF NOT EXISTS {name}_update AFTER UPDATE ON {table} BEGIN {insert} END;
+             CREATE TRIGGER IF NOT EXISTS {name}_delete AFTER DELETE ON {table} BEGIN
+                 DELETE FROM search_index WHERE entity_type = '{entity}' AND entity_id = OLD.id;
+             END;",
+            table = self.table,
+            insert = self.index_row_sql("NEW."),
+            entity = self.entity.code(),
+        )
+    }
+
+    fn rebuild_sql(&self) -> String {
+        let (title, body, client_id) = self.columns("t.");
+        format!(
+            "INSERT INTO search_index (entity_type, entity_id, client_id, title, body)
+             SELECT '{}', t.id, {}, {}, {} FROM {} t;",
+            self.entity.code(),
+            client_id,
+            title,
+            body,
+            self.table,
+        )
+    }
+}
+
+/// Initialize the search index
+///
+/// Run after every indexed table exists. The index is filled from the
+/// existing records when first created. Without FTS5 nothing is created and
+/// searches fall back to LIKE.
+pub fn init_search_schema(db: &Database) -> OptioResult<()> {
+    let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+
+    let existed = has_search_index(&conn)?;
+    if let Err(e) = conn.execute_batch(
+        r#"
+        -- Full-text index over searchable records, maintained by triggers
+        CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+            entity_type UNINDEXED,
+            entity_id UNINDEXED,
+            client_id UNINDEXED,
+            title,
+            body,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );
+    "#,
+    ) {
+        tracing::warn!("Full-text search unavailable, falling back to LIKE: {}", e);
+        return Ok(());
+    }
+
+    let mut sql = String::new();
+    for source in &INDEXED_SOURCES {
+        sql.push_str(&source.triggers_sql());
+        if !existed {
+            sql.push_str(&source.rebuild_sql());
+        }
+    }
+    conn.execute_batch(&sql)?;
+
+    tracing::info!("Search schema initialized");
+    Ok(())
+}
+
+fn has_search_index(conn: &Connection) -> OptioResult<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'search_index'",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// A searchable record before scoring
+struct Candidate {
+    entity_type: SearchEntityType,
+    id: String,
+    client_id: Option<String>,
+    title: String,
+    body: String,
+}
+
+/// Search everything, best matches first
+///
+/// Every whitespace-separated term must match; the last one also matches
+/// as a prefix. `entity_types` limits the kinds of record searched, and
+/// `client_id` limits records to one client (catalog controls belong to no
+/// client and are always searched).
+pub fn search_everything(
+    db: &Database,
+    query: &str,
+    client_id: Option<&str>,
+    entity_types: Option<&[SearchEntityType]>,
+    limit: usize,
+) -> OptioResult<Vec<SearchHit>> {
+    let terms: Vec<&str> = query.split_whitespace().map(|t| t.trim_matches('"')).filter(|t| !t.is_empty()).collect();
+    if terms.is_empty() {
+        return Ok(vec![]);
+    }
+    let wanted = |entity: SearchEntityType| entity_types.map_or(true, |types| types.contains(&entity));
+
+    let mut candidates = {
+        let conn = db.conn.lock().map_err(|e| OptioError::Database(e.to_string()))?;
+        if has_search_index(&conn)? {
+            index_candidates(&conn, &terms, client_id)?
+        } else {
+            like_candidates(&conn, &terms, client_id, &wanted)?
+        }
+    };
+    candidates.retain(|c| wanted(c.entity_type));
+    name_assessed_controls(&mut candidates);
+    if wanted(SearchEntityType::Control) {
+        candidates.extend(catalog_candidates());
+    }
+
+    let folded: Vec<Vec<char>> = terms.iter().map(|t| fold_case(t)).collect();
+    let mut hits: Vec<SearchHit> = candidates
+        .into_iter()
+        .filter_map(|candidate| score_candidate(candidate, &folded))
+        .collect();
+    hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+/// FTS5 query requiring every term, the last as a prefix
+fn match_expression(terms: &[&str]) -> String {
+    let mut phrases: Vec<String> = terms.iter().map(|t| format!("\"{}\"", t.replace('"', "\"\""))).collect();
+    if let Some(last) = phrases.last_mut() {
+        last.push_str(" *");
+    }
+    phrases.join(" ")
+}
+
+fn index_candidates(conn: &Connection, terms: &[&str], client_id: Option<&str>) -> OptioResult<Vec<Candidate>> {
+    let mut stmt = conn.prepare(
+        "SELECT entity_type, entity_id, client_id, title, body FROM search_index
+         WHERE search_index MATCH ?1 AND (?2 IS NULL OR client_id = ?2)
+         ORDER BY bm25(search_index, 0.0, 0.0, 0.0, 5.0, 1.0)
+         LIMIT ?3",
+    )?;
+    let rows = stmt.query_map(
+        rusqlite::params![match_expression(terms), client_id, CANDIDATE_LIMIT as i64],
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                Candidate {
+                    entity_type: SearchEntityType::Client,
+                    id: row.get(1)?,
+                    client_id: row.get(2)?,
+                    title: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    body: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                },
+            ))
+        },
+    )?;
+
+    let mut candidates = Vec::new();
+    for row in rows {
+        let (code, mut candidate) = row?;
+        if let Some(entity_type) = SearchEntityType::from_code(&code) {
+            candidate.entity_type = entity_type;
+            candidates.push(candidate);
+        }
+    }
+    Ok(candidates)
+}
+
+fn like_candidates(
+    conn: &Connection,
+    terms: &[&str],
+    client_id: Option<&str>,
+    wanted: &dyn Fn(SearchEntityType) -> bool,
+) -> OptioResult<Vec<Candidate>> {
+    let mut candidates = Vec::new();
+    for source in INDEXED_SOURCES.iter().filter(|s| wanted(s.entity)) {
+        let (title, body, client) = source.columns("t.");
+        let conditions: Vec<String> = (1..=terms.len())
+            .map(|i| format!("({} || ' ' || {}) LIKE ?{} ESCAPE '\\'", title, body, i))
+            .collect();
+        let mut values: Vec<String> = terms.iter().map(|t| format!("%{}%", escape_like(t))).collect();
+        let client_filter = match client_id {
+            Some(id) => {
+                values.push(id.to_string());
+                format!(" AND {} = ?{}", client, values.len())
+            }
+            None => String::new(),
+        };
+        let sql = format!(
+            "SELECT t.id, {}, {}, {} FROM {} t WHERE {}{} LIMIT {}",
+            client,
+            title,
+            body,
+            source.table,
+            conditions.join(" AND "),
+            client_filter,
+            CANDIDATE_LIMIT,
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(values.iter()), |row| {
+            Ok(Candidate {
+                entity_type: source.entity,
+                id: row.get(0)?,
+                client_id: row.get(1)?,
+                title: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                body: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            })
+        })?;
+        for row in rows {
+            candidates.push(row?);
+        }
+    }
+    Ok(candidates)
+}
+
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Every framework's controls by ID and by code
+fn control_titles() -> HashMap<String, String> {
+    let mut titles = HashMap::new();
+    for control in Framework::all().into_iter().flat_map(get_framework_controls) {
+        let title = format!("{} {}", control.code, control.title);
+        titles.insert(control.code.clone(), title.clone());
+        titles.insert(control.id.clone(), title);
+    }
+    titles
+}
+
+/// Title control assessment hits with their control's code and title
+fn name_assessed_controls(candidates: &mut [Candidate]) {
+    if !candidates.iter().any(|c| c.entity_type == SearchEntityType::ControlAssessment) {
+        return;
+    }
+    let titles = control_titles();
+    for candidate in candidates.iter_mut().filter(|c| c.entity_type == SearchEntityType::ControlAssessment) {
+        if let Some(title) = titles.get(&candidate.title) {
+            candidate.title = title.clone();
+        }
+    }
+}
+
+fn catalog_candidates() -> Vec<Candidate> {
+    Framework::all()
+        .into_iter()
+        .flat_map(get_framework_controls)
+        .map(|control| Candidate {
+            entity_type: SearchEntityType::Control,
+            title: format!("{} {}", control.code, control.title),
+            body: format!("{} {}", control.description, control.guidance.unwrap_or_default()),
+            id: control.id,
+            client_id: None,
+        })
+        .collect()
+}
+
+/// Score a candidate and cut its snippet, or None unless every term matches
+///
+/// Title matches count more than body matches, and matches at the start of
+/// a word a little more than those inside one.
+fn score_candidate(candidate: Candidate, terms: &[Vec<char>]) -> Option<SearchHit> {
+    let title = fold_case(&candidate.title);
+    let body = fold_case(&candidate.body);
+
+    let mut score = 0;
+    for term in terms {
+        let word_start = |text: &[char], start: usize| start == 0 || !text[start - 1].is_alphanumeric();
+        let in_title = find_term(&title, term);
+        let in_body = find_term(&body, term);
+        score += match (in_title.first(), in_body.first()) {
+            (Some(_), _) => 10 + u32::from(in_title.iter().any(|&s| word_start(&title, s))) * 2,
+            (None, Some(_)) => 3 + u32::from(in_body.iter().any(|&s| word_start(&body, s))),
+            (None, None) => return None,
+        };
+    }
+
+    let snippet_source = if terms.iter().any(|t| !find_term(&body, t).is_empty()) {
+        &candidate.body
+    } else if candidate.body.trim().is_empty() {
+        &candidate.title
+    } else {
+        &candidate.body
+    };
+    let (snippet, highlights) = make_snippet(snippet_source, terms);
+
+    Some(SearchHit {
+        entity_type: candidate.entity_type,
+        id: candidate.id,
+        title: candidate.title,
+        snippet,
+        highlights,
+        client_id: candidate.client_id,
+        score,
+    })
+}
+
+/// About `SNIPPET_WIDTH` characters of `text` around the first match
+fn make_snippet(text: &str, terms: &[Vec<char>]) -> (String, Vec<SnippetHighlight>) {
+    let chars: Vec<char> = text.split_whitespace().collect::<Vec<_>>().join(" ").chars().collect();
+    let folded = fold_case(&chars.iter().collect::<String>());
+
+    let first = terms
+        .iter()
+        .filter_map(|term| find_term(&folded, term).first().map(|&start| (start, term.len())))
+        .min();
+    let (start, end) = match first {
+        Some((at, len)) if chars.len() > SNIPPET_WIDTH => {
+            let start = at.saturating_sub(SNIPPET_WIDTH.saturating_sub(len) / 2);
+            let start = start.min(chars.len() - SNIPPET_WIDTH);
+            (start, start + SNIPPET_WIDTH)
+        }
+        _ => (0, chars.len().min(SNIPPET_WIDTH)),
+    };
+
+    let prefix = usize::from(start > 0);
+    let mut snippet: String = chars[start..end].iter().collect();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+
+    let mut highlights: Vec<SnippetHighlight> = terms
+        .iter()
+        .flat_map(|term| {
+            find_term(&folded[start..end], term)
+                .into_iter()
+                .map(move |at| SnippetHighlight { start: at + prefix, end: at + prefix + term.len() })
+        })
+        .collect();
+    highlights.sort_by_key(|h| (h.start, h.end));
+    highlights.dedup();
+    (snippet, highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Client, ClientRepository};
+    use crate::network::models::{Asset, AssetCategory, AssetStatus, Criticality};
+    use crate::network::repository::AssetRepository;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn test_db(with_index: bool) -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::grc::repository::init_grc_schema(&db).unwrap();
+        crate::network::repository::init_network_schema(&db).unwrap();
+        crate::reporting::repository::init_reporting_schema(&db).unwrap();
+        if with_index {
+            init_search_schema(&db).unwrap();
+        }
+        db
+    }
+
+    fn seed(db: &Database) -> Client {
+        let client = Client::new(
+            "Acme Corp".to_string(),
+            None,
+            None,
+            Some("Firewall is a Palo Alto PA-3220; config exported during kickoff".to_string()),
+        );
+        ClientRepository::new(db).create(&client).unwrap();
+        let asset = Asset {
+            id: "asset-1".to_string(),
+            client_id: client.id.clone(),
+            name: "fileserver".to_string(),
+            ip_address: "10.20.30.40".to_string(),
+            mac_address: None,
+            category: AssetCategory::Server,
+            operating_system: None,
+            criticality: Criticality::High,
+            status: AssetStatus::Active,
+            location: None,
+            owner: None,
+            description: Some("Holds finance shares".to_string()),
+            services: vec![],
+            tags: vec!["finance".to_string()],
+            first_seen: "2026-01-01T00:00:00Z".to_string(),
+            last_seen: "2026-01-01T00:00:00Z".to_string(),
+            scan_ids: vec![],
+            metadata: None,
+        };
+        AssetRepository::new(db).save(&asset).unwrap();
+        client
+    }
+
+    #[test]
+    fn test_index_finds_records_and_follows_changes() {
+        let db = test_db(true);
+        let client = seed(&db);
+
+        let hits = search_everything(&db, "10.20.30.40", None, None, 10).unwrap();
+        assert_eq!(hits[0].entity_type, SearchEntityType::Asset);
+        assert_eq!(hits[0].title, "fileserver (10.20.30.40)");
+        assert_eq!(hits[0].client_id.as_deref(), Some(client.id.as_str()));
+
+        let hits = search_everything(&db, "palo alt", Some(&client.id), Some(&[SearchEntityType::Client]), 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        let highlighted: Vec<String> = hits[0]
+            .highlights
+            .iter()
+            .map(|h| hits[0].snippet.chars().skip(h.start).take(h.end - h.start).collect())
+            .collect();
+        assert_eq!(highlighted, vec!["Palo", "Alt"]);
+
+        AssetRepository::new(&db).delete("asset-1").unwrap();
+        assert!(search_everything(&db, "10.20.30.40", None, None, 10).unwrap().is_empty());
+        assert!(search_everything(&db, "palo", Some("other-client"), None, 10)
+            .unwrap()
+            .iter()
+            .all(|h| h.entity_type == SearchEntityType::Control));
+    }
+
+    #[test]
+    fn test_like_fallback_and_catalog_controls() {
+        let db = test_db(false);
+        seed(&db);
+
+        let hits = search_everything(&db, "FINANCE", None, Some(&[SearchEntityType::Asset]), 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "asset-1");
+
+        let hits = search_everything(&db, "mfa", None, Some(&[SearchEntityType::Control]), 5).unwrap();
+        assert!(!hits.is_empty());
+        assert!(hits.iter().all(|h| h.client_id.is_none()));
+        assert!(search_everything(&db, "   ", None, None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_snippet_is_cut_around_the_match() {
+        let text = format!("{} needle {}", "a".repeat(100), "b".repeat(100));
+        let (snippet, highlights) = make_snippet(&text, &[fold_case("needle")]);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert_eq!(snippet.chars().count(), SNIPPET_WIDTH + 2);
+        let matched: String = snippet.chars().skip(highlights[0].start).take(6).collect();
+        assert_eq!(matched, "needle");
+        assert_eq!(match_expression(&["10.0.0.1", "say\"hi"]), "\"10.0.0.1\" \"say\"\"hi\" *");
+    }
+}
@@ -0,0 +1,199 @@
+//! Secret Storage
+//!
+//! Plaintext secrets (agent auth tokens) live in the operating system's
+//! credential store rather than the Optio database, so a copied database or
+//! backup never carries them. The stores are reached through:
+//!
+//! - macOS: the login keychain via the Security framework
+//! - Linux: the Secret Service (GNOME Keyring, KWallet) via `secret-tool`
+//! - Windows: DPAPI, bound to the current user, via PowerShell
+//!
+//! Command-line tools get secrets on stdin, never as arguments, and secrets
+//! are never logged.
+
+use crate::error::{OptioError, OptioResult};
+#[cfg(not(target_os = "macos"))]
+use std::io::Write;
+#[cfg(not(target_os = "macos"))]
+use std::process::{Command, Output, Stdio};
+
+/// Service name secrets are filed under
+pub const SECRET_SERVICE: &str = "optio";
+
+/// Key/value store for secrets
+pub trait SecretStore {
+    fn set(&self, key: &str, secret: &str) -> OptioResult<()>;
+    /// None when no secret is stored under `key`
+    fn get(&self, key: &str) -> OptioResult<Option<String>>;
+    /// Removing a missing secret is not an error
+    fn delete(&self, key: &str) -> OptioResult<()>;
+}
+
+/// The operating system's credential store
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsKeychain;
+
+/// Run a credential tool, feeding `input` on stdin when given
+#[cfg(not(target_os = "macos"))]
+fn run_tool(program: &str, args: &[&str], input: Option<&str>) -> OptioResult<Output> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| OptioError::Encryption(format!("Credential store unavailable ({}): {}", program, e)))?;
+
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input.as_bytes())?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn tool_error(action: &str, output: &Output) -> OptioError {
+    OptioError::Encryption(format!(
+        "Failed to {} secret: {}",
+        action,
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+}
+
+/// errSecItemNotFound
+#[cfg(target_os = "macos")]
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+#[cfg(target_os = "macos")]
+fn keychain_error(action: &str, error: security_framework::base::Error) -> OptioError {
+    OptioError::Encryption(format!("Failed to {} secret: {}", action, error))
+}
+
+#[cfg(target_os = "macos")]
+impl SecretStore for OsKeychain {
+    fn set(&self, key: &str, secret: &str) -> OptioResult<()> {
+        // Updates the item if there already is one
+        security_framework::passwords::set_generic_password(SECRET_SERVICE, key, secret.as_bytes())
+            .map_err(|e| keychain_error("store", e))
+    }
+
+    fn get(&self, key: &str) -> OptioResult<Option<String>> {
+        match security_framework::passwords::get_generic_password(SECRET_SERVICE, key) {
+            Ok(secret) => Ok(Some(String::from_utf8_lossy(&secret).to_string())),
+            Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+            Err(e) => Err(keychain_error("read", e)),
+        }
+    }
+
+    fn delete(&self, key: &str) -> OptioResult<()> {
+        match security_framework::passwords::delete_generic_password(SECRET_SERVICE, key) {
+            Err(e) if e.code() != ERR_SEC_ITEM_NOT_FOUND => Err(keychain_error("delete", e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl SecretStore for OsKeychain {
+    fn set(&self, key: &str, secret: &str) -> OptioResult<()> {
+        let label = format!("Optio secret {}", key);
+        let output = run_tool(
+            "secret-tool",
+            &["store", "--label", &label, "service", SECRET_SERVICE, "account", key],
+            Some(secret),
+        )?;
+        if output.status.success() { Ok(()) } else { Err(tool_error("store", &output)) }
+    }
+
+    fn get(&self, key: &str) -> OptioResult<Option<String>> {
+        let output = run_tool("secret-tool", &["lookup", "service", SECRET_SERVICE, "account", key], None)?;
+        if output.status.success() {
+            Ok(Some(String::from_utf8_lossy(&output.stdout).trim_end_matches('\n').to_string()))
+        } else if output.stderr.is_empty() {
+            // Lookup exits non-zero without a message when nothing matches
+            Ok(None)
+        } else {
+            Err(tool_error("read", &output))
+        }
+    }
+
+    fn delete(&self, key: &str) -> OptioResult<()> {
+        let output = run_tool("secret-tool", &["clear", "service", SECRET_SERVICE, "account", key], None)?;
+        if output.status.success() || output.stderr.is_empty() { Ok(()) } else { Err(tool_error("delete", &output)) }
+    }
+}
+
+/// Encrypts stdin with DPAPI for the current user
+#[cfg(windows)]
+const DPAPI_PROTECT: &str = "$s = [Console]::In.ReadToEnd(); \
+    ConvertTo-SecureString $s -AsPlainText -Force | ConvertFrom-SecureString";
+
+/// Decrypts a DPAPI blob from stdin
+#[cfg(windows)]
+const DPAPI_UNPROTECT: &str = "$b = [Console]::In.ReadToEnd().Trim(); \
+    [Console]::Out.Write([System.Net.NetworkCredential]::new('', (ConvertTo-SecureString $b)).Password)";
+
+/// DPAPI blob for `key`, under %APPDATA%\Optio\secrets
+#[cfg(windows)]
+fn dpapi_path(key: &str) -> OptioResult<std::path::PathBuf> {
+    let appdata = std::env::var("APPDATA")
+        .map_err(|_| OptioError::Encryption("APPDATA is not set".to_string()))?;
+    let dir = std::path::Path::new(&appdata).join("Optio").join("secrets");
+    std::fs::create_dir_all(&dir)?;
+    let name: String = key.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect();
+    Ok(dir.join(format!("{}-{}.dpapi", SECRET_SERVICE, name)))
+}
+
+#[cfg(windows)]
+impl SecretStore for OsKeychain {
+    fn set(&self, key: &str, secret: &str) -> OptioResult<()> {
+        let output = run_tool("powershell", &["-NoProfile", "-NonInteractive", "-Command", DPAPI_PROTECT], Some(secret))?;
+        if !output.status.success() {
+            return Err(tool_error("store", &output));
+        }
+        std::fs::write(dpapi_path(key)?, &output.stdout)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> OptioResult<Option<String>> {
+        let blob = match std::fs::read_to_string(dpapi_path(key)?) {
+            Ok(blob) => blob,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let output = run_tool("powershell", &["-NoProfile", "-NonInteractive", "-Command", DPAPI_UNPROTECT], Some(&blob))?;
+        if output.status.success() {
+            Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+        } else {
+            Err(tool_error("read", &output))
+        }
+    }
+
+    fn delete(&self, key: &str) -> OptioResult<()> {
+        match std::fs::remove_file(dpapi_path(key)?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// In-memory store for tests
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemorySecretStore(std::sync::Mutex<std::collections::HashMap<String, String>>);
+
+#[cfg(test)]
+impl SecretStore for MemorySecretStore {
+    fn set(&self, key: &str, secret: &str) -> OptioResult<()> {
+        self.0.lock().unwrap().insert(key.to_string(), secret.to_string());
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> OptioResult<Option<String>> {
+        Ok(self.0.lock().unwrap().get(key).cloned())
+    }
+
+    fn delete(&self, key: &str) -> OptioResult<()> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
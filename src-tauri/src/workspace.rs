@@ -0,0 +1,798 @@
+//! Engagement Workspace Archive
+//!
+//! One zip with everything Optio holds for a client, for handing over and
+//! keeping at engagement close-out. Artifacts go into fixed directories:
+//!
+//! - `reports/`: each report as JSON and HTML, plus its exported file
+//! - `assessments/`: assessment bundles (see [`crate::grc::transfer`]) and
+//!   Kubernetes hardening audits
+//! - `evidence/<assessment id>/`: evidence files
+//! - `scans/`: scan records with results, and the raw Nmap XML
+//! - `scripts/`: generated script records and agent token metadata
+//! - `finops/`: the cloud readiness assessment and its score; TCO and cost
+//!   projections are computed on demand and not stored, so aren't included
+//!
+//! `manifest.json` lists every artifact with its SHA-256, size and source
+//! timestamp, so the receiving side can check the archive is intact.
+//! Artifacts are written into the zip one at a time as they are read.
+
+use crate::db::{ClientRepository, Database};
+use crate::error::{OptioError, OptioResult};
+use crate::factory::history::ScriptHistoryRepository;
+use crate::factory::tokens::{redact_agent_tokens, AgentTokenRepository};
+use crate::grc::repository::{AssessmentRepository, EvidenceRepository};
+use crate::grc::transfer::export_bundle;
+use crate::infrastructure::cloud_readiness::{score_readiness, CloudReadinessRepository};
+use crate::infrastructure::k8s_hardening::K8sAuditRepository;
+use crate::network::repository::ScanRepository;
+use crate::reporting::generator::content_to_html;
+use crate::reporting::repository::ReportRepository;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Identifies a zip as an Optio workspace archive
+pub const WORKSPACE_ARCHIVE_FORMAT: &str = "optio-workspace";
+
+/// Current archive version
+pub const WORKSPACE_ARCHIVE_VERSION: u32 = 1;
+
+/// Name of the manifest at the root of the archive
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Event emitted as each artifact is written
+pub const WORKSPACE_EXPORT_PROGRESS_EVENT: &str = "workspace-export-progress";
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// What to leave out of a workspace archive
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceExportOptions {
+    /// Leave out agent token metadata and redact tokens in script text
+    #[serde(default)]
+    pub exclude_agent_tokens: bool,
+    /// Leave out the raw Nmap XML of scans
+    #[serde(default)]
+    pub exclude_raw_scan_xml: bool,
+}
+
+/// Kind of artifact in a workspace archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ArtifactKind {
+    Report,
+    ReportFile,
+    Assessment,
+    K8sAudit,
+    Evidence,
+    Scan,
+    ScanXml,
+    Script,
+    AgentTokens,
+    CloudReadiness,
+}
+
+/// One file in the archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// Path within the archive
+    pub path: String,
+    pub kind: ArtifactKind,
+    /// Hex-encoded SHA-256 of the file as stored
+    pub sha256: String,
+    pub size: u64,
+    /// Record the file came from
+    pub source_id: String,
+    /// When the source record was created, collected or last changed
+    pub recorded_at: Option<String>,
+}
+
+/// Index of a workspace archive, stored as `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceManifest {
+    pub format: String,
+    pub version: u32,
+    pub client_id: String,
+    pub client_name: String,
+    pub exported_at: DateTime<Utc>,
+    pub exported_by: String,
+    pub options: WorkspaceExportOptions,
+    pub artifacts: Vec<ManifestEntry>,
+    /// Artifacts that were skipped, and why
+    pub warnings: Vec<String>,
+}
+
+/// Export progress, emitted after each artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceExportProgress {
+    pub client_id: String,
+    /// Artifacts handled so far, written or skipped
+    pub completed: usize,
+    pub total: usize,
+    /// Archive path of the artifact just handled
+    pub current: String,
+}
+
+/// Outcome of a workspace export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceExportSummary {
+    pub file_path: String,
+    pub artifact_count: usize,
+    /// Uncompressed size of all artifacts
+    pub total_bytes: u64,
+    pub warnings: Vec<String>,
+}
+
+/// Result of checking an archive against its manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceArchiveValidation {
+    /// Every listed artifact is present and matches, and nothing is unlisted
+    pub valid: bool,
+    pub client_id: String,
+    pub client_name: String,
+    pub exported_at: DateTime<Utc>,
+    pub artifact_count: usize,
+    pub verified: usize,
+    /// Listed artifacts whose hash or size differs
+    pub mismatched: Vec<String>,
+    /// Listed artifacts absent from the archive
+    pub missing: Vec<String>,
+    /// Files in the archive the manifest doesn't list
+    pub unlisted: Vec<String>,
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// An artifact to write, read from its source only when its turn comes
+enum Artifact {
+    Report { id: String },
+    Assessment { id: String },
+    K8sAudit { id: String },
+    Evidence { assessment_id: String, evidence_id: String, title: String, path: PathBuf, collected_at: DateTime<Utc> },
+    Scan { id: String },
+    ScanXml { id: String },
+    Script { id: String },
+    AgentTokens,
+    CloudReadiness,
+}
+
+/// Hashes and counts everything written through it
+struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        HashingWriter { inner, hasher: Sha256::new(), size: 0 }
+    }
+
+    fn finish(self) -> (String, u64) {
+        let hash = self.hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+        (hash, self.size)
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn zip_error(e: zip::result::ZipError) -> OptioError {
+    match e {
+        zip::result::ZipError::Io(e) => OptioError::Io(e),
+        other => OptioError::Validation(format!("Archive error: {}", other)),
+    }
+}
+
+/// Zip being written, with the manifest entries so far
+struct ArchiveWriter<W: Write + Seek> {
+    zip: ZipWriter<W>,
+    entries: Vec<ManifestEntry>,
+}
+
+impl<W: Write + Seek> ArchiveWriter<W> {
+    fn add(
+        &mut self,
+        path: &str,
+        kind: ArtifactKind,
+        source_id: &str,
+        recorded_at: Option<String>,
+        write: impl FnOnce(&mut dyn Write) -> OptioResult<()>,
+    ) -> OptioResult<()> {
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(true);
+        self.zip.start_file(path, options).map_err(zip_error)?;
+
+        let mut writer = HashingWriter::new(&mut self.zip);
+        write(&mut writer)?;
+        let (sha256, size) = writer.finish();
+
+        self.entries.push(ManifestEntry {
+            path: path.to_string(),
+            kind,
+            sha256,
+            size,
+            source_id: source_id.to_string(),
+            recorded_at,
+        });
+        Ok(())
+    }
+
+    fn add_json<T: Serialize>(
+        &mut self,
+        path: &str,
+        kind: ArtifactKind,
+        source_id: &str,
+        recorded_at: Option<String>,
+        value: &T,
+    ) -> OptioResult<()> {
+        self.add(path, kind, source_id, recorded_at, |w| Ok(serde_json::to_writer_pretty(w, value)?))
+    }
+
+    fn add_text(
+        &mut self,
+        path: &str,
+        kind: ArtifactKind,
+        source_id: &str,
+        recorded_at: Option<String>,
+        text: &str,
+    ) -> OptioResult<()> {
+        self.add(path, kind, source_id, recorded_at, |w| Ok(w.write_all(text.as_bytes())?))
+    }
+
+    fn add_file(
+        &mut self,
+        path: &str,
+        kind: ArtifactKind,
+        source_id: &str,
+        recorded_at: Option<String>,
+        mut file: File,
+    ) -> OptioResult<()> {
+        self.add(path, kind, source_id, recorded_at, |w| {
+            io::copy(&mut file, w)?;
+            Ok(())
+        })
+    }
+}
+
+/// A file name safe to use as one path segment in the archive
+fn archive_file_name(path: &Path, fallback: &str) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().replace(['/', '\\'], "_"))
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// List what a client's archive will hold, without loading the content
+fn plan_artifacts(db: &Database, client_id: &str, options: &WorkspaceExportOptions) -> OptioResult<Vec<Artifact>> {
+    let mut artifacts = Vec::new();
+
+    for report in ReportRepository::new(db).list_summaries(Some(client_id))? {
+        artifacts.push(Artifact::Report { id: report.id });
+    }
+
+    let evidence_repo = EvidenceRepository::new(db);
+    for assessment in AssessmentRepository::new(db).list_by_client(client_id)? {
+        for item in evidence_repo.get_by_assessment(&assessment.id)? {
+            // Carried-forward evidence belongs to, and is archived with, the earlier assessment
+            if item.carried_forward {
+                continue;
+            }
+            if let Some(path) = &item.file_path {
+                artifacts.push(Artifact::Evidence {
+                    assessment_id: assessment.id.clone(),
+                    evidence_id: item.id.clone(),
+                    title: item.title.clone(),
+                    path: PathBuf::from(path),
+                    collected_at: item.collected_at,
+                });
+            }
+        }
+        artifacts.push(Artifact::Assessment { id: assessment.id });
+    }
+    for audit in K8sAuditRepository::new(db).list_by_client(client_id)? {
+        artifacts.push(Artifact::K8sAudit { id: audit.id });
+    }
+
+    let scans = ScanRepository::new(db);
+    for scan in scans.list_by_client(client_id)? {
+        if !options.exclude_raw_scan_xml && scans.has_raw_xml(&scan.id)? {
+            artifacts.push(Artifact::ScanXml { id: scan.id.clone() });
+        }
+        artifacts.push(Artifact::Scan { id: scan.id });
+    }
+
+    for script in ScriptHistoryRepository::new(db).list_by_client(client_id)? {
+        artifacts.push(Artifact::Script { id: script.script_id });
+    }
+    if !options.exclude_agent_tokens && !AgentTokenRepository::new(db).list_by_client(client_id)?.is_empty() {
+        artifacts.push(Artifact::AgentTokens);
+    }
+
+    if CloudReadinessRepository::new(db).get_by_client(client_id)?.is_some() {
+        artifacts.push(Artifact::CloudReadiness);
+    }
+
+    Ok(artifacts)
+}
+
+/// Write one artifact; returns its main archive path, or a warning when it was skipped
+fn write_artifact<W: Write + Seek>(
+    db: &Database,
+    client_id: &str,
+    options: &WorkspaceExportOptions,
+    archive: &mut ArchiveWriter<W>,
+    artifact: &Artifact,
+) -> OptioResult<Result<String, String>> {
+    let path = match artifact {
+        Artifact::Report { id } => {
+            let report = match ReportRepository::new(db).get(id)? {
+                Some(report) => report,
+                None => return Ok(Err(format!("Report {} was deleted during the export", id))),
+            };
+            let path = format!("reports/{}.json", id);
+            let recorded_at = Some(report.updated_at.clone());
+            archive.add_json(&path, ArtifactKind::Report, id, recorded_at.clone(), &report)?;
+            if let Some(content) = &report.content {
+                archive.add_text(&format!("reports/{}.html", id), ArtifactKind::Report, id, recorded_at.clone(), &content_to_html(content))?;
+            }
+            if let Some(file_path) = &report.file_path {
+                let file_path = Path::new(file_path);
+                match File::open(file_path) {
+                    Ok(file) => {
+                        let name = format!("reports/{}-{}", id, archive_file_name(file_path, "export"));
+                        archive.add_file(&name, ArtifactKind::ReportFile, id, recorded_at, file)?;
+                    }
+                    Err(e) => return Ok(Err(format!(
+                        "Report \"{}\": exported file {} skipped: {}", report.config.title, file_path.display(), e
+                    ))),
+                }
+            }
+            path
+        }
+
+        Artifact::Assessment { id } => {
+            let (bundle, _) = export_bundle(db, id, false)?;
+            let path = format!("assessments/{}.json", id);
+            let recorded_at = Some(bundle.assessment.completed_at.unwrap_or(bundle.assessment.started_at).to_rfc3339());
+            archive.add_json(&path, ArtifactKind::Assessment, id, recorded_at, &bundle)?;
+            path
+        }
+
+        Artifact::K8sAudit { id } => {
+            let audit = match K8sAuditRepository::new(db).get(id)? {
+                Some(audit) => audit,
+                None => return Ok(Err(format!("Kubernetes audit {} was deleted during the export", id))),
+            };
+            let path = format!("assessments/k8s-audit-{}.json", id);
+            archive.add_json(&path, ArtifactKind::K8sAudit, id, Some(audit.audited_at.to_rfc3339()), &audit)?;
+            path
+        }
+
+        Artifact::Evidence { assessment_id, evidence_id, title, path, collected_at } => {
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(e) => return Ok(Err(format!("Evidence \"{}\": {} skipped: {}", title, path.display(), e))),
+            };
+            let name = format!("evidence/{}/{}-{}", assessment_id, evidence_id, archive_file_name(path, evidence_id));
+            archive.add_file(&name, ArtifactKind::Evidence, evidence_id, Some(collected_at.to_rfc3339()), file)?;
+            name
+        }
+
+        Artifact::Scan { id } => {
+            let scans = ScanRepository::new(db);
+            let job = match scans.get(id)? {
+                Some(job) => job,
+                None => return Ok(Err(format!("Scan {} was deleted during the export", id))),
+            };
+            let record = serde_json::json!({ "scan": job, "results": scans.get_results(id)? });
+            let path = format!("scans/{}.json", id);
+            let recorded_at = Some(job.completed_at.clone().unwrap_or_else(|| job.created_at.clone()));
+            archive.add_json(&path, ArtifactKind::Scan, id, recorded_at, &record)?;
+            path
+        }
+
+        Artifact::ScanXml { id } => {
+            let xml = match ScanRepository::new(db).get_raw_xml(id)? {
+                Some(xml) => xml,
+                None => return Ok(Err(format!("Raw XML of scan {} is no longer stored", id))),
+            };
+            let path = format!("scans/{}.xml", id);
+            archive.add_text(&path, ArtifactKind::ScanXml, id, None, &xml)?;
+            path
+        }
+
+        Artifact::Script { id } => {
+            let record = match ScriptHistoryRepository::new(db).get(id)? {
+                Some(record) => record,
+                None => return Ok(Err(format!("Script {} was deleted during the export", id))),
+            };
+            let mut json = serde_json::to_string_pretty(&record)?;
+            if options.exclude_agent_tokens {
+                json = redact_agent_tokens(&json);
+            }
+            let path = format!("scripts/{}.json", id);
+            archive.add_text(&path, ArtifactKind::Script, id, Some(record.generated_at.to_rfc3339()), &json)?;
+            path
+        }
+
+        Artifact::AgentTokens => {
+            // Metadata only; the tokens themselves live in the OS keychain
+            let tokens = AgentTokenRepository::new(db).list_by_client(client_id)?;
+            let path = "scripts/agent-tokens.json".to_string();
+            archive.add_json(&path, ArtifactKind::AgentTokens, client_id, None, &tokens)?;
+            path
+        }
+
+        Artifact::CloudReadiness => {
+            let assessment = match CloudReadinessRepository::new(db).get_by_client(client_id)? {
+                Some(assessment) => assessment,
+                None => return Ok(Err("Cloud readiness assessment was deleted during the export".to_string())),
+            };
+            let record = serde_json::json!({ "assessment": assessment, "score": score_readiness(&assessment) });
+            let path = "finops/cloud-readiness.json".to_string();
+            archive.add_json(&path, ArtifactKind::CloudReadiness, &assessment.id, Some(assessment.updated_at.to_rfc3339()), &record)?;
+            path
+        }
+    };
+    Ok(Ok(path))
+}
+
+/// Write a client's workspace archive to `dest`
+///
+/// The zip is written next to `dest` and moved into place once complete,
+/// so a failed export leaves nothing behind. Files that can't be read,
+/// such as evidence moved since it was recorded, are skipped with a
+/// warning. `progress` is called after each artifact.
+pub fn export_workspace(
+    db: &Database,
+    client_id: &str,
+    dest: &Path,
+    options: &WorkspaceExportOptions,
+    mut progress: impl FnMut(&WorkspaceExportProgress),
+) -> OptioResult<WorkspaceExportSummary> {
+    let client = ClientRepository::new(db)
+        .get(client_id)?
+        .ok_or_else(|| OptioError::ClientNotFound(client_id.to_string()))?;
+    let artifacts = plan_artifacts(db, client_id, options)?;
+
+    let partial = dest.with_extension("partial");
+    let result = (|| -> OptioResult<(Vec<ManifestEntry>, Vec<String>)> {
+        let mut archive = ArchiveWriter { zip: ZipWriter::new(File::create(&partial)?), entries: Vec::new() };
+        let mut warnings = Vec::new();
+
+        for (i, artifact) in artifacts.iter().enumerate() {
+            let current = match write_artifact(db, client_id, options, &mut archive, artifact)? {
+                Ok(path) => path,
+                Err(warning) => {
+                    tracing::warn!("Workspace export: {}", warning);
+                    warnings.push(warning);
+                    String::new()
+                }
+            };
+            progress(&WorkspaceExportProgress {
+                client_id: client_id.to_string(),
+                completed: i + 1,
+                total: artifacts.len(),
+                current,
+            });
+        }
+
+        let manifest = WorkspaceManifest {
+            format: WORKSPACE_ARCHIVE_FORMAT.to_string(),
+            version: WORKSPACE_ARCHIVE_VERSION,
+            client_id: client.id.clone(),
+            client_name: client.name.clone(),
+            exported_at: Utc::now(),
+            exported_by: whoami::username(),
+            options: options.clone(),
+            artifacts: archive.entries,
+            warnings: warnings.clone(),
+        };
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        archive.zip.start_file(MANIFEST_FILE, options).map_err(zip_error)?;
+        serde_json::to_writer_pretty(&mut archive.zip, &manifest)?;
+        archive.zip.finish().map_err(zip_error)?.sync_all()?;
+
+        Ok((manifest.artifacts, warnings))
+    })();
+
+    let (entries, warnings) = match result {
+        Ok(done) => done,
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e);
+        }
+    };
+    std::fs::rename(&partial, dest)?;
+    tracing::info!("Exported workspace for client {} to {} ({} artifacts)", client_id, dest.display(), entries.len());
+
+    Ok(WorkspaceExportSummary {
+        file_path: dest.to_string_lossy().into_owned(),
+        artifact_count: entries.len(),
+        total_bytes: entries.iter().map(|e| e.size).sum(),
+        warnings,
+    })
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Read an archive's manifest, checking it is a workspace archive this build understands
+fn read_manifest<R: Read + Seek>(zip: &mut ZipArchive<R>) -> OptioResult<WorkspaceManifest> {
+    let file = zip
+        .by_name(MANIFEST_FILE)
+        .map_err(|_| OptioError::Validation("Not a workspace archive: manifest.json is missing".to_string()))?;
+    let manifest: WorkspaceManifest = serde_json::from_reader(file)
+        .map_err(|e| OptioError::Validation(format!("Workspace manifest is not valid: {}", e)))?;
+
+    if manifest.format != WORKSPACE_ARCHIVE_FORMAT {
+        return Err(OptioError::Validation(format!("Not a workspace archive: format is {}", manifest.format)));
+    }
+    if manifest.version > WORKSPACE_ARCHIVE_VERSION {
+        return Err(OptioError::Validation(format!(
+            "Workspace archive version {} is newer than this version of Optio supports ({})",
+            manifest.version, WORKSPACE_ARCHIVE_VERSION
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Check every file in an archive against the hashes in its manifest
+pub fn validate_workspace(path: &Path) -> OptioResult<WorkspaceArchiveValidation> {
+    let mut zip = ZipArchive::new(File::open(path)?).map_err(zip_error)?;
+    validate_archive(&mut zip)
+}
+
+fn validate_archive<R: Read + Seek>(zip: &mut ZipArchive<R>) -> OptioResult<WorkspaceArchiveValidation> {
+    let manifest = read_manifest(zip)?;
+
+    let mut verified = 0;
+    let mut mismatched = Vec::new();
+    let mut missing = Vec::new();
+    for entry in &manifest.artifacts {
+        let mut file = match zip.by_name(&entry.path) {
+            Ok(file) => file,
+            Err(_) => {
+                missing.push(entry.path.clone());
+                continue;
+            }
+        };
+        let mut hashing = HashingWriter::new(io::sink());
+        io::copy(&mut file, &mut hashing)?;
+        let (sha256, size) = hashing.finish();
+        if sha256 == entry.sha256 && size == entry.size {
+            verified += 1;
+        } else {
+            mismatched.push(entry.path.clone());
+        }
+    }
+
+    let listed: HashSet<&str> = manifest.artifacts.iter().map(|a| a.path.as_str()).collect();
+    let unlisted: Vec<String> = zip
+        .file_names()
+        .filter(|name| *name != MANIFEST_FILE && !name.ends_with('/') && !listed.contains(name))
+        .map(|name| name.to_string())
+        .collect();
+
+    Ok(WorkspaceArchiveValidation {
+        valid: mismatched.is_empty() && missing.is_empty() && unlisted.is_empty(),
+        client_id: manifest.client_id,
+        client_name: manifest.client_name,
+        exported_at: manifest.exported_at,
+        artifact_count: manifest.artifacts.len(),
+        verified,
+        mismatched,
+        missing,
+        unlisted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Client;
+    use crate::grc::models::{Assessment, AssessmentStatus, Evidence, EvidenceType, Framework};
+    use rusqlite::Connection;
+    use std::io::Cursor;
+    use std::sync::Mutex;
+
+    fn seeded_db() -> Database {
+        let db = Database {
+            conn: Mutex::new(Connection::open_in_memory().unwrap()),
+        };
+        db.init_schema().unwrap();
+        crate::grc::repository::init_grc_schema(&db).unwrap();
+        crate::reporting::repository::init_reporting_schema(&db).unwrap();
+        crate::network::repository::init_network_schema(&db).unwrap();
+        crate::factory::history::init_script_history_schema(&db).unwrap();
+        crate::factory::tokens::init_agent_token_schema(&db).unwrap();
+        crate::infrastructure::k8s_hardening::init_k8s_audit_schema(&db).unwrap();
+        crate::infrastructure::cloud_readiness::init_cloud_readiness_schema(&db).unwrap();
+
+        let mut client = Client::new("Acme".to_string(), None, None, None);
+        client.id = "client-1".to_string();
+        ClientRepository::new(&db).create(&client).unwrap();
+        AssessmentRepository::new(&db).create(&Assessment {
+            id: "assessment-1".to_string(),
+            client_id: "client-1".to_string(),
+            name: "Acme SOC 2".to_string(),
+            description: None,
+            framework: Framework::Soc2TypeII,
+            scope: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            lead_assessor: "Jane Auditor".to_string(),
+            status: AssessmentStatus::InProgress,
+            source_assessment_id: None,
+        }).unwrap();
+        db
+    }
+
+    fn evidence(id: &str, path: &Path) -> Evidence {
+        Evidence {
+            id: id.to_string(),
+            assessment_id: "assessment-1".to_string(),
+            control_ids: vec!["CC6.1".to_string()],
+            evidence_type: EvidenceType::Document,
+            title: format!("Evidence {}", id),
+            description: None,
+            file_path: Some(path.to_string_lossy().into_owned()),
+            url: None,
+            file_hash: None,
+            collected_at: Utc::now(),
+            collected_by: "Jane Auditor".to_string(),
+            notes: None,
+            carried_forward: false,
+        }
+    }
+
+    fn archive(files: &[(&str, &str)], manifest_files: &[(&str, &str)]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut archive = ArchiveWriter { zip: ZipWriter::new(Cursor::new(Vec::new())), entries: Vec::new() };
+        for (path, text) in manifest_files {
+            archive.add_text(path, ArtifactKind::Report, "r-1", None, text).unwrap();
+        }
+        let entries = archive.entries;
+        let mut zip = archive.zip;
+        for (path, text) in files {
+            zip.start_file(*path, SimpleFileOptions::default()).unwrap();
+            zip.write_all(text.as_bytes()).unwrap();
+        }
+
+        let manifest = WorkspaceManifest {
+            format: WORKSPACE_ARCHIVE_FORMAT.to_string(),
+            version: WORKSPACE_ARCHIVE_VERSION,
+            client_id: "client-1".to_string(),
+            client_name: "Acme".to_string(),
+            exported_at: Utc::now(),
+            exported_by: "tester".to_string(),
+            options: WorkspaceExportOptions::default(),
+            artifacts: entries,
+            warnings: vec![],
+        };
+        zip.start_file(MANIFEST_FILE, SimpleFileOptions::default()).unwrap();
+        serde_json::to_writer(&mut zip, &manifest).unwrap();
+        ZipArchive::new(zip.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_export_skips_missing_evidence_and_validates() {
+        let db = seeded_db();
+        let dir = std::env::temp_dir().join(format!("optio-workspace-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let policy = dir.join("mfa policy.pdf");
+        std::fs::write(&policy, b"%PDF-1.4").unwrap();
+        let evidence_repo = EvidenceRepository::new(&db);
+        evidence_repo.create(&evidence("e-1", &policy)).unwrap();
+        evidence_repo.create(&evidence("e-2", &dir.join("moved.png"))).unwrap();
+
+        let dest = dir.join("acme.zip");
+        let mut events = Vec::new();
+        let summary = export_workspace(&db, "client-1", &dest, &WorkspaceExportOptions::default(), |p| {
+            events.push((p.completed, p.total))
+        })
+        .unwrap();
+
+        assert_eq!(summary.artifact_count, 2);
+        assert_eq!(summary.warnings.len(), 1);
+        assert!(summary.warnings[0].contains("moved.png"));
+        assert_eq!(events, vec![(1, 3), (2, 3), (3, 3)]);
+        assert!(!dest.with_extension("partial").exists());
+
+        let mut zip = ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+        let manifest = read_manifest(&mut zip).unwrap();
+        let paths: Vec<&str> = manifest.artifacts.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(paths, vec!["evidence/assessment-1/e-1-mfa policy.pdf", "assessments/assessment-1.json"]);
+        assert_eq!(manifest.artifacts[0].size, 8);
+        assert_eq!(manifest.warnings, summary.warnings);
+
+        let validation = validate_workspace(&dest).unwrap();
+        assert!(validation.valid);
+        assert_eq!(validation.verified, 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_manifest_hashes_verify() {
+        let mut zip = archive(&[], &[("reports/r-1.json", "{}"), ("reports/r-1.html", "<p>Hi</p>")]);
+        let validation = validate_archive(&mut zip).unwrap();
+        assert!(validation.valid);
+        assert_eq!(validation.verified, 2);
+        assert_eq!(validation.client_name, "Acme");
+
+        // SHA-256 of "{}"
+        let manifest = read_manifest(&mut zip).unwrap();
+        assert_eq!(manifest.artifacts[0].sha256, "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a");
+        assert_eq!(manifest.artifacts[0].size, 2);
+    }
+
+    #[test]
+    fn test_tampered_archive_fails_validation() {
+        // A file added after export, under a listed name and a new one
+        let mut zip = archive(&[("scans/s-1.xml", "<nmaprun/>")], &[("reports/r-1.json", "{}")]);
+        let validation = validate_archive(&mut zip).unwrap();
+        assert!(!validation.valid);
+        assert_eq!(validation.unlisted, vec!["scans/s-1.xml".to_string()]);
+
+        let mut archive = ArchiveWriter { zip: ZipWriter::new(Cursor::new(Vec::new())), entries: Vec::new() };
+        archive.add_text("reports/r-1.json", ArtifactKind::Report, "r-1", None, "{}").unwrap();
+        archive.entries[0].sha256 = "0".repeat(64);
+        archive.entries.push(ManifestEntry {
+            path: "evidence/a-1/e-1-policy.pdf".to_string(),
+            kind: ArtifactKind::Evidence,
+            sha256: "0".repeat(64),
+            size: 1,
+            source_id: "e-1".to_string(),
+            recorded_at: None,
+        });
+        let manifest = WorkspaceManifest {
+            format: WORKSPACE_ARCHIVE_FORMAT.to_string(),
+            version: WORKSPACE_ARCHIVE_VERSION,
+            client_id: "client-1".to_string(),
+            client_name: "Acme".to_string(),
+            exported_at: Utc::now(),
+            exported_by: "tester".to_string(),
+            options: WorkspaceExportOptions::default(),
+            artifacts: archive.entries,
+            warnings: vec![],
+        };
+        let mut zip = archive.zip;
+        zip.start_file(MANIFEST_FILE, SimpleFileOptions::default()).unwrap();
+        serde_json::to_writer(&mut zip, &manifest).unwrap();
+        let mut zip = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+        let validation = validate_archive(&mut zip).unwrap();
+        assert!(!validation.valid);
+        assert_eq!(validation.mismatched, vec!["reports/r-1.json".to_string()]);
+        assert_eq!(validation.missing, vec!["evidence/a-1/e-1-policy.pdf".to_string()]);
+    }
+}